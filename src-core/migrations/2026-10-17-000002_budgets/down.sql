@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS activity_categories;
+DROP TABLE IF EXISTS budget_month_amounts;
+DROP TABLE IF EXISTS budget_categories;
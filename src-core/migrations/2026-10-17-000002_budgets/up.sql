@@ -0,0 +1,28 @@
+-- Monthly spending budgets per category
+CREATE TABLE IF NOT EXISTS budget_categories (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    monthly_amount TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    rollover TEXT NOT NULL DEFAULT 'NONE',
+    start_month TEXT NOT NULL,
+    is_archived BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Budgeted amount for a single month (YYYY-MM), overriding the category's monthly amount
+CREATE TABLE IF NOT EXISTS budget_month_amounts (
+    category_id TEXT NOT NULL REFERENCES budget_categories(id) ON DELETE CASCADE,
+    month TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    PRIMARY KEY (category_id, month)
+);
+
+-- Category assigned to an activity; actual spending is summed from these
+CREATE TABLE IF NOT EXISTS activity_categories (
+    activity_id TEXT PRIMARY KEY REFERENCES activities(id) ON DELETE CASCADE,
+    category_id TEXT NOT NULL REFERENCES budget_categories(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_activity_categories_category ON activity_categories(category_id);
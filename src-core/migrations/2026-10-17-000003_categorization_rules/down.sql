@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS categorization_rules;
+ALTER TABLE activity_categories DROP COLUMN source;
@@ -0,0 +1,17 @@
+-- How an activity got its category: MANUAL, RULE or LEARNED
+ALTER TABLE activity_categories ADD COLUMN source TEXT NOT NULL DEFAULT 'MANUAL';
+
+-- User-defined rules that assign budget categories to activities
+CREATE TABLE IF NOT EXISTS categorization_rules (
+    id TEXT PRIMARY KEY,
+    category_id TEXT NOT NULL REFERENCES budget_categories(id) ON DELETE CASCADE,
+    field TEXT NOT NULL,
+    match_type TEXT NOT NULL,
+    pattern TEXT NOT NULL,
+    priority INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_categorization_rules_category ON categorization_rules(category_id);
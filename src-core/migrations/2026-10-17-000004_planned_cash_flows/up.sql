@@ -0,0 +1,17 @@
+-- Known future cash movements used by the cash flow forecast
+CREATE TABLE IF NOT EXISTS planned_cash_flows (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    account_id TEXT REFERENCES accounts(id) ON DELETE CASCADE,
+    amount TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    frequency TEXT NOT NULL DEFAULT 'ONCE',
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_planned_cash_flows_account ON planned_cash_flows(account_id);
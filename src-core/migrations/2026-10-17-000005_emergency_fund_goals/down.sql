@@ -0,0 +1,2 @@
+ALTER TABLE goals DROP COLUMN target_months;
+ALTER TABLE goals DROP COLUMN goal_type;
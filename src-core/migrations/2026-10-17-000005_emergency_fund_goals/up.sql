@@ -0,0 +1,3 @@
+-- Goal kinds; emergency funds set their target in months of expenses
+ALTER TABLE goals ADD COLUMN goal_type TEXT NOT NULL DEFAULT 'STANDARD';
+ALTER TABLE goals ADD COLUMN target_months INTEGER;
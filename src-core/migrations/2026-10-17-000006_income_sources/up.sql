@@ -0,0 +1,19 @@
+-- Expected income: salaries, 13th-month/Tet bonuses, rent
+CREATE TABLE IF NOT EXISTS income_sources (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    account_id TEXT REFERENCES accounts(id) ON DELETE SET NULL,
+    amount TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    frequency TEXT NOT NULL DEFAULT 'MONTHLY',
+    pay_day INTEGER NOT NULL DEFAULT 1,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    match_pattern TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_income_sources_account ON income_sources(account_id);
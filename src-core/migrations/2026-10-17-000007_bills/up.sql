@@ -0,0 +1,35 @@
+-- Recurring bills and the payments that settle each due date
+CREATE TABLE IF NOT EXISTS bills (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    payee TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    account_id TEXT REFERENCES accounts(id) ON DELETE SET NULL,
+    category_id TEXT REFERENCES budget_categories(id) ON DELETE SET NULL,
+    frequency TEXT NOT NULL DEFAULT 'MONTHLY',
+    due_day INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    reminder_days INTEGER NOT NULL DEFAULT 3,
+    match_pattern TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_bills_account ON bills(account_id);
+
+CREATE TABLE IF NOT EXISTS bill_payments (
+    id TEXT PRIMARY KEY,
+    bill_id TEXT NOT NULL REFERENCES bills(id) ON DELETE CASCADE,
+    due_date TEXT NOT NULL,
+    activity_id TEXT REFERENCES activities(id) ON DELETE CASCADE,
+    amount TEXT NOT NULL,
+    paid_date TEXT NOT NULL,
+    source TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (bill_id, due_date)
+);
+
+CREATE INDEX idx_bill_payments_activity ON bill_payments(activity_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS envelope_transfers;
+DROP TABLE IF EXISTS envelopes;
@@ -0,0 +1,25 @@
+-- Envelope (zero-based) budgeting: cash account balances assigned to named envelopes
+CREATE TABLE IF NOT EXISTS envelopes (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    balance TEXT NOT NULL DEFAULT '0',
+    is_archived BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_envelopes_account ON envelopes(account_id);
+
+-- Every change to an envelope balance; a NULL side is the account's unassigned cash
+CREATE TABLE IF NOT EXISTS envelope_transfers (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    from_envelope_id TEXT REFERENCES envelopes(id) ON DELETE SET NULL,
+    to_envelope_id TEXT REFERENCES envelopes(id) ON DELETE SET NULL,
+    amount TEXT NOT NULL,
+    note TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_envelope_transfers_account ON envelope_transfers(account_id, created_at);
@@ -0,0 +1,28 @@
+-- Loan terms for liability accounts; the amortization schedule is generated from these
+CREATE TABLE IF NOT EXISTS loans (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL UNIQUE REFERENCES accounts(id) ON DELETE CASCADE,
+    principal TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    term_months INTEGER NOT NULL,
+    repayment_method TEXT NOT NULL DEFAULT 'EQUAL_PRINCIPAL',
+    -- Annual rates in percent
+    fixed_rate TEXT NOT NULL,
+    fixed_months INTEGER NOT NULL DEFAULT 0,
+    base_rate TEXT,
+    floating_margin TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Extra principal paid ahead of schedule
+CREATE TABLE IF NOT EXISTS loan_prepayments (
+    id TEXT PRIMARY KEY,
+    loan_id TEXT NOT NULL REFERENCES loans(id) ON DELETE CASCADE,
+    payment_date TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    note TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_loan_prepayments_loan ON loan_prepayments(loan_id, payment_date);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS education_stages;
+DROP TABLE IF EXISTS education_plans;
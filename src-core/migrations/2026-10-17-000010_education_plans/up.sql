@@ -0,0 +1,25 @@
+-- Education cost plans, one per child, optionally funded through a linked goal
+CREATE TABLE IF NOT EXISTS education_plans (
+    id TEXT PRIMARY KEY,
+    child_name TEXT NOT NULL,
+    birth_year INTEGER NOT NULL,
+    goal_id TEXT REFERENCES goals(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Schooling stages; costs are per school year in today's money
+CREATE TABLE IF NOT EXISTS education_stages (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL REFERENCES education_plans(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    school_type TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    start_year INTEGER NOT NULL,
+    years INTEGER NOT NULL,
+    annual_cost TEXT NOT NULL,
+    -- Annual tuition inflation in percent; the school type's default when NULL
+    tuition_inflation TEXT
+);
+
+CREATE INDEX idx_education_stages_plan ON education_stages(plan_id, start_year);
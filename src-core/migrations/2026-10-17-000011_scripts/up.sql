@@ -0,0 +1,13 @@
+-- User Rhai scripts run at import, quote sync and goal progress events
+CREATE TABLE IF NOT EXISTS scripts (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- on_import_row, on_quote_update or on_goal_progress
+    event TEXT NOT NULL,
+    source TEXT NOT NULL,
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_scripts_event ON scripts(event, is_enabled);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS bank_connection_imports;
+DROP TABLE IF EXISTS bank_connections;
@@ -0,0 +1,27 @@
+-- Bank and broker feeds pulled into an account; access tokens live in the keyring
+CREATE TABLE IF NOT EXISTS bank_connections (
+    id TEXT PRIMARY KEY,
+    -- TIMO, CAKE or BROKER_API
+    provider TEXT NOT NULL,
+    name TEXT NOT NULL,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    base_url TEXT NOT NULL,
+    sync_interval_hours INTEGER NOT NULL DEFAULT 24,
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    -- Last pull of any outcome, which the schedule counts from
+    last_attempt_at TIMESTAMP,
+    -- Last successful pull, which the next one overlaps
+    last_synced_at TIMESTAMP,
+    last_sync_error TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Provider transaction ids already imported, so overlapping pulls do not duplicate activities
+CREATE TABLE IF NOT EXISTS bank_connection_imports (
+    connection_id TEXT NOT NULL REFERENCES bank_connections(id) ON DELETE CASCADE,
+    external_id TEXT NOT NULL,
+    activity_id TEXT NOT NULL,
+    imported_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (connection_id, external_id)
+);
@@ -0,0 +1,18 @@
+-- Reports pushed to a Google Sheet tab on a schedule; OAuth credentials live in the keyring
+CREATE TABLE IF NOT EXISTS sheet_exports (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- HOLDINGS, NET_WORTH or GOAL_PROGRESS
+    report TEXT NOT NULL,
+    spreadsheet_id TEXT NOT NULL,
+    -- Tab the report overwrites; created by the user beforehand
+    sheet_name TEXT NOT NULL,
+    sync_interval_hours INTEGER NOT NULL DEFAULT 24,
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    -- Last push of any outcome, which the schedule counts from
+    last_attempt_at TIMESTAMP,
+    last_synced_at TIMESTAMP,
+    last_sync_error TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
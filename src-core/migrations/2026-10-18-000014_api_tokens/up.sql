@@ -0,0 +1,14 @@
+-- Tokens for the server's token-protected routes; only a SHA-256 of the secret is kept
+CREATE TABLE IF NOT EXISTS api_tokens (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    -- Start of the secret, shown so users can tell tokens apart
+    token_prefix TEXT NOT NULL,
+    -- Comma-separated READ_ONLY, REPORTS and IMPORT
+    scopes TEXT NOT NULL,
+    expires_at TIMESTAMP,
+    revoked_at TIMESTAMP,
+    last_used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
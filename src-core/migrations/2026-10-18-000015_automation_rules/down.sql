@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS activity_tags;
+DROP TABLE IF EXISTS automation_rule_firings;
+DROP TABLE IF EXISTS automation_rules;
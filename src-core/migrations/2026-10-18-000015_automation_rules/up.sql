@@ -0,0 +1,32 @@
+-- If-this-then-that rules; the trigger and actions are stored as JSON
+CREATE TABLE IF NOT EXISTS automation_rules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- {"type": "IMPORT_COMPLETED" | "GOAL_PROGRESS_REACHED" | "QUOTE_MOVED", ...}
+    trigger_config TEXT NOT NULL,
+    -- Array of {"type": "TAG_ACTIVITIES" | "NOTIFY" | "RUN_REPORT" | "CALL_WEBHOOK", ...}
+    actions TEXT NOT NULL,
+    is_enabled BOOLEAN NOT NULL DEFAULT 1,
+    last_triggered_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- What a threshold rule last fired for, so a goal or a symbol only fires once per crossing or day
+CREATE TABLE IF NOT EXISTS automation_rule_firings (
+    rule_id TEXT NOT NULL REFERENCES automation_rules(id) ON DELETE CASCADE,
+    -- Goal id or symbol
+    subject TEXT NOT NULL,
+    -- Quote date for QUOTE_MOVED, empty for goals
+    marker TEXT NOT NULL,
+    fired_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (rule_id, subject)
+);
+
+-- Free-form labels put on activities by TAG_ACTIVITIES actions
+CREATE TABLE IF NOT EXISTS activity_tags (
+    activity_id TEXT NOT NULL REFERENCES activities(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (activity_id, tag)
+);
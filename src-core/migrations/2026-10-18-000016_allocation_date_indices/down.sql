@@ -0,0 +1,3 @@
+CREATE INDEX IF NOT EXISTS idx_allocation_versions_allocation_id ON allocation_versions(allocation_id);
+DROP INDEX IF EXISTS idx_allocation_versions_allocation_start;
+DROP INDEX IF EXISTS idx_goals_allocation_account_dates;
@@ -0,0 +1,8 @@
+-- Allocations active on a date for one account (get_allocations_for_account_on_date)
+CREATE INDEX IF NOT EXISTS idx_goals_allocation_account_dates
+    ON goals_allocation(account_id, start_date, end_date);
+
+-- Versions of an allocation in date order; also serves lookups by allocation_id alone
+CREATE INDEX IF NOT EXISTS idx_allocation_versions_allocation_start
+    ON allocation_versions(allocation_id, version_start_date);
+DROP INDEX IF EXISTS idx_allocation_versions_allocation_id;
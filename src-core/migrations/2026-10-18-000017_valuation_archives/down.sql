@@ -0,0 +1,2 @@
+-- Archived months are lost; recalculate valuations after rolling back
+DROP TABLE IF EXISTS valuation_archives;
@@ -0,0 +1,17 @@
+-- Closed months of daily_account_valuation, one delta-encoded blob per account and month.
+-- The repository moves rows here once a month is no longer recalculated incrementally.
+CREATE TABLE IF NOT EXISTS valuation_archives (
+    account_id TEXT NOT NULL,
+    -- First day of the month
+    month_start DATE NOT NULL,
+    -- First and last valuation date in the blob, for range lookups without decoding
+    first_date DATE NOT NULL,
+    last_date DATE NOT NULL,
+    row_count INTEGER NOT NULL,
+    account_currency TEXT NOT NULL,
+    base_currency TEXT NOT NULL,
+    -- Latest calculated_at of the archived rows
+    calculated_at TEXT NOT NULL,
+    data BLOB NOT NULL,
+    PRIMARY KEY (account_id, month_start)
+);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_goal_monthly_progress_month;
+DROP TABLE IF EXISTS goal_monthly_progress;
+DROP TABLE IF EXISTS account_monthly_summaries;
@@ -0,0 +1,62 @@
+-- Month-end totals per account, maintained by the valuation repository whenever it saves or
+-- deletes valuations, so charts and dashboards read one row per month
+CREATE TABLE IF NOT EXISTS account_monthly_summaries (
+    account_id TEXT NOT NULL,
+    -- First day of the month
+    month_start DATE NOT NULL,
+    first_date DATE NOT NULL,
+    last_date DATE NOT NULL,
+    value_count INTEGER NOT NULL,
+    account_currency TEXT NOT NULL,
+    -- Decimals as TEXT, in the account currency; the closing values are those of last_date
+    opening_total_value TEXT NOT NULL,
+    closing_total_value TEXT NOT NULL,
+    closing_fx_rate_to_base TEXT NOT NULL,
+    closing_cost_basis TEXT NOT NULL,
+    closing_net_contribution TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (account_id, month_start)
+);
+
+-- Progress of each goal at the end of a closed month; the current month is always computed.
+-- Valuation, goal and loan writes delete the rows they invalidate; readers fill in missing months.
+CREATE TABLE IF NOT EXISTS goal_monthly_progress (
+    goal_id TEXT NOT NULL REFERENCES goals(id) ON DELETE CASCADE,
+    month_start DATE NOT NULL,
+    as_of DATE NOT NULL,
+    init_value DOUBLE NOT NULL,
+    current_value DOUBLE NOT NULL,
+    growth DOUBLE NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (goal_id, month_start)
+);
+CREATE INDEX IF NOT EXISTS idx_goal_monthly_progress_month ON goal_monthly_progress(month_start);
+
+-- Summaries of the valuations stored so far
+INSERT OR REPLACE INTO account_monthly_summaries (
+    account_id, month_start, first_date, last_date, value_count, account_currency,
+    opening_total_value, closing_total_value, closing_fx_rate_to_base, closing_cost_basis,
+    closing_net_contribution
+)
+SELECT
+    account_id, month_start, first_date, last_date, value_count, account_currency,
+    opening_total_value, total_value, fx_rate_to_base, cost_basis, net_contribution
+FROM (
+    SELECT
+        account_id,
+        date(valuation_date, 'start of month') AS month_start,
+        valuation_date,
+        account_currency,
+        total_value,
+        fx_rate_to_base,
+        cost_basis,
+        net_contribution,
+        MIN(valuation_date) OVER month AS first_date,
+        MAX(valuation_date) OVER month AS last_date,
+        COUNT(*) OVER month AS value_count,
+        FIRST_VALUE(total_value) OVER (month ORDER BY valuation_date) AS opening_total_value,
+        ROW_NUMBER() OVER (month ORDER BY valuation_date DESC) AS rn
+    FROM daily_account_valuation
+    WINDOW month AS (PARTITION BY account_id, date(valuation_date, 'start of month'))
+)
+WHERE rn = 1;
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_goal_progress_history_as_of;
+DROP TABLE IF EXISTS goal_progress_history;
@@ -0,0 +1,15 @@
+-- Progress of each goal as of a day, written after every change it is computed from (portfolio
+-- updates, goal and allocation writes) so the goals screen only reads. Valuation, goal and loan
+-- writes delete the rows they invalidate; readers compute and store a missing day.
+CREATE TABLE IF NOT EXISTS goal_progress_history (
+    goal_id TEXT NOT NULL REFERENCES goals(id) ON DELETE CASCADE,
+    as_of DATE NOT NULL,
+    init_value DOUBLE NOT NULL,
+    current_value DOUBLE NOT NULL,
+    growth DOUBLE NOT NULL,
+    -- JSON array of the per-account breakdown
+    allocation_details TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now')),
+    PRIMARY KEY (goal_id, as_of)
+);
+CREATE INDEX IF NOT EXISTS idx_goal_progress_history_as_of ON goal_progress_history(as_of);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS property_appraisals;
+DROP TABLE IF EXISTS properties;
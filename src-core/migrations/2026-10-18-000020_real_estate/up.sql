@@ -0,0 +1,33 @@
+-- Purchase details of the property held by a real-estate account. The account holds the
+-- property as a manual asset whose quotes are the purchase price and the appraisals.
+CREATE TABLE IF NOT EXISTS properties (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL UNIQUE REFERENCES accounts(id) ON DELETE CASCADE,
+    asset_id TEXT NOT NULL,
+    -- The ADD_HOLDING activity that puts the property in the account at its purchase price
+    activity_id TEXT REFERENCES activities(id) ON DELETE SET NULL,
+    name TEXT NOT NULL,
+    address TEXT,
+    purchase_date TEXT NOT NULL,
+    purchase_price TEXT NOT NULL,
+    -- Registration fee, notary, brokerage and other costs of the purchase
+    transaction_costs TEXT NOT NULL DEFAULT '0',
+    -- Liability account holding the mortgage, if any
+    mortgage_account_id TEXT REFERENCES accounts(id) ON DELETE SET NULL,
+    -- Income source the rent is paid as, if let out
+    rental_income_source_id TEXT REFERENCES income_sources(id) ON DELETE SET NULL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Market values of a property over time
+CREATE TABLE IF NOT EXISTS property_appraisals (
+    id TEXT PRIMARY KEY,
+    property_id TEXT NOT NULL REFERENCES properties(id) ON DELETE CASCADE,
+    appraisal_date TEXT NOT NULL,
+    value TEXT NOT NULL,
+    source TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (property_id, appraisal_date)
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS bonus_plan_lines;
+DROP TABLE IF EXISTS bonus_plans;
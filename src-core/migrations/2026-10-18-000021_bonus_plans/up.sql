@@ -0,0 +1,33 @@
+-- How an expected 13th-month salary or Tet bonus is split once confirmed. Confirming records
+-- the bonus as a draft deposit into the account and each goal share as a goal allocation.
+CREATE TABLE IF NOT EXISTS bonus_plans (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    income_source_id TEXT REFERENCES income_sources(id) ON DELETE SET NULL,
+    amount TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    expected_date TEXT NOT NULL,
+    -- The draft DEPOSIT activity the bonus is recorded as until it arrives
+    activity_id TEXT REFERENCES activities(id) ON DELETE SET NULL,
+    note TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Shares of a bonus plan: a goal, an envelope of the account, or lì xì and gift spending
+CREATE TABLE IF NOT EXISTS bonus_plan_lines (
+    id TEXT PRIMARY KEY,
+    plan_id TEXT NOT NULL REFERENCES bonus_plans(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    goal_id TEXT REFERENCES goals(id) ON DELETE SET NULL,
+    envelope_id TEXT REFERENCES envelopes(id) ON DELETE SET NULL,
+    label TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    -- Allocation created for a goal share on confirmation
+    allocation_id TEXT REFERENCES goals_allocation(id) ON DELETE SET NULL,
+    -- Transfer that filled an envelope share once the bonus arrived
+    transfer_id TEXT REFERENCES envelope_transfers(id) ON DELETE SET NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX IF NOT EXISTS idx_bonus_plan_lines_plan_id ON bonus_plan_lines(plan_id);
@@ -0,0 +1,4 @@
+ALTER TABLE planned_cash_flows DROP COLUMN interest_rate;
+ALTER TABLE planned_cash_flows DROP COLUMN term_months;
+ALTER TABLE planned_cash_flows DROP COLUMN bank_code;
+DROP TABLE IF EXISTS deposit_rates;
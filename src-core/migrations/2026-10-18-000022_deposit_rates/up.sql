@@ -0,0 +1,17 @@
+-- Counter rates of term deposits at Vietnamese banks, one row per bank and tenor. Refreshed
+-- from the configured feed, or from the rates bundled with the app.
+CREATE TABLE IF NOT EXISTS deposit_rates (
+    bank_code TEXT NOT NULL,
+    term_months INTEGER NOT NULL,
+    bank_name TEXT NOT NULL,
+    -- Annual interest rate in percent
+    rate TEXT NOT NULL,
+    source TEXT NOT NULL,
+    fetched_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (bank_code, term_months)
+);
+
+-- Bank, tenor and annual rate of a term deposit, for maturities to be compared against the rates
+ALTER TABLE planned_cash_flows ADD COLUMN bank_code TEXT;
+ALTER TABLE planned_cash_flows ADD COLUMN term_months INTEGER;
+ALTER TABLE planned_cash_flows ADD COLUMN interest_rate TEXT;
@@ -0,0 +1 @@
+ALTER TABLE activity_import_profiles DROP COLUMN number_locale;
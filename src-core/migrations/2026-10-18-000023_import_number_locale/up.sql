@@ -0,0 +1,2 @@
+-- How numbers are written in the files imported with a profile: en, vi or auto
+ALTER TABLE activity_import_profiles ADD COLUMN number_locale TEXT NOT NULL DEFAULT 'en';
@@ -0,0 +1,2 @@
+-- Lunar month of bills due once a year on a lunar date; due_day is then the lunar day
+ALTER TABLE bills ADD COLUMN lunar_month INTEGER;
@@ -0,0 +1,33 @@
+-- Government and corporate bonds held by an account. Each holding is a manual asset whose
+-- quotes are the clean price plus the interest accrued since the last coupon.
+CREATE TABLE IF NOT EXISTS bonds (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    asset_id TEXT NOT NULL,
+    -- The ADD_HOLDING activity that puts the bonds in the account at their purchase price
+    activity_id TEXT REFERENCES activities(id) ON DELETE SET NULL,
+    -- Listing or issue code, e.g. TD2535001 or VHM12403
+    code TEXT NOT NULL,
+    issuer TEXT NOT NULL,
+    -- GOVERNMENT or CORPORATE
+    issuer_type TEXT NOT NULL,
+    -- Per bond, in the account currency
+    face_value TEXT NOT NULL,
+    quantity TEXT NOT NULL,
+    -- Annual coupon rate in percent
+    coupon_rate TEXT NOT NULL,
+    -- ANNUAL, SEMI_ANNUAL, QUARTERLY, MONTHLY or AT_MATURITY
+    coupon_frequency TEXT NOT NULL,
+    issue_date TEXT NOT NULL,
+    maturity_date TEXT NOT NULL,
+    purchase_date TEXT NOT NULL,
+    -- Price paid per bond
+    purchase_price TEXT NOT NULL,
+    -- Latest clean market price per bond; the face value when unset
+    market_price TEXT,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_bonds_account_id ON bonds(account_id);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS private_loan_repayments;
+DROP TABLE IF EXISTS private_loans;
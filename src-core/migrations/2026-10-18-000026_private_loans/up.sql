@@ -0,0 +1,41 @@
+-- Money lent to friends, family or through P2P platforms. Each loan is a manual asset whose
+-- quotes are the principal still owed.
+CREATE TABLE IF NOT EXISTS private_loans (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    asset_id TEXT NOT NULL,
+    -- The ADD_HOLDING activity that puts the loan in the account at its principal
+    activity_id TEXT REFERENCES activities(id) ON DELETE SET NULL,
+    borrower TEXT NOT NULL,
+    -- P2P platform the loan was made through, e.g. Tima or Vay Muon
+    platform TEXT,
+    principal TEXT NOT NULL,
+    -- Agreed annual interest rate in percent
+    interest_rate TEXT NOT NULL,
+    -- INSTALLMENT, INTEREST_ONLY or BULLET
+    repayment_plan TEXT NOT NULL DEFAULT 'INSTALLMENT',
+    term_months INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    -- Text a repayment's comment contains; the borrower's name when unset
+    match_pattern TEXT,
+    written_off BOOLEAN NOT NULL DEFAULT FALSE,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_private_loans_account_id ON private_loans(account_id);
+
+CREATE TABLE IF NOT EXISTS private_loan_repayments (
+    id TEXT PRIMARY KEY,
+    loan_id TEXT NOT NULL REFERENCES private_loans(id) ON DELETE CASCADE,
+    activity_id TEXT REFERENCES activities(id) ON DELETE CASCADE,
+    payment_date TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    -- MATCHED or MANUAL
+    source TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_private_loan_repayments_loan ON private_loan_repayments(loan_id);
+CREATE INDEX IF NOT EXISTS idx_private_loan_repayments_activity ON private_loan_repayments(activity_id);
@@ -0,0 +1,29 @@
+-- Shares granted by an employer under an ESOP. Each grant is a manual asset held as one unit
+-- whose quotes are what the vested shares are worth above their strike price.
+CREATE TABLE IF NOT EXISTS esop_grants (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    asset_id TEXT NOT NULL,
+    -- The ADD_HOLDING activity that puts the grant in the account
+    activity_id TEXT REFERENCES activities(id) ON DELETE SET NULL,
+    company TEXT NOT NULL,
+    -- Ticker of a listed company, e.g. FPT; unlisted companies use the fair value
+    symbol TEXT,
+    grant_date TEXT NOT NULL,
+    vesting_start_date TEXT NOT NULL,
+    total_shares TEXT NOT NULL,
+    -- Per share, in the account currency; 0 for share awards
+    strike_price TEXT NOT NULL,
+    cliff_months INTEGER NOT NULL DEFAULT 0,
+    vesting_months INTEGER NOT NULL,
+    vesting_interval_months INTEGER NOT NULL,
+    -- Months after each vesting date before the shares can be sold
+    lockup_months INTEGER NOT NULL DEFAULT 0,
+    -- Price per share when there is no ticker or it has no quotes
+    fair_value TEXT,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_esop_grants_account_id ON esop_grants(account_id);
@@ -0,0 +1,21 @@
+-- Monthly purchase programs for fund certificates (SIP). Purchases are the BUY activities of
+-- the fund in the plan's account, matched to the installment nearest their date.
+CREATE TABLE IF NOT EXISTS sip_plans (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    -- The fund bought, e.g. E1VFVN30
+    asset_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    -- Invested each month, fees included, in the account currency
+    amount TEXT NOT NULL,
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    -- The monthly contribution that puts the installments in the cash flow forecast
+    planned_cash_flow_id TEXT REFERENCES planned_cash_flows(id) ON DELETE SET NULL,
+    is_active BOOLEAN NOT NULL DEFAULT 1,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_sip_plans_account_id ON sip_plans(account_id);
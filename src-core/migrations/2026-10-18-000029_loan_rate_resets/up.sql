@@ -0,0 +1,13 @@
+-- Base rates set by the lender over a floating-rate loan's life. Each one prices the
+-- floating payments due after its date.
+CREATE TABLE IF NOT EXISTS loan_rate_resets (
+    id TEXT PRIMARY KEY,
+    loan_id TEXT NOT NULL REFERENCES loans(id) ON DELETE CASCADE,
+    effective_date TEXT NOT NULL,
+    -- Annual rate in percent; the loan's floating margin is added on top
+    base_rate TEXT NOT NULL,
+    note TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_loan_rate_resets_loan ON loan_rate_resets(loan_id, effective_date);
@@ -0,0 +1,2 @@
+-- When a goal was archived; archived goals are hidden but keep their allocations and history
+ALTER TABLE goals ADD COLUMN archived_at TEXT;
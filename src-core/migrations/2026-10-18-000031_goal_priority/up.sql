@@ -0,0 +1,2 @@
+-- Funding order of goals; new cash goes to a higher priority first
+ALTER TABLE goals ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1 @@
+ALTER TABLE goals DROP COLUMN depends_on_goal_id;
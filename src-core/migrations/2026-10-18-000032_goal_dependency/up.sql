@@ -0,0 +1,3 @@
+-- Goal that has to be achieved before this one takes new cash. Deleting the prerequisite
+-- clears it in the repository, so no foreign key here.
+ALTER TABLE goals ADD COLUMN depends_on_goal_id TEXT;
@@ -0,0 +1,2 @@
+-- Currency the goal's target is set in; NULL keeps the base currency
+ALTER TABLE goals ADD COLUMN currency TEXT;
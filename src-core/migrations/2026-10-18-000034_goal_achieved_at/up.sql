@@ -0,0 +1,2 @@
+-- Day a goal reached its target; NULL while it has not
+ALTER TABLE goals ADD COLUMN achieved_at TEXT;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS account_group_members;
+DROP TABLE IF EXISTS account_groups;
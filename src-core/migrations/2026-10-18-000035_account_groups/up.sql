@@ -0,0 +1,17 @@
+-- Named groups of accounts, e.g. "Retirement" or "Bank savings"; a group may sit inside another
+CREATE TABLE IF NOT EXISTS account_groups (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    parent_id TEXT REFERENCES account_groups(id) ON DELETE SET NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Accounts in a group; an account may be in several groups
+CREATE TABLE IF NOT EXISTS account_group_members (
+    group_id TEXT NOT NULL REFERENCES account_groups(id) ON DELETE CASCADE,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    PRIMARY KEY (group_id, account_id)
+);
+
+CREATE INDEX idx_account_group_members_account ON account_group_members(account_id);
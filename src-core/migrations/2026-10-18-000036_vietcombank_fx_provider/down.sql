@@ -0,0 +1 @@
+DELETE FROM market_data_providers WHERE id = 'VIETCOMBANK';
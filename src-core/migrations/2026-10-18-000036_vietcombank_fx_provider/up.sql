@@ -0,0 +1,4 @@
+-- Daily exchange rates against the dong from Vietcombank's rate board; needs no API key
+INSERT OR IGNORE INTO market_data_providers (id, name, description, url, priority, enabled, logo_filename, last_synced_at, last_sync_status, last_sync_error)
+VALUES
+    ('VIETCOMBANK', 'Vietcombank', 'Daily exchange rates against VND published by Vietcombank. Set it as the data source of a currency pair such as USD/VND; the board only has the current day, so earlier rates come from another provider or manual entries.', 'https://portal.vietcombank.com.vn/Personal/TG/Pages/ty-gia.aspx', 4, TRUE, NULL, NULL, NULL, NULL);
@@ -0,0 +1,19 @@
+-- Indices accounts and goals can be compared against. Their prices are kept in `quotes`
+-- under the benchmark's symbol, fetched from its data source or entered by hand (MANUAL).
+CREATE TABLE IF NOT EXISTS benchmarks (
+    id TEXT NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    symbol TEXT NOT NULL UNIQUE,
+    data_source TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    -- Added by the user rather than shipped with the app
+    is_custom BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT OR IGNORE INTO benchmarks (id, name, symbol, data_source, currency, is_custom)
+VALUES
+    ('VNINDEX', 'VN-Index', 'VNINDEX', 'VN_MARKET', 'VND', FALSE),
+    ('VN30', 'VN30', 'VN30', 'VN_MARKET', 'VND', FALSE),
+    ('HNXINDEX', 'HNX-Index', 'HNXINDEX', 'VN_MARKET', 'VND', FALSE),
+    ('SP500', 'S&P 500', '^GSPC', 'YAHOO', 'USD', FALSE);
@@ -0,0 +1,22 @@
+-- Term deposits (sổ tiết kiệm) held in a cash account. Values are worked out from the terms
+-- on any date, so nothing but the contract is stored.
+CREATE TABLE IF NOT EXISTS term_deposits (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    bank_name TEXT NOT NULL,
+    -- In the account currency
+    principal TEXT NOT NULL,
+    -- Annual rate in percent
+    interest_rate TEXT NOT NULL,
+    term_months INTEGER NOT NULL,
+    start_date TEXT NOT NULL,
+    -- SIMPLE, MONTHLY or QUARTERLY
+    compounding TEXT NOT NULL DEFAULT 'SIMPLE',
+    -- NONE, PRINCIPAL or PRINCIPAL_AND_INTEREST
+    rollover TEXT NOT NULL DEFAULT 'NONE',
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_term_deposits_account_id ON term_deposits(account_id);
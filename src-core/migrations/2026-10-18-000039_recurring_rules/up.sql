@@ -0,0 +1,25 @@
+-- Activities that repeat on a schedule (salary, fund purchases, loan payments). Occurrences
+-- are posted to the ledger as they fall due; next_due_date is the first one not yet posted.
+CREATE TABLE IF NOT EXISTS recurring_rules (
+    id TEXT PRIMARY KEY,
+    account_id TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    activity_type TEXT NOT NULL,
+    -- Asset bought by a BUY rule; cash movements use the cash asset
+    asset_id TEXT,
+    amount TEXT NOT NULL,
+    -- NULL for the account currency
+    currency TEXT,
+    -- ONCE, MONTHLY, QUARTERLY or YEARLY
+    frequency TEXT NOT NULL DEFAULT 'MONTHLY',
+    start_date TEXT NOT NULL,
+    end_date TEXT,
+    -- NULL once every occurrence has been posted
+    next_due_date TEXT,
+    is_paused BOOLEAN NOT NULL DEFAULT FALSE,
+    comment TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_recurring_rules_account_id ON recurring_rules(account_id);
@@ -0,0 +1,18 @@
+-- One net worth statement per month in the base currency, for month-over-month changes. A month
+-- is recorded at its end once that statement is generated, and to date while it runs; a later
+-- statement for the month's last day replaces it.
+CREATE TABLE IF NOT EXISTS net_worth_history (
+    -- First day of the month
+    month_start DATE NOT NULL PRIMARY KEY,
+    -- Date the statement was drawn up for
+    as_of DATE NOT NULL,
+    base_currency TEXT NOT NULL,
+    total_assets TEXT NOT NULL,
+    total_liabilities TEXT NOT NULL,
+    net_worth TEXT NOT NULL,
+    goal_allocated TEXT NOT NULL,
+    free_assets TEXT NOT NULL,
+    -- JSON array of the per-class breakdown
+    assets_by_class TEXT NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
@@ -0,0 +1,2 @@
+-- What the loan is for; margin debt and the like are often repaid interest-only
+ALTER TABLE loans ADD COLUMN kind TEXT NOT NULL DEFAULT 'OTHER';
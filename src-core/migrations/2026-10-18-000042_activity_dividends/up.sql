@@ -0,0 +1,12 @@
+-- Dividend details recorded against a DIVIDEND activity, whose amount is the cash received
+-- after tax. Dates are NULL when unknown; the activity date stands in for the pay date.
+CREATE TABLE IF NOT EXISTS activity_dividends (
+    activity_id TEXT PRIMARY KEY NOT NULL REFERENCES activities(id) ON DELETE CASCADE,
+    ex_date TEXT,
+    pay_date TEXT,
+    amount_per_share TEXT,
+    tax_withheld TEXT NOT NULL DEFAULT '0',
+    -- Paid out in new units rather than cash
+    is_reinvested BOOLEAN NOT NULL DEFAULT FALSE,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,226 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Database row for `account_groups`
+#[derive(Queryable, Identifiable, Insertable, Selectable, Debug, Clone)]
+#[diesel(table_name = crate::schema::account_groups)]
+pub struct AccountGroupDB {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Database row for `account_group_members`
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::account_group_members)]
+pub struct AccountGroupMemberDB {
+    pub group_id: String,
+    pub account_id: String,
+}
+
+/// A named set of accounts, such as "Retirement" or "Bank savings". Groups nest: a group
+/// covers its own accounts and those of the groups inside it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGroup {
+    pub id: String,
+    pub name: String,
+    /// Group this one sits in; `None` at the top
+    pub parent_id: Option<String>,
+    /// Accounts put in this group directly
+    pub account_ids: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl AccountGroup {
+    pub fn from_db(db: AccountGroupDB, account_ids: Vec<String>) -> Self {
+        AccountGroup {
+            id: db.id,
+            name: db.name,
+            parent_id: db.parent_id,
+            account_ids,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        }
+    }
+}
+
+/// Input for creating or updating a group; its accounts replace the ones it had
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAccountGroup {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub account_ids: Vec<String>,
+}
+
+impl NewAccountGroup {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Account group name cannot be empty".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Value of a group and the groups inside it, in the base currency. An account in several
+/// groups of the tree counts once in the total.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGroupValuation {
+    pub group_id: String,
+    pub name: String,
+    pub base_currency: String,
+    /// Latest value of each account the group covers, its subgroups' included
+    pub account_values: BTreeMap<String, Decimal>,
+    pub total_value: Decimal,
+    pub children: Vec<AccountGroupValuation>,
+}
+
+/// A group can only sit in an existing group, and never inside itself through any chain of
+/// groups
+pub fn validate_group_parent(
+    groups: &[AccountGroup],
+    group_id: Option<&str>,
+    parent_id: Option<&str>,
+) -> Result<()> {
+    let Some(parent) = parent_id else {
+        return Ok(());
+    };
+    let parents: HashMap<&str, Option<&str>> = groups
+        .iter()
+        .map(|group| (group.id.as_str(), group.parent_id.as_deref()))
+        .collect();
+    if !parents.contains_key(parent) {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Parent account group {} does not exist",
+            parent
+        ))));
+    }
+    let mut seen = HashSet::new();
+    let mut current = Some(parent);
+    while let Some(ancestor) = current {
+        if Some(ancestor) == group_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "An account group cannot sit inside itself".to_string(),
+            )));
+        }
+        if !seen.insert(ancestor) {
+            break;
+        }
+        current = parents.get(ancestor).copied().flatten();
+    }
+    Ok(())
+}
+
+/// Accounts a group covers: its own and those of every group inside it, each once, in the
+/// order they are first met
+pub fn group_account_ids(groups: &[AccountGroup], group_id: &str) -> Vec<String> {
+    let mut account_ids = Vec::new();
+    let mut seen_accounts = HashSet::new();
+    let mut seen_groups = HashSet::new();
+    let mut pending = vec![group_id];
+    while let Some(current) = pending.pop() {
+        if !seen_groups.insert(current) {
+            continue;
+        }
+        for group in groups {
+            if group.id == current {
+                for account_id in &group.account_ids {
+                    if seen_accounts.insert(account_id.as_str()) {
+                        account_ids.push(account_id.clone());
+                    }
+                }
+            } else if group.parent_id.as_deref() == Some(current) {
+                pending.push(&group.id);
+            }
+        }
+    }
+    account_ids
+}
+
+/// Rolls account values up a group and the groups inside it; accounts without a value count
+/// as zero
+pub fn roll_up_group(
+    groups: &[AccountGroup],
+    group: &AccountGroup,
+    values: &HashMap<String, Decimal>,
+    base_currency: &str,
+) -> AccountGroupValuation {
+    let account_values: BTreeMap<String, Decimal> = group_account_ids(groups, &group.id)
+        .into_iter()
+        .map(|account_id| {
+            let value = values.get(&account_id).copied().unwrap_or_default();
+            (account_id, value)
+        })
+        .collect();
+    let children = groups
+        .iter()
+        .filter(|child| child.parent_id.as_deref() == Some(group.id.as_str()))
+        .map(|child| roll_up_group(groups, child, values, base_currency))
+        .collect();
+    AccountGroupValuation {
+        group_id: group.id.clone(),
+        name: group.name.clone(),
+        base_currency: base_currency.to_string(),
+        total_value: account_values.values().copied().sum(),
+        account_values,
+        children,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn group(id: &str, parent_id: Option<&str>, account_ids: &[&str]) -> AccountGroup {
+        let now = chrono::Utc::now().naive_utc();
+        AccountGroup {
+            id: id.to_string(),
+            name: id.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            account_ids: account_ids.iter().map(|a| a.to_string()).collect(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn groups_roll_up_their_subgroups_counting_each_account_once() {
+        let groups = vec![
+            group("savings", None, &["vcb"]),
+            group("banks", Some("savings"), &["vcb", "tcb"]),
+            group("brokers", None, &["ssi"]),
+        ];
+        assert_eq!(group_account_ids(&groups, "savings"), vec!["vcb", "tcb"]);
+
+        let values = HashMap::from([
+            ("vcb".to_string(), dec!(100_000_000)),
+            ("tcb".to_string(), dec!(50_000_000)),
+            ("ssi".to_string(), dec!(70_000_000)),
+        ]);
+        let valuation = roll_up_group(&groups, &groups[0], &values, "VND");
+        assert_eq!(valuation.total_value, dec!(150_000_000));
+        assert_eq!(valuation.children.len(), 1);
+        assert_eq!(valuation.children[0].total_value, dec!(150_000_000));
+
+        assert!(validate_group_parent(&groups, Some("banks"), Some("savings")).is_ok());
+        assert!(validate_group_parent(&groups, Some("savings"), Some("banks")).is_err());
+        assert!(validate_group_parent(&groups, None, Some("missing")).is_err());
+    }
+}
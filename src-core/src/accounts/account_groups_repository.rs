@@ -0,0 +1,152 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::account_groups_model::{
+    AccountGroup, AccountGroupDB, AccountGroupMemberDB, NewAccountGroup,
+};
+use super::accounts_traits::AccountGroupRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::{account_group_members, account_groups};
+
+pub struct AccountGroupRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl AccountGroupRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        AccountGroupRepository { pool, writer }
+    }
+
+    fn load_group(conn: &mut SqliteConnection, id: &str) -> Result<AccountGroup> {
+        let group = account_groups::table
+            .find(id)
+            .select(AccountGroupDB::as_select())
+            .first(conn)?;
+        let account_ids = account_group_members::table
+            .filter(account_group_members::group_id.eq(id))
+            .select(account_group_members::account_id)
+            .order(account_group_members::account_id.asc())
+            .load::<String>(conn)?;
+        Ok(AccountGroup::from_db(group, account_ids))
+    }
+
+    fn replace_members(
+        conn: &mut SqliteConnection,
+        id: &str,
+        account_ids: Vec<String>,
+    ) -> Result<()> {
+        diesel::delete(
+            account_group_members::table.filter(account_group_members::group_id.eq(id)),
+        )
+        .execute(conn)?;
+        let mut account_ids = account_ids;
+        account_ids.sort();
+        account_ids.dedup();
+        let members: Vec<AccountGroupMemberDB> = account_ids
+            .into_iter()
+            .map(|account_id| AccountGroupMemberDB {
+                group_id: id.to_string(),
+                account_id,
+            })
+            .collect();
+        diesel::insert_into(account_group_members::table)
+            .values(&members)
+            .execute(conn)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AccountGroupRepositoryTrait for AccountGroupRepository {
+    fn get_groups(&self) -> Result<Vec<AccountGroup>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut members: HashMap<String, Vec<String>> = HashMap::new();
+        for member in account_group_members::table
+            .order(account_group_members::account_id.asc())
+            .load::<AccountGroupMemberDB>(&mut conn)?
+        {
+            members
+                .entry(member.group_id)
+                .or_default()
+                .push(member.account_id);
+        }
+        Ok(account_groups::table
+            .select(AccountGroupDB::as_select())
+            .order(account_groups::name.asc())
+            .load::<AccountGroupDB>(&mut conn)?
+            .into_iter()
+            .map(|group| {
+                let account_ids = members.remove(&group.id).unwrap_or_default();
+                AccountGroup::from_db(group, account_ids)
+            })
+            .collect())
+    }
+
+    async fn insert_group(&self, group: NewAccountGroup) -> Result<AccountGroup> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<AccountGroup> {
+                let now = chrono::Utc::now().naive_utc();
+                let record = AccountGroupDB {
+                    id: group.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    name: group.name.trim().to_string(),
+                    parent_id: group.parent_id,
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(account_groups::table)
+                    .values(&record)
+                    .execute(conn)?;
+                Self::replace_members(conn, &record.id, group.account_ids)?;
+                Self::load_group(conn, &record.id)
+            })
+            .await
+    }
+
+    async fn update_group(&self, id: &str, group: NewAccountGroup) -> Result<AccountGroup> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<AccountGroup> {
+                let updated = diesel::update(account_groups::table.find(&id_owned))
+                    .set((
+                        account_groups::name.eq(group.name.trim()),
+                        account_groups::parent_id.eq(group.parent_id),
+                        account_groups::updated_at.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)?;
+                if updated == 0 {
+                    return Err(diesel::result::Error::NotFound.into());
+                }
+                Self::replace_members(conn, &id_owned, group.account_ids)?;
+                Self::load_group(conn, &id_owned)
+            })
+            .await
+    }
+
+    async fn delete_group(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                // Groups inside move up to the deleted group's parent rather than to the top
+                let parent: Option<String> = account_groups::table
+                    .find(&id_owned)
+                    .select(account_groups::parent_id)
+                    .first(conn)
+                    .optional()?
+                    .flatten();
+                diesel::update(
+                    account_groups::table.filter(account_groups::parent_id.eq(&id_owned)),
+                )
+                .set(account_groups::parent_id.eq(parent))
+                .execute(conn)?;
+                Ok(diesel::delete(account_groups::table.find(&id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
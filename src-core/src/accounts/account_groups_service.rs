@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use super::account_groups_model::{
+    group_account_ids, roll_up_group, validate_group_parent, AccountGroup,
+    AccountGroupValuation, NewAccountGroup,
+};
+use super::accounts_traits::{
+    AccountGroupRepositoryTrait, AccountGroupServiceTrait, AccountRepositoryTrait,
+};
+use crate::errors::{Error, Result, ValidationError};
+use crate::portfolio::valuation::ValuationServiceTrait;
+
+pub struct AccountGroupService {
+    repository: Arc<dyn AccountGroupRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    valuation_service: Arc<dyn ValuationServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl AccountGroupService {
+    pub fn new(
+        repository: Arc<dyn AccountGroupRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        valuation_service: Arc<dyn ValuationServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            valuation_service,
+            base_currency,
+        }
+    }
+
+    fn find_group(groups: &[AccountGroup], group_id: &str) -> Result<AccountGroup> {
+        groups
+            .iter()
+            .find(|group| group.id == group_id)
+            .cloned()
+            .ok_or_else(|| diesel::result::Error::NotFound.into())
+    }
+
+    fn validate(&self, group_id: Option<&str>, group: &NewAccountGroup) -> Result<()> {
+        group.validate()?;
+        validate_group_parent(
+            &self.repository.get_groups()?,
+            group_id,
+            group.parent_id.as_deref(),
+        )?;
+        let known: HashSet<String> = self
+            .account_repository
+            .list(None, Some(&group.account_ids))?
+            .into_iter()
+            .map(|account| account.id)
+            .collect();
+        if let Some(unknown) = group.account_ids.iter().find(|id| !known.contains(*id)) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Account {} does not exist",
+                unknown
+            ))));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AccountGroupServiceTrait for AccountGroupService {
+    fn get_account_groups(&self) -> Result<Vec<AccountGroup>> {
+        self.repository.get_groups()
+    }
+
+    async fn create_account_group(&self, group: NewAccountGroup) -> Result<AccountGroup> {
+        self.validate(group.id.as_deref(), &group)?;
+        self.repository.insert_group(group).await
+    }
+
+    async fn update_account_group(
+        &self,
+        id: &str,
+        group: NewAccountGroup,
+    ) -> Result<AccountGroup> {
+        self.validate(Some(id), &group)?;
+        self.repository.update_group(id, group).await
+    }
+
+    async fn delete_account_group(&self, id: &str) -> Result<usize> {
+        self.repository.delete_group(id).await
+    }
+
+    fn get_group_account_ids(&self, group_id: &str) -> Result<Vec<String>> {
+        let groups = self.repository.get_groups()?;
+        Self::find_group(&groups, group_id)?;
+        Ok(group_account_ids(&groups, group_id))
+    }
+
+    fn get_group_valuation(&self, group_id: &str) -> Result<AccountGroupValuation> {
+        let groups = self.repository.get_groups()?;
+        let group = Self::find_group(&groups, group_id)?;
+        let account_ids = group_account_ids(&groups, group_id);
+        let values: HashMap<String, Decimal> = self
+            .valuation_service
+            .get_latest_valuations(&account_ids)?
+            .into_iter()
+            .map(|valuation| {
+                let value = valuation.total_value * valuation.fx_rate_to_base;
+                (valuation.account_id, value)
+            })
+            .collect();
+        let base_currency = self.base_currency.read().unwrap().clone();
+        Ok(roll_up_group(&groups, &group, &values, &base_currency))
+    }
+}
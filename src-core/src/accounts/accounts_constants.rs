@@ -1,2 +1,14 @@
 /// Default account type for new accounts
 pub const DEFAULT_ACCOUNT_TYPE: &str = "SECURITIES";
+
+/// Account type for bank and cash accounts
+pub const ACCOUNT_TYPE_CASH: &str = "CASH";
+
+/// Account type for loans and other debts
+pub const ACCOUNT_TYPE_LIABILITY: &str = "LIABILITY";
+
+/// Account type for a house, apartment or land plot held as a single property
+pub const ACCOUNT_TYPE_REAL_ESTATE: &str = "REAL_ESTATE";
+
+/// Account type for physical gold (SJC bars, DOJI rings, ...) kept at home or in a vault
+pub const ACCOUNT_TYPE_GOLD: &str = "GOLD";
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use diesel::sqlite::SqliteConnection;
 
+use super::account_groups_model::{AccountGroup, AccountGroupValuation, NewAccountGroup};
 use super::accounts_model::{Account, AccountUpdate, NewAccount};
 use crate::errors::Result;
 
@@ -38,3 +39,26 @@ pub trait AccountServiceTrait: Send + Sync {
     fn get_active_accounts(&self) -> Result<Vec<Account>>;
     fn get_accounts_by_ids(&self, account_ids: &[String]) -> Result<Vec<Account>>;
 }
+
+#[async_trait]
+pub trait AccountGroupRepositoryTrait: Send + Sync {
+    /// Every group with the accounts put in it directly, by name
+    fn get_groups(&self) -> Result<Vec<AccountGroup>>;
+    async fn insert_group(&self, group: NewAccountGroup) -> Result<AccountGroup>;
+    async fn update_group(&self, id: &str, group: NewAccountGroup) -> Result<AccountGroup>;
+    /// Groups inside the deleted one move up to its parent
+    async fn delete_group(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait AccountGroupServiceTrait: Send + Sync {
+    fn get_account_groups(&self) -> Result<Vec<AccountGroup>>;
+    async fn create_account_group(&self, group: NewAccountGroup) -> Result<AccountGroup>;
+    async fn update_account_group(&self, id: &str, group: NewAccountGroup)
+        -> Result<AccountGroup>;
+    async fn delete_account_group(&self, id: &str) -> Result<usize>;
+    /// Accounts a group covers, those of the groups inside it included
+    fn get_group_account_ids(&self, group_id: &str) -> Result<Vec<String>>;
+    /// Latest value of a group and the groups inside it, in the base currency
+    fn get_group_valuation(&self, group_id: &str) -> Result<AccountGroupValuation>;
+}
@@ -1,5 +1,6 @@
 use crate::activities::activities_errors::ActivityError;
 use crate::Result;
+use crate::utils::number_format::NumberLocale;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use diesel::prelude::*;
 use rust_decimal::prelude::FromPrimitive;
@@ -354,12 +355,31 @@ pub struct ActivityImport {
     pub account_name: Option<String>,
     pub symbol_name: Option<String>,
     pub errors: Option<std::collections::HashMap<String, Vec<String>>>,
+    /// Notes that don't block the import, such as an odd-lot trade
+    #[serde(default)]
+    pub warnings: Option<Vec<String>>,
     pub is_draft: bool,
     pub is_valid: bool,
     pub line_number: Option<i32>,
     pub asset_data_source: Option<String>,
 }
 
+/// Shares bought on a Vietnamese exchange that have not settled yet (T+2.5)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSettlement {
+    pub activity_id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub exchange: String,
+    pub quantity: Decimal,
+    pub trade_date: NaiveDate,
+    pub settles_on: NaiveDate,
+    /// When the shares can first be sold
+    pub available_at: DateTime<Utc>,
+    pub odd_lot: bool,
+}
+
 /// Model for sorting activities
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -384,6 +404,7 @@ pub struct ImportMapping {
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub account_mappings: String,
+    pub number_locale: String,
 }
 
 /// Model for activity import mapping data with structured mappings
@@ -395,6 +416,9 @@ pub struct ImportMappingData {
     pub activity_mappings: std::collections::HashMap<String, Vec<String>>,
     pub symbol_mappings: std::collections::HashMap<String, String>,
     pub account_mappings: std::collections::HashMap<String, String>,
+    /// How numbers in the profile's files are written
+    #[serde(default)]
+    pub number_locale: NumberLocale,
 }
 
 impl Default for ImportMappingData {
@@ -435,6 +459,7 @@ impl Default for ImportMappingData {
             activity_mappings,
             symbol_mappings: std::collections::HashMap::new(),
             account_mappings: std::collections::HashMap::new(),
+            number_locale: NumberLocale::default(),
         }
     }
 }
@@ -447,6 +472,7 @@ impl ImportMapping {
             activity_mappings: serde_json::from_str(&self.activity_mappings)?,
             symbol_mappings: serde_json::from_str(&self.symbol_mappings)?,
             account_mappings: serde_json::from_str(&self.account_mappings)?,
+            number_locale: self.number_locale.parse().unwrap_or_default(),
         })
     }
 
@@ -459,6 +485,7 @@ impl ImportMapping {
             activity_mappings: serde_json::to_string(&data.activity_mappings)?,
             symbol_mappings: serde_json::to_string(&data.symbol_mappings)?,
             account_mappings: serde_json::to_string(&data.account_mappings)?,
+            number_locale: data.number_locale.as_str().to_string(),
             created_at: chrono::Utc::now().naive_utc(),
             updated_at: chrono::Utc::now().naive_utc(),
         })
@@ -765,6 +792,10 @@ impl From<ActivityUpdate> for ActivityDB {
 pub struct IncomeData {
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub date: String,
+    /// Activity day (`%Y-%m-%d`), used to place income in fiscal years that start mid-month
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    #[serde(skip)]
+    pub day: String,
     #[diesel(sql_type = diesel::sql_types::Text)]
     pub income_type: String,
     #[diesel(sql_type = diesel::sql_types::Text)]
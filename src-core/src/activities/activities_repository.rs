@@ -510,6 +510,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
         let mut conn = get_connection(&self.pool)?;
 
         let query = "SELECT strftime('%Y-%m', a.activity_date) as date,
+             strftime('%Y-%m-%d', a.activity_date) as day,
              a.activity_type as income_type,
              a.asset_id as symbol,
              COALESCE(ast.name, 'Unknown') as symbol_name,
@@ -528,6 +529,8 @@ impl ActivityRepositoryTrait for ActivityRepository {
             #[diesel(sql_type = diesel::sql_types::Text)]
             pub date: String,
             #[diesel(sql_type = diesel::sql_types::Text)]
+            pub day: String,
+            #[diesel(sql_type = diesel::sql_types::Text)]
             pub income_type: String,
             #[diesel(sql_type = diesel::sql_types::Text)]
             pub symbol: String,
@@ -550,6 +553,7 @@ impl ActivityRepositoryTrait for ActivityRepository {
                 let amount = Decimal::from_str(&raw.amount).unwrap_or_else(|_| Decimal::zero());
                 Ok(IncomeData {
                     date: raw.date,
+                    day: raw.day,
                     income_type: raw.income_type,
                     symbol: raw.symbol,
                     symbol_name: raw.symbol_name,
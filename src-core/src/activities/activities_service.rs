@@ -1,19 +1,45 @@
-use chrono::Utc;
+use chrono::{Days, NaiveDate, Utc};
 use log::debug;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tracing::instrument;
 
 use crate::accounts::{Account, AccountServiceTrait};
 use crate::activities::activities_errors::ActivityError;
 use crate::activities::activities_model::*;
-use crate::activities::{ActivityRepositoryTrait, ActivityServiceTrait};
-use crate::market_data::MarketDataServiceTrait;
+use crate::activities::{
+    ActivityRepositoryTrait, ActivityServiceTrait, ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_SELL,
+};
+use crate::market_data::{MarketDataServiceTrait, DATA_SOURCE_VN_MARKET};
 use crate::market_data::market_data_model::{Quote, DataSource};
 use crate::Result;
-use crate::assets::AssetServiceTrait;
+use crate::assets::{Asset, AssetServiceTrait};
 use crate::fx::FxServiceTrait;
+use crate::vn_market::trading_rules::{self, LotKind, VnExchange, BOARD_LOT};
 use uuid::Uuid;
 use chrono::DateTime;
 
+/// Exchange of a HOSE, HNX or UPCoM listed asset, which the VN market provider records as
+/// the asset sub-class
+fn vn_exchange(asset: &Asset) -> Option<VnExchange> {
+    if asset.data_source != DATA_SOURCE_VN_MARKET {
+        return None;
+    }
+    asset
+        .asset_sub_class
+        .as_deref()
+        .and_then(VnExchange::from_code)
+}
+
+/// Trade date in Vietnam time of an activity date given as RFC3339 or YYYY-MM-DD
+fn parse_trade_date(activity_date: &str) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(activity_date)
+        .map(|dt| trading_rules::trade_date(dt.with_timezone(&Utc)))
+        .or_else(|_| NaiveDate::parse_from_str(activity_date, "%Y-%m-%d"))
+        .ok()
+}
+
 /// Service for managing activities
 pub struct ActivityService {
     activity_repository: Arc<dyn ActivityRepositoryTrait>,
@@ -65,6 +91,16 @@ impl ActivityService {
                 .await?;
         }
 
+        if !activity.is_draft {
+            self.check_trading_rules(
+                &asset,
+                &activity.activity_type,
+                &activity.activity_date,
+                activity.quantity,
+                activity.unit_price,
+            )?;
+        }
+
         Ok(activity)
     }
 
@@ -95,8 +131,64 @@ impl ActivityService {
                 .await?;
         }
 
+        if !activity.is_draft {
+            self.check_trading_rules(
+                &asset,
+                &activity.activity_type,
+                &activity.activity_date,
+                activity.quantity,
+                activity.unit_price,
+            )?;
+        }
+
         Ok(activity)
     }
+
+    /// Checks a trade in a HOSE, HNX or UPCoM stock against the exchange's order rules and
+    /// returns its lot kind. Other activities and assets pass unchecked.
+    fn check_trading_rules(
+        &self,
+        asset: &Asset,
+        activity_type: &str,
+        activity_date: &str,
+        quantity: Option<Decimal>,
+        unit_price: Option<Decimal>,
+    ) -> Result<Option<LotKind>> {
+        if activity_type != ACTIVITY_TYPE_BUY && activity_type != ACTIVITY_TYPE_SELL {
+            return Ok(None);
+        }
+        let Some(exchange) = vn_exchange(asset) else {
+            return Ok(None);
+        };
+
+        let lot = trading_rules::check_lot(exchange, quantity.unwrap_or_default())
+            .map_err(ActivityError::InvalidData)?;
+        if let (Some(price), Some(date)) = (unit_price, parse_trade_date(activity_date)) {
+            // Without quotes before the trade there is no band to check against
+            if let Some(reference) = self.reference_price(&asset.id, date)? {
+                trading_rules::check_price(exchange, price, reference)
+                    .map_err(ActivityError::InvalidData)?;
+            }
+        }
+        Ok(Some(lot))
+    }
+
+    /// Close of the last session before `date`, looking back a week
+    fn reference_price(&self, asset_id: &str, date: NaiveDate) -> Result<Option<Decimal>> {
+        let symbols = HashSet::from([asset_id.to_string()]);
+        let quotes = self
+            .market_data_service
+            .get_historical_quotes_for_symbols_in_range(
+                &symbols,
+                date - Days::new(7),
+                date - Days::new(1),
+            )?;
+        Ok(quotes
+            .into_iter()
+            .filter(|quote| quote.timestamp.date_naive() < date)
+            .max_by_key(|quote| quote.timestamp)
+            .map(|quote| quote.close))
+    }
 }
 
 #[async_trait::async_trait]
@@ -235,6 +327,7 @@ impl ActivityServiceTrait for ActivityService {
     }
 
     /// Verifies the activities import from CSV file
+    #[instrument(name = "activities.check_import", skip(self, activities), fields(rows = activities.len()))]
     async fn check_activities_import(
         &self,
         account_id: String,
@@ -244,6 +337,37 @@ impl ActivityServiceTrait for ActivityService {
 
         let mut activities_with_status: Vec<ActivityImport> = Vec::new();
 
+        // Each symbol and currency pair is resolved once, not once per row. Manual assets
+        // that don't exist yet are created together in a single write.
+        let context_currency = |activity: &ActivityImport| {
+            if !activity.currency.is_empty() {
+                activity.currency.clone()
+            } else {
+                // Fallback to account currency for context if import data lacks currency
+                account.currency.clone()
+            }
+        };
+        let manual_assets: Vec<(String, String)> = activities
+            .iter()
+            .filter(|activity| activity.asset_data_source.as_deref() == Some("MANUAL"))
+            .map(|activity| (activity.symbol.clone(), context_currency(activity)))
+            .collect();
+        let mut resolved_assets: HashMap<String, std::result::Result<Asset, String>> =
+            HashMap::new();
+        if !manual_assets.is_empty() {
+            match self.asset_service.create_manual_assets(manual_assets).await {
+                Ok(assets) => {
+                    for asset in assets {
+                        resolved_assets.insert(asset.symbol.clone(), Ok(asset));
+                    }
+                }
+                // Rows fall back to creating their asset one at a time, with their own errors
+                Err(e) => debug!("Could not create manual assets together: {}", e),
+            }
+        }
+        let mut registered_pairs: HashMap<String, std::result::Result<(), String>> =
+            HashMap::new();
+
         for mut activity in activities {
             activity.id = Some(Uuid::new_v4().to_string());
             if activity.account_name.is_none() {
@@ -253,25 +377,28 @@ impl ActivityServiceTrait for ActivityService {
                 activity.account_id = Some(account_id.clone());
             }
 
-            // Determine context currency for potential asset creation during check
-            let asset_context_currency = if !activity.currency.is_empty() {
-                activity.currency.clone()
-            } else {
-                // Fallback to account currency for context if import data lacks currency
-                account.currency.clone()
-            };
-
-            // Check if this should be created as a manual asset
-            let is_manual_asset = activity.asset_data_source.as_ref().map_or(false, |source| source == "MANUAL");
-
-            let symbol_profile_result = if is_manual_asset {
-                // Create manual asset directly without searching providers
-                self.asset_service.create_manual_asset(&activity.symbol, asset_context_currency).await
-            } else {
-                // Try to find/create asset from market data providers
-                self.asset_service
-                    .get_or_create_asset(&activity.symbol, Some(asset_context_currency))
-                    .await
+            let symbol_profile_result = match resolved_assets.get(&activity.symbol) {
+                Some(resolved) => resolved.clone(),
+                None => {
+                    // Determine context currency for potential asset creation during check
+                    let asset_context_currency = context_currency(&activity);
+                    // Check if this should be created as a manual asset
+                    let is_manual_asset = activity.asset_data_source.as_deref() == Some("MANUAL");
+                    let resolved = if is_manual_asset {
+                        // Create manual asset directly without searching providers
+                        self.asset_service
+                            .create_manual_asset(&activity.symbol, asset_context_currency)
+                            .await
+                    } else {
+                        // Try to find/create asset from market data providers
+                        self.asset_service
+                            .get_or_create_asset(&activity.symbol, Some(asset_context_currency))
+                            .await
+                    }
+                    .map_err(|e| e.to_string());
+                    resolved_assets.insert(activity.symbol.clone(), resolved.clone());
+                    resolved
+                }
             };
 
             let (mut is_valid, mut error_message) = (true, None);
@@ -279,7 +406,7 @@ impl ActivityServiceTrait for ActivityService {
             match symbol_profile_result {
                 Ok(asset) => {
                     // symbol_profile_result now returns Asset
-                    activity.symbol_name = asset.name; // Use asset name
+                    activity.symbol_name = asset.name.clone(); // Use asset name
 
                     // Check if activity currency (from import) is valid and handle FX
                     if activity.currency.is_empty() {
@@ -288,14 +415,23 @@ impl ActivityServiceTrait for ActivityService {
                         error_message =
                             Some("Activity currency is missing in the import data.".to_string());
                     } else if activity.currency != account.currency {
-                        match self
-                            .fx_service
-                            .register_currency_pair(
-                                account.currency.as_str(),
-                                activity.currency.as_str(), // Use currency from import data
-                            )
-                            .await
-                        {
+                        let registered = match registered_pairs.get(&activity.currency) {
+                            Some(registered) => registered.clone(),
+                            None => {
+                                let registered = self
+                                    .fx_service
+                                    .register_currency_pair(
+                                        account.currency.as_str(),
+                                        activity.currency.as_str(), // Use currency from import data
+                                    )
+                                    .await
+                                    .map_err(|e| e.to_string());
+                                registered_pairs
+                                    .insert(activity.currency.clone(), registered.clone());
+                                registered
+                            }
+                        };
+                        match registered {
                             Ok(_) => { /* FX pair registered or already exists */ }
                             Err(e) => {
                                 is_valid = false;
@@ -304,6 +440,33 @@ impl ActivityServiceTrait for ActivityService {
                             }
                         }
                     }
+
+                    if is_valid && !activity.is_draft {
+                        match self.check_trading_rules(
+                            &asset,
+                            &activity.activity_type,
+                            &activity.date,
+                            Some(activity.quantity),
+                            Some(activity.unit_price),
+                        ) {
+                            Ok(Some(LotKind::Odd)) => {
+                                activity.warnings = Some(vec![format!(
+                                    "Odd lot of {} shares, traded on the odd-lot board",
+                                    activity.quantity.normalize()
+                                )]);
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                is_valid = false;
+                                error_message = Some(match e {
+                                    crate::Error::Activity(ActivityError::InvalidData(message)) => {
+                                        message
+                                    }
+                                    e => e.to_string(),
+                                });
+                            }
+                        }
+                    }
                 }
                 Err(e) => {
                     // Failed to get or create asset
@@ -330,6 +493,7 @@ impl ActivityServiceTrait for ActivityService {
     }
 
     /// Imports activities after validation
+    #[instrument(name = "activities.import", skip(self, activities), fields(rows = activities.len()))]
     async fn import_activities(
         &self,
         account_id: String,
@@ -375,7 +539,8 @@ impl ActivityServiceTrait for ActivityService {
             .await?;
         debug!("Successfully imported {} activities", count);
 
-        // Create initial quotes for manual assets
+        // Create initial quotes for manual assets, all in one write
+        let mut initial_quotes = Vec::new();
         for activity in &validated_activities {
             // Check if activity is marked as manual
             let is_manual = activity.asset_data_source.as_ref().map_or(false, |source| source == "MANUAL");
@@ -398,11 +563,14 @@ impl ActivityServiceTrait for ActivityService {
                     created_at: chrono::Utc::now(),
                 };
 
-                if let Err(e) = self.market_data_service.add_quote(&quote).await {
-                    debug!("Failed to create quote for manual asset {}: {}", activity.symbol, e);
-                } else {
-                    debug!("Created initial quote for manual asset {} at price {}", activity.symbol, activity.unit_price);
-                }
+                initial_quotes.push(quote);
+            }
+        }
+
+        if !initial_quotes.is_empty() {
+            match self.market_data_service.add_quotes(&initial_quotes).await {
+                Ok(()) => debug!("Created {} initial quotes for manual assets", initial_quotes.len()),
+                Err(e) => debug!("Failed to create initial quotes for manual assets: {}", e),
             }
         }
 
@@ -443,4 +611,55 @@ impl ActivityServiceTrait for ActivityService {
             .await?;
         Ok(mapping_data)
     }
+
+    fn get_pending_settlements(
+        &self,
+        account_id: Option<String>,
+    ) -> Result<Vec<PendingSettlement>> {
+        let activities = match account_id {
+            Some(account_id) => self
+                .activity_repository
+                .get_activities_by_account_id(&account_id)?,
+            None => self.activity_repository.get_trading_activities()?,
+        };
+
+        let now = Utc::now();
+        let mut exchanges: HashMap<String, Option<VnExchange>> = HashMap::new();
+        let mut pending = Vec::new();
+        for activity in activities {
+            if activity.activity_type != ACTIVITY_TYPE_BUY || activity.is_draft {
+                continue;
+            }
+            let trade_date = trading_rules::trade_date(activity.activity_date);
+            let available_at = trading_rules::available_at(trade_date);
+            if available_at <= now {
+                continue;
+            }
+            let exchange = *exchanges
+                .entry(activity.asset_id.clone())
+                .or_insert_with(|| {
+                    self.asset_service
+                        .get_asset_by_id(&activity.asset_id)
+                        .ok()
+                        .as_ref()
+                        .and_then(vn_exchange)
+                });
+            let Some(exchange) = exchange else {
+                continue;
+            };
+
+            pending.push(PendingSettlement {
+                activity_id: activity.id,
+                account_id: activity.account_id,
+                asset_id: activity.asset_id,
+                exchange: exchange.code().to_string(),
+                odd_lot: activity.quantity < BOARD_LOT,
+                quantity: activity.quantity,
+                trade_date,
+                settles_on: trading_rules::settlement_date(trade_date),
+                available_at,
+            });
+        }
+        Ok(pending)
+    }
 }
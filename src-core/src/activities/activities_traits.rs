@@ -96,4 +96,7 @@ pub trait ActivityServiceTrait: Send + Sync {
         &self,
         mapping_data: ImportMappingData,
     ) -> Result<ImportMappingData>;
+    /// Buys on HOSE, HNX or UPCoM whose shares are not yet available to sell
+    fn get_pending_settlements(&self, account_id: Option<String>)
+        -> Result<Vec<PendingSettlement>>;
 }
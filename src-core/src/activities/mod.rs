@@ -11,7 +11,7 @@ pub use activities_model::{
     Activity, ActivityBulkIdentifierMapping, ActivityBulkMutationError,
     ActivityBulkMutationRequest, ActivityBulkMutationResult, ActivityDB, ActivityDetails,
     ActivityImport, ActivitySearchResponse, ActivitySearchResponseMeta, ActivityType,
-    ActivityUpdate, ImportMapping, ImportMappingData, NewActivity, Sort,
+    ActivityUpdate, ImportMapping, ImportMappingData, NewActivity, PendingSettlement, Sort,
 };
 pub use activities_repository::ActivityRepository;
 pub use activities_service::ActivityService;
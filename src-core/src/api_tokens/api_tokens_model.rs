@@ -0,0 +1,149 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Every issued secret starts with this, so leaked tokens are easy to grep for
+pub const API_TOKEN_PREFIX: &str = "wvn_";
+
+/// What a token may do on the server's token-protected routes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ApiTokenScope {
+    /// Dashboard reads: goals, holdings, net worth, GraphQL and the event channel
+    ReadOnly,
+    /// Report downloads such as the ledger export
+    Reports,
+    /// Handing over activities and quotes as import payloads
+    Import,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiTokenScope::ReadOnly => "READ_ONLY",
+            ApiTokenScope::Reports => "REPORTS",
+            ApiTokenScope::Import => "IMPORT",
+        }
+    }
+}
+
+impl FromStr for ApiTokenScope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "READ_ONLY" => Ok(ApiTokenScope::ReadOnly),
+            "REPORTS" => Ok(ApiTokenScope::Reports),
+            "IMPORT" => Ok(ApiTokenScope::Import),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown API token scope: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `api_tokens`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::api_tokens)]
+pub struct ApiTokenDB {
+    pub id: String,
+    pub name: String,
+    pub token_hash: String,
+    pub token_prefix: String,
+    pub scopes: String,
+    pub expires_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+/// An issued token; the secret itself is only returned once, by `create_token`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    /// Start of the secret, e.g. `wvn_1a2b3c4d`
+    pub token_prefix: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    /// Updated at most once a minute
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl ApiToken {
+    /// Not revoked and not past its expiry
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+
+    pub fn allows(&self, scope: ApiTokenScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+impl TryFrom<ApiTokenDB> for ApiToken {
+    type Error = Error;
+
+    fn try_from(db: ApiTokenDB) -> Result<Self> {
+        Ok(ApiToken {
+            id: db.id,
+            name: db.name,
+            token_prefix: db.token_prefix,
+            scopes: db
+                .scopes
+                .split(',')
+                .filter(|scope| !scope.is_empty())
+                .map(ApiTokenScope::from_str)
+                .collect::<Result<_>>()?,
+            expires_at: db.expires_at,
+            revoked_at: db.revoked_at,
+            last_used_at: db.last_used_at,
+            created_at: db.created_at,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewApiToken {
+    pub name: String,
+    pub scopes: Vec<ApiTokenScope>,
+    /// Never expires when `None`
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl NewApiToken {
+    pub fn validate(&self, now: NaiveDateTime) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.scopes.is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "scopes".to_string(),
+            )));
+        }
+        if self.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Token expiry must be in the future".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A newly created token with its secret, which is not stored and cannot be shown again
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IssuedApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
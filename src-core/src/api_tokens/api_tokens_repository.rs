@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use super::api_tokens_model::{ApiToken, ApiTokenDB};
+use super::api_tokens_traits::ApiTokenRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::api_tokens;
+
+pub struct ApiTokenRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl ApiTokenRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        ApiTokenRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepositoryTrait for ApiTokenRepository {
+    fn get_tokens(&self) -> Result<Vec<ApiToken>> {
+        let mut conn = get_connection(&self.pool)?;
+        api_tokens::table
+            .order(api_tokens::created_at.desc())
+            .load::<ApiTokenDB>(&mut conn)?
+            .into_iter()
+            .map(ApiToken::try_from)
+            .collect()
+    }
+
+    fn get_token(&self, id: &str) -> Result<ApiToken> {
+        let mut conn = get_connection(&self.pool)?;
+        api_tokens::table
+            .find(id)
+            .first::<ApiTokenDB>(&mut conn)?
+            .try_into()
+    }
+
+    fn find_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>> {
+        let mut conn = get_connection(&self.pool)?;
+        api_tokens::table
+            .filter(api_tokens::token_hash.eq(token_hash))
+            .first::<ApiTokenDB>(&mut conn)
+            .optional()?
+            .map(ApiToken::try_from)
+            .transpose()
+    }
+
+    async fn insert_token(&self, token: ApiTokenDB) -> Result<ApiToken> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<ApiToken> {
+                diesel::insert_into(api_tokens::table)
+                    .values(&token)
+                    .get_result::<ApiTokenDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn revoke_token(&self, id: &str, revoked_at: NaiveDateTime) -> Result<ApiToken> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<ApiToken> {
+                diesel::update(api_tokens::table.find(id_owned))
+                    .set(api_tokens::revoked_at.eq(Some(revoked_at)))
+                    .get_result::<ApiTokenDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_token(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(api_tokens::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    async fn record_use(&self, id: &str, used_at: NaiveDateTime) -> Result<()> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::update(api_tokens::table.find(id_owned))
+                    .set(api_tokens::last_used_at.eq(Some(used_at)))
+                    .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+}
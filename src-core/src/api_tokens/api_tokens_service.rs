@@ -0,0 +1,274 @@
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::api_tokens_model::{
+    ApiToken, ApiTokenDB, IssuedApiToken, NewApiToken, API_TOKEN_PREFIX,
+};
+use super::api_tokens_traits::{ApiTokenRepositoryTrait, ApiTokenServiceTrait};
+use crate::errors::Result;
+
+/// Random bytes in a secret, hex-encoded after the prefix
+const SECRET_BYTES: usize = 32;
+/// Hex characters of the secret kept for display
+const DISPLAY_CHARS: usize = 8;
+/// `last_used_at` is only rewritten when older than this, so busy dashboards don't write on every call
+const LAST_USED_RESOLUTION_SECS: i64 = 60;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{:02x}", byte);
+        out
+    })
+}
+
+fn hash_secret(secret: &str) -> String {
+    to_hex(&Sha256::digest(secret.as_bytes()))
+}
+
+/// Issues and checks tokens; secrets are long random strings, so a plain SHA-256 is enough to store them
+pub struct ApiTokenService {
+    repository: Arc<dyn ApiTokenRepositoryTrait>,
+}
+
+impl ApiTokenService {
+    pub fn new(repository: Arc<dyn ApiTokenRepositoryTrait>) -> Self {
+        ApiTokenService { repository }
+    }
+}
+
+#[async_trait]
+impl ApiTokenServiceTrait for ApiTokenService {
+    fn get_tokens(&self) -> Result<Vec<ApiToken>> {
+        self.repository.get_tokens()
+    }
+
+    fn get_token(&self, id: &str) -> Result<ApiToken> {
+        self.repository.get_token(id)
+    }
+
+    async fn create_token(&self, token: NewApiToken) -> Result<IssuedApiToken> {
+        let now = Utc::now().naive_utc();
+        token.validate(now)?;
+
+        let mut bytes = [0u8; SECRET_BYTES];
+        OsRng.fill_bytes(&mut bytes);
+        let random = to_hex(&bytes);
+        let secret = format!("{}{}", API_TOKEN_PREFIX, random);
+
+        let mut scopes = Vec::with_capacity(token.scopes.len());
+        for scope in token.scopes {
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        let record = ApiTokenDB {
+            id: Uuid::new_v4().to_string(),
+            name: token.name.trim().to_string(),
+            token_hash: hash_secret(&secret),
+            token_prefix: format!("{}{}", API_TOKEN_PREFIX, &random[..DISPLAY_CHARS]),
+            scopes: scopes
+                .iter()
+                .map(|scope| scope.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            expires_at: token.expires_at,
+            revoked_at: None,
+            last_used_at: None,
+            created_at: now,
+        };
+        let token = self.repository.insert_token(record).await?;
+        Ok(IssuedApiToken { token, secret })
+    }
+
+    async fn revoke_token(&self, id: &str) -> Result<ApiToken> {
+        let token = self.repository.get_token(id)?;
+        if token.revoked_at.is_some() {
+            return Ok(token);
+        }
+        self.repository
+            .revoke_token(id, Utc::now().naive_utc())
+            .await
+    }
+
+    async fn delete_token(&self, id: &str) -> Result<usize> {
+        self.repository.delete_token(id).await
+    }
+
+    async fn authenticate(&self, secret: &str) -> Result<Option<ApiToken>> {
+        if !secret.starts_with(API_TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let now = Utc::now().naive_utc();
+        let Some(mut token) = self.repository.find_by_hash(&hash_secret(secret))? else {
+            return Ok(None);
+        };
+        if !token.is_active(now) {
+            return Ok(None);
+        }
+        let stale = token
+            .last_used_at
+            .is_none_or(|used| now - used >= Duration::seconds(LAST_USED_RESOLUTION_SECS));
+        if stale {
+            self.repository.record_use(&token.id, now).await?;
+            token.last_used_at = Some(now);
+        }
+        Ok(Some(token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_tokens::ApiTokenScope;
+    use chrono::NaiveDateTime;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryTokenRepository {
+        rows: Mutex<Vec<ApiTokenDB>>,
+        uses: Mutex<usize>,
+    }
+
+    impl InMemoryTokenRepository {
+        fn update(&self, id: &str, change: impl FnOnce(&mut ApiTokenDB)) -> Result<ApiToken> {
+            let mut rows = self.rows.lock().unwrap();
+            let row = rows.iter_mut().find(|row| row.id == id).unwrap();
+            change(row);
+            row.clone().try_into()
+        }
+    }
+
+    #[async_trait]
+    impl ApiTokenRepositoryTrait for InMemoryTokenRepository {
+        fn get_tokens(&self) -> Result<Vec<ApiToken>> {
+            let rows = self.rows.lock().unwrap().clone();
+            rows.into_iter().map(ApiToken::try_from).collect()
+        }
+
+        fn get_token(&self, id: &str) -> Result<ApiToken> {
+            self.update(id, |_| {})
+        }
+
+        fn find_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>> {
+            let rows = self.rows.lock().unwrap();
+            rows.iter()
+                .find(|row| row.token_hash == token_hash)
+                .cloned()
+                .map(ApiToken::try_from)
+                .transpose()
+        }
+
+        async fn insert_token(&self, token: ApiTokenDB) -> Result<ApiToken> {
+            self.rows.lock().unwrap().push(token.clone());
+            token.try_into()
+        }
+
+        async fn revoke_token(&self, id: &str, revoked_at: NaiveDateTime) -> Result<ApiToken> {
+            self.update(id, |row| row.revoked_at = Some(revoked_at))
+        }
+
+        async fn delete_token(&self, id: &str) -> Result<usize> {
+            let mut rows = self.rows.lock().unwrap();
+            let before = rows.len();
+            rows.retain(|row| row.id != id);
+            Ok(before - rows.len())
+        }
+
+        async fn record_use(&self, id: &str, used_at: NaiveDateTime) -> Result<()> {
+            *self.uses.lock().unwrap() += 1;
+            self.update(id, |row| row.last_used_at = Some(used_at))
+                .map(|_| ())
+        }
+    }
+
+    fn new_token(scopes: Vec<ApiTokenScope>) -> NewApiToken {
+        NewApiToken {
+            name: "Home dashboard".to_string(),
+            scopes,
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn issued_secret_authenticates_until_revoked() {
+        let repo = Arc::new(InMemoryTokenRepository::default());
+        let service = ApiTokenService::new(repo.clone());
+
+        let issued = service
+            .create_token(new_token(vec![
+                ApiTokenScope::ReadOnly,
+                ApiTokenScope::ReadOnly,
+            ]))
+            .await
+            .unwrap();
+        assert!(issued.secret.starts_with(API_TOKEN_PREFIX));
+        assert!(issued.secret.starts_with(&issued.token.token_prefix));
+        assert_eq!(issued.token.scopes, vec![ApiTokenScope::ReadOnly]);
+        assert!(!repo.rows.lock().unwrap()[0]
+            .token_hash
+            .contains(&issued.secret));
+
+        let token = service.authenticate(&issued.secret).await.unwrap().unwrap();
+        assert!(token.allows(ApiTokenScope::ReadOnly));
+        assert!(!token.allows(ApiTokenScope::Import));
+        assert!(token.last_used_at.is_some());
+        // A second call within the minute doesn't write again
+        service.authenticate(&issued.secret).await.unwrap().unwrap();
+        assert_eq!(*repo.uses.lock().unwrap(), 1);
+
+        assert!(service
+            .authenticate("wvn_not-a-token")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(service
+            .authenticate("dashboard-token")
+            .await
+            .unwrap()
+            .is_none());
+
+        let revoked = service.revoke_token(&issued.token.id).await.unwrap();
+        assert!(revoked.revoked_at.is_some());
+        assert!(service
+            .authenticate(&issued.secret)
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(service.get_tokens().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_tokens_are_rejected() {
+        let repo = Arc::new(InMemoryTokenRepository::default());
+        let service = ApiTokenService::new(repo.clone());
+
+        let mut past = new_token(vec![ApiTokenScope::Reports]);
+        past.expires_at = Some(Utc::now().naive_utc() - Duration::days(1));
+        assert!(service.create_token(past).await.is_err());
+        assert!(service.create_token(new_token(vec![])).await.is_err());
+
+        let mut expiring = new_token(vec![ApiTokenScope::Reports]);
+        expiring.expires_at = Some(Utc::now().naive_utc() + Duration::days(1));
+        let issued = service.create_token(expiring).await.unwrap();
+        assert!(service
+            .authenticate(&issued.secret)
+            .await
+            .unwrap()
+            .is_some());
+
+        repo.update(&issued.token.id, |row| {
+            row.expires_at = Some(Utc::now().naive_utc() - Duration::seconds(1))
+        })
+        .unwrap();
+        assert!(service
+            .authenticate(&issued.secret)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
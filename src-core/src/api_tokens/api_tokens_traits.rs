@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use super::api_tokens_model::{ApiToken, ApiTokenDB, IssuedApiToken, NewApiToken};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait ApiTokenRepositoryTrait: Send + Sync {
+    fn get_tokens(&self) -> Result<Vec<ApiToken>>;
+    fn get_token(&self, id: &str) -> Result<ApiToken>;
+    fn find_by_hash(&self, token_hash: &str) -> Result<Option<ApiToken>>;
+    async fn insert_token(&self, token: ApiTokenDB) -> Result<ApiToken>;
+    async fn revoke_token(&self, id: &str, revoked_at: NaiveDateTime) -> Result<ApiToken>;
+    async fn delete_token(&self, id: &str) -> Result<usize>;
+    async fn record_use(&self, id: &str, used_at: NaiveDateTime) -> Result<()>;
+}
+
+#[async_trait]
+pub trait ApiTokenServiceTrait: Send + Sync {
+    /// Newest first
+    fn get_tokens(&self) -> Result<Vec<ApiToken>>;
+    fn get_token(&self, id: &str) -> Result<ApiToken>;
+    async fn create_token(&self, token: NewApiToken) -> Result<IssuedApiToken>;
+    /// Keeps the token listed so its history stays visible
+    async fn revoke_token(&self, id: &str) -> Result<ApiToken>;
+    async fn delete_token(&self, id: &str) -> Result<usize>;
+    /// The active token for `secret`, if any; records that it was used
+    async fn authenticate(&self, secret: &str) -> Result<Option<ApiToken>>;
+}
@@ -0,0 +1,11 @@
+mod api_tokens_model;
+mod api_tokens_repository;
+mod api_tokens_service;
+mod api_tokens_traits;
+
+pub use api_tokens_model::{
+    ApiToken, ApiTokenDB, ApiTokenScope, IssuedApiToken, NewApiToken, API_TOKEN_PREFIX,
+};
+pub use api_tokens_repository::ApiTokenRepository;
+pub use api_tokens_service::ApiTokenService;
+pub use api_tokens_traits::{ApiTokenRepositoryTrait, ApiTokenServiceTrait};
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// `app_settings` key holding the argon2 PHC string of the passphrase
+pub const PASSPHRASE_HASH_KEY: &str = "app_lock_passphrase_hash";
+
+/// `app_settings` key holding the inactivity timeout in minutes (0 disables auto-lock)
+pub const AUTO_LOCK_MINUTES_KEY: &str = "app_lock_auto_lock_minutes";
+
+pub const DEFAULT_AUTO_LOCK_MINUTES: u32 = 5;
+
+/// Commands that stay callable while the app is locked
+pub const APP_LOCK_ALLOWED_COMMANDS: &[&str] = &[
+    "get_app_lock_status",
+    "unlock_app",
+    "get_platform",
+    "is_mobile",
+    "is_desktop",
+    "get_app_info",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLockStatus {
+    /// A passphrase has been configured
+    pub enabled: bool,
+    pub locked: bool,
+    pub auto_lock_minutes: u32,
+}
@@ -0,0 +1,287 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::app_lock_model::{
+    AppLockStatus, AUTO_LOCK_MINUTES_KEY, DEFAULT_AUTO_LOCK_MINUTES, PASSPHRASE_HASH_KEY,
+};
+use crate::errors::{DatabaseError, Error, Result, ValidationError};
+use crate::settings::SettingsRepositoryTrait;
+
+const MIN_PASSPHRASE_LENGTH: usize = 4;
+
+#[async_trait]
+pub trait AppLockServiceTrait: Send + Sync {
+    fn get_status(&self) -> AppLockStatus;
+    /// Records user activity. Returns true when the app is (or just became) locked.
+    fn record_activity(&self) -> bool;
+    /// Locks the app if the inactivity timeout has elapsed. Returns true if it locked now.
+    fn check_auto_lock(&self) -> bool;
+    fn lock(&self);
+    fn unlock(&self, passphrase: &str) -> Result<AppLockStatus>;
+    /// Checks the passphrase without changing the lock state. Always succeeds when no passphrase is set.
+    fn verify_passphrase(&self, passphrase: &str) -> Result<()>;
+    async fn set_passphrase(
+        &self,
+        current_passphrase: Option<&str>,
+        new_passphrase: &str,
+    ) -> Result<AppLockStatus>;
+    async fn remove_passphrase(&self, current_passphrase: &str) -> Result<AppLockStatus>;
+    async fn set_auto_lock_minutes(&self, minutes: u32) -> Result<AppLockStatus>;
+}
+
+/// Keeps the lock state in memory and the passphrase hash in `app_settings`.
+/// The app starts locked whenever a passphrase is configured.
+pub struct AppLockService {
+    settings_repository: Arc<dyn SettingsRepositoryTrait>,
+    passphrase_hash: Mutex<Option<String>>,
+    locked: AtomicBool,
+    auto_lock_minutes: AtomicU32,
+    last_activity: Mutex<Instant>,
+}
+
+impl AppLockService {
+    pub fn new(settings_repository: Arc<dyn SettingsRepositoryTrait>) -> Result<Self> {
+        let passphrase_hash = read_optional_setting(settings_repository.as_ref(), PASSPHRASE_HASH_KEY)?
+            .filter(|h| !h.is_empty());
+        let auto_lock_minutes =
+            read_optional_setting(settings_repository.as_ref(), AUTO_LOCK_MINUTES_KEY)?
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_AUTO_LOCK_MINUTES);
+
+        Ok(Self {
+            locked: AtomicBool::new(passphrase_hash.is_some()),
+            passphrase_hash: Mutex::new(passphrase_hash),
+            auto_lock_minutes: AtomicU32::new(auto_lock_minutes),
+            last_activity: Mutex::new(Instant::now()),
+            settings_repository,
+        })
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.passphrase_hash.lock().unwrap().is_some()
+    }
+
+    fn verify(&self, passphrase: &str) -> Result<()> {
+        let stored = self.passphrase_hash.lock().unwrap().clone();
+        let Some(stored) = stored else {
+            return Ok(());
+        };
+        let parsed = PasswordHash::new(&stored).map_err(|e| Error::Secret(e.to_string()))?;
+        Argon2::default()
+            .verify_password(passphrase.as_bytes(), &parsed)
+            .map_err(|_| {
+                Error::Validation(ValidationError::InvalidInput(
+                    "Incorrect passphrase".to_string(),
+                ))
+            })
+    }
+
+    fn inactivity_elapsed(&self) -> bool {
+        let minutes = self.auto_lock_minutes.load(Ordering::SeqCst);
+        minutes > 0
+            && self.last_activity.lock().unwrap().elapsed()
+                >= Duration::from_secs(minutes as u64 * 60)
+    }
+}
+
+fn read_optional_setting(repo: &dyn SettingsRepositoryTrait, key: &str) -> Result<Option<String>> {
+    match repo.get_setting(key) {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::Database(DatabaseError::QueryFailed(diesel::result::Error::NotFound))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[async_trait]
+impl AppLockServiceTrait for AppLockService {
+    fn get_status(&self) -> AppLockStatus {
+        AppLockStatus {
+            enabled: self.is_enabled(),
+            locked: self.locked.load(Ordering::SeqCst),
+            auto_lock_minutes: self.auto_lock_minutes.load(Ordering::SeqCst),
+        }
+    }
+
+    fn record_activity(&self) -> bool {
+        if !self.is_enabled() {
+            return false;
+        }
+        if self.locked.load(Ordering::SeqCst) || self.check_auto_lock() {
+            return true;
+        }
+        *self.last_activity.lock().unwrap() = Instant::now();
+        false
+    }
+
+    fn check_auto_lock(&self) -> bool {
+        if !self.is_enabled() || self.locked.load(Ordering::SeqCst) {
+            return false;
+        }
+        if self.inactivity_elapsed() {
+            log::info!("Locking app after inactivity timeout");
+            self.locked.store(true, Ordering::SeqCst);
+            return true;
+        }
+        false
+    }
+
+    fn lock(&self) {
+        if self.is_enabled() {
+            self.locked.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn unlock(&self, passphrase: &str) -> Result<AppLockStatus> {
+        self.verify(passphrase)?;
+        self.locked.store(false, Ordering::SeqCst);
+        *self.last_activity.lock().unwrap() = Instant::now();
+        Ok(self.get_status())
+    }
+
+    fn verify_passphrase(&self, passphrase: &str) -> Result<()> {
+        self.verify(passphrase)
+    }
+
+    async fn set_passphrase(
+        &self,
+        current_passphrase: Option<&str>,
+        new_passphrase: &str,
+    ) -> Result<AppLockStatus> {
+        if self.is_enabled() {
+            self.verify(current_passphrase.unwrap_or_default())?;
+        }
+        if new_passphrase.chars().count() < MIN_PASSPHRASE_LENGTH {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Passphrase must be at least {} characters",
+                MIN_PASSPHRASE_LENGTH
+            ))));
+        }
+
+        let salt = SaltString::generate(&mut OsRng);
+        let hash = Argon2::default()
+            .hash_password(new_passphrase.as_bytes(), &salt)
+            .map_err(|e| Error::Secret(e.to_string()))?
+            .to_string();
+
+        self.settings_repository
+            .update_setting(PASSPHRASE_HASH_KEY, &hash)
+            .await?;
+        *self.passphrase_hash.lock().unwrap() = Some(hash);
+        *self.last_activity.lock().unwrap() = Instant::now();
+        Ok(self.get_status())
+    }
+
+    async fn remove_passphrase(&self, current_passphrase: &str) -> Result<AppLockStatus> {
+        self.verify(current_passphrase)?;
+        self.settings_repository
+            .update_setting(PASSPHRASE_HASH_KEY, "")
+            .await?;
+        *self.passphrase_hash.lock().unwrap() = None;
+        self.locked.store(false, Ordering::SeqCst);
+        Ok(self.get_status())
+    }
+
+    async fn set_auto_lock_minutes(&self, minutes: u32) -> Result<AppLockStatus> {
+        self.settings_repository
+            .update_setting(AUTO_LOCK_MINUTES_KEY, &minutes.to_string())
+            .await?;
+        self.auto_lock_minutes.store(minutes, Ordering::SeqCst);
+        Ok(self.get_status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{Settings, SettingsUpdate};
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct InMemorySettingsRepository {
+        values: Mutex<HashMap<String, String>>,
+    }
+
+    #[async_trait]
+    impl SettingsRepositoryTrait for InMemorySettingsRepository {
+        fn get_settings(&self) -> Result<Settings> {
+            Ok(Settings::default())
+        }
+
+        async fn update_settings(&self, _new_settings: &SettingsUpdate) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_setting(&self, key: &str) -> Result<String> {
+            self.values
+                .lock()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .ok_or_else(|| Error::from(diesel::result::Error::NotFound))
+        }
+
+        async fn update_setting(&self, key: &str, value: &str) -> Result<()> {
+            self.values
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn get_distinct_currencies_excluding_base(&self, _base_currency: &str) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lock_and_unlock_with_passphrase() {
+        let repo = Arc::new(InMemorySettingsRepository::default());
+        let service = AppLockService::new(repo.clone()).unwrap();
+        assert!(!service.get_status().enabled);
+        assert!(!service.record_activity());
+
+        service.set_passphrase(None, "1234").await.unwrap();
+        assert!(repo.get_setting(PASSPHRASE_HASH_KEY).unwrap().starts_with("$argon2"));
+
+        service.lock();
+        assert!(service.record_activity());
+        assert!(service.unlock("0000").is_err());
+        assert!(service.unlock("1234").is_ok());
+        assert!(!service.record_activity());
+
+        // A fresh service over the same storage starts locked
+        let restarted = AppLockService::new(repo).unwrap();
+        assert!(restarted.get_status().locked);
+    }
+
+    #[tokio::test]
+    async fn test_changing_passphrase_requires_current() {
+        let repo = Arc::new(InMemorySettingsRepository::default());
+        let service = AppLockService::new(repo).unwrap();
+        service.set_passphrase(None, "secret-one").await.unwrap();
+
+        assert!(service.set_passphrase(Some("wrong"), "secret-two").await.is_err());
+        assert!(service.set_passphrase(Some("secret-one"), "12").await.is_err());
+        service.set_passphrase(Some("secret-one"), "secret-two").await.unwrap();
+
+        service.remove_passphrase("secret-two").await.unwrap();
+        assert!(!service.get_status().enabled);
+    }
+
+    #[tokio::test]
+    async fn test_auto_lock_after_inactivity() {
+        let repo = Arc::new(InMemorySettingsRepository::default());
+        let service = AppLockService::new(repo).unwrap();
+        service.set_passphrase(None, "1234").await.unwrap();
+        service.set_auto_lock_minutes(1).await.unwrap();
+
+        *service.last_activity.lock().unwrap() = Instant::now() - Duration::from_secs(61);
+        assert!(service.check_auto_lock());
+        assert!(service.get_status().locked);
+    }
+}
@@ -0,0 +1,8 @@
+mod app_lock_model;
+mod app_lock_service;
+
+pub use app_lock_model::{
+    AppLockStatus, APP_LOCK_ALLOWED_COMMANDS, AUTO_LOCK_MINUTES_KEY, DEFAULT_AUTO_LOCK_MINUTES,
+    PASSPHRASE_HASH_KEY,
+};
+pub use app_lock_service::{AppLockService, AppLockServiceTrait};
@@ -5,7 +5,7 @@ use diesel::sqlite::SqliteConnection;
 use log::debug;
 use std::sync::Arc;
 
-use crate::db::{get_connection, WriteHandle};
+use crate::db::{get_connection, WriteHandle, WriteOp};
 use crate::errors::{Error, Result};
 use crate::schema::{activities, assets, quotes};
 
@@ -92,6 +92,23 @@ impl AssetRepositoryTrait for AssetRepository {
             .await
     }
 
+    async fn create_many(&self, new_assets: Vec<NewAsset>) -> Result<Vec<Asset>> {
+        let mut ops: Vec<WriteOp<Asset>> = Vec::with_capacity(new_assets.len());
+        for new_asset in new_assets {
+            new_asset.validate()?;
+            let asset_db: AssetDB = new_asset.into();
+            ops.push(Box::new(
+                move |conn: &mut SqliteConnection| -> Result<Asset> {
+                    let result_db = diesel::insert_into(assets::table)
+                        .values(&asset_db)
+                        .get_result::<AssetDB>(conn)?;
+                    Ok(result_db.into())
+                },
+            ));
+        }
+        self.writer.exec_batch(ops).await
+    }
+
     /// Updates an existing asset in the database
     async fn update_profile(&self, asset_id: &str, payload: UpdateAssetProfile) -> Result<Asset> {
         payload.validate()?;
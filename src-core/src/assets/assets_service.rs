@@ -1,4 +1,5 @@
 use log::{debug, error};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::market_data::market_data_traits::{MarketDataServiceTrait, MarketDataRepositoryTrait};
@@ -30,6 +31,29 @@ impl AssetService {
     }
 }
 
+/// Profile of an asset priced by hand rather than by a market data provider
+fn manual_asset(symbol: &str, currency: String) -> NewAsset {
+    NewAsset {
+        id: Some(symbol.to_string()),
+        isin: None,
+        name: Some(symbol.to_string()),
+        asset_type: Some("EQUITY".to_string()),
+        symbol: symbol.to_string(),
+        symbol_mapping: None,
+        asset_class: Some("Equity".to_string()),
+        asset_sub_class: Some("Stock".to_string()),
+        notes: None,
+        countries: None,
+        categories: None,
+        classes: None,
+        attributes: None,
+        currency,
+        data_source: "MANUAL".to_string(),
+        sectors: None,
+        url: None,
+    }
+}
+
 // Implement the service trait
 #[async_trait::async_trait]
 impl AssetServiceTrait for AssetService {
@@ -76,27 +100,9 @@ impl AssetServiceTrait for AssetService {
             Ok(existing_asset) => Ok(existing_asset),
             Err(Error::Database(DatabaseError::QueryFailed(DieselError::NotFound))) => {
                 debug!("Creating manual asset: {}", symbol);
-                // Create a simple manual asset profile
-                let new_asset = NewAsset {
-                    id: Some(symbol.to_string()),
-                    isin: None,
-                    name: Some(symbol.to_string()),
-                    asset_type: Some("EQUITY".to_string()),
-                    symbol: symbol.to_string(),
-                    symbol_mapping: None,
-                    asset_class: Some("Equity".to_string()),
-                    asset_sub_class: Some("Stock".to_string()),
-                    notes: None,
-                    countries: None,
-                    categories: None,
-                    classes: None,
-                    attributes: None,
-                    currency,
-                    data_source: "MANUAL".to_string(),
-                    sectors: None,
-                    url: None,
-                };
-                self.asset_repository.create(new_asset).await
+                self.asset_repository
+                    .create(manual_asset(symbol, currency))
+                    .await
             }
             Err(e) => {
                 error!("Error checking for existing manual asset '{}': {}", symbol, e);
@@ -105,6 +111,41 @@ impl AssetServiceTrait for AssetService {
         }
     }
 
+    async fn create_manual_assets(&self, assets: Vec<(String, String)>) -> Result<Vec<Asset>> {
+        let mut found: HashMap<String, Asset> = HashMap::new();
+        let mut missing: Vec<NewAsset> = Vec::new();
+        for (symbol, currency) in &assets {
+            if found.contains_key(symbol) || missing.iter().any(|asset| &asset.symbol == symbol) {
+                continue;
+            }
+            match self.asset_repository.get_by_id(symbol) {
+                Ok(existing_asset) => {
+                    found.insert(symbol.clone(), existing_asset);
+                }
+                Err(Error::Database(DatabaseError::QueryFailed(DieselError::NotFound))) => {
+                    missing.push(manual_asset(symbol, currency.clone()));
+                }
+                Err(e) => {
+                    error!(
+                        "Error checking for existing manual asset '{}': {}",
+                        symbol, e
+                    );
+                    return Err(e);
+                }
+            }
+        }
+        if !missing.is_empty() {
+            debug!("Creating {} manual assets", missing.len());
+            for asset in self.asset_repository.create_many(missing).await? {
+                found.insert(asset.symbol.clone(), asset);
+            }
+        }
+        Ok(assets
+            .iter()
+            .filter_map(|(symbol, _)| found.get(symbol).cloned())
+            .collect())
+    }
+
     /// Retrieves or creates an asset by its ID
     async fn get_or_create_asset(
         &self,
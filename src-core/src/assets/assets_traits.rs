@@ -15,6 +15,9 @@ pub trait AssetServiceTrait: Send + Sync {
     fn load_cash_assets(&self, base_currency: &str) -> Result<Vec<Asset>>;
     async fn create_cash_asset(&self, currency: &str) -> Result<Asset>;
     async fn create_manual_asset(&self, symbol: &str, currency: String) -> Result<Asset>;
+    /// Manual assets for `(symbol, currency)` pairs, creating the missing ones together in
+    /// one write. Returned in the order given.
+    async fn create_manual_assets(&self, assets: Vec<(String, String)>) -> Result<Vec<Asset>>;
     async fn get_or_create_asset(
         &self,
         asset_id: &str,
@@ -28,6 +31,8 @@ pub trait AssetServiceTrait: Send + Sync {
 #[async_trait::async_trait]
 pub trait AssetRepositoryTrait: Send + Sync {
     async fn create(&self, new_asset: NewAsset) -> Result<Asset>;
+    /// Inserts the assets in one transaction; none are created if any insert fails
+    async fn create_many(&self, new_assets: Vec<NewAsset>) -> Result<Vec<Asset>>;
     async fn update_profile(&self, asset_id: &str, payload: UpdateAssetProfile) -> Result<Asset>;
     async fn update_data_source(&self, asset_id: &str, data_source: String) -> Result<Asset>;
     fn get_by_id(&self, asset_id: &str) -> Result<Asset>;
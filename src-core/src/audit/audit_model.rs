@@ -0,0 +1,137 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, ValidationError};
+
+/// Actor recorded for changes made directly by the person using the app
+pub const AUDIT_ACTOR_USER: &str = "user";
+/// Actor recorded for changes that came from a CSV/broker import
+pub const AUDIT_ACTOR_IMPORT: &str = "import";
+/// Actor recorded for activities a recurring rule posted when they fell due
+pub const AUDIT_ACTOR_RECURRING: &str = "recurring";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Import,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "CREATE",
+            AuditAction::Update => "UPDATE",
+            AuditAction::Delete => "DELETE",
+            AuditAction::Import => "IMPORT",
+        }
+    }
+}
+
+impl FromStr for AuditAction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "CREATE" => Ok(AuditAction::Create),
+            "UPDATE" => Ok(AuditAction::Update),
+            "DELETE" => Ok(AuditAction::Delete),
+            "IMPORT" => Ok(AuditAction::Import),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown audit action: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// A stored audit record. `changes` holds a JSON document describing what changed.
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::audit_log)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    pub id: String,
+    pub entity_type: String,
+    pub entity_id: String,
+    pub action: String,
+    pub actor: String,
+    pub changes: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAuditLogEntry {
+    /// e.g. "goal_allocation", "account", "activity"
+    pub entity_type: String,
+    pub entity_id: String,
+    pub action: AuditAction,
+    /// Who made the change: "user", "import", "system" or "addon:<id>"
+    pub actor: String,
+    pub changes: Option<serde_json::Value>,
+}
+
+impl NewAuditLogEntry {
+    pub fn new(
+        entity_type: impl Into<String>,
+        entity_id: impl Into<String>,
+        action: AuditAction,
+        actor: impl Into<String>,
+    ) -> Self {
+        Self {
+            entity_type: entity_type.into(),
+            entity_id: entity_id.into(),
+            action,
+            actor: actor.into(),
+            changes: None,
+        }
+    }
+
+    pub fn with_changes(mut self, changes: serde_json::Value) -> Self {
+        self.changes = Some(changes);
+        self
+    }
+
+    /// Stores the before/after snapshots of the entity as the change document
+    pub fn with_snapshots<B: Serialize, A: Serialize>(
+        self,
+        before: Option<&B>,
+        after: Option<&A>,
+    ) -> Self {
+        self.with_changes(serde_json::json!({
+            "before": before.and_then(|b| serde_json::to_value(b).ok()),
+            "after": after.and_then(|a| serde_json::to_value(a).ok()),
+        }))
+    }
+}
+
+/// Filter for the audit log viewer. Dates are inclusive; pages are 0-based like activity search.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AuditLogQuery {
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub action: Option<AuditAction>,
+    pub actor: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub page: i64,
+    pub page_size: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogResponseMeta {
+    pub total_row_count: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogResponse {
+    pub data: Vec<AuditLogEntry>,
+    pub meta: AuditLogResponseMeta,
+}
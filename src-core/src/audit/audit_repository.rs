@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::audit_model::{
+    AuditLogEntry, AuditLogQuery, AuditLogResponse, AuditLogResponseMeta, NewAuditLogEntry,
+};
+use super::audit_traits::AuditRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Error, Result};
+use crate::schema::audit_log;
+
+pub struct AuditRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl AuditRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        AuditRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl AuditRepositoryTrait for AuditRepository {
+    async fn insert_entry(&self, entry: NewAuditLogEntry) -> Result<AuditLogEntry> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<AuditLogEntry> {
+                let record = (
+                    audit_log::id.eq(Uuid::new_v4().to_string()),
+                    audit_log::entity_type.eq(entry.entity_type),
+                    audit_log::entity_id.eq(entry.entity_id),
+                    audit_log::action.eq(entry.action.as_str()),
+                    audit_log::actor.eq(entry.actor),
+                    audit_log::changes.eq(entry.changes.map(|c| c.to_string())),
+                    audit_log::created_at.eq(chrono::Utc::now().naive_utc()),
+                );
+
+                diesel::insert_into(audit_log::table)
+                    .values(record)
+                    .get_result(conn)
+                    .map_err(Error::from)
+            })
+            .await
+    }
+
+    fn query_entries(&self, query: &AuditLogQuery) -> Result<AuditLogResponse> {
+        let mut conn = get_connection(&self.pool)?;
+
+        let create_base_query = || {
+            let mut q = audit_log::table.into_boxed();
+            if let Some(ref entity_type) = query.entity_type {
+                q = q.filter(audit_log::entity_type.eq(entity_type.clone()));
+            }
+            if let Some(ref entity_id) = query.entity_id {
+                q = q.filter(audit_log::entity_id.eq(entity_id.clone()));
+            }
+            if let Some(action) = query.action {
+                q = q.filter(audit_log::action.eq(action.as_str()));
+            }
+            if let Some(ref actor) = query.actor {
+                q = q.filter(audit_log::actor.eq(actor.clone()));
+            }
+            if let Some(start) = query.start_date {
+                q = q.filter(audit_log::created_at.ge(start.and_hms_opt(0, 0, 0).unwrap()));
+            }
+            if let Some(end) = query.end_date {
+                // Inclusive end date: everything before the following midnight
+                let next_day = end.succ_opt().unwrap_or(end).and_hms_opt(0, 0, 0).unwrap();
+                q = q.filter(audit_log::created_at.lt(next_day));
+            }
+            q
+        };
+
+        let total_row_count = create_base_query().count().get_result::<i64>(&mut conn)?;
+
+        let data = create_base_query()
+            .order((audit_log::created_at.desc(), audit_log::id.asc()))
+            .offset(query.page * query.page_size)
+            .limit(query.page_size)
+            .load::<AuditLogEntry>(&mut conn)?;
+
+        Ok(AuditLogResponse {
+            data,
+            meta: AuditLogResponseMeta { total_row_count },
+        })
+    }
+}
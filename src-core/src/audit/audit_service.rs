@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::audit_model::{AuditLogEntry, AuditLogQuery, AuditLogResponse, NewAuditLogEntry};
+use super::audit_traits::{AuditRepositoryTrait, AuditServiceTrait};
+use crate::errors::{Error, Result, ValidationError};
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+const DEFAULT_PAGE_SIZE: i64 = 50;
+const MAX_PAGE_SIZE: i64 = 500;
+
+pub struct AuditService {
+    repository: Arc<dyn AuditRepositoryTrait>,
+}
+
+impl AuditService {
+    pub fn new(repository: Arc<dyn AuditRepositoryTrait>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl AuditServiceTrait for AuditService {
+    async fn record(&self, entry: NewAuditLogEntry) -> Result<AuditLogEntry> {
+        self.repository.insert_entry(entry).await
+    }
+
+    fn query_audit_log(&self, mut query: AuditLogQuery) -> Result<AuditLogResponse> {
+        if let (Some(start), Some(end)) = (query.start_date, query.end_date) {
+            if start > end {
+                return Err(LocalizedMessage::new(MessageCode::InvalidDateRange).into());
+            }
+        }
+        if query.page < 0 {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Page must not be negative".to_string(),
+            )));
+        }
+        query.page_size = match query.page_size {
+            size if size <= 0 => DEFAULT_PAGE_SIZE,
+            size => size.min(MAX_PAGE_SIZE),
+        };
+        self.repository.query_entries(&query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::audit_model::AuditLogResponseMeta;
+    use chrono::NaiveDate;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingRepository {
+        last_query: Mutex<Option<AuditLogQuery>>,
+    }
+
+    #[async_trait]
+    impl AuditRepositoryTrait for RecordingRepository {
+        async fn insert_entry(&self, _entry: NewAuditLogEntry) -> Result<AuditLogEntry> {
+            unimplemented!()
+        }
+
+        fn query_entries(&self, query: &AuditLogQuery) -> Result<AuditLogResponse> {
+            *self.last_query.lock().unwrap() = Some(query.clone());
+            Ok(AuditLogResponse {
+                data: Vec::new(),
+                meta: AuditLogResponseMeta { total_row_count: 0 },
+            })
+        }
+    }
+
+    #[test]
+    fn test_query_validates_range_and_clamps_page_size() {
+        let repo = Arc::new(RecordingRepository::default());
+        let service = AuditService::new(repo.clone());
+
+        let inverted = AuditLogQuery {
+            start_date: NaiveDate::from_ymd_opt(2025, 10, 31),
+            end_date: NaiveDate::from_ymd_opt(2025, 10, 1),
+            ..Default::default()
+        };
+        assert!(service.query_audit_log(inverted).is_err());
+
+        service
+            .query_audit_log(AuditLogQuery {
+                page_size: 10_000,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(repo.last_query.lock().unwrap().as_ref().unwrap().page_size, MAX_PAGE_SIZE);
+
+        service.query_audit_log(AuditLogQuery::default()).unwrap();
+        assert_eq!(
+            repo.last_query.lock().unwrap().as_ref().unwrap().page_size,
+            DEFAULT_PAGE_SIZE
+        );
+    }
+}
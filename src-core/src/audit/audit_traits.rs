@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use super::audit_model::{AuditLogEntry, AuditLogQuery, AuditLogResponse, NewAuditLogEntry};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait AuditRepositoryTrait: Send + Sync {
+    async fn insert_entry(&self, entry: NewAuditLogEntry) -> Result<AuditLogEntry>;
+    fn query_entries(&self, query: &AuditLogQuery) -> Result<AuditLogResponse>;
+}
+
+#[async_trait]
+pub trait AuditServiceTrait: Send + Sync {
+    async fn record(&self, entry: NewAuditLogEntry) -> Result<AuditLogEntry>;
+    fn query_audit_log(&self, query: AuditLogQuery) -> Result<AuditLogResponse>;
+}
@@ -0,0 +1,12 @@
+mod audit_model;
+mod audit_repository;
+mod audit_service;
+mod audit_traits;
+
+pub use audit_model::{
+    AuditAction, AuditLogEntry, AuditLogQuery, AuditLogResponse, AuditLogResponseMeta,
+    NewAuditLogEntry, AUDIT_ACTOR_IMPORT, AUDIT_ACTOR_RECURRING, AUDIT_ACTOR_USER,
+};
+pub use audit_repository::AuditRepository;
+pub use audit_service::AuditService;
+pub use audit_traits::{AuditRepositoryTrait, AuditServiceTrait};
@@ -0,0 +1,302 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::notifications::{Notification, NotificationChannel};
+
+/// Most actions one rule may run
+pub const MAX_RULE_ACTIONS: usize = 10;
+/// Longest tag a TAG_ACTIVITIES action may set
+pub const MAX_TAG_LENGTH: usize = 50;
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message.into()))
+}
+
+/// What makes a rule fire
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AutomationTrigger {
+    /// Activities were imported, from a file, a payload or a bank connection
+    #[serde(rename_all = "camelCase")]
+    ImportCompleted {
+        /// Only imports into this account; any account when unset
+        #[serde(default)]
+        account_id: Option<String>,
+    },
+    /// A goal's progress reached `percent`; fires again only after it drops back below
+    #[serde(rename_all = "camelCase")]
+    GoalProgressReached {
+        #[serde(default)]
+        goal_id: Option<String>,
+        percent: Decimal,
+    },
+    /// The latest close moved by at least `percent` from the previous close, up or down;
+    /// fires at most once per symbol and quote day
+    #[serde(rename_all = "camelCase")]
+    QuoteMoved {
+        #[serde(default)]
+        symbol: Option<String>,
+        percent: Decimal,
+    },
+}
+
+impl AutomationTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutomationTrigger::ImportCompleted { .. } => "IMPORT_COMPLETED",
+            AutomationTrigger::GoalProgressReached { .. } => "GOAL_PROGRESS_REACHED",
+            AutomationTrigger::QuoteMoved { .. } => "QUOTE_MOVED",
+        }
+    }
+
+    /// Channel of the notifications its rules send
+    pub fn notification_channel(&self) -> NotificationChannel {
+        match self {
+            AutomationTrigger::ImportCompleted { .. } => NotificationChannel::System,
+            AutomationTrigger::GoalProgressReached { .. } => NotificationChannel::GoalProgress,
+            AutomationTrigger::QuoteMoved { .. } => NotificationChannel::PriceAlert,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self {
+            AutomationTrigger::ImportCompleted { .. } => Ok(()),
+            AutomationTrigger::GoalProgressReached { percent, .. }
+            | AutomationTrigger::QuoteMoved { percent, .. } => {
+                if *percent <= Decimal::ZERO {
+                    return Err(invalid(format!(
+                        "{} percent must be greater than zero",
+                        self.as_str()
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// What a rule does when it fires. `title` and `body` of NOTIFY may use the placeholders of
+/// the trigger, e.g. `{symbol}` and `{change}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AutomationAction {
+    /// Tags the activities of the import; only for IMPORT_COMPLETED rules
+    #[serde(rename_all = "camelCase")]
+    TagActivities { tag: String },
+    #[serde(rename_all = "camelCase")]
+    Notify {
+        title: String,
+        #[serde(default)]
+        body: String,
+    },
+    /// Pushes a Google Sheets export
+    #[serde(rename_all = "camelCase")]
+    RunReport { sheet_export_id: String },
+    /// POSTs the rule and the event as JSON
+    #[serde(rename_all = "camelCase")]
+    CallWebhook { url: String },
+}
+
+impl AutomationAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutomationAction::TagActivities { .. } => "TAG_ACTIVITIES",
+            AutomationAction::Notify { .. } => "NOTIFY",
+            AutomationAction::RunReport { .. } => "RUN_REPORT",
+            AutomationAction::CallWebhook { .. } => "CALL_WEBHOOK",
+        }
+    }
+
+    fn validate(&self, trigger: &AutomationTrigger) -> Result<()> {
+        match self {
+            AutomationAction::TagActivities { tag } => {
+                if !matches!(trigger, AutomationTrigger::ImportCompleted { .. }) {
+                    return Err(invalid(
+                        "TAG_ACTIVITIES only works with the IMPORT_COMPLETED trigger",
+                    ));
+                }
+                if tag.trim().is_empty() || tag.trim().chars().count() > MAX_TAG_LENGTH {
+                    return Err(invalid(format!(
+                        "Tag must be between 1 and {} characters",
+                        MAX_TAG_LENGTH
+                    )));
+                }
+            }
+            AutomationAction::Notify { title, .. } => {
+                if title.trim().is_empty() {
+                    return Err(Error::Validation(ValidationError::MissingField(
+                        "title".to_string(),
+                    )));
+                }
+            }
+            AutomationAction::RunReport { sheet_export_id } => {
+                if sheet_export_id.trim().is_empty() {
+                    return Err(Error::Validation(ValidationError::MissingField(
+                        "sheetExportId".to_string(),
+                    )));
+                }
+            }
+            AutomationAction::CallWebhook { url } => {
+                let url = url.trim();
+                if !(url.starts_with("https://") || url.starts_with("http://")) {
+                    return Err(invalid("Webhook URL must start with http:// or https://"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Database row for `automation_rules`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::automation_rules)]
+pub struct AutomationRuleDB {
+    pub id: String,
+    pub name: String,
+    pub trigger_config: String,
+    pub actions: String,
+    pub is_enabled: bool,
+    pub last_triggered_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRule {
+    pub id: String,
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    /// Run in order; a failing action doesn't stop the ones after it
+    pub actions: Vec<AutomationAction>,
+    pub is_enabled: bool,
+    pub last_triggered_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<AutomationRuleDB> for AutomationRule {
+    type Error = Error;
+
+    fn try_from(db: AutomationRuleDB) -> Result<Self> {
+        let trigger = serde_json::from_str(&db.trigger_config)
+            .map_err(|e| invalid(format!("Invalid trigger of rule '{}': {}", db.name, e)))?;
+        let actions = serde_json::from_str(&db.actions)
+            .map_err(|e| invalid(format!("Invalid actions of rule '{}': {}", db.name, e)))?;
+        Ok(AutomationRule {
+            id: db.id,
+            name: db.name,
+            trigger,
+            actions,
+            is_enabled: db.is_enabled,
+            last_triggered_at: db.last_triggered_at,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAutomationRule {
+    pub id: Option<String>,
+    pub name: String,
+    pub trigger: AutomationTrigger,
+    pub actions: Vec<AutomationAction>,
+    pub is_enabled: bool,
+}
+
+impl NewAutomationRule {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.actions.is_empty() || self.actions.len() > MAX_RULE_ACTIONS {
+            return Err(invalid(format!(
+                "A rule needs between 1 and {} actions",
+                MAX_RULE_ACTIONS
+            )));
+        }
+        self.trigger.validate()?;
+        for action in &self.actions {
+            action.validate(&self.trigger)?;
+        }
+        Ok(())
+    }
+
+    /// Trigger and actions as stored in `trigger_config` and `actions`
+    pub(crate) fn to_json(&self) -> Result<(String, String)> {
+        let trigger =
+            serde_json::to_string(&self.trigger).map_err(|e| Error::Unexpected(e.to_string()))?;
+        let actions =
+            serde_json::to_string(&self.actions).map_err(|e| Error::Unexpected(e.to_string()))?;
+        Ok((trigger, actions))
+    }
+}
+
+/// Database row for `automation_rule_firings`
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::automation_rule_firings)]
+pub struct AutomationRuleFiringDB {
+    pub rule_id: String,
+    pub subject: String,
+    pub marker: String,
+    pub fired_at: NaiveDateTime,
+}
+
+/// A tag set on an activity by an automation rule
+#[derive(Queryable, Insertable, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[diesel(table_name = crate::schema::activity_tags)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityTag {
+    pub activity_id: String,
+    pub tag: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// An import that just finished, as IMPORT_COMPLETED rules see it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportCompletion {
+    pub account_id: String,
+    pub imported: usize,
+    /// Activities of the account created since then belong to the import
+    pub started_at: NaiveDateTime,
+}
+
+/// A rule that fired
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationFiring {
+    pub rule_id: String,
+    pub rule_name: String,
+    /// Account id, goal id or symbol the rule fired for
+    pub subject: String,
+}
+
+/// An action that failed; the rule carries on with its other actions
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationFailure {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub action: String,
+    pub message: String,
+}
+
+/// What the rules of one trigger did
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AutomationRunReport {
+    pub fired: Vec<AutomationFiring>,
+    /// Sent by NOTIFY actions; delivering them is up to the app
+    pub notifications: Vec<Notification>,
+    /// Activities tagged by TAG_ACTIVITIES actions
+    pub tagged: usize,
+    pub failures: Vec<AutomationFailure>,
+}
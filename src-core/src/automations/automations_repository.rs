@@ -0,0 +1,215 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::automations_model::{
+    ActivityTag, AutomationRule, AutomationRuleDB, AutomationRuleFiringDB, NewAutomationRule,
+};
+use super::automations_traits::AutomationRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::{activities, activity_tags, automation_rule_firings, automation_rules};
+
+pub struct AutomationRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl AutomationRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        AutomationRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl AutomationRepositoryTrait for AutomationRepository {
+    fn get_rules(&self) -> Result<Vec<AutomationRule>> {
+        let mut conn = get_connection(&self.pool)?;
+        automation_rules::table
+            .order(automation_rules::created_at.asc())
+            .load::<AutomationRuleDB>(&mut conn)?
+            .into_iter()
+            .map(AutomationRule::try_from)
+            .collect()
+    }
+
+    fn get_rule(&self, id: &str) -> Result<AutomationRule> {
+        let mut conn = get_connection(&self.pool)?;
+        automation_rules::table
+            .find(id)
+            .first::<AutomationRuleDB>(&mut conn)?
+            .try_into()
+    }
+
+    fn get_enabled_rules(&self) -> Result<Vec<AutomationRule>> {
+        let mut conn = get_connection(&self.pool)?;
+        automation_rules::table
+            .filter(automation_rules::is_enabled.eq(true))
+            .order(automation_rules::created_at.asc())
+            .load::<AutomationRuleDB>(&mut conn)?
+            .into_iter()
+            .map(AutomationRule::try_from)
+            .collect()
+    }
+
+    async fn insert_rule(&self, rule: NewAutomationRule) -> Result<AutomationRule> {
+        let (trigger_config, actions) = rule.to_json()?;
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<AutomationRule> {
+                    let now = Utc::now().naive_utc();
+                    let record = AutomationRuleDB {
+                        id: rule.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        name: rule.name.trim().to_string(),
+                        trigger_config,
+                        actions,
+                        is_enabled: rule.is_enabled,
+                        last_triggered_at: None,
+                        created_at: now,
+                        updated_at: now,
+                    };
+
+                    diesel::insert_into(automation_rules::table)
+                        .values(&record)
+                        .get_result::<AutomationRuleDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn update_rule(&self, id: &str, rule: NewAutomationRule) -> Result<AutomationRule> {
+        let id_owned = id.to_string();
+        let (trigger_config, actions) = rule.to_json()?;
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<AutomationRule> {
+                    // A changed trigger starts from scratch rather than inheriting old firings
+                    diesel::delete(
+                        automation_rule_firings::table
+                            .filter(automation_rule_firings::rule_id.eq(&id_owned)),
+                    )
+                    .execute(conn)?;
+                    diesel::update(automation_rules::table.find(&id_owned))
+                        .set((
+                            automation_rules::name.eq(rule.name.trim()),
+                            automation_rules::trigger_config.eq(trigger_config),
+                            automation_rules::actions.eq(actions),
+                            automation_rules::is_enabled.eq(rule.is_enabled),
+                            automation_rules::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .get_result::<AutomationRuleDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_rule(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(automation_rules::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    async fn mark_triggered(&self, id: &str, triggered_at: NaiveDateTime) -> Result<()> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::update(automation_rules::table.find(id_owned))
+                    .set(automation_rules::last_triggered_at.eq(Some(triggered_at)))
+                    .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+
+    fn get_firings(&self, rule_id: &str) -> Result<Vec<AutomationRuleFiringDB>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(automation_rule_firings::table
+            .filter(automation_rule_firings::rule_id.eq(rule_id))
+            .load::<AutomationRuleFiringDB>(&mut conn)?)
+    }
+
+    async fn record_firing(&self, firing: AutomationRuleFiringDB) -> Result<()> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::replace_into(automation_rule_firings::table)
+                    .values(&firing)
+                    .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn clear_firings(&self, rule_id: &str, subjects: Vec<String>) -> Result<()> {
+        let rule_id_owned = rule_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::delete(
+                    automation_rule_firings::table
+                        .filter(automation_rule_firings::rule_id.eq(rule_id_owned))
+                        .filter(automation_rule_firings::subject.eq_any(subjects)),
+                )
+                .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn tag_activities_since(
+        &self,
+        account_id: &str,
+        since: NaiveDateTime,
+        tag: &str,
+    ) -> Result<usize> {
+        let account_id_owned = account_id.to_string();
+        let tag_owned = tag.trim().to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                // created_at is RFC 3339 text, so it is compared after parsing rather than in SQL
+                let ids: Vec<String> = activities::table
+                    .filter(activities::account_id.eq(&account_id_owned))
+                    .select((activities::id, activities::created_at))
+                    .load::<(String, String)>(conn)?
+                    .into_iter()
+                    .filter(|(_, created_at)| {
+                        DateTime::parse_from_rfc3339(created_at)
+                            .is_ok_and(|created| created.naive_utc() >= since)
+                    })
+                    .map(|(id, _)| id)
+                    .collect();
+
+                let now = Utc::now().naive_utc();
+                let mut tagged = 0;
+                for chunk in ids.chunks(500) {
+                    let rows: Vec<ActivityTag> = chunk
+                        .iter()
+                        .map(|activity_id| ActivityTag {
+                            activity_id: activity_id.clone(),
+                            tag: tag_owned.clone(),
+                            created_at: now,
+                        })
+                        .collect();
+                    tagged += diesel::insert_or_ignore_into(activity_tags::table)
+                        .values(&rows)
+                        .execute(conn)?;
+                }
+                Ok(tagged)
+            })
+            .await
+    }
+
+    fn get_activity_tags(&self) -> Result<Vec<ActivityTag>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(activity_tags::table
+            .order((activity_tags::tag.asc(), activity_tags::created_at.desc()))
+            .load::<ActivityTag>(&mut conn)?)
+    }
+}
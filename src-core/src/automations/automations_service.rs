@@ -0,0 +1,816 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::automations_model::{
+    ActivityTag, AutomationAction, AutomationFailure, AutomationFiring, AutomationRule,
+    AutomationRuleFiringDB, AutomationRunReport, AutomationTrigger, ImportCompletion,
+    NewAutomationRule,
+};
+use super::automations_traits::{AutomationRepositoryTrait, AutomationServiceTrait, WebhookClient};
+use super::automations_webhook::HttpWebhookClient;
+use crate::errors::{Error, Result};
+use crate::market_data::LatestQuotePair;
+use crate::notifications::Notification;
+use crate::scripting::ScriptGoalProgress;
+use crate::sheets::SheetExportServiceTrait;
+
+/// What one rule fired for: the subject, the `{placeholders}` of its notifications and the
+/// event sent to webhooks
+struct FiringContext {
+    subject: String,
+    values: Vec<(&'static str, String)>,
+    event: Value,
+}
+
+impl FiringContext {
+    fn fill(&self, template: &str) -> String {
+        self.values
+            .iter()
+            .fold(template.to_string(), |text, (key, value)| {
+                text.replace(&format!("{{{}}}", key), value)
+            })
+    }
+}
+
+pub struct AutomationService {
+    repository: Arc<dyn AutomationRepositoryTrait>,
+    sheet_export_service: Arc<dyn SheetExportServiceTrait>,
+    webhook_client: Arc<dyn WebhookClient>,
+}
+
+impl AutomationService {
+    pub fn new(
+        repository: Arc<dyn AutomationRepositoryTrait>,
+        sheet_export_service: Arc<dyn SheetExportServiceTrait>,
+    ) -> Self {
+        AutomationService {
+            repository,
+            sheet_export_service,
+            webhook_client: Arc::new(HttpWebhookClient::default()),
+        }
+    }
+
+    /// Uses the given client instead of plain HTTP for CALL_WEBHOOK actions
+    pub fn with_webhook_client(mut self, webhook_client: Arc<dyn WebhookClient>) -> Self {
+        self.webhook_client = webhook_client;
+        self
+    }
+
+    async fn run_action(
+        &self,
+        rule: &AutomationRule,
+        action: &AutomationAction,
+        context: &FiringContext,
+        import: Option<&ImportCompletion>,
+        report: &mut AutomationRunReport,
+    ) -> Result<()> {
+        match action {
+            AutomationAction::TagActivities { tag } => {
+                if let Some(import) = import {
+                    report.tagged += self
+                        .repository
+                        .tag_activities_since(&import.account_id, import.started_at, tag)
+                        .await?;
+                }
+            }
+            AutomationAction::Notify { title, body } => {
+                report.notifications.push(Notification {
+                    id: Uuid::new_v4().to_string(),
+                    channel: rule.trigger.notification_channel(),
+                    title: context.fill(title),
+                    body: context.fill(body),
+                    created_at: Utc::now(),
+                });
+            }
+            AutomationAction::RunReport { sheet_export_id } => {
+                let result = self
+                    .sheet_export_service
+                    .push_export(sheet_export_id)
+                    .await?;
+                if let Some(error) = result.error {
+                    return Err(Error::SheetExport(error));
+                }
+            }
+            AutomationAction::CallWebhook { url } => {
+                let body = json!({
+                    "rule": { "id": rule.id, "name": rule.name },
+                    "trigger": rule.trigger.as_str(),
+                    "subject": context.subject,
+                    "event": context.event,
+                });
+                self.webhook_client.post_json(url.trim(), &body).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs every action of the rule, noting the ones that fail
+    async fn fire(
+        &self,
+        rule: &AutomationRule,
+        context: FiringContext,
+        import: Option<&ImportCompletion>,
+        report: &mut AutomationRunReport,
+    ) -> Result<()> {
+        for action in &rule.actions {
+            if let Err(e) = self
+                .run_action(rule, action, &context, import, report)
+                .await
+            {
+                report.failures.push(AutomationFailure {
+                    rule_id: rule.id.clone(),
+                    rule_name: rule.name.clone(),
+                    action: action.as_str().to_string(),
+                    message: e.to_string(),
+                });
+            }
+        }
+        self.repository
+            .mark_triggered(&rule.id, Utc::now().naive_utc())
+            .await?;
+        report.fired.push(AutomationFiring {
+            rule_id: rule.id.clone(),
+            rule_name: rule.name.clone(),
+            subject: context.subject,
+        });
+        Ok(())
+    }
+
+    /// Firing markers of the rule keyed by subject
+    fn firings(&self, rule_id: &str) -> Result<HashMap<String, String>> {
+        Ok(self
+            .repository
+            .get_firings(rule_id)?
+            .into_iter()
+            .map(|firing| (firing.subject, firing.marker))
+            .collect())
+    }
+
+    async fn record_firing(&self, rule_id: &str, subject: &str, marker: String) -> Result<()> {
+        self.repository
+            .record_firing(AutomationRuleFiringDB {
+                rule_id: rule_id.to_string(),
+                subject: subject.to_string(),
+                marker,
+                fired_at: Utc::now().naive_utc(),
+            })
+            .await
+    }
+}
+
+/// Change from the previous close in percent, when there is a previous close to compare with
+fn quote_change(pair: &LatestQuotePair) -> Option<Decimal> {
+    let previous = pair.previous.as_ref()?.close;
+    if previous.is_zero() {
+        return None;
+    }
+    Some((pair.latest.close - previous) / previous * Decimal::ONE_HUNDRED)
+}
+
+#[async_trait]
+impl AutomationServiceTrait for AutomationService {
+    fn get_rules(&self) -> Result<Vec<AutomationRule>> {
+        self.repository.get_rules()
+    }
+
+    fn get_rule(&self, id: &str) -> Result<AutomationRule> {
+        self.repository.get_rule(id)
+    }
+
+    async fn create_rule(&self, rule: NewAutomationRule) -> Result<AutomationRule> {
+        rule.validate()?;
+        self.repository.insert_rule(rule).await
+    }
+
+    async fn update_rule(&self, id: &str, rule: NewAutomationRule) -> Result<AutomationRule> {
+        rule.validate()?;
+        self.repository.get_rule(id)?;
+        self.repository.update_rule(id, rule).await
+    }
+
+    async fn delete_rule(&self, id: &str) -> Result<usize> {
+        self.repository.delete_rule(id).await
+    }
+
+    fn get_activity_tags(&self) -> Result<Vec<ActivityTag>> {
+        self.repository.get_activity_tags()
+    }
+
+    async fn on_import_completed(&self, import: &ImportCompletion) -> Result<AutomationRunReport> {
+        let mut report = AutomationRunReport::default();
+        if import.imported == 0 {
+            return Ok(report);
+        }
+        for rule in self.repository.get_enabled_rules()? {
+            let AutomationTrigger::ImportCompleted { account_id } = &rule.trigger else {
+                continue;
+            };
+            if account_id
+                .as_deref()
+                .is_some_and(|account_id| account_id != import.account_id)
+            {
+                continue;
+            }
+            let context = FiringContext {
+                subject: import.account_id.clone(),
+                values: vec![
+                    ("account", import.account_id.clone()),
+                    ("count", import.imported.to_string()),
+                ],
+                event: json!(import),
+            };
+            self.fire(&rule, context, Some(import), &mut report).await?;
+        }
+        Ok(report)
+    }
+
+    async fn on_goal_progress(&self, goals: &[ScriptGoalProgress]) -> Result<AutomationRunReport> {
+        let mut report = AutomationRunReport::default();
+        for rule in self.repository.get_enabled_rules()? {
+            let AutomationTrigger::GoalProgressReached { goal_id, percent } = &rule.trigger else {
+                continue;
+            };
+            let fired = self.firings(&rule.id)?;
+            let mut rearmed = Vec::new();
+            for goal in goals
+                .iter()
+                .filter(|goal| goal_id.as_deref().is_none_or(|id| id == goal.goal_id))
+            {
+                let progress = goal
+                    .progress
+                    .map(|progress| progress * Decimal::ONE_HUNDRED);
+                let reached = progress.is_some_and(|progress| progress >= *percent);
+                match (reached, fired.contains_key(&goal.goal_id)) {
+                    (true, false) => {
+                        let context = FiringContext {
+                            subject: goal.goal_id.clone(),
+                            values: vec![
+                                ("goal", goal.goal_title.clone()),
+                                (
+                                    "progress",
+                                    progress.unwrap_or_default().round_dp(1).to_string(),
+                                ),
+                                ("current", goal.current_amount.round_dp(2).to_string()),
+                                ("target", goal.target_amount.round_dp(2).to_string()),
+                            ],
+                            event: json!(goal),
+                        };
+                        self.fire(&rule, context, None, &mut report).await?;
+                        self.record_firing(&rule.id, &goal.goal_id, String::new())
+                            .await?;
+                    }
+                    // Dropping back below the threshold re-arms the rule for that goal
+                    (false, true) => rearmed.push(goal.goal_id.clone()),
+                    _ => {}
+                }
+            }
+            if !rearmed.is_empty() {
+                self.repository.clear_firings(&rule.id, rearmed).await?;
+            }
+        }
+        Ok(report)
+    }
+
+    async fn on_quote_update(&self, quotes: &[LatestQuotePair]) -> Result<AutomationRunReport> {
+        let mut report = AutomationRunReport::default();
+        for rule in self.repository.get_enabled_rules()? {
+            let AutomationTrigger::QuoteMoved { symbol, percent } = &rule.trigger else {
+                continue;
+            };
+            let fired = self.firings(&rule.id)?;
+            for pair in quotes.iter().filter(|pair| {
+                symbol
+                    .as_deref()
+                    .is_none_or(|symbol| symbol.eq_ignore_ascii_case(&pair.latest.symbol))
+            }) {
+                let Some(change) = quote_change(pair) else {
+                    continue;
+                };
+                let day = pair.latest.timestamp.date_naive().to_string();
+                if change.abs() < *percent || fired.get(&pair.latest.symbol) == Some(&day) {
+                    continue;
+                }
+                let quote = &pair.latest;
+                let previous_close = pair
+                    .previous
+                    .as_ref()
+                    .map(|previous| previous.close)
+                    .unwrap_or_default();
+                let context = FiringContext {
+                    subject: quote.symbol.clone(),
+                    values: vec![
+                        ("symbol", quote.symbol.clone()),
+                        ("change", change.round_dp(2).to_string()),
+                        ("close", quote.close.to_string()),
+                        ("previousClose", previous_close.to_string()),
+                        ("date", day.clone()),
+                    ],
+                    event: json!({
+                        "symbol": quote.symbol,
+                        "date": day,
+                        "close": quote.close,
+                        "previousClose": previous_close,
+                        "changePercent": change.round_dp(4),
+                        "currency": quote.currency,
+                    }),
+                };
+                self.fire(&rule, context, None, &mut report).await?;
+                self.record_firing(&rule.id, &quote.symbol, day).await?;
+            }
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market_data::{DataSource, Quote};
+    use crate::sheets::{GoogleCredentials, NewSheetExport, SheetExport, SheetExportResult};
+    use chrono::{NaiveDateTime, TimeZone};
+    use rust_decimal_macros::dec;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryAutomationRepository {
+        rules: Mutex<Vec<AutomationRule>>,
+        firings: Mutex<Vec<AutomationRuleFiringDB>>,
+        tagged: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl AutomationRepositoryTrait for InMemoryAutomationRepository {
+        fn get_rules(&self) -> Result<Vec<AutomationRule>> {
+            Ok(self.rules.lock().unwrap().clone())
+        }
+
+        fn get_rule(&self, id: &str) -> Result<AutomationRule> {
+            self.rules
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|rule| rule.id == id)
+                .cloned()
+                .ok_or_else(|| Error::Unexpected(format!("No rule {}", id)))
+        }
+
+        fn get_enabled_rules(&self) -> Result<Vec<AutomationRule>> {
+            let rules = self.rules.lock().unwrap();
+            Ok(rules
+                .iter()
+                .filter(|rule| rule.is_enabled)
+                .cloned()
+                .collect())
+        }
+
+        async fn insert_rule(&self, rule: NewAutomationRule) -> Result<AutomationRule> {
+            let now = Utc::now().naive_utc();
+            let created = AutomationRule {
+                id: rule.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                name: rule.name,
+                trigger: rule.trigger,
+                actions: rule.actions,
+                is_enabled: rule.is_enabled,
+                last_triggered_at: None,
+                created_at: now,
+                updated_at: now,
+            };
+            self.rules.lock().unwrap().push(created.clone());
+            Ok(created)
+        }
+
+        async fn update_rule(&self, id: &str, rule: NewAutomationRule) -> Result<AutomationRule> {
+            let mut rules = self.rules.lock().unwrap();
+            let existing = rules.iter_mut().find(|r| r.id == id).unwrap();
+            existing.name = rule.name;
+            existing.trigger = rule.trigger;
+            existing.actions = rule.actions;
+            existing.is_enabled = rule.is_enabled;
+            Ok(existing.clone())
+        }
+
+        async fn delete_rule(&self, id: &str) -> Result<usize> {
+            let mut rules = self.rules.lock().unwrap();
+            let before = rules.len();
+            rules.retain(|rule| rule.id != id);
+            Ok(before - rules.len())
+        }
+
+        async fn mark_triggered(&self, id: &str, triggered_at: NaiveDateTime) -> Result<()> {
+            let mut rules = self.rules.lock().unwrap();
+            if let Some(rule) = rules.iter_mut().find(|rule| rule.id == id) {
+                rule.last_triggered_at = Some(triggered_at);
+            }
+            Ok(())
+        }
+
+        fn get_firings(&self, rule_id: &str) -> Result<Vec<AutomationRuleFiringDB>> {
+            let firings = self.firings.lock().unwrap();
+            Ok(firings
+                .iter()
+                .filter(|firing| firing.rule_id == rule_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn record_firing(&self, firing: AutomationRuleFiringDB) -> Result<()> {
+            let mut firings = self.firings.lock().unwrap();
+            firings.retain(|f| !(f.rule_id == firing.rule_id && f.subject == firing.subject));
+            firings.push(firing);
+            Ok(())
+        }
+
+        async fn clear_firings(&self, rule_id: &str, subjects: Vec<String>) -> Result<()> {
+            self.firings
+                .lock()
+                .unwrap()
+                .retain(|f| !(f.rule_id == rule_id && subjects.contains(&f.subject)));
+            Ok(())
+        }
+
+        async fn tag_activities_since(
+            &self,
+            account_id: &str,
+            _since: NaiveDateTime,
+            tag: &str,
+        ) -> Result<usize> {
+            self.tagged
+                .lock()
+                .unwrap()
+                .push((account_id.to_string(), tag.to_string()));
+            Ok(3)
+        }
+
+        fn get_activity_tags(&self) -> Result<Vec<ActivityTag>> {
+            Ok(Vec::new())
+        }
+    }
+
+    /// Only `push_export` is reached by automation rules
+    #[derive(Default)]
+    struct RecordingSheetExports {
+        pushed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SheetExportServiceTrait for RecordingSheetExports {
+        fn get_exports(&self) -> Result<Vec<SheetExport>> {
+            unimplemented!()
+        }
+        fn get_export(&self, _id: &str) -> Result<SheetExport> {
+            unimplemented!()
+        }
+        async fn create_export(&self, _export: NewSheetExport) -> Result<SheetExport> {
+            unimplemented!()
+        }
+        async fn update_export(&self, _id: &str, _export: NewSheetExport) -> Result<SheetExport> {
+            unimplemented!()
+        }
+        async fn delete_export(&self, _id: &str) -> Result<usize> {
+            unimplemented!()
+        }
+        fn has_credentials(&self) -> Result<bool> {
+            unimplemented!()
+        }
+        fn set_credentials(&self, _credentials: GoogleCredentials) -> Result<()> {
+            unimplemented!()
+        }
+        fn delete_credentials(&self) -> Result<()> {
+            unimplemented!()
+        }
+        async fn push_export(&self, id: &str) -> Result<SheetExportResult> {
+            self.pushed.lock().unwrap().push(id.to_string());
+            Ok(SheetExportResult {
+                export_id: id.to_string(),
+                export_name: "Holdings".to_string(),
+                rows: 4,
+                error: None,
+            })
+        }
+        async fn push_due_exports(&self) -> Result<Vec<SheetExportResult>> {
+            unimplemented!()
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingWebhooks {
+        calls: Mutex<Vec<(String, Value)>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl WebhookClient for RecordingWebhooks {
+        async fn post_json(&self, url: &str, body: &Value) -> Result<()> {
+            if self.fail {
+                return Err(Error::Webhook(format!("{} answered 500", url)));
+            }
+            self.calls
+                .lock()
+                .unwrap()
+                .push((url.to_string(), body.clone()));
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        repo: Arc<InMemoryAutomationRepository>,
+        sheets: Arc<RecordingSheetExports>,
+        webhooks: Arc<RecordingWebhooks>,
+        service: AutomationService,
+    }
+
+    fn fixture(failing_webhooks: bool) -> Fixture {
+        let repo = Arc::new(InMemoryAutomationRepository::default());
+        let sheets = Arc::new(RecordingSheetExports::default());
+        let webhooks = Arc::new(RecordingWebhooks {
+            fail: failing_webhooks,
+            ..Default::default()
+        });
+        let service = AutomationService::new(repo.clone(), sheets.clone())
+            .with_webhook_client(webhooks.clone());
+        Fixture {
+            repo,
+            sheets,
+            webhooks,
+            service,
+        }
+    }
+
+    fn rule(trigger: AutomationTrigger, actions: Vec<AutomationAction>) -> NewAutomationRule {
+        NewAutomationRule {
+            id: None,
+            name: "Rule".to_string(),
+            trigger,
+            actions,
+            is_enabled: true,
+        }
+    }
+
+    fn notify(title: &str, body: &str) -> AutomationAction {
+        AutomationAction::Notify {
+            title: title.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    fn quote(symbol: &str, day: u32, close: Decimal) -> Quote {
+        let timestamp = Utc.with_ymd_and_hms(2026, 10, day, 8, 0, 0).unwrap();
+        Quote {
+            id: format!("{}-{}", symbol, day),
+            symbol: symbol.to_string(),
+            timestamp,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            adjclose: close,
+            volume: Decimal::ZERO,
+            currency: "VND".to_string(),
+            data_source: DataSource::Manual,
+            created_at: timestamp,
+        }
+    }
+
+    fn goal(progress: Decimal) -> ScriptGoalProgress {
+        ScriptGoalProgress {
+            goal_id: "goal-1".to_string(),
+            goal_title: "Quỹ khẩn cấp".to_string(),
+            goal_type: "EMERGENCY_FUND".to_string(),
+            target_amount: dec!(100000000),
+            current_amount: dec!(100000000) * progress,
+            progress: Some(progress),
+        }
+    }
+
+    #[test]
+    fn rules_are_validated_as_data() {
+        let tag = AutomationAction::TagActivities {
+            tag: "imported".to_string(),
+        };
+        let quote_moved = AutomationTrigger::QuoteMoved {
+            symbol: None,
+            percent: dec!(5),
+        };
+        assert!(rule(quote_moved.clone(), vec![tag.clone()])
+            .validate()
+            .is_err());
+        assert!(rule(quote_moved.clone(), vec![]).validate().is_err());
+        assert!(rule(
+            quote_moved,
+            vec![AutomationAction::CallWebhook {
+                url: "ftp://example.com".to_string()
+            }]
+        )
+        .validate()
+        .is_err());
+        assert!(rule(
+            AutomationTrigger::GoalProgressReached {
+                goal_id: None,
+                percent: dec!(0)
+            },
+            vec![notify("Goal", "")]
+        )
+        .validate()
+        .is_err());
+
+        let parsed: NewAutomationRule = serde_json::from_value(json!({
+            "name": "Tag broker imports",
+            "trigger": { "type": "IMPORT_COMPLETED", "accountId": "acc-1" },
+            "actions": [{ "type": "TAG_ACTIVITIES", "tag": "ssi" }],
+            "isEnabled": true
+        }))
+        .unwrap();
+        assert!(parsed.validate().is_ok());
+        assert_eq!(
+            parsed.trigger,
+            AutomationTrigger::ImportCompleted {
+                account_id: Some("acc-1".to_string())
+            }
+        );
+        assert_eq!(
+            parsed.actions,
+            vec![AutomationAction::TagActivities {
+                tag: "ssi".to_string()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn import_rules_tag_notify_and_run_reports() {
+        let f = fixture(false);
+        f.service
+            .create_rule(rule(
+                AutomationTrigger::ImportCompleted { account_id: None },
+                vec![
+                    AutomationAction::TagActivities {
+                        tag: "imported".to_string(),
+                    },
+                    notify("Import done", "{count} activities in {account}"),
+                    AutomationAction::RunReport {
+                        sheet_export_id: "export-1".to_string(),
+                    },
+                ],
+            ))
+            .await
+            .unwrap();
+        f.service
+            .create_rule(rule(
+                AutomationTrigger::ImportCompleted {
+                    account_id: Some("other".to_string()),
+                },
+                vec![notify("Other account", "")],
+            ))
+            .await
+            .unwrap();
+
+        let import = ImportCompletion {
+            account_id: "acc-1".to_string(),
+            imported: 3,
+            started_at: Utc::now().naive_utc(),
+        };
+        let report = f.service.on_import_completed(&import).await.unwrap();
+        assert_eq!(report.fired.len(), 1);
+        assert_eq!(report.tagged, 3);
+        assert_eq!(report.notifications.len(), 1);
+        assert_eq!(report.notifications[0].body, "3 activities in acc-1");
+        assert!(report.failures.is_empty());
+        assert_eq!(*f.sheets.pushed.lock().unwrap(), vec!["export-1"]);
+        assert_eq!(
+            *f.repo.tagged.lock().unwrap(),
+            vec![("acc-1".to_string(), "imported".to_string())]
+        );
+        assert!(f.service.get_rules().unwrap()[0]
+            .last_triggered_at
+            .is_some());
+
+        let empty = ImportCompletion {
+            imported: 0,
+            ..import
+        };
+        let report = f.service.on_import_completed(&empty).await.unwrap();
+        assert!(report.fired.is_empty());
+    }
+
+    #[tokio::test]
+    async fn goal_rules_fire_once_per_crossing() {
+        let f = fixture(false);
+        f.service
+            .create_rule(rule(
+                AutomationTrigger::GoalProgressReached {
+                    goal_id: None,
+                    percent: dec!(80),
+                },
+                vec![notify("{goal}", "{progress}% reached")],
+            ))
+            .await
+            .unwrap();
+
+        let report = f
+            .service
+            .on_goal_progress(&[goal(dec!(0.5))])
+            .await
+            .unwrap();
+        assert!(report.fired.is_empty());
+
+        let report = f
+            .service
+            .on_goal_progress(&[goal(dec!(0.82))])
+            .await
+            .unwrap();
+        assert_eq!(report.fired.len(), 1);
+        assert_eq!(report.notifications[0].title, "Quỹ khẩn cấp");
+        assert_eq!(report.notifications[0].body, "82.0% reached");
+
+        let report = f
+            .service
+            .on_goal_progress(&[goal(dec!(0.9))])
+            .await
+            .unwrap();
+        assert!(report.fired.is_empty());
+
+        // Falling back below re-arms the rule
+        f.service
+            .on_goal_progress(&[goal(dec!(0.7))])
+            .await
+            .unwrap();
+        let report = f
+            .service
+            .on_goal_progress(&[goal(dec!(0.85))])
+            .await
+            .unwrap();
+        assert_eq!(report.fired.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn quote_rules_fire_once_per_day_and_report_webhook_failures() {
+        let f = fixture(false);
+        f.service
+            .create_rule(rule(
+                AutomationTrigger::QuoteMoved {
+                    symbol: None,
+                    percent: dec!(5),
+                },
+                vec![AutomationAction::CallWebhook {
+                    url: "https://example.com/hook".to_string(),
+                }],
+            ))
+            .await
+            .unwrap();
+
+        let quotes = vec![
+            LatestQuotePair {
+                latest: quote("FPT", 17, dec!(94500)),
+                previous: Some(quote("FPT", 16, dec!(100000))),
+            },
+            LatestQuotePair {
+                latest: quote("VNM", 17, dec!(61000)),
+                previous: Some(quote("VNM", 16, dec!(60000))),
+            },
+            LatestQuotePair {
+                latest: quote("HPG", 17, dec!(27000)),
+                previous: None,
+            },
+        ];
+        let report = f.service.on_quote_update(&quotes).await.unwrap();
+        assert_eq!(report.fired.len(), 1);
+        assert_eq!(report.fired[0].subject, "FPT");
+        {
+            let calls = f.webhooks.calls.lock().unwrap();
+            assert_eq!(calls.len(), 1);
+            assert_eq!(calls[0].1["trigger"], "QUOTE_MOVED");
+            assert_eq!(calls[0].1["event"]["date"], "2026-10-17");
+        }
+
+        // Same quote day again: nothing new
+        let report = f.service.on_quote_update(&quotes).await.unwrap();
+        assert!(report.fired.is_empty());
+
+        let failing = fixture(true);
+        failing
+            .service
+            .create_rule(rule(
+                AutomationTrigger::QuoteMoved {
+                    symbol: Some("fpt".to_string()),
+                    percent: dec!(5),
+                },
+                vec![
+                    AutomationAction::CallWebhook {
+                        url: "https://example.com/hook".to_string(),
+                    },
+                    notify("{symbol} moved {change}%", ""),
+                ],
+            ))
+            .await
+            .unwrap();
+        let report = failing.service.on_quote_update(&quotes).await.unwrap();
+        assert_eq!(report.fired.len(), 1);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].action, "CALL_WEBHOOK");
+        assert_eq!(report.notifications[0].title, "FPT moved -5.50%");
+    }
+}
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde_json::Value;
+
+use super::automations_model::{
+    ActivityTag, AutomationRule, AutomationRuleFiringDB, AutomationRunReport, ImportCompletion,
+    NewAutomationRule,
+};
+use crate::errors::Result;
+use crate::market_data::LatestQuotePair;
+use crate::scripting::ScriptGoalProgress;
+
+/// Sends the JSON body of CALL_WEBHOOK actions
+#[async_trait]
+pub trait WebhookClient: Send + Sync {
+    async fn post_json(&self, url: &str, body: &Value) -> Result<()>;
+}
+
+#[async_trait]
+pub trait AutomationRepositoryTrait: Send + Sync {
+    fn get_rules(&self) -> Result<Vec<AutomationRule>>;
+    fn get_rule(&self, id: &str) -> Result<AutomationRule>;
+    fn get_enabled_rules(&self) -> Result<Vec<AutomationRule>>;
+    async fn insert_rule(&self, rule: NewAutomationRule) -> Result<AutomationRule>;
+    async fn update_rule(&self, id: &str, rule: NewAutomationRule) -> Result<AutomationRule>;
+    async fn delete_rule(&self, id: &str) -> Result<usize>;
+    async fn mark_triggered(&self, id: &str, triggered_at: NaiveDateTime) -> Result<()>;
+    fn get_firings(&self, rule_id: &str) -> Result<Vec<AutomationRuleFiringDB>>;
+    /// Replaces the rule's firing for the same subject
+    async fn record_firing(&self, firing: AutomationRuleFiringDB) -> Result<()>;
+    async fn clear_firings(&self, rule_id: &str, subjects: Vec<String>) -> Result<()>;
+    /// Tags the account's activities created at or after `since`; returns the newly tagged count
+    async fn tag_activities_since(
+        &self,
+        account_id: &str,
+        since: NaiveDateTime,
+        tag: &str,
+    ) -> Result<usize>;
+    fn get_activity_tags(&self) -> Result<Vec<ActivityTag>>;
+}
+
+#[async_trait]
+pub trait AutomationServiceTrait: Send + Sync {
+    fn get_rules(&self) -> Result<Vec<AutomationRule>>;
+    fn get_rule(&self, id: &str) -> Result<AutomationRule>;
+    async fn create_rule(&self, rule: NewAutomationRule) -> Result<AutomationRule>;
+    async fn update_rule(&self, id: &str, rule: NewAutomationRule) -> Result<AutomationRule>;
+    async fn delete_rule(&self, id: &str) -> Result<usize>;
+    fn get_activity_tags(&self) -> Result<Vec<ActivityTag>>;
+    /// Runs IMPORT_COMPLETED rules; nothing fires for an import that brought nothing in
+    async fn on_import_completed(&self, import: &ImportCompletion) -> Result<AutomationRunReport>;
+    /// Runs GOAL_PROGRESS_REACHED rules over every goal with tracked progress
+    async fn on_goal_progress(&self, goals: &[ScriptGoalProgress]) -> Result<AutomationRunReport>;
+    /// Runs QUOTE_MOVED rules over the latest two quotes of each symbol
+    async fn on_quote_update(&self, quotes: &[LatestQuotePair]) -> Result<AutomationRunReport>;
+}
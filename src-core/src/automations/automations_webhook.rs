@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::Value;
+use std::time::Duration;
+
+use super::automations_traits::WebhookClient;
+use crate::errors::{Error, Result};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Plain HTTPS POST; any 2xx answer counts as delivered
+pub struct HttpWebhookClient {
+    client: Client,
+}
+
+impl Default for HttpWebhookClient {
+    fn default() -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("WealthVN/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        HttpWebhookClient { client }
+    }
+}
+
+#[async_trait]
+impl WebhookClient for HttpWebhookClient {
+    async fn post_json(&self, url: &str, body: &Value) -> Result<()> {
+        let response = self
+            .client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| Error::Webhook(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Webhook(format!("{} answered {}", url, status)));
+        }
+        Ok(())
+    }
+}
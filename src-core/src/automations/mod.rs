@@ -0,0 +1,15 @@
+mod automations_model;
+mod automations_repository;
+mod automations_service;
+mod automations_traits;
+mod automations_webhook;
+
+pub use automations_model::{
+    ActivityTag, AutomationAction, AutomationFailure, AutomationFiring, AutomationRule,
+    AutomationRunReport, AutomationTrigger, ImportCompletion, NewAutomationRule, MAX_RULE_ACTIONS,
+    MAX_TAG_LENGTH,
+};
+pub use automations_repository::AutomationRepository;
+pub use automations_service::AutomationService;
+pub use automations_traits::{AutomationRepositoryTrait, AutomationServiceTrait, WebhookClient};
+pub use automations_webhook::HttpWebhookClient;
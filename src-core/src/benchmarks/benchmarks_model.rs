@@ -0,0 +1,258 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Value both series of a comparison start from
+pub const NORMALIZED_BASE: Decimal = Decimal::ONE_HUNDRED;
+
+/// Days compared when no start date is given
+pub const DEFAULT_COMPARISON_DAYS: u64 = 365;
+
+/// Database row for `benchmarks`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::benchmarks)]
+pub struct BenchmarkDB {
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub data_source: String,
+    pub currency: String,
+    pub is_custom: bool,
+    pub created_at: NaiveDateTime,
+}
+
+/// An index to compare accounts and goals against. Its prices are the quotes of `symbol`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Benchmark {
+    pub id: String,
+    pub name: String,
+    /// Symbol the index is quoted under, e.g. VNINDEX or ^GSPC
+    pub symbol: String,
+    /// Provider of the prices; MANUAL for an index whose prices are entered by hand
+    pub data_source: String,
+    pub currency: String,
+    pub is_custom: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<BenchmarkDB> for Benchmark {
+    fn from(db: BenchmarkDB) -> Self {
+        Benchmark {
+            id: db.id,
+            name: db.name,
+            symbol: db.symbol,
+            data_source: db.data_source,
+            currency: db.currency,
+            is_custom: db.is_custom,
+            created_at: db.created_at,
+        }
+    }
+}
+
+/// A custom index, such as a fund's NAV or a blend kept by hand
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBenchmark {
+    pub name: String,
+    pub symbol: String,
+    pub data_source: String,
+    pub currency: String,
+}
+
+impl NewBenchmark {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("name", &self.name),
+            ("symbol", &self.symbol),
+            ("dataSource", &self.data_source),
+            ("currency", &self.currency),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What a benchmark is compared against
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ComparisonTarget {
+    Account,
+    /// The accounts funding a goal, each counted for the share allocated to it
+    Goal,
+}
+
+/// Both series on one day, each as the value of 100 invested at the start
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkComparisonPoint {
+    pub date: NaiveDate,
+    pub target: Decimal,
+    pub benchmark: Decimal,
+}
+
+/// An account or goal against a benchmark over a range. The target's growth leaves out
+/// deposits and withdrawals (time-weighted), and the benchmark is taken in its own currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkComparison {
+    pub target_id: String,
+    pub target_type: ComparisonTarget,
+    pub benchmark_id: String,
+    /// First day both series have a value; `None` when they never overlap
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub series: Vec<BenchmarkComparisonPoint>,
+    /// Growth over the range as a fraction, e.g. 0.12 for 12%
+    pub target_return: Decimal,
+    pub benchmark_return: Decimal,
+    /// `target_return - benchmark_return`; positive when the target beat the market
+    pub excess_return: Decimal,
+}
+
+/// Value and net contributions of the target on one day, in the base currency
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TargetValue {
+    pub value: Decimal,
+    pub net_contribution: Decimal,
+}
+
+/// Aligns the target's daily values with the benchmark's prices and rebases both to
+/// `NORMALIZED_BASE`. Each day of the target is matched with the last price on or before it;
+/// days before the first price, or while the target holds nothing, start no series. The
+/// target compounds its daily returns net of the contributions made that day.
+pub fn compare_series(
+    target: &BTreeMap<NaiveDate, TargetValue>,
+    prices: &BTreeMap<NaiveDate, Decimal>,
+) -> Vec<BenchmarkComparisonPoint> {
+    let mut series = Vec::new();
+    let mut start_price = Decimal::ZERO;
+    let mut target_index = NORMALIZED_BASE;
+    let mut previous: Option<TargetValue> = None;
+    for (date, today) in target {
+        let Some(price) = prices
+            .range(..=*date)
+            .next_back()
+            .map(|(_, price)| *price)
+            .filter(|price| !price.is_zero())
+        else {
+            continue;
+        };
+        match previous {
+            None if today.value <= Decimal::ZERO => continue,
+            None => start_price = price,
+            Some(yesterday) if yesterday.value > Decimal::ZERO => {
+                let flow = today.net_contribution - yesterday.net_contribution;
+                target_index *= (today.value - flow) / yesterday.value;
+            }
+            // Emptied out and funded again: the index carries on from where it stood
+            Some(_) => {}
+        }
+        previous = Some(*today);
+        series.push(BenchmarkComparisonPoint {
+            date: *date,
+            target: target_index.round_dp(4),
+            benchmark: (NORMALIZED_BASE * price / start_price).round_dp(4),
+        });
+    }
+    series
+}
+
+/// Growth of a rebased series from its first point to its last
+pub fn series_return(
+    series: &[BenchmarkComparisonPoint],
+    pick: fn(&BenchmarkComparisonPoint) -> Decimal,
+) -> Decimal {
+    series.last().map_or(Decimal::ZERO, |last| {
+        (pick(last) / NORMALIZED_BASE - Decimal::ONE).round_dp(6)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn deposits_do_not_count_as_growth_and_prices_carry_over_weekends() {
+        let target = BTreeMap::from([
+            (
+                date("2026-10-09"),
+                TargetValue {
+                    value: dec!(0),
+                    net_contribution: dec!(0),
+                },
+            ),
+            (
+                date("2026-10-10"),
+                TargetValue {
+                    value: dec!(100_000_000),
+                    net_contribution: dec!(100_000_000),
+                },
+            ),
+            // Up 10% before a deposit of 50m the same day
+            (
+                date("2026-10-11"),
+                TargetValue {
+                    value: dec!(160_000_000),
+                    net_contribution: dec!(150_000_000),
+                },
+            ),
+            (
+                date("2026-10-12"),
+                TargetValue {
+                    value: dec!(144_000_000),
+                    net_contribution: dec!(150_000_000),
+                },
+            ),
+        ]);
+        // No price on the 11th, a Sunday
+        let prices = BTreeMap::from([
+            (date("2026-10-09"), dec!(1_250)),
+            (date("2026-10-10"), dec!(1_250)),
+            (date("2026-10-12"), dec!(1_300)),
+        ]);
+
+        let series = compare_series(&target, &prices);
+
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0].date, date("2026-10-10"));
+        assert_eq!(series[0].target, dec!(100));
+        assert_eq!(series[1].target, dec!(110));
+        assert_eq!(series[1].benchmark, dec!(100));
+        assert_eq!(series[2].target, dec!(99));
+        assert_eq!(series[2].benchmark, dec!(104));
+        assert_eq!(series_return(&series, |p| p.target), dec!(-0.01));
+        assert_eq!(series_return(&series, |p| p.benchmark), dec!(0.04));
+    }
+
+    #[test]
+    fn no_series_before_the_first_price() {
+        let target = BTreeMap::from([(
+            date("2026-10-10"),
+            TargetValue {
+                value: dec!(1_000),
+                net_contribution: dec!(1_000),
+            },
+        )]);
+        let prices = BTreeMap::from([(date("2026-10-12"), dec!(1_300))]);
+
+        let series = compare_series(&target, &prices);
+
+        assert!(series.is_empty());
+        assert_eq!(series_return(&series, |p| p.target), Decimal::ZERO);
+    }
+}
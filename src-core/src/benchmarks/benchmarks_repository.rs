@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::benchmarks_model::{Benchmark, BenchmarkDB, NewBenchmark};
+use super::benchmarks_traits::BenchmarkRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::benchmarks;
+
+pub struct BenchmarkRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl BenchmarkRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        BenchmarkRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl BenchmarkRepositoryTrait for BenchmarkRepository {
+    fn get_benchmarks(&self) -> Result<Vec<Benchmark>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(benchmarks::table
+            .order((benchmarks::is_custom.asc(), benchmarks::name.asc()))
+            .load::<BenchmarkDB>(&mut conn)?
+            .into_iter()
+            .map(Benchmark::from)
+            .collect())
+    }
+
+    fn get_benchmark(&self, id: &str) -> Result<Benchmark> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(benchmarks::table
+            .find(id)
+            .first::<BenchmarkDB>(&mut conn)?
+            .into())
+    }
+
+    async fn insert_benchmark(&self, benchmark: NewBenchmark) -> Result<Benchmark> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Benchmark> {
+                let record = BenchmarkDB {
+                    id: Uuid::new_v4().to_string(),
+                    name: benchmark.name.trim().to_string(),
+                    symbol: benchmark.symbol.trim().to_uppercase(),
+                    data_source: benchmark.data_source.trim().to_uppercase(),
+                    currency: benchmark.currency.trim().to_uppercase(),
+                    is_custom: true,
+                    created_at: Utc::now().naive_utc(),
+                };
+                Ok(diesel::insert_into(benchmarks::table)
+                    .values(&record)
+                    .get_result::<BenchmarkDB>(conn)?
+                    .into())
+            })
+            .await
+    }
+
+    async fn delete_benchmark(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(benchmarks::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::benchmarks_model::{
+    compare_series, series_return, Benchmark, BenchmarkComparison, ComparisonTarget, NewBenchmark,
+    TargetValue, DEFAULT_COMPARISON_DAYS,
+};
+use super::benchmarks_traits::{BenchmarkRepositoryTrait, BenchmarkServiceTrait};
+use crate::errors::{Error, Result, ValidationError};
+use crate::goals::GoalRepositoryTrait;
+use crate::market_data::{MarketDataServiceTrait, DATA_SOURCE_MANUAL};
+use crate::portfolio::valuation::ValuationServiceTrait;
+
+/// Days before the start a price is looked for, so a range starting on a holiday has one
+const PRICE_LOOKBACK_DAYS: u64 = 7;
+
+pub struct BenchmarkService {
+    repository: Arc<dyn BenchmarkRepositoryTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    valuation_service: Arc<dyn ValuationServiceTrait>,
+    goal_repository: Arc<dyn GoalRepositoryTrait>,
+}
+
+impl BenchmarkService {
+    pub fn new(
+        repository: Arc<dyn BenchmarkRepositoryTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        valuation_service: Arc<dyn ValuationServiceTrait>,
+        goal_repository: Arc<dyn GoalRepositoryTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            market_data_service,
+            valuation_service,
+            goal_repository,
+        }
+    }
+
+    /// Closing prices of the benchmark from shortly before `start` to `end`. Prices missing
+    /// from the quotes are fetched from the benchmark's provider and kept.
+    async fn prices(
+        &self,
+        benchmark: &Benchmark,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, Decimal>> {
+        let from = start - Days::new(PRICE_LOOKBACK_DAYS);
+        let quotes = if benchmark.data_source == DATA_SOURCE_MANUAL {
+            self.market_data_service
+                .get_historical_quotes_for_symbol(&benchmark.symbol)?
+        } else {
+            self.market_data_service
+                .get_historical_quotes_from_provider(&benchmark.symbol, from, end)
+                .await?
+        };
+        Ok(quotes
+            .into_iter()
+            .map(|quote| (quote.timestamp.date_naive(), quote.close))
+            .filter(|(date, _)| (from..=end).contains(date))
+            .collect())
+    }
+
+    /// Daily value and contributions of the accounts in the base currency, each account
+    /// counted for the percentage given
+    fn target_values(
+        &self,
+        accounts: &[(String, Decimal)],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<BTreeMap<NaiveDate, TargetValue>> {
+        let mut values: BTreeMap<NaiveDate, TargetValue> = BTreeMap::new();
+        for (account_id, percent) in accounts {
+            let share = *percent / Decimal::ONE_HUNDRED;
+            for valuation in self.valuation_service.get_historical_valuations(
+                account_id,
+                Some(start),
+                Some(end),
+            )? {
+                let day = values.entry(valuation.valuation_date).or_default();
+                day.value += valuation.total_value * valuation.fx_rate_to_base * share;
+                day.net_contribution +=
+                    valuation.net_contribution * valuation.fx_rate_to_base * share;
+            }
+        }
+        Ok(values)
+    }
+
+    /// The accounts behind the id with the percentage of each that counts: every allocation
+    /// of a goal, or the whole of an account
+    async fn target_accounts(
+        &self,
+        account_or_goal_id: &str,
+    ) -> Result<(ComparisonTarget, Vec<(String, Decimal)>)> {
+        let is_goal = self
+            .goal_repository
+            .load_all_goals()
+            .await?
+            .iter()
+            .any(|goal| goal.id == account_or_goal_id);
+        if !is_goal {
+            return Ok((
+                ComparisonTarget::Account,
+                vec![(account_or_goal_id.to_string(), Decimal::ONE_HUNDRED)],
+            ));
+        }
+        let accounts = self
+            .goal_repository
+            .get_allocations_for_goal(account_or_goal_id)
+            .await?
+            .into_iter()
+            .filter_map(|allocation| {
+                Decimal::from_f64_retain(allocation.allocation_percentage)
+                    .filter(|percent| *percent > Decimal::ZERO)
+                    .map(|percent| (allocation.account_id, percent))
+            })
+            .collect();
+        Ok((ComparisonTarget::Goal, accounts))
+    }
+}
+
+#[async_trait]
+impl BenchmarkServiceTrait for BenchmarkService {
+    fn get_benchmarks(&self) -> Result<Vec<Benchmark>> {
+        self.repository.get_benchmarks()
+    }
+
+    async fn create_benchmark(&self, benchmark: NewBenchmark) -> Result<Benchmark> {
+        benchmark.validate()?;
+        self.repository.insert_benchmark(benchmark).await
+    }
+
+    async fn delete_benchmark(&self, id: &str) -> Result<usize> {
+        let benchmark = self.repository.get_benchmark(id)?;
+        if !benchmark.is_custom {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} comes with the app and cannot be removed",
+                benchmark.name
+            ))));
+        }
+        self.repository.delete_benchmark(id).await
+    }
+
+    async fn compare_to_benchmark(
+        &self,
+        account_or_goal_id: &str,
+        benchmark_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<BenchmarkComparison> {
+        let end = end_date.unwrap_or_else(|| Utc::now().date_naive());
+        let start = start_date.unwrap_or(end - Days::new(DEFAULT_COMPARISON_DAYS));
+        if start > end {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Start date {} is after end date {}",
+                start, end
+            ))));
+        }
+        let benchmark = self.repository.get_benchmark(benchmark_id)?;
+        let (target_type, accounts) = self.target_accounts(account_or_goal_id).await?;
+        let values = self.target_values(&accounts, start, end)?;
+        let prices = self.prices(&benchmark, start, end).await?;
+
+        let series = compare_series(&values, &prices);
+        let target_return = series_return(&series, |point| point.target);
+        let benchmark_return = series_return(&series, |point| point.benchmark);
+        Ok(BenchmarkComparison {
+            target_id: account_or_goal_id.to_string(),
+            target_type,
+            benchmark_id: benchmark.id,
+            start_date: series.first().map(|point| point.date),
+            end_date: series.last().map(|point| point.date),
+            series,
+            target_return,
+            benchmark_return,
+            excess_return: target_return - benchmark_return,
+        })
+    }
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::benchmarks_model::{Benchmark, BenchmarkComparison, NewBenchmark};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait BenchmarkRepositoryTrait: Send + Sync {
+    fn get_benchmarks(&self) -> Result<Vec<Benchmark>>;
+    fn get_benchmark(&self, id: &str) -> Result<Benchmark>;
+    async fn insert_benchmark(&self, benchmark: NewBenchmark) -> Result<Benchmark>;
+    async fn delete_benchmark(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait BenchmarkServiceTrait: Send + Sync {
+    fn get_benchmarks(&self) -> Result<Vec<Benchmark>>;
+    async fn create_benchmark(&self, benchmark: NewBenchmark) -> Result<Benchmark>;
+    /// Only custom benchmarks can be removed; their quotes are kept
+    async fn delete_benchmark(&self, id: &str) -> Result<usize>;
+    /// Compares an account, or the accounts funding a goal, with a benchmark between the
+    /// dates; by default over the last `DEFAULT_COMPARISON_DAYS` up to today
+    async fn compare_to_benchmark(
+        &self,
+        account_or_goal_id: &str,
+        benchmark_id: &str,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<BenchmarkComparison>;
+}
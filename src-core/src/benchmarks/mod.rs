@@ -0,0 +1,13 @@
+mod benchmarks_model;
+mod benchmarks_repository;
+mod benchmarks_service;
+mod benchmarks_traits;
+
+pub use benchmarks_model::{
+    compare_series, series_return, Benchmark, BenchmarkComparison, BenchmarkComparisonPoint,
+    BenchmarkDB, ComparisonTarget, NewBenchmark, TargetValue, DEFAULT_COMPARISON_DAYS,
+    NORMALIZED_BASE,
+};
+pub use benchmarks_repository::BenchmarkRepository;
+pub use benchmarks_service::BenchmarkService;
+pub use benchmarks_traits::{BenchmarkRepositoryTrait, BenchmarkServiceTrait};
@@ -0,0 +1,416 @@
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+use crate::utils::lunar_calendar::{lunar_anniversary, LunarDate};
+
+/// Days either side of a due date in which a payment can match it
+pub const BILL_MATCH_WINDOW_DAYS: u64 = 7;
+/// Unpaid due dates older than this are no longer reminded or matched
+pub const BILL_OVERDUE_LOOKBACK_DAYS: u64 = 60;
+pub const DEFAULT_BILL_REMINDER_DAYS: i32 = 3;
+pub const MAX_BILL_REMINDER_DAYS: i32 = 30;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BillFrequency {
+    Monthly,
+    /// Every three months from the start month
+    Quarterly,
+    /// Once a year in the start month
+    Yearly,
+    /// Once a year on a lunar date, e.g. a giỗ or Tet preparations; `due_day` is the lunar
+    /// day and `lunar_month` the lunar month
+    LunarYearly,
+}
+
+impl BillFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BillFrequency::Monthly => "MONTHLY",
+            BillFrequency::Quarterly => "QUARTERLY",
+            BillFrequency::Yearly => "YEARLY",
+            BillFrequency::LunarYearly => "LUNAR_YEARLY",
+        }
+    }
+
+    pub fn step_months(&self) -> u32 {
+        match self {
+            BillFrequency::Monthly => 1,
+            BillFrequency::Quarterly => 3,
+            BillFrequency::Yearly | BillFrequency::LunarYearly => 12,
+        }
+    }
+}
+
+impl FromStr for BillFrequency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "MONTHLY" => Ok(BillFrequency::Monthly),
+            "QUARTERLY" => Ok(BillFrequency::Quarterly),
+            "YEARLY" => Ok(BillFrequency::Yearly),
+            "LUNAR_YEARLY" => Ok(BillFrequency::LunarYearly),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown bill frequency: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `bills`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::bills)]
+pub struct BillDB {
+    pub id: String,
+    pub name: String,
+    pub payee: String,
+    pub amount: String,
+    pub currency: String,
+    pub account_id: Option<String>,
+    pub category_id: Option<String>,
+    pub frequency: String,
+    pub due_day: i32,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub reminder_days: i32,
+    pub match_pattern: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub lunar_month: Option<i32>,
+}
+
+/// A recurring bill such as rent, electricity or school fees
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Bill {
+    pub id: String,
+    pub name: String,
+    pub payee: String,
+    pub amount: Decimal,
+    pub currency: String,
+    /// Account the bill is paid from; payments from any account match when unset
+    pub account_id: Option<String>,
+    /// Budget category given to the activity that pays the bill
+    pub category_id: Option<String>,
+    pub frequency: BillFrequency,
+    /// Day of month the bill is due, clamped to the last day of shorter months
+    pub due_day: i32,
+    /// Lunar month of a `LunarYearly` bill
+    pub lunar_month: Option<i32>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    /// Days before the due date that a reminder is raised
+    pub reminder_days: i32,
+    /// Text looked for in a payment's comment; the payee is used when unset
+    pub match_pattern: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Bill {
+    /// Due dates within `from..=to`
+    pub fn due_dates(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let first = from.max(self.start_date);
+        let last = self.end_date.map_or(to, |end| end.min(to));
+        if first > last {
+            return Vec::new();
+        }
+        if self.frequency == BillFrequency::LunarYearly {
+            return self.lunar_due_dates(first, last);
+        }
+
+        let step = self.frequency.step_months();
+        let day = self.due_day.max(1) as u32;
+        let anchor = self.start_date.with_day(1).unwrap_or(self.start_date);
+        let mut dates = Vec::new();
+        let mut n = 0;
+        while let Some(month) = anchor.checked_add_months(Months::new(step * n)) {
+            if month > last {
+                break;
+            }
+            let due = month
+                .checked_add_months(Months::new(1))
+                .map(|next| next - Days::new(1))
+                .and_then(|month_end| month.with_day(day.min(month_end.day())));
+            if let Some(date) = due.filter(|d| *d >= first && *d <= last) {
+                dates.push(date);
+            }
+            n += 1;
+        }
+        dates
+    }
+
+    /// Solar dates of the bill's lunar day within `first..=last`. A lunar year's late months
+    /// fall in the next solar year, so the lunar year before `first` is checked too.
+    fn lunar_due_dates(&self, first: NaiveDate, last: NaiveDate) -> Vec<NaiveDate> {
+        let month = self.lunar_month.unwrap_or(1).max(1) as u32;
+        let day = self.due_day.max(1) as u32;
+        (first.year() - 1..=last.year())
+            .filter_map(|year| lunar_anniversary(year, month, day))
+            .filter(|d| *d >= first && *d <= last)
+            .collect()
+    }
+
+    /// Lowercased text a paying activity's comment must contain
+    pub fn match_text(&self) -> String {
+        self.match_pattern
+            .as_deref()
+            .unwrap_or(&self.payee)
+            .to_lowercase()
+    }
+}
+
+impl TryFrom<BillDB> for Bill {
+    type Error = Error;
+
+    fn try_from(db: BillDB) -> Result<Self> {
+        Ok(Bill {
+            id: db.id,
+            name: db.name,
+            payee: db.payee,
+            amount: Decimal::from_str(&db.amount)?,
+            currency: db.currency,
+            account_id: db.account_id,
+            category_id: db.category_id,
+            frequency: db.frequency.parse()?,
+            due_day: db.due_day,
+            lunar_month: db.lunar_month,
+            start_date: parse_forecast_date(&db.start_date)?,
+            end_date: db
+                .end_date
+                .as_deref()
+                .map(parse_forecast_date)
+                .transpose()?,
+            reminder_days: db.reminder_days,
+            match_pattern: db.match_pattern,
+            is_active: db.is_active,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a bill
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBill {
+    pub id: Option<String>,
+    pub name: String,
+    pub payee: String,
+    pub amount: Decimal,
+    pub currency: String,
+    pub account_id: Option<String>,
+    pub category_id: Option<String>,
+    pub frequency: BillFrequency,
+    pub due_day: i32,
+    /// Required for `LunarYearly` bills and ignored otherwise
+    pub lunar_month: Option<i32>,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default = "default_reminder_days")]
+    pub reminder_days: i32,
+    pub match_pattern: Option<String>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+fn default_reminder_days() -> i32 {
+    DEFAULT_BILL_REMINDER_DAYS
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl NewBill {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.payee.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "payee".to_string(),
+            )));
+        }
+        if self.currency.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "currency".to_string(),
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Bill amount must be positive".to_string(),
+            )));
+        }
+        if !(1..=31).contains(&self.due_day) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid due day {}",
+                self.due_day
+            ))));
+        }
+        if self.frequency == BillFrequency::LunarYearly {
+            let Some(month) = self.lunar_month else {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    "lunarMonth".to_string(),
+                )));
+            };
+            LunarDate::new(
+                self.start_date.year(),
+                month.max(0) as u32,
+                self.due_day as u32,
+            )
+            .validate()?;
+        }
+        if !(0..=MAX_BILL_REMINDER_DAYS).contains(&self.reminder_days) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Reminder days must be between 0 and {}",
+                MAX_BILL_REMINDER_DAYS
+            ))));
+        }
+        if self.end_date.is_some_and(|end| end < self.start_date) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Bill end date is before its start date".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How a bill payment was recorded
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BillPaymentSource {
+    /// Matched to an imported or entered activity
+    Matched,
+    /// Marked paid by the user
+    Manual,
+}
+
+impl BillPaymentSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BillPaymentSource::Matched => "MATCHED",
+            BillPaymentSource::Manual => "MANUAL",
+        }
+    }
+}
+
+impl FromStr for BillPaymentSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "MATCHED" => Ok(BillPaymentSource::Matched),
+            "MANUAL" => Ok(BillPaymentSource::Manual),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown bill payment source: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `bill_payments`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::bill_payments)]
+pub struct BillPaymentDB {
+    pub id: String,
+    pub bill_id: String,
+    pub due_date: String,
+    pub activity_id: Option<String>,
+    pub amount: String,
+    pub paid_date: String,
+    pub source: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// Settles one due date of a bill
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillPayment {
+    pub id: String,
+    pub bill_id: String,
+    pub due_date: NaiveDate,
+    pub activity_id: Option<String>,
+    /// Amount paid, in the bill's currency
+    pub amount: Decimal,
+    pub paid_date: NaiveDate,
+    pub source: BillPaymentSource,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<BillPaymentDB> for BillPayment {
+    type Error = Error;
+
+    fn try_from(db: BillPaymentDB) -> Result<Self> {
+        Ok(BillPayment {
+            id: db.id,
+            bill_id: db.bill_id,
+            due_date: parse_forecast_date(&db.due_date)?,
+            activity_id: db.activity_id,
+            amount: Decimal::from_str(&db.amount)?,
+            paid_date: parse_forecast_date(&db.paid_date)?,
+            source: db.source.parse()?,
+            created_at: db.created_at,
+        })
+    }
+}
+
+/// Input for marking a bill's due date paid by hand
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBillPayment {
+    pub bill_id: String,
+    pub due_date: NaiveDate,
+    /// Activity that paid the bill, if there is one
+    pub activity_id: Option<String>,
+    /// Defaults to the bill amount
+    pub amount: Option<Decimal>,
+    /// Defaults to the due date
+    pub paid_date: Option<NaiveDate>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BillReminderStatus {
+    Upcoming,
+    DueToday,
+    Overdue,
+}
+
+/// An unpaid due date that is coming up or has passed
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BillReminder {
+    pub bill_id: String,
+    pub bill_name: String,
+    pub payee: String,
+    pub due_date: NaiveDate,
+    pub amount: Decimal,
+    pub currency: String,
+    pub account_id: Option<String>,
+    /// The due date in the lunar calendar, for bills scheduled on a lunar date
+    pub lunar_due_date: Option<LunarDate>,
+    /// Negative once the due date has passed
+    pub days_until_due: i64,
+    pub status: BillReminderStatus,
+}
+
+/// Outcome of matching activities against unpaid bills
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BillMatchResult {
+    pub matched: usize,
+    pub payments: Vec<BillPayment>,
+}
@@ -0,0 +1,198 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::bills_model::{Bill, BillDB, BillFrequency, BillPayment, BillPaymentDB, NewBill};
+use super::bills_traits::BillRepositoryTrait;
+use crate::budgets::ActivityCategory;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::{activity_categories, bill_payments, bills};
+
+pub struct BillRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl BillRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        BillRepository { pool, writer }
+    }
+}
+
+fn normalize_pattern(pattern: Option<String>) -> Option<String> {
+    pattern
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+/// Only bills scheduled on a lunar date keep a lunar month
+fn stored_lunar_month(bill: &NewBill) -> Option<i32> {
+    bill.lunar_month
+        .filter(|_| bill.frequency == BillFrequency::LunarYearly)
+}
+
+fn payment_record(payment: BillPayment) -> BillPaymentDB {
+    BillPaymentDB {
+        id: payment.id,
+        bill_id: payment.bill_id,
+        due_date: payment.due_date.format(FORECAST_DATE_FORMAT).to_string(),
+        activity_id: payment.activity_id,
+        amount: payment.amount.to_string(),
+        paid_date: payment.paid_date.format(FORECAST_DATE_FORMAT).to_string(),
+        source: payment.source.as_str().to_string(),
+        created_at: payment.created_at,
+    }
+}
+
+#[async_trait]
+impl BillRepositoryTrait for BillRepository {
+    fn get_bills(&self) -> Result<Vec<Bill>> {
+        let mut conn = get_connection(&self.pool)?;
+        bills::table
+            .order(bills::name.asc())
+            .load::<BillDB>(&mut conn)?
+            .into_iter()
+            .map(Bill::try_from)
+            .collect()
+    }
+
+    async fn insert_bill(&self, bill: NewBill) -> Result<Bill> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Bill> {
+                let now = chrono::Utc::now().naive_utc();
+                let lunar_month = stored_lunar_month(&bill);
+                let record = BillDB {
+                    id: bill.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    name: bill.name.trim().to_string(),
+                    payee: bill.payee.trim().to_string(),
+                    amount: bill.amount.to_string(),
+                    currency: bill.currency,
+                    account_id: bill.account_id,
+                    category_id: bill.category_id,
+                    frequency: bill.frequency.as_str().to_string(),
+                    due_day: bill.due_day,
+                    start_date: bill.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    end_date: bill
+                        .end_date
+                        .map(|d| d.format(FORECAST_DATE_FORMAT).to_string()),
+                    reminder_days: bill.reminder_days,
+                    match_pattern: normalize_pattern(bill.match_pattern),
+                    is_active: bill.is_active,
+                    created_at: now,
+                    updated_at: now,
+                    lunar_month,
+                };
+
+                diesel::insert_into(bills::table)
+                    .values(&record)
+                    .get_result::<BillDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_bill(&self, id: &str, bill: NewBill) -> Result<Bill> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Bill> {
+                let lunar_month = stored_lunar_month(&bill);
+                diesel::update(bills::table.find(id_owned))
+                    .set((
+                        bills::name.eq(bill.name.trim()),
+                        bills::payee.eq(bill.payee.trim()),
+                        bills::amount.eq(bill.amount.to_string()),
+                        bills::currency.eq(bill.currency),
+                        bills::account_id.eq(bill.account_id),
+                        bills::category_id.eq(bill.category_id),
+                        bills::frequency.eq(bill.frequency.as_str()),
+                        bills::due_day.eq(bill.due_day),
+                        bills::lunar_month.eq(lunar_month),
+                        bills::start_date
+                            .eq(bill.start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        bills::end_date.eq(bill
+                            .end_date
+                            .map(|d| d.format(FORECAST_DATE_FORMAT).to_string())),
+                        bills::reminder_days.eq(bill.reminder_days),
+                        bills::match_pattern.eq(normalize_pattern(bill.match_pattern)),
+                        bills::is_active.eq(bill.is_active),
+                        bills::updated_at.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<BillDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_bill(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(bills::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_bill_payments(&self, bill_id: Option<&str>) -> Result<Vec<BillPayment>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = bill_payments::table.into_boxed();
+        if let Some(bill_id) = bill_id {
+            query = query.filter(bill_payments::bill_id.eq(bill_id.to_string()));
+        }
+        query
+            .order(bill_payments::due_date.desc())
+            .load::<BillPaymentDB>(&mut conn)?
+            .into_iter()
+            .map(BillPayment::try_from)
+            .collect()
+    }
+
+    async fn save_bill_payments(
+        &self,
+        payments: Vec<BillPayment>,
+        categories: Vec<ActivityCategory>,
+    ) -> Result<Vec<BillPayment>> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<Vec<BillPayment>> {
+                    for payment in &payments {
+                        // A due date holds one payment; a newer record replaces the old one
+                        diesel::delete(
+                            bill_payments::table
+                                .filter(bill_payments::bill_id.eq(&payment.bill_id))
+                                .filter(
+                                    bill_payments::due_date.eq(payment
+                                        .due_date
+                                        .format(FORECAST_DATE_FORMAT)
+                                        .to_string()),
+                                ),
+                        )
+                        .execute(conn)?;
+                        diesel::insert_into(bill_payments::table)
+                            .values(payment_record(payment.clone()))
+                            .execute(conn)?;
+                    }
+                    if !categories.is_empty() {
+                        diesel::insert_or_ignore_into(activity_categories::table)
+                            .values(&categories)
+                            .execute(conn)?;
+                    }
+                    Ok(payments)
+                },
+            )
+            .await
+    }
+
+    async fn delete_bill_payment(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(bill_payments::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,417 @@
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::bills_model::{
+    Bill, BillFrequency, BillMatchResult, BillPayment, BillPaymentSource, BillReminder,
+    BillReminderStatus, NewBill, NewBillPayment, BILL_MATCH_WINDOW_DAYS,
+    BILL_OVERDUE_LOOKBACK_DAYS,
+};
+use super::bills_traits::{BillRepositoryTrait, BillServiceTrait};
+use crate::activities::{
+    ActivityRepositoryTrait, ACTIVITY_TYPE_TRANSFER_OUT, ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::budgets::{ActivityCategory, CategorySource};
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::{convert_or_keep, FxServiceTrait};
+use crate::utils::lunar_calendar::solar_to_lunar;
+
+/// A withdrawal that may pay a bill, with its amount in the bill's currency
+#[derive(Debug, Clone)]
+pub(crate) struct PaidActivity {
+    pub activity_id: String,
+    pub account_id: String,
+    pub date: NaiveDate,
+    pub amount: Decimal,
+    pub comment: Option<String>,
+}
+
+pub struct BillService {
+    repository: Arc<dyn BillRepositoryTrait>,
+    activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+}
+
+impl BillService {
+    pub fn new(
+        repository: Arc<dyn BillRepositoryTrait>,
+        activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            activity_repository,
+            fx_service,
+        }
+    }
+
+    fn get_bill(&self, id: &str) -> Result<Bill> {
+        self.repository
+            .get_bills()?
+            .into_iter()
+            .find(|b| b.id == id)
+            .ok_or_else(|| {
+                Error::Validation(ValidationError::InvalidInput(format!(
+                    "Bill {} not found",
+                    id
+                )))
+            })
+    }
+}
+
+fn category_for(bill: &Bill, payment: &BillPayment) -> Option<ActivityCategory> {
+    Some(ActivityCategory {
+        activity_id: payment.activity_id.clone()?,
+        category_id: bill.category_id.clone()?,
+        source: CategorySource::Rule.as_str().to_string(),
+    })
+}
+
+/// Pays each unpaid due date with the closest unused activity in its match window whose
+/// comment mentions the bill. Activities used here are added to `used`.
+pub(crate) fn match_bill(
+    bill: &Bill,
+    due_dates: &[NaiveDate],
+    activities: &[PaidActivity],
+    used: &mut HashSet<String>,
+) -> Vec<BillPayment> {
+    let window = BILL_MATCH_WINDOW_DAYS as i64;
+    let text = bill.match_text();
+    let mut payments = Vec::new();
+    for due in due_dates {
+        let matched = activities
+            .iter()
+            .filter(|a| !used.contains(&a.activity_id))
+            .filter(|a| {
+                bill.account_id
+                    .as_ref()
+                    .is_none_or(|account| *account == a.account_id)
+            })
+            .filter(|a| (a.date - *due).num_days().abs() <= window)
+            .filter(|a| {
+                a.comment
+                    .as_ref()
+                    .is_some_and(|c| c.to_lowercase().contains(&text))
+            })
+            .min_by_key(|a| {
+                (
+                    (a.date - *due).num_days().abs(),
+                    (a.amount - bill.amount).abs(),
+                )
+            });
+        if let Some(activity) = matched {
+            used.insert(activity.activity_id.clone());
+            payments.push(BillPayment {
+                id: Uuid::new_v4().to_string(),
+                bill_id: bill.id.clone(),
+                due_date: *due,
+                activity_id: Some(activity.activity_id.clone()),
+                amount: activity.amount,
+                paid_date: activity.date,
+                source: BillPaymentSource::Matched,
+                created_at: Utc::now().naive_utc(),
+            });
+        }
+    }
+    payments
+}
+
+#[async_trait]
+impl BillServiceTrait for BillService {
+    fn get_bills(&self) -> Result<Vec<Bill>> {
+        self.repository.get_bills()
+    }
+
+    async fn create_bill(&self, bill: NewBill) -> Result<Bill> {
+        bill.validate()?;
+        self.repository.insert_bill(bill).await
+    }
+
+    async fn update_bill(&self, id: &str, bill: NewBill) -> Result<Bill> {
+        bill.validate()?;
+        self.repository.update_bill(id, bill).await
+    }
+
+    async fn delete_bill(&self, id: &str) -> Result<usize> {
+        self.repository.delete_bill(id).await
+    }
+
+    fn get_bill_payments(&self, bill_id: Option<String>) -> Result<Vec<BillPayment>> {
+        self.repository.get_bill_payments(bill_id.as_deref())
+    }
+
+    async fn mark_bill_paid(&self, payment: NewBillPayment) -> Result<BillPayment> {
+        let bill = self.get_bill(&payment.bill_id)?;
+        if !bill
+            .due_dates(payment.due_date, payment.due_date)
+            .contains(&payment.due_date)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} is not a due date of bill {}",
+                payment.due_date, bill.name
+            ))));
+        }
+        let amount = payment.amount.unwrap_or(bill.amount);
+        if amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Payment amount must be positive".to_string(),
+            )));
+        }
+
+        let record = BillPayment {
+            id: Uuid::new_v4().to_string(),
+            bill_id: bill.id.clone(),
+            due_date: payment.due_date,
+            activity_id: payment.activity_id,
+            amount,
+            paid_date: payment.paid_date.unwrap_or(payment.due_date),
+            source: BillPaymentSource::Manual,
+            created_at: Utc::now().naive_utc(),
+        };
+        let categories = category_for(&bill, &record).into_iter().collect();
+        self.repository
+            .save_bill_payments(vec![record], categories)
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                Error::Validation(ValidationError::InvalidInput(
+                    "Bill payment was not saved".to_string(),
+                ))
+            })
+    }
+
+    async fn delete_bill_payment(&self, id: &str) -> Result<usize> {
+        self.repository.delete_bill_payment(id).await
+    }
+
+    fn get_bill_reminders(&self) -> Result<Vec<BillReminder>> {
+        let today = Utc::now().date_naive();
+        let from = today - Days::new(BILL_OVERDUE_LOOKBACK_DAYS);
+        let paid: HashSet<(String, NaiveDate)> = self
+            .repository
+            .get_bill_payments(None)?
+            .into_iter()
+            .map(|p| (p.bill_id, p.due_date))
+            .collect();
+
+        let mut reminders: Vec<BillReminder> = self
+            .repository
+            .get_bills()?
+            .into_iter()
+            .filter(|b| b.is_active)
+            .flat_map(|bill| {
+                let until = today + Days::new(bill.reminder_days.max(0) as u64);
+                bill.due_dates(from, until)
+                    .into_iter()
+                    .filter(|due| !paid.contains(&(bill.id.clone(), *due)))
+                    .map(|due| {
+                        let days_until_due = (due - today).num_days();
+                        BillReminder {
+                            bill_id: bill.id.clone(),
+                            bill_name: bill.name.clone(),
+                            payee: bill.payee.clone(),
+                            due_date: due,
+                            amount: bill.amount,
+                            currency: bill.currency.clone(),
+                            account_id: bill.account_id.clone(),
+                            lunar_due_date: (bill.frequency == BillFrequency::LunarYearly)
+                                .then(|| solar_to_lunar(due)),
+                            days_until_due,
+                            status: match days_until_due {
+                                d if d < 0 => BillReminderStatus::Overdue,
+                                0 => BillReminderStatus::DueToday,
+                                _ => BillReminderStatus::Upcoming,
+                            },
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        reminders.sort_by_key(|r| r.due_date);
+        Ok(reminders)
+    }
+
+    async fn match_bill_payments(&self) -> Result<BillMatchResult> {
+        let bills: Vec<Bill> = self
+            .repository
+            .get_bills()?
+            .into_iter()
+            .filter(|b| b.is_active)
+            .collect();
+        if bills.is_empty() {
+            return Ok(BillMatchResult::default());
+        }
+
+        let today = Utc::now().date_naive();
+        let window = Days::new(BILL_MATCH_WINDOW_DAYS);
+        let from = today - Days::new(BILL_OVERDUE_LOOKBACK_DAYS);
+        let existing = self.repository.get_bill_payments(None)?;
+        let paid: HashSet<(&str, NaiveDate)> = existing
+            .iter()
+            .map(|p| (p.bill_id.as_str(), p.due_date))
+            .collect();
+        let mut used: HashSet<String> = existing
+            .iter()
+            .filter_map(|p| p.activity_id.clone())
+            .collect();
+
+        let withdrawals: Vec<_> = self
+            .activity_repository
+            .get_activities()?
+            .into_iter()
+            .filter(|a| {
+                !a.is_draft
+                    && (a.activity_type == ACTIVITY_TYPE_WITHDRAWAL
+                        || a.activity_type == ACTIVITY_TYPE_TRANSFER_OUT)
+            })
+            .filter(|a| a.activity_date.date_naive() >= from - window)
+            .collect();
+
+        let mut payments = Vec::new();
+        let mut categories = Vec::new();
+        for bill in &bills {
+            let due_dates: Vec<NaiveDate> = bill
+                .due_dates(from, today + window)
+                .into_iter()
+                .filter(|due| !paid.contains(&(bill.id.as_str(), *due)))
+                .collect();
+            if due_dates.is_empty() {
+                continue;
+            }
+            let activities: Vec<PaidActivity> = withdrawals
+                .iter()
+                .map(|a| {
+                    let date = a.activity_date.date_naive();
+                    let amount = a
+                        .amount
+                        .filter(|amount| !amount.is_zero())
+                        .unwrap_or(a.quantity * a.unit_price);
+                    PaidActivity {
+                        activity_id: a.id.clone(),
+                        account_id: a.account_id.clone(),
+                        date,
+                        amount: convert_or_keep(
+                            self.fx_service.as_ref(),
+                            amount,
+                            &a.currency,
+                            &bill.currency,
+                            Some(date),
+                        ),
+                        comment: a.comment.clone(),
+                    }
+                })
+                .collect();
+            for payment in match_bill(bill, &due_dates, &activities, &mut used) {
+                categories.extend(category_for(bill, &payment));
+                payments.push(payment);
+            }
+        }
+        if payments.is_empty() {
+            return Ok(BillMatchResult::default());
+        }
+
+        let payments = self
+            .repository
+            .save_bill_payments(payments, categories)
+            .await?;
+        Ok(BillMatchResult {
+            matched: payments.len(),
+            payments,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn bill(match_pattern: Option<&str>) -> Bill {
+        let now = Utc::now().naive_utc();
+        Bill {
+            id: "rent".to_string(),
+            name: "Rent".to_string(),
+            payee: "Chu nha".to_string(),
+            amount: dec!(8_000_000),
+            currency: "VND".to_string(),
+            account_id: Some("bank".to_string()),
+            category_id: Some("housing".to_string()),
+            frequency: BillFrequency::Monthly,
+            due_day: 31,
+            lunar_month: None,
+            start_date: date("2026-01-01"),
+            end_date: None,
+            reminder_days: 3,
+            match_pattern: match_pattern.map(str::to_string),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn activity(id: &str, day: &str, account: &str, comment: &str) -> PaidActivity {
+        PaidActivity {
+            activity_id: id.to_string(),
+            account_id: account.to_string(),
+            date: date(day),
+            amount: dec!(8_000_000),
+            comment: Some(comment.to_string()),
+        }
+    }
+
+    #[test]
+    fn due_dates_clamp_to_month_end() {
+        assert_eq!(
+            bill(None).due_dates(date("2026-01-15"), date("2026-04-15")),
+            vec![date("2026-01-31"), date("2026-02-28"), date("2026-03-31")]
+        );
+    }
+
+    #[test]
+    fn lunar_bills_follow_the_lunar_day_each_year() {
+        // Ông Công ông Táo offerings on the 23rd of the 12th lunar month
+        let offerings = Bill {
+            frequency: BillFrequency::LunarYearly,
+            due_day: 23,
+            lunar_month: Some(12),
+            ..bill(None)
+        };
+        assert_eq!(
+            offerings.due_dates(date("2026-01-01"), date("2027-12-31")),
+            vec![date("2026-02-10"), date("2027-01-30")]
+        );
+    }
+
+    #[test]
+    fn payments_match_on_text_window_and_account() {
+        let rent = bill(Some("TIEN NHA"));
+        let due_dates = rent.due_dates(date("2026-01-01"), date("2026-03-31"));
+        let activities = vec![
+            activity("jan", "2026-01-29", "bank", "CK tien nha T1"),
+            // Mentions the payee but not the pattern
+            activity("feb-payee", "2026-02-27", "bank", "Chu nha"),
+            // Right text, wrong account
+            activity("mar-card", "2026-03-30", "card", "tien nha T3"),
+            activity("mar", "2026-04-05", "bank", "TIEN NHA T3"),
+        ];
+
+        let mut used = HashSet::from(["jan".to_string()]);
+        let payments = match_bill(&rent, &due_dates, &activities, &mut used);
+        let matched: Vec<(NaiveDate, Option<&str>)> = payments
+            .iter()
+            .map(|p| (p.due_date, p.activity_id.as_deref()))
+            .collect();
+        assert_eq!(matched, vec![(date("2026-03-31"), Some("mar"))]);
+        assert_eq!(
+            category_for(&rent, &payments[0]).map(|c| c.category_id),
+            Some("housing".to_string())
+        );
+    }
+}
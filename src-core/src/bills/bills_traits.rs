@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+
+use super::bills_model::{
+    Bill, BillMatchResult, BillPayment, BillReminder, NewBill, NewBillPayment,
+};
+use crate::budgets::ActivityCategory;
+use crate::errors::Result;
+
+#[async_trait]
+pub trait BillRepositoryTrait: Send + Sync {
+    fn get_bills(&self) -> Result<Vec<Bill>>;
+    async fn insert_bill(&self, bill: NewBill) -> Result<Bill>;
+    async fn update_bill(&self, id: &str, bill: NewBill) -> Result<Bill>;
+    async fn delete_bill(&self, id: &str) -> Result<usize>;
+    fn get_bill_payments(&self, bill_id: Option<&str>) -> Result<Vec<BillPayment>>;
+    /// Saves payments, replacing any already recorded for the same due date, and gives the
+    /// paying activities their bill's category unless they already have one
+    async fn save_bill_payments(
+        &self,
+        payments: Vec<BillPayment>,
+        categories: Vec<ActivityCategory>,
+    ) -> Result<Vec<BillPayment>>;
+    async fn delete_bill_payment(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait BillServiceTrait: Send + Sync {
+    fn get_bills(&self) -> Result<Vec<Bill>>;
+    async fn create_bill(&self, bill: NewBill) -> Result<Bill>;
+    async fn update_bill(&self, id: &str, bill: NewBill) -> Result<Bill>;
+    async fn delete_bill(&self, id: &str) -> Result<usize>;
+    fn get_bill_payments(&self, bill_id: Option<String>) -> Result<Vec<BillPayment>>;
+    async fn mark_bill_paid(&self, payment: NewBillPayment) -> Result<BillPayment>;
+    async fn delete_bill_payment(&self, id: &str) -> Result<usize>;
+    /// Unpaid due dates inside each bill's reminder window, plus recent overdue ones
+    fn get_bill_reminders(&self) -> Result<Vec<BillReminder>>;
+    /// Marks unpaid bills paid from matching withdrawals, e.g. after an import
+    async fn match_bill_payments(&self) -> Result<BillMatchResult>;
+}
@@ -0,0 +1,13 @@
+mod bills_model;
+mod bills_repository;
+mod bills_service;
+mod bills_traits;
+
+pub use bills_model::{
+    Bill, BillFrequency, BillMatchResult, BillPayment, BillPaymentSource, BillReminder,
+    BillReminderStatus, NewBill, NewBillPayment, BILL_MATCH_WINDOW_DAYS,
+    BILL_OVERDUE_LOOKBACK_DAYS, DEFAULT_BILL_REMINDER_DAYS, MAX_BILL_REMINDER_DAYS,
+};
+pub use bills_repository::BillRepository;
+pub use bills_service::BillService;
+pub use bills_traits::{BillRepositoryTrait, BillServiceTrait};
@@ -0,0 +1,438 @@
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+
+/// Symbol of the manual asset a bond holding is held as
+pub fn bond_asset_symbol(bond_id: &str) -> String {
+    format!("BOND-{}", bond_id)
+}
+
+/// Day count of the year that bonds paying at maturity accrue interest over
+const DAYS_IN_YEAR: i64 = 365;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BondIssuerType {
+    /// Treasury bonds (trái phiếu Chính phủ) and other state-backed issues
+    Government,
+    /// Bonds issued by companies and banks (trái phiếu doanh nghiệp)
+    Corporate,
+}
+
+impl BondIssuerType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BondIssuerType::Government => "GOVERNMENT",
+            BondIssuerType::Corporate => "CORPORATE",
+        }
+    }
+
+    /// Asset sub-class the holding is reported under
+    pub fn asset_sub_class(&self) -> &'static str {
+        match self {
+            BondIssuerType::Government => "Government Bond",
+            BondIssuerType::Corporate => "Corporate Bond",
+        }
+    }
+}
+
+impl FromStr for BondIssuerType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "GOVERNMENT" => Ok(BondIssuerType::Government),
+            "CORPORATE" => Ok(BondIssuerType::Corporate),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown bond issuer type: {}",
+                other
+            )))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CouponFrequency {
+    Annual,
+    SemiAnnual,
+    Quarterly,
+    Monthly,
+    /// All interest is paid with the principal, as with many short corporate issues
+    AtMaturity,
+}
+
+impl CouponFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CouponFrequency::Annual => "ANNUAL",
+            CouponFrequency::SemiAnnual => "SEMI_ANNUAL",
+            CouponFrequency::Quarterly => "QUARTERLY",
+            CouponFrequency::Monthly => "MONTHLY",
+            CouponFrequency::AtMaturity => "AT_MATURITY",
+        }
+    }
+
+    /// Months between coupons; `None` when interest is only paid at maturity
+    pub fn step_months(&self) -> Option<u32> {
+        match self {
+            CouponFrequency::Annual => Some(12),
+            CouponFrequency::SemiAnnual => Some(6),
+            CouponFrequency::Quarterly => Some(3),
+            CouponFrequency::Monthly => Some(1),
+            CouponFrequency::AtMaturity => None,
+        }
+    }
+}
+
+impl FromStr for CouponFrequency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ANNUAL" => Ok(CouponFrequency::Annual),
+            "SEMI_ANNUAL" => Ok(CouponFrequency::SemiAnnual),
+            "QUARTERLY" => Ok(CouponFrequency::Quarterly),
+            "MONTHLY" => Ok(CouponFrequency::Monthly),
+            "AT_MATURITY" => Ok(CouponFrequency::AtMaturity),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown coupon frequency: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `bonds`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::bonds)]
+pub struct BondDB {
+    pub id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub activity_id: Option<String>,
+    pub code: String,
+    pub issuer: String,
+    pub issuer_type: String,
+    pub face_value: String,
+    pub quantity: String,
+    pub coupon_rate: String,
+    pub coupon_frequency: String,
+    pub issue_date: String,
+    pub maturity_date: String,
+    pub purchase_date: String,
+    pub purchase_price: String,
+    pub market_price: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A holding of one bond issue. Amounts are per bond and in the account currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Bond {
+    pub id: String,
+    pub account_id: String,
+    /// Manual asset whose quotes value the bonds
+    pub asset_id: String,
+    /// Activity that added the bonds to the account
+    pub activity_id: Option<String>,
+    /// Listing or issue code
+    pub code: String,
+    pub issuer: String,
+    pub issuer_type: BondIssuerType,
+    pub face_value: Decimal,
+    /// Number of bonds held
+    pub quantity: Decimal,
+    /// Annual coupon rate in percent of the face value
+    pub coupon_rate: Decimal,
+    pub coupon_frequency: CouponFrequency,
+    pub issue_date: NaiveDate,
+    pub maturity_date: NaiveDate,
+    pub purchase_date: NaiveDate,
+    pub purchase_price: Decimal,
+    /// Latest clean price quoted by the broker or exchange; the face value when unset
+    pub market_price: Option<Decimal>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Bond {
+    /// Price per bond without accrued interest
+    pub fn clean_price(&self) -> Decimal {
+        self.market_price.unwrap_or(self.face_value)
+    }
+
+    /// Interest a full year of coupons pays on one bond
+    fn annual_coupon(&self) -> Decimal {
+        self.face_value * self.coupon_rate / Decimal::ONE_HUNDRED
+    }
+
+    /// Interest the holding pays over a year
+    pub fn annual_coupon_income(&self) -> Decimal {
+        self.annual_coupon() * self.quantity
+    }
+
+    /// Coupon schedule counted back from maturity, so periodic coupons fall on the maturity
+    /// day of the month, starting with the last schedule date on or before issue. An issue
+    /// between two schedule dates makes the first period shorter.
+    fn schedule(&self) -> Vec<NaiveDate> {
+        let Some(step) = self.coupon_frequency.step_months() else {
+            return vec![self.issue_date, self.maturity_date];
+        };
+        let mut dates = Vec::new();
+        for n in 0.. {
+            let Some(date) = self.maturity_date.checked_sub_months(Months::new(step * n)) else {
+                break;
+            };
+            dates.push(date);
+            if date <= self.issue_date {
+                break;
+            }
+        }
+        dates.reverse();
+        dates
+    }
+
+    /// Coupon dates from the first after issue to maturity
+    pub fn coupon_dates(&self) -> Vec<NaiveDate> {
+        self.schedule().into_iter().skip(1).collect()
+    }
+
+    /// Where the coupon period ending on `coupon_date` starts: the regular schedule date and
+    /// the day interest starts accruing, later for an odd first period
+    fn period_start(&self, coupon_date: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let regular = self
+            .schedule()
+            .into_iter()
+            .take_while(|date| *date < coupon_date)
+            .last()
+            .unwrap_or(self.issue_date);
+        (regular, regular.max(self.issue_date))
+    }
+
+    /// Interest one bond has earned up to `date` in the period ending on
+    /// `coupon_date`, actual/actual within the period or actual/365 when paid at maturity
+    fn interest_between(&self, coupon_date: NaiveDate, date: NaiveDate) -> Decimal {
+        let (regular, start) = self.period_start(coupon_date);
+        let days = Decimal::from((date - start).num_days().max(0));
+        match self.coupon_frequency.step_months() {
+            None => self.annual_coupon() * days / Decimal::from(DAYS_IN_YEAR),
+            Some(step) => {
+                let period_days = (coupon_date - regular).num_days();
+                if period_days <= 0 {
+                    return Decimal::ZERO;
+                }
+                self.annual_coupon() * Decimal::from(step) / Decimal::from(12) * days
+                    / Decimal::from(period_days)
+            }
+        }
+    }
+
+    /// Coupon one bond receives on `coupon_date`, less than a full one after an odd first
+    /// period
+    pub fn coupon_amount(&self, coupon_date: NaiveDate) -> Decimal {
+        self.interest_between(coupon_date, coupon_date).round_dp(2)
+    }
+
+    /// First coupon after `date`, if the bond has not matured
+    pub fn next_coupon_date(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.coupon_dates()
+            .into_iter()
+            .find(|coupon| *coupon > date)
+    }
+
+    /// Interest one bond has accrued since its last coupon (or issue), which a buyer pays
+    /// the seller on top of the clean price
+    pub fn accrued_interest(&self, date: NaiveDate) -> Decimal {
+        if date < self.issue_date {
+            return Decimal::ZERO;
+        }
+        let Some(next) = self.next_coupon_date(date) else {
+            return Decimal::ZERO;
+        };
+        self.interest_between(next, date).round_dp(2)
+    }
+
+    /// Clean price plus accrued interest, what one bond is worth on `date`
+    pub fn dirty_price(&self, date: NaiveDate) -> Decimal {
+        if date >= self.maturity_date {
+            return self.face_value;
+        }
+        self.clean_price() + self.accrued_interest(date)
+    }
+
+    /// Coupons and redemption the holding receives after `from` up to and including `to`
+    pub fn payments_between(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        currency: &str,
+    ) -> Vec<BondPayment> {
+        self.coupon_dates()
+            .into_iter()
+            .filter(|date| *date > from && *date <= to && *date > self.purchase_date)
+            .map(|date| BondPayment {
+                bond_id: self.id.clone(),
+                code: self.code.clone(),
+                issuer: self.issuer.clone(),
+                payment_date: date,
+                coupon: self.coupon_amount(date) * self.quantity,
+                principal: if date == self.maturity_date {
+                    self.face_value * self.quantity
+                } else {
+                    Decimal::ZERO
+                },
+                currency: currency.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<BondDB> for Bond {
+    type Error = Error;
+
+    fn try_from(db: BondDB) -> Result<Self> {
+        Ok(Bond {
+            id: db.id,
+            account_id: db.account_id,
+            asset_id: db.asset_id,
+            activity_id: db.activity_id,
+            code: db.code,
+            issuer: db.issuer,
+            issuer_type: BondIssuerType::from_str(&db.issuer_type)?,
+            face_value: Decimal::from_str(&db.face_value)?,
+            quantity: Decimal::from_str(&db.quantity)?,
+            coupon_rate: Decimal::from_str(&db.coupon_rate)?,
+            coupon_frequency: CouponFrequency::from_str(&db.coupon_frequency)?,
+            issue_date: parse_forecast_date(&db.issue_date)?,
+            maturity_date: parse_forecast_date(&db.maturity_date)?,
+            purchase_date: parse_forecast_date(&db.purchase_date)?,
+            purchase_price: Decimal::from_str(&db.purchase_price)?,
+            market_price: db
+                .market_price
+                .as_deref()
+                .map(Decimal::from_str)
+                .transpose()?,
+            notes: db.notes,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a bond holding
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBond {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub code: String,
+    pub issuer: String,
+    pub issuer_type: BondIssuerType,
+    pub face_value: Decimal,
+    pub quantity: Decimal,
+    pub coupon_rate: Decimal,
+    pub coupon_frequency: CouponFrequency,
+    pub issue_date: NaiveDate,
+    pub maturity_date: NaiveDate,
+    pub purchase_date: NaiveDate,
+    pub purchase_price: Decimal,
+    pub market_price: Option<Decimal>,
+    pub notes: Option<String>,
+}
+
+impl NewBond {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("accountId", &self.account_id),
+            ("code", &self.code),
+            ("issuer", &self.issuer),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        if self.face_value <= Decimal::ZERO
+            || self.quantity <= Decimal::ZERO
+            || self.purchase_price <= Decimal::ZERO
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Face value, quantity and purchase price must be positive".to_string(),
+            )));
+        }
+        if self
+            .market_price
+            .is_some_and(|price| price <= Decimal::ZERO)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Market price must be positive".to_string(),
+            )));
+        }
+        if self.coupon_rate < Decimal::ZERO || self.coupon_rate >= Decimal::ONE_HUNDRED {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Coupon rate must be a percentage between 0 and 100".to_string(),
+            )));
+        }
+        if self.maturity_date <= self.issue_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Maturity date must be after the issue date".to_string(),
+            )));
+        }
+        if self.purchase_date < self.issue_date || self.purchase_date >= self.maturity_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Purchase date must be between the issue and maturity dates".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A coupon or redemption expected from a bond holding
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BondPayment {
+    pub bond_id: String,
+    pub code: String,
+    pub issuer: String,
+    pub payment_date: NaiveDate,
+    /// Interest paid on the whole holding
+    pub coupon: Decimal,
+    /// Face value repaid, on the maturity date only
+    pub principal: Decimal,
+    pub currency: String,
+}
+
+/// A bond holding valued as of a date
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BondSummary {
+    pub bond: Bond,
+    pub currency: String,
+    pub valued_on: NaiveDate,
+    /// Clean price times quantity
+    pub clean_value: Decimal,
+    /// Interest accrued on the holding since the last coupon
+    pub accrued_interest: Decimal,
+    /// Clean value plus accrued interest
+    pub market_value: Decimal,
+    /// Purchase price times quantity
+    pub cost: Decimal,
+    /// Market value less cost
+    pub unrealized_gain: Decimal,
+    pub annual_coupon_income: Decimal,
+    /// Annual coupon income as a percentage of the clean value
+    pub current_yield: Decimal,
+    pub next_payment: Option<BondPayment>,
+    pub days_to_maturity: i64,
+    pub is_matured: bool,
+}
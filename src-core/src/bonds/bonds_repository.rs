@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::bonds_model::{Bond, BondDB, NewBond};
+use super::bonds_traits::BondRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::bonds;
+use crate::utils::text_utils::trimmed;
+
+pub struct BondRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl BondRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        BondRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl BondRepositoryTrait for BondRepository {
+    fn get_bonds(&self) -> Result<Vec<Bond>> {
+        let mut conn = get_connection(&self.pool)?;
+        bonds::table
+            .order((bonds::maturity_date.asc(), bonds::code.asc()))
+            .load::<BondDB>(&mut conn)?
+            .into_iter()
+            .map(Bond::try_from)
+            .collect()
+    }
+
+    fn get_bond(&self, id: &str) -> Result<Bond> {
+        let mut conn = get_connection(&self.pool)?;
+        bonds::table.find(id).first::<BondDB>(&mut conn)?.try_into()
+    }
+
+    async fn insert_bond(
+        &self,
+        bond: NewBond,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<Bond> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Bond> {
+                let now = Utc::now().naive_utc();
+                let record = BondDB {
+                    id: bond.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: bond.account_id,
+                    asset_id,
+                    activity_id,
+                    code: bond.code.trim().to_uppercase(),
+                    issuer: bond.issuer.trim().to_string(),
+                    issuer_type: bond.issuer_type.as_str().to_string(),
+                    face_value: bond.face_value.to_string(),
+                    quantity: bond.quantity.to_string(),
+                    coupon_rate: bond.coupon_rate.to_string(),
+                    coupon_frequency: bond.coupon_frequency.as_str().to_string(),
+                    issue_date: bond.issue_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    maturity_date: bond.maturity_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    purchase_date: bond.purchase_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    purchase_price: bond.purchase_price.to_string(),
+                    market_price: bond.market_price.map(|price| price.to_string()),
+                    notes: trimmed(bond.notes),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(bonds::table)
+                    .values(&record)
+                    .get_result::<BondDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_bond(&self, id: &str, bond: NewBond) -> Result<Bond> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Bond> {
+                diesel::update(bonds::table.find(id_owned))
+                    .set((
+                        bonds::code.eq(bond.code.trim().to_uppercase()),
+                        bonds::issuer.eq(bond.issuer.trim().to_string()),
+                        bonds::issuer_type.eq(bond.issuer_type.as_str()),
+                        bonds::face_value.eq(bond.face_value.to_string()),
+                        bonds::quantity.eq(bond.quantity.to_string()),
+                        bonds::coupon_rate.eq(bond.coupon_rate.to_string()),
+                        bonds::coupon_frequency.eq(bond.coupon_frequency.as_str()),
+                        bonds::issue_date
+                            .eq(bond.issue_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        bonds::maturity_date
+                            .eq(bond.maturity_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        bonds::purchase_date
+                            .eq(bond.purchase_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        bonds::purchase_price.eq(bond.purchase_price.to_string()),
+                        bonds::market_price.eq(bond.market_price.map(|price| price.to_string())),
+                        bonds::notes.eq(trimmed(bond.notes)),
+                        bonds::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<BondDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_bond(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(bonds::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
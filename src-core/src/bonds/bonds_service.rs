@@ -0,0 +1,421 @@
+use async_trait::async_trait;
+use chrono::{Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+use super::bonds_model::{bond_asset_symbol, Bond, BondPayment, BondSummary, NewBond};
+use super::bonds_traits::{BondRepositoryTrait, BondServiceTrait};
+use crate::accounts::{
+    Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY, ACCOUNT_TYPE_REAL_ESTATE,
+};
+use crate::activities::{
+    ActivityServiceTrait, ActivityUpdate, NewActivity, ACTIVITY_TYPE_ADD_HOLDING,
+};
+use crate::assets::{AssetServiceTrait, UpdateAssetProfile};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::market_data::{DataSource, MarketDataServiceTrait, Quote};
+
+pub struct BondService {
+    repository: Arc<dyn BondRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+}
+
+/// Quote id of a bond's price on a date, one per day like imported quotes
+fn quote_id(symbol: &str, date: NaiveDate) -> String {
+    format!("{}_{}", symbol, date.format(FORECAST_DATE_FORMAT))
+}
+
+fn bond_quote(symbol: &str, date: NaiveDate, price: Decimal, currency: &str) -> Quote {
+    let now = Utc::now();
+    Quote {
+        id: quote_id(symbol, date),
+        symbol: symbol.to_string(),
+        timestamp: date.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc(),
+        open: price,
+        high: price,
+        low: price,
+        close: price,
+        adjclose: price,
+        volume: Decimal::ZERO,
+        currency: currency.to_string(),
+        data_source: DataSource::Manual,
+        created_at: now,
+    }
+}
+
+/// Values a bond holding on `date` at its clean price plus accrued interest
+pub(crate) fn summarize(bond: Bond, currency: String, date: NaiveDate) -> BondSummary {
+    let is_matured = date >= bond.maturity_date;
+    let (clean_value, accrued_interest) = if is_matured {
+        (bond.face_value * bond.quantity, Decimal::ZERO)
+    } else {
+        (
+            bond.clean_price() * bond.quantity,
+            bond.accrued_interest(date) * bond.quantity,
+        )
+    };
+    let market_value = clean_value + accrued_interest;
+    let cost = bond.purchase_price * bond.quantity;
+    let annual_coupon_income = bond.annual_coupon_income();
+    let current_yield = if clean_value.is_zero() {
+        Decimal::ZERO
+    } else {
+        (annual_coupon_income / clean_value * Decimal::ONE_HUNDRED).round_dp(2)
+    };
+    let next_payment = bond
+        .payments_between(date, bond.maturity_date, &currency)
+        .into_iter()
+        .next();
+
+    BondSummary {
+        valued_on: date,
+        clean_value,
+        accrued_interest,
+        market_value,
+        cost,
+        unrealized_gain: market_value - cost,
+        annual_coupon_income,
+        current_yield,
+        next_payment,
+        days_to_maturity: (bond.maturity_date - date).num_days().max(0),
+        is_matured,
+        currency,
+        bond,
+    }
+}
+
+impl BondService {
+    pub fn new(
+        repository: Arc<dyn BondRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            asset_service,
+            activity_service,
+            market_data_service,
+        }
+    }
+
+    fn holding_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type == ACCOUNT_TYPE_LIABILITY
+            || account.account_type == ACCOUNT_TYPE_REAL_ESTATE
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Bonds cannot be held in {}",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+
+    /// Names the bond's asset after its code and issuer and files it under fixed income
+    async fn update_asset_profile(&self, bond: &Bond) -> Result<()> {
+        self.asset_service
+            .update_asset_profile(
+                &bond.asset_id,
+                UpdateAssetProfile {
+                    symbol: bond.asset_id.clone(),
+                    name: Some(format!("{} - {}", bond.code, bond.issuer)),
+                    sectors: None,
+                    countries: None,
+                    notes: bond.notes.clone().unwrap_or_default(),
+                    asset_sub_class: Some(bond.issuer_type.asset_sub_class().to_string()),
+                    asset_class: Some("Fixed Income".to_string()),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Quotes the bond at its purchase price on the purchase date and at its value today
+    async fn quote_bond(&self, bond: &Bond, currency: &str) -> Result<()> {
+        self.market_data_service
+            .add_quote(&bond_quote(
+                &bond.asset_id,
+                bond.purchase_date,
+                bond.purchase_price,
+                currency,
+            ))
+            .await?;
+        let today = Utc::now().date_naive();
+        if today > bond.purchase_date {
+            self.market_data_service
+                .add_quote(&bond_quote(
+                    &bond.asset_id,
+                    today,
+                    bond.dirty_price(today),
+                    currency,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn delete_quotes(&self, asset_id: &str) -> Result<()> {
+        for quote in self
+            .market_data_service
+            .get_historical_quotes_for_symbol(asset_id)?
+        {
+            self.market_data_service.delete_quote(&quote.id).await?;
+        }
+        Ok(())
+    }
+
+    fn summary(&self, bond: Bond) -> Result<BondSummary> {
+        let account = self.account_repository.get_by_id(&bond.account_id)?;
+        Ok(summarize(bond, account.currency, Utc::now().date_naive()))
+    }
+}
+
+#[async_trait]
+impl BondServiceTrait for BondService {
+    fn get_bonds(&self) -> Result<Vec<Bond>> {
+        self.repository.get_bonds()
+    }
+
+    fn get_bond_summaries(&self) -> Result<Vec<BondSummary>> {
+        self.repository
+            .get_bonds()?
+            .into_iter()
+            .map(|bond| self.summary(bond))
+            .collect()
+    }
+
+    fn get_bond_summary(&self, id: &str) -> Result<BondSummary> {
+        self.summary(self.repository.get_bond(id)?)
+    }
+
+    async fn create_bond(&self, mut bond: NewBond) -> Result<Bond> {
+        bond.validate()?;
+        let account = self.holding_account(&bond.account_id)?;
+
+        let id = bond
+            .id
+            .get_or_insert_with(|| uuid::Uuid::new_v4().to_string())
+            .clone();
+        let asset = self
+            .asset_service
+            .create_manual_asset(&bond_asset_symbol(&id), account.currency.clone())
+            .await?;
+        let activity = self
+            .activity_service
+            .create_activity(NewActivity {
+                id: None,
+                account_id: account.id.clone(),
+                asset_id: asset.id.clone(),
+                activity_type: ACTIVITY_TYPE_ADD_HOLDING.to_string(),
+                activity_date: bond.purchase_date.format(FORECAST_DATE_FORMAT).to_string(),
+                quantity: Some(bond.quantity),
+                unit_price: Some(bond.purchase_price),
+                currency: account.currency.clone(),
+                fee: Some(Decimal::ZERO),
+                amount: None,
+                is_draft: false,
+                comment: Some(format!("{} - {}", bond.code.trim(), bond.issuer.trim())),
+            })
+            .await?;
+
+        let created = self
+            .repository
+            .insert_bond(bond, asset.id, Some(activity.id))
+            .await?;
+        self.update_asset_profile(&created).await?;
+        self.quote_bond(&created, &account.currency).await?;
+        Ok(created)
+    }
+
+    async fn update_bond(&self, id: &str, bond: NewBond) -> Result<Bond> {
+        bond.validate()?;
+        let existing = self.repository.get_bond(id)?;
+        if existing.account_id != bond.account_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A bond cannot be moved to another account".to_string(),
+            )));
+        }
+        let account = self.holding_account(&existing.account_id)?;
+
+        let updated = self.repository.update_bond(id, bond).await?;
+        let holding_changed = updated.purchase_date != existing.purchase_date
+            || updated.purchase_price != existing.purchase_price
+            || updated.quantity != existing.quantity;
+        if holding_changed {
+            if let Some(activity_id) = updated.activity_id.clone() {
+                self.activity_service
+                    .update_activity(ActivityUpdate {
+                        id: activity_id,
+                        account_id: account.id.clone(),
+                        asset_id: updated.asset_id.clone(),
+                        activity_type: ACTIVITY_TYPE_ADD_HOLDING.to_string(),
+                        activity_date: updated
+                            .purchase_date
+                            .format(FORECAST_DATE_FORMAT)
+                            .to_string(),
+                        quantity: Some(updated.quantity),
+                        unit_price: Some(updated.purchase_price),
+                        currency: account.currency.clone(),
+                        fee: Some(Decimal::ZERO),
+                        amount: None,
+                        is_draft: false,
+                        comment: Some(format!("{} - {}", updated.code, updated.issuer)),
+                    })
+                    .await?;
+            }
+        }
+        // Terms and prices feed every quote, so the bond is quoted afresh
+        self.delete_quotes(&updated.asset_id).await?;
+        self.quote_bond(&updated, &account.currency).await?;
+        self.update_asset_profile(&updated).await?;
+        Ok(updated)
+    }
+
+    async fn delete_bond(&self, id: &str) -> Result<usize> {
+        let bond = self.repository.get_bond(id)?;
+        let deleted = self.repository.delete_bond(id).await?;
+
+        if let Some(activity_id) = bond.activity_id.clone() {
+            self.activity_service.delete_activity(activity_id).await?;
+        }
+        self.delete_quotes(&bond.asset_id).await?;
+        match self.asset_service.delete_asset(&bond.asset_id).await {
+            // Activities recorded on the bond since, e.g. its sale, keep the asset around
+            Ok(()) | Err(Error::ConstraintViolation(_)) => Ok(deleted),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_bond_payments(&self, months: u32) -> Result<Vec<BondPayment>> {
+        let today = Utc::now().date_naive();
+        let until = today
+            .checked_add_months(Months::new(months))
+            .unwrap_or(NaiveDate::MAX);
+        let mut payments = Vec::new();
+        for bond in self.repository.get_bonds()? {
+            let account = self.account_repository.get_by_id(&bond.account_id)?;
+            payments.extend(bond.payments_between(today, until, &account.currency));
+        }
+        payments.sort_by(|a, b| {
+            a.payment_date
+                .cmp(&b.payment_date)
+                .then_with(|| a.code.cmp(&b.code))
+        });
+        Ok(payments)
+    }
+
+    async fn refresh_bond_quotes(&self, date: NaiveDate) -> Result<usize> {
+        let mut quoted = 0;
+        for bond in self.repository.get_bonds()? {
+            if date <= bond.purchase_date || date >= bond.maturity_date {
+                continue;
+            }
+            let account = self.account_repository.get_by_id(&bond.account_id)?;
+            self.market_data_service
+                .add_quote(&bond_quote(
+                    &bond.asset_id,
+                    date,
+                    bond.dirty_price(date),
+                    &account.currency,
+                ))
+                .await?;
+            quoted += 1;
+        }
+        Ok(quoted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bonds::{BondIssuerType, CouponFrequency};
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn bond(frequency: CouponFrequency, issue: &str, maturity: &str) -> Bond {
+        let now = Utc::now().naive_utc();
+        Bond {
+            id: "td".to_string(),
+            account_id: "broker".to_string(),
+            asset_id: bond_asset_symbol("td"),
+            activity_id: None,
+            code: "TD2535001".to_string(),
+            issuer: "Kho bạc Nhà nước".to_string(),
+            issuer_type: BondIssuerType::Government,
+            face_value: dec!(100_000),
+            quantity: dec!(100),
+            coupon_rate: dec!(3),
+            coupon_frequency: frequency,
+            issue_date: date(issue),
+            maturity_date: date(maturity),
+            purchase_date: date(issue),
+            purchase_price: dec!(99_000),
+            market_price: Some(dec!(98_000)),
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn summary_adds_interest_accrued_since_the_last_coupon() {
+        let bond = bond(CouponFrequency::SemiAnnual, "2025-03-15", "2035-03-15");
+        assert_eq!(bond.coupon_dates().len(), 20);
+
+        // 92 of the 184 days from the March coupon to the September one
+        let summary = summarize(bond, "VND".to_string(), date("2026-06-15"));
+        assert_eq!(summary.accrued_interest, dec!(75_000));
+        assert_eq!(summary.clean_value, dec!(9_800_000));
+        assert_eq!(summary.market_value, dec!(9_875_000));
+        assert_eq!(summary.unrealized_gain, dec!(-25_000));
+        assert_eq!(summary.current_yield, dec!(3.06));
+        let next = summary.next_payment.unwrap();
+        assert_eq!(next.payment_date, date("2026-09-15"));
+        assert_eq!(next.coupon, dec!(150_000));
+        assert_eq!(next.principal, Decimal::ZERO);
+    }
+
+    #[test]
+    fn odd_first_period_pays_a_short_coupon() {
+        let mut bond = bond(CouponFrequency::Quarterly, "2026-02-10", "2028-03-31");
+        bond.face_value = dec!(1_000_000);
+        bond.coupon_rate = dec!(8);
+        let dates = bond.coupon_dates();
+        assert_eq!(dates.first(), Some(&date("2026-03-31")));
+        assert!(dates.contains(&date("2027-03-31")));
+        // 49 of the 90 days from 2025-12-31, on a 20,000 quarterly coupon
+        assert_eq!(bond.coupon_amount(date("2026-03-31")), dec!(10_888.89));
+        // Month-end periods keep their full coupon
+        assert_eq!(bond.coupon_amount(date("2027-06-30")), dec!(20_000));
+        assert_eq!(bond.accrued_interest(date("2027-03-31")), Decimal::ZERO);
+    }
+
+    #[test]
+    fn bonds_paying_at_maturity_accrue_over_the_whole_term() {
+        let mut bond = bond(CouponFrequency::AtMaturity, "2026-01-01", "2027-01-01");
+        bond.issuer_type = BondIssuerType::Corporate;
+        bond.coupon_rate = dec!(10);
+        // 182 of 365 days of a 10,000 yearly coupon
+        assert_eq!(bond.accrued_interest(date("2026-07-02")), dec!(4_986.30));
+
+        let payments = bond.payments_between(date("2026-01-01"), date("2027-12-31"), "VND");
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].coupon, dec!(1_000_000));
+        assert_eq!(payments[0].principal, dec!(10_000_000));
+
+        let matured = summarize(bond, "VND".to_string(), date("2027-02-01"));
+        assert!(matured.is_matured);
+        assert_eq!(matured.market_value, dec!(10_000_000));
+        assert_eq!(matured.next_payment, None);
+    }
+}
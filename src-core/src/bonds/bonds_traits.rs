@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::bonds_model::{Bond, BondPayment, BondSummary, NewBond};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait BondRepositoryTrait: Send + Sync {
+    fn get_bonds(&self) -> Result<Vec<Bond>>;
+    fn get_bond(&self, id: &str) -> Result<Bond>;
+    /// Stores a bond under `bond.id`, which the caller has already assigned
+    async fn insert_bond(
+        &self,
+        bond: NewBond,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<Bond>;
+    async fn update_bond(&self, id: &str, bond: NewBond) -> Result<Bond>;
+    async fn delete_bond(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait BondServiceTrait: Send + Sync {
+    fn get_bonds(&self) -> Result<Vec<Bond>>;
+    fn get_bond_summaries(&self) -> Result<Vec<BondSummary>>;
+    fn get_bond_summary(&self, id: &str) -> Result<BondSummary>;
+    /// Sets up the holding on its account: a manual asset added as a holding at the purchase
+    /// price, quoted at its clean price plus accrued interest
+    async fn create_bond(&self, bond: NewBond) -> Result<Bond>;
+    async fn update_bond(&self, id: &str, bond: NewBond) -> Result<Bond>;
+    /// Removes the holding with its activity and quotes; the account stays
+    async fn delete_bond(&self, id: &str) -> Result<usize>;
+    /// Coupons and redemptions due over the next `months` months, in date order
+    fn get_bond_payments(&self, months: u32) -> Result<Vec<BondPayment>>;
+    /// Quotes every bond not yet matured at its value on `date`, as accrued interest grows
+    /// daily; returns how many were quoted
+    async fn refresh_bond_quotes(&self, date: NaiveDate) -> Result<usize>;
+}
@@ -0,0 +1,11 @@
+mod bonds_model;
+mod bonds_repository;
+mod bonds_service;
+mod bonds_traits;
+
+pub use bonds_model::{
+    bond_asset_symbol, Bond, BondIssuerType, BondPayment, BondSummary, CouponFrequency, NewBond,
+};
+pub use bonds_repository::BondRepository;
+pub use bonds_service::BondService;
+pub use bonds_traits::{BondRepositoryTrait, BondServiceTrait};
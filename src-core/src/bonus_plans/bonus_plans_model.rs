@@ -0,0 +1,402 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+
+/// Share of a bonus proposed for lì xì and gifts, in percent
+pub const DEFAULT_GIFT_PERCENT: Decimal = dec!(10);
+
+/// Share of a bonus proposed for the account's envelopes, in percent
+pub const DEFAULT_ENVELOPE_PERCENT: Decimal = dec!(20);
+
+/// Label of the gift share when none is given
+pub const GIFT_LINE_LABEL: &str = "Lì xì & quà Tết";
+
+/// What a share of a bonus goes to
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BonusLineKind {
+    Goal,
+    Envelope,
+    /// Lì xì and Tet gifts, spent rather than set aside
+    Gifts,
+}
+
+impl BonusLineKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BonusLineKind::Goal => "GOAL",
+            BonusLineKind::Envelope => "ENVELOPE",
+            BonusLineKind::Gifts => "GIFTS",
+        }
+    }
+}
+
+impl FromStr for BonusLineKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "GOAL" => Ok(BonusLineKind::Goal),
+            "ENVELOPE" => Ok(BonusLineKind::Envelope),
+            "GIFTS" => Ok(BonusLineKind::Gifts),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown bonus plan line kind: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `bonus_plans`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::bonus_plans)]
+pub struct BonusPlanDB {
+    pub id: String,
+    pub account_id: String,
+    pub income_source_id: Option<String>,
+    pub amount: String,
+    pub currency: String,
+    pub expected_date: String,
+    pub activity_id: Option<String>,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Database row for `bonus_plan_lines`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::bonus_plan_lines)]
+pub struct BonusPlanLineDB {
+    pub id: String,
+    pub plan_id: String,
+    pub kind: String,
+    pub goal_id: Option<String>,
+    pub envelope_id: Option<String>,
+    pub label: String,
+    pub amount: String,
+    pub allocation_id: Option<String>,
+    pub transfer_id: Option<String>,
+    pub sort_order: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BonusPlanLine {
+    pub id: String,
+    pub plan_id: String,
+    pub kind: BonusLineKind,
+    pub goal_id: Option<String>,
+    pub envelope_id: Option<String>,
+    pub label: String,
+    pub amount: Decimal,
+    /// Goal allocation the share was recorded as
+    pub allocation_id: Option<String>,
+    /// Transfer that filled the envelope, once made
+    pub transfer_id: Option<String>,
+}
+
+impl TryFrom<BonusPlanLineDB> for BonusPlanLine {
+    type Error = Error;
+
+    fn try_from(db: BonusPlanLineDB) -> Result<Self> {
+        Ok(BonusPlanLine {
+            id: db.id,
+            plan_id: db.plan_id,
+            kind: BonusLineKind::from_str(&db.kind)?,
+            goal_id: db.goal_id,
+            envelope_id: db.envelope_id,
+            label: db.label,
+            amount: Decimal::from_str(&db.amount)?,
+            allocation_id: db.allocation_id,
+            transfer_id: db.transfer_id,
+        })
+    }
+}
+
+/// A confirmed split of an expected bonus. Amounts are in the account currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BonusPlan {
+    pub id: String,
+    pub account_id: String,
+    pub income_source_id: Option<String>,
+    pub amount: Decimal,
+    pub currency: String,
+    pub expected_date: NaiveDate,
+    /// Deposit the bonus is recorded as
+    pub activity_id: Option<String>,
+    /// Whether the deposit is still a draft, i.e. the bonus has not arrived yet
+    pub deposit_pending: bool,
+    pub note: Option<String>,
+    pub lines: Vec<BonusPlanLine>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl BonusPlan {
+    pub(crate) fn from_db(
+        db: BonusPlanDB,
+        lines: Vec<BonusPlanLine>,
+        deposit_pending: bool,
+    ) -> Result<Self> {
+        Ok(BonusPlan {
+            id: db.id,
+            account_id: db.account_id,
+            income_source_id: db.income_source_id,
+            amount: Decimal::from_str(&db.amount)?,
+            currency: db.currency,
+            expected_date: parse_forecast_date(&db.expected_date)?,
+            activity_id: db.activity_id,
+            deposit_pending,
+            note: db.note,
+            lines,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+
+    /// Part of the bonus no line takes, left as unassigned cash
+    pub fn unassigned(&self) -> Decimal {
+        self.amount - self.lines.iter().map(|line| line.amount).sum::<Decimal>()
+    }
+}
+
+/// What to propose a split for. The amount and date default to the next payment of the
+/// income source.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BonusPlanRequest {
+    pub account_id: String,
+    pub income_source_id: Option<String>,
+    pub amount: Option<Decimal>,
+    pub expected_date: Option<NaiveDate>,
+    /// Percent of the bonus for lì xì and gifts; 10 when unset
+    pub gift_percent: Option<Decimal>,
+    /// Percent of the bonus for envelopes; 20 when unset
+    pub envelope_percent: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBonusPlanLine {
+    pub kind: BonusLineKind,
+    pub goal_id: Option<String>,
+    pub envelope_id: Option<String>,
+    pub label: String,
+    pub amount: Decimal,
+}
+
+/// A proposed split, as returned for review and sent back, possibly edited, to confirm
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBonusPlan {
+    pub account_id: String,
+    pub income_source_id: Option<String>,
+    pub amount: Decimal,
+    pub expected_date: NaiveDate,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub lines: Vec<NewBonusPlanLine>,
+}
+
+impl NewBonusPlan {
+    pub fn validate(&self) -> Result<()> {
+        if self.account_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "accountId".to_string(),
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Bonus amount must be positive".to_string(),
+            )));
+        }
+
+        let mut goals = HashSet::new();
+        let mut envelopes = HashSet::new();
+        for line in &self.lines {
+            if line.amount <= Decimal::ZERO {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "The share for {} must be positive",
+                    line.label
+                ))));
+            }
+            let target = match line.kind {
+                BonusLineKind::Goal => line.goal_id.as_ref().map(|id| goals.insert(id)),
+                BonusLineKind::Envelope => line.envelope_id.as_ref().map(|id| envelopes.insert(id)),
+                BonusLineKind::Gifts => Some(true),
+            };
+            match target {
+                None => {
+                    return Err(Error::Validation(ValidationError::MissingField(format!(
+                        "{}Id",
+                        line.kind.as_str().to_lowercase()
+                    ))))
+                }
+                Some(false) => {
+                    return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                        "{} has more than one share",
+                        line.label
+                    ))))
+                }
+                Some(true) => {}
+            }
+        }
+
+        let assigned: Decimal = self.lines.iter().map(|line| line.amount).sum();
+        if assigned > self.amount {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "The shares add up to {}, more than the bonus of {}",
+                assigned, self.amount
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// A goal the bonus can go to and what it still needs, in the account currency
+#[derive(Debug, Clone)]
+pub struct GoalShortfall {
+    pub goal_id: String,
+    pub title: String,
+    pub shortfall: Decimal,
+}
+
+/// Splits a bonus: `gift_percent` for lì xì and gifts, `envelope_percent` evenly across the
+/// envelopes, and the rest to goals in proportion to what each still needs, never more than
+/// that. Without envelopes their share goes to goals too. Shares are whole currency units;
+/// whatever is left over stays unassigned.
+pub fn split_bonus(
+    amount: Decimal,
+    gift_percent: Decimal,
+    envelope_percent: Decimal,
+    goals: &[GoalShortfall],
+    envelopes: &[(String, String)],
+) -> Vec<NewBonusPlanLine> {
+    let percent_of = |percent: Decimal| (amount * percent / Decimal::ONE_HUNDRED).floor();
+    let mut lines = Vec::new();
+
+    let gifts = percent_of(gift_percent);
+    if gifts > Decimal::ZERO {
+        lines.push(NewBonusPlanLine {
+            kind: BonusLineKind::Gifts,
+            goal_id: None,
+            envelope_id: None,
+            label: GIFT_LINE_LABEL.to_string(),
+            amount: gifts,
+        });
+    }
+
+    let mut envelope_total = Decimal::ZERO;
+    if !envelopes.is_empty() {
+        let each = (percent_of(envelope_percent) / Decimal::from(envelopes.len())).floor();
+        if each > Decimal::ZERO {
+            for (id, name) in envelopes {
+                lines.push(NewBonusPlanLine {
+                    kind: BonusLineKind::Envelope,
+                    goal_id: None,
+                    envelope_id: Some(id.clone()),
+                    label: name.clone(),
+                    amount: each,
+                });
+                envelope_total += each;
+            }
+        }
+    }
+
+    let for_goals = amount - gifts - envelope_total;
+    let needed: Vec<&GoalShortfall> = goals
+        .iter()
+        .filter(|goal| goal.shortfall > Decimal::ZERO)
+        .collect();
+    let total_needed: Decimal = needed.iter().map(|goal| goal.shortfall).sum();
+    for goal in needed {
+        let share = if total_needed <= for_goals {
+            goal.shortfall.floor()
+        } else {
+            (for_goals * goal.shortfall / total_needed).floor()
+        };
+        if share > Decimal::ZERO {
+            lines.push(NewBonusPlanLine {
+                kind: BonusLineKind::Goal,
+                goal_id: Some(goal.goal_id.clone()),
+                envelope_id: None,
+                label: goal.title.clone(),
+                amount: share,
+            });
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(id: &str, shortfall: Decimal) -> GoalShortfall {
+        GoalShortfall {
+            goal_id: id.to_string(),
+            title: id.to_string(),
+            shortfall,
+        }
+    }
+
+    fn amount_of(lines: &[NewBonusPlanLine], label: &str) -> Decimal {
+        lines
+            .iter()
+            .find(|line| line.label == label)
+            .map(|line| line.amount)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn split_favours_goals_that_need_more() {
+        let envelopes = vec![
+            ("e1".to_string(), "Sửa nhà".to_string()),
+            ("e2".to_string(), "Du lịch".to_string()),
+        ];
+        let goals = vec![
+            goal("car", dec!(300_000_000)),
+            goal("house", dec!(900_000_000)),
+            goal("done", Decimal::ZERO),
+        ];
+        let lines = split_bonus(dec!(50_000_000), dec!(10), dec!(20), &goals, &envelopes);
+
+        assert_eq!(amount_of(&lines, GIFT_LINE_LABEL), dec!(5_000_000));
+        assert_eq!(amount_of(&lines, "Sửa nhà"), dec!(5_000_000));
+        assert_eq!(amount_of(&lines, "Du lịch"), dec!(5_000_000));
+        // 35m left for goals needing 1.2bn between them, split one to three
+        assert_eq!(amount_of(&lines, "car"), dec!(8_750_000));
+        assert_eq!(amount_of(&lines, "house"), dec!(26_250_000));
+        assert!(lines.iter().all(|line| line.label != "done"));
+    }
+
+    #[test]
+    fn split_caps_goals_at_what_they_need() {
+        let goals = vec![goal("phone", dec!(12_000_000))];
+        let lines = split_bonus(dec!(30_000_000), dec!(10), dec!(20), &goals, &[]);
+
+        // No envelopes, so their share is left for the goal, which only needs 12m
+        assert_eq!(amount_of(&lines, "phone"), dec!(12_000_000));
+        let plan = NewBonusPlan {
+            account_id: "cash".to_string(),
+            income_source_id: None,
+            amount: dec!(30_000_000),
+            expected_date: NaiveDate::from_ymd_opt(2027, 1, 25).unwrap(),
+            note: None,
+            lines,
+        };
+        assert!(plan.validate().is_ok());
+
+        let mut overdrawn = plan.clone();
+        overdrawn.amount = dec!(10_000_000);
+        assert!(overdrawn.validate().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::bonus_plans_model::{
+    BonusLineKind, BonusPlan, BonusPlanDB, BonusPlanLine, BonusPlanLineDB, NewBonusPlan,
+};
+use super::bonus_plans_traits::BonusPlanRepositoryTrait;
+use crate::activities::{ActivityDB, NewActivity};
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::goals::monthly_summaries::clear_goal_progress;
+use crate::goals::GoalsAllocation;
+use crate::schema::{activities, bonus_plan_lines, bonus_plans, goals_allocation};
+
+pub struct BonusPlanRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl BonusPlanRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        BonusPlanRepository { pool, writer }
+    }
+}
+
+/// Attaches their lines and the state of their deposit to plan rows
+fn load_plans(conn: &mut SqliteConnection, rows: Vec<BonusPlanDB>) -> Result<Vec<BonusPlan>> {
+    let plan_ids: Vec<&String> = rows.iter().map(|row| &row.id).collect();
+    let mut lines: HashMap<String, Vec<BonusPlanLine>> = HashMap::new();
+    for line in bonus_plan_lines::table
+        .filter(bonus_plan_lines::plan_id.eq_any(&plan_ids))
+        .order(bonus_plan_lines::sort_order.asc())
+        .load::<BonusPlanLineDB>(conn)?
+    {
+        let line = BonusPlanLine::try_from(line)?;
+        lines.entry(line.plan_id.clone()).or_default().push(line);
+    }
+
+    let activity_ids: Vec<&String> = rows
+        .iter()
+        .filter_map(|row| row.activity_id.as_ref())
+        .collect();
+    let drafts: HashSet<String> = activities::table
+        .filter(activities::id.eq_any(&activity_ids))
+        .filter(activities::is_draft.eq(true))
+        .select(activities::id)
+        .load::<String>(conn)?
+        .into_iter()
+        .collect();
+
+    rows.into_iter()
+        .map(|row| {
+            let pending = row
+                .activity_id
+                .as_ref()
+                .is_some_and(|id| drafts.contains(id));
+            let plan_lines = lines.remove(&row.id).unwrap_or_default();
+            BonusPlan::from_db(row, plan_lines, pending)
+        })
+        .collect()
+}
+
+#[async_trait]
+impl BonusPlanRepositoryTrait for BonusPlanRepository {
+    fn get_plans(&self) -> Result<Vec<BonusPlan>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = bonus_plans::table
+            .order(bonus_plans::expected_date.desc())
+            .load::<BonusPlanDB>(&mut conn)?;
+        load_plans(&mut conn, rows)
+    }
+
+    fn get_plan(&self, id: &str) -> Result<BonusPlan> {
+        let mut conn = get_connection(&self.pool)?;
+        let row = bonus_plans::table
+            .find(id)
+            .first::<BonusPlanDB>(&mut conn)?;
+        let mut plans = load_plans(&mut conn, vec![row])?;
+        Ok(plans.remove(0))
+    }
+
+    async fn insert_plan(
+        &self,
+        plan: NewBonusPlan,
+        currency: String,
+        deposit: NewActivity,
+        allocations: Vec<GoalsAllocation>,
+    ) -> Result<BonusPlan> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<BonusPlan> {
+                let activity = ActivityDB::from(deposit);
+                diesel::insert_into(activities::table)
+                    .values(&activity)
+                    .execute(conn)?;
+
+                let mut allocation_ids = HashMap::new();
+                for allocation in &allocations {
+                    diesel::insert_into(goals_allocation::table)
+                        .values(allocation)
+                        .execute(conn)?;
+                    clear_goal_progress(conn, Some(&allocation.goal_id), None)?;
+                    allocation_ids.insert(allocation.goal_id.clone(), allocation.id.clone());
+                }
+
+                let now = Utc::now().naive_utc();
+                let record = BonusPlanDB {
+                    id: Uuid::new_v4().to_string(),
+                    account_id: plan.account_id,
+                    income_source_id: plan.income_source_id,
+                    amount: plan.amount.to_string(),
+                    currency,
+                    expected_date: plan.expected_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    activity_id: Some(activity.id.clone()),
+                    note: plan
+                        .note
+                        .map(|note| note.trim().to_string())
+                        .filter(|note| !note.is_empty()),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(bonus_plans::table)
+                    .values(&record)
+                    .execute(conn)?;
+
+                let lines: Vec<BonusPlanLineDB> = plan
+                    .lines
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, line)| BonusPlanLineDB {
+                        id: Uuid::new_v4().to_string(),
+                        plan_id: record.id.clone(),
+                        kind: line.kind.as_str().to_string(),
+                        allocation_id: match line.kind {
+                            BonusLineKind::Goal => line
+                                .goal_id
+                                .as_ref()
+                                .and_then(|goal_id| allocation_ids.get(goal_id).cloned()),
+                            _ => None,
+                        },
+                        goal_id: line.goal_id,
+                        envelope_id: line.envelope_id,
+                        label: line.label.trim().to_string(),
+                        amount: line.amount.to_string(),
+                        transfer_id: None,
+                        sort_order: index as i32,
+                    })
+                    .collect();
+                diesel::insert_into(bonus_plan_lines::table)
+                    .values(&lines)
+                    .execute(conn)?;
+
+                let mut plans = load_plans(conn, vec![record])?;
+                Ok(plans.remove(0))
+            })
+            .await
+    }
+
+    async fn set_line_transfer(&self, line_id: &str, transfer_id: &str) -> Result<()> {
+        let line_id = line_id.to_string();
+        let transfer_id = transfer_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::update(bonus_plan_lines::table.find(line_id))
+                    .set(bonus_plan_lines::transfer_id.eq(transfer_id))
+                    .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn delete_plan(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                let activity_id: Option<String> = bonus_plans::table
+                    .find(&id_owned)
+                    .select(bonus_plans::activity_id)
+                    .first(conn)?;
+                let shares: Vec<(Option<String>, Option<String>)> = bonus_plan_lines::table
+                    .filter(bonus_plan_lines::plan_id.eq(&id_owned))
+                    .select((bonus_plan_lines::goal_id, bonus_plan_lines::allocation_id))
+                    .load(conn)?;
+
+                for (goal_id, allocation_id) in shares {
+                    if let Some(allocation_id) = allocation_id {
+                        diesel::delete(goals_allocation::table.find(allocation_id))
+                            .execute(conn)?;
+                    }
+                    if let Some(goal_id) = goal_id {
+                        clear_goal_progress(conn, Some(&goal_id), None)?;
+                    }
+                }
+                // A bonus that has arrived stays on the account
+                if let Some(activity_id) = activity_id {
+                    diesel::delete(
+                        activities::table
+                            .find(activity_id)
+                            .filter(activities::is_draft.eq(true)),
+                    )
+                    .execute(conn)?;
+                }
+                Ok(diesel::delete(bonus_plans::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
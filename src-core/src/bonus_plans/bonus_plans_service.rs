@@ -0,0 +1,364 @@
+use async_trait::async_trait;
+use chrono::{Days, Utc};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use super::bonus_plans_model::{
+    split_bonus, BonusLineKind, BonusPlan, BonusPlanRequest, GoalShortfall, NewBonusPlan,
+    DEFAULT_ENVELOPE_PERCENT, DEFAULT_GIFT_PERCENT,
+};
+use super::bonus_plans_traits::{BonusPlanRepositoryTrait, BonusPlanServiceTrait};
+use crate::accounts::{Account, AccountRepositoryTrait, ACCOUNT_TYPE_CASH};
+use crate::activities::{NewActivity, ACTIVITY_TYPE_DEPOSIT};
+use crate::assets::AssetServiceTrait;
+use crate::envelopes::{EnvelopeServiceTrait, NewEnvelopeTransfer};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::fx::FxServiceTrait;
+use crate::goals::goals_model::{Goal, GOAL_TYPE_NET_WORTH};
+use crate::goals::{
+    get_goals_progress, GoalServiceTrait, GoalsAllocation, NetWorthGoalServiceTrait,
+};
+use crate::income_sources::{IncomeSource, IncomeSourceKind, IncomeSourceRepositoryTrait};
+
+/// Comment on the bonus deposit when no income source names it
+const BONUS_DEPOSIT_COMMENT: &str = "Tet bonus";
+
+pub struct BonusPlanService {
+    repository: Arc<dyn BonusPlanRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    income_source_repository: Arc<dyn IncomeSourceRepositoryTrait>,
+    goal_service: Arc<dyn GoalServiceTrait>,
+    net_worth_service: Arc<dyn NetWorthGoalServiceTrait>,
+    envelope_service: Arc<dyn EnvelopeServiceTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+fn invalid(message: String) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message))
+}
+
+impl BonusPlanService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: Arc<dyn BonusPlanRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        income_source_repository: Arc<dyn IncomeSourceRepositoryTrait>,
+        goal_service: Arc<dyn GoalServiceTrait>,
+        net_worth_service: Arc<dyn NetWorthGoalServiceTrait>,
+        envelope_service: Arc<dyn EnvelopeServiceTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            income_source_repository,
+            goal_service,
+            net_worth_service,
+            envelope_service,
+            asset_service,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    /// The bonus income source, which must pay in the account currency
+    fn bonus_source(&self, source_id: &str, account: &Account) -> Result<IncomeSource> {
+        let source = self
+            .income_source_repository
+            .get_income_sources()?
+            .into_iter()
+            .find(|source| source.id == source_id)
+            .ok_or_else(|| invalid(format!("Income source {} not found", source_id)))?;
+        if source.kind != IncomeSourceKind::Bonus {
+            return Err(invalid(format!("{} is not a bonus", source.name)));
+        }
+        if source.currency != account.currency {
+            return Err(invalid(format!(
+                "{} is paid in {}, not in {} like {}",
+                source.name, source.currency, account.currency, account.name
+            )));
+        }
+        Ok(source)
+    }
+
+    /// Goals that can take a share: not achieved and not tracking net worth, which a deposit
+    /// can't be set aside for
+    async fn open_goals(&self) -> Result<Vec<Goal>> {
+        Ok(self
+            .goal_service
+            .get_goals()
+            .await?
+            .into_iter()
+            .filter(|goal| !goal.is_achieved && goal.goal_type != GOAL_TYPE_NET_WORTH)
+            .collect())
+    }
+
+    /// What each open goal still needs, in `currency`
+    async fn goal_shortfalls(&self, currency: &str) -> Result<Vec<GoalShortfall>> {
+        let goals = self.open_goals().await?;
+        let progress: HashMap<String, f64> = get_goals_progress(
+            self.goal_service.as_ref(),
+            self.net_worth_service.as_ref(),
+            None,
+        )
+        .await?
+        .into_iter()
+        .map(|snapshot| (snapshot.goal_id, snapshot.current_value))
+        .collect();
+        let base_currency = self.base_currency.read().unwrap().clone();
+
+        let mut shortfalls = Vec::new();
+        for goal in goals {
+            let current = progress.get(&goal.id).copied().unwrap_or_default();
+            let needed = Decimal::from_f64(goal.target_amount - current).unwrap_or_default();
+            if needed <= Decimal::ZERO {
+                continue;
+            }
+            // Until a base currency is set, goals are taken to be in the account's
+            let shortfall = if base_currency.is_empty() || base_currency == currency {
+                needed
+            } else {
+                self.fx_service
+                    .convert_currency(needed, &base_currency, currency)?
+            };
+            shortfalls.push(GoalShortfall {
+                goal_id: goal.id,
+                title: goal.title,
+                shortfall,
+            });
+        }
+        Ok(shortfalls)
+    }
+
+    /// Active envelopes of a cash account, as (id, name)
+    fn account_envelopes(&self, account: &Account) -> Result<Vec<(String, String)>> {
+        if account.account_type != ACCOUNT_TYPE_CASH {
+            return Ok(Vec::new());
+        }
+        Ok(self
+            .envelope_service
+            .get_account_envelopes(&account.id)?
+            .envelopes
+            .into_iter()
+            .map(|envelope| (envelope.id, envelope.name))
+            .collect())
+    }
+
+    /// Checks every share points at a goal or envelope it can go to
+    async fn check_lines(&self, plan: &NewBonusPlan, account: &Account) -> Result<Vec<Goal>> {
+        let goals = self.open_goals().await?;
+        let mut targeted = Vec::new();
+        for line in &plan.lines {
+            match line.kind {
+                BonusLineKind::Goal => {
+                    let goal_id = line.goal_id.as_deref().unwrap_or_default();
+                    let goal = goals
+                        .iter()
+                        .find(|goal| goal.id == goal_id)
+                        .ok_or_else(|| {
+                            invalid(format!(
+                                "{} is not an open goal a bonus can go to",
+                                line.label
+                            ))
+                        })?;
+                    targeted.push(goal.clone());
+                }
+                BonusLineKind::Envelope => {
+                    let envelope_id = line.envelope_id.as_deref().unwrap_or_default();
+                    let envelope = self.envelope_service.get_envelope(envelope_id)?;
+                    if envelope.account_id != account.id || envelope.is_archived {
+                        return Err(invalid(format!(
+                            "{} is not an envelope of {}",
+                            envelope.name, account.name
+                        )));
+                    }
+                }
+                BonusLineKind::Gifts => {}
+            }
+        }
+        Ok(targeted)
+    }
+}
+
+#[async_trait]
+impl BonusPlanServiceTrait for BonusPlanService {
+    fn get_bonus_plans(&self) -> Result<Vec<BonusPlan>> {
+        self.repository.get_plans()
+    }
+
+    fn get_bonus_plan(&self, id: &str) -> Result<BonusPlan> {
+        self.repository.get_plan(id)
+    }
+
+    async fn propose_bonus_plan(&self, request: BonusPlanRequest) -> Result<NewBonusPlan> {
+        let account = self.account_repository.get_by_id(&request.account_id)?;
+        let source = request
+            .income_source_id
+            .as_deref()
+            .map(|id| self.bonus_source(id, &account))
+            .transpose()?;
+
+        let amount = request
+            .amount
+            .or_else(|| source.as_ref().map(|source| source.amount))
+            .ok_or_else(|| {
+                Error::Validation(ValidationError::MissingField("amount".to_string()))
+            })?;
+        let today = Utc::now().date_naive();
+        // The next payment within about a year, as bonuses come once a year
+        let expected_date = request
+            .expected_date
+            .or_else(|| {
+                source.as_ref().and_then(|source| {
+                    source
+                        .expected_dates(today, today + Days::new(400))
+                        .into_iter()
+                        .next()
+                })
+            })
+            .unwrap_or(today);
+
+        let percent = |value: Option<Decimal>, default: Decimal| -> Result<Decimal> {
+            let value = value.unwrap_or(default);
+            if value < Decimal::ZERO || value > Decimal::ONE_HUNDRED {
+                return Err(invalid("Percentages must be between 0 and 100".to_string()));
+            }
+            Ok(value)
+        };
+        let gift_percent = percent(request.gift_percent, DEFAULT_GIFT_PERCENT)?;
+        let envelope_percent = percent(request.envelope_percent, DEFAULT_ENVELOPE_PERCENT)?;
+        if gift_percent + envelope_percent > Decimal::ONE_HUNDRED {
+            return Err(invalid(
+                "Gifts and envelopes cannot take more than the whole bonus".to_string(),
+            ));
+        }
+
+        let goals = self.goal_shortfalls(&account.currency).await?;
+        let envelopes = self.account_envelopes(&account)?;
+        let plan = NewBonusPlan {
+            account_id: account.id,
+            income_source_id: source.map(|source| source.id),
+            amount,
+            expected_date,
+            note: None,
+            lines: split_bonus(amount, gift_percent, envelope_percent, &goals, &envelopes),
+        };
+        plan.validate()?;
+        Ok(plan)
+    }
+
+    async fn confirm_bonus_plan(&self, plan: NewBonusPlan) -> Result<BonusPlan> {
+        plan.validate()?;
+        let account = self.account_repository.get_by_id(&plan.account_id)?;
+        let source = plan
+            .income_source_id
+            .as_deref()
+            .map(|id| self.bonus_source(id, &account))
+            .transpose()?;
+        let goals = self.check_lines(&plan, &account).await?;
+
+        let cash_asset_id = format!("$CASH-{}", account.currency);
+        if self.asset_service.get_asset_by_id(&cash_asset_id).is_err() {
+            self.asset_service
+                .create_cash_asset(&account.currency)
+                .await?;
+        }
+        let date = plan.expected_date.format(FORECAST_DATE_FORMAT).to_string();
+        // A draft until the bonus arrives and the deposit is confirmed
+        let deposit = NewActivity {
+            id: Some(Uuid::new_v4().to_string()),
+            account_id: account.id.clone(),
+            asset_id: cash_asset_id,
+            activity_type: ACTIVITY_TYPE_DEPOSIT.to_string(),
+            activity_date: date.clone(),
+            quantity: None,
+            unit_price: None,
+            currency: account.currency.clone(),
+            fee: None,
+            amount: Some(plan.amount),
+            is_draft: true,
+            comment: Some(
+                source
+                    .map(|source| source.name)
+                    .unwrap_or_else(|| BONUS_DEPOSIT_COMMENT.to_string()),
+            ),
+        };
+        deposit.validate()?;
+
+        let allocations: Vec<GoalsAllocation> = plan
+            .lines
+            .iter()
+            .filter(|line| line.kind == BonusLineKind::Goal)
+            .zip(&goals)
+            .map(|(line, goal)| {
+                let amount = line.amount.to_f64().unwrap_or_default();
+                GoalsAllocation {
+                    id: Uuid::new_v4().to_string(),
+                    goal_id: goal.id.clone(),
+                    account_id: account.id.clone(),
+                    init_amount: amount,
+                    allocation_percentage: 0.0,
+                    allocation_date: Some(date.clone()),
+                    percent_allocation: 0,
+                    start_date: Some(date.clone()),
+                    // Runs to the goal's due date, unless that has passed
+                    end_date: goal.due_date.clone().filter(|due| *due >= date),
+                    allocation_amount: amount,
+                }
+            })
+            .collect();
+
+        let created = self
+            .repository
+            .insert_plan(plan, account.currency, deposit, allocations)
+            .await?;
+        self.goal_service.invalidate_allocation_cache();
+        Ok(created)
+    }
+
+    async fn fund_bonus_plan_envelopes(&self, id: &str) -> Result<BonusPlan> {
+        let plan = self.repository.get_plan(id)?;
+        if plan.deposit_pending {
+            return Err(invalid(
+                "The bonus has not arrived yet; confirm its deposit before filling envelopes"
+                    .to_string(),
+            ));
+        }
+        for line in &plan.lines {
+            let Some(envelope_id) = line.envelope_id.as_ref() else {
+                continue;
+            };
+            if line.transfer_id.is_some() {
+                continue;
+            }
+            let transfer = self
+                .envelope_service
+                .transfer_envelope_funds(NewEnvelopeTransfer {
+                    account_id: plan.account_id.clone(),
+                    from_envelope_id: None,
+                    to_envelope_id: Some(envelope_id.clone()),
+                    amount: line.amount,
+                    note: Some(BONUS_DEPOSIT_COMMENT.to_string()),
+                })
+                .await?;
+            self.repository
+                .set_line_transfer(&line.id, &transfer.id)
+                .await?;
+        }
+        self.repository.get_plan(id)
+    }
+
+    async fn delete_bonus_plan(&self, id: &str) -> Result<usize> {
+        let deleted = self.repository.delete_plan(id).await?;
+        self.goal_service.invalidate_allocation_cache();
+        Ok(deleted)
+    }
+}
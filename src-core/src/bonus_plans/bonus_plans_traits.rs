@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+
+use super::bonus_plans_model::{BonusPlan, BonusPlanRequest, NewBonusPlan};
+use crate::activities::NewActivity;
+use crate::errors::Result;
+use crate::goals::GoalsAllocation;
+
+#[async_trait]
+pub trait BonusPlanRepositoryTrait: Send + Sync {
+    fn get_plans(&self) -> Result<Vec<BonusPlan>>;
+    fn get_plan(&self, id: &str) -> Result<BonusPlan>;
+    /// Stores the plan with the bonus deposit and one allocation per goal share, matched to the
+    /// shares by goal, in one transaction
+    async fn insert_plan(
+        &self,
+        plan: NewBonusPlan,
+        currency: String,
+        deposit: NewActivity,
+        allocations: Vec<GoalsAllocation>,
+    ) -> Result<BonusPlan>;
+    async fn set_line_transfer(&self, line_id: &str, transfer_id: &str) -> Result<()>;
+    /// Deletes the plan with its goal allocations, and its deposit while that is still a draft
+    async fn delete_plan(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait BonusPlanServiceTrait: Send + Sync {
+    fn get_bonus_plans(&self) -> Result<Vec<BonusPlan>>;
+    fn get_bonus_plan(&self, id: &str) -> Result<BonusPlan>;
+    /// Proposes a split of the bonus across lì xì and gifts, the account's envelopes and the
+    /// goals still short of their target. Nothing is stored.
+    async fn propose_bonus_plan(&self, request: BonusPlanRequest) -> Result<NewBonusPlan>;
+    /// Records the bonus as a draft deposit into the account and each goal share as a goal
+    /// allocation, together with the plan
+    async fn confirm_bonus_plan(&self, plan: NewBonusPlan) -> Result<BonusPlan>;
+    /// Fills the envelope shares from the account's cash once the bonus has arrived
+    async fn fund_bonus_plan_envelopes(&self, id: &str) -> Result<BonusPlan>;
+    async fn delete_bonus_plan(&self, id: &str) -> Result<usize>;
+}
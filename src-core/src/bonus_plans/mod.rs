@@ -0,0 +1,12 @@
+mod bonus_plans_model;
+mod bonus_plans_repository;
+mod bonus_plans_service;
+mod bonus_plans_traits;
+
+pub use bonus_plans_model::{
+    split_bonus, BonusLineKind, BonusPlan, BonusPlanLine, BonusPlanRequest, GoalShortfall,
+    NewBonusPlan, NewBonusPlanLine, DEFAULT_ENVELOPE_PERCENT, DEFAULT_GIFT_PERCENT,
+};
+pub use bonus_plans_repository::BonusPlanRepository;
+pub use bonus_plans_service::BonusPlanService;
+pub use bonus_plans_traits::{BonusPlanRepositoryTrait, BonusPlanServiceTrait};
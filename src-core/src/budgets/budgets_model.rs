@@ -0,0 +1,360 @@
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::activities::{
+    ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_FEE, ACTIVITY_TYPE_INTEREST,
+    ACTIVITY_TYPE_TAX, ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT,
+    ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::errors::{Error, Result, ValidationError};
+
+/// Budget months are written as `YYYY-MM`
+pub const BUDGET_MONTH_FORMAT: &str = "%Y-%m";
+
+/// First day of a `YYYY-MM` budget month
+pub fn parse_budget_month(month: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").map_err(|_| {
+        Error::Validation(ValidationError::InvalidInput(format!(
+            "Invalid budget month '{}', expected YYYY-MM",
+            month
+        )))
+    })
+}
+
+pub fn next_budget_month(month: NaiveDate) -> NaiveDate {
+    month
+        .checked_add_months(Months::new(1))
+        .unwrap_or(NaiveDate::MAX)
+}
+
+/// What happens to a category's unspent or overspent amount at month end
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BudgetRollover {
+    /// Every month starts from its own budgeted amount
+    None,
+    /// Unspent money is added to next month; overspending is forgiven
+    CarrySurplus,
+    /// Both unspent money and overspending carry into next month
+    CarryAll,
+}
+
+impl BudgetRollover {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetRollover::None => "NONE",
+            BudgetRollover::CarrySurplus => "CARRY_SURPLUS",
+            BudgetRollover::CarryAll => "CARRY_ALL",
+        }
+    }
+
+    /// Amount carried into the next month given what remained of this one
+    pub fn carry(&self, remaining: Decimal) -> Decimal {
+        match self {
+            BudgetRollover::None => Decimal::ZERO,
+            BudgetRollover::CarrySurplus => remaining.max(Decimal::ZERO),
+            BudgetRollover::CarryAll => remaining,
+        }
+    }
+}
+
+impl FromStr for BudgetRollover {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "NONE" => Ok(BudgetRollover::None),
+            "CARRY_SURPLUS" => Ok(BudgetRollover::CarrySurplus),
+            "CARRY_ALL" => Ok(BudgetRollover::CarryAll),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown budget rollover rule: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `budget_categories`
+#[derive(Queryable, Identifiable, Insertable, AsChangeset, Debug, Clone)]
+#[diesel(table_name = crate::schema::budget_categories)]
+pub struct BudgetCategoryDB {
+    pub id: String,
+    pub name: String,
+    pub monthly_amount: String,
+    pub currency: String,
+    pub rollover: String,
+    pub start_month: String,
+    pub is_archived: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCategory {
+    pub id: String,
+    pub name: String,
+    pub monthly_amount: Decimal,
+    pub currency: String,
+    pub rollover: BudgetRollover,
+    /// First month (`YYYY-MM`) the budget applies; rollover accumulates from here
+    pub start_month: String,
+    pub is_archived: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<BudgetCategoryDB> for BudgetCategory {
+    type Error = Error;
+
+    fn try_from(db: BudgetCategoryDB) -> Result<Self> {
+        Ok(BudgetCategory {
+            id: db.id,
+            name: db.name,
+            monthly_amount: Decimal::from_str(&db.monthly_amount)?,
+            currency: db.currency,
+            rollover: db.rollover.parse()?,
+            start_month: db.start_month,
+            is_archived: db.is_archived,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a budget category
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBudgetCategory {
+    pub id: Option<String>,
+    pub name: String,
+    pub monthly_amount: Decimal,
+    pub currency: String,
+    pub rollover: BudgetRollover,
+    pub start_month: String,
+    #[serde(default)]
+    pub is_archived: bool,
+}
+
+impl NewBudgetCategory {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.monthly_amount < Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Monthly budget cannot be negative".to_string(),
+            )));
+        }
+        if self.currency.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "currency".to_string(),
+            )));
+        }
+        parse_budget_month(&self.start_month)?;
+        Ok(())
+    }
+}
+
+/// Database row for `budget_month_amounts`
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::budget_month_amounts)]
+pub struct BudgetMonthAmountDB {
+    pub category_id: String,
+    pub month: String,
+    pub amount: String,
+}
+
+/// Budgeted amount for one month, replacing the category's monthly amount
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetMonthAmount {
+    pub category_id: String,
+    pub month: String,
+    pub amount: Decimal,
+}
+
+impl TryFrom<BudgetMonthAmountDB> for BudgetMonthAmount {
+    type Error = Error;
+
+    fn try_from(db: BudgetMonthAmountDB) -> Result<Self> {
+        Ok(BudgetMonthAmount {
+            category_id: db.category_id,
+            month: db.month,
+            amount: Decimal::from_str(&db.amount)?,
+        })
+    }
+}
+
+/// Change to one month's budget; no amount restores the category's monthly amount
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetMonthAmountUpdate {
+    pub category_id: String,
+    pub month: String,
+    pub amount: Option<Decimal>,
+}
+
+/// How an activity's category was assigned
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CategorySource {
+    /// Picked by the user; never replaced automatically
+    Manual,
+    /// Matched a categorization rule
+    Rule,
+    /// Inferred from earlier manual picks for the same payee
+    Learned,
+}
+
+impl CategorySource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CategorySource::Manual => "MANUAL",
+            CategorySource::Rule => "RULE",
+            CategorySource::Learned => "LEARNED",
+        }
+    }
+}
+
+impl FromStr for CategorySource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "MANUAL" => Ok(CategorySource::Manual),
+            "RULE" => Ok(CategorySource::Rule),
+            "LEARNED" => Ok(CategorySource::Learned),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown category source: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `activity_categories`. `source` holds a `CategorySource`.
+#[derive(Queryable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = crate::schema::activity_categories)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityCategory {
+    pub activity_id: String,
+    pub category_id: String,
+    pub source: String,
+}
+
+/// A categorized activity with the values needed to work out what it cost
+#[derive(Debug, Clone)]
+pub struct CategorizedActivity {
+    pub category_id: String,
+    pub activity_type: String,
+    pub activity_date: NaiveDate,
+    pub currency: String,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    pub fee: Decimal,
+    pub amount: Option<Decimal>,
+}
+
+impl CategorizedActivity {
+    /// Spending in the activity's currency. Money coming back into a category
+    /// (refunds, reimbursements) is negative; trades don't count as spending.
+    pub fn spending(&self) -> Decimal {
+        let value = self
+            .amount
+            .filter(|amount| !amount.is_zero())
+            .unwrap_or(self.quantity * self.unit_price);
+
+        match self.activity_type.as_str() {
+            ACTIVITY_TYPE_WITHDRAWAL | ACTIVITY_TYPE_TRANSFER_OUT => value + self.fee,
+            ACTIVITY_TYPE_FEE | ACTIVITY_TYPE_TAX => {
+                if self.fee.is_zero() {
+                    value
+                } else {
+                    self.fee
+                }
+            }
+            ACTIVITY_TYPE_DEPOSIT
+            | ACTIVITY_TYPE_TRANSFER_IN
+            | ACTIVITY_TYPE_INTEREST
+            | ACTIVITY_TYPE_DIVIDEND => -value,
+            _ => Decimal::ZERO,
+        }
+    }
+}
+
+/// Budget position of one category for one month, in the category's currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetProgress {
+    pub category_id: String,
+    pub category_name: String,
+    pub month: String,
+    pub currency: String,
+    pub budgeted: Decimal,
+    /// Carried over from earlier months under the category's rollover rule
+    pub rollover_in: Decimal,
+    pub available: Decimal,
+    pub spent: Decimal,
+    pub remaining: Decimal,
+    /// Share of `available` spent, as a percentage; `None` when nothing is available
+    pub percent_used: Option<Decimal>,
+    pub is_over_budget: bool,
+}
+
+/// Every active category's progress for a month, with totals in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetMonthProgress {
+    pub month: String,
+    pub base_currency: String,
+    pub categories: Vec<BudgetProgress>,
+    pub total_available: Decimal,
+    pub total_spent: Decimal,
+    pub total_remaining: Decimal,
+    /// Unspent budget the month lets go of: what is left in categories that don't roll over
+    pub surplus: Decimal,
+}
+
+/// Budget against actual spending for a run of months, in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetReport {
+    pub from_month: String,
+    pub to_month: String,
+    pub base_currency: String,
+    pub months: Vec<BudgetMonthProgress>,
+    /// Budgeted over the period, rollover aside
+    pub total_budgeted: Decimal,
+    pub total_spent: Decimal,
+    /// Budgeted less spent; negative when the period went over budget
+    pub total_variance: Decimal,
+    /// Surplus of the months that have ended, which can be put towards goals
+    pub available_to_allocate: Decimal,
+}
+
+/// Where a monthly expense estimate comes from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExpenseBasis {
+    /// Average of categorized spending in recent months
+    Spending,
+    /// Sum of active category budgets, used when there is no spending history
+    Budget,
+}
+
+/// Typical monthly expenses in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyExpenses {
+    pub base_currency: String,
+    pub average_monthly: Decimal,
+    pub basis: ExpenseBasis,
+    /// Months with spending that went into the average; zero for budget-based estimates
+    pub months_sampled: u32,
+}
@@ -0,0 +1,280 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::budgets_model::{
+    ActivityCategory, BudgetCategory, BudgetCategoryDB, BudgetMonthAmount, BudgetMonthAmountDB,
+    CategorizedActivity, CategorySource, NewBudgetCategory,
+};
+use super::budgets_traits::BudgetRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Error, Result, ValidationError};
+use crate::schema::{
+    activities, activity_categories, budget_categories, budget_month_amounts, categorization_rules,
+};
+
+pub struct BudgetRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl BudgetRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        BudgetRepository { pool, writer }
+    }
+}
+
+fn parse_activity_date(value: &str) -> Result<NaiveDate> {
+    value
+        .get(..10)
+        .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+        .ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid activity date: {}",
+                value
+            )))
+        })
+}
+
+#[async_trait]
+impl BudgetRepositoryTrait for BudgetRepository {
+    fn get_categories(&self) -> Result<Vec<BudgetCategory>> {
+        let mut conn = get_connection(&self.pool)?;
+        budget_categories::table
+            .order(budget_categories::name.asc())
+            .load::<BudgetCategoryDB>(&mut conn)?
+            .into_iter()
+            .map(BudgetCategory::try_from)
+            .collect()
+    }
+
+    fn get_category(&self, id: &str) -> Result<BudgetCategory> {
+        let mut conn = get_connection(&self.pool)?;
+        budget_categories::table
+            .find(id)
+            .first::<BudgetCategoryDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_category(&self, category: NewBudgetCategory) -> Result<BudgetCategory> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<BudgetCategory> {
+                    let now = chrono::Utc::now().naive_utc();
+                    let record = BudgetCategoryDB {
+                        id: category.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        name: category.name.trim().to_string(),
+                        monthly_amount: category.monthly_amount.to_string(),
+                        currency: category.currency,
+                        rollover: category.rollover.as_str().to_string(),
+                        start_month: category.start_month,
+                        is_archived: category.is_archived,
+                        created_at: now,
+                        updated_at: now,
+                    };
+
+                    diesel::insert_into(budget_categories::table)
+                        .values(&record)
+                        .get_result::<BudgetCategoryDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn update_category(
+        &self,
+        id: &str,
+        category: NewBudgetCategory,
+    ) -> Result<BudgetCategory> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<BudgetCategory> {
+                    diesel::update(budget_categories::table.find(id_owned))
+                        .set((
+                            budget_categories::name.eq(category.name.trim()),
+                            budget_categories::monthly_amount
+                                .eq(category.monthly_amount.to_string()),
+                            budget_categories::currency.eq(category.currency),
+                            budget_categories::rollover.eq(category.rollover.as_str()),
+                            budget_categories::start_month.eq(category.start_month),
+                            budget_categories::is_archived.eq(category.is_archived),
+                            budget_categories::updated_at.eq(chrono::Utc::now().naive_utc()),
+                        ))
+                        .get_result::<BudgetCategoryDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_category(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                diesel::delete(
+                    activity_categories::table
+                        .filter(activity_categories::category_id.eq(&id_owned)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    budget_month_amounts::table
+                        .filter(budget_month_amounts::category_id.eq(&id_owned)),
+                )
+                .execute(conn)?;
+                diesel::delete(
+                    categorization_rules::table
+                        .filter(categorization_rules::category_id.eq(&id_owned)),
+                )
+                .execute(conn)?;
+                Ok(diesel::delete(budget_categories::table.find(&id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_month_amounts(&self) -> Result<Vec<BudgetMonthAmount>> {
+        let mut conn = get_connection(&self.pool)?;
+        budget_month_amounts::table
+            .order((
+                budget_month_amounts::category_id.asc(),
+                budget_month_amounts::month.asc(),
+            ))
+            .load::<BudgetMonthAmountDB>(&mut conn)?
+            .into_iter()
+            .map(BudgetMonthAmount::try_from)
+            .collect()
+    }
+
+    async fn set_month_amount(
+        &self,
+        category_id: &str,
+        month: &str,
+        amount: Option<Decimal>,
+    ) -> Result<()> {
+        let category_id = category_id.to_string();
+        let month = month.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                match amount {
+                    Some(amount) => {
+                        diesel::replace_into(budget_month_amounts::table)
+                            .values(&BudgetMonthAmountDB {
+                                category_id,
+                                month,
+                                amount: amount.to_string(),
+                            })
+                            .execute(conn)?;
+                    }
+                    None => {
+                        diesel::delete(
+                            budget_month_amounts::table
+                                .filter(budget_month_amounts::category_id.eq(category_id))
+                                .filter(budget_month_amounts::month.eq(month)),
+                        )
+                        .execute(conn)?;
+                    }
+                }
+                Ok(())
+            })
+            .await
+    }
+
+    async fn set_activity_category(
+        &self,
+        activity_ids: Vec<String>,
+        category_id: Option<String>,
+    ) -> Result<usize> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                match category_id {
+                    Some(category_id) => {
+                        let rows: Vec<ActivityCategory> = activity_ids
+                            .into_iter()
+                            .map(|activity_id| ActivityCategory {
+                                activity_id,
+                                category_id: category_id.clone(),
+                                source: CategorySource::Manual.as_str().to_string(),
+                            })
+                            .collect();
+                        Ok(diesel::replace_into(activity_categories::table)
+                            .values(&rows)
+                            .execute(conn)?)
+                    }
+                    None => Ok(diesel::delete(
+                        activity_categories::table
+                            .filter(activity_categories::activity_id.eq_any(activity_ids)),
+                    )
+                    .execute(conn)?),
+                }
+            })
+            .await
+    }
+
+    fn get_activity_categories(&self, activity_ids: &[String]) -> Result<Vec<ActivityCategory>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(activity_categories::table
+            .filter(activity_categories::activity_id.eq_any(activity_ids))
+            .load::<ActivityCategory>(&mut conn)?)
+    }
+
+    fn get_categorized_activities(&self, before: NaiveDate) -> Result<Vec<CategorizedActivity>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = activities::table
+            .inner_join(activity_categories::table)
+            .filter(activities::is_draft.eq(false))
+            .filter(activities::activity_date.lt(before.format("%Y-%m-%d").to_string()))
+            .select((
+                activity_categories::category_id,
+                activities::activity_type,
+                activities::activity_date,
+                activities::currency,
+                activities::quantity,
+                activities::unit_price,
+                activities::fee,
+                activities::amount,
+            ))
+            .load::<(
+                String,
+                String,
+                String,
+                String,
+                String,
+                String,
+                String,
+                Option<String>,
+            )>(&mut conn)?;
+
+        rows.into_iter()
+            .map(
+                |(
+                    category_id,
+                    activity_type,
+                    date,
+                    currency,
+                    quantity,
+                    unit_price,
+                    fee,
+                    amount,
+                )| {
+                    Ok(CategorizedActivity {
+                        category_id,
+                        activity_type,
+                        activity_date: parse_activity_date(&date)?,
+                        currency,
+                        quantity: Decimal::from_str(&quantity)?,
+                        unit_price: Decimal::from_str(&unit_price)?,
+                        fee: Decimal::from_str(&fee)?,
+                        amount: amount.map(|a| Decimal::from_str(&a)).transpose()?,
+                    })
+                },
+            )
+            .collect()
+    }
+}
@@ -0,0 +1,542 @@
+use async_trait::async_trait;
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use super::budgets_model::{
+    next_budget_month, parse_budget_month, ActivityCategory, BudgetCategory, BudgetMonthAmount,
+    BudgetMonthProgress, BudgetProgress, BudgetReport, ExpenseBasis, MonthlyExpenses,
+    NewBudgetCategory, BUDGET_MONTH_FORMAT,
+};
+use super::budgets_traits::{BudgetRepositoryTrait, BudgetServiceTrait};
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::{convert_or_keep, FxServiceTrait};
+
+/// Longest run of months a budget report covers
+const MAX_REPORT_MONTHS: u32 = 60;
+
+/// Budget data loaded once for any number of months. Overrides and spending are keyed by
+/// category, then by `YYYY-MM`.
+struct BudgetLedger {
+    categories: Vec<BudgetCategory>,
+    overrides: HashMap<String, HashMap<String, Decimal>>,
+    spent: HashMap<String, HashMap<String, Decimal>>,
+}
+
+pub struct BudgetService {
+    repository: Arc<dyn BudgetRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl BudgetService {
+    pub fn new(
+        repository: Arc<dyn BudgetRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    /// Active categories with their month overrides and their spending before `before`, in
+    /// each category's currency
+    fn load_ledger(&self, before: NaiveDate) -> Result<BudgetLedger> {
+        let categories: Vec<BudgetCategory> = self
+            .repository
+            .get_categories()?
+            .into_iter()
+            .filter(|category| !category.is_archived)
+            .collect();
+        let currencies: HashMap<&str, &str> = categories
+            .iter()
+            .map(|category| (category.id.as_str(), category.currency.as_str()))
+            .collect();
+
+        let mut overrides: HashMap<String, HashMap<String, Decimal>> = HashMap::new();
+        for amount in self.repository.get_month_amounts()? {
+            overrides
+                .entry(amount.category_id)
+                .or_default()
+                .insert(amount.month, amount.amount);
+        }
+
+        let mut spent: HashMap<String, HashMap<String, Decimal>> = HashMap::new();
+        for activity in self.repository.get_categorized_activities(before)? {
+            let Some(currency) = currencies.get(activity.category_id.as_str()) else {
+                continue;
+            };
+            let amount = convert_or_keep(
+                self.fx_service.as_ref(),
+                activity.spending(),
+                &activity.currency,
+                currency,
+                Some(activity.activity_date),
+            );
+            *spent
+                .entry(activity.category_id.clone())
+                .or_default()
+                .entry(
+                    activity
+                        .activity_date
+                        .format(BUDGET_MONTH_FORMAT)
+                        .to_string(),
+                )
+                .or_default() += amount;
+        }
+
+        Ok(BudgetLedger {
+            categories,
+            overrides,
+            spent,
+        })
+    }
+
+    /// Every active category's progress in the month starting `month_start`
+    fn month_progress(
+        &self,
+        ledger: &BudgetLedger,
+        month_start: NaiveDate,
+        base_currency: &str,
+    ) -> Result<BudgetMonthProgress> {
+        let empty = HashMap::new();
+        let mut progress = Vec::with_capacity(ledger.categories.len());
+        let mut total_available = Decimal::ZERO;
+        let mut total_spent = Decimal::ZERO;
+        let mut surplus = Decimal::ZERO;
+        for category in &ledger.categories {
+            let item = category_progress(
+                category,
+                ledger.overrides.get(&category.id).unwrap_or(&empty),
+                ledger.spent.get(&category.id).unwrap_or(&empty),
+                month_start,
+            )?;
+            total_available += convert_or_keep(
+                self.fx_service.as_ref(),
+                item.available,
+                &item.currency,
+                base_currency,
+                Some(month_start),
+            );
+            total_spent += convert_or_keep(
+                self.fx_service.as_ref(),
+                item.spent,
+                &item.currency,
+                base_currency,
+                Some(month_start),
+            );
+            surplus += convert_or_keep(
+                self.fx_service.as_ref(),
+                released_surplus(category, &item),
+                &item.currency,
+                base_currency,
+                Some(month_start),
+            );
+            progress.push(item);
+        }
+
+        Ok(BudgetMonthProgress {
+            month: month_start.format(BUDGET_MONTH_FORMAT).to_string(),
+            base_currency: base_currency.to_string(),
+            categories: progress,
+            total_available,
+            total_spent,
+            total_remaining: total_available - total_spent,
+            surplus,
+        })
+    }
+}
+
+/// What a category lets go of at the end of a month: whatever is left that its rollover rule
+/// doesn't carry into the next one
+pub(crate) fn released_surplus(category: &BudgetCategory, progress: &BudgetProgress) -> Decimal {
+    (progress.remaining - category.rollover.carry(progress.remaining)).max(Decimal::ZERO)
+}
+
+/// Walks a category month by month from its start month to `month`, applying its
+/// rollover rule. `overrides` and `spent` are keyed by `YYYY-MM`.
+pub(crate) fn category_progress(
+    category: &BudgetCategory,
+    overrides: &HashMap<String, Decimal>,
+    spent: &HashMap<String, Decimal>,
+    month: NaiveDate,
+) -> Result<BudgetProgress> {
+    let month_key = month.format(BUDGET_MONTH_FORMAT).to_string();
+    let mut budgeted = Decimal::ZERO;
+    let mut rollover_in = Decimal::ZERO;
+    let mut carry = Decimal::ZERO;
+
+    let mut current = parse_budget_month(&category.start_month)?;
+    while current <= month {
+        let key = current.format(BUDGET_MONTH_FORMAT).to_string();
+        budgeted = overrides
+            .get(&key)
+            .copied()
+            .unwrap_or(category.monthly_amount);
+        rollover_in = carry;
+        let remaining = budgeted + rollover_in - spent.get(&key).copied().unwrap_or_default();
+        carry = category.rollover.carry(remaining);
+        current = next_budget_month(current);
+    }
+
+    let spent_this_month = spent.get(&month_key).copied().unwrap_or_default();
+    let available = budgeted + rollover_in;
+    let remaining = available - spent_this_month;
+    let percent_used = if available > Decimal::ZERO {
+        Some((spent_this_month / available * Decimal::ONE_HUNDRED).round_dp(2))
+    } else {
+        None
+    };
+
+    Ok(BudgetProgress {
+        category_id: category.id.clone(),
+        category_name: category.name.clone(),
+        month: month_key,
+        currency: category.currency.clone(),
+        budgeted,
+        rollover_in,
+        available,
+        spent: spent_this_month,
+        remaining,
+        percent_used,
+        is_over_budget: remaining < Decimal::ZERO,
+    })
+}
+
+/// Average of the months that had net spending, with how many months that was.
+/// Months without spending are skipped so a short history isn't diluted.
+pub(crate) fn average_monthly_spending(
+    spent_by_month: &HashMap<String, Decimal>,
+) -> Option<(Decimal, u32)> {
+    let months: Vec<Decimal> = spent_by_month
+        .values()
+        .copied()
+        .filter(|spent| *spent > Decimal::ZERO)
+        .collect();
+    if months.is_empty() {
+        return None;
+    }
+    let total: Decimal = months.iter().copied().sum();
+    Some((total / Decimal::from(months.len()), months.len() as u32))
+}
+
+#[async_trait]
+impl BudgetServiceTrait for BudgetService {
+    fn get_budget_categories(&self) -> Result<Vec<BudgetCategory>> {
+        self.repository.get_categories()
+    }
+
+    async fn create_budget_category(&self, category: NewBudgetCategory) -> Result<BudgetCategory> {
+        category.validate()?;
+        self.repository.insert_category(category).await
+    }
+
+    async fn update_budget_category(
+        &self,
+        id: &str,
+        category: NewBudgetCategory,
+    ) -> Result<BudgetCategory> {
+        category.validate()?;
+        self.repository.update_category(id, category).await
+    }
+
+    async fn delete_budget_category(&self, id: &str) -> Result<usize> {
+        self.repository.delete_category(id).await
+    }
+
+    fn get_budget_month_amounts(&self) -> Result<Vec<BudgetMonthAmount>> {
+        self.repository.get_month_amounts()
+    }
+
+    async fn set_budget_month_amount(
+        &self,
+        category_id: &str,
+        month: &str,
+        amount: Option<Decimal>,
+    ) -> Result<()> {
+        parse_budget_month(month)?;
+        if amount.is_some_and(|amount| amount < Decimal::ZERO) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Monthly budget cannot be negative".to_string(),
+            )));
+        }
+        self.repository.get_category(category_id)?;
+        self.repository
+            .set_month_amount(category_id, month, amount)
+            .await
+    }
+
+    async fn categorize_activities(
+        &self,
+        activity_ids: Vec<String>,
+        category_id: Option<String>,
+    ) -> Result<usize> {
+        if activity_ids.is_empty() {
+            return Ok(0);
+        }
+        if let Some(ref id) = category_id {
+            self.repository.get_category(id)?;
+        }
+        self.repository
+            .set_activity_category(activity_ids, category_id)
+            .await
+    }
+
+    fn get_activity_categories(&self, activity_ids: &[String]) -> Result<Vec<ActivityCategory>> {
+        self.repository.get_activity_categories(activity_ids)
+    }
+
+    fn get_budget_progress(&self, month: &str) -> Result<BudgetMonthProgress> {
+        let month_start = parse_budget_month(month)?;
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let ledger = self.load_ledger(next_budget_month(month_start))?;
+        self.month_progress(&ledger, month_start, &base_currency)
+    }
+
+    fn get_budget_report(&self, from_month: &str, to_month: &str) -> Result<BudgetReport> {
+        let from = parse_budget_month(from_month)?;
+        let to = parse_budget_month(to_month)?;
+        if to < from {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The report cannot end before it starts".to_string(),
+            )));
+        }
+        if from.checked_add_months(Months::new(MAX_REPORT_MONTHS)) <= Some(to) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "A budget report covers at most {} months",
+                MAX_REPORT_MONTHS
+            ))));
+        }
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let ledger = self.load_ledger(next_budget_month(to))?;
+        let today = Utc::now().date_naive();
+
+        let mut months = Vec::new();
+        let mut total_budgeted = Decimal::ZERO;
+        let mut total_spent = Decimal::ZERO;
+        let mut available_to_allocate = Decimal::ZERO;
+        let mut month = from;
+        while month <= to {
+            let progress = self.month_progress(&ledger, month, &base_currency)?;
+            total_budgeted += progress
+                .categories
+                .iter()
+                .map(|item| {
+                    convert_or_keep(
+                        self.fx_service.as_ref(),
+                        item.budgeted,
+                        &item.currency,
+                        &base_currency,
+                        Some(month),
+                    )
+                })
+                .sum::<Decimal>();
+            total_spent += progress.total_spent;
+            // The running month can still be spent
+            if next_budget_month(month) <= today {
+                available_to_allocate += progress.surplus;
+            }
+            months.push(progress);
+            month = next_budget_month(month);
+        }
+
+        Ok(BudgetReport {
+            from_month: from.format(BUDGET_MONTH_FORMAT).to_string(),
+            to_month: to.format(BUDGET_MONTH_FORMAT).to_string(),
+            base_currency,
+            months,
+            total_budgeted,
+            total_spent,
+            total_variance: total_budgeted - total_spent,
+            available_to_allocate,
+        })
+    }
+
+    fn get_monthly_expenses(&self, months: u32) -> Result<MonthlyExpenses> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let today = Utc::now().date_naive();
+        let current_month = today.with_day(1).unwrap_or(today);
+        let window_start = current_month
+            .checked_sub_months(Months::new(months.max(1)))
+            .unwrap_or(NaiveDate::MIN);
+
+        let mut spent_by_month: HashMap<String, Decimal> = HashMap::new();
+        for activity in self.repository.get_categorized_activities(current_month)? {
+            if activity.activity_date < window_start {
+                continue;
+            }
+            let amount = convert_or_keep(
+                self.fx_service.as_ref(),
+                activity.spending(),
+                &activity.currency,
+                &base_currency,
+                Some(activity.activity_date),
+            );
+            *spent_by_month
+                .entry(
+                    activity
+                        .activity_date
+                        .format(BUDGET_MONTH_FORMAT)
+                        .to_string(),
+                )
+                .or_default() += amount;
+        }
+
+        if let Some((average_monthly, months_sampled)) = average_monthly_spending(&spent_by_month) {
+            return Ok(MonthlyExpenses {
+                base_currency,
+                average_monthly,
+                basis: ExpenseBasis::Spending,
+                months_sampled,
+            });
+        }
+
+        let budgeted = self
+            .repository
+            .get_categories()?
+            .into_iter()
+            .filter(|category| !category.is_archived)
+            .map(|category| {
+                convert_or_keep(
+                    self.fx_service.as_ref(),
+                    category.monthly_amount,
+                    &category.currency,
+                    &base_currency,
+                    Some(today),
+                )
+            })
+            .sum();
+        Ok(MonthlyExpenses {
+            base_currency,
+            average_monthly: budgeted,
+            basis: ExpenseBasis::Budget,
+            months_sampled: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budgets::BudgetRollover;
+    use rust_decimal_macros::dec;
+
+    fn category(rollover: BudgetRollover) -> BudgetCategory {
+        let now = chrono::Utc::now().naive_utc();
+        BudgetCategory {
+            id: "food".to_string(),
+            name: "Ăn uống".to_string(),
+            monthly_amount: dec!(5000000),
+            currency: "VND".to_string(),
+            rollover,
+            start_month: "2026-01".to_string(),
+            is_archived: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn month(value: &str) -> NaiveDate {
+        parse_budget_month(value).unwrap()
+    }
+
+    fn spending() -> HashMap<String, Decimal> {
+        HashMap::from([
+            ("2026-01".to_string(), dec!(3000000)),
+            ("2026-02".to_string(), dec!(7000000)),
+            ("2026-03".to_string(), dec!(1000000)),
+        ])
+    }
+
+    #[test]
+    fn rollover_rules_carry_between_months() {
+        let overrides = HashMap::new();
+        let spent = spending();
+
+        let none = category_progress(
+            &category(BudgetRollover::None),
+            &overrides,
+            &spent,
+            month("2026-02"),
+        )
+        .unwrap();
+        assert_eq!(none.available, dec!(5000000));
+        assert_eq!(none.remaining, dec!(-2000000));
+        assert!(none.is_over_budget);
+
+        // January leaves 2M, February overspends by 0 after the carry
+        let surplus = category_progress(
+            &category(BudgetRollover::CarrySurplus),
+            &overrides,
+            &spent,
+            month("2026-02"),
+        )
+        .unwrap();
+        assert_eq!(surplus.rollover_in, dec!(2000000));
+        assert_eq!(surplus.remaining, dec!(0));
+        assert_eq!(surplus.percent_used, Some(dec!(100)));
+
+        let carry_all = category_progress(
+            &category(BudgetRollover::CarryAll),
+            &overrides,
+            &spent,
+            month("2026-03"),
+        )
+        .unwrap();
+        assert_eq!(carry_all.rollover_in, dec!(0));
+        assert_eq!(carry_all.remaining, dec!(4000000));
+    }
+
+    #[test]
+    fn month_override_and_months_before_start() {
+        let overrides = HashMap::from([("2026-03".to_string(), dec!(2000000))]);
+        let spent = spending();
+
+        let progress = category_progress(
+            &category(BudgetRollover::CarrySurplus),
+            &overrides,
+            &spent,
+            month("2026-03"),
+        )
+        .unwrap();
+        assert_eq!(progress.budgeted, dec!(2000000));
+        assert_eq!(progress.remaining, dec!(1000000));
+
+        let before_start = category_progress(
+            &category(BudgetRollover::CarryAll),
+            &overrides,
+            &spent,
+            month("2025-12"),
+        )
+        .unwrap();
+        assert_eq!(before_start.available, dec!(0));
+        assert_eq!(before_start.percent_used, None);
+    }
+
+    #[test]
+    fn only_money_that_does_not_roll_over_is_released() {
+        let overrides = HashMap::new();
+        let spent = spending();
+
+        // March leaves 4M unspent
+        for (rollover, released) in [
+            (BudgetRollover::None, dec!(4000000)),
+            (BudgetRollover::CarrySurplus, dec!(0)),
+            (BudgetRollover::CarryAll, dec!(0)),
+        ] {
+            let category = category(rollover);
+            let progress =
+                category_progress(&category, &overrides, &spent, month("2026-03")).unwrap();
+            assert_eq!(released_surplus(&category, &progress), released);
+        }
+
+        // Overspending releases nothing
+        let category = category(BudgetRollover::None);
+        let february = category_progress(&category, &overrides, &spent, month("2026-02")).unwrap();
+        assert_eq!(released_surplus(&category, &february), dec!(0));
+    }
+}
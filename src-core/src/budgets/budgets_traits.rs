@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::budgets_model::{
+    ActivityCategory, BudgetCategory, BudgetMonthAmount, BudgetMonthProgress, BudgetReport,
+    CategorizedActivity, MonthlyExpenses, NewBudgetCategory,
+};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait BudgetRepositoryTrait: Send + Sync {
+    fn get_categories(&self) -> Result<Vec<BudgetCategory>>;
+    fn get_category(&self, id: &str) -> Result<BudgetCategory>;
+    async fn insert_category(&self, category: NewBudgetCategory) -> Result<BudgetCategory>;
+    async fn update_category(
+        &self,
+        id: &str,
+        category: NewBudgetCategory,
+    ) -> Result<BudgetCategory>;
+    async fn delete_category(&self, id: &str) -> Result<usize>;
+    fn get_month_amounts(&self) -> Result<Vec<BudgetMonthAmount>>;
+    /// Sets the budget for one month; `None` removes the override
+    async fn set_month_amount(
+        &self,
+        category_id: &str,
+        month: &str,
+        amount: Option<Decimal>,
+    ) -> Result<()>;
+    /// Assigns activities to a category; `None` clears their category
+    async fn set_activity_category(
+        &self,
+        activity_ids: Vec<String>,
+        category_id: Option<String>,
+    ) -> Result<usize>;
+    fn get_activity_categories(&self, activity_ids: &[String]) -> Result<Vec<ActivityCategory>>;
+    /// Categorized, non-draft activities dated before `before`
+    fn get_categorized_activities(&self, before: NaiveDate) -> Result<Vec<CategorizedActivity>>;
+}
+
+#[async_trait]
+pub trait BudgetServiceTrait: Send + Sync {
+    fn get_budget_categories(&self) -> Result<Vec<BudgetCategory>>;
+    async fn create_budget_category(&self, category: NewBudgetCategory) -> Result<BudgetCategory>;
+    async fn update_budget_category(
+        &self,
+        id: &str,
+        category: NewBudgetCategory,
+    ) -> Result<BudgetCategory>;
+    async fn delete_budget_category(&self, id: &str) -> Result<usize>;
+    fn get_budget_month_amounts(&self) -> Result<Vec<BudgetMonthAmount>>;
+    async fn set_budget_month_amount(
+        &self,
+        category_id: &str,
+        month: &str,
+        amount: Option<Decimal>,
+    ) -> Result<()>;
+    async fn categorize_activities(
+        &self,
+        activity_ids: Vec<String>,
+        category_id: Option<String>,
+    ) -> Result<usize>;
+    fn get_activity_categories(&self, activity_ids: &[String]) -> Result<Vec<ActivityCategory>>;
+    /// Budgeted, spent and remaining amounts for every active category in a `YYYY-MM` month
+    fn get_budget_progress(&self, month: &str) -> Result<BudgetMonthProgress>;
+    /// Budget against actual spending for each month from `from_month` to `to_month`
+    /// (`YYYY-MM`, both included), with the surplus of ended months available to allocate
+    fn get_budget_report(&self, from_month: &str, to_month: &str) -> Result<BudgetReport>;
+    /// Average spending over the last `months` complete months, falling back to budgeted amounts
+    fn get_monthly_expenses(&self, months: u32) -> Result<MonthlyExpenses>;
+}
@@ -0,0 +1,13 @@
+mod budgets_model;
+mod budgets_repository;
+mod budgets_service;
+mod budgets_traits;
+
+pub use budgets_model::{
+    ActivityCategory, BudgetCategory, BudgetMonthAmount, BudgetMonthAmountUpdate,
+    BudgetMonthProgress, BudgetProgress, BudgetReport, BudgetRollover, CategorizedActivity,
+    CategorySource, ExpenseBasis, MonthlyExpenses, NewBudgetCategory, BUDGET_MONTH_FORMAT,
+};
+pub use budgets_repository::BudgetRepository;
+pub use budgets_service::BudgetService;
+pub use budgets_traits::{BudgetRepositoryTrait, BudgetServiceTrait};
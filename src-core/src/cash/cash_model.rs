@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Cash an account holds in one currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CashBalance {
+    pub account_id: String,
+    pub currency: String,
+    pub amount: Decimal,
+}
+
+/// All the cash of an account
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountCash {
+    pub account_id: String,
+    pub account_currency: String,
+    /// Date of the snapshot the balances come from; `None` before the first portfolio update
+    pub as_of: Option<NaiveDate>,
+    /// One entry per currency, sorted by currency
+    pub balances: Vec<CashBalance>,
+    /// Every balance converted to the account's currency
+    pub total: Decimal,
+}
+
+impl AccountCash {
+    /// Balance held in `currency`, zero when there is none
+    pub fn balance(&self, currency: &str) -> Decimal {
+        self.balances
+            .iter()
+            .find(|balance| balance.currency == currency)
+            .map(|balance| balance.amount)
+            .unwrap_or_default()
+    }
+}
+
+/// Cash paid into or taken out of an account
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CashMovement {
+    pub account_id: String,
+    /// Defaults to the account's currency
+    #[serde(default)]
+    pub currency: Option<String>,
+    pub amount: Decimal,
+    /// Defaults to today
+    #[serde(default)]
+    pub date: Option<NaiveDate>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl CashMovement {
+    pub fn validate(&self) -> Result<()> {
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Cash amount must be positive, got {}",
+                self.amount
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// A withdrawal can take at most the cash the account holds in its currency
+pub fn check_withdrawal(cash: &AccountCash, currency: &str, amount: Decimal) -> Result<()> {
+    if amount > cash.balance(currency) {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Withdrawal exceeds the account's {} cash balance",
+            currency
+        ))));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn withdrawals_are_limited_to_the_cash_in_their_currency() {
+        let cash = AccountCash {
+            account_id: "broker".to_string(),
+            account_currency: "VND".to_string(),
+            as_of: None,
+            balances: vec![
+                CashBalance {
+                    account_id: "broker".to_string(),
+                    currency: "USD".to_string(),
+                    amount: dec!(100),
+                },
+                CashBalance {
+                    account_id: "broker".to_string(),
+                    currency: "VND".to_string(),
+                    amount: dec!(5_000_000),
+                },
+            ],
+            total: dec!(7_500_000),
+        };
+        assert!(check_withdrawal(&cash, "VND", dec!(5_000_000)).is_ok());
+        assert!(check_withdrawal(&cash, "VND", dec!(5_000_001)).is_err());
+        assert!(check_withdrawal(&cash, "USD", dec!(200)).is_err());
+        assert!(check_withdrawal(&cash, "EUR", dec!(1)).is_err());
+    }
+}
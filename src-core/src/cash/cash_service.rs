@@ -0,0 +1,142 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::cash_model::{check_withdrawal, AccountCash, CashBalance, CashMovement};
+use super::cash_traits::CashServiceTrait;
+use crate::accounts::AccountRepositoryTrait;
+use crate::activities::{
+    Activity, ActivityServiceTrait, NewActivity, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::assets::AssetServiceTrait;
+use crate::constants::CASH_ASSET_PREFIX;
+use crate::errors::Result;
+use crate::fx::FxServiceTrait;
+use crate::portfolio::snapshot::SnapshotServiceTrait;
+
+pub struct CashService {
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+}
+
+impl CashService {
+    pub fn new(
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+    ) -> Self {
+        Self {
+            account_repository,
+            snapshot_service,
+            activity_service,
+            asset_service,
+            fx_service,
+        }
+    }
+
+    async fn record(
+        &self,
+        movement: CashMovement,
+        activity_type: &str,
+        account_currency: &str,
+    ) -> Result<Activity> {
+        let currency = movement
+            .currency
+            .as_deref()
+            .unwrap_or(account_currency)
+            .to_uppercase();
+        let cash_asset_id = format!("{}-{}", CASH_ASSET_PREFIX, currency);
+        if self.asset_service.get_asset_by_id(&cash_asset_id).is_err() {
+            self.asset_service.create_cash_asset(&currency).await?;
+        }
+        let date = movement.date.unwrap_or_else(|| Utc::now().date_naive());
+        self.activity_service
+            .create_activity(NewActivity {
+                id: Some(Uuid::new_v4().to_string()),
+                account_id: movement.account_id,
+                asset_id: cash_asset_id,
+                activity_type: activity_type.to_string(),
+                activity_date: date.format("%Y-%m-%d").to_string(),
+                quantity: None,
+                unit_price: None,
+                currency,
+                fee: None,
+                amount: Some(movement.amount),
+                is_draft: false,
+                comment: movement.comment,
+            })
+            .await
+    }
+}
+
+#[async_trait]
+impl CashServiceTrait for CashService {
+    fn get_account_cash(&self, account_id: &str) -> Result<AccountCash> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        let snapshot = self.snapshot_service.get_latest_holdings_snapshot(account_id)?;
+        let mut balances: Vec<CashBalance> = snapshot
+            .as_ref()
+            .map(|snapshot| {
+                snapshot
+                    .cash_balances
+                    .iter()
+                    .filter(|(_, amount)| !amount.is_zero())
+                    .map(|(currency, amount)| CashBalance {
+                        account_id: account_id.to_string(),
+                        currency: currency.clone(),
+                        amount: *amount,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        balances.sort_by(|a, b| a.currency.cmp(&b.currency));
+
+        let mut total = Decimal::ZERO;
+        for balance in &balances {
+            total += self.fx_service.convert_currency(
+                balance.amount,
+                &balance.currency,
+                &account.currency,
+            )?;
+        }
+        Ok(AccountCash {
+            account_id: account.id,
+            account_currency: account.currency,
+            as_of: snapshot.map(|snapshot| snapshot.snapshot_date),
+            balances,
+            total,
+        })
+    }
+
+    async fn deposit_cash(&self, movement: CashMovement) -> Result<Activity> {
+        movement.validate()?;
+        let account = self.account_repository.get_by_id(&movement.account_id)?;
+        self.record(movement, ACTIVITY_TYPE_DEPOSIT, &account.currency)
+            .await
+    }
+
+    async fn withdraw_cash(&self, movement: CashMovement) -> Result<Activity> {
+        movement.validate()?;
+        // Bring the snapshots up to date first, so cash moved since the last portfolio update
+        // counts towards the balance
+        self.snapshot_service
+            .calculate_holdings_snapshots(Some(std::slice::from_ref(&movement.account_id)))
+            .await?;
+        let cash = self.get_account_cash(&movement.account_id)?;
+        let currency = movement
+            .currency
+            .as_deref()
+            .unwrap_or(&cash.account_currency)
+            .to_uppercase();
+        check_withdrawal(&cash, &currency, movement.amount)?;
+        self.record(movement, ACTIVITY_TYPE_WITHDRAWAL, &cash.account_currency)
+            .await
+    }
+}
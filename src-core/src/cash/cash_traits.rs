@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use super::cash_model::{AccountCash, CashMovement};
+use crate::activities::Activity;
+use crate::errors::Result;
+
+#[async_trait]
+pub trait CashServiceTrait: Send + Sync {
+    /// Cash of an account in each currency as of its latest holdings snapshot, with the total
+    /// in the account's currency
+    fn get_account_cash(&self, account_id: &str) -> Result<AccountCash>;
+    /// Records cash paid into an account as a deposit activity
+    async fn deposit_cash(&self, movement: CashMovement) -> Result<Activity>;
+    /// Records cash taken out of an account as a withdrawal activity; refused when the
+    /// account holds less cash in that currency once its holdings snapshots are recalculated
+    async fn withdraw_cash(&self, movement: CashMovement) -> Result<Activity>;
+}
@@ -0,0 +1,11 @@
+//! Cash each account holds, per currency. Balances come from the holdings snapshots, which
+//! already move cash for buys, sells, income and fees; deposits and withdrawals made here are
+//! recorded as activities so the next portfolio update accounts for them too.
+
+mod cash_model;
+mod cash_service;
+mod cash_traits;
+
+pub use cash_model::{check_withdrawal, AccountCash, CashBalance, CashMovement};
+pub use cash_service::CashService;
+pub use cash_traits::CashServiceTrait;
@@ -0,0 +1,229 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::categorization_model::{
+    CategorizationCandidate, CategorizationRule, RuleField, RuleMatchType,
+};
+use crate::budgets::CategorySource;
+
+/// Reduces a bank description to a stable payee key: lowercase words with
+/// reference numbers and punctuation removed, so "GRAB*8841 HCM" and
+/// "Grab 1290 HCM" both become "grab hcm".
+pub fn normalize_payee(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+enum Matcher {
+    Text(String),
+    Pattern(Regex),
+}
+
+struct CompiledRule {
+    category_id: String,
+    field: RuleField,
+    match_type: RuleMatchType,
+    matcher: Matcher,
+}
+
+impl CompiledRule {
+    fn compile(rule: &CategorizationRule) -> Option<Self> {
+        let matcher = match rule.match_type {
+            RuleMatchType::Regex => {
+                Matcher::Pattern(Regex::new(&format!("(?i){}", rule.pattern)).ok()?)
+            }
+            _ => Matcher::Text(rule.pattern.trim().to_lowercase()),
+        };
+        Some(Self {
+            category_id: rule.category_id.clone(),
+            field: rule.field,
+            match_type: rule.match_type,
+            matcher,
+        })
+    }
+
+    fn matches(&self, candidate: &CategorizationCandidate) -> bool {
+        let value = match self.field {
+            RuleField::Payee => candidate.comment.as_deref().unwrap_or_default(),
+            RuleField::AccountId => candidate.account_id.as_str(),
+            RuleField::AssetId => candidate.asset_id.as_str(),
+            RuleField::ActivityType => candidate.activity_type.as_str(),
+        };
+        if value.is_empty() {
+            return false;
+        }
+
+        match &self.matcher {
+            Matcher::Pattern(regex) => regex.is_match(value),
+            Matcher::Text(pattern) => {
+                let value = value.to_lowercase();
+                match self.match_type {
+                    RuleMatchType::Equals => value.trim() == pattern,
+                    RuleMatchType::StartsWith => value.trim_start().starts_with(pattern.as_str()),
+                    _ => value.contains(pattern.as_str()),
+                }
+            }
+        }
+    }
+}
+
+/// Assigns categories from rules first, then from the categories the user
+/// previously picked by hand for the same payee
+pub struct CategorizationEngine {
+    rules: Vec<CompiledRule>,
+    learned: HashMap<String, String>,
+}
+
+impl CategorizationEngine {
+    /// `rules` should only contain active rules for categories that can be assigned;
+    /// `history` is (payee text, category id) for manually categorized activities.
+    pub fn new<'a>(
+        rules: &[CategorizationRule],
+        history: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        let mut ordered: Vec<&CategorizationRule> = rules.iter().collect();
+        ordered.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.created_at.cmp(&b.created_at))
+        });
+
+        let mut counts: HashMap<String, HashMap<&str, usize>> = HashMap::new();
+        for (payee, category_id) in history {
+            let key = normalize_payee(payee);
+            if key.is_empty() {
+                continue;
+            }
+            *counts
+                .entry(key)
+                .or_default()
+                .entry(category_id)
+                .or_default() += 1;
+        }
+        // Most frequent category per payee; ties go to the smallest id so runs are repeatable
+        let learned = counts
+            .into_iter()
+            .filter_map(|(payee, categories)| {
+                categories
+                    .into_iter()
+                    .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+                    .map(|(category_id, _)| (payee, category_id.to_string()))
+            })
+            .collect();
+
+        Self {
+            rules: ordered
+                .into_iter()
+                .filter_map(CompiledRule::compile)
+                .collect(),
+            learned,
+        }
+    }
+
+    pub fn categorize(
+        &self,
+        candidate: &CategorizationCandidate,
+    ) -> Option<(String, CategorySource)> {
+        if let Some(rule) = self.rules.iter().find(|rule| rule.matches(candidate)) {
+            return Some((rule.category_id.clone(), CategorySource::Rule));
+        }
+
+        let payee = normalize_payee(candidate.comment.as_deref()?);
+        self.learned
+            .get(&payee)
+            .map(|category_id| (category_id.clone(), CategorySource::Learned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        category: &str,
+        field: RuleField,
+        match_type: RuleMatchType,
+        pattern: &str,
+        priority: i32,
+    ) -> CategorizationRule {
+        let now = chrono::Utc::now().naive_utc();
+        CategorizationRule {
+            id: format!("{}-{}", category, pattern),
+            category_id: category.to_string(),
+            field,
+            match_type,
+            pattern: pattern.to_string(),
+            priority,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn candidate(comment: &str) -> CategorizationCandidate {
+        CategorizationCandidate {
+            activity_id: "a1".to_string(),
+            account_id: "vcb".to_string(),
+            asset_id: "$CASH-VND".to_string(),
+            activity_type: "WITHDRAWAL".to_string(),
+            comment: Some(comment.to_string()),
+            category_id: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn normalizes_bank_descriptions() {
+        assert_eq!(normalize_payee("GRAB*8841 HCM"), "grab hcm");
+        assert_eq!(normalize_payee("Grab 1290 HCM"), "grab hcm");
+        assert_eq!(
+            normalize_payee("Điện lực EVN HCMC 05/2026"),
+            "điện lực evn hcmc"
+        );
+    }
+
+    #[test]
+    fn rules_win_by_priority_then_history_fills_gaps() {
+        let rules = vec![
+            rule(
+                "transport",
+                RuleField::Payee,
+                RuleMatchType::Contains,
+                "grab",
+                0,
+            ),
+            rule(
+                "food",
+                RuleField::Payee,
+                RuleMatchType::Regex,
+                r"grab\s*food",
+                10,
+            ),
+        ];
+        let engine = CategorizationEngine::new(
+            &rules,
+            [
+                ("Circle K 123", "groceries"),
+                ("CIRCLE K 456", "groceries"),
+                ("Circle K 789", "snacks"),
+            ],
+        );
+
+        assert_eq!(
+            engine.categorize(&candidate("GRABFOOD*1234")),
+            Some(("food".to_string(), CategorySource::Rule))
+        );
+        assert_eq!(
+            engine.categorize(&candidate("GRAB*8841 HCM")),
+            Some(("transport".to_string(), CategorySource::Rule))
+        );
+        assert_eq!(
+            engine.categorize(&candidate("CIRCLE K 999")),
+            Some(("groceries".to_string(), CategorySource::Learned))
+        );
+        assert_eq!(engine.categorize(&candidate("Shopee 42")), None);
+    }
+}
@@ -0,0 +1,214 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::activities::{
+    ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_FEE, ACTIVITY_TYPE_INTEREST,
+    ACTIVITY_TYPE_TAX, ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT,
+    ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::errors::{Error, Result, ValidationError};
+
+/// Activity types that can carry a spending category; trades are never categorized
+pub const CATEGORIZABLE_ACTIVITY_TYPES: [&str; 8] = [
+    ACTIVITY_TYPE_WITHDRAWAL,
+    ACTIVITY_TYPE_DEPOSIT,
+    ACTIVITY_TYPE_TRANSFER_IN,
+    ACTIVITY_TYPE_TRANSFER_OUT,
+    ACTIVITY_TYPE_FEE,
+    ACTIVITY_TYPE_TAX,
+    ACTIVITY_TYPE_INTEREST,
+    ACTIVITY_TYPE_DIVIDEND,
+];
+
+/// Activity attribute a rule is matched against
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RuleField {
+    /// The activity comment, where bank imports put the payee/description
+    Payee,
+    AccountId,
+    AssetId,
+    ActivityType,
+}
+
+impl RuleField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleField::Payee => "PAYEE",
+            RuleField::AccountId => "ACCOUNT_ID",
+            RuleField::AssetId => "ASSET_ID",
+            RuleField::ActivityType => "ACTIVITY_TYPE",
+        }
+    }
+}
+
+impl FromStr for RuleField {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "PAYEE" => Ok(RuleField::Payee),
+            "ACCOUNT_ID" => Ok(RuleField::AccountId),
+            "ASSET_ID" => Ok(RuleField::AssetId),
+            "ACTIVITY_TYPE" => Ok(RuleField::ActivityType),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown rule field: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// How a rule pattern is compared; text comparisons ignore case
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RuleMatchType {
+    Contains,
+    Equals,
+    StartsWith,
+    Regex,
+}
+
+impl RuleMatchType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleMatchType::Contains => "CONTAINS",
+            RuleMatchType::Equals => "EQUALS",
+            RuleMatchType::StartsWith => "STARTS_WITH",
+            RuleMatchType::Regex => "REGEX",
+        }
+    }
+}
+
+impl FromStr for RuleMatchType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "CONTAINS" => Ok(RuleMatchType::Contains),
+            "EQUALS" => Ok(RuleMatchType::Equals),
+            "STARTS_WITH" => Ok(RuleMatchType::StartsWith),
+            "REGEX" => Ok(RuleMatchType::Regex),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown rule match type: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `categorization_rules`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::categorization_rules)]
+pub struct CategorizationRuleDB {
+    pub id: String,
+    pub category_id: String,
+    pub field: String,
+    pub match_type: String,
+    pub pattern: String,
+    pub priority: i32,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorizationRule {
+    pub id: String,
+    pub category_id: String,
+    pub field: RuleField,
+    pub match_type: RuleMatchType,
+    pub pattern: String,
+    /// Rules are tried from the highest priority down; the first match wins
+    pub priority: i32,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<CategorizationRuleDB> for CategorizationRule {
+    type Error = Error;
+
+    fn try_from(db: CategorizationRuleDB) -> Result<Self> {
+        Ok(CategorizationRule {
+            id: db.id,
+            category_id: db.category_id,
+            field: db.field.parse()?,
+            match_type: db.match_type.parse()?,
+            pattern: db.pattern,
+            priority: db.priority,
+            is_active: db.is_active,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a categorization rule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewCategorizationRule {
+    pub id: Option<String>,
+    pub category_id: String,
+    pub field: RuleField,
+    pub match_type: RuleMatchType,
+    pub pattern: String,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl NewCategorizationRule {
+    pub fn validate(&self) -> Result<()> {
+        if self.category_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "categoryId".to_string(),
+            )));
+        }
+        if self.pattern.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "pattern".to_string(),
+            )));
+        }
+        if self.match_type == RuleMatchType::Regex {
+            regex::Regex::new(&self.pattern).map_err(|e| {
+                Error::Validation(ValidationError::InvalidInput(format!(
+                    "Invalid rule pattern: {}",
+                    e
+                )))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// An activity that categorization may assign, with its current category if any
+#[derive(Debug, Clone)]
+pub struct CategorizationCandidate {
+    pub activity_id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub activity_type: String,
+    pub comment: Option<String>,
+    pub category_id: Option<String>,
+    pub source: Option<String>,
+}
+
+/// Counts from an auto-categorization run
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorizationResult {
+    pub categorized_by_rule: usize,
+    pub categorized_by_history: usize,
+    /// Automatic categories removed because nothing matches any more
+    pub cleared: usize,
+    pub uncategorized: usize,
+}
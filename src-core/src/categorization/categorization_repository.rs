@@ -0,0 +1,179 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::categorization_model::{
+    CategorizationCandidate, CategorizationRule, CategorizationRuleDB, NewCategorizationRule,
+    CATEGORIZABLE_ACTIVITY_TYPES,
+};
+use super::categorization_traits::CategorizationRepositoryTrait;
+use crate::budgets::ActivityCategory;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::{activities, activity_categories, categorization_rules};
+
+pub struct CategorizationRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl CategorizationRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        CategorizationRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl CategorizationRepositoryTrait for CategorizationRepository {
+    fn get_rules(&self) -> Result<Vec<CategorizationRule>> {
+        let mut conn = get_connection(&self.pool)?;
+        categorization_rules::table
+            .order((
+                categorization_rules::priority.desc(),
+                categorization_rules::created_at.asc(),
+            ))
+            .load::<CategorizationRuleDB>(&mut conn)?
+            .into_iter()
+            .map(CategorizationRule::try_from)
+            .collect()
+    }
+
+    async fn insert_rule(&self, rule: NewCategorizationRule) -> Result<CategorizationRule> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<CategorizationRule> {
+                    let now = chrono::Utc::now().naive_utc();
+                    let record = CategorizationRuleDB {
+                        id: rule.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        category_id: rule.category_id,
+                        field: rule.field.as_str().to_string(),
+                        match_type: rule.match_type.as_str().to_string(),
+                        pattern: rule.pattern,
+                        priority: rule.priority,
+                        is_active: rule.is_active,
+                        created_at: now,
+                        updated_at: now,
+                    };
+
+                    diesel::insert_into(categorization_rules::table)
+                        .values(&record)
+                        .get_result::<CategorizationRuleDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn update_rule(
+        &self,
+        id: &str,
+        rule: NewCategorizationRule,
+    ) -> Result<CategorizationRule> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<CategorizationRule> {
+                    diesel::update(categorization_rules::table.find(id_owned))
+                        .set((
+                            categorization_rules::category_id.eq(rule.category_id),
+                            categorization_rules::field.eq(rule.field.as_str()),
+                            categorization_rules::match_type.eq(rule.match_type.as_str()),
+                            categorization_rules::pattern.eq(rule.pattern),
+                            categorization_rules::priority.eq(rule.priority),
+                            categorization_rules::is_active.eq(rule.is_active),
+                            categorization_rules::updated_at.eq(chrono::Utc::now().naive_utc()),
+                        ))
+                        .get_result::<CategorizationRuleDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_rule(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(categorization_rules::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_candidates(&self) -> Result<Vec<CategorizationCandidate>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = activities::table
+            .left_join(activity_categories::table)
+            .filter(activities::is_draft.eq(false))
+            .filter(activities::activity_type.eq_any(CATEGORIZABLE_ACTIVITY_TYPES))
+            .select((
+                activities::id,
+                activities::account_id,
+                activities::asset_id,
+                activities::activity_type,
+                activities::comment,
+                activity_categories::category_id.nullable(),
+                activity_categories::source.nullable(),
+            ))
+            .load::<(
+                String,
+                String,
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+            )>(&mut conn)?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    activity_id,
+                    account_id,
+                    asset_id,
+                    activity_type,
+                    comment,
+                    category_id,
+                    source,
+                )| {
+                    CategorizationCandidate {
+                        activity_id,
+                        account_id,
+                        asset_id,
+                        activity_type,
+                        comment,
+                        category_id,
+                        source,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    async fn apply_assignments(
+        &self,
+        assignments: Vec<ActivityCategory>,
+        cleared: Vec<String>,
+    ) -> Result<()> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                if !cleared.is_empty() {
+                    diesel::delete(
+                        activity_categories::table
+                            .filter(activity_categories::activity_id.eq_any(cleared)),
+                    )
+                    .execute(conn)?;
+                }
+                for chunk in assignments.chunks(500) {
+                    diesel::replace_into(activity_categories::table)
+                        .values(chunk)
+                        .execute(conn)?;
+                }
+                Ok(())
+            })
+            .await
+    }
+}
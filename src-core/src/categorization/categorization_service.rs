@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::categorization_engine::CategorizationEngine;
+use super::categorization_model::{
+    CategorizationCandidate, CategorizationResult, CategorizationRule, NewCategorizationRule,
+};
+use super::categorization_traits::{CategorizationRepositoryTrait, CategorizationServiceTrait};
+use crate::budgets::{ActivityCategory, BudgetRepositoryTrait, CategorySource};
+use crate::errors::{Error, Result, ValidationError};
+
+pub struct CategorizationService {
+    repository: Arc<dyn CategorizationRepositoryTrait>,
+    budget_repository: Arc<dyn BudgetRepositoryTrait>,
+}
+
+impl CategorizationService {
+    pub fn new(
+        repository: Arc<dyn CategorizationRepositoryTrait>,
+        budget_repository: Arc<dyn BudgetRepositoryTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            budget_repository,
+        }
+    }
+
+    fn validate_rule(&self, rule: &NewCategorizationRule) -> Result<()> {
+        rule.validate()?;
+        let category = self.budget_repository.get_category(&rule.category_id)?;
+        if category.is_archived {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Category '{}' is archived",
+                category.name
+            ))));
+        }
+        Ok(())
+    }
+
+    /// Builds an engine from active rules and manual categorizations, skipping archived categories
+    fn build_engine(&self, candidates: &[CategorizationCandidate]) -> Result<CategorizationEngine> {
+        let assignable: HashSet<String> = self
+            .budget_repository
+            .get_categories()?
+            .into_iter()
+            .filter(|category| !category.is_archived)
+            .map(|category| category.id)
+            .collect();
+
+        let rules: Vec<CategorizationRule> = self
+            .repository
+            .get_rules()?
+            .into_iter()
+            .filter(|rule| rule.is_active && assignable.contains(&rule.category_id))
+            .collect();
+
+        let manual = CategorySource::Manual.as_str();
+        let history = candidates.iter().filter_map(|candidate| {
+            match (
+                &candidate.comment,
+                &candidate.category_id,
+                &candidate.source,
+            ) {
+                (Some(comment), Some(category_id), Some(source))
+                    if source == manual && assignable.contains(category_id) =>
+                {
+                    Some((comment.as_str(), category_id.as_str()))
+                }
+                _ => None,
+            }
+        });
+
+        Ok(CategorizationEngine::new(&rules, history))
+    }
+
+    /// Categorizes the candidates selected by `include`. When `clear_unmatched` is set, automatic
+    /// categories that no longer match anything are removed.
+    async fn run(
+        &self,
+        include: impl Fn(&CategorizationCandidate) -> bool,
+        clear_unmatched: bool,
+    ) -> Result<CategorizationResult> {
+        let candidates = self.repository.get_candidates()?;
+        let engine = self.build_engine(&candidates)?;
+
+        let mut result = CategorizationResult::default();
+        let mut assignments = Vec::new();
+        let mut cleared = Vec::new();
+        for candidate in candidates.iter().filter(|c| include(c)) {
+            match engine.categorize(candidate) {
+                Some((category_id, source)) => {
+                    match source {
+                        CategorySource::Learned => result.categorized_by_history += 1,
+                        _ => result.categorized_by_rule += 1,
+                    }
+                    let unchanged = candidate.category_id.as_deref() == Some(category_id.as_str())
+                        && candidate.source.as_deref() == Some(source.as_str());
+                    if !unchanged {
+                        assignments.push(ActivityCategory {
+                            activity_id: candidate.activity_id.clone(),
+                            category_id,
+                            source: source.as_str().to_string(),
+                        });
+                    }
+                }
+                None if clear_unmatched && candidate.category_id.is_some() => {
+                    result.cleared += 1;
+                    cleared.push(candidate.activity_id.clone());
+                }
+                None => result.uncategorized += 1,
+            }
+        }
+
+        if !assignments.is_empty() || !cleared.is_empty() {
+            self.repository
+                .apply_assignments(assignments, cleared)
+                .await?;
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl CategorizationServiceTrait for CategorizationService {
+    fn get_categorization_rules(&self) -> Result<Vec<CategorizationRule>> {
+        self.repository.get_rules()
+    }
+
+    async fn create_categorization_rule(
+        &self,
+        rule: NewCategorizationRule,
+    ) -> Result<CategorizationRule> {
+        self.validate_rule(&rule)?;
+        self.repository.insert_rule(rule).await
+    }
+
+    async fn update_categorization_rule(
+        &self,
+        id: &str,
+        rule: NewCategorizationRule,
+    ) -> Result<CategorizationRule> {
+        self.validate_rule(&rule)?;
+        self.repository.update_rule(id, rule).await
+    }
+
+    async fn delete_categorization_rule(&self, id: &str) -> Result<usize> {
+        self.repository.delete_rule(id).await
+    }
+
+    async fn auto_categorize(&self) -> Result<CategorizationResult> {
+        self.run(|candidate| candidate.category_id.is_none(), false)
+            .await
+    }
+
+    async fn recategorize_all(&self) -> Result<CategorizationResult> {
+        let manual = CategorySource::Manual.as_str();
+        self.run(
+            |candidate| candidate.source.as_deref() != Some(manual),
+            true,
+        )
+        .await
+    }
+}
@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+
+use super::categorization_model::{
+    CategorizationCandidate, CategorizationResult, CategorizationRule, NewCategorizationRule,
+};
+use crate::budgets::ActivityCategory;
+use crate::errors::Result;
+
+#[async_trait]
+pub trait CategorizationRepositoryTrait: Send + Sync {
+    fn get_rules(&self) -> Result<Vec<CategorizationRule>>;
+    async fn insert_rule(&self, rule: NewCategorizationRule) -> Result<CategorizationRule>;
+    async fn update_rule(
+        &self,
+        id: &str,
+        rule: NewCategorizationRule,
+    ) -> Result<CategorizationRule>;
+    async fn delete_rule(&self, id: &str) -> Result<usize>;
+    /// Non-draft activities of a categorizable type, with their current category
+    fn get_candidates(&self) -> Result<Vec<CategorizationCandidate>>;
+    /// Writes new automatic assignments and removes `cleared` ones in one transaction
+    async fn apply_assignments(
+        &self,
+        assignments: Vec<ActivityCategory>,
+        cleared: Vec<String>,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait CategorizationServiceTrait: Send + Sync {
+    fn get_categorization_rules(&self) -> Result<Vec<CategorizationRule>>;
+    async fn create_categorization_rule(
+        &self,
+        rule: NewCategorizationRule,
+    ) -> Result<CategorizationRule>;
+    async fn update_categorization_rule(
+        &self,
+        id: &str,
+        rule: NewCategorizationRule,
+    ) -> Result<CategorizationRule>;
+    async fn delete_categorization_rule(&self, id: &str) -> Result<usize>;
+    /// Categorizes activities that have no category yet, e.g. after an import
+    async fn auto_categorize(&self) -> Result<CategorizationResult>;
+    /// Re-runs rules and learning over every activity not categorized by hand
+    async fn recategorize_all(&self) -> Result<CategorizationResult>;
+}
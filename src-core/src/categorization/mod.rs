@@ -0,0 +1,14 @@
+mod categorization_engine;
+mod categorization_model;
+mod categorization_repository;
+mod categorization_service;
+mod categorization_traits;
+
+pub use categorization_engine::{normalize_payee, CategorizationEngine};
+pub use categorization_model::{
+    CategorizationCandidate, CategorizationResult, CategorizationRule, NewCategorizationRule,
+    RuleField, RuleMatchType, CATEGORIZABLE_ACTIVITY_TYPES,
+};
+pub use categorization_repository::CategorizationRepository;
+pub use categorization_service::CategorizationService;
+pub use categorization_traits::{CategorizationRepositoryTrait, CategorizationServiceTrait};
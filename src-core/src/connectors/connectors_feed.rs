@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use reqwest::{Client, StatusCode};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::connectors_model::{
+    connection_secret_id, BankConnection, ConnectorProvider, ConnectorTransaction,
+};
+use super::connectors_traits::{BankConnector, ConnectorTokenStore};
+use crate::errors::{Error, Result};
+use crate::secrets::SecretManager;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Token-authenticated JSON feed: `GET {base_url}?since=YYYY-MM-DD` with
+/// `Authorization: Bearer <token>`, answering `{"transactions": [...]}`.
+/// Timo and Cake statement exports and broker APIs are reached through this contract.
+pub struct TokenFeedConnector {
+    provider: ConnectorProvider,
+    client: Client,
+}
+
+impl TokenFeedConnector {
+    pub fn new(provider: ConnectorProvider) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        TokenFeedConnector { provider, client }
+    }
+}
+
+/// The built-in connector of every provider
+pub fn default_connectors() -> Vec<Arc<dyn BankConnector>> {
+    [
+        ConnectorProvider::Timo,
+        ConnectorProvider::Cake,
+        ConnectorProvider::BrokerApi,
+    ]
+    .into_iter()
+    .map(|provider| Arc::new(TokenFeedConnector::new(provider)) as Arc<dyn BankConnector>)
+    .collect()
+}
+
+#[derive(Deserialize)]
+struct FeedResponse {
+    transactions: Vec<FeedTransaction>,
+}
+
+/// Numbers may come as JSON numbers or strings
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedTransaction {
+    id: Value,
+    date: String,
+    #[serde(default, rename = "type")]
+    activity_type: Option<String>,
+    #[serde(default)]
+    symbol: Option<String>,
+    #[serde(default)]
+    quantity: Value,
+    #[serde(default)]
+    unit_price: Value,
+    amount: Value,
+    #[serde(default)]
+    fee: Value,
+    currency: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+fn feed_decimal(value: &Value, field: &str, id: &str) -> Result<Option<Decimal>> {
+    let parsed = match value {
+        Value::Null => return Ok(None),
+        Value::Number(number) => Decimal::from_str(&number.to_string()).ok(),
+        Value::String(text) if text.trim().is_empty() => return Ok(None),
+        Value::String(text) => Decimal::from_str(text.trim()).ok(),
+        _ => None,
+    };
+    parsed
+        .map(Some)
+        .ok_or_else(|| Error::Connector(format!("Transaction {}: invalid {} {}", id, field, value)))
+}
+
+impl FeedTransaction {
+    fn into_transaction(self) -> Result<ConnectorTransaction> {
+        let id = match &self.id {
+            Value::String(text) => text.clone(),
+            Value::Number(number) => number.to_string(),
+            other => {
+                return Err(Error::Connector(format!(
+                    "Invalid transaction id {}",
+                    other
+                )))
+            }
+        };
+        // Banks often send timestamps; only the day matters
+        let date = self
+            .date
+            .get(..10)
+            .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+            .ok_or_else(|| {
+                Error::Connector(format!("Transaction {}: invalid date {}", id, self.date))
+            })?;
+        let amount = feed_decimal(&self.amount, "amount", &id)?
+            .ok_or_else(|| Error::Connector(format!("Transaction {}: missing amount", id)))?;
+        Ok(ConnectorTransaction {
+            date,
+            activity_type: self
+                .activity_type
+                .map(|t| t.trim().to_uppercase())
+                .filter(|t| !t.is_empty()),
+            symbol: self
+                .symbol
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty()),
+            quantity: feed_decimal(&self.quantity, "quantity", &id)?,
+            unit_price: feed_decimal(&self.unit_price, "unitPrice", &id)?,
+            amount,
+            fee: feed_decimal(&self.fee, "fee", &id)?,
+            currency: self.currency.trim().to_uppercase(),
+            description: self.description.filter(|d| !d.trim().is_empty()),
+            external_id: id,
+        })
+    }
+}
+
+/// Parses a feed body; a malformed transaction fails the whole pull so nothing is half-imported
+pub(crate) fn parse_feed(body: &str) -> Result<Vec<ConnectorTransaction>> {
+    let response: FeedResponse = serde_json::from_str(body)
+        .map_err(|e| Error::Connector(format!("Unexpected feed response: {}", e)))?;
+    response
+        .transactions
+        .into_iter()
+        .map(FeedTransaction::into_transaction)
+        .collect()
+}
+
+#[async_trait]
+impl BankConnector for TokenFeedConnector {
+    fn provider(&self) -> ConnectorProvider {
+        self.provider
+    }
+
+    async fn fetch_transactions(
+        &self,
+        connection: &BankConnection,
+        token: &str,
+        since: Option<NaiveDate>,
+    ) -> Result<Vec<ConnectorTransaction>> {
+        let mut request = self.client.get(&connection.base_url).bearer_auth(token);
+        if let Some(since) = since {
+            request = request.query(&[("since", since.format("%Y-%m-%d").to_string())]);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Connector(format!("{}: {}", connection.name, e)))?;
+        match response.status() {
+            status if status.is_success() => {}
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                return Err(Error::Connector(format!(
+                    "{}: the access token was rejected",
+                    connection.name
+                )))
+            }
+            status => {
+                return Err(Error::Connector(format!(
+                    "{}: feed returned {}",
+                    connection.name, status
+                )))
+            }
+        }
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::Connector(format!("{}: {}", connection.name, e)))?;
+        parse_feed(&body)
+    }
+}
+
+/// Keeps tokens in the operating system keyring next to the market data API keys
+#[derive(Default)]
+pub struct KeyringTokenStore;
+
+impl ConnectorTokenStore for KeyringTokenStore {
+    fn get_token(&self, connection_id: &str) -> Result<Option<String>> {
+        SecretManager::get_secret(&connection_secret_id(connection_id))
+    }
+
+    fn set_token(&self, connection_id: &str, token: &str) -> Result<()> {
+        SecretManager::set_secret(&connection_secret_id(connection_id), token)
+    }
+
+    fn delete_token(&self, connection_id: &str) -> Result<()> {
+        SecretManager::delete_secret(&connection_secret_id(connection_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn parses_numbers_and_strings_and_timestamps() {
+        let body = r#"{"transactions": [
+            {"id": 42, "date": "2026-10-01T08:30:00+07:00", "amount": -150000, "currency": "vnd", "description": "Grab"},
+            {"id": "t-2", "date": "2026-10-02", "type": "buy", "symbol": "fpt", "quantity": "100", "unitPrice": "95000.5", "amount": "-9500050", "currency": "VND"}
+        ]}"#;
+        let transactions = parse_feed(body).unwrap();
+        assert_eq!(transactions[0].external_id, "42");
+        assert_eq!(
+            transactions[0].date,
+            NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()
+        );
+        assert_eq!(transactions[0].amount, dec!(-150000));
+        assert_eq!(transactions[0].currency, "VND");
+        assert_eq!(transactions[1].activity_type.as_deref(), Some("BUY"));
+        assert_eq!(transactions[1].symbol.as_deref(), Some("FPT"));
+        assert_eq!(transactions[1].unit_price, Some(dec!(95000.5)));
+
+        let bad = r#"{"transactions": [{"id": 1, "date": "2026-10-01", "amount": "abc", "currency": "VND"}]}"#;
+        assert!(parse_feed(bad).is_err());
+    }
+}
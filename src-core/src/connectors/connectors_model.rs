@@ -0,0 +1,197 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Longest gap between scheduled pulls, in hours
+pub const MAX_SYNC_INTERVAL_HOURS: i32 = 24 * 7;
+
+/// Bank or broker a connection pulls from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ConnectorProvider {
+    Timo,
+    Cake,
+    /// Any broker exposing the transaction feed with a bearer token
+    BrokerApi,
+}
+
+impl ConnectorProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConnectorProvider::Timo => "TIMO",
+            ConnectorProvider::Cake => "CAKE",
+            ConnectorProvider::BrokerApi => "BROKER_API",
+        }
+    }
+
+    /// Bank feeds carry cash movements only; broker feeds also carry trades
+    pub fn is_bank(&self) -> bool {
+        matches!(self, ConnectorProvider::Timo | ConnectorProvider::Cake)
+    }
+}
+
+impl FromStr for ConnectorProvider {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "TIMO" => Ok(ConnectorProvider::Timo),
+            "CAKE" => Ok(ConnectorProvider::Cake),
+            "BROKER_API" => Ok(ConnectorProvider::BrokerApi),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown connector provider: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `bank_connections`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::bank_connections)]
+pub struct BankConnectionDB {
+    pub id: String,
+    pub provider: String,
+    pub name: String,
+    pub account_id: String,
+    pub base_url: String,
+    pub sync_interval_hours: i32,
+    pub is_enabled: bool,
+    pub last_attempt_at: Option<NaiveDateTime>,
+    pub last_synced_at: Option<NaiveDateTime>,
+    pub last_sync_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A feed pulled into one account on a schedule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BankConnection {
+    pub id: String,
+    pub provider: ConnectorProvider,
+    pub name: String,
+    pub account_id: String,
+    pub base_url: String,
+    pub sync_interval_hours: i32,
+    pub is_enabled: bool,
+    /// Last pull, successful or not; the schedule counts from here
+    pub last_attempt_at: Option<NaiveDateTime>,
+    /// Last successful pull
+    pub last_synced_at: Option<NaiveDateTime>,
+    /// Error of the last pull, cleared once a pull succeeds
+    pub last_sync_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Keyring entry holding a connection's access token
+pub(crate) fn connection_secret_id(connection_id: &str) -> String {
+    format!("connector_{}", connection_id)
+}
+
+impl TryFrom<BankConnectionDB> for BankConnection {
+    type Error = Error;
+
+    fn try_from(db: BankConnectionDB) -> Result<Self> {
+        Ok(BankConnection {
+            id: db.id,
+            provider: ConnectorProvider::from_str(&db.provider)?,
+            name: db.name,
+            account_id: db.account_id,
+            base_url: db.base_url,
+            sync_interval_hours: db.sync_interval_hours,
+            is_enabled: db.is_enabled,
+            last_attempt_at: db.last_attempt_at,
+            last_synced_at: db.last_synced_at,
+            last_sync_error: db.last_sync_error,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewBankConnection {
+    pub id: Option<String>,
+    pub provider: ConnectorProvider,
+    pub name: String,
+    pub account_id: String,
+    pub base_url: String,
+    pub sync_interval_hours: i32,
+    pub is_enabled: bool,
+    /// Stored in the keyring, never in the database; left out on update to keep the current one
+    #[serde(default, skip_serializing)]
+    pub token: Option<String>,
+}
+
+impl NewBankConnection {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.account_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "accountId".to_string(),
+            )));
+        }
+        // Tokens are sent as bearer headers, so only local test feeds may skip TLS
+        let url = self.base_url.trim();
+        let is_local = ["http://localhost", "http://127.0.0.1"]
+            .iter()
+            .any(|prefix| url.starts_with(prefix));
+        if !url.starts_with("https://") && !is_local {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Feed URL must use https".to_string(),
+            )));
+        }
+        if !(1..=MAX_SYNC_INTERVAL_HOURS).contains(&self.sync_interval_hours) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Sync interval must be between 1 and {} hours",
+                MAX_SYNC_INTERVAL_HOURS
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// One transaction as a connector reports it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectorTransaction {
+    /// Provider's id, used to skip transactions already imported
+    pub external_id: String,
+    pub date: NaiveDate,
+    /// Activity type when the provider gives one (trades); cash rows are typed by sign
+    pub activity_type: Option<String>,
+    pub symbol: Option<String>,
+    pub quantity: Option<Decimal>,
+    pub unit_price: Option<Decimal>,
+    /// Signed cash amount: positive into the account, negative out of it
+    pub amount: Decimal,
+    pub fee: Option<Decimal>,
+    pub currency: String,
+    pub description: Option<String>,
+}
+
+/// Outcome of one pull
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectorSyncResult {
+    pub connection_id: String,
+    pub connection_name: String,
+    pub fetched: usize,
+    pub imported: usize,
+    /// Already imported by an earlier pull
+    pub duplicates: usize,
+    /// Rows the import check rejected; not recorded, so a pull that still covers them retries them
+    pub invalid: usize,
+    pub error: Option<String>,
+}
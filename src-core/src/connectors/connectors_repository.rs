@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::connectors_model::{BankConnection, BankConnectionDB, NewBankConnection};
+use super::connectors_traits::ConnectorRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::{bank_connection_imports, bank_connections};
+
+pub struct ConnectorRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl ConnectorRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        ConnectorRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl ConnectorRepositoryTrait for ConnectorRepository {
+    fn get_connections(&self) -> Result<Vec<BankConnection>> {
+        let mut conn = get_connection(&self.pool)?;
+        bank_connections::table
+            .order(bank_connections::created_at.asc())
+            .load::<BankConnectionDB>(&mut conn)?
+            .into_iter()
+            .map(BankConnection::try_from)
+            .collect()
+    }
+
+    fn get_connection(&self, id: &str) -> Result<BankConnection> {
+        let mut conn = get_connection(&self.pool)?;
+        bank_connections::table
+            .find(id)
+            .first::<BankConnectionDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_connection(&self, connection: NewBankConnection) -> Result<BankConnection> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<BankConnection> {
+                    let now = Utc::now().naive_utc();
+                    let record = BankConnectionDB {
+                        id: connection.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        provider: connection.provider.as_str().to_string(),
+                        name: connection.name.trim().to_string(),
+                        account_id: connection.account_id,
+                        base_url: connection.base_url.trim().to_string(),
+                        sync_interval_hours: connection.sync_interval_hours,
+                        is_enabled: connection.is_enabled,
+                        last_attempt_at: None,
+                        last_synced_at: None,
+                        last_sync_error: None,
+                        created_at: now,
+                        updated_at: now,
+                    };
+
+                    diesel::insert_into(bank_connections::table)
+                        .values(&record)
+                        .get_result::<BankConnectionDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn update_connection(
+        &self,
+        id: &str,
+        connection: NewBankConnection,
+    ) -> Result<BankConnection> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<BankConnection> {
+                    diesel::update(bank_connections::table.find(id_owned))
+                        .set((
+                            bank_connections::provider.eq(connection.provider.as_str()),
+                            bank_connections::name.eq(connection.name.trim()),
+                            bank_connections::account_id.eq(connection.account_id),
+                            bank_connections::base_url.eq(connection.base_url.trim()),
+                            bank_connections::sync_interval_hours
+                                .eq(connection.sync_interval_hours),
+                            bank_connections::is_enabled.eq(connection.is_enabled),
+                            bank_connections::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .get_result::<BankConnectionDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_connection(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                diesel::delete(
+                    bank_connection_imports::table
+                        .filter(bank_connection_imports::connection_id.eq(&id_owned)),
+                )
+                .execute(conn)?;
+                Ok(diesel::delete(bank_connections::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_imported_ids(
+        &self,
+        connection_id: &str,
+        external_ids: &[String],
+    ) -> Result<Vec<String>> {
+        let mut conn = get_connection(&self.pool)?;
+        Ok(bank_connection_imports::table
+            .filter(bank_connection_imports::connection_id.eq(connection_id))
+            .filter(bank_connection_imports::external_id.eq_any(external_ids))
+            .select(bank_connection_imports::external_id)
+            .load::<String>(&mut conn)?)
+    }
+
+    async fn record_sync(
+        &self,
+        connection_id: &str,
+        imported: Vec<(String, String)>,
+        attempted_at: NaiveDateTime,
+        error: Option<String>,
+    ) -> Result<()> {
+        let id_owned = connection_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                for (external_id, activity_id) in imported {
+                    diesel::insert_or_ignore_into(bank_connection_imports::table)
+                        .values((
+                            bank_connection_imports::connection_id.eq(&id_owned),
+                            bank_connection_imports::external_id.eq(external_id),
+                            bank_connection_imports::activity_id.eq(activity_id),
+                            bank_connection_imports::imported_at.eq(attempted_at),
+                        ))
+                        .execute(conn)?;
+                }
+                let target = bank_connections::table.find(&id_owned);
+                if error.is_none() {
+                    diesel::update(target)
+                        .set(bank_connections::last_synced_at.eq(Some(attempted_at)))
+                        .execute(conn)?;
+                }
+                diesel::update(target)
+                    .set((
+                        bank_connections::last_attempt_at.eq(Some(attempted_at)),
+                        bank_connections::last_sync_error.eq(error),
+                    ))
+                    .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+}
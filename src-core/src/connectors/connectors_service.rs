@@ -0,0 +1,417 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use log::{debug, warn};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use super::connectors_feed::{default_connectors, KeyringTokenStore};
+use super::connectors_model::{
+    BankConnection, ConnectorProvider, ConnectorSyncResult, ConnectorTransaction, NewBankConnection,
+};
+use super::connectors_traits::{
+    BankConnector, ConnectorRepositoryTrait, ConnectorServiceTrait, ConnectorTokenStore,
+};
+use crate::activities::{
+    ActivityImport, ActivityServiceTrait, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_WITHDRAWAL,
+    TRADING_ACTIVITY_TYPES,
+};
+use crate::errors::{Error, Result, ValidationError};
+use crate::scripting::ScriptServiceTrait;
+
+/// Each pull starts this many days before the last successful one, so transactions the
+/// bank books late are still picked up; already imported ids are skipped
+const SYNC_OVERLAP_DAYS: i64 = 7;
+
+pub struct ConnectorService {
+    repository: Arc<dyn ConnectorRepositoryTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    script_service: Arc<dyn ScriptServiceTrait>,
+    token_store: Arc<dyn ConnectorTokenStore>,
+    connectors: HashMap<ConnectorProvider, Arc<dyn BankConnector>>,
+}
+
+impl ConnectorService {
+    pub fn new(
+        repository: Arc<dyn ConnectorRepositoryTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        script_service: Arc<dyn ScriptServiceTrait>,
+    ) -> Self {
+        Self::with_connectors(
+            repository,
+            activity_service,
+            script_service,
+            Arc::new(KeyringTokenStore),
+            default_connectors(),
+        )
+    }
+
+    /// Uses the given token store and connectors instead of the keyring and HTTP feeds
+    pub fn with_connectors(
+        repository: Arc<dyn ConnectorRepositoryTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        script_service: Arc<dyn ScriptServiceTrait>,
+        token_store: Arc<dyn ConnectorTokenStore>,
+        connectors: Vec<Arc<dyn BankConnector>>,
+    ) -> Self {
+        ConnectorService {
+            repository,
+            activity_service,
+            script_service,
+            token_store,
+            connectors: connectors
+                .into_iter()
+                .map(|connector| (connector.provider(), connector))
+                .collect(),
+        }
+    }
+
+    fn save_token(&self, connection_id: &str, token: Option<&str>) -> Result<()> {
+        match token.map(str::trim) {
+            Some(token) if !token.is_empty() => self.token_store.set_token(connection_id, token),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fetches, de-duplicates and imports; returns the result and the imported id pairs
+    async fn pull(
+        &self,
+        connection: &BankConnection,
+    ) -> Result<(ConnectorSyncResult, Vec<(String, String)>)> {
+        let connector = self.connectors.get(&connection.provider).ok_or_else(|| {
+            Error::Connector(format!("No connector for {}", connection.provider.as_str()))
+        })?;
+        let token = self
+            .token_store
+            .get_token(&connection.id)?
+            .filter(|token| !token.is_empty())
+            .ok_or_else(|| {
+                Error::Connector(format!("{}: no access token saved", connection.name))
+            })?;
+        let since = connection
+            .last_synced_at
+            .map(|at| at.date() - Duration::days(SYNC_OVERLAP_DAYS));
+        let transactions = connector
+            .fetch_transactions(connection, &token, since)
+            .await?;
+
+        let mut result = ConnectorSyncResult {
+            connection_id: connection.id.clone(),
+            connection_name: connection.name.clone(),
+            fetched: transactions.len(),
+            imported: 0,
+            duplicates: 0,
+            invalid: 0,
+            error: None,
+        };
+        let ids: Vec<String> = transactions.iter().map(|t| t.external_id.clone()).collect();
+        let already_imported: HashSet<String> = self
+            .repository
+            .get_imported_ids(&connection.id, &ids)?
+            .into_iter()
+            .collect();
+        let mut seen = HashSet::new();
+        let fresh: Vec<ConnectorTransaction> = transactions
+            .into_iter()
+            .filter(|t| {
+                !already_imported.contains(&t.external_id) && seen.insert(t.external_id.clone())
+            })
+            .collect();
+        result.duplicates = result.fetched - fresh.len();
+        if fresh.is_empty() {
+            return Ok((result, Vec::new()));
+        }
+
+        // Same pipeline as a file import: user scripts, the import check, then the import
+        let mut rows: Vec<ActivityImport> = fresh
+            .iter()
+            .enumerate()
+            .map(|(index, transaction)| to_activity_import(transaction, connection, index))
+            .collect();
+        let report = self.script_service.run_import_row_hooks(&mut rows)?;
+        for failure in &report.failures {
+            warn!(
+                "Import script '{}' failed on {}: {}",
+                failure.script_name, connection.name, failure.message
+            );
+        }
+        let checked = self
+            .activity_service
+            .check_activities_import(connection.account_id.clone(), rows)
+            .await?;
+        let (valid, invalid): (Vec<ActivityImport>, Vec<ActivityImport>) =
+            checked.into_iter().partition(|row| row.is_valid);
+        result.invalid = invalid.len();
+        for row in &invalid {
+            warn!(
+                "{}: skipped transaction {} ({:?})",
+                connection.name,
+                external_id_of(row, &fresh).unwrap_or_default(),
+                row.errors
+            );
+        }
+        if valid.is_empty() {
+            return Ok((result, Vec::new()));
+        }
+
+        let imported = self
+            .activity_service
+            .import_activities(connection.account_id.clone(), valid)
+            .await?;
+        let pairs: Vec<(String, String)> = imported
+            .iter()
+            .filter_map(|row| Some((external_id_of(row, &fresh)?, row.id.clone()?)))
+            .collect();
+        result.imported = pairs.len();
+        debug!(
+            "{}: imported {} of {} transactions",
+            connection.name, result.imported, result.fetched
+        );
+        Ok((result, pairs))
+    }
+}
+
+/// Rows carry their position in `transactions` as the line number
+fn external_id_of(row: &ActivityImport, transactions: &[ConnectorTransaction]) -> Option<String> {
+    let index = usize::try_from(row.line_number?).ok()?.checked_sub(1)?;
+    transactions.get(index).map(|t| t.external_id.clone())
+}
+
+/// Maps a feed transaction onto an import row. Bank feeds only move cash; broker feeds
+/// keep their symbol, and trades keep quantity and price.
+pub(crate) fn to_activity_import(
+    transaction: &ConnectorTransaction,
+    connection: &BankConnection,
+    index: usize,
+) -> ActivityImport {
+    let activity_type = transaction.activity_type.clone().unwrap_or_else(|| {
+        if transaction.amount.is_sign_negative() {
+            ACTIVITY_TYPE_WITHDRAWAL.to_string()
+        } else {
+            ACTIVITY_TYPE_DEPOSIT.to_string()
+        }
+    });
+    let is_trade = TRADING_ACTIVITY_TYPES.contains(&activity_type.as_str());
+    let symbol = transaction
+        .symbol
+        .clone()
+        .filter(|_| !connection.provider.is_bank())
+        .unwrap_or_else(|| format!("$CASH-{}", transaction.currency));
+    let (quantity, unit_price) = if is_trade {
+        (
+            transaction.quantity.unwrap_or_default().abs(),
+            transaction.unit_price.unwrap_or_default().abs(),
+        )
+    } else {
+        Default::default()
+    };
+
+    ActivityImport {
+        id: None,
+        date: transaction.date.format("%Y-%m-%d").to_string(),
+        symbol,
+        activity_type,
+        quantity,
+        unit_price,
+        currency: transaction.currency.clone(),
+        fee: transaction.fee.unwrap_or_default().abs(),
+        amount: Some(transaction.amount.abs()),
+        comment: transaction.description.clone(),
+        account_id: Some(connection.account_id.clone()),
+        account_name: None,
+        symbol_name: None,
+        errors: None,
+        warnings: None,
+        is_draft: false,
+        is_valid: false,
+        line_number: Some(index as i32 + 1),
+        asset_data_source: None,
+    }
+}
+
+fn is_due(connection: &BankConnection, now: NaiveDateTime) -> bool {
+    connection.is_enabled
+        && connection
+            .last_attempt_at
+            .is_none_or(|at| now - at >= Duration::hours(i64::from(connection.sync_interval_hours)))
+}
+
+#[async_trait]
+impl ConnectorServiceTrait for ConnectorService {
+    fn get_connections(&self) -> Result<Vec<BankConnection>> {
+        self.repository.get_connections()
+    }
+
+    fn get_connection(&self, id: &str) -> Result<BankConnection> {
+        self.repository.get_connection(id)
+    }
+
+    async fn create_connection(&self, connection: NewBankConnection) -> Result<BankConnection> {
+        connection.validate()?;
+        if connection
+            .token
+            .as_deref()
+            .is_none_or(|t| t.trim().is_empty())
+        {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "token".to_string(),
+            )));
+        }
+        let token = connection.token.clone();
+        let created = self.repository.insert_connection(connection).await?;
+        self.save_token(&created.id, token.as_deref())?;
+        Ok(created)
+    }
+
+    async fn update_connection(
+        &self,
+        id: &str,
+        connection: NewBankConnection,
+    ) -> Result<BankConnection> {
+        connection.validate()?;
+        let token = connection.token.clone();
+        let updated = self.repository.update_connection(id, connection).await?;
+        self.save_token(id, token.as_deref())?;
+        Ok(updated)
+    }
+
+    async fn delete_connection(&self, id: &str) -> Result<usize> {
+        let deleted = self.repository.delete_connection(id).await?;
+        self.token_store.delete_token(id)?;
+        Ok(deleted)
+    }
+
+    async fn sync_connection(&self, id: &str) -> Result<ConnectorSyncResult> {
+        let connection = self.repository.get_connection(id)?;
+        let attempted_at = Utc::now().naive_utc();
+        match self.pull(&connection).await {
+            Ok((result, imported)) => {
+                self.repository
+                    .record_sync(id, imported, attempted_at, None)
+                    .await?;
+                Ok(result)
+            }
+            Err(e) => {
+                self.repository
+                    .record_sync(id, Vec::new(), attempted_at, Some(e.to_string()))
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn sync_due_connections(&self) -> Result<Vec<ConnectorSyncResult>> {
+        let now = Utc::now().naive_utc();
+        let mut results = Vec::new();
+        for connection in self.repository.get_connections()? {
+            if !is_due(&connection, now) {
+                continue;
+            }
+            // One failing bank must not stop the others
+            let result = self
+                .sync_connection(&connection.id)
+                .await
+                .unwrap_or_else(|e| ConnectorSyncResult {
+                    connection_id: connection.id.clone(),
+                    connection_name: connection.name.clone(),
+                    fetched: 0,
+                    imported: 0,
+                    duplicates: 0,
+                    invalid: 0,
+                    error: Some(e.to_string()),
+                });
+            results.push(result);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    fn connection(provider: ConnectorProvider) -> BankConnection {
+        let now = Utc::now().naive_utc();
+        BankConnection {
+            id: "conn-1".to_string(),
+            provider,
+            name: "Timo".to_string(),
+            account_id: "acc-1".to_string(),
+            base_url: "https://feed.example".to_string(),
+            sync_interval_hours: 24,
+            is_enabled: true,
+            last_attempt_at: None,
+            last_synced_at: None,
+            last_sync_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn transaction(amount: rust_decimal::Decimal) -> ConnectorTransaction {
+        ConnectorTransaction {
+            external_id: "tx-1".to_string(),
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            activity_type: None,
+            symbol: None,
+            quantity: None,
+            unit_price: None,
+            amount,
+            fee: None,
+            currency: "VND".to_string(),
+            description: Some("Lương tháng 9".to_string()),
+        }
+    }
+
+    #[test]
+    fn bank_rows_become_cash_activities_typed_by_sign() {
+        let bank = connection(ConnectorProvider::Timo);
+
+        let salary = to_activity_import(&transaction(dec!(25_000_000)), &bank, 0);
+        assert_eq!(salary.activity_type, "DEPOSIT");
+        assert_eq!(salary.symbol, "$CASH-VND");
+        assert_eq!(salary.amount, Some(dec!(25_000_000)));
+        assert_eq!(salary.date, "2026-10-01");
+        assert_eq!(salary.line_number, Some(1));
+
+        let mut spend = transaction(dec!(-150_000));
+        // Banks cannot hold securities, whatever the feed says
+        spend.symbol = Some("FPT".to_string());
+        let spend = to_activity_import(&spend, &bank, 1);
+        assert_eq!(spend.activity_type, "WITHDRAWAL");
+        assert_eq!(spend.symbol, "$CASH-VND");
+        assert_eq!(spend.amount, Some(dec!(150_000)));
+    }
+
+    #[test]
+    fn broker_trades_keep_symbol_quantity_and_price() {
+        let broker = connection(ConnectorProvider::BrokerApi);
+        let mut buy = transaction(dec!(-9_500_000));
+        buy.activity_type = Some("BUY".to_string());
+        buy.symbol = Some("FPT".to_string());
+        buy.quantity = Some(dec!(100));
+        buy.unit_price = Some(dec!(95_000));
+        buy.fee = Some(dec!(-14_250));
+
+        let row = to_activity_import(&buy, &broker, 0);
+        assert_eq!(row.activity_type, "BUY");
+        assert_eq!(row.symbol, "FPT");
+        assert_eq!(row.quantity, dec!(100));
+        assert_eq!(row.unit_price, dec!(95_000));
+        assert_eq!(row.fee, dec!(14_250));
+    }
+
+    #[test]
+    fn due_after_the_interval_since_the_last_attempt() {
+        let mut conn = connection(ConnectorProvider::Cake);
+        let now = Utc::now().naive_utc();
+        assert!(is_due(&conn, now));
+        conn.last_attempt_at = Some(now - Duration::hours(2));
+        assert!(!is_due(&conn, now));
+        conn.last_attempt_at = Some(now - Duration::hours(25));
+        assert!(is_due(&conn, now));
+        conn.is_enabled = false;
+        assert!(!is_due(&conn, now));
+    }
+}
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime};
+
+use super::connectors_model::{
+    BankConnection, ConnectorProvider, ConnectorSyncResult, ConnectorTransaction, NewBankConnection,
+};
+use crate::errors::Result;
+
+/// Client for one bank or broker feed; add an implementation to support a new provider
+#[async_trait]
+pub trait BankConnector: Send + Sync {
+    fn provider(&self) -> ConnectorProvider;
+
+    /// Transactions on or after `since`, or the feed's full history when `None`
+    async fn fetch_transactions(
+        &self,
+        connection: &BankConnection,
+        token: &str,
+        since: Option<NaiveDate>,
+    ) -> Result<Vec<ConnectorTransaction>>;
+}
+
+/// Where access tokens are kept; the keyring in the apps
+pub trait ConnectorTokenStore: Send + Sync {
+    fn get_token(&self, connection_id: &str) -> Result<Option<String>>;
+    fn set_token(&self, connection_id: &str, token: &str) -> Result<()>;
+    fn delete_token(&self, connection_id: &str) -> Result<()>;
+}
+
+#[async_trait]
+pub trait ConnectorRepositoryTrait: Send + Sync {
+    fn get_connections(&self) -> Result<Vec<BankConnection>>;
+    fn get_connection(&self, id: &str) -> Result<BankConnection>;
+    async fn insert_connection(&self, connection: NewBankConnection) -> Result<BankConnection>;
+    async fn update_connection(
+        &self,
+        id: &str,
+        connection: NewBankConnection,
+    ) -> Result<BankConnection>;
+    async fn delete_connection(&self, id: &str) -> Result<usize>;
+    /// Of `external_ids`, the ones already imported through this connection
+    fn get_imported_ids(&self, connection_id: &str, external_ids: &[String])
+        -> Result<Vec<String>>;
+    /// Records imported `(external_id, activity_id)` pairs and the outcome of a pull;
+    /// `last_synced_at` only moves when there is no error
+    async fn record_sync(
+        &self,
+        connection_id: &str,
+        imported: Vec<(String, String)>,
+        attempted_at: NaiveDateTime,
+        error: Option<String>,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait ConnectorServiceTrait: Send + Sync {
+    fn get_connections(&self) -> Result<Vec<BankConnection>>;
+    fn get_connection(&self, id: &str) -> Result<BankConnection>;
+    /// A token is required when creating a connection
+    async fn create_connection(&self, connection: NewBankConnection) -> Result<BankConnection>;
+    async fn update_connection(
+        &self,
+        id: &str,
+        connection: NewBankConnection,
+    ) -> Result<BankConnection>;
+    async fn delete_connection(&self, id: &str) -> Result<usize>;
+    /// Pulls new transactions and imports them into the connection's account
+    async fn sync_connection(&self, id: &str) -> Result<ConnectorSyncResult>;
+    /// Pulls every enabled connection whose interval has passed
+    async fn sync_due_connections(&self) -> Result<Vec<ConnectorSyncResult>>;
+}
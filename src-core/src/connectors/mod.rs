@@ -0,0 +1,16 @@
+mod connectors_feed;
+mod connectors_model;
+mod connectors_repository;
+mod connectors_service;
+mod connectors_traits;
+
+pub use connectors_feed::{default_connectors, KeyringTokenStore, TokenFeedConnector};
+pub use connectors_model::{
+    BankConnection, ConnectorProvider, ConnectorSyncResult, ConnectorTransaction,
+    NewBankConnection, MAX_SYNC_INTERVAL_HOURS,
+};
+pub use connectors_repository::ConnectorRepository;
+pub use connectors_service::ConnectorService;
+pub use connectors_traits::{
+    BankConnector, ConnectorRepositoryTrait, ConnectorServiceTrait, ConnectorTokenStore,
+};
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Rows read from the database, or from an import file, per step
+pub const TRANSFER_CHUNK_SIZE: usize = 1_000;
+
+/// Rejected rows described in an import result; the rest are only counted
+pub const MAX_REPORTED_IMPORT_ERRORS: usize = 100;
+
+/// Table an export reads from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportDataset {
+    Accounts,
+    Activities,
+    Goals,
+    /// Daily valuations of the whole portfolio
+    PortfolioHistory,
+    Quotes,
+}
+
+impl ExportDataset {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportDataset::Accounts => "accounts",
+            ExportDataset::Activities => "activities",
+            ExportDataset::Goals => "goals",
+            ExportDataset::PortfolioHistory => "portfolio-history",
+            ExportDataset::Quotes => "quotes",
+        }
+    }
+}
+
+impl FromStr for ExportDataset {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "accounts" => Ok(ExportDataset::Accounts),
+            "activities" => Ok(ExportDataset::Activities),
+            "goals" => Ok(ExportDataset::Goals),
+            "portfolio-history" => Ok(ExportDataset::PortfolioHistory),
+            "quotes" => Ok(ExportDataset::Quotes),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown export dataset: {}",
+                other
+            )))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFileFormat {
+    #[serde(rename = "CSV")]
+    Csv,
+    #[serde(rename = "JSON")]
+    Json,
+}
+
+impl ExportFileFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportFileFormat::Csv => "CSV",
+            ExportFileFormat::Json => "JSON",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ExportFileFormat::Csv => "csv",
+            ExportFileFormat::Json => "json",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFileFormat::Csv => "text/csv; charset=utf-8",
+            ExportFileFormat::Json => "application/json",
+        }
+    }
+}
+
+impl FromStr for ExportFileFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "CSV" => Ok(ExportFileFormat::Csv),
+            "JSON" => Ok(ExportFileFormat::Json),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown export format: {}",
+                other
+            )))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransferOperation {
+    Export,
+    Import,
+}
+
+/// Sent after every chunk so the apps can show how far a long export or import has got
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferProgress {
+    /// Id to cancel the transfer with, when it was started as a cancellable operation
+    pub operation_id: Option<String>,
+    pub operation: TransferOperation,
+    /// Export dataset, `ledger`, or `quotes` for a quote import
+    pub dataset: String,
+    pub processed: usize,
+    /// Rows expected, when known before the transfer starts
+    pub total: Option<usize>,
+    pub done: bool,
+}
+
+/// What an export wrote; the rows themselves went straight to the output
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DataExportSummary {
+    pub dataset: ExportDataset,
+    pub format: ExportFileFormat,
+    pub file_name: String,
+    pub rows: usize,
+}
+
+/// Outcome of importing a quotes CSV a chunk at a time
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct QuoteFileImportResult {
+    pub rows: usize,
+    pub imported: usize,
+    /// Already stored and left alone because overwriting was off
+    pub skipped: usize,
+    pub failed: usize,
+    /// Why rows were rejected, for the first `MAX_REPORTED_IMPORT_ERRORS` of them
+    pub errors: Vec<String>,
+}
@@ -0,0 +1,149 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use super::data_transfer_model::ExportDataset;
+use super::data_transfer_traits::{ChunkCursor, DataTransferRepositoryTrait};
+use crate::accounts::{Account, AccountDB};
+use crate::activities::{Activity, ActivityDB};
+use crate::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use crate::db::get_connection;
+use crate::errors::Result;
+use crate::goals::goals_model::Goal;
+use crate::market_data::{Quote, QuoteDb};
+use crate::portfolio::valuation::valuation_archive;
+use crate::portfolio::valuation::DailyAccountValuation;
+use crate::schema::{accounts, activities, goals, quotes};
+
+const VALUATION_DATE_FORMAT: &str = "%Y-%m-%d";
+
+pub struct DataTransferRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+}
+
+impl DataTransferRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>) -> Self {
+        DataTransferRepository { pool }
+    }
+}
+
+/// The last id handed out, for tables paged by id alone
+fn last_id(cursor: &ChunkCursor) -> Option<String> {
+    cursor.last.as_ref().map(|(_, id)| id.clone())
+}
+
+impl DataTransferRepositoryTrait for DataTransferRepository {
+    fn count_rows(&self, dataset: ExportDataset) -> Result<usize> {
+        let mut conn = get_connection(&self.pool)?;
+        let count: i64 = match dataset {
+            ExportDataset::Accounts => accounts::table.count().get_result(&mut conn)?,
+            ExportDataset::Activities => activities::table.count().get_result(&mut conn)?,
+            ExportDataset::Goals => goals::table.count().get_result(&mut conn)?,
+            // Closed months are archived, so this counts rows and archived days alike
+            ExportDataset::PortfolioHistory => {
+                valuation_archive::count_valuations(&mut conn, PORTFOLIO_TOTAL_ACCOUNT_ID)? as i64
+            }
+            ExportDataset::Quotes => quotes::table.count().get_result(&mut conn)?,
+        };
+        Ok(count as usize)
+    }
+
+    fn load_accounts(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Account>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = accounts::table
+            .order(accounts::id.asc())
+            .limit(limit as i64)
+            .into_boxed();
+        if let Some(after) = last_id(cursor) {
+            query = query.filter(accounts::id.gt(after));
+        }
+        let rows = query.load::<AccountDB>(&mut conn)?;
+        if let Some(row) = rows.last() {
+            cursor.last = Some((row.id.clone(), row.id.clone()));
+        }
+        Ok(rows.into_iter().map(Account::from).collect())
+    }
+
+    fn load_activities(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Activity>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = activities::table
+            .order((activities::activity_date.asc(), activities::id.asc()))
+            .limit(limit as i64)
+            .into_boxed();
+        if let Some((date, id)) = cursor.last.clone() {
+            query = query.filter(
+                activities::activity_date
+                    .gt(date.clone())
+                    .or(activities::activity_date
+                        .eq(date)
+                        .and(activities::id.gt(id))),
+            );
+        }
+        let rows = query.load::<ActivityDB>(&mut conn)?;
+        if let Some(row) = rows.last() {
+            cursor.last = Some((row.activity_date.clone(), row.id.clone()));
+        }
+        Ok(rows.into_iter().map(Activity::from).collect())
+    }
+
+    fn load_goals(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Goal>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = goals::table
+            .select(Goal::as_select())
+            .order(goals::id.asc())
+            .limit(limit as i64)
+            .into_boxed();
+        if let Some(after) = last_id(cursor) {
+            query = query.filter(goals::id.gt(after));
+        }
+        let rows = query.load::<Goal>(&mut conn)?;
+        if let Some(row) = rows.last() {
+            cursor.last = Some((row.id.clone(), row.id.clone()));
+        }
+        Ok(rows)
+    }
+
+    fn load_portfolio_history(
+        &self,
+        cursor: &mut ChunkCursor,
+        limit: usize,
+    ) -> Result<Vec<DailyAccountValuation>> {
+        let mut conn = get_connection(&self.pool)?;
+        // One valuation per day, so the date alone marks the position
+        let after = match &cursor.last {
+            Some((date, _)) => Some(NaiveDate::parse_from_str(date, VALUATION_DATE_FORMAT)?),
+            None => None,
+        };
+        let rows = valuation_archive::load_valuations_after(
+            &mut conn,
+            PORTFOLIO_TOTAL_ACCOUNT_ID,
+            after,
+            limit,
+        )?;
+        if let Some(row) = rows.last() {
+            cursor.last = Some((
+                row.valuation_date.format(VALUATION_DATE_FORMAT).to_string(),
+                row.id.clone(),
+            ));
+        }
+        Ok(rows)
+    }
+
+    fn load_quotes(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Quote>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = quotes::table
+            .order(quotes::id.asc())
+            .limit(limit as i64)
+            .into_boxed();
+        if let Some(after) = last_id(cursor) {
+            query = query.filter(quotes::id.gt(after));
+        }
+        let rows = query.load::<QuoteDb>(&mut conn)?;
+        if let Some(row) = rows.last() {
+            cursor.last = Some((row.id.clone(), row.id.clone()));
+        }
+        Ok(rows.into_iter().map(Quote::from).collect())
+    }
+}
@@ -0,0 +1,463 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use log::debug;
+use rust_decimal::Decimal;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use tracing::instrument;
+
+use super::data_transfer_model::{
+    DataExportSummary, ExportDataset, ExportFileFormat, QuoteFileImportResult, TransferOperation,
+    TransferProgress, MAX_REPORTED_IMPORT_ERRORS, TRANSFER_CHUNK_SIZE,
+};
+use super::data_transfer_traits::{
+    ChunkCursor, DataTransferRepositoryTrait, DataTransferServiceTrait,
+};
+use super::data_transfer_writer::RowWriter;
+use crate::errors::Result;
+use crate::market_data::{ImportValidationStatus, MarketDataServiceTrait, QuoteImport};
+use crate::operations::CancellationToken;
+use crate::utils::number_format::{parse_localized_decimal, NumberLocale};
+
+/// Currency of imported quotes whose row leaves it blank, as in the app's CSV import
+const DEFAULT_QUOTE_CURRENCY: &str = "USD";
+
+pub struct DataTransferService {
+    repository: Arc<dyn DataTransferRepositoryTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+}
+
+impl DataTransferService {
+    pub fn new(
+        repository: Arc<dyn DataTransferRepositoryTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+    ) -> Self {
+        DataTransferService {
+            repository,
+            market_data_service,
+        }
+    }
+}
+
+pub fn export_file_name(dataset: ExportDataset, format: ExportFileFormat) -> String {
+    format!(
+        "{}_{}.{}",
+        dataset.as_str(),
+        Utc::now().format("%Y-%m-%d"),
+        format.file_extension()
+    )
+}
+
+/// Pages through `dataset` and writes each chunk before reading the next
+fn export_rows(
+    repository: &dyn DataTransferRepositoryTrait,
+    dataset: ExportDataset,
+    format: ExportFileFormat,
+    out: &mut dyn Write,
+    progress: &dyn Fn(&TransferProgress),
+    cancel: &CancellationToken,
+) -> Result<usize> {
+    let total = repository.count_rows(dataset)?;
+    let report = |processed: usize, done: bool| {
+        progress(&TransferProgress {
+            operation_id: cancel.operation_id().map(str::to_string),
+            operation: TransferOperation::Export,
+            dataset: dataset.as_str().to_string(),
+            processed,
+            total: Some(total),
+            done,
+        })
+    };
+
+    let mut writer = RowWriter::new(format, out)?;
+    let mut cursor = ChunkCursor::default();
+    let mut processed = 0;
+    loop {
+        cancel.check()?;
+        let limit = TRANSFER_CHUNK_SIZE;
+        let written = match dataset {
+            ExportDataset::Accounts => {
+                let rows = repository.load_accounts(&mut cursor, limit)?;
+                writer.write_chunk(&rows)?;
+                rows.len()
+            }
+            ExportDataset::Activities => {
+                let rows = repository.load_activities(&mut cursor, limit)?;
+                writer.write_chunk(&rows)?;
+                rows.len()
+            }
+            ExportDataset::Goals => {
+                let rows = repository.load_goals(&mut cursor, limit)?;
+                writer.write_chunk(&rows)?;
+                rows.len()
+            }
+            ExportDataset::PortfolioHistory => {
+                let rows = repository.load_portfolio_history(&mut cursor, limit)?;
+                writer.write_chunk(&rows)?;
+                rows.len()
+            }
+            ExportDataset::Quotes => {
+                let rows = repository.load_quotes(&mut cursor, limit)?;
+                writer.write_chunk(&rows)?;
+                rows.len()
+            }
+        };
+        processed += written;
+        if written < limit {
+            break;
+        }
+        report(processed, false);
+    }
+    let rows = writer.finish()?;
+    report(rows, true);
+    Ok(rows)
+}
+
+/// A number written in `locale`; blank is `None`
+fn parse_quote_number(
+    value: Option<&str>,
+    field: &str,
+    locale: NumberLocale,
+) -> std::result::Result<Option<Decimal>, String> {
+    let Some(value) = value.filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    match parse_localized_decimal(value, locale) {
+        Ok(Some(number)) => Ok(Some(number)),
+        Ok(None) => Err(format!("{} '{}' is not a number", field, value)),
+        Err(_) => Err(format!(
+            "{} '{}' does not match the {} number format",
+            field,
+            value,
+            locale.as_str()
+        )),
+    }
+}
+
+/// One CSV record as a quote to import; `headers` are already lowercased
+fn quote_from_record(
+    headers: &csv::StringRecord,
+    record: &csv::StringRecord,
+    locale: NumberLocale,
+) -> std::result::Result<QuoteImport, String> {
+    let field = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .and_then(|index| record.get(index))
+            .map(str::trim)
+    };
+    let symbol = field("symbol").unwrap_or_default();
+    let date = field("date").unwrap_or_default();
+    if symbol.is_empty() || date.is_empty() {
+        return Err("symbol and date are required".to_string());
+    }
+    let close = parse_quote_number(field("close"), "close", locale)?
+        .ok_or_else(|| "close price is required".to_string())?;
+    Ok(QuoteImport {
+        symbol: symbol.to_string(),
+        date: date.to_string(),
+        open: parse_quote_number(field("open"), "open", locale)?,
+        high: parse_quote_number(field("high"), "high", locale)?,
+        low: parse_quote_number(field("low"), "low", locale)?,
+        close,
+        volume: parse_quote_number(field("volume"), "volume", locale)?,
+        currency: field("currency")
+            .filter(|currency| !currency.is_empty())
+            .unwrap_or(DEFAULT_QUOTE_CURRENCY)
+            .to_string(),
+        validation_status: ImportValidationStatus::Valid,
+        error_message: None,
+    })
+}
+
+impl QuoteFileImportResult {
+    fn reject(&mut self, line: usize, reason: &str) {
+        self.failed += 1;
+        if self.errors.len() < MAX_REPORTED_IMPORT_ERRORS {
+            self.errors.push(format!("Line {}: {}", line, reason));
+        }
+    }
+}
+
+#[async_trait]
+impl DataTransferServiceTrait for DataTransferService {
+    #[instrument(name = "data_transfer.export", skip(self, out, progress, cancel))]
+    fn export_dataset(
+        &self,
+        dataset: ExportDataset,
+        format: ExportFileFormat,
+        out: &mut dyn Write,
+        progress: &dyn Fn(&TransferProgress),
+        cancel: &CancellationToken,
+    ) -> Result<DataExportSummary> {
+        debug!("Exporting {} as {}", dataset.as_str(), format.as_str());
+        let rows = export_rows(
+            self.repository.as_ref(),
+            dataset,
+            format,
+            out,
+            progress,
+            cancel,
+        )?;
+        Ok(DataExportSummary {
+            dataset,
+            format,
+            file_name: export_file_name(dataset, format),
+            rows,
+        })
+    }
+
+    #[instrument(
+        name = "data_transfer.import_quotes",
+        skip(self, input, progress, cancel)
+    )]
+    async fn import_quotes_csv(
+        &self,
+        input: Box<dyn Read + Send>,
+        overwrite: bool,
+        number_locale: NumberLocale,
+        progress: &(dyn for<'p> Fn(&'p TransferProgress) + Send + Sync),
+        cancel: &CancellationToken,
+    ) -> Result<QuoteFileImportResult> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(input);
+        let headers: csv::StringRecord = reader
+            .headers()?
+            .iter()
+            .map(|header| header.to_ascii_lowercase())
+            .collect();
+
+        let mut result = QuoteFileImportResult::default();
+        let mut records = reader.into_records();
+        let mut line = 1;
+        loop {
+            cancel.check()?;
+            let mut chunk = Vec::with_capacity(TRANSFER_CHUNK_SIZE);
+            let mut lines = Vec::with_capacity(TRANSFER_CHUNK_SIZE);
+            for record in records.by_ref() {
+                line += 1;
+                let record = match record {
+                    Ok(record) => record,
+                    Err(e) => {
+                        result.rows += 1;
+                        result.reject(line, &e.to_string());
+                        continue;
+                    }
+                };
+                if record.iter().all(|value| value.is_empty()) {
+                    continue;
+                }
+                result.rows += 1;
+                match quote_from_record(&headers, &record, number_locale) {
+                    Ok(quote) => {
+                        chunk.push(quote);
+                        lines.push(line);
+                    }
+                    Err(reason) => result.reject(line, &reason),
+                }
+                if chunk.len() == TRANSFER_CHUNK_SIZE {
+                    break;
+                }
+            }
+            let finished = chunk.len() < TRANSFER_CHUNK_SIZE;
+
+            if !chunk.is_empty() {
+                let checked = self
+                    .market_data_service
+                    .import_quotes_from_csv(chunk, overwrite)
+                    .await?;
+                for (quote, line) in checked.iter().zip(&lines) {
+                    match &quote.validation_status {
+                        ImportValidationStatus::Valid => result.imported += 1,
+                        ImportValidationStatus::Warning(_) => result.skipped += 1,
+                        ImportValidationStatus::Error(reason) => result.reject(*line, reason),
+                    }
+                }
+            }
+            progress(&TransferProgress {
+                operation_id: cancel.operation_id().map(str::to_string),
+                operation: TransferOperation::Import,
+                dataset: "quotes".to_string(),
+                processed: result.rows,
+                total: None,
+                done: finished,
+            });
+            if finished {
+                break;
+            }
+        }
+        debug!(
+            "Imported {} of {} quote rows ({} skipped, {} failed)",
+            result.imported, result.rows, result.skipped, result.failed
+        );
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::Account;
+    use crate::activities::Activity;
+    use crate::goals::goals_model::Goal;
+    use crate::market_data::Quote;
+    use crate::portfolio::valuation::DailyAccountValuation;
+    use rust_decimal_macros::dec;
+    use std::sync::Mutex;
+
+    /// Quotes `q0000..` served from memory, recording the size of every page asked for
+    struct QuoteTable {
+        count: usize,
+        pages: Mutex<Vec<usize>>,
+    }
+
+    impl DataTransferRepositoryTrait for QuoteTable {
+        fn count_rows(&self, _dataset: ExportDataset) -> Result<usize> {
+            Ok(self.count)
+        }
+        fn load_accounts(&self, _cursor: &mut ChunkCursor, _limit: usize) -> Result<Vec<Account>> {
+            unimplemented!()
+        }
+        fn load_activities(
+            &self,
+            _cursor: &mut ChunkCursor,
+            _limit: usize,
+        ) -> Result<Vec<Activity>> {
+            unimplemented!()
+        }
+        fn load_goals(&self, _cursor: &mut ChunkCursor, _limit: usize) -> Result<Vec<Goal>> {
+            unimplemented!()
+        }
+        fn load_portfolio_history(
+            &self,
+            _cursor: &mut ChunkCursor,
+            _limit: usize,
+        ) -> Result<Vec<DailyAccountValuation>> {
+            unimplemented!()
+        }
+        fn load_quotes(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Quote>> {
+            let start = cursor
+                .last
+                .as_ref()
+                .map_or(0, |(_, id)| id[1..].parse::<usize>().unwrap() + 1);
+            let rows: Vec<Quote> = (start..self.count.min(start + limit))
+                .map(|i| Quote {
+                    id: format!("q{:04}", i),
+                    symbol: "FPT".to_string(),
+                    close: Decimal::from(i),
+                    ..Default::default()
+                })
+                .collect();
+            if let Some(row) = rows.last() {
+                cursor.last = Some((row.id.clone(), row.id.clone()));
+            }
+            self.pages.lock().unwrap().push(rows.len());
+            Ok(rows)
+        }
+    }
+
+    #[test]
+    fn exports_a_chunk_at_a_time_and_reports_progress() {
+        let table = QuoteTable {
+            count: TRANSFER_CHUNK_SIZE * 2 + 5,
+            pages: Mutex::new(Vec::new()),
+        };
+        let reports = Mutex::new(Vec::new());
+        let mut out = Vec::new();
+
+        let rows = export_rows(
+            &table,
+            ExportDataset::Quotes,
+            ExportFileFormat::Csv,
+            &mut out,
+            &|p: &TransferProgress| reports.lock().unwrap().push((p.processed, p.done)),
+            &CancellationToken::default(),
+        )
+        .unwrap();
+
+        assert_eq!(rows, TRANSFER_CHUNK_SIZE * 2 + 5);
+        assert_eq!(
+            *table.pages.lock().unwrap(),
+            vec![TRANSFER_CHUNK_SIZE, TRANSFER_CHUNK_SIZE, 5]
+        );
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![
+                (TRANSFER_CHUNK_SIZE, false),
+                (TRANSFER_CHUNK_SIZE * 2, false),
+                (rows, true)
+            ]
+        );
+        let csv = String::from_utf8(out).unwrap();
+        // One header, then every row exactly once
+        assert_eq!(csv.lines().count(), rows + 1);
+        assert!(csv.lines().nth(1).unwrap().starts_with("q0000,FPT,"));
+        assert!(csv.lines().last().unwrap().starts_with("q2004,FPT,"));
+    }
+
+    #[test]
+    fn a_cancelled_export_stops_before_the_next_chunk() {
+        let table = QuoteTable {
+            count: TRANSFER_CHUNK_SIZE * 3,
+            pages: Mutex::new(Vec::new()),
+        };
+        let cancel = CancellationToken::new("export-1", None);
+        let mut out = Vec::new();
+
+        let result = export_rows(
+            &table,
+            ExportDataset::Quotes,
+            ExportFileFormat::Csv,
+            &mut out,
+            &|p: &TransferProgress| {
+                assert_eq!(p.operation_id.as_deref(), Some("export-1"));
+                cancel.cancel();
+            },
+            &cancel,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(*table.pages.lock().unwrap(), vec![TRANSFER_CHUNK_SIZE]);
+    }
+
+    #[test]
+    fn quote_rows_are_read_like_the_app_import() {
+        let headers =
+            csv::StringRecord::from(vec!["symbol", "date", "close", "volume", "currency"]);
+        let quote = quote_from_record(
+            &headers,
+            &csv::StringRecord::from(vec!["VNM", "2026-10-16", "70,500", "1,200", ""]),
+            NumberLocale::En,
+        )
+        .unwrap();
+        assert_eq!(quote.close, dec!(70500));
+        assert_eq!(quote.volume, Some(dec!(1200)));
+        assert_eq!(quote.open, None);
+        assert_eq!(quote.currency, DEFAULT_QUOTE_CURRENCY);
+
+        let missing_close = quote_from_record(
+            &headers,
+            &csv::StringRecord::from(vec!["VNM", "2026-10-16", "", "", "VND"]),
+            NumberLocale::En,
+        );
+        assert_eq!(missing_close.unwrap_err(), "close price is required");
+        let bad_number = quote_from_record(
+            &headers,
+            &csv::StringRecord::from(vec!["VNM", "2026-10-16", "n/a", "", "VND"]),
+            NumberLocale::En,
+        );
+        assert_eq!(bad_number.unwrap_err(), "close 'n/a' is not a number");
+
+        // A Vietnamese export read as US-style would be off by 1000
+        let vietnamese = csv::StringRecord::from(vec!["FUEVFVND", "2026-10-16", "25.350,5", "", ""]);
+        let quote = quote_from_record(&headers, &vietnamese, NumberLocale::Vi).unwrap();
+        assert_eq!(quote.close, dec!(25350.5));
+        assert_eq!(
+            quote_from_record(&headers, &vietnamese, NumberLocale::En).unwrap_err(),
+            "close '25.350,5' does not match the en number format"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use std::io::{Read, Write};
+
+use super::data_transfer_model::{
+    DataExportSummary, ExportDataset, ExportFileFormat, QuoteFileImportResult, TransferProgress,
+};
+use crate::accounts::Account;
+use crate::activities::Activity;
+use crate::errors::Result;
+use crate::goals::goals_model::Goal;
+use crate::market_data::Quote;
+use crate::operations::CancellationToken;
+use crate::portfolio::valuation::DailyAccountValuation;
+use crate::utils::number_format::NumberLocale;
+
+/// Where the next chunk of a dataset starts: the sort key and id of the last row handed out
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkCursor {
+    pub(crate) last: Option<(String, String)>,
+}
+
+/// Keyset-paged reads, so an export never holds a whole table in memory.
+/// Each call returns up to `limit` rows after `cursor` and moves it past them.
+pub trait DataTransferRepositoryTrait: Send + Sync {
+    fn count_rows(&self, dataset: ExportDataset) -> Result<usize>;
+    fn load_accounts(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Account>>;
+    /// Oldest first
+    fn load_activities(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Activity>>;
+    fn load_goals(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Goal>>;
+    /// Valuations of the TOTAL portfolio, oldest first
+    fn load_portfolio_history(
+        &self,
+        cursor: &mut ChunkCursor,
+        limit: usize,
+    ) -> Result<Vec<DailyAccountValuation>>;
+    fn load_quotes(&self, cursor: &mut ChunkCursor, limit: usize) -> Result<Vec<Quote>>;
+}
+
+#[async_trait]
+pub trait DataTransferServiceTrait: Send + Sync {
+    /// Writes `dataset` to `out` one chunk at a time, calling `progress` after each and
+    /// stopping before the next once `cancel` is cancelled.
+    /// Blocks on the database and on `out`, so callers run it on a blocking thread.
+    fn export_dataset(
+        &self,
+        dataset: ExportDataset,
+        format: ExportFileFormat,
+        out: &mut dyn Write,
+        progress: &dyn Fn(&TransferProgress),
+        cancel: &CancellationToken,
+    ) -> Result<DataExportSummary>;
+
+    /// Reads a `symbol,date,open,high,low,close,volume,currency` CSV from `input` and
+    /// imports it a chunk at a time, so the whole file is never parsed up front. Numbers are
+    /// read as written in `number_locale`. Chunks already imported stay when `cancel` stops
+    /// the import.
+    async fn import_quotes_csv(
+        &self,
+        input: Box<dyn Read + Send>,
+        overwrite: bool,
+        number_locale: NumberLocale,
+        progress: &(dyn for<'p> Fn(&'p TransferProgress) + Send + Sync),
+        cancel: &CancellationToken,
+    ) -> Result<QuoteFileImportResult>;
+}
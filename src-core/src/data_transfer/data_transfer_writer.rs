@@ -0,0 +1,128 @@
+use serde::Serialize;
+use std::io::Write;
+
+use super::data_transfer_model::ExportFileFormat;
+use crate::errors::Result;
+
+enum Sink<'a> {
+    Csv(Box<csv::Writer<&'a mut dyn Write>>),
+    Json { out: &'a mut dyn Write, empty: bool },
+}
+
+/// Writes rows as CSV, or as a JSON array with one row per line, a chunk at a time.
+/// The CSV header comes from the first row's field names.
+pub(crate) struct RowWriter<'a> {
+    sink: Sink<'a>,
+    rows: usize,
+}
+
+impl<'a> RowWriter<'a> {
+    pub fn new(format: ExportFileFormat, out: &'a mut dyn Write) -> Result<Self> {
+        let sink = match format {
+            ExportFileFormat::Csv => Sink::Csv(Box::new(csv::Writer::from_writer(out))),
+            ExportFileFormat::Json => {
+                out.write_all(b"[")?;
+                Sink::Json { out, empty: true }
+            }
+        };
+        Ok(RowWriter { sink, rows: 0 })
+    }
+
+    pub fn write_chunk<T: Serialize>(&mut self, rows: &[T]) -> Result<()> {
+        match &mut self.sink {
+            Sink::Csv(writer) => {
+                for row in rows {
+                    writer.serialize(row)?;
+                }
+                writer.flush()?;
+            }
+            Sink::Json { out, empty } => {
+                for row in rows {
+                    out.write_all(if *empty { b"\n  " } else { b",\n  " })?;
+                    serde_json::to_writer(&mut **out, row)?;
+                    *empty = false;
+                }
+                out.flush()?;
+            }
+        }
+        self.rows += rows.len();
+        Ok(())
+    }
+
+    /// Closes the JSON array and returns how many rows were written
+    pub fn finish(self) -> Result<usize> {
+        match self.sink {
+            Sink::Csv(mut writer) => writer.flush()?,
+            Sink::Json { out, empty } => {
+                out.write_all(if empty { b"]\n" } else { b"\n]\n" })?;
+                out.flush()?;
+            }
+        }
+        Ok(self.rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Row {
+        symbol: String,
+        close: f64,
+        note: Option<String>,
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                symbol: "FPT".to_string(),
+                close: 120.5,
+                note: None,
+            },
+            Row {
+                symbol: "VNM".to_string(),
+                close: 70.0,
+                note: Some("cổ tức, tháng 6".to_string()),
+            },
+        ]
+    }
+
+    fn write(format: ExportFileFormat, chunks: &[&[Row]]) -> String {
+        let mut out = Vec::new();
+        let mut writer = RowWriter::new(format, &mut out).unwrap();
+        for chunk in chunks {
+            writer.write_chunk(chunk).unwrap();
+        }
+        assert_eq!(
+            writer.finish().unwrap(),
+            chunks.iter().map(|c| c.len()).sum::<usize>()
+        );
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn chunks_join_into_one_document() {
+        let rows = rows();
+        let (first, second) = rows.split_at(1);
+
+        let csv = write(ExportFileFormat::Csv, &[first, second]);
+        assert_eq!(
+            csv,
+            "symbol,close,note\nFPT,120.5,\nVNM,70.0,\"cổ tức, tháng 6\"\n"
+        );
+
+        let json = write(ExportFileFormat::Json, &[first, second]);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 2);
+        assert_eq!(parsed[1]["note"], "cổ tức, tháng 6");
+
+        let empty = write(ExportFileFormat::Json, &[]);
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&empty).unwrap(),
+            serde_json::json!([])
+        );
+        assert_eq!(write(ExportFileFormat::Csv, &[]), "");
+    }
+}
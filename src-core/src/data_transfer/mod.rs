@@ -0,0 +1,15 @@
+mod data_transfer_model;
+mod data_transfer_repository;
+mod data_transfer_service;
+mod data_transfer_traits;
+mod data_transfer_writer;
+
+pub use data_transfer_model::{
+    DataExportSummary, ExportDataset, ExportFileFormat, QuoteFileImportResult, TransferOperation,
+    TransferProgress, MAX_REPORTED_IMPORT_ERRORS, TRANSFER_CHUNK_SIZE,
+};
+pub use data_transfer_repository::DataTransferRepository;
+pub use data_transfer_service::{export_file_name, DataTransferService};
+pub use data_transfer_traits::{
+    ChunkCursor, DataTransferRepositoryTrait, DataTransferServiceTrait,
+};
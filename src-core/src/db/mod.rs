@@ -19,25 +19,28 @@ pub type DbPool = r2d2::Pool<ConnectionManager<SqliteConnection>>;
 pub type DbConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
 
 pub mod write_actor;
-pub use write_actor::WriteHandle;
+pub use write_actor::{WriteHandle, WriteOp};
 
 pub fn init(app_data_dir: &str) -> Result<String> {
-    let db_path = get_db_path(app_data_dir);
+    init_file(&get_db_path(app_data_dir))
+}
 
+/// Prepares the database at exactly `db_path`, without consulting DATABASE_URL
+pub fn init_file(db_path: &str) -> Result<String> {
     // 1. Ensure directory exists
-    let db_dir = Path::new(&db_path).parent().unwrap();
+    let db_dir = Path::new(db_path).parent().unwrap();
     if !db_dir.exists() {
         fs::create_dir_all(db_dir)?;
     }
 
     {
-        let mut conn = SqliteConnection::establish(&db_path)?;
+        let mut conn = SqliteConnection::establish(db_path)?;
         conn.batch_execute(
             "\n            PRAGMA journal_mode = WAL;\n            PRAGMA foreign_keys = ON;\n            PRAGMA busy_timeout = 30000;\n            PRAGMA synchronous  = NORMAL;\n        ",
         )?;
     }
 
-    Ok(db_path)
+    Ok(db_path.to_string())
 }
 
 pub fn create_pool(db_path: &str) -> Result<Arc<DbPool>> {
@@ -302,6 +305,23 @@ pub fn get_connection(pool: &Pool<ConnectionManager<SqliteConnection>>) -> Resul
     Ok(pool.get()?)
 }
 
+/// Runs a read on a pooled connection from tokio's blocking pool, so a slow query doesn't
+/// stall the async runtime threads. Writes still go through the `WriteHandle`.
+pub async fn spawn_read<F, T>(pool: &Arc<DbPool>, job: F) -> Result<T>
+where
+    F: FnOnce(&mut SqliteConnection) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = Arc::clone(pool);
+    tokio::task::spawn_blocking(move || {
+        let _span = tracing::info_span!("db.read").entered();
+        let mut conn = get_connection(&pool)?;
+        job(&mut conn)
+    })
+    .await
+    .map_err(|e| Error::Database(DatabaseError::Internal(format!("Read task failed: {}", e))))?
+}
+
 #[derive(Debug)]
 struct ConnectionCustomizer;
 
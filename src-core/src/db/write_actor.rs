@@ -1,5 +1,6 @@
 use crate::db::{DbPool, Result};
-use diesel::SqliteConnection;
+use crate::errors::{DatabaseError, Error};
+use diesel::{Connection, SqliteConnection};
 use std::any::Any;
 use tokio::sync::{mpsc, oneshot};
 
@@ -7,16 +8,24 @@ use tokio::sync::{mpsc, oneshot};
 // It takes a mutable reference to a SqliteConnection and returns a Result.
 type Job<T> = Box<dyn FnOnce(&mut SqliteConnection) -> Result<T> + Send + 'static>;
 
+/// One operation of a batch submitted with `WriteHandle::exec_batch`
+pub type WriteOp<T> = Job<T>;
+
+type ErasedJob = (
+    Job<Box<dyn Any + Send + 'static>>,
+    oneshot::Sender<Result<Box<dyn Any + Send + 'static>>>,
+);
+
+/// Most jobs already waiting in the queue that the actor commits together
+const MAX_COALESCED_JOBS: usize = 64;
+
 /// Handle for sending jobs to the writer actor.
 #[derive(Clone)]
 pub struct WriteHandle {
     // Sender part of the MPSC channel to send jobs.
     // Each job is a boxed closure, and a oneshot sender is used for the reply.
     // The Box<dyn Any + Send> is used for type erasure of the job's return type.
-    tx: mpsc::Sender<(
-        Job<Box<dyn Any + Send + 'static>>,
-        oneshot::Sender<Result<Box<dyn Any + Send + 'static>>>,
-    )>,
+    tx: mpsc::Sender<ErasedJob>,
 }
 
 impl WriteHandle {
@@ -58,6 +67,22 @@ impl WriteHandle {
                     .unwrap_or_else(|_| panic!("Failed to downcast writer actor result."))
             })
     }
+
+    /// Executes the operations in order as a single job, so they share one transaction:
+    /// either all of them are committed or, as soon as one fails, none are.
+    ///
+    /// # Returns
+    /// The result of each operation, in the order they were given.
+    pub async fn exec_batch<T>(&self, ops: Vec<WriteOp<T>>) -> Result<Vec<T>>
+    where
+        T: Send + 'static + Any,
+    {
+        if ops.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.exec(move |conn| ops.into_iter().map(|op| op(conn)).collect())
+            .await
+    }
 }
 
 /// Spawns a background Tokio task that acts as a single writer to the database.
@@ -71,10 +96,7 @@ impl WriteHandle {
 pub fn spawn_writer(pool: DbPool) -> WriteHandle {
     // Create an MPSC channel for sending jobs to the actor.
     // The channel is bounded; 1024 is an arbitrary size.
-    let (tx, mut rx) = mpsc::channel::<(
-        Job<Box<dyn Any + Send + 'static>>,
-        oneshot::Sender<Result<Box<dyn Any + Send + 'static>>>,
-    )>(1024);
+    let (tx, mut rx) = mpsc::channel::<ErasedJob>(1024);
 
     tokio::spawn(async move {
         // Acquire a single connection from the pool for this actor.
@@ -82,14 +104,18 @@ pub fn spawn_writer(pool: DbPool) -> WriteHandle {
         let mut conn = pool.get().expect("Failed to get a connection from the DB pool for the writer actor. The pool might be exhausted or misconfigured.");
 
         // Loop to receive and process jobs.
-        while let Some((job, reply_tx)) = rx.recv().await {
-            // Execute the job within an immediate database transaction.
-            // This ensures atomicity for each job.
-            let result = conn.immediate_transaction(|c| job(c));
-
-            // Send the result back to the requester.
-            // Ignore error if the receiver has dropped (e.g., request timed out or was cancelled).
-            let _ = reply_tx.send(result);
+        while let Some(first) = rx.recv().await {
+            // Jobs that queued up while the previous commit ran are committed together,
+            // which saves a commit per job when many small writes arrive at once.
+            let mut jobs = vec![first];
+            while jobs.len() < MAX_COALESCED_JOBS {
+                match rx.try_recv() {
+                    Ok(job) => jobs.push(job),
+                    Err(_) => break,
+                }
+            }
+            let _span = tracing::info_span!("db.write", jobs = jobs.len()).entered();
+            run_jobs(&mut conn, jobs);
         }
         // If rx.recv() returns None, it means the sender (WriteHandle) was dropped,
         // so the actor can terminate.
@@ -98,5 +124,116 @@ pub fn spawn_writer(pool: DbPool) -> WriteHandle {
     WriteHandle { tx }
 }
 
+/// Runs queued jobs in one immediate transaction and replies to each. A lone job is the
+/// transaction itself; several each get a savepoint, so one failing job only rolls back
+/// its own writes and leaves the others to be committed.
+fn run_jobs(conn: &mut SqliteConnection, jobs: Vec<ErasedJob>) {
+    if jobs.len() == 1 {
+        let (job, reply_tx) = jobs.into_iter().next().unwrap();
+        // Ignore error if the receiver has dropped (e.g., request timed out or was cancelled).
+        let _ = reply_tx.send(conn.immediate_transaction(|c| job(c)));
+        return;
+    }
+
+    let (jobs, reply_txs): (Vec<_>, Vec<_>) = jobs.into_iter().unzip();
+    let outcome = conn.immediate_transaction(|c| -> Result<Vec<_>> {
+        Ok(jobs
+            .into_iter()
+            .map(|job| c.transaction(|c| job(c)))
+            .collect())
+    });
+    match outcome {
+        Ok(results) => {
+            for (reply_tx, result) in reply_txs.into_iter().zip(results) {
+                let _ = reply_tx.send(result);
+            }
+        }
+        Err(e) => {
+            // Nothing was committed, so every job failed with the transaction
+            let message = e.to_string();
+            for reply_tx in reply_txs {
+                let _ = reply_tx.send(Err(Error::Database(DatabaseError::Internal(format!(
+                    "Batched write was not committed: {}",
+                    message
+                )))));
+            }
+        }
+    }
+}
+
 // Note: DbConnection (PooledConnection) derefs to SqliteConnection.
 // The immediate_transaction method is on SqliteConnection via the Connection trait.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use diesel::prelude::*;
+    use diesel::r2d2::{ConnectionManager, Pool};
+    use diesel::sql_query;
+
+    fn writer() -> WriteHandle {
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .unwrap();
+        spawn_writer(pool)
+    }
+
+    fn insert(value: i32) -> WriteOp<usize> {
+        Box::new(move |conn| {
+            Ok(sql_query(format!("INSERT INTO numbers (n) VALUES ({})", value)).execute(conn)?)
+        })
+    }
+
+    async fn count(writer: &WriteHandle) -> i64 {
+        #[derive(QueryableByName)]
+        struct Count {
+            #[diesel(sql_type = diesel::sql_types::BigInt)]
+            n: i64,
+        }
+        writer
+            .exec(|conn| {
+                Ok(sql_query("SELECT COUNT(*) AS n FROM numbers")
+                    .get_result::<Count>(conn)?
+                    .n)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn batches_commit_together_and_coalesced_jobs_fail_alone() {
+        let writer = writer();
+        writer
+            .exec(|conn| {
+                Ok(sql_query("CREATE TABLE numbers (n INTEGER PRIMARY KEY)").execute(conn)?)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            writer.exec_batch(vec![insert(1), insert(2)]).await.unwrap(),
+            [1, 1]
+        );
+        // The duplicate fails the batch, and the insert before it is rolled back with it
+        assert!(writer.exec_batch(vec![insert(3), insert(1)]).await.is_err());
+        assert_eq!(count(&writer).await, 2);
+
+        // Queued jobs share a commit, but a failing one doesn't take the others down
+        let jobs: Vec<_> = [10, 11, 1, 12]
+            .into_iter()
+            .map(|value| {
+                let writer = writer.clone();
+                tokio::spawn(async move { writer.exec(insert(value)).await })
+            })
+            .collect();
+        let mut failed = 0;
+        for job in jobs {
+            if job.await.unwrap().is_err() {
+                failed += 1;
+            }
+        }
+        assert_eq!(failed, 1);
+        assert_eq!(count(&writer).await, 5);
+    }
+}
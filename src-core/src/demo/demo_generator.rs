@@ -0,0 +1,640 @@
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::demo_model::DemoPortfolio;
+use crate::accounts::NewAccount;
+use crate::activities::{
+    NewActivity, ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_DIVIDEND,
+    ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT, ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::assets::NewAsset;
+use crate::goals::goals_model::{
+    GoalsAllocation, NewGoal, GOAL_TYPE_EMERGENCY_FUND, GOAL_TYPE_STANDARD,
+};
+use crate::market_data::{DataSource, Quote};
+
+pub const DEMO_BASE_CURRENCY: &str = "VND";
+pub const DEMO_PROFILE_NAME: &str = "Demo portfolio";
+
+/// How much history the demo covers
+const DEMO_YEARS: i32 = 3;
+const CASH_ASSET_ID: &str = "$CASH-VND";
+/// Fixed seed so every demo profile tells the same story
+const PRICE_SEED: u64 = 0x5EED_2026;
+/// How strongly prices are pulled back towards their trend line each day
+const TREND_REVERSION: f64 = 0.97;
+/// Brokerage fee charged on stock purchases
+const STOCK_FEE_RATE: Decimal = dec!(0.0015);
+
+struct Instrument {
+    symbol: &'static str,
+    name: &'static str,
+    asset_type: &'static str,
+    asset_class: &'static str,
+    asset_sub_class: &'static str,
+    start_price: f64,
+    end_price: f64,
+    daily_volatility: f64,
+    tick: f64,
+}
+
+const INSTRUMENTS: [Instrument; 8] = [
+    Instrument {
+        symbol: "FPT",
+        name: "FPT Corporation",
+        asset_type: "EQUITY",
+        asset_class: "Equity",
+        asset_sub_class: "Stock",
+        start_price: 80_000.0,
+        end_price: 130_000.0,
+        daily_volatility: 0.015,
+        tick: 100.0,
+    },
+    Instrument {
+        symbol: "VCB",
+        name: "Vietcombank",
+        asset_type: "EQUITY",
+        asset_class: "Equity",
+        asset_sub_class: "Stock",
+        start_price: 85_000.0,
+        end_price: 95_000.0,
+        daily_volatility: 0.012,
+        tick: 100.0,
+    },
+    Instrument {
+        symbol: "HPG",
+        name: "Hoa Phat Group",
+        asset_type: "EQUITY",
+        asset_class: "Equity",
+        asset_sub_class: "Stock",
+        start_price: 27_000.0,
+        end_price: 26_500.0,
+        daily_volatility: 0.018,
+        tick: 50.0,
+    },
+    Instrument {
+        symbol: "MWG",
+        name: "Mobile World Investment",
+        asset_type: "EQUITY",
+        asset_class: "Equity",
+        asset_sub_class: "Stock",
+        start_price: 45_000.0,
+        end_price: 62_000.0,
+        daily_volatility: 0.02,
+        tick: 100.0,
+    },
+    Instrument {
+        symbol: "VNM",
+        name: "Vinamilk",
+        asset_type: "EQUITY",
+        asset_class: "Equity",
+        asset_sub_class: "Stock",
+        start_price: 75_000.0,
+        end_price: 62_000.0,
+        daily_volatility: 0.012,
+        tick: 100.0,
+    },
+    Instrument {
+        symbol: "E1VFVN30",
+        name: "DCVFMVN30 ETF",
+        asset_type: "EQUITY",
+        asset_class: "Equity",
+        asset_sub_class: "ETF",
+        start_price: 18_000.0,
+        end_price: 24_000.0,
+        daily_volatility: 0.012,
+        tick: 10.0,
+    },
+    Instrument {
+        symbol: "VESAF",
+        name: "VinaCapital Equity Special Access Fund",
+        asset_type: "FUND",
+        asset_class: "Equity",
+        asset_sub_class: "Mutual Fund",
+        start_price: 22_000.0,
+        end_price: 30_000.0,
+        daily_volatility: 0.008,
+        tick: 1.0,
+    },
+    Instrument {
+        symbol: "VN.GOLD",
+        name: "SJC Gold (lượng)",
+        asset_type: "COMMODITY",
+        asset_class: "Commodity",
+        asset_sub_class: "Precious Metal",
+        start_price: 66_000_000.0,
+        end_price: 120_000_000.0,
+        daily_volatility: 0.007,
+        tick: 100_000.0,
+    },
+];
+
+/// Stocks bought in rotation, one per month
+const STOCK_ROTATION: [&str; 5] = ["FPT", "VCB", "HPG", "MWG", "VNM"];
+/// (symbol, month paid, cash dividend per share)
+const DIVIDENDS: [(&str, u32, Decimal); 2] = [("FPT", 6, dec!(2000)), ("VNM", 8, dec!(1500))];
+
+/// Small deterministic generator; the demo must look the same on every machine
+struct Lcg(u64);
+
+impl Lcg {
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Roughly standard normal noise
+    fn next_noise(&mut self) -> f64 {
+        (self.next_f64() + self.next_f64() + self.next_f64() - 1.5) * 2.0
+    }
+}
+
+struct PriceSeries {
+    dates: Vec<NaiveDate>,
+    closes: Vec<Decimal>,
+}
+
+impl PriceSeries {
+    fn generate(instrument: &Instrument, seed: u64, start: NaiveDate, end: NaiveDate) -> Self {
+        let dates: Vec<NaiveDate> = start
+            .iter_days()
+            .take_while(|d| *d <= end)
+            .filter(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun))
+            .collect();
+        let steps = dates.len().max(1) as f64;
+        let start_log = instrument.start_price.ln();
+        let slope = (instrument.end_price.ln() - start_log) / steps;
+
+        let mut rng = Lcg(seed);
+        let mut deviation = 0.0;
+        let closes = (0..dates.len())
+            .map(|i| {
+                deviation =
+                    deviation * TREND_REVERSION + instrument.daily_volatility * rng.next_noise();
+                let price = (start_log + slope * i as f64 + deviation).exp();
+                round_to_tick(price, instrument.tick)
+            })
+            .collect();
+
+        Self { dates, closes }
+    }
+
+    /// Close of the last trading day on or before `date`
+    fn price_on(&self, date: NaiveDate) -> Decimal {
+        let index = self.dates.partition_point(|d| *d <= date);
+        self.closes[index.saturating_sub(1)]
+    }
+}
+
+fn round_to_tick(price: f64, tick: f64) -> Decimal {
+    Decimal::from_f64_retain(((price / tick).round() * tick).max(tick))
+        .unwrap_or_default()
+        .round()
+}
+
+/// Moves weekend dates to the following Monday
+fn business_day(year: i32, month: u32, day: u32) -> NaiveDate {
+    let date = NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default();
+    match date.weekday() {
+        Weekday::Sat => date + Duration::days(2),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+fn round_vnd(amount: f64, step: f64) -> Decimal {
+    Decimal::from_f64_retain((amount / step).round() * step)
+        .unwrap_or_default()
+        .round()
+}
+
+struct ActivityLog {
+    activities: Vec<NewActivity>,
+    cash: HashMap<String, Decimal>,
+    positions: HashMap<(String, String), Decimal>,
+}
+
+impl ActivityLog {
+    fn new() -> Self {
+        Self {
+            activities: Vec::new(),
+            cash: HashMap::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    fn cash_of(&self, account_id: &str) -> Decimal {
+        self.cash.get(account_id).copied().unwrap_or_default()
+    }
+
+    fn position_of(&self, account_id: &str, symbol: &str) -> Decimal {
+        self.positions
+            .get(&(account_id.to_string(), symbol.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn base_activity(
+        account_id: &str,
+        asset_id: &str,
+        activity_type: &str,
+        date: NaiveDate,
+    ) -> NewActivity {
+        NewActivity {
+            id: Some(Uuid::new_v4().to_string()),
+            account_id: account_id.to_string(),
+            asset_id: asset_id.to_string(),
+            activity_type: activity_type.to_string(),
+            activity_date: date.format("%Y-%m-%d").to_string(),
+            quantity: None,
+            unit_price: None,
+            currency: DEMO_BASE_CURRENCY.to_string(),
+            fee: None,
+            amount: None,
+            is_draft: false,
+            comment: None,
+        }
+    }
+
+    fn cash_flow(
+        &mut self,
+        account_id: &str,
+        activity_type: &str,
+        date: NaiveDate,
+        amount: Decimal,
+        comment: &str,
+    ) {
+        let signed = match activity_type {
+            ACTIVITY_TYPE_WITHDRAWAL | ACTIVITY_TYPE_TRANSFER_OUT => -amount,
+            _ => amount,
+        };
+        *self.cash.entry(account_id.to_string()).or_default() += signed;
+        self.activities.push(NewActivity {
+            amount: Some(amount),
+            comment: Some(comment.to_string()),
+            ..Self::base_activity(account_id, CASH_ASSET_ID, activity_type, date)
+        });
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, date: NaiveDate, amount: Decimal, comment: &str) {
+        self.cash_flow(from, ACTIVITY_TYPE_TRANSFER_OUT, date, amount, comment);
+        self.cash_flow(to, ACTIVITY_TYPE_TRANSFER_IN, date, amount, comment);
+    }
+
+    fn buy(
+        &mut self,
+        account_id: &str,
+        symbol: &str,
+        date: NaiveDate,
+        quantity: Decimal,
+        price: Decimal,
+        fee: Decimal,
+    ) {
+        *self.cash.entry(account_id.to_string()).or_default() -= quantity * price + fee;
+        *self
+            .positions
+            .entry((account_id.to_string(), symbol.to_string()))
+            .or_default() += quantity;
+        self.activities.push(NewActivity {
+            quantity: Some(quantity),
+            unit_price: Some(price),
+            fee: Some(fee),
+            ..Self::base_activity(account_id, symbol, ACTIVITY_TYPE_BUY, date)
+        });
+    }
+
+    fn dividend(&mut self, account_id: &str, symbol: &str, date: NaiveDate, amount: Decimal) {
+        *self.cash.entry(account_id.to_string()).or_default() += amount;
+        self.activities.push(NewActivity {
+            amount: Some(amount),
+            ..Self::base_activity(account_id, symbol, ACTIVITY_TYPE_DIVIDEND, date)
+        });
+    }
+}
+
+fn new_account(name: &str, account_type: &str, group: &str, is_default: bool) -> NewAccount {
+    NewAccount {
+        id: Some(Uuid::new_v4().to_string()),
+        name: name.to_string(),
+        account_type: account_type.to_string(),
+        group: Some(group.to_string()),
+        currency: DEMO_BASE_CURRENCY.to_string(),
+        is_default,
+        is_active: true,
+        platform_id: None,
+    }
+}
+
+fn allocation(goal_id: &str, account_id: &str, start_date: &str) -> GoalsAllocation {
+    GoalsAllocation {
+        id: Uuid::new_v4().to_string(),
+        goal_id: goal_id.to_string(),
+        account_id: account_id.to_string(),
+        init_amount: 0.0,
+        allocation_percentage: 100.0,
+        allocation_date: Some(start_date.to_string()),
+        percent_allocation: 100,
+        start_date: Some(start_date.to_string()),
+        end_date: None,
+        allocation_amount: 0.0,
+    }
+}
+
+/// Builds a multi-year Vietnamese household portfolio ending at `today`: a salary account,
+/// a stock account buying VN30 names, monthly fund SIPs, a yearly gold purchase and two goals.
+/// Prices come from a seeded random walk around each instrument's trend, so no network is needed.
+pub fn generate_demo_portfolio(today: NaiveDate) -> DemoPortfolio {
+    let start_date =
+        NaiveDate::from_ymd_opt(today.year() - DEMO_YEARS, today.month(), 1).unwrap_or(today);
+
+    let series: HashMap<&str, PriceSeries> = INSTRUMENTS
+        .iter()
+        .enumerate()
+        .map(|(i, instrument)| {
+            let seed = PRICE_SEED ^ (i as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            (
+                instrument.symbol,
+                PriceSeries::generate(instrument, seed, start_date, today),
+            )
+        })
+        .collect();
+
+    let assets: Vec<NewAsset> = std::iter::once(NewAsset::new_cash_asset(DEMO_BASE_CURRENCY))
+        .chain(INSTRUMENTS.iter().map(|instrument| NewAsset {
+            id: Some(instrument.symbol.to_string()),
+            symbol: instrument.symbol.to_string(),
+            name: Some(instrument.name.to_string()),
+            asset_type: Some(instrument.asset_type.to_string()),
+            asset_class: Some(instrument.asset_class.to_string()),
+            asset_sub_class: Some(instrument.asset_sub_class.to_string()),
+            currency: DEMO_BASE_CURRENCY.to_string(),
+            data_source: DataSource::Manual.as_str().to_string(),
+            ..Default::default()
+        }))
+        .collect();
+
+    let created_at = Utc::now();
+    let quotes: Vec<Quote> = INSTRUMENTS
+        .iter()
+        .flat_map(|instrument| {
+            let prices = &series[instrument.symbol];
+            prices
+                .dates
+                .iter()
+                .zip(prices.closes.iter())
+                .map(move |(date, close)| Quote {
+                    id: format!("{}_{}", date.format("%Y%m%d"), instrument.symbol),
+                    symbol: instrument.symbol.to_string(),
+                    timestamp: Utc
+                        .from_utc_datetime(&date.and_hms_opt(16, 0, 0).unwrap_or_default()),
+                    open: *close,
+                    high: *close,
+                    low: *close,
+                    close: *close,
+                    adjclose: *close,
+                    volume: Decimal::ZERO,
+                    currency: DEMO_BASE_CURRENCY.to_string(),
+                    data_source: DataSource::Manual,
+                    created_at,
+                })
+        })
+        .collect();
+
+    let salary = new_account("Techcombank Salary", "CASH", "Banking", true);
+    let stocks = new_account("VPS Stocks", "SECURITIES", "Investments", false);
+    let funds = new_account("Fmarket Funds", "SECURITIES", "Investments", false);
+    let gold = new_account("Gold Stash", "SECURITIES", "Investments", false);
+    let salary_id = salary.id.clone().unwrap_or_default();
+    let stocks_id = stocks.id.clone().unwrap_or_default();
+    let funds_id = funds.id.clone().unwrap_or_default();
+    let gold_id = gold.id.clone().unwrap_or_default();
+
+    let mut log = ActivityLog::new();
+    let mut month_index = 0;
+    loop {
+        let month_start = start_date
+            .checked_add_months(chrono::Months::new(month_index))
+            .unwrap_or(today);
+        if month_start > today {
+            break;
+        }
+        let (year, month) = (month_start.year(), month_start.month());
+        let year_index = (month_index / 12) as i32;
+        let on = |day: u32| Some(business_day(year, month, day)).filter(|d| *d <= today);
+
+        let monthly_salary = round_vnd(28_000_000.0 * 1.08f64.powi(year_index), 100_000.0);
+        if let Some(date) = on(5) {
+            log.cash_flow(
+                &salary_id,
+                ACTIVITY_TYPE_DEPOSIT,
+                date,
+                monthly_salary,
+                "Monthly salary",
+            );
+        }
+        if let Some(date) = on(6) {
+            log.transfer(
+                &salary_id,
+                &stocks_id,
+                date,
+                dec!(8_000_000),
+                "Monthly stock top-up",
+            );
+            log.transfer(
+                &salary_id,
+                &funds_id,
+                date,
+                dec!(3_000_000),
+                "Monthly fund SIP",
+            );
+        }
+        if let Some(date) = on(8) {
+            for (symbol, budget) in [("E1VFVN30", dec!(2_000_000)), ("VESAF", dec!(1_000_000))] {
+                let nav = series[symbol].price_on(date);
+                let units = (budget / nav).round_dp(2);
+                log.buy(&funds_id, symbol, date, units, nav, Decimal::ZERO);
+            }
+        }
+        if let Some(date) = on(10) {
+            let expenses = round_vnd(14_000_000.0 * 1.04f64.powi(year_index), 100_000.0);
+            log.cash_flow(
+                &salary_id,
+                ACTIVITY_TYPE_WITHDRAWAL,
+                date,
+                expenses,
+                "Living expenses",
+            );
+        }
+        if let Some(date) = on(12) {
+            let symbol = STOCK_ROTATION[month_index as usize % STOCK_ROTATION.len()];
+            let price = series[symbol].price_on(date);
+            let budget = log.cash_of(&stocks_id) * dec!(0.95);
+            let lots = (budget / (price * (Decimal::ONE + STOCK_FEE_RATE) * dec!(10))).floor();
+            if lots > Decimal::ZERO {
+                let quantity = lots * dec!(10);
+                let fee = (quantity * price * STOCK_FEE_RATE).round();
+                log.buy(&stocks_id, symbol, date, quantity, price, fee);
+            }
+        }
+        if month == 1 {
+            if let Some(date) = on(20) {
+                log.cash_flow(
+                    &salary_id,
+                    ACTIVITY_TYPE_DEPOSIT,
+                    date,
+                    monthly_salary,
+                    "Tet bonus",
+                );
+            }
+        }
+        // Gold for the God of Wealth day, a few weeks after Tet
+        if month == 2 {
+            if let Some(date) = on(15) {
+                let price = series["VN.GOLD"].price_on(date);
+                let affordable = log.cash_of(&salary_id) * dec!(0.8) / price;
+                let quantity = [dec!(1), dec!(0.5), dec!(0.2)]
+                    .into_iter()
+                    .find(|q| *q <= affordable);
+                if let Some(quantity) = quantity {
+                    let cost = quantity * price;
+                    log.transfer(&salary_id, &gold_id, date, cost, "Gold for Vía Thần Tài");
+                    log.buy(&gold_id, "VN.GOLD", date, quantity, price, Decimal::ZERO);
+                }
+            }
+        }
+        for (symbol, pay_month, per_share) in DIVIDENDS {
+            if month != pay_month {
+                continue;
+            }
+            let shares = log.position_of(&stocks_id, symbol);
+            if let (Some(date), true) = (on(20), shares > Decimal::ZERO) {
+                log.dividend(&stocks_id, symbol, date, shares * per_share);
+            }
+        }
+
+        month_index += 1;
+    }
+
+    let start_str = start_date.format("%Y-%m-%d").to_string();
+    let emergency_fund = NewGoal {
+        id: Some(Uuid::new_v4().to_string()),
+        title: "Emergency fund".to_string(),
+        description: Some("Six months of living expenses".to_string()),
+        target_amount: 100_000_000.0,
+        is_achieved: false,
+        target_return_rate: None,
+        due_date: None,
+        monthly_investment: None,
+        start_date: Some(start_str.clone()),
+        initial_actual_value: None,
+        goal_type: GOAL_TYPE_EMERGENCY_FUND.to_string(),
+        target_months: Some(6),
+        priority: 1,
+        depends_on_goal_id: None,
+        currency: None,
+    };
+    let down_payment_due = NaiveDate::from_ymd_opt(today.year() + 4, 12, 31).unwrap_or(today);
+    let down_payment = NewGoal {
+        id: Some(Uuid::new_v4().to_string()),
+        title: "Apartment down payment".to_string(),
+        description: Some("30% down payment for a two-bedroom apartment".to_string()),
+        target_amount: 1_500_000_000.0,
+        is_achieved: false,
+        target_return_rate: Some(8.0),
+        due_date: Some(down_payment_due.format("%Y-%m-%d").to_string()),
+        monthly_investment: Some(11_000_000.0),
+        start_date: Some(start_str.clone()),
+        initial_actual_value: None,
+        goal_type: GOAL_TYPE_STANDARD.to_string(),
+        target_months: None,
+        priority: 0,
+        depends_on_goal_id: None,
+        currency: None,
+    };
+
+    let emergency_id = emergency_fund.id.clone().unwrap_or_default();
+    let down_payment_id = down_payment.id.clone().unwrap_or_default();
+    let goal_allocations = vec![
+        allocation(&emergency_id, &salary_id, &start_str),
+        allocation(&down_payment_id, &stocks_id, &start_str),
+        allocation(&down_payment_id, &funds_id, &start_str),
+        allocation(&down_payment_id, &gold_id, &start_str),
+    ];
+
+    DemoPortfolio {
+        base_currency: DEMO_BASE_CURRENCY.to_string(),
+        start_date,
+        assets,
+        quotes,
+        accounts: vec![salary, stocks, funds, gold],
+        activities: log.activities,
+        goals: vec![emergency_fund, down_payment],
+        goal_allocations,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()
+    }
+
+    #[test]
+    fn demo_portfolio_is_deterministic_and_ends_today() {
+        let first = generate_demo_portfolio(today());
+        let second = generate_demo_portfolio(today());
+
+        assert_eq!(
+            first.start_date,
+            NaiveDate::from_ymd_opt(2023, 10, 1).unwrap()
+        );
+        assert_eq!(first.accounts.len(), 4);
+        assert_eq!(first.goals.len(), 2);
+        assert_eq!(first.activities.len(), second.activities.len());
+
+        let closes: Vec<Decimal> = first.quotes.iter().map(|q| q.close).collect();
+        let closes_again: Vec<Decimal> = second.quotes.iter().map(|q| q.close).collect();
+        assert_eq!(closes, closes_again);
+
+        let last_date = today().format("%Y-%m-%d").to_string();
+        assert!(first
+            .activities
+            .iter()
+            .all(|a| a.activity_date <= last_date && a.validate().is_ok()));
+    }
+
+    #[test]
+    fn demo_portfolio_covers_every_activity_kind() {
+        let portfolio = generate_demo_portfolio(today());
+        for activity_type in [
+            ACTIVITY_TYPE_DEPOSIT,
+            ACTIVITY_TYPE_WITHDRAWAL,
+            ACTIVITY_TYPE_TRANSFER_IN,
+            ACTIVITY_TYPE_TRANSFER_OUT,
+            ACTIVITY_TYPE_BUY,
+            ACTIVITY_TYPE_DIVIDEND,
+        ] {
+            assert!(
+                portfolio
+                    .activities
+                    .iter()
+                    .any(|a| a.activity_type == activity_type),
+                "missing {activity_type}"
+            );
+        }
+        assert!(portfolio.activities.iter().any(|a| a.asset_id == "VN.GOLD"));
+        // Every traded symbol has an asset row to point at
+        assert!(portfolio.activities.iter().all(|a| portfolio
+            .assets
+            .iter()
+            .any(|asset| asset.id.as_deref() == Some(a.asset_id.as_str()))));
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::accounts::NewAccount;
+use crate::activities::NewActivity;
+use crate::assets::NewAsset;
+use crate::goals::goals_model::{GoalsAllocation, NewGoal};
+use crate::market_data::Quote;
+
+/// Generated sample portfolio, ready to be written into an empty profile
+#[derive(Debug, Clone)]
+pub struct DemoPortfolio {
+    pub base_currency: String,
+    pub start_date: NaiveDate,
+    pub assets: Vec<NewAsset>,
+    pub quotes: Vec<Quote>,
+    pub accounts: Vec<NewAccount>,
+    pub activities: Vec<NewActivity>,
+    pub goals: Vec<NewGoal>,
+    pub goal_allocations: Vec<GoalsAllocation>,
+}
+
+/// What was written by a demo seed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DemoDataSummary {
+    pub start_date: NaiveDate,
+    pub accounts: usize,
+    pub activities: usize,
+    pub assets: usize,
+    pub quotes: usize,
+    pub goals: usize,
+}
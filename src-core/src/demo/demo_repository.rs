@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use super::demo_model::{DemoDataSummary, DemoPortfolio};
+use super::demo_traits::DemoRepositoryTrait;
+use crate::accounts::AccountDB;
+use crate::activities::ActivityDB;
+use crate::assets::assets_model::AssetDB;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::market_data::market_data_model::QuoteDb;
+use crate::schema::{accounts, activities, app_settings, assets, goals, goals_allocation, quotes};
+use crate::settings::AppSetting;
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+pub struct DemoRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl DemoRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        DemoRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl DemoRepositoryTrait for DemoRepository {
+    fn has_accounts(&self) -> Result<bool> {
+        let mut conn = get_connection(&self.pool)?;
+        let count: i64 = accounts::table.count().get_result(&mut conn)?;
+        Ok(count > 0)
+    }
+
+    async fn insert_portfolio(&self, portfolio: DemoPortfolio) -> Result<DemoDataSummary> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<DemoDataSummary> {
+                    let existing_accounts: i64 = accounts::table.count().get_result(conn)?;
+                    if existing_accounts > 0 {
+                        return Err(LocalizedMessage::new(MessageCode::ProfileNotEmpty).into());
+                    }
+
+                    let summary = DemoDataSummary {
+                        start_date: portfolio.start_date,
+                        accounts: portfolio.accounts.len(),
+                        activities: portfolio.activities.len(),
+                        assets: portfolio.assets.len(),
+                        quotes: portfolio.quotes.len(),
+                        goals: portfolio.goals.len(),
+                    };
+
+                    for new_asset in portfolio.assets {
+                        let asset_db: AssetDB = new_asset.into();
+                        diesel::insert_or_ignore_into(assets::table)
+                            .values(&asset_db)
+                            .execute(conn)?;
+                    }
+
+                    let quote_rows: Vec<QuoteDb> =
+                        portfolio.quotes.iter().map(QuoteDb::from).collect();
+                    for chunk in quote_rows.chunks(500) {
+                        diesel::replace_into(quotes::table)
+                            .values(chunk)
+                            .execute(conn)?;
+                    }
+
+                    for new_account in portfolio.accounts {
+                        let account_db: AccountDB = new_account.into();
+                        diesel::insert_into(accounts::table)
+                            .values(&account_db)
+                            .execute(conn)?;
+                    }
+
+                    let activity_rows: Vec<ActivityDB> = portfolio
+                        .activities
+                        .into_iter()
+                        .map(ActivityDB::from)
+                        .collect();
+                    for chunk in activity_rows.chunks(500) {
+                        diesel::insert_into(activities::table)
+                            .values(chunk)
+                            .execute(conn)?;
+                    }
+
+                    for new_goal in portfolio.goals {
+                        diesel::insert_into(goals::table)
+                            .values(&new_goal)
+                            .execute(conn)?;
+                    }
+                    diesel::insert_into(goals_allocation::table)
+                        .values(&portfolio.goal_allocations)
+                        .execute(conn)?;
+
+                    for (key, value) in [
+                        ("base_currency", portfolio.base_currency),
+                        ("onboarding_completed", true.to_string()),
+                    ] {
+                        diesel::replace_into(app_settings::table)
+                            .values(&AppSetting {
+                                setting_key: key.to_string(),
+                                setting_value: value,
+                            })
+                            .execute(conn)?;
+                    }
+
+                    Ok(summary)
+                },
+            )
+            .await
+    }
+}
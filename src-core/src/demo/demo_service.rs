@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use log::{info, warn};
+use std::sync::{Arc, RwLock};
+
+use super::demo_generator::generate_demo_portfolio;
+use super::demo_model::DemoDataSummary;
+use super::demo_traits::{DemoRepositoryTrait, DemoServiceTrait};
+use crate::errors::Result;
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+pub struct DemoService {
+    repository: Arc<dyn DemoRepositoryTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl DemoService {
+    pub fn new(
+        repository: Arc<dyn DemoRepositoryTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            base_currency,
+        }
+    }
+}
+
+#[async_trait]
+impl DemoServiceTrait for DemoService {
+    async fn seed_demo_data(&self) -> Result<DemoDataSummary> {
+        if self.repository.has_accounts()? {
+            return Err(LocalizedMessage::new(MessageCode::ProfileNotEmpty).into());
+        }
+
+        let portfolio = generate_demo_portfolio(chrono::Local::now().date_naive());
+        let base_currency = portfolio.base_currency.clone();
+        let summary = self.repository.insert_portfolio(portfolio).await?;
+        info!(
+            "Seeded demo data: {} accounts, {} activities, {} quotes",
+            summary.accounts, summary.activities, summary.quotes
+        );
+
+        match self.base_currency.write() {
+            Ok(mut current) => *current = base_currency,
+            Err(e) => warn!("Failed to update cached base currency: {}", e),
+        }
+
+        Ok(summary)
+    }
+}
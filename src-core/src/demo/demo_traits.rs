@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+
+use super::demo_model::{DemoDataSummary, DemoPortfolio};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait DemoRepositoryTrait: Send + Sync {
+    fn has_accounts(&self) -> Result<bool>;
+    /// Writes the whole portfolio in a single transaction; nothing is persisted on failure
+    async fn insert_portfolio(&self, portfolio: DemoPortfolio) -> Result<DemoDataSummary>;
+}
+
+#[async_trait]
+pub trait DemoServiceTrait: Send + Sync {
+    /// Fills an empty profile with the generated sample portfolio
+    async fn seed_demo_data(&self) -> Result<DemoDataSummary>;
+}
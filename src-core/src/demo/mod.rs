@@ -0,0 +1,11 @@
+mod demo_generator;
+mod demo_model;
+mod demo_repository;
+mod demo_service;
+mod demo_traits;
+
+pub use demo_generator::{generate_demo_portfolio, DEMO_BASE_CURRENCY, DEMO_PROFILE_NAME};
+pub use demo_model::{DemoDataSummary, DemoPortfolio};
+pub use demo_repository::DemoRepository;
+pub use demo_service::DemoService;
+pub use demo_traits::{DemoRepositoryTrait, DemoServiceTrait};
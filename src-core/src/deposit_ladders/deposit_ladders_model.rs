@@ -0,0 +1,353 @@
+use chrono::{Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::accounts::Account;
+use crate::deposit_rates::DepositRate;
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::PlannedCashFlow;
+
+/// Group the deposit accounts of a confirmed ladder are filed under
+pub const LADDER_ACCOUNT_GROUP: &str = "Term deposits";
+
+fn invalid(message: String) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message))
+}
+
+/// Share of the lump sum that must be back in cash within a number of months
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiquidityNeed {
+    pub within_months: i32,
+    /// Percent of the lump sum
+    pub percent: Decimal,
+}
+
+/// What to propose a ladder for. The needs' percents add up to 100.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositLadderRequest {
+    /// Cash account the deposits are funded from
+    pub account_id: String,
+    pub amount: Decimal,
+    /// Today when unset
+    pub start_date: Option<NaiveDate>,
+    pub liquidity: Vec<LiquidityNeed>,
+}
+
+impl DepositLadderRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.account_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "accountId".to_string(),
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(invalid(
+                "The amount to deposit must be positive".to_string(),
+            ));
+        }
+        if self.liquidity.is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "liquidity".to_string(),
+            )));
+        }
+        for need in &self.liquidity {
+            if need.within_months <= 0 {
+                return Err(invalid(format!(
+                    "Cash needed within {} months cannot be planned for",
+                    need.within_months
+                )));
+            }
+            if need.percent <= Decimal::ZERO {
+                return Err(invalid(format!(
+                    "The share needed within {} months must be positive",
+                    need.within_months
+                )));
+            }
+        }
+        let total: Decimal = self.liquidity.iter().map(|need| need.percent).sum();
+        if total != Decimal::ONE_HUNDRED {
+            return Err(invalid(format!(
+                "The liquidity shares add up to {}%, not 100%",
+                total.normalize()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// One term deposit of a ladder
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositLadderRung {
+    pub bank_code: String,
+    pub bank_name: String,
+    pub term_months: i32,
+    /// Annual rate in percent
+    pub rate: Decimal,
+    pub amount: Decimal,
+    pub maturity_date: NaiveDate,
+    /// Simple interest paid at maturity
+    pub interest: Decimal,
+}
+
+impl DepositLadderRung {
+    fn new(
+        rate: &DepositRate,
+        amount: Decimal,
+        start_date: NaiveDate,
+    ) -> Result<DepositLadderRung> {
+        Ok(DepositLadderRung {
+            bank_code: rate.bank_code.clone(),
+            bank_name: rate.bank_name.clone(),
+            term_months: rate.term_months,
+            rate: rate.rate,
+            amount,
+            maturity_date: maturity_date(start_date, rate.term_months)?,
+            interest: deposit_interest(amount, rate.rate, rate.term_months),
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.bank_code.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "bankCode".to_string(),
+            )));
+        }
+        if self.term_months <= 0 {
+            return Err(invalid(format!(
+                "{} deposit has a term of {} months",
+                self.bank_code, self.term_months
+            )));
+        }
+        if self.rate < Decimal::ZERO || self.rate >= Decimal::ONE_HUNDRED {
+            return Err(invalid(format!(
+                "{} deposit has a rate of {}%",
+                self.bank_code, self.rate
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(invalid(format!(
+                "{} deposit for {} months must have a positive amount",
+                self.bank_code, self.term_months
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A proposed ladder, as returned for review and sent back, possibly edited, to confirm
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositLadder {
+    pub account_id: String,
+    pub start_date: NaiveDate,
+    /// Shortest term first
+    pub rungs: Vec<DepositLadderRung>,
+    pub total_amount: Decimal,
+    pub total_interest: Decimal,
+    /// Rate across the rungs weighted by amount, in percent
+    pub average_rate: Decimal,
+}
+
+impl DepositLadder {
+    /// Orders the rungs and works out their maturities, interest and the totals again, so a
+    /// ladder edited for review is stored consistently
+    pub fn from_rungs(
+        account_id: String,
+        start_date: NaiveDate,
+        rungs: Vec<DepositLadderRung>,
+    ) -> Result<DepositLadder> {
+        if rungs.is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "rungs".to_string(),
+            )));
+        }
+        let mut rungs = rungs
+            .into_iter()
+            .map(|rung| {
+                rung.validate()?;
+                Ok(DepositLadderRung {
+                    maturity_date: maturity_date(start_date, rung.term_months)?,
+                    interest: deposit_interest(rung.amount, rung.rate, rung.term_months),
+                    ..rung
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        rungs.sort_by(|a, b| (a.term_months, &a.bank_code).cmp(&(b.term_months, &b.bank_code)));
+
+        let total_amount: Decimal = rungs.iter().map(|rung| rung.amount).sum();
+        let total_interest = rungs.iter().map(|rung| rung.interest).sum();
+        let average_rate = (rungs
+            .iter()
+            .map(|rung| rung.amount * rung.rate)
+            .sum::<Decimal>()
+            / total_amount)
+            .round_dp(2);
+        Ok(DepositLadder {
+            account_id,
+            start_date,
+            rungs,
+            total_amount,
+            total_interest,
+            average_rate,
+        })
+    }
+}
+
+/// The deposit accounts opened for a confirmed ladder, with the maturity of each
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmedDepositLadder {
+    pub accounts: Vec<Account>,
+    pub maturities: Vec<PlannedCashFlow>,
+}
+
+fn maturity_date(start_date: NaiveDate, term_months: i32) -> Result<NaiveDate> {
+    u32::try_from(term_months)
+        .ok()
+        .and_then(|months| start_date.checked_add_months(Months::new(months)))
+        .ok_or_else(|| invalid(format!("A term of {} months is out of range", term_months)))
+}
+
+/// Simple interest over the term, in whole VND
+pub fn deposit_interest(amount: Decimal, rate: Decimal, term_months: i32) -> Decimal {
+    (amount * rate / Decimal::ONE_HUNDRED * Decimal::from(term_months) / Decimal::from(12)).floor()
+}
+
+/// Splits `amount` across the liquidity needs and puts each share in the best paying deposit
+/// that matures in time: the highest rate among the tenors no longer than the need, the longer
+/// tenor and then the first bank by code on a tie. Shares landing on the same bank and tenor are one deposit. Rounding is
+/// left on the last need so the rungs add up to `amount`.
+pub fn plan_deposit_ladder(
+    request: &DepositLadderRequest,
+    rates: &[DepositRate],
+    start_date: NaiveDate,
+) -> Result<DepositLadder> {
+    request.validate()?;
+    let mut rungs: Vec<DepositLadderRung> = Vec::new();
+    let mut allocated = Decimal::ZERO;
+    for (index, need) in request.liquidity.iter().enumerate() {
+        let best = rates
+            .iter()
+            .filter(|rate| rate.term_months > 0 && rate.term_months <= need.within_months)
+            .max_by(|a, b| {
+                (a.rate, a.term_months)
+                    .cmp(&(b.rate, b.term_months))
+                    .then_with(|| b.bank_code.cmp(&a.bank_code))
+            })
+            .ok_or_else(|| {
+                invalid(format!(
+                    "No bank in the catalog offers a deposit maturing within {} months",
+                    need.within_months
+                ))
+            })?;
+        let share = if index + 1 == request.liquidity.len() {
+            request.amount - allocated
+        } else {
+            (request.amount * need.percent / Decimal::ONE_HUNDRED).floor()
+        };
+        allocated += share;
+
+        match rungs
+            .iter_mut()
+            .find(|rung| rung.bank_code == best.bank_code && rung.term_months == best.term_months)
+        {
+            Some(rung) => rung.amount += share,
+            None => rungs.push(DepositLadderRung::new(best, share, start_date)?),
+        }
+    }
+    DepositLadder::from_rungs(request.account_id.clone(), start_date, rungs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deposit_rates::{bundled_deposit_rates, BUNDLED_SOURCE};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn catalog() -> Vec<DepositRate> {
+        let fetched_at = Utc::now().naive_utc();
+        bundled_deposit_rates()
+            .into_iter()
+            .map(|rate| DepositRate {
+                bank_code: rate.bank_code,
+                bank_name: rate.bank_name,
+                term_months: rate.term_months,
+                rate: rate.rate,
+                source: BUNDLED_SOURCE.to_string(),
+                fetched_at,
+            })
+            .collect()
+    }
+
+    fn need(within_months: i32, percent: Decimal) -> LiquidityNeed {
+        LiquidityNeed {
+            within_months,
+            percent,
+        }
+    }
+
+    #[test]
+    fn ladder_puts_each_share_at_the_best_rate_maturing_in_time() {
+        let start = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let request = DepositLadderRequest {
+            account_id: "cash".to_string(),
+            amount: dec!(1_000_000_000),
+            start_date: None,
+            liquidity: vec![
+                need(2, dec!(20)),
+                need(4, dec!(30)),
+                // Nothing matures in 9 months, so the 6-month tenor is used
+                need(9, dec!(20)),
+                need(36, dec!(30)),
+            ],
+        };
+
+        let ladder = plan_deposit_ladder(&request, &catalog(), start).unwrap();
+        let rungs: Vec<(&str, i32, Decimal)> = ladder
+            .rungs
+            .iter()
+            .map(|rung| (rung.bank_code.as_str(), rung.term_months, rung.amount))
+            .collect();
+        assert_eq!(
+            rungs,
+            vec![
+                ("TPB", 1, dec!(200_000_000)),
+                ("TPB", 3, dec!(300_000_000)),
+                ("TPB", 6, dec!(200_000_000)),
+                // MB and TPBank both pay 5.7% for 24 months
+                ("MBB", 24, dec!(300_000_000)),
+            ]
+        );
+        assert_eq!(
+            ladder.rungs[2].maturity_date,
+            NaiveDate::from_ymd_opt(2027, 4, 18).unwrap()
+        );
+        // 4.9% on 200m for half a year
+        assert_eq!(ladder.rungs[2].interest, dec!(4_900_000));
+        assert_eq!(ladder.total_amount, dec!(1_000_000_000));
+        assert_eq!(ladder.average_rate, dec!(4.63));
+    }
+
+    #[test]
+    fn ladder_rejects_needs_that_do_not_add_up_or_cannot_be_met() {
+        let start = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let mut request = DepositLadderRequest {
+            account_id: "cash".to_string(),
+            amount: dec!(100_000_000),
+            start_date: None,
+            liquidity: vec![need(3, dec!(50)), need(12, dec!(40))],
+        };
+        assert!(plan_deposit_ladder(&request, &catalog(), start).is_err());
+
+        request.liquidity = vec![need(3, dec!(50)), need(12, dec!(50))];
+        let rates: Vec<DepositRate> = catalog()
+            .into_iter()
+            .filter(|rate| rate.term_months >= 6)
+            .collect();
+        assert!(plan_deposit_ladder(&request, &rates, start).is_err());
+    }
+}
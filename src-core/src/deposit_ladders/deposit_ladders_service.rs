@@ -0,0 +1,199 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::deposit_ladders_model::{
+    plan_deposit_ladder, ConfirmedDepositLadder, DepositLadder, DepositLadderRequest,
+    DepositLadderRung, LADDER_ACCOUNT_GROUP,
+};
+use super::deposit_ladders_traits::DepositLadderServiceTrait;
+use crate::accounts::{
+    Account, AccountRepositoryTrait, AccountServiceTrait, NewAccount, ACCOUNT_TYPE_CASH,
+};
+use crate::activities::{
+    ActivityServiceTrait, NewActivity, ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT,
+};
+use crate::assets::AssetServiceTrait;
+use crate::deposit_rates::{DepositRateRepositoryTrait, CATALOG_CURRENCY};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::{
+    CashFlowFrequency, CashFlowKind, ForecastServiceTrait, NewPlannedCashFlow, TermDepositTerms,
+    FORECAST_DATE_FORMAT,
+};
+
+pub struct DepositLadderService {
+    rate_repository: Arc<dyn DepositRateRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    account_service: Arc<dyn AccountServiceTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    forecast_service: Arc<dyn ForecastServiceTrait>,
+}
+
+fn invalid(message: String) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message))
+}
+
+/// Name of the account opened for a rung
+fn deposit_name(rung: &DepositLadderRung) -> String {
+    format!(
+        "{} {}-month deposit ({})",
+        rung.bank_name,
+        rung.term_months,
+        rung.maturity_date.format(FORECAST_DATE_FORMAT)
+    )
+}
+
+impl DepositLadderService {
+    pub fn new(
+        rate_repository: Arc<dyn DepositRateRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        account_service: Arc<dyn AccountServiceTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        forecast_service: Arc<dyn ForecastServiceTrait>,
+    ) -> Self {
+        Self {
+            rate_repository,
+            account_repository,
+            account_service,
+            asset_service,
+            activity_service,
+            forecast_service,
+        }
+    }
+
+    /// The cash account a ladder is funded from, in the catalog's currency
+    fn funding_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type != ACCOUNT_TYPE_CASH {
+            return Err(invalid(format!(
+                "{} is not a cash account; term deposits are funded from cash",
+                account.name
+            )));
+        }
+        if account.currency != CATALOG_CURRENCY {
+            return Err(invalid(format!(
+                "{} holds {}, but the deposit rates are for {}",
+                account.name, account.currency, CATALOG_CURRENCY
+            )));
+        }
+        Ok(account)
+    }
+
+    fn transfer(
+        account_id: &str,
+        activity_type: &str,
+        cash_asset_id: &str,
+        date: &str,
+        rung: &DepositLadderRung,
+        comment: &str,
+    ) -> NewActivity {
+        NewActivity {
+            id: Some(Uuid::new_v4().to_string()),
+            account_id: account_id.to_string(),
+            asset_id: cash_asset_id.to_string(),
+            activity_type: activity_type.to_string(),
+            activity_date: date.to_string(),
+            quantity: None,
+            unit_price: None,
+            currency: CATALOG_CURRENCY.to_string(),
+            fee: None,
+            amount: Some(rung.amount),
+            is_draft: false,
+            comment: Some(comment.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl DepositLadderServiceTrait for DepositLadderService {
+    fn propose_deposit_ladder(&self, request: DepositLadderRequest) -> Result<DepositLadder> {
+        self.funding_account(&request.account_id)?;
+        let rates = self.rate_repository.get_rates()?;
+        let start_date = request
+            .start_date
+            .unwrap_or_else(|| Utc::now().date_naive());
+        plan_deposit_ladder(&request, &rates, start_date)
+    }
+
+    async fn confirm_deposit_ladder(
+        &self,
+        ladder: DepositLadder,
+    ) -> Result<ConfirmedDepositLadder> {
+        let account = self.funding_account(&ladder.account_id)?;
+        let ladder =
+            DepositLadder::from_rungs(account.id.clone(), ladder.start_date, ladder.rungs)?;
+
+        let cash_asset_id = format!("$CASH-{}", CATALOG_CURRENCY);
+        if self.asset_service.get_asset_by_id(&cash_asset_id).is_err() {
+            self.asset_service
+                .create_cash_asset(CATALOG_CURRENCY)
+                .await?;
+        }
+        let date = ladder.start_date.format(FORECAST_DATE_FORMAT).to_string();
+
+        let mut confirmed = ConfirmedDepositLadder {
+            accounts: Vec::with_capacity(ladder.rungs.len()),
+            maturities: Vec::with_capacity(ladder.rungs.len()),
+        };
+        for rung in &ladder.rungs {
+            let name = deposit_name(rung);
+            let deposit = self
+                .account_service
+                .create_account(NewAccount {
+                    id: None,
+                    name: name.clone(),
+                    account_type: ACCOUNT_TYPE_CASH.to_string(),
+                    group: Some(LADDER_ACCOUNT_GROUP.to_string()),
+                    currency: CATALOG_CURRENCY.to_string(),
+                    is_default: false,
+                    is_active: true,
+                    platform_id: None,
+                })
+                .await?;
+
+            for (account_id, activity_type) in [
+                (&account.id, ACTIVITY_TYPE_TRANSFER_OUT),
+                (&deposit.id, ACTIVITY_TYPE_TRANSFER_IN),
+            ] {
+                self.activity_service
+                    .create_activity(Self::transfer(
+                        account_id,
+                        activity_type,
+                        &cash_asset_id,
+                        &date,
+                        rung,
+                        &name,
+                    ))
+                    .await?;
+            }
+
+            // Principal and interest come back on the maturity date
+            let maturity = self
+                .forecast_service
+                .create_planned_cash_flow(NewPlannedCashFlow {
+                    id: None,
+                    name,
+                    kind: CashFlowKind::TermDepositMaturity,
+                    account_id: Some(deposit.id.clone()),
+                    amount: rung.amount + rung.interest,
+                    currency: CATALOG_CURRENCY.to_string(),
+                    frequency: CashFlowFrequency::Once,
+                    start_date: rung.maturity_date,
+                    end_date: None,
+                    is_active: true,
+                    term_deposit: TermDepositTerms {
+                        bank_code: Some(rung.bank_code.clone()),
+                        term_months: Some(rung.term_months),
+                        interest_rate: Some(rung.rate),
+                    },
+                })
+                .await?;
+            confirmed.accounts.push(deposit);
+            confirmed.maturities.push(maturity);
+        }
+        Ok(confirmed)
+    }
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use super::deposit_ladders_model::{ConfirmedDepositLadder, DepositLadder, DepositLadderRequest};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait DepositLadderServiceTrait: Send + Sync {
+    /// Proposes staggered term deposits across the catalog's tenors so each share of the lump
+    /// sum is back in cash when the liquidity profile needs it. Nothing is stored.
+    fn propose_deposit_ladder(&self, request: DepositLadderRequest) -> Result<DepositLadder>;
+    /// Opens a cash account per rung, transfers its amount there from the funding account and
+    /// plans its maturity, so rollover suggestions follow the deposits
+    async fn confirm_deposit_ladder(&self, ladder: DepositLadder)
+        -> Result<ConfirmedDepositLadder>;
+}
@@ -0,0 +1,10 @@
+mod deposit_ladders_model;
+mod deposit_ladders_service;
+mod deposit_ladders_traits;
+
+pub use deposit_ladders_model::{
+    deposit_interest, plan_deposit_ladder, ConfirmedDepositLadder, DepositLadder,
+    DepositLadderRequest, DepositLadderRung, LiquidityNeed, LADDER_ACCOUNT_GROUP,
+};
+pub use deposit_ladders_service::DepositLadderService;
+pub use deposit_ladders_traits::DepositLadderServiceTrait;
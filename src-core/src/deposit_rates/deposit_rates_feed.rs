@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use std::time::Duration;
+
+use super::deposit_rates_model::NewDepositRate;
+use super::deposit_rates_traits::DepositRateFeed;
+use crate::errors::{Error, Result};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Reads a JSON array of rates from a URL
+pub struct HttpDepositRateFeed {
+    client: Client,
+    url: String,
+}
+
+impl HttpDepositRateFeed {
+    pub fn new(url: String) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("WealthVN/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        HttpDepositRateFeed { client, url }
+    }
+}
+
+#[async_trait]
+impl DepositRateFeed for HttpDepositRateFeed {
+    async fn fetch_rates(&self) -> Result<Vec<NewDepositRate>> {
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .map_err(|e| Error::DepositRateFeed(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::DepositRateFeed(format!(
+                "{} answered {}",
+                self.url, status
+            )));
+        }
+        response
+            .json::<Vec<NewDepositRate>>()
+            .await
+            .map_err(|e| Error::DepositRateFeed(e.to_string()))
+    }
+}
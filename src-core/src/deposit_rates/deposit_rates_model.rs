@@ -0,0 +1,392 @@
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::{occurrences, CashFlowKind, PlannedCashFlow};
+use crate::notifications::{Notification, NotificationChannel};
+
+/// Source of the rates shipped with the app, used until a feed is configured
+pub const BUNDLED_SOURCE: &str = "BUNDLED";
+
+/// Hours after which the catalog is fetched again
+pub const RATE_REFRESH_HOURS: i64 = 24;
+
+/// Maturities this many days ahead are compared against the catalog
+pub const MATURITY_WINDOW_DAYS: u64 = 30;
+
+/// Smallest gain in annual rate, in percentage points, worth suggesting a move for
+pub const MIN_RATE_GAIN: Decimal = dec!(0.3);
+
+/// Tenor assumed for a deposit whose term is not recorded
+pub const DEFAULT_TERM_MONTHS: i32 = 12;
+
+/// Currency the catalog's rates are for
+pub const CATALOG_CURRENCY: &str = "VND";
+
+/// Tenors of the bundled rates, in months
+const BUNDLED_TERMS: [i32; 5] = [1, 3, 6, 12, 24];
+
+/// Online counter rates for individuals at major banks, in percent a year, for the tenors
+/// in `BUNDLED_TERMS`
+const BUNDLED_RATES: [(&str, &str, [Decimal; 5]); 10] = [
+    (
+        "VCB",
+        "Vietcombank",
+        [dec!(1.6), dec!(1.9), dec!(2.9), dec!(4.6), dec!(4.7)],
+    ),
+    (
+        "BIDV",
+        "BIDV",
+        [dec!(1.7), dec!(2.0), dec!(3.0), dec!(4.7), dec!(4.8)],
+    ),
+    (
+        "CTG",
+        "VietinBank",
+        [dec!(1.7), dec!(2.0), dec!(3.0), dec!(4.7), dec!(4.8)],
+    ),
+    (
+        "AGR",
+        "Agribank",
+        [dec!(2.4), dec!(2.9), dec!(3.6), dec!(4.8), dec!(4.8)],
+    ),
+    (
+        "TCB",
+        "Techcombank",
+        [dec!(3.25), dec!(3.55), dec!(4.55), dec!(4.85), dec!(4.85)],
+    ),
+    (
+        "VPB",
+        "VPBank",
+        [dec!(3.7), dec!(3.9), dec!(4.8), dec!(5.3), dec!(5.3)],
+    ),
+    (
+        "MBB",
+        "MB",
+        [dec!(3.5), dec!(3.8), dec!(4.4), dec!(4.9), dec!(5.7)],
+    ),
+    (
+        "ACB",
+        "ACB",
+        [dec!(3.1), dec!(3.5), dec!(4.2), dec!(4.9), dec!(4.9)],
+    ),
+    (
+        "STB",
+        "Sacombank",
+        [dec!(3.3), dec!(3.6), dec!(4.6), dec!(5.3), dec!(5.6)],
+    ),
+    (
+        "TPB",
+        "TPBank",
+        [dec!(3.7), dec!(4.0), dec!(4.9), dec!(5.5), dec!(5.7)],
+    ),
+];
+
+/// Database row for `deposit_rates`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::deposit_rates)]
+#[diesel(primary_key(bank_code, term_months))]
+pub struct DepositRateDB {
+    pub bank_code: String,
+    pub term_months: i32,
+    pub bank_name: String,
+    pub rate: String,
+    pub source: String,
+    pub fetched_at: NaiveDateTime,
+}
+
+/// A bank's counter rate for a term deposit of one tenor
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositRate {
+    pub bank_code: String,
+    pub bank_name: String,
+    pub term_months: i32,
+    /// Annual rate in percent
+    pub rate: Decimal,
+    pub source: String,
+    pub fetched_at: NaiveDateTime,
+}
+
+impl TryFrom<DepositRateDB> for DepositRate {
+    type Error = Error;
+
+    fn try_from(db: DepositRateDB) -> Result<Self> {
+        Ok(DepositRate {
+            bank_code: db.bank_code,
+            bank_name: db.bank_name,
+            term_months: db.term_months,
+            rate: Decimal::from_str(&db.rate)?,
+            source: db.source,
+            fetched_at: db.fetched_at,
+        })
+    }
+}
+
+/// A rate as a feed publishes it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NewDepositRate {
+    pub bank_code: String,
+    pub bank_name: String,
+    pub term_months: i32,
+    pub rate: Decimal,
+}
+
+impl NewDepositRate {
+    pub fn validate(&self) -> Result<()> {
+        if self.bank_code.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "bankCode".to_string(),
+            )));
+        }
+        if self.term_months <= 0 {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} has a rate for a term of {} months",
+                self.bank_code, self.term_months
+            ))));
+        }
+        if self.rate < Decimal::ZERO || self.rate > Decimal::ONE_HUNDRED {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} has a rate of {}% for {} months",
+                self.bank_code, self.rate, self.term_months
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// The rates shipped with the app
+pub fn bundled_deposit_rates() -> Vec<NewDepositRate> {
+    BUNDLED_RATES
+        .iter()
+        .flat_map(|(code, name, rates)| {
+            BUNDLED_TERMS
+                .iter()
+                .zip(rates)
+                .map(|(term_months, rate)| NewDepositRate {
+                    bank_code: code.to_string(),
+                    bank_name: name.to_string(),
+                    term_months: *term_months,
+                    rate: *rate,
+                })
+        })
+        .collect()
+}
+
+/// Highest rate offered for a tenor
+pub fn best_rate(rates: &[DepositRate], term_months: i32) -> Option<&DepositRate> {
+    rates
+        .iter()
+        .filter(|rate| rate.term_months == term_months)
+        .max_by_key(|rate| rate.rate)
+}
+
+/// A maturing deposit that would earn more rolled over at another bank
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RolloverSuggestion {
+    /// Planned cash flow of the maturity
+    pub cash_flow_id: String,
+    pub name: String,
+    pub account_id: Option<String>,
+    pub maturity_date: NaiveDate,
+    pub amount: Decimal,
+    pub term_months: i32,
+    pub current_bank_code: Option<String>,
+    /// Rate of the deposit, or what its bank offers now for the tenor when not recorded
+    pub current_rate: Decimal,
+    pub best_bank_code: String,
+    pub best_bank_name: String,
+    pub best_rate: Decimal,
+    /// Extra interest over the term at the best rate, in VND
+    pub extra_interest: Decimal,
+}
+
+impl RolloverSuggestion {
+    pub fn notification(&self, now: DateTime<Utc>) -> Notification {
+        Notification {
+            id: format!("deposit-rate-{}-{}", self.cash_flow_id, self.maturity_date),
+            channel: NotificationChannel::DepositRate,
+            title: format!("{} matures on {}", self.name, self.maturity_date),
+            body: format!(
+                "{} pays {}% for {} months against {}% now; rolling over there earns about {} VND \
+                 more",
+                self.best_bank_name,
+                self.best_rate.normalize(),
+                self.term_months,
+                self.current_rate.normalize(),
+                self.extra_interest.to_i64().unwrap_or_default()
+            ),
+            created_at: now,
+        }
+    }
+}
+
+/// Term deposits in VND maturing between `today` and `within_days` later that would earn at
+/// least `MIN_RATE_GAIN` points more at the best bank for their tenor. Deposits without a
+/// recorded rate are compared at what their bank offers now; those with neither a rate nor a
+/// bank in the catalog are left out.
+pub fn suggest_rollovers(
+    flows: &[PlannedCashFlow],
+    rates: &[DepositRate],
+    today: NaiveDate,
+    within_days: u64,
+) -> Vec<RolloverSuggestion> {
+    let until = today + Days::new(within_days);
+    let mut suggestions = Vec::new();
+    for flow in flows {
+        if !flow.is_active
+            || flow.kind != CashFlowKind::TermDepositMaturity
+            || flow.currency != CATALOG_CURRENCY
+        {
+            continue;
+        }
+        let Some(maturity_date) =
+            occurrences(flow.start_date, flow.end_date, flow.frequency, today, until)
+                .into_iter()
+                .next()
+        else {
+            continue;
+        };
+        let terms = &flow.term_deposit;
+        let term_months = terms.term_months.unwrap_or(DEFAULT_TERM_MONTHS);
+        let Some(best) = best_rate(rates, term_months) else {
+            continue;
+        };
+        let current_rate = terms.interest_rate.or_else(|| {
+            rates
+                .iter()
+                .find(|rate| {
+                    Some(&rate.bank_code) == terms.bank_code.as_ref()
+                        && rate.term_months == term_months
+                })
+                .map(|rate| rate.rate)
+        });
+        let Some(current_rate) = current_rate else {
+            continue;
+        };
+        if terms.bank_code.as_ref() == Some(&best.bank_code)
+            || best.rate - current_rate < MIN_RATE_GAIN
+        {
+            continue;
+        }
+
+        let extra_interest = (flow.amount * (best.rate - current_rate) / Decimal::ONE_HUNDRED
+            * Decimal::from(term_months)
+            / Decimal::from(12))
+        .floor();
+        suggestions.push(RolloverSuggestion {
+            cash_flow_id: flow.id.clone(),
+            name: flow.name.clone(),
+            account_id: flow.account_id.clone(),
+            maturity_date,
+            amount: flow.amount,
+            term_months,
+            current_bank_code: terms.bank_code.clone(),
+            current_rate,
+            best_bank_code: best.bank_code.clone(),
+            best_bank_name: best.bank_name.clone(),
+            best_rate: best.rate,
+            extra_interest,
+        });
+    }
+    suggestions.sort_by_key(|suggestion| suggestion.maturity_date);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forecast::{CashFlowFrequency, TermDepositTerms};
+
+    fn catalog() -> Vec<DepositRate> {
+        let fetched_at = Utc::now().naive_utc();
+        bundled_deposit_rates()
+            .into_iter()
+            .map(|rate| DepositRate {
+                bank_code: rate.bank_code,
+                bank_name: rate.bank_name,
+                term_months: rate.term_months,
+                rate: rate.rate,
+                source: BUNDLED_SOURCE.to_string(),
+                fetched_at,
+            })
+            .collect()
+    }
+
+    fn maturity(id: &str, date: &str, terms: TermDepositTerms) -> PlannedCashFlow {
+        let now = Utc::now().naive_utc();
+        PlannedCashFlow {
+            id: id.to_string(),
+            name: id.to_string(),
+            kind: CashFlowKind::TermDepositMaturity,
+            account_id: None,
+            amount: dec!(500_000_000),
+            currency: "VND".to_string(),
+            frequency: CashFlowFrequency::Once,
+            start_date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            end_date: None,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            term_deposit: terms,
+        }
+    }
+
+    #[test]
+    fn suggests_better_banks_for_deposits_maturing_soon() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let flows = vec![
+            // 12 months at Vietcombank's 4.6%; TPBank pays 5.5%
+            maturity(
+                "vcb",
+                "2026-11-02",
+                TermDepositTerms {
+                    bank_code: Some("VCB".to_string()),
+                    term_months: Some(12),
+                    interest_rate: None,
+                },
+            ),
+            // Already at the best rate
+            maturity(
+                "tpb",
+                "2026-11-05",
+                TermDepositTerms {
+                    bank_code: Some("TPB".to_string()),
+                    term_months: Some(12),
+                    interest_rate: None,
+                },
+            ),
+            // Matures too late to act on yet
+            maturity(
+                "later",
+                "2027-03-01",
+                TermDepositTerms {
+                    bank_code: Some("VCB".to_string()),
+                    term_months: Some(6),
+                    interest_rate: None,
+                },
+            ),
+            // Nothing to compare against
+            maturity("unknown", "2026-10-25", TermDepositTerms::default()),
+        ];
+
+        let suggestions = suggest_rollovers(&flows, &catalog(), today, MATURITY_WINDOW_DAYS);
+        assert_eq!(suggestions.len(), 1);
+        let suggestion = &suggestions[0];
+        assert_eq!(suggestion.cash_flow_id, "vcb");
+        assert_eq!(suggestion.best_bank_code, "TPB");
+        assert_eq!(suggestion.current_rate, dec!(4.6));
+        // 0.9 points on 500m for a year
+        assert_eq!(suggestion.extra_interest, dec!(4_500_000));
+        assert!(suggestion
+            .notification(Utc::now())
+            .body
+            .contains("TPBank pays 5.5%"));
+    }
+}
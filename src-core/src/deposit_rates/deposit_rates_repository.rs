@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use diesel::upsert::excluded;
+use std::sync::Arc;
+
+use super::deposit_rates_model::{DepositRate, DepositRateDB, NewDepositRate};
+use super::deposit_rates_traits::DepositRateRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::deposit_rates;
+
+pub struct DepositRateRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl DepositRateRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        DepositRateRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl DepositRateRepositoryTrait for DepositRateRepository {
+    fn get_rates(&self) -> Result<Vec<DepositRate>> {
+        let mut conn = get_connection(&self.pool)?;
+        deposit_rates::table
+            .order((
+                deposit_rates::term_months.asc(),
+                deposit_rates::bank_code.asc(),
+            ))
+            .load::<DepositRateDB>(&mut conn)?
+            .into_iter()
+            .map(DepositRate::try_from)
+            .collect()
+    }
+
+    async fn replace_rates(&self, rates: Vec<NewDepositRate>, source: String) -> Result<usize> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                let fetched_at = Utc::now().naive_utc();
+                let rows: Vec<DepositRateDB> = rates
+                    .into_iter()
+                    .map(|rate| DepositRateDB {
+                        bank_code: rate.bank_code.trim().to_uppercase(),
+                        term_months: rate.term_months,
+                        bank_name: rate.bank_name.trim().to_string(),
+                        rate: rate.rate.to_string(),
+                        source: source.clone(),
+                        fetched_at,
+                    })
+                    .collect();
+                let mut affected_rows = 0;
+                for row in &rows {
+                    affected_rows += diesel::insert_into(deposit_rates::table)
+                        .values(row)
+                        .on_conflict((deposit_rates::bank_code, deposit_rates::term_months))
+                        .do_update()
+                        .set((
+                            deposit_rates::bank_name.eq(excluded(deposit_rates::bank_name)),
+                            deposit_rates::rate.eq(excluded(deposit_rates::rate)),
+                            deposit_rates::source.eq(excluded(deposit_rates::source)),
+                            deposit_rates::fetched_at.eq(excluded(deposit_rates::fetched_at)),
+                        ))
+                        .execute(conn)?;
+                }
+                Ok(affected_rows)
+            })
+            .await
+    }
+}
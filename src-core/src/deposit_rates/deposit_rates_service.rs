@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, warn};
+use std::sync::Arc;
+
+use super::deposit_rates_model::{
+    bundled_deposit_rates, suggest_rollovers, DepositRate, NewDepositRate, RolloverSuggestion,
+    BUNDLED_SOURCE, MATURITY_WINDOW_DAYS, RATE_REFRESH_HOURS,
+};
+use super::deposit_rates_traits::{
+    DepositRateFeed, DepositRateRepositoryTrait, DepositRateServiceTrait,
+};
+use crate::errors::Result;
+use crate::forecast::ForecastRepositoryTrait;
+use crate::notifications::Notification;
+
+/// Source recorded for rates read from the configured feed
+const FEED_SOURCE: &str = "FEED";
+
+pub struct DepositRateService {
+    repository: Arc<dyn DepositRateRepositoryTrait>,
+    forecast_repository: Arc<dyn ForecastRepositoryTrait>,
+    feed: Option<Arc<dyn DepositRateFeed>>,
+}
+
+impl DepositRateService {
+    /// Without a feed the catalog holds the bundled rates
+    pub fn new(
+        repository: Arc<dyn DepositRateRepositoryTrait>,
+        forecast_repository: Arc<dyn ForecastRepositoryTrait>,
+        feed: Option<Arc<dyn DepositRateFeed>>,
+    ) -> Self {
+        DepositRateService {
+            repository,
+            forecast_repository,
+            feed,
+        }
+    }
+
+    async fn store_bundled(&self) -> Result<usize> {
+        self.repository
+            .replace_rates(bundled_deposit_rates(), BUNDLED_SOURCE.to_string())
+            .await
+    }
+}
+
+#[async_trait]
+impl DepositRateServiceTrait for DepositRateService {
+    fn get_deposit_rates(&self) -> Result<Vec<DepositRate>> {
+        self.repository.get_rates()
+    }
+
+    async fn refresh_deposit_rates(&self, force: bool) -> Result<usize> {
+        let current = self.repository.get_rates()?;
+        let Some(feed) = &self.feed else {
+            // The bundled rates never change, so they are only stored once
+            if current.is_empty() || force {
+                return self.store_bundled().await;
+            }
+            return Ok(0);
+        };
+
+        let stale_before = Utc::now().naive_utc() - Duration::hours(RATE_REFRESH_HOURS);
+        let fresh = current
+            .iter()
+            .any(|rate| rate.source == FEED_SOURCE && rate.fetched_at > stale_before);
+        if fresh && !force {
+            return Ok(0);
+        }
+
+        let fetched = feed.fetch_rates().await.and_then(|rates| {
+            rates
+                .iter()
+                .try_for_each(NewDepositRate::validate)
+                .map(|_| rates)
+        });
+        match fetched {
+            Ok(rates) => {
+                debug!("Fetched {} deposit rates", rates.len());
+                self.repository
+                    .replace_rates(rates, FEED_SOURCE.to_string())
+                    .await
+            }
+            // Suggestions still work from the bundled rates until the feed answers
+            Err(e) if current.is_empty() => {
+                warn!("Deposit rate feed failed, using bundled rates: {}", e);
+                self.store_bundled().await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_rollover_suggestions(
+        &self,
+        within_days: Option<u64>,
+    ) -> Result<Vec<RolloverSuggestion>> {
+        let flows = self.forecast_repository.get_planned_cash_flows()?;
+        let rates = self.repository.get_rates()?;
+        Ok(suggest_rollovers(
+            &flows,
+            &rates,
+            Utc::now().date_naive(),
+            within_days.unwrap_or(MATURITY_WINDOW_DAYS),
+        ))
+    }
+
+    fn rollover_notifications(&self, now: DateTime<Utc>) -> Result<Vec<Notification>> {
+        Ok(self
+            .get_rollover_suggestions(None)?
+            .iter()
+            .map(|suggestion| suggestion.notification(now))
+            .collect())
+    }
+}
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use super::deposit_rates_model::{DepositRate, NewDepositRate, RolloverSuggestion};
+use crate::errors::Result;
+use crate::notifications::Notification;
+
+/// Publishes the current counter rates of banks
+#[async_trait]
+pub trait DepositRateFeed: Send + Sync {
+    async fn fetch_rates(&self) -> Result<Vec<NewDepositRate>>;
+}
+
+#[async_trait]
+pub trait DepositRateRepositoryTrait: Send + Sync {
+    fn get_rates(&self) -> Result<Vec<DepositRate>>;
+    /// Upserts the rates, leaving banks and tenors the source did not publish untouched
+    async fn replace_rates(&self, rates: Vec<NewDepositRate>, source: String) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait DepositRateServiceTrait: Send + Sync {
+    fn get_deposit_rates(&self) -> Result<Vec<DepositRate>>;
+    /// Fetches the catalog again once it is older than `RATE_REFRESH_HOURS`, or right away when
+    /// forced; returns the number of rates stored, 0 when still fresh
+    async fn refresh_deposit_rates(&self, force: bool) -> Result<usize>;
+    /// Maturing deposits that would earn more elsewhere, by default within
+    /// `MATURITY_WINDOW_DAYS`
+    fn get_rollover_suggestions(&self, within_days: Option<u64>)
+        -> Result<Vec<RolloverSuggestion>>;
+    fn rollover_notifications(&self, now: DateTime<Utc>) -> Result<Vec<Notification>>;
+}
@@ -0,0 +1,17 @@
+mod deposit_rates_feed;
+mod deposit_rates_model;
+mod deposit_rates_repository;
+mod deposit_rates_service;
+mod deposit_rates_traits;
+
+pub use deposit_rates_feed::HttpDepositRateFeed;
+pub use deposit_rates_model::{
+    best_rate, bundled_deposit_rates, suggest_rollovers, DepositRate, DepositRateDB,
+    NewDepositRate, RolloverSuggestion, BUNDLED_SOURCE, CATALOG_CURRENCY, DEFAULT_TERM_MONTHS,
+    MATURITY_WINDOW_DAYS, MIN_RATE_GAIN, RATE_REFRESH_HOURS,
+};
+pub use deposit_rates_repository::DepositRateRepository;
+pub use deposit_rates_service::DepositRateService;
+pub use deposit_rates_traits::{
+    DepositRateFeed, DepositRateRepositoryTrait, DepositRateServiceTrait,
+};
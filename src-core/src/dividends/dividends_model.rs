@@ -0,0 +1,284 @@
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+
+/// Months of payments the trailing yield is measured over
+pub const TRAILING_MONTHS: u32 = 12;
+
+/// Database row for `activity_dividends`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::activity_dividends)]
+#[diesel(primary_key(activity_id))]
+pub struct ActivityDividendDB {
+    pub activity_id: String,
+    pub ex_date: Option<String>,
+    pub pay_date: Option<String>,
+    pub amount_per_share: Option<String>,
+    pub tax_withheld: String,
+    pub is_reinvested: bool,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Dividend details of a DIVIDEND activity. The activity amount is the cash received, after
+/// any tax withheld at source.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityDividend {
+    pub activity_id: String,
+    pub ex_date: Option<NaiveDate>,
+    /// The activity date stands in when not recorded
+    pub pay_date: Option<NaiveDate>,
+    /// Declared dividend per share, before tax, in the activity currency
+    pub amount_per_share: Option<Decimal>,
+    pub tax_withheld: Decimal,
+    /// Paid out in new units rather than cash
+    pub is_reinvested: bool,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<ActivityDividendDB> for ActivityDividend {
+    type Error = Error;
+
+    fn try_from(db: ActivityDividendDB) -> Result<Self> {
+        Ok(ActivityDividend {
+            activity_id: db.activity_id,
+            ex_date: db.ex_date.as_deref().map(parse_forecast_date).transpose()?,
+            pay_date: db
+                .pay_date
+                .as_deref()
+                .map(parse_forecast_date)
+                .transpose()?,
+            amount_per_share: db
+                .amount_per_share
+                .as_deref()
+                .map(Decimal::from_str)
+                .transpose()?,
+            tax_withheld: Decimal::from_str(&db.tax_withheld)?,
+            is_reinvested: db.is_reinvested,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Dividend details to record against an activity, replacing any recorded before
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewActivityDividend {
+    pub activity_id: String,
+    pub ex_date: Option<NaiveDate>,
+    pub pay_date: Option<NaiveDate>,
+    pub amount_per_share: Option<Decimal>,
+    #[serde(default)]
+    pub tax_withheld: Decimal,
+    #[serde(default)]
+    pub is_reinvested: bool,
+}
+
+impl NewActivityDividend {
+    pub fn validate(&self) -> Result<()> {
+        if self.activity_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "activityId".to_string(),
+            )));
+        }
+        if self.tax_withheld < Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Tax withheld cannot be negative".to_string(),
+            )));
+        }
+        if self
+            .amount_per_share
+            .is_some_and(|amount| amount <= Decimal::ZERO)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Dividend per share must be positive".to_string(),
+            )));
+        }
+        if let (Some(ex_date), Some(pay_date)) = (self.ex_date, self.pay_date) {
+            if pay_date < ex_date {
+                return Err(Error::Validation(ValidationError::InvalidInput(
+                    "Pay date cannot be before the ex-dividend date".to_string(),
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One dividend on the calendar
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DividendPayment {
+    pub activity_id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub ex_date: Option<NaiveDate>,
+    pub pay_date: NaiveDate,
+    pub currency: String,
+    pub amount_per_share: Option<Decimal>,
+    /// Before tax, in the activity currency
+    pub gross_amount: Decimal,
+    pub tax_withheld: Decimal,
+    /// Cash received, in the activity currency
+    pub net_amount: Decimal,
+    pub gross_amount_base: Decimal,
+    pub net_amount_base: Decimal,
+    pub is_reinvested: bool,
+    /// Paid after the summary date
+    pub is_upcoming: bool,
+}
+
+/// Dividends of one holding over the trailing twelve months, in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldingDividendYield {
+    pub asset_id: String,
+    pub symbol: String,
+    pub name: Option<String>,
+    pub quantity: Decimal,
+    pub market_value: Decimal,
+    /// Before tax
+    pub trailing_dividends: Decimal,
+    pub trailing_tax_withheld: Decimal,
+    pub trailing_reinvested: Decimal,
+    /// Declared per share, in the asset currency; `None` unless every payment recorded it
+    pub trailing_dividends_per_share: Option<Decimal>,
+    /// Percent of the current price or, without a per-share figure, of the market value
+    pub trailing_yield: Option<Decimal>,
+    pub payments: usize,
+    /// The next twelve months at the trailing yield with dividends taken in cash
+    pub projected_income: Decimal,
+    /// The same with each payment reinvested into the holding at the current price
+    pub projected_income_reinvested: Decimal,
+}
+
+/// Dividend calendar and trailing yields of the current holdings
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DividendSummary {
+    pub as_of: NaiveDate,
+    pub base_currency: String,
+    /// Payments of the trailing twelve months and those still to come, by pay date
+    pub calendar: Vec<DividendPayment>,
+    /// Holdings with dividends in the trailing twelve months, highest yield first
+    pub holdings: Vec<HoldingDividendYield>,
+    pub trailing_dividends: Decimal,
+    pub trailing_tax_withheld: Decimal,
+    pub trailing_reinvested: Decimal,
+    /// Trailing dividends of the current holdings in percent of their market value
+    pub portfolio_yield: Option<Decimal>,
+    pub projected_income: Decimal,
+    pub projected_income_reinvested: Decimal,
+}
+
+/// First day of the trailing window ending on `as_of`
+pub fn trailing_window_start(as_of: NaiveDate) -> NaiveDate {
+    as_of
+        .checked_sub_months(Months::new(TRAILING_MONTHS))
+        .and_then(|date| date.succ_opt())
+        .unwrap_or(NaiveDate::MIN)
+}
+
+/// Trailing yield in percent: dividends per share over the price when both are known, or else
+/// the holding's trailing dividends over its market value
+pub fn trailing_yield(
+    dividends_per_share: Option<Decimal>,
+    price: Option<Decimal>,
+    trailing_dividends: Decimal,
+    market_value: Decimal,
+) -> Option<Decimal> {
+    let (dividends, value) = match (dividends_per_share, price) {
+        (Some(per_share), Some(price)) if price > Decimal::ZERO => (per_share, price),
+        _ => (trailing_dividends, market_value),
+    };
+    (value > Decimal::ZERO).then(|| (dividends / value * Decimal::ONE_HUNDRED).round_dp(2))
+}
+
+/// A year's income when each of `payments_per_year` payments buys more of the holding at the
+/// current price, so every payment is paid on the units the ones before it bought
+pub fn reinvested_income(
+    annual_income: Decimal,
+    yield_percent: Decimal,
+    payments_per_year: usize,
+) -> Decimal {
+    let payments = payments_per_year.max(1);
+    let count = Decimal::from(payments as u64);
+    let growth = Decimal::ONE + yield_percent / Decimal::ONE_HUNDRED / count;
+    let mut units = Decimal::ONE;
+    let mut factor = Decimal::ZERO;
+    for _ in 0..payments {
+        factor += units;
+        units *= growth;
+    }
+    (annual_income * factor / count).round_dp(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn window_covers_twelve_months_up_to_the_date() {
+        assert_eq!(
+            trailing_window_start(date("2026-10-18")),
+            date("2025-10-19")
+        );
+        assert_eq!(
+            trailing_window_start(date("2024-02-29")),
+            date("2023-03-01")
+        );
+    }
+
+    #[test]
+    fn yield_prefers_dividends_per_share() {
+        assert_eq!(
+            trailing_yield(Some(dec!(2_000)), Some(dec!(40_000)), dec!(1), dec!(1)),
+            Some(dec!(5))
+        );
+        // Without a price the holding's own figures are used
+        assert_eq!(
+            trailing_yield(Some(dec!(2_000)), None, dec!(300_000), dec!(10_000_000)),
+            Some(dec!(3))
+        );
+        assert_eq!(
+            trailing_yield(None, None, dec!(300_000), Decimal::ZERO),
+            None
+        );
+    }
+
+    #[test]
+    fn reinvesting_compounds_over_the_payments() {
+        // A single payment has nothing to compound on
+        assert_eq!(reinvested_income(dec!(1_000), dec!(8), 1), dec!(1_000));
+        // Four payments of 250, each on 2% more units than the last
+        assert_eq!(reinvested_income(dec!(1_000), dec!(8), 4), dec!(1_030.40));
+        assert_eq!(reinvested_income(dec!(1_000), dec!(8), 0), dec!(1_000));
+    }
+
+    #[test]
+    fn pay_date_cannot_precede_the_ex_date() {
+        let mut dividend = NewActivityDividend {
+            activity_id: "div-1".to_string(),
+            ex_date: Some(date("2026-06-10")),
+            pay_date: Some(date("2026-06-05")),
+            amount_per_share: Some(dec!(1_500)),
+            tax_withheld: dec!(7_500),
+            is_reinvested: false,
+        };
+        assert!(dividend.validate().is_err());
+        dividend.pay_date = Some(date("2026-07-01"));
+        assert!(dividend.validate().is_ok());
+        dividend.tax_withheld = dec!(-1);
+        assert!(dividend.validate().is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use super::dividends_model::{ActivityDividend, ActivityDividendDB, NewActivityDividend};
+use super::dividends_traits::DividendRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::activity_dividends;
+
+pub struct DividendRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl DividendRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        DividendRepository { pool, writer }
+    }
+}
+
+fn format_date(date: Option<NaiveDate>) -> Option<String> {
+    date.map(|date| date.format(FORECAST_DATE_FORMAT).to_string())
+}
+
+#[async_trait]
+impl DividendRepositoryTrait for DividendRepository {
+    fn get_activity_dividends(
+        &self,
+        activity_ids: Option<&[String]>,
+    ) -> Result<Vec<ActivityDividend>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = activity_dividends::table.into_boxed();
+        if let Some(activity_ids) = activity_ids {
+            query = query.filter(activity_dividends::activity_id.eq_any(activity_ids));
+        }
+        query
+            .load::<ActivityDividendDB>(&mut conn)?
+            .into_iter()
+            .map(ActivityDividend::try_from)
+            .collect()
+    }
+
+    async fn save_activity_dividend(
+        &self,
+        dividend: NewActivityDividend,
+    ) -> Result<ActivityDividend> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<ActivityDividend> {
+                    let record = ActivityDividendDB {
+                        activity_id: dividend.activity_id,
+                        ex_date: format_date(dividend.ex_date),
+                        pay_date: format_date(dividend.pay_date),
+                        amount_per_share: dividend
+                            .amount_per_share
+                            .map(|amount| amount.to_string()),
+                        tax_withheld: dividend.tax_withheld.to_string(),
+                        is_reinvested: dividend.is_reinvested,
+                        updated_at: Utc::now().naive_utc(),
+                    };
+                    diesel::replace_into(activity_dividends::table)
+                        .values(&record)
+                        .execute(conn)?;
+                    record.try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_activity_dividend(&self, activity_id: &str) -> Result<usize> {
+        let activity_id = activity_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(activity_dividends::table.find(activity_id)).execute(conn)?)
+            })
+            .await
+    }
+}
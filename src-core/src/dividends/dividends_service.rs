@@ -0,0 +1,435 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+
+use super::dividends_model::{
+    reinvested_income, trailing_window_start, trailing_yield, ActivityDividend, DividendPayment,
+    DividendSummary, HoldingDividendYield, NewActivityDividend,
+};
+use super::dividends_traits::{DividendRepositoryTrait, DividendServiceTrait};
+use crate::activities::{Activity, ActivityRepositoryTrait, ACTIVITY_TYPE_DIVIDEND};
+use crate::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::FxServiceTrait;
+use crate::portfolio::holdings::{Holding, HoldingType, HoldingsServiceTrait};
+
+/// Date a dividend was paid: the recorded pay date, or else the activity date
+fn pay_date(activity: &Activity, details: Option<&ActivityDividend>) -> NaiveDate {
+    details
+        .and_then(|details| details.pay_date)
+        .unwrap_or_else(|| activity.activity_date.date_naive())
+}
+
+/// Calendar entry of a dividend activity, with `rate` converting its currency to the base
+/// currency
+fn dividend_payment(
+    activity: &Activity,
+    details: Option<&ActivityDividend>,
+    rate: Decimal,
+    as_of: NaiveDate,
+) -> DividendPayment {
+    let net_amount = activity
+        .amount
+        .unwrap_or(activity.quantity * activity.unit_price);
+    let tax_withheld = details.map_or(Decimal::ZERO, |details| details.tax_withheld);
+    let gross_amount = net_amount + tax_withheld;
+    let pay_date = pay_date(activity, details);
+    DividendPayment {
+        activity_id: activity.id.clone(),
+        account_id: activity.account_id.clone(),
+        asset_id: activity.asset_id.clone(),
+        ex_date: details.and_then(|details| details.ex_date),
+        pay_date,
+        currency: activity.currency.clone(),
+        amount_per_share: details.and_then(|details| details.amount_per_share),
+        gross_amount,
+        tax_withheld,
+        net_amount,
+        gross_amount_base: (gross_amount * rate).round_dp(2),
+        net_amount_base: (net_amount * rate).round_dp(2),
+        is_reinvested: details.is_some_and(|details| details.is_reinvested),
+        is_upcoming: pay_date > as_of,
+    }
+}
+
+/// Trailing dividends of a security held, from the payments of the trailing window
+fn holding_yield(holding: &Holding, payments: &[&DividendPayment]) -> Option<HoldingDividendYield> {
+    let instrument = holding.instrument.as_ref()?;
+    if payments.is_empty() {
+        return None;
+    }
+    let trailing_dividends: Decimal = payments.iter().map(|p| p.gross_amount_base).sum();
+    // A dividend paid into several accounts is declared once, so per-share amounts are
+    // counted once per pay date
+    let mut per_share_by_date: BTreeMap<NaiveDate, Decimal> = BTreeMap::new();
+    let mut per_share_complete = true;
+    for payment in payments {
+        match payment.amount_per_share {
+            Some(amount) => {
+                per_share_by_date.insert(payment.pay_date, amount);
+            }
+            None => per_share_complete = false,
+        }
+    }
+    let trailing_dividends_per_share =
+        per_share_complete.then(|| per_share_by_date.values().copied().sum::<Decimal>());
+
+    let market_value = holding.market_value.base;
+    let yield_percent = trailing_yield(
+        trailing_dividends_per_share,
+        holding.price,
+        trailing_dividends,
+        market_value,
+    );
+    let payments_per_year = payments
+        .iter()
+        .map(|p| p.pay_date)
+        .collect::<BTreeSet<_>>()
+        .len();
+    let projected_income = yield_percent
+        .map(|y| (market_value * y / Decimal::ONE_HUNDRED).round_dp(2))
+        .unwrap_or_default();
+
+    Some(HoldingDividendYield {
+        asset_id: instrument.id.clone(),
+        symbol: instrument.symbol.clone(),
+        name: instrument.name.clone(),
+        quantity: holding.quantity,
+        market_value,
+        trailing_dividends,
+        trailing_tax_withheld: payments
+            .iter()
+            .map(|p| p.gross_amount_base - p.net_amount_base)
+            .sum(),
+        trailing_reinvested: payments
+            .iter()
+            .filter(|p| p.is_reinvested)
+            .map(|p| p.net_amount_base)
+            .sum(),
+        trailing_dividends_per_share,
+        trailing_yield: yield_percent,
+        payments: payments.len(),
+        projected_income,
+        projected_income_reinvested: yield_percent.map_or(Decimal::ZERO, |y| {
+            reinvested_income(projected_income, y, payments_per_year)
+        }),
+    })
+}
+
+/// Summary of the dividends on `calendar`, with the yields of the securities in `holdings`
+fn dividend_summary(
+    as_of: NaiveDate,
+    base_currency: &str,
+    mut calendar: Vec<DividendPayment>,
+    holdings: &[Holding],
+) -> DividendSummary {
+    calendar.sort_by(|a, b| {
+        a.pay_date
+            .cmp(&b.pay_date)
+            .then_with(|| a.asset_id.cmp(&b.asset_id))
+    });
+    let window_start = trailing_window_start(as_of);
+    let trailing: Vec<&DividendPayment> = calendar
+        .iter()
+        .filter(|p| !p.is_upcoming && p.pay_date >= window_start)
+        .collect();
+    let mut by_asset: HashMap<&str, Vec<&DividendPayment>> = HashMap::new();
+    for payment in &trailing {
+        by_asset
+            .entry(payment.asset_id.as_str())
+            .or_default()
+            .push(payment);
+    }
+
+    let securities: Vec<&Holding> = holdings
+        .iter()
+        .filter(|h| h.holding_type == HoldingType::Security && h.quantity > Decimal::ZERO)
+        .collect();
+    let mut yields: Vec<HoldingDividendYield> = securities
+        .iter()
+        .filter_map(|holding| {
+            let asset_id = holding.instrument.as_ref()?.id.as_str();
+            holding_yield(holding, by_asset.get(asset_id)?)
+        })
+        .collect();
+    yields.sort_by(|a, b| {
+        b.trailing_yield
+            .cmp(&a.trailing_yield)
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+
+    let market_value: Decimal = securities.iter().map(|h| h.market_value.base).sum();
+    let held_dividends: Decimal = yields.iter().map(|y| y.trailing_dividends).sum();
+    let trailing_dividends: Decimal = trailing.iter().map(|p| p.gross_amount_base).sum();
+    let trailing_net: Decimal = trailing.iter().map(|p| p.net_amount_base).sum();
+    DividendSummary {
+        as_of,
+        base_currency: base_currency.to_string(),
+        trailing_dividends,
+        trailing_tax_withheld: trailing_dividends - trailing_net,
+        trailing_reinvested: trailing
+            .iter()
+            .filter(|p| p.is_reinvested)
+            .map(|p| p.net_amount_base)
+            .sum(),
+        portfolio_yield: (market_value > Decimal::ZERO)
+            .then(|| (held_dividends / market_value * Decimal::ONE_HUNDRED).round_dp(2)),
+        projected_income: yields.iter().map(|y| y.projected_income).sum(),
+        projected_income_reinvested: yields.iter().map(|y| y.projected_income_reinvested).sum(),
+        calendar: calendar
+            .into_iter()
+            .filter(|p| p.pay_date >= window_start)
+            .collect(),
+        holdings: yields,
+    }
+}
+
+pub struct DividendService {
+    repository: Arc<dyn DividendRepositoryTrait>,
+    activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    holdings_service: Arc<dyn HoldingsServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl DividendService {
+    pub fn new(
+        repository: Arc<dyn DividendRepositoryTrait>,
+        activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        holdings_service: Arc<dyn HoldingsServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            activity_repository,
+            holdings_service,
+            fx_service,
+            base_currency,
+        }
+    }
+}
+
+#[async_trait]
+impl DividendServiceTrait for DividendService {
+    fn get_activity_dividend(&self, activity_id: &str) -> Result<Option<ActivityDividend>> {
+        Ok(self
+            .repository
+            .get_activity_dividends(Some(&[activity_id.to_string()]))?
+            .pop())
+    }
+
+    async fn save_activity_dividend(
+        &self,
+        dividend: NewActivityDividend,
+    ) -> Result<ActivityDividend> {
+        dividend.validate()?;
+        let activity = self
+            .activity_repository
+            .get_activity(&dividend.activity_id)?;
+        if activity.activity_type != ACTIVITY_TYPE_DIVIDEND {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Activity {} is not a dividend",
+                activity.id
+            ))));
+        }
+        self.repository.save_activity_dividend(dividend).await
+    }
+
+    async fn delete_activity_dividend(&self, activity_id: &str) -> Result<usize> {
+        self.repository.delete_activity_dividend(activity_id).await
+    }
+
+    async fn get_dividend_summary(&self, as_of: NaiveDate) -> Result<DividendSummary> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let window_start = trailing_window_start(as_of);
+        let details: HashMap<String, ActivityDividend> = self
+            .repository
+            .get_activity_dividends(None)?
+            .into_iter()
+            .map(|details| (details.activity_id.clone(), details))
+            .collect();
+
+        let mut calendar = Vec::new();
+        for activity in self.activity_repository.get_income_activities()? {
+            if activity.activity_type != ACTIVITY_TYPE_DIVIDEND || activity.is_draft {
+                continue;
+            }
+            let details = details.get(&activity.id);
+            let paid_on = pay_date(&activity, details);
+            if paid_on < window_start {
+                continue;
+            }
+            // Dividends still to come are valued at the latest rate
+            let rate = if activity.currency == base_currency {
+                Decimal::ONE
+            } else {
+                self.fx_service.convert_currency_for_date(
+                    Decimal::ONE,
+                    &activity.currency,
+                    &base_currency,
+                    paid_on.min(as_of),
+                )?
+            };
+            calendar.push(dividend_payment(&activity, details, rate, as_of));
+        }
+
+        let holdings = self
+            .holdings_service
+            .get_holdings(PORTFOLIO_TOTAL_ACCOUNT_ID, &base_currency)
+            .await?;
+        Ok(dividend_summary(as_of, &base_currency, calendar, &holdings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::holdings::{Instrument, MonetaryValue};
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn dividend(id: &str, asset_id: &str, paid: &str, amount: Decimal) -> Activity {
+        let paid_at = Utc.from_utc_datetime(&date(paid).and_hms_opt(0, 0, 0).unwrap());
+        Activity {
+            id: id.to_string(),
+            account_id: "broker".to_string(),
+            asset_id: asset_id.to_string(),
+            activity_type: ACTIVITY_TYPE_DIVIDEND.to_string(),
+            activity_date: paid_at,
+            quantity: Decimal::ZERO,
+            unit_price: Decimal::ZERO,
+            currency: "VND".to_string(),
+            fee: Decimal::ZERO,
+            amount: Some(amount),
+            is_draft: false,
+            comment: None,
+            created_at: paid_at,
+            updated_at: paid_at,
+        }
+    }
+
+    fn details(id: &str, per_share: Option<Decimal>, tax: Decimal) -> ActivityDividend {
+        ActivityDividend {
+            activity_id: id.to_string(),
+            ex_date: None,
+            pay_date: None,
+            amount_per_share: per_share,
+            tax_withheld: tax,
+            is_reinvested: false,
+            updated_at: date("2026-01-01").and_hms_opt(0, 0, 0).unwrap(),
+        }
+    }
+
+    fn security(symbol: &str, quantity: Decimal, price: Decimal) -> Holding {
+        let value = quantity * price;
+        Holding {
+            id: format!("TOTAL-{}", symbol),
+            account_id: "TOTAL".to_string(),
+            holding_type: HoldingType::Security,
+            instrument: Some(Instrument {
+                id: symbol.to_string(),
+                symbol: symbol.to_string(),
+                name: None,
+                currency: "VND".to_string(),
+                notes: None,
+                data_source: None,
+                asset_class: Some("Equity".to_string()),
+                asset_subclass: None,
+                countries: None,
+                sectors: None,
+            }),
+            quantity,
+            open_date: None,
+            lots: None,
+            local_currency: "VND".to_string(),
+            base_currency: "VND".to_string(),
+            fx_rate: None,
+            market_value: MonetaryValue {
+                local: value,
+                base: value,
+            },
+            cost_basis: None,
+            price: Some(price),
+            unrealized_gain: None,
+            unrealized_gain_pct: None,
+            realized_gain: None,
+            realized_gain_pct: None,
+            total_gain: None,
+            total_gain_pct: None,
+            day_change: None,
+            day_change_pct: None,
+            prev_close_value: None,
+            weight: Decimal::ZERO,
+            as_of_date: date("2026-10-18"),
+        }
+    }
+
+    #[test]
+    fn summary_yields_follow_trailing_payments() {
+        let as_of = date("2026-10-18");
+        let fpt = [
+            (
+                dividend("fpt-1", "FPT", "2026-05-20", dec!(950_000)),
+                details("fpt-1", Some(dec!(1_000)), dec!(50_000)),
+            ),
+            (
+                dividend("fpt-2", "FPT", "2026-09-10", dec!(950_000)),
+                details("fpt-2", Some(dec!(1_000)), dec!(50_000)),
+            ),
+        ];
+        let mut calendar: Vec<DividendPayment> = fpt
+            .iter()
+            .map(|(activity, details)| dividend_payment(activity, Some(details), dec!(1), as_of))
+            .collect();
+        // Outside the window, still to come, and from a holding since sold
+        calendar.push(dividend_payment(
+            &dividend("fpt-0", "FPT", "2025-09-10", dec!(900_000)),
+            None,
+            dec!(1),
+            as_of,
+        ));
+        calendar.push(dividend_payment(
+            &dividend("fpt-3", "FPT", "2026-12-01", dec!(950_000)),
+            None,
+            dec!(1),
+            as_of,
+        ));
+        calendar.push(dividend_payment(
+            &dividend("vnm-1", "VNM", "2026-07-01", dec!(300_000)),
+            None,
+            dec!(1),
+            as_of,
+        ));
+
+        let holdings = vec![
+            security("FPT", dec!(1_000), dec!(40_000)),
+            security("HPG", dec!(2_000), dec!(30_000)),
+        ];
+        let summary = dividend_summary(as_of, "VND", calendar, &holdings);
+
+        assert_eq!(summary.calendar.len(), 4);
+        assert_eq!(summary.calendar[0].activity_id, "fpt-1");
+        assert!(summary.calendar[3].is_upcoming);
+        assert_eq!(summary.trailing_dividends, dec!(2_300_000));
+        assert_eq!(summary.trailing_tax_withheld, dec!(100_000));
+
+        // Only FPT paid out; HPG is held but had no dividends
+        assert_eq!(summary.holdings.len(), 1);
+        let fpt = &summary.holdings[0];
+        assert_eq!(fpt.trailing_dividends, dec!(2_000_000));
+        assert_eq!(fpt.trailing_dividends_per_share, Some(dec!(2_000)));
+        assert_eq!(fpt.trailing_yield, Some(dec!(5)));
+        assert_eq!(fpt.projected_income, dec!(2_000_000));
+        // Two payments, the second on 2.5% more units
+        assert_eq!(fpt.projected_income_reinvested, dec!(2_025_000));
+        // 2,000,000 over the 100,000,000 held in securities
+        assert_eq!(summary.portfolio_yield, Some(dec!(2)));
+    }
+}
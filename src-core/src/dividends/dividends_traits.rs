@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::dividends_model::{ActivityDividend, DividendSummary, NewActivityDividend};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait DividendRepositoryTrait: Send + Sync {
+    /// Details of the given activities, or of every activity when `None`
+    fn get_activity_dividends(
+        &self,
+        activity_ids: Option<&[String]>,
+    ) -> Result<Vec<ActivityDividend>>;
+    async fn save_activity_dividend(
+        &self,
+        dividend: NewActivityDividend,
+    ) -> Result<ActivityDividend>;
+    async fn delete_activity_dividend(&self, activity_id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait DividendServiceTrait: Send + Sync {
+    fn get_activity_dividend(&self, activity_id: &str) -> Result<Option<ActivityDividend>>;
+    /// Records the dividend details of a DIVIDEND activity
+    async fn save_activity_dividend(
+        &self,
+        dividend: NewActivityDividend,
+    ) -> Result<ActivityDividend>;
+    async fn delete_activity_dividend(&self, activity_id: &str) -> Result<usize>;
+    /// Dividend calendar and trailing twelve-month yield per holding as of `as_of`
+    async fn get_dividend_summary(&self, as_of: NaiveDate) -> Result<DividendSummary>;
+}
@@ -0,0 +1,12 @@
+mod dividends_model;
+mod dividends_repository;
+mod dividends_service;
+mod dividends_traits;
+
+pub use dividends_model::{
+    reinvested_income, trailing_window_start, trailing_yield, ActivityDividend, DividendPayment,
+    DividendSummary, HoldingDividendYield, NewActivityDividend, TRAILING_MONTHS,
+};
+pub use dividends_repository::DividendRepository;
+pub use dividends_service::DividendService;
+pub use dividends_traits::{DividendRepositoryTrait, DividendServiceTrait};
@@ -0,0 +1,273 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Month the school year starts and its tuition falls due
+pub const SCHOOL_YEAR_START_MONTH: u32 = 9;
+/// Longest stage accepted, in school years
+pub const MAX_EDUCATION_STAGE_YEARS: i32 = 12;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SchoolType {
+    /// Vietnamese public school
+    VnPublic,
+    /// Vietnamese private school
+    VnPrivate,
+    /// International school in Vietnam
+    International,
+    /// Study abroad
+    Overseas,
+}
+
+impl SchoolType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchoolType::VnPublic => "VN_PUBLIC",
+            SchoolType::VnPrivate => "VN_PRIVATE",
+            SchoolType::International => "INTERNATIONAL",
+            SchoolType::Overseas => "OVERSEAS",
+        }
+    }
+
+    /// Annual tuition inflation in percent used when a stage doesn't set one
+    pub fn default_tuition_inflation(&self) -> Decimal {
+        match self {
+            SchoolType::VnPublic => dec!(5),
+            SchoolType::VnPrivate => dec!(8),
+            SchoolType::International => dec!(7),
+            SchoolType::Overseas => dec!(4),
+        }
+    }
+}
+
+impl FromStr for SchoolType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "VN_PUBLIC" => Ok(SchoolType::VnPublic),
+            "VN_PRIVATE" => Ok(SchoolType::VnPrivate),
+            "INTERNATIONAL" => Ok(SchoolType::International),
+            "OVERSEAS" => Ok(SchoolType::Overseas),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown school type: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `education_plans`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::education_plans)]
+pub struct EducationPlanDB {
+    pub id: String,
+    pub child_name: String,
+    pub birth_year: i32,
+    pub goal_id: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Database row for `education_stages`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::education_stages)]
+pub struct EducationStageDB {
+    pub id: String,
+    pub plan_id: String,
+    pub name: String,
+    pub school_type: String,
+    pub currency: String,
+    pub start_year: i32,
+    pub years: i32,
+    pub annual_cost: String,
+    pub tuition_inflation: Option<String>,
+}
+
+/// A stretch of schooling at one kind of school, e.g. high school or a degree abroad
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EducationStage {
+    pub id: String,
+    pub name: String,
+    pub school_type: SchoolType,
+    pub currency: String,
+    /// Calendar year the first school year starts in
+    pub start_year: i32,
+    pub years: i32,
+    /// Tuition and fees per school year in today's money
+    pub annual_cost: Decimal,
+    /// Annual tuition inflation in percent; the school type's default when unset
+    pub tuition_inflation: Option<Decimal>,
+}
+
+impl EducationStage {
+    pub fn inflation_rate(&self) -> Decimal {
+        self.tuition_inflation
+            .unwrap_or_else(|| self.school_type.default_tuition_inflation())
+    }
+}
+
+impl TryFrom<EducationStageDB> for EducationStage {
+    type Error = Error;
+
+    fn try_from(db: EducationStageDB) -> Result<Self> {
+        Ok(EducationStage {
+            id: db.id,
+            name: db.name,
+            school_type: db.school_type.parse()?,
+            currency: db.currency,
+            start_year: db.start_year,
+            years: db.years,
+            annual_cost: Decimal::from_str(&db.annual_cost)?,
+            tuition_inflation: db
+                .tuition_inflation
+                .as_deref()
+                .map(Decimal::from_str)
+                .transpose()?,
+        })
+    }
+}
+
+/// A child's education plan
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EducationPlan {
+    pub id: String,
+    pub child_name: String,
+    pub birth_year: i32,
+    /// Goal funding the plan, if one has been created or linked
+    pub goal_id: Option<String>,
+    /// Stages ordered by start year
+    pub stages: Vec<EducationStage>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Input for a stage of a new or updated plan
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewEducationStage {
+    pub name: String,
+    pub school_type: SchoolType,
+    pub currency: String,
+    pub start_year: i32,
+    pub years: i32,
+    pub annual_cost: Decimal,
+    pub tuition_inflation: Option<Decimal>,
+}
+
+/// Input for creating or updating a plan; updating replaces all of its stages
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewEducationPlan {
+    pub id: Option<String>,
+    pub child_name: String,
+    pub birth_year: i32,
+    pub stages: Vec<NewEducationStage>,
+}
+
+impl NewEducationPlan {
+    pub fn validate(&self) -> Result<()> {
+        if self.child_name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "childName".to_string(),
+            )));
+        }
+        if self.stages.is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "stages".to_string(),
+            )));
+        }
+        for stage in &self.stages {
+            if stage.name.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    "name".to_string(),
+                )));
+            }
+            if stage.currency.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    "currency".to_string(),
+                )));
+            }
+            if stage.start_year < self.birth_year {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "{} starts before {} was born",
+                    stage.name, self.child_name
+                ))));
+            }
+            if !(1..=MAX_EDUCATION_STAGE_YEARS).contains(&stage.years) {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "{} must last between 1 and {} years",
+                    stage.name, MAX_EDUCATION_STAGE_YEARS
+                ))));
+            }
+            if stage.annual_cost < Decimal::ZERO {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "{} has a negative annual cost",
+                    stage.name
+                ))));
+            }
+            if stage
+                .tuition_inflation
+                .is_some_and(|rate| rate < Decimal::ZERO || rate >= Decimal::ONE_HUNDRED)
+            {
+                return Err(Error::Validation(ValidationError::InvalidInput(
+                    "Tuition inflation must be between 0 and 100 percent".to_string(),
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Projected cost of one school year of one stage
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EducationCostYear {
+    pub year: i32,
+    pub stage_name: String,
+    pub school_type: SchoolType,
+    pub currency: String,
+    pub cost_today: Decimal,
+    /// Cost once tuition inflation is applied, in the stage currency
+    pub projected_cost: Decimal,
+    /// Projected cost in the base currency at today's exchange rate
+    pub projected_cost_base: Decimal,
+}
+
+/// How much must be saved by each school year's due date
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EducationFundingYear {
+    pub year: i32,
+    pub due_date: NaiveDate,
+    /// Cost of the school year across stages, in the base currency
+    pub cost: Decimal,
+    pub cumulative_cost: Decimal,
+    /// Months of saving left before the due date
+    pub months_until_due: u32,
+}
+
+/// Projected costs of a plan and the saving needed to meet them
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EducationProjection {
+    pub plan_id: String,
+    pub child_name: String,
+    pub base_currency: String,
+    pub costs: Vec<EducationCostYear>,
+    pub schedule: Vec<EducationFundingYear>,
+    /// Sum of projected costs in the base currency
+    pub total_need: Decimal,
+    pub first_due_date: Option<NaiveDate>,
+    pub last_due_date: Option<NaiveDate>,
+    /// Level monthly saving from now that covers every school year when it falls due
+    pub monthly_contribution: Decimal,
+}
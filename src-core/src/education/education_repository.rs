@@ -0,0 +1,190 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::education_model::{
+    EducationPlan, EducationPlanDB, EducationStage, EducationStageDB, NewEducationPlan,
+    NewEducationStage,
+};
+use super::education_traits::EducationRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::{education_plans, education_stages};
+
+pub struct EducationRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl EducationRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        EducationRepository { pool, writer }
+    }
+}
+
+fn stage_records(plan_id: &str, stages: Vec<NewEducationStage>) -> Vec<EducationStageDB> {
+    stages
+        .into_iter()
+        .map(|stage| EducationStageDB {
+            id: Uuid::new_v4().to_string(),
+            plan_id: plan_id.to_string(),
+            name: stage.name.trim().to_string(),
+            school_type: stage.school_type.as_str().to_string(),
+            currency: stage.currency,
+            start_year: stage.start_year,
+            years: stage.years,
+            annual_cost: stage.annual_cost.to_string(),
+            tuition_inflation: stage.tuition_inflation.map(|r| r.to_string()),
+        })
+        .collect()
+}
+
+fn with_stages(plan: EducationPlanDB, stages: Vec<EducationStageDB>) -> Result<EducationPlan> {
+    Ok(EducationPlan {
+        id: plan.id,
+        child_name: plan.child_name,
+        birth_year: plan.birth_year,
+        goal_id: plan.goal_id,
+        stages: stages
+            .into_iter()
+            .map(EducationStage::try_from)
+            .collect::<Result<_>>()?,
+        created_at: plan.created_at,
+        updated_at: plan.updated_at,
+    })
+}
+
+fn load_plan(conn: &mut SqliteConnection, id: &str) -> Result<EducationPlan> {
+    let plan = education_plans::table
+        .find(id)
+        .first::<EducationPlanDB>(conn)?;
+    let stages = education_stages::table
+        .filter(education_stages::plan_id.eq(id))
+        .order((
+            education_stages::start_year.asc(),
+            education_stages::name.asc(),
+        ))
+        .load::<EducationStageDB>(conn)?;
+    with_stages(plan, stages)
+}
+
+fn replace_stages(
+    conn: &mut SqliteConnection,
+    plan_id: &str,
+    stages: Vec<NewEducationStage>,
+) -> Result<()> {
+    diesel::delete(education_stages::table.filter(education_stages::plan_id.eq(plan_id)))
+        .execute(conn)?;
+    let records = stage_records(plan_id, stages);
+    if !records.is_empty() {
+        diesel::insert_into(education_stages::table)
+            .values(&records)
+            .execute(conn)?;
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl EducationRepositoryTrait for EducationRepository {
+    fn get_plans(&self) -> Result<Vec<EducationPlan>> {
+        let mut conn = get_connection(&self.pool)?;
+        let plans = education_plans::table
+            .order(education_plans::child_name.asc())
+            .load::<EducationPlanDB>(&mut conn)?;
+        let mut stages: HashMap<String, Vec<EducationStageDB>> = HashMap::new();
+        for stage in education_stages::table
+            .order((
+                education_stages::start_year.asc(),
+                education_stages::name.asc(),
+            ))
+            .load::<EducationStageDB>(&mut conn)?
+        {
+            stages.entry(stage.plan_id.clone()).or_default().push(stage);
+        }
+        plans
+            .into_iter()
+            .map(|plan| {
+                let plan_stages = stages.remove(&plan.id).unwrap_or_default();
+                with_stages(plan, plan_stages)
+            })
+            .collect()
+    }
+
+    fn get_plan(&self, id: &str) -> Result<EducationPlan> {
+        let mut conn = get_connection(&self.pool)?;
+        load_plan(&mut conn, id)
+    }
+
+    async fn insert_plan(&self, plan: NewEducationPlan) -> Result<EducationPlan> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<EducationPlan> {
+                    let now = Utc::now().naive_utc();
+                    let record = EducationPlanDB {
+                        id: plan.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        child_name: plan.child_name.trim().to_string(),
+                        birth_year: plan.birth_year,
+                        goal_id: None,
+                        created_at: now,
+                        updated_at: now,
+                    };
+                    diesel::insert_into(education_plans::table)
+                        .values(&record)
+                        .execute(conn)?;
+                    replace_stages(conn, &record.id, plan.stages)?;
+                    load_plan(conn, &record.id)
+                },
+            )
+            .await
+    }
+
+    async fn update_plan(&self, id: &str, plan: NewEducationPlan) -> Result<EducationPlan> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<EducationPlan> {
+                    diesel::update(education_plans::table.find(&id_owned))
+                        .set((
+                            education_plans::child_name.eq(plan.child_name.trim()),
+                            education_plans::birth_year.eq(plan.birth_year),
+                            education_plans::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .execute(conn)?;
+                    replace_stages(conn, &id_owned, plan.stages)?;
+                    load_plan(conn, &id_owned)
+                },
+            )
+            .await
+    }
+
+    async fn delete_plan(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(education_plans::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    async fn set_plan_goal(&self, id: &str, goal_id: Option<String>) -> Result<EducationPlan> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<EducationPlan> {
+                    diesel::update(education_plans::table.find(&id_owned))
+                        .set((
+                            education_plans::goal_id.eq(goal_id),
+                            education_plans::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .execute(conn)?;
+                    load_plan(conn, &id_owned)
+                },
+            )
+            .await
+    }
+}
@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, MathematicalOps};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use super::education_model::{
+    EducationCostYear, EducationFundingYear, EducationPlan, EducationProjection, NewEducationPlan,
+    SCHOOL_YEAR_START_MONTH,
+};
+use super::education_traits::{EducationRepositoryTrait, EducationServiceTrait};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::fx::FxServiceTrait;
+use crate::goals::goals_model::{Goal, NewGoal, GOAL_TYPE_EDUCATION};
+use crate::goals::GoalServiceTrait;
+
+pub struct EducationService {
+    repository: Arc<dyn EducationRepositoryTrait>,
+    goal_service: Arc<dyn GoalServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl EducationService {
+    pub fn new(
+        repository: Arc<dyn EducationRepositoryTrait>,
+        goal_service: Arc<dyn GoalServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            goal_service,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    fn project(&self, plan: &EducationPlan) -> Result<EducationProjection> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        project_plan(
+            plan,
+            Utc::now().date_naive(),
+            &base_currency,
+            &|amount, currency| {
+                if currency == base_currency || amount.is_zero() {
+                    return Ok(amount);
+                }
+                self.fx_service
+                    .convert_currency(amount, currency, &base_currency)
+            },
+        )
+    }
+}
+
+/// Date a school year's tuition falls due
+fn school_year_due_date(year: i32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, SCHOOL_YEAR_START_MONTH, 1)
+}
+
+/// Whole months from `today` until `due_date`
+fn months_until(today: NaiveDate, due_date: NaiveDate) -> u32 {
+    let months = (due_date.year() - today.year()) * 12 + due_date.month() as i32
+        - today.month() as i32
+        - i32::from(due_date.day() < today.day());
+    months.max(0) as u32
+}
+
+/// Projects each remaining school year's cost with tuition inflation. School years that
+/// fell due before `today` are treated as paid. The monthly contribution is the smallest
+/// level saving that has every year's cost put aside by its due date.
+pub(crate) fn project_plan(
+    plan: &EducationPlan,
+    today: NaiveDate,
+    base_currency: &str,
+    to_base: &dyn Fn(Decimal, &str) -> Result<Decimal>,
+) -> Result<EducationProjection> {
+    let mut costs = Vec::new();
+    for stage in &plan.stages {
+        let growth = Decimal::ONE + stage.inflation_rate() / Decimal::ONE_HUNDRED;
+        for offset in 0..stage.years {
+            let year = stage.start_year + offset;
+            if school_year_due_date(year).is_none_or(|due| due < today) {
+                continue;
+            }
+            let years_ahead = (year - today.year()).max(0);
+            let projected_cost = (stage.annual_cost * growth.powi(years_ahead as i64)).round_dp(2);
+            costs.push(EducationCostYear {
+                year,
+                stage_name: stage.name.clone(),
+                school_type: stage.school_type,
+                currency: stage.currency.clone(),
+                cost_today: stage.annual_cost,
+                projected_cost,
+                projected_cost_base: to_base(projected_cost, &stage.currency)?.round_dp(2),
+            });
+        }
+    }
+    costs.sort_by_key(|c| c.year);
+
+    let mut by_year: BTreeMap<i32, Decimal> = BTreeMap::new();
+    for cost in &costs {
+        *by_year.entry(cost.year).or_default() += cost.projected_cost_base;
+    }
+    let mut schedule = Vec::with_capacity(by_year.len());
+    let mut cumulative_cost = Decimal::ZERO;
+    let mut monthly_contribution = Decimal::ZERO;
+    for (year, cost) in by_year {
+        let Some(due_date) = school_year_due_date(year) else {
+            continue;
+        };
+        cumulative_cost += cost;
+        let months_until_due = months_until(today, due_date);
+        monthly_contribution =
+            monthly_contribution.max(cumulative_cost / Decimal::from(months_until_due.max(1)));
+        schedule.push(EducationFundingYear {
+            year,
+            due_date,
+            cost,
+            cumulative_cost,
+            months_until_due,
+        });
+    }
+
+    Ok(EducationProjection {
+        plan_id: plan.id.clone(),
+        child_name: plan.child_name.clone(),
+        base_currency: base_currency.to_string(),
+        first_due_date: schedule.first().map(|s| s.due_date),
+        last_due_date: schedule.last().map(|s| s.due_date),
+        total_need: cumulative_cost,
+        monthly_contribution: monthly_contribution.round_dp(2),
+        costs,
+        schedule,
+    })
+}
+
+#[async_trait]
+impl EducationServiceTrait for EducationService {
+    fn get_education_plans(&self) -> Result<Vec<EducationPlan>> {
+        self.repository.get_plans()
+    }
+
+    fn get_education_plan(&self, id: &str) -> Result<EducationPlan> {
+        self.repository.get_plan(id)
+    }
+
+    async fn create_education_plan(&self, plan: NewEducationPlan) -> Result<EducationPlan> {
+        plan.validate()?;
+        self.repository.insert_plan(plan).await
+    }
+
+    async fn update_education_plan(
+        &self,
+        id: &str,
+        plan: NewEducationPlan,
+    ) -> Result<EducationPlan> {
+        plan.validate()?;
+        self.repository.update_plan(id, plan).await
+    }
+
+    async fn delete_education_plan(&self, id: &str) -> Result<usize> {
+        self.repository.delete_plan(id).await
+    }
+
+    fn project_education_costs(&self, id: &str) -> Result<EducationProjection> {
+        let plan = self.repository.get_plan(id)?;
+        self.project(&plan)
+    }
+
+    async fn link_education_goal(
+        &self,
+        id: &str,
+        goal_id: Option<String>,
+    ) -> Result<EducationPlan> {
+        let plan = self.repository.get_plan(id)?;
+        let projection = self.project(&plan)?;
+        let Some(last_due_date) = projection.last_due_date else {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{}'s plan has no school years left to fund",
+                plan.child_name
+            ))));
+        };
+        let target_amount = projection.total_need.to_f64().unwrap_or_default();
+        let monthly_investment = projection.monthly_contribution.to_f64();
+        let due_date = Some(last_due_date.format(FORECAST_DATE_FORMAT).to_string());
+
+        let requested = goal_id.is_some();
+        let existing: Option<Goal> = match goal_id.or_else(|| plan.goal_id.clone()) {
+            Some(goal_id) => self
+                .goal_service
+                .get_goals()
+                .await?
+                .into_iter()
+                .find(|g| g.id == goal_id),
+            None => None,
+        };
+
+        let goal = match existing {
+            Some(goal) => {
+                self.goal_service
+                    .update_goal(Goal {
+                        target_amount,
+                        due_date,
+                        monthly_investment,
+                        goal_type: GOAL_TYPE_EDUCATION.to_string(),
+                        target_months: None,
+                        ..goal
+                    })
+                    .await?
+            }
+            None if requested => {
+                return Err(Error::Validation(ValidationError::InvalidInput(
+                    "The goal to link no longer exists".to_string(),
+                )));
+            }
+            None => {
+                self.goal_service
+                    .create_goal(NewGoal {
+                        id: None,
+                        title: format!("Education: {}", plan.child_name),
+                        description: None,
+                        target_amount,
+                        is_achieved: false,
+                        target_return_rate: None,
+                        due_date,
+                        monthly_investment,
+                        start_date: Some(
+                            Utc::now()
+                                .date_naive()
+                                .format(FORECAST_DATE_FORMAT)
+                                .to_string(),
+                        ),
+                        initial_actual_value: None,
+                        goal_type: GOAL_TYPE_EDUCATION.to_string(),
+                        target_months: None,
+                        priority: 0,
+                        depends_on_goal_id: None,
+                        currency: None,
+                    })
+                    .await?
+            }
+        };
+
+        self.repository.set_plan_goal(id, Some(goal.id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::education::education_model::{EducationStage, SchoolType};
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn projects_inflated_tuition_and_level_contribution() {
+        let now = Utc::now().naive_utc();
+        let plan = EducationPlan {
+            id: "an".to_string(),
+            child_name: "An".to_string(),
+            birth_year: 2015,
+            goal_id: None,
+            stages: vec![
+                EducationStage {
+                    id: "high-school".to_string(),
+                    name: "High school".to_string(),
+                    school_type: SchoolType::VnPrivate,
+                    currency: "VND".to_string(),
+                    start_year: 2026,
+                    years: 3,
+                    annual_cost: dec!(100_000_000),
+                    tuition_inflation: Some(dec!(10)),
+                },
+                EducationStage {
+                    id: "university".to_string(),
+                    name: "University".to_string(),
+                    school_type: SchoolType::Overseas,
+                    currency: "USD".to_string(),
+                    start_year: 2029,
+                    years: 1,
+                    annual_cost: dec!(20_000),
+                    tuition_inflation: Some(dec!(0)),
+                },
+            ],
+            created_at: now,
+            updated_at: now,
+        };
+        let to_base = |amount: Decimal, currency: &str| -> Result<Decimal> {
+            Ok(if currency == "USD" {
+                amount * dec!(25_000)
+            } else {
+                amount
+            })
+        };
+
+        let projection = project_plan(&plan, date("2026-10-17"), "VND", &to_base).unwrap();
+        // The 2026 school year started in September and is treated as paid
+        let years: Vec<i32> = projection.costs.iter().map(|c| c.year).collect();
+        assert_eq!(years, vec![2027, 2028, 2029]);
+        assert_eq!(projection.costs[0].projected_cost, dec!(110_000_000));
+        assert_eq!(projection.costs[1].projected_cost, dec!(121_000_000));
+        assert_eq!(projection.costs[2].projected_cost_base, dec!(500_000_000));
+        assert_eq!(projection.total_need, dec!(731_000_000));
+        assert_eq!(projection.last_due_date, Some(date("2029-09-01")));
+
+        // 110m within 10 months needs 11m a month, but the year abroad needs 731m
+        // within 34 months, so that sets the contribution
+        assert_eq!(projection.schedule[0].months_until_due, 10);
+        assert_eq!(projection.schedule[2].months_until_due, 34);
+        assert_eq!(projection.monthly_contribution, dec!(21_500_000));
+    }
+}
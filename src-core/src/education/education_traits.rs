@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+use super::education_model::{EducationPlan, EducationProjection, NewEducationPlan};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait EducationRepositoryTrait: Send + Sync {
+    fn get_plans(&self) -> Result<Vec<EducationPlan>>;
+    fn get_plan(&self, id: &str) -> Result<EducationPlan>;
+    async fn insert_plan(&self, plan: NewEducationPlan) -> Result<EducationPlan>;
+    /// Updates the plan and replaces its stages
+    async fn update_plan(&self, id: &str, plan: NewEducationPlan) -> Result<EducationPlan>;
+    async fn delete_plan(&self, id: &str) -> Result<usize>;
+    async fn set_plan_goal(&self, id: &str, goal_id: Option<String>) -> Result<EducationPlan>;
+}
+
+#[async_trait]
+pub trait EducationServiceTrait: Send + Sync {
+    fn get_education_plans(&self) -> Result<Vec<EducationPlan>>;
+    fn get_education_plan(&self, id: &str) -> Result<EducationPlan>;
+    async fn create_education_plan(&self, plan: NewEducationPlan) -> Result<EducationPlan>;
+    async fn update_education_plan(
+        &self,
+        id: &str,
+        plan: NewEducationPlan,
+    ) -> Result<EducationPlan>;
+    async fn delete_education_plan(&self, id: &str) -> Result<usize>;
+    /// Projects tuition per school year with inflation and the saving needed to meet it
+    fn project_education_costs(&self, id: &str) -> Result<EducationProjection>;
+    /// Points the plan's goal at the projected target and contribution. Links `goal_id`
+    /// when given, otherwise updates the plan's goal or creates one.
+    async fn link_education_goal(&self, id: &str, goal_id: Option<String>)
+        -> Result<EducationPlan>;
+}
@@ -0,0 +1,13 @@
+mod education_model;
+mod education_repository;
+mod education_service;
+mod education_traits;
+
+pub use education_model::{
+    EducationCostYear, EducationFundingYear, EducationPlan, EducationProjection, EducationStage,
+    NewEducationPlan, NewEducationStage, SchoolType, MAX_EDUCATION_STAGE_YEARS,
+    SCHOOL_YEAR_START_MONTH,
+};
+pub use education_repository::EducationRepository;
+pub use education_service::EducationService;
+pub use education_traits::{EducationRepositoryTrait, EducationServiceTrait};
@@ -0,0 +1,173 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Database row for `envelopes`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::envelopes)]
+pub struct EnvelopeDB {
+    pub id: String,
+    pub account_id: String,
+    pub name: String,
+    pub balance: String,
+    pub is_archived: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A named share of a cash account's balance, in the account's currency
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Envelope {
+    pub id: String,
+    pub account_id: String,
+    pub name: String,
+    pub balance: Decimal,
+    /// Archived envelopes are empty and kept only so transfer history can name them
+    pub is_archived: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<EnvelopeDB> for Envelope {
+    type Error = Error;
+
+    fn try_from(db: EnvelopeDB) -> Result<Self> {
+        Ok(Envelope {
+            id: db.id,
+            account_id: db.account_id,
+            name: db.name,
+            balance: Decimal::from_str(&db.balance)?,
+            is_archived: db.is_archived,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or renaming an envelope. Envelopes start empty; money is
+/// assigned with a transfer from the account's unassigned cash.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewEnvelope {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub name: String,
+}
+
+impl NewEnvelope {
+    pub fn validate(&self) -> Result<()> {
+        if self.account_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "accountId".to_string(),
+            )));
+        }
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Database row for `envelope_transfers`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::envelope_transfers)]
+pub struct EnvelopeTransferDB {
+    pub id: String,
+    pub account_id: String,
+    pub from_envelope_id: Option<String>,
+    pub to_envelope_id: Option<String>,
+    pub amount: String,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Money moved between envelopes. A missing side is the account's unassigned cash.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvelopeTransfer {
+    pub id: String,
+    pub account_id: String,
+    pub from_envelope_id: Option<String>,
+    pub to_envelope_id: Option<String>,
+    pub amount: Decimal,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+    #[serde(default)]
+    pub from_envelope_name: Option<String>,
+    #[serde(default)]
+    pub to_envelope_name: Option<String>,
+}
+
+impl TryFrom<EnvelopeTransferDB> for EnvelopeTransfer {
+    type Error = Error;
+
+    fn try_from(db: EnvelopeTransferDB) -> Result<Self> {
+        Ok(EnvelopeTransfer {
+            id: db.id,
+            account_id: db.account_id,
+            from_envelope_id: db.from_envelope_id,
+            to_envelope_id: db.to_envelope_id,
+            amount: Decimal::from_str(&db.amount)?,
+            note: db.note,
+            created_at: db.created_at,
+            from_envelope_name: None,
+            to_envelope_name: None,
+        })
+    }
+}
+
+/// Input for assigning, moving or releasing envelope money
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewEnvelopeTransfer {
+    pub account_id: String,
+    /// Unset to assign unassigned cash
+    pub from_envelope_id: Option<String>,
+    /// Unset to release money back to unassigned cash
+    pub to_envelope_id: Option<String>,
+    pub amount: Decimal,
+    pub note: Option<String>,
+}
+
+impl NewEnvelopeTransfer {
+    pub fn validate(&self) -> Result<()> {
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Transfer amount must be positive".to_string(),
+            )));
+        }
+        if self.from_envelope_id.is_none() && self.to_envelope_id.is_none() {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A transfer needs an envelope on at least one side".to_string(),
+            )));
+        }
+        if self.from_envelope_id == self.to_envelope_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Cannot transfer an envelope to itself".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A cash account's envelopes and how much of its balance is still unassigned
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountEnvelopes {
+    pub account_id: String,
+    pub currency: String,
+    /// Cash held by the account, from its latest snapshot
+    pub cash_balance: Decimal,
+    pub allocated: Decimal,
+    /// Negative when spending has left less cash than the envelopes hold
+    pub unassigned: Decimal,
+    pub overallocated: bool,
+    pub envelopes: Vec<Envelope>,
+}
@@ -0,0 +1,329 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::envelopes_model::{
+    Envelope, EnvelopeDB, EnvelopeTransfer, EnvelopeTransferDB, NewEnvelope, NewEnvelopeTransfer,
+};
+use super::envelopes_traits::EnvelopeRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Error, Result, ValidationError};
+use crate::schema::{envelope_transfers, envelopes};
+
+pub struct EnvelopeRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl EnvelopeRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        EnvelopeRepository { pool, writer }
+    }
+}
+
+fn load_envelope(conn: &mut SqliteConnection, id: &str) -> Result<Envelope> {
+    envelopes::table
+        .find(id)
+        .first::<EnvelopeDB>(conn)?
+        .try_into()
+}
+
+fn set_balance(conn: &mut SqliteConnection, id: &str, balance: Decimal) -> Result<()> {
+    diesel::update(envelopes::table.find(id))
+        .set((
+            envelopes::balance.eq(balance.to_string()),
+            envelopes::updated_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn)?;
+    Ok(())
+}
+
+fn insert_transfer(
+    conn: &mut SqliteConnection,
+    transfer: NewEnvelopeTransfer,
+) -> Result<EnvelopeTransfer> {
+    let record = EnvelopeTransferDB {
+        id: Uuid::new_v4().to_string(),
+        account_id: transfer.account_id,
+        from_envelope_id: transfer.from_envelope_id,
+        to_envelope_id: transfer.to_envelope_id,
+        amount: transfer.amount.to_string(),
+        note: transfer
+            .note
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty()),
+        created_at: Utc::now().naive_utc(),
+    };
+    diesel::insert_into(envelope_transfers::table)
+        .values(&record)
+        .get_result::<EnvelopeTransferDB>(conn)?
+        .try_into()
+}
+
+/// Checks that a transfer can use the envelope
+fn check_usable(envelope: &Envelope, account_id: &str) -> Result<()> {
+    if envelope.account_id != account_id {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Envelope {} belongs to another account",
+            envelope.name
+        ))));
+    }
+    if envelope.is_archived {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Envelope {} is archived",
+            envelope.name
+        ))));
+    }
+    Ok(())
+}
+
+/// Rejects transfers that would overdraw an envelope, or that assign more than the
+/// account's unassigned cash. `allocated` is what the account's envelopes hold now.
+pub(crate) fn check_transfer(
+    transfer: &NewEnvelopeTransfer,
+    from: Option<&Envelope>,
+    to: Option<&Envelope>,
+    allocated: Decimal,
+    cash_balance: Decimal,
+) -> Result<()> {
+    if let Some(to) = to {
+        check_usable(to, &transfer.account_id)?;
+    }
+    match from {
+        Some(from) => {
+            check_usable(from, &transfer.account_id)?;
+            if from.balance < transfer.amount {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "Envelope {} only holds {}",
+                    from.name, from.balance
+                ))));
+            }
+        }
+        None => {
+            let unassigned = cash_balance - allocated;
+            if transfer.amount > unassigned {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "Only {} of the account balance is unassigned",
+                    unassigned.max(Decimal::ZERO)
+                ))));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl EnvelopeRepositoryTrait for EnvelopeRepository {
+    fn get_envelopes(&self, account_id: &str) -> Result<Vec<Envelope>> {
+        let mut conn = get_connection(&self.pool)?;
+        envelopes::table
+            .filter(envelopes::account_id.eq(account_id))
+            .order(envelopes::name.asc())
+            .load::<EnvelopeDB>(&mut conn)?
+            .into_iter()
+            .map(Envelope::try_from)
+            .collect()
+    }
+
+    fn get_envelope(&self, id: &str) -> Result<Envelope> {
+        let mut conn = get_connection(&self.pool)?;
+        load_envelope(&mut conn, id)
+    }
+
+    async fn insert_envelope(&self, envelope: NewEnvelope) -> Result<Envelope> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Envelope> {
+                let now = Utc::now().naive_utc();
+                let record = EnvelopeDB {
+                    id: envelope.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: envelope.account_id,
+                    name: envelope.name.trim().to_string(),
+                    balance: Decimal::ZERO.to_string(),
+                    is_archived: false,
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(envelopes::table)
+                    .values(&record)
+                    .get_result::<EnvelopeDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn rename_envelope(&self, id: &str, name: &str) -> Result<Envelope> {
+        let id_owned = id.to_string();
+        let name_owned = name.trim().to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Envelope> {
+                diesel::update(envelopes::table.find(id_owned))
+                    .set((
+                        envelopes::name.eq(name_owned),
+                        envelopes::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<EnvelopeDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn archive_envelope(&self, id: &str) -> Result<Envelope> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Envelope> {
+                let envelope = load_envelope(conn, &id_owned)?;
+                if !envelope.balance.is_zero() {
+                    insert_transfer(
+                        conn,
+                        NewEnvelopeTransfer {
+                            account_id: envelope.account_id.clone(),
+                            from_envelope_id: Some(envelope.id.clone()),
+                            to_envelope_id: None,
+                            amount: envelope.balance,
+                            note: None,
+                        },
+                    )?;
+                }
+                diesel::update(envelopes::table.find(id_owned))
+                    .set((
+                        envelopes::balance.eq(Decimal::ZERO.to_string()),
+                        envelopes::is_archived.eq(true),
+                        envelopes::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<EnvelopeDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    fn get_transfers(&self, account_id: &str) -> Result<Vec<EnvelopeTransfer>> {
+        let mut conn = get_connection(&self.pool)?;
+        envelope_transfers::table
+            .filter(envelope_transfers::account_id.eq(account_id))
+            .order(envelope_transfers::created_at.desc())
+            .load::<EnvelopeTransferDB>(&mut conn)?
+            .into_iter()
+            .map(EnvelopeTransfer::try_from)
+            .collect()
+    }
+
+    async fn apply_transfer(
+        &self,
+        transfer: NewEnvelopeTransfer,
+        cash_balance: Decimal,
+    ) -> Result<EnvelopeTransfer> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<EnvelopeTransfer> {
+                    let from = transfer
+                        .from_envelope_id
+                        .as_deref()
+                        .map(|id| load_envelope(conn, id))
+                        .transpose()?;
+                    let to = transfer
+                        .to_envelope_id
+                        .as_deref()
+                        .map(|id| load_envelope(conn, id))
+                        .transpose()?;
+                    let allocated: Decimal = envelopes::table
+                        .filter(envelopes::account_id.eq(&transfer.account_id))
+                        .select(envelopes::balance)
+                        .load::<String>(conn)?
+                        .iter()
+                        .map(|b| b.parse::<Decimal>())
+                        .sum::<std::result::Result<Decimal, _>>()?;
+                    check_transfer(
+                        &transfer,
+                        from.as_ref(),
+                        to.as_ref(),
+                        allocated,
+                        cash_balance,
+                    )?;
+
+                    if let Some(from) = &from {
+                        set_balance(conn, &from.id, from.balance - transfer.amount)?;
+                    }
+                    if let Some(to) = &to {
+                        set_balance(conn, &to.id, to.balance + transfer.amount)?;
+                    }
+                    insert_transfer(conn, transfer)
+                },
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn envelope(id: &str, balance: Decimal, is_archived: bool) -> Envelope {
+        let now = Utc::now().naive_utc();
+        Envelope {
+            id: id.to_string(),
+            account_id: "bank".to_string(),
+            name: id.to_string(),
+            balance,
+            is_archived,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn transfer(from: Option<&str>, to: Option<&str>, amount: Decimal) -> NewEnvelopeTransfer {
+        NewEnvelopeTransfer {
+            account_id: "bank".to_string(),
+            from_envelope_id: from.map(str::to_string),
+            to_envelope_id: to.map(str::to_string),
+            amount,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn transfers_never_exceed_balances() {
+        let food = envelope("food", dec!(400_000), false);
+        let rent = envelope("rent", dec!(500_000), false);
+        let old = envelope("old", Decimal::ZERO, true);
+        let allocated = food.balance + rent.balance;
+        let cash = dec!(1_000_000);
+
+        // 100k of the account is unassigned
+        let assign = |amount| transfer(None, Some("food"), amount);
+        assert!(check_transfer(&assign(dec!(100_000)), None, Some(&food), allocated, cash).is_ok());
+        assert!(
+            check_transfer(&assign(dec!(100_001)), None, Some(&food), allocated, cash).is_err()
+        );
+
+        let move_food = |amount| transfer(Some("food"), Some("rent"), amount);
+        assert!(check_transfer(
+            &move_food(dec!(400_000)),
+            Some(&food),
+            Some(&rent),
+            allocated,
+            cash
+        )
+        .is_ok());
+        assert!(check_transfer(
+            &move_food(dec!(400_001)),
+            Some(&food),
+            Some(&rent),
+            allocated,
+            cash
+        )
+        .is_err());
+
+        // Releasing is always allowed, even when spending left the account overallocated
+        let release = transfer(Some("rent"), None, dec!(500_000));
+        assert!(check_transfer(&release, Some(&rent), None, allocated, dec!(0)).is_ok());
+
+        let to_archived = transfer(Some("food"), Some("old"), dec!(1));
+        assert!(check_transfer(&to_archived, Some(&food), Some(&old), allocated, cash).is_err());
+    }
+}
@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::envelopes_model::{
+    AccountEnvelopes, Envelope, EnvelopeTransfer, NewEnvelope, NewEnvelopeTransfer,
+};
+use super::envelopes_traits::{EnvelopeRepositoryTrait, EnvelopeServiceTrait};
+use crate::accounts::{Account, AccountRepositoryTrait, ACCOUNT_TYPE_CASH};
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::{convert_or_keep, FxServiceTrait};
+use crate::portfolio::snapshot::SnapshotRepositoryTrait;
+
+pub struct EnvelopeService {
+    repository: Arc<dyn EnvelopeRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    snapshot_repository: Arc<dyn SnapshotRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+}
+
+impl EnvelopeService {
+    pub fn new(
+        repository: Arc<dyn EnvelopeRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        snapshot_repository: Arc<dyn SnapshotRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            snapshot_repository,
+            fx_service,
+        }
+    }
+
+    fn cash_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type != ACCOUNT_TYPE_CASH {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Envelopes are only available for cash accounts, not {}",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+
+    /// Envelope names are unique among an account's active envelopes
+    fn check_name_free(&self, account_id: &str, name: &str, except_id: Option<&str>) -> Result<()> {
+        let name = name.trim();
+        let taken = self.repository.get_envelopes(account_id)?.iter().any(|e| {
+            !e.is_archived && Some(e.id.as_str()) != except_id && e.name.eq_ignore_ascii_case(name)
+        });
+        if taken {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "An envelope named {} already exists",
+                name
+            ))));
+        }
+        Ok(())
+    }
+
+    /// Cash held by the account from its latest snapshot, in the account currency
+    fn cash_balance(&self, account: &Account) -> Result<Decimal> {
+        let snapshots = self
+            .snapshot_repository
+            .get_all_latest_snapshots(std::slice::from_ref(&account.id))?;
+        Ok(snapshots
+            .get(&account.id)
+            .map(|snapshot| {
+                snapshot
+                    .cash_balances
+                    .iter()
+                    .map(|(currency, amount)| {
+                        convert_or_keep(
+                            self.fx_service.as_ref(),
+                            *amount,
+                            currency,
+                            &account.currency,
+                            None,
+                        )
+                    })
+                    .sum()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl EnvelopeServiceTrait for EnvelopeService {
+    fn get_account_envelopes(&self, account_id: &str) -> Result<AccountEnvelopes> {
+        let account = self.cash_account(account_id)?;
+        let cash_balance = self.cash_balance(&account)?;
+        let envelopes: Vec<Envelope> = self
+            .repository
+            .get_envelopes(account_id)?
+            .into_iter()
+            .filter(|e| !e.is_archived)
+            .collect();
+        let allocated: Decimal = envelopes.iter().map(|e| e.balance).sum();
+        let unassigned = cash_balance - allocated;
+
+        Ok(AccountEnvelopes {
+            account_id: account.id,
+            currency: account.currency,
+            cash_balance,
+            allocated,
+            unassigned,
+            overallocated: unassigned < Decimal::ZERO,
+            envelopes,
+        })
+    }
+
+    fn get_envelope(&self, id: &str) -> Result<Envelope> {
+        self.repository.get_envelope(id)
+    }
+
+    async fn create_envelope(&self, envelope: NewEnvelope) -> Result<Envelope> {
+        envelope.validate()?;
+        self.cash_account(&envelope.account_id)?;
+        self.check_name_free(&envelope.account_id, &envelope.name, None)?;
+        self.repository.insert_envelope(envelope).await
+    }
+
+    async fn rename_envelope(&self, id: &str, name: &str) -> Result<Envelope> {
+        if name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        let envelope = self.repository.get_envelope(id)?;
+        self.check_name_free(&envelope.account_id, name, Some(id))?;
+        self.repository.rename_envelope(id, name).await
+    }
+
+    async fn archive_envelope(&self, id: &str) -> Result<Envelope> {
+        self.repository.archive_envelope(id).await
+    }
+
+    fn get_envelope_transfers(&self, account_id: &str) -> Result<Vec<EnvelopeTransfer>> {
+        let names: HashMap<String, String> = self
+            .repository
+            .get_envelopes(account_id)?
+            .into_iter()
+            .map(|e| (e.id, e.name))
+            .collect();
+        let name_of = |id: &Option<String>| id.as_ref().and_then(|id| names.get(id).cloned());
+        Ok(self
+            .repository
+            .get_transfers(account_id)?
+            .into_iter()
+            .map(|transfer| EnvelopeTransfer {
+                from_envelope_name: name_of(&transfer.from_envelope_id),
+                to_envelope_name: name_of(&transfer.to_envelope_id),
+                ..transfer
+            })
+            .collect())
+    }
+
+    async fn transfer_envelope_funds(
+        &self,
+        transfer: NewEnvelopeTransfer,
+    ) -> Result<EnvelopeTransfer> {
+        transfer.validate()?;
+        let account = self.cash_account(&transfer.account_id)?;
+        let cash_balance = self.cash_balance(&account)?;
+        self.repository.apply_transfer(transfer, cash_balance).await
+    }
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use super::envelopes_model::{
+    AccountEnvelopes, Envelope, EnvelopeTransfer, NewEnvelope, NewEnvelopeTransfer,
+};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait EnvelopeRepositoryTrait: Send + Sync {
+    /// All of the account's envelopes, archived ones included
+    fn get_envelopes(&self, account_id: &str) -> Result<Vec<Envelope>>;
+    fn get_envelope(&self, id: &str) -> Result<Envelope>;
+    async fn insert_envelope(&self, envelope: NewEnvelope) -> Result<Envelope>;
+    async fn rename_envelope(&self, id: &str, name: &str) -> Result<Envelope>;
+    /// Releases any balance back to unassigned cash, recording the transfer, then archives
+    async fn archive_envelope(&self, id: &str) -> Result<Envelope>;
+    fn get_transfers(&self, account_id: &str) -> Result<Vec<EnvelopeTransfer>>;
+    /// Records the transfer and moves the balances. Fails if the source envelope holds less
+    /// than the amount, or if assigning would put more in envelopes than `cash_balance`.
+    async fn apply_transfer(
+        &self,
+        transfer: NewEnvelopeTransfer,
+        cash_balance: Decimal,
+    ) -> Result<EnvelopeTransfer>;
+}
+
+#[async_trait]
+pub trait EnvelopeServiceTrait: Send + Sync {
+    fn get_account_envelopes(&self, account_id: &str) -> Result<AccountEnvelopes>;
+    fn get_envelope(&self, id: &str) -> Result<Envelope>;
+    async fn create_envelope(&self, envelope: NewEnvelope) -> Result<Envelope>;
+    async fn rename_envelope(&self, id: &str, name: &str) -> Result<Envelope>;
+    /// Returns the envelope's money to unassigned cash and hides it; its history is kept
+    async fn archive_envelope(&self, id: &str) -> Result<Envelope>;
+    fn get_envelope_transfers(&self, account_id: &str) -> Result<Vec<EnvelopeTransfer>>;
+    async fn transfer_envelope_funds(
+        &self,
+        transfer: NewEnvelopeTransfer,
+    ) -> Result<EnvelopeTransfer>;
+}
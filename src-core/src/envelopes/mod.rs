@@ -0,0 +1,11 @@
+mod envelopes_model;
+mod envelopes_repository;
+mod envelopes_service;
+mod envelopes_traits;
+
+pub use envelopes_model::{
+    AccountEnvelopes, Envelope, EnvelopeTransfer, NewEnvelope, NewEnvelopeTransfer,
+};
+pub use envelopes_repository::EnvelopeRepository;
+pub use envelopes_service::EnvelopeService;
+pub use envelopes_traits::{EnvelopeRepositoryTrait, EnvelopeServiceTrait};
@@ -5,6 +5,7 @@ use thiserror::Error;
 
 use crate::activities::ActivityError;
 use crate::fx::FxError;
+use crate::i18n::{LocalizedMessage, MessageLanguage};
 use crate::market_data::MarketDataError;
 
 // Create a type alias for Result using our Error type
@@ -54,6 +55,18 @@ pub enum Error {
     #[error("Secret store error: {0}")]
     Secret(String),
 
+    #[error("Bank connector failed: {0}")]
+    Connector(String),
+
+    #[error("Google Sheets export failed: {0}")]
+    SheetExport(String),
+
+    #[error("Webhook call failed: {0}")]
+    Webhook(String),
+
+    #[error("Deposit rate feed failed: {0}")]
+    DepositRateFeed(String),
+
     #[error("Unexpected error: {0}")]
     Unexpected(String),
 
@@ -138,6 +151,33 @@ pub enum ValidationError {
 
     #[error("Failed to parse date/time: {0}")]
     DateTimeParse(#[from] ChronoParseError),
+
+    #[error("{0}")]
+    Localized(LocalizedMessage),
+}
+
+impl Error {
+    /// The catalog message behind this error, when it was raised with a message code
+    pub fn localized_message(&self) -> Option<&LocalizedMessage> {
+        match self {
+            Error::Validation(ValidationError::Localized(message)) => Some(message),
+            _ => None,
+        }
+    }
+
+    /// User-facing text in the requested language; uncoded errors keep their English text
+    pub fn localized(&self, language: MessageLanguage) -> String {
+        match self.localized_message() {
+            Some(message) => message.render(language),
+            None => self.to_string(),
+        }
+    }
+}
+
+impl From<LocalizedMessage> for Error {
+    fn from(message: LocalizedMessage) -> Self {
+        Error::Validation(ValidationError::Localized(message))
+    }
 }
 
 // Implement From for DieselError to Error directly
@@ -182,6 +222,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<csv::Error> for Error {
+    fn from(err: csv::Error) -> Self {
+        Error::Validation(ValidationError::InvalidInput(err.to_string()))
+    }
+}
+
 // Add this implementation
 impl From<r2d2::Error> for Error {
     fn from(e: r2d2::Error) -> Self {
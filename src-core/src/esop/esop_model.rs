@@ -0,0 +1,467 @@
+use chrono::{DateTime, Months, NaiveDate, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+use crate::notifications::{Notification, NotificationChannel};
+
+/// Symbol of the manual asset a grant's vested value is held as
+pub fn esop_asset_symbol(grant_id: &str) -> String {
+    format!("ESOP-{}", grant_id)
+}
+
+/// Days ahead of a vesting date that a reminder is sent
+pub const VESTING_REMINDER_DAYS: i64 = 7;
+
+/// Longest vesting schedule accepted, in months
+pub const MAX_VESTING_MONTHS: i32 = 120;
+
+/// Database row for `esop_grants`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::esop_grants)]
+pub struct EsopGrantDB {
+    pub id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub activity_id: Option<String>,
+    pub company: String,
+    pub symbol: Option<String>,
+    pub grant_date: String,
+    pub vesting_start_date: String,
+    pub total_shares: String,
+    pub strike_price: String,
+    pub cliff_months: i32,
+    pub vesting_months: i32,
+    pub vesting_interval_months: i32,
+    pub lockup_months: i32,
+    pub fair_value: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Shares granted by an employer under an ESOP. Prices are per share and in the account
+/// currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EsopGrant {
+    pub id: String,
+    pub account_id: String,
+    /// Manual asset whose quotes value the vested shares
+    pub asset_id: String,
+    /// Activity that added the grant to the account
+    pub activity_id: Option<String>,
+    pub company: String,
+    /// Ticker of the listed company, priced from its quotes
+    pub symbol: Option<String>,
+    pub grant_date: NaiveDate,
+    /// Vesting is counted from this date, often the grant date
+    pub vesting_start_date: NaiveDate,
+    pub total_shares: Decimal,
+    /// Price paid per share on exercise or purchase, zero for share awards
+    pub strike_price: Decimal,
+    /// Nothing vests before the cliff; what would have vested by then vests at once
+    pub cliff_months: i32,
+    /// Months until every share has vested
+    pub vesting_months: i32,
+    /// Months between vesting dates after the cliff
+    pub vesting_interval_months: i32,
+    /// Months vested shares cannot be sold for
+    pub lockup_months: i32,
+    /// Price per share of an unlisted company, or of a listed one without quotes
+    pub fair_value: Option<Decimal>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Shares vesting on a date
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EsopVestingEvent {
+    pub vesting_date: NaiveDate,
+    pub shares: Decimal,
+    /// Shares vested up to and including this date
+    pub vested_shares: Decimal,
+    /// The shares can be sold from this date
+    pub lockup_end_date: NaiveDate,
+}
+
+impl EsopGrant {
+    fn months_after(date: NaiveDate, months: i32) -> NaiveDate {
+        date.checked_add_months(Months::new(months.max(0) as u32))
+            .unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Vesting dates from the cliff to the end of the schedule, each vesting what is due
+    /// pro rata since the last, in whole shares with the remainder on the last date
+    pub fn vesting_schedule(&self) -> Vec<EsopVestingEvent> {
+        let mut months: Vec<i32> = Vec::new();
+        if self.cliff_months > 0 {
+            months.push(self.cliff_months);
+        }
+        let step = self.vesting_interval_months.max(1);
+        let mut month = step;
+        while month < self.vesting_months {
+            if month > self.cliff_months {
+                months.push(month);
+            }
+            month += step;
+        }
+        if months.last() != Some(&self.vesting_months) {
+            months.push(self.vesting_months);
+        }
+
+        let total_months = Decimal::from(self.vesting_months.max(1));
+        let mut vested = Decimal::ZERO;
+        let mut events = Vec::new();
+        for month in months {
+            let cumulative = if month >= self.vesting_months {
+                self.total_shares
+            } else {
+                (self.total_shares * Decimal::from(month) / total_months).floor()
+            };
+            if cumulative <= vested {
+                continue;
+            }
+            let vesting_date = Self::months_after(self.vesting_start_date, month);
+            events.push(EsopVestingEvent {
+                vesting_date,
+                shares: cumulative - vested,
+                vested_shares: cumulative,
+                lockup_end_date: Self::months_after(vesting_date, self.lockup_months),
+            });
+            vested = cumulative;
+        }
+        events
+    }
+
+    /// Shares vested on or before `date`
+    pub fn vested_shares(&self, date: NaiveDate) -> Decimal {
+        self.vesting_schedule()
+            .iter()
+            .take_while(|event| event.vesting_date <= date)
+            .last()
+            .map_or(Decimal::ZERO, |event| event.vested_shares)
+    }
+
+    /// What a vested share is worth at `price`: the price above the strike, nothing when the
+    /// option is under water
+    pub fn intrinsic_value(&self, price: Decimal) -> Decimal {
+        (price - self.strike_price).max(Decimal::ZERO)
+    }
+}
+
+impl TryFrom<EsopGrantDB> for EsopGrant {
+    type Error = Error;
+
+    fn try_from(db: EsopGrantDB) -> Result<Self> {
+        Ok(EsopGrant {
+            id: db.id,
+            account_id: db.account_id,
+            asset_id: db.asset_id,
+            activity_id: db.activity_id,
+            company: db.company,
+            symbol: db.symbol,
+            grant_date: parse_forecast_date(&db.grant_date)?,
+            vesting_start_date: parse_forecast_date(&db.vesting_start_date)?,
+            total_shares: Decimal::from_str(&db.total_shares)?,
+            strike_price: Decimal::from_str(&db.strike_price)?,
+            cliff_months: db.cliff_months,
+            vesting_months: db.vesting_months,
+            vesting_interval_months: db.vesting_interval_months,
+            lockup_months: db.lockup_months,
+            fair_value: db
+                .fair_value
+                .as_deref()
+                .map(Decimal::from_str)
+                .transpose()?,
+            notes: db.notes,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a grant
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewEsopGrant {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub company: String,
+    pub symbol: Option<String>,
+    pub grant_date: NaiveDate,
+    /// The grant date when unset
+    pub vesting_start_date: Option<NaiveDate>,
+    pub total_shares: Decimal,
+    #[serde(default)]
+    pub strike_price: Decimal,
+    #[serde(default)]
+    pub cliff_months: i32,
+    pub vesting_months: i32,
+    pub vesting_interval_months: i32,
+    #[serde(default)]
+    pub lockup_months: i32,
+    pub fair_value: Option<Decimal>,
+    pub notes: Option<String>,
+}
+
+impl NewEsopGrant {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [("accountId", &self.account_id), ("company", &self.company)] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        let listed = self
+            .symbol
+            .as_deref()
+            .is_some_and(|symbol| !symbol.trim().is_empty());
+        if !listed && self.fair_value.is_none() {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A grant needs the company's ticker or a fair value per share".to_string(),
+            )));
+        }
+        if self.total_shares <= Decimal::ZERO || self.total_shares.fract() != Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Shares granted must be a positive whole number".to_string(),
+            )));
+        }
+        if self.strike_price < Decimal::ZERO
+            || self.fair_value.is_some_and(|value| value < Decimal::ZERO)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Strike price and fair value cannot be negative".to_string(),
+            )));
+        }
+        if self.vesting_months <= 0 || self.vesting_months > MAX_VESTING_MONTHS {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Vesting must take between 1 and {} months",
+                MAX_VESTING_MONTHS
+            ))));
+        }
+        if self.vesting_interval_months <= 0 || self.vesting_interval_months > self.vesting_months {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Shares must vest at least once over the vesting period".to_string(),
+            )));
+        }
+        if self.cliff_months < 0 || self.cliff_months > self.vesting_months {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The cliff cannot be longer than the vesting period".to_string(),
+            )));
+        }
+        if self.lockup_months < 0 {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Lockup months cannot be negative".to_string(),
+            )));
+        }
+        if self
+            .vesting_start_date
+            .is_some_and(|start| start < self.grant_date)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Vesting cannot start before the grant date".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A grant valued as of a date
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EsopGrantSummary {
+    pub grant: EsopGrant,
+    pub currency: String,
+    pub valued_on: NaiveDate,
+    /// Price per share the grant is valued at
+    pub share_price: Decimal,
+    pub schedule: Vec<EsopVestingEvent>,
+    pub vested_shares: Decimal,
+    pub unvested_shares: Decimal,
+    /// Vested shares still in lockup
+    pub locked_shares: Decimal,
+    /// Vested shares out of lockup
+    pub sellable_shares: Decimal,
+    /// What the vested shares are worth above their strike price, counted in net worth
+    pub vested_value: Decimal,
+    /// The same for the shares still to vest, not counted in net worth
+    pub unvested_value: Decimal,
+    /// Strike price of the vested shares
+    pub exercise_cost: Decimal,
+    pub next_vesting: Option<EsopVestingEvent>,
+    pub fully_vested: bool,
+}
+
+/// A vesting date coming up on one of the grants
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingVesting {
+    pub grant_id: String,
+    pub company: String,
+    pub vesting_date: NaiveDate,
+    pub shares: Decimal,
+    /// The shares' value above their strike price at today's share price
+    pub value: Decimal,
+    pub currency: String,
+    pub lockup_end_date: NaiveDate,
+}
+
+impl UpcomingVesting {
+    pub fn notification(&self, now: DateTime<Utc>) -> Notification {
+        let mut body = format!(
+            "{} shares worth about {} {} vest",
+            self.shares.normalize(),
+            self.value.round().to_i64().unwrap_or_default(),
+            self.currency
+        );
+        if self.lockup_end_date > self.vesting_date {
+            body.push_str(&format!("; they can be sold from {}", self.lockup_end_date));
+        }
+        Notification {
+            id: format!("esop-vesting-{}-{}", self.grant_id, self.vesting_date),
+            channel: NotificationChannel::Vesting,
+            title: format!("{} shares vest on {}", self.company, self.vesting_date),
+            body,
+            created_at: now,
+        }
+    }
+}
+
+/// Values a grant's shares at `share_price` on `date`
+pub fn summarize_esop_grant(
+    grant: EsopGrant,
+    currency: String,
+    share_price: Decimal,
+    date: NaiveDate,
+) -> EsopGrantSummary {
+    let schedule = grant.vesting_schedule();
+    let vested: Vec<&EsopVestingEvent> = schedule
+        .iter()
+        .filter(|event| event.vesting_date <= date)
+        .collect();
+    let vested_shares = vested
+        .last()
+        .map_or(Decimal::ZERO, |event| event.vested_shares);
+    let locked_shares: Decimal = vested
+        .iter()
+        .filter(|event| event.lockup_end_date > date)
+        .map(|event| event.shares)
+        .sum();
+    let unvested_shares = grant.total_shares - vested_shares;
+    let per_share = grant.intrinsic_value(share_price);
+    let next_vesting = schedule
+        .iter()
+        .find(|event| event.vesting_date > date)
+        .cloned();
+
+    EsopGrantSummary {
+        currency,
+        valued_on: date,
+        share_price,
+        vested_shares,
+        unvested_shares,
+        locked_shares,
+        sellable_shares: vested_shares - locked_shares,
+        vested_value: vested_shares * per_share,
+        unvested_value: unvested_shares * per_share,
+        exercise_cost: vested_shares * grant.strike_price,
+        fully_vested: next_vesting.is_none(),
+        next_vesting,
+        schedule,
+        grant,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn grant(cliff_months: i32, interval: i32) -> EsopGrant {
+        let now = Utc::now().naive_utc();
+        EsopGrant {
+            id: "fpt".to_string(),
+            account_id: "ssi".to_string(),
+            asset_id: esop_asset_symbol("fpt"),
+            activity_id: None,
+            company: "FPT".to_string(),
+            symbol: Some("FPT".to_string()),
+            grant_date: date("2025-01-15"),
+            vesting_start_date: date("2025-01-15"),
+            total_shares: dec!(10_000),
+            strike_price: dec!(10_000),
+            cliff_months,
+            vesting_months: 48,
+            vesting_interval_months: interval,
+            lockup_months: 12,
+            fair_value: None,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn cliff_vests_the_first_year_at_once_then_every_quarter() {
+        let schedule = grant(12, 3).vesting_schedule();
+        // The cliff and twelve quarters after it
+        assert_eq!(schedule.len(), 13);
+        assert_eq!(schedule[0].vesting_date, date("2026-01-15"));
+        assert_eq!(schedule[0].shares, dec!(2_500));
+        assert_eq!(schedule[0].lockup_end_date, date("2027-01-15"));
+        assert_eq!(schedule[1].vesting_date, date("2026-04-15"));
+        assert_eq!(schedule[1].shares, dec!(625));
+        assert_eq!(schedule.last().unwrap().vesting_date, date("2029-01-15"));
+        assert_eq!(schedule.last().unwrap().vested_shares, dec!(10_000));
+
+        // Monthly vesting over 48 months leaves the odd shares for the last date
+        let mut monthly = grant(0, 1);
+        monthly.total_shares = dec!(1_000);
+        let schedule = monthly.vesting_schedule();
+        assert_eq!(schedule.len(), 48);
+        assert_eq!(schedule[0].shares, dec!(20));
+        let total: Decimal = schedule.iter().map(|event| event.shares).sum();
+        assert_eq!(total, dec!(1_000));
+    }
+
+    #[test]
+    fn summary_counts_only_vested_shares_above_the_strike() {
+        let summary = summarize_esop_grant(
+            grant(12, 3),
+            "VND".to_string(),
+            dec!(130_000),
+            date("2026-10-18"),
+        );
+        // Cliff plus the April, July and October quarters
+        assert_eq!(summary.vested_shares, dec!(4_375));
+        assert_eq!(summary.unvested_shares, dec!(5_625));
+        // Everything vested this year is still locked up
+        assert_eq!(summary.locked_shares, dec!(4_375));
+        assert_eq!(summary.vested_value, dec!(525_000_000));
+        assert_eq!(summary.exercise_cost, dec!(43_750_000));
+        assert_eq!(
+            summary.next_vesting.unwrap().vesting_date,
+            date("2027-01-15")
+        );
+
+        let under_water = summarize_esop_grant(
+            grant(12, 3),
+            "VND".to_string(),
+            dec!(9_000),
+            date("2026-10-18"),
+        );
+        assert_eq!(under_water.vested_value, Decimal::ZERO);
+    }
+}
@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::esop_model::{EsopGrant, EsopGrantDB, NewEsopGrant};
+use super::esop_traits::EsopRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::esop_grants;
+use crate::utils::text_utils::trimmed;
+
+pub struct EsopRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl EsopRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        EsopRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl EsopRepositoryTrait for EsopRepository {
+    fn get_grants(&self) -> Result<Vec<EsopGrant>> {
+        let mut conn = get_connection(&self.pool)?;
+        esop_grants::table
+            .order((esop_grants::grant_date.asc(), esop_grants::company.asc()))
+            .load::<EsopGrantDB>(&mut conn)?
+            .into_iter()
+            .map(EsopGrant::try_from)
+            .collect()
+    }
+
+    fn get_grant(&self, id: &str) -> Result<EsopGrant> {
+        let mut conn = get_connection(&self.pool)?;
+        esop_grants::table
+            .find(id)
+            .first::<EsopGrantDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_grant(
+        &self,
+        grant: NewEsopGrant,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<EsopGrant> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<EsopGrant> {
+                let now = Utc::now().naive_utc();
+                let vesting_start_date = grant.vesting_start_date.unwrap_or(grant.grant_date);
+                let record = EsopGrantDB {
+                    id: grant.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: grant.account_id,
+                    asset_id,
+                    activity_id,
+                    company: grant.company.trim().to_string(),
+                    symbol: trimmed(grant.symbol).map(|symbol| symbol.to_uppercase()),
+                    grant_date: grant.grant_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    vesting_start_date: vesting_start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    total_shares: grant.total_shares.to_string(),
+                    strike_price: grant.strike_price.to_string(),
+                    cliff_months: grant.cliff_months,
+                    vesting_months: grant.vesting_months,
+                    vesting_interval_months: grant.vesting_interval_months,
+                    lockup_months: grant.lockup_months,
+                    fair_value: grant.fair_value.map(|value| value.to_string()),
+                    notes: trimmed(grant.notes),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(esop_grants::table)
+                    .values(&record)
+                    .get_result::<EsopGrantDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_grant(&self, id: &str, grant: NewEsopGrant) -> Result<EsopGrant> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<EsopGrant> {
+                let vesting_start_date = grant.vesting_start_date.unwrap_or(grant.grant_date);
+                diesel::update(esop_grants::table.find(id_owned))
+                    .set((
+                        esop_grants::company.eq(grant.company.trim().to_string()),
+                        esop_grants::symbol
+                            .eq(trimmed(grant.symbol).map(|symbol| symbol.to_uppercase())),
+                        esop_grants::grant_date
+                            .eq(grant.grant_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        esop_grants::vesting_start_date
+                            .eq(vesting_start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        esop_grants::total_shares.eq(grant.total_shares.to_string()),
+                        esop_grants::strike_price.eq(grant.strike_price.to_string()),
+                        esop_grants::cliff_months.eq(grant.cliff_months),
+                        esop_grants::vesting_months.eq(grant.vesting_months),
+                        esop_grants::vesting_interval_months.eq(grant.vesting_interval_months),
+                        esop_grants::lockup_months.eq(grant.lockup_months),
+                        esop_grants::fair_value.eq(grant.fair_value.map(|value| value.to_string())),
+                        esop_grants::notes.eq(trimmed(grant.notes)),
+                        esop_grants::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<EsopGrantDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_grant(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(esop_grants::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,375 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::esop_model::{
+    esop_asset_symbol, summarize_esop_grant, EsopGrant, EsopGrantSummary, NewEsopGrant,
+    UpcomingVesting, VESTING_REMINDER_DAYS,
+};
+use super::esop_traits::{EsopRepositoryTrait, EsopServiceTrait};
+use crate::accounts::{
+    Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY, ACCOUNT_TYPE_REAL_ESTATE,
+};
+use crate::activities::{
+    ActivityServiceTrait, ActivityUpdate, NewActivity, ACTIVITY_TYPE_ADD_HOLDING,
+};
+use crate::assets::{AssetServiceTrait, UpdateAssetProfile};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::market_data::{DataSource, MarketDataServiceTrait, Quote};
+use crate::notifications::Notification;
+
+pub struct EsopService {
+    repository: Arc<dyn EsopRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+}
+
+fn grant_quote(symbol: &str, date: NaiveDate, value: Decimal, currency: &str) -> Quote {
+    let now = Utc::now();
+    Quote {
+        id: format!("{}_{}", symbol, date.format(FORECAST_DATE_FORMAT)),
+        symbol: symbol.to_string(),
+        timestamp: date.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc(),
+        open: value,
+        high: value,
+        low: value,
+        close: value,
+        adjclose: value,
+        volume: Decimal::ZERO,
+        currency: currency.to_string(),
+        data_source: DataSource::Manual,
+        created_at: now,
+    }
+}
+
+/// Share prices of a grant's company by date, from the ticker's quotes when it has any
+struct SharePrices {
+    quotes: Vec<(NaiveDate, Decimal)>,
+    fair_value: Decimal,
+}
+
+impl SharePrices {
+    /// Close of the last quote on or before `date`; the fair value before the first quote
+    /// or without a ticker
+    fn on(&self, date: NaiveDate) -> Decimal {
+        self.quotes
+            .iter()
+            .rev()
+            .find(|(quoted_on, _)| *quoted_on <= date)
+            .map_or(self.fair_value, |(_, close)| *close)
+    }
+}
+
+impl EsopService {
+    pub fn new(
+        repository: Arc<dyn EsopRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            asset_service,
+            activity_service,
+            market_data_service,
+        }
+    }
+
+    fn holding_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type == ACCOUNT_TYPE_LIABILITY
+            || account.account_type == ACCOUNT_TYPE_REAL_ESTATE
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "ESOP grants cannot be held in {}",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+
+    /// The grant is held as a single unit costing nothing; its quotes carry the value
+    fn grant_activity(grant: &NewEsopGrant, account: &Account, asset_id: &str) -> NewActivity {
+        NewActivity {
+            id: None,
+            account_id: account.id.clone(),
+            asset_id: asset_id.to_string(),
+            activity_type: ACTIVITY_TYPE_ADD_HOLDING.to_string(),
+            activity_date: grant.grant_date.format(FORECAST_DATE_FORMAT).to_string(),
+            quantity: Some(Decimal::ONE),
+            unit_price: Some(Decimal::ZERO),
+            currency: account.currency.clone(),
+            fee: Some(Decimal::ZERO),
+            amount: None,
+            is_draft: false,
+            comment: Some(format!("ESOP grant from {}", grant.company.trim())),
+        }
+    }
+
+    /// Names the grant's asset after the company and files it under equity
+    async fn update_asset_profile(&self, grant: &EsopGrant) -> Result<()> {
+        self.asset_service
+            .update_asset_profile(
+                &grant.asset_id,
+                UpdateAssetProfile {
+                    symbol: grant.asset_id.clone(),
+                    name: Some(format!("{} ESOP ({})", grant.company, grant.grant_date)),
+                    sectors: None,
+                    countries: None,
+                    notes: grant.notes.clone().unwrap_or_default(),
+                    asset_sub_class: Some("ESOP".to_string()),
+                    asset_class: Some("Equity".to_string()),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn share_prices(&self, grant: &EsopGrant) -> Result<SharePrices> {
+        let mut quotes: Vec<(NaiveDate, Decimal)> = match &grant.symbol {
+            Some(symbol) => self
+                .market_data_service
+                .get_historical_quotes_for_symbol(symbol)?
+                .into_iter()
+                .map(|quote| (quote.timestamp.date_naive(), quote.close))
+                .collect(),
+            None => Vec::new(),
+        };
+        quotes.sort_by_key(|(date, _)| *date);
+        Ok(SharePrices {
+            quotes,
+            fair_value: grant.fair_value.unwrap_or(Decimal::ZERO),
+        })
+    }
+
+    fn vested_value(grant: &EsopGrant, prices: &SharePrices, date: NaiveDate) -> Decimal {
+        grant.vested_shares(date) * grant.intrinsic_value(prices.on(date))
+    }
+
+    /// Quotes the grant afresh: nothing on the grant date, then the vested shares' value on
+    /// each vesting date so far and today
+    async fn requote(&self, grant: &EsopGrant) -> Result<()> {
+        for quote in self
+            .market_data_service
+            .get_historical_quotes_for_symbol(&grant.asset_id)?
+        {
+            self.market_data_service.delete_quote(&quote.id).await?;
+        }
+        let currency = self
+            .account_repository
+            .get_by_id(&grant.account_id)?
+            .currency;
+        let prices = self.share_prices(grant)?;
+        let today = Utc::now().date_naive();
+
+        let mut dates: BTreeSet<NaiveDate> = grant
+            .vesting_schedule()
+            .into_iter()
+            .map(|event| event.vesting_date)
+            .filter(|date| *date <= today)
+            .collect();
+        dates.insert(grant.grant_date);
+        if today > grant.grant_date {
+            dates.insert(today);
+        }
+        for date in dates {
+            self.market_data_service
+                .add_quote(&grant_quote(
+                    &grant.asset_id,
+                    date,
+                    Self::vested_value(grant, &prices, date),
+                    &currency,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn summary(&self, grant: EsopGrant) -> Result<EsopGrantSummary> {
+        let account = self.account_repository.get_by_id(&grant.account_id)?;
+        let today = Utc::now().date_naive();
+        let share_price = self.share_prices(&grant)?.on(today);
+        Ok(summarize_esop_grant(
+            grant,
+            account.currency,
+            share_price,
+            today,
+        ))
+    }
+}
+
+#[async_trait]
+impl EsopServiceTrait for EsopService {
+    fn get_esop_grants(&self) -> Result<Vec<EsopGrant>> {
+        self.repository.get_grants()
+    }
+
+    fn get_esop_grant_summaries(&self) -> Result<Vec<EsopGrantSummary>> {
+        self.repository
+            .get_grants()?
+            .into_iter()
+            .map(|grant| self.summary(grant))
+            .collect()
+    }
+
+    fn get_esop_grant_summary(&self, id: &str) -> Result<EsopGrantSummary> {
+        self.summary(self.repository.get_grant(id)?)
+    }
+
+    async fn create_esop_grant(&self, mut grant: NewEsopGrant) -> Result<EsopGrant> {
+        grant.validate()?;
+        let account = self.holding_account(&grant.account_id)?;
+
+        let id = grant
+            .id
+            .get_or_insert_with(|| Uuid::new_v4().to_string())
+            .clone();
+        let asset = self
+            .asset_service
+            .create_manual_asset(&esop_asset_symbol(&id), account.currency.clone())
+            .await?;
+        let activity = self
+            .activity_service
+            .create_activity(Self::grant_activity(&grant, &account, &asset.id))
+            .await?;
+
+        let created = self
+            .repository
+            .insert_grant(grant, asset.id, Some(activity.id))
+            .await?;
+        self.update_asset_profile(&created).await?;
+        self.requote(&created).await?;
+        Ok(created)
+    }
+
+    async fn update_esop_grant(&self, id: &str, grant: NewEsopGrant) -> Result<EsopGrant> {
+        grant.validate()?;
+        let existing = self.repository.get_grant(id)?;
+        if existing.account_id != grant.account_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A grant cannot be moved to another account".to_string(),
+            )));
+        }
+        let account = self.holding_account(&existing.account_id)?;
+
+        if grant.grant_date != existing.grant_date || grant.company.trim() != existing.company {
+            if let Some(activity_id) = existing.activity_id.clone() {
+                let activity = Self::grant_activity(&grant, &account, &existing.asset_id);
+                self.activity_service
+                    .update_activity(ActivityUpdate {
+                        id: activity_id,
+                        account_id: activity.account_id,
+                        asset_id: activity.asset_id,
+                        activity_type: activity.activity_type,
+                        activity_date: activity.activity_date,
+                        quantity: activity.quantity,
+                        unit_price: activity.unit_price,
+                        currency: activity.currency,
+                        fee: activity.fee,
+                        amount: activity.amount,
+                        is_draft: activity.is_draft,
+                        comment: activity.comment,
+                    })
+                    .await?;
+            }
+        }
+        let updated = self.repository.update_grant(id, grant).await?;
+        // The schedule and prices feed every quote, so the grant is quoted afresh
+        self.requote(&updated).await?;
+        self.update_asset_profile(&updated).await?;
+        Ok(updated)
+    }
+
+    async fn delete_esop_grant(&self, id: &str) -> Result<usize> {
+        let grant = self.repository.get_grant(id)?;
+        let deleted = self.repository.delete_grant(id).await?;
+
+        if let Some(activity_id) = grant.activity_id.clone() {
+            self.activity_service.delete_activity(activity_id).await?;
+        }
+        for quote in self
+            .market_data_service
+            .get_historical_quotes_for_symbol(&grant.asset_id)?
+        {
+            self.market_data_service.delete_quote(&quote.id).await?;
+        }
+        match self.asset_service.delete_asset(&grant.asset_id).await {
+            // Activities recorded on the grant since, e.g. an exercise, keep the asset around
+            Ok(()) | Err(Error::ConstraintViolation(_)) => Ok(deleted),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_upcoming_vestings(&self, days: i64) -> Result<Vec<UpcomingVesting>> {
+        let today = Utc::now().date_naive();
+        let until = today
+            .checked_add_days(Days::new(days.max(0) as u64))
+            .unwrap_or(NaiveDate::MAX);
+        let mut vestings = Vec::new();
+        for grant in self.repository.get_grants()? {
+            let account = self.account_repository.get_by_id(&grant.account_id)?;
+            let per_share = grant.intrinsic_value(self.share_prices(&grant)?.on(today));
+            vestings.extend(
+                grant
+                    .vesting_schedule()
+                    .into_iter()
+                    .filter(|event| event.vesting_date >= today && event.vesting_date <= until)
+                    .map(|event| UpcomingVesting {
+                        grant_id: grant.id.clone(),
+                        company: grant.company.clone(),
+                        vesting_date: event.vesting_date,
+                        shares: event.shares,
+                        value: event.shares * per_share,
+                        currency: account.currency.clone(),
+                        lockup_end_date: event.lockup_end_date,
+                    }),
+            );
+        }
+        vestings.sort_by(|a, b| {
+            a.vesting_date
+                .cmp(&b.vesting_date)
+                .then_with(|| a.company.cmp(&b.company))
+        });
+        Ok(vestings)
+    }
+
+    fn vesting_notifications(&self, now: DateTime<Utc>) -> Result<Vec<Notification>> {
+        Ok(self
+            .get_upcoming_vestings(VESTING_REMINDER_DAYS)?
+            .iter()
+            .map(|vesting| vesting.notification(now))
+            .collect())
+    }
+
+    async fn refresh_esop_quotes(&self, date: NaiveDate) -> Result<usize> {
+        let mut quoted = 0;
+        for grant in self.repository.get_grants()? {
+            if date <= grant.grant_date {
+                continue;
+            }
+            let currency = self
+                .account_repository
+                .get_by_id(&grant.account_id)?
+                .currency;
+            let prices = self.share_prices(&grant)?;
+            self.market_data_service
+                .add_quote(&grant_quote(
+                    &grant.asset_id,
+                    date,
+                    Self::vested_value(&grant, &prices, date),
+                    &currency,
+                ))
+                .await?;
+            quoted += 1;
+        }
+        Ok(quoted)
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use super::esop_model::{EsopGrant, EsopGrantSummary, NewEsopGrant, UpcomingVesting};
+use crate::errors::Result;
+use crate::notifications::Notification;
+
+#[async_trait]
+pub trait EsopRepositoryTrait: Send + Sync {
+    fn get_grants(&self) -> Result<Vec<EsopGrant>>;
+    fn get_grant(&self, id: &str) -> Result<EsopGrant>;
+    /// Stores a grant under `grant.id`, which the caller has already assigned
+    async fn insert_grant(
+        &self,
+        grant: NewEsopGrant,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<EsopGrant>;
+    async fn update_grant(&self, id: &str, grant: NewEsopGrant) -> Result<EsopGrant>;
+    async fn delete_grant(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait EsopServiceTrait: Send + Sync {
+    fn get_esop_grants(&self) -> Result<Vec<EsopGrant>>;
+    /// Every grant with its vesting schedule, valued at today's share price
+    fn get_esop_grant_summaries(&self) -> Result<Vec<EsopGrantSummary>>;
+    fn get_esop_grant_summary(&self, id: &str) -> Result<EsopGrantSummary>;
+    /// Adds the grant to its account as a manual asset quoted at the vested shares' value
+    /// above their strike price, so it counts in net worth and in the goals the account is
+    /// allocated to
+    async fn create_esop_grant(&self, grant: NewEsopGrant) -> Result<EsopGrant>;
+    async fn update_esop_grant(&self, id: &str, grant: NewEsopGrant) -> Result<EsopGrant>;
+    /// Removes the grant with its activity and quotes; the account stays
+    async fn delete_esop_grant(&self, id: &str) -> Result<usize>;
+    /// Vesting dates over the next `days` days, soonest first
+    fn get_upcoming_vestings(&self, days: i64) -> Result<Vec<UpcomingVesting>>;
+    /// Reminders for the shares vesting within `VESTING_REMINDER_DAYS`
+    fn vesting_notifications(&self, now: DateTime<Utc>) -> Result<Vec<Notification>>;
+    /// Quotes every grant at its vested value on `date`, as shares vest and the share price
+    /// moves; returns how many were quoted
+    async fn refresh_esop_quotes(&self, date: NaiveDate) -> Result<usize>;
+}
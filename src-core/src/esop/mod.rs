@@ -0,0 +1,12 @@
+mod esop_model;
+mod esop_repository;
+mod esop_service;
+mod esop_traits;
+
+pub use esop_model::{
+    esop_asset_symbol, summarize_esop_grant, EsopGrant, EsopGrantDB, EsopGrantSummary,
+    EsopVestingEvent, NewEsopGrant, UpcomingVesting, MAX_VESTING_MONTHS, VESTING_REMINDER_DAYS,
+};
+pub use esop_repository::EsopRepository;
+pub use esop_service::EsopService;
+pub use esop_traits::{EsopRepositoryTrait, EsopServiceTrait};
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, ValidationError};
+
+/// Prefix of the environment variables that override a flag, e.g. `WEALTHVN_FEATURE_SYNC=1`
+pub const ENV_OVERRIDE_PREFIX: &str = "WEALTHVN_FEATURE_";
+
+/// Experimental subsystems that can ship dark and be toggled per profile
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureFlag {
+    NewAllocationModel,
+    Sync,
+    RestServer,
+    /// Time core operations into the local metrics sink (see `telemetry`)
+    PerformanceMetrics,
+}
+
+impl FeatureFlag {
+    pub const ALL: [FeatureFlag; 4] = [
+        FeatureFlag::NewAllocationModel,
+        FeatureFlag::Sync,
+        FeatureFlag::RestServer,
+        FeatureFlag::PerformanceMetrics,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeatureFlag::NewAllocationModel => "new_allocation_model",
+            FeatureFlag::Sync => "sync",
+            FeatureFlag::RestServer => "rest_server",
+            FeatureFlag::PerformanceMetrics => "performance_metrics",
+        }
+    }
+
+    /// Every flag is off until explicitly turned on
+    pub fn default_enabled(&self) -> bool {
+        false
+    }
+
+    /// `app_settings` key holding the persisted value
+    pub fn setting_key(&self) -> String {
+        format!("feature_flag.{}", self.as_str())
+    }
+
+    pub fn env_var(&self) -> String {
+        format!("{}{}", ENV_OVERRIDE_PREFIX, self.as_str().to_uppercase())
+    }
+}
+
+impl FromStr for FeatureFlag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FeatureFlag::ALL
+            .into_iter()
+            .find(|flag| flag.as_str() == s)
+            .ok_or_else(|| {
+                Error::Validation(ValidationError::InvalidInput(format!(
+                    "Unknown feature flag: {}",
+                    s
+                )))
+            })
+    }
+}
+
+/// Where the effective value of a flag came from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FeatureFlagSource {
+    Default,
+    Setting,
+    Env,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureFlagState {
+    pub flag: FeatureFlag,
+    pub enabled: bool,
+    pub source: FeatureFlagSource,
+}
+
+/// Parses the usual truthy/falsy spellings; anything else is ignored
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
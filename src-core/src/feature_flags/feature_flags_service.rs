@@ -0,0 +1,179 @@
+use async_trait::async_trait;
+use log::warn;
+use std::sync::Arc;
+
+use super::feature_flags_model::{parse_bool, FeatureFlag, FeatureFlagSource, FeatureFlagState};
+use crate::errors::{DatabaseError, Error, Result};
+use crate::settings::SettingsRepositoryTrait;
+use crate::telemetry::performance_metrics;
+
+type EnvLookup = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+#[async_trait]
+pub trait FeatureFlagServiceTrait: Send + Sync {
+    /// Effective value; storage errors fall back to the flag default
+    fn is_enabled(&self, flag: FeatureFlag) -> bool;
+    fn get_flag(&self, flag: FeatureFlag) -> Result<FeatureFlagState>;
+    fn list_flags(&self) -> Result<Vec<FeatureFlagState>>;
+    /// Persists the flag. An environment override still wins over the stored value.
+    async fn set_flag(&self, flag: FeatureFlag, enabled: bool) -> Result<FeatureFlagState>;
+}
+
+/// Resolves flags as: environment override, then `app_settings`, then the default.
+pub struct FeatureFlagService {
+    settings_repository: Arc<dyn SettingsRepositoryTrait>,
+    env: Box<EnvLookup>,
+}
+
+impl FeatureFlagService {
+    pub fn new(settings_repository: Arc<dyn SettingsRepositoryTrait>) -> Self {
+        Self::with_env(settings_repository, |name| std::env::var(name).ok())
+    }
+
+    pub fn with_env(
+        settings_repository: Arc<dyn SettingsRepositoryTrait>,
+        env: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            settings_repository,
+            env: Box::new(env),
+        }
+    }
+
+    fn env_override(&self, flag: FeatureFlag) -> Option<bool> {
+        let name = flag.env_var();
+        let value = (self.env)(&name)?;
+        let parsed = parse_bool(&value);
+        if parsed.is_none() {
+            warn!("Ignoring {}={}: expected true or false", name, value);
+        }
+        parsed
+    }
+
+    fn stored_value(&self, flag: FeatureFlag) -> Result<Option<bool>> {
+        match self.settings_repository.get_setting(&flag.setting_key()) {
+            Ok(value) => Ok(parse_bool(&value)),
+            Err(Error::Database(DatabaseError::QueryFailed(diesel::result::Error::NotFound))) => {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+impl FeatureFlagServiceTrait for FeatureFlagService {
+    fn is_enabled(&self, flag: FeatureFlag) -> bool {
+        match self.get_flag(flag) {
+            Ok(state) => state.enabled,
+            Err(e) => {
+                warn!("Failed to read feature flag {}: {}", flag.as_str(), e);
+                flag.default_enabled()
+            }
+        }
+    }
+
+    fn get_flag(&self, flag: FeatureFlag) -> Result<FeatureFlagState> {
+        let (enabled, source) = if let Some(enabled) = self.env_override(flag) {
+            (enabled, FeatureFlagSource::Env)
+        } else if let Some(enabled) = self.stored_value(flag)? {
+            (enabled, FeatureFlagSource::Setting)
+        } else {
+            (flag.default_enabled(), FeatureFlagSource::Default)
+        };
+        Ok(FeatureFlagState {
+            flag,
+            enabled,
+            source,
+        })
+    }
+
+    fn list_flags(&self) -> Result<Vec<FeatureFlagState>> {
+        FeatureFlag::ALL
+            .into_iter()
+            .map(|flag| self.get_flag(flag))
+            .collect()
+    }
+
+    async fn set_flag(&self, flag: FeatureFlag, enabled: bool) -> Result<FeatureFlagState> {
+        self.settings_repository
+            .update_setting(&flag.setting_key(), &enabled.to_string())
+            .await?;
+        let state = self.get_flag(flag)?;
+        if flag == FeatureFlag::PerformanceMetrics {
+            performance_metrics().set_enabled(state.enabled);
+        }
+        Ok(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{Settings, SettingsUpdate};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemorySettingsRepository {
+        values: Mutex<HashMap<String, String>>,
+    }
+
+    #[async_trait]
+    impl SettingsRepositoryTrait for InMemorySettingsRepository {
+        fn get_settings(&self) -> Result<Settings> {
+            Ok(Settings::default())
+        }
+
+        async fn update_settings(&self, _new_settings: &SettingsUpdate) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_setting(&self, key: &str) -> Result<String> {
+            self.values
+                .lock()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .ok_or_else(|| Error::from(diesel::result::Error::NotFound))
+        }
+
+        async fn update_setting(&self, key: &str, value: &str) -> Result<()> {
+            self.values
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn get_distinct_currencies_excluding_base(&self, _base_currency: &str) -> Result<Vec<String>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_setting_then_env_override() {
+        let repo = Arc::new(InMemorySettingsRepository::default());
+        let service = FeatureFlagService::with_env(repo.clone(), |_| None);
+        assert!(!service.is_enabled(FeatureFlag::Sync));
+
+        let state = service.set_flag(FeatureFlag::Sync, true).await.unwrap();
+        assert_eq!(state.source, FeatureFlagSource::Setting);
+        assert!(service.is_enabled(FeatureFlag::Sync));
+
+        let overridden = FeatureFlagService::with_env(repo, |name| {
+            (name == "WEALTHVN_FEATURE_SYNC").then(|| "off".to_string())
+        });
+        let state = overridden.get_flag(FeatureFlag::Sync).unwrap();
+        assert!(!state.enabled);
+        assert_eq!(state.source, FeatureFlagSource::Env);
+    }
+
+    #[test]
+    fn test_flag_names_round_trip() {
+        for flag in FeatureFlag::ALL {
+            assert_eq!(flag.as_str().parse::<FeatureFlag>().unwrap(), flag);
+        }
+        assert!("teleport".parse::<FeatureFlag>().is_err());
+    }
+}
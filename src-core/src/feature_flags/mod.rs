@@ -0,0 +1,5 @@
+mod feature_flags_model;
+mod feature_flags_service;
+
+pub use feature_flags_model::{FeatureFlag, FeatureFlagSource, FeatureFlagState};
+pub use feature_flags_service::{FeatureFlagService, FeatureFlagServiceTrait};
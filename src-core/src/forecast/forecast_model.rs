@@ -0,0 +1,383 @@
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Planned cash flow dates are written as `YYYY-MM-DD`
+pub const FORECAST_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Shortest and longest forecast horizons, in months
+pub const MIN_FORECAST_MONTHS: u32 = 6;
+pub const MAX_FORECAST_MONTHS: u32 = 24;
+
+pub fn parse_forecast_date(value: &str) -> Result<NaiveDate> {
+    value
+        .get(..10)
+        .and_then(|day| NaiveDate::parse_from_str(day, FORECAST_DATE_FORMAT).ok())
+        .ok_or_else(|| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid date '{}', expected YYYY-MM-DD",
+                value
+            )))
+        })
+}
+
+/// What a planned cash flow represents; the kind decides whether it adds or removes cash
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CashFlowKind {
+    Income,
+    /// Money moved out of cash towards an investment or goal
+    Contribution,
+    Bill,
+    /// Principal and interest paid back when a term deposit matures
+    TermDepositMaturity,
+    /// Principal and interest due on a loan
+    LoanPayment,
+}
+
+impl CashFlowKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CashFlowKind::Income => "INCOME",
+            CashFlowKind::Contribution => "CONTRIBUTION",
+            CashFlowKind::Bill => "BILL",
+            CashFlowKind::TermDepositMaturity => "TERM_DEPOSIT_MATURITY",
+            CashFlowKind::LoanPayment => "LOAN_PAYMENT",
+        }
+    }
+
+    pub fn is_inflow(&self) -> bool {
+        matches!(
+            self,
+            CashFlowKind::Income | CashFlowKind::TermDepositMaturity
+        )
+    }
+}
+
+impl FromStr for CashFlowKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "INCOME" => Ok(CashFlowKind::Income),
+            "CONTRIBUTION" => Ok(CashFlowKind::Contribution),
+            "BILL" => Ok(CashFlowKind::Bill),
+            "TERM_DEPOSIT_MATURITY" => Ok(CashFlowKind::TermDepositMaturity),
+            "LOAN_PAYMENT" => Ok(CashFlowKind::LoanPayment),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown cash flow kind: {}",
+                other
+            )))),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CashFlowFrequency {
+    Once,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl CashFlowFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CashFlowFrequency::Once => "ONCE",
+            CashFlowFrequency::Monthly => "MONTHLY",
+            CashFlowFrequency::Quarterly => "QUARTERLY",
+            CashFlowFrequency::Yearly => "YEARLY",
+        }
+    }
+
+    /// Months between occurrences, or `None` for a one-off flow
+    pub fn step_months(&self) -> Option<u32> {
+        match self {
+            CashFlowFrequency::Once => None,
+            CashFlowFrequency::Monthly => Some(1),
+            CashFlowFrequency::Quarterly => Some(3),
+            CashFlowFrequency::Yearly => Some(12),
+        }
+    }
+}
+
+impl FromStr for CashFlowFrequency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ONCE" => Ok(CashFlowFrequency::Once),
+            "MONTHLY" => Ok(CashFlowFrequency::Monthly),
+            "QUARTERLY" => Ok(CashFlowFrequency::Quarterly),
+            "YEARLY" => Ok(CashFlowFrequency::Yearly),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown cash flow frequency: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Dates on which a flow anchored at `start` occurs within `from..=to`. Occurrences keep the
+/// start day of month, falling back to the month's last day when it is shorter.
+pub fn occurrences(
+    start: NaiveDate,
+    end: Option<NaiveDate>,
+    frequency: CashFlowFrequency,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<NaiveDate> {
+    let last = end.map_or(to, |end| end.min(to));
+    let Some(step) = frequency.step_months() else {
+        return if start >= from && start <= last {
+            vec![start]
+        } else {
+            Vec::new()
+        };
+    };
+
+    let mut dates = Vec::new();
+    let mut n = 0;
+    while let Some(date) = start.checked_add_months(Months::new(step * n)) {
+        if date > last {
+            break;
+        }
+        if date >= from {
+            dates.push(date);
+        }
+        n += 1;
+    }
+    dates
+}
+
+/// Database row for `planned_cash_flows`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::planned_cash_flows)]
+pub struct PlannedCashFlowDB {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    pub account_id: Option<String>,
+    pub amount: String,
+    pub currency: String,
+    pub frequency: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub bank_code: Option<String>,
+    pub term_months: Option<i32>,
+    pub interest_rate: Option<String>,
+}
+
+/// A known future income, bill, contribution or maturity
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedCashFlow {
+    pub id: String,
+    pub name: String,
+    pub kind: CashFlowKind,
+    /// Account the cash moves through; flows without one only count in whole-portfolio forecasts
+    pub account_id: Option<String>,
+    /// Always positive; the kind gives the direction
+    pub amount: Decimal,
+    pub currency: String,
+    pub frequency: CashFlowFrequency,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    #[serde(default, flatten)]
+    pub term_deposit: TermDepositTerms,
+}
+
+impl TryFrom<PlannedCashFlowDB> for PlannedCashFlow {
+    type Error = Error;
+
+    fn try_from(db: PlannedCashFlowDB) -> Result<Self> {
+        Ok(PlannedCashFlow {
+            id: db.id,
+            name: db.name,
+            kind: db.kind.parse()?,
+            account_id: db.account_id,
+            amount: Decimal::from_str(&db.amount)?,
+            currency: db.currency,
+            frequency: db.frequency.parse()?,
+            start_date: parse_forecast_date(&db.start_date)?,
+            end_date: db
+                .end_date
+                .as_deref()
+                .map(parse_forecast_date)
+                .transpose()?,
+            is_active: db.is_active,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+            term_deposit: TermDepositTerms {
+                bank_code: db.bank_code,
+                term_months: db.term_months,
+                interest_rate: db
+                    .interest_rate
+                    .as_deref()
+                    .map(Decimal::from_str)
+                    .transpose()?,
+            },
+        })
+    }
+}
+
+/// Input for creating or updating a planned cash flow
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPlannedCashFlow {
+    pub id: Option<String>,
+    pub name: String,
+    pub kind: CashFlowKind,
+    pub account_id: Option<String>,
+    pub amount: Decimal,
+    pub currency: String,
+    pub frequency: CashFlowFrequency,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    #[serde(default, flatten)]
+    pub term_deposit: TermDepositTerms,
+}
+
+/// Bank, tenor and annual rate (in percent) of the term deposit a maturity pays out, all
+/// optional. Maturing deposits are compared against the deposit rate catalog with them.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TermDepositTerms {
+    pub bank_code: Option<String>,
+    pub term_months: Option<i32>,
+    pub interest_rate: Option<Decimal>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl NewPlannedCashFlow {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.currency.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "currency".to_string(),
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Cash flow amount must be positive".to_string(),
+            )));
+        }
+        if self.end_date.is_some_and(|end| end < self.start_date) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Cash flow end date is before its start date".to_string(),
+            )));
+        }
+        if self
+            .term_deposit
+            .term_months
+            .is_some_and(|months| months <= 0)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Deposit term must be at least a month".to_string(),
+            )));
+        }
+        if self
+            .term_deposit
+            .interest_rate
+            .is_some_and(|rate| rate < Decimal::ZERO)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Deposit interest rate cannot be negative".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Options for a cash flow forecast
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CashFlowForecastRequest {
+    /// Horizon in months, between 6 and 24
+    pub months: u32,
+    /// Balance, in base currency, that the forecast warns about dropping below
+    #[serde(default)]
+    pub minimum_balance: Decimal,
+    /// Limit the forecast to these accounts; all active accounts when omitted
+    pub account_ids: Option<Vec<String>>,
+    /// Include each open goal's monthly investment as a planned contribution
+    #[serde(default = "default_true")]
+    pub include_goal_contributions: bool,
+    /// Include the scheduled payments of loans on the selected liability accounts
+    #[serde(default = "default_true")]
+    pub include_loan_payments: bool,
+    /// Percentage points added to loans' floating rates, to see how a rise squeezes cash
+    #[serde(default)]
+    pub loan_rate_increase: Decimal,
+}
+
+/// Where a forecast event comes from
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ForecastEventSource {
+    PlannedCashFlow,
+    Goal,
+    IncomeSource,
+    Loan,
+}
+
+/// One projected cash movement, signed and converted to base currency
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastEvent {
+    pub date: NaiveDate,
+    pub name: String,
+    pub kind: CashFlowKind,
+    pub source: ForecastEventSource,
+    pub source_id: String,
+    pub amount: Decimal,
+}
+
+/// Projected balance at the end of a day that has events, or at a month end
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastPoint {
+    pub date: NaiveDate,
+    pub inflow: Decimal,
+    pub outflow: Decimal,
+    pub balance: Decimal,
+    pub below_minimum: bool,
+    pub events: Vec<ForecastEvent>,
+}
+
+/// Projected balances over the horizon, in base currency
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CashFlowForecast {
+    pub base_currency: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub starting_balance: Decimal,
+    pub minimum_balance: Decimal,
+    pub points: Vec<ForecastPoint>,
+    pub lowest_balance: Decimal,
+    pub lowest_balance_date: NaiveDate,
+    /// First date the balance drops below the minimum, if it does
+    pub first_shortfall_date: Option<NaiveDate>,
+    pub ending_balance: Decimal,
+}
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::forecast_model::{
+    NewPlannedCashFlow, PlannedCashFlow, PlannedCashFlowDB, FORECAST_DATE_FORMAT,
+};
+use super::forecast_traits::ForecastRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::planned_cash_flows;
+
+pub struct ForecastRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl ForecastRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        ForecastRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl ForecastRepositoryTrait for ForecastRepository {
+    fn get_planned_cash_flows(&self) -> Result<Vec<PlannedCashFlow>> {
+        let mut conn = get_connection(&self.pool)?;
+        planned_cash_flows::table
+            .order((
+                planned_cash_flows::start_date.asc(),
+                planned_cash_flows::name.asc(),
+            ))
+            .load::<PlannedCashFlowDB>(&mut conn)?
+            .into_iter()
+            .map(PlannedCashFlow::try_from)
+            .collect()
+    }
+
+    async fn insert_planned_cash_flow(&self, flow: NewPlannedCashFlow) -> Result<PlannedCashFlow> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<PlannedCashFlow> {
+                    let now = chrono::Utc::now().naive_utc();
+                    let record = PlannedCashFlowDB {
+                        id: flow.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        name: flow.name.trim().to_string(),
+                        kind: flow.kind.as_str().to_string(),
+                        account_id: flow.account_id,
+                        amount: flow.amount.to_string(),
+                        currency: flow.currency,
+                        frequency: flow.frequency.as_str().to_string(),
+                        start_date: flow.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                        end_date: flow
+                            .end_date
+                            .map(|d| d.format(FORECAST_DATE_FORMAT).to_string()),
+                        is_active: flow.is_active,
+                        created_at: now,
+                        updated_at: now,
+                        bank_code: flow.term_deposit.bank_code,
+                        term_months: flow.term_deposit.term_months,
+                        interest_rate: flow.term_deposit.interest_rate.map(|r| r.to_string()),
+                    };
+
+                    diesel::insert_into(planned_cash_flows::table)
+                        .values(&record)
+                        .get_result::<PlannedCashFlowDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn update_planned_cash_flow(
+        &self,
+        id: &str,
+        flow: NewPlannedCashFlow,
+    ) -> Result<PlannedCashFlow> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<PlannedCashFlow> {
+                    diesel::update(planned_cash_flows::table.find(id_owned))
+                        .set((
+                            planned_cash_flows::name.eq(flow.name.trim()),
+                            planned_cash_flows::kind.eq(flow.kind.as_str()),
+                            planned_cash_flows::account_id.eq(flow.account_id),
+                            planned_cash_flows::amount.eq(flow.amount.to_string()),
+                            planned_cash_flows::currency.eq(flow.currency),
+                            planned_cash_flows::frequency.eq(flow.frequency.as_str()),
+                            planned_cash_flows::start_date
+                                .eq(flow.start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                            planned_cash_flows::end_date.eq(flow
+                                .end_date
+                                .map(|d| d.format(FORECAST_DATE_FORMAT).to_string())),
+                            planned_cash_flows::is_active.eq(flow.is_active),
+                            planned_cash_flows::bank_code.eq(flow.term_deposit.bank_code),
+                            planned_cash_flows::term_months.eq(flow.term_deposit.term_months),
+                            planned_cash_flows::interest_rate
+                                .eq(flow.term_deposit.interest_rate.map(|r| r.to_string())),
+                            planned_cash_flows::updated_at.eq(chrono::Utc::now().naive_utc()),
+                        ))
+                        .get_result::<PlannedCashFlowDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_planned_cash_flow(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(planned_cash_flows::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
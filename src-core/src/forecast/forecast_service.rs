@@ -0,0 +1,463 @@
+use async_trait::async_trait;
+use chrono::{Datelike, Days, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use super::forecast_model::{
+    occurrences, parse_forecast_date, CashFlowForecast, CashFlowForecastRequest, CashFlowFrequency,
+    CashFlowKind, ForecastEvent, ForecastEventSource, ForecastPoint, NewPlannedCashFlow,
+    PlannedCashFlow, MAX_FORECAST_MONTHS, MIN_FORECAST_MONTHS,
+};
+use super::forecast_traits::{ForecastRepositoryTrait, ForecastServiceTrait};
+use crate::accounts::AccountRepositoryTrait;
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::{convert_or_keep, FxServiceTrait};
+use crate::goals::GoalRepositoryTrait;
+use crate::income_sources::IncomeSourceRepositoryTrait;
+use crate::loans::{amortize_stressed, LoanRepositoryTrait};
+use crate::portfolio::snapshot::SnapshotRepositoryTrait;
+
+pub struct ForecastService {
+    repository: Arc<dyn ForecastRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    snapshot_repository: Arc<dyn SnapshotRepositoryTrait>,
+    goal_repository: Arc<dyn GoalRepositoryTrait>,
+    income_source_repository: Arc<dyn IncomeSourceRepositoryTrait>,
+    loan_repository: Arc<dyn LoanRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl ForecastService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: Arc<dyn ForecastRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        snapshot_repository: Arc<dyn SnapshotRepositoryTrait>,
+        goal_repository: Arc<dyn GoalRepositoryTrait>,
+        income_source_repository: Arc<dyn IncomeSourceRepositoryTrait>,
+        loan_repository: Arc<dyn LoanRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            snapshot_repository,
+            goal_repository,
+            income_source_repository,
+            loan_repository,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    fn validate_flow(&self, flow: &NewPlannedCashFlow) -> Result<()> {
+        flow.validate()?;
+        if let Some(account_id) = &flow.account_id {
+            self.account_repository.get_by_id(account_id)?;
+        }
+        Ok(())
+    }
+
+    /// Cash currently held across the accounts, from their latest snapshots
+    fn starting_balance(&self, account_ids: &[String], base_currency: &str) -> Result<Decimal> {
+        let snapshots = self
+            .snapshot_repository
+            .get_all_latest_snapshots(account_ids)?;
+        Ok(snapshots
+            .values()
+            .flat_map(|snapshot| snapshot.cash_balances.iter())
+            .map(|(currency, amount)| {
+                convert_or_keep(
+                    self.fx_service.as_ref(),
+                    *amount,
+                    currency,
+                    base_currency,
+                    None,
+                )
+            })
+            .sum())
+    }
+
+    /// Monthly investments of open goals, paid on the goal's start day until its due date
+    async fn goal_events(&self, from: NaiveDate, to: NaiveDate) -> Result<Vec<ForecastEvent>> {
+        let mut events = Vec::new();
+        for goal in self.goal_repository.load_goals().await? {
+            let monthly = goal
+                .monthly_investment
+                .and_then(Decimal::from_f64_retain)
+                .unwrap_or_default()
+                .round_dp(2);
+            if goal.is_achieved || monthly <= Decimal::ZERO {
+                continue;
+            }
+            let anchor = goal
+                .start_date
+                .as_deref()
+                .and_then(|d| parse_forecast_date(d).ok())
+                .unwrap_or(from);
+            let due = goal
+                .due_date
+                .as_deref()
+                .and_then(|d| parse_forecast_date(d).ok());
+            for date in occurrences(anchor, due, CashFlowFrequency::Monthly, from, to) {
+                events.push(ForecastEvent {
+                    date,
+                    name: goal.title.clone(),
+                    kind: CashFlowKind::Contribution,
+                    source: ForecastEventSource::Goal,
+                    source_id: goal.id.clone(),
+                    amount: -monthly,
+                });
+            }
+        }
+        Ok(events)
+    }
+
+    /// Scheduled payments of the loans on the selected accounts, with their floating rates
+    /// raised by `rate_increase` points from `today` on
+    fn loan_events(
+        &self,
+        selected: &HashSet<&str>,
+        today: NaiveDate,
+        to: NaiveDate,
+        rate_increase: Decimal,
+        base_currency: &str,
+    ) -> Result<Vec<ForecastEvent>> {
+        let mut events = Vec::new();
+        for loan in self.loan_repository.get_loans()? {
+            if !selected.contains(loan.account_id.as_str()) {
+                continue;
+            }
+            let account = self.account_repository.get_by_id(&loan.account_id)?;
+            let prepayments = self.loan_repository.get_prepayments(Some(&loan.id))?;
+            let resets = self.loan_repository.get_rate_resets(Some(&loan.id))?;
+            for row in amortize_stressed(&loan, &prepayments, &resets, rate_increase, today) {
+                if row.payment_date <= today || row.payment_date > to {
+                    continue;
+                }
+                events.push(ForecastEvent {
+                    date: row.payment_date,
+                    name: account.name.clone(),
+                    kind: CashFlowKind::LoanPayment,
+                    source: ForecastEventSource::Loan,
+                    source_id: loan.id.clone(),
+                    amount: -convert_or_keep(
+                        self.fx_service.as_ref(),
+                        row.payment,
+                        &account.currency,
+                        base_currency,
+                        None,
+                    ),
+                });
+            }
+        }
+        Ok(events)
+    }
+}
+
+/// Applies events day by day to `starting_balance`. A point is emitted for every day with
+/// events and for every month end, so the series stays readable across quiet months.
+pub(crate) fn project_forecast(
+    base_currency: &str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    starting_balance: Decimal,
+    minimum_balance: Decimal,
+    events: Vec<ForecastEvent>,
+) -> CashFlowForecast {
+    let mut days: BTreeMap<NaiveDate, Vec<ForecastEvent>> = BTreeMap::new();
+    let mut month_start = start_date.with_day0(0).unwrap_or(start_date);
+    while let Some(next) = month_start.checked_add_months(Months::new(1)) {
+        let month_end = next - Days::new(1);
+        if month_end > end_date {
+            break;
+        }
+        if month_end > start_date {
+            days.entry(month_end).or_default();
+        }
+        month_start = next;
+    }
+    days.entry(end_date).or_default();
+    for event in events {
+        if event.date > start_date && event.date <= end_date {
+            days.entry(event.date).or_default().push(event);
+        }
+    }
+
+    let mut balance = starting_balance;
+    let mut lowest_balance = starting_balance;
+    let mut lowest_balance_date = start_date;
+    let mut first_shortfall_date = (starting_balance < minimum_balance).then_some(start_date);
+    let mut points = Vec::with_capacity(days.len());
+    for (date, events) in days {
+        let inflow: Decimal = events
+            .iter()
+            .map(|e| e.amount)
+            .filter(|a| a.is_sign_positive())
+            .sum();
+        let outflow: Decimal = events
+            .iter()
+            .map(|e| e.amount)
+            .filter(|a| a.is_sign_negative())
+            .map(|a| -a)
+            .sum();
+        balance += inflow - outflow;
+
+        if balance < lowest_balance {
+            lowest_balance = balance;
+            lowest_balance_date = date;
+        }
+        let below_minimum = balance < minimum_balance;
+        if below_minimum && first_shortfall_date.is_none() {
+            first_shortfall_date = Some(date);
+        }
+        points.push(ForecastPoint {
+            date,
+            inflow,
+            outflow,
+            balance,
+            below_minimum,
+            events,
+        });
+    }
+
+    CashFlowForecast {
+        base_currency: base_currency.to_string(),
+        start_date,
+        end_date,
+        starting_balance,
+        minimum_balance,
+        points,
+        lowest_balance,
+        lowest_balance_date,
+        first_shortfall_date,
+        ending_balance: balance,
+    }
+}
+
+#[async_trait]
+impl ForecastServiceTrait for ForecastService {
+    fn get_planned_cash_flows(&self) -> Result<Vec<PlannedCashFlow>> {
+        self.repository.get_planned_cash_flows()
+    }
+
+    async fn create_planned_cash_flow(&self, flow: NewPlannedCashFlow) -> Result<PlannedCashFlow> {
+        self.validate_flow(&flow)?;
+        self.repository.insert_planned_cash_flow(flow).await
+    }
+
+    async fn update_planned_cash_flow(
+        &self,
+        id: &str,
+        flow: NewPlannedCashFlow,
+    ) -> Result<PlannedCashFlow> {
+        self.validate_flow(&flow)?;
+        self.repository.update_planned_cash_flow(id, flow).await
+    }
+
+    async fn delete_planned_cash_flow(&self, id: &str) -> Result<usize> {
+        self.repository.delete_planned_cash_flow(id).await
+    }
+
+    async fn get_cash_flow_forecast(
+        &self,
+        request: CashFlowForecastRequest,
+    ) -> Result<CashFlowForecast> {
+        if !(MIN_FORECAST_MONTHS..=MAX_FORECAST_MONTHS).contains(&request.months) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Forecast horizon must be between {} and {} months",
+                MIN_FORECAST_MONTHS, MAX_FORECAST_MONTHS
+            ))));
+        }
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let start_date = Utc::now().date_naive();
+        let end_date = start_date
+            .checked_add_months(Months::new(request.months))
+            .unwrap_or(NaiveDate::MAX);
+
+        let accounts = self
+            .account_repository
+            .list(Some(true), request.account_ids.as_deref())?;
+        let account_ids: Vec<String> = accounts.into_iter().map(|a| a.id).collect();
+        let starting_balance = self.starting_balance(&account_ids, &base_currency)?;
+
+        // Flows and goals that aren't tied to an account only apply to the whole portfolio
+        let whole_portfolio = request.account_ids.is_none();
+        let selected: HashSet<&str> = account_ids.iter().map(String::as_str).collect();
+        let from = start_date + Days::new(1);
+        let mut events = Vec::new();
+        for flow in self.repository.get_planned_cash_flows()? {
+            let included = match &flow.account_id {
+                Some(account_id) => selected.contains(account_id.as_str()),
+                None => whole_portfolio,
+            };
+            if !flow.is_active || !included {
+                continue;
+            }
+            let amount = convert_or_keep(
+                self.fx_service.as_ref(),
+                flow.amount,
+                &flow.currency,
+                &base_currency,
+                None,
+            );
+            let signed = if flow.kind.is_inflow() {
+                amount
+            } else {
+                -amount
+            };
+            for date in occurrences(
+                flow.start_date,
+                flow.end_date,
+                flow.frequency,
+                from,
+                end_date,
+            ) {
+                events.push(ForecastEvent {
+                    date,
+                    name: flow.name.clone(),
+                    kind: flow.kind,
+                    source: ForecastEventSource::PlannedCashFlow,
+                    source_id: flow.id.clone(),
+                    amount: signed,
+                });
+            }
+        }
+        for source in self.income_source_repository.get_income_sources()? {
+            let included = match &source.account_id {
+                Some(account_id) => selected.contains(account_id.as_str()),
+                None => whole_portfolio,
+            };
+            if !source.is_active || !included {
+                continue;
+            }
+            let amount = convert_or_keep(
+                self.fx_service.as_ref(),
+                source.amount,
+                &source.currency,
+                &base_currency,
+                None,
+            );
+            for date in source.expected_dates(from, end_date) {
+                events.push(ForecastEvent {
+                    date,
+                    name: source.name.clone(),
+                    kind: CashFlowKind::Income,
+                    source: ForecastEventSource::IncomeSource,
+                    source_id: source.id.clone(),
+                    amount,
+                });
+            }
+        }
+        if whole_portfolio && request.include_goal_contributions {
+            events.extend(self.goal_events(from, end_date).await?);
+        }
+        if request.include_loan_payments {
+            events.extend(self.loan_events(
+                &selected,
+                start_date,
+                end_date,
+                request.loan_rate_increase,
+                &base_currency,
+            )?);
+        }
+
+        Ok(project_forecast(
+            &base_currency,
+            start_date,
+            end_date,
+            starting_balance,
+            request.minimum_balance,
+            events,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn event(day: &str, kind: CashFlowKind, amount: Decimal) -> ForecastEvent {
+        ForecastEvent {
+            date: date(day),
+            name: kind.as_str().to_string(),
+            kind,
+            source: ForecastEventSource::PlannedCashFlow,
+            source_id: "flow".to_string(),
+            amount,
+        }
+    }
+
+    #[test]
+    fn recurring_occurrences_keep_day_and_respect_end() {
+        let monthly = occurrences(
+            date("2026-01-31"),
+            Some(date("2026-04-30")),
+            CashFlowFrequency::Monthly,
+            date("2026-02-01"),
+            date("2026-12-31"),
+        );
+        assert_eq!(
+            monthly,
+            vec![date("2026-02-28"), date("2026-03-31"), date("2026-04-30")]
+        );
+
+        let once = occurrences(
+            date("2026-05-15"),
+            None,
+            CashFlowFrequency::Once,
+            date("2026-06-01"),
+            date("2026-12-31"),
+        );
+        assert!(once.is_empty());
+    }
+
+    #[test]
+    fn forecast_tracks_lowest_balance_and_first_shortfall() {
+        let forecast = project_forecast(
+            "VND",
+            date("2026-01-10"),
+            date("2026-03-10"),
+            dec!(10_000_000),
+            dec!(5_000_000),
+            vec![
+                event("2026-01-25", CashFlowKind::Income, dec!(20_000_000)),
+                event("2026-02-05", CashFlowKind::Bill, dec!(-28_000_000)),
+                event(
+                    "2026-02-20",
+                    CashFlowKind::TermDepositMaturity,
+                    dec!(50_000_000),
+                ),
+                // Outside the horizon
+                event("2026-04-01", CashFlowKind::Bill, dec!(-1_000_000)),
+            ],
+        );
+
+        let dates: Vec<NaiveDate> = forecast.points.iter().map(|p| p.date).collect();
+        assert_eq!(
+            dates,
+            vec![
+                date("2026-01-25"),
+                date("2026-01-31"),
+                date("2026-02-05"),
+                date("2026-02-20"),
+                date("2026-02-28"),
+                date("2026-03-10"),
+            ]
+        );
+        assert_eq!(forecast.lowest_balance, dec!(2_000_000));
+        assert_eq!(forecast.lowest_balance_date, date("2026-02-05"));
+        assert_eq!(forecast.first_shortfall_date, Some(date("2026-02-05")));
+        assert!(forecast.points[2].below_minimum);
+        assert!(!forecast.points[3].below_minimum);
+        assert_eq!(forecast.ending_balance, dec!(52_000_000));
+    }
+}
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+
+use super::forecast_model::{
+    CashFlowForecast, CashFlowForecastRequest, NewPlannedCashFlow, PlannedCashFlow,
+};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait ForecastRepositoryTrait: Send + Sync {
+    fn get_planned_cash_flows(&self) -> Result<Vec<PlannedCashFlow>>;
+    async fn insert_planned_cash_flow(&self, flow: NewPlannedCashFlow) -> Result<PlannedCashFlow>;
+    async fn update_planned_cash_flow(
+        &self,
+        id: &str,
+        flow: NewPlannedCashFlow,
+    ) -> Result<PlannedCashFlow>;
+    async fn delete_planned_cash_flow(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait ForecastServiceTrait: Send + Sync {
+    fn get_planned_cash_flows(&self) -> Result<Vec<PlannedCashFlow>>;
+    async fn create_planned_cash_flow(&self, flow: NewPlannedCashFlow) -> Result<PlannedCashFlow>;
+    async fn update_planned_cash_flow(
+        &self,
+        id: &str,
+        flow: NewPlannedCashFlow,
+    ) -> Result<PlannedCashFlow>;
+    async fn delete_planned_cash_flow(&self, id: &str) -> Result<usize>;
+    /// Projects cash balances from today over the requested horizon
+    async fn get_cash_flow_forecast(
+        &self,
+        request: CashFlowForecastRequest,
+    ) -> Result<CashFlowForecast>;
+}
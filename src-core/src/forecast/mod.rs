@@ -0,0 +1,14 @@
+mod forecast_model;
+mod forecast_repository;
+mod forecast_service;
+mod forecast_traits;
+
+pub use forecast_model::{
+    occurrences, parse_forecast_date, CashFlowForecast, CashFlowForecastRequest,
+    CashFlowFrequency, CashFlowKind, ForecastEvent, ForecastEventSource, ForecastPoint,
+    NewPlannedCashFlow, PlannedCashFlow, TermDepositTerms, FORECAST_DATE_FORMAT,
+    MAX_FORECAST_MONTHS, MIN_FORECAST_MONTHS,
+};
+pub use forecast_repository::ForecastRepository;
+pub use forecast_service::ForecastService;
+pub use forecast_traits::{ForecastRepositoryTrait, ForecastServiceTrait};
@@ -389,3 +389,35 @@ impl FxServiceTrait for FxService {
         Ok(())
     }
 }
+
+/// `amount` in `to`, at the rate on `date` or at the latest rate without one. Plans and
+/// projections keep the unconverted amount when no rate is known rather than failing.
+pub fn convert_or_keep(
+    fx_service: &dyn FxServiceTrait,
+    amount: Decimal,
+    from: &str,
+    to: &str,
+    date: Option<NaiveDate>,
+) -> Decimal {
+    if from == to || amount.is_zero() {
+        return amount;
+    }
+    let converted = match date {
+        Some(date) => fx_service.convert_currency_for_date(amount, from, to, date),
+        None => fx_service.convert_currency(amount, from, to),
+    };
+    converted.unwrap_or_else(|e| {
+        match date {
+            Some(date) => log::warn!(
+                "Failed to convert {} {} to {} on {}: {}",
+                amount,
+                from,
+                to,
+                date,
+                e
+            ),
+            None => log::warn!("Failed to convert {} {} to {}: {}", amount, from, to, e),
+        }
+        amount
+    })
+}
@@ -13,5 +13,5 @@ pub use currency_converter::CurrencyConverter;
 pub use fx_errors::FxError;
 pub use fx_model::{ExchangeRate, NewExchangeRate};
 pub use fx_repository::FxRepository;
-pub use fx_service::FxService;
+pub use fx_service::{convert_or_keep, FxService};
 pub use fx_traits::{FxRepositoryTrait, FxServiceTrait};
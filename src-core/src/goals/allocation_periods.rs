@@ -0,0 +1,93 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::errors::Result;
+use crate::goals::goals_model::{AllocationVersion, GoalsAllocation};
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+/// Far-off bound for allocations and versions without an end date
+pub(crate) const OPEN_END_DATE: &str = "9999-12-31";
+
+/// The percentage an allocation holds on an account from `start` to `end` (inclusive)
+pub(crate) struct AllocationPeriod<'a> {
+    pub(crate) start: &'a str,
+    pub(crate) end: &'a str,
+    pub(crate) percent: f64,
+    /// Part of the set being written rather than already stored
+    pub(crate) is_new: bool,
+}
+
+/// Periods of each allocation by account: one per version, or the allocation's own dates and
+/// percentage when it has no versions
+pub(crate) fn allocation_periods<'a>(
+    allocations: &'a [GoalsAllocation],
+    versions: &'a [AllocationVersion],
+    is_new: bool,
+    periods: &mut BTreeMap<&'a str, Vec<AllocationPeriod<'a>>>,
+) {
+    let mut versions_by_allocation: HashMap<&str, Vec<&AllocationVersion>> = HashMap::new();
+    for version in versions {
+        versions_by_allocation
+            .entry(version.allocation_id.as_str())
+            .or_default()
+            .push(version);
+    }
+    for allocation in allocations {
+        let start = allocation
+            .start_date
+            .as_deref()
+            .or(allocation.allocation_date.as_deref())
+            .unwrap_or_default();
+        let end = allocation.end_date.as_deref().unwrap_or(OPEN_END_DATE);
+        let account_periods = periods.entry(allocation.account_id.as_str()).or_default();
+        match versions_by_allocation.get(allocation.id.as_str()) {
+            Some(versions) => account_periods.extend(versions.iter().map(|version| {
+                AllocationPeriod {
+                    start: version.version_start_date.as_str(),
+                    end: version.version_end_date.as_deref().unwrap_or(end),
+                    percent: version.allocation_percentage,
+                    is_new,
+                }
+            })),
+            None => account_periods.push(AllocationPeriod {
+                start,
+                end,
+                percent: allocation.allocation_percentage,
+                is_new,
+            }),
+        }
+    }
+}
+
+/// Checks that no account is over 100% on any day a new period covers. Periods that do not
+/// overlap in time never add up, and days only stored periods cover are left alone.
+pub(crate) fn check_new_periods(periods: &BTreeMap<&str, Vec<AllocationPeriod>>) -> Result<()> {
+    for (account_id, account_periods) in periods {
+        // The total only rises where a period starts, so those are the days to check
+        let mut check_dates: Vec<&str> = account_periods
+            .iter()
+            .map(|period| period.start)
+            .filter(|date| {
+                account_periods
+                    .iter()
+                    .any(|p| p.is_new && p.start <= *date && *date <= p.end)
+            })
+            .collect();
+        check_dates.sort_unstable();
+        check_dates.dedup();
+        for date in check_dates {
+            let total: f64 = account_periods
+                .iter()
+                .filter(|p| p.start <= date && date <= p.end)
+                .map(|p| p.percent)
+                .sum();
+            if total > 100.0 {
+                return Err(LocalizedMessage::new(MessageCode::GoalAllocationExceedsLimitOnDate)
+                    .with_param("percent", format!("{:.1}", total))
+                    .with_param("account", *account_id)
+                    .with_param("date", date)
+                    .into());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::errors::Result;
+use crate::goals::goal_progress_model::{
+    EmergencyFundProgress, NetWorthGoalProgress, NetWorthPoint, SinkingFundProgress,
+};
+use crate::goals::goals_model::{
+    GOAL_TYPE_EMERGENCY_FUND, GOAL_TYPE_NET_WORTH, GOAL_TYPE_SINKING_FUND,
+};
+use crate::goals::goals_traits::{
+    EmergencyFundServiceTrait, NetWorthGoalServiceTrait, SinkingFundServiceTrait,
+};
+
+/// Goals a summary lists when the caller does not ask for a number
+pub const DEFAULT_SUMMARY_GOALS: usize = 3;
+pub const MAX_SUMMARY_GOALS: usize = 10;
+
+/// Small, flat snapshot for widgets, menu bar apps and e-ink displays
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardSummary {
+    pub base_currency: String,
+    /// Day of the latest net worth; `None` before any account is valued
+    pub as_of: Option<NaiveDate>,
+    pub net_worth: Decimal,
+    /// Change since the day before `as_of`
+    pub daily_change: Decimal,
+    /// `daily_change` as a share of the previous net worth; `None` when that was zero
+    pub daily_change_pct: Option<Decimal>,
+    /// Goals still short of their target, closest to it first
+    pub goals: Vec<DashboardGoal>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardGoal {
+    pub goal_id: String,
+    pub title: String,
+    pub goal_type: String,
+    pub current_amount: Decimal,
+    pub target_amount: Decimal,
+    /// Share of the target reached, between 0 and 1
+    pub progress: Option<Decimal>,
+}
+
+/// Builds the summary from a net worth series covering at least the last two days
+pub fn build_dashboard_summary(
+    base_currency: &str,
+    series: &[NetWorthPoint],
+    emergency_funds: &[EmergencyFundProgress],
+    sinking_funds: &[SinkingFundProgress],
+    net_worth_goals: &[NetWorthGoalProgress],
+    goal_limit: usize,
+) -> DashboardSummary {
+    let latest = series.last();
+    let previous = latest.and_then(|latest| {
+        let day_before = latest.date - Duration::days(1);
+        series.iter().rev().find(|point| point.date <= day_before)
+    });
+    let net_worth = latest.map(|point| point.net_worth).unwrap_or_default();
+    let daily_change = previous
+        .map(|point| net_worth - point.net_worth)
+        .unwrap_or_default();
+    let daily_change_pct = previous
+        .filter(|point| !point.net_worth.is_zero())
+        .map(|point| (daily_change / point.net_worth.abs()).round_dp(4));
+
+    let goal =
+        |goal_id: &str, title: &str, goal_type: &str, current, target, progress| DashboardGoal {
+            goal_id: goal_id.to_string(),
+            title: title.to_string(),
+            goal_type: goal_type.to_string(),
+            current_amount: current,
+            target_amount: target,
+            progress,
+        };
+    let mut goals: Vec<DashboardGoal> = emergency_funds
+        .iter()
+        .map(|fund| {
+            goal(
+                &fund.goal_id,
+                &fund.goal_title,
+                GOAL_TYPE_EMERGENCY_FUND,
+                fund.current_value,
+                fund.target_amount,
+                fund.progress,
+            )
+        })
+        .chain(
+            sinking_funds
+                .iter()
+                .filter(|fund| !fund.is_funded)
+                .map(|fund| {
+                    goal(
+                        &fund.goal_id,
+                        &fund.goal_title,
+                        GOAL_TYPE_SINKING_FUND,
+                        fund.current_value,
+                        fund.target_amount,
+                        fund.progress,
+                    )
+                }),
+        )
+        .chain(net_worth_goals.iter().filter(|g| !g.is_reached).map(|g| {
+            goal(
+                &g.goal_id,
+                &g.goal_title,
+                GOAL_TYPE_NET_WORTH,
+                g.current.net_worth,
+                g.target_amount,
+                g.progress,
+            )
+        }))
+        .filter(|goal| goal.progress.is_none_or(|progress| progress < Decimal::ONE))
+        .collect();
+    // Goals without a measurable progress go last
+    goals.sort_by(|a, b| match (a.progress, b.progress) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+    goals.truncate(goal_limit.min(MAX_SUMMARY_GOALS));
+
+    DashboardSummary {
+        base_currency: base_currency.to_string(),
+        as_of: latest.map(|point| point.date),
+        net_worth,
+        daily_change,
+        daily_change_pct,
+        goals,
+    }
+}
+
+/// Loads what the summary needs; only the last two days of net worth are read
+pub async fn get_dashboard_summary(
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    emergency_fund_service: &dyn EmergencyFundServiceTrait,
+    sinking_fund_service: &dyn SinkingFundServiceTrait,
+    base_currency: &str,
+    goal_limit: usize,
+) -> Result<DashboardSummary> {
+    let today = Utc::now().date_naive();
+    let series = net_worth_service.get_net_worth_series(Some(today - Duration::days(1)), None)?;
+    Ok(build_dashboard_summary(
+        base_currency,
+        &series,
+        &emergency_fund_service.get_emergency_fund_progress().await?,
+        &sinking_fund_service.get_sinking_fund_progress().await?,
+        &net_worth_service.get_net_worth_goal_progress().await?,
+        goal_limit,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budgets::ExpenseBasis;
+    use rust_decimal_macros::dec;
+
+    fn point(date: &str, net_worth: Decimal) -> NetWorthPoint {
+        NetWorthPoint {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            total_assets: net_worth,
+            total_liabilities: Decimal::ZERO,
+            net_worth,
+        }
+    }
+
+    fn emergency_fund(id: &str, progress: Option<Decimal>) -> EmergencyFundProgress {
+        EmergencyFundProgress {
+            goal_id: id.to_string(),
+            goal_title: id.to_string(),
+            base_currency: "VND".to_string(),
+            target_months: 6,
+            monthly_expenses: dec!(10_000_000),
+            expense_basis: ExpenseBasis::Budget,
+            target_amount: dec!(60_000_000),
+            current_value: dec!(30_000_000),
+            months_covered: None,
+            progress,
+        }
+    }
+
+    #[test]
+    fn daily_change_compares_with_the_day_before_the_latest_point() {
+        let series = vec![
+            point("2026-10-16", dec!(1_000)),
+            point("2026-10-17", dec!(1_000)),
+            point("2026-10-18", dec!(1_050)),
+        ];
+        let summary = build_dashboard_summary("VND", &series, &[], &[], &[], 3);
+        assert_eq!(summary.as_of, NaiveDate::from_ymd_opt(2026, 10, 18));
+        assert_eq!(summary.net_worth, dec!(1_050));
+        assert_eq!(summary.daily_change, dec!(50));
+        assert_eq!(summary.daily_change_pct, Some(dec!(0.05)));
+
+        let empty = build_dashboard_summary("VND", &[], &[], &[], &[], 3);
+        assert_eq!(empty.as_of, None);
+        assert_eq!(empty.daily_change, Decimal::ZERO);
+        assert_eq!(empty.daily_change_pct, None);
+    }
+
+    #[test]
+    fn lists_unreached_goals_closest_first() {
+        let funds = vec![
+            emergency_fund("low", Some(dec!(0.2))),
+            emergency_fund("unknown", None),
+            emergency_fund("done", Some(dec!(1.1))),
+            emergency_fund("high", Some(dec!(0.8))),
+        ];
+        let summary = build_dashboard_summary("VND", &[], &funds, &[], &[], 3);
+        let ids: Vec<&str> = summary.goals.iter().map(|g| g.goal_id.as_str()).collect();
+        assert_eq!(ids, vec!["high", "low", "unknown"]);
+
+        let summary = build_dashboard_summary("VND", &[], &funds, &[], &[], 1);
+        assert_eq!(summary.goals.len(), 1);
+    }
+}
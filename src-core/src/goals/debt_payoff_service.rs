@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::accounts::{Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY};
+use crate::errors::Result;
+use crate::forecast::parse_forecast_date;
+use crate::fx::FxServiceTrait;
+use crate::goals::goal_progress_model::{DebtBalance, DebtPayoffProgress, GoalProgressInputs};
+use crate::goals::goals_model::{Goal, GOAL_TYPE_DEBT_PAYOFF};
+use crate::goals::goals_traits::{DebtPayoffServiceTrait, GoalRepositoryTrait};
+use crate::loans::{
+    amortize, outstanding_on, Loan, LoanPrepayment, LoanRepositoryTrait, LoanScheduleRow,
+};
+
+/// Amortization of the loan held by a liability account, valued in the base currency
+pub(crate) struct DebtSchedule {
+    pub loan: Loan,
+    rows: Vec<LoanScheduleRow>,
+    prepayments: Vec<LoanPrepayment>,
+    /// Account currency to base currency, at the latest rate
+    rate: Decimal,
+}
+
+impl DebtSchedule {
+    /// Principal owed on `date`. Before the loan starts that is the whole principal, since a
+    /// debt payoff goal set up ahead of the loan still has all of it to repay.
+    pub fn owed_on(&self, date: NaiveDate) -> Decimal {
+        outstanding_on(&self.loan, &self.rows, &self.prepayments, date) * self.rate
+    }
+
+    pub fn payoff_date(&self) -> Option<NaiveDate> {
+        self.rows.last().map(|row| row.payment_date)
+    }
+}
+
+/// Schedules of the loans on `accounts`, by account id. Accounts without a loan are left out.
+pub(crate) fn load_debt_schedules<'a>(
+    loan_repository: &dyn LoanRepositoryTrait,
+    fx_service: Option<&dyn FxServiceTrait>,
+    base_currency: &str,
+    accounts: impl IntoIterator<Item = &'a Account>,
+) -> Result<HashMap<String, DebtSchedule>> {
+    let mut schedules = HashMap::new();
+    for account in accounts {
+        if account.account_type != ACCOUNT_TYPE_LIABILITY {
+            continue;
+        }
+        let Some(loan) = loan_repository.get_loan_for_account(&account.id)? else {
+            continue;
+        };
+        let rate = match fx_service {
+            Some(fx_service) if account.currency != base_currency => {
+                fx_service.convert_currency(Decimal::ONE, &account.currency, base_currency)?
+            }
+            _ => Decimal::ONE,
+        };
+        let prepayments = loan_repository.get_prepayments(Some(&loan.id))?;
+        let resets = loan_repository.get_rate_resets(Some(&loan.id))?;
+        let rows = amortize(&loan, &prepayments, &resets);
+        schedules.insert(
+            account.id.clone(),
+            DebtSchedule {
+                loan,
+                rows,
+                prepayments,
+                rate,
+            },
+        );
+    }
+    Ok(schedules)
+}
+
+/// Owed on an account on `date` in the base currency: from its loan's schedule, or else its
+/// valuation taken as a positive amount
+pub(crate) fn owed_on(
+    account_id: &str,
+    schedules: &HashMap<String, DebtSchedule>,
+    inputs: &GoalProgressInputs,
+    date: NaiveDate,
+) -> Decimal {
+    match schedules.get(account_id) {
+        Some(schedule) => schedule.owed_on(date),
+        None => inputs
+            .account_value_on(account_id, date)
+            .map(|value| value.abs())
+            .unwrap_or_default(),
+    }
+}
+
+pub(crate) fn debt_payoff_progress(
+    goal: &Goal,
+    start_balance: Decimal,
+    debts: Vec<DebtBalance>,
+    base_currency: &str,
+) -> Result<DebtPayoffProgress> {
+    let target_amount = Decimal::from_f64_retain(goal.target_amount).unwrap_or_default();
+    let due_date = goal
+        .due_date
+        .as_deref()
+        .map(parse_forecast_date)
+        .transpose()?;
+    let remaining_principal: Decimal = debts.iter().map(|debt| debt.remaining_principal).sum();
+    let principal_repaid = (start_balance - remaining_principal).max(Decimal::ZERO);
+    // Debts already repaid have no say in when the rest is
+    let payoff_date = debts
+        .iter()
+        .filter(|debt| !debt.remaining_principal.is_zero())
+        .map(|debt| debt.payoff_date)
+        .collect::<Option<Vec<NaiveDate>>>()
+        .map(|dates| dates.into_iter().max());
+
+    Ok(DebtPayoffProgress {
+        goal_id: goal.id.clone(),
+        goal_title: goal.title.clone(),
+        base_currency: base_currency.to_string(),
+        target_amount,
+        due_date,
+        start_balance,
+        remaining_principal,
+        principal_repaid,
+        progress: (target_amount > Decimal::ZERO)
+            .then(|| (principal_repaid / target_amount).round_dp(4)),
+        payoff_date: payoff_date.flatten(),
+        is_on_track: match (payoff_date, due_date) {
+            (Some(None), Some(_)) => Some(true),
+            (Some(Some(payoff)), Some(due)) => Some(payoff <= due),
+            _ => None,
+        },
+        is_paid_off: remaining_principal.is_zero(),
+        debts,
+    })
+}
+
+pub struct DebtPayoffService {
+    goal_repo: Arc<dyn GoalRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    loan_repository: Arc<dyn LoanRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl DebtPayoffService {
+    pub fn new(
+        goal_repo: Arc<dyn GoalRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        loan_repository: Arc<dyn LoanRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            goal_repo,
+            account_repository,
+            loan_repository,
+            fx_service,
+            base_currency,
+        }
+    }
+}
+
+#[async_trait]
+impl DebtPayoffServiceTrait for DebtPayoffService {
+    async fn get_debt_payoff_progress(&self) -> Result<Vec<DebtPayoffProgress>> {
+        let goals: Vec<Goal> = self
+            .goal_repo
+            .load_goals()
+            .await?
+            .into_iter()
+            .filter(|goal| goal.goal_type == GOAL_TYPE_DEBT_PAYOFF && !goal.is_achieved)
+            .collect();
+        if goals.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let today = Utc::now().date_naive();
+        let today_str = today.format("%Y-%m-%d").to_string();
+        let mut earliest = today;
+        for goal in &goals {
+            if let Some(start_date) = goal.start_date.as_deref() {
+                earliest = earliest.min(parse_forecast_date(start_date)?);
+            }
+        }
+
+        let goal_ids: Vec<String> = goals.iter().map(|goal| goal.id.clone()).collect();
+        let inputs = self
+            .goal_repo
+            .load_goal_progress_inputs(&goal_ids, earliest, today)
+            .await?;
+        let mut accounts: HashMap<String, Account> = HashMap::new();
+        for allocation in &inputs.allocations {
+            if !accounts.contains_key(&allocation.account_id) {
+                let account = self.account_repository.get_by_id(&allocation.account_id)?;
+                accounts.insert(account.id.clone(), account);
+            }
+        }
+        let schedules = load_debt_schedules(
+            self.loan_repository.as_ref(),
+            Some(self.fx_service.as_ref()),
+            &base_currency,
+            accounts.values(),
+        )?;
+
+        let mut progress = Vec::with_capacity(goals.len());
+        for goal in &goals {
+            let start = match goal.start_date.as_deref() {
+                Some(start_date) => parse_forecast_date(start_date)?,
+                None => today,
+            };
+            let mut start_balance = Decimal::ZERO;
+            let mut debts = Vec::new();
+            for allocation in inputs.allocations_for_goal(&goal.id).filter(|a| {
+                a.allocation_date
+                    .as_deref()
+                    .is_none_or(|date| date <= today_str.as_str())
+            }) {
+                let Some(account) = accounts.get(&allocation.account_id) else {
+                    continue;
+                };
+                let share = Decimal::from_f64_retain(allocation.allocation_percentage)
+                    .unwrap_or_default()
+                    / Decimal::ONE_HUNDRED;
+                let schedule = schedules.get(&account.id);
+                start_balance += owed_on(&account.id, &schedules, &inputs, start) * share;
+                debts.push(DebtBalance {
+                    account_id: account.id.clone(),
+                    account_name: account.name.clone(),
+                    kind: schedule.map(|schedule| schedule.loan.kind),
+                    remaining_principal: (owed_on(&account.id, &schedules, &inputs, today) * share)
+                        .round_dp(2),
+                    payoff_date: schedule.and_then(DebtSchedule::payoff_date),
+                });
+            }
+            progress.push(debt_payoff_progress(
+                goal,
+                start_balance.round_dp(2),
+                debts,
+                &base_currency,
+            )?);
+        }
+        Ok(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loans::LoanKind;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn debt(remaining_principal: Decimal, payoff_date: Option<&str>) -> DebtBalance {
+        DebtBalance {
+            account_id: "car".to_string(),
+            account_name: "Car loan".to_string(),
+            kind: payoff_date.map(|_| LoanKind::Car),
+            remaining_principal,
+            payoff_date: payoff_date.map(date),
+        }
+    }
+
+    #[test]
+    fn progress_is_principal_repaid_and_payoff_follows_the_last_loan() {
+        let goal = Goal {
+            id: "debt-free".to_string(),
+            title: "Debt free by 2029".to_string(),
+            description: None,
+            target_amount: 800_000_000.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: Some("2029-06-30".to_string()),
+            monthly_investment: None,
+            start_date: Some("2026-01-01".to_string()),
+            initial_actual_value: None,
+            goal_type: GOAL_TYPE_DEBT_PAYOFF.to_string(),
+            target_months: None,
+            archived_at: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+            achieved_at: None,
+        };
+        let progress = debt_payoff_progress(
+            &goal,
+            dec!(800_000_000),
+            vec![
+                debt(dec!(450_000_000), Some("2029-01-10")),
+                debt(dec!(150_000_000), Some("2027-08-05")),
+                debt(Decimal::ZERO, None),
+            ],
+            "VND",
+        )
+        .unwrap();
+        assert_eq!(progress.remaining_principal, dec!(600_000_000));
+        assert_eq!(progress.principal_repaid, dec!(200_000_000));
+        assert_eq!(progress.progress, Some(dec!(0.25)));
+        // The account repaid without a loan does not hold the payoff date back
+        assert_eq!(progress.payoff_date, Some(date("2029-01-10")));
+        assert_eq!(progress.is_on_track, Some(true));
+        assert!(!progress.is_paid_off);
+
+        // A debt followed by its valuations has no schedule to date the payoff by
+        let unscheduled = debt_payoff_progress(
+            &goal,
+            dec!(800_000_000),
+            vec![debt(dec!(300_000_000), None)],
+            "VND",
+        )
+        .unwrap();
+        assert_eq!(unscheduled.payoff_date, None);
+        assert_eq!(unscheduled.is_on_track, None);
+    }
+}
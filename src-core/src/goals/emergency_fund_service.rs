@@ -0,0 +1,201 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::budgets::{BudgetServiceTrait, MonthlyExpenses};
+use crate::errors::Result;
+use crate::goals::goal_progress_model::EmergencyFundProgress;
+use crate::goals::goals_model::{Goal, GoalsAllocation, GOAL_TYPE_EMERGENCY_FUND};
+use crate::goals::goals_traits::{EmergencyFundServiceTrait, GoalRepositoryTrait};
+
+/// Recent months averaged to estimate monthly expenses
+pub const EMERGENCY_FUND_EXPENSE_MONTHS: u32 = 6;
+
+pub struct EmergencyFundService {
+    goal_repo: Arc<dyn GoalRepositoryTrait>,
+    budget_service: Arc<dyn BudgetServiceTrait>,
+}
+
+impl EmergencyFundService {
+    pub fn new(
+        goal_repo: Arc<dyn GoalRepositoryTrait>,
+        budget_service: Arc<dyn BudgetServiceTrait>,
+    ) -> Self {
+        Self {
+            goal_repo,
+            budget_service,
+        }
+    }
+}
+
+/// Value allocated to a goal: each started allocation's share of its account's value.
+/// Mirrors how goal progress is shown for standard goals.
+pub(crate) fn allocated_value<'a>(
+    allocations: impl IntoIterator<Item = &'a GoalsAllocation>,
+    account_values: &HashMap<String, Decimal>,
+    today: &str,
+) -> Decimal {
+    allocations
+        .into_iter()
+        .filter(|a| {
+            a.allocation_date
+                .as_deref()
+                .is_none_or(|date| date <= today)
+        })
+        .map(|a| {
+            let account_value = account_values
+                .get(&a.account_id)
+                .copied()
+                .unwrap_or_default();
+            let percent = Decimal::from_f64_retain(a.allocation_percentage).unwrap_or_default();
+            account_value * percent / Decimal::ONE_HUNDRED
+        })
+        .sum()
+}
+
+pub(crate) fn emergency_fund_progress(
+    goal: &Goal,
+    expenses: &MonthlyExpenses,
+    current_value: Decimal,
+) -> EmergencyFundProgress {
+    let target_months = goal.target_months.unwrap_or_default();
+    let monthly = expenses.average_monthly;
+    let target_amount = monthly * Decimal::from(target_months);
+    let months_covered = (monthly > Decimal::ZERO).then(|| (current_value / monthly).round_dp(2));
+    let progress =
+        (target_amount > Decimal::ZERO).then(|| (current_value / target_amount).round_dp(4));
+
+    EmergencyFundProgress {
+        goal_id: goal.id.clone(),
+        goal_title: goal.title.clone(),
+        base_currency: expenses.base_currency.clone(),
+        target_months,
+        monthly_expenses: monthly,
+        expense_basis: expenses.basis,
+        target_amount,
+        current_value,
+        months_covered,
+        progress,
+    }
+}
+
+#[async_trait]
+impl EmergencyFundServiceTrait for EmergencyFundService {
+    async fn get_emergency_fund_progress(&self) -> Result<Vec<EmergencyFundProgress>> {
+        let goals: Vec<Goal> = self
+            .goal_repo
+            .load_goals()
+            .await?
+            .into_iter()
+            .filter(|goal| goal.goal_type == GOAL_TYPE_EMERGENCY_FUND)
+            .collect();
+        if goals.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let expenses = self
+            .budget_service
+            .get_monthly_expenses(EMERGENCY_FUND_EXPENSE_MONTHS)?;
+        let today = Utc::now().date_naive();
+        let today_str = today.format("%Y-%m-%d").to_string();
+
+        let goal_ids: Vec<String> = goals.iter().map(|goal| goal.id.clone()).collect();
+        let inputs = self
+            .goal_repo
+            .load_goal_progress_inputs(&goal_ids, today, today)
+            .await?;
+        let account_values = inputs.account_values_on(today);
+
+        let mut progress = Vec::with_capacity(goals.len());
+        for goal in &goals {
+            let current_value = allocated_value(
+                inputs.allocations_for_goal(&goal.id),
+                &account_values,
+                &today_str,
+            );
+            progress.push(emergency_fund_progress(goal, &expenses, current_value));
+        }
+        Ok(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budgets::ExpenseBasis;
+    use crate::goals::goals_model::GOAL_TYPE_EMERGENCY_FUND;
+    use rust_decimal_macros::dec;
+
+    fn allocation(account_id: &str, percent: f64, date: &str) -> GoalsAllocation {
+        GoalsAllocation {
+            id: format!("{}-{}", account_id, date),
+            goal_id: "ef".to_string(),
+            account_id: account_id.to_string(),
+            init_amount: 0.0,
+            allocation_percentage: percent,
+            allocation_date: Some(date.to_string()),
+            percent_allocation: 0,
+            start_date: Some(date.to_string()),
+            end_date: None,
+            allocation_amount: 0.0,
+        }
+    }
+
+    #[test]
+    fn months_covered_from_allocated_value() {
+        let account_values = HashMap::from([
+            ("savings".to_string(), dec!(80_000_000)),
+            ("broker".to_string(), dec!(100_000_000)),
+        ]);
+        let allocations = vec![
+            allocation("savings", 100.0, "2026-01-01"),
+            allocation("broker", 10.0, "2026-01-01"),
+            // Not started yet
+            allocation("broker", 50.0, "2099-01-01"),
+        ];
+        let value = allocated_value(&allocations, &account_values, "2026-10-17");
+        assert_eq!(value, dec!(90_000_000));
+
+        let goal = Goal {
+            id: "ef".to_string(),
+            title: "Emergency fund".to_string(),
+            description: None,
+            target_amount: 0.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: None,
+            monthly_investment: None,
+            start_date: None,
+            initial_actual_value: None,
+            goal_type: GOAL_TYPE_EMERGENCY_FUND.to_string(),
+            target_months: Some(6),
+            archived_at: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+            achieved_at: None,
+        };
+        let expenses = MonthlyExpenses {
+            base_currency: "VND".to_string(),
+            average_monthly: dec!(20_000_000),
+            basis: ExpenseBasis::Spending,
+            months_sampled: 6,
+        };
+        let progress = emergency_fund_progress(&goal, &expenses, value);
+        assert_eq!(progress.target_amount, dec!(120_000_000));
+        assert_eq!(progress.months_covered, Some(dec!(4.5)));
+        assert_eq!(progress.progress, Some(dec!(0.75)));
+
+        let no_expenses = MonthlyExpenses {
+            average_monthly: Decimal::ZERO,
+            basis: ExpenseBasis::Budget,
+            months_sampled: 0,
+            ..expenses
+        };
+        let progress = emergency_fund_progress(&goal, &no_expenses, value);
+        assert_eq!(progress.months_covered, None);
+        assert_eq!(progress.progress, None);
+    }
+}
@@ -0,0 +1,383 @@
+use chrono::{Days, NaiveDate, Utc};
+use diesel::dsl::max;
+use diesel::prelude::*;
+
+use crate::errors::{Error, Result};
+use crate::goals::goal_progress_model::{AllocationDetail, GoalProgressSnapshot, ProgressInterval};
+use crate::goals::goals_model::{Goal, GOAL_TYPE_NET_WORTH};
+use crate::goals::goals_traits::{GoalServiceTrait, NetWorthGoalServiceTrait};
+use crate::schema::{goal_progress_history, goals};
+
+/// Most days the history job fills in at once after the app was closed for a while; older
+/// gaps are computed when read
+pub const GOAL_PROGRESS_BACKFILL_DAYS: u64 = 31;
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = goal_progress_history)]
+struct GoalProgressHistoryDb {
+    goal_id: String,
+    as_of: NaiveDate,
+    init_value: f64,
+    current_value: f64,
+    growth: f64,
+    allocation_details: String,
+    updated_at: String,
+}
+
+/// Drops stored daily progress of one goal (all when `None`) from `from_date` on (all days
+/// when `None`)
+pub(crate) fn clear_goal_progress_history(
+    conn: &mut SqliteConnection,
+    goal_id: Option<&str>,
+    from_date: Option<NaiveDate>,
+) -> Result<()> {
+    let mut query = diesel::delete(goal_progress_history::table).into_boxed();
+    if let Some(goal_id) = goal_id {
+        query = query.filter(goal_progress_history::goal_id.eq(goal_id));
+    }
+    if let Some(from_date) = from_date {
+        query = query.filter(goal_progress_history::as_of.ge(from_date));
+    }
+    query.execute(conn)?;
+    Ok(())
+}
+
+fn to_snapshot((row, goal_title): (GoalProgressHistoryDb, String)) -> Result<GoalProgressSnapshot> {
+    let allocation_details: Vec<AllocationDetail> =
+        serde_json::from_str(&row.allocation_details)
+            .map_err(|e| Error::Unexpected(e.to_string()))?;
+    // Rows stored before growth was split read as all returns
+    Ok(GoalProgressSnapshot {
+        goal_id: row.goal_id,
+        goal_title,
+        query_date: row.as_of.format("%Y-%m-%d").to_string(),
+        init_value: row.init_value,
+        current_value: row.current_value,
+        growth: row.growth,
+        contributed_amount: allocation_details.iter().map(|d| d.contributed_amount).sum(),
+        market_growth: allocation_details.iter().map(|d| d.market_growth).sum(),
+        allocation_details,
+    })
+}
+
+pub(crate) fn load_goal_progress(
+    conn: &mut SqliteConnection,
+    goal_ids: &[String],
+    as_of: NaiveDate,
+) -> Result<Vec<GoalProgressSnapshot>> {
+    goal_progress_history::table
+        .inner_join(goals::table)
+        .filter(goal_progress_history::goal_id.eq_any(goal_ids))
+        .filter(goal_progress_history::as_of.eq(as_of))
+        .select((goal_progress_history::all_columns, goals::title))
+        .load::<(GoalProgressHistoryDb, String)>(conn)?
+        .into_iter()
+        .map(to_snapshot)
+        .collect()
+}
+
+pub(crate) fn load_goal_progress_between(
+    conn: &mut SqliteConnection,
+    goal_id: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<GoalProgressSnapshot>> {
+    goal_progress_history::table
+        .inner_join(goals::table)
+        .filter(goal_progress_history::goal_id.eq(goal_id))
+        .filter(goal_progress_history::as_of.between(from, to))
+        .order(goal_progress_history::as_of.asc())
+        .select((goal_progress_history::all_columns, goals::title))
+        .load::<(GoalProgressHistoryDb, String)>(conn)?
+        .into_iter()
+        .map(to_snapshot)
+        .collect()
+}
+
+pub(crate) fn save_goal_progress(
+    conn: &mut SqliteConnection,
+    as_of: NaiveDate,
+    progress: &[GoalProgressSnapshot],
+) -> Result<usize> {
+    let updated_at = Utc::now().to_rfc3339();
+    let rows = progress
+        .iter()
+        .map(|p| {
+            Ok(GoalProgressHistoryDb {
+                goal_id: p.goal_id.clone(),
+                as_of,
+                init_value: p.init_value,
+                current_value: p.current_value,
+                growth: p.growth,
+                allocation_details: serde_json::to_string(&p.allocation_details)
+                    .map_err(|e| Error::Unexpected(e.to_string()))?,
+                updated_at: updated_at.clone(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut saved = 0;
+    for chunk in rows.chunks(500) {
+        saved += diesel::replace_into(goal_progress_history::table)
+            .values(chunk)
+            .execute(conn)?;
+    }
+    Ok(saved)
+}
+
+pub(crate) fn latest_goal_progress_date(conn: &mut SqliteConnection) -> Result<Option<NaiveDate>> {
+    Ok(goal_progress_history::table
+        .select(max(goal_progress_history::as_of))
+        .first::<Option<NaiveDate>>(conn)?)
+}
+
+/// Days the history is missing, from the day after `latest` (the last stored) through
+/// `today`, at most the last `GOAL_PROGRESS_BACKFILL_DAYS`
+fn days_to_record(latest: Option<NaiveDate>, today: NaiveDate) -> Vec<NaiveDate> {
+    let earliest = today
+        .checked_sub_days(Days::new(GOAL_PROGRESS_BACKFILL_DAYS - 1))
+        .unwrap_or(today);
+    let first = match latest {
+        Some(latest) if latest >= today => return Vec::new(),
+        Some(latest) => latest.succ_opt().unwrap_or(today).max(earliest),
+        None => today,
+    };
+    first.iter_days().take_while(|day| *day <= today).collect()
+}
+
+fn goal_start(goal: &Goal) -> Option<NaiveDate> {
+    goal.start_date
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+/// Computes the progress of every goal with a start date as of `as_of` and, unless `as_of`
+/// is still ahead, stores it. Run after every change the progress is computed from, so
+/// readers find it ready.
+pub async fn refresh_goal_progress(
+    goal_service: &dyn GoalServiceTrait,
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    as_of: NaiveDate,
+) -> Result<Vec<GoalProgressSnapshot>> {
+    let goals = goal_service.get_goals().await?;
+    // Only net-worth goals read the series, from the earliest of their start dates
+    let net_worth_start = goals
+        .iter()
+        .filter(|goal| goal.goal_type == GOAL_TYPE_NET_WORTH)
+        .filter_map(goal_start)
+        .map(|start| start.min(as_of))
+        .min();
+    let series = match net_worth_start {
+        Some(start) => net_worth_service.get_net_worth_series(Some(start), Some(as_of))?,
+        None => Vec::new(),
+    };
+    let progress = goal_service
+        .get_goals_progress_on_date(&series, as_of)
+        .await?;
+    if as_of <= Utc::now().date_naive() && !progress.is_empty() {
+        goal_service
+            .get_repository()
+            .save_goal_progress(as_of, progress.clone())
+            .await?;
+    }
+    Ok(progress)
+}
+
+/// Stores the daily progress of every goal for the days since the history was last written,
+/// so it can be charted without recomputing it from the valuations. Today's row is kept
+/// current by the portfolio updates; this catches the days the app was closed or left open
+/// past midnight. Returns the number of days written.
+pub async fn record_goal_progress_history(
+    goal_service: &dyn GoalServiceTrait,
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    today: NaiveDate,
+) -> Result<usize> {
+    let latest = goal_service
+        .get_repository()
+        .latest_goal_progress_date()
+        .await?;
+    let days = days_to_record(latest, today);
+    for day in &days {
+        refresh_goal_progress(goal_service, net_worth_service, *day).await?;
+    }
+    Ok(days.len())
+}
+
+/// Progress of one goal every `interval` from `from` (or its start date, if later) to `to`
+/// (today by default), for charting. Days in the stored history are read from it; the others
+/// are computed.
+pub async fn get_goal_progress_series(
+    goal_service: &dyn GoalServiceTrait,
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    goal_id: &str,
+    from: NaiveDate,
+    to: Option<NaiveDate>,
+    interval: ProgressInterval,
+) -> Result<Vec<GoalProgressSnapshot>> {
+    let to = to.unwrap_or_else(|| Utc::now().date_naive());
+    let goal = goal_service
+        .get_goals()
+        .await?
+        .into_iter()
+        .find(|goal| goal.id == goal_id)
+        .ok_or_else(|| Error::from(diesel::result::Error::NotFound))?;
+    // Only net-worth goals read the series
+    let series = match goal_start(&goal) {
+        Some(start) if goal.goal_type == GOAL_TYPE_NET_WORTH && start <= to => {
+            net_worth_service.get_net_worth_series(Some(start), Some(to))?
+        }
+        _ => Vec::new(),
+    };
+    goal_service
+        .get_progress_series(goal_id, from, to, interval, &series)
+        .await
+}
+
+/// Progress of every goal with a start date as of `date` (today by default). Read from
+/// storage; computed (then stored) only when a goal's row for the day is missing.
+pub async fn get_goals_progress(
+    goal_service: &dyn GoalServiceTrait,
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    date: Option<NaiveDate>,
+) -> Result<Vec<GoalProgressSnapshot>> {
+    let as_of = date.unwrap_or_else(|| Utc::now().date_naive());
+    let goal_ids: Vec<String> = goal_service
+        .get_goals()
+        .await?
+        .into_iter()
+        .filter(|goal| goal_start(goal).is_some())
+        .map(|goal| goal.id)
+        .collect();
+    if goal_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let stored = goal_service
+        .get_repository()
+        .load_goal_progress(&goal_ids, as_of)
+        .await?;
+    if stored.len() == goal_ids.len() {
+        return Ok(stored);
+    }
+    refresh_goal_progress(goal_service, net_worth_service, as_of).await
+}
+
+/// Progress of one goal as of `date` (today by default); `None` for a goal without a start
+/// date or that does not exist
+pub async fn get_goal_progress(
+    goal_service: &dyn GoalServiceTrait,
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    goal_id: &str,
+    date: Option<NaiveDate>,
+) -> Result<Option<GoalProgressSnapshot>> {
+    let as_of = date.unwrap_or_else(|| Utc::now().date_naive());
+    let stored = goal_service
+        .get_repository()
+        .load_goal_progress(&[goal_id.to_string()], as_of)
+        .await?;
+    if let Some(snapshot) = stored.into_iter().next() {
+        return Ok(Some(snapshot));
+    }
+    let progress = refresh_goal_progress(goal_service, net_worth_service, as_of).await?;
+    Ok(progress
+        .into_iter()
+        .find(|snapshot| snapshot.goal_id == goal_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::run_migrations;
+    use crate::goals::goals_model::NewGoal;
+    use crate::goals::monthly_summaries::clear_goal_progress;
+    use diesel::r2d2::{ConnectionManager, Pool};
+
+    fn snapshot(current_value: f64) -> GoalProgressSnapshot {
+        GoalProgressSnapshot {
+            goal_id: "house".to_string(),
+            goal_title: String::new(),
+            query_date: String::new(),
+            init_value: 0.0,
+            current_value,
+            growth: current_value,
+            contributed_amount: 40.0,
+            market_growth: current_value - 40.0,
+            allocation_details: vec![AllocationDetail {
+                account_id: "broker".to_string(),
+                percent_allocation: 50,
+                account_value_at_goal_start: 1_000.0,
+                account_current_value: 1_000.0 + current_value * 2.0,
+                account_growth: current_value * 2.0,
+                allocated_growth: current_value,
+                contributed_amount: 40.0,
+                market_growth: current_value - 40.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn stored_progress_is_read_back_until_a_write_clears_it() {
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .unwrap();
+        run_migrations(&pool).unwrap();
+        let conn = &mut pool.get().unwrap();
+        diesel::insert_into(goals::table)
+            .values(NewGoal {
+                id: Some("house".to_string()),
+                title: "House".to_string(),
+                description: None,
+                target_amount: 1_000_000.0,
+                is_achieved: false,
+                target_return_rate: None,
+                due_date: None,
+                monthly_investment: None,
+                start_date: Some("2026-01-01".to_string()),
+                initial_actual_value: None,
+                goal_type: "STANDARD".to_string(),
+                target_months: None,
+                priority: 0,
+                depends_on_goal_id: None,
+                currency: None,
+            })
+            .execute(conn)
+            .unwrap();
+
+        let day = NaiveDate::from_ymd_opt(2026, 9, 30).unwrap();
+        let next_day = day.succ_opt().unwrap();
+        let ids = vec!["house".to_string()];
+        save_goal_progress(conn, day, &[snapshot(100.0)]).unwrap();
+        save_goal_progress(conn, next_day, &[snapshot(150.0)]).unwrap();
+
+        let stored = load_goal_progress(conn, &ids, day).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].goal_title, "House");
+        assert_eq!(stored[0].query_date, "2026-09-30");
+        assert_eq!(stored[0].current_value, 100.0);
+        assert_eq!(stored[0].allocation_details[0].allocated_growth, 100.0);
+        // The split is read back from the allocations
+        assert_eq!((stored[0].contributed_amount, stored[0].market_growth), (40.0, 60.0));
+
+        // A valuation written for the next day invalidates that day only
+        clear_goal_progress(conn, None, Some(next_day)).unwrap();
+        assert!(load_goal_progress(conn, &ids, next_day).unwrap().is_empty());
+        assert_eq!(load_goal_progress(conn, &ids, day).unwrap().len(), 1);
+        assert_eq!(latest_goal_progress_date(conn).unwrap(), Some(day));
+    }
+
+    #[test]
+    fn history_job_fills_the_days_since_the_last_stored_one() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let date = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        assert_eq!(days_to_record(None, today), vec![today]);
+        assert!(days_to_record(Some(today), today).is_empty());
+        assert_eq!(
+            days_to_record(Some(date(15)), today),
+            vec![date(16), date(17), today]
+        );
+        // A long gap only goes back so far
+        let days = days_to_record(NaiveDate::from_ymd_opt(2026, 1, 1), today);
+        assert_eq!(days.len() as u64, GOAL_PROGRESS_BACKFILL_DAYS);
+        assert_eq!(days.last(), Some(&today));
+    }
+}
@@ -1,4 +1,11 @@
+use chrono::{Days, Months, NaiveDate};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::budgets::ExpenseBasis;
+use crate::goals::goals_model::{AllocationVersion, GoalsAllocation};
+use crate::loans::LoanKind;
 
 /// Represents the progress of a goal on a specific date
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -7,16 +14,63 @@ pub struct GoalProgressSnapshot {
     pub goal_id: String,
     pub goal_title: String,
     pub query_date: String,
-    /// Initial value is always 0 under the new logic
+    /// Initial value is always 0 under the new logic, except net-worth goals start from
+    /// net worth on the goal start date
     pub init_value: f64,
     /// Current accumulated value from growth since goal start date
     pub current_value: f64,
     /// Growth = current_value - init_value
     pub growth: f64,
+    /// Part of the growth paid into the goal's accounts, net of withdrawals. Zero for
+    /// net-worth goals, whose growth is not split.
+    #[serde(default)]
+    pub contributed_amount: f64,
+    /// Part of the growth earned by the investments: growth - contributed_amount
+    #[serde(default)]
+    pub market_growth: f64,
     /// Allocation breakdown by account
     pub allocation_details: Vec<AllocationDetail>,
 }
 
+/// Most points a progress series is computed for; a longer interval covers a longer range
+pub const MAX_PROGRESS_SERIES_POINTS: usize = 1_000;
+
+/// Spacing of the points in a goal's progress series
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProgressInterval {
+    #[default]
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl ProgressInterval {
+    /// Dates from `from` to `to`, one interval apart counting from `from`; `to` is always the
+    /// last one
+    pub fn series_dates(self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut dates = Vec::new();
+        let mut step = 0;
+        while let Some(date) = self.nth_date(from, step).filter(|date| *date < to) {
+            dates.push(date);
+            step += 1;
+        }
+        if from <= to {
+            dates.push(to);
+        }
+        dates
+    }
+
+    fn nth_date(self, from: NaiveDate, step: u32) -> Option<NaiveDate> {
+        match self {
+            ProgressInterval::Daily => from.checked_add_days(Days::new(step.into())),
+            ProgressInterval::Weekly => from.checked_add_days(Days::new(u64::from(step) * 7)),
+            // Counted from `from` rather than the previous date, so 31 Jan keeps month ends
+            ProgressInterval::Monthly => from.checked_add_months(Months::new(step)),
+        }
+    }
+}
+
 /// Details of how a goal is performing on a specific account
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +85,13 @@ pub struct AllocationDetail {
     pub account_growth: f64,
     /// This allocation's portion of growth
     pub allocated_growth: f64,
+    /// This allocation's portion of the deposits into the account, net of withdrawals, since
+    /// the goal started
+    #[serde(default)]
+    pub contributed_amount: f64,
+    /// This allocation's portion of investment returns: allocated_growth - contributed_amount
+    #[serde(default)]
+    pub market_growth: f64,
 }
 
 /// Summary of goal across all dates (historical view)
@@ -44,3 +105,224 @@ pub struct GoalProgressHistory {
     /// Snapshots at key dates
     pub snapshots: Vec<GoalProgressSnapshot>,
 }
+
+/// Progress of an emergency fund goal, measured in months of expenses covered
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmergencyFundProgress {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub base_currency: String,
+    pub target_months: i32,
+    /// Typical monthly expenses the target is based on
+    pub monthly_expenses: Decimal,
+    pub expense_basis: ExpenseBasis,
+    /// `target_months` worth of expenses
+    pub target_amount: Decimal,
+    /// Value allocated to the goal from its accounts
+    pub current_value: Decimal,
+    /// `None` when there are no expenses to measure against
+    pub months_covered: Option<Decimal>,
+    /// Share of the month target covered, between 0 and 1 (can exceed 1)
+    pub progress: Option<Decimal>,
+}
+
+/// Progress of a sinking fund and what to set aside each month to fund it on time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SinkingFundProgress {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub base_currency: String,
+    pub target_amount: Decimal,
+    pub due_date: NaiveDate,
+    /// Value allocated to the goal from its accounts
+    pub current_value: Decimal,
+    /// Still to save; zero once funded
+    pub remaining: Decimal,
+    /// Monthly set-asides left before the due date, counting this month
+    pub months_remaining: u32,
+    /// `remaining` spread over `months_remaining`; the whole remainder once the date has passed
+    pub monthly_set_aside: Decimal,
+    /// Share of the target saved, between 0 and 1 (can exceed 1)
+    pub progress: Option<Decimal>,
+    pub is_funded: bool,
+}
+
+/// Net worth on one day, in the base currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetWorthPoint {
+    pub date: NaiveDate,
+    pub total_assets: Decimal,
+    /// Owed on liability accounts, as a positive amount
+    pub total_liabilities: Decimal,
+    pub net_worth: Decimal,
+}
+
+/// Progress of a net-worth goal, measured against everything owned minus everything owed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetWorthGoalProgress {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub base_currency: String,
+    pub target_amount: Decimal,
+    pub due_date: Option<NaiveDate>,
+    /// Net worth on the goal's start date, when there is history that far back
+    pub start_net_worth: Option<Decimal>,
+    pub current: NetWorthPoint,
+    /// Still to grow; zero once reached
+    pub remaining: Decimal,
+    /// Share of the target reached, between 0 and 1 (can exceed 1)
+    pub progress: Option<Decimal>,
+    pub is_reached: bool,
+}
+
+/// What is left to repay on one liability account allocated to a debt payoff goal, in the
+/// base currency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebtBalance {
+    pub account_id: String,
+    pub account_name: String,
+    /// `None` when the account has no loan set up and follows its valuations
+    pub kind: Option<LoanKind>,
+    /// The goal's share of the principal still owed
+    pub remaining_principal: Decimal,
+    /// Date of the loan's last scheduled payment
+    pub payoff_date: Option<NaiveDate>,
+}
+
+/// Progress of a debt payoff goal, measured by the principal repaid since the goal started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebtPayoffProgress {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub base_currency: String,
+    pub target_amount: Decimal,
+    pub due_date: Option<NaiveDate>,
+    /// Principal owed on the goal's start date
+    pub start_balance: Decimal,
+    pub remaining_principal: Decimal,
+    pub principal_repaid: Decimal,
+    /// Share of the target repaid, between 0 and 1 (can exceed 1)
+    pub progress: Option<Decimal>,
+    /// When the last of the debts is repaid on schedule; `None` if one of them has no loan
+    pub payoff_date: Option<NaiveDate>,
+    /// Whether the schedule repays everything by the due date; `None` without either date
+    pub is_on_track: Option<bool>,
+    pub is_paid_off: bool,
+    pub debts: Vec<DebtBalance>,
+}
+
+/// Progress of a goal at the end of a month; for the current month, as of today
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalMonthlyProgress {
+    pub goal_id: String,
+    /// First day of the month
+    pub month: NaiveDate,
+    pub as_of: NaiveDate,
+    pub init_value: f64,
+    pub current_value: f64,
+    pub growth: f64,
+}
+
+/// Allocations of a set of goals with what their progress is computed from, loaded at once
+/// instead of goal by goal
+#[derive(Debug, Clone, Default)]
+pub struct GoalProgressInputs {
+    pub allocations: Vec<GoalsAllocation>,
+    /// Versions of each allocation by allocation id, oldest first
+    pub versions: HashMap<String, Vec<AllocationVersion>>,
+    /// Daily values in the base currency by account id, oldest first. The first value may
+    /// predate the requested range: it is the one carried into its start.
+    pub account_values: HashMap<String, Vec<(NaiveDate, Decimal)>>,
+    /// Net contributions in the base currency by account id, on the same dates as
+    /// `account_values`
+    pub account_contributions: HashMap<String, Vec<(NaiveDate, Decimal)>>,
+}
+
+/// Entry of a dated series on `date`, carried forward from the last one before it
+fn carried_value(series: &[(NaiveDate, Decimal)], date: NaiveDate) -> Option<Decimal> {
+    let index = series.partition_point(|(value_date, _)| *value_date <= date);
+    index.checked_sub(1).map(|i| series[i].1)
+}
+
+fn carried_values(
+    series: &HashMap<String, Vec<(NaiveDate, Decimal)>>,
+    date: NaiveDate,
+) -> HashMap<String, Decimal> {
+    series
+        .iter()
+        .filter_map(|(account_id, values)| {
+            carried_value(values, date).map(|value| (account_id.clone(), value))
+        })
+        .collect()
+}
+
+impl GoalProgressInputs {
+    pub fn allocations_for_goal<'a>(
+        &'a self,
+        goal_id: &'a str,
+    ) -> impl Iterator<Item = &'a GoalsAllocation> + 'a {
+        self.allocations.iter().filter(move |a| a.goal_id == goal_id)
+    }
+
+    /// Value of an account on `date`, carried forward from its last valuation
+    pub fn account_value_on(&self, account_id: &str, date: NaiveDate) -> Option<Decimal> {
+        carried_value(self.account_values.get(account_id)?, date)
+    }
+
+    /// Value of every loaded account on `date`; accounts without a valuation yet are left out
+    pub fn account_values_on(&self, date: NaiveDate) -> HashMap<String, Decimal> {
+        carried_values(&self.account_values, date)
+    }
+
+    /// Net contribution of every loaded account on `date`, carried forward like the values
+    pub fn account_contributions_on(&self, date: NaiveDate) -> HashMap<String, Decimal> {
+        carried_values(&self.account_contributions, date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn series_dates_step_from_the_start_and_end_on_the_last_day() {
+        let dates = ProgressInterval::Weekly.series_dates(date("2026-10-01"), date("2026-10-18"));
+        assert_eq!(
+            dates,
+            vec![
+                date("2026-10-01"),
+                date("2026-10-08"),
+                date("2026-10-15"),
+                date("2026-10-18")
+            ]
+        );
+        let dates = ProgressInterval::Monthly.series_dates(date("2026-01-31"), date("2026-04-30"));
+        assert_eq!(
+            dates,
+            vec![
+                date("2026-01-31"),
+                date("2026-02-28"),
+                date("2026-03-31"),
+                date("2026-04-30")
+            ]
+        );
+        assert_eq!(
+            ProgressInterval::Daily.series_dates(date("2026-10-18"), date("2026-10-18")),
+            vec![date("2026-10-18")]
+        );
+        assert!(ProgressInterval::Daily
+            .series_dates(date("2026-10-18"), date("2026-10-17"))
+            .is_empty());
+    }
+}
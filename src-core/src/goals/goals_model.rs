@@ -1,12 +1,69 @@
 use crate::accounts::Account;
+use crate::errors::{Error, Result, ValidationError};
+use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use diesel::Queryable;
 use diesel::Selectable;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+pub const GOAL_TYPE_STANDARD: &str = "STANDARD";
+/// Target is a number of months of expenses instead of a fixed amount
+pub const GOAL_TYPE_EMERGENCY_FUND: &str = "EMERGENCY_FUND";
+/// Saves up for a known expense by its due date, e.g. an insurance premium or Tet gifts
+pub const GOAL_TYPE_SINKING_FUND: &str = "SINKING_FUND";
+/// Funds a child's schooling; target and contributions come from an education plan
+pub const GOAL_TYPE_EDUCATION: &str = "EDUCATION";
+/// Target is total net worth (assets minus liabilities) rather than allocated account growth
+pub const GOAL_TYPE_NET_WORTH: &str = "NET_WORTH";
+/// Pays off the home; progress is the equity built in the allocated real-estate accounts,
+/// which count at their value less the mortgage
+pub const GOAL_TYPE_PROPERTY_PAYOFF: &str = "PROPERTY_PAYOFF";
+/// Pays down loans; progress is the principal repaid on the allocated liability accounts
+/// rather than their growth
+pub const GOAL_TYPE_DEBT_PAYOFF: &str = "DEBT_PAYOFF";
+
+/// Longest emergency fund target, in months of expenses
+pub const MAX_EMERGENCY_FUND_MONTHS: i32 = 60;
+
+fn default_goal_type() -> String {
+    GOAL_TYPE_STANDARD.to_string()
+}
+
+/// Checks that a goal's type is known, that emergency funds have a month target and that
+/// sinking funds have a due date
+pub fn validate_goal_type(
+    goal_type: &str,
+    target_months: Option<i32>,
+    due_date: Option<&str>,
+) -> Result<()> {
+    match goal_type {
+        GOAL_TYPE_STANDARD | GOAL_TYPE_EDUCATION | GOAL_TYPE_NET_WORTH
+        | GOAL_TYPE_PROPERTY_PAYOFF | GOAL_TYPE_DEBT_PAYOFF => Ok(()),
+        GOAL_TYPE_EMERGENCY_FUND => match target_months {
+            Some(months) if (1..=MAX_EMERGENCY_FUND_MONTHS).contains(&months) => Ok(()),
+            _ => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Emergency fund target must be between 1 and {} months of expenses",
+                MAX_EMERGENCY_FUND_MONTHS
+            )))),
+        },
+        GOAL_TYPE_SINKING_FUND => match due_date {
+            Some(date) => crate::forecast::parse_forecast_date(date).map(|_| ()),
+            None => Err(Error::Validation(ValidationError::MissingField(
+                "dueDate".to_string(),
+            ))),
+        },
+        other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "Unknown goal type: {}",
+            other
+        )))),
+    }
+}
 
 #[derive(
     Queryable,
     Identifiable,
+    Insertable,
     AsChangeset,
     Selectable,
     PartialEq,
@@ -29,6 +86,27 @@ pub struct Goal {
     pub monthly_investment: Option<f64>,
     pub start_date: Option<String>,
     pub initial_actual_value: Option<f64>,
+    #[serde(default = "default_goal_type")]
+    pub goal_type: String,
+    /// Months of expenses an emergency fund should cover; unused by standard goals
+    #[serde(default)]
+    pub target_months: Option<i32>,
+    /// When the goal was archived. Archived goals are left out of lists and progress but keep
+    /// their allocations, so they can be restored; updates never clear it.
+    #[serde(default)]
+    pub archived_at: Option<String>,
+    /// Funding order: new cash goes to goals with a higher priority first
+    #[serde(default)]
+    pub priority: i32,
+    /// Goal that has to be achieved before this one takes new cash
+    #[serde(default)]
+    pub depends_on_goal_id: Option<String>,
+    /// Currency the target is set in and progress is reported in; `None` is the base currency
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// Day the goal reached its target, `YYYY-MM-DD`; cleared when it is marked not achieved
+    #[serde(default)]
+    pub achieved_at: Option<String>,
 }
 
 #[derive(Insertable, Serialize, Deserialize, Debug, Clone)]
@@ -45,6 +123,20 @@ pub struct NewGoal {
     pub monthly_investment: Option<f64>,
     pub start_date: Option<String>,
     pub initial_actual_value: Option<f64>,
+    #[serde(default = "default_goal_type")]
+    pub goal_type: String,
+    /// Months of expenses an emergency fund should cover; unused by standard goals
+    #[serde(default)]
+    pub target_months: Option<i32>,
+    /// Funding order: new cash goes to goals with a higher priority first
+    #[serde(default)]
+    pub priority: i32,
+    /// Goal that has to be achieved before this one takes new cash
+    #[serde(default)]
+    pub depends_on_goal_id: Option<String>,
+    /// Currency the target is set in and progress is reported in; `None` is the base currency
+    #[serde(default)]
+    pub currency: Option<String>,
 }
 
 #[derive(
@@ -109,3 +201,202 @@ pub struct AllocationVersion {
     pub version_end_date: Option<String>,
     pub created_at: String,
 }
+
+/// What `bulk_insert_allocations` wrote, all in one transaction
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationBulkInsertSummary {
+    pub allocations: usize,
+    pub versions: usize,
+    /// Distinct goals and accounts the allocations belong to
+    pub goals: usize,
+    pub accounts: usize,
+}
+
+/// Goals without a due date are paced as if due this many months out when rebalancing
+pub const UNDATED_GOAL_HORIZON_MONTHS: u32 = 120;
+
+/// A proposed percentage for one allocation of an account
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationRebalanceSuggestion {
+    pub allocation_id: String,
+    pub goal_id: String,
+    pub goal_title: String,
+    pub target_amount: f64,
+    /// Progress of the goal across all of its accounts
+    pub current_value: f64,
+    /// Still to reach; zero once funded
+    pub remaining: f64,
+    pub due_date: Option<String>,
+    /// Months until the due date, at least one; `None` for a goal without one
+    pub months_left: Option<u32>,
+    pub current_percent: f64,
+    pub suggested_percent: f64,
+}
+
+/// The share of an account its goals hold, split again in proportion to what each goal still
+/// needs per month to be reached on time. Allocations that are not active, or whose goal is
+/// achieved or tracks net worth, keep their share.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationRebalance {
+    pub account_id: String,
+    pub as_of: String,
+    /// Share the rebalanced allocations hold together, before and after
+    pub rebalanced_percent: f64,
+    /// Share of the account no allocation holds; left free
+    pub unallocated_percent: f64,
+    pub suggestions: Vec<AllocationRebalanceSuggestion>,
+}
+
+/// Cash proposed for one goal out of a new deposit
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalDeposit {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub priority: i32,
+    pub due_date: Option<String>,
+    /// Months until the due date, at least one; `None` for a goal without one
+    pub months_left: Option<u32>,
+    /// Still to reach before the deposit
+    pub remaining: f64,
+    pub amount: f64,
+}
+
+/// How a new deposit into an account would be split across the goals it funds. Goals are
+/// funded a priority at a time; within one priority the cash follows what each goal still
+/// needs per month, so nearer due dates and larger remainders get more, and no goal gets more
+/// than it still needs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositDistribution {
+    pub account_id: String,
+    pub as_of: String,
+    pub amount: f64,
+    /// Part of the deposit no goal needs, rounding included
+    pub unassigned: f64,
+    pub goals: Vec<GoalDeposit>,
+}
+
+/// Inflation assumed for funding requirements when none is given, in percent a year
+pub const DEFAULT_GOAL_INFLATION_RATE: f64 = 4.0;
+
+/// Monthly contribution a goal needs to reach its target by its due date, from what it holds
+/// today and an expected return
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalFundingRequirement {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub target_amount: f64,
+    pub current_value: f64,
+    pub due_date: String,
+    /// Months until the due date, at least one
+    pub months_left: u32,
+    /// Percent a year, compounded monthly
+    pub expected_return_rate: f64,
+    /// Percent a year
+    pub inflation_rate: f64,
+    /// Monthly contribution for the target as set
+    pub required_monthly: f64,
+    /// The target grown by inflation until the due date, so it buys what it would today
+    pub inflation_adjusted_target: f64,
+    /// Monthly contribution for the inflation-adjusted target
+    pub inflation_adjusted_monthly: f64,
+}
+
+/// A change to one allocation to preview before saving it
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AllocationChange {
+    /// New share of the account's growth, 0 to 100
+    Percentage { value: f64 },
+    /// New amount set aside from the account
+    Amount { value: f64 },
+}
+
+/// Where a goal funded from the account would stand with and without the previewed change.
+/// Completion is projected at the pace the goal has grown since its start.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalChangePreview {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub target_amount: f64,
+    pub current_value: f64,
+    pub new_value: f64,
+    /// `None` while the goal is not growing
+    pub completion_date: Option<String>,
+    pub new_completion_date: Option<String>,
+}
+
+/// What changing an allocation would do, computed without saving anything
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationChangePreview {
+    pub allocation_id: String,
+    pub account_id: String,
+    pub as_of: String,
+    pub change: AllocationChange,
+    /// Account value no allocation has set aside, after the change
+    pub unallocated_balance: f64,
+    /// Goals the account funds, the changed allocation's among them
+    pub goals: Vec<GoalChangePreview>,
+    /// Why the change could not be saved as it is; empty when it can
+    pub errors: Vec<String>,
+}
+
+/// Bumped whenever the goal plan layout changes in a way older builds can't read
+pub const GOAL_PLAN_EXPORT_VERSION: u32 = 1;
+
+/// Goals with their allocations and allocation versions, to move a plan to another profile or
+/// machine. Ids are the exporting profile's; an import gives everything new ones.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalPlanExport {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub goals: Vec<Goal>,
+    #[serde(default)]
+    pub allocations: Vec<GoalsAllocation>,
+    #[serde(default)]
+    pub versions: Vec<AllocationVersion>,
+    /// Names of the allocated accounts by id, so allocations find their account where ids
+    /// differ
+    #[serde(default)]
+    pub account_names: BTreeMap<String, String>,
+}
+
+impl GoalPlanExport {
+    /// Account here for each exported account: the one with the same id, or else the only one
+    /// with the same name
+    pub fn match_accounts(&self, accounts: &[Account]) -> HashMap<String, String> {
+        let mut by_name: HashMap<&str, Vec<&str>> = HashMap::new();
+        for account in accounts {
+            by_name.entry(account.name.as_str()).or_default().push(account.id.as_str());
+        }
+        let mut matched = HashMap::new();
+        for (exported_id, name) in &self.account_names {
+            if accounts.iter().any(|account| &account.id == exported_id) {
+                matched.insert(exported_id.clone(), exported_id.clone());
+            } else if let Some([account_id]) = by_name.get(name.as_str()).map(Vec::as_slice) {
+                matched.insert(exported_id.clone(), account_id.to_string());
+            }
+        }
+        matched
+    }
+}
+
+/// What an import of a goal plan created
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalPlanImportResult {
+    pub goals: usize,
+    pub allocations: usize,
+    pub versions: usize,
+    /// Exported accounts no account here matches; their allocations were left out
+    pub unmatched_accounts: Vec<String>,
+}
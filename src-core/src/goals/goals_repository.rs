@@ -1,16 +1,30 @@
-use crate::db::{get_connection, WriteHandle};
+use crate::db::{spawn_read, WriteHandle};
 use crate::errors::Result;
+use crate::goals::goal_progress_model::{GoalMonthlyProgress, GoalProgressInputs, GoalProgressSnapshot};
+use crate::goals::allocation_periods::{allocation_periods, check_new_periods};
+use crate::goals::goal_progress_history;
+use crate::goals::monthly_summaries;
 use crate::goals::goals_model::{Goal, GoalsAllocation, NewGoal, AllocationVersion};
 use crate::goals::goals_traits::GoalRepositoryTrait;
+use crate::portfolio::valuation::valuation_archive;
+use crate::real_estate::load_property_mortgages;
+use crate::portfolio::valuation::{DailyAccountValuation, DailyAccountValuationDb};
 use crate::schema::goals;
 use crate::schema::goals::dsl::*;
 use crate::schema::goals_allocation;
 use crate::schema::allocation_versions;
 use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
 use diesel::prelude::*;
 use diesel::r2d2::{self, Pool};
+use diesel::sql_query;
+use diesel::sql_types::{Date, Text};
+use diesel::sqlite::Sqlite;
 use diesel::SqliteConnection;
+use rust_decimal::Decimal;
+use tracing::instrument;
 
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -27,105 +41,489 @@ impl GoalRepository {
         GoalRepository { pool, writer }
     }
 
-    pub fn load_goals_impl(&self) -> Result<Vec<Goal>> {
-        let mut conn = get_connection(&self.pool)?;
+    fn load_goals_impl(conn: &mut SqliteConnection) -> Result<Vec<Goal>> {
         Ok(goals
+            .filter(archived_at.is_null())
             .select(Goal::as_select())
-            .load::<Goal>(&mut conn)?)
+            .load::<Goal>(conn)?)
     }
 
-    pub fn load_allocations_for_non_achieved_goals_impl(&self) -> Result<Vec<GoalsAllocation>> {
-        let mut conn = get_connection(&self.pool)?;
+    /// Load ALL goals including archived ones
+    fn load_all_goals_impl(conn: &mut SqliteConnection) -> Result<Vec<Goal>> {
+        Ok(goals
+            .select(Goal::as_select())
+            .load::<Goal>(conn)?)
+    }
+
+    /// Archives or restores a goal. A restored goal's allocations count toward the 100% cap
+    /// again, so they are checked against what other goals took in the meantime; run inside
+    /// the writer's transaction, a restore that would exceed the cap is rolled back.
+    fn set_goal_archived_impl(
+        conn: &mut SqliteConnection,
+        goal_id: &str,
+        archived: Option<String>,
+    ) -> Result<Goal> {
+        let restoring = archived.is_none();
+        let updated = diesel::update(goals.find(goal_id))
+            .set(archived_at.eq(archived))
+            .execute(conn)?;
+        if updated == 0 {
+            return Err(diesel::result::Error::NotFound.into());
+        }
+        if restoring {
+            let restored = Self::get_allocations_for_goal_impl(conn, goal_id.to_string())?;
+            Self::check_allocation_caps(conn, &restored)?;
+        }
+        monthly_summaries::clear_goal_progress(conn, Some(goal_id), None)?;
+        Ok(goals.find(goal_id).select(Goal::as_select()).first(conn)?)
+    }
+
+    fn set_goal_achieved_impl(
+        conn: &mut SqliteConnection,
+        goal_id: &str,
+        achieved: Option<String>,
+    ) -> Result<Goal> {
+        let updated = diesel::update(goals.find(goal_id))
+            .set((is_achieved.eq(achieved.is_some()), achieved_at.eq(achieved)))
+            .execute(conn)?;
+        if updated == 0 {
+            return Err(diesel::result::Error::NotFound.into());
+        }
+        Ok(goals.find(goal_id).select(Goal::as_select()).first(conn)?)
+    }
+
+    fn load_allocations_for_non_achieved_goals_impl(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<GoalsAllocation>> {
         Ok(goals_allocation::table
             .inner_join(goals::table.on(goals::id.eq(goals_allocation::goal_id)))
             .filter(goals::is_achieved.eq(false))
             .select(GoalsAllocation::as_select())
-            .load::<GoalsAllocation>(&mut conn)?)
+            .load::<GoalsAllocation>(conn)?)
     }
 
     /// Load ALL allocations including from completed goals (for display purposes)
-    pub fn load_all_allocations_impl(&self) -> Result<Vec<GoalsAllocation>> {
-        let mut conn = get_connection(&self.pool)?;
+    fn load_all_allocations_impl(conn: &mut SqliteConnection) -> Result<Vec<GoalsAllocation>> {
         Ok(goals_allocation::table
             .select(GoalsAllocation::as_select())
-            .load::<GoalsAllocation>(&mut conn)?)
+            .load::<GoalsAllocation>(conn)?)
     }
 
-    pub fn get_allocations_for_account_on_date(
-        &self,
-        account_id: &str,
-        query_date: &str,
-    ) -> Result<Vec<GoalsAllocation>> {
-        let mut conn = get_connection(&self.pool)?;
-        let query_date = query_date.to_string();
-        let account_id = account_id.to_string();
-
-        Ok(goals_allocation::table
+    /// Allocations of an account active on a date; served by `idx_goals_allocation_account_dates`
+    fn allocations_on_date_query<'a>(
+        account_id: &'a str,
+        query_date: &'a str,
+    ) -> goals_allocation::BoxedQuery<'a, Sqlite> {
+        goals_allocation::table
             .filter(goals_allocation::account_id.eq(account_id))
-            .filter(goals_allocation::start_date.le(&query_date))
-            .filter(goals_allocation::end_date.ge(&query_date))
-            .select(GoalsAllocation::as_select())
-            .load::<GoalsAllocation>(&mut conn)?)
+            .filter(goals_allocation::start_date.le(query_date))
+            .filter(goals_allocation::end_date.ge(query_date))
+            .into_boxed()
     }
 
-    pub fn get_allocations_for_goal_impl(
-        &self,
-        goal_id: &str,
+    /// Versions of the given allocations, oldest first; served by
+    /// `idx_allocation_versions_allocation_start`
+    fn versions_query<'a>(
+        allocation_ids: &'a [&'a str],
+    ) -> allocation_versions::BoxedQuery<'a, Sqlite> {
+        allocation_versions::table
+            .filter(allocation_versions::allocation_id.eq_any(allocation_ids))
+            .order_by(allocation_versions::version_start_date.asc())
+            .into_boxed()
+    }
+
+    fn get_allocations_for_account_on_date_impl(
+        conn: &mut SqliteConnection,
+        account_id: String,
+        query_date: String,
     ) -> Result<Vec<GoalsAllocation>> {
-        let mut conn = get_connection(&self.pool)?;
-        let goal_id = goal_id.to_string();
+        Ok(Self::allocations_on_date_query(&account_id, &query_date)
+            .select(GoalsAllocation::as_select())
+            .load::<GoalsAllocation>(conn)?)
+    }
 
+    fn get_allocations_for_goal_impl(
+        conn: &mut SqliteConnection,
+        goal_id: String,
+    ) -> Result<Vec<GoalsAllocation>> {
         Ok(goals_allocation::table
             .filter(goals_allocation::goal_id.eq(goal_id))
             .select(GoalsAllocation::as_select())
-            .load::<GoalsAllocation>(&mut conn)?)
+            .load::<GoalsAllocation>(conn)?)
     }
 
-    pub fn get_allocation_versions_impl(
-        &self,
-        allocation_id: &str,
+    fn get_allocation_versions_impl(
+        conn: &mut SqliteConnection,
+        allocation_id: String,
     ) -> Result<Vec<AllocationVersion>> {
-        let mut conn = get_connection(&self.pool)?;
-        let allocation_id = allocation_id.to_string();
-
-        Ok(allocation_versions::table
-            .filter(allocation_versions::allocation_id.eq(allocation_id))
-            .order_by(allocation_versions::version_start_date.asc())
+        Ok(Self::versions_query(&[allocation_id.as_str()])
             .select(AllocationVersion::as_select())
-            .load::<AllocationVersion>(&mut conn)?)
+            .load::<AllocationVersion>(conn)?)
     }
 
-    pub fn get_allocation_by_id_impl(
-        &self,
-        allocation_id: &str,
+    fn get_allocation_by_id_impl(
+        conn: &mut SqliteConnection,
+        allocation_id: String,
     ) -> Result<GoalsAllocation> {
-        let mut conn = get_connection(&self.pool)?;
-        let allocation_id_str = allocation_id.to_string();
-
         Ok(goals_allocation::table
-            .find(&allocation_id_str)
+            .find(&allocation_id)
             .select(GoalsAllocation::as_select())
-            .first(&mut conn)?)
+            .first(conn)?)
     }
 
-    pub fn get_allocations_for_account_impl(
-        &self,
-        account_id: &str,
+    fn get_allocations_for_account_impl(
+        conn: &mut SqliteConnection,
+        account_id: String,
     ) -> Result<Vec<GoalsAllocation>> {
-        let mut conn = get_connection(&self.pool)?;
-        let account_id = account_id.to_string();
-
         Ok(goals_allocation::table
             .filter(goals_allocation::account_id.eq(account_id))
             .select(GoalsAllocation::as_select())
-            .load::<GoalsAllocation>(&mut conn)?)
+            .load::<GoalsAllocation>(conn)?)
+    }
+
+    fn load_goal_progress_inputs_impl(
+        conn: &mut SqliteConnection,
+        goal_ids: &[String],
+        range_start: NaiveDate,
+        range_end: NaiveDate,
+    ) -> Result<GoalProgressInputs> {
+        if goal_ids.is_empty() {
+            return Ok(GoalProgressInputs::default());
+        }
+
+        let allocations = goals_allocation::table
+            .filter(goals_allocation::goal_id.eq_any(goal_ids))
+            .select(GoalsAllocation::as_select())
+            .load::<GoalsAllocation>(conn)?;
+        if allocations.is_empty() {
+            return Ok(GoalProgressInputs::default());
+        }
+
+        let allocation_ids: Vec<&str> = allocations.iter().map(|a| a.id.as_str()).collect();
+        let mut versions: HashMap<String, Vec<AllocationVersion>> = HashMap::new();
+        for version in Self::versions_query(&allocation_ids)
+            .select(AllocationVersion::as_select())
+            .load::<AllocationVersion>(conn)?
+        {
+            versions
+                .entry(version.allocation_id.clone())
+                .or_default()
+                .push(version);
+        }
+
+        let account_ids: Vec<String> = allocations
+            .iter()
+            .map(|a| a.account_id.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        // The last valuation before the range is the value carried into its first days
+        let placeholders = vec!["?"; account_ids.len()].join(", ");
+        let sql = format!(
+            "WITH RankedValuations AS ( \
+                SELECT \
+                    id, account_id, valuation_date, account_currency, base_currency, \
+                    fx_rate_to_base, cash_balance, investment_market_value, total_value, \
+                    cost_basis, net_contribution, calculated_at, \
+                    ROW_NUMBER() OVER (PARTITION BY account_id ORDER BY valuation_date DESC) as rn \
+                FROM daily_account_valuation \
+                WHERE account_id IN ({}) AND valuation_date < ? \
+            ) \
+            SELECT \
+                id, account_id, valuation_date, account_currency, base_currency, \
+                fx_rate_to_base, cash_balance, investment_market_value, total_value, \
+                cost_basis, net_contribution, calculated_at \
+            FROM RankedValuations \
+            WHERE rn = 1",
+            placeholders
+        );
+        let mut carried_query = sql_query(sql).into_boxed::<Sqlite>();
+        for account_id in &account_ids {
+            carried_query = carried_query.bind::<Text, _>(account_id);
+        }
+        let mut carried: Vec<DailyAccountValuation> = carried_query
+            .bind::<Date, _>(range_start)
+            .load::<DailyAccountValuationDb>(conn)?
+            .into_iter()
+            .map(DailyAccountValuation::from)
+            .collect();
+        // Accounts whose rows all start later may still have archived months before the range
+        let carried_accounts: HashSet<String> =
+            carried.iter().map(|valuation| valuation.account_id.clone()).collect();
+        for account_id in &account_ids {
+            if !carried_accounts.contains(account_id) {
+                carried.extend(valuation_archive::latest_archived_valuation(
+                    conn,
+                    account_id,
+                    Some(range_start),
+                )?);
+            }
+        }
+
+        let in_range = valuation_archive::load_valuations(
+            conn,
+            &account_ids,
+            Some(range_start),
+            Some(range_end),
+        )?;
+
+        // A mortgaged property counts at its equity, the mortgage being in the property's currency
+        let mortgages = load_property_mortgages(conn, &account_ids)?;
+        let mut account_values: HashMap<String, Vec<(NaiveDate, Decimal)>> = HashMap::new();
+        let mut account_contributions: HashMap<String, Vec<(NaiveDate, Decimal)>> =
+            HashMap::new();
+        for valuation in carried.into_iter().chain(in_range) {
+            let owed = mortgages
+                .get(&valuation.account_id)
+                .map_or(Decimal::ZERO, |mortgage| mortgage.balance_on(valuation.valuation_date));
+            // Converted at the day's rate like the value, which keeps the two comparable
+            account_contributions
+                .entry(valuation.account_id.clone())
+                .or_default()
+                .push((
+                    valuation.valuation_date,
+                    valuation.net_contribution * valuation.fx_rate_to_base,
+                ));
+            account_values
+                .entry(valuation.account_id)
+                .or_default()
+                .push((
+                    valuation.valuation_date,
+                    (valuation.total_value - owed) * valuation.fx_rate_to_base,
+                ));
+        }
+
+        Ok(GoalProgressInputs {
+            allocations,
+            versions,
+            account_values,
+            account_contributions,
+        })
+    }
+
+    /// Stored monthly progress of the allocation's goal no longer holds once it changes
+    fn clear_allocation_goal_progress(conn: &mut SqliteConnection, allocation_id: &str) -> Result<()> {
+        let allocation_goal_id: Option<String> = goals_allocation::table
+            .find(allocation_id)
+            .select(goals_allocation::goal_id)
+            .first(conn)
+            .optional()?;
+        match allocation_goal_id {
+            Some(allocation_goal_id) => monthly_summaries::clear_goal_progress(conn, Some(&allocation_goal_id), None),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes the whole batch, then checks every account it touched against the 100% cap.
+    /// Run inside the writer's transaction, an account above 100% rolls the batch back.
+    fn upsert_goal_allocations_impl(
+        conn: &mut SqliteConnection,
+        allocations: &[GoalsAllocation],
+    ) -> Result<usize> {
+        let mut affected_rows = 0;
+        for allocation in allocations {
+            affected_rows += diesel::insert_into(goals_allocation::table)
+                .values(allocation)
+                .on_conflict(goals_allocation::id)
+                .do_update()
+                .set(allocation)
+                .execute(conn)?;
+            monthly_summaries::clear_goal_progress(conn, Some(&allocation.goal_id), None)?;
+        }
+        Self::check_allocation_caps(conn, allocations)?;
+        Ok(affected_rows)
+    }
+
+    /// Inserts allocations with their versions, then checks the accounts they belong to
+    /// against the 100% cap; run inside the writer's transaction like the upsert
+    fn bulk_insert_allocations_impl(
+        conn: &mut SqliteConnection,
+        allocations: &[GoalsAllocation],
+        versions: &[AllocationVersion],
+    ) -> Result<(usize, usize)> {
+        let mut inserted_allocations = 0;
+        for chunk in allocations.chunks(500) {
+            inserted_allocations += diesel::insert_into(goals_allocation::table)
+                .values(chunk)
+                .execute(conn)?;
+        }
+        let mut inserted_versions = 0;
+        for chunk in versions.chunks(500) {
+            inserted_versions += diesel::insert_into(allocation_versions::table)
+                .values(chunk)
+                .execute(conn)?;
+        }
+        let allocation_goal_ids: HashSet<&str> =
+            allocations.iter().map(|a| a.goal_id.as_str()).collect();
+        for allocation_goal_id in allocation_goal_ids {
+            monthly_summaries::clear_goal_progress(conn, Some(allocation_goal_id), None)?;
+        }
+        Self::check_allocation_caps(conn, allocations)?;
+        Ok((inserted_allocations, inserted_versions))
+    }
+
+    /// Inserts the goals of an imported plan, then their allocations and versions like a bulk
+    /// insert. Archive dates are set last, since allocations are only added to goals in use.
+    fn import_goal_plan_impl(
+        conn: &mut SqliteConnection,
+        plan_goals: &[Goal],
+        allocations: &[GoalsAllocation],
+        versions: &[AllocationVersion],
+    ) -> Result<(usize, usize)> {
+        for goal in plan_goals {
+            diesel::insert_into(goals::table)
+                .values(&Goal {
+                    archived_at: None,
+                    ..goal.clone()
+                })
+                .execute(conn)?;
+        }
+        let inserted = Self::bulk_insert_allocations_impl(conn, allocations, versions)?;
+        for goal in plan_goals.iter().filter(|goal| goal.archived_at.is_some()) {
+            Self::set_goal_archived_impl(conn, &goal.id, goal.archived_at.clone())?;
+        }
+        Ok(inserted)
+    }
+
+    /// Checks the accounts of `written` against the 100% cap as stored, on every day a
+    /// written allocation covers. Each allocation counts with the percentage of its version on
+    /// the day, so allocations that do not overlap in time never add up. Allocations of
+    /// archived goals do not count.
+    fn check_allocation_caps(
+        conn: &mut SqliteConnection,
+        written: &[GoalsAllocation],
+    ) -> Result<()> {
+        let written_ids: HashSet<&str> = written.iter().map(|a| a.id.as_str()).collect();
+        let account_ids: HashSet<&str> = written.iter().map(|a| a.account_id.as_str()).collect();
+        let (new, stored): (Vec<GoalsAllocation>, Vec<GoalsAllocation>) = goals_allocation::table
+            .inner_join(goals::table.on(goals::id.eq(goals_allocation::goal_id)))
+            .filter(goals::archived_at.is_null())
+            .filter(goals_allocation::account_id.eq_any(account_ids))
+            .select(GoalsAllocation::as_select())
+            .load::<GoalsAllocation>(conn)?
+            .into_iter()
+            .partition(|allocation| written_ids.contains(allocation.id.as_str()));
+        let allocation_ids: Vec<&str> = new.iter().chain(&stored).map(|a| a.id.as_str()).collect();
+        let versions = Self::versions_query(&allocation_ids)
+            .select(AllocationVersion::as_select())
+            .load::<AllocationVersion>(conn)?;
+
+        let mut periods = BTreeMap::new();
+        allocation_periods(&stored, &versions, false, &mut periods);
+        allocation_periods(&new, &versions, true, &mut periods);
+        check_new_periods(&periods)
+    }
+
+    /// Saves an allocation; when its percentage or amount changes, the open version ends the
+    /// day before `today` and the new values are versioned from `today`
+    fn update_allocation_impl(
+        conn: &mut SqliteConnection,
+        allocation: &GoalsAllocation,
+        today: NaiveDate,
+    ) -> Result<GoalsAllocation> {
+        let previous = Self::get_allocation_by_id_impl(conn, allocation.id.clone())?;
+        diesel::update(goals_allocation::table.find(&allocation.id))
+            .set(allocation)
+            .execute(conn)?;
+        Self::clear_allocation_goal_progress(conn, &allocation.id)?;
+        if previous.allocation_percentage != allocation.allocation_percentage
+            || previous.allocation_amount != allocation.allocation_amount
+        {
+            Self::capture_allocation_version(conn, &previous, allocation, today)?;
+        }
+        Self::get_allocation_by_id_impl(conn, allocation.id.clone())
+    }
+
+    fn capture_allocation_version(
+        conn: &mut SqliteConnection,
+        previous: &GoalsAllocation,
+        allocation: &GoalsAllocation,
+        today: NaiveDate,
+    ) -> Result<()> {
+        let today_str = today.format("%Y-%m-%d").to_string();
+        let yesterday = today.pred_opt().unwrap_or(today).format("%Y-%m-%d").to_string();
+        let versions = Self::get_allocation_versions_impl(conn, allocation.id.clone())?;
+        match versions.iter().find(|v| v.version_end_date.is_none()) {
+            // A version that only starts today is corrected rather than closed
+            Some(open) if open.version_start_date >= today_str => {
+                diesel::update(allocation_versions::table.find(&open.id))
+                    .set((
+                        allocation_versions::allocation_percentage
+                            .eq(allocation.allocation_percentage),
+                        allocation_versions::allocation_amount.eq(allocation.allocation_amount),
+                    ))
+                    .execute(conn)?;
+                return Ok(());
+            }
+            Some(open) => {
+                diesel::update(allocation_versions::table.find(&open.id))
+                    .set(allocation_versions::version_end_date.eq(&yesterday))
+                    .execute(conn)?;
+            }
+            None if versions.is_empty() => {
+                // Without versions the allocation's own values stand for its whole period, so
+                // they are kept as the first version before they are replaced
+                let earlier_start = previous
+                    .start_date
+                    .clone()
+                    .filter(|start| *start < today_str);
+                if let Some(start) = earlier_start {
+                    Self::insert_version(conn, previous, start, Some(yesterday))?;
+                }
+            }
+            None => {}
+        }
+        Self::insert_version(conn, allocation, today_str, None)
+    }
+
+    fn insert_version(
+        conn: &mut SqliteConnection,
+        allocation: &GoalsAllocation,
+        version_start_date: String,
+        version_end_date: Option<String>,
+    ) -> Result<()> {
+        diesel::insert_into(allocation_versions::table)
+            .values(&AllocationVersion {
+                id: Uuid::new_v4().to_string(),
+                allocation_id: allocation.id.clone(),
+                allocation_percentage: allocation.allocation_percentage,
+                allocation_amount: allocation.allocation_amount,
+                version_start_date,
+                version_end_date,
+                created_at: Utc::now().to_rfc3339(),
+            })
+            .execute(conn)?;
+        Ok(())
     }
 }
 
 #[async_trait]
 impl GoalRepositoryTrait for GoalRepository {
-    fn load_goals(&self) -> Result<Vec<Goal>> {
-        self.load_goals_impl()
+    async fn load_goals(&self) -> Result<Vec<Goal>> {
+        spawn_read(&self.pool, Self::load_goals_impl).await
+    }
+
+    async fn load_all_goals(&self) -> Result<Vec<Goal>> {
+        spawn_read(&self.pool, Self::load_all_goals_impl).await
+    }
+
+    async fn set_goal_archived(&self, goal_id: &str, archived: Option<String>) -> Result<Goal> {
+        let goal_id = goal_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Goal> {
+                Self::set_goal_archived_impl(conn, &goal_id, archived)
+            })
+            .await
+    }
+
+    async fn set_goal_achieved(&self, goal_id: &str, achieved: Option<String>) -> Result<Goal> {
+        let goal_id = goal_id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Goal> {
+                Self::set_goal_achieved_impl(conn, &goal_id, achieved)
+            })
+            .await
     }
 
     async fn insert_new_goal(&self, new_goal: NewGoal) -> Result<Goal> {
@@ -151,6 +549,16 @@ impl GoalRepositoryTrait for GoalRepository {
                 diesel::update(goals.find(goal_id_owned.clone()))
                     .set(&goal_update_owned)
                     .execute(conn)?;
+                // The changeset skips `None`, and a dependency, currency or achievement date has to
+                // be removable
+                diesel::update(goals.find(goal_id_owned.clone()))
+                    .set((
+                        depends_on_goal_id.eq(goal_update_owned.depends_on_goal_id.clone()),
+                        currency.eq(goal_update_owned.currency.clone()),
+                        achieved_at.eq(goal_update_owned.achieved_at.clone()),
+                    ))
+                    .execute(conn)?;
+                monthly_summaries::clear_goal_progress(conn, Some(&goal_id_owned), None)?;
                 Ok(goals.filter(id.eq(goal_id_owned)).first(conn)?)
             })
             .await
@@ -159,65 +567,84 @@ impl GoalRepositoryTrait for GoalRepository {
     async fn delete_goal(&self, goal_id_to_delete: String) -> Result<usize> {
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                // Goals waiting on this one are free to take cash again
+                diesel::update(goals.filter(depends_on_goal_id.eq(&goal_id_to_delete)))
+                    .set(depends_on_goal_id.eq(None::<String>))
+                    .execute(conn)?;
                 Ok(diesel::delete(goals.find(goal_id_to_delete)).execute(conn)?)
             })
             .await
     }
 
-    fn load_allocations_for_non_achieved_goals(&self) -> Result<Vec<GoalsAllocation>> {
-        self.load_allocations_for_non_achieved_goals_impl()
+    async fn load_allocations_for_non_achieved_goals(&self) -> Result<Vec<GoalsAllocation>> {
+        spawn_read(&self.pool, Self::load_allocations_for_non_achieved_goals_impl).await
     }
 
-    fn load_all_allocations(&self) -> Result<Vec<GoalsAllocation>> {
-        self.load_all_allocations_impl()
+    async fn load_all_allocations(&self) -> Result<Vec<GoalsAllocation>> {
+        spawn_read(&self.pool, Self::load_all_allocations_impl).await
     }
 
-    fn get_allocations_for_account_on_date(
+    #[instrument(name = "goals_repository.allocations_on_date", skip(self))]
+    async fn get_allocations_for_account_on_date(
         &self,
         account_id: &str,
         query_date: &str,
     ) -> Result<Vec<GoalsAllocation>> {
-        self.get_allocations_for_account_on_date(account_id, query_date)
+        let account_id = account_id.to_string();
+        let query_date = query_date.to_string();
+        spawn_read(&self.pool, move |conn| {
+            Self::get_allocations_for_account_on_date_impl(conn, account_id, query_date)
+        })
+        .await
     }
 
     async fn upsert_goal_allocations(&self, allocations: Vec<GoalsAllocation>) -> Result<usize> {
-        let allocations_owned = allocations.clone();
-
+        if allocations.is_empty() {
+            return Ok(0);
+        }
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
-                let mut affected_rows = 0;
-                for allocation in allocations_owned {
-                    affected_rows += diesel::insert_into(goals_allocation::table)
-                        .values(&allocation)
-                        .on_conflict(goals_allocation::id)
-                        .do_update()
-                        .set(&allocation)
-                        .execute(conn)?;
-                }
-                Ok(affected_rows)
+                Self::upsert_goal_allocations_impl(conn, &allocations)
             })
             .await
     }
 
-    fn get_allocations_for_goal(&self, goal_id: &str) -> Result<Vec<GoalsAllocation>> {
-        self.get_allocations_for_goal_impl(goal_id)
+    async fn get_allocations_for_goal(&self, goal_id: &str) -> Result<Vec<GoalsAllocation>> {
+        let goal_id = goal_id.to_string();
+        spawn_read(&self.pool, move |conn| {
+            Self::get_allocations_for_goal_impl(conn, goal_id)
+        })
+        .await
     }
 
-    fn get_allocation_versions(&self, allocation_id: &str) -> Result<Vec<AllocationVersion>> {
-        self.get_allocation_versions_impl(allocation_id)
+    async fn get_allocation_versions(&self, allocation_id: &str) -> Result<Vec<AllocationVersion>> {
+        let allocation_id = allocation_id.to_string();
+        spawn_read(&self.pool, move |conn| {
+            Self::get_allocation_versions_impl(conn, allocation_id)
+        })
+        .await
     }
 
-    fn get_allocation_by_id(&self, allocation_id: &str) -> Result<GoalsAllocation> {
-        self.get_allocation_by_id_impl(allocation_id)
+    async fn get_allocation_by_id(&self, allocation_id: &str) -> Result<GoalsAllocation> {
+        let allocation_id = allocation_id.to_string();
+        spawn_read(&self.pool, move |conn| {
+            Self::get_allocation_by_id_impl(conn, allocation_id)
+        })
+        .await
     }
 
-    fn get_allocations_for_account(&self, account_id: &str) -> Result<Vec<GoalsAllocation>> {
-        self.get_allocations_for_account_impl(account_id)
+    async fn get_allocations_for_account(&self, account_id: &str) -> Result<Vec<GoalsAllocation>> {
+        let account_id = account_id.to_string();
+        spawn_read(&self.pool, move |conn| {
+            Self::get_allocations_for_account_impl(conn, account_id)
+        })
+        .await
     }
 
     async fn insert_allocation_version(&self, version: AllocationVersion) -> Result<AllocationVersion> {
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<AllocationVersion> {
+                Self::clear_allocation_goal_progress(conn, &version.allocation_id)?;
                 Ok(diesel::insert_into(allocation_versions::table)
                     .values(&version)
                     .returning(AllocationVersion::as_select())
@@ -227,18 +654,10 @@ impl GoalRepositoryTrait for GoalRepository {
     }
 
     async fn update_allocation(&self, allocation: GoalsAllocation) -> Result<GoalsAllocation> {
-        let allocation_id_owned = allocation.id.clone();
-        let allocation_owned = allocation.clone();
-
+        let today = Utc::now().date_naive();
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<GoalsAllocation> {
-                diesel::update(goals_allocation::table.find(allocation_id_owned.clone()))
-                    .set(&allocation_owned)
-                    .execute(conn)?;
-                Ok(goals_allocation::table
-                    .filter(goals_allocation::id.eq(allocation_id_owned))
-                    .select(GoalsAllocation::as_select())
-                    .first(conn)?)
+                Self::update_allocation_impl(conn, &allocation, today)
             })
             .await
     }
@@ -246,6 +665,7 @@ impl GoalRepositoryTrait for GoalRepository {
     async fn delete_allocation(&self, allocation_id: String) -> Result<usize> {
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Self::clear_allocation_goal_progress(conn, &allocation_id)?;
                 Ok(diesel::delete(goals_allocation::table.find(allocation_id)).execute(conn)?)
             })
             .await
@@ -254,6 +674,7 @@ impl GoalRepositoryTrait for GoalRepository {
     async fn reset_allocations_for_goal(&self, goal_id_to_reset: String, new_start_date: Option<String>, new_end_date: Option<String>) -> Result<usize> {
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                monthly_summaries::clear_goal_progress(conn, Some(&goal_id_to_reset), None)?;
                 Ok(diesel::update(
                     goals_allocation::table.filter(goals_allocation::goal_id.eq(goal_id_to_reset))
                 )
@@ -273,6 +694,7 @@ impl GoalRepositoryTrait for GoalRepository {
     async fn update_allocations_end_date_for_goal(&self, goal_id_to_update: String, new_end_date: String) -> Result<usize> {
         self.writer
             .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                monthly_summaries::clear_goal_progress(conn, Some(&goal_id_to_update), None)?;
                 Ok(diesel::update(
                     goals_allocation::table.filter(goals_allocation::goal_id.eq(goal_id_to_update))
                 )
@@ -281,4 +703,497 @@ impl GoalRepositoryTrait for GoalRepository {
             })
             .await
     }
+
+    #[instrument(name = "goals_repository.load_progress_inputs", skip_all, fields(goals = goal_ids.len()))]
+    async fn load_goal_progress_inputs(
+        &self,
+        goal_ids: &[String],
+        range_start: NaiveDate,
+        range_end: NaiveDate,
+    ) -> Result<GoalProgressInputs> {
+        let goal_ids = goal_ids.to_vec();
+        spawn_read(&self.pool, move |conn| {
+            Self::load_goal_progress_inputs_impl(conn, &goal_ids, range_start, range_end)
+        })
+        .await
+    }
+
+    async fn load_goal_monthly_progress(
+        &self,
+        goal_ids: &[String],
+        start_month: NaiveDate,
+        end_month: NaiveDate,
+    ) -> Result<Vec<GoalMonthlyProgress>> {
+        let goal_ids = goal_ids.to_vec();
+        spawn_read(&self.pool, move |conn| {
+            monthly_summaries::load_goal_monthly_progress(conn, &goal_ids, start_month, end_month)
+        })
+        .await
+    }
+
+    async fn save_goal_monthly_progress(&self, progress: Vec<GoalMonthlyProgress>) -> Result<usize> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                monthly_summaries::save_goal_monthly_progress(conn, &progress)
+            })
+            .await
+    }
+
+    async fn load_goal_progress(&self, goal_ids: &[String], as_of: NaiveDate) -> Result<Vec<GoalProgressSnapshot>> {
+        let goal_ids = goal_ids.to_vec();
+        spawn_read(&self.pool, move |conn| {
+            goal_progress_history::load_goal_progress(conn, &goal_ids, as_of)
+        })
+        .await
+    }
+
+    async fn save_goal_progress(&self, as_of: NaiveDate, progress: Vec<GoalProgressSnapshot>) -> Result<usize> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                goal_progress_history::save_goal_progress(conn, as_of, &progress)
+            })
+            .await
+    }
+
+    async fn load_goal_progress_between(&self, goal_id: &str, from: NaiveDate, to: NaiveDate) -> Result<Vec<GoalProgressSnapshot>> {
+        let goal_id = goal_id.to_string();
+        spawn_read(&self.pool, move |conn| {
+            goal_progress_history::load_goal_progress_between(conn, &goal_id, from, to)
+        })
+        .await
+    }
+
+    async fn latest_goal_progress_date(&self) -> Result<Option<NaiveDate>> {
+        spawn_read(&self.pool, goal_progress_history::latest_goal_progress_date).await
+    }
+
+    async fn load_allocation_versions(&self, allocation_ids: &[String]) -> Result<Vec<AllocationVersion>> {
+        let allocation_ids = allocation_ids.to_vec();
+        spawn_read(&self.pool, move |conn| {
+            let allocation_ids: Vec<&str> = allocation_ids.iter().map(String::as_str).collect();
+            Ok(Self::versions_query(&allocation_ids)
+                .select(AllocationVersion::as_select())
+                .load::<AllocationVersion>(conn)?)
+        })
+        .await
+    }
+
+    #[instrument(
+        name = "goals_repository.bulk_insert_allocations",
+        skip_all,
+        fields(allocations = allocations.len(), versions = versions.len())
+    )]
+    async fn bulk_insert_allocations(
+        &self,
+        allocations: Vec<GoalsAllocation>,
+        versions: Vec<AllocationVersion>,
+    ) -> Result<(usize, usize)> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<(usize, usize)> {
+                Self::bulk_insert_allocations_impl(conn, &allocations, &versions)
+            })
+            .await
+    }
+
+    #[instrument(
+        name = "goals_repository.import_goal_plan",
+        skip_all,
+        fields(goals = plan_goals.len(), allocations = allocations.len())
+    )]
+    async fn import_goal_plan(
+        &self,
+        plan_goals: Vec<Goal>,
+        allocations: Vec<GoalsAllocation>,
+        versions: Vec<AllocationVersion>,
+    ) -> Result<(usize, usize)> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<(usize, usize)> {
+                Self::import_goal_plan_impl(conn, &plan_goals, &allocations, &versions)
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::run_migrations;
+    use crate::i18n::MessageCode;
+    use diesel::debug_query;
+    use diesel::query_builder::QueryFragment;
+    use diesel::r2d2::ConnectionManager;
+
+    #[derive(QueryableByName)]
+    struct PlanStep {
+        #[diesel(sql_type = Text)]
+        detail: String,
+    }
+
+    fn migrated_connection() -> r2d2::PooledConnection<ConnectionManager<SqliteConnection>> {
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .unwrap();
+        run_migrations(&pool).unwrap();
+        pool.get().unwrap()
+    }
+
+    /// `EXPLAIN QUERY PLAN` of a query, one step per line; binds are left unset
+    fn query_plan(conn: &mut SqliteConnection, query: &impl QueryFragment<Sqlite>) -> String {
+        let sql = debug_query::<Sqlite, _>(query).to_string();
+        let sql = sql.split(" -- binds:").next().unwrap();
+        sql_query(format!("EXPLAIN QUERY PLAN {}", sql))
+            .load::<PlanStep>(conn)
+            .unwrap()
+            .into_iter()
+            .map(|step| step.detail)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn allocation_lookups_stay_indexed() {
+        let mut conn = migrated_connection();
+
+        let on_date = GoalRepository::allocations_on_date_query("broker", "2026-10-18")
+            .select(GoalsAllocation::as_select());
+        let plan = query_plan(&mut conn, &on_date);
+        assert!(
+            plan.contains("USING INDEX idx_goals_allocation_account_dates"),
+            "{}",
+            plan
+        );
+
+        let one = ["allocation-1"];
+        let versions = GoalRepository::versions_query(&one).select(AllocationVersion::as_select());
+        let plan = query_plan(&mut conn, &versions);
+        assert!(
+            plan.contains("USING INDEX idx_allocation_versions_allocation_start"),
+            "{}",
+            plan
+        );
+        assert!(!plan.contains("TEMP B-TREE"), "{}", plan);
+
+        let several = ["allocation-1", "allocation-2"];
+        let versions =
+            GoalRepository::versions_query(&several).select(AllocationVersion::as_select());
+        let plan = query_plan(&mut conn, &versions);
+        assert!(
+            plan.contains("USING INDEX idx_allocation_versions_allocation_start"),
+            "{}",
+            plan
+        );
+    }
+
+    /// Accounts `broker` and `bank` and the goal `house` for allocations to point at
+    fn seed_accounts_and_goal(conn: &mut SqliteConnection) {
+        use diesel::connection::SimpleConnection;
+
+        conn.batch_execute(
+            "INSERT INTO accounts (id, name, account_type, currency, is_default, is_active, created_at, updated_at)
+             VALUES ('broker', 'Broker', 'SECURITIES', 'VND', 0, 1, '2026-01-01 00:00:00', '2026-01-01 00:00:00'),
+                    ('bank', 'Bank', 'CASH', 'VND', 0, 1, '2026-01-01 00:00:00', '2026-01-01 00:00:00');
+             INSERT INTO goals (id, title, target_amount, is_achieved, goal_type)
+             VALUES ('house', 'House', 1000000000, 0, 'STANDARD');",
+        )
+        .unwrap();
+    }
+
+    fn allocation(allocation_id: &str, account: &str, percent: f64) -> GoalsAllocation {
+        GoalsAllocation {
+            id: allocation_id.to_string(),
+            goal_id: "house".to_string(),
+            account_id: account.to_string(),
+            init_amount: 0.0,
+            allocation_percentage: percent,
+            allocation_date: None,
+            percent_allocation: percent as i32,
+            start_date: Some("2026-01-01".to_string()),
+            end_date: None,
+            allocation_amount: 0.0,
+        }
+    }
+
+    #[test]
+    fn allocation_batch_is_rolled_back_when_an_account_exceeds_100_percent() {
+        let mut conn = migrated_connection();
+        seed_accounts_and_goal(&mut conn);
+        let upsert = |conn: &mut SqliteConnection, batch: Vec<GoalsAllocation>| {
+            conn.immediate_transaction(|c| GoalRepository::upsert_goal_allocations_impl(c, &batch))
+        };
+
+        let written = upsert(
+            &mut conn,
+            vec![allocation("a", "broker", 60.0), allocation("b", "broker", 30.0)],
+        )
+        .unwrap();
+        assert_eq!(written, 2);
+
+        // Raising one allocation past the cap also undoes the other account's new row
+        let error = upsert(
+            &mut conn,
+            vec![allocation("c", "bank", 50.0), allocation("b", "broker", 45.0)],
+        )
+        .unwrap_err();
+        let message = error.localized_message().unwrap();
+        assert_eq!(message.code, MessageCode::GoalAllocationExceedsLimitOnDate);
+
+        let stored: Vec<(String, f64)> = goals_allocation::table
+            .select((goals_allocation::id, goals_allocation::allocation_percentage))
+            .order_by(goals_allocation::id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(stored, vec![("a".to_string(), 60.0), ("b".to_string(), 30.0)]);
+
+        // An update that keeps the account at the cap goes through
+        upsert(&mut conn, vec![allocation("b", "broker", 40.0)]).unwrap();
+    }
+
+    #[test]
+    fn the_cap_only_adds_up_allocations_that_overlap_in_time() {
+        use diesel::connection::SimpleConnection;
+
+        let mut conn = migrated_connection();
+        seed_accounts_and_goal(&mut conn);
+        conn.batch_execute(
+            "INSERT INTO goals (id, title, target_amount, is_achieved, goal_type, archived_at)
+             VALUES ('boat', 'Boat', 500000000, 0, 'STANDARD', '2026-09-01T00:00:00+00:00');",
+        )
+        .unwrap();
+        let upsert = |conn: &mut SqliteConnection, batch: Vec<GoalsAllocation>| {
+            conn.immediate_transaction(|c| GoalRepository::upsert_goal_allocations_impl(c, &batch))
+        };
+        let dated = |allocation_id: &str, percent: f64, start: &str, end: Option<&str>| {
+            let mut dated = allocation(allocation_id, "broker", percent);
+            dated.start_date = Some(start.to_string());
+            dated.end_date = end.map(str::to_string);
+            dated
+        };
+
+        // 60% until June and 70% from July never hold at the same time
+        upsert(
+            &mut conn,
+            vec![
+                dated("first-half", 60.0, "2026-01-01", Some("2026-06-30")),
+                dated("second-half", 70.0, "2026-07-01", None),
+            ],
+        )
+        .unwrap();
+        // An archived goal's allocation does not count either
+        let mut archived = allocation("boat-broker", "broker", 50.0);
+        archived.goal_id = "boat".to_string();
+        upsert(&mut conn, vec![archived]).unwrap();
+
+        // Each day counts the version in force: 30% until March, then 60%
+        conn.batch_execute(
+            "INSERT INTO allocation_versions (id, allocation_id, allocation_percentage, allocation_amount, version_start_date, version_end_date, created_at)
+             VALUES ('v1', 'first-half', 30, 0, '2026-01-01', '2026-03-31', '2026-04-01'),
+                    ('v2', 'first-half', 60, 0, '2026-04-01', NULL, '2026-04-01');",
+        )
+        .unwrap();
+        upsert(
+            &mut conn,
+            vec![dated("spring", 40.0, "2026-02-01", Some("2026-03-31"))],
+        )
+        .unwrap();
+        let summer = dated("summer", 50.0, "2026-06-01", Some("2026-06-30"));
+        let error = upsert(&mut conn, vec![summer]).unwrap_err();
+        let message = error.localized_message().unwrap();
+        assert_eq!(message.code, MessageCode::GoalAllocationExceedsLimitOnDate);
+        assert_eq!(message.params["date"], "2026-06-01");
+
+        // Bulk inserts are held to the same cap in their own transaction
+        let bulk = |conn: &mut SqliteConnection, batch: Vec<GoalsAllocation>| {
+            conn.immediate_transaction(|c| {
+                GoalRepository::bulk_insert_allocations_impl(c, &batch, &[])
+            })
+        };
+        bulk(&mut conn, vec![dated("autumn", 30.0, "2026-09-01", None)]).unwrap();
+        assert!(bulk(&mut conn, vec![dated("winter", 1.0, "2026-12-01", None)]).is_err());
+        let stored: Vec<String> = goals_allocation::table
+            .select(goals_allocation::id)
+            .order_by(goals_allocation::id)
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(
+            stored,
+            [
+                "autumn",
+                "boat-broker",
+                "first-half",
+                "second-half",
+                "spring"
+            ]
+        );
+    }
+
+    #[test]
+    fn changing_an_allocation_closes_its_version_and_opens_a_new_one() {
+        let mut conn = migrated_connection();
+        seed_accounts_and_goal(&mut conn);
+        GoalRepository::upsert_goal_allocations_impl(&mut conn, &[allocation("a", "broker", 40.0)])
+            .unwrap();
+        let update = |conn: &mut SqliteConnection, percent: f64, end_date: &str, today: &str| {
+            let mut changed = allocation("a", "broker", percent);
+            changed.end_date = Some(end_date.to_string());
+            let today = NaiveDate::parse_from_str(today, "%Y-%m-%d").unwrap();
+            conn.immediate_transaction(|c| {
+                GoalRepository::update_allocation_impl(c, &changed, today)
+            })
+            .unwrap()
+        };
+        let versions = |conn: &mut SqliteConnection| -> Vec<(f64, String, Option<String>)> {
+            GoalRepository::get_allocation_versions_impl(conn, "a".to_string())
+                .unwrap()
+                .into_iter()
+                .map(|v| (v.allocation_percentage, v.version_start_date, v.version_end_date))
+                .collect()
+        };
+
+        // The first change keeps the original share as history
+        update(&mut conn, 50.0, "2030-01-01", "2026-07-01");
+        // A second change on the same day corrects the version opened that day
+        update(&mut conn, 55.0, "2030-01-01", "2026-07-01");
+        // Only the dates change, so there is no new version
+        update(&mut conn, 55.0, "2031-01-01", "2026-09-01");
+        let saved = update(&mut conn, 60.0, "2031-01-01", "2026-10-18");
+        assert_eq!(saved.allocation_percentage, 60.0);
+        assert_eq!(saved.end_date.as_deref(), Some("2031-01-01"));
+
+        let day = |date: &str| date.to_string();
+        assert_eq!(
+            versions(&mut conn),
+            vec![
+                (40.0, day("2026-01-01"), Some(day("2026-06-30"))),
+                (55.0, day("2026-07-01"), Some(day("2026-10-17"))),
+                (60.0, day("2026-10-18"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn archived_goals_are_hidden_until_restored() {
+        let mut conn = migrated_connection();
+        seed_accounts_and_goal(&mut conn);
+        GoalRepository::upsert_goal_allocations_impl(&mut conn, &[allocation("a", "broker", 40.0)])
+            .unwrap();
+
+        let archived = GoalRepository::set_goal_archived_impl(
+            &mut conn,
+            "house",
+            Some("2026-10-18T00:00:00+00:00".to_string()),
+        )
+        .unwrap();
+        assert_eq!(archived.archived_at.as_deref(), Some("2026-10-18T00:00:00+00:00"));
+        assert!(GoalRepository::load_goals_impl(&mut conn).unwrap().is_empty());
+        assert_eq!(GoalRepository::load_all_goals_impl(&mut conn).unwrap().len(), 1);
+        // The allocation stays for when the goal comes back
+        assert_eq!(GoalRepository::load_all_allocations_impl(&mut conn).unwrap().len(), 1);
+
+        let restored = GoalRepository::set_goal_archived_impl(&mut conn, "house", None).unwrap();
+        assert_eq!(restored.archived_at, None);
+        assert_eq!(GoalRepository::load_goals_impl(&mut conn).unwrap().len(), 1);
+
+        let missing = GoalRepository::set_goal_archived_impl(&mut conn, "car", None);
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn restoring_a_goal_is_refused_when_its_share_was_taken_meanwhile() {
+        use diesel::connection::SimpleConnection;
+
+        let mut conn = migrated_connection();
+        seed_accounts_and_goal(&mut conn);
+        conn.batch_execute(
+            "INSERT INTO goals (id, title, target_amount, is_achieved, goal_type)
+             VALUES ('boat', 'Boat', 500000000, 0, 'STANDARD');",
+        )
+        .unwrap();
+        let upsert = |conn: &mut SqliteConnection, batch: Vec<GoalsAllocation>| {
+            conn.immediate_transaction(|c| GoalRepository::upsert_goal_allocations_impl(c, &batch))
+        };
+        let set_archived = |conn: &mut SqliteConnection, archived: Option<&str>| {
+            conn.immediate_transaction(|c| {
+                GoalRepository::set_goal_archived_impl(c, "house", archived.map(str::to_string))
+            })
+        };
+        upsert(&mut conn, vec![allocation("a", "broker", 60.0)]).unwrap();
+        set_archived(&mut conn, Some("2026-10-18T00:00:00+00:00")).unwrap();
+
+        // While the house is archived, the boat takes its share
+        let mut boat = allocation("boat-broker", "broker", 60.0);
+        boat.goal_id = "boat".to_string();
+        upsert(&mut conn, vec![boat.clone()]).unwrap();
+
+        let error = set_archived(&mut conn, None).unwrap_err();
+        let message = error.localized_message().unwrap();
+        assert_eq!(message.code, MessageCode::GoalAllocationExceedsLimitOnDate);
+        assert!(GoalRepository::load_goals_impl(&mut conn)
+            .unwrap()
+            .iter()
+            .all(|goal| goal.id == "boat"));
+
+        // Once the boat gives enough back, the house can return
+        boat.allocation_percentage = 40.0;
+        upsert(&mut conn, vec![boat]).unwrap();
+        let restored = set_archived(&mut conn, None).unwrap();
+        assert_eq!(restored.archived_at, None);
+    }
+
+    #[test]
+    fn a_goal_plan_is_imported_whole_or_not_at_all() {
+        let mut conn = migrated_connection();
+        seed_accounts_and_goal(&mut conn);
+        let house = GoalRepository::load_goals_impl(&mut conn).unwrap().remove(0);
+        let plan_goal = |goal_id: &str, archived: Option<&str>| Goal {
+            id: goal_id.to_string(),
+            title: goal_id.to_string(),
+            archived_at: archived.map(str::to_string),
+            ..house.clone()
+        };
+        let plan_allocation = |allocation_id: &str, goal_id: &str, percent: f64| GoalsAllocation {
+            goal_id: goal_id.to_string(),
+            ..allocation(allocation_id, "broker", percent)
+        };
+        let import = |conn: &mut SqliteConnection, allocations: Vec<GoalsAllocation>| {
+            let plan_goals = [plan_goal("car", None), plan_goal("boat", Some("2026-09-01"))];
+            conn.immediate_transaction(|c| {
+                GoalRepository::import_goal_plan_impl(c, &plan_goals, &allocations, &[])
+            })
+        };
+        GoalRepository::upsert_goal_allocations_impl(&mut conn, &[allocation("a", "broker", 40.0)])
+            .unwrap();
+
+        // The second allocation takes the broker over 100%, so the goals go as well
+        let error = import(
+            &mut conn,
+            vec![
+                plan_allocation("car-broker", "car", 30.0),
+                plan_allocation("boat-broker", "boat", 40.0),
+            ],
+        )
+        .unwrap_err();
+        let message = error.localized_message().unwrap();
+        assert_eq!(message.code, MessageCode::GoalAllocationExceedsLimitOnDate);
+        assert_eq!(GoalRepository::load_all_goals_impl(&mut conn).unwrap().len(), 1);
+        assert_eq!(GoalRepository::load_all_allocations_impl(&mut conn).unwrap().len(), 1);
+
+        let inserted = import(
+            &mut conn,
+            vec![
+                plan_allocation("car-broker", "car", 30.0),
+                plan_allocation("boat-broker", "boat", 30.0),
+            ],
+        )
+        .unwrap();
+        assert_eq!(inserted, (2, 0));
+        let mut active: Vec<String> = GoalRepository::load_goals_impl(&mut conn)
+            .unwrap()
+            .into_iter()
+            .map(|goal| goal.id)
+            .collect();
+        active.sort();
+        assert_eq!(active, ["car", "house"]);
+        assert_eq!(GoalRepository::load_all_goals_impl(&mut conn).unwrap().len(), 3);
+    }
 }
@@ -1,30 +1,272 @@
+use crate::accounts::{Account, AccountRepositoryTrait};
+use crate::cash::CashServiceTrait;
 use crate::errors::Result;
-use crate::goals::goals_model::{Goal, GoalsAllocation, NewGoal};
+use crate::fx::FxServiceTrait;
+use crate::goals::allocation_periods::{
+    allocation_periods, check_new_periods, AllocationPeriod, OPEN_END_DATE,
+};
+use crate::goals::goals_model::{
+    validate_goal_type, AllocationBulkInsertSummary, AllocationChange, AllocationChangePreview,
+    AllocationRebalance, AllocationRebalanceSuggestion, AllocationVersion, DepositDistribution,
+    Goal, GoalChangePreview, GoalDeposit, GoalFundingRequirement, GoalPlanExport,
+    GoalPlanImportResult, GoalsAllocation, NewGoal, DEFAULT_GOAL_INFLATION_RATE,
+    GOAL_PLAN_EXPORT_VERSION, GOAL_TYPE_DEBT_PAYOFF, GOAL_TYPE_EMERGENCY_FUND, GOAL_TYPE_NET_WORTH,
+    UNDATED_GOAL_HORIZON_MONTHS,
+};
 use crate::goals::goals_traits::{GoalRepositoryTrait, GoalServiceTrait};
-use crate::goals::goal_progress_model::{GoalProgressSnapshot, AllocationDetail};
+use crate::goals::goal_progress_model::{
+    AllocationDetail, GoalProgressInputs, GoalProgressSnapshot, NetWorthPoint, ProgressInterval,
+    MAX_PROGRESS_SERIES_POINTS,
+};
+use crate::goals::debt_payoff_service::{load_debt_schedules, DebtSchedule};
+use crate::goals::net_worth_service::net_worth_snapshot;
+use crate::i18n::{LocalizedMessage, MessageCode};
+use crate::loans::LoanRepositoryTrait;
+use crate::portfolio::valuation::ValuationServiceTrait;
 use async_trait::async_trait;
-use std::collections::HashMap;
-use std::sync::Arc;
+use chrono::{Datelike, Days, NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+/// Allocations of one account, either all of them or those active on a date
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct AllocationCacheKey {
+    account_id: String,
+    query_date: Option<String>,
+}
+
+/// Cached allocations, and how many times the cache was cleared. A read that started before a
+/// clear may have loaded what the clear was for, so it is only kept while the count is unchanged.
+#[derive(Default)]
+struct AllocationCache {
+    generation: u64,
+    entries: HashMap<AllocationCacheKey, Vec<GoalsAllocation>>,
+}
+
+/// FX service and the base currency it converts from
+type CurrencyConversion = (Arc<dyn FxServiceTrait>, Arc<RwLock<String>>);
 
 pub struct GoalService<T: GoalRepositoryTrait> {
     goal_repo: Arc<T>,
+    /// Read-through cache of allocations by account, since validating a percentage while it is
+    /// being dragged asks for the same allocations over and over. Cleared whenever allocations
+    /// change, through this service or `invalidate_allocation_cache`.
+    allocation_cache: RwLock<AllocationCache>,
+    /// Converts account values, kept in the base currency, for goals set in another currency
+    fx: Option<CurrencyConversion>,
+    /// Actual cash of accounts, for unallocated balances that need no value from the caller
+    cash: Option<Arc<dyn CashServiceTrait>>,
+    /// Stored daily valuations, for unallocated balances at an account's latest value
+    valuations: Option<Arc<dyn ValuationServiceTrait>>,
+    /// Loans and the accounts holding them, for what debt payoff goals still owe
+    loans: Option<(
+        Arc<dyn LoanRepositoryTrait>,
+        Arc<dyn AccountRepositoryTrait>,
+    )>,
 }
 
 impl<T: GoalRepositoryTrait> GoalService<T> {
     pub fn new(goal_repo: Arc<T>) -> Self {
-        GoalService { goal_repo }
+        GoalService {
+            goal_repo,
+            allocation_cache: RwLock::new(AllocationCache::default()),
+            fx: None,
+            cash: None,
+            valuations: None,
+            loans: None,
+        }
+    }
+
+    /// Reports the progress of goals with their own currency in that currency. Without it
+    /// every goal is reported in the base currency.
+    pub fn with_fx(
+        mut self,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        self.fx = Some((fx_service, base_currency));
+        self
+    }
+
+    /// Lets unallocated balances be worked out from the cash an account actually holds
+    pub fn with_cash(mut self, cash_service: Arc<dyn CashServiceTrait>) -> Self {
+        self.cash = Some(cash_service);
+        self
+    }
+
+    /// Lets unallocated balances be worked out from an account's latest stored valuation
+    pub fn with_valuations(mut self, valuation_service: Arc<dyn ValuationServiceTrait>) -> Self {
+        self.valuations = Some(valuation_service);
+        self
+    }
+
+    /// Lets debt payoff goals follow the amortization schedules of their accounts' loans.
+    /// Without it they follow the accounts' valuations.
+    pub fn with_loans(
+        mut self,
+        loan_repository: Arc<dyn LoanRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+    ) -> Self {
+        self.loans = Some((loan_repository, account_repository));
+        self
+    }
+
+    /// Schedules of the loans on accounts allocated to the debt payoff goals among `goals`,
+    /// by account id
+    fn debt_schedules<'a>(
+        &self,
+        goals: impl IntoIterator<Item = &'a Goal>,
+        inputs: &GoalProgressInputs,
+    ) -> Result<HashMap<String, DebtSchedule>> {
+        let Some((loan_repository, account_repository)) = &self.loans else {
+            return Ok(HashMap::new());
+        };
+        let debt_goals: HashSet<&str> = goals
+            .into_iter()
+            .filter(|goal| goal.goal_type == GOAL_TYPE_DEBT_PAYOFF)
+            .map(|goal| goal.id.as_str())
+            .collect();
+        if debt_goals.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let accounts = inputs
+            .allocations
+            .iter()
+            .filter(|allocation| debt_goals.contains(allocation.goal_id.as_str()))
+            .map(|allocation| allocation.account_id.as_str())
+            .collect::<BTreeSet<&str>>()
+            .into_iter()
+            .map(|account_id| account_repository.get_by_id(account_id))
+            .collect::<Result<Vec<Account>>>()?;
+        let (fx_service, base_currency) = match &self.fx {
+            Some((fx_service, base_currency)) => (
+                Some(fx_service.as_ref()),
+                base_currency.read().unwrap().clone(),
+            ),
+            None => (None, String::new()),
+        };
+        load_debt_schedules(
+            loan_repository.as_ref(),
+            fx_service,
+            &base_currency,
+            &accounts,
+        )
+    }
+
+    /// Account values converted from the base currency into the goal's currency at the rate of
+    /// `date`; unchanged when the goal has no currency of its own
+    fn in_goal_currency(
+        &self,
+        goal: &Goal,
+        values: &HashMap<String, f64>,
+        date: NaiveDate,
+    ) -> Result<HashMap<String, f64>> {
+        let (Some(currency), Some((fx_service, base_currency))) =
+            (goal.currency.as_deref(), &self.fx)
+        else {
+            return Ok(values.clone());
+        };
+        let base_currency = base_currency.read().unwrap().clone();
+        if currency == base_currency {
+            return Ok(values.clone());
+        }
+        let rate = fx_service.get_exchange_rate_for_date(&base_currency, currency, date)?;
+        // A rate that does not fit an f64 would otherwise read as no progress at all
+        let rate = rate.to_f64().ok_or_else(|| {
+            crate::errors::Error::InvalidExchangeRate(format!(
+                "{} to {} on {} is out of range: {}",
+                base_currency, currency, date, rate
+            ))
+        })?;
+        Ok(values
+            .iter()
+            .map(|(account_id, value)| (account_id.clone(), value * rate))
+            .collect())
+    }
+
+    async fn cached_allocations(
+        &self,
+        key: AllocationCacheKey,
+        load: impl Future<Output = Result<Vec<GoalsAllocation>>>,
+    ) -> Result<Vec<GoalsAllocation>> {
+        let generation = {
+            let cache = self.allocation_cache.read().unwrap();
+            if let Some(allocations) = cache.entries.get(&key) {
+                return Ok(allocations.clone());
+            }
+            cache.generation
+        };
+        let allocations = load.await?;
+        let mut cache = self.allocation_cache.write().unwrap();
+        if cache.generation == generation {
+            cache.entries.insert(key, allocations.clone());
+        }
+        Ok(allocations)
+    }
+
+    /// All allocations of an account, served from the cache when possible
+    pub async fn get_allocations_for_account(
+        &self,
+        account_id: &str,
+    ) -> Result<Vec<GoalsAllocation>> {
+        let key = AllocationCacheKey {
+            account_id: account_id.to_string(),
+            query_date: None,
+        };
+        self.cached_allocations(key, self.goal_repo.get_allocations_for_account(account_id))
+            .await
+    }
+
+    /// Allocations of an account that count toward its 100% cap; those of archived goals do not
+    async fn capped_allocations_for_account(
+        &self,
+        account_id: &str,
+    ) -> Result<Vec<GoalsAllocation>> {
+        let archived_goal_ids: HashSet<String> = self
+            .goal_repo
+            .load_all_goals()
+            .await?
+            .into_iter()
+            .filter(|goal| goal.archived_at.is_some())
+            .map(|goal| goal.id)
+            .collect();
+        Ok(self
+            .get_allocations_for_account(account_id)
+            .await?
+            .into_iter()
+            .filter(|allocation| !archived_goal_ids.contains(&allocation.goal_id))
+            .collect())
     }
 
-    pub fn get_allocations_for_account_on_date(
+    pub async fn get_allocations_for_account_on_date(
         &self,
         account_id: &str,
         query_date: &str,
     ) -> Result<Vec<GoalsAllocation>> {
-        self.goal_repo
-            .get_allocations_for_account_on_date(account_id, query_date)
+        let key = AllocationCacheKey {
+            account_id: account_id.to_string(),
+            query_date: Some(query_date.to_string()),
+        };
+        self.cached_allocations(
+            key,
+            self.goal_repo
+                .get_allocations_for_account_on_date(account_id, query_date),
+        )
+        .await
+    }
+
+    /// Drops every cached allocation; call it when allocations change outside this service
+    pub fn invalidate_allocation_cache(&self) {
+        let mut cache = self.allocation_cache.write().unwrap();
+        cache.generation += 1;
+        cache.entries.clear();
     }
 
-    pub fn validate_allocation_conflicts(
+    pub async fn validate_allocation_conflicts(
         &self,
         account_id: &str,
         new_start_date: &str,
@@ -36,7 +278,7 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
         // Use validate_allocation_percentages() instead for new hybrid system
 
         // Get allocations that overlap with the new allocation's date range
-        let allocations = self.goal_repo.load_allocations_for_non_achieved_goals()?;
+        let allocations = self.goal_repo.load_allocations_for_non_achieved_goals().await?;
 
         let mut conflicting_percent = new_percent_allocation as f64;
 
@@ -62,14 +304,10 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
         }
 
         if conflicting_percent > 100.0 {
-            return Err(crate::errors::Error::Validation(
-                crate::errors::ValidationError::InvalidInput(
-                    format!(
-                        "Total allocation {:.1}% exceeds 100% on account {} during this period",
-                        conflicting_percent, account_id
-                    )
-                )
-            ));
+            return Err(LocalizedMessage::new(MessageCode::GoalAllocationExceedsLimitInPeriod)
+                .with_param("percent", format!("{:.1}", conflicting_percent))
+                .with_param("account", account_id)
+                .into());
         }
 
         Ok(())
@@ -78,109 +316,160 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
     /// Calculate goal progress on a specific date
     /// Parameters:
     ///   goal: The goal to calculate progress for
-    ///   account_values_at_goal_start: Map of account_id -> value at goal.start_date
-    ///   current_account_values: Map of account_id -> current value at query_date
+    ///   account_values_at_goal_start: Map of account_id -> value at goal.start_date, in the
+    ///     base currency
+    ///   current_account_values: Map of account_id -> current value at query_date, in the base
+    ///     currency
+    ///   net_worth_series: Net worth history, read instead of the account values by net-worth goals
     ///   query_date: The date to calculate progress for (format: YYYY-MM-DD)
-    pub fn calculate_goal_progress_on_date(
+    pub async fn calculate_goal_progress_on_date(
         &self,
         goal: &Goal,
         account_values_at_goal_start: &HashMap<String, f64>,
         current_account_values: &HashMap<String, f64>,
+        net_worth_series: &[NetWorthPoint],
         query_date: &str,
     ) -> Result<GoalProgressSnapshot> {
-        // Ensure goal has a start_date (validates goal structure)
-        let _goal_start_date = goal
-            .start_date
-            .as_ref()
-            .ok_or_else(|| {
-                crate::errors::Error::Validation(
-                    crate::errors::ValidationError::InvalidInput(
-                        "Goal must have a start_date".to_string(),
-                    ),
-                )
-            })?;
-
-        // Get allocations active on the query_date
-        let active_allocations = self.goal_repo.get_allocations_for_account_on_date("", query_date)?;
+        let allocations = self.goal_repo.get_allocations_for_goal(&goal.id).await?;
+        let (start_values, current_values) = if goal.currency.is_some() {
+            let parse = |date: &str| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|e| invalid_input(format!("Invalid date '{}': {}", date, e)))
+            };
+            let start = parse(goal.start_date.as_deref().unwrap_or(query_date))?;
+            (
+                self.in_goal_currency(goal, account_values_at_goal_start, start)?,
+                self.in_goal_currency(goal, current_account_values, parse(query_date)?)?,
+            )
+        } else {
+            (account_values_at_goal_start.clone(), current_account_values.clone())
+        };
+        // Values handed in come without contributions, so all of the growth counts as returns
+        goal_progress_snapshot(
+            goal,
+            allocations.iter(),
+            &AccountFigures {
+                values: start_values,
+                ..Default::default()
+            },
+            &AccountFigures {
+                values: current_values,
+                ..Default::default()
+            },
+            net_worth_series,
+            query_date,
+        )
+    }
 
-        // Filter for allocations of this goal only
-        let goal_allocations: Vec<_> = active_allocations
+    /// Calculate the progress of several goals on a specific date in one pass: allocations and
+    /// account values of all of them are loaded together rather than goal by goal.
+    /// Goals without a valid start_date are skipped. A goal with its own currency has the
+    /// values at its start and on the day converted at the rates of those days, so currency
+    /// moves count towards its growth.
+    pub async fn calculate_goals_progress_on_date(
+        &self,
+        goals: &[Goal],
+        net_worth_series: &[NetWorthPoint],
+        query_date: NaiveDate,
+    ) -> Result<Vec<GoalProgressSnapshot>> {
+        let started: Vec<(&Goal, NaiveDate)> = goals
             .iter()
-            .filter(|a| a.goal_id == goal.id)
+            .filter_map(|goal| {
+                let start = goal
+                    .start_date
+                    .as_deref()
+                    .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+                if start.is_none() {
+                    log::warn!("Goal {} has no valid start_date, skipping its progress", goal.id);
+                }
+                start.map(|start| (goal, start))
+            })
             .collect();
 
-        let mut total_growth = 0.0;
-        let mut allocation_details = Vec::new();
-
-        for allocation in goal_allocations {
-            let account_value_at_start = account_values_at_goal_start
-                .get(&allocation.account_id)
-                .copied()
-                .unwrap_or(0.0);
-
-            let current_account_value = current_account_values
-                .get(&allocation.account_id)
-                .copied()
-                .unwrap_or(0.0);
-
-            let account_growth = current_account_value - account_value_at_start;
-            let allocation_percent = allocation.percent_allocation as f64 / 100.0;
-            let allocated_growth = account_growth * allocation_percent;
-
-            total_growth += allocated_growth;
+        // Net-worth goals read the series, so only the others need allocations and values
+        let allocated: Vec<&(&Goal, NaiveDate)> = started
+            .iter()
+            .filter(|(goal, _)| goal.goal_type != GOAL_TYPE_NET_WORTH)
+            .collect();
+        let inputs = match allocated.iter().map(|(_, start)| *start).min() {
+            Some(earliest_start) => {
+                let goal_ids: Vec<String> =
+                    allocated.iter().map(|(goal, _)| goal.id.clone()).collect();
+                self.goal_repo.load_goal_progress_inputs(
+                    &goal_ids,
+                    earliest_start.min(query_date),
+                    query_date,
+                ).await?
+            }
+            None => GoalProgressInputs::default(),
+        };
 
-            allocation_details.push(AllocationDetail {
-                account_id: allocation.account_id.clone(),
-                percent_allocation: allocation.percent_allocation,
-                account_value_at_goal_start: account_value_at_start,
-                account_current_value: current_account_value,
-                account_growth,
-                allocated_growth,
-            });
-        }
+        let query_date_str = query_date.format("%Y-%m-%d").to_string();
+        let debts = self.debt_schedules(allocated.iter().map(|(goal, _)| *goal), &inputs)?;
+        let figures_on = |goal: &Goal, date: NaiveDate| -> Result<AccountFigures> {
+            let figures = if goal.goal_type == GOAL_TYPE_DEBT_PAYOFF {
+                AccountFigures::owed_on(&inputs, &debts, date)
+            } else {
+                AccountFigures::on(&inputs, date)
+            };
+            Ok(AccountFigures {
+                values: self.in_goal_currency(goal, &figures.values, date)?,
+                contributions: self.in_goal_currency(goal, &figures.contributions, date)?,
+            })
+        };
+        started
+            .iter()
+            .map(|(goal, start)| {
+                goal_progress_snapshot(
+                    goal,
+                    inputs.allocations_for_goal(&goal.id),
+                    &figures_on(goal, *start)?,
+                    &figures_on(goal, query_date)?,
+                    net_worth_series,
+                    &query_date_str,
+                )
+            })
+            .collect()
+    }
 
-        Ok(GoalProgressSnapshot {
-            goal_id: goal.id.clone(),
-            goal_title: goal.title.clone(),
-            query_date: query_date.to_string(),
-            init_value: 0.0, // Always 0 under new logic
-            current_value: total_growth,
-            growth: total_growth, // growth = current_value - init_value = total_growth - 0
-            allocation_details,
-        })
+    /// Progress of one goal on `query_date`, computed from the stored account valuations rather
+    /// than values handed in by the caller; `None` for a goal without a start date. Net-worth
+    /// goals are read from the net-worth series, so `goal_progress_history::get_goal_progress`
+    /// is the reader for those.
+    pub async fn get_goal_progress(
+        &self,
+        goal_id: &str,
+        query_date: NaiveDate,
+    ) -> Result<Option<GoalProgressSnapshot>> {
+        let goal = self.find_goal(goal_id).await?;
+        let progress = self
+            .calculate_goals_progress_on_date(std::slice::from_ref(&goal), &[], query_date)
+            .await?;
+        Ok(progress.into_iter().next())
     }
 
     /// Get all active allocations for a specific goal on a given date
-    pub fn get_goal_allocations_on_date(
+    pub async fn get_goal_allocations_on_date(
         &self,
         goal_id: &str,
         query_date: &str,
     ) -> Result<Vec<GoalsAllocation>> {
-        let all_allocations = self.goal_repo.load_allocations_for_non_achieved_goals()?;
+        let all_allocations = self.goal_repo.load_allocations_for_non_achieved_goals().await?;
 
         Ok(all_allocations
             .into_iter()
-            .filter(|a| {
-                if a.goal_id != goal_id {
-                    return false;
-                }
-                // Check if date is within allocation's active range
-                match (&a.start_date, &a.end_date) {
-                    (Some(start), Some(end)) => start.as_str() <= query_date && query_date <= end.as_str(),
-                    _ => false, // Allocation without dates is not active
-                }
-            })
+            .filter(|a| a.goal_id == goal_id && is_active_on(a, query_date))
             .collect())
     }
 
     /// Get unallocated balance for an account on a given date
     /// Unallocated = account_current_value - sum(all_goal_allocations)
-    pub fn get_unallocated_balance(
+    pub async fn get_unallocated_balance(
         &self,
         account_id: &str,
         current_account_value: f64,
     ) -> Result<f64> {
-        let allocations = self.goal_repo.get_allocations_for_account(account_id)?;
+        let allocations = self.get_allocations_for_account(account_id).await?;
 
         let total_allocated: f64 = allocations
             .iter()
@@ -190,14 +479,84 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
         Ok((current_account_value - total_allocated).max(0.0))
     }
 
+    /// Unallocated part of the cash an account holds, in the account's currency
+    pub async fn get_unallocated_cash(&self, account_id: &str) -> Result<f64> {
+        let Some(cash_service) = &self.cash else {
+            return Err(invalid_input(
+                "Cash balances are not available to the goal service".to_string(),
+            ));
+        };
+        let cash = cash_service.get_account_cash(account_id)?;
+        self.get_unallocated_balance(account_id, cash.total.to_f64().unwrap_or_default())
+            .await
+    }
+
+    /// Unallocated part of an account's latest stored value, in the account's currency; the
+    /// account has nothing unallocated until it has been valued
+    pub async fn get_unallocated_value(&self, account_id: &str) -> Result<f64> {
+        let Some(valuation_service) = &self.valuations else {
+            return Err(invalid_input(
+                "Account valuations are not available to the goal service".to_string(),
+            ));
+        };
+        let value = valuation_service
+            .get_latest_valuations(&[account_id.to_string()])?
+            .into_iter()
+            .next()
+            .map_or(0.0, |valuation| valuation.total_value.to_f64().unwrap_or_default());
+        self.get_unallocated_balance(account_id, value).await
+    }
+
+    /// Unallocated balance of several accounts together, such as an account group's members,
+    /// from the current value of each
+    pub async fn get_unallocated_balance_for_accounts(
+        &self,
+        account_values: &HashMap<String, f64>,
+    ) -> Result<f64> {
+        let mut unallocated = 0.0;
+        for (account_id, value) in account_values {
+            unallocated += self.get_unallocated_balance(account_id, *value).await?;
+        }
+        Ok(unallocated)
+    }
+
+    /// Funds a goal from several accounts at once, such as an account group's members, with
+    /// one allocation of `percentage` in each. Validated like any bulk insert: nothing is saved
+    /// if any account would go over 100%.
+    pub async fn allocate_goal_to_accounts(
+        &self,
+        goal_id: &str,
+        account_ids: &[String],
+        percentage: f64,
+    ) -> Result<AllocationBulkInsertSummary> {
+        let allocations = account_ids
+            .iter()
+            .map(|account_id| GoalsAllocation {
+                id: Uuid::new_v4().to_string(),
+                goal_id: goal_id.to_string(),
+                account_id: account_id.clone(),
+                init_amount: 0.0,
+                allocation_percentage: percentage,
+                allocation_date: None,
+                percent_allocation: percentage.round() as i32,
+                start_date: None,
+                end_date: None,
+                allocation_amount: 0.0,
+            })
+            .collect();
+        GoalServiceTrait::bulk_insert_allocations(self, allocations, Vec::new()).await
+    }
+
     /// Validate that unallocated balance is sufficient for a new allocation
-    pub fn validate_unallocated_balance(
+    pub async fn validate_unallocated_balance(
         &self,
         account_id: &str,
         allocation_amount: f64,
         current_account_value: f64,
     ) -> Result<()> {
-        let unallocated = self.get_unallocated_balance(account_id, current_account_value)?;
+        let unallocated = self
+            .get_unallocated_balance(account_id, current_account_value)
+            .await?;
 
         if allocation_amount > unallocated {
             return Err(crate::errors::Error::Validation(
@@ -213,39 +572,323 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
         Ok(())
     }
 
-    /// Validate that total allocation percentages don't exceed 100%
-    pub fn validate_allocation_percentages(
+    /// Validate that total allocation percentages don't exceed 100% from today on
+    pub async fn validate_allocation_percentages(
         &self,
         account_id: &str,
         new_percentage: f64,
         exclude_allocation_id: Option<&str>,
     ) -> Result<()> {
-        let allocations = self.goal_repo.get_allocations_for_account(account_id)?;
+        self.validate_allocation_percentages_from(
+            account_id,
+            new_percentage,
+            exclude_allocation_id,
+            Utc::now().date_naive(),
+        )
+        .await
+    }
 
-        let mut total_percent = new_percentage;
+    /// Validate that `new_percentage`, in force from `from`, keeps the account within 100%.
+    /// Other allocations count with the percentage of their version on each day, so those
+    /// that ended before `from` no longer count. An allocation being changed is checked until
+    /// its own end date; a new one has no end.
+    pub async fn validate_allocation_percentages_from(
+        &self,
+        account_id: &str,
+        new_percentage: f64,
+        exclude_allocation_id: Option<&str>,
+        from: NaiveDate,
+    ) -> Result<()> {
+        let changed: Vec<GoalsAllocation> = match exclude_allocation_id {
+            Some(allocation_id) => self
+                .get_allocations_for_account(account_id)
+                .await?
+                .into_iter()
+                .filter(|allocation| allocation.id == allocation_id)
+                .collect(),
+            None => Vec::new(),
+        };
+        let others: Vec<GoalsAllocation> = self
+            .capped_allocations_for_account(account_id)
+            .await?
+            .into_iter()
+            .filter(|allocation| Some(allocation.id.as_str()) != exclude_allocation_id)
+            .collect();
+        let allocation_ids: Vec<String> = others.iter().map(|a| a.id.clone()).collect();
+        let versions = self.goal_repo.load_allocation_versions(&allocation_ids).await?;
+        let mut periods = BTreeMap::new();
+        allocation_periods(&others, &versions, false, &mut periods);
 
-        for allocation in allocations {
-            // Skip the allocation being updated
-            if let Some(exclude_id) = exclude_allocation_id {
-                if allocation.id == exclude_id {
-                    continue;
-                }
+        let from = from.format("%Y-%m-%d").to_string();
+        let until = changed
+            .first()
+            .and_then(|allocation| allocation.end_date.as_deref())
+            .unwrap_or(OPEN_END_DATE);
+        let account_periods = periods.remove(account_id).unwrap_or_default();
+        // The total only rises where a period starts, so those are the days to check
+        let mut check_dates: Vec<&str> = account_periods
+            .iter()
+            .map(|period| period.start)
+            .filter(|date| from.as_str() < *date && *date <= until)
+            .collect();
+        check_dates.push(from.as_str());
+        check_dates.sort_unstable();
+        check_dates.dedup();
+        for date in check_dates {
+            let total_percent = new_percentage
+                + account_periods
+                    .iter()
+                    .filter(|p| p.start <= date && date <= p.end)
+                    .map(|p| p.percent)
+                    .sum::<f64>();
+            if total_percent > 100.0 {
+                let message = if date == from {
+                    LocalizedMessage::new(MessageCode::GoalAllocationExceedsLimit)
+                } else {
+                    LocalizedMessage::new(MessageCode::GoalAllocationExceedsLimitOnDate)
+                        .with_param("date", date)
+                };
+                return Err(message
+                    .with_param("percent", format!("{:.1}", total_percent))
+                    .with_param("account", account_id)
+                    .into());
             }
-            total_percent += allocation.allocation_percentage;
         }
 
-        if total_percent > 100.0 {
-            return Err(crate::errors::Error::Validation(
-                crate::errors::ValidationError::InvalidInput(
-                    format!(
-                        "Total allocation percentage {:.1}% exceeds 100% on account {}",
-                        total_percent, account_id
-                    )
-                )
-            ));
+        Ok(())
+    }
+
+    /// Proposes new percentages for the allocations of an account, in proportion to what each
+    /// goal still needs per month to be reached by its due date. The share they hold together
+    /// stays the same, so the account stays within the 100% cap. Goals still waiting on a
+    /// prerequisite are left out.
+    pub async fn suggest_allocation_rebalance(
+        &self,
+        account_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<AllocationRebalance> {
+        let (allocations, goals, progress) = self.load_account_goals(account_id, as_of).await?;
+        Ok(rebalance_allocations(
+            account_id,
+            as_of,
+            &allocations,
+            &goals,
+            &progress,
+        ))
+    }
+
+    /// Proposes how a deposit of `amount` into an account should be split across the goals
+    /// it funds, by priority, then by what each goal still needs per month. Goals still waiting
+    /// on a prerequisite get nothing. Nothing is saved.
+    pub async fn distribute_unallocated(
+        &self,
+        account_id: &str,
+        amount: f64,
+        as_of: NaiveDate,
+    ) -> Result<DepositDistribution> {
+        if !(amount.is_finite() && amount > 0.0) {
+            return Err(invalid_input(format!(
+                "Deposit amount must be positive, got {}",
+                amount
+            )));
         }
+        let (allocations, goals, progress) = self.load_account_goals(account_id, as_of).await?;
+        Ok(distribute_deposit(
+            account_id,
+            amount,
+            as_of,
+            &allocations,
+            &goals,
+            &progress,
+        ))
+    }
 
-        Ok(())
+    /// Monthly contribution that takes a goal from its current value to its target by the due
+    /// date, at `expected_return_rate` percent a year
+    pub async fn calculate_required_monthly_investment(
+        &self,
+        goal_id: &str,
+        expected_return_rate: f64,
+        as_of: NaiveDate,
+    ) -> Result<f64> {
+        let requirement = self
+            .get_funding_requirement(goal_id, Some(expected_return_rate), Some(0.0), as_of)
+            .await?;
+        Ok(requirement.required_monthly)
+    }
+
+    /// What a goal needs each month, for its target as set and for the target grown by
+    /// inflation. The return defaults to the goal's own target return rate, or none, and
+    /// inflation to `DEFAULT_GOAL_INFLATION_RATE`.
+    pub async fn get_funding_requirement(
+        &self,
+        goal_id: &str,
+        expected_return_rate: Option<f64>,
+        inflation_rate: Option<f64>,
+        as_of: NaiveDate,
+    ) -> Result<GoalFundingRequirement> {
+        let goal = self.find_goal(goal_id).await?;
+        let current_value = self
+            .get_goal_progress(goal_id, as_of)
+            .await?
+            .map_or(0.0, |snapshot| snapshot.current_value);
+        funding_requirement(
+            &goal,
+            current_value,
+            expected_return_rate.or(goal.target_return_rate).unwrap_or(0.0),
+            inflation_rate.unwrap_or(DEFAULT_GOAL_INFLATION_RATE),
+            as_of,
+        )
+    }
+
+    /// What changing an allocation to a new percentage or amount would do on `as_of`: the
+    /// account's unallocated balance, where the goals it funds would stand and when they would
+    /// be reached, and why the change could not be saved. Nothing is saved.
+    pub async fn preview_allocation_change(
+        &self,
+        allocation_id: &str,
+        change: AllocationChange,
+        as_of: NaiveDate,
+    ) -> Result<AllocationChangePreview> {
+        let allocation = self
+            .goal_repo
+            .load_all_allocations()
+            .await?
+            .into_iter()
+            .find(|allocation| allocation.id == allocation_id)
+            .ok_or_else(|| crate::errors::Error::from(diesel::result::Error::NotFound))?;
+        let account_id = allocation.account_id.as_str();
+        let (allocations, goals, progress) = self.load_account_goals(account_id, as_of).await?;
+        let account_value = to_f64_values(
+            self.goal_repo
+                .load_goal_progress_inputs(
+                    std::slice::from_ref(&allocation.goal_id),
+                    as_of,
+                    as_of,
+                )
+                .await?
+                .account_values_on(as_of),
+        )
+        .get(account_id)
+        .copied()
+        .unwrap_or(0.0);
+        let set_aside_by_others: f64 = allocations
+            .iter()
+            .filter(|other| other.id != allocation.id)
+            .map(|other| other.allocation_amount)
+            .sum();
+
+        let mut errors = Vec::new();
+        let (new_percentage, new_amount) = match change {
+            AllocationChange::Percentage { value } => {
+                if !(0.0..=100.0).contains(&value) {
+                    errors.push(format!(
+                        "Allocation percentage must be between 0 and 100, got {}",
+                        value
+                    ));
+                } else if let Err(e) = self
+                    .validate_allocation_percentages_from(
+                        account_id,
+                        value,
+                        Some(allocation_id),
+                        as_of,
+                    )
+                    .await
+                {
+                    errors.push(e.to_string());
+                }
+                (value, allocation.allocation_amount)
+            }
+            AllocationChange::Amount { value } => {
+                let available = (account_value - set_aside_by_others).max(0.0);
+                if value < 0.0 {
+                    errors.push(format!("Allocation amount must not be negative, got {}", value));
+                } else if value > available {
+                    // The balance itself is left out, so privacy mode can show the message
+                    errors.push(format!(
+                        "Allocation amount ${} exceeds the account's unallocated balance",
+                        value
+                    ));
+                }
+                (allocation.allocation_percentage, value)
+            }
+        };
+
+        let goals: HashMap<&str, &Goal> =
+            goals.iter().map(|goal| (goal.id.as_str(), goal)).collect();
+        let goal_previews = progress
+            .iter()
+            .filter_map(|snapshot| {
+                let goal = goals.get(snapshot.goal_id.as_str())?;
+                // Only the changed allocation's share of its account's growth moves
+                let new_value = match snapshot
+                    .allocation_details
+                    .iter()
+                    .find(|detail| {
+                        goal.id == allocation.goal_id && detail.account_id == account_id
+                    })
+                {
+                    Some(detail) => {
+                        snapshot.current_value - detail.allocated_growth
+                            + detail.account_growth * new_percentage / 100.0
+                    }
+                    None => snapshot.current_value,
+                };
+                Some(GoalChangePreview {
+                    goal_id: goal.id.clone(),
+                    goal_title: goal.title.clone(),
+                    target_amount: goal.target_amount,
+                    current_value: snapshot.current_value,
+                    new_value,
+                    completion_date: projected_completion(goal, snapshot.current_value, as_of),
+                    new_completion_date: projected_completion(goal, new_value, as_of),
+                })
+            })
+            .collect();
+
+        Ok(AllocationChangePreview {
+            allocation_id: allocation.id.clone(),
+            account_id: account_id.to_string(),
+            as_of: as_of.format("%Y-%m-%d").to_string(),
+            change,
+            unallocated_balance: (account_value - set_aside_by_others - new_amount).max(0.0),
+            goals: goal_previews,
+            errors,
+        })
+    }
+
+    /// Allocations of an account, the goals they fund that are not waiting on a prerequisite,
+    /// and the progress on `as_of` of those that still take new cash
+    async fn load_account_goals(
+        &self,
+        account_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<(Vec<GoalsAllocation>, Vec<Goal>, Vec<GoalProgressSnapshot>)> {
+        let allocations = self.get_allocations_for_account(account_id).await?;
+        let goal_ids: HashSet<&str> = allocations.iter().map(|a| a.goal_id.as_str()).collect();
+        let goals: Vec<Goal> = ready_goals(self.goal_repo.load_goals().await?)
+            .into_iter()
+            .filter(|goal| goal_ids.contains(goal.id.as_str()))
+            .collect();
+        let tracked: Vec<Goal> = goals
+            .iter()
+            .filter(|goal| is_rebalanced(goal))
+            .cloned()
+            .collect();
+        let progress = self
+            .calculate_goals_progress_on_date(&tracked, &[], as_of)
+            .await?;
+        Ok((allocations, goals, progress))
+    }
+
+    /// A goal by id, archived or not
+    async fn find_goal(&self, goal_id: &str) -> Result<Goal> {
+        self.goal_repo
+            .load_all_goals()
+            .await?
+            .into_iter()
+            .find(|goal| goal.id == goal_id)
+            .ok_or_else(|| diesel::result::Error::NotFound.into())
     }
 
     /// Calculate growth for an allocation over a period
@@ -274,6 +917,25 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
             .sum()
     }
 
+    /// Growth of an allocation over the span of `account_value_series` (dated values of its
+    /// account, oldest first), split wherever one of its stored versions starts or ends so each
+    /// stretch uses the percentage in force then. Without versions the allocation's own dates
+    /// and percentage apply, as in the allocation checks.
+    pub async fn calculate_versioned_growth(
+        &self,
+        allocation_id: &str,
+        account_value_series: &[(NaiveDate, f64)],
+    ) -> Result<f64> {
+        let allocation = self.goal_repo.get_allocation_by_id(allocation_id).await?;
+        let versions = self.goal_repo.get_allocation_versions(allocation_id).await?;
+        let mut periods = BTreeMap::new();
+        allocation_periods(std::slice::from_ref(&allocation), &versions, false, &mut periods);
+        let periods = periods
+            .remove(allocation.account_id.as_str())
+            .unwrap_or_default();
+        Ok(self.calculate_segmented_growth(&versioned_segments(&periods, account_value_series)))
+    }
+
     /// Get current value for an allocation (init_amount + growth)
     pub fn calculate_allocation_current_value(
         &self,
@@ -285,7 +947,7 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
 
     /// Validate historical allocation constraints
     /// Ensures that at the time of allocation, it didn't exceed available balance
-    pub fn validate_historical_allocation(
+    pub async fn validate_historical_allocation(
         &self,
         account_id: &str,
         allocation_amount: f64,
@@ -293,7 +955,7 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
         account_value_at_allocation_date: f64,
     ) -> Result<()> {
         // Get all allocations for this account
-        let allocations = self.goal_repo.get_allocations_for_account(account_id)?;
+        let allocations = self.get_allocations_for_account(account_id).await?;
 
         // Sum up allocations that were active on the allocation_date
         let mut total_allocated_at_date = 0.0;
@@ -328,79 +990,786 @@ impl<T: GoalRepositoryTrait> GoalService<T> {
     }
 }
 
-#[async_trait]
-impl<T: GoalRepositoryTrait + Send + Sync> GoalServiceTrait for GoalService<T> {
-    fn get_goals(&self) -> Result<Vec<Goal>> {
-        self.goal_repo.load_goals()
+/// Net-worth goals are not funded through allocations, debt payoff goals are allocated what is
+/// owed rather than what is held, and achieved goals need nothing more
+fn is_rebalanced(goal: &Goal) -> bool {
+    !goal.is_achieved
+        && goal.goal_type != GOAL_TYPE_NET_WORTH
+        && goal.goal_type != GOAL_TYPE_DEBT_PAYOFF
+}
+
+/// Drops goals whose prerequisite is not achieved yet. A prerequisite that is archived or gone
+/// no longer holds anything back.
+fn ready_goals(goals: Vec<Goal>) -> Vec<Goal> {
+    let achieved: HashMap<&str, bool> = goals
+        .iter()
+        .map(|goal| (goal.id.as_str(), goal.is_achieved))
+        .collect();
+    let waiting: HashSet<String> = goals
+        .iter()
+        .filter(|goal| {
+            goal.depends_on_goal_id
+                .as_deref()
+                .and_then(|parent| achieved.get(parent))
+                .is_some_and(|&is_achieved| !is_achieved)
+        })
+        .map(|goal| goal.id.clone())
+        .collect();
+    goals.into_iter().filter(|goal| !waiting.contains(&goal.id)).collect()
+}
+
+/// A goal's own currency has to be a three-letter code such as VND or USD
+fn validate_goal_currency(currency: Option<&str>) -> Result<()> {
+    match currency {
+        Some(code) if !(code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase())) => Err(
+            invalid_input(format!("Goal currency must be a three-letter code, got '{}'", code)),
+        ),
+        _ => Ok(()),
     }
+}
 
-    async fn create_goal(&self, new_goal: NewGoal) -> Result<Goal> {
-        self.goal_repo.insert_new_goal(new_goal).await
+/// A goal may wait on another existing goal, but never on itself through any chain of them
+fn validate_goal_dependency(
+    goal_id: Option<&str>,
+    depends_on_goal_id: Option<&str>,
+    goals: &[Goal],
+) -> Result<()> {
+    let Some(parent) = depends_on_goal_id else {
+        return Ok(());
+    };
+    let by_id: HashMap<&str, &Goal> = goals.iter().map(|goal| (goal.id.as_str(), goal)).collect();
+    if !by_id.contains_key(parent) {
+        return Err(invalid_input(format!("Prerequisite goal {} does not exist", parent)));
+    }
+    let mut seen = HashSet::new();
+    let mut current = Some(parent);
+    while let Some(ancestor) = current {
+        if Some(ancestor) == goal_id {
+            return Err(invalid_input(
+                "A goal cannot depend on itself, directly or through other goals".to_string(),
+            ));
+        }
+        // A cycle that does not pass through this goal is not its to report
+        if !seen.insert(ancestor) {
+            break;
+        }
+        current = by_id
+            .get(ancestor)
+            .and_then(|goal| goal.depends_on_goal_id.as_deref());
     }
+    Ok(())
+}
 
-    async fn update_goal(&self, updated_goal_data: Goal) -> Result<Goal> {
-        // Get the existing goal to compare dates
-        let existing_goals = self.goal_repo.load_goals()?;
-        let existing_goal = existing_goals.iter().find(|g| g.id == updated_goal_data.id);
+/// Day a goal holding `value` on `as_of` reaches its target if it keeps growing at the pace it
+/// has since its start; `None` for a goal that is not growing or has no start date
+fn projected_completion(goal: &Goal, value: f64, as_of: NaiveDate) -> Option<String> {
+    if value >= goal.target_amount {
+        return Some(as_of.format("%Y-%m-%d").to_string());
+    }
+    let start = NaiveDate::parse_from_str(goal.start_date.as_deref()?, "%Y-%m-%d").ok()?;
+    let days_so_far = (as_of - start).num_days();
+    if days_so_far <= 0 || value <= 0.0 {
+        return None;
+    }
+    let days_left = ((goal.target_amount - value) * days_so_far as f64 / value).ceil();
+    as_of
+        .checked_add_days(Days::new(days_left as u64))
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
 
-        if let Some(existing) = existing_goal {
-            let start_date_changed = existing.start_date != updated_goal_data.start_date;
-            let due_date_changed = existing.due_date != updated_goal_data.due_date;
+/// Months from `from` until `to`, a started month counting as one, and never less than one
+fn months_until(from: NaiveDate, to: NaiveDate) -> u32 {
+    let mut months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+    if to.day() > from.day() {
+        months += 1;
+    }
+    months.max(1) as u32
+}
 
-            // If start_date changed, reset all allocations for this goal to 0 and update dates
-            // This is because allocations are tied to historical values at the start date
-            if start_date_changed {
-                log::info!(
-                    "Goal {} start_date changed from {:?} to {:?}. Resetting all allocations to 0 and updating dates.",
-                    updated_goal_data.id,
-                    existing.start_date,
-                    updated_goal_data.start_date
-                );
-                self.goal_repo
-                    .reset_allocations_for_goal(
-                        updated_goal_data.id.clone(),
-                        updated_goal_data.start_date.clone(),
-                        updated_goal_data.due_date.clone(),
-                    )
-                    .await?;
-            }
-            // If only due_date changed (not start_date), extend allocations' end_date
-            // This preserves existing allocations while adjusting the timeline
-            else if due_date_changed {
-                if let Some(ref new_end_date) = updated_goal_data.due_date {
-                    log::info!(
-                        "Goal {} due_date changed from {:?} to {:?}. Updating allocations' end_date.",
-                        updated_goal_data.id,
-                        existing.due_date,
-                        updated_goal_data.due_date
-                    );
-                    self.goal_repo
-                        .update_allocations_end_date_for_goal(
-                            updated_goal_data.id.clone(),
-                            new_end_date.clone(),
-                        )
-                        .await?;
-                }
-            }
-        }
+/// Splits the share held by the account's active allocations of unfinished goals in
+/// proportion to each goal's remaining amount per month left. Shares are rounded down to a
+/// tenth of a percent so they never add up to more than before.
+fn rebalance_allocations(
+    account_id: &str,
+    as_of: NaiveDate,
+    allocations: &[GoalsAllocation],
+    goals: &[Goal],
+    progress: &[GoalProgressSnapshot],
+) -> AllocationRebalance {
+    let as_of_str = as_of.format("%Y-%m-%d").to_string();
+    let goals: HashMap<&str, &Goal> = goals.iter().map(|goal| (goal.id.as_str(), goal)).collect();
+    let progress: HashMap<&str, &GoalProgressSnapshot> = progress
+        .iter()
+        .map(|snapshot| (snapshot.goal_id.as_str(), snapshot))
+        .collect();
 
-        self.goal_repo.update_goal(updated_goal_data).await
+    let mut suggestions = Vec::new();
+    let mut needs = Vec::new();
+    for allocation in allocations {
+        let goal = match goals.get(allocation.goal_id.as_str()) {
+            Some(goal) if is_rebalanced(goal) && is_active_on(allocation, &as_of_str) => goal,
+            _ => continue,
+        };
+        let Some(snapshot) = progress.get(goal.id.as_str()) else {
+            continue;
+        };
+        let remaining = (goal.target_amount - snapshot.current_value).max(0.0);
+        let months_left = goal
+            .due_date
+            .as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .map(|due| months_until(as_of, due));
+        needs.push(remaining / months_left.unwrap_or(UNDATED_GOAL_HORIZON_MONTHS) as f64);
+        suggestions.push(AllocationRebalanceSuggestion {
+            allocation_id: allocation.id.clone(),
+            goal_id: goal.id.clone(),
+            goal_title: goal.title.clone(),
+            target_amount: goal.target_amount,
+            current_value: snapshot.current_value,
+            remaining,
+            due_date: goal.due_date.clone(),
+            months_left,
+            current_percent: allocation.allocation_percentage,
+            suggested_percent: allocation.allocation_percentage,
+        });
     }
 
-    async fn delete_goal(&self, goal_id_to_delete: String) -> Result<usize> {
-        self.goal_repo.delete_goal(goal_id_to_delete).await
+    let rebalanced_percent: f64 = suggestions.iter().map(|s| s.current_percent).sum();
+    let total_need: f64 = needs.iter().sum();
+    // With every goal funded there is nothing to move
+    if total_need > 0.0 {
+        for (suggestion, need) in suggestions.iter_mut().zip(&needs) {
+            suggestion.suggested_percent =
+                (rebalanced_percent * need / total_need * 10.0).floor() / 10.0;
+        }
     }
+    let allocated: f64 = allocations.iter().map(|a| a.allocation_percentage).sum();
 
-    async fn upsert_goal_allocations(&self, mut allocations: Vec<GoalsAllocation>) -> Result<usize> {
-        // Backfill allocation dates from their associated goals
-        let goals = self.goal_repo.load_goals()?;
-        let goal_map: HashMap<String, Goal> = goals
-            .into_iter()
-            .map(|g| (g.id.clone(), g))
-            .collect();
+    AllocationRebalance {
+        account_id: account_id.to_string(),
+        as_of: as_of_str,
+        rebalanced_percent,
+        unallocated_percent: (100.0 - allocated).max(0.0),
+        suggestions,
+    }
+}
 
-        for allocation in &mut allocations {
-            if let Some(goal) = goal_map.get(&allocation.goal_id) {
+/// Funds the goals active allocations of the account point at, highest priority first. Within
+/// a priority the cash is shared in proportion to each goal's remaining amount per month left;
+/// a goal's share never exceeds what it still needs, and the excess goes to the others. Amounts
+/// are rounded down to whole units.
+fn distribute_deposit(
+    account_id: &str,
+    amount: f64,
+    as_of: NaiveDate,
+    allocations: &[GoalsAllocation],
+    goals: &[Goal],
+    progress: &[GoalProgressSnapshot],
+) -> DepositDistribution {
+    let as_of_str = as_of.format("%Y-%m-%d").to_string();
+    let funded: HashSet<&str> = allocations
+        .iter()
+        .filter(|a| a.account_id == account_id && is_active_on(a, &as_of_str))
+        .map(|a| a.goal_id.as_str())
+        .collect();
+    let progress: HashMap<&str, &GoalProgressSnapshot> = progress
+        .iter()
+        .map(|snapshot| (snapshot.goal_id.as_str(), snapshot))
+        .collect();
+
+    let mut candidates: Vec<(GoalDeposit, f64)> = goals
+        .iter()
+        .filter(|goal| is_rebalanced(goal) && funded.contains(goal.id.as_str()))
+        .filter_map(|goal| {
+            let snapshot = progress.get(goal.id.as_str())?;
+            let remaining = goal.target_amount - snapshot.current_value;
+            if remaining <= 0.0 {
+                return None;
+            }
+            let months_left = goal
+                .due_date
+                .as_deref()
+                .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                .map(|due| months_until(as_of, due));
+            let need = remaining / months_left.unwrap_or(UNDATED_GOAL_HORIZON_MONTHS) as f64;
+            let deposit = GoalDeposit {
+                goal_id: goal.id.clone(),
+                goal_title: goal.title.clone(),
+                priority: goal.priority,
+                due_date: goal.due_date.clone(),
+                months_left,
+                remaining,
+                amount: 0.0,
+            };
+            Some((deposit, need))
+        })
+        .collect();
+    candidates.sort_by(|(a, _), (b, _)| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| {
+                let undated_last = |deposit: &GoalDeposit| deposit.months_left.unwrap_or(u32::MAX);
+                undated_last(a).cmp(&undated_last(b))
+            })
+            .then_with(|| a.goal_title.cmp(&b.goal_title))
+    });
+
+    let mut left = amount;
+    for tier in candidates.chunk_by_mut(|(a, _), (b, _)| a.priority == b.priority) {
+        // Goals that would get more than they need are filled first and leave the group
+        loop {
+            let open: Vec<usize> = (0..tier.len())
+                .filter(|&i| tier[i].0.amount < tier[i].0.remaining)
+                .collect();
+            let total_need: f64 = open.iter().map(|&i| tier[i].1).sum();
+            if left <= 0.0 || open.is_empty() || total_need <= 0.0 {
+                break;
+            }
+            let filled: Vec<usize> = open
+                .iter()
+                .copied()
+                .filter(|&i| {
+                    let (deposit, need) = &tier[i];
+                    left * need / total_need >= deposit.remaining - deposit.amount
+                })
+                .collect();
+            if filled.is_empty() {
+                for &i in &open {
+                    tier[i].0.amount += left * tier[i].1 / total_need;
+                }
+                left = 0.0;
+            } else {
+                for &i in &filled {
+                    left -= tier[i].0.remaining - tier[i].0.amount;
+                    tier[i].0.amount = tier[i].0.remaining;
+                }
+            }
+        }
+    }
+
+    let goals: Vec<GoalDeposit> = candidates
+        .into_iter()
+        .map(|(mut deposit, _)| {
+            deposit.amount = deposit.amount.floor();
+            deposit
+        })
+        .collect();
+    let assigned: f64 = goals.iter().map(|deposit| deposit.amount).sum();
+    DepositDistribution {
+        account_id: account_id.to_string(),
+        as_of: as_of_str,
+        amount,
+        unassigned: amount - assigned,
+        goals,
+    }
+}
+
+/// Level monthly contribution that grows `current_value` to `target` in `months`, both earning
+/// `annual_rate` percent a year compounded monthly; zero when the current value gets there on
+/// its own
+fn required_monthly_investment(target: f64, current_value: f64, annual_rate: f64, months: u32) -> f64 {
+    let monthly_rate = annual_rate / 100.0 / 12.0;
+    let required = if monthly_rate == 0.0 {
+        (target - current_value) / months as f64
+    } else {
+        let growth = (1.0 + monthly_rate).powi(months as i32);
+        (target - current_value * growth) * monthly_rate / (growth - 1.0)
+    };
+    required.max(0.0)
+}
+
+fn funding_requirement(
+    goal: &Goal,
+    current_value: f64,
+    expected_return_rate: f64,
+    inflation_rate: f64,
+    as_of: NaiveDate,
+) -> Result<GoalFundingRequirement> {
+    for (name, rate) in [("Expected return", expected_return_rate), ("Inflation", inflation_rate)] {
+        if !(rate.is_finite() && rate > -100.0 && rate <= 100.0) {
+            return Err(invalid_input(format!(
+                "{} rate must be above -100 and at most 100 percent, got {}",
+                name, rate
+            )));
+        }
+    }
+    let due_date = goal
+        .due_date
+        .clone()
+        .ok_or_else(|| invalid_input(format!("Goal {} has no due date", goal.title)))?;
+    let due = NaiveDate::parse_from_str(&due_date, "%Y-%m-%d")
+        .map_err(|e| invalid_input(format!("Invalid due date '{}': {}", due_date, e)))?;
+    let months_left = months_until(as_of, due);
+    let inflation_adjusted_target =
+        goal.target_amount * (1.0 + inflation_rate / 100.0).powf(months_left as f64 / 12.0);
+
+    Ok(GoalFundingRequirement {
+        goal_id: goal.id.clone(),
+        goal_title: goal.title.clone(),
+        target_amount: goal.target_amount,
+        current_value,
+        due_date,
+        months_left,
+        expected_return_rate,
+        inflation_rate,
+        required_monthly: required_monthly_investment(
+            goal.target_amount,
+            current_value,
+            expected_return_rate,
+            months_left,
+        ),
+        inflation_adjusted_target,
+        inflation_adjusted_monthly: required_monthly_investment(
+            inflation_adjusted_target,
+            current_value,
+            expected_return_rate,
+            months_left,
+        ),
+    })
+}
+
+fn to_f64_values(values: HashMap<String, Decimal>) -> HashMap<String, f64> {
+    values
+        .into_iter()
+        .map(|(account_id, value)| (account_id, value.to_f64().unwrap_or_default()))
+        .collect()
+}
+
+/// Allocations active on `query_date`: started on or before it and not yet ended
+fn is_active_on(allocation: &GoalsAllocation, query_date: &str) -> bool {
+    match (&allocation.start_date, &allocation.end_date) {
+        (Some(start), Some(end)) => start.as_str() <= query_date && query_date <= end.as_str(),
+        _ => false,
+    }
+}
+
+/// Account values and net contributions on one date, in the goal's currency. Accounts
+/// missing from either count as zero.
+#[derive(Default)]
+struct AccountFigures {
+    values: HashMap<String, f64>,
+    contributions: HashMap<String, f64>,
+}
+
+impl AccountFigures {
+    /// Figures of every loaded account on `date`, in the base currency
+    fn on(inputs: &GoalProgressInputs, date: NaiveDate) -> Self {
+        AccountFigures {
+            values: to_f64_values(inputs.account_values_on(date)),
+            contributions: to_f64_values(inputs.account_contributions_on(date)),
+        }
+    }
+
+    /// What every loaded account owes on `date`, in the base currency, as the value of a debt
+    /// payoff goal's accounts. Accounts with a loan follow its schedule; the others their
+    /// valuation as a positive amount. Repayments are not contributions, so none are kept.
+    fn owed_on(
+        inputs: &GoalProgressInputs,
+        debts: &HashMap<String, DebtSchedule>,
+        date: NaiveDate,
+    ) -> Self {
+        let mut values: HashMap<String, f64> = to_f64_values(inputs.account_values_on(date))
+            .into_iter()
+            .map(|(account_id, value)| (account_id, value.abs()))
+            .collect();
+        for (account_id, debt) in debts {
+            values.insert(
+                account_id.clone(),
+                debt.owed_on(date).to_f64().unwrap_or_default(),
+            );
+        }
+        AccountFigures {
+            values,
+            ..Default::default()
+        }
+    }
+
+    fn value(&self, account_id: &str) -> f64 {
+        self.values.get(account_id).copied().unwrap_or(0.0)
+    }
+
+    fn contribution(&self, account_id: &str) -> f64 {
+        self.contributions.get(account_id).copied().unwrap_or(0.0)
+    }
+}
+
+/// Progress of one goal from the allocations of its own, whichever way they were loaded.
+/// Each allocation's growth is split into its share of what was paid into the account since
+/// the goal started and what the investments earned. Debt payoff goals grow by what their
+/// accounts owe less, all of it repaid rather than earned.
+fn goal_progress_snapshot<'a>(
+    goal: &Goal,
+    allocations: impl Iterator<Item = &'a GoalsAllocation>,
+    at_goal_start: &AccountFigures,
+    current: &AccountFigures,
+    net_worth_series: &[NetWorthPoint],
+    query_date: &str,
+) -> Result<GoalProgressSnapshot> {
+    // Ensure goal has a start_date (validates goal structure)
+    let goal_start_date = goal
+        .start_date
+        .as_ref()
+        .ok_or_else(|| {
+            crate::errors::Error::Validation(
+                crate::errors::ValidationError::InvalidInput(
+                    "Goal must have a start_date".to_string(),
+                ),
+            )
+        })?;
+
+    // Net worth goals track everything owned minus everything owed, not allocations
+    if goal.goal_type == GOAL_TYPE_NET_WORTH {
+        return net_worth_snapshot(goal, goal_start_date, net_worth_series, query_date);
+    }
+
+    let is_debt = goal.goal_type == GOAL_TYPE_DEBT_PAYOFF;
+    let mut total_growth = 0.0;
+    let mut total_contributed = 0.0;
+    let mut allocation_details = Vec::new();
+
+    for allocation in allocations.filter(|a| a.goal_id == goal.id && is_active_on(a, query_date)) {
+        let account_value_at_start = at_goal_start.value(&allocation.account_id);
+        let current_account_value = current.value(&allocation.account_id);
+
+        let account_growth = if is_debt {
+            account_value_at_start - current_account_value
+        } else {
+            current_account_value - account_value_at_start
+        };
+        let allocation_percent = allocation.percent_allocation as f64 / 100.0;
+        let allocated_growth = account_growth * allocation_percent;
+        let contributed_amount = if is_debt {
+            allocated_growth
+        } else {
+            (current.contribution(&allocation.account_id)
+                - at_goal_start.contribution(&allocation.account_id))
+                * allocation_percent
+        };
+
+        total_growth += allocated_growth;
+        total_contributed += contributed_amount;
+
+        allocation_details.push(AllocationDetail {
+            account_id: allocation.account_id.clone(),
+            percent_allocation: allocation.percent_allocation,
+            account_value_at_goal_start: account_value_at_start,
+            account_current_value: current_account_value,
+            account_growth,
+            allocated_growth,
+            contributed_amount,
+            market_growth: allocated_growth - contributed_amount,
+        });
+    }
+
+    Ok(GoalProgressSnapshot {
+        goal_id: goal.id.clone(),
+        goal_title: goal.title.clone(),
+        query_date: query_date.to_string(),
+        init_value: 0.0, // Always 0 under new logic
+        current_value: total_growth,
+        growth: total_growth, // growth = current_value - init_value = total_growth - 0
+        contributed_amount: total_contributed,
+        market_growth: total_growth - total_contributed,
+        allocation_details,
+    })
+}
+
+fn invalid_input(message: String) -> crate::errors::Error {
+    crate::errors::Error::Validation(crate::errors::ValidationError::InvalidInput(message))
+}
+
+/// Cuts the span of `series` at every period boundary and pairs each stretch with the
+/// percentage in force, the latest-starting period winning where periods overlap. Stretches no
+/// period covers are left out. Entries are (percentage, value_start, value_end).
+fn versioned_segments(
+    periods: &[AllocationPeriod],
+    series: &[(NaiveDate, f64)],
+) -> Vec<(f64, f64, f64)> {
+    let (Some(&(first, _)), Some(&(last, _))) = (series.first(), series.last()) else {
+        return Vec::new();
+    };
+    let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    // A period grows from the close of the day before it starts to the close of its last day
+    let bounds: Vec<(NaiveDate, NaiveDate, f64)> = periods
+        .iter()
+        .map(|period| {
+            let start = parse(period.start)
+                .and_then(|start| start.pred_opt())
+                .unwrap_or(first);
+            (start, parse(period.end).unwrap_or(last), period.percent)
+        })
+        .collect();
+    let mut cuts: Vec<NaiveDate> = bounds
+        .iter()
+        .flat_map(|&(start, end, _)| [start, end])
+        .chain([first, last])
+        .map(|date| date.clamp(first, last))
+        .collect();
+    cuts.sort();
+    cuts.dedup();
+
+    let value_on = |date: NaiveDate| {
+        let index = series.partition_point(|(value_date, _)| *value_date <= date);
+        series[index.saturating_sub(1)].1
+    };
+    cuts.windows(2)
+        .filter_map(|cut| {
+            let (from, to) = (cut[0], cut[1]);
+            bounds
+                .iter()
+                .filter(|&&(start, end, _)| start <= from && end >= to)
+                .max_by_key(|&&(start, _, _)| start)
+                .map(|&(_, _, percent)| (percent, value_on(from), value_on(to)))
+        })
+        .collect()
+}
+
+/// Checks a set of allocations and versions to insert against each other and against the
+/// stored allocations of the same accounts: ids are new, versions belong to the set, dates and
+/// percentages are sane, and no account is over 100% on any day a new allocation covers.
+fn validate_allocation_set(
+    existing: &[GoalsAllocation],
+    existing_versions: &[AllocationVersion],
+    allocations: &[GoalsAllocation],
+    versions: &[AllocationVersion],
+) -> Result<()> {
+    let existing_ids: HashSet<&str> = existing.iter().map(|a| a.id.as_str()).collect();
+    let mut allocation_ids = HashSet::new();
+    for allocation in allocations {
+        if existing_ids.contains(allocation.id.as_str()) {
+            return Err(invalid_input(format!("Allocation {} already exists", allocation.id)));
+        }
+        if !allocation_ids.insert(allocation.id.as_str()) {
+            return Err(invalid_input(format!(
+                "Allocation {} appears more than once",
+                allocation.id
+            )));
+        }
+        if !(0.0..=100.0).contains(&allocation.allocation_percentage) {
+            return Err(invalid_input(format!(
+                "Allocation {} has percentage {} outside 0-100",
+                allocation.id, allocation.allocation_percentage
+            )));
+        }
+        if let (Some(start), Some(end)) = (&allocation.start_date, &allocation.end_date) {
+            if start > end {
+                return Err(LocalizedMessage::new(MessageCode::InvalidDateRange).into());
+            }
+        }
+    }
+    let mut version_ids = HashSet::new();
+    for version in versions {
+        if !allocation_ids.contains(version.allocation_id.as_str()) {
+            return Err(invalid_input(format!(
+                "Version {} belongs to allocation {}, which is not in the set",
+                version.id, version.allocation_id
+            )));
+        }
+        if !version_ids.insert(version.id.as_str()) {
+            return Err(invalid_input(format!("Version {} appears more than once", version.id)));
+        }
+        if !(0.0..=100.0).contains(&version.allocation_percentage) {
+            return Err(invalid_input(format!(
+                "Version {} has percentage {} outside 0-100",
+                version.id, version.allocation_percentage
+            )));
+        }
+        if version
+            .version_end_date
+            .as_deref()
+            .is_some_and(|end| version.version_start_date.as_str() > end)
+        {
+            return Err(LocalizedMessage::new(MessageCode::InvalidDateRange).into());
+        }
+    }
+
+    let mut periods = BTreeMap::new();
+    allocation_periods(existing, existing_versions, false, &mut periods);
+    allocation_periods(allocations, versions, true, &mut periods);
+    check_new_periods(&periods)
+}
+
+#[async_trait]
+impl<T: GoalRepositoryTrait + Send + Sync> GoalServiceTrait for GoalService<T> {
+    async fn get_goals(&self) -> Result<Vec<Goal>> {
+        self.goal_repo.load_goals().await
+    }
+
+    async fn get_all_goals(&self) -> Result<Vec<Goal>> {
+        self.goal_repo.load_all_goals().await
+    }
+
+    async fn archive_goal(&self, goal_id: &str) -> Result<Goal> {
+        let goal = self.find_goal(goal_id).await?;
+        if goal.archived_at.is_some() {
+            return Ok(goal);
+        }
+        self.goal_repo
+            .set_goal_archived(goal_id, Some(Utc::now().to_rfc3339()))
+            .await
+    }
+
+    async fn restore_goal(&self, goal_id: &str) -> Result<Goal> {
+        let goal = self.find_goal(goal_id).await?;
+        if goal.archived_at.is_none() {
+            return Ok(goal);
+        }
+        self.goal_repo.set_goal_archived(goal_id, None).await
+    }
+
+    async fn mark_achieved_goals(
+        &self,
+        progress: &[GoalProgressSnapshot],
+        on: NaiveDate,
+    ) -> Result<Vec<Goal>> {
+        let values: HashMap<&str, f64> = progress
+            .iter()
+            .map(|snapshot| (snapshot.goal_id.as_str(), snapshot.current_value))
+            .collect();
+        let achieved_at = on.format("%Y-%m-%d").to_string();
+        let mut achieved = Vec::new();
+        for goal in self.goal_repo.load_goals().await? {
+            // An emergency fund's target is months of expenses, which the amount does not track
+            if goal.is_achieved
+                || goal.goal_type == GOAL_TYPE_EMERGENCY_FUND
+                || goal.target_amount <= 0.0
+            {
+                continue;
+            }
+            let reached = values
+                .get(goal.id.as_str())
+                .is_some_and(|value| *value >= goal.target_amount);
+            if reached {
+                achieved.push(
+                    self.goal_repo
+                        .set_goal_achieved(&goal.id, Some(achieved_at.clone()))
+                        .await?,
+                );
+            }
+        }
+        if !achieved.is_empty() {
+            // Achieved goals no longer take part in distributing cash
+            self.invalidate_allocation_cache();
+        }
+        Ok(achieved)
+    }
+
+    async fn create_goal(&self, new_goal: NewGoal) -> Result<Goal> {
+        validate_goal_type(
+            &new_goal.goal_type,
+            new_goal.target_months,
+            new_goal.due_date.as_deref(),
+        )?;
+        validate_goal_currency(new_goal.currency.as_deref())?;
+        if new_goal.depends_on_goal_id.is_some() {
+            validate_goal_dependency(
+                new_goal.id.as_deref(),
+                new_goal.depends_on_goal_id.as_deref(),
+                &self.goal_repo.load_all_goals().await?,
+            )?;
+        }
+        self.goal_repo.insert_new_goal(new_goal).await
+    }
+
+    async fn update_goal(&self, mut updated_goal_data: Goal) -> Result<Goal> {
+        validate_goal_type(
+            &updated_goal_data.goal_type,
+            updated_goal_data.target_months,
+            updated_goal_data.due_date.as_deref(),
+        )?;
+        validate_goal_currency(updated_goal_data.currency.as_deref())?;
+
+        // Get the existing goal to compare dates; archived goals can be edited too
+        let existing_goals = self.goal_repo.load_all_goals().await?;
+        let existing_goal = existing_goals.iter().find(|g| g.id == updated_goal_data.id);
+        validate_goal_dependency(
+            Some(&updated_goal_data.id),
+            updated_goal_data.depends_on_goal_id.as_deref(),
+            &existing_goals,
+        )?;
+
+        // A goal marked achieved by hand is dated today; marking it not achieved clears the date
+        updated_goal_data.achieved_at = if updated_goal_data.is_achieved {
+            updated_goal_data
+                .achieved_at
+                .take()
+                .or_else(|| existing_goal.and_then(|existing| existing.achieved_at.clone()))
+                .or_else(|| Some(Utc::now().format("%Y-%m-%d").to_string()))
+        } else {
+            None
+        };
+
+        if let Some(existing) = existing_goal {
+            let start_date_changed = existing.start_date != updated_goal_data.start_date;
+            let due_date_changed = existing.due_date != updated_goal_data.due_date;
+
+            // If start_date changed, reset all allocations for this goal to 0 and update dates
+            // This is because allocations are tied to historical values at the start date
+            if start_date_changed {
+                log::info!(
+                    "Goal {} start_date changed from {:?} to {:?}. Resetting all allocations to 0 and updating dates.",
+                    updated_goal_data.id,
+                    existing.start_date,
+                    updated_goal_data.start_date
+                );
+                self.goal_repo
+                    .reset_allocations_for_goal(
+                        updated_goal_data.id.clone(),
+                        updated_goal_data.start_date.clone(),
+                        updated_goal_data.due_date.clone(),
+                    )
+                    .await?;
+                self.invalidate_allocation_cache();
+            }
+            // If only due_date changed (not start_date), extend allocations' end_date
+            // This preserves existing allocations while adjusting the timeline
+            else if due_date_changed {
+                if let Some(ref new_end_date) = updated_goal_data.due_date {
+                    log::info!(
+                        "Goal {} due_date changed from {:?} to {:?}. Updating allocations' end_date.",
+                        updated_goal_data.id,
+                        existing.due_date,
+                        updated_goal_data.due_date
+                    );
+                    self.goal_repo
+                        .update_allocations_end_date_for_goal(
+                            updated_goal_data.id.clone(),
+                            new_end_date.clone(),
+                        )
+                        .await?;
+                    self.invalidate_allocation_cache();
+                }
+            }
+        }
+
+        self.goal_repo.update_goal(updated_goal_data).await
+    }
+
+    async fn delete_goal(&self, goal_id_to_delete: String) -> Result<usize> {
+        // Only archived goals can be deleted, so a goal is never lost in one click
+        let goal = self.find_goal(&goal_id_to_delete).await?;
+        if goal.archived_at.is_none() {
+            return Err(invalid_input(format!(
+                "Goal {} must be archived before it can be deleted",
+                goal.id
+            )));
+        }
+        // Allocations go with their goal
+        let result = self.goal_repo.delete_goal(goal_id_to_delete).await;
+        self.invalidate_allocation_cache();
+        result
+    }
+
+    async fn upsert_goal_allocations(&self, mut allocations: Vec<GoalsAllocation>) -> Result<usize> {
+        // Backfill allocation dates from their associated goals
+        let goals = self.goal_repo.load_goals().await?;
+        let goal_map: HashMap<String, Goal> = goals
+            .into_iter()
+            .map(|g| (g.id.clone(), g))
+            .collect();
+
+        for allocation in &mut allocations {
+            if let Some(goal) = goal_map.get(&allocation.goal_id) {
                 // Backfill start_date if missing
                 if allocation.start_date.is_none() {
                     allocation.start_date = goal.start_date.clone();
@@ -412,15 +1781,230 @@ impl<T: GoalRepositoryTrait + Send + Sync> GoalServiceTrait for GoalService<T> {
             }
         }
 
-        self.goal_repo.upsert_goal_allocations(allocations).await
+        let result = self.goal_repo.upsert_goal_allocations(allocations).await;
+        self.invalidate_allocation_cache();
+        result
     }
 
-    fn load_goals_allocations(&self) -> Result<Vec<GoalsAllocation>> {
+    async fn bulk_insert_allocations(
+        &self,
+        mut allocations: Vec<GoalsAllocation>,
+        versions: Vec<AllocationVersion>,
+    ) -> Result<AllocationBulkInsertSummary> {
+        if allocations.is_empty() && versions.is_empty() {
+            return Ok(AllocationBulkInsertSummary::default());
+        }
+
+        // Same date backfill as upsert_goal_allocations, but a goal that does not exist is an error
+        let goals = self.goal_repo.load_goals().await?;
+        let goal_map: HashMap<&str, &Goal> = goals.iter().map(|g| (g.id.as_str(), g)).collect();
+        for allocation in &mut allocations {
+            let goal = goal_map.get(allocation.goal_id.as_str()).ok_or_else(|| {
+                invalid_input(format!(
+                    "Allocation {} belongs to unknown goal {}",
+                    allocation.id, allocation.goal_id
+                ))
+            })?;
+            if allocation.start_date.is_none() {
+                allocation.start_date = goal.start_date.clone();
+            }
+            if allocation.end_date.is_none() {
+                allocation.end_date = goal.due_date.clone();
+            }
+        }
+
+        let account_ids: HashSet<String> =
+            allocations.iter().map(|a| a.account_id.clone()).collect();
+        let mut existing = Vec::new();
+        for account_id in &account_ids {
+            existing.extend(self.capped_allocations_for_account(account_id).await?);
+        }
+        let existing_ids: Vec<String> = existing.iter().map(|a| a.id.clone()).collect();
+        let existing_versions = self.goal_repo.load_allocation_versions(&existing_ids).await?;
+        validate_allocation_set(&existing, &existing_versions, &allocations, &versions)?;
+
+        let goal_count = allocations
+            .iter()
+            .map(|a| a.goal_id.as_str())
+            .collect::<HashSet<_>>()
+            .len();
+        let result = self.goal_repo.bulk_insert_allocations(allocations, versions).await;
+        self.invalidate_allocation_cache();
+        let (allocation_count, version_count) = result?;
+        Ok(AllocationBulkInsertSummary {
+            allocations: allocation_count,
+            versions: version_count,
+            goals: goal_count,
+            accounts: account_ids.len(),
+        })
+    }
+
+    async fn export_goal_plan(&self, accounts: &[Account]) -> Result<GoalPlanExport> {
+        let goals = self.goal_repo.load_all_goals().await?;
+        let allocations = self.goal_repo.load_all_allocations().await?;
+        let allocation_ids: Vec<String> = allocations.iter().map(|a| a.id.clone()).collect();
+        let versions = self.goal_repo.load_allocation_versions(&allocation_ids).await?;
+        let allocated: HashSet<&str> = allocations.iter().map(|a| a.account_id.as_str()).collect();
+        let account_names = accounts
+            .iter()
+            .filter(|account| allocated.contains(account.id.as_str()))
+            .map(|account| (account.id.clone(), account.name.clone()))
+            .collect();
+        Ok(GoalPlanExport {
+            version: GOAL_PLAN_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            goals,
+            allocations,
+            versions,
+            account_names,
+        })
+    }
+
+    async fn import_goal_plan(
+        &self,
+        plan: GoalPlanExport,
+        accounts: &[Account],
+    ) -> Result<GoalPlanImportResult> {
+        if plan.version > GOAL_PLAN_EXPORT_VERSION {
+            return Err(invalid_input(format!(
+                "Goal plan version {} is newer than supported version {}",
+                plan.version, GOAL_PLAN_EXPORT_VERSION
+            )));
+        }
+        let mut goal_ids = HashSet::new();
+        for goal in &plan.goals {
+            if !goal_ids.insert(goal.id.as_str()) {
+                return Err(invalid_input(format!("Goal {} appears more than once", goal.id)));
+            }
+            validate_goal_type(&goal.goal_type, goal.target_months, goal.due_date.as_deref())?;
+            validate_goal_currency(goal.currency.as_deref())?;
+        }
+        for goal in &plan.goals {
+            validate_goal_dependency(
+                Some(&goal.id),
+                goal.depends_on_goal_id.as_deref(),
+                &plan.goals,
+            )?;
+        }
+
+        // Allocations get new ids and this profile's accounts; goal ids follow once inserted
+        let account_ids = plan.match_accounts(accounts);
+        let mut unmatched_accounts = BTreeSet::new();
+        let mut allocation_ids: HashMap<String, Option<String>> = HashMap::new();
+        let mut allocations = Vec::new();
+        for allocation in plan.allocations {
+            if !goal_ids.contains(allocation.goal_id.as_str()) {
+                return Err(invalid_input(format!(
+                    "Allocation {} belongs to goal {}, which is not in the plan",
+                    allocation.id, allocation.goal_id
+                )));
+            }
+            let account_id = account_ids.get(&allocation.account_id).cloned();
+            if account_id.is_none() {
+                unmatched_accounts.insert(allocation.account_id.clone());
+            }
+            let new_id = account_id.as_ref().map(|_| Uuid::new_v4().to_string());
+            if allocation_ids.insert(allocation.id.clone(), new_id.clone()).is_some() {
+                return Err(invalid_input(format!(
+                    "Allocation {} appears more than once",
+                    allocation.id
+                )));
+            }
+            if let (Some(id), Some(account_id)) = (new_id, account_id) {
+                allocations.push(GoalsAllocation {
+                    id,
+                    account_id,
+                    ..allocation
+                });
+            }
+        }
+        let mut versions = Vec::new();
+        for version in plan.versions {
+            match allocation_ids.get(&version.allocation_id) {
+                Some(Some(allocation_id)) => versions.push(AllocationVersion {
+                    id: Uuid::new_v4().to_string(),
+                    allocation_id: allocation_id.clone(),
+                    ..version
+                }),
+                Some(None) => {}
+                None => {
+                    return Err(invalid_input(format!(
+                        "Version {} belongs to allocation {}, which is not in the plan",
+                        version.id, version.allocation_id
+                    )))
+                }
+            }
+        }
+
+        // Same date backfill as bulk_insert_allocations, from the goals in the plan
+        let plan_goals: HashMap<&str, &Goal> =
+            plan.goals.iter().map(|g| (g.id.as_str(), g)).collect();
+        for allocation in &mut allocations {
+            let goal = plan_goals[allocation.goal_id.as_str()];
+            if allocation.start_date.is_none() {
+                allocation.start_date = goal.start_date.clone();
+            }
+            if allocation.end_date.is_none() {
+                allocation.end_date = goal.due_date.clone();
+            }
+        }
+
+        // Checked against the accounts' allocations before anything is written; the
+        // repository checks again inside its transaction
+        let mut existing = Vec::new();
+        for account_id in allocations.iter().map(|a| &a.account_id).collect::<HashSet<_>>() {
+            existing.extend(self.capped_allocations_for_account(account_id).await?);
+        }
+        let existing_ids: Vec<String> = existing.iter().map(|a| a.id.clone()).collect();
+        let existing_versions = self.goal_repo.load_allocation_versions(&existing_ids).await?;
+        validate_allocation_set(&existing, &existing_versions, &allocations, &versions)?;
+
+        // Prerequisites first, so each dependency can point at its goal's new id
+        let mut new_goal_ids: HashMap<String, String> = HashMap::new();
+        let mut goals = Vec::with_capacity(plan.goals.len());
+        let mut pending = plan.goals;
+        while !pending.is_empty() {
+            let (ready, waiting): (Vec<Goal>, Vec<Goal>) = pending.into_iter().partition(|goal| {
+                goal.depends_on_goal_id
+                    .as_ref()
+                    .is_none_or(|parent| new_goal_ids.contains_key(parent))
+            });
+            for goal in ready {
+                let new_id = Uuid::new_v4().to_string();
+                new_goal_ids.insert(goal.id.clone(), new_id.clone());
+                goals.push(Goal {
+                    id: new_id,
+                    depends_on_goal_id: goal
+                        .depends_on_goal_id
+                        .and_then(|parent| new_goal_ids.get(&parent).cloned()),
+                    ..goal
+                });
+            }
+            pending = waiting;
+        }
+
+        for allocation in &mut allocations {
+            allocation.goal_id = new_goal_ids[&allocation.goal_id].clone();
+        }
+        let goal_count = goals.len();
+        let result = self.goal_repo.import_goal_plan(goals, allocations, versions).await;
+        self.invalidate_allocation_cache();
+        let (allocation_count, version_count) = result?;
+
+        Ok(GoalPlanImportResult {
+            goals: goal_count,
+            allocations: allocation_count,
+            versions: version_count,
+            unmatched_accounts: unmatched_accounts.into_iter().collect(),
+        })
+    }
+
+    async fn load_goals_allocations(&self) -> Result<Vec<GoalsAllocation>> {
         // Use load_all_allocations to include completed goals' allocations (for display/chart)
-        self.goal_repo.load_all_allocations()
+        self.goal_repo.load_all_allocations().await
     }
 
-    fn validate_allocation_conflicts(
+    async fn validate_allocation_conflicts(
         &self,
         account_id: &str,
         start_date: &str,
@@ -429,21 +2013,1692 @@ impl<T: GoalRepositoryTrait + Send + Sync> GoalServiceTrait for GoalService<T> {
         exclude_allocation_id: Option<&str>,
     ) -> Result<()> {
         self.validate_allocation_conflicts(account_id, start_date, end_date, percent_allocation, exclude_allocation_id)
+            .await
+    }
+
+    async fn get_unallocated_balance(&self, account_id: &str, current_account_value: f64) -> Result<f64> {
+        self.get_unallocated_balance(account_id, current_account_value).await
+    }
+
+    async fn validate_unallocated_balance(&self, account_id: &str, allocation_amount: f64, current_account_value: f64) -> Result<()> {
+        self.validate_unallocated_balance(account_id, allocation_amount, current_account_value).await
+    }
+
+    async fn get_unallocated_cash(&self, account_id: &str) -> Result<f64> {
+        self.get_unallocated_cash(account_id).await
+    }
+
+    async fn get_unallocated_value(&self, account_id: &str) -> Result<f64> {
+        self.get_unallocated_value(account_id).await
+    }
+
+    async fn get_unallocated_balance_for_accounts(
+        &self,
+        account_values: &HashMap<String, f64>,
+    ) -> Result<f64> {
+        self.get_unallocated_balance_for_accounts(account_values).await
+    }
+
+    async fn allocate_goal_to_accounts(
+        &self,
+        goal_id: &str,
+        account_ids: &[String],
+        percentage: f64,
+    ) -> Result<AllocationBulkInsertSummary> {
+        self.allocate_goal_to_accounts(goal_id, account_ids, percentage).await
+    }
+
+    async fn validate_allocation_percentages(&self, account_id: &str, new_percentage: f64, exclude_allocation_id: Option<&str>) -> Result<()> {
+        self.validate_allocation_percentages(account_id, new_percentage, exclude_allocation_id).await
+    }
+
+    async fn get_goals_progress_on_date(
+        &self,
+        net_worth_series: &[NetWorthPoint],
+        query_date: NaiveDate,
+    ) -> Result<Vec<GoalProgressSnapshot>> {
+        let goals = self.goal_repo.load_goals().await?;
+        self.calculate_goals_progress_on_date(&goals, net_worth_series, query_date)
+            .await
+    }
+
+    async fn get_progress_series(
+        &self,
+        goal_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: ProgressInterval,
+        net_worth_series: &[NetWorthPoint],
+    ) -> Result<Vec<GoalProgressSnapshot>> {
+        if from > to {
+            return Err(invalid_input(format!(
+                "Series start {} is after its end {}",
+                from, to
+            )));
+        }
+        let goal = self
+            .goal_repo
+            .load_goals()
+            .await?
+            .into_iter()
+            .find(|goal| goal.id == goal_id)
+            .ok_or_else(|| crate::errors::Error::from(diesel::result::Error::NotFound))?;
+        let start = goal
+            .start_date
+            .as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .ok_or_else(|| invalid_input(format!("Goal {} has no start date", goal.title)))?;
+        let dates = interval.series_dates(from.max(start), to);
+        if dates.len() > MAX_PROGRESS_SERIES_POINTS {
+            return Err(invalid_input(format!(
+                "A progress series is limited to {} points; choose a longer interval",
+                MAX_PROGRESS_SERIES_POINTS
+            )));
+        }
+        let Some(first) = dates.first().copied() else {
+            return Ok(Vec::new());
+        };
+
+        let mut stored: HashMap<String, GoalProgressSnapshot> = self
+            .goal_repo
+            .load_goal_progress_between(goal_id, first, to)
+            .await?
+            .into_iter()
+            .map(|snapshot| (snapshot.query_date.clone(), snapshot))
+            .collect();
+        let keyed: Vec<(NaiveDate, String)> = dates
+            .into_iter()
+            .map(|date| (date, date.format("%Y-%m-%d").to_string()))
+            .collect();
+        let missing = keyed.iter().any(|(_, key)| !stored.contains_key(key));
+        let inputs = if missing && goal.goal_type != GOAL_TYPE_NET_WORTH {
+            self.goal_repo
+                .load_goal_progress_inputs(std::slice::from_ref(&goal.id), start, to)
+                .await?
+        } else {
+            GoalProgressInputs::default()
+        };
+        let debts = self.debt_schedules(std::iter::once(&goal), &inputs)?;
+        let figures_on = |date: NaiveDate| {
+            if goal.goal_type == GOAL_TYPE_DEBT_PAYOFF {
+                AccountFigures::owed_on(&inputs, &debts, date)
+            } else {
+                AccountFigures::on(&inputs, date)
+            }
+        };
+        let at_start = figures_on(start);
+        keyed
+            .into_iter()
+            .map(|(date, key)| match stored.remove(&key) {
+                Some(snapshot) => Ok(snapshot),
+                None => goal_progress_snapshot(
+                    &goal,
+                    inputs.allocations_for_goal(&goal.id),
+                    &at_start,
+                    &figures_on(date),
+                    net_worth_series,
+                    &key,
+                ),
+            })
+            .collect()
+    }
+
+    async fn suggest_allocation_rebalance(
+        &self,
+        account_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<AllocationRebalance> {
+        self.suggest_allocation_rebalance(account_id, as_of).await
     }
 
-    fn get_unallocated_balance(&self, account_id: &str, current_account_value: f64) -> Result<f64> {
-        self.get_unallocated_balance(account_id, current_account_value)
+    async fn distribute_unallocated(
+        &self,
+        account_id: &str,
+        amount: f64,
+        as_of: NaiveDate,
+    ) -> Result<DepositDistribution> {
+        self.distribute_unallocated(account_id, amount, as_of).await
     }
 
-    fn validate_unallocated_balance(&self, account_id: &str, allocation_amount: f64, current_account_value: f64) -> Result<()> {
-        self.validate_unallocated_balance(account_id, allocation_amount, current_account_value)
+    async fn preview_allocation_change(
+        &self,
+        allocation_id: &str,
+        change: AllocationChange,
+        as_of: NaiveDate,
+    ) -> Result<AllocationChangePreview> {
+        self.preview_allocation_change(allocation_id, change, as_of).await
     }
 
-    fn validate_allocation_percentages(&self, account_id: &str, new_percentage: f64, exclude_allocation_id: Option<&str>) -> Result<()> {
-        self.validate_allocation_percentages(account_id, new_percentage, exclude_allocation_id)
+    async fn calculate_required_monthly_investment(
+        &self,
+        goal_id: &str,
+        expected_return_rate: f64,
+        as_of: NaiveDate,
+    ) -> Result<f64> {
+        self.calculate_required_monthly_investment(goal_id, expected_return_rate, as_of)
+            .await
+    }
+
+    async fn get_funding_requirement(
+        &self,
+        goal_id: &str,
+        expected_return_rate: Option<f64>,
+        inflation_rate: Option<f64>,
+        as_of: NaiveDate,
+    ) -> Result<GoalFundingRequirement> {
+        self.get_funding_requirement(goal_id, expected_return_rate, inflation_rate, as_of)
+            .await
+    }
+
+    fn invalidate_allocation_cache(&self) {
+        self.invalidate_allocation_cache()
     }
 
     fn get_repository(&self) -> &dyn GoalRepositoryTrait {
         self.goal_repo.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goals::goal_progress_model::GoalMonthlyProgress;
+    use crate::fx::{ExchangeRate, NewExchangeRate};
+    use crate::goals::goals_model::AllocationVersion;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct CountingGoalRepository {
+        goals: Vec<Goal>,
+        /// Goals inserted through the repository, after `goals`
+        created: Mutex<Vec<Goal>>,
+        allocations: Mutex<Vec<GoalsAllocation>>,
+        versions: Mutex<Vec<AllocationVersion>>,
+        account_queries: AtomicUsize,
+        account_values: HashMap<String, Vec<(NaiveDate, Decimal)>>,
+        account_contributions: HashMap<String, Vec<(NaiveDate, Decimal)>>,
+        stored_progress: Vec<GoalProgressSnapshot>,
+    }
+
+    #[async_trait]
+    impl GoalRepositoryTrait for CountingGoalRepository {
+        async fn load_goals(&self) -> Result<Vec<Goal>> {
+            let goals = self.load_all_goals().await?;
+            Ok(goals.into_iter().filter(|g| g.archived_at.is_none()).collect())
+        }
+        async fn load_all_goals(&self) -> Result<Vec<Goal>> {
+            let created = self.created.lock().unwrap().clone();
+            Ok(self.goals.iter().cloned().chain(created).collect())
+        }
+        async fn set_goal_archived(&self, goal_id: &str, archived: Option<String>) -> Result<Goal> {
+            let mut created = self.created.lock().unwrap();
+            let goal = created.iter_mut().find(|g| g.id == goal_id).unwrap();
+            goal.archived_at = archived;
+            Ok(goal.clone())
+        }
+        async fn set_goal_achieved(&self, goal_id: &str, achieved: Option<String>) -> Result<Goal> {
+            let mut goal = self
+                .load_all_goals()
+                .await?
+                .into_iter()
+                .find(|g| g.id == goal_id)
+                .unwrap();
+            goal.is_achieved = achieved.is_some();
+            goal.achieved_at = achieved;
+            Ok(goal)
+        }
+        async fn insert_new_goal(&self, new_goal: NewGoal) -> Result<Goal> {
+            let mut created = self.created.lock().unwrap();
+            let mut goal = goal(&format!("new-{}", created.len()), "2026-01-01");
+            goal.title = new_goal.title;
+            goal.start_date = new_goal.start_date;
+            goal.depends_on_goal_id = new_goal.depends_on_goal_id;
+            created.push(goal.clone());
+            Ok(goal)
+        }
+        async fn update_goal(&self, _goal_update: Goal) -> Result<Goal> {
+            unimplemented!()
+        }
+        async fn delete_goal(&self, _goal_id_to_delete: String) -> Result<usize> {
+            unimplemented!()
+        }
+        async fn load_allocations_for_non_achieved_goals(&self) -> Result<Vec<GoalsAllocation>> {
+            unimplemented!()
+        }
+        async fn load_all_allocations(&self) -> Result<Vec<GoalsAllocation>> {
+            Ok(self.allocations.lock().unwrap().clone())
+        }
+        async fn upsert_goal_allocations(&self, allocations: Vec<GoalsAllocation>) -> Result<usize> {
+            let count = allocations.len();
+            self.allocations.lock().unwrap().extend(allocations);
+            Ok(count)
+        }
+        async fn get_allocations_for_account_on_date(
+            &self,
+            account_id: &str,
+            query_date: &str,
+        ) -> Result<Vec<GoalsAllocation>> {
+            Ok(self
+                .get_allocations_for_account(account_id)
+                .await?
+                .into_iter()
+                .filter(|a| is_active_on(a, query_date))
+                .collect())
+        }
+        async fn get_allocations_for_goal(&self, goal_id: &str) -> Result<Vec<GoalsAllocation>> {
+            Ok(self
+                .allocations
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|a| a.goal_id == goal_id)
+                .cloned()
+                .collect())
+        }
+        async fn get_allocation_versions(&self, allocation_id: &str) -> Result<Vec<AllocationVersion>> {
+            Ok(self
+                .versions
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|v| v.allocation_id == allocation_id)
+                .cloned()
+                .collect())
+        }
+        async fn get_allocation_by_id(&self, allocation_id: &str) -> Result<GoalsAllocation> {
+            self.allocations
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|a| a.id == allocation_id)
+                .cloned()
+                .ok_or_else(|| diesel::result::Error::NotFound.into())
+        }
+        async fn get_allocations_for_account(&self, account_id: &str) -> Result<Vec<GoalsAllocation>> {
+            self.account_queries.fetch_add(1, Ordering::SeqCst);
+            Ok(self
+                .allocations
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|a| a.account_id == account_id)
+                .cloned()
+                .collect())
+        }
+        async fn insert_allocation_version(
+            &self,
+            _version: AllocationVersion,
+        ) -> Result<AllocationVersion> {
+            unimplemented!()
+        }
+        async fn update_allocation(&self, _allocation: GoalsAllocation) -> Result<GoalsAllocation> {
+            unimplemented!()
+        }
+        async fn delete_allocation(&self, _allocation_id: String) -> Result<usize> {
+            unimplemented!()
+        }
+        async fn reset_allocations_for_goal(
+            &self,
+            _goal_id: String,
+            _new_start_date: Option<String>,
+            _new_end_date: Option<String>,
+        ) -> Result<usize> {
+            unimplemented!()
+        }
+        async fn update_allocations_end_date_for_goal(
+            &self,
+            _goal_id: String,
+            _new_end_date: String,
+        ) -> Result<usize> {
+            unimplemented!()
+        }
+        async fn load_goal_progress_inputs(
+            &self,
+            _goal_ids: &[String],
+            _range_start: NaiveDate,
+            _range_end: NaiveDate,
+        ) -> Result<GoalProgressInputs> {
+            Ok(GoalProgressInputs {
+                allocations: self.allocations.lock().unwrap().clone(),
+                versions: HashMap::new(),
+                account_values: self.account_values.clone(),
+                account_contributions: self.account_contributions.clone(),
+            })
+        }
+        async fn load_goal_monthly_progress(
+            &self,
+            _goal_ids: &[String],
+            _start_month: NaiveDate,
+            _end_month: NaiveDate,
+        ) -> Result<Vec<GoalMonthlyProgress>> {
+            unimplemented!()
+        }
+        async fn save_goal_monthly_progress(&self, _progress: Vec<GoalMonthlyProgress>) -> Result<usize> {
+            unimplemented!()
+        }
+        async fn load_goal_progress(
+            &self,
+            _goal_ids: &[String],
+            _as_of: NaiveDate,
+        ) -> Result<Vec<GoalProgressSnapshot>> {
+            unimplemented!()
+        }
+        async fn save_goal_progress(
+            &self,
+            _as_of: NaiveDate,
+            _progress: Vec<GoalProgressSnapshot>,
+        ) -> Result<usize> {
+            unimplemented!()
+        }
+        async fn load_goal_progress_between(
+            &self,
+            goal_id: &str,
+            from: NaiveDate,
+            to: NaiveDate,
+        ) -> Result<Vec<GoalProgressSnapshot>> {
+            let (from, to) = (from.to_string(), to.to_string());
+            Ok(self
+                .stored_progress
+                .iter()
+                .filter(|p| p.goal_id == goal_id && p.query_date >= from && p.query_date <= to)
+                .cloned()
+                .collect())
+        }
+        async fn latest_goal_progress_date(&self) -> Result<Option<NaiveDate>> {
+            unimplemented!()
+        }
+        async fn load_allocation_versions(&self, allocation_ids: &[String]) -> Result<Vec<AllocationVersion>> {
+            Ok(self
+                .versions
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|v| allocation_ids.contains(&v.allocation_id))
+                .cloned()
+                .collect())
+        }
+        async fn bulk_insert_allocations(
+            &self,
+            allocations: Vec<GoalsAllocation>,
+            versions: Vec<AllocationVersion>,
+        ) -> Result<(usize, usize)> {
+            let counts = (allocations.len(), versions.len());
+            self.allocations.lock().unwrap().extend(allocations);
+            self.versions.lock().unwrap().extend(versions);
+            Ok(counts)
+        }
+        async fn import_goal_plan(
+            &self,
+            goals: Vec<Goal>,
+            allocations: Vec<GoalsAllocation>,
+            versions: Vec<AllocationVersion>,
+        ) -> Result<(usize, usize)> {
+            self.created.lock().unwrap().extend(goals);
+            self.bulk_insert_allocations(allocations, versions).await
+        }
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn goal(id: &str, start_date: &str) -> Goal {
+        Goal {
+            id: id.to_string(),
+            title: id.to_string(),
+            description: None,
+            target_amount: 100_000_000.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: Some("2030-01-01".to_string()),
+            monthly_investment: None,
+            start_date: Some(start_date.to_string()),
+            initial_actual_value: None,
+            goal_type: "STANDARD".to_string(),
+            target_months: None,
+            archived_at: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+            achieved_at: None,
+        }
+    }
+
+    fn allocation(goal_id: &str, account_id: &str, percent: i32) -> GoalsAllocation {
+        GoalsAllocation {
+            id: format!("{}-{}", goal_id, account_id),
+            goal_id: goal_id.to_string(),
+            account_id: account_id.to_string(),
+            init_amount: 0.0,
+            allocation_percentage: percent as f64,
+            allocation_date: None,
+            percent_allocation: percent,
+            start_date: Some("2026-01-01".to_string()),
+            end_date: Some("2030-01-01".to_string()),
+            allocation_amount: 0.0,
+        }
+    }
+
+    #[test]
+    fn progress_of_several_goals_comes_from_one_load() {
+        let inputs = GoalProgressInputs {
+            allocations: vec![
+                allocation("house", "broker", 50),
+                allocation("car", "broker", 50),
+                allocation("car", "savings", 100),
+            ],
+            versions: HashMap::new(),
+            account_values: HashMap::from([
+                (
+                    "broker".to_string(),
+                    vec![
+                        // Carried into the range from before it starts
+                        (date("2025-12-31"), dec!(100_000_000)),
+                        (date("2026-06-30"), dec!(120_000_000)),
+                        (date("2026-10-16"), dec!(140_000_000)),
+                    ],
+                ),
+                (
+                    "savings".to_string(),
+                    vec![(date("2026-03-01"), dec!(10_000_000))],
+                ),
+            ]),
+            account_contributions: HashMap::new(),
+        };
+
+        // A weekend query date reads the last valuation before it
+        assert_eq!(
+            inputs.account_value_on("broker", date("2026-10-18")),
+            Some(dec!(140_000_000))
+        );
+        assert_eq!(inputs.account_value_on("savings", date("2026-01-01")), None);
+
+        let query_date = date("2026-10-18");
+        let current = AccountFigures::on(&inputs, query_date);
+        let house = goal("house", "2026-01-01");
+        let car = goal("car", "2026-07-01");
+        let snapshots: Vec<GoalProgressSnapshot> = [(&house, "2026-01-01"), (&car, "2026-07-01")]
+            .iter()
+            .map(|(goal, start)| {
+                goal_progress_snapshot(
+                    goal,
+                    inputs.allocations_for_goal(&goal.id),
+                    &AccountFigures::on(&inputs, date(start)),
+                    &current,
+                    &[],
+                    "2026-10-18",
+                )
+                .unwrap()
+            })
+            .collect();
+
+        // Half of the broker's growth since 1 Jan
+        assert_eq!(snapshots[0].current_value, 20_000_000.0);
+        assert_eq!(snapshots[0].allocation_details.len(), 1);
+        // Half of the broker's growth since 1 Jul, and no growth on savings
+        assert_eq!(snapshots[1].current_value, 10_000_000.0);
+        assert_eq!(snapshots[1].allocation_details.len(), 2);
+    }
+
+    #[test]
+    fn debt_payoff_progress_is_principal_repaid() {
+        let inputs = GoalProgressInputs {
+            allocations: vec![allocation("debt-free", "card", 50)],
+            versions: HashMap::new(),
+            // Liability accounts without a loan are valued at what they owe, below zero
+            account_values: HashMap::from([(
+                "card".to_string(),
+                vec![
+                    (date("2025-12-31"), dec!(-500_000_000)),
+                    (date("2026-10-16"), dec!(-420_000_000)),
+                ],
+            )]),
+            account_contributions: HashMap::new(),
+        };
+        let mut debt_free = goal("debt-free", "2026-01-01");
+        debt_free.goal_type = GOAL_TYPE_DEBT_PAYOFF.to_string();
+        let debts = HashMap::new();
+
+        let snapshot = goal_progress_snapshot(
+            &debt_free,
+            inputs.allocations_for_goal(&debt_free.id),
+            &AccountFigures::owed_on(&inputs, &debts, date("2026-01-01")),
+            &AccountFigures::owed_on(&inputs, &debts, date("2026-10-18")),
+            &[],
+            "2026-10-18",
+        )
+        .unwrap();
+        // Half of the 80m repaid, none of it market growth
+        assert_eq!(snapshot.current_value, 40_000_000.0);
+        assert_eq!(snapshot.contributed_amount, 40_000_000.0);
+        assert_eq!(snapshot.market_growth, 0.0);
+        assert_eq!(
+            snapshot.allocation_details[0].account_current_value,
+            420_000_000.0
+        );
+    }
+
+    #[tokio::test]
+    async fn account_allocations_are_cached_until_they_change() {
+        let repo = Arc::new(CountingGoalRepository::default());
+        let service = GoalService::new(repo.clone());
+        service
+            .upsert_goal_allocations(vec![allocation("house", "broker", 40)])
+            .await
+            .unwrap();
+
+        // Dragging a slider validates the same account again and again
+        for percent in [10.0, 20.0, 30.0] {
+            service
+                .validate_allocation_percentages("broker", percent, None)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            service
+                .get_unallocated_balance("broker", 0.0)
+                .await
+                .unwrap(),
+            0.0
+        );
+        assert_eq!(repo.account_queries.load(Ordering::SeqCst), 1);
+
+        // Dates are cached apart from the whole list
+        service
+            .get_allocations_for_account_on_date("broker", "2026-10-18")
+            .await
+            .unwrap();
+        service
+            .get_allocations_for_account_on_date("broker", "2026-10-18")
+            .await
+            .unwrap();
+        assert_eq!(repo.account_queries.load(Ordering::SeqCst), 2);
+
+        // Saving allocations through the service clears the cache
+        service
+            .upsert_goal_allocations(vec![allocation("car", "broker", 50)])
+            .await
+            .unwrap();
+        assert!(service
+            .validate_allocation_percentages("broker", 20.0, None)
+            .await
+            .is_err());
+        assert_eq!(repo.account_queries.load(Ordering::SeqCst), 3);
+
+        // So does an allocation-changed event for writes made behind its back
+        repo.allocations.lock().unwrap().clear();
+        GoalServiceTrait::invalidate_allocation_cache(&service);
+        service
+            .validate_allocation_percentages("broker", 100.0, None)
+            .await
+            .unwrap();
+        assert_eq!(repo.account_queries.load(Ordering::SeqCst), 4);
+
+        // A read the event overtakes is returned but not kept
+        let key = AllocationCacheKey {
+            account_id: "bank".to_string(),
+            query_date: None,
+        };
+        let stale = async {
+            GoalServiceTrait::invalidate_allocation_cache(&service);
+            Ok(vec![allocation("car", "bank", 10)])
+        };
+        assert_eq!(service.cached_allocations(key, stale).await.unwrap().len(), 1);
+        assert!(service.get_allocations_for_account("bank").await.unwrap().is_empty());
+        assert_eq!(repo.account_queries.load(Ordering::SeqCst), 5);
+    }
+
+    fn version(
+        id: &str,
+        allocation_id: &str,
+        percent: f64,
+        start: &str,
+        end: Option<&str>,
+    ) -> AllocationVersion {
+        AllocationVersion {
+            id: id.to_string(),
+            allocation_id: allocation_id.to_string(),
+            allocation_percentage: percent,
+            allocation_amount: 0.0,
+            version_start_date: start.to_string(),
+            version_end_date: end.map(str::to_string),
+            created_at: "2026-10-18T00:00:00Z".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn bulk_allocations_are_validated_as_a_whole() {
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("house", "2026-01-01"), goal("car", "2026-01-01")],
+            ..Default::default()
+        });
+        repo.allocations
+            .lock()
+            .unwrap()
+            .push(allocation("house", "broker", 40));
+        let service = GoalService::new(repo.clone());
+
+        // The car goes from 50% to 70% of the broker in July, on top of the house's 40%
+        let mut car = allocation("car", "broker", 50);
+        car.start_date = None;
+        car.end_date = None;
+        let error = service
+            .bulk_insert_allocations(
+                vec![car.clone()],
+                vec![
+                    version("v1", &car.id, 50.0, "2026-01-01", Some("2026-06-30")),
+                    version("v2", &car.id, 70.0, "2026-07-01", None),
+                ],
+            )
+            .await
+            .unwrap_err();
+        let message = error.localized_message().unwrap();
+        assert_eq!(message.code, MessageCode::GoalAllocationExceedsLimitOnDate);
+        assert_eq!(
+            message.to_string(),
+            "Total allocation 110.0% exceeds 100% on account broker on 2026-07-01"
+        );
+        assert_eq!(repo.allocations.lock().unwrap().len(), 1);
+
+        // Versions must belong to an allocation of the set
+        assert!(service
+            .bulk_insert_allocations(
+                vec![car.clone()],
+                vec![version("v1", "house-broker", 10.0, "2026-01-01", None)],
+            )
+            .await
+            .is_err());
+
+        let summary = service
+            .bulk_insert_allocations(
+                vec![car.clone(), allocation("car", "savings", 100)],
+                vec![
+                    version("v1", &car.id, 50.0, "2026-01-01", Some("2026-06-30")),
+                    version("v2", &car.id, 60.0, "2026-07-01", None),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            AllocationBulkInsertSummary {
+                allocations: 2,
+                versions: 2,
+                goals: 1,
+                accounts: 2,
+            }
+        );
+        // Dates were filled in from the goal
+        let stored = repo.allocations.lock().unwrap().clone();
+        assert_eq!(stored[1].start_date.as_deref(), Some("2026-01-01"));
+        assert_eq!(stored[1].end_date.as_deref(), Some("2030-01-01"));
+
+        // Inserting the same allocation again is refused
+        assert!(service
+            .bulk_insert_allocations(vec![car], Vec::new())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn progress_series_reads_stored_days_and_computes_the_rest() {
+        let stored = GoalProgressSnapshot {
+            goal_id: "house".to_string(),
+            goal_title: "house".to_string(),
+            query_date: "2026-02-01".to_string(),
+            init_value: 0.0,
+            current_value: 1.0,
+            growth: 1.0,
+            contributed_amount: 0.0,
+            market_growth: 1.0,
+            allocation_details: Vec::new(),
+        };
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("house", "2026-01-01")],
+            allocations: Mutex::new(vec![allocation("house", "broker", 50)]),
+            account_values: HashMap::from([(
+                "broker".to_string(),
+                vec![
+                    (date("2025-12-31"), dec!(100_000_000)),
+                    (date("2026-02-01"), dec!(110_000_000)),
+                    (date("2026-03-01"), dec!(130_000_000)),
+                ],
+            )]),
+            stored_progress: vec![stored],
+            ..Default::default()
+        });
+        let service = GoalService::new(repo);
+
+        // Points before the goal started are left out
+        let series = service
+            .get_progress_series(
+                "house",
+                date("2025-12-01"),
+                date("2026-03-15"),
+                ProgressInterval::Monthly,
+                &[],
+            )
+            .await
+            .unwrap();
+        let points: Vec<(&str, f64)> = series
+            .iter()
+            .map(|p| (p.query_date.as_str(), p.current_value))
+            .collect();
+        assert_eq!(
+            points,
+            vec![
+                ("2026-01-01", 0.0),
+                // Read back rather than computed
+                ("2026-02-01", 1.0),
+                ("2026-03-01", 15_000_000.0),
+                ("2026-03-15", 15_000_000.0),
+            ]
+        );
+
+        assert!(service
+            .get_progress_series(
+                "house",
+                date("2026-03-15"),
+                date("2026-03-01"),
+                ProgressInterval::Daily,
+                &[],
+            )
+            .await
+            .is_err());
+        assert!(service
+            .get_progress_series(
+                "car",
+                date("2026-01-01"),
+                date("2026-03-01"),
+                ProgressInterval::Daily,
+                &[],
+            )
+            .await
+            .is_err());
+        // Over ten years of days is too many points
+        assert!(service
+            .get_progress_series(
+                "house",
+                date("2026-01-01"),
+                date("2036-01-01"),
+                ProgressInterval::Daily,
+                &[],
+            )
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn rebalance_follows_what_each_goal_needs_per_month() {
+        let snapshot = |goal_id: &str, current_value: f64| GoalProgressSnapshot {
+            goal_id: goal_id.to_string(),
+            goal_title: goal_id.to_string(),
+            query_date: "2026-10-18".to_string(),
+            init_value: 0.0,
+            current_value,
+            growth: current_value,
+            contributed_amount: 0.0,
+            market_growth: current_value,
+            allocation_details: Vec::new(),
+        };
+        let mut house = goal("house", "2026-01-01");
+        house.target_amount = 1_000_000_000.0;
+        house.due_date = Some("2031-10-18".to_string());
+        let mut car = goal("car", "2026-01-01");
+        car.target_amount = 300_000_000.0;
+        car.due_date = Some("2027-10-18".to_string());
+        let mut phone = goal("phone", "2026-01-01");
+        phone.is_achieved = true;
+        let mut ended = allocation("car", "broker", 10);
+        ended.id = "car-broker-2025".to_string();
+        ended.start_date = Some("2025-01-01".to_string());
+        ended.end_date = Some("2025-12-31".to_string());
+        let allocations: Vec<GoalsAllocation> = [
+            allocation("house", "broker", 40),
+            allocation("car", "broker", 40),
+            allocation("phone", "broker", 5),
+            ended,
+        ]
+        .into_iter()
+        .map(|mut a| {
+            a.allocation_percentage = a.percent_allocation as f64;
+            a
+        })
+        .collect();
+
+        let rebalance = rebalance_allocations(
+            "broker",
+            date("2026-10-18"),
+            &allocations,
+            &[house, car, phone],
+            &[snapshot("house", 100_000_000.0), snapshot("car", 60_000_000.0)],
+        );
+
+        // 900m over 60 months against 240m over 12: 15m and 20m a month
+        let suggested: Vec<(&str, Option<u32>, f64, f64)> = rebalance
+            .suggestions
+            .iter()
+            .map(|s| {
+                (s.goal_id.as_str(), s.months_left, s.current_percent, s.suggested_percent)
+            })
+            .collect();
+        assert_eq!(
+            suggested,
+            vec![("house", Some(60), 40.0, 34.2), ("car", Some(12), 40.0, 45.7)]
+        );
+        assert_eq!(rebalance.rebalanced_percent, 80.0);
+        // The achieved goal and the ended allocation keep theirs
+        assert_eq!(rebalance.unallocated_percent, 5.0);
+        let total: f64 = rebalance.suggestions.iter().map(|s| s.suggested_percent).sum();
+        assert!(total <= rebalance.rebalanced_percent);
+    }
+
+    #[tokio::test]
+    async fn versioned_growth_follows_the_percentage_in_force() {
+        let repo = Arc::new(CountingGoalRepository {
+            allocations: Mutex::new(vec![allocation("house", "broker", 50)]),
+            ..Default::default()
+        });
+        let service = GoalService::new(repo.clone());
+        let series = [
+            (date("2025-12-31"), 100.0),
+            (date("2026-03-31"), 120.0),
+            (date("2026-06-30"), 150.0),
+            (date("2026-09-30"), 140.0),
+            (date("2026-12-31"), 200.0),
+        ];
+        let growth = || service.calculate_versioned_growth("house-broker", &series);
+
+        // Without versions the allocation's own 50% applies
+        assert_eq!(growth().await.unwrap(), 50.0);
+
+        // 40% of the first half's 50, then 80% of the second half's 50 until the open end
+        *repo.versions.lock().unwrap() = vec![
+            version("v1", "house-broker", 40.0, "2026-01-01", Some("2026-06-30")),
+            version("v2", "house-broker", 80.0, "2026-07-01", None),
+        ];
+        assert_eq!(growth().await.unwrap(), 60.0);
+
+        // Where versions overlap the later one is in force, so nothing is counted twice
+        repo.versions.lock().unwrap()[0].version_end_date = Some("2026-09-30".to_string());
+        assert_eq!(growth().await.unwrap(), 60.0);
+
+        // A stretch no version covers adds nothing
+        repo.versions.lock().unwrap()[0].version_end_date = Some("2026-03-31".to_string());
+        assert_eq!(growth().await.unwrap(), 48.0);
+    }
+
+    #[test]
+    fn deposits_fill_higher_priorities_first_then_follow_monthly_need() {
+        let snapshot = |goal_id: &str, current_value: f64| GoalProgressSnapshot {
+            goal_id: goal_id.to_string(),
+            goal_title: goal_id.to_string(),
+            query_date: "2026-10-18".to_string(),
+            init_value: 0.0,
+            current_value,
+            growth: current_value,
+            contributed_amount: 0.0,
+            market_growth: current_value,
+            allocation_details: Vec::new(),
+        };
+        let mut cushion = goal("cushion", "2026-01-01");
+        cushion.target_amount = 50_000_000.0;
+        cushion.due_date = None;
+        cushion.priority = 2;
+        let mut house = goal("house", "2026-01-01");
+        house.target_amount = 1_000_000_000.0;
+        house.due_date = Some("2031-10-18".to_string());
+        house.priority = 1;
+        let mut car = goal("car", "2026-01-01");
+        car.target_amount = 300_000_000.0;
+        car.due_date = Some("2027-10-18".to_string());
+        car.priority = 1;
+        let mut trip = goal("trip", "2026-01-01");
+        trip.target_amount = 20_000_000.0;
+        let goals = [cushion, house, car, trip];
+        let allocations = [
+            allocation("cushion", "broker", 10),
+            allocation("house", "broker", 40),
+            allocation("car", "broker", 40),
+            // Funded elsewhere, so no share of this deposit
+            allocation("trip", "bank", 100),
+        ];
+        let progress = [
+            snapshot("cushion", 30_000_000.0),
+            snapshot("house", 100_000_000.0),
+            snapshot("car", 60_000_000.0),
+            snapshot("trip", 0.0),
+        ];
+        let distribute = |amount: f64| {
+            let plan = distribute_deposit(
+                "broker",
+                amount,
+                date("2026-10-18"),
+                &allocations,
+                &goals,
+                &progress,
+            );
+            let amounts: Vec<(String, f64)> = plan
+                .goals
+                .iter()
+                .map(|deposit| (deposit.goal_id.clone(), deposit.amount))
+                .collect();
+            (amounts, plan.unassigned)
+        };
+
+        // The cushion is topped up first; the car needs 20m a month against the house's 15m
+        let (amounts, unassigned) = distribute(100_000_000.0);
+        assert_eq!(
+            amounts,
+            vec![
+                ("cushion".to_string(), 20_000_000.0),
+                ("car".to_string(), 45_714_285.0),
+                ("house".to_string(), 34_285_714.0),
+            ]
+        );
+        assert_eq!(unassigned, 1.0);
+
+        // No goal gets more than it still needs
+        let (amounts, unassigned) = distribute(2_000_000_000.0);
+        assert_eq!(
+            amounts,
+            vec![
+                ("cushion".to_string(), 20_000_000.0),
+                ("car".to_string(), 240_000_000.0),
+                ("house".to_string(), 900_000_000.0),
+            ]
+        );
+        assert_eq!(unassigned, 840_000_000.0);
+    }
+
+    #[test]
+    fn funding_requirement_solves_for_the_monthly_contribution() {
+        // Without a return the gap is simply spread over the months left
+        assert_eq!(
+            required_monthly_investment(300_000_000.0, 60_000_000.0, 0.0, 12),
+            20_000_000.0
+        );
+        // At 6% a year what is already saved grows too, so less is needed each month
+        let monthly = required_monthly_investment(300_000_000.0, 60_000_000.0, 6.0, 12);
+        assert!((monthly - 19_155_943.0).abs() < 1.0, "{}", monthly);
+        // A value that reaches the target on its own needs nothing more
+        assert_eq!(required_monthly_investment(100.0, 100.0, 6.0, 12), 0.0);
+
+        let mut car = goal("car", "2026-01-01");
+        car.target_amount = 300_000_000.0;
+        car.due_date = Some("2028-10-18".to_string());
+        let requirement =
+            funding_requirement(&car, 60_000_000.0, 0.0, 4.0, date("2026-10-18")).unwrap();
+        assert_eq!(requirement.months_left, 24);
+        assert_eq!(requirement.required_monthly, 10_000_000.0);
+        // Two years at 4% turn 300m into 324.48m
+        assert!((requirement.inflation_adjusted_target - 324_480_000.0).abs() < 1.0);
+        assert!((requirement.inflation_adjusted_monthly - 11_020_000.0).abs() < 1.0);
+
+        car.due_date = None;
+        assert!(funding_requirement(&car, 0.0, 0.0, 4.0, date("2026-10-18")).is_err());
+        car.due_date = Some("2028-10-18".to_string());
+        assert!(funding_requirement(&car, 0.0, -100.0, 4.0, date("2026-10-18")).is_err());
+    }
+
+    #[test]
+    fn goals_wait_for_their_prerequisite_without_forming_cycles() {
+        let cushion = goal("cushion", "2026-01-01");
+        let mut house = goal("house", "2026-01-01");
+        house.depends_on_goal_id = Some("cushion".to_string());
+        let mut renovation = goal("renovation", "2026-01-01");
+        renovation.depends_on_goal_id = Some("house".to_string());
+        let goals = vec![cushion, house, renovation];
+
+        // Only the goal at the head of the chain takes cash until its prerequisite is reached
+        let ready: Vec<String> = ready_goals(goals.clone()).into_iter().map(|g| g.id).collect();
+        assert_eq!(ready, ["cushion"]);
+        let mut reached = goals.clone();
+        reached[0].is_achieved = true;
+        let ready: Vec<String> = ready_goals(reached).into_iter().map(|g| g.id).collect();
+        assert_eq!(ready, ["cushion", "house"]);
+
+        assert!(validate_goal_dependency(Some("car"), Some("house"), &goals).is_ok());
+        assert!(validate_goal_dependency(None, Some("renovation"), &goals).is_ok());
+        assert!(validate_goal_dependency(Some("car"), Some("boat"), &goals).is_err());
+        assert!(validate_goal_dependency(Some("house"), Some("house"), &goals).is_err());
+        // cushion -> renovation -> house -> cushion
+        assert!(validate_goal_dependency(Some("cushion"), Some("renovation"), &goals).is_err());
+    }
+
+    #[tokio::test]
+    async fn goal_progress_reads_the_account_values_itself() {
+        let mut undated = goal("undated", "2026-01-01");
+        undated.start_date = None;
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("house", "2026-01-01"), undated],
+            allocations: Mutex::new(vec![allocation("house", "broker", 50)]),
+            account_values: HashMap::from([(
+                "broker".to_string(),
+                vec![
+                    (date("2025-12-31"), dec!(100_000_000)),
+                    (date("2026-03-01"), dec!(130_000_000)),
+                ],
+            )]),
+            ..Default::default()
+        });
+        let service = GoalService::new(repo.clone());
+
+        let progress = service
+            .get_goal_progress("house", date("2026-03-15"))
+            .await
+            .unwrap()
+            .unwrap();
+        // Same as handing the values at the start and on the day in by hand
+        let by_hand = goal_progress_snapshot(
+            &repo.goals[0],
+            repo.allocations.lock().unwrap().iter(),
+            &AccountFigures {
+                values: HashMap::from([("broker".to_string(), 100_000_000.0)]),
+                ..Default::default()
+            },
+            &AccountFigures {
+                values: HashMap::from([("broker".to_string(), 130_000_000.0)]),
+                ..Default::default()
+            },
+            &[],
+            "2026-03-15",
+        )
+        .unwrap();
+        assert_eq!(
+            (progress.init_value, progress.current_value, progress.growth),
+            (by_hand.init_value, by_hand.current_value, by_hand.growth)
+        );
+        // Half of the 30m the account grew since the goal started
+        assert_eq!(progress.current_value, 15_000_000.0);
+
+        assert!(service
+            .get_goal_progress("undated", date("2026-03-15"))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(service.get_goal_progress("missing", date("2026-03-15")).await.is_err());
+    }
+
+    /// Dollar to dong by day, the only pair a goal test converts
+    struct DongRates(HashMap<NaiveDate, Decimal>);
+
+    #[async_trait]
+    impl FxServiceTrait for DongRates {
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn get_historical_rates(
+            &self,
+            _from_currency: &str,
+            _to_currency: &str,
+            _days: i64,
+        ) -> Result<Vec<ExchangeRate>> {
+            unimplemented!()
+        }
+        fn get_latest_exchange_rate(&self, _from: &str, _to: &str) -> Result<Decimal> {
+            unimplemented!()
+        }
+        fn get_exchange_rate_for_date(
+            &self,
+            from_currency: &str,
+            to_currency: &str,
+            date: NaiveDate,
+        ) -> Result<Decimal> {
+            assert_eq!((from_currency, to_currency), ("USD", "VND"));
+            Ok(self.0[&date])
+        }
+        fn convert_currency(&self, _amount: Decimal, _from: &str, _to: &str) -> Result<Decimal> {
+            unimplemented!()
+        }
+        fn convert_currency_for_date(
+            &self,
+            _amount: Decimal,
+            _from_currency: &str,
+            _to_currency: &str,
+            _date: NaiveDate,
+        ) -> Result<Decimal> {
+            unimplemented!()
+        }
+        fn get_latest_exchange_rates(&self) -> Result<Vec<ExchangeRate>> {
+            unimplemented!()
+        }
+        async fn add_exchange_rate(&self, _new_rate: NewExchangeRate) -> Result<ExchangeRate> {
+            unimplemented!()
+        }
+        async fn update_exchange_rate(
+            &self,
+            _from_currency: &str,
+            _to_currency: &str,
+            _rate: Decimal,
+        ) -> Result<ExchangeRate> {
+            unimplemented!()
+        }
+        async fn delete_exchange_rate(&self, _rate_id: &str) -> Result<()> {
+            unimplemented!()
+        }
+        async fn register_currency_pair(&self, _from: &str, _to: &str) -> Result<()> {
+            unimplemented!()
+        }
+        async fn register_currency_pair_manual(&self, _from: &str, _to: &str) -> Result<()> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn goals_in_their_own_currency_convert_the_account_growth() {
+        let mut house = goal("house", "2026-01-01");
+        house.currency = Some("VND".to_string());
+        let mut car = goal("car", "2026-01-01");
+        car.currency = Some("USD".to_string());
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![house.clone(), car.clone(), goal("boat", "2026-01-01")],
+            allocations: Mutex::new(vec![
+                allocation("house", "broker", 50),
+                allocation("car", "broker", 25),
+                allocation("boat", "broker", 25),
+            ]),
+            // A dollar brokerage account, in a dollar base currency
+            account_values: HashMap::from([(
+                "broker".to_string(),
+                vec![
+                    (date("2025-12-31"), dec!(10_000)),
+                    (date("2026-03-01"), dec!(12_000)),
+                ],
+            )]),
+            ..Default::default()
+        });
+        let rates = DongRates(HashMap::from([
+            (date("2026-01-01"), dec!(25_000)),
+            (date("2026-03-15"), dec!(26_000)),
+        ]));
+        let service = GoalService::new(repo.clone())
+            .with_fx(Arc::new(rates), Arc::new(RwLock::new("USD".to_string())));
+
+        let progress = service
+            .calculate_goals_progress_on_date(&repo.goals, &[], date("2026-03-15"))
+            .await
+            .unwrap();
+        let values: Vec<(&str, f64)> = progress
+            .iter()
+            .map(|p| (p.goal_id.as_str(), p.current_value))
+            .collect();
+        // Half of 250m growing to 312m dong; the base currency and no currency stay in dollars
+        assert_eq!(values, [("house", 31_000_000.0), ("car", 500.0), ("boat", 500.0)]);
+
+        // Values handed in are converted the same way
+        let by_hand = service
+            .calculate_goal_progress_on_date(
+                &house,
+                &HashMap::from([("broker".to_string(), 10_000.0)]),
+                &HashMap::from([("broker".to_string(), 12_000.0)]),
+                &[],
+                "2026-03-15",
+            )
+            .await
+            .unwrap();
+        assert_eq!(by_hand.current_value, 31_000_000.0);
+
+        assert!(validate_goal_currency(Some("VND")).is_ok());
+        assert!(validate_goal_currency(Some("dong")).is_err());
+    }
+
+    #[tokio::test]
+    async fn percentages_only_add_up_where_allocations_overlap_in_time() {
+        let mut ended = allocation("boat", "broker", 60);
+        ended.end_date = Some("2026-06-30".to_string());
+        let mut current = allocation("house", "broker", 40);
+        current.end_date = Some("2026-12-31".to_string());
+        let mut later = allocation("car", "broker", 60);
+        later.start_date = Some("2027-01-01".to_string());
+        let repo = Arc::new(CountingGoalRepository {
+            allocations: Mutex::new(vec![
+                ended,
+                current,
+                later,
+                allocation("school", "broker", 10),
+            ]),
+            // Lowered from 30% to 10% at the start of October
+            versions: Mutex::new(vec![
+                version("v1", "school-broker", 30.0, "2026-01-01", Some("2026-09-30")),
+                version("v2", "school-broker", 10.0, "2026-10-01", None),
+            ]),
+            ..Default::default()
+        });
+        let service = GoalService::new(repo);
+        let validate = |percent: f64, exclude: Option<&'static str>| {
+            let service = &service;
+            async move {
+                let today = date("2026-10-18");
+                service
+                    .validate_allocation_percentages_from("broker", percent, exclude, today)
+                    .await
+            }
+        };
+
+        // The ended allocation and the old school version no longer count: 40 + 10 today, and
+        // from January the car's 60 + 10
+        assert!(validate(30.0, None).await.is_ok());
+        let err = validate(31.0, None).await.unwrap_err().to_string();
+        assert!(err.contains("2027-01-01"), "{}", err);
+        // The house allocation ends before the car starts, so it can go up to 90%
+        assert!(validate(90.0, Some("house-broker")).await.is_ok());
+        assert!(validate(91.0, Some("house-broker")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn goal_plans_move_between_profiles_under_new_ids() {
+        let mut house = goal("house", "2026-01-01");
+        house.depends_on_goal_id = Some("cushion".to_string());
+        let mut boat = goal("boat", "2026-01-01");
+        boat.archived_at = Some("2026-09-01T00:00:00+00:00".to_string());
+        // The dependent goal comes first, to be inserted after its prerequisite all the same
+        let source = Arc::new(CountingGoalRepository {
+            goals: vec![house, goal("cushion", "2026-01-01"), boat],
+            allocations: Mutex::new(vec![
+                allocation("cushion", "broker", 30),
+                allocation("house", "bank", 50),
+                allocation("boat", "broker", 10),
+            ]),
+            versions: Mutex::new(vec![version(
+                "v1",
+                "cushion-broker",
+                30.0,
+                "2026-01-01",
+                None,
+            )]),
+            ..Default::default()
+        });
+        let account = |id: &str, name: &str| Account {
+            id: id.to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let plan = GoalService::new(source)
+            .export_goal_plan(&[account("broker", "Broker"), account("bank", "Bank")])
+            .await
+            .unwrap();
+        assert_eq!(plan.account_names.len(), 2);
+
+        // The broker is found by name on the other profile; the bank is not there
+        let accounts = [account("broker-2", "Broker")];
+        let target = Arc::new(CountingGoalRepository {
+            allocations: Mutex::new(vec![allocation("car", "broker-2", 61)]),
+            ..Default::default()
+        });
+        let service = GoalService::new(target.clone());
+        assert!(service.import_goal_plan(plan.clone(), &accounts).await.is_err());
+        assert!(target.created.lock().unwrap().is_empty());
+
+        target.allocations.lock().unwrap().clear();
+        GoalServiceTrait::invalidate_allocation_cache(&service);
+        let result = service.import_goal_plan(plan, &accounts).await.unwrap();
+        assert_eq!(
+            result,
+            GoalPlanImportResult {
+                goals: 3,
+                allocations: 2,
+                versions: 1,
+                unmatched_accounts: vec!["bank".to_string()],
+            }
+        );
+        let created = target.created.lock().unwrap().clone();
+        let id_of = |title: &str| created.iter().find(|g| g.title == title).unwrap().id.clone();
+        let house = created.iter().find(|g| g.title == "house").unwrap();
+        assert_eq!(house.depends_on_goal_id, Some(id_of("cushion")));
+        assert!(created.iter().any(|g| g.title == "boat" && g.archived_at.is_some()));
+        let allocations = target.allocations.lock().unwrap().clone();
+        assert!(allocations.iter().all(|a| a.account_id == "broker-2"));
+        let versions = target.versions.lock().unwrap().clone();
+        let cushion_allocation = allocations
+            .iter()
+            .find(|a| a.goal_id == id_of("cushion"))
+            .unwrap();
+        assert_eq!(versions[0].allocation_id, cushion_allocation.id);
+    }
+
+    #[tokio::test]
+    async fn goals_reaching_their_target_are_marked_achieved() {
+        let snapshot = |goal_id: &str, current_value: f64| GoalProgressSnapshot {
+            goal_id: goal_id.to_string(),
+            goal_title: goal_id.to_string(),
+            query_date: "2026-10-18".to_string(),
+            init_value: 0.0,
+            current_value,
+            growth: current_value,
+            contributed_amount: 0.0,
+            market_growth: current_value,
+            allocation_details: Vec::new(),
+        };
+        let mut phone = goal("phone", "2026-01-01");
+        phone.is_achieved = true;
+        let mut cushion = goal("cushion", "2026-01-01");
+        cushion.goal_type = GOAL_TYPE_EMERGENCY_FUND.to_string();
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("car", "2026-01-01"), goal("house", "2026-01-01"), phone, cushion],
+            ..Default::default()
+        });
+        let service = GoalService::new(repo);
+
+        let achieved = service
+            .mark_achieved_goals(
+                &[
+                    snapshot("car", 100_000_000.0),
+                    snapshot("house", 99_999_999.0),
+                    snapshot("phone", 500_000_000.0),
+                    snapshot("cushion", 500_000_000.0),
+                ],
+                date("2026-10-18"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(achieved.len(), 1);
+        assert_eq!(achieved[0].id, "car");
+        assert!(achieved[0].is_achieved);
+        assert_eq!(achieved[0].achieved_at.as_deref(), Some("2026-10-18"));
+    }
+
+    #[tokio::test]
+    async fn allocation_changes_are_previewed_without_saving() {
+        let mut house = allocation("house", "broker", 40);
+        house.allocation_amount = 30_000_000.0;
+        let mut car = allocation("car", "broker", 40);
+        car.allocation_amount = 50_000_000.0;
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("house", "2026-01-01"), goal("car", "2026-01-01")],
+            allocations: Mutex::new(vec![house, car]),
+            account_values: HashMap::from([(
+                "broker".to_string(),
+                vec![
+                    (date("2025-12-31"), dec!(100_000_000)),
+                    (date("2026-03-01"), dec!(150_000_000)),
+                ],
+            )]),
+            ..Default::default()
+        });
+        let service = GoalService::new(repo.clone());
+        let as_of = date("2026-03-01");
+
+        let preview = service
+            .preview_allocation_change(
+                "house-broker",
+                AllocationChange::Percentage { value: 50.0 },
+                as_of,
+            )
+            .await
+            .unwrap();
+        assert!(preview.errors.is_empty());
+        assert_eq!(preview.unallocated_balance, 70_000_000.0);
+        let house = preview.goals.iter().find(|g| g.goal_id == "house").unwrap();
+        // 20m of the 100m in 59 days puts the rest 236 days away; at 25m it is 177 days
+        assert_eq!((house.current_value, house.new_value), (20_000_000.0, 25_000_000.0));
+        assert_eq!(house.completion_date.as_deref(), Some("2026-10-23"));
+        assert_eq!(house.new_completion_date.as_deref(), Some("2026-08-25"));
+        let car = preview.goals.iter().find(|g| g.goal_id == "car").unwrap();
+        assert_eq!(car.new_completion_date, car.completion_date);
+
+        let too_much = service
+            .preview_allocation_change(
+                "house-broker",
+                AllocationChange::Percentage { value: 61.0 },
+                as_of,
+            )
+            .await
+            .unwrap();
+        assert_eq!(too_much.errors.len(), 1);
+
+        let amount = service
+            .preview_allocation_change(
+                "house-broker",
+                AllocationChange::Amount { value: 60_000_000.0 },
+                as_of,
+            )
+            .await
+            .unwrap();
+        assert!(amount.errors.is_empty());
+        assert_eq!(amount.unallocated_balance, 40_000_000.0);
+        let over = service
+            .preview_allocation_change(
+                "house-broker",
+                AllocationChange::Amount { value: 130_000_000.0 },
+                as_of,
+            )
+            .await
+            .unwrap();
+        assert_eq!(over.errors.len(), 1);
+        assert_eq!(over.unallocated_balance, 0.0);
+
+        // Nothing was saved
+        assert_eq!(repo.allocations.lock().unwrap()[0].allocation_percentage, 40.0);
+        assert!(service
+            .preview_allocation_change(
+                "missing",
+                AllocationChange::Amount { value: 0.0 },
+                as_of,
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn goals_are_funded_from_every_account_of_a_group() {
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("house", "2026-01-01"), goal("car", "2026-01-01")],
+            allocations: Mutex::new(vec![allocation("car", "broker", 80)]),
+            ..Default::default()
+        });
+        let service = GoalService::new(repo.clone());
+        let group = vec!["broker".to_string(), "bank".to_string()];
+
+        // The broker has 20% left, so 30% of it is too much and nothing is saved
+        assert!(service.allocate_goal_to_accounts("house", &group, 30.0).await.is_err());
+        assert_eq!(repo.allocations.lock().unwrap().len(), 1);
+
+        let summary = service
+            .allocate_goal_to_accounts("house", &group, 20.0)
+            .await
+            .unwrap();
+        assert_eq!(summary.allocations, 2);
+        let allocations = repo.allocations.lock().unwrap().clone();
+        assert!(allocations
+            .iter()
+            .filter(|a| a.goal_id == "house")
+            .all(|a| a.allocation_percentage == 20.0
+                && a.start_date.as_deref() == Some("2026-01-01")));
+
+        let mut broker = allocation("car", "broker", 80);
+        broker.allocation_amount = 60_000_000.0;
+        *repo.allocations.lock().unwrap() = vec![broker];
+        GoalServiceTrait::invalidate_allocation_cache(&service);
+        let unallocated = service
+            .get_unallocated_balance_for_accounts(&HashMap::from([
+                ("broker".to_string(), 100_000_000.0),
+                ("bank".to_string(), 50_000_000.0),
+            ]))
+            .await
+            .unwrap();
+        assert_eq!(unallocated, 90_000_000.0);
+    }
+
+    struct FixedCash(Decimal);
+
+    #[async_trait]
+    impl CashServiceTrait for FixedCash {
+        fn get_account_cash(&self, account_id: &str) -> Result<crate::cash::AccountCash> {
+            Ok(crate::cash::AccountCash {
+                account_id: account_id.to_string(),
+                account_currency: "VND".to_string(),
+                as_of: None,
+                balances: Vec::new(),
+                total: self.0,
+            })
+        }
+
+        async fn deposit_cash(
+            &self,
+            _movement: crate::cash::CashMovement,
+        ) -> Result<crate::activities::Activity> {
+            unimplemented!()
+        }
+
+        async fn withdraw_cash(
+            &self,
+            _movement: crate::cash::CashMovement,
+        ) -> Result<crate::activities::Activity> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn unallocated_balance_can_come_from_the_account_cash() {
+        let mut broker = allocation("car", "broker", 50);
+        broker.allocation_amount = 15_000_000.0;
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("car", "2026-01-01")],
+            allocations: Mutex::new(vec![broker]),
+            ..Default::default()
+        });
+        assert!(GoalService::new(repo.clone())
+            .get_unallocated_cash("broker")
+            .await
+            .is_err());
+
+        let service =
+            GoalService::new(repo).with_cash(Arc::new(FixedCash(dec!(40_000_000))));
+        assert_eq!(
+            service.get_unallocated_cash("broker").await.unwrap(),
+            25_000_000.0
+        );
+    }
+
+    struct LatestValuation(Decimal);
+
+    #[async_trait]
+    impl ValuationServiceTrait for LatestValuation {
+        async fn calculate_valuation_history(&self, _account_id: &str, _all: bool) -> Result<()> {
+            unimplemented!()
+        }
+
+        fn get_historical_valuations(
+            &self,
+            _account_id: &str,
+            _start_date_opt: Option<NaiveDate>,
+            _end_date_opt: Option<NaiveDate>,
+        ) -> Result<Vec<crate::portfolio::valuation::DailyAccountValuation>> {
+            unimplemented!()
+        }
+
+        fn get_latest_valuations(
+            &self,
+            account_ids: &[String],
+        ) -> Result<Vec<crate::portfolio::valuation::DailyAccountValuation>> {
+            Ok(account_ids
+                .iter()
+                .filter(|account_id| account_id.as_str() == "broker")
+                .map(|account_id| crate::portfolio::valuation::DailyAccountValuation {
+                    id: format!("{}_2026-10-16", account_id),
+                    account_id: account_id.clone(),
+                    valuation_date: date("2026-10-16"),
+                    account_currency: "VND".to_string(),
+                    base_currency: "VND".to_string(),
+                    fx_rate_to_base: Decimal::ONE,
+                    cash_balance: Decimal::ZERO,
+                    investment_market_value: self.0,
+                    total_value: self.0,
+                    cost_basis: self.0,
+                    net_contribution: self.0,
+                    calculated_at: Utc::now(),
+                })
+                .collect())
+        }
+
+        fn get_valuations_on_date(
+            &self,
+            _account_ids: &[String],
+            _date: NaiveDate,
+        ) -> Result<Vec<crate::portfolio::valuation::DailyAccountValuation>> {
+            unimplemented!()
+        }
+
+        fn get_monthly_summaries(
+            &self,
+            _account_ids: Option<&[String]>,
+            _start_date: Option<NaiveDate>,
+            _end_date: Option<NaiveDate>,
+        ) -> Result<Vec<crate::portfolio::valuation::AccountMonthlySummary>> {
+            unimplemented!()
+        }
+    }
+
+    #[tokio::test]
+    async fn unallocated_balance_can_come_from_the_latest_valuation() {
+        let mut broker = allocation("car", "broker", 50);
+        broker.allocation_amount = 60_000_000.0;
+        let mut bank = allocation("car", "bank", 50);
+        bank.allocation_amount = 10_000_000.0;
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("car", "2026-01-01")],
+            allocations: Mutex::new(vec![broker, bank]),
+            ..Default::default()
+        });
+        assert!(GoalService::new(repo.clone())
+            .get_unallocated_value("broker")
+            .await
+            .is_err());
+
+        let service = GoalService::new(repo)
+            .with_valuations(Arc::new(LatestValuation(dec!(100_000_000))));
+        assert_eq!(
+            service.get_unallocated_value("broker").await.unwrap(),
+            40_000_000.0
+        );
+        // An account not valued yet has nothing left to allocate
+        assert_eq!(service.get_unallocated_value("bank").await.unwrap(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn goal_growth_is_split_into_contributions_and_market_growth() {
+        let series = |points: &[(&str, Decimal)]| {
+            points
+                .iter()
+                .map(|(day, value)| (date(day), *value))
+                .collect::<Vec<_>>()
+        };
+        let repo = Arc::new(CountingGoalRepository {
+            goals: vec![goal("house", "2026-01-01")],
+            allocations: Mutex::new(vec![allocation("house", "broker", 50)]),
+            account_values: HashMap::from([(
+                "broker".to_string(),
+                series(&[
+                    ("2025-12-31", dec!(100_000_000)),
+                    ("2026-10-16", dec!(150_000_000)),
+                ]),
+            )]),
+            // 20m of the 50m the broker grew by was paid in
+            account_contributions: HashMap::from([(
+                "broker".to_string(),
+                series(&[
+                    ("2025-12-31", dec!(90_000_000)),
+                    ("2026-10-16", dec!(110_000_000)),
+                ]),
+            )]),
+            ..Default::default()
+        });
+        let progress = GoalService::new(repo)
+            .get_goal_progress("house", date("2026-10-18"))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(progress.growth, 25_000_000.0);
+        assert_eq!(progress.contributed_amount, 10_000_000.0);
+        assert_eq!(progress.market_growth, 15_000_000.0);
+        let detail = &progress.allocation_details[0];
+        assert_eq!(
+            (detail.contributed_amount, detail.market_growth),
+            (10_000_000.0, 15_000_000.0)
+        );
+    }
+}
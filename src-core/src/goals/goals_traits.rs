@@ -1,47 +1,144 @@
+use crate::accounts::Account;
 use crate::errors::Result;
-use crate::goals::goals_model::{Goal, GoalsAllocation, NewGoal, AllocationVersion};
+use crate::goals::goal_progress_model::{
+    DebtPayoffProgress, EmergencyFundProgress, GoalMonthlyProgress, GoalProgressInputs,
+    GoalProgressSnapshot, NetWorthGoalProgress, NetWorthPoint, ProgressInterval,
+    SinkingFundProgress,
+};
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use crate::goals::goals_model::{
+    AllocationBulkInsertSummary, AllocationChange, AllocationChangePreview, AllocationRebalance,
+    AllocationVersion, DepositDistribution, Goal, GoalFundingRequirement, GoalPlanExport,
+    GoalPlanImportResult, GoalsAllocation, NewGoal,
+};
 use async_trait::async_trait;
 
-/// Trait for goal repository operations
+/// Trait for goal repository operations. Reads run on the blocking pool (see `db::spawn_read`)
+/// rather than on the async runtime threads.
 #[async_trait]
 pub trait GoalRepositoryTrait: Send + Sync {
-    fn load_goals(&self) -> Result<Vec<Goal>>;
+    /// Goals that are not archived
+    async fn load_goals(&self) -> Result<Vec<Goal>>;
+    /// Load ALL goals including archived ones (for restoring and history)
+    async fn load_all_goals(&self) -> Result<Vec<Goal>>;
+    /// Sets or, with `None`, clears when a goal was archived
+    async fn set_goal_archived(&self, goal_id: &str, archived: Option<String>) -> Result<Goal>;
+    /// Marks a goal achieved on the given day or, with `None`, not achieved
+    async fn set_goal_achieved(&self, goal_id: &str, achieved: Option<String>) -> Result<Goal>;
     async fn insert_new_goal(&self, new_goal: NewGoal) -> Result<Goal>;
     async fn update_goal(&self, goal_update: Goal) -> Result<Goal>;
     async fn delete_goal(&self, goal_id_to_delete: String) -> Result<usize>;
-    fn load_allocations_for_non_achieved_goals(&self) -> Result<Vec<GoalsAllocation>>;
+    async fn load_allocations_for_non_achieved_goals(&self) -> Result<Vec<GoalsAllocation>>;
     /// Load ALL allocations including from completed goals (for display/chart purposes)
-    fn load_all_allocations(&self) -> Result<Vec<GoalsAllocation>>;
+    async fn load_all_allocations(&self) -> Result<Vec<GoalsAllocation>>;
+    /// Writes the batch in one transaction; nothing is kept if an account it touches ends
+    /// up above 100%
     async fn upsert_goal_allocations(&self, allocations: Vec<GoalsAllocation>) -> Result<usize>;
-    fn get_allocations_for_account_on_date(
+    async fn get_allocations_for_account_on_date(
         &self,
         account_id: &str,
         query_date: &str,
     ) -> Result<Vec<GoalsAllocation>>;
     // New hybrid allocation methods
-    fn get_allocations_for_goal(&self, goal_id: &str) -> Result<Vec<GoalsAllocation>>;
-    fn get_allocation_versions(&self, allocation_id: &str) -> Result<Vec<AllocationVersion>>;
-    fn get_allocation_by_id(&self, allocation_id: &str) -> Result<GoalsAllocation>;
-    fn get_allocations_for_account(&self, account_id: &str) -> Result<Vec<GoalsAllocation>>;
+    async fn get_allocations_for_goal(&self, goal_id: &str) -> Result<Vec<GoalsAllocation>>;
+    async fn get_allocation_versions(&self, allocation_id: &str) -> Result<Vec<AllocationVersion>>;
+    /// Versions of all the given allocations, oldest first
+    async fn load_allocation_versions(&self, allocation_ids: &[String]) -> Result<Vec<AllocationVersion>>;
+    async fn get_allocation_by_id(&self, allocation_id: &str) -> Result<GoalsAllocation>;
+    async fn get_allocations_for_account(&self, account_id: &str) -> Result<Vec<GoalsAllocation>>;
     async fn insert_allocation_version(&self, version: AllocationVersion) -> Result<AllocationVersion>;
+    /// Saves an allocation and, when its percentage or amount changed, closes its open version
+    /// and starts a new one from today in the same transaction
     async fn update_allocation(&self, allocation: GoalsAllocation) -> Result<GoalsAllocation>;
     async fn delete_allocation(&self, allocation_id: String) -> Result<usize>;
     /// Reset all allocations for a specific goal to 0 and update their dates (used when goal start_date changes)
     async fn reset_allocations_for_goal(&self, goal_id: String, new_start_date: Option<String>, new_end_date: Option<String>) -> Result<usize>;
     /// Update end_date for all allocations of a goal (used when goal due_date changes)
     async fn update_allocations_end_date_for_goal(&self, goal_id: String, new_end_date: String) -> Result<usize>;
+    /// Allocations of `goal_ids`, their versions and the values of their accounts from
+    /// `start_date` to `end_date`, in a handful of queries whatever the number of goals
+    async fn load_goal_progress_inputs(
+        &self,
+        goal_ids: &[String],
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<GoalProgressInputs>;
+    /// Stored month-end progress of `goal_ids` for the months between the two (inclusive)
+    async fn load_goal_monthly_progress(
+        &self,
+        goal_ids: &[String],
+        start_month: NaiveDate,
+        end_month: NaiveDate,
+    ) -> Result<Vec<GoalMonthlyProgress>>;
+    async fn save_goal_monthly_progress(&self, progress: Vec<GoalMonthlyProgress>) -> Result<usize>;
+    /// Stored progress of `goal_ids` as of `as_of`; goals without a row are left out
+    async fn load_goal_progress(&self, goal_ids: &[String], as_of: NaiveDate) -> Result<Vec<GoalProgressSnapshot>>;
+    async fn save_goal_progress(&self, as_of: NaiveDate, progress: Vec<GoalProgressSnapshot>) -> Result<usize>;
+    /// Stored progress of one goal from `from` to `to` (inclusive), oldest first
+    async fn load_goal_progress_between(&self, goal_id: &str, from: NaiveDate, to: NaiveDate) -> Result<Vec<GoalProgressSnapshot>>;
+    /// Last day any goal's progress is stored for
+    async fn latest_goal_progress_date(&self) -> Result<Option<NaiveDate>>;
+    /// Inserts the allocations, then the versions, in one transaction; returns how many of each
+    async fn bulk_insert_allocations(
+        &self,
+        allocations: Vec<GoalsAllocation>,
+        versions: Vec<AllocationVersion>,
+    ) -> Result<(usize, usize)>;
+    /// Writes an imported goal plan in one transaction: the goals in the order given, then
+    /// their allocations and versions held to the 100% cap, and last the archive dates.
+    /// Nothing is kept when any of it fails; returns how many allocations and versions went in.
+    async fn import_goal_plan(
+        &self,
+        goals: Vec<Goal>,
+        allocations: Vec<GoalsAllocation>,
+        versions: Vec<AllocationVersion>,
+    ) -> Result<(usize, usize)>;
 }
 
 /// Trait for goal service operations
 #[async_trait]
 pub trait GoalServiceTrait: Send + Sync {
-    fn get_goals(&self) -> Result<Vec<Goal>>;
+    async fn get_goals(&self) -> Result<Vec<Goal>>;
+    /// Goals including archived ones
+    async fn get_all_goals(&self) -> Result<Vec<Goal>>;
+    /// Hides a goal from lists and progress while keeping its allocations and history
+    async fn archive_goal(&self, goal_id: &str) -> Result<Goal>;
+    /// Brings an archived goal back
+    async fn restore_goal(&self, goal_id: &str) -> Result<Goal>;
+    /// Marks the goals whose value in `progress` reached their target as achieved on `on` and
+    /// returns them; goals already achieved are left alone
+    async fn mark_achieved_goals(
+        &self,
+        progress: &[GoalProgressSnapshot],
+        on: NaiveDate,
+    ) -> Result<Vec<Goal>>;
     async fn create_goal(&self, new_goal: NewGoal) -> Result<Goal>;
     async fn update_goal(&self, updated_goal_data: Goal) -> Result<Goal>;
+    /// Deletes an archived goal and its allocations; active goals have to be archived first
     async fn delete_goal(&self, goal_id_to_delete: String) -> Result<usize>;
     async fn upsert_goal_allocations(&self, allocations: Vec<GoalsAllocation>) -> Result<usize>;
-    fn load_goals_allocations(&self) -> Result<Vec<GoalsAllocation>>;
-    fn validate_allocation_conflicts(
+    async fn load_goals_allocations(&self) -> Result<Vec<GoalsAllocation>>;
+    /// Inserts many allocations and their versions at once, e.g. when migrating a spreadsheet.
+    /// The whole set is validated first, percentage totals per account included, and nothing
+    /// is written unless all of it is valid.
+    async fn bulk_insert_allocations(
+        &self,
+        allocations: Vec<GoalsAllocation>,
+        versions: Vec<AllocationVersion>,
+    ) -> Result<AllocationBulkInsertSummary>;
+    /// Every goal, archived ones included, with all allocations and versions, and the names of
+    /// the allocated accounts among `accounts`
+    async fn export_goal_plan(&self, accounts: &[Account]) -> Result<GoalPlanExport>;
+    /// Recreates an exported plan under new ids, allocations going to the matching account
+    /// among `accounts`. The plan is validated as a whole, allocation totals per account
+    /// included, before anything is written.
+    async fn import_goal_plan(
+        &self,
+        plan: GoalPlanExport,
+        accounts: &[Account],
+    ) -> Result<GoalPlanImportResult>;
+    async fn validate_allocation_conflicts(
         &self,
         account_id: &str,
         start_date: &str,
@@ -50,8 +147,115 @@ pub trait GoalServiceTrait: Send + Sync {
         exclude_allocation_id: Option<&str>,
     ) -> Result<()>;
     // New hybrid allocation methods
-    fn get_unallocated_balance(&self, account_id: &str, current_account_value: f64) -> Result<f64>;
-    fn validate_unallocated_balance(&self, account_id: &str, allocation_amount: f64, current_account_value: f64) -> Result<()>;
-    fn validate_allocation_percentages(&self, account_id: &str, new_percentage: f64, exclude_allocation_id: Option<&str>) -> Result<()>;
+    async fn get_unallocated_balance(&self, account_id: &str, current_account_value: f64) -> Result<f64>;
+    async fn validate_unallocated_balance(&self, account_id: &str, allocation_amount: f64, current_account_value: f64) -> Result<()>;
+    /// Unallocated part of the cash an account actually holds, in the account's currency;
+    /// fails when the service was built without cash balances
+    async fn get_unallocated_cash(&self, account_id: &str) -> Result<f64>;
+    /// Unallocated part of an account's latest stored valuation, in the account's currency;
+    /// fails when the service was built without valuations
+    async fn get_unallocated_value(&self, account_id: &str) -> Result<f64>;
+    /// Unallocated balance of several accounts together, from the current value of each
+    async fn get_unallocated_balance_for_accounts(
+        &self,
+        account_values: &HashMap<String, f64>,
+    ) -> Result<f64>;
+    /// One allocation of `percentage` in each account, validated and saved together
+    async fn allocate_goal_to_accounts(
+        &self,
+        goal_id: &str,
+        account_ids: &[String],
+        percentage: f64,
+    ) -> Result<AllocationBulkInsertSummary>;
+    async fn validate_allocation_percentages(&self, account_id: &str, new_percentage: f64, exclude_allocation_id: Option<&str>) -> Result<()>;
+    /// Progress of every goal with a start date on `query_date`, computed in one pass
+    async fn get_goals_progress_on_date(
+        &self,
+        net_worth_series: &[NetWorthPoint],
+        query_date: NaiveDate,
+    ) -> Result<Vec<GoalProgressSnapshot>>;
+    /// Progress of one goal every `interval` from `from` (or the goal's start, if later) to
+    /// `to`, both included. Stored days are read back; the rest are computed from one load
+    /// of the goal's allocations and account values.
+    async fn get_progress_series(
+        &self,
+        goal_id: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: ProgressInterval,
+        net_worth_series: &[NetWorthPoint],
+    ) -> Result<Vec<GoalProgressSnapshot>>;
+    /// New percentages for the allocations of an account that follow what each goal still
+    /// needs per month; nothing is changed
+    async fn suggest_allocation_rebalance(
+        &self,
+        account_id: &str,
+        as_of: NaiveDate,
+    ) -> Result<AllocationRebalance>;
+    /// How a deposit into an account should be split across the goals it funds, by priority
+    /// and monthly need; nothing is changed
+    async fn distribute_unallocated(
+        &self,
+        account_id: &str,
+        amount: f64,
+        as_of: NaiveDate,
+    ) -> Result<DepositDistribution>;
+    /// What changing an allocation would do to the account's unallocated balance and to when
+    /// its goals are reached, and why it could not be saved; nothing is changed
+    async fn preview_allocation_change(
+        &self,
+        allocation_id: &str,
+        change: AllocationChange,
+        as_of: NaiveDate,
+    ) -> Result<AllocationChangePreview>;
+    /// Monthly contribution that reaches a goal's target by its due date at the given return
+    async fn calculate_required_monthly_investment(
+        &self,
+        goal_id: &str,
+        expected_return_rate: f64,
+        as_of: NaiveDate,
+    ) -> Result<f64>;
+    /// Monthly contribution a goal needs, nominal and with its target grown by inflation
+    async fn get_funding_requirement(
+        &self,
+        goal_id: &str,
+        expected_return_rate: Option<f64>,
+        inflation_rate: Option<f64>,
+        as_of: NaiveDate,
+    ) -> Result<GoalFundingRequirement>;
+    /// Drops cached allocations after they were changed through the repository directly
+    fn invalidate_allocation_cache(&self);
+    /// Bypasses the allocation cache; writes through it must be followed by
+    /// `invalidate_allocation_cache`
     fn get_repository(&self) -> &dyn GoalRepositoryTrait;
 }
+
+/// Progress of emergency fund goals, which target months of expenses
+#[async_trait]
+pub trait EmergencyFundServiceTrait: Send + Sync {
+    async fn get_emergency_fund_progress(&self) -> Result<Vec<EmergencyFundProgress>>;
+}
+
+/// Progress of sinking funds, which are reported apart from investment goals
+#[async_trait]
+pub trait SinkingFundServiceTrait: Send + Sync {
+    async fn get_sinking_fund_progress(&self) -> Result<Vec<SinkingFundProgress>>;
+}
+
+/// Progress of debt payoff goals, read from the remaining principal of their loans
+#[async_trait]
+pub trait DebtPayoffServiceTrait: Send + Sync {
+    async fn get_debt_payoff_progress(&self) -> Result<Vec<DebtPayoffProgress>>;
+}
+
+/// Net worth history and progress of net-worth goals
+#[async_trait]
+pub trait NetWorthGoalServiceTrait: Send + Sync {
+    /// Daily net worth in the base currency; `end_date` defaults to today
+    fn get_net_worth_series(
+        &self,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<NetWorthPoint>>;
+    async fn get_net_worth_goal_progress(&self) -> Result<Vec<NetWorthGoalProgress>>;
+}
@@ -1,11 +1,43 @@
+pub mod allocation_periods;
+pub mod dashboard_summary;
+pub mod debt_payoff_service;
+pub mod emergency_fund_service;
 pub mod goals_model;
 pub mod goals_repository;
 pub mod goals_service;
 pub mod goals_traits;
+pub mod goal_progress_history;
 pub mod goal_progress_model;
+pub mod monthly_summaries;
+pub mod net_worth_service;
+pub mod sinking_fund_service;
 
+pub use dashboard_summary::{
+    build_dashboard_summary, get_dashboard_summary, DashboardGoal, DashboardSummary,
+    DEFAULT_SUMMARY_GOALS, MAX_SUMMARY_GOALS,
+};
+pub use debt_payoff_service::DebtPayoffService;
+pub use emergency_fund_service::EmergencyFundService;
 pub use goals_repository::GoalRepository;
 pub use goals_service::GoalService;
-pub use goals_traits::{GoalRepositoryTrait, GoalServiceTrait};
-pub use goal_progress_model::{GoalProgressSnapshot, GoalProgressHistory, AllocationDetail};
-pub use goals_model::{GoalsAllocation, AllocationVersion};
+pub use net_worth_service::NetWorthGoalService;
+pub use sinking_fund_service::SinkingFundService;
+pub use goals_traits::{
+    DebtPayoffServiceTrait, EmergencyFundServiceTrait, GoalRepositoryTrait, GoalServiceTrait, NetWorthGoalServiceTrait,
+    SinkingFundServiceTrait,
+};
+pub use goal_progress_model::{GoalProgressSnapshot, DebtBalance, DebtPayoffProgress, GoalMonthlyProgress, GoalProgressHistory, GoalProgressInputs, AllocationDetail, EmergencyFundProgress, NetWorthGoalProgress, NetWorthPoint, ProgressInterval, SinkingFundProgress, MAX_PROGRESS_SERIES_POINTS};
+pub use goals_model::{
+    AllocationBulkInsertSummary, AllocationChange, AllocationChangePreview, AllocationRebalance,
+    AllocationRebalanceSuggestion, AllocationVersion, DepositDistribution, GoalChangePreview,
+    GoalDeposit, GoalFundingRequirement, GoalPlanExport, GoalPlanImportResult, GoalsAllocation,
+    DEFAULT_GOAL_INFLATION_RATE, GOAL_PLAN_EXPORT_VERSION, UNDATED_GOAL_HORIZON_MONTHS,
+};
+pub use goal_progress_history::{
+    get_goal_progress, get_goal_progress_series, get_goals_progress, record_goal_progress_history,
+    refresh_goal_progress,
+    GOAL_PROGRESS_BACKFILL_DAYS,
+};
+pub use monthly_summaries::{
+    get_goal_monthly_progress, get_monthly_summaries, MonthlySummaries, DEFAULT_SUMMARY_MONTHS,
+};
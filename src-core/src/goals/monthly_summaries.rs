@@ -0,0 +1,228 @@
+use chrono::{Months, NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::errors::Result;
+use crate::goals::goal_progress_history;
+use crate::goals::goal_progress_model::GoalMonthlyProgress;
+use crate::goals::goals_model::{Goal, GOAL_TYPE_NET_WORTH};
+use crate::goals::goals_traits::{GoalServiceTrait, NetWorthGoalServiceTrait};
+use crate::portfolio::valuation::{AccountMonthlySummary, ValuationServiceTrait};
+use crate::schema::goal_monthly_progress;
+use crate::utils::time_utils::{month_start, next_month_start};
+
+/// Month-by-month totals for charts and dashboards, read from the summary tables instead of
+/// being re-aggregated from daily rows
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlySummaries {
+    /// By account, then month
+    pub accounts: Vec<AccountMonthlySummary>,
+    /// By goal, then month; the current month is as of today
+    pub goals: Vec<GoalMonthlyProgress>,
+}
+
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = goal_monthly_progress)]
+struct GoalMonthlyProgressDb {
+    goal_id: String,
+    month_start: NaiveDate,
+    as_of: NaiveDate,
+    init_value: f64,
+    current_value: f64,
+    growth: f64,
+    updated_at: String,
+}
+
+/// Drops stored progress, monthly and daily, of one goal (all when `None`) from `from_date`'s
+/// month on (all months when `None`). Called in the transaction of any write the progress is
+/// computed from.
+pub(crate) fn clear_goal_progress(
+    conn: &mut SqliteConnection,
+    goal_id: Option<&str>,
+    from_date: Option<NaiveDate>,
+) -> Result<()> {
+    let mut query = diesel::delete(goal_monthly_progress::table).into_boxed();
+    if let Some(goal_id) = goal_id {
+        query = query.filter(goal_monthly_progress::goal_id.eq(goal_id));
+    }
+    if let Some(from_date) = from_date {
+        query = query.filter(goal_monthly_progress::month_start.ge(month_start(from_date)));
+    }
+    query.execute(conn)?;
+    goal_progress_history::clear_goal_progress_history(conn, goal_id, from_date)
+}
+
+pub(crate) fn load_goal_monthly_progress(
+    conn: &mut SqliteConnection,
+    goal_ids: &[String],
+    start_month: NaiveDate,
+    end_month: NaiveDate,
+) -> Result<Vec<GoalMonthlyProgress>> {
+    Ok(goal_monthly_progress::table
+        .filter(goal_monthly_progress::goal_id.eq_any(goal_ids))
+        .filter(goal_monthly_progress::month_start.ge(start_month))
+        .filter(goal_monthly_progress::month_start.le(end_month))
+        .load::<GoalMonthlyProgressDb>(conn)?
+        .into_iter()
+        .map(|row| GoalMonthlyProgress {
+            goal_id: row.goal_id,
+            month: row.month_start,
+            as_of: row.as_of,
+            init_value: row.init_value,
+            current_value: row.current_value,
+            growth: row.growth,
+        })
+        .collect())
+}
+
+pub(crate) fn save_goal_monthly_progress(
+    conn: &mut SqliteConnection,
+    progress: &[GoalMonthlyProgress],
+) -> Result<usize> {
+    let updated_at = Utc::now().to_rfc3339();
+    let rows: Vec<GoalMonthlyProgressDb> = progress
+        .iter()
+        .map(|p| GoalMonthlyProgressDb {
+            goal_id: p.goal_id.clone(),
+            month_start: p.month,
+            as_of: p.as_of,
+            init_value: p.init_value,
+            current_value: p.current_value,
+            growth: p.growth,
+            updated_at: updated_at.clone(),
+        })
+        .collect();
+    let mut saved = 0;
+    for chunk in rows.chunks(500) {
+        saved += diesel::replace_into(goal_monthly_progress::table)
+            .values(chunk)
+            .execute(conn)?;
+    }
+    Ok(saved)
+}
+
+fn goal_start(goal: &Goal) -> Option<NaiveDate> {
+    goal.start_date
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+}
+
+/// Progress of every started goal at the end of each month from `start_date`'s month to
+/// `today`'s. Closed months are read from storage and only computed (then stored) when
+/// missing; the current month is always computed as of `today`.
+pub async fn get_goal_monthly_progress(
+    goal_service: &dyn GoalServiceTrait,
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    start_date: NaiveDate,
+    today: NaiveDate,
+) -> Result<Vec<GoalMonthlyProgress>> {
+    let goals: Vec<(Goal, NaiveDate)> = goal_service
+        .get_goals()
+        .await?
+        .into_iter()
+        .filter_map(|goal| goal_start(&goal).map(|start| (goal, start)))
+        .collect();
+    let first_month = month_start(start_date);
+    let current_month = month_start(today);
+    if goals.is_empty() || first_month > current_month {
+        return Ok(Vec::new());
+    }
+
+    let goal_ids: Vec<String> = goals.iter().map(|(goal, _)| goal.id.clone()).collect();
+    let repository = goal_service.get_repository();
+    let mut progress: HashMap<(String, NaiveDate), GoalMonthlyProgress> = repository
+        .load_goal_monthly_progress(&goal_ids, first_month, current_month)
+        .await?
+        .into_iter()
+        .map(|p| ((p.goal_id.clone(), p.month), p))
+        .collect();
+
+    // Only net-worth goals read the series, from the earliest of their start dates
+    let net_worth_start = goals
+        .iter()
+        .filter(|(goal, _)| goal.goal_type == GOAL_TYPE_NET_WORTH)
+        .map(|(_, start)| (*start).min(today))
+        .min();
+    let mut series = None;
+    let mut computed = Vec::new();
+    let mut month = first_month;
+    while month <= current_month {
+        let is_closed = month < current_month;
+        let as_of = if is_closed {
+            next_month_start(month).pred_opt().unwrap_or(month)
+        } else {
+            today
+        };
+        let started: HashSet<&str> = goals
+            .iter()
+            .filter(|(_, start)| *start <= as_of)
+            .map(|(goal, _)| goal.id.as_str())
+            .collect();
+        let missing = !is_closed
+            || started
+                .iter()
+                .any(|goal_id| !progress.contains_key(&(goal_id.to_string(), month)));
+        if missing && !started.is_empty() {
+            if series.is_none() {
+                series = Some(match net_worth_start {
+                    Some(start) => {
+                        net_worth_service.get_net_worth_series(Some(start), Some(today))?
+                    }
+                    None => Vec::new(),
+                });
+            }
+            let snapshots = goal_service
+                .get_goals_progress_on_date(series.as_deref().unwrap_or_default(), as_of)
+                .await?;
+            for snapshot in snapshots {
+                if !started.contains(snapshot.goal_id.as_str()) {
+                    continue;
+                }
+                let row = GoalMonthlyProgress {
+                    goal_id: snapshot.goal_id,
+                    month,
+                    as_of,
+                    init_value: snapshot.init_value,
+                    current_value: snapshot.current_value,
+                    growth: snapshot.growth,
+                };
+                if is_closed {
+                    computed.push(row.clone());
+                }
+                progress.insert((row.goal_id.clone(), month), row);
+            }
+        }
+        month = next_month_start(month);
+    }
+    if !computed.is_empty() {
+        repository.save_goal_monthly_progress(computed).await?;
+    }
+
+    let mut progress: Vec<GoalMonthlyProgress> = progress.into_values().collect();
+    progress.sort_by(|a, b| (&a.goal_id, a.month).cmp(&(&b.goal_id, b.month)));
+    Ok(progress)
+}
+
+/// Months a summary covers, the current one included, when the caller does not say
+pub const DEFAULT_SUMMARY_MONTHS: u32 = 12;
+
+/// Account totals and goal progress by month from `start_date`'s month (by default
+/// `DEFAULT_SUMMARY_MONTHS` back) to today's
+pub async fn get_monthly_summaries(
+    valuation_service: &dyn ValuationServiceTrait,
+    goal_service: &dyn GoalServiceTrait,
+    net_worth_service: &dyn NetWorthGoalServiceTrait,
+    start_date: Option<NaiveDate>,
+) -> Result<MonthlySummaries> {
+    let today = Utc::now().date_naive();
+    let start_date = start_date.unwrap_or_else(|| {
+        month_start(today) - Months::new(DEFAULT_SUMMARY_MONTHS.saturating_sub(1))
+    });
+    Ok(MonthlySummaries {
+        accounts: valuation_service.get_monthly_summaries(None, Some(start_date), Some(today))?,
+        goals: get_goal_monthly_progress(goal_service, net_worth_service, start_date, today)
+            .await?,
+    })
+}
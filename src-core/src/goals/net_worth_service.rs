@@ -0,0 +1,387 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use std::sync::{Arc, RwLock};
+
+use crate::accounts::{AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+use crate::fx::FxServiceTrait;
+use crate::goals::goal_progress_model::{
+    GoalProgressSnapshot, NetWorthGoalProgress, NetWorthPoint,
+};
+use crate::goals::goals_model::{Goal, GOAL_TYPE_NET_WORTH};
+use crate::goals::goals_traits::{GoalRepositoryTrait, NetWorthGoalServiceTrait};
+use crate::loans::{amortize, outstanding_on, LoanRepositoryTrait};
+use crate::portfolio::valuation::ValuationRepositoryTrait;
+use crate::utils::time_utils::month_start;
+
+pub struct NetWorthGoalService {
+    goal_repo: Arc<dyn GoalRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    valuation_repository: Arc<dyn ValuationRepositoryTrait>,
+    loan_repository: Arc<dyn LoanRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+/// Dated values of one account in the base currency
+pub(crate) struct AccountHistory {
+    pub is_liability: bool,
+    /// Ordered by date
+    pub values: Vec<(NaiveDate, Decimal)>,
+}
+
+impl NetWorthGoalService {
+    pub fn new(
+        goal_repo: Arc<dyn GoalRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        valuation_repository: Arc<dyn ValuationRepositoryTrait>,
+        loan_repository: Arc<dyn LoanRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            goal_repo,
+            account_repository,
+            valuation_repository,
+            loan_repository,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    /// Active accounts' histories up to `end`. Accounts with a loan follow its amortization
+    /// schedule; other accounts follow their daily valuations. With a `start`, daily
+    /// valuations are only read from its month on, and the value carried into that month
+    /// comes from the previous month's summary.
+    fn account_histories(
+        &self,
+        start: Option<NaiveDate>,
+        end: NaiveDate,
+    ) -> Result<Vec<AccountHistory>> {
+        let from_month = start.map(month_start);
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let mut histories = Vec::new();
+        for account in self.account_repository.list(Some(true), None)? {
+            let is_liability = account.account_type == ACCOUNT_TYPE_LIABILITY;
+            let loan = if is_liability {
+                self.loan_repository.get_loan_for_account(&account.id)?
+            } else {
+                None
+            };
+
+            let values = match loan {
+                Some(loan) => {
+                    let rate = if account.currency == base_currency {
+                        Decimal::ONE
+                    } else {
+                        self.fx_service.convert_currency(
+                            Decimal::ONE,
+                            &account.currency,
+                            &base_currency,
+                        )?
+                    };
+                    let prepayments = self.loan_repository.get_prepayments(Some(&loan.id))?;
+                    let resets = self.loan_repository.get_rate_resets(Some(&loan.id))?;
+                    let rows = amortize(&loan, &prepayments, &resets);
+                    let dates: BTreeSet<NaiveDate> = std::iter::once(loan.start_date)
+                        .chain(rows.iter().map(|r| r.payment_date))
+                        .chain(prepayments.iter().map(|p| p.payment_date))
+                        .filter(|date| *date <= end)
+                        .collect();
+                    dates
+                        .into_iter()
+                        .map(|date| {
+                            let balance = outstanding_on(&loan, &rows, &prepayments, date);
+                            (date, balance * rate)
+                        })
+                        .collect()
+                }
+                None => {
+                    let in_base = |value: Decimal| if is_liability { value.abs() } else { value };
+                    let carried = match from_month.and_then(|month| month.pred_opt()) {
+                        Some(before) => self
+                            .valuation_repository
+                            .get_monthly_summaries(
+                                Some(std::slice::from_ref(&account.id)),
+                                None,
+                                Some(before),
+                            )?
+                            .pop()
+                            .map(|summary| {
+                                (summary.last_date, in_base(summary.closing_value_in_base()))
+                            }),
+                        None => None,
+                    };
+                    carried
+                        .into_iter()
+                        .chain(
+                            self.valuation_repository
+                                .get_historical_valuations(&account.id, from_month, Some(end))?
+                                .into_iter()
+                                .map(|v| {
+                                    (v.valuation_date, in_base(v.total_value * v.fx_rate_to_base))
+                                }),
+                        )
+                        .collect()
+                }
+            };
+            histories.push(AccountHistory {
+                is_liability,
+                values,
+            });
+        }
+        Ok(histories)
+    }
+}
+
+/// Combines account histories into net worth on every date any account has a value for.
+/// An account keeps its last value on dates it has none of its own.
+pub(crate) fn net_worth_series(histories: &[AccountHistory]) -> Vec<NetWorthPoint> {
+    let dates: BTreeSet<NaiveDate> = histories
+        .iter()
+        .flat_map(|h| h.values.iter().map(|(date, _)| *date))
+        .collect();
+    let mut cursors = vec![0usize; histories.len()];
+    let mut latest = vec![Decimal::ZERO; histories.len()];
+
+    let mut series = Vec::with_capacity(dates.len());
+    for date in dates {
+        let mut total_assets = Decimal::ZERO;
+        let mut total_liabilities = Decimal::ZERO;
+        for (i, history) in histories.iter().enumerate() {
+            while let Some((value_date, value)) = history.values.get(cursors[i]) {
+                if *value_date > date {
+                    break;
+                }
+                latest[i] = *value;
+                cursors[i] += 1;
+            }
+            if history.is_liability {
+                total_liabilities += latest[i];
+            } else {
+                total_assets += latest[i];
+            }
+        }
+        series.push(NetWorthPoint {
+            date,
+            total_assets,
+            total_liabilities,
+            net_worth: total_assets - total_liabilities,
+        });
+    }
+    series
+}
+
+/// Net worth as it stood on `date`
+pub(crate) fn net_worth_on(series: &[NetWorthPoint], date: NaiveDate) -> Option<&NetWorthPoint> {
+    series.iter().rev().find(|point| point.date <= date)
+}
+
+/// Progress snapshot of a net-worth goal: the current value is net worth on `query_date`
+/// and growth is measured from net worth on the goal's start date
+pub(crate) fn net_worth_snapshot(
+    goal: &Goal,
+    goal_start_date: &str,
+    net_worth_series: &[NetWorthPoint],
+    query_date: &str,
+) -> Result<GoalProgressSnapshot> {
+    let value_on = |date: &str| -> Result<f64> {
+        let date = parse_forecast_date(date)?;
+        Ok(net_worth_on(net_worth_series, date)
+            .and_then(|point| point.net_worth.to_f64())
+            .unwrap_or_default())
+    };
+    let init_value = value_on(goal_start_date)?;
+    let current_value = value_on(query_date)?;
+
+    Ok(GoalProgressSnapshot {
+        goal_id: goal.id.clone(),
+        goal_title: goal.title.clone(),
+        query_date: query_date.to_string(),
+        init_value,
+        current_value,
+        growth: current_value - init_value,
+        contributed_amount: 0.0,
+        market_growth: 0.0,
+        allocation_details: Vec::new(),
+    })
+}
+
+pub(crate) fn net_worth_goal_progress(
+    goal: &Goal,
+    series: &[NetWorthPoint],
+    today: NaiveDate,
+    base_currency: &str,
+) -> Result<NetWorthGoalProgress> {
+    let target_amount = Decimal::from_f64_retain(goal.target_amount).unwrap_or_default();
+    let start_net_worth = match goal.start_date.as_deref() {
+        Some(date) => net_worth_on(series, parse_forecast_date(date)?).map(|p| p.net_worth),
+        None => None,
+    };
+    let current = net_worth_on(series, today)
+        .cloned()
+        .map(|point| NetWorthPoint {
+            date: today,
+            ..point
+        })
+        .unwrap_or(NetWorthPoint {
+            date: today,
+            total_assets: Decimal::ZERO,
+            total_liabilities: Decimal::ZERO,
+            net_worth: Decimal::ZERO,
+        });
+    let remaining = (target_amount - current.net_worth).max(Decimal::ZERO);
+
+    Ok(NetWorthGoalProgress {
+        goal_id: goal.id.clone(),
+        goal_title: goal.title.clone(),
+        base_currency: base_currency.to_string(),
+        target_amount,
+        due_date: goal
+            .due_date
+            .as_deref()
+            .map(parse_forecast_date)
+            .transpose()?,
+        start_net_worth,
+        progress: (target_amount > Decimal::ZERO)
+            .then(|| (current.net_worth / target_amount).round_dp(4)),
+        is_reached: remaining.is_zero(),
+        remaining,
+        current,
+    })
+}
+
+#[async_trait]
+impl NetWorthGoalServiceTrait for NetWorthGoalService {
+    fn get_net_worth_series(
+        &self,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<NetWorthPoint>> {
+        let end = end_date.unwrap_or_else(|| Utc::now().date_naive());
+        if start_date.is_some_and(|start| start > end) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Start date must be on or before the end date".to_string(),
+            )));
+        }
+        let series = net_worth_series(&self.account_histories(start_date, end)?);
+        let Some(start) = start_date else {
+            return Ok(series);
+        };
+        // Keep the value carried into the range so it starts from the right net worth
+        let opening = net_worth_on(&series, start)
+            .cloned()
+            .map(|point| NetWorthPoint {
+                date: start,
+                ..point
+            });
+        Ok(opening
+            .into_iter()
+            .chain(series.into_iter().filter(|point| point.date > start))
+            .collect())
+    }
+
+    async fn get_net_worth_goal_progress(&self) -> Result<Vec<NetWorthGoalProgress>> {
+        let goals: Vec<Goal> = self
+            .goal_repo
+            .load_goals()
+            .await?
+            .into_iter()
+            .filter(|goal| goal.goal_type == GOAL_TYPE_NET_WORTH && !goal.is_achieved)
+            .collect();
+        if goals.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let today = Utc::now().date_naive();
+        // Only the goals' start dates and today are looked up in the series
+        let mut earliest = today;
+        for goal in &goals {
+            if let Some(start_date) = goal.start_date.as_deref() {
+                earliest = earliest.min(parse_forecast_date(start_date)?);
+            }
+        }
+        let series = net_worth_series(&self.account_histories(Some(earliest), today)?);
+        goals
+            .iter()
+            .map(|goal| net_worth_goal_progress(goal, &series, today, &base_currency))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn subtracts_liabilities_and_carries_values_forward() {
+        let histories = vec![
+            AccountHistory {
+                is_liability: false,
+                values: vec![
+                    (date("2026-01-01"), dec!(3_000_000_000)),
+                    (date("2026-03-01"), dec!(3_500_000_000)),
+                ],
+            },
+            AccountHistory {
+                is_liability: true,
+                values: vec![
+                    (date("2026-02-01"), dec!(1_000_000_000)),
+                    (date("2026-04-01"), dec!(900_000_000)),
+                ],
+            },
+        ];
+
+        let series = net_worth_series(&histories);
+        let net_worth: Vec<Decimal> = series.iter().map(|p| p.net_worth).collect();
+        // The loan only counts from when it was taken out
+        assert_eq!(
+            net_worth,
+            vec![
+                dec!(3_000_000_000),
+                dec!(2_000_000_000),
+                dec!(2_500_000_000),
+                dec!(2_600_000_000),
+            ]
+        );
+
+        let goal = Goal {
+            id: "fi".to_string(),
+            title: "5 tỷ by 40".to_string(),
+            description: None,
+            target_amount: 5_000_000_000.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: Some("2030-06-01".to_string()),
+            monthly_investment: None,
+            start_date: Some("2026-02-15".to_string()),
+            initial_actual_value: None,
+            goal_type: GOAL_TYPE_NET_WORTH.to_string(),
+            target_months: None,
+            archived_at: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+            achieved_at: None,
+        };
+        let progress = net_worth_goal_progress(&goal, &series, date("2026-10-17"), "VND").unwrap();
+        assert_eq!(progress.start_net_worth, Some(dec!(2_000_000_000)));
+        assert_eq!(progress.current.total_liabilities, dec!(900_000_000));
+        assert_eq!(progress.remaining, dec!(2_400_000_000));
+        assert_eq!(progress.progress, Some(dec!(0.52)));
+
+        let snapshot = net_worth_snapshot(&goal, "2026-02-15", &series, "2026-03-15").unwrap();
+        assert_eq!(snapshot.current_value, 2_500_000_000.0);
+        assert_eq!(snapshot.growth, 500_000_000.0);
+    }
+}
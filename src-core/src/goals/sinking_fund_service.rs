@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use chrono::{Months, NaiveDate, Utc};
+use log::warn;
+use rust_decimal::Decimal;
+use std::sync::{Arc, RwLock};
+
+use crate::errors::Result;
+use crate::forecast::parse_forecast_date;
+use crate::goals::emergency_fund_service::allocated_value;
+use crate::goals::goal_progress_model::SinkingFundProgress;
+use crate::goals::goals_model::{Goal, GOAL_TYPE_SINKING_FUND};
+use crate::goals::goals_traits::{GoalRepositoryTrait, SinkingFundServiceTrait};
+
+pub struct SinkingFundService {
+    goal_repo: Arc<dyn GoalRepositoryTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl SinkingFundService {
+    pub fn new(goal_repo: Arc<dyn GoalRepositoryTrait>, base_currency: Arc<RwLock<String>>) -> Self {
+        Self {
+            goal_repo,
+            base_currency,
+        }
+    }
+}
+
+/// Monthly set-asides left before `due_date`, counting one made today
+fn set_aside_months(today: NaiveDate, due_date: NaiveDate) -> u32 {
+    let mut months = 0;
+    while today
+        .checked_add_months(Months::new(months))
+        .is_some_and(|date| date < due_date)
+    {
+        months += 1;
+    }
+    months
+}
+
+pub(crate) fn sinking_fund_progress(
+    goal: &Goal,
+    due_date: NaiveDate,
+    current_value: Decimal,
+    today: NaiveDate,
+    base_currency: &str,
+) -> SinkingFundProgress {
+    let target_amount = Decimal::from_f64_retain(goal.target_amount).unwrap_or_default();
+    let remaining = (target_amount - current_value).max(Decimal::ZERO);
+    let months_remaining = set_aside_months(today, due_date);
+    let monthly_set_aside = (remaining / Decimal::from(months_remaining.max(1))).round_dp(2);
+
+    SinkingFundProgress {
+        goal_id: goal.id.clone(),
+        goal_title: goal.title.clone(),
+        base_currency: base_currency.to_string(),
+        target_amount,
+        due_date,
+        current_value,
+        remaining,
+        months_remaining,
+        monthly_set_aside,
+        progress: (target_amount > Decimal::ZERO)
+            .then(|| (current_value / target_amount).round_dp(4)),
+        is_funded: remaining.is_zero(),
+    }
+}
+
+#[async_trait]
+impl SinkingFundServiceTrait for SinkingFundService {
+    async fn get_sinking_fund_progress(&self) -> Result<Vec<SinkingFundProgress>> {
+        let goals: Vec<Goal> = self
+            .goal_repo
+            .load_goals()
+            .await?
+            .into_iter()
+            .filter(|goal| goal.goal_type == GOAL_TYPE_SINKING_FUND && !goal.is_achieved)
+            .collect();
+        if goals.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let today = Utc::now().date_naive();
+        let today_str = today.format("%Y-%m-%d").to_string();
+
+        let goal_ids: Vec<String> = goals.iter().map(|goal| goal.id.clone()).collect();
+        let inputs = self
+            .goal_repo
+            .load_goal_progress_inputs(&goal_ids, today, today)
+            .await?;
+        let account_values = inputs.account_values_on(today);
+
+        let mut progress = Vec::with_capacity(goals.len());
+        for goal in &goals {
+            let Some(due_date) = goal
+                .due_date
+                .as_deref()
+                .and_then(|date| parse_forecast_date(date).ok())
+            else {
+                warn!("Sinking fund {} has no valid due date, skipping", goal.id);
+                continue;
+            };
+            let current_value = allocated_value(
+                inputs.allocations_for_goal(&goal.id),
+                &account_values,
+                &today_str,
+            );
+            progress.push(sinking_fund_progress(
+                goal,
+                due_date,
+                current_value,
+                today,
+                &base_currency,
+            ));
+        }
+        progress.sort_by_key(|p| p.due_date);
+        Ok(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn monthly_set_aside_spreads_remainder_until_due() {
+        let goal = Goal {
+            id: "tet".to_string(),
+            title: "Tet gifts".to_string(),
+            description: None,
+            target_amount: 12_000_000.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: Some("2027-01-15".to_string()),
+            monthly_investment: None,
+            start_date: None,
+            initial_actual_value: None,
+            goal_type: GOAL_TYPE_SINKING_FUND.to_string(),
+            target_months: None,
+            archived_at: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+            achieved_at: None,
+        };
+        let due = date("2027-01-15");
+
+        // Set-asides on 17 Oct, 17 Nov and 17 Dec fall before the due date
+        let progress =
+            sinking_fund_progress(&goal, due, dec!(3_000_000), date("2026-10-17"), "VND");
+        assert_eq!(progress.months_remaining, 3);
+        assert_eq!(progress.remaining, dec!(9_000_000));
+        assert_eq!(progress.monthly_set_aside, dec!(3_000_000));
+        assert_eq!(progress.progress, Some(dec!(0.25)));
+
+        // Past the due date the whole remainder is needed now
+        let late = sinking_fund_progress(&goal, due, dec!(3_000_000), date("2027-02-01"), "VND");
+        assert_eq!(late.months_remaining, 0);
+        assert_eq!(late.monthly_set_aside, dec!(9_000_000));
+
+        let funded = sinking_fund_progress(&goal, due, dec!(13_000_000), date("2026-10-17"), "VND");
+        assert!(funded.is_funded);
+        assert_eq!(funded.monthly_set_aside, Decimal::ZERO);
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use super::i18n_model::{MessageCode, MessageLanguage, MessageParams};
+
+/// Message template for a code in the given language.
+/// Placeholders are written as `{name}` and filled from the message parameters.
+pub fn template(code: MessageCode, language: MessageLanguage) -> &'static str {
+    match (code, language) {
+        (MessageCode::GoalAllocationExceedsLimit, MessageLanguage::En) => {
+            "Total allocation percentage {percent}% exceeds 100% on account {account}"
+        }
+        (MessageCode::GoalAllocationExceedsLimit, MessageLanguage::Vi) => {
+            "Tổng tỷ lệ phân bổ {percent}% vượt quá 100% trên tài khoản {account}"
+        }
+        (MessageCode::GoalAllocationExceedsLimitInPeriod, MessageLanguage::En) => {
+            "Total allocation {percent}% exceeds 100% on account {account} during this period"
+        }
+        (MessageCode::GoalAllocationExceedsLimitInPeriod, MessageLanguage::Vi) => {
+            "Tổng tỷ lệ phân bổ {percent}% vượt quá 100% trên tài khoản {account} trong giai đoạn này"
+        }
+        (MessageCode::GoalAllocationExceedsLimitOnDate, MessageLanguage::En) => {
+            "Total allocation {percent}% exceeds 100% on account {account} on {date}"
+        }
+        (MessageCode::GoalAllocationExceedsLimitOnDate, MessageLanguage::Vi) => {
+            "Tổng tỷ lệ phân bổ {percent}% vượt quá 100% trên tài khoản {account} vào ngày {date}"
+        }
+        (MessageCode::InvalidDateRange, MessageLanguage::En) => {
+            "Start date must not be after end date"
+        }
+        (MessageCode::InvalidDateRange, MessageLanguage::Vi) => {
+            "Ngày bắt đầu không được sau ngày kết thúc"
+        }
+        (MessageCode::OperationAlreadyRunning, MessageLanguage::En) => {
+            "Operation {operation} is already running"
+        }
+        (MessageCode::OperationAlreadyRunning, MessageLanguage::Vi) => {
+            "Tác vụ {operation} đang chạy"
+        }
+        (MessageCode::OperationCancelled, MessageLanguage::En) => {
+            "Operation {operation} was cancelled"
+        }
+        (MessageCode::OperationCancelled, MessageLanguage::Vi) => {
+            "Tác vụ {operation} đã bị hủy"
+        }
+        (MessageCode::OperationTimedOut, MessageLanguage::En) => {
+            "Operation {operation} timed out after {seconds} seconds"
+        }
+        (MessageCode::OperationTimedOut, MessageLanguage::Vi) => {
+            "Tác vụ {operation} đã hết thời gian sau {seconds} giây"
+        }
+        (MessageCode::ProfileNotEmpty, MessageLanguage::En) => {
+            "This can only be done on an empty profile"
+        }
+        (MessageCode::ProfileNotEmpty, MessageLanguage::Vi) => {
+            "Chỉ có thể thực hiện thao tác này trên hồ sơ trống"
+        }
+    }
+}
+
+/// Renders a message, substituting `{name}` placeholders from `params`.
+/// Unknown placeholders are left as written so a missing parameter stays visible.
+pub fn render_message(
+    code: MessageCode,
+    language: MessageLanguage,
+    params: &MessageParams,
+) -> String {
+    let template = template(code, language);
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match params.get(name) {
+                    Some(value) => rendered.push_str(value),
+                    None => rendered.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                rendered.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Every template for a language, keyed by message code, for clients that translate on their side
+pub fn message_catalog(language: MessageLanguage) -> BTreeMap<String, String> {
+    MessageCode::ALL
+        .into_iter()
+        .map(|code| (code.as_str().to_string(), template(code, language).to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::LocalizedMessage;
+
+    fn placeholders(template: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_code_has_matching_placeholders_in_each_language() {
+        for code in MessageCode::ALL {
+            let english = placeholders(template(code, MessageLanguage::En));
+            for language in MessageLanguage::ALL {
+                assert_eq!(
+                    placeholders(template(code, language)),
+                    english,
+                    "placeholder mismatch for {} in {}",
+                    code,
+                    language.as_str()
+                );
+            }
+            assert_eq!(code.as_str().parse::<MessageCode>(), Ok(code));
+        }
+    }
+
+    #[test]
+    fn renders_parameters_in_requested_language() {
+        let message = LocalizedMessage::new(MessageCode::GoalAllocationExceedsLimit)
+            .with_param("percent", "120.0")
+            .with_param("account", "TCBS");
+
+        assert_eq!(
+            message.to_string(),
+            "Total allocation percentage 120.0% exceeds 100% on account TCBS"
+        );
+        assert_eq!(
+            message.render(MessageLanguage::from_tag("vi-VN")),
+            "Tổng tỷ lệ phân bổ 120.0% vượt quá 100% trên tài khoản TCBS"
+        );
+
+        let missing = LocalizedMessage::new(MessageCode::GoalAllocationExceedsLimit);
+        assert_eq!(
+            missing.render(MessageLanguage::En),
+            "Total allocation percentage {percent}% exceeds 100% on account {account}"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::i18n_catalog::render_message;
+
+/// Named values interpolated into `{param}` placeholders of a message template
+pub type MessageParams = BTreeMap<String, String>;
+
+/// Languages with a message catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageLanguage {
+    #[default]
+    En,
+    Vi,
+}
+
+impl MessageLanguage {
+    pub const ALL: [MessageLanguage; 2] = [MessageLanguage::En, MessageLanguage::Vi];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageLanguage::En => "en",
+            MessageLanguage::Vi => "vi",
+        }
+    }
+
+    /// Resolves a language or locale tag (`vi`, `vi-VN`, `en_US`), falling back to English
+    pub fn from_tag(tag: &str) -> Self {
+        let primary = tag
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match primary.as_str() {
+            "vi" => MessageLanguage::Vi,
+            _ => MessageLanguage::En,
+        }
+    }
+}
+
+/// Stable identifiers for user-facing messages raised by core.
+/// The code is what clients should key their own translations on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MessageCode {
+    GoalAllocationExceedsLimit,
+    GoalAllocationExceedsLimitInPeriod,
+    GoalAllocationExceedsLimitOnDate,
+    InvalidDateRange,
+    OperationAlreadyRunning,
+    OperationCancelled,
+    OperationTimedOut,
+    ProfileNotEmpty,
+}
+
+impl MessageCode {
+    pub const ALL: [MessageCode; 8] = [
+        MessageCode::GoalAllocationExceedsLimit,
+        MessageCode::GoalAllocationExceedsLimitInPeriod,
+        MessageCode::GoalAllocationExceedsLimitOnDate,
+        MessageCode::InvalidDateRange,
+        MessageCode::OperationAlreadyRunning,
+        MessageCode::OperationCancelled,
+        MessageCode::OperationTimedOut,
+        MessageCode::ProfileNotEmpty,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageCode::GoalAllocationExceedsLimit => "GOAL_ALLOCATION_EXCEEDS_LIMIT",
+            MessageCode::GoalAllocationExceedsLimitInPeriod => {
+                "GOAL_ALLOCATION_EXCEEDS_LIMIT_IN_PERIOD"
+            }
+            MessageCode::GoalAllocationExceedsLimitOnDate => {
+                "GOAL_ALLOCATION_EXCEEDS_LIMIT_ON_DATE"
+            }
+            MessageCode::InvalidDateRange => "INVALID_DATE_RANGE",
+            MessageCode::OperationAlreadyRunning => "OPERATION_ALREADY_RUNNING",
+            MessageCode::OperationCancelled => "OPERATION_CANCELLED",
+            MessageCode::OperationTimedOut => "OPERATION_TIMED_OUT",
+            MessageCode::ProfileNotEmpty => "PROFILE_NOT_EMPTY",
+        }
+    }
+}
+
+impl fmt::Display for MessageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MessageCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MessageCode::ALL
+            .into_iter()
+            .find(|code| code.as_str() == s)
+            .ok_or_else(|| format!("Unknown message code: {}", s))
+    }
+}
+
+/// A catalog message identified by code, carrying the raw parameters it was raised with.
+/// Displays in English so logs and untranslated callers keep readable text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedMessage {
+    pub code: MessageCode,
+    pub params: MessageParams,
+}
+
+impl LocalizedMessage {
+    pub fn new(code: MessageCode) -> Self {
+        Self {
+            code,
+            params: MessageParams::new(),
+        }
+    }
+
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn render(&self, language: MessageLanguage) -> String {
+        render_message(self.code, language, &self.params)
+    }
+}
+
+impl fmt::Display for LocalizedMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(MessageLanguage::En))
+    }
+}
@@ -0,0 +1,5 @@
+mod i18n_catalog;
+mod i18n_model;
+
+pub use i18n_catalog::{message_catalog, render_message, template};
+pub use i18n_model::{LocalizedMessage, MessageCode, MessageLanguage, MessageParams};
@@ -0,0 +1,274 @@
+use chrono::NaiveDate;
+use roxmltree::{Document, Node};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use super::import_payload_model::{
+    ImportPayload, ImportPayloadData, PayloadActivity, IMPORT_PAYLOAD_VERSION,
+};
+use crate::activities::{ACTIVITY_TYPE_TAX, ACTIVITY_TYPE_WITHDRAWAL};
+use crate::errors::{Error, Result, ValidationError};
+
+/// Source recorded for payloads built from e-invoices
+pub const EINVOICE_SOURCE: &str = "E-invoice";
+const DEFAULT_INVOICE_CURRENCY: &str = "VND";
+
+/// A purchase invoice in the e-invoice XML format of Decree 123/2020 and Circular 78/2021
+/// (hóa đơn điện tử), as issued through the tax authority's portal
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EInvoice {
+    /// Template and series symbols (`KHMSHDon` and `KHHDon`), e.g. `1C24TAA`
+    pub series: String,
+    pub number: String,
+    pub issue_date: NaiveDate,
+    pub seller_name: String,
+    pub seller_tax_code: Option<String>,
+    pub currency: String,
+    /// Total before VAT, after discounts
+    pub net_amount: Decimal,
+    pub vat_amount: Decimal,
+    pub total_amount: Decimal,
+}
+
+fn invalid(message: String) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message))
+}
+
+/// First element reached by following `path` through child elements, by local name
+fn find<'a, 'input>(node: Node<'a, 'input>, path: &[&str]) -> Option<Node<'a, 'input>> {
+    path.iter().try_fold(node, |parent, name| {
+        parent
+            .children()
+            .find(|child| child.is_element() && child.tag_name().name() == *name)
+    })
+}
+
+fn text<'a>(node: Node<'a, '_>, path: &[&str]) -> Option<&'a str> {
+    find(node, path)
+        .and_then(|n| n.text())
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+}
+
+fn amount(node: Node, path: &[&str]) -> Result<Option<Decimal>> {
+    text(node, path)
+        .map(|value| {
+            Decimal::from_str(value).map_err(|_| {
+                invalid(format!(
+                    "{} '{}' is not an amount",
+                    path.last().unwrap_or(&""),
+                    value
+                ))
+            })
+        })
+        .transpose()
+}
+
+impl EInvoice {
+    /// Reads every invoice (`HDon`) in `xml`, whether the file holds a single invoice or a
+    /// message (`TDiep`) wrapping several
+    pub fn parse_xml(xml: &str) -> Result<Vec<EInvoice>> {
+        let document =
+            Document::parse(xml).map_err(|e| invalid(format!("Not an e-invoice: {}", e)))?;
+        let invoices: Vec<EInvoice> = document
+            .descendants()
+            .filter(|n| n.is_element() && n.tag_name().name() == "HDon")
+            .map(EInvoice::from_node)
+            .collect::<Result<_>>()?;
+        if invoices.is_empty() {
+            return Err(invalid("The file has no e-invoice (HDon)".to_string()));
+        }
+        Ok(invoices)
+    }
+
+    fn from_node(invoice: Node) -> Result<EInvoice> {
+        let data = find(invoice, &["DLHDon"])
+            .ok_or_else(|| invalid("The e-invoice has no data (DLHDon)".to_string()))?;
+        let general = find(data, &["TTChung"]);
+        let content = find(data, &["NDHDon"])
+            .ok_or_else(|| invalid("The e-invoice has no content (NDHDon)".to_string()))?;
+
+        let number = general
+            .and_then(|g| text(g, &["SHDon"]))
+            .ok_or_else(|| Error::Validation(ValidationError::MissingField("SHDon".to_string())))?
+            .to_string();
+        let series = general.map_or_else(String::new, |g| {
+            format!(
+                "{}{}",
+                text(g, &["KHMSHDon"]).unwrap_or_default(),
+                text(g, &["KHHDon"]).unwrap_or_default()
+            )
+        });
+        let issue_date = general
+            .and_then(|g| text(g, &["NLap"]))
+            .ok_or_else(|| Error::Validation(ValidationError::MissingField("NLap".to_string())))
+            .and_then(|d| {
+                NaiveDate::parse_from_str(d, "%Y-%m-%d")
+                    .map_err(|_| invalid(format!("Invoice date '{}' is not YYYY-MM-DD", d)))
+            })?;
+        let currency = general
+            .and_then(|g| text(g, &["DVTTe"]))
+            .unwrap_or(DEFAULT_INVOICE_CURRENCY)
+            .to_uppercase();
+        let seller_name = text(content, &["NBan", "Ten"])
+            .ok_or_else(|| invalid(format!("Invoice {} has no seller name", number)))?
+            .to_string();
+        let seller_tax_code = text(content, &["NBan", "MST"]).map(str::to_string);
+
+        let vat_amount = amount(content, &["TToan", "TgTThue"])?.unwrap_or_default();
+        let total_amount = match amount(content, &["TToan", "TgTTTBSo"])? {
+            Some(total) => total,
+            None => {
+                amount(content, &["TToan", "TgTCThue"])?
+                    .ok_or_else(|| invalid(format!("Invoice {} has no total", number)))?
+                    + vat_amount
+            }
+        };
+        if total_amount <= Decimal::ZERO || vat_amount < Decimal::ZERO {
+            return Err(invalid(format!(
+                "Invoice {} has no amount to import",
+                number
+            )));
+        }
+
+        Ok(EInvoice {
+            series,
+            number,
+            issue_date,
+            seller_name,
+            seller_tax_code,
+            currency,
+            net_amount: total_amount - vat_amount,
+            vat_amount,
+            total_amount,
+        })
+    }
+
+    /// `series-number`, as printed on the invoice
+    pub fn reference(&self) -> String {
+        if self.series.is_empty() {
+            self.number.clone()
+        } else {
+            format!("{}-{}", self.series, self.number)
+        }
+    }
+
+    /// The expense before VAT as a withdrawal, and the VAT as a separate tax row. Both
+    /// comments name the seller, so payee categorization rules apply.
+    fn to_activities(&self) -> Vec<PayloadActivity> {
+        let row = |activity_type: &str, amount: Decimal, comment: String| PayloadActivity {
+            date: self.issue_date.format("%Y-%m-%d").to_string(),
+            symbol: format!("$CASH-{}", self.currency),
+            activity_type: activity_type.to_string(),
+            quantity: Decimal::ZERO,
+            unit_price: Decimal::ZERO,
+            currency: self.currency.clone(),
+            fee: Decimal::ZERO,
+            amount: Some(amount),
+            comment: Some(comment),
+        };
+        let description = format!("{} - HĐ {}", self.seller_name, self.reference());
+        let mut rows = vec![row(
+            ACTIVITY_TYPE_WITHDRAWAL,
+            self.net_amount,
+            description.clone(),
+        )];
+        if self.vat_amount > Decimal::ZERO {
+            rows.push(row(
+                ACTIVITY_TYPE_TAX,
+                self.vat_amount,
+                format!("VAT {}", description),
+            ));
+        }
+        rows
+    }
+}
+
+impl ImportPayload {
+    /// Payload importing the invoices in an e-invoice XML file as expenses of `account_id`,
+    /// checked and deduplicated like any other handed-over payload
+    pub fn from_einvoice_xml(xml: &str, account_id: &str) -> Result<Self> {
+        let activities = EInvoice::parse_xml(xml)?
+            .iter()
+            .flat_map(EInvoice::to_activities)
+            .collect();
+        Ok(ImportPayload {
+            version: IMPORT_PAYLOAD_VERSION,
+            source: Some(EINVOICE_SOURCE.to_string()),
+            data: ImportPayloadData::Activities {
+                account_id: account_id.to_string(),
+                activities,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const INVOICE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<HDon>
+  <DLHDon Id="data">
+    <TTChung>
+      <PBan>2.0.1</PBan>
+      <THDon>Hóa đơn giá trị gia tăng</THDon>
+      <KHMSHDon>1</KHMSHDon>
+      <KHHDon>C26TAA</KHHDon>
+      <SHDon>1024</SHDon>
+      <NLap>2026-10-15</NLap>
+      <DVTTe>VND</DVTTe>
+    </TTChung>
+    <NDHDon>
+      <NBan>
+        <Ten>Công ty TNHH Văn phòng phẩm Hồng Hà</Ten>
+        <MST>0100100079</MST>
+      </NBan>
+      <NMua><Ten>Nguyễn Văn A</Ten></NMua>
+      <TToan>
+        <TgTCThue>1200000</TgTCThue>
+        <TgTThue>96000</TgTThue>
+        <TgTTTBSo>1296000</TgTTTBSo>
+      </TToan>
+    </NDHDon>
+  </DLHDon>
+</HDon>"#;
+
+    #[test]
+    fn splits_vat_out_of_the_expense() {
+        let invoices = EInvoice::parse_xml(INVOICE).unwrap();
+        assert_eq!(invoices.len(), 1);
+        assert_eq!(invoices[0].reference(), "1C26TAA-1024");
+        assert_eq!(invoices[0].seller_tax_code.as_deref(), Some("0100100079"));
+
+        let payload = ImportPayload::from_einvoice_xml(INVOICE, "acc-1").unwrap();
+        payload.validate().unwrap();
+        let ImportPayloadData::Activities { activities, .. } = payload.data else {
+            panic!("expected activities");
+        };
+        let rows: Vec<(&str, Option<Decimal>)> = activities
+            .iter()
+            .map(|a| (a.activity_type.as_str(), a.amount))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (ACTIVITY_TYPE_WITHDRAWAL, Some(dec!(1200000))),
+                (ACTIVITY_TYPE_TAX, Some(dec!(96000))),
+            ]
+        );
+        assert_eq!(
+            activities[0].comment.as_deref(),
+            Some("Công ty TNHH Văn phòng phẩm Hồng Hà - HĐ 1C26TAA-1024")
+        );
+    }
+
+    #[test]
+    fn rejects_files_without_invoices() {
+        assert!(EInvoice::parse_xml("<TDiep><DLieu/></TDiep>").is_err());
+        assert!(EInvoice::parse_xml("not xml").is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::activities::ActivityImport;
+use crate::errors::{Error, Result, ValidationError};
+use crate::market_data::{ImportValidationStatus, QuoteImport};
+
+/// Payload format this build understands
+pub const IMPORT_PAYLOAD_VERSION: u32 = 1;
+/// Largest payload accepted in one hand-over
+pub const MAX_PAYLOAD_ROWS: usize = 10_000;
+
+/// Structured data another app hands over for import, e.g.
+/// `{"version": 1, "source": "MyBroker", "kind": "activities", "accountId": "...", "activities": [...]}`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPayload {
+    pub version: u32,
+    /// Name of the sending app, recorded in the audit log
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(flatten)]
+    pub data: ImportPayloadData,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ImportPayloadData {
+    #[serde(rename_all = "camelCase")]
+    Activities {
+        account_id: String,
+        activities: Vec<PayloadActivity>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Quotes {
+        /// Replace quotes already stored for the same symbol and day
+        #[serde(default)]
+        overwrite: bool,
+        quotes: Vec<PayloadQuote>,
+    },
+}
+
+/// One activity, with the columns of a CSV import
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadActivity {
+    /// `YYYY-MM-DD` or an RFC 3339 timestamp
+    pub date: String,
+    pub symbol: String,
+    pub activity_type: String,
+    #[serde(default)]
+    pub quantity: Decimal,
+    #[serde(default)]
+    pub unit_price: Decimal,
+    pub currency: String,
+    #[serde(default)]
+    pub fee: Decimal,
+    #[serde(default)]
+    pub amount: Option<Decimal>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// One daily quote, with the columns of a quote CSV import
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PayloadQuote {
+    pub symbol: String,
+    /// `YYYY-MM-DD`
+    pub date: String,
+    #[serde(default)]
+    pub open: Option<Decimal>,
+    #[serde(default)]
+    pub high: Option<Decimal>,
+    #[serde(default)]
+    pub low: Option<Decimal>,
+    pub close: Decimal,
+    #[serde(default)]
+    pub volume: Option<Decimal>,
+    pub currency: String,
+}
+
+impl ImportPayload {
+    /// Parses a payload from JSON text, such as a dropped `.json` file
+    pub fn from_json(text: &str) -> Result<Self> {
+        serde_json::from_str(text).map_err(|e| {
+            Error::Validation(ValidationError::InvalidInput(format!(
+                "Not an import payload: {}",
+                e
+            )))
+        })
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.version != IMPORT_PAYLOAD_VERSION {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unsupported import payload version {}; expected {}",
+                self.version, IMPORT_PAYLOAD_VERSION
+            ))));
+        }
+        let rows = match &self.data {
+            ImportPayloadData::Activities {
+                account_id,
+                activities,
+            } => {
+                if account_id.trim().is_empty() {
+                    return Err(Error::Validation(ValidationError::MissingField(
+                        "accountId".to_string(),
+                    )));
+                }
+                activities.len()
+            }
+            ImportPayloadData::Quotes { quotes, .. } => quotes.len(),
+        };
+        if rows == 0 {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The payload has no rows".to_string(),
+            )));
+        }
+        if rows > MAX_PAYLOAD_ROWS {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "At most {} rows can be imported at once",
+                MAX_PAYLOAD_ROWS
+            ))));
+        }
+        Ok(())
+    }
+
+    /// The account an activities payload imports into
+    pub fn account_id(&self) -> Option<&str> {
+        match &self.data {
+            ImportPayloadData::Activities { account_id, .. } => Some(account_id),
+            ImportPayloadData::Quotes { .. } => None,
+        }
+    }
+}
+
+impl PayloadActivity {
+    /// Same row a CSV line becomes; `line_number` lets callers point back into the payload
+    pub(crate) fn into_import(self, account_id: &str, line_number: usize) -> ActivityImport {
+        ActivityImport {
+            id: None,
+            date: self.date.trim().to_string(),
+            symbol: self.symbol.trim().to_uppercase(),
+            activity_type: self.activity_type.trim().to_uppercase(),
+            quantity: self.quantity,
+            unit_price: self.unit_price,
+            currency: self.currency.trim().to_uppercase(),
+            fee: self.fee,
+            amount: self.amount,
+            comment: self.comment.filter(|c| !c.trim().is_empty()),
+            account_id: Some(account_id.to_string()),
+            account_name: None,
+            symbol_name: None,
+            errors: None,
+            warnings: None,
+            is_draft: false,
+            is_valid: false,
+            line_number: Some(line_number as i32),
+            asset_data_source: None,
+        }
+    }
+}
+
+impl From<PayloadQuote> for QuoteImport {
+    fn from(quote: PayloadQuote) -> Self {
+        QuoteImport {
+            symbol: quote.symbol.trim().to_uppercase(),
+            date: quote.date.trim().to_string(),
+            open: quote.open,
+            high: quote.high,
+            low: quote.low,
+            close: quote.close,
+            volume: quote.volume,
+            currency: quote.currency.trim().to_uppercase(),
+            validation_status: ImportValidationStatus::Valid,
+            error_message: None,
+        }
+    }
+}
+
+/// What an import would do, row by row, without saving anything
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPayloadPreview {
+    /// Checked activity rows; duplicates carry a `duplicate` error
+    pub activities: Vec<ActivityImport>,
+    /// Checked quote rows
+    pub quotes: Vec<QuoteImport>,
+    /// Rows that will be imported
+    pub valid: usize,
+    /// Rows with errors; the payload cannot be imported until they are fixed
+    pub invalid: usize,
+    /// Rows left out without being errors: activities already stored and quotes with warnings
+    pub skipped: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPayloadResult {
+    pub account_id: Option<String>,
+    pub imported: usize,
+    pub skipped: usize,
+}
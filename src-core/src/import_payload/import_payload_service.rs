@@ -0,0 +1,401 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use log::{debug, warn};
+use std::sync::Arc;
+
+use super::import_payload_model::{
+    ImportPayload, ImportPayloadData, ImportPayloadPreview, ImportPayloadResult, MAX_PAYLOAD_ROWS,
+};
+use super::import_payload_traits::ImportPayloadServiceTrait;
+use crate::activities::{Activity, ActivityImport, ActivityServiceTrait};
+use crate::errors::{Error, Result, ValidationError};
+use crate::market_data::{ImportValidationStatus, MarketDataServiceTrait, QuoteImport};
+use crate::scripting::ScriptServiceTrait;
+
+/// Error key marking an activity row that repeats a stored activity
+pub const DUPLICATE_ERROR_KEY: &str = "duplicate";
+
+pub struct ImportPayloadService {
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    script_service: Arc<dyn ScriptServiceTrait>,
+}
+
+impl ImportPayloadService {
+    pub fn new(
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        script_service: Arc<dyn ScriptServiceTrait>,
+    ) -> Self {
+        ImportPayloadService {
+            activity_service,
+            market_data_service,
+            script_service,
+        }
+    }
+
+    async fn check_activities(
+        &self,
+        account_id: &str,
+        rows: Vec<ActivityImport>,
+    ) -> Result<ImportPayloadPreview> {
+        // Rows that could not be read already carry their errors and skip the checks
+        let (unreadable, mut rows): (Vec<ActivityImport>, Vec<ActivityImport>) =
+            rows.into_iter().partition(|row| !is_clean_of_errors(row));
+        // Same pipeline as a file import: user scripts, the import check, then duplicates
+        let report = self.script_service.run_import_row_hooks(&mut rows)?;
+        for failure in &report.failures {
+            warn!(
+                "Import script '{}' failed on a handed-over row: {}",
+                failure.script_name, failure.message
+            );
+        }
+        let mut checked = if rows.is_empty() {
+            Vec::new()
+        } else {
+            self.activity_service
+                .check_activities_import(account_id.to_string(), rows)
+                .await?
+        };
+        let existing = self
+            .activity_service
+            .get_activities_by_account_id(&account_id.to_string())?;
+
+        let mut preview = ImportPayloadPreview::default();
+        for row in &mut checked {
+            if is_duplicate(row, &existing) {
+                row.is_valid = false;
+                row.errors
+                    .get_or_insert_with(Default::default)
+                    .entry(DUPLICATE_ERROR_KEY.to_string())
+                    .or_default()
+                    .push("Already in this account".to_string());
+                preview.skipped += 1;
+            } else if is_clean(row) {
+                preview.valid += 1;
+            } else {
+                preview.invalid += 1;
+            }
+        }
+        preview.invalid += unreadable.len();
+        checked.extend(unreadable);
+        checked.sort_by_key(|row| row.line_number);
+        preview.activities = checked;
+        Ok(preview)
+    }
+
+    /// Saves the rows of a checked preview, leaving duplicates out
+    async fn save_activities(
+        &self,
+        account_id: &str,
+        preview: ImportPayloadPreview,
+    ) -> Result<usize> {
+        let rows: Vec<ActivityImport> = preview
+            .activities
+            .into_iter()
+            .filter(|row| !is_marked_duplicate(row))
+            .collect();
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let imported = self
+            .activity_service
+            .import_activities(account_id.to_string(), rows)
+            .await?;
+        // The import hands rows back unsaved when its own check fails
+        if !imported.iter().all(is_clean) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The activities changed while importing; preview the payload again".to_string(),
+            )));
+        }
+        Ok(imported.len())
+    }
+
+    fn check_quotes(
+        &self,
+        quotes: Vec<QuoteImport>,
+        overwrite: bool,
+    ) -> Result<ImportPayloadPreview> {
+        let checked = self
+            .market_data_service
+            .check_quotes_import(quotes, overwrite)?;
+        let mut preview = ImportPayloadPreview::default();
+        for quote in &checked {
+            match quote.validation_status {
+                ImportValidationStatus::Valid => preview.valid += 1,
+                // The quote import only saves valid rows
+                ImportValidationStatus::Warning(_) => preview.skipped += 1,
+                ImportValidationStatus::Error(_) => preview.invalid += 1,
+            }
+        }
+        preview.quotes = checked;
+        Ok(preview)
+    }
+}
+
+fn is_clean_of_errors(row: &ActivityImport) -> bool {
+    row.errors.as_ref().is_none_or(|errors| errors.is_empty())
+}
+
+fn is_clean(row: &ActivityImport) -> bool {
+    row.is_valid && is_clean_of_errors(row)
+}
+
+fn is_marked_duplicate(row: &ActivityImport) -> bool {
+    row.errors
+        .as_ref()
+        .is_some_and(|errors| errors.contains_key(DUPLICATE_ERROR_KEY))
+}
+
+/// Whether `row` repeats a stored activity: same day, type and symbol, and the same amount,
+/// or the same quantity and price when either side has no amount
+pub(crate) fn is_duplicate(row: &ActivityImport, existing: &[Activity]) -> bool {
+    let Some(day) = row
+        .date
+        .get(..10)
+        .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+    else {
+        return false;
+    };
+    existing.iter().any(|activity| {
+        activity.activity_date.date_naive() == day
+            && activity.activity_type == row.activity_type
+            && activity.asset_id.eq_ignore_ascii_case(&row.symbol)
+            && match (activity.amount, row.amount) {
+                (Some(stored), Some(amount)) => stored == amount,
+                _ => activity.quantity == row.quantity && activity.unit_price == row.unit_price,
+            }
+    })
+}
+
+fn reject_invalid(preview: &ImportPayloadPreview) -> Result<()> {
+    if preview.invalid == 0 {
+        return Ok(());
+    }
+    Err(Error::Validation(ValidationError::InvalidInput(format!(
+        "{} of {} rows are invalid; preview the payload to see why",
+        preview.invalid,
+        preview.valid + preview.invalid + preview.skipped
+    ))))
+}
+
+#[async_trait]
+impl ImportPayloadServiceTrait for ImportPayloadService {
+    async fn preview_payload(&self, payload: ImportPayload) -> Result<ImportPayloadPreview> {
+        payload.validate()?;
+        match payload.data {
+            ImportPayloadData::Activities {
+                account_id,
+                activities,
+            } => {
+                let rows = activities
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, activity)| activity.into_import(&account_id, index + 1))
+                    .collect();
+                self.check_activities(&account_id, rows).await
+            }
+            ImportPayloadData::Quotes { overwrite, quotes } => self.check_quotes(
+                quotes.into_iter().map(QuoteImport::from).collect(),
+                overwrite,
+            ),
+        }
+    }
+
+    async fn import_payload(&self, payload: ImportPayload) -> Result<ImportPayloadResult> {
+        let source = payload.source.clone().unwrap_or_default();
+        let account_id = payload.account_id().map(str::to_string);
+        let overwrite = matches!(
+            payload.data,
+            ImportPayloadData::Quotes {
+                overwrite: true,
+                ..
+            }
+        );
+        let preview = self.preview_payload(payload).await?;
+        reject_invalid(&preview)?;
+        let skipped = preview.skipped;
+
+        let imported = if let Some(account_id) = &account_id {
+            self.save_activities(account_id, preview).await?
+        } else {
+            let quotes: Vec<QuoteImport> = preview
+                .quotes
+                .into_iter()
+                .filter(|quote| matches!(quote.validation_status, ImportValidationStatus::Valid))
+                .collect();
+            if quotes.is_empty() {
+                0
+            } else {
+                self.market_data_service
+                    .import_quotes_from_csv(quotes, overwrite)
+                    .await?
+                    .iter()
+                    .filter(|quote| {
+                        matches!(quote.validation_status, ImportValidationStatus::Valid)
+                    })
+                    .count()
+            }
+        };
+        debug!(
+            "Imported {} rows handed over by '{}', skipped {}",
+            imported, source, skipped
+        );
+        Ok(ImportPayloadResult {
+            account_id,
+            imported,
+            skipped,
+        })
+    }
+
+    async fn preview_activity_rows(
+        &self,
+        account_id: &str,
+        rows: Vec<ActivityImport>,
+    ) -> Result<ImportPayloadPreview> {
+        if rows.is_empty() {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The file has no rows".to_string(),
+            )));
+        }
+        if rows.len() > MAX_PAYLOAD_ROWS {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "At most {} rows can be imported at once",
+                MAX_PAYLOAD_ROWS
+            ))));
+        }
+        self.check_activities(account_id, rows).await
+    }
+
+    async fn import_activity_rows(
+        &self,
+        account_id: &str,
+        rows: Vec<ActivityImport>,
+        source: &str,
+    ) -> Result<ImportPayloadResult> {
+        let preview = self.preview_activity_rows(account_id, rows).await?;
+        reject_invalid(&preview)?;
+        let skipped = preview.skipped;
+        let imported = self.save_activities(account_id, preview).await?;
+        debug!(
+            "Imported {} rows from a {} file, skipped {}",
+            imported, source, skipped
+        );
+        Ok(ImportPayloadResult {
+            account_id: Some(account_id.to_string()),
+            imported,
+            skipped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::import_payload::import_payload_model::{PayloadActivity, IMPORT_PAYLOAD_VERSION};
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    fn stored(activity_type: &str, amount: Option<rust_decimal::Decimal>) -> Activity {
+        let at = Utc.with_ymd_and_hms(2026, 10, 1, 3, 0, 0).unwrap();
+        Activity {
+            id: "a-1".to_string(),
+            account_id: "acc-1".to_string(),
+            asset_id: "FPT".to_string(),
+            activity_type: activity_type.to_string(),
+            activity_date: at,
+            quantity: dec!(100),
+            unit_price: dec!(95_000),
+            currency: "VND".to_string(),
+            fee: dec!(0),
+            amount,
+            is_draft: false,
+            comment: None,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    fn payload(json: &str) -> ImportPayload {
+        ImportPayload::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn parses_both_payload_kinds() {
+        let activities = payload(
+            r#"{"version": 1, "source": "MyBroker", "kind": "activities", "accountId": "acc-1",
+                "activities": [{"date": "2026-10-01", "symbol": "fpt", "activityType": "buy",
+                                "quantity": 100, "unitPrice": 95000, "currency": "vnd"}]}"#,
+        );
+        assert_eq!(activities.account_id(), Some("acc-1"));
+        activities.validate().unwrap();
+        let ImportPayloadData::Activities {
+            activities: rows, ..
+        } = activities.data
+        else {
+            panic!("expected activities");
+        };
+        let row = rows[0].clone().into_import("acc-1", 1);
+        assert_eq!(row.symbol, "FPT");
+        assert_eq!(row.activity_type, "BUY");
+        assert_eq!(row.currency, "VND");
+
+        let quotes = payload(
+            r#"{"version": 1, "kind": "quotes", "quotes": [{"symbol": "FPT", "date": "2026-10-01", "close": 95000, "currency": "VND"}]}"#,
+        );
+        assert_eq!(quotes.account_id(), None);
+        quotes.validate().unwrap();
+
+        let future = payload(&format!(
+            r#"{{"version": {}, "kind": "quotes", "quotes": []}}"#,
+            IMPORT_PAYLOAD_VERSION + 1
+        ));
+        assert!(future.validate().is_err());
+        assert!(ImportPayload::from_json(r#"{"version": 1, "kind": "trades"}"#).is_err());
+    }
+
+    #[test]
+    fn duplicates_match_day_type_symbol_and_figures() {
+        let existing = vec![stored("BUY", None)];
+        let buy = PayloadActivity {
+            date: "2026-10-01T15:00:00+07:00".to_string(),
+            symbol: "fpt".to_string(),
+            activity_type: "BUY".to_string(),
+            quantity: dec!(100),
+            unit_price: dec!(95_000),
+            currency: "VND".to_string(),
+            fee: dec!(0),
+            amount: None,
+            comment: None,
+        };
+        assert!(is_duplicate(
+            &buy.clone().into_import("acc-1", 1),
+            &existing
+        ));
+
+        let other_day = PayloadActivity {
+            date: "2026-10-02".to_string(),
+            ..buy.clone()
+        };
+        assert!(!is_duplicate(&other_day.into_import("acc-1", 1), &existing));
+
+        let other_price = PayloadActivity {
+            unit_price: dec!(96_000),
+            ..buy.clone()
+        };
+        assert!(!is_duplicate(
+            &other_price.into_import("acc-1", 1),
+            &existing
+        ));
+
+        // Cash rows are compared by amount
+        let deposits = vec![stored("DEPOSIT", Some(dec!(5_000_000)))];
+        let deposit = PayloadActivity {
+            activity_type: "DEPOSIT".to_string(),
+            quantity: dec!(0),
+            amount: Some(dec!(5_000_000)),
+            ..buy
+        };
+        assert!(is_duplicate(&deposit.into_import("acc-1", 1), &deposits));
+    }
+}
@@ -0,0 +1,27 @@
+use async_trait::async_trait;
+
+use super::import_payload_model::{ImportPayload, ImportPayloadPreview, ImportPayloadResult};
+use crate::activities::ActivityImport;
+use crate::errors::Result;
+
+/// Imports data handed over by other apps through the same checks as a CSV import
+#[async_trait]
+pub trait ImportPayloadServiceTrait: Send + Sync {
+    /// Checks every row and flags duplicates of stored data, without saving anything
+    async fn preview_payload(&self, payload: ImportPayload) -> Result<ImportPayloadPreview>;
+    /// Imports the payload; nothing is saved if any row is invalid, and duplicates are left out
+    async fn import_payload(&self, payload: ImportPayload) -> Result<ImportPayloadResult>;
+    /// Checks rows already read from a file; rows that carry errors are reported as invalid
+    async fn preview_activity_rows(
+        &self,
+        account_id: &str,
+        rows: Vec<ActivityImport>,
+    ) -> Result<ImportPayloadPreview>;
+    /// Imports rows already read from a file, on the same terms as `import_payload`
+    async fn import_activity_rows(
+        &self,
+        account_id: &str,
+        rows: Vec<ActivityImport>,
+        source: &str,
+    ) -> Result<ImportPayloadResult>;
+}
@@ -0,0 +1,12 @@
+mod einvoice;
+mod import_payload_model;
+mod import_payload_service;
+mod import_payload_traits;
+
+pub use einvoice::{EInvoice, EINVOICE_SOURCE};
+pub use import_payload_model::{
+    ImportPayload, ImportPayloadData, ImportPayloadPreview, ImportPayloadResult, PayloadActivity,
+    PayloadQuote, IMPORT_PAYLOAD_VERSION, MAX_PAYLOAD_ROWS,
+};
+pub use import_payload_service::{ImportPayloadService, DUPLICATE_ERROR_KEY};
+pub use import_payload_traits::ImportPayloadServiceTrait;
@@ -0,0 +1,247 @@
+use super::csv_rows::{build_row, cell, column_in, CsvTable, RawRow};
+use super::importers_model::{ImportContext, ImportFormat};
+use super::importers_traits::ActivityImporter;
+use crate::activities::{
+    ActivityImport, ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_DIVIDEND,
+    ACTIVITY_TYPE_FEE, ACTIVITY_TYPE_INTEREST, ACTIVITY_TYPE_SELL, ACTIVITY_TYPE_TAX,
+    ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::errors::Result;
+use crate::utils::number_format::NumberLocale;
+
+/// Words in a broker's side or transaction column and the activity each means, checked in
+/// order so that `bán` wins over a row that also mentions `mua`
+const SIDE_KEYWORDS: [(&str, &str); 16] = [
+    ("bán", ACTIVITY_TYPE_SELL),
+    ("sell", ACTIVITY_TYPE_SELL),
+    ("mua", ACTIVITY_TYPE_BUY),
+    ("buy", ACTIVITY_TYPE_BUY),
+    ("cổ tức", ACTIVITY_TYPE_DIVIDEND),
+    ("dividend", ACTIVITY_TYPE_DIVIDEND),
+    ("nộp tiền", ACTIVITY_TYPE_DEPOSIT),
+    ("deposit", ACTIVITY_TYPE_DEPOSIT),
+    ("rút tiền", ACTIVITY_TYPE_WITHDRAWAL),
+    ("withdraw", ACTIVITY_TYPE_WITHDRAWAL),
+    ("lãi", ACTIVITY_TYPE_INTEREST),
+    ("interest", ACTIVITY_TYPE_INTEREST),
+    ("thuế", ACTIVITY_TYPE_TAX),
+    ("tax", ACTIVITY_TYPE_TAX),
+    ("phí", ACTIVITY_TYPE_FEE),
+    ("fee", ACTIVITY_TYPE_FEE),
+];
+/// Single letters brokers use for the side of an order; `B` is bán, a sale
+const SIDE_CODES: [(&str, &str); 3] = [
+    ("m", ACTIVITY_TYPE_BUY),
+    ("b", ACTIVITY_TYPE_SELL),
+    ("s", ACTIVITY_TYPE_SELL),
+];
+
+/// Column titles of a broker's trade history export. Each field lists the titles the broker
+/// has used, Vietnamese first; the first one present in the file is read.
+#[derive(Debug)]
+pub struct BrokerLayout {
+    pub format: ImportFormat,
+    pub date: &'static [&'static str],
+    pub symbol: &'static [&'static str],
+    pub side: &'static [&'static str],
+    pub quantity: &'static [&'static str],
+    pub unit_price: &'static [&'static str],
+    pub fee: &'static [&'static str],
+    pub tax: &'static [&'static str],
+    pub amount: &'static [&'static str],
+    pub comment: &'static [&'static str],
+}
+
+/// SSI iBoard, order match history (Lịch sử khớp lệnh)
+pub const SSI_LAYOUT: BrokerLayout = BrokerLayout {
+    format: ImportFormat::Ssi,
+    date: &["ngày gd", "ngày giao dịch", "ngày khớp", "trade date"],
+    symbol: &["mã ck", "mã chứng khoán", "symbol"],
+    side: &["mua/bán", "lệnh", "loại lệnh", "side"],
+    quantity: &["kl khớp", "khối lượng khớp", "matched volume", "quantity"],
+    unit_price: &["giá khớp", "matched price", "price"],
+    fee: &["phí gd", "phí giao dịch", "phí", "fee"],
+    tax: &["thuế", "thuế tncn", "tax"],
+    amount: &["gt khớp", "giá trị khớp", "matched value"],
+    comment: &["số hiệu lệnh", "order no"],
+};
+
+/// VNDirect, transaction history (Lịch sử giao dịch)
+pub const VNDIRECT_LAYOUT: BrokerLayout = BrokerLayout {
+    format: ImportFormat::Vndirect,
+    date: &["ngày giao dịch", "ngày gd", "transaction date"],
+    symbol: &["mã ck", "mã chứng khoán", "symbol"],
+    side: &["loại giao dịch", "giao dịch", "mua/bán", "transaction type"],
+    quantity: &["khối lượng", "kl khớp", "số lượng", "volume"],
+    unit_price: &["giá", "giá khớp", "price"],
+    fee: &["phí giao dịch", "phí", "fee"],
+    tax: &["thuế bán", "thuế", "tax"],
+    amount: &["giá trị giao dịch", "giá trị", "value"],
+    comment: &["diễn giải", "ghi chú", "description"],
+};
+
+/// TCBS TCInvest, matched orders (Lệnh đã khớp)
+pub const TCBS_LAYOUT: BrokerLayout = BrokerLayout {
+    format: ImportFormat::Tcbs,
+    date: &["ngày khớp", "ngày giao dịch", "thời gian", "match date"],
+    symbol: &["mã cp", "mã ck", "mã", "ticker"],
+    side: &["loại lệnh", "lệnh", "mua/bán", "side"],
+    quantity: &["kl khớp", "khối lượng khớp", "matched volume"],
+    unit_price: &["giá khớp tb", "giá khớp", "matched price"],
+    fee: &["phí", "phí gd", "fee"],
+    tax: &["thuế", "thuế tncn", "tax"],
+    amount: &["giá trị khớp", "gt khớp", "matched value"],
+    comment: &["số hiệu lệnh", "order id"],
+};
+
+/// VPS SmartOne, order history (Lịch sử lệnh)
+pub const VPS_LAYOUT: BrokerLayout = BrokerLayout {
+    format: ImportFormat::Vps,
+    date: &["ngày", "ngày gd", "ngày giao dịch", "date"],
+    symbol: &["mã ck", "mã", "symbol"],
+    side: &["m/b", "mua/bán", "lệnh", "side"],
+    quantity: &["kl khớp", "số lượng khớp", "số lượng", "volume"],
+    unit_price: &["giá khớp", "giá", "price"],
+    fee: &["phí gd", "phí", "fee"],
+    tax: &["thuế", "tax"],
+    amount: &["giá trị khớp", "giá trị", "value"],
+    comment: &["số hiệu lệnh", "ghi chú"],
+};
+
+/// Reads a broker's export, which writes numbers the Vietnamese way and amounts in dong
+pub struct BrokerImporter {
+    layout: &'static BrokerLayout,
+}
+
+impl BrokerImporter {
+    pub fn new(layout: &'static BrokerLayout) -> Self {
+        BrokerImporter { layout }
+    }
+
+    /// Importers for every supported broker
+    pub fn all() -> Vec<BrokerImporter> {
+        [&SSI_LAYOUT, &VNDIRECT_LAYOUT, &TCBS_LAYOUT, &VPS_LAYOUT]
+            .into_iter()
+            .map(BrokerImporter::new)
+            .collect()
+    }
+}
+
+/// Activity type named by a side or transaction cell
+fn side_activity_type(value: &str) -> Option<&'static str> {
+    let value = value.trim().to_lowercase();
+    SIDE_CODES
+        .iter()
+        .find(|(code, _)| *code == value)
+        .or_else(|| {
+            SIDE_KEYWORDS
+                .iter()
+                .find(|(keyword, _)| value.contains(keyword))
+        })
+        .map(|(_, activity_type)| *activity_type)
+}
+
+impl ActivityImporter for BrokerImporter {
+    fn format(&self) -> ImportFormat {
+        self.layout.format
+    }
+
+    fn parse(&self, content: &str, context: &ImportContext) -> Result<Vec<ActivityImport>> {
+        let layout = self.layout;
+        let table = CsvTable::read(content, |headers| {
+            column_in(headers, layout.date).is_some()
+                && column_in(headers, layout.symbol).is_some()
+                && column_in(headers, layout.side).is_some()
+        })?;
+        let date = table.column(layout.date);
+        let symbol = table.column(layout.symbol);
+        let side = table.column(layout.side);
+        let quantity = table.column(layout.quantity);
+        let unit_price = table.column(layout.unit_price);
+        let fee = table.column(layout.fee);
+        let tax = table.column(layout.tax);
+        let amount = table.column(layout.amount);
+        let comment = table.column(layout.comment);
+
+        Ok(table
+            .rows
+            .iter()
+            // Totals at the foot of an export have neither a date nor a symbol
+            .filter(|(_, record)| cell(record, date).is_some() || cell(record, symbol).is_some())
+            .map(|(line, record)| {
+                let activity_type = match cell(record, side) {
+                    Some(value) => side_activity_type(value)
+                        .map(str::to_string)
+                        .ok_or_else(|| format!("'{}' is not a known transaction", value)),
+                    None => Err("The transaction type is missing".to_string()),
+                };
+                let raw = RawRow {
+                    date: cell(record, date),
+                    symbol: cell(record, symbol),
+                    activity_type,
+                    quantity: cell(record, quantity),
+                    unit_price: cell(record, unit_price),
+                    fee: cell(record, fee),
+                    tax: cell(record, tax),
+                    amount: cell(record, amount),
+                    currency: None,
+                    comment: cell(record, comment),
+                };
+                build_row(raw, *line, NumberLocale::Vi, context)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn context() -> ImportContext {
+        ImportContext {
+            account_id: "acc-1".to_string(),
+            currency: "VND".to_string(),
+        }
+    }
+
+    #[test]
+    fn reads_a_broker_export_row_by_row() {
+        let export = "\u{feff}LỊCH SỬ KHỚP LỆNH\n\
+            Tài khoản: 012C345678\n\
+            Ngày GD;Mã CK;Mua/Bán;KL khớp;Giá khớp;GT khớp;Phí GD;Thuế\n\
+            02/10/2026;FPT;Mua;100;95,5;9.550.000;14.325;0\n\
+            03/10/2026;VNM;Bán;200;70.500;14.100.000;21.150;14.100\n\
+            04/10/2026;HPG;Chuyển khoản;10;25.000;250.000;0;0\n\
+            31/02/2026;SSI;Mua;100;30.000;3.000.000;0;0\n\
+            ;;;;;26.900.000;35.475;14.100\n";
+        let rows = BrokerImporter::new(&SSI_LAYOUT)
+            .parse(export, &context())
+            .unwrap();
+        assert_eq!(rows.len(), 4);
+
+        // The price was exported in thousands and the value shows it
+        let buy = &rows[0];
+        assert!(buy.errors.is_none());
+        assert_eq!(buy.date, "2026-10-02");
+        assert_eq!(buy.activity_type, "BUY");
+        assert_eq!(buy.unit_price, dec!(95_500));
+        assert_eq!(buy.amount, Some(dec!(9_550_000)));
+        assert_eq!(buy.currency, "VND");
+        assert_eq!(buy.line_number, Some(4));
+
+        // Tax on a sale joins the fee
+        let sell = &rows[1];
+        assert_eq!(sell.activity_type, "SELL");
+        assert_eq!(sell.fee, dec!(35_250));
+
+        let unknown = rows[2].errors.as_ref().unwrap();
+        assert!(unknown.contains_key("activityType"));
+        let bad_date = rows[3].errors.as_ref().unwrap();
+        assert!(bad_date.contains_key("date"));
+
+        assert!(BrokerImporter::new(&VPS_LAYOUT)
+            .parse("Symbol,Qty\nFPT,1\n", &context())
+            .is_err());
+    }
+}
@@ -0,0 +1,299 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use csv::StringRecord;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use super::importers_model::ImportContext;
+use crate::activities::{
+    ActivityImport, ActivityType, ACTIVITY_TYPE_ADD_HOLDING, ACTIVITY_TYPE_BUY,
+    ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_FEE, ACTIVITY_TYPE_INTEREST, ACTIVITY_TYPE_REMOVE_HOLDING,
+    ACTIVITY_TYPE_SELL, ACTIVITY_TYPE_TAX, ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT,
+    ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::constants::CASH_ASSET_PREFIX;
+use crate::errors::{Error, Result, ValidationError};
+use crate::utils::number_format::{parse_localized_decimal, NumberLocale};
+
+const DELIMITERS: [u8; 3] = [b',', b';', b'\t'];
+/// Day first, as Vietnamese exports write dates, then ISO
+const DATE_FORMATS: [&str; 4] = ["%d/%m/%Y", "%d-%m-%Y", "%Y-%m-%d", "%Y/%m/%d"];
+const DATE_TIME_FORMATS: [&str; 4] = [
+    "%d/%m/%Y %H:%M:%S",
+    "%d/%m/%Y %H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+];
+
+/// Types whose rows move cash only and book against the cash asset
+const CASH_ACTIVITY_TYPES: [&str; 5] = [
+    ACTIVITY_TYPE_DEPOSIT,
+    ACTIVITY_TYPE_WITHDRAWAL,
+    ACTIVITY_TYPE_INTEREST,
+    ACTIVITY_TYPE_FEE,
+    ACTIVITY_TYPE_TAX,
+];
+/// Types whose amount is quantity times price
+const PRICED_ACTIVITY_TYPES: [&str; 4] = [
+    ACTIVITY_TYPE_BUY,
+    ACTIVITY_TYPE_SELL,
+    ACTIVITY_TYPE_ADD_HOLDING,
+    ACTIVITY_TYPE_REMOVE_HOLDING,
+];
+
+fn invalid(message: String) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message))
+}
+
+/// Header text compared without case, a byte order mark or repeated spaces
+pub(crate) fn normalize_header(header: &str) -> String {
+    header
+        .trim_start_matches('\u{feff}')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Rows of a CSV below its header row, with the line each starts on
+pub(crate) struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<(usize, StringRecord)>,
+}
+
+impl CsvTable {
+    /// Reads `content` with the first delimiter that yields a row `is_header` accepts, given
+    /// normalized cells. Title lines above the header, as broker exports have, are skipped.
+    pub fn read(content: &str, is_header: impl Fn(&[String]) -> bool) -> Result<CsvTable> {
+        let content = content.trim_start_matches('\u{feff}');
+        for delimiter in DELIMITERS {
+            let mut reader = csv::ReaderBuilder::new()
+                .delimiter(delimiter)
+                .has_headers(false)
+                .flexible(true)
+                .trim(csv::Trim::All)
+                .from_reader(content.as_bytes());
+            let mut headers: Option<Vec<String>> = None;
+            let mut rows = Vec::new();
+            for record in reader.records() {
+                let record =
+                    record.map_err(|e| invalid(format!("Could not read the file: {}", e)))?;
+                if headers.is_some() {
+                    if record.iter().any(|cell| !cell.is_empty()) {
+                        let line = record.position().map_or(0, |p| p.line() as usize);
+                        rows.push((line, record));
+                    }
+                    continue;
+                }
+                let cells: Vec<String> = record.iter().map(normalize_header).collect();
+                if is_header(&cells) {
+                    headers = Some(cells);
+                }
+            }
+            if let Some(headers) = headers {
+                return Ok(CsvTable { headers, rows });
+            }
+        }
+        Err(invalid(
+            "The file has no header row with the columns this format needs".to_string(),
+        ))
+    }
+
+    /// Index of the first of `names` found among the headers
+    pub fn column(&self, names: &[&str]) -> Option<usize> {
+        column_in(&self.headers, names)
+    }
+}
+
+pub(crate) fn column_in(headers: &[String], names: &[&str]) -> Option<usize> {
+    names.iter().find_map(|name| {
+        let name = normalize_header(name);
+        headers.iter().position(|header| *header == name)
+    })
+}
+
+/// Non-blank cell at `column`
+pub(crate) fn cell(record: &StringRecord, column: Option<usize>) -> Option<&str> {
+    column
+        .and_then(|index| record.get(index))
+        .filter(|value| !value.is_empty())
+}
+
+/// One row as its cells read, before the activity rules apply
+#[derive(Debug)]
+pub(crate) struct RawRow<'a> {
+    pub date: Option<&'a str>,
+    pub symbol: Option<&'a str>,
+    /// Activity type the importer resolved, or why it couldn't
+    pub activity_type: std::result::Result<String, String>,
+    pub quantity: Option<&'a str>,
+    pub unit_price: Option<&'a str>,
+    pub fee: Option<&'a str>,
+    /// Tax withheld on the row, such as the 0.1% tax on a sale; added to the fee
+    pub tax: Option<&'a str>,
+    pub amount: Option<&'a str>,
+    pub currency: Option<&'a str>,
+    pub comment: Option<&'a str>,
+}
+
+fn parse_date(value: &str) -> Option<String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.to_rfc3339());
+    }
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_TIME_FORMATS
+                .iter()
+                .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+                .map(|at| at.date())
+        })
+        .map(|day| day.format("%Y-%m-%d").to_string())
+}
+
+#[derive(Default)]
+struct RowErrors(HashMap<String, Vec<String>>);
+
+impl RowErrors {
+    fn add(&mut self, field: &str, message: String) {
+        self.0.entry(field.to_string()).or_default().push(message);
+    }
+
+    fn number(&mut self, field: &str, value: Option<&str>, locale: NumberLocale) -> Decimal {
+        match value.map(|value| parse_localized_decimal(value, locale)) {
+            Some(Ok(number)) => number.unwrap_or_default().abs(),
+            Some(Err(e)) => {
+                self.add(field, e.to_string());
+                Decimal::ZERO
+            }
+            None => Decimal::ZERO,
+        }
+    }
+}
+
+/// Applies the rules of the CSV import screen to a row: cash rows book against the cash asset
+/// of their currency, figures are positive, trades are valued at quantity times price, and
+/// tax joins the fee. A price exported in thousands of dong, as some brokers write it, is
+/// scaled when the row's value shows it.
+pub(crate) fn build_row(
+    raw: RawRow,
+    line: usize,
+    locale: NumberLocale,
+    context: &ImportContext,
+) -> ActivityImport {
+    let mut errors = RowErrors::default();
+
+    let date = match raw.date.map(|value| (value, parse_date(value))) {
+        Some((_, Some(date))) => date,
+        Some((value, None)) => {
+            errors.add("date", format!("'{}' is not a date", value));
+            value.to_string()
+        }
+        None => {
+            errors.add("date", "The date is missing".to_string());
+            String::new()
+        }
+    };
+    let activity_type = match raw.activity_type {
+        Ok(activity_type) => {
+            let activity_type = activity_type.trim().to_uppercase();
+            if ActivityType::from_str(&activity_type).is_err() {
+                errors.add(
+                    "activityType",
+                    format!("'{}' is not an activity type", activity_type),
+                );
+            }
+            activity_type
+        }
+        Err(message) => {
+            errors.add("activityType", message);
+            String::new()
+        }
+    };
+
+    let quantity = errors.number("quantity", raw.quantity, locale);
+    let mut unit_price = errors.number("unitPrice", raw.unit_price, locale);
+    let mut fee = errors.number("fee", raw.fee, locale) + errors.number("fee", raw.tax, locale);
+    let stated_amount = errors.number("amount", raw.amount, locale);
+    let currency = raw
+        .currency
+        .map(|currency| currency.trim().to_uppercase())
+        .filter(|currency| !currency.is_empty())
+        .unwrap_or_else(|| context.currency.clone());
+    let cash_symbol = format!("{}-{}", CASH_ASSET_PREFIX, currency);
+    let symbol = raw.symbol.map(|symbol| symbol.trim().to_uppercase());
+
+    let is_cash = CASH_ACTIVITY_TYPES.contains(&activity_type.as_str());
+    let is_priced = PRICED_ACTIVITY_TYPES.contains(&activity_type.as_str());
+    let symbol = if is_cash {
+        cash_symbol
+    } else if activity_type == ACTIVITY_TYPE_TRANSFER_IN
+        || activity_type == ACTIVITY_TYPE_TRANSFER_OUT
+    {
+        symbol.unwrap_or(cash_symbol)
+    } else {
+        symbol.unwrap_or_else(|| {
+            errors.add("symbol", "The symbol is missing".to_string());
+            String::new()
+        })
+    };
+
+    let thousand = Decimal::ONE_THOUSAND;
+    if is_priced && unit_price > Decimal::ZERO && unit_price < thousand {
+        let scaled = quantity * unit_price * thousand;
+        if stated_amount > Decimal::ZERO
+            && (scaled - stated_amount).abs() * Decimal::from(200) <= stated_amount
+        {
+            unit_price *= thousand;
+        }
+    }
+
+    let amount = if is_priced {
+        if quantity > Decimal::ZERO && unit_price > Decimal::ZERO {
+            Some(quantity * unit_price)
+        } else {
+            Some(stated_amount).filter(|amount| !amount.is_zero())
+        }
+    } else if activity_type == ACTIVITY_TYPE_FEE {
+        if fee.is_zero() {
+            fee = stated_amount;
+        }
+        None
+    } else if stated_amount > Decimal::ZERO {
+        Some(stated_amount)
+    } else {
+        Some(quantity * unit_price).filter(|amount| !amount.is_zero())
+    };
+
+    if (activity_type == ACTIVITY_TYPE_BUY || activity_type == ACTIVITY_TYPE_SELL)
+        && quantity.is_zero()
+    {
+        errors.add("quantity", "A trade needs a quantity".to_string());
+    }
+    if is_cash && activity_type != ACTIVITY_TYPE_FEE && amount.is_none() {
+        errors.add("amount", "The amount is missing".to_string());
+    }
+
+    ActivityImport {
+        id: None,
+        date,
+        symbol,
+        activity_type,
+        quantity,
+        unit_price,
+        currency,
+        fee,
+        amount,
+        comment: raw.comment.map(str::to_string),
+        account_id: Some(context.account_id.clone()),
+        account_name: None,
+        symbol_name: None,
+        errors: Some(errors.0).filter(|errors| !errors.is_empty()),
+        warnings: None,
+        is_draft: false,
+        is_valid: false,
+        line_number: Some(line as i32),
+        asset_data_source: None,
+    }
+}
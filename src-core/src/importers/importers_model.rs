@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+/// Layout of an activity CSV: a broker's own export, or a generic file read through the
+/// column-mapping profile saved for the account
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ImportFormat {
+    Ssi,
+    Vndirect,
+    Tcbs,
+    Vps,
+    Generic,
+}
+
+impl ImportFormat {
+    pub const ALL: [ImportFormat; 5] = [
+        ImportFormat::Ssi,
+        ImportFormat::Vndirect,
+        ImportFormat::Tcbs,
+        ImportFormat::Vps,
+        ImportFormat::Generic,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::Ssi => "SSI",
+            ImportFormat::Vndirect => "VNDIRECT",
+            ImportFormat::Tcbs => "TCBS",
+            ImportFormat::Vps => "VPS",
+            ImportFormat::Generic => "GENERIC",
+        }
+    }
+
+    /// Name shown to the user and recorded as the import source
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImportFormat::Ssi => "SSI",
+            ImportFormat::Vndirect => "VNDirect",
+            ImportFormat::Tcbs => "TCBS",
+            ImportFormat::Vps => "VPS",
+            ImportFormat::Generic => "CSV",
+        }
+    }
+}
+
+/// A CSV file to read into `account_id`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityImportRequest {
+    pub account_id: String,
+    pub format: ImportFormat,
+    /// Text of the file
+    pub content: String,
+}
+
+/// What an importer needs to know about the account it reads into
+#[derive(Debug, Clone)]
+pub struct ImportContext {
+    pub account_id: String,
+    /// Used for rows without a currency column
+    pub currency: String,
+}
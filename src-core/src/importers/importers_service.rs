@@ -0,0 +1,100 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use super::broker_importers::BrokerImporter;
+use super::importers_model::{ActivityImportRequest, ImportContext, ImportFormat};
+use super::importers_traits::{ActivityImportServiceTrait, ActivityImporter};
+use super::mapped_importer::MappedImporter;
+use crate::accounts::AccountServiceTrait;
+use crate::activities::{ActivityImport, ActivityServiceTrait};
+use crate::errors::{Error, Result, ValidationError};
+use crate::import_payload::{ImportPayloadPreview, ImportPayloadResult, ImportPayloadServiceTrait};
+
+/// Reads broker and generic CSV files into activities, then checks and imports the rows the
+/// same way as data handed over by other apps
+pub struct ActivityImportService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    import_payload_service: Arc<dyn ImportPayloadServiceTrait>,
+    importers: Vec<Arc<dyn ActivityImporter>>,
+}
+
+impl ActivityImportService {
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        import_payload_service: Arc<dyn ImportPayloadServiceTrait>,
+    ) -> Self {
+        let importers = BrokerImporter::all()
+            .into_iter()
+            .map(|importer| Arc::new(importer) as Arc<dyn ActivityImporter>)
+            .collect();
+        ActivityImportService {
+            account_service,
+            activity_service,
+            import_payload_service,
+            importers,
+        }
+    }
+
+    /// Adds an importer, replacing the one registered for the same format
+    pub fn with_importer(mut self, importer: Arc<dyn ActivityImporter>) -> Self {
+        self.importers
+            .retain(|existing| existing.format() != importer.format());
+        self.importers.push(importer);
+        self
+    }
+
+    fn read_rows(&self, request: &ActivityImportRequest) -> Result<Vec<ActivityImport>> {
+        if request.content.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The file is empty".to_string(),
+            )));
+        }
+        let account = self.account_service.get_account(&request.account_id)?;
+        let context = ImportContext {
+            account_id: account.id,
+            currency: account.currency,
+        };
+        let registered = self
+            .importers
+            .iter()
+            .find(|importer| importer.format() == request.format);
+        match (registered, request.format) {
+            (Some(importer), _) => importer.parse(&request.content, &context),
+            (None, ImportFormat::Generic) => {
+                let mapping = self
+                    .activity_service
+                    .get_import_mapping(request.account_id.clone())?;
+                MappedImporter::new(mapping).parse(&request.content, &context)
+            }
+            (None, format) => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "No importer reads {} files",
+                format.label()
+            )))),
+        }
+    }
+}
+
+#[async_trait]
+impl ActivityImportServiceTrait for ActivityImportService {
+    async fn preview_activity_import(
+        &self,
+        request: ActivityImportRequest,
+    ) -> Result<ImportPayloadPreview> {
+        let rows = self.read_rows(&request)?;
+        self.import_payload_service
+            .preview_activity_rows(&request.account_id, rows)
+            .await
+    }
+
+    async fn commit_activity_import(
+        &self,
+        request: ActivityImportRequest,
+    ) -> Result<ImportPayloadResult> {
+        let rows = self.read_rows(&request)?;
+        self.import_payload_service
+            .import_activity_rows(&request.account_id, rows, request.format.label())
+            .await
+    }
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use super::importers_model::{ActivityImportRequest, ImportContext, ImportFormat};
+use crate::activities::ActivityImport;
+use crate::errors::Result;
+use crate::import_payload::{ImportPayloadPreview, ImportPayloadResult};
+
+/// Reads one layout of activity CSV into import rows
+pub trait ActivityImporter: Send + Sync {
+    fn format(&self) -> ImportFormat;
+
+    /// Reads every row of `content`. A row that can't be read comes back invalid, with its
+    /// errors by field, rather than failing the whole file.
+    fn parse(&self, content: &str, context: &ImportContext) -> Result<Vec<ActivityImport>>;
+}
+
+#[async_trait]
+pub trait ActivityImportServiceTrait: Send + Sync {
+    /// Reads and checks the file row by row without saving anything
+    async fn preview_activity_import(
+        &self,
+        request: ActivityImportRequest,
+    ) -> Result<ImportPayloadPreview>;
+    /// Imports the file; nothing is saved if any row is invalid, and duplicates are left out
+    async fn commit_activity_import(
+        &self,
+        request: ActivityImportRequest,
+    ) -> Result<ImportPayloadResult>;
+}
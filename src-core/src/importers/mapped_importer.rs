@@ -0,0 +1,143 @@
+use super::csv_rows::{build_row, cell, column_in, normalize_header, CsvTable, RawRow};
+use super::importers_model::{ImportContext, ImportFormat};
+use super::importers_traits::ActivityImporter;
+use crate::activities::{ActivityImport, ImportMappingData};
+use crate::errors::Result;
+
+/// Reads any CSV through the column-mapping profile saved for the account: which column
+/// holds each field, which cell values mean each activity type, and symbol renames
+pub struct MappedImporter {
+    mapping: ImportMappingData,
+}
+
+impl MappedImporter {
+    pub fn new(mapping: ImportMappingData) -> Self {
+        MappedImporter { mapping }
+    }
+
+    /// Column header mapped to `field`, as the profile's keys name fields
+    fn header(&self, field: &str) -> Option<String> {
+        self.mapping
+            .field_mappings
+            .get(field)
+            .map(|header| normalize_header(header))
+            .filter(|header| !header.is_empty())
+    }
+
+    /// Activity type a cell means: a value listed in the profile, or the type's own name
+    fn activity_type(&self, value: &str) -> std::result::Result<String, String> {
+        let value = value.trim();
+        self.mapping
+            .activity_mappings
+            .iter()
+            .find(|(_, values)| {
+                values
+                    .iter()
+                    .any(|mapped| mapped.trim().eq_ignore_ascii_case(value))
+            })
+            .map(|(activity_type, _)| activity_type.clone())
+            .or_else(|| Some(value.to_uppercase()))
+            .filter(|activity_type| !activity_type.is_empty())
+            .ok_or_else(|| "The activity type is missing".to_string())
+    }
+}
+
+impl ActivityImporter for MappedImporter {
+    fn format(&self) -> ImportFormat {
+        ImportFormat::Generic
+    }
+
+    fn parse(&self, content: &str, context: &ImportContext) -> Result<Vec<ActivityImport>> {
+        let date_header = self.header("date");
+        let type_header = self.header("activityType");
+        let table = CsvTable::read(content, |headers| {
+            [&date_header, &type_header].iter().all(|header| {
+                header
+                    .as_deref()
+                    .is_some_and(|header| column_in(headers, &[header]).is_some())
+            })
+        })?;
+        let column = |field: &str| {
+            self.header(field)
+                .and_then(|header| table.column(&[header.as_str()]))
+        };
+        let date = column("date");
+        let symbol = column("symbol");
+        let activity_type = column("activityType");
+        let quantity = column("quantity");
+        let unit_price = column("unitPrice");
+        let fee = column("fee");
+        let amount = column("amount");
+        let currency = column("currency");
+        let comment = column("comment");
+
+        Ok(table
+            .rows
+            .iter()
+            .map(|(line, record)| {
+                let symbol = cell(record, symbol).map(|value| {
+                    self.mapping
+                        .symbol_mappings
+                        .get(value)
+                        .map(String::as_str)
+                        .unwrap_or(value)
+                });
+                let raw = RawRow {
+                    date: cell(record, date),
+                    symbol,
+                    activity_type: self.activity_type(cell(record, activity_type).unwrap_or("")),
+                    quantity: cell(record, quantity),
+                    unit_price: cell(record, unit_price),
+                    fee: cell(record, fee),
+                    tax: None,
+                    amount: cell(record, amount),
+                    currency: cell(record, currency),
+                    comment: cell(record, comment),
+                };
+                build_row(raw, *line, self.mapping.number_locale, context)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::number_format::NumberLocale;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn reads_columns_and_values_through_the_profile() {
+        let mut mapping = ImportMappingData::default();
+        mapping
+            .field_mappings
+            .insert("date".to_string(), "Ngày".to_string());
+        mapping
+            .field_mappings
+            .insert("activityType".to_string(), "Loại".to_string());
+        mapping
+            .activity_mappings
+            .insert("DEPOSIT".to_string(), vec!["Nạp".to_string()]);
+        mapping
+            .symbol_mappings
+            .insert("E1VFVN30".to_string(), "E1VFVN30.VN".to_string());
+        mapping.number_locale = NumberLocale::Vi;
+
+        let file = "Ngày,Loại,symbol,quantity,unitPrice,amount,currency\n\
+            2026-10-01,Nạp,,,,\"5.000.000\",\n\
+            2026-10-02,buy,E1VFVN30,100,\"25.350,5\",,\n";
+        let context = ImportContext {
+            account_id: "acc-1".to_string(),
+            currency: "VND".to_string(),
+        };
+        let rows = MappedImporter::new(mapping).parse(file, &context).unwrap();
+
+        assert_eq!(rows[0].activity_type, "DEPOSIT");
+        assert_eq!(rows[0].symbol, "$CASH-VND");
+        assert_eq!(rows[0].amount, Some(dec!(5_000_000)));
+        assert_eq!(rows[1].activity_type, "BUY");
+        assert_eq!(rows[1].symbol, "E1VFVN30.VN");
+        assert_eq!(rows[1].amount, Some(dec!(2_535_050)));
+        assert!(rows.iter().all(|row| row.errors.is_none()));
+    }
+}
@@ -0,0 +1,18 @@
+//! Activity CSV files read into import rows. Each layout has an `ActivityImporter`: the trade
+//! history exports of Vietnamese brokers, and a generic reader driven by the column-mapping
+//! profile saved for the account. Rows are checked and saved by the import payload pipeline.
+
+mod broker_importers;
+mod csv_rows;
+mod importers_model;
+mod importers_service;
+mod importers_traits;
+mod mapped_importer;
+
+pub use broker_importers::{
+    BrokerImporter, BrokerLayout, SSI_LAYOUT, TCBS_LAYOUT, VNDIRECT_LAYOUT, VPS_LAYOUT,
+};
+pub use importers_model::{ActivityImportRequest, ImportContext, ImportFormat};
+pub use importers_service::ActivityImportService;
+pub use importers_traits::{ActivityImportServiceTrait, ActivityImporter};
+pub use mapped_importer::MappedImporter;
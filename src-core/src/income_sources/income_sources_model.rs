@@ -0,0 +1,325 @@
+use chrono::{Datelike, Days, Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::budgets::ExpenseBasis;
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+use crate::settings::fiscal_year::lunar_new_year;
+
+/// Days either side of an expected payment in which a deposit can match it
+pub const INCOME_MATCH_WINDOW_DAYS: u64 = 7;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IncomeSourceKind {
+    Salary,
+    /// 13th-month salary or Tet bonus
+    Bonus,
+    Rental,
+    Other,
+}
+
+impl IncomeSourceKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncomeSourceKind::Salary => "SALARY",
+            IncomeSourceKind::Bonus => "BONUS",
+            IncomeSourceKind::Rental => "RENTAL",
+            IncomeSourceKind::Other => "OTHER",
+        }
+    }
+}
+
+impl FromStr for IncomeSourceKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "SALARY" => Ok(IncomeSourceKind::Salary),
+            "BONUS" => Ok(IncomeSourceKind::Bonus),
+            "RENTAL" => Ok(IncomeSourceKind::Rental),
+            "OTHER" => Ok(IncomeSourceKind::Other),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown income source kind: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// When an income source pays. `pay_day` is the day of month for calendar schedules and the
+/// number of days before Tet for `LunarNewYear`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IncomeFrequency {
+    Monthly,
+    /// Every three months from the start month
+    Quarterly,
+    /// Once a year in the start month
+    Yearly,
+    /// Once a year, a set number of days before Lunar New Year
+    LunarNewYear,
+}
+
+impl IncomeFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncomeFrequency::Monthly => "MONTHLY",
+            IncomeFrequency::Quarterly => "QUARTERLY",
+            IncomeFrequency::Yearly => "YEARLY",
+            IncomeFrequency::LunarNewYear => "LUNAR_NEW_YEAR",
+        }
+    }
+
+    /// Expected payments per year, used to turn amounts into a monthly average
+    pub fn payments_per_year(&self) -> u32 {
+        match self {
+            IncomeFrequency::Monthly => 12,
+            IncomeFrequency::Quarterly => 4,
+            IncomeFrequency::Yearly | IncomeFrequency::LunarNewYear => 1,
+        }
+    }
+}
+
+impl FromStr for IncomeFrequency {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "MONTHLY" => Ok(IncomeFrequency::Monthly),
+            "QUARTERLY" => Ok(IncomeFrequency::Quarterly),
+            "YEARLY" => Ok(IncomeFrequency::Yearly),
+            "LUNAR_NEW_YEAR" => Ok(IncomeFrequency::LunarNewYear),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown income frequency: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// `day` of the month starting at `month_start`, or the month's last day if it is shorter
+fn day_in_month(month_start: NaiveDate, day: u32) -> Option<NaiveDate> {
+    let last = month_start.checked_add_months(Months::new(1))? - Days::new(1);
+    month_start.with_day(day.min(last.day()))
+}
+
+/// Database row for `income_sources`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::income_sources)]
+pub struct IncomeSourceDB {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    pub account_id: Option<String>,
+    pub amount: String,
+    pub currency: String,
+    pub frequency: String,
+    pub pay_day: i32,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub match_pattern: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeSource {
+    pub id: String,
+    pub name: String,
+    pub kind: IncomeSourceKind,
+    /// Account the income is paid into; deposits elsewhere don't count towards it
+    pub account_id: Option<String>,
+    /// Expected amount of each payment, after tax
+    pub amount: Decimal,
+    pub currency: String,
+    pub frequency: IncomeFrequency,
+    pub pay_day: i32,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    /// Text the deposit's comment must contain to match, e.g. the employer's name
+    pub match_pattern: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl IncomeSource {
+    /// Dates payments are expected within `from..=to`
+    pub fn expected_dates(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let first = from.max(self.start_date);
+        let last = self.end_date.map_or(to, |end| end.min(to));
+        if first > last {
+            return Vec::new();
+        }
+
+        let day = self.pay_day.max(0) as u32;
+        let mut dates = Vec::new();
+        match self.frequency {
+            IncomeFrequency::LunarNewYear => {
+                for year in first.year()..=last.year() + 1 {
+                    let paid = lunar_new_year(year)
+                        .and_then(|tet| tet.checked_sub_days(Days::new(day as u64)));
+                    if let Some(date) = paid.filter(|d| *d >= first && *d <= last) {
+                        dates.push(date);
+                    }
+                }
+            }
+            frequency => {
+                let step = 12 / frequency.payments_per_year();
+                let anchor = self.start_date.with_day(1).unwrap_or(self.start_date);
+                let mut n = 0;
+                while let Some(month) = anchor.checked_add_months(Months::new(step * n)) {
+                    if month > last {
+                        break;
+                    }
+                    if let Some(date) = day_in_month(month, day.max(1)) {
+                        if date >= first && date <= last {
+                            dates.push(date);
+                        }
+                    }
+                    n += 1;
+                }
+            }
+        }
+        dates
+    }
+}
+
+impl TryFrom<IncomeSourceDB> for IncomeSource {
+    type Error = Error;
+
+    fn try_from(db: IncomeSourceDB) -> Result<Self> {
+        Ok(IncomeSource {
+            id: db.id,
+            name: db.name,
+            kind: db.kind.parse()?,
+            account_id: db.account_id,
+            amount: Decimal::from_str(&db.amount)?,
+            currency: db.currency,
+            frequency: db.frequency.parse()?,
+            pay_day: db.pay_day,
+            start_date: parse_forecast_date(&db.start_date)?,
+            end_date: db
+                .end_date
+                .as_deref()
+                .map(parse_forecast_date)
+                .transpose()?,
+            match_pattern: db.match_pattern,
+            is_active: db.is_active,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating an income source
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewIncomeSource {
+    pub id: Option<String>,
+    pub name: String,
+    pub kind: IncomeSourceKind,
+    pub account_id: Option<String>,
+    pub amount: Decimal,
+    pub currency: String,
+    pub frequency: IncomeFrequency,
+    pub pay_day: i32,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub match_pattern: Option<String>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl NewIncomeSource {
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.currency.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "currency".to_string(),
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Income amount must be positive".to_string(),
+            )));
+        }
+        let pay_day_valid = match self.frequency {
+            IncomeFrequency::LunarNewYear => (0..=60).contains(&self.pay_day),
+            _ => (1..=31).contains(&self.pay_day),
+        };
+        if !pay_day_valid {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid pay day {} for {} income",
+                self.pay_day,
+                self.frequency.as_str()
+            ))));
+        }
+        if self.end_date.is_some_and(|end| end < self.start_date) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Income end date is before its start date".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum IncomeVarianceStatus {
+    /// A deposit matched and its amount is as expected
+    Received,
+    /// A deposit matched but the amount differs
+    Different,
+    /// No deposit matched and the match window has passed
+    Missing,
+    /// The payment isn't due yet, or its match window is still open
+    Upcoming,
+}
+
+/// One expected payment compared with the deposit that matched it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IncomeVariance {
+    pub source_id: String,
+    pub source_name: String,
+    pub kind: IncomeSourceKind,
+    pub expected_date: NaiveDate,
+    pub expected_amount: Decimal,
+    /// Currency of both amounts; deposits are converted to the source's currency
+    pub currency: String,
+    pub activity_id: Option<String>,
+    pub actual_date: Option<NaiveDate>,
+    pub actual_amount: Option<Decimal>,
+    /// Actual minus expected, so a missing payment shows the negated expected amount
+    pub variance: Decimal,
+    pub status: IncomeVarianceStatus,
+}
+
+/// Share of expected income left after typical expenses, in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SavingsRate {
+    pub base_currency: String,
+    /// Expected income over a year, averaged per month so bonuses are spread out
+    pub monthly_income: Decimal,
+    pub monthly_expenses: Decimal,
+    pub expense_basis: ExpenseBasis,
+    pub monthly_savings: Decimal,
+    /// Savings as a share of income, negative when spending exceeds it; `None` without expected income
+    pub savings_rate: Option<Decimal>,
+}
@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::income_sources_model::{IncomeSource, IncomeSourceDB, NewIncomeSource};
+use super::income_sources_traits::IncomeSourceRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::income_sources;
+
+pub struct IncomeSourceRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl IncomeSourceRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        IncomeSourceRepository { pool, writer }
+    }
+}
+
+fn normalize_pattern(pattern: Option<String>) -> Option<String> {
+    pattern
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+#[async_trait]
+impl IncomeSourceRepositoryTrait for IncomeSourceRepository {
+    fn get_income_sources(&self) -> Result<Vec<IncomeSource>> {
+        let mut conn = get_connection(&self.pool)?;
+        income_sources::table
+            .order(income_sources::name.asc())
+            .load::<IncomeSourceDB>(&mut conn)?
+            .into_iter()
+            .map(IncomeSource::try_from)
+            .collect()
+    }
+
+    async fn insert_income_source(&self, source: NewIncomeSource) -> Result<IncomeSource> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<IncomeSource> {
+                let now = chrono::Utc::now().naive_utc();
+                let record = IncomeSourceDB {
+                    id: source.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    name: source.name.trim().to_string(),
+                    kind: source.kind.as_str().to_string(),
+                    account_id: source.account_id,
+                    amount: source.amount.to_string(),
+                    currency: source.currency,
+                    frequency: source.frequency.as_str().to_string(),
+                    pay_day: source.pay_day,
+                    start_date: source.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    end_date: source
+                        .end_date
+                        .map(|d| d.format(FORECAST_DATE_FORMAT).to_string()),
+                    match_pattern: normalize_pattern(source.match_pattern),
+                    is_active: source.is_active,
+                    created_at: now,
+                    updated_at: now,
+                };
+
+                diesel::insert_into(income_sources::table)
+                    .values(&record)
+                    .get_result::<IncomeSourceDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_income_source(
+        &self,
+        id: &str,
+        source: NewIncomeSource,
+    ) -> Result<IncomeSource> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<IncomeSource> {
+                diesel::update(income_sources::table.find(id_owned))
+                    .set((
+                        income_sources::name.eq(source.name.trim()),
+                        income_sources::kind.eq(source.kind.as_str()),
+                        income_sources::account_id.eq(source.account_id),
+                        income_sources::amount.eq(source.amount.to_string()),
+                        income_sources::currency.eq(source.currency),
+                        income_sources::frequency.eq(source.frequency.as_str()),
+                        income_sources::pay_day.eq(source.pay_day),
+                        income_sources::start_date
+                            .eq(source.start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        income_sources::end_date.eq(source
+                            .end_date
+                            .map(|d| d.format(FORECAST_DATE_FORMAT).to_string())),
+                        income_sources::match_pattern.eq(normalize_pattern(source.match_pattern)),
+                        income_sources::is_active.eq(source.is_active),
+                        income_sources::updated_at.eq(chrono::Utc::now().naive_utc()),
+                    ))
+                    .get_result::<IncomeSourceDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_income_source(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(income_sources::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
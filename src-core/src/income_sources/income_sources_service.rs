@@ -0,0 +1,367 @@
+use async_trait::async_trait;
+use chrono::{Days, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+use super::income_sources_model::{
+    IncomeSource, IncomeVariance, IncomeVarianceStatus, NewIncomeSource, SavingsRate,
+    INCOME_MATCH_WINDOW_DAYS,
+};
+use super::income_sources_traits::{IncomeSourceRepositoryTrait, IncomeSourceServiceTrait};
+use crate::activities::{
+    ActivityRepositoryTrait, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_TRANSFER_IN,
+};
+use crate::budgets::BudgetServiceTrait;
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::{convert_or_keep, FxServiceTrait};
+
+/// Recent months averaged to estimate expenses for the savings rate
+const SAVINGS_RATE_EXPENSE_MONTHS: u32 = 6;
+
+/// A received deposit, with its amount in the currency of the source it may match
+#[derive(Debug, Clone)]
+pub(crate) struct ReceivedDeposit {
+    pub activity_id: String,
+    pub account_id: String,
+    pub date: NaiveDate,
+    pub amount: Decimal,
+    pub comment: Option<String>,
+}
+
+pub struct IncomeSourceService {
+    repository: Arc<dyn IncomeSourceRepositoryTrait>,
+    activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    budget_service: Arc<dyn BudgetServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl IncomeSourceService {
+    pub fn new(
+        repository: Arc<dyn IncomeSourceRepositoryTrait>,
+        activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        budget_service: Arc<dyn BudgetServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            activity_repository,
+            budget_service,
+            fx_service,
+            base_currency,
+        }
+    }
+}
+
+/// Pairs each expected payment with the closest unused deposit in its match window.
+/// Deposits matched here are added to `used` so a later source can't claim them too.
+pub(crate) fn match_income(
+    source: &IncomeSource,
+    expected_dates: &[NaiveDate],
+    deposits: &[ReceivedDeposit],
+    used: &mut HashSet<String>,
+    today: NaiveDate,
+) -> Vec<IncomeVariance> {
+    let window = INCOME_MATCH_WINDOW_DAYS as i64;
+    let pattern = source.match_pattern.as_ref().map(|p| p.to_lowercase());
+    // Amounts within 1% of the expected amount count as received in full
+    let tolerance = source.amount / Decimal::ONE_HUNDRED;
+
+    expected_dates
+        .iter()
+        .map(|expected| {
+            let matched = deposits
+                .iter()
+                .filter(|d| !used.contains(&d.activity_id))
+                .filter(|d| {
+                    source
+                        .account_id
+                        .as_ref()
+                        .is_none_or(|account| *account == d.account_id)
+                })
+                .filter(|d| (d.date - *expected).num_days().abs() <= window)
+                .filter(|d| {
+                    pattern.as_ref().is_none_or(|p| {
+                        d.comment
+                            .as_ref()
+                            .is_some_and(|c| c.to_lowercase().contains(p))
+                    })
+                })
+                .min_by_key(|d| {
+                    (
+                        (d.date - *expected).num_days().abs(),
+                        (d.amount - source.amount).abs(),
+                    )
+                });
+
+            let (status, variance) = match matched {
+                Some(deposit) => {
+                    let variance = deposit.amount - source.amount;
+                    let status = if variance.abs() <= tolerance {
+                        IncomeVarianceStatus::Received
+                    } else {
+                        IncomeVarianceStatus::Different
+                    };
+                    (status, variance)
+                }
+                None if (today - *expected).num_days() > window => {
+                    (IncomeVarianceStatus::Missing, -source.amount)
+                }
+                None => (IncomeVarianceStatus::Upcoming, Decimal::ZERO),
+            };
+            if let Some(deposit) = matched {
+                used.insert(deposit.activity_id.clone());
+            }
+
+            IncomeVariance {
+                source_id: source.id.clone(),
+                source_name: source.name.clone(),
+                kind: source.kind,
+                expected_date: *expected,
+                expected_amount: source.amount,
+                currency: source.currency.clone(),
+                activity_id: matched.map(|d| d.activity_id.clone()),
+                actual_date: matched.map(|d| d.date),
+                actual_amount: matched.map(|d| d.amount),
+                variance,
+                status,
+            }
+        })
+        .collect()
+}
+
+#[async_trait]
+impl IncomeSourceServiceTrait for IncomeSourceService {
+    fn get_income_sources(&self) -> Result<Vec<IncomeSource>> {
+        self.repository.get_income_sources()
+    }
+
+    async fn create_income_source(&self, source: NewIncomeSource) -> Result<IncomeSource> {
+        source.validate()?;
+        self.repository.insert_income_source(source).await
+    }
+
+    async fn update_income_source(
+        &self,
+        id: &str,
+        source: NewIncomeSource,
+    ) -> Result<IncomeSource> {
+        source.validate()?;
+        self.repository.update_income_source(id, source).await
+    }
+
+    async fn delete_income_source(&self, id: &str) -> Result<usize> {
+        self.repository.delete_income_source(id).await
+    }
+
+    fn get_income_variance(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<IncomeVariance>> {
+        if start_date > end_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Start date must be before end date".to_string(),
+            )));
+        }
+        let sources: Vec<IncomeSource> = self
+            .repository
+            .get_income_sources()?
+            .into_iter()
+            .filter(|s| s.is_active)
+            .collect();
+        if sources.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let window = Days::new(INCOME_MATCH_WINDOW_DAYS);
+        let from = start_date - window;
+        let to = end_date + window;
+        let received: Vec<_> = self
+            .activity_repository
+            .get_activities()?
+            .into_iter()
+            .filter(|a| {
+                !a.is_draft
+                    && (a.activity_type == ACTIVITY_TYPE_DEPOSIT
+                        || a.activity_type == ACTIVITY_TYPE_TRANSFER_IN)
+            })
+            .filter(|a| {
+                let date = a.activity_date.date_naive();
+                date >= from && date <= to
+            })
+            .collect();
+
+        let today = Utc::now().date_naive();
+        let mut used = HashSet::new();
+        let mut variances = Vec::new();
+        for source in &sources {
+            let deposits: Vec<ReceivedDeposit> = received
+                .iter()
+                .map(|a| {
+                    let date = a.activity_date.date_naive();
+                    let amount = a
+                        .amount
+                        .filter(|amount| !amount.is_zero())
+                        .unwrap_or(a.quantity * a.unit_price);
+                    ReceivedDeposit {
+                        activity_id: a.id.clone(),
+                        account_id: a.account_id.clone(),
+                        date,
+                        amount: convert_or_keep(
+                            self.fx_service.as_ref(),
+                            amount,
+                            &a.currency,
+                            &source.currency,
+                            Some(date),
+                        ),
+                        comment: a.comment.clone(),
+                    }
+                })
+                .collect();
+            let expected = source.expected_dates(start_date, end_date);
+            variances.extend(match_income(source, &expected, &deposits, &mut used, today));
+        }
+        variances.sort_by_key(|v| v.expected_date);
+        Ok(variances)
+    }
+
+    fn get_savings_rate(&self) -> Result<SavingsRate> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        let today = Utc::now().date_naive();
+        let year_end = today
+            .checked_add_months(Months::new(12))
+            .map_or(today, |d| d - Days::new(1));
+
+        let yearly_income: Decimal = self
+            .repository
+            .get_income_sources()?
+            .iter()
+            .filter(|s| s.is_active)
+            .flat_map(|s| {
+                s.expected_dates(today, year_end)
+                    .into_iter()
+                    .map(move |date| (s, date))
+            })
+            .map(|(s, date)| {
+                convert_or_keep(
+                    self.fx_service.as_ref(),
+                    s.amount,
+                    &s.currency,
+                    &base_currency,
+                    Some(date),
+                )
+            })
+            .sum();
+        let monthly_income = (yearly_income / Decimal::from(12)).round_dp(2);
+
+        let expenses = self
+            .budget_service
+            .get_monthly_expenses(SAVINGS_RATE_EXPENSE_MONTHS)?;
+        let monthly_expenses = convert_or_keep(
+            self.fx_service.as_ref(),
+            expenses.average_monthly,
+            &expenses.base_currency,
+            &base_currency,
+            Some(today),
+        );
+        let monthly_savings = monthly_income - monthly_expenses;
+
+        Ok(SavingsRate {
+            base_currency,
+            monthly_income,
+            monthly_expenses,
+            expense_basis: expenses.basis,
+            monthly_savings,
+            savings_rate: (monthly_income > Decimal::ZERO)
+                .then(|| (monthly_savings / monthly_income).round_dp(4)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::income_sources::{IncomeFrequency, IncomeSourceKind};
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn source(frequency: IncomeFrequency, pay_day: i32, amount: Decimal) -> IncomeSource {
+        let now = Utc::now().naive_utc();
+        IncomeSource {
+            id: "salary".to_string(),
+            name: "Salary".to_string(),
+            kind: IncomeSourceKind::Salary,
+            account_id: Some("bank".to_string()),
+            amount,
+            currency: "VND".to_string(),
+            frequency,
+            pay_day,
+            start_date: date("2026-01-01"),
+            end_date: None,
+            match_pattern: Some("LUONG".to_string()),
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn deposit(id: &str, day: &str, amount: Decimal, comment: &str) -> ReceivedDeposit {
+        ReceivedDeposit {
+            activity_id: id.to_string(),
+            account_id: "bank".to_string(),
+            date: date(day),
+            amount,
+            comment: Some(comment.to_string()),
+        }
+    }
+
+    #[test]
+    fn expected_dates_for_month_end_pay_day_and_tet_bonus() {
+        let salary = source(IncomeFrequency::Monthly, 31, dec!(30_000_000));
+        assert_eq!(
+            salary.expected_dates(date("2026-01-15"), date("2026-04-15")),
+            vec![date("2026-01-31"), date("2026-02-28"), date("2026-03-31")]
+        );
+
+        // Tet 2027 falls on 6 February; the bonus is paid ten days earlier
+        let bonus = source(IncomeFrequency::LunarNewYear, 10, dec!(30_000_000));
+        assert_eq!(
+            bonus.expected_dates(date("2026-06-01"), date("2027-06-01")),
+            vec![date("2027-01-27")]
+        );
+    }
+
+    #[test]
+    fn deposits_match_by_window_and_pattern() {
+        let salary = source(IncomeFrequency::Monthly, 5, dec!(30_000_000));
+        let expected = salary.expected_dates(date("2026-01-01"), date("2026-04-30"));
+        let deposits = vec![
+            deposit("jan", "2026-01-05", dec!(30_000_000), "CK LUONG T1"),
+            deposit("feb", "2026-02-06", dec!(27_500_000), "ck luong t2"),
+            // Right amount but not a salary payment
+            deposit("mar-other", "2026-03-05", dec!(30_000_000), "HOAN TIEN"),
+        ];
+
+        let mut used = HashSet::new();
+        let report = match_income(&salary, &expected, &deposits, &mut used, date("2026-04-08"));
+        let statuses: Vec<IncomeVarianceStatus> = report.iter().map(|v| v.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                IncomeVarianceStatus::Received,
+                IncomeVarianceStatus::Different,
+                IncomeVarianceStatus::Missing,
+                IncomeVarianceStatus::Upcoming,
+            ]
+        );
+        assert_eq!(report[1].variance, dec!(-2_500_000));
+        assert_eq!(report[2].variance, dec!(-30_000_000));
+        assert!(used.contains("feb") && !used.contains("mar-other"));
+    }
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::income_sources_model::{IncomeSource, IncomeVariance, NewIncomeSource, SavingsRate};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait IncomeSourceRepositoryTrait: Send + Sync {
+    fn get_income_sources(&self) -> Result<Vec<IncomeSource>>;
+    async fn insert_income_source(&self, source: NewIncomeSource) -> Result<IncomeSource>;
+    async fn update_income_source(&self, id: &str, source: NewIncomeSource)
+        -> Result<IncomeSource>;
+    async fn delete_income_source(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait IncomeSourceServiceTrait: Send + Sync {
+    fn get_income_sources(&self) -> Result<Vec<IncomeSource>>;
+    async fn create_income_source(&self, source: NewIncomeSource) -> Result<IncomeSource>;
+    async fn update_income_source(&self, id: &str, source: NewIncomeSource)
+        -> Result<IncomeSource>;
+    async fn delete_income_source(&self, id: &str) -> Result<usize>;
+    /// Compares payments expected between the dates with the deposits actually received
+    fn get_income_variance(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<IncomeVariance>>;
+    fn get_savings_rate(&self) -> Result<SavingsRate>;
+}
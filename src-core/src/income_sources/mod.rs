@@ -0,0 +1,12 @@
+mod income_sources_model;
+mod income_sources_repository;
+mod income_sources_service;
+mod income_sources_traits;
+
+pub use income_sources_model::{
+    IncomeFrequency, IncomeSource, IncomeSourceKind, IncomeVariance, IncomeVarianceStatus,
+    NewIncomeSource, SavingsRate, INCOME_MATCH_WINDOW_DAYS,
+};
+pub use income_sources_repository::IncomeSourceRepository;
+pub use income_sources_service::IncomeSourceService;
+pub use income_sources_traits::{IncomeSourceRepositoryTrait, IncomeSourceServiceTrait};
@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Dataset name ledger exports report their progress under
+pub const LEDGER_DATASET: &str = "ledger";
+
+/// Plain-text accounting format an export is written in
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LedgerFormat {
+    Beancount,
+    /// ledger-cli, also read by hledger
+    Ledger,
+}
+
+impl LedgerFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerFormat::Beancount => "BEANCOUNT",
+            LedgerFormat::Ledger => "LEDGER",
+        }
+    }
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            LedgerFormat::Beancount => "beancount",
+            LedgerFormat::Ledger => "ledger",
+        }
+    }
+}
+
+impl FromStr for LedgerFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_uppercase().as_str() {
+            "BEANCOUNT" => Ok(LedgerFormat::Beancount),
+            "LEDGER" => Ok(LedgerFormat::Ledger),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown ledger format: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// A rendered journal, ready to be saved
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerExport {
+    pub format: LedgerFormat,
+    pub file_name: String,
+    pub content: String,
+    pub accounts: usize,
+    pub transactions: usize,
+    /// Activities left out, with the reason
+    pub skipped: Vec<String>,
+}
+
+/// What was written by a ledger export streamed straight to a file or response
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerExportSummary {
+    pub format: LedgerFormat,
+    pub file_name: String,
+    pub accounts: usize,
+    pub transactions: usize,
+    /// Activities left out, with the reason
+    pub skipped: Vec<String>,
+}
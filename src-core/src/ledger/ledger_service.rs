@@ -0,0 +1,524 @@
+use chrono::{NaiveDate, Utc};
+use log::warn;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::sync::{Arc, RwLock};
+use tracing::instrument;
+
+use super::ledger_model::{LedgerExport, LedgerExportSummary, LedgerFormat, LEDGER_DATASET};
+use super::ledger_traits::LedgerExportServiceTrait;
+use super::ledger_writer::{
+    account_component, commodity_name, render, write_journal, Amount, Cost, Entry, Journal,
+    Posting, Price,
+};
+use crate::accounts::{Account, AccountServiceTrait};
+use crate::activities::{
+    Activity, ActivityServiceTrait, ACTIVITY_TYPE_ADD_HOLDING, ACTIVITY_TYPE_BUY,
+    ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_DIVIDEND, ACTIVITY_TYPE_FEE, ACTIVITY_TYPE_INTEREST,
+    ACTIVITY_TYPE_REMOVE_HOLDING, ACTIVITY_TYPE_SELL, ACTIVITY_TYPE_SPLIT, ACTIVITY_TYPE_TAX,
+    ACTIVITY_TYPE_TRANSFER_IN, ACTIVITY_TYPE_TRANSFER_OUT, ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::assets::{Asset, AssetServiceTrait};
+use crate::constants::CASH_ASSET_PREFIX;
+use crate::data_transfer::{TransferOperation, TransferProgress};
+use crate::errors::Result;
+use crate::fx::FxServiceTrait;
+use crate::operations::CancellationToken;
+
+const CONTRIBUTIONS_ACCOUNT: &str = "Equity:Contributions";
+const TRANSFERS_ACCOUNT: &str = "Equity:Transfers";
+const OPENING_BALANCES_ACCOUNT: &str = "Equity:Opening-Balances";
+const DIVIDENDS_ACCOUNT: &str = "Income:Dividends";
+const INTEREST_ACCOUNT: &str = "Income:Interest";
+const FEES_ACCOUNT: &str = "Expenses:Fees";
+const TAXES_ACCOUNT: &str = "Expenses:Taxes";
+/// Decimal places kept when cash is converted into the account currency
+const CONVERTED_DP: u32 = 4;
+
+/// A WealthVN account as it appears in the journal
+struct JournalAccount {
+    name: String,
+    currency: String,
+}
+
+pub struct LedgerExportService {
+    account_service: Arc<dyn AccountServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl LedgerExportService {
+    pub fn new(
+        account_service: Arc<dyn AccountServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        LedgerExportService {
+            account_service,
+            activity_service,
+            asset_service,
+            fx_service,
+            base_currency,
+        }
+    }
+}
+
+/// `Assets:<name>` for every account, made unique when two names fold to the same text
+fn journal_accounts(accounts: &[Account]) -> HashMap<String, JournalAccount> {
+    let mut taken: HashMap<String, usize> = HashMap::new();
+    accounts
+        .iter()
+        .map(|account| {
+            let base = format!("Assets:{}", account_component(&account.name));
+            let seen = taken.entry(base.clone()).or_insert(0);
+            *seen += 1;
+            let name = if *seen == 1 {
+                base
+            } else {
+                format!("{}-{}", base, seen)
+            };
+            (
+                account.id.clone(),
+                JournalAccount {
+                    name,
+                    currency: account.currency.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Turns one activity into journal postings the way the holdings calculator books it:
+/// cash moves in the account currency, converted with `convert` at the activity date,
+/// and everything else stays in the activity currency. `Err` carries why it was left out.
+fn activity_entry(
+    activity: &Activity,
+    account: &JournalAccount,
+    commodity: &str,
+    convert: impl Fn(Decimal) -> Option<Decimal>,
+) -> std::result::Result<Entry, String> {
+    let date = activity.activity_date.naive_utc().date();
+    let currency = activity.currency.as_str();
+    let amount = activity.amount.unwrap_or(Decimal::ZERO);
+    let fee = activity.fee;
+    let value = activity.quantity * activity.unit_price;
+    let is_cash_asset = activity.asset_id.starts_with(CASH_ASSET_PREFIX);
+
+    let cash = |change: Decimal| -> Posting {
+        if currency == account.currency {
+            return Posting::new(&account.name, Amount::new(change, currency));
+        }
+        match convert(change) {
+            Some(converted) => Posting::new(
+                &account.name,
+                Amount::new(converted.round_dp(CONVERTED_DP), &account.currency),
+            )
+            .with_price(Price::Total(Amount::new(change.abs(), currency))),
+            // Same fallback as the calculator: keep the original currency
+            None => Posting::new(&account.name, Amount::new(change, currency)),
+        }
+    };
+    let units = |quantity: Decimal| Amount::new(quantity, commodity);
+    let in_currency = |number: Decimal| Amount::new(number, currency);
+
+    let mut postings = Vec::new();
+    let mut realizes_gains = false;
+    match activity.activity_type.as_str() {
+        ACTIVITY_TYPE_BUY => {
+            postings.push(
+                Posting::new(&account.name, units(activity.quantity))
+                    .with_cost(Cost::Lot(in_currency(activity.unit_price))),
+            );
+            postings.push(cash(-(value + fee)));
+        }
+        ACTIVITY_TYPE_SELL => {
+            postings.push(
+                Posting::new(&account.name, units(-activity.quantity))
+                    .with_cost(Cost::Booked)
+                    .with_price(Price::PerUnit(in_currency(activity.unit_price))),
+            );
+            postings.push(cash(value - fee));
+            realizes_gains = true;
+        }
+        ACTIVITY_TYPE_DEPOSIT
+        | ACTIVITY_TYPE_WITHDRAWAL
+        | ACTIVITY_TYPE_DIVIDEND
+        | ACTIVITY_TYPE_INTEREST => {
+            let (counter, sign) = match activity.activity_type.as_str() {
+                ACTIVITY_TYPE_DEPOSIT => (CONTRIBUTIONS_ACCOUNT, Decimal::ONE),
+                ACTIVITY_TYPE_WITHDRAWAL => (CONTRIBUTIONS_ACCOUNT, Decimal::NEGATIVE_ONE),
+                ACTIVITY_TYPE_DIVIDEND => (DIVIDENDS_ACCOUNT, Decimal::ONE),
+                _ => (INTEREST_ACCOUNT, Decimal::ONE),
+            };
+            postings.push(cash(sign * amount - fee));
+            postings.push(Posting::new(counter, in_currency(-sign * amount)));
+        }
+        ACTIVITY_TYPE_FEE | ACTIVITY_TYPE_TAX => {
+            let charge = if fee.is_zero() { amount } else { fee };
+            let counter = if activity.activity_type == ACTIVITY_TYPE_FEE {
+                FEES_ACCOUNT
+            } else {
+                TAXES_ACCOUNT
+            };
+            postings.push(cash(-charge));
+            postings.push(Posting::new(counter, in_currency(charge)));
+            return finish(activity, date, postings, false);
+        }
+        ACTIVITY_TYPE_TRANSFER_IN | ACTIVITY_TYPE_TRANSFER_OUT if is_cash_asset => {
+            let sign = if activity.activity_type == ACTIVITY_TYPE_TRANSFER_IN {
+                Decimal::ONE
+            } else {
+                Decimal::NEGATIVE_ONE
+            };
+            postings.push(cash(sign * amount - fee));
+            postings.push(Posting::new(TRANSFERS_ACCOUNT, in_currency(-sign * amount)));
+        }
+        ACTIVITY_TYPE_TRANSFER_IN | ACTIVITY_TYPE_ADD_HOLDING => {
+            let counter = if activity.activity_type == ACTIVITY_TYPE_ADD_HOLDING {
+                OPENING_BALANCES_ACCOUNT
+            } else {
+                TRANSFERS_ACCOUNT
+            };
+            postings.push(
+                Posting::new(&account.name, units(activity.quantity))
+                    .with_cost(Cost::Lot(in_currency(activity.unit_price))),
+            );
+            postings.push(Posting::balancing(counter));
+            if !fee.is_zero() {
+                postings.push(cash(-fee));
+            }
+        }
+        ACTIVITY_TYPE_TRANSFER_OUT | ACTIVITY_TYPE_REMOVE_HOLDING => {
+            let counter = if activity.activity_type == ACTIVITY_TYPE_REMOVE_HOLDING {
+                OPENING_BALANCES_ACCOUNT
+            } else {
+                TRANSFERS_ACCOUNT
+            };
+            postings.push(
+                Posting::new(&account.name, units(-activity.quantity)).with_cost(Cost::Booked),
+            );
+            postings.push(Posting::balancing(counter));
+            if !fee.is_zero() {
+                postings.push(cash(-fee));
+            }
+        }
+        ACTIVITY_TYPE_SPLIT => {
+            return Ok(Entry::Note {
+                date,
+                account: account.name.clone(),
+                text: format!("SPLIT {} ratio {}", commodity, amount.normalize()),
+            });
+        }
+        other => {
+            return Err(format!(
+                "{}: unsupported activity type {}",
+                activity.id, other
+            ))
+        }
+    }
+    if !fee.is_zero() {
+        postings.push(Posting::new(FEES_ACCOUNT, in_currency(fee)));
+    }
+    finish(activity, date, postings, realizes_gains)
+}
+
+fn finish(
+    activity: &Activity,
+    date: NaiveDate,
+    postings: Vec<Posting>,
+    realizes_gains: bool,
+) -> std::result::Result<Entry, String> {
+    let moves_anything = postings.iter().any(|posting| {
+        posting
+            .units
+            .as_ref()
+            .is_some_and(|units| !units.number.is_zero())
+    });
+    if !moves_anything {
+        return Err(format!("{}: nothing to post", activity.id));
+    }
+    let mut narration = activity.activity_type.clone();
+    if !activity.asset_id.starts_with(CASH_ASSET_PREFIX) {
+        narration = format!("{} {}", narration, activity.asset_id);
+    }
+    if let Some(comment) = activity.comment.as_deref().filter(|c| !c.trim().is_empty()) {
+        narration = format!("{} - {}", narration, comment.trim());
+    }
+    Ok(Entry::Transaction {
+        date,
+        narration,
+        activity_id: activity.id.clone(),
+        postings,
+        realizes_gains,
+    })
+}
+
+/// A journal built from the selected accounts, with the activities it had to leave out
+struct BuiltJournal {
+    journal: Journal,
+    accounts: usize,
+    skipped: Vec<String>,
+}
+
+impl BuiltJournal {
+    fn transactions(&self) -> usize {
+        self.journal
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry, Entry::Transaction { .. }))
+            .count()
+    }
+}
+
+fn ledger_file_name(format: LedgerFormat) -> String {
+    format!(
+        "wealthvn-{}.{}",
+        Utc::now().format("%Y-%m-%d"),
+        format.file_extension()
+    )
+}
+
+impl LedgerExportService {
+    fn build_journal(&self, account_ids: Option<&[String]>) -> Result<BuiltJournal> {
+        let accounts = match account_ids {
+            Some(ids) => self.account_service.get_accounts_by_ids(ids)?,
+            None => self.account_service.get_all_accounts()?,
+        };
+        let account_ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+        let journal_accounts = journal_accounts(&accounts);
+        let assets: HashMap<String, Asset> = self
+            .asset_service
+            .get_assets()?
+            .into_iter()
+            .map(|asset| (asset.id.clone(), asset))
+            .collect();
+
+        let mut activities = self
+            .activity_service
+            .get_activities_by_account_ids(&account_ids)?;
+        activities.retain(|activity| !activity.is_draft);
+        activities.sort_by_key(|activity| (activity.activity_date, activity.created_at));
+
+        let mut journal = Journal {
+            operating_currency: self.base_currency.read().unwrap().clone(),
+            commodity_names: BTreeMap::new(),
+            entries: Vec::with_capacity(activities.len()),
+        };
+        let mut skipped = Vec::new();
+        for activity in &activities {
+            let Some(account) = journal_accounts.get(&activity.account_id) else {
+                continue;
+            };
+            let asset = assets.get(&activity.asset_id);
+            let commodity =
+                commodity_name(asset.map_or(activity.asset_id.as_str(), |a| a.symbol.as_str()));
+            let date = activity.activity_date.naive_utc().date();
+            let convert = |number: Decimal| match self.fx_service.convert_currency_for_date(
+                number,
+                &activity.currency,
+                &account.currency,
+                date,
+            ) {
+                Ok(converted) => Some(converted),
+                Err(e) => {
+                    warn!(
+                        "Ledger export (activity {}): failed conversion {}->{} on {}: {}. Using original currency.",
+                        activity.id, activity.currency, account.currency, date, e
+                    );
+                    None
+                }
+            };
+            match activity_entry(activity, account, &commodity, convert) {
+                Ok(entry) => {
+                    if !activity.asset_id.starts_with(CASH_ASSET_PREFIX) {
+                        let name = asset
+                            .and_then(|a| a.name.clone())
+                            .unwrap_or_else(|| activity.asset_id.clone());
+                        journal.commodity_names.entry(commodity).or_insert(name);
+                    }
+                    journal.entries.push(entry);
+                }
+                Err(reason) => skipped.push(reason),
+            }
+        }
+
+        Ok(BuiltJournal {
+            journal,
+            accounts: accounts.len(),
+            skipped,
+        })
+    }
+}
+
+impl LedgerExportServiceTrait for LedgerExportService {
+    #[instrument(name = "ledger.export", skip(self))]
+    fn export_ledger(
+        &self,
+        format: LedgerFormat,
+        account_ids: Option<&[String]>,
+    ) -> Result<LedgerExport> {
+        let built = self.build_journal(account_ids)?;
+        Ok(LedgerExport {
+            format,
+            file_name: ledger_file_name(format),
+            content: render(format, &built.journal),
+            accounts: built.accounts,
+            transactions: built.transactions(),
+            skipped: built.skipped,
+        })
+    }
+
+    #[instrument(name = "ledger.export_to", skip(self, out, progress, cancel))]
+    fn export_ledger_to(
+        &self,
+        format: LedgerFormat,
+        account_ids: Option<&[String]>,
+        out: &mut dyn Write,
+        progress: &dyn Fn(&TransferProgress),
+        cancel: &CancellationToken,
+    ) -> Result<LedgerExportSummary> {
+        cancel.check()?;
+        let built = self.build_journal(account_ids)?;
+        let total = built.journal.entries.len();
+        let report = |processed: usize| {
+            progress(&TransferProgress {
+                operation_id: cancel.operation_id().map(str::to_string),
+                operation: TransferOperation::Export,
+                dataset: LEDGER_DATASET.to_string(),
+                processed,
+                total: Some(total),
+                done: processed == total,
+            });
+            if cancel.is_cancelled() {
+                return Err(std::io::Error::from(std::io::ErrorKind::Interrupted));
+            }
+            Ok(())
+        };
+        if let Err(e) = write_journal(format, &built.journal, out, &report) {
+            // Report the cancellation rather than the write it interrupted
+            cancel.check()?;
+            return Err(e.into());
+        }
+        Ok(LedgerExportSummary {
+            format,
+            file_name: ledger_file_name(format),
+            accounts: built.accounts,
+            transactions: built.transactions(),
+            skipped: built.skipped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use rust_decimal_macros::dec;
+
+    fn activity(activity_type: &str, asset_id: &str, currency: &str) -> Activity {
+        let at = Utc.with_ymd_and_hms(2026, 5, 4, 0, 0, 0).unwrap();
+        Activity {
+            id: "a1".to_string(),
+            account_id: "acc".to_string(),
+            asset_id: asset_id.to_string(),
+            activity_type: activity_type.to_string(),
+            activity_date: at,
+            quantity: Decimal::ZERO,
+            unit_price: Decimal::ZERO,
+            currency: currency.to_string(),
+            fee: Decimal::ZERO,
+            amount: None,
+            is_draft: false,
+            comment: None,
+            created_at: at,
+            updated_at: at,
+        }
+    }
+
+    fn vnd_account() -> JournalAccount {
+        JournalAccount {
+            name: "Assets:Ssi".to_string(),
+            currency: "VND".to_string(),
+        }
+    }
+
+    fn postings(entry: Entry) -> Vec<Posting> {
+        match entry {
+            Entry::Transaction { postings, .. } => postings,
+            Entry::Note { .. } => panic!("expected a transaction"),
+        }
+    }
+
+    #[test]
+    fn foreign_buy_converts_the_cash_leg_into_the_account_currency() {
+        let mut buy = activity(ACTIVITY_TYPE_BUY, "AAPL", "USD");
+        buy.quantity = dec!(2);
+        buy.unit_price = dec!(150);
+        buy.fee = dec!(1);
+
+        let entry =
+            activity_entry(&buy, &vnd_account(), "AAPL", |usd| Some(usd * dec!(25000))).unwrap();
+        assert_eq!(
+            postings(entry),
+            vec![
+                Posting::new("Assets:Ssi", Amount::new(dec!(2), "AAPL"))
+                    .with_cost(Cost::Lot(Amount::new(dec!(150), "USD"))),
+                Posting::new("Assets:Ssi", Amount::new(dec!(-7525000), "VND"))
+                    .with_price(Price::Total(Amount::new(dec!(301), "USD"))),
+                Posting::new(FEES_ACCOUNT, Amount::new(dec!(1), "USD")),
+            ]
+        );
+    }
+
+    #[test]
+    fn cash_activities_post_against_equity_income_and_expenses() {
+        let mut withdrawal = activity(ACTIVITY_TYPE_WITHDRAWAL, "$CASH-VND", "VND");
+        withdrawal.amount = Some(dec!(5_000_000));
+        let entry = activity_entry(&withdrawal, &vnd_account(), "VND", |_| None).unwrap();
+        assert_eq!(
+            postings(entry),
+            vec![
+                Posting::new("Assets:Ssi", Amount::new(dec!(-5_000_000), "VND")),
+                Posting::new(CONTRIBUTIONS_ACCOUNT, Amount::new(dec!(5_000_000), "VND")),
+            ]
+        );
+
+        let mut tax = activity(ACTIVITY_TYPE_TAX, "$CASH-VND", "VND");
+        tax.amount = Some(dec!(1_000));
+        let entry = activity_entry(&tax, &vnd_account(), "VND", |_| None).unwrap();
+        assert_eq!(
+            postings(entry),
+            vec![
+                Posting::new("Assets:Ssi", Amount::new(dec!(-1_000), "VND")),
+                Posting::new(TAXES_ACCOUNT, Amount::new(dec!(1_000), "VND")),
+            ]
+        );
+
+        let empty = activity(ACTIVITY_TYPE_DEPOSIT, "$CASH-VND", "VND");
+        assert!(activity_entry(&empty, &vnd_account(), "VND", |_| None).is_err());
+    }
+
+    #[test]
+    fn accounts_with_the_same_folded_name_stay_distinct() {
+        let account = |id: &str, name: &str| Account {
+            id: id.to_string(),
+            name: name.to_string(),
+            account_type: "SECURITIES".to_string(),
+            group: None,
+            currency: "VND".to_string(),
+            is_default: false,
+            is_active: true,
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            platform_id: None,
+        };
+        let names = journal_accounts(&[account("a", "Tiết kiệm"), account("b", "Tiet kiem")]);
+        assert_eq!(names["a"].name, "Assets:Tiet-Kiem");
+        assert_eq!(names["b"].name, "Assets:Tiet-Kiem-2");
+    }
+}
@@ -0,0 +1,27 @@
+use std::io::Write;
+
+use super::ledger_model::{LedgerExport, LedgerExportSummary, LedgerFormat};
+use crate::data_transfer::TransferProgress;
+use crate::errors::Result;
+use crate::operations::CancellationToken;
+
+pub trait LedgerExportServiceTrait: Send + Sync {
+    /// Journal of the given accounts, or of every account when `None`
+    fn export_ledger(
+        &self,
+        format: LedgerFormat,
+        account_ids: Option<&[String]>,
+    ) -> Result<LedgerExport>;
+
+    /// Same journal written straight to `out`, calling `progress` as entries go out and
+    /// stopping once `cancel` is cancelled.
+    /// Blocks on the database and on `out`, so callers run it on a blocking thread.
+    fn export_ledger_to(
+        &self,
+        format: LedgerFormat,
+        account_ids: Option<&[String]>,
+        out: &mut dyn Write,
+        progress: &dyn Fn(&TransferProgress),
+        cancel: &CancellationToken,
+    ) -> Result<LedgerExportSummary>;
+}
@@ -0,0 +1,481 @@
+//! Renders journal entries as Beancount or ledger-cli text. Everything here is pure so the
+//! service only has to decide which postings an activity turns into.
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+
+use super::ledger_model::LedgerFormat;
+use crate::data_transfer::TRANSFER_CHUNK_SIZE;
+
+/// Beancount caps commodity names at 24 characters
+const MAX_COMMODITY_LEN: usize = 24;
+/// Where Beancount books realized gains on sales; ledger-cli works them out on its own
+pub(crate) const GAINS_ACCOUNT: &str = "Income:Capital-Gains";
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Amount {
+    pub number: Decimal,
+    pub commodity: String,
+}
+
+impl Amount {
+    pub fn new(number: Decimal, commodity: &str) -> Self {
+        Amount {
+            number,
+            commodity: commodity.to_string(),
+        }
+    }
+}
+
+/// Lot annotation on a security posting
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Cost {
+    /// Opens a lot at this unit cost
+    Lot(Amount),
+    /// Reduces whichever lots the booking method picks
+    Booked,
+}
+
+/// Conversion annotation on a posting
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Price {
+    PerUnit(Amount),
+    Total(Amount),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Posting {
+    pub account: String,
+    /// `None` lets the tool balance the transaction with this posting
+    pub units: Option<Amount>,
+    pub cost: Option<Cost>,
+    pub price: Option<Price>,
+}
+
+impl Posting {
+    pub fn new(account: &str, units: Amount) -> Self {
+        Posting {
+            account: account.to_string(),
+            units: Some(units),
+            cost: None,
+            price: None,
+        }
+    }
+
+    pub fn balancing(account: &str) -> Self {
+        Posting {
+            account: account.to_string(),
+            units: None,
+            cost: None,
+            price: None,
+        }
+    }
+
+    pub fn with_cost(mut self, cost: Cost) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    pub fn with_price(mut self, price: Price) -> Self {
+        self.price = Some(price);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Entry {
+    Transaction {
+        date: NaiveDate,
+        narration: String,
+        activity_id: String,
+        postings: Vec<Posting>,
+        /// Sells lots at a price, so Beancount needs somewhere to put the gain
+        realizes_gains: bool,
+    },
+    /// Something the formats cannot express, such as a split
+    Note {
+        date: NaiveDate,
+        account: String,
+        text: String,
+    },
+}
+
+impl Entry {
+    fn date(&self) -> NaiveDate {
+        match self {
+            Entry::Transaction { date, .. } | Entry::Note { date, .. } => *date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Journal {
+    pub operating_currency: String,
+    /// Commodity name to the asset name it stands for
+    pub commodity_names: BTreeMap<String, String>,
+    pub entries: Vec<Entry>,
+}
+
+/// Folds Vietnamese letters to their ASCII base letter, keeping case
+fn fold_diacritic(c: char) -> char {
+    const FOLDS: [(&str, char); 7] = [
+        ("àáảãạăằắẳẵặâầấẩẫậ", 'a'),
+        ("èéẻẽẹêềếểễệ", 'e'),
+        ("ìíỉĩị", 'i'),
+        ("òóỏõọôồốổỗộơờớởỡợ", 'o'),
+        ("ùúủũụưừứửữự", 'u'),
+        ("ỳýỷỹỵ", 'y'),
+        ("đ", 'd'),
+    ];
+    if c.is_ascii() {
+        return c;
+    }
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    match FOLDS.iter().find(|(letters, _)| letters.contains(lower)) {
+        Some((_, base)) if c.is_uppercase() => base.to_ascii_uppercase(),
+        Some((_, base)) => *base,
+        None => c,
+    }
+}
+
+/// Account name component for a free-form name, e.g. `Tài khoản chứng khoán` -> `Tai-Khoan-Chung-Khoan`
+pub(crate) fn account_component(name: &str) -> String {
+    let folded: String = name.chars().map(fold_diacritic).collect();
+    let words: Vec<String> = folded
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    if words.is_empty() {
+        "Account".to_string()
+    } else {
+        words.join("-")
+    }
+}
+
+/// Commodity name both tools accept for a symbol, e.g. `^VNINDEX` -> `VNINDEX`
+pub(crate) fn commodity_name(symbol: &str) -> String {
+    let cleaned: String = symbol
+        .chars()
+        .map(fold_diacritic)
+        .map(|c| c.to_ascii_uppercase())
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '\'') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let trimmed = cleaned.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+    let mut name = match trimmed.chars().next() {
+        None => return "UNKNOWN".to_string(),
+        Some(first) if first.is_ascii_digit() => format!("X{}", trimmed),
+        Some(_) => trimmed.to_string(),
+    };
+    name.truncate(MAX_COMMODITY_LEN);
+    name.trim_end_matches(|c: char| !c.is_ascii_alphanumeric())
+        .to_string()
+}
+
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace(['\r', '\n'], " ").replace('"', "'"))
+}
+
+fn format_commodity(format: LedgerFormat, commodity: &str) -> String {
+    // ledger-cli reads digits and punctuation as part of the amount unless quoted
+    if format == LedgerFormat::Ledger && !commodity.chars().all(|c| c.is_ascii_alphabetic()) {
+        quoted(commodity)
+    } else {
+        commodity.to_string()
+    }
+}
+
+fn format_amount(format: LedgerFormat, amount: &Amount) -> String {
+    format!(
+        "{} {}",
+        amount.number.normalize(),
+        format_commodity(format, &amount.commodity)
+    )
+}
+
+fn format_posting(format: LedgerFormat, posting: &Posting) -> String {
+    let indent = match format {
+        LedgerFormat::Beancount => "  ",
+        LedgerFormat::Ledger => "    ",
+    };
+    let mut line = format!("{}{}", indent, posting.account);
+    if let Some(units) = &posting.units {
+        let _ = write!(line, "  {}", format_amount(format, units));
+    }
+    let price = match (format, &posting.cost) {
+        (LedgerFormat::Beancount, Some(Cost::Lot(cost))) => {
+            let _ = write!(line, " {{{}}}", format_amount(format, cost));
+            posting.price.clone()
+        }
+        (LedgerFormat::Beancount, Some(Cost::Booked)) => {
+            line.push_str(" {}");
+            posting.price.clone()
+        }
+        // ledger-cli balances on the price, so a new lot is written at its cost
+        (LedgerFormat::Ledger, Some(Cost::Lot(cost))) => {
+            posting.price.clone().or(Some(Price::PerUnit(cost.clone())))
+        }
+        (LedgerFormat::Ledger, _) | (_, None) => posting.price.clone(),
+    };
+    match price {
+        Some(Price::PerUnit(price)) => {
+            let _ = write!(line, " @ {}", format_amount(format, &price));
+        }
+        Some(Price::Total(price)) => {
+            let _ = write!(line, " @@ {}", format_amount(format, &price));
+        }
+        None => {}
+    }
+    line
+}
+
+/// Accounts in the order they are first used, with the date of first use
+fn opened_accounts(format: LedgerFormat, entries: &[Entry]) -> Vec<(NaiveDate, String)> {
+    let mut opened: Vec<(NaiveDate, String)> = Vec::new();
+    let mut note = |date: NaiveDate, account: &str| match opened
+        .iter_mut()
+        .find(|(_, name)| name == account)
+    {
+        Some((first, _)) => *first = (*first).min(date),
+        None => opened.push((date, account.to_string())),
+    };
+    for entry in entries {
+        match entry {
+            Entry::Transaction {
+                date,
+                postings,
+                realizes_gains,
+                ..
+            } => {
+                for posting in postings {
+                    note(*date, &posting.account);
+                }
+                if *realizes_gains && format == LedgerFormat::Beancount {
+                    note(*date, GAINS_ACCOUNT);
+                }
+            }
+            Entry::Note { date, account, .. } => note(*date, account),
+        }
+    }
+    opened
+}
+
+/// Writes the journal to `out`; entries are written in the order given, and `progress`
+/// hears how many have gone out after every `TRANSFER_CHUNK_SIZE` of them. An error from
+/// `progress` stops the write.
+pub(crate) fn write_journal(
+    format: LedgerFormat,
+    journal: &Journal,
+    out: &mut dyn io::Write,
+    progress: &dyn Fn(usize) -> io::Result<()>,
+) -> io::Result<()> {
+    let first_date = journal.entries.iter().map(Entry::date).min();
+    writeln!(out, "; Exported from WealthVN")?;
+    match format {
+        LedgerFormat::Beancount => {
+            writeln!(out, "option \"title\" \"WealthVN\"")?;
+            writeln!(
+                out,
+                "option \"operating_currency\" \"{}\"",
+                journal.operating_currency
+            )?;
+            writeln!(out, "option \"booking_method\" \"FIFO\"")?;
+            writeln!(out, "plugin \"beancount.plugins.implicit_prices\"")?;
+            out.write_all(b"\n")?;
+            if let Some(first_date) = first_date {
+                for (commodity, name) in &journal.commodity_names {
+                    writeln!(out, "{} commodity {}", first_date, commodity)?;
+                    writeln!(out, "  name: {}", quoted(name))?;
+                }
+            }
+            for (date, account) in opened_accounts(format, &journal.entries) {
+                writeln!(out, "{} open {}", date, account)?;
+            }
+        }
+        LedgerFormat::Ledger => {
+            out.write_all(b"\n")?;
+            for (commodity, name) in &journal.commodity_names {
+                writeln!(out, "commodity {}", format_commodity(format, commodity))?;
+                writeln!(out, "    note {}", name.replace(['\r', '\n'], " "))?;
+            }
+            for (_, account) in opened_accounts(format, &journal.entries) {
+                writeln!(out, "account {}", account)?;
+            }
+        }
+    }
+
+    for (index, entry) in journal.entries.iter().enumerate() {
+        if index > 0 && index % TRANSFER_CHUNK_SIZE == 0 {
+            out.flush()?;
+            progress(index)?;
+        }
+        out.write_all(b"\n")?;
+        match entry {
+            Entry::Transaction {
+                date,
+                narration,
+                activity_id,
+                postings,
+                realizes_gains,
+            } => match format {
+                LedgerFormat::Beancount => {
+                    writeln!(out, "{} * {}", date, quoted(narration))?;
+                    writeln!(out, "  activity-id: {}", quoted(activity_id))?;
+                    for posting in postings {
+                        writeln!(out, "{}", format_posting(format, posting))?;
+                    }
+                    if *realizes_gains {
+                        writeln!(out, "  {}", GAINS_ACCOUNT)?;
+                    }
+                }
+                LedgerFormat::Ledger => {
+                    writeln!(out, "{} * {}", date, narration.replace(['\r', '\n'], " "))?;
+                    writeln!(out, "    ; activity-id: {}", activity_id)?;
+                    for posting in postings {
+                        writeln!(out, "{}", format_posting(format, posting))?;
+                    }
+                }
+            },
+            Entry::Note {
+                date,
+                account,
+                text,
+            } => match format {
+                LedgerFormat::Beancount => {
+                    writeln!(out, "{} note {} {}", date, account, quoted(text))?;
+                }
+                LedgerFormat::Ledger => {
+                    writeln!(out, "; {} {}: {}", date, account, text)?;
+                }
+            },
+        }
+    }
+    out.flush()?;
+    progress(journal.entries.len())
+}
+
+/// The whole journal as text
+pub(crate) fn render(format: LedgerFormat, journal: &Journal) -> String {
+    let mut out = Vec::new();
+    write_journal(format, journal, &mut out, &|_| Ok(())).expect("writing to memory cannot fail");
+    String::from_utf8(out).expect("journals are written as UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn buy_journal() -> Journal {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        Journal {
+            operating_currency: "VND".to_string(),
+            commodity_names: BTreeMap::from([(
+                "E1VFVN30".to_string(),
+                "Quỹ ETF DCVFMVN30".to_string(),
+            )]),
+            entries: vec![Entry::Transaction {
+                date,
+                narration: "BUY E1VFVN30".to_string(),
+                activity_id: "a1".to_string(),
+                postings: vec![
+                    Posting::new("Assets:Ssi", Amount::new(dec!(100), "E1VFVN30"))
+                        .with_cost(Cost::Lot(Amount::new(dec!(25000.00), "VND"))),
+                    Posting::new("Expenses:Fees", Amount::new(dec!(10000), "VND")),
+                    Posting::new("Assets:Ssi", Amount::new(dec!(-100.4), "USD"))
+                        .with_price(Price::Total(Amount::new(dec!(2510000), "VND"))),
+                ],
+                realizes_gains: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn sanitizes_account_and_commodity_names() {
+        assert_eq!(
+            account_component("Tài khoản chứng khoán"),
+            "Tai-Khoan-Chung-Khoan"
+        );
+        assert_eq!(account_component("Đầu tư (SSI)"), "Dau-Tu-SSI");
+        assert_eq!(account_component("  ***  "), "Account");
+        assert_eq!(commodity_name("^VNINDEX"), "VNINDEX");
+        assert_eq!(commodity_name("btc-usd"), "BTC-USD");
+        assert_eq!(commodity_name("3988.HK"), "X3988.HK");
+        assert_eq!(commodity_name("Vàng SJC"), "VANG-SJC");
+        assert_eq!(commodity_name("?"), "UNKNOWN");
+    }
+
+    #[test]
+    fn renders_lots_and_conversions_in_both_formats() {
+        let journal = buy_journal();
+
+        let beancount = render(LedgerFormat::Beancount, &journal);
+        assert!(beancount.contains("option \"operating_currency\" \"VND\""));
+        assert!(beancount.contains("2026-03-02 commodity E1VFVN30\n  name: \"Quỹ ETF DCVFMVN30\""));
+        assert!(beancount.contains("2026-03-02 open Assets:Ssi\n2026-03-02 open Expenses:Fees"));
+        assert!(beancount.contains("2026-03-02 * \"BUY E1VFVN30\"\n  activity-id: \"a1\""));
+        assert!(beancount.contains("  Assets:Ssi  100 E1VFVN30 {25000 VND}\n"));
+        assert!(beancount.contains("  Assets:Ssi  -100.4 USD @@ 2510000 VND\n"));
+
+        let ledger = render(LedgerFormat::Ledger, &journal);
+        assert!(ledger.contains("commodity \"E1VFVN30\"\n    note Quỹ ETF DCVFMVN30"));
+        assert!(ledger.contains("account Assets:Ssi\naccount Expenses:Fees"));
+        assert!(ledger.contains("    Assets:Ssi  100 \"E1VFVN30\" @ 25000 VND\n"));
+        assert!(ledger.contains("    Assets:Ssi  -100.4 USD @@ 2510000 VND\n"));
+        assert!(!ledger.contains("option"));
+    }
+
+    #[test]
+    fn beancount_books_gains_on_sales_but_ledger_does_not() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+        let journal = Journal {
+            operating_currency: "VND".to_string(),
+            commodity_names: BTreeMap::new(),
+            entries: vec![
+                Entry::Transaction {
+                    date,
+                    narration: "SELL FPT".to_string(),
+                    activity_id: "s1".to_string(),
+                    postings: vec![
+                        Posting::new("Assets:Ssi", Amount::new(dec!(-10), "FPT"))
+                            .with_cost(Cost::Booked)
+                            .with_price(Price::PerUnit(Amount::new(dec!(120000), "VND"))),
+                        Posting::new("Assets:Ssi", Amount::new(dec!(1200000), "VND")),
+                    ],
+                    realizes_gains: true,
+                },
+                Entry::Note {
+                    date,
+                    account: "Assets:Ssi".to_string(),
+                    text: "SPLIT FPT 2".to_string(),
+                },
+            ],
+        };
+
+        let beancount = render(LedgerFormat::Beancount, &journal);
+        assert!(beancount.contains("  Assets:Ssi  -10 FPT {} @ 120000 VND\n"));
+        assert!(beancount.contains("  Income:Capital-Gains\n"));
+        assert!(beancount.contains("2026-04-01 open Income:Capital-Gains"));
+        assert!(beancount.contains("2026-04-01 note Assets:Ssi \"SPLIT FPT 2\""));
+
+        let ledger = render(LedgerFormat::Ledger, &journal);
+        assert!(ledger.contains("    Assets:Ssi  -10 FPT @ 120000 VND\n"));
+        assert!(!ledger.contains(GAINS_ACCOUNT));
+        assert!(ledger.contains("; 2026-04-01 Assets:Ssi: SPLIT FPT 2"));
+    }
+}
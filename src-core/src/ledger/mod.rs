@@ -0,0 +1,8 @@
+mod ledger_model;
+mod ledger_service;
+mod ledger_traits;
+mod ledger_writer;
+
+pub use ledger_model::{LedgerExport, LedgerExportSummary, LedgerFormat, LEDGER_DATASET};
+pub use ledger_service::LedgerExportService;
+pub use ledger_traits::LedgerExportServiceTrait;
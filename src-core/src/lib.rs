@@ -1,19 +1,63 @@
 pub mod accounts;
 pub mod activities;
+pub mod api_tokens;
+pub mod app_lock;
 pub mod addons;
 pub mod assets;
+pub mod audit;
+pub mod automations;
+pub mod benchmarks;
+pub mod bills;
+pub mod bonds;
+pub mod bonus_plans;
+pub mod budgets;
+pub mod cash;
+pub mod categorization;
+pub mod connectors;
+pub mod data_transfer;
+pub mod deposit_ladders;
+pub mod deposit_rates;
+pub mod dividends;
 pub mod constants;
 pub mod db;
+pub mod demo;
+pub mod education;
 
+pub mod envelopes;
+pub mod esop;
 pub mod errors;
+pub mod feature_flags;
+pub mod forecast;
 pub mod fx;
 pub mod goals;
+pub mod i18n;
+pub mod import_payload;
+pub mod importers;
+pub mod income_sources;
+pub mod ledger;
 pub mod limits;
+pub mod loans;
 pub mod market_data;
+pub mod notifications;
+pub mod onboarding;
+pub mod operations;
+pub mod pension;
 pub mod portfolio;
+pub mod privacy;
+pub mod private_loans;
+pub mod profiles;
+pub mod real_estate;
+pub mod recurring_rules;
+pub mod reports;
+pub mod query_cache;
 pub mod schema;
+pub mod scripting;
 pub mod secrets;
 pub mod settings;
+pub mod sheets;
+pub mod sip_plans;
+pub mod telemetry;
+pub mod term_deposits;
 pub mod utils;
 pub mod vn_market;
 pub use assets::*;
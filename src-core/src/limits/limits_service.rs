@@ -7,6 +7,7 @@ use rust_decimal::Decimal;
 use crate::activities::activities_traits::ActivityRepositoryTrait;
 use crate::errors::{Error, Result, ValidationError};
 use crate::fx::fx_traits::FxServiceTrait;
+use crate::settings::SettingsServiceTrait;
 
 use super::limits_model::{
     AccountDeposit, ContributionLimit, DepositsCalculation, NewContributionLimit,
@@ -18,6 +19,7 @@ pub struct ContributionLimitService {
     fx_service: Arc<dyn FxServiceTrait>,
     limit_repository: Arc<dyn ContributionLimitRepositoryTrait>,
     activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    settings_service: Arc<dyn SettingsServiceTrait>,
 }
 
 impl ContributionLimitService {
@@ -25,11 +27,13 @@ impl ContributionLimitService {
         fx_service: Arc<dyn FxServiceTrait>,
         limit_repository: Arc<dyn ContributionLimitRepositoryTrait>,
         activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        settings_service: Arc<dyn SettingsServiceTrait>,
     ) -> Self {
         ContributionLimitService {
             fx_service,
             limit_repository,
             activity_repository,
+            settings_service,
         }
     }
 
@@ -145,21 +149,15 @@ impl ContributionLimitServiceTrait for ContributionLimitService {
                 .map_err(|e| Error::Validation(ValidationError::DateTimeParse(e)))?;
             self.calculate_deposits_by_period(&account_ids, start, end, base_currency)
         } else {
+            // Limits without explicit dates cover the fiscal year labelled by contribution_year
             let year = limit.contribution_year;
+            let fiscal_year = self.settings_service.get_fiscal_year()?;
             let start = NaiveDateTime::new(
-                chrono::NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| {
-                    Error::Validation(ValidationError::InvalidInput(
-                        "Invalid start date".to_string(),
-                    ))
-                })?,
+                fiscal_year.start_of(year),
                 chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
             );
             let end = NaiveDateTime::new(
-                chrono::NaiveDate::from_ymd_opt(year, 12, 31).ok_or_else(|| {
-                    Error::Validation(ValidationError::InvalidInput(
-                        "Invalid start date".to_string(),
-                    ))
-                })?,
+                fiscal_year.end_of(year),
                 chrono::NaiveTime::from_hms_opt(23, 59, 59).unwrap(),
             );
             self.calculate_deposits_by_period(&account_ids, start, end, base_currency)
@@ -0,0 +1,447 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+
+/// Longest loan term accepted, in months
+pub const MAX_LOAN_TERM_MONTHS: i32 = 600;
+
+/// Largest rise or fall in the floating rate a stress test accepts, in percentage points
+pub const MAX_RATE_SHOCK_POINTS: i64 = 20;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RepaymentMethod {
+    /// Same principal every month with interest on the reducing balance, the usual
+    /// repayment for Vietnamese bank loans
+    #[default]
+    EqualPrincipal,
+    /// Level instalments of principal and interest, recalculated when the rate changes
+    Annuity,
+    /// Interest every month and the principal with the last payment, as margin debt and
+    /// bullet loans are repaid
+    InterestOnly,
+}
+
+impl RepaymentMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RepaymentMethod::EqualPrincipal => "EQUAL_PRINCIPAL",
+            RepaymentMethod::Annuity => "ANNUITY",
+            RepaymentMethod::InterestOnly => "INTEREST_ONLY",
+        }
+    }
+}
+
+impl FromStr for RepaymentMethod {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "EQUAL_PRINCIPAL" => Ok(RepaymentMethod::EqualPrincipal),
+            "ANNUITY" => Ok(RepaymentMethod::Annuity),
+            "INTEREST_ONLY" => Ok(RepaymentMethod::InterestOnly),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown repayment method: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// What a loan was taken out for
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LoanKind {
+    Mortgage,
+    Car,
+    Consumer,
+    /// Money borrowed from a broker against a securities account
+    Margin,
+    #[default]
+    Other,
+}
+
+impl LoanKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LoanKind::Mortgage => "MORTGAGE",
+            LoanKind::Car => "CAR",
+            LoanKind::Consumer => "CONSUMER",
+            LoanKind::Margin => "MARGIN",
+            LoanKind::Other => "OTHER",
+        }
+    }
+}
+
+impl FromStr for LoanKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "MORTGAGE" => Ok(LoanKind::Mortgage),
+            "CAR" => Ok(LoanKind::Car),
+            "CONSUMER" => Ok(LoanKind::Consumer),
+            "MARGIN" => Ok(LoanKind::Margin),
+            "OTHER" => Ok(LoanKind::Other),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown loan kind: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `loans`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::loans)]
+pub struct LoanDB {
+    pub id: String,
+    pub account_id: String,
+    pub principal: String,
+    pub start_date: String,
+    pub term_months: i32,
+    pub repayment_method: String,
+    pub fixed_rate: String,
+    pub fixed_months: i32,
+    pub base_rate: Option<String>,
+    pub floating_margin: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub kind: String,
+}
+
+/// Terms of the loan held by a liability account. Amounts are in the account currency
+/// and rates are annual percentages.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Loan {
+    pub id: String,
+    pub account_id: String,
+    pub kind: LoanKind,
+    pub principal: Decimal,
+    /// Disbursement date; the first payment falls a month later
+    pub start_date: NaiveDate,
+    pub term_months: i32,
+    pub repayment_method: RepaymentMethod,
+    /// Promotional rate for the first `fixed_months` payments
+    pub fixed_rate: Decimal,
+    pub fixed_months: i32,
+    /// Reference rate the floating period is priced from
+    pub base_rate: Option<Decimal>,
+    /// Margin added to the base rate once the fixed period ends
+    pub floating_margin: Option<Decimal>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Loan {
+    pub fn is_fixed_period(&self, period: i32) -> bool {
+        period <= self.fixed_months
+    }
+
+    /// Base rate for a payment due on `payment_date`: that of the last reset dated before
+    /// it, or the one the loan was set up with
+    pub fn base_rate_on(
+        &self,
+        payment_date: NaiveDate,
+        resets: &[LoanRateReset],
+    ) -> Option<Decimal> {
+        resets
+            .iter()
+            .filter(|reset| reset.effective_date < payment_date)
+            .max_by_key(|reset| (reset.effective_date, reset.created_at))
+            .map(|reset| reset.base_rate)
+            .or(self.base_rate)
+    }
+
+    /// Annual rate charged for the given payment, counting from 1, due on `payment_date`
+    pub fn rate_for_period(
+        &self,
+        period: i32,
+        payment_date: NaiveDate,
+        resets: &[LoanRateReset],
+    ) -> Decimal {
+        if self.is_fixed_period(period) {
+            return self.fixed_rate;
+        }
+        match self.base_rate_on(payment_date, resets) {
+            Some(base) => base + self.floating_margin.unwrap_or_default(),
+            None => self.fixed_rate,
+        }
+    }
+}
+
+impl TryFrom<LoanDB> for Loan {
+    type Error = Error;
+
+    fn try_from(db: LoanDB) -> Result<Self> {
+        Ok(Loan {
+            id: db.id,
+            account_id: db.account_id,
+            kind: db.kind.parse()?,
+            principal: Decimal::from_str(&db.principal)?,
+            start_date: parse_forecast_date(&db.start_date)?,
+            term_months: db.term_months,
+            repayment_method: db.repayment_method.parse()?,
+            fixed_rate: Decimal::from_str(&db.fixed_rate)?,
+            fixed_months: db.fixed_months,
+            base_rate: db.base_rate.as_deref().map(Decimal::from_str).transpose()?,
+            floating_margin: db
+                .floating_margin
+                .as_deref()
+                .map(Decimal::from_str)
+                .transpose()?,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a loan
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewLoan {
+    pub id: Option<String>,
+    pub account_id: String,
+    #[serde(default)]
+    pub kind: LoanKind,
+    pub principal: Decimal,
+    pub start_date: NaiveDate,
+    pub term_months: i32,
+    #[serde(default)]
+    pub repayment_method: RepaymentMethod,
+    pub fixed_rate: Decimal,
+    #[serde(default)]
+    pub fixed_months: i32,
+    pub base_rate: Option<Decimal>,
+    pub floating_margin: Option<Decimal>,
+}
+
+fn check_rate(name: &str, rate: Decimal) -> Result<()> {
+    if rate < Decimal::ZERO || rate >= Decimal::ONE_HUNDRED {
+        return Err(Error::Validation(ValidationError::InvalidInput(format!(
+            "{} must be between 0 and 100 percent",
+            name
+        ))));
+    }
+    Ok(())
+}
+
+impl NewLoan {
+    pub fn validate(&self) -> Result<()> {
+        if self.account_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "accountId".to_string(),
+            )));
+        }
+        if self.principal <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Loan principal must be positive".to_string(),
+            )));
+        }
+        if !(1..=MAX_LOAN_TERM_MONTHS).contains(&self.term_months) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Loan term must be between 1 and {} months",
+                MAX_LOAN_TERM_MONTHS
+            ))));
+        }
+        if !(0..=self.term_months).contains(&self.fixed_months) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Fixed rate period cannot be longer than the loan term".to_string(),
+            )));
+        }
+        check_rate("Fixed rate", self.fixed_rate)?;
+        if self.fixed_months < self.term_months {
+            // The floating period needs a rate to price from
+            let base_rate = self.base_rate.ok_or_else(|| {
+                Error::Validation(ValidationError::MissingField("baseRate".to_string()))
+            })?;
+            let margin = self.floating_margin.ok_or_else(|| {
+                Error::Validation(ValidationError::MissingField("floatingMargin".to_string()))
+            })?;
+            check_rate("Base rate", base_rate)?;
+            check_rate("Floating margin", margin)?;
+            check_rate("Floating rate", base_rate + margin)?;
+        }
+        Ok(())
+    }
+}
+
+/// Database row for `loan_prepayments`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::loan_prepayments)]
+pub struct LoanPrepaymentDB {
+    pub id: String,
+    pub loan_id: String,
+    pub payment_date: String,
+    pub amount: String,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Extra principal paid ahead of schedule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanPrepayment {
+    pub id: String,
+    pub loan_id: String,
+    pub payment_date: NaiveDate,
+    pub amount: Decimal,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<LoanPrepaymentDB> for LoanPrepayment {
+    type Error = Error;
+
+    fn try_from(db: LoanPrepaymentDB) -> Result<Self> {
+        Ok(LoanPrepayment {
+            id: db.id,
+            loan_id: db.loan_id,
+            payment_date: parse_forecast_date(&db.payment_date)?,
+            amount: Decimal::from_str(&db.amount)?,
+            note: db.note,
+            created_at: db.created_at,
+        })
+    }
+}
+
+/// Input for recording a prepayment
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewLoanPrepayment {
+    pub loan_id: String,
+    pub payment_date: NaiveDate,
+    pub amount: Decimal,
+    pub note: Option<String>,
+}
+
+impl NewLoanPrepayment {
+    pub fn validate(&self) -> Result<()> {
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Prepayment amount must be positive".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Database row for `loan_rate_resets`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::loan_rate_resets)]
+pub struct LoanRateResetDB {
+    pub id: String,
+    pub loan_id: String,
+    pub effective_date: String,
+    pub base_rate: String,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A new base rate set by the lender. It prices the floating payments due after its date,
+/// and the rest of the schedule is worked out again from there.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanRateReset {
+    pub id: String,
+    pub loan_id: String,
+    pub effective_date: NaiveDate,
+    pub base_rate: Decimal,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<LoanRateResetDB> for LoanRateReset {
+    type Error = Error;
+
+    fn try_from(db: LoanRateResetDB) -> Result<Self> {
+        Ok(LoanRateReset {
+            id: db.id,
+            loan_id: db.loan_id,
+            effective_date: parse_forecast_date(&db.effective_date)?,
+            base_rate: Decimal::from_str(&db.base_rate)?,
+            note: db.note,
+            created_at: db.created_at,
+        })
+    }
+}
+
+/// Input for recording a rate reset
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewLoanRateReset {
+    pub loan_id: String,
+    pub effective_date: NaiveDate,
+    pub base_rate: Decimal,
+    pub note: Option<String>,
+}
+
+impl NewLoanRateReset {
+    pub fn validate(&self) -> Result<()> {
+        check_rate("Base rate", self.base_rate)
+    }
+}
+
+/// One scheduled payment
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanScheduleRow {
+    pub period: i32,
+    pub payment_date: NaiveDate,
+    pub annual_rate: Decimal,
+    pub is_fixed_rate: bool,
+    pub opening_balance: Decimal,
+    pub principal: Decimal,
+    pub interest: Decimal,
+    /// Principal plus interest
+    pub payment: Decimal,
+    /// Prepayments made since the previous payment, applied after this one
+    pub prepayment: Decimal,
+    pub closing_balance: Decimal,
+}
+
+/// A loan with its amortization schedule regenerated from the terms and prepayments
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanSchedule {
+    pub loan: Loan,
+    pub currency: String,
+    pub rows: Vec<LoanScheduleRow>,
+    pub prepayments: Vec<LoanPrepayment>,
+    pub rate_resets: Vec<LoanRateReset>,
+    pub total_interest: Decimal,
+    /// Scheduled payments plus prepayments
+    pub total_paid: Decimal,
+    /// Date of the last payment
+    pub payoff_date: Option<NaiveDate>,
+    /// Balance after the payments due by today
+    pub outstanding_balance: Decimal,
+    pub next_payment: Option<LoanScheduleRow>,
+}
+
+/// The payments left on a loan at the current rates next to the same payments with the
+/// floating rate moved by `rate_increase` points from `from_date` on
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanStressTest {
+    pub loan_id: String,
+    pub currency: String,
+    /// Percentage points added to the floating rate; negative for a fall
+    pub rate_increase: Decimal,
+    pub from_date: NaiveDate,
+    pub baseline: Vec<LoanScheduleRow>,
+    pub stressed: Vec<LoanScheduleRow>,
+    pub baseline_interest: Decimal,
+    pub stressed_interest: Decimal,
+    pub extra_interest: Decimal,
+    /// Largest payment left with the rate moved
+    pub peak_payment: Decimal,
+    /// Most any one payment goes up by
+    pub max_payment_increase: Decimal,
+}
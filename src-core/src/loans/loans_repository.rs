@@ -0,0 +1,259 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::loans_model::{
+    Loan, LoanDB, LoanPrepayment, LoanPrepaymentDB, LoanRateReset, LoanRateResetDB, NewLoan,
+    NewLoanPrepayment, NewLoanRateReset,
+};
+use super::loans_traits::LoanRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::goals::monthly_summaries::clear_goal_progress;
+use crate::schema::{loan_prepayments, loan_rate_resets, loans};
+
+pub struct LoanRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl LoanRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        LoanRepository { pool, writer }
+    }
+}
+
+/// The loan held by a liability account, if any
+pub(crate) fn find_loan_for_account(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+) -> Result<Option<Loan>> {
+    loans::table
+        .filter(loans::account_id.eq(account_id))
+        .first::<LoanDB>(conn)
+        .optional()?
+        .map(Loan::try_from)
+        .transpose()
+}
+
+/// Prepayments in date order; all loans when `loan_id` is unset
+pub(crate) fn load_prepayments(
+    conn: &mut SqliteConnection,
+    loan_id: Option<&str>,
+) -> Result<Vec<LoanPrepayment>> {
+    let mut query = loan_prepayments::table.into_boxed();
+    if let Some(loan_id) = loan_id {
+        query = query.filter(loan_prepayments::loan_id.eq(loan_id.to_string()));
+    }
+    query
+        .order((
+            loan_prepayments::payment_date.asc(),
+            loan_prepayments::created_at.asc(),
+        ))
+        .load::<LoanPrepaymentDB>(conn)?
+        .into_iter()
+        .map(LoanPrepayment::try_from)
+        .collect()
+}
+
+/// Rate resets in date order; all loans when `loan_id` is unset
+pub(crate) fn load_rate_resets(
+    conn: &mut SqliteConnection,
+    loan_id: Option<&str>,
+) -> Result<Vec<LoanRateReset>> {
+    let mut query = loan_rate_resets::table.into_boxed();
+    if let Some(loan_id) = loan_id {
+        query = query.filter(loan_rate_resets::loan_id.eq(loan_id.to_string()));
+    }
+    query
+        .order((
+            loan_rate_resets::effective_date.asc(),
+            loan_rate_resets::created_at.asc(),
+        ))
+        .load::<LoanRateResetDB>(conn)?
+        .into_iter()
+        .map(LoanRateReset::try_from)
+        .collect()
+}
+
+#[async_trait]
+impl LoanRepositoryTrait for LoanRepository {
+    fn get_loans(&self) -> Result<Vec<Loan>> {
+        let mut conn = get_connection(&self.pool)?;
+        loans::table
+            .order(loans::start_date.asc())
+            .load::<LoanDB>(&mut conn)?
+            .into_iter()
+            .map(Loan::try_from)
+            .collect()
+    }
+
+    fn get_loan(&self, id: &str) -> Result<Loan> {
+        let mut conn = get_connection(&self.pool)?;
+        loans::table.find(id).first::<LoanDB>(&mut conn)?.try_into()
+    }
+
+    fn get_loan_for_account(&self, account_id: &str) -> Result<Option<Loan>> {
+        let mut conn = get_connection(&self.pool)?;
+        find_loan_for_account(&mut conn, account_id)
+    }
+
+    async fn insert_loan(&self, loan: NewLoan) -> Result<Loan> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Loan> {
+                let now = Utc::now().naive_utc();
+                let record = LoanDB {
+                    id: loan.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: loan.account_id,
+                    principal: loan.principal.to_string(),
+                    start_date: loan.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    term_months: loan.term_months,
+                    repayment_method: loan.repayment_method.as_str().to_string(),
+                    fixed_rate: loan.fixed_rate.to_string(),
+                    fixed_months: loan.fixed_months,
+                    base_rate: loan.base_rate.map(|r| r.to_string()),
+                    floating_margin: loan.floating_margin.map(|r| r.to_string()),
+                    created_at: now,
+                    updated_at: now,
+                    kind: loan.kind.as_str().to_string(),
+                };
+
+                // Loans move net worth, which net-worth goals' stored progress was computed from
+                clear_goal_progress(conn, None, None)?;
+                diesel::insert_into(loans::table)
+                    .values(&record)
+                    .get_result::<LoanDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_loan(&self, id: &str, loan: NewLoan) -> Result<Loan> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Loan> {
+                clear_goal_progress(conn, None, None)?;
+                diesel::update(loans::table.find(id_owned))
+                    .set((
+                        loans::kind.eq(loan.kind.as_str()),
+                        loans::principal.eq(loan.principal.to_string()),
+                        loans::start_date
+                            .eq(loan.start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        loans::term_months.eq(loan.term_months),
+                        loans::repayment_method.eq(loan.repayment_method.as_str()),
+                        loans::fixed_rate.eq(loan.fixed_rate.to_string()),
+                        loans::fixed_months.eq(loan.fixed_months),
+                        loans::base_rate.eq(loan.base_rate.map(|r| r.to_string())),
+                        loans::floating_margin.eq(loan.floating_margin.map(|r| r.to_string())),
+                        loans::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<LoanDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_loan(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                clear_goal_progress(conn, None, None)?;
+                Ok(diesel::delete(loans::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_prepayments(&self, loan_id: Option<&str>) -> Result<Vec<LoanPrepayment>> {
+        let mut conn = get_connection(&self.pool)?;
+        load_prepayments(&mut conn, loan_id)
+    }
+
+    async fn insert_prepayment(&self, prepayment: NewLoanPrepayment) -> Result<LoanPrepayment> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<LoanPrepayment> {
+                    let record = LoanPrepaymentDB {
+                        id: Uuid::new_v4().to_string(),
+                        loan_id: prepayment.loan_id,
+                        payment_date: prepayment
+                            .payment_date
+                            .format(FORECAST_DATE_FORMAT)
+                            .to_string(),
+                        amount: prepayment.amount.to_string(),
+                        note: prepayment
+                            .note
+                            .map(|n| n.trim().to_string())
+                            .filter(|n| !n.is_empty()),
+                        created_at: Utc::now().naive_utc(),
+                    };
+
+                    clear_goal_progress(conn, None, Some(prepayment.payment_date))?;
+                    diesel::insert_into(loan_prepayments::table)
+                        .values(&record)
+                        .get_result::<LoanPrepaymentDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_prepayment(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                clear_goal_progress(conn, None, None)?;
+                Ok(diesel::delete(loan_prepayments::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_rate_resets(&self, loan_id: Option<&str>) -> Result<Vec<LoanRateReset>> {
+        let mut conn = get_connection(&self.pool)?;
+        load_rate_resets(&mut conn, loan_id)
+    }
+
+    async fn insert_rate_reset(&self, reset: NewLoanRateReset) -> Result<LoanRateReset> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<LoanRateReset> {
+                    let record = LoanRateResetDB {
+                        id: Uuid::new_v4().to_string(),
+                        loan_id: reset.loan_id,
+                        effective_date: reset
+                            .effective_date
+                            .format(FORECAST_DATE_FORMAT)
+                            .to_string(),
+                        base_rate: reset.base_rate.to_string(),
+                        note: reset
+                            .note
+                            .map(|n| n.trim().to_string())
+                            .filter(|n| !n.is_empty()),
+                        created_at: Utc::now().naive_utc(),
+                    };
+
+                    // The balance owed changes from the first payment the new rate prices
+                    clear_goal_progress(conn, None, Some(reset.effective_date))?;
+                    diesel::insert_into(loan_rate_resets::table)
+                        .values(&record)
+                        .get_result::<LoanRateResetDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_rate_reset(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                clear_goal_progress(conn, None, None)?;
+                Ok(diesel::delete(loan_rate_resets::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
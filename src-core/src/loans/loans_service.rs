@@ -0,0 +1,529 @@
+use async_trait::async_trait;
+use chrono::{Months, NaiveDate, Utc};
+use rust_decimal::{Decimal, MathematicalOps};
+use std::sync::Arc;
+
+use super::loans_model::{
+    Loan, LoanPrepayment, LoanRateReset, LoanSchedule, LoanScheduleRow, LoanStressTest, NewLoan,
+    NewLoanPrepayment, NewLoanRateReset, RepaymentMethod, MAX_RATE_SHOCK_POINTS,
+};
+use super::loans_traits::{LoanRepositoryTrait, LoanServiceTrait};
+use crate::accounts::{Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY};
+use crate::errors::{Error, Result, ValidationError};
+
+pub struct LoanService {
+    repository: Arc<dyn LoanRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+}
+
+impl LoanService {
+    pub fn new(
+        repository: Arc<dyn LoanRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+        }
+    }
+
+    fn liability_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type != ACCOUNT_TYPE_LIABILITY {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Loans can only be set up on liability accounts, not {}",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+}
+
+/// Level payment that repays `balance` over `periods` at `monthly_rate`
+fn annuity_payment(balance: Decimal, monthly_rate: Decimal, periods: i32) -> Decimal {
+    if monthly_rate.is_zero() {
+        return balance / Decimal::from(periods);
+    }
+    let growth = (Decimal::ONE + monthly_rate).powi(periods as i64);
+    balance * monthly_rate * growth / (growth - Decimal::ONE)
+}
+
+/// Builds the payment schedule. Each payment's principal is worked out from the balance
+/// and payments left, so a prepayment or a rate reset re-amortizes the rest of the term
+/// rather than shortening it. Prepayments are applied after the first payment due on or
+/// after their date.
+pub(crate) fn amortize(
+    loan: &Loan,
+    prepayments: &[LoanPrepayment],
+    resets: &[LoanRateReset],
+) -> Vec<LoanScheduleRow> {
+    amortize_at(loan, prepayments, |period, payment_date| {
+        loan.rate_for_period(period, payment_date, resets)
+    })
+}
+
+/// [`amortize`] with the floating rate moved by `increase` points for payments due after
+/// `from`; the promotional fixed rate is left alone
+pub(crate) fn amortize_stressed(
+    loan: &Loan,
+    prepayments: &[LoanPrepayment],
+    resets: &[LoanRateReset],
+    increase: Decimal,
+    from: NaiveDate,
+) -> Vec<LoanScheduleRow> {
+    amortize_at(loan, prepayments, |period, payment_date| {
+        let rate = loan.rate_for_period(period, payment_date, resets);
+        if loan.is_fixed_period(period) || payment_date <= from {
+            rate
+        } else {
+            (rate + increase).max(Decimal::ZERO)
+        }
+    })
+}
+
+fn amortize_at(
+    loan: &Loan,
+    prepayments: &[LoanPrepayment],
+    rate_for_period: impl Fn(i32, NaiveDate) -> Decimal,
+) -> Vec<LoanScheduleRow> {
+    let mut prepayments: Vec<&LoanPrepayment> = prepayments.iter().collect();
+    prepayments.sort_by_key(|p| p.payment_date);
+    let mut pending = prepayments.into_iter().peekable();
+
+    let mut rows = Vec::new();
+    let mut balance = loan.principal;
+    for period in 1..=loan.term_months {
+        if balance <= Decimal::ZERO {
+            break;
+        }
+        let Some(payment_date) = loan
+            .start_date
+            .checked_add_months(Months::new(period as u32))
+        else {
+            break;
+        };
+
+        let annual_rate = rate_for_period(period, payment_date);
+        let monthly_rate = annual_rate / Decimal::ONE_HUNDRED / Decimal::from(12);
+        let interest = (balance * monthly_rate).round_dp(2);
+        let periods_left = loan.term_months - period + 1;
+        let principal = if periods_left == 1 {
+            balance
+        } else {
+            match loan.repayment_method {
+                RepaymentMethod::EqualPrincipal => balance / Decimal::from(periods_left),
+                RepaymentMethod::Annuity => {
+                    annuity_payment(balance, monthly_rate, periods_left) - interest
+                }
+                RepaymentMethod::InterestOnly => Decimal::ZERO,
+            }
+            .round_dp(2)
+            .clamp(Decimal::ZERO, balance)
+        };
+        let opening_balance = balance;
+        balance -= principal;
+
+        let mut prepayment = Decimal::ZERO;
+        while let Some(extra) = pending.next_if(|p| p.payment_date <= payment_date) {
+            prepayment += extra.amount;
+        }
+        let prepayment = prepayment.min(balance);
+        balance -= prepayment;
+
+        rows.push(LoanScheduleRow {
+            period,
+            payment_date,
+            annual_rate,
+            is_fixed_rate: loan.is_fixed_period(period),
+            opening_balance,
+            principal,
+            interest,
+            payment: principal + interest,
+            prepayment,
+            closing_balance: balance,
+        });
+    }
+    rows
+}
+
+/// Balance still owed on `date` given the schedule from [`amortize`]
+pub(crate) fn outstanding_on(
+    loan: &Loan,
+    rows: &[LoanScheduleRow],
+    prepayments: &[LoanPrepayment],
+    date: NaiveDate,
+) -> Decimal {
+    let last_due = rows.iter().rev().find(|r| r.payment_date <= date);
+    let paid_since = last_due.map_or(loan.start_date, |r| r.payment_date);
+    // Prepayments made since the last payment have not reached a schedule row yet
+    let prepaid_since: Decimal = prepayments
+        .iter()
+        .filter(|p| p.payment_date > paid_since && p.payment_date <= date)
+        .map(|p| p.amount)
+        .sum();
+    (last_due.map_or(loan.principal, |r| r.closing_balance) - prepaid_since).max(Decimal::ZERO)
+}
+
+pub(crate) fn build_schedule(
+    loan: Loan,
+    currency: String,
+    prepayments: Vec<LoanPrepayment>,
+    rate_resets: Vec<LoanRateReset>,
+    today: NaiveDate,
+) -> LoanSchedule {
+    let rows = amortize(&loan, &prepayments, &rate_resets);
+
+    let total_interest = rows.iter().map(|r| r.interest).sum();
+    let total_paid = rows.iter().map(|r| r.payment + r.prepayment).sum();
+    let outstanding_balance = outstanding_on(&loan, &rows, &prepayments, today);
+
+    LoanSchedule {
+        payoff_date: rows.last().map(|r| r.payment_date),
+        next_payment: rows.iter().find(|r| r.payment_date > today).cloned(),
+        loan,
+        currency,
+        rows,
+        prepayments,
+        rate_resets,
+        total_interest,
+        total_paid,
+        outstanding_balance,
+    }
+}
+
+/// Compares the payments due after `today` with and without the rate moved
+pub(crate) fn stress_test(
+    loan: &Loan,
+    currency: String,
+    prepayments: &[LoanPrepayment],
+    resets: &[LoanRateReset],
+    rate_increase: Decimal,
+    today: NaiveDate,
+) -> LoanStressTest {
+    let remaining = |rows: Vec<LoanScheduleRow>| -> Vec<LoanScheduleRow> {
+        rows.into_iter()
+            .filter(|row| row.payment_date > today)
+            .collect()
+    };
+    let baseline = remaining(amortize(loan, prepayments, resets));
+    let stressed = remaining(amortize_stressed(
+        loan,
+        prepayments,
+        resets,
+        rate_increase,
+        today,
+    ));
+    let baseline_interest: Decimal = baseline.iter().map(|row| row.interest).sum();
+    let stressed_interest: Decimal = stressed.iter().map(|row| row.interest).sum();
+    let max_payment_increase = baseline
+        .iter()
+        .zip(&stressed)
+        .map(|(before, after)| after.payment - before.payment)
+        .max()
+        .unwrap_or_default();
+
+    LoanStressTest {
+        loan_id: loan.id.clone(),
+        currency,
+        rate_increase,
+        from_date: today,
+        peak_payment: stressed
+            .iter()
+            .map(|row| row.payment)
+            .max()
+            .unwrap_or_default(),
+        max_payment_increase,
+        extra_interest: stressed_interest - baseline_interest,
+        baseline_interest,
+        stressed_interest,
+        baseline,
+        stressed,
+    }
+}
+
+/// The reset base rate plus the loan's margin must still be a valid rate
+fn check_floating_rate(loan: &Loan, base_rate: Decimal) -> Result<()> {
+    if base_rate + loan.floating_margin.unwrap_or_default() >= Decimal::ONE_HUNDRED {
+        return Err(Error::Validation(ValidationError::InvalidInput(
+            "Floating rate must be between 0 and 100 percent".to_string(),
+        )));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl LoanServiceTrait for LoanService {
+    fn get_loans(&self) -> Result<Vec<Loan>> {
+        self.repository.get_loans()
+    }
+
+    fn get_loan_schedule(&self, id: &str) -> Result<LoanSchedule> {
+        let loan = self.repository.get_loan(id)?;
+        let account = self.account_repository.get_by_id(&loan.account_id)?;
+        let prepayments = self.repository.get_prepayments(Some(id))?;
+        let rate_resets = self.repository.get_rate_resets(Some(id))?;
+        Ok(build_schedule(
+            loan,
+            account.currency,
+            prepayments,
+            rate_resets,
+            Utc::now().date_naive(),
+        ))
+    }
+
+    fn stress_test_loan(&self, id: &str, rate_increase: Decimal) -> Result<LoanStressTest> {
+        if rate_increase.abs() > Decimal::from(MAX_RATE_SHOCK_POINTS) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Rate change must be within {} percentage points",
+                MAX_RATE_SHOCK_POINTS
+            ))));
+        }
+        let loan = self.repository.get_loan(id)?;
+        let account = self.account_repository.get_by_id(&loan.account_id)?;
+        let prepayments = self.repository.get_prepayments(Some(id))?;
+        let rate_resets = self.repository.get_rate_resets(Some(id))?;
+        Ok(stress_test(
+            &loan,
+            account.currency,
+            &prepayments,
+            &rate_resets,
+            rate_increase,
+            Utc::now().date_naive(),
+        ))
+    }
+
+    async fn create_loan(&self, loan: NewLoan) -> Result<Loan> {
+        loan.validate()?;
+        let account = self.liability_account(&loan.account_id)?;
+        if self.repository.get_loan_for_account(&account.id)?.is_some() {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} already has a loan",
+                account.name
+            ))));
+        }
+        self.repository.insert_loan(loan).await
+    }
+
+    async fn update_loan(&self, id: &str, loan: NewLoan) -> Result<Loan> {
+        loan.validate()?;
+        let existing = self.repository.get_loan(id)?;
+        if existing.account_id != loan.account_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A loan cannot be moved to another account".to_string(),
+            )));
+        }
+        self.repository.update_loan(id, loan).await
+    }
+
+    async fn delete_loan(&self, id: &str) -> Result<usize> {
+        self.repository.delete_loan(id).await
+    }
+
+    fn get_loan_prepayments(&self, loan_id: Option<String>) -> Result<Vec<LoanPrepayment>> {
+        self.repository.get_prepayments(loan_id.as_deref())
+    }
+
+    async fn add_loan_prepayment(&self, prepayment: NewLoanPrepayment) -> Result<LoanPrepayment> {
+        prepayment.validate()?;
+        let loan = self.repository.get_loan(&prepayment.loan_id)?;
+        if prepayment.payment_date <= loan.start_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Prepayment date must be after the loan start date".to_string(),
+            )));
+        }
+
+        let existing = self.repository.get_prepayments(Some(&loan.id))?;
+        let resets = self.repository.get_rate_resets(Some(&loan.id))?;
+        let rows = amortize(&loan, &existing, &resets);
+        let Some(row) = rows
+            .iter()
+            .find(|r| r.payment_date >= prepayment.payment_date)
+        else {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The loan is fully repaid by the prepayment date".to_string(),
+            )));
+        };
+        // Prepayments already recorded against the same payment come off first
+        let available = row.opening_balance - row.principal - row.prepayment;
+        if prepayment.amount > available {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Prepayment exceeds the {} left on the loan",
+                available
+            ))));
+        }
+        self.repository.insert_prepayment(prepayment).await
+    }
+
+    async fn delete_loan_prepayment(&self, id: &str) -> Result<usize> {
+        self.repository.delete_prepayment(id).await
+    }
+
+    fn get_loan_rate_resets(&self, loan_id: Option<String>) -> Result<Vec<LoanRateReset>> {
+        self.repository.get_rate_resets(loan_id.as_deref())
+    }
+
+    async fn add_loan_rate_reset(&self, reset: NewLoanRateReset) -> Result<LoanRateReset> {
+        reset.validate()?;
+        let loan = self.repository.get_loan(&reset.loan_id)?;
+        if loan.fixed_months >= loan.term_months {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The loan is fixed-rate for its whole term".to_string(),
+            )));
+        }
+        if reset.effective_date <= loan.start_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Rate reset must be after the loan start date".to_string(),
+            )));
+        }
+        check_floating_rate(&loan, reset.base_rate)?;
+        self.repository.insert_rate_reset(reset).await
+    }
+
+    async fn delete_loan_rate_reset(&self, id: &str) -> Result<usize> {
+        self.repository.delete_rate_reset(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loans::LoanKind;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn loan(method: RepaymentMethod) -> Loan {
+        let now = Utc::now().naive_utc();
+        Loan {
+            id: "home".to_string(),
+            account_id: "mortgage".to_string(),
+            kind: LoanKind::Mortgage,
+            principal: dec!(1_200_000_000),
+            start_date: date("2026-01-10"),
+            term_months: 12,
+            repayment_method: method,
+            fixed_rate: dec!(6),
+            fixed_months: 6,
+            base_rate: Some(dec!(6.5)),
+            floating_margin: Some(dec!(3.5)),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn prepayment(on: &str, amount: Decimal) -> LoanPrepayment {
+        LoanPrepayment {
+            id: on.to_string(),
+            loan_id: "home".to_string(),
+            payment_date: date(on),
+            amount,
+            note: None,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn equal_principal_switches_to_floating_rate_after_promotion() {
+        let rows = amortize(&loan(RepaymentMethod::EqualPrincipal), &[], &[]);
+        assert_eq!(rows.len(), 12);
+        assert_eq!(rows[0].payment_date, date("2026-02-10"));
+        assert_eq!(rows[0].principal, dec!(100_000_000));
+        // 6% a year on 1.2bn is 6m a month
+        assert_eq!(rows[0].interest, dec!(6_000_000));
+        assert!(rows[5].is_fixed_rate);
+        // Month 7 pays base 6.5% + margin 3.5% on the 600m left
+        assert!(!rows[6].is_fixed_rate);
+        assert_eq!(rows[6].annual_rate, dec!(10));
+        assert_eq!(rows[6].interest, dec!(5_000_000));
+        assert_eq!(rows[11].closing_balance, Decimal::ZERO);
+    }
+
+    #[test]
+    fn prepayment_re_amortizes_remaining_term() {
+        let base = loan(RepaymentMethod::EqualPrincipal);
+        let rows = amortize(&base, &[prepayment("2026-03-01", dec!(200_000_000))], &[]);
+        // Applied after the 10 March payment, leaving 800m over 10 payments
+        assert_eq!(rows[1].prepayment, dec!(200_000_000));
+        assert_eq!(rows[1].closing_balance, dec!(800_000_000));
+        assert_eq!(rows[2].principal, dec!(80_000_000));
+        assert_eq!(rows.len(), 12);
+        assert_eq!(rows[11].closing_balance, Decimal::ZERO);
+
+        let annuity = amortize(&loan(RepaymentMethod::Annuity), &[], &[]);
+        assert_eq!(annuity[0].payment, annuity[5].payment);
+        assert_eq!(annuity[11].closing_balance, Decimal::ZERO);
+
+        let schedule = build_schedule(base, "VND".to_string(), vec![], vec![], date("2026-04-15"));
+        assert_eq!(schedule.outstanding_balance, dec!(900_000_000));
+        assert_eq!(
+            schedule.next_payment.map(|r| r.payment_date),
+            Some(date("2026-05-10"))
+        );
+    }
+
+    #[test]
+    fn interest_only_repays_the_principal_with_the_last_payment() {
+        let bullet = loan(RepaymentMethod::InterestOnly);
+        let rows = amortize(&bullet, &[prepayment("2026-04-01", dec!(200_000_000))], &[]);
+        assert_eq!(rows[0].principal, Decimal::ZERO);
+        assert_eq!(rows[0].payment, dec!(6_000_000));
+        // A prepayment lowers the balance interest is charged on but no payment is added
+        assert_eq!(rows[3].interest, dec!(5_000_000));
+        assert_eq!(rows[10].closing_balance, dec!(1_000_000_000));
+        assert_eq!(rows[11].principal, dec!(1_000_000_000));
+        assert_eq!(rows[11].closing_balance, Decimal::ZERO);
+    }
+
+    fn reset(on: &str, base_rate: Decimal) -> LoanRateReset {
+        LoanRateReset {
+            id: on.to_string(),
+            loan_id: "home".to_string(),
+            effective_date: date(on),
+            base_rate,
+            note: None,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn rate_reset_reprices_the_floating_payments_after_it() {
+        let annuity = loan(RepaymentMethod::Annuity);
+        let resets = [reset("2026-09-01", dec!(8.5))];
+        let rows = amortize(&annuity, &[], &resets);
+        // August still pays base 6.5% + 3.5%; September on pays 8.5% + 3.5%
+        assert_eq!(rows[6].annual_rate, dec!(10));
+        assert_eq!(rows[7].annual_rate, dec!(12));
+        // The level payment is worked out again at the new rate
+        assert!(rows[8].payment > rows[6].payment);
+        assert_eq!(rows[7].payment, rows[10].payment);
+        assert_eq!(rows[11].closing_balance, Decimal::ZERO);
+        // A reset during the promotion leaves the fixed payments alone
+        let early = amortize(&annuity, &[], &[reset("2026-02-01", dec!(9))]);
+        assert_eq!(early[0].annual_rate, dec!(6));
+        assert_eq!(early[6].annual_rate, dec!(12.5));
+    }
+
+    #[test]
+    fn stress_test_raises_only_floating_payments_after_today() {
+        let base = loan(RepaymentMethod::EqualPrincipal);
+        let test = stress_test(
+            &base,
+            "VND".to_string(),
+            &[],
+            &[],
+            dec!(2),
+            date("2026-05-15"),
+        );
+        assert_eq!(test.baseline.len(), 8);
+        assert_eq!(test.stressed.len(), 8);
+        // June and July are still fixed; August's 600m balance now pays 12%
+        assert_eq!(test.stressed[1].annual_rate, dec!(6));
+        assert_eq!(test.stressed[2].annual_rate, dec!(12));
+        assert_eq!(test.stressed[2].interest, dec!(6_000_000));
+        assert_eq!(test.max_payment_increase, dec!(1_000_000));
+        // 2% a year on the 600m, 500m, ... 100m left over the floating months
+        assert_eq!(test.extra_interest, dec!(3_500_000));
+    }
+}
@@ -0,0 +1,44 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use super::loans_model::{
+    Loan, LoanPrepayment, LoanRateReset, LoanSchedule, LoanStressTest, NewLoan, NewLoanPrepayment,
+    NewLoanRateReset,
+};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait LoanRepositoryTrait: Send + Sync {
+    fn get_loans(&self) -> Result<Vec<Loan>>;
+    fn get_loan(&self, id: &str) -> Result<Loan>;
+    fn get_loan_for_account(&self, account_id: &str) -> Result<Option<Loan>>;
+    async fn insert_loan(&self, loan: NewLoan) -> Result<Loan>;
+    async fn update_loan(&self, id: &str, loan: NewLoan) -> Result<Loan>;
+    async fn delete_loan(&self, id: &str) -> Result<usize>;
+    /// Prepayments in date order; all loans when `loan_id` is unset
+    fn get_prepayments(&self, loan_id: Option<&str>) -> Result<Vec<LoanPrepayment>>;
+    async fn insert_prepayment(&self, prepayment: NewLoanPrepayment) -> Result<LoanPrepayment>;
+    async fn delete_prepayment(&self, id: &str) -> Result<usize>;
+    /// Rate resets in date order; all loans when `loan_id` is unset
+    fn get_rate_resets(&self, loan_id: Option<&str>) -> Result<Vec<LoanRateReset>>;
+    async fn insert_rate_reset(&self, reset: NewLoanRateReset) -> Result<LoanRateReset>;
+    async fn delete_rate_reset(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait LoanServiceTrait: Send + Sync {
+    fn get_loans(&self) -> Result<Vec<Loan>>;
+    /// Regenerates the amortization schedule from the loan terms, prepayments and rate resets
+    fn get_loan_schedule(&self, id: &str) -> Result<LoanSchedule>;
+    /// The payments left with the floating rate moved by `rate_increase` points from today
+    fn stress_test_loan(&self, id: &str, rate_increase: Decimal) -> Result<LoanStressTest>;
+    async fn create_loan(&self, loan: NewLoan) -> Result<Loan>;
+    async fn update_loan(&self, id: &str, loan: NewLoan) -> Result<Loan>;
+    async fn delete_loan(&self, id: &str) -> Result<usize>;
+    fn get_loan_prepayments(&self, loan_id: Option<String>) -> Result<Vec<LoanPrepayment>>;
+    async fn add_loan_prepayment(&self, prepayment: NewLoanPrepayment) -> Result<LoanPrepayment>;
+    async fn delete_loan_prepayment(&self, id: &str) -> Result<usize>;
+    fn get_loan_rate_resets(&self, loan_id: Option<String>) -> Result<Vec<LoanRateReset>>;
+    async fn add_loan_rate_reset(&self, reset: NewLoanRateReset) -> Result<LoanRateReset>;
+    async fn delete_loan_rate_reset(&self, id: &str) -> Result<usize>;
+}
@@ -0,0 +1,15 @@
+mod loans_model;
+mod loans_repository;
+mod loans_service;
+mod loans_traits;
+
+pub use loans_model::{
+    Loan, LoanKind, LoanPrepayment, LoanRateReset, LoanSchedule, LoanScheduleRow, LoanStressTest,
+    NewLoan, NewLoanPrepayment, NewLoanRateReset, RepaymentMethod, MAX_LOAN_TERM_MONTHS,
+    MAX_RATE_SHOCK_POINTS,
+};
+pub use loans_repository::LoanRepository;
+pub(crate) use loans_repository::{find_loan_for_account, load_prepayments, load_rate_resets};
+pub use loans_service::LoanService;
+pub(crate) use loans_service::{amortize, amortize_stressed, outstanding_on};
+pub use loans_traits::{LoanRepositoryTrait, LoanServiceTrait};
@@ -6,6 +6,7 @@ pub const DATA_SOURCE_CALCULATED: &str = "CALCULATED";
 pub const DATA_SOURCE_ALPHA_VANTAGE: &str = "ALPHA_VANTAGE";
 pub const DATA_SOURCE_METAL_PRICE_API: &str = "METAL_PRICE_API";
 pub const DATA_SOURCE_VN_MARKET: &str = "VN_MARKET";
+pub const DATA_SOURCE_VIETCOMBANK: &str = "VIETCOMBANK";
 
 /// Default values
 pub const DEFAULT_QUOTE_BATCH_SIZE: usize = 1000;
@@ -1,6 +1,6 @@
 use crate::market_data::market_data_constants::{
     DATA_SOURCE_ALPHA_VANTAGE, DATA_SOURCE_MANUAL, DATA_SOURCE_MARKET_DATA_APP,
-    DATA_SOURCE_METAL_PRICE_API, DATA_SOURCE_YAHOO, DATA_SOURCE_VN_MARKET,
+    DATA_SOURCE_METAL_PRICE_API, DATA_SOURCE_YAHOO, DATA_SOURCE_VN_MARKET, DATA_SOURCE_VIETCOMBANK,
 };
 use crate::schema::quotes;
 use chrono::{DateTime, Utc};
@@ -150,6 +150,7 @@ pub enum DataSource {
     AlphaVantage,
     MetalPriceApi,
     VnMarket,
+    Vietcombank,
     #[default]
     Manual,
 }
@@ -162,6 +163,7 @@ impl DataSource {
             DataSource::AlphaVantage => DATA_SOURCE_ALPHA_VANTAGE,
             DataSource::MetalPriceApi => DATA_SOURCE_METAL_PRICE_API,
             DataSource::VnMarket => DATA_SOURCE_VN_MARKET,
+            DataSource::Vietcombank => DATA_SOURCE_VIETCOMBANK,
             DataSource::Manual => DATA_SOURCE_MANUAL,
         }
     }
@@ -181,6 +183,7 @@ impl From<&str> for DataSource {
             DATA_SOURCE_ALPHA_VANTAGE => DataSource::AlphaVantage,
             DATA_SOURCE_METAL_PRICE_API => DataSource::MetalPriceApi,
             DATA_SOURCE_VN_MARKET => DataSource::VnMarket,
+            DATA_SOURCE_VIETCOMBANK => DataSource::Vietcombank,
             _ => DataSource::Manual,
         }
     }
@@ -230,6 +233,24 @@ pub struct MarketDataProviderSetting {
     pub last_sync_error: Option<String>,
 }
 
+/// Whether a provider has an API key in the OS keychain. The key itself never leaves core.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCredentialStatus {
+    pub provider_id: String,
+    pub requires_credential: bool,
+    pub has_credential: bool,
+}
+
+/// Outcome of a live check of a provider API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCredentialTestResult {
+    pub provider_id: String,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Insertable, AsChangeset)]
 #[diesel(table_name = crate::schema::market_data_providers)]
 #[diesel(check_for_backend(diesel::sqlite::Sqlite))]
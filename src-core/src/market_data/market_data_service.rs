@@ -8,20 +8,23 @@ use std::collections::btree_map::Entry as BTreeEntry;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::SystemTime;
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
+use tracing::instrument;
 
 type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
 use super::market_data_constants::*;
 use super::market_data_model::{
     ImportValidationStatus, LatestQuotePair, MarketDataProviderInfo, MarketDataProviderSetting,
-    Quote, QuoteImport, QuoteRequest, QuoteSummary, UpdateMarketDataProviderSetting, DataSource,
+    ProviderCredentialStatus, ProviderCredentialTestResult, Quote, QuoteImport, QuoteRequest,
+    QuoteSummary, UpdateMarketDataProviderSetting, DataSource,
 };
 use super::market_data_traits::{MarketDataRepositoryTrait, MarketDataServiceTrait};
 use super::providers::models::AssetProfile;
 use crate::assets::assets_traits::AssetRepositoryTrait;
-use crate::errors::Result;
+use crate::errors::{Error, Result, ValidationError};
 use crate::market_data::providers::ProviderRegistry;
+use crate::secrets::SecretManager;
 use crate::utils::time_utils;
 
 const QUOTE_LOOKBACK_DAYS: i64 = 7;
@@ -35,6 +38,9 @@ struct SymbolSyncPlanItem {
 
 pub struct MarketDataService {
     provider_registry: Arc<RwLock<ProviderRegistry>>,
+    /// Set once the configured providers are in the registry; until then it holds only the
+    /// manual profiler
+    providers_loaded: OnceCell<()>,
     repository: Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
     asset_repository: Arc<dyn AssetRepositoryTrait + Send + Sync>,
     pool: Option<Arc<DbPool>>,
@@ -76,6 +82,7 @@ impl MarketDataServiceTrait for MarketDataService {
         }
 
         // 3. Search all external providers in parallel (only if no exact local match)
+        self.initialize_providers().await?;
         let provider_results_with_ids = self.provider_registry
             .read()
             .await
@@ -164,6 +171,7 @@ impl MarketDataServiceTrait for MarketDataService {
     }
 
     async fn get_asset_profile(&self, symbol: &str) -> Result<AssetProfile> {
+        self.initialize_providers().await?;
         self.provider_registry
             .read()
             .await
@@ -182,6 +190,10 @@ impl MarketDataServiceTrait for MarketDataService {
         self.repository.save_quote(quote).await
     }
 
+    async fn add_quotes(&self, quotes: &[Quote]) -> Result<()> {
+        self.repository.save_quotes(quotes).await
+    }
+
     async fn update_quote(&self, quote: Quote) -> Result<Quote> {
         self.repository.save_quote(&quote).await
     }
@@ -242,6 +254,7 @@ impl MarketDataServiceTrait for MarketDataService {
             .from_utc_datetime(&end_date.and_hms_opt(23, 59, 59).unwrap())
             .into();
 
+        self.initialize_providers().await?;
         let fetched_quotes = self.provider_registry
             .read()
             .await
@@ -263,6 +276,7 @@ impl MarketDataServiceTrait for MarketDataService {
         Ok(fetched_quotes)
     }
 
+    #[instrument(name = "market_data.sync", skip_all)]
     async fn sync_market_data(&self) -> Result<((), Vec<(String, String)>)> {
         debug!("Syncing market data.");
 
@@ -283,6 +297,7 @@ impl MarketDataServiceTrait for MarketDataService {
         self.process_market_data_sync(quote_requests, false).await
     }
 
+    #[instrument(name = "market_data.resync", skip(self))]
     async fn resync_market_data(
         &self,
         symbols: Option<Vec<String>>,
@@ -432,27 +447,78 @@ impl MarketDataServiceTrait for MarketDataService {
         Ok(updated_setting)
     }
 
-    async fn import_quotes_from_csv(
+    fn get_provider_credential_statuses(&self) -> Result<Vec<ProviderCredentialStatus>> {
+        self.repository
+            .get_all_providers()?
+            .into_iter()
+            .map(|provider| {
+                let requires_credential = ProviderRegistry::requires_credential(&provider.id);
+                let has_credential = requires_credential
+                    && SecretManager::get_secret(&provider.id)?.is_some_and(|key| !key.is_empty());
+                Ok(ProviderCredentialStatus {
+                    provider_id: provider.id,
+                    requires_credential,
+                    has_credential,
+                })
+            })
+            .collect()
+    }
+
+    async fn set_provider_credential(&self, provider_id: &str, api_key: &str) -> Result<()> {
+        let api_key = api_key.trim();
+        if api_key.is_empty() {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "API key cannot be empty".to_string(),
+            )));
+        }
+        SecretManager::set_secret(provider_id, api_key)?;
+        debug!("Stored credential for provider {}, refreshing registry", provider_id);
+        self.refresh_provider_registry().await
+    }
+
+    async fn test_provider_credential(
+        &self,
+        provider_id: &str,
+        api_key: Option<String>,
+    ) -> Result<ProviderCredentialTestResult> {
+        let api_key = match api_key {
+            Some(key) => Some(key),
+            None => SecretManager::get_secret(provider_id)?,
+        };
+        let outcome = match api_key {
+            Some(key) if !key.trim().is_empty() => {
+                ProviderRegistry::test_provider_credential(provider_id, key.trim().to_string())
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            _ => Err("No API key configured".to_string()),
+        };
+
+        Ok(ProviderCredentialTestResult {
+            provider_id: provider_id.to_string(),
+            success: outcome.is_ok(),
+            message: outcome.err(),
+        })
+    }
+
+    async fn remove_provider_credential(&self, provider_id: &str) -> Result<()> {
+        SecretManager::delete_secret(provider_id)?;
+        self.refresh_provider_registry().await
+    }
+
+    fn check_quotes_import(
         &self,
         quotes: Vec<QuoteImport>,
         overwrite: bool,
-        ) -> Result<Vec<QuoteImport>> {
-        debug!("🚀 SERVICE: import_quotes_from_csv called");
-        debug!(
-            "📊 Processing {} quotes, overwrite: {}",
-            quotes.len(),
-            overwrite
-        );
-
+    ) -> Result<Vec<QuoteImport>> {
         let mut results = Vec::new();
-        let mut quotes_to_import = Vec::new();
 
         debug!("🔍 Starting quote validation and duplicate checking...");
         for (index, mut quote) in quotes.into_iter().enumerate() {
             debug!(
             "📋 Processing quote {}/{}: symbol={}, date={}",
                 index + 1,
-            results.len() + quotes_to_import.len() + 1,
+            results.len() + 1,
         quote.symbol,
         quote.date
         );
@@ -465,7 +531,6 @@ impl MarketDataServiceTrait for MarketDataService {
                 if overwrite {
                     debug!("🔄 Quote exists but overwrite=true, will import");
                     quote.validation_status = ImportValidationStatus::Valid;
-                    quotes_to_import.push(quote.clone());
                 } else {
                     debug!("⚠️ Quote exists and overwrite=false, skipping");
                     quote.validation_status = ImportValidationStatus::Warning(
@@ -476,12 +541,31 @@ impl MarketDataServiceTrait for MarketDataService {
                 debug!("✨ New quote, validating...");
                 quote.validation_status = self.validate_quote_data(&quote);
                 debug!("📋 Validation result: {:?}", quote.validation_status);
-                if matches!(quote.validation_status, ImportValidationStatus::Valid) {
-                    quotes_to_import.push(quote.clone());
-                }
             }
             results.push(quote);
         }
+        Ok(results)
+    }
+
+    #[instrument(name = "market_data.import_quotes", skip(self, quotes), fields(rows = quotes.len()))]
+    async fn import_quotes_from_csv(
+        &self,
+        quotes: Vec<QuoteImport>,
+        overwrite: bool,
+        ) -> Result<Vec<QuoteImport>> {
+        debug!("🚀 SERVICE: import_quotes_from_csv called");
+        debug!(
+            "📊 Processing {} quotes, overwrite: {}",
+            quotes.len(),
+            overwrite
+        );
+
+        let results = self.check_quotes_import(quotes, overwrite)?;
+        let quotes_to_import: Vec<QuoteImport> = results
+            .iter()
+            .filter(|quote| matches!(quote.validation_status, ImportValidationStatus::Valid))
+            .cloned()
+            .collect();
 
         debug!(
             "📊 Validation complete: {} total, {} to import",
@@ -550,6 +634,25 @@ impl MarketDataServiceTrait for MarketDataService {
         self.repository.bulk_upsert_quotes(quotes).await
     }
 
+    async fn initialize_providers(&self) -> Result<()> {
+        self.providers_loaded
+            .get_or_init(|| async {
+                // Same fallback as `with_pool`: keep the manual-only registry rather than fail
+                if let Err(e) = self.refresh_provider_registry().await {
+                    log::warn!(
+                        "Provider registry initialization failed: {}. Keeping empty registry.",
+                        e
+                    );
+                }
+            })
+            .await;
+        Ok(())
+    }
+
+    fn providers_ready(&self) -> bool {
+        self.providers_loaded.initialized()
+    }
+
 
 }
 
@@ -586,6 +689,25 @@ impl MarketDataService {
 
         Ok(Self {
             provider_registry,
+            providers_loaded: OnceCell::new_with(Some(())),
+            repository,
+            asset_repository,
+            pool,
+        })
+    }
+
+    /// Create a market data service whose providers are configured on first use (or by
+    /// `initialize_providers`) instead of now. Building the registry reads the OS keychain and
+    /// may reach the network, which is too slow to block startup on.
+    pub async fn deferred(
+        repository: Arc<dyn MarketDataRepositoryTrait + Send + Sync>,
+        asset_repository: Arc<dyn AssetRepositoryTrait + Send + Sync>,
+        pool: Option<Arc<DbPool>>,
+    ) -> Result<Self> {
+        let registry = ProviderRegistry::new(Vec::new()).await?;
+        Ok(Self {
+            provider_registry: Arc::new(RwLock::new(registry)),
+            providers_loaded: OnceCell::new(),
             repository,
             asset_repository,
             pool,
@@ -730,6 +852,7 @@ impl MarketDataService {
         if sync_plan.is_empty() {
             debug!("All tracked symbols are already up to date; nothing to fetch from providers.");
         } else {
+            self.initialize_providers().await?;
             let mut grouped_requests: BTreeMap<NaiveDateTime, (SystemTime, Vec<(String, String)>)> =
                 BTreeMap::new();
 
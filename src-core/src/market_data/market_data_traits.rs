@@ -8,7 +8,8 @@ use super::market_data_model::{
 use super::providers::models::AssetProfile;
 use crate::errors::Result;
 use crate::market_data::market_data_model::{
-    MarketDataProviderSetting, UpdateMarketDataProviderSetting,
+    MarketDataProviderSetting, ProviderCredentialStatus, ProviderCredentialTestResult,
+    UpdateMarketDataProviderSetting,
 };
 
 #[async_trait]
@@ -20,6 +21,8 @@ pub trait MarketDataServiceTrait: Send + Sync {
     async fn get_asset_profile(&self, symbol: &str) -> Result<AssetProfile>;
     fn get_historical_quotes_for_symbol(&self, symbol: &str) -> Result<Vec<Quote>>;
     async fn add_quote(&self, quote: &Quote) -> Result<Quote>;
+    /// Saves the quotes in one write
+    async fn add_quotes(&self, quotes: &[Quote]) -> Result<()>;
     async fn update_quote(&self, quote: Quote) -> Result<Quote>;
     async fn delete_quote(&self, quote_id: &str) -> Result<()>;
     async fn get_historical_quotes_from_provider(
@@ -59,7 +62,30 @@ pub trait MarketDataServiceTrait: Send + Sync {
         enabled: bool,
     ) -> Result<MarketDataProviderSetting>;
 
+    /// Configures the enabled providers if that was deferred; later calls wait for or reuse
+    /// the first. Provider failures fall back to manual-only data rather than an error.
+    async fn initialize_providers(&self) -> Result<()>;
+    /// Whether the enabled providers are configured, i.e. `initialize_providers` has finished
+    fn providers_ready(&self) -> bool;
+
+    // --- Provider Credentials (stored in the OS keychain) ---
+    fn get_provider_credential_statuses(&self) -> Result<Vec<ProviderCredentialStatus>>;
+    async fn set_provider_credential(&self, provider_id: &str, api_key: &str) -> Result<()>;
+    /// Tests `api_key` if given, otherwise the stored key
+    async fn test_provider_credential(
+        &self,
+        provider_id: &str,
+        api_key: Option<String>,
+    ) -> Result<ProviderCredentialTestResult>;
+    async fn remove_provider_credential(&self, provider_id: &str) -> Result<()>;
+
     // --- Quote Import Methods ---
+    /// Validates quotes and flags the ones already stored, without saving anything
+    fn check_quotes_import(
+        &self,
+        quotes: Vec<QuoteImport>,
+        overwrite: bool,
+    ) -> Result<Vec<QuoteImport>>;
     async fn import_quotes_from_csv(
         &self,
         quotes: Vec<QuoteImport>,
@@ -9,9 +9,11 @@ pub(crate) mod providers;
 // Re-export the public interface
 pub use market_data_constants::*;
 pub use market_data_model::{
-    DataSource, ImportValidationStatus, MarketDataProviderInfo, MarketDataProviderSetting, Quote,
+    DataSource, ImportValidationStatus, LatestQuotePair, MarketDataProviderInfo,
+    MarketDataProviderSetting, ProviderCredentialStatus, ProviderCredentialTestResult, Quote,
     QuoteImport, QuoteRequest, QuoteSummary,
 };
+pub(crate) use market_data_model::QuoteDb;
 pub use market_data_repository::MarketDataRepository;
 pub use market_data_service::MarketDataService;
 pub use market_data_traits::MarketDataServiceTrait;
@@ -5,6 +5,7 @@ pub mod marketdata_app_provider;
 pub mod metal_price_api_provider;
 pub mod models;
 pub mod provider_registry;
+pub mod vietcombank_provider;
 pub mod vn_market_provider;
 pub mod yahoo_provider;
 
@@ -1,6 +1,6 @@
 use crate::market_data::market_data_constants::{
     DATA_SOURCE_ALPHA_VANTAGE, DATA_SOURCE_MANUAL, DATA_SOURCE_MARKET_DATA_APP,
-    DATA_SOURCE_METAL_PRICE_API, DATA_SOURCE_VN_MARKET, DATA_SOURCE_YAHOO,
+    DATA_SOURCE_METAL_PRICE_API, DATA_SOURCE_VIETCOMBANK, DATA_SOURCE_VN_MARKET, DATA_SOURCE_YAHOO,
 };
 use crate::market_data::market_data_errors::MarketDataError;
 use crate::market_data::market_data_model::{
@@ -15,6 +15,7 @@ use crate::market_data::providers::marketdata_app_provider::MarketDataAppProvide
 use crate::market_data::providers::metal_price_api_provider::MetalPriceApiProvider;
 use crate::market_data::providers::yahoo_provider::YahooProvider;
 use crate::market_data::providers::vn_market_provider::VnMarketProvider;
+use crate::market_data::providers::vietcombank_provider::VietcombankProvider;
 use crate::secrets::SecretManager;
 use diesel::r2d2::{ConnectionManager, Pool};
 use diesel::SqliteConnection;
@@ -30,7 +31,46 @@ pub struct ProviderRegistry {
     ordered_profiler_ids: Vec<String>,
 }
 
+/// Providers that need an API key, with a symbol each supports for a cheap probe request
+const CREDENTIAL_PROBES: &[(&str, &str)] = &[
+    (DATA_SOURCE_MARKET_DATA_APP, "AAPL"),
+    (DATA_SOURCE_ALPHA_VANTAGE, "IBM"),
+    (DATA_SOURCE_METAL_PRICE_API, "XAU"),
+];
+
 impl ProviderRegistry {
+    pub fn requires_credential(provider_id: &str) -> bool {
+        CREDENTIAL_PROBES.iter().any(|(id, _)| *id == provider_id)
+    }
+
+    /// Checks an API key by fetching one quote from the provider with it.
+    /// The key is used as given and is not stored.
+    pub async fn test_provider_credential(
+        provider_id: &str,
+        api_key: String,
+    ) -> Result<(), MarketDataError> {
+        let symbol = CREDENTIAL_PROBES
+            .iter()
+            .find(|(id, _)| *id == provider_id)
+            .map(|(_, symbol)| *symbol)
+            .ok_or_else(|| {
+                MarketDataError::ProviderError(format!(
+                    "Provider {} does not use an API key",
+                    provider_id
+                ))
+            })?;
+
+        let provider: Arc<dyn MarketDataProvider + Send + Sync> = match provider_id {
+            DATA_SOURCE_MARKET_DATA_APP => Arc::new(MarketDataAppProvider::new(api_key).await?),
+            DATA_SOURCE_ALPHA_VANTAGE => Arc::new(AlphaVantageProvider::new(api_key)),
+            _ => Arc::new(MetalPriceApiProvider::new(api_key)),
+        };
+        provider
+            .get_latest_quote(symbol, "USD".to_string())
+            .await
+            .map(|_| ())
+    }
+
     /// Create a new provider registry without DB pool (VN gold cache disabled)
     pub async fn new(
         provider_settings: Vec<MarketDataProviderSetting>,
@@ -147,6 +187,11 @@ impl ProviderRegistry {
                         Some(p as Arc<dyn AssetProfiler + Send + Sync>),
                     )
                 }
+                DATA_SOURCE_VIETCOMBANK => {
+                    // Rate board only; FX pairs need no asset profile
+                    let p = Arc::new(VietcombankProvider::new());
+                    (Some(p as Arc<dyn MarketDataProvider + Send + Sync>), None)
+                }
                 _ => {
                     warn!("Unknown market data provider ID: {}. Skipping.", setting.id);
                     (None, None)
@@ -364,6 +409,7 @@ impl ProviderRegistry {
                 DataSource::MetalPriceApi => DATA_SOURCE_METAL_PRICE_API.to_string(),
                 DataSource::MarketDataApp => DATA_SOURCE_MARKET_DATA_APP.to_string(),
                 DataSource::VnMarket => DATA_SOURCE_VN_MARKET.to_string(),
+                DataSource::Vietcombank => DATA_SOURCE_VIETCOMBANK.to_string(),
                 DataSource::Manual => {
                     warn!("Manual data source requested for sync, skipping: {}", quote_request.symbol);
                     continue;
@@ -0,0 +1,217 @@
+use crate::market_data::market_data_constants::DATA_SOURCE_VIETCOMBANK;
+use crate::market_data::market_data_errors::MarketDataError;
+use crate::market_data::{market_data_model::DataSource, MarketDataProvider, Quote as ModelQuote};
+use crate::utils::number_format::{parse_localized_decimal, NumberLocale};
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use roxmltree::Document;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+/// Vietcombank's published rate board, refreshed through the trading day
+const RATE_BOARD_URL: &str =
+    "https://portal.vietcombank.com.vn/Usercontrols/TVPortal.TyGia/pXML.aspx";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+const VND: &str = "VND";
+
+/// Exchange rates against the dong from Vietcombank's counter board, for FX pairs such as
+/// `USDVND=X` and their inverses. The board only shows today's rates, so each sync adds a
+/// day; history before the first sync comes from another provider or manual rates.
+pub struct VietcombankProvider {
+    client: Client,
+}
+
+impl VietcombankProvider {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("WealthVN/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+        VietcombankProvider { client }
+    }
+
+    async fn fetch_board(&self) -> Result<HashMap<String, Decimal>, MarketDataError> {
+        let response = self.client.get(RATE_BOARD_URL).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(MarketDataError::ProviderError(format!(
+                "Vietcombank answered {}",
+                status
+            )));
+        }
+        parse_rate_board(&response.text().await?)
+    }
+}
+
+impl Default for VietcombankProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Dong per unit of each currency on the board. The transfer rate is used, as for money
+/// moving between accounts; currencies quoted for cash only fall back to the buying rate.
+pub(crate) fn parse_rate_board(xml: &str) -> Result<HashMap<String, Decimal>, MarketDataError> {
+    let document =
+        Document::parse(xml).map_err(|e| MarketDataError::ParsingError(e.to_string()))?;
+    let rate = |value: Option<&str>| {
+        value
+            .and_then(|value| parse_localized_decimal(value, NumberLocale::En).ok())
+            .flatten()
+            .filter(|rate| *rate > Decimal::ZERO)
+    };
+    let rates: HashMap<String, Decimal> = document
+        .descendants()
+        .filter(|node| node.is_element() && node.tag_name().name() == "Exrate")
+        .filter_map(|node| {
+            let code = node.attribute("CurrencyCode")?.trim().to_uppercase();
+            let rate = rate(node.attribute("Transfer")).or_else(|| rate(node.attribute("Buy")))?;
+            Some((code, rate))
+        })
+        .collect();
+    if rates.is_empty() {
+        return Err(MarketDataError::NoData);
+    }
+    Ok(rates)
+}
+
+/// Rate of an FX symbol from the board: `USDVND=X` directly, `VNDUSD=X` inverted
+fn rate_for_symbol(
+    board: &HashMap<String, Decimal>,
+    symbol: &str,
+) -> Result<(String, Decimal), MarketDataError> {
+    let pair = symbol.strip_suffix("=X").unwrap_or(symbol);
+    let not_found = || MarketDataError::NotFound(symbol.to_string());
+    if pair.len() != 6 {
+        return Err(not_found());
+    }
+    let (from, to) = pair.split_at(3);
+    match (from, to) {
+        (currency, VND) => board
+            .get(currency)
+            .map(|rate| (currency.to_string(), *rate))
+            .ok_or_else(not_found),
+        (VND, currency) => board
+            .get(currency)
+            .map(|rate| (VND.to_string(), Decimal::ONE / *rate))
+            .ok_or_else(not_found),
+        _ => Err(not_found()),
+    }
+}
+
+fn quote(symbol: &str, currency: String, rate: Decimal, at: DateTime<Utc>) -> ModelQuote {
+    ModelQuote {
+        id: format!("{}_{}", at.format("%Y%m%d"), symbol),
+        created_at: at,
+        data_source: DataSource::Vietcombank,
+        timestamp: at,
+        symbol: symbol.to_string(),
+        open: rate,
+        high: rate,
+        low: rate,
+        close: rate,
+        adjclose: rate,
+        volume: Decimal::ZERO,
+        currency,
+    }
+}
+
+/// Whether a requested range reaches today, the only day the board covers
+fn covers_today(end: SystemTime) -> bool {
+    DateTime::<Utc>::from(end).date_naive() >= Utc::now().date_naive()
+}
+
+#[async_trait::async_trait]
+impl MarketDataProvider for VietcombankProvider {
+    fn name(&self) -> &'static str {
+        DATA_SOURCE_VIETCOMBANK
+    }
+
+    fn priority(&self) -> u8 {
+        5
+    }
+
+    async fn get_latest_quote(
+        &self,
+        symbol: &str,
+        _fallback_currency: String,
+    ) -> Result<ModelQuote, MarketDataError> {
+        let board = self.fetch_board().await?;
+        let (currency, rate) = rate_for_symbol(&board, symbol)?;
+        Ok(quote(symbol, currency, rate, Utc::now()))
+    }
+
+    async fn get_historical_quotes(
+        &self,
+        symbol: &str,
+        _start: SystemTime,
+        end: SystemTime,
+        fallback_currency: String,
+    ) -> Result<Vec<ModelQuote>, MarketDataError> {
+        if !covers_today(end) {
+            return Ok(vec![]);
+        }
+        Ok(vec![
+            self.get_latest_quote(symbol, fallback_currency).await?,
+        ])
+    }
+
+    async fn get_historical_quotes_bulk(
+        &self,
+        symbols_with_currencies: &[(String, String)],
+        _start: SystemTime,
+        end: SystemTime,
+    ) -> Result<(Vec<ModelQuote>, Vec<(String, String)>), MarketDataError> {
+        if !covers_today(end) {
+            return Ok((vec![], vec![]));
+        }
+        let board = self.fetch_board().await?;
+        let now = Utc::now();
+        let mut quotes = Vec::new();
+        let mut failed = Vec::new();
+        for (symbol, currency) in symbols_with_currencies {
+            match rate_for_symbol(&board, symbol) {
+                Ok((quote_currency, rate)) => quotes.push(quote(symbol, quote_currency, rate, now)),
+                Err(_) => failed.push((symbol.clone(), currency.clone())),
+            }
+        }
+        Ok((quotes, failed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn reads_transfer_rates_and_inverts_dong_pairs() {
+        let board = parse_rate_board(
+            r#"<ExrateList>
+                <DateTime>10/16/2026 8:30:00 AM</DateTime>
+                <Exrate CurrencyCode="USD " CurrencyName="US DOLLAR" Buy="25,120.00" Transfer="25,150.00" Sell="25,490.00" />
+                <Exrate CurrencyCode="KWD" CurrencyName="KUWAITI DINAR" Buy="-" Transfer="82,100.50" Sell="85,300.00" />
+                <Exrate CurrencyCode="THB" CurrencyName="THAI BAHT" Buy="680.00" Transfer="-" Sell="790.00" />
+                <Source>Joint Stock Commercial Bank for Foreign Trade of Vietnam - Vietcombank</Source>
+            </ExrateList>"#,
+        )
+        .unwrap();
+
+        assert_eq!(rate_for_symbol(&board, "USDVND=X").unwrap().1, dec!(25_150));
+        assert_eq!(
+            rate_for_symbol(&board, "KWDVND").unwrap().1,
+            dec!(82_100.50)
+        );
+        // Cash-only currencies fall back to the buying rate
+        assert_eq!(rate_for_symbol(&board, "THBVND=X").unwrap().1, dec!(680));
+        let (currency, inverse) = rate_for_symbol(&board, "VNDUSD=X").unwrap();
+        assert_eq!(currency, "VND");
+        assert_eq!((inverse * dec!(25_150)).round_dp(6), dec!(1));
+
+        assert!(rate_for_symbol(&board, "EURVND=X").is_err());
+        assert!(rate_for_symbol(&board, "USDEUR=X").is_err());
+        assert!(parse_rate_board("<ExrateList></ExrateList>").is_err());
+    }
+}
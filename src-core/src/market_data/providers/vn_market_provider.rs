@@ -12,7 +12,7 @@ use crate::market_data::market_data_errors::MarketDataError;
 use crate::market_data::{
     market_data_model::{DataSource, Quote},
     AssetProfiler, MarketDataProvider,
-    providers::models::AssetProfile,
+    providers::models::{AssetClass, AssetProfile, AssetSubClass},
     QuoteSummary,
 };
 use crate::vn_market::{
@@ -212,6 +212,15 @@ impl AssetProfiler for VnMarketProvider {
             .or_else(|| search_results.first().cloned())
             .ok_or_else(|| MarketDataError::NotFound(symbol.to_string()))?;
 
+        // Gold bars and rings sit with other precious metals in the allocation
+        let (asset_class, asset_sub_class) = match asset.asset_type {
+            VnAssetType::Gold => (
+                AssetClass::Commodity.to_string(),
+                AssetSubClass::PreciousMetal.to_string(),
+            ),
+            _ => (asset_type_to_string(&asset.asset_type), asset.exchange.clone()),
+        };
+
         Ok(AssetProfile {
             id: Some(asset.symbol.clone()),
             isin: None,
@@ -219,8 +228,8 @@ impl AssetProfiler for VnMarketProvider {
             asset_type: Some(asset_type_to_string(&asset.asset_type)),
             symbol: asset.symbol,
             symbol_mapping: None,
-            asset_class: Some(asset_type_to_string(&asset.asset_type)),
-            asset_sub_class: Some(asset.exchange.clone()),
+            asset_class: Some(asset_class),
+            asset_sub_class: Some(asset_sub_class),
             notes: None,
             countries: Some("Vietnam".to_string()),
             categories: None,
@@ -0,0 +1,8 @@
+mod notifications_dispatcher;
+mod notifications_model;
+
+pub use notifications_dispatcher::{NotificationDispatcher, NotificationSink};
+pub use notifications_model::{
+    ChannelThrottle, DispatchOutcome, Notification, NotificationChannel, NotificationDigest,
+    NotificationSettings, QuietHours, DEFAULT_UTC_OFFSET_MINUTES,
+};
@@ -0,0 +1,239 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+
+use super::notifications_model::{
+    DispatchOutcome, Notification, NotificationChannel, NotificationDigest, NotificationSettings,
+};
+use crate::errors::Result;
+
+/// Destination for notifications (desktop toast, web push, ...)
+pub trait NotificationSink: Send + Sync {
+    fn send(&self, notification: &Notification) -> Result<()>;
+    fn send_digest(&self, digest: &NotificationDigest) -> Result<()>;
+}
+
+#[derive(Default)]
+struct DispatcherState {
+    /// Delivery timestamps per channel, oldest first, pruned to the throttle window
+    recent: HashMap<NotificationChannel, VecDeque<DateTime<Utc>>>,
+    /// Notifications waiting for the next digest
+    pending: Vec<Notification>,
+}
+
+/// Dispatches notifications while enforcing per-channel throttling and quiet hours.
+/// Anything held back is queued and folded into the next digest.
+pub struct NotificationDispatcher {
+    settings: RwLock<NotificationSettings>,
+    sink: Arc<dyn NotificationSink>,
+    state: Mutex<DispatcherState>,
+}
+
+impl NotificationDispatcher {
+    pub fn new(settings: NotificationSettings, sink: Arc<dyn NotificationSink>) -> Self {
+        Self {
+            settings: RwLock::new(settings),
+            sink,
+            state: Mutex::new(DispatcherState::default()),
+        }
+    }
+
+    pub fn settings(&self) -> NotificationSettings {
+        self.settings.read().unwrap().clone()
+    }
+
+    pub fn update_settings(&self, settings: NotificationSettings) {
+        *self.settings.write().unwrap() = settings;
+    }
+
+    /// Number of notifications waiting for the next digest
+    pub fn pending_count(&self) -> usize {
+        self.state.lock().unwrap().pending.len()
+    }
+
+    /// Delivers the notification immediately when allowed, otherwise queues it for the next digest.
+    /// `now` is passed explicitly so callers (and tests) control the clock.
+    pub fn dispatch(&self, notification: Notification, now: DateTime<Utc>) -> Result<DispatchOutcome> {
+        let settings = self.settings();
+        let mut state = self.state.lock().unwrap();
+
+        if settings.is_quiet_at(now) {
+            log::debug!(
+                "Quiet hours active, queueing {:?} notification {}",
+                notification.channel,
+                notification.id
+            );
+            state.pending.push(notification);
+            return Ok(DispatchOutcome::QueuedQuietHours);
+        }
+
+        if let Some(throttle) = settings.throttles.get(&notification.channel) {
+            let window_start = now - Duration::minutes(throttle.window_minutes as i64);
+            let recent = state.recent.entry(notification.channel).or_default();
+            while recent.front().is_some_and(|t| *t <= window_start) {
+                recent.pop_front();
+            }
+            if recent.len() >= throttle.max_per_window as usize {
+                log::debug!(
+                    "Channel {:?} throttled ({} in {} min), queueing notification {}",
+                    notification.channel,
+                    recent.len(),
+                    throttle.window_minutes,
+                    notification.id
+                );
+                state.pending.push(notification);
+                return Ok(DispatchOutcome::QueuedThrottled);
+            }
+        }
+
+        self.sink.send(&notification)?;
+        state
+            .recent
+            .entry(notification.channel)
+            .or_default()
+            .push_back(now);
+        Ok(DispatchOutcome::Delivered)
+    }
+
+    /// Sends queued notifications as a single digest once quiet hours are over.
+    /// Returns the digest that was sent, or None when nothing was due.
+    pub fn flush_digest(&self, now: DateTime<Utc>) -> Result<Option<NotificationDigest>> {
+        if self.settings().is_quiet_at(now) {
+            return Ok(None);
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.pending.is_empty() {
+            return Ok(None);
+        }
+
+        let notifications = std::mem::take(&mut state.pending);
+        let mut counts_by_channel = HashMap::new();
+        for notification in &notifications {
+            *counts_by_channel.entry(notification.channel).or_insert(0) += 1;
+        }
+        let digest = NotificationDigest {
+            generated_at: now,
+            counts_by_channel,
+            notifications,
+        };
+
+        if let Err(e) = self.sink.send_digest(&digest) {
+            // Put everything back so the next flush retries
+            state.pending = digest.notifications;
+            return Err(e);
+        }
+        Ok(Some(digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::notifications_model::{ChannelThrottle, QuietHours};
+    use chrono::{NaiveTime, TimeZone};
+
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: Mutex<Vec<String>>,
+        digests: Mutex<Vec<usize>>,
+    }
+
+    impl NotificationSink for RecordingSink {
+        fn send(&self, notification: &Notification) -> Result<()> {
+            self.sent.lock().unwrap().push(notification.id.clone());
+            Ok(())
+        }
+
+        fn send_digest(&self, digest: &NotificationDigest) -> Result<()> {
+            self.digests.lock().unwrap().push(digest.notifications.len());
+            Ok(())
+        }
+    }
+
+    fn price_alert(id: &str, at: DateTime<Utc>) -> Notification {
+        Notification {
+            id: id.to_string(),
+            channel: NotificationChannel::PriceAlert,
+            title: "VNM".to_string(),
+            body: "Price moved".to_string(),
+            created_at: at,
+        }
+    }
+
+    fn settings() -> NotificationSettings {
+        let mut throttles = HashMap::new();
+        throttles.insert(
+            NotificationChannel::PriceAlert,
+            ChannelThrottle {
+                max_per_window: 2,
+                window_minutes: 60,
+            },
+        );
+        NotificationSettings {
+            quiet_hours: Some(QuietHours {
+                start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+            }),
+            utc_offset_minutes: 7 * 60,
+            throttles,
+        }
+    }
+
+    #[test]
+    fn test_quiet_hours_wrap_midnight() {
+        let quiet = settings().quiet_hours.unwrap();
+        assert!(quiet.contains(NaiveTime::from_hms_opt(23, 30, 0).unwrap()));
+        assert!(quiet.contains(NaiveTime::from_hms_opt(2, 0, 0).unwrap()));
+        assert!(!quiet.contains(NaiveTime::from_hms_opt(7, 0, 0).unwrap()));
+        assert!(!quiet.contains(NaiveTime::from_hms_opt(12, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_throttled_notifications_fold_into_digest() {
+        let sink = Arc::new(RecordingSink::default());
+        let dispatcher = NotificationDispatcher::new(settings(), sink.clone());
+        // 03:00 UTC = 10:00 in Vietnam
+        let now = Utc.with_ymd_and_hms(2025, 3, 10, 3, 0, 0).unwrap();
+
+        assert_eq!(dispatcher.dispatch(price_alert("a", now), now).unwrap(), DispatchOutcome::Delivered);
+        assert_eq!(dispatcher.dispatch(price_alert("b", now), now).unwrap(), DispatchOutcome::Delivered);
+        assert_eq!(
+            dispatcher.dispatch(price_alert("c", now), now).unwrap(),
+            DispatchOutcome::QueuedThrottled
+        );
+
+        // Window has rolled over, channel is open again
+        let later = now + Duration::minutes(61);
+        assert_eq!(dispatcher.dispatch(price_alert("d", later), later).unwrap(), DispatchOutcome::Delivered);
+
+        let digest = dispatcher.flush_digest(later).unwrap().unwrap();
+        assert_eq!(digest.notifications.len(), 1);
+        assert_eq!(digest.counts_by_channel[&NotificationChannel::PriceAlert], 1);
+        assert_eq!(*sink.sent.lock().unwrap(), vec!["a", "b", "d"]);
+        assert_eq!(dispatcher.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_quiet_hours_hold_until_morning() {
+        let sink = Arc::new(RecordingSink::default());
+        let dispatcher = NotificationDispatcher::new(settings(), sink.clone());
+        // 19:00 UTC = 02:00 in Vietnam
+        let night = Utc.with_ymd_and_hms(2025, 3, 10, 19, 0, 0).unwrap();
+
+        for i in 0..50 {
+            let outcome = dispatcher
+                .dispatch(price_alert(&i.to_string(), night), night)
+                .unwrap();
+            assert_eq!(outcome, DispatchOutcome::QueuedQuietHours);
+        }
+        assert!(dispatcher.flush_digest(night).unwrap().is_none());
+        assert!(sink.sent.lock().unwrap().is_empty());
+
+        // 01:00 UTC = 08:00 in Vietnam
+        let morning = Utc.with_ymd_and_hms(2025, 3, 11, 1, 0, 0).unwrap();
+        let digest = dispatcher.flush_digest(morning).unwrap().unwrap();
+        assert_eq!(digest.notifications.len(), 50);
+        assert_eq!(*sink.digests.lock().unwrap(), vec![50]);
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, FixedOffset, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Vietnam local time offset (UTC+7), used when no offset is configured.
+pub const DEFAULT_UTC_OFFSET_MINUTES: i32 = 7 * 60;
+
+/// Logical channel a notification is sent on. Throttling is tracked per channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NotificationChannel {
+    PriceAlert,
+    GoalProgress,
+    BillReminder,
+    /// A maturing term deposit would earn more rolled over at another bank
+    DepositRate,
+    /// Shares of an ESOP grant vest soon
+    Vesting,
+    System,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub id: String,
+    pub channel: NotificationChannel,
+    pub title: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Window of local time during which notifications are held back.
+/// The window may wrap past midnight (e.g. 22:00 - 07:00).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Returns true when `time` falls inside the quiet window (start inclusive, end exclusive)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Rate limit for a single channel: at most `max_per_window` deliveries per `window_minutes`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelThrottle {
+    pub max_per_window: u32,
+    pub window_minutes: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationSettings {
+    pub quiet_hours: Option<QuietHours>,
+    /// Offset from UTC used to evaluate quiet hours
+    pub utc_offset_minutes: i32,
+    #[serde(default)]
+    pub throttles: HashMap<NotificationChannel, ChannelThrottle>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        let mut throttles = HashMap::new();
+        throttles.insert(
+            NotificationChannel::PriceAlert,
+            ChannelThrottle {
+                max_per_window: 5,
+                window_minutes: 60,
+            },
+        );
+        Self {
+            quiet_hours: Some(QuietHours {
+                start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+                end: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+            }),
+            utc_offset_minutes: DEFAULT_UTC_OFFSET_MINUTES,
+            throttles,
+        }
+    }
+}
+
+impl NotificationSettings {
+    /// Returns true when `at` falls inside the configured quiet hours
+    pub fn is_quiet_at(&self, at: DateTime<Utc>) -> bool {
+        let Some(quiet_hours) = &self.quiet_hours else {
+            return false;
+        };
+        let offset = FixedOffset::east_opt(self.utc_offset_minutes * 60)
+            .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+        quiet_hours.contains(at.with_timezone(&offset).time())
+    }
+}
+
+/// What the dispatcher did with a notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DispatchOutcome {
+    Delivered,
+    QueuedQuietHours,
+    QueuedThrottled,
+}
+
+/// Notifications held back by quiet hours or throttling, folded into a single message
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationDigest {
+    pub generated_at: DateTime<Utc>,
+    pub counts_by_channel: HashMap<NotificationChannel, usize>,
+    pub notifications: Vec<Notification>,
+}
@@ -0,0 +1,11 @@
+mod onboarding_model;
+mod onboarding_repository;
+mod onboarding_service;
+mod onboarding_traits;
+
+pub use onboarding_model::{
+    OnboardingAccount, OnboardingGoal, OnboardingPlan, OnboardingResult, OnboardingSeed,
+};
+pub use onboarding_repository::OnboardingRepository;
+pub use onboarding_service::OnboardingService;
+pub use onboarding_traits::{OnboardingRepositoryTrait, OnboardingServiceTrait};
@@ -0,0 +1,64 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::accounts::{Account, NewAccount};
+use crate::activities::{Activity, NewActivity};
+use crate::assets::NewAsset;
+use crate::goals::goals_model::{Goal, NewGoal};
+
+/// Onboarding payload submitted once by the first-run wizard
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OnboardingPlan {
+    /// Falls back to the currently configured base currency when omitted
+    pub base_currency: Option<String>,
+    pub accounts: Vec<OnboardingAccount>,
+    pub first_goal: Option<OnboardingGoal>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingAccount {
+    pub name: String,
+    /// Defaults to `SECURITIES`
+    pub account_type: Option<String>,
+    /// Defaults to the plan's base currency
+    pub currency: Option<String>,
+    pub group: Option<String>,
+    /// Recorded as a deposit into the account's cash balance
+    pub starting_balance: Option<Decimal>,
+    /// Defaults to today
+    pub starting_balance_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingGoal {
+    pub title: String,
+    pub description: Option<String>,
+    pub target_amount: f64,
+    pub target_return_rate: Option<f64>,
+    pub due_date: Option<String>,
+    pub monthly_investment: Option<f64>,
+}
+
+/// Everything created by a successful seed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingResult {
+    pub base_currency: String,
+    pub accounts: Vec<Account>,
+    pub activities: Vec<Activity>,
+    pub goal: Option<Goal>,
+}
+
+/// Fully resolved plan with ids and defaults filled in, ready to be written in one transaction
+#[derive(Debug, Clone)]
+pub struct OnboardingSeed {
+    pub base_currency: String,
+    pub cash_assets: Vec<NewAsset>,
+    pub accounts: Vec<NewAccount>,
+    pub deposits: Vec<NewActivity>,
+    pub goal: Option<NewGoal>,
+}
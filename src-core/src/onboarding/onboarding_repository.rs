@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use super::onboarding_model::{OnboardingResult, OnboardingSeed};
+use super::onboarding_traits::OnboardingRepositoryTrait;
+use crate::accounts::AccountDB;
+use crate::activities::{Activity, ActivityDB};
+use crate::assets::assets_model::AssetDB;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::goals::goals_model::Goal;
+use crate::schema::{accounts, activities, app_settings, assets, goals};
+use crate::settings::AppSetting;
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+pub struct OnboardingRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl OnboardingRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        OnboardingRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl OnboardingRepositoryTrait for OnboardingRepository {
+    fn has_accounts(&self) -> Result<bool> {
+        let mut conn = get_connection(&self.pool)?;
+        let count: i64 = accounts::table.count().get_result(&mut conn)?;
+        Ok(count > 0)
+    }
+
+    async fn seed(&self, seed: OnboardingSeed) -> Result<OnboardingResult> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<OnboardingResult> {
+                    let existing_accounts: i64 = accounts::table.count().get_result(conn)?;
+                    if existing_accounts > 0 {
+                        return Err(LocalizedMessage::new(MessageCode::ProfileNotEmpty).into());
+                    }
+
+                    for new_asset in seed.cash_assets {
+                        let asset_db: AssetDB = new_asset.into();
+                        diesel::insert_or_ignore_into(assets::table)
+                            .values(&asset_db)
+                            .execute(conn)?;
+                    }
+
+                    let mut created_accounts = Vec::with_capacity(seed.accounts.len());
+                    for new_account in seed.accounts {
+                        let account_db: AccountDB = new_account.into();
+                        diesel::insert_into(accounts::table)
+                            .values(&account_db)
+                            .execute(conn)?;
+                        created_accounts.push(account_db.into());
+                    }
+
+                    let mut created_activities = Vec::with_capacity(seed.deposits.len());
+                    for deposit in seed.deposits {
+                        let activity_db: ActivityDB = deposit.into();
+                        let inserted = diesel::insert_into(activities::table)
+                            .values(&activity_db)
+                            .get_result::<ActivityDB>(conn)?;
+                        created_activities.push(Activity::from(inserted));
+                    }
+
+                    let goal = match seed.goal {
+                        Some(new_goal) => Some(
+                            diesel::insert_into(goals::table)
+                                .values(&new_goal)
+                                .returning(goals::all_columns)
+                                .get_result::<Goal>(conn)?,
+                        ),
+                        None => None,
+                    };
+
+                    for (key, value) in [
+                        ("base_currency", seed.base_currency.clone()),
+                        ("onboarding_completed", true.to_string()),
+                    ] {
+                        diesel::replace_into(app_settings::table)
+                            .values(&AppSetting {
+                                setting_key: key.to_string(),
+                                setting_value: value,
+                            })
+                            .execute(conn)?;
+                    }
+
+                    Ok(OnboardingResult {
+                        base_currency: seed.base_currency,
+                        accounts: created_accounts,
+                        activities: created_activities,
+                        goal,
+                    })
+                },
+            )
+            .await
+    }
+}
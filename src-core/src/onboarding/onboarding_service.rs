@@ -0,0 +1,290 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use log::warn;
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use super::onboarding_model::{OnboardingPlan, OnboardingResult, OnboardingSeed};
+use super::onboarding_traits::{OnboardingRepositoryTrait, OnboardingServiceTrait};
+use crate::accounts::{NewAccount, DEFAULT_ACCOUNT_TYPE};
+use crate::activities::{NewActivity, ACTIVITY_TYPE_DEPOSIT};
+use crate::assets::NewAsset;
+use crate::errors::{Error, Result, ValidationError};
+use crate::fx::FxServiceTrait;
+use crate::goals::goals_model::{NewGoal, GOAL_TYPE_STANDARD};
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+const STARTING_BALANCE_COMMENT: &str = "Starting balance";
+
+pub struct OnboardingService {
+    repository: Arc<dyn OnboardingRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl OnboardingService {
+    pub fn new(
+        repository: Arc<dyn OnboardingRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            fx_service,
+            base_currency,
+        }
+    }
+}
+
+/// Validates the plan and fills in ids and defaults
+fn resolve_plan(
+    plan: OnboardingPlan,
+    current_base_currency: &str,
+    today: NaiveDate,
+) -> Result<OnboardingSeed> {
+    let base_currency = plan
+        .base_currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| current_base_currency.to_string());
+
+    if plan.accounts.is_empty() {
+        return Err(invalid("At least one account is required"));
+    }
+
+    let mut cash_currencies = HashSet::new();
+    let mut accounts = Vec::with_capacity(plan.accounts.len());
+    let mut deposits = Vec::new();
+
+    for (index, account) in plan.accounts.into_iter().enumerate() {
+        let name = account.name.trim().to_string();
+        if name.is_empty() {
+            return Err(invalid("Account name cannot be empty"));
+        }
+        let currency = account
+            .currency
+            .map(|c| c.trim().to_uppercase())
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| base_currency.clone());
+
+        let new_account = NewAccount {
+            id: Some(Uuid::new_v4().to_string()),
+            name,
+            account_type: account
+                .account_type
+                .filter(|t| !t.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_ACCOUNT_TYPE.to_string()),
+            group: account.group,
+            currency: currency.clone(),
+            is_default: index == 0,
+            is_active: true,
+            platform_id: None,
+        };
+        new_account.validate()?;
+
+        match account.starting_balance {
+            Some(balance) if balance < Decimal::ZERO => {
+                return Err(invalid(&format!(
+                    "Starting balance for '{}' cannot be negative",
+                    new_account.name
+                )));
+            }
+            Some(balance) if balance > Decimal::ZERO => {
+                let balance_date = account.starting_balance_date.unwrap_or(today);
+                if balance_date > today {
+                    return Err(invalid(&format!(
+                        "Starting balance date for '{}' cannot be in the future",
+                        new_account.name
+                    )));
+                }
+                let deposit = NewActivity {
+                    id: Some(Uuid::new_v4().to_string()),
+                    account_id: new_account.id.clone().unwrap_or_default(),
+                    asset_id: format!("$CASH-{}", currency),
+                    activity_type: ACTIVITY_TYPE_DEPOSIT.to_string(),
+                    activity_date: balance_date.format("%Y-%m-%d").to_string(),
+                    quantity: None,
+                    unit_price: None,
+                    currency: currency.clone(),
+                    fee: None,
+                    amount: Some(balance),
+                    is_draft: false,
+                    comment: Some(STARTING_BALANCE_COMMENT.to_string()),
+                };
+                deposit.validate().map_err(|e| invalid(&e.to_string()))?;
+                cash_currencies.insert(currency);
+                deposits.push(deposit);
+            }
+            _ => {}
+        }
+
+        accounts.push(new_account);
+    }
+
+    let goal = match plan.first_goal {
+        Some(goal) => {
+            if goal.title.trim().is_empty() {
+                return Err(invalid("Goal title cannot be empty"));
+            }
+            if goal.target_amount <= 0.0 {
+                return Err(invalid("Goal target amount must be greater than zero"));
+            }
+            Some(NewGoal {
+                id: Some(Uuid::new_v4().to_string()),
+                title: goal.title.trim().to_string(),
+                description: goal.description,
+                target_amount: goal.target_amount,
+                is_achieved: false,
+                target_return_rate: goal.target_return_rate,
+                due_date: goal.due_date,
+                monthly_investment: goal.monthly_investment,
+                start_date: Some(today.format("%Y-%m-%d").to_string()),
+                initial_actual_value: None,
+                goal_type: GOAL_TYPE_STANDARD.to_string(),
+                target_months: None,
+                priority: 0,
+                depends_on_goal_id: None,
+                currency: None,
+            })
+        }
+        None => None,
+    };
+
+    let mut cash_currencies: Vec<String> = cash_currencies.into_iter().collect();
+    cash_currencies.sort();
+
+    Ok(OnboardingSeed {
+        base_currency,
+        cash_assets: cash_currencies
+            .iter()
+            .map(|currency| NewAsset::new_cash_asset(currency))
+            .collect(),
+        accounts,
+        deposits,
+        goal,
+    })
+}
+
+fn invalid(message: &str) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message.to_string()))
+}
+
+#[async_trait]
+impl OnboardingServiceTrait for OnboardingService {
+    async fn seed_initial_data(&self, plan: OnboardingPlan) -> Result<OnboardingResult> {
+        if self.repository.has_accounts()? {
+            return Err(LocalizedMessage::new(MessageCode::ProfileNotEmpty).into());
+        }
+
+        let current_base_currency = self.base_currency.read().unwrap().clone();
+        let seed = resolve_plan(
+            plan,
+            &current_base_currency,
+            chrono::Local::now().date_naive(),
+        )?;
+
+        let foreign_currencies: HashSet<&str> = seed
+            .accounts
+            .iter()
+            .map(|account| account.currency.as_str())
+            .filter(|currency| *currency != seed.base_currency)
+            .collect();
+        for currency in foreign_currencies {
+            self.fx_service
+                .register_currency_pair(currency, &seed.base_currency)
+                .await?;
+        }
+
+        let result = self.repository.seed(seed).await?;
+
+        match self.base_currency.write() {
+            Ok(mut base_currency) => *base_currency = result.base_currency.clone(),
+            Err(e) => warn!("Failed to update cached base currency: {}", e),
+        }
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::onboarding::{OnboardingAccount, OnboardingGoal};
+    use rust_decimal_macros::dec;
+
+    fn account(name: &str, currency: Option<&str>, balance: Option<Decimal>) -> OnboardingAccount {
+        OnboardingAccount {
+            name: name.to_string(),
+            account_type: None,
+            currency: currency.map(str::to_string),
+            group: None,
+            starting_balance: balance,
+            starting_balance_date: None,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()
+    }
+
+    #[test]
+    fn resolve_plan_fills_defaults_and_links_deposits() {
+        let plan = OnboardingPlan {
+            base_currency: None,
+            accounts: vec![
+                account("Brokerage", None, Some(dec!(50000000))),
+                account("USD Savings", Some("usd"), Some(dec!(0))),
+            ],
+            first_goal: Some(OnboardingGoal {
+                title: "Emergency fund".to_string(),
+                description: None,
+                target_amount: 100_000_000.0,
+                target_return_rate: None,
+                due_date: None,
+                monthly_investment: None,
+            }),
+        };
+
+        let seed = resolve_plan(plan, "VND", today()).unwrap();
+
+        assert_eq!(seed.base_currency, "VND");
+        assert_eq!(seed.accounts.len(), 2);
+        assert!(seed.accounts[0].is_default);
+        assert!(!seed.accounts[1].is_default);
+        assert_eq!(seed.accounts[0].account_type, DEFAULT_ACCOUNT_TYPE);
+        assert_eq!(seed.accounts[1].currency, "USD");
+
+        // Zero balances don't produce a deposit
+        assert_eq!(seed.deposits.len(), 1);
+        let deposit = &seed.deposits[0];
+        assert_eq!(Some(deposit.account_id.clone()), seed.accounts[0].id);
+        assert_eq!(deposit.asset_id, "$CASH-VND");
+        assert_eq!(deposit.activity_date, "2026-10-17");
+        assert_eq!(seed.cash_assets.len(), 1);
+
+        let goal = seed.goal.unwrap();
+        assert_eq!(goal.start_date.as_deref(), Some("2026-10-17"));
+    }
+
+    #[test]
+    fn resolve_plan_rejects_invalid_plans() {
+        let empty = OnboardingPlan::default();
+        assert!(resolve_plan(empty, "VND", today()).is_err());
+
+        let negative = OnboardingPlan {
+            accounts: vec![account("Brokerage", None, Some(dec!(-1)))],
+            ..Default::default()
+        };
+        assert!(resolve_plan(negative, "VND", today()).is_err());
+
+        let mut future = account("Brokerage", None, Some(dec!(10)));
+        future.starting_balance_date = NaiveDate::from_ymd_opt(2026, 10, 18);
+        let future = OnboardingPlan {
+            accounts: vec![future],
+            ..Default::default()
+        };
+        assert!(resolve_plan(future, "VND", today()).is_err());
+    }
+}
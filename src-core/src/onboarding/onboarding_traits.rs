@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use super::onboarding_model::{OnboardingPlan, OnboardingResult, OnboardingSeed};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait OnboardingRepositoryTrait: Send + Sync {
+    fn has_accounts(&self) -> Result<bool>;
+    /// Writes the whole seed in a single transaction; nothing is persisted on failure
+    async fn seed(&self, seed: OnboardingSeed) -> Result<OnboardingResult>;
+}
+
+#[async_trait]
+pub trait OnboardingServiceTrait: Send + Sync {
+    async fn seed_initial_data(&self, plan: OnboardingPlan) -> Result<OnboardingResult>;
+}
@@ -0,0 +1,8 @@
+mod operations_model;
+mod operations_service;
+
+pub use operations_model::{
+    CancellationToken, OperationInfo, OPERATION_EXPORT, OPERATION_LEDGER_EXPORT,
+    OPERATION_MARKET_DATA_RESYNC, OPERATION_QUOTE_IMPORT,
+};
+pub use operations_service::{OperationGuard, OperationRegistry};
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+use crate::errors::Result;
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+/// Kinds of long-running operation that can be cancelled
+pub const OPERATION_EXPORT: &str = "export";
+pub const OPERATION_LEDGER_EXPORT: &str = "ledger-export";
+pub const OPERATION_QUOTE_IMPORT: &str = "quote-import";
+/// Re-fetching the whole quote history of symbols from the providers
+pub const OPERATION_MARKET_DATA_RESYNC: &str = "market-data-resync";
+
+/// A running operation as listed to clients
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationInfo {
+    pub id: String,
+    pub kind: String,
+    pub started_at: DateTime<Utc>,
+    pub timeout_secs: Option<u64>,
+}
+
+struct TokenState {
+    operation_id: Option<String>,
+    cancelled: AtomicBool,
+    notify: Notify,
+    timeout: Option<Duration>,
+    started: Instant,
+}
+
+/// Cooperative cancellation for a long operation: blocking loops call `check` between chunks,
+/// async ones run under `run`. A token with a timeout counts as cancelled once it runs out.
+#[derive(Clone)]
+pub struct CancellationToken {
+    state: Arc<TokenState>,
+}
+
+impl Default for CancellationToken {
+    /// Never cancelled, for callers (e.g. the CLI) that have nothing to cancel from
+    fn default() -> Self {
+        Self::with_state(None, None)
+    }
+}
+
+impl CancellationToken {
+    pub fn new(operation_id: impl Into<String>, timeout: Option<Duration>) -> Self {
+        Self::with_state(Some(operation_id.into()), timeout)
+    }
+
+    fn with_state(operation_id: Option<String>, timeout: Option<Duration>) -> Self {
+        Self {
+            state: Arc::new(TokenState {
+                operation_id,
+                cancelled: AtomicBool::new(false),
+                notify: Notify::new(),
+                timeout,
+                started: Instant::now(),
+            }),
+        }
+    }
+
+    /// `None` for the default, never-cancelled token
+    pub fn operation_id(&self) -> Option<&str> {
+        self.state.operation_id.as_deref()
+    }
+
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+        self.state.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire) || self.timed_out()
+    }
+
+    fn timed_out(&self) -> bool {
+        self.state
+            .timeout
+            .is_some_and(|timeout| self.state.started.elapsed() >= timeout)
+    }
+
+    /// The error an operation stops with once cancelled or out of time
+    fn stopped(&self) -> crate::errors::Error {
+        let operation = self.state.operation_id.clone().unwrap_or_default();
+        match self.state.timeout {
+            Some(timeout) if !self.state.cancelled.load(Ordering::Acquire) => {
+                LocalizedMessage::new(MessageCode::OperationTimedOut)
+                    .with_param("operation", operation)
+                    .with_param("seconds", timeout.as_secs().to_string())
+                    .into()
+            }
+            _ => LocalizedMessage::new(MessageCode::OperationCancelled)
+                .with_param("operation", operation)
+                .into(),
+        }
+    }
+
+    /// Fails once the operation has been cancelled or has run out of time
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(self.stopped());
+        }
+        Ok(())
+    }
+
+    /// Runs `future` until it completes, the token is cancelled or its time runs out; the
+    /// future is dropped at its next await point in the latter two cases
+    pub async fn run<T>(&self, future: impl Future<Output = Result<T>>) -> Result<T> {
+        self.check()?;
+        let remaining = self
+            .state
+            .timeout
+            .map(|timeout| timeout.saturating_sub(self.state.started.elapsed()));
+        let deadline = async {
+            match remaining {
+                Some(remaining) => tokio::time::sleep(remaining).await,
+                None => std::future::pending().await,
+            }
+        };
+        let cancelled = async {
+            loop {
+                let notified = self.state.notify.notified();
+                if self.state.cancelled.load(Ordering::Acquire) {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        tokio::select! {
+            result = future => result,
+            _ = cancelled => Err(self.stopped()),
+            _ = deadline => Err(self.stopped()),
+        }
+    }
+}
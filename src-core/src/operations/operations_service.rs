@@ -0,0 +1,155 @@
+use chrono::Utc;
+use log::debug;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use super::operations_model::{CancellationToken, OperationInfo};
+use crate::errors::Result;
+use crate::i18n::{LocalizedMessage, MessageCode};
+
+/// Long operations currently running, by id, so a client can list and cancel them
+#[derive(Default)]
+pub struct OperationRegistry {
+    running: RwLock<HashMap<String, (OperationInfo, CancellationToken)>>,
+}
+
+/// Keeps an operation listed until dropped; hands out its cancellation token
+pub struct OperationGuard {
+    registry: Arc<OperationRegistry>,
+    id: String,
+    token: CancellationToken,
+}
+
+impl OperationGuard {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        self.registry.running.write().unwrap().remove(&self.id);
+    }
+}
+
+impl OperationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an operation of `kind` under `id` (the client's, so it can cancel before the
+    /// call returns, or a new one). Fails while another operation runs under the same id.
+    pub fn start(
+        self: &Arc<Self>,
+        id: Option<String>,
+        kind: &str,
+        timeout: Option<Duration>,
+    ) -> Result<OperationGuard> {
+        let id = id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let mut running = self.running.write().unwrap();
+        if running.contains_key(&id) {
+            return Err(LocalizedMessage::new(MessageCode::OperationAlreadyRunning)
+                .with_param("operation", id)
+                .into());
+        }
+        let token = CancellationToken::new(id.clone(), timeout);
+        let info = OperationInfo {
+            id: id.clone(),
+            kind: kind.to_string(),
+            started_at: Utc::now(),
+            timeout_secs: timeout.map(|timeout| timeout.as_secs()),
+        };
+        debug!("Operation {} ({}) started", id, kind);
+        running.insert(id.clone(), (info, token.clone()));
+        Ok(OperationGuard {
+            registry: Arc::clone(self),
+            id,
+            token,
+        })
+    }
+
+    /// Asks the operation to stop; false when no operation runs under `id`
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.running.read().unwrap().get(id) {
+            Some((_, token)) => {
+                debug!("Cancelling operation {}", id);
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Oldest first
+    pub fn list(&self) -> Vec<OperationInfo> {
+        let mut operations: Vec<OperationInfo> = self
+            .running
+            .read()
+            .unwrap()
+            .values()
+            .map(|(info, _)| info.clone())
+            .collect();
+        operations.sort_by_key(|info| info.started_at);
+        operations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::Error;
+    use crate::operations::OPERATION_EXPORT;
+
+    fn message_code(error: Error) -> MessageCode {
+        error.localized_message().expect("a localized error").code
+    }
+
+    #[tokio::test]
+    async fn cancelled_operations_stop_at_their_next_check() {
+        let registry = Arc::new(OperationRegistry::new());
+        let guard = registry
+            .start(Some("export-1".to_string()), OPERATION_EXPORT, None)
+            .unwrap();
+        assert!(registry
+            .start(Some("export-1".to_string()), OPERATION_EXPORT, None)
+            .is_err());
+        assert_eq!(registry.list().len(), 1);
+
+        assert!(guard.token().check().is_ok());
+        assert!(registry.cancel("export-1"));
+        let error = guard.token().check().unwrap_err();
+        assert_eq!(message_code(error), MessageCode::OperationCancelled);
+
+        // A pending future is abandoned too
+        let token = guard.token().clone();
+        let result: Result<()> = token.run(std::future::pending()).await;
+        assert_eq!(
+            message_code(result.unwrap_err()),
+            MessageCode::OperationCancelled
+        );
+
+        drop(guard);
+        assert!(registry.list().is_empty());
+        assert!(!registry.cancel("export-1"));
+    }
+
+    #[tokio::test]
+    async fn operations_time_out() {
+        let registry = Arc::new(OperationRegistry::new());
+        let guard = registry
+            .start(None, OPERATION_EXPORT, Some(Duration::from_millis(20)))
+            .unwrap();
+        // Stands in for a provider call that never checks the token
+        let result: Result<()> = guard.token().run(std::future::pending()).await;
+        assert_eq!(
+            message_code(result.unwrap_err()),
+            MessageCode::OperationTimedOut
+        );
+        assert!(guard.token().check().is_err());
+    }
+}
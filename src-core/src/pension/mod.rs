@@ -0,0 +1,7 @@
+mod pension_model;
+
+pub use pension_model::{
+    estimate_pension, pension_rate, statutory_retirement_date, Gender, NestEggOffset,
+    PensionEstimate, PensionRequest, SalaryPeriod, DEFAULT_SALARY_ADJUSTMENT_RATE,
+    DEFAULT_WITHDRAWAL_RATE, MAX_SALARY_MULTIPLE, MIN_CONTRIBUTION_MONTHS, REFERENCE_LEVEL,
+};
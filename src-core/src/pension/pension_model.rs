@@ -0,0 +1,337 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Reference level (the former base salary) from July 2024, in VND a month. Pensions are
+/// never paid below it and contributions are capped at `MAX_SALARY_MULTIPLE` times it.
+pub const REFERENCE_LEVEL: Decimal = dec!(2_340_000);
+
+/// Highest contribution salary, as a multiple of the reference level
+pub const MAX_SALARY_MULTIPLE: Decimal = dec!(20);
+
+/// Months of contributions needed for a monthly pension (Social Insurance Law 2024)
+pub const MIN_CONTRIBUTION_MONTHS: u32 = 15 * 12;
+
+/// Annual revaluation, in percent, applied to past salaries when the request doesn't set one.
+/// The state publishes yearly CPI-based coefficients; this stands in for them.
+pub const DEFAULT_SALARY_ADJUSTMENT_RATE: Decimal = dec!(4);
+
+/// Share of the nest egg withdrawn each year, in percent, when the request doesn't set one
+pub const DEFAULT_WITHDRAWAL_RATE: Decimal = dec!(4);
+
+/// Highest pension rate, in percent of the average salary
+const MAX_PENSION_RATE: Decimal = dec!(75);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+impl Gender {
+    /// Statutory retirement age in months for someone reaching it in `year` (Labour Code 2019,
+    /// Decree 135/2020): from 60y3m for men and 55y4m for women in 2021, rising by 3 and 4
+    /// months a year until 62 in 2028 and 60 in 2035
+    fn retirement_age_months(&self, year: i32) -> u32 {
+        let years_since = (year - 2020).max(0) as u32;
+        match self {
+            Gender::Male => (720 + 3 * years_since).min(744),
+            Gender::Female => (660 + 4 * years_since).min(720),
+        }
+    }
+
+    /// Contribution years at which the pension rate reaches its maximum
+    fn full_rate_years(&self) -> u32 {
+        match self {
+            Gender::Male => 35,
+            Gender::Female => 30,
+        }
+    }
+}
+
+/// Date someone born on `birth_date` reaches the statutory retirement age
+pub fn statutory_retirement_date(gender: Gender, birth_date: NaiveDate) -> NaiveDate {
+    let mut year = 2021;
+    loop {
+        let date = birth_date + Months::new(gender.retirement_age_months(year));
+        if date.year() <= year {
+            return date;
+        }
+        year += 1;
+    }
+}
+
+/// Pension rate in percent for the contribution months: 45% at 15 years for women and 20 years
+/// for men plus 2% a year up to 75%; men with 15 to 20 years get 40% plus 1% a year. Leftover
+/// months count as half a year up to 6 and as a full year above.
+pub fn pension_rate(gender: Gender, contribution_months: u32) -> Decimal {
+    if contribution_months < MIN_CONTRIBUTION_MONTHS {
+        return Decimal::ZERO;
+    }
+    let half_years = Decimal::from(contribution_half_years(contribution_months));
+    let rate = match gender {
+        Gender::Female => dec!(45) + (half_years - dec!(30)),
+        Gender::Male if half_years >= dec!(40) => dec!(45) + (half_years - dec!(40)),
+        Gender::Male => dec!(40) + (half_years - dec!(30)) / dec!(2),
+    };
+    rate.min(MAX_PENSION_RATE)
+}
+
+fn contribution_half_years(contribution_months: u32) -> u32 {
+    let leftover = match contribution_months % 12 {
+        0 => 0,
+        1..=6 => 1,
+        _ => 2,
+    };
+    contribution_months / 12 * 2 + leftover
+}
+
+/// Monthly salary BHXH contributions were based on over a period
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SalaryPeriod {
+    pub start_date: NaiveDate,
+    /// Open periods run until retirement at the same salary
+    pub end_date: Option<NaiveDate>,
+    pub monthly_salary: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PensionRequest {
+    pub gender: Gender,
+    pub birth_date: NaiveDate,
+    pub salary_history: Vec<SalaryPeriod>,
+    /// Annual revaluation of past salaries in percent
+    pub salary_adjustment_rate: Option<Decimal>,
+    /// Spending the nest egg and the pension have to cover in retirement, in today's VND
+    pub desired_monthly_spending: Option<Decimal>,
+    pub withdrawal_rate: Option<Decimal>,
+}
+
+impl PensionRequest {
+    pub fn validate(&self) -> Result<()> {
+        if self.salary_history.is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "salaryHistory".to_string(),
+            )));
+        }
+        for period in &self.salary_history {
+            if period.monthly_salary <= Decimal::ZERO {
+                return Err(Error::Validation(ValidationError::InvalidInput(
+                    "Monthly salary must be positive".to_string(),
+                )));
+            }
+            if period.start_date < self.birth_date
+                || period.end_date.is_some_and(|end| end < period.start_date)
+            {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "Salary period starting {} has invalid dates",
+                    period.start_date
+                ))));
+            }
+        }
+        if self
+            .salary_adjustment_rate
+            .is_some_and(|rate| rate < Decimal::ZERO)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Salary adjustment rate cannot be negative".to_string(),
+            )));
+        }
+        if self
+            .desired_monthly_spending
+            .is_some_and(|spending| spending < Decimal::ZERO)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Monthly spending cannot be negative".to_string(),
+            )));
+        }
+        if self
+            .withdrawal_rate
+            .is_some_and(|rate| rate <= Decimal::ZERO || rate > Decimal::ONE_HUNDRED)
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Withdrawal rate must be between 0 and 100%".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// How the pension shrinks the savings retirement needs
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NestEggOffset {
+    pub monthly_spending: Decimal,
+    pub withdrawal_rate: Decimal,
+    /// Savings needed to fund all of the spending
+    pub without_pension: Decimal,
+    /// Savings needed for what the pension leaves uncovered
+    pub with_pension: Decimal,
+    pub reduction: Decimal,
+}
+
+/// Estimated BHXH retirement benefits, in today's VND
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PensionEstimate {
+    pub retirement_date: NaiveDate,
+    pub contribution_months: u32,
+    /// Revalued average of the capped contribution salaries
+    pub average_salary: Decimal,
+    /// False below `MIN_CONTRIBUTION_MONTHS`; no monthly pension is paid then
+    pub eligible: bool,
+    /// Percent of the average salary
+    pub pension_rate: Decimal,
+    pub monthly_pension: Decimal,
+    /// One-off allowance of half a month's average salary per year beyond the full rate
+    pub lump_sum: Decimal,
+    pub nest_egg: Option<NestEggOffset>,
+}
+
+/// Estimates the pension of someone contributing on `request.salary_history` until the
+/// statutory retirement age. Past salaries are revalued to `today` at the adjustment rate;
+/// future ones are taken as they are, so every amount is in today's money.
+pub fn estimate_pension(request: &PensionRequest, today: NaiveDate) -> Result<PensionEstimate> {
+    request.validate()?;
+    let retirement_date = statutory_retirement_date(request.gender, request.birth_date);
+    let adjustment = Decimal::ONE
+        + request
+            .salary_adjustment_rate
+            .unwrap_or(DEFAULT_SALARY_ADJUSTMENT_RATE)
+            / Decimal::ONE_HUNDRED;
+    let salary_cap = REFERENCE_LEVEL * MAX_SALARY_MULTIPLE;
+
+    let mut contribution_months = 0u32;
+    let mut salary_total = Decimal::ZERO;
+    for period in &request.salary_history {
+        let end = period
+            .end_date
+            .map_or(retirement_date, |end| end.min(retirement_date));
+        let Some(mut month) = period.start_date.with_day(1) else {
+            continue;
+        };
+        while month < end {
+            let years_ago = (today.year() - month.year()).max(0);
+            salary_total +=
+                period.monthly_salary.min(salary_cap) * adjustment.powi(years_ago as i64);
+            contribution_months += 1;
+            month = month + Months::new(1);
+        }
+    }
+
+    let average_salary = if contribution_months == 0 {
+        Decimal::ZERO
+    } else {
+        (salary_total / Decimal::from(contribution_months)).round_dp(0)
+    };
+    let eligible = contribution_months >= MIN_CONTRIBUTION_MONTHS;
+    let rate = pension_rate(request.gender, contribution_months);
+    let monthly_pension = if eligible {
+        (average_salary * rate / Decimal::ONE_HUNDRED)
+            .round_dp(0)
+            .max(REFERENCE_LEVEL)
+    } else {
+        Decimal::ZERO
+    };
+    let extra_half_years = contribution_half_years(contribution_months)
+        .saturating_sub(request.gender.full_rate_years() * 2);
+    let lump_sum = (average_salary * Decimal::from(extra_half_years) / dec!(4)).round_dp(0);
+
+    let nest_egg = request.desired_monthly_spending.map(|monthly_spending| {
+        let withdrawal_rate = request.withdrawal_rate.unwrap_or(DEFAULT_WITHDRAWAL_RATE);
+        let capital = |monthly: Decimal| {
+            (monthly * dec!(12) / withdrawal_rate * Decimal::ONE_HUNDRED).round_dp(0)
+        };
+        let without_pension = capital(monthly_spending);
+        let with_pension = capital((monthly_spending - monthly_pension).max(Decimal::ZERO));
+        NestEggOffset {
+            monthly_spending,
+            withdrawal_rate,
+            without_pension,
+            with_pension,
+            reduction: without_pension - with_pension,
+        }
+    });
+
+    Ok(PensionEstimate {
+        retirement_date,
+        contribution_months,
+        average_salary,
+        eligible,
+        pension_rate: rate,
+        monthly_pension,
+        lump_sum,
+        nest_egg,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn retirement_age_follows_the_roadmap() {
+        // Reaches 60y3m in 2021
+        assert_eq!(
+            statutory_retirement_date(Gender::Male, date("1961-01-15")),
+            date("2021-04-15")
+        );
+        // Full 62 from 2028 on
+        assert_eq!(
+            statutory_retirement_date(Gender::Male, date("1990-06-01")),
+            date("2052-06-01")
+        );
+        assert_eq!(
+            statutory_retirement_date(Gender::Female, date("1990-06-01")),
+            date("2050-06-01")
+        );
+    }
+
+    #[test]
+    fn pension_rate_grows_with_contribution_years() {
+        assert_eq!(pension_rate(Gender::Female, 14 * 12), Decimal::ZERO);
+        assert_eq!(pension_rate(Gender::Female, 15 * 12), dec!(45));
+        // 20 years and 4 months counts as 20.5
+        assert_eq!(pension_rate(Gender::Female, 20 * 12 + 4), dec!(56));
+        assert_eq!(pension_rate(Gender::Female, 40 * 12), dec!(75));
+        assert_eq!(pension_rate(Gender::Male, 15 * 12), dec!(40));
+        assert_eq!(pension_rate(Gender::Male, 18 * 12), dec!(43));
+        assert_eq!(pension_rate(Gender::Male, 25 * 12), dec!(55));
+    }
+
+    #[test]
+    fn pension_reduces_the_nest_egg() {
+        let request = PensionRequest {
+            gender: Gender::Female,
+            birth_date: date("1990-06-01"),
+            salary_history: vec![SalaryPeriod {
+                start_date: date("2015-06-01"),
+                end_date: None,
+                monthly_salary: dec!(20_000_000),
+            }],
+            salary_adjustment_rate: Some(Decimal::ZERO),
+            desired_monthly_spending: Some(dec!(25_000_000)),
+            withdrawal_rate: None,
+        };
+        let estimate = estimate_pension(&request, date("2026-10-18")).unwrap();
+        assert_eq!(estimate.retirement_date, date("2050-06-01"));
+        assert_eq!(estimate.contribution_months, 35 * 12);
+        assert_eq!(estimate.pension_rate, dec!(75));
+        assert_eq!(estimate.monthly_pension, dec!(15_000_000));
+        // 5 years past the 30 that give the full rate
+        assert_eq!(estimate.lump_sum, dec!(50_000_000));
+        let nest_egg = estimate.nest_egg.unwrap();
+        assert_eq!(nest_egg.without_pension, dec!(7_500_000_000));
+        assert_eq!(nest_egg.with_pension, dec!(3_000_000_000));
+    }
+}
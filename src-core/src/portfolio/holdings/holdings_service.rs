@@ -14,6 +14,7 @@ use rust_decimal_macros::dec;
 use serde_json;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use tracing::instrument;
 
 use super::HoldingsValuationServiceTrait;
 
@@ -258,6 +259,7 @@ fn normalize_holding_currency(holding: &mut Holding) {
 
 #[async_trait]
 impl HoldingsServiceTrait for HoldingsService {
+    #[instrument(name = "holdings.get_holdings", skip(self))]
     async fn get_holdings(&self, account_id: &str, base_currency: &str) -> Result<Vec<Holding>> {
         debug!(
             "Getting holdings for account {} in base currency {}",
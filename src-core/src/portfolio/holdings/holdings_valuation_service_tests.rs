@@ -183,6 +183,9 @@ mod tests {
         async fn add_quote(&self, _quote: &Quote) -> Result<Quote> {
             unimplemented!()
         }
+        async fn add_quotes(&self, _quotes: &[Quote]) -> Result<()> {
+            unimplemented!()
+        }
         async fn update_quote(&self, _quote: Quote) -> Result<Quote> {
             unimplemented!()
         }
@@ -238,6 +241,37 @@ mod tests {
         ) -> Result<MarketDataProviderSetting> {
             unimplemented!()
         }
+        fn get_provider_credential_statuses(
+            &self,
+        ) -> Result<Vec<crate::market_data::ProviderCredentialStatus>> {
+            unimplemented!()
+        }
+        async fn set_provider_credential(&self, _provider_id: &str, _api_key: &str) -> Result<()> {
+            unimplemented!()
+        }
+        async fn test_provider_credential(
+            &self,
+            _provider_id: &str,
+            _api_key: Option<String>,
+        ) -> Result<crate::market_data::ProviderCredentialTestResult> {
+            unimplemented!()
+        }
+        async fn initialize_providers(&self) -> Result<()> {
+            Ok(())
+        }
+        fn providers_ready(&self) -> bool {
+            true
+        }
+        async fn remove_provider_credential(&self, _provider_id: &str) -> Result<()> {
+            unimplemented!()
+        }
+        fn check_quotes_import(
+            &self,
+            _quotes: Vec<crate::market_data::market_data_model::QuoteImport>,
+            _overwrite: bool,
+        ) -> Result<Vec<crate::market_data::market_data_model::QuoteImport>> {
+            unimplemented!()
+        }
         async fn import_quotes_from_csv(
             &self,
             _quotes: Vec<crate::market_data::market_data_model::QuoteImport>,
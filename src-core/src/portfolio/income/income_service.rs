@@ -10,6 +10,7 @@ use chrono::{Datelike, NaiveDate, Utc};
 
 use super::IncomeSummary;
 use crate::fx::fx_traits::FxServiceTrait;
+use crate::settings::SettingsServiceTrait;
 use log::{debug, error};
 use num_traits::Zero;
 use rust_decimal::Decimal;
@@ -22,6 +23,7 @@ pub trait IncomeServiceTrait: Send + Sync {
 pub struct IncomeService {
     fx_service: Arc<dyn FxServiceTrait>,
     activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    settings_service: Arc<dyn SettingsServiceTrait>,
     base_currency: Arc<RwLock<String>>,
 }
 
@@ -29,11 +31,13 @@ impl IncomeService {
     pub fn new(
         fx_service: Arc<dyn FxServiceTrait>,
         activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        settings_service: Arc<dyn SettingsServiceTrait>,
         base_currency: Arc<RwLock<String>>,
     ) -> Self {
         IncomeService {
             fx_service,
             activity_repository,
+            settings_service,
             base_currency,
         }
     }
@@ -45,6 +49,15 @@ impl IncomeService {
             Decimal::zero()
         }
     }
+
+    /// Months of a past fiscal year that had activity, counting from the first transaction
+    fn months_with_history(start: NaiveDate, end: NaiveDate, oldest_date: NaiveDate) -> i32 {
+        let from = start.max(oldest_date);
+        if from > end {
+            return 12;
+        }
+        (end.year() - from.year()) * 12 + end.month() as i32 - from.month() as i32 + 1
+    }
 }
 
 // Implement the trait for IncomeService
@@ -66,10 +79,10 @@ impl IncomeServiceTrait for IncomeService {
 
         let base_currency = self.base_currency.read().unwrap().clone();
         let current_date = Utc::now().naive_utc().date();
-        let current_year = current_date.year();
+        let fiscal_year = self.settings_service.get_fiscal_year()?;
+        let current_year = fiscal_year.year_of(current_date);
         let last_year = current_year - 1;
         let two_years_ago = current_year - 2;
-        let current_month = current_date.month();
 
         let oldest_date = match self.activity_repository.get_first_activity_date_overall() {
             Ok(date) => date,
@@ -84,15 +97,17 @@ impl IncomeServiceTrait for IncomeService {
             + current_date.month() as i32
             - oldest_date.month() as i32;
 
-        let mut months_in_last_year: i32 = 12;
-        if oldest_date.year() >= current_year - 1 {
-            months_in_last_year = 13 - oldest_date.month() as i32
-        }
-
-        let mut months_two_years_ago: i32 = 12;
-        if oldest_date.year() >= current_year - 2 {
-            months_two_years_ago = 13 - oldest_date.month() as i32
-        }
+        let oldest_day = oldest_date.date_naive();
+        let months_in_last_year = IncomeService::months_with_history(
+            fiscal_year.start_of(last_year),
+            fiscal_year.end_of(last_year),
+            oldest_day,
+        );
+        let months_two_years_ago = IncomeService::months_with_history(
+            fiscal_year.start_of(two_years_ago),
+            fiscal_year.end_of(two_years_ago),
+            oldest_day,
+        );
 
         let mut total_summary = IncomeSummary::new("TOTAL", base_currency.clone());
         let mut ytd_summary = IncomeSummary::new("YTD", base_currency.clone());
@@ -100,11 +115,10 @@ impl IncomeServiceTrait for IncomeService {
         let mut two_years_ago_summary = IncomeSummary::new("TWO_YEARS_AGO", base_currency.clone());
 
         for activity in activities {
-            let date = match NaiveDate::parse_from_str(&format!("{}-01", activity.date), "%Y-%m-%d")
-            {
+            let date = match NaiveDate::parse_from_str(&activity.day, "%Y-%m-%d") {
                 Ok(d) => d,
                 Err(e) => {
-                    error!("Error parsing date {}: {:?}", activity.date, e);
+                    error!("Error parsing date {}: {:?}", activity.day, e);
                     continue;
                 }
             };
@@ -126,6 +140,7 @@ impl IncomeServiceTrait for IncomeService {
             // Create a copy of the activity with cloned fields to avoid ownership issues
             let activity_copy = IncomeData {
                 date: activity.date.clone(),
+                day: activity.day.clone(),
                 income_type: activity.income_type.clone(),
                 symbol: activity.symbol.clone(),
                 symbol_name: activity.symbol_name.clone(),
@@ -135,17 +150,18 @@ impl IncomeServiceTrait for IncomeService {
 
             total_summary.add_income(&activity_copy, converted_amount.clone());
 
-            if date.year() == current_year {
+            let activity_year = fiscal_year.year_of(date);
+            if activity_year == current_year {
                 ytd_summary.add_income(&activity_copy, converted_amount.clone());
-            } else if date.year() == last_year {
+            } else if activity_year == last_year {
                 last_year_summary.add_income(&activity_copy, converted_amount.clone());
-            } else if date.year() == two_years_ago {
+            } else if activity_year == two_years_ago {
                 two_years_ago_summary.add_income(&activity_copy, converted_amount.clone());
             }
         }
 
         total_summary.calculate_monthly_average(Some(months_since_first_transaction as u32));
-        ytd_summary.calculate_monthly_average(Some(current_month as u32));
+        ytd_summary.calculate_monthly_average(Some(fiscal_year.months_elapsed(current_date)));
         last_year_summary.calculate_monthly_average(Some(months_in_last_year as u32));
         two_years_ago_summary.calculate_monthly_average(Some(months_two_years_ago as u32));
 
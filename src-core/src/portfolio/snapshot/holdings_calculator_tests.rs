@@ -65,6 +65,10 @@ mod tests {
             unimplemented!("Not needed for tests")
         }
 
+        async fn create_many(&self, _new_assets: Vec<NewAsset>) -> Result<Vec<Asset>> {
+            unimplemented!("Not needed for tests")
+        }
+
         async fn update_profile(
             &self,
             _asset_id: &str,
@@ -15,6 +15,7 @@ use log::{debug, error, info, warn};
 use rust_decimal::Decimal;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::sync::{Arc, RwLock};
+use tracing::instrument;
 
 // --- Service Trait ---
 #[async_trait]
@@ -975,11 +976,13 @@ impl SnapshotService {
 
 #[async_trait]
 impl SnapshotServiceTrait for SnapshotService {
+    #[instrument(name = "snapshot.calculate_holdings", skip(self))]
     async fn calculate_holdings_snapshots(&self, account_ids: Option<&[String]>) -> Result<usize> {
         self.calculate_holdings_snapshots_internal(account_ids, false)
             .await
     }
 
+    #[instrument(name = "snapshot.force_recalculate_holdings", skip(self))]
     async fn force_recalculate_holdings_snapshots(
         &self,
         account_ids: Option<&[String]>,
@@ -1137,6 +1140,7 @@ impl SnapshotServiceTrait for SnapshotService {
         }
     }
 
+    #[instrument(name = "snapshot.calculate_total_portfolio", skip_all)]
     async fn calculate_total_portfolio_snapshots(&self) -> Result<usize> {
         self.calculate_total_portfolio_snapshots_impl().await
     }
@@ -211,6 +211,10 @@ mod tests {
             unimplemented!("create not implemented for MockAssetRepository")
         }
 
+        async fn create_many(&self, _new_assets: Vec<NewAsset>) -> AppResult<Vec<Asset>> {
+            unimplemented!("create_many not implemented for MockAssetRepository")
+        }
+
         async fn update_profile(
             &self,
             _asset_id: &str,
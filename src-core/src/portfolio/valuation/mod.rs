@@ -1,3 +1,5 @@
+pub(crate) mod valuation_archive;
+pub(crate) mod valuation_summary;
 pub mod valuation_calculator;
 pub mod valuation_model;
 pub mod valuation_repository;
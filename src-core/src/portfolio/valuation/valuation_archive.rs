@@ -0,0 +1,575 @@
+//! Compact storage for closed months of daily valuations.
+//!
+//! A month is stored as one `valuation_archives` row per account: the dates as day offsets and
+//! each decimal column as zig-zag varint deltas of its mantissa at a fixed scale, so a month of
+//! barely moving values takes a few hundred bytes instead of ~31 text rows and their index
+//! entries. The latest `HOT_MONTHS` months stay in `daily_account_valuation`, where incremental
+//! recalculation rewrites them.
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use diesel::prelude::*;
+use log::debug;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::errors::{Error, Result};
+use crate::portfolio::valuation::valuation_model::{
+    DailyAccountValuation, DailyAccountValuationDb,
+};
+use crate::schema::{daily_account_valuation, valuation_archives};
+use crate::utils::time_utils::{month_start, next_month_start};
+
+/// Months, counting the one of the latest valuation, kept as rows
+pub(crate) const HOT_MONTHS: u32 = 2;
+
+const FORMAT_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Queryable, Insertable)]
+#[diesel(table_name = valuation_archives)]
+pub(crate) struct ValuationArchiveDb {
+    pub account_id: String,
+    pub month_start: NaiveDate,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    pub row_count: i32,
+    pub account_currency: String,
+    pub base_currency: String,
+    pub calculated_at: String,
+    pub data: Vec<u8>,
+}
+
+/// Column order in the blob
+fn decimal_columns(valuation: &DailyAccountValuation) -> [Decimal; 6] {
+    [
+        valuation.fx_rate_to_base,
+        valuation.cash_balance,
+        valuation.investment_market_value,
+        valuation.total_value,
+        valuation.cost_basis,
+        valuation.net_contribution,
+    ]
+}
+
+fn put_varint(out: &mut Vec<u8>, mut value: u128) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+fn unzigzag(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
+
+struct BlobReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl BlobReader<'_> {
+    fn byte(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    fn varint(&mut self) -> Option<u128> {
+        let mut value = 0u128;
+        for shift in (0..128).step_by(7) {
+            let byte = self.byte()?;
+            value |= u128::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+/// Packs one account's valuations of one month. `None` when the month can't be packed
+/// losslessly (mixed currencies, non-standard ids, values too large), so it stays as rows.
+fn encode_month(valuations: &[DailyAccountValuation]) -> Option<ValuationArchiveDb> {
+    let first = valuations.first()?;
+    let month = month_start(first.valuation_date);
+    let mut previous_date: Option<NaiveDate> = None;
+    for valuation in valuations {
+        if valuation.account_id != first.account_id
+            || valuation.account_currency != first.account_currency
+            || valuation.base_currency != first.base_currency
+            || valuation.id != format!("{}_{}", valuation.account_id, valuation.valuation_date)
+            || month_start(valuation.valuation_date) != month
+            || previous_date.is_some_and(|previous| valuation.valuation_date <= previous)
+        {
+            return None;
+        }
+        previous_date = Some(valuation.valuation_date);
+    }
+
+    let mut data = vec![FORMAT_VERSION];
+    put_varint(&mut data, valuations.len() as u128);
+    let mut previous_day = 0;
+    for valuation in valuations {
+        let day = valuation.valuation_date.day0();
+        put_varint(&mut data, u128::from(day - previous_day));
+        previous_day = day;
+    }
+    for column in 0..6 {
+        let values: Vec<Decimal> = valuations
+            .iter()
+            .map(|v| decimal_columns(v)[column])
+            .collect();
+        let scale = values.iter().map(Decimal::scale).max().unwrap_or(0);
+        data.push(scale as u8);
+        let mut previous = 0i128;
+        for value in values {
+            let mut scaled = value;
+            scaled.rescale(scale);
+            if scaled != value {
+                return None;
+            }
+            let mantissa = scaled.mantissa();
+            put_varint(&mut data, zigzag(mantissa - previous));
+            previous = mantissa;
+        }
+    }
+
+    Some(ValuationArchiveDb {
+        account_id: first.account_id.clone(),
+        month_start: month,
+        first_date: first.valuation_date,
+        last_date: previous_date?,
+        row_count: valuations.len() as i32,
+        account_currency: first.account_currency.clone(),
+        base_currency: first.base_currency.clone(),
+        calculated_at: valuations
+            .iter()
+            .map(|v| v.calculated_at)
+            .max()?
+            .to_rfc3339(),
+        data,
+    })
+}
+
+fn decode_month(archive: &ValuationArchiveDb) -> Result<Vec<DailyAccountValuation>> {
+    let corrupt = || {
+        Error::Repository(format!(
+            "Corrupt valuation archive for account {} month {}",
+            archive.account_id, archive.month_start
+        ))
+    };
+    let mut reader = BlobReader {
+        data: &archive.data,
+        position: 0,
+    };
+    if reader.byte() != Some(FORMAT_VERSION) {
+        return Err(corrupt());
+    }
+    let count = reader.varint().ok_or_else(corrupt)? as usize;
+    if count != archive.row_count as usize {
+        return Err(corrupt());
+    }
+
+    let mut dates = Vec::with_capacity(count);
+    let mut day = 0u128;
+    for _ in 0..count {
+        day += reader.varint().ok_or_else(corrupt)?;
+        let date = archive
+            .month_start
+            .checked_add_days(chrono::Days::new(day as u64))
+            .filter(|date| month_start(*date) == archive.month_start)
+            .ok_or_else(corrupt)?;
+        dates.push(date);
+    }
+
+    let mut columns: Vec<Vec<Decimal>> = Vec::with_capacity(6);
+    for _ in 0..6 {
+        let scale = u32::from(reader.byte().ok_or_else(corrupt)?);
+        let mut values = Vec::with_capacity(count);
+        let mut mantissa = 0i128;
+        for _ in 0..count {
+            mantissa = mantissa
+                .checked_add(unzigzag(reader.varint().ok_or_else(corrupt)?))
+                .ok_or_else(corrupt)?;
+            let value =
+                Decimal::try_from_i128_with_scale(mantissa, scale).map_err(|_| corrupt())?;
+            values.push(value.normalize());
+        }
+        columns.push(values);
+    }
+
+    let calculated_at = DateTime::parse_from_rfc3339(&archive.calculated_at)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+    Ok(dates
+        .into_iter()
+        .enumerate()
+        .map(|(row, date)| DailyAccountValuation {
+            id: format!("{}_{}", archive.account_id, date),
+            account_id: archive.account_id.clone(),
+            valuation_date: date,
+            account_currency: archive.account_currency.clone(),
+            base_currency: archive.base_currency.clone(),
+            fx_rate_to_base: columns[0][row],
+            cash_balance: columns[1][row],
+            investment_market_value: columns[2][row],
+            total_value: columns[3][row],
+            cost_basis: columns[4][row],
+            net_contribution: columns[5][row],
+            calculated_at,
+        })
+        .collect())
+}
+
+/// Moves the account's months before the hot window from rows into archives
+pub(crate) fn archive_closed_months(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+) -> Result<usize> {
+    let latest: Option<NaiveDate> = daily_account_valuation::table
+        .filter(daily_account_valuation::account_id.eq(account_id))
+        .select(diesel::dsl::max(daily_account_valuation::valuation_date))
+        .first(conn)?;
+    let Some(cutoff) = latest
+        .and_then(|latest| month_start(latest).checked_sub_months(Months::new(HOT_MONTHS - 1)))
+    else {
+        return Ok(0);
+    };
+
+    let closed = daily_account_valuation::table
+        .filter(daily_account_valuation::account_id.eq(account_id))
+        .filter(daily_account_valuation::valuation_date.lt(cutoff))
+        .order(daily_account_valuation::valuation_date.asc())
+        .load::<DailyAccountValuationDb>(conn)?;
+    let mut months: BTreeMap<NaiveDate, Vec<DailyAccountValuation>> = BTreeMap::new();
+    for row in closed {
+        months
+            .entry(month_start(row.valuation_date))
+            .or_default()
+            .push(DailyAccountValuation::from(row));
+    }
+
+    let mut archived = 0;
+    for (month, valuations) in months {
+        let Some(archive) = encode_month(&valuations) else {
+            debug!(
+                "Keeping valuations of {} for account {} as rows; they can't be archived",
+                month, account_id
+            );
+            continue;
+        };
+        diesel::replace_into(valuation_archives::table)
+            .values(&archive)
+            .execute(conn)?;
+        diesel::delete(
+            daily_account_valuation::table
+                .filter(daily_account_valuation::account_id.eq(account_id))
+                .filter(daily_account_valuation::valuation_date.ge(month))
+                .filter(daily_account_valuation::valuation_date.lt(next_month_start(month))),
+        )
+        .execute(conn)?;
+        archived += valuations.len();
+    }
+    Ok(archived)
+}
+
+/// Turns the archived months containing `dates` back into rows, so they can be overwritten
+pub(crate) fn unarchive_months(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+    dates: impl IntoIterator<Item = NaiveDate>,
+) -> Result<()> {
+    let months: BTreeSet<NaiveDate> = dates.into_iter().map(month_start).collect();
+    let archives = valuation_archives::table
+        .filter(valuation_archives::account_id.eq(account_id))
+        .filter(valuation_archives::month_start.eq_any(&months))
+        .load::<ValuationArchiveDb>(conn)?;
+    for archive in archives {
+        let rows: Vec<DailyAccountValuationDb> = decode_month(&archive)?
+            .into_iter()
+            .map(DailyAccountValuationDb::from)
+            .collect();
+        diesel::replace_into(daily_account_valuation::table)
+            .values(&rows)
+            .execute(conn)?;
+        diesel::delete(
+            valuation_archives::table
+                .filter(valuation_archives::account_id.eq(account_id))
+                .filter(valuation_archives::month_start.eq(archive.month_start)),
+        )
+        .execute(conn)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn delete_archives(conn: &mut SqliteConnection, account_id: &str) -> Result<()> {
+    diesel::delete(valuation_archives::table.filter(valuation_archives::account_id.eq(account_id)))
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Archived and row valuations of the accounts between the dates (inclusive), by date
+pub(crate) fn load_valuations(
+    conn: &mut SqliteConnection,
+    account_ids: &[String],
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> Result<Vec<DailyAccountValuation>> {
+    let mut archives = valuation_archives::table
+        .filter(valuation_archives::account_id.eq_any(account_ids))
+        .order(valuation_archives::month_start.asc())
+        .into_boxed();
+    let mut rows = daily_account_valuation::table
+        .filter(daily_account_valuation::account_id.eq_any(account_ids))
+        .order(daily_account_valuation::valuation_date.asc())
+        .into_boxed();
+    if let Some(start_date) = start_date {
+        archives = archives.filter(valuation_archives::last_date.ge(start_date));
+        rows = rows.filter(daily_account_valuation::valuation_date.ge(start_date));
+    }
+    if let Some(end_date) = end_date {
+        archives = archives.filter(valuation_archives::first_date.le(end_date));
+        rows = rows.filter(daily_account_valuation::valuation_date.le(end_date));
+    }
+
+    let in_range = |date: NaiveDate| {
+        start_date.is_none_or(|start| date >= start) && end_date.is_none_or(|end| date <= end)
+    };
+    let mut valuations = Vec::new();
+    for archive in archives.load::<ValuationArchiveDb>(conn)? {
+        valuations.extend(
+            decode_month(&archive)?
+                .into_iter()
+                .filter(|v| in_range(v.valuation_date)),
+        );
+    }
+    valuations.extend(
+        rows.load::<DailyAccountValuationDb>(conn)?
+            .into_iter()
+            .map(DailyAccountValuation::from),
+    );
+    valuations.sort_by_key(|v| v.valuation_date);
+    Ok(valuations)
+}
+
+/// Up to `limit` valuations of one account after `after`, by date; decodes a month at a time
+pub(crate) fn load_valuations_after(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+    after: Option<NaiveDate>,
+    limit: usize,
+) -> Result<Vec<DailyAccountValuation>> {
+    let mut valuations: Vec<DailyAccountValuation> = Vec::new();
+    let mut position = after;
+    while valuations.len() < limit {
+        let mut next = valuation_archives::table
+            .filter(valuation_archives::account_id.eq(account_id))
+            .order(valuation_archives::month_start.asc())
+            .into_boxed();
+        if let Some(position) = position {
+            next = next.filter(valuation_archives::last_date.gt(position));
+        }
+        let Some(archive) = next.first::<ValuationArchiveDb>(conn).optional()? else {
+            break;
+        };
+        valuations.extend(
+            decode_month(&archive)?
+                .into_iter()
+                .filter(|v| position.is_none_or(|position| v.valuation_date > position)),
+        );
+        position = Some(archive.last_date);
+    }
+    valuations.truncate(limit);
+
+    if valuations.len() < limit {
+        let mut rows = daily_account_valuation::table
+            .filter(daily_account_valuation::account_id.eq(account_id))
+            .order(daily_account_valuation::valuation_date.asc())
+            .limit((limit - valuations.len()) as i64)
+            .into_boxed();
+        if let Some(position) = valuations.last().map(|v| v.valuation_date).or(after) {
+            rows = rows.filter(daily_account_valuation::valuation_date.gt(position));
+        }
+        valuations.extend(
+            rows.load::<DailyAccountValuationDb>(conn)?
+                .into_iter()
+                .map(DailyAccountValuation::from),
+        );
+    }
+    Ok(valuations)
+}
+
+/// The account's last archived valuation, before `before` when given
+pub(crate) fn latest_archived_valuation(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+    before: Option<NaiveDate>,
+) -> Result<Option<DailyAccountValuation>> {
+    let mut query = valuation_archives::table
+        .filter(valuation_archives::account_id.eq(account_id))
+        .order(valuation_archives::month_start.desc())
+        .into_boxed();
+    if let Some(before) = before {
+        query = query.filter(valuation_archives::first_date.lt(before));
+    }
+    let Some(archive) = query.first::<ValuationArchiveDb>(conn).optional()? else {
+        return Ok(None);
+    };
+    Ok(decode_month(&archive)?
+        .into_iter()
+        .rev()
+        .find(|v| before.is_none_or(|before| v.valuation_date < before)))
+}
+
+pub(crate) fn latest_archived_date(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+) -> Result<Option<NaiveDate>> {
+    Ok(valuation_archives::table
+        .filter(valuation_archives::account_id.eq(account_id))
+        .select(diesel::dsl::max(valuation_archives::last_date))
+        .first(conn)?)
+}
+
+/// Number of valuations of the account, archived or not
+pub(crate) fn count_valuations(conn: &mut SqliteConnection, account_id: &str) -> Result<usize> {
+    let rows: i64 = daily_account_valuation::table
+        .filter(daily_account_valuation::account_id.eq(account_id))
+        .count()
+        .get_result(conn)?;
+    let archived: Option<i64> = valuation_archives::table
+        .filter(valuation_archives::account_id.eq(account_id))
+        .select(diesel::dsl::sum(valuation_archives::row_count))
+        .first(conn)?;
+    Ok((rows + archived.unwrap_or(0)) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::run_migrations;
+    use diesel::r2d2::{ConnectionManager, Pool};
+    use std::str::FromStr;
+
+    fn valuation(date: &str, total: &str, fx: &str) -> DailyAccountValuation {
+        let valuation_date = NaiveDate::from_str(date).unwrap();
+        let total = Decimal::from_str(total).unwrap();
+        DailyAccountValuation {
+            id: format!("broker_{}", valuation_date),
+            account_id: "broker".to_string(),
+            valuation_date,
+            account_currency: "USD".to_string(),
+            base_currency: "VND".to_string(),
+            fx_rate_to_base: Decimal::from_str(fx).unwrap(),
+            cash_balance: Decimal::from_str("-12.5").unwrap(),
+            investment_market_value: total,
+            total_value: total,
+            cost_basis: Decimal::from_str("1000.123456").unwrap(),
+            net_contribution: Decimal::ZERO,
+            calculated_at: DateTime::parse_from_rfc3339("2026-10-18T08:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn closed_months_are_archived_and_read_back_unchanged() {
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .unwrap();
+        run_migrations(&pool).unwrap();
+        let conn = &mut pool.get().unwrap();
+
+        let start = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let valuations: Vec<DailyAccountValuation> = (0..140)
+            .filter(|day| day % 7 != 5)
+            .map(|day| {
+                let date = start + chrono::Days::new(day);
+                let total = format!("{}.{:02}", 1_000_000 + day * 37, day % 100);
+                valuation(
+                    &date.to_string(),
+                    &total,
+                    if day < 60 { "25400" } else { "25412.75" },
+                )
+            })
+            .collect();
+        let rows: Vec<DailyAccountValuationDb> = valuations
+            .iter()
+            .cloned()
+            .map(DailyAccountValuationDb::from)
+            .collect();
+        diesel::insert_into(daily_account_valuation::table)
+            .values(&rows)
+            .execute(conn)
+            .unwrap();
+
+        // Latest is in October, so June to August are closed and September stays hot
+        let archived = archive_closed_months(conn, "broker").unwrap();
+        let months: Vec<NaiveDate> = valuation_archives::table
+            .select(valuation_archives::month_start)
+            .load(conn)
+            .unwrap();
+        assert_eq!(months.len(), 3);
+        let remaining: i64 = daily_account_valuation::table
+            .count()
+            .get_result(conn)
+            .unwrap();
+        assert_eq!(archived + remaining as usize, valuations.len());
+        assert!(remaining > 0);
+
+        assert_eq!(
+            load_valuations(conn, &["broker".to_string()], None, None).unwrap(),
+            valuations
+        );
+        assert_eq!(count_valuations(conn, "broker").unwrap(), valuations.len());
+        let july = load_valuations(
+            conn,
+            &["broker".to_string()],
+            NaiveDate::from_ymd_opt(2026, 7, 10),
+            NaiveDate::from_ymd_opt(2026, 9, 2),
+        )
+        .unwrap();
+        assert_eq!(
+            july.first().unwrap().valuation_date.to_string(),
+            "2026-07-10"
+        );
+        assert_eq!(
+            july.last().unwrap().valuation_date.to_string(),
+            "2026-09-02"
+        );
+
+        let mut paged = Vec::new();
+        loop {
+            let after = paged
+                .last()
+                .map(|v: &DailyAccountValuation| v.valuation_date);
+            let chunk = load_valuations_after(conn, "broker", after, 45).unwrap();
+            if chunk.is_empty() {
+                break;
+            }
+            assert!(chunk.len() <= 45);
+            paged.extend(chunk);
+        }
+        assert_eq!(paged, valuations);
+
+        let before_september =
+            latest_archived_valuation(conn, "broker", NaiveDate::from_ymd_opt(2026, 9, 1)).unwrap();
+        assert_eq!(
+            before_september.unwrap().valuation_date.to_string(),
+            "2026-08-31"
+        );
+
+        unarchive_months(conn, "broker", NaiveDate::from_ymd_opt(2026, 7, 15)).unwrap();
+        let remaining_archives: i64 = valuation_archives::table.count().get_result(conn).unwrap();
+        assert_eq!(remaining_archives, 2);
+        assert_eq!(
+            load_valuations(conn, &["broker".to_string()], None, None).unwrap(),
+            valuations
+        );
+    }
+}
@@ -88,3 +88,65 @@ impl From<DailyAccountValuationDb> for DailyAccountValuation {
         }
     }
 }
+
+/// One account's valuations over a month, kept up to date as valuations are saved
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMonthlySummary {
+    pub account_id: String,
+    /// First day of the month
+    pub month: NaiveDate,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    pub value_count: i32,
+    pub account_currency: String,
+    pub opening_total_value: Decimal,
+    /// Values of `last_date`, in the account currency
+    pub closing_total_value: Decimal,
+    pub closing_fx_rate_to_base: Decimal,
+    pub closing_cost_basis: Decimal,
+    pub closing_net_contribution: Decimal,
+}
+
+impl AccountMonthlySummary {
+    /// Closing value in the base currency
+    pub fn closing_value_in_base(&self) -> Decimal {
+        self.closing_total_value * self.closing_fx_rate_to_base
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Queryable, Insertable)]
+#[diesel(table_name = crate::schema::account_monthly_summaries)]
+pub struct AccountMonthlySummaryDb {
+    pub account_id: String,
+    pub month_start: NaiveDate,
+    pub first_date: NaiveDate,
+    pub last_date: NaiveDate,
+    pub value_count: i32,
+    pub account_currency: String,
+    pub opening_total_value: String,
+    pub closing_total_value: String,
+    pub closing_fx_rate_to_base: String,
+    pub closing_cost_basis: String,
+    pub closing_net_contribution: String,
+    pub updated_at: String,
+}
+
+impl From<AccountMonthlySummaryDb> for AccountMonthlySummary {
+    fn from(value: AccountMonthlySummaryDb) -> Self {
+        let decimal = |text: &str| Decimal::from_str(text).unwrap_or_default();
+        AccountMonthlySummary {
+            account_id: value.account_id,
+            month: value.month_start,
+            first_date: value.first_date,
+            last_date: value.last_date,
+            value_count: value.value_count,
+            account_currency: value.account_currency,
+            opening_total_value: decimal(&value.opening_total_value),
+            closing_total_value: decimal(&value.closing_total_value),
+            closing_fx_rate_to_base: decimal(&value.closing_fx_rate_to_base),
+            closing_cost_basis: decimal(&value.closing_cost_basis),
+            closing_net_contribution: decimal(&value.closing_net_contribution),
+        }
+    }
+}
@@ -6,14 +6,15 @@ use diesel::sql_query;
 use diesel::sql_types::Text;
 use diesel::sqlite::Sqlite;
 use diesel::sqlite::SqliteConnection;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
 use crate::db::{get_connection, WriteHandle};
 use crate::errors::Result;
 use crate::portfolio::valuation::valuation_model::{
-    DailyAccountValuation, DailyAccountValuationDb,
+    AccountMonthlySummary, DailyAccountValuation, DailyAccountValuationDb,
 };
+use crate::portfolio::valuation::{valuation_archive, valuation_summary};
 use crate::schema::daily_account_valuation;
 use crate::schema::daily_account_valuation::dsl::*;
 
@@ -37,6 +38,13 @@ pub trait ValuationRepositoryTrait: Send + Sync {
         account_ids: &[String],
         date: NaiveDate,
     ) -> Result<Vec<DailyAccountValuation>>;
+    /// Month-end summaries of the accounts (all when `None`) for the months between the dates
+    fn get_monthly_summaries(
+        &self,
+        account_ids: Option<&[String]>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<AccountMonthlySummary>>;
 }
 
 pub struct ValuationRepository {
@@ -66,11 +74,39 @@ impl ValuationRepositoryTrait for ValuationRepository {
 
         self.writer
             .exec(move |conn| {
+                let account_ids: BTreeSet<String> = records_to_save
+                    .iter()
+                    .map(|record| record.account_id.clone())
+                    .collect();
+                // Archived months being rewritten go back to rows first, then get re-archived
+                for archived_account_id in &account_ids {
+                    valuation_archive::unarchive_months(
+                        conn,
+                        archived_account_id,
+                        records_to_save
+                            .iter()
+                            .filter(|record| &record.account_id == archived_account_id)
+                            .map(|record| record.valuation_date),
+                    )?;
+                }
                 for chunk in records_to_save.chunks(1000) {
                     diesel::replace_into(daily_account_valuation::table)
                         .values(chunk) // Pass the chunk directly
                         .execute(conn)?;
                 }
+                for summarized_account_id in &account_ids {
+                    valuation_summary::refresh_monthly_summaries(
+                        conn,
+                        summarized_account_id,
+                        records_to_save
+                            .iter()
+                            .filter(|record| &record.account_id == summarized_account_id)
+                            .map(|record| record.valuation_date),
+                    )?;
+                }
+                for archived_account_id in &account_ids {
+                    valuation_archive::archive_closed_months(conn, archived_account_id)?;
+                }
                 Ok(())
             })
             .await
@@ -84,29 +120,13 @@ impl ValuationRepositoryTrait for ValuationRepository {
     ) -> Result<Vec<DailyAccountValuation>> {
         let mut conn = get_connection(&self.pool)?;
 
-        let mut query = daily_account_valuation::table
-            .filter(account_id.eq(input_account_id))
-            .order(valuation_date.asc())
-            .into_boxed();
-
-        if let Some(start_date_val) = start_date_opt {
-            query = query.filter(valuation_date.ge(start_date_val));
-        }
-
-        if let Some(end_date_val) = end_date_opt {
-            query = query.filter(valuation_date.le(end_date_val));
-        }
-
-        let history_dbs = query.load::<DailyAccountValuationDb>(&mut conn)?;
-
-        // Convert Vec<DailyAccountValuationDb> to Vec<DailyAccountValuation>
-        // Handle potential conversion errors if necessary (using From implicitly handles unwrap_or_default)
-        let history_records: Vec<DailyAccountValuation> = history_dbs
-            .into_iter()
-            .map(DailyAccountValuation::from)
-            .collect();
-
-        Ok(history_records)
+        // Closed months come from the archive, recent ones from rows
+        valuation_archive::load_valuations(
+            &mut conn,
+            &[input_account_id.to_string()],
+            start_date_opt,
+            end_date_opt,
+        )
     }
 
     fn load_latest_valuation_date(&self, input_account_id: &str) -> Result<Option<NaiveDate>> {
@@ -124,9 +144,10 @@ impl ValuationRepositoryTrait for ValuationRepository {
             .optional()?;
 
         // Flatten the Option<Option<NaiveDate>> to Option<NaiveDate>
-        let latest_date = result.flatten();
-
-        Ok(latest_date)
+        match result.flatten() {
+            Some(latest_date) => Ok(Some(latest_date)),
+            None => valuation_archive::latest_archived_date(&mut conn, input_account_id),
+        }
     }
 
     async fn delete_valuations_for_account(&self, input_account_id: &str) -> Result<()> {
@@ -134,10 +155,11 @@ impl ValuationRepositoryTrait for ValuationRepository {
         self.writer
             .exec(move |conn| {
                 diesel::delete(
-                    daily_account_valuation::table.filter(account_id.eq(account_id_owned)),
+                    daily_account_valuation::table.filter(account_id.eq(&account_id_owned)),
                 )
                 .execute(conn)?;
-                Ok(())
+                valuation_archive::delete_archives(conn, &account_id_owned)?;
+                valuation_summary::delete_monthly_summaries(conn, &account_id_owned)
             })
             .await
     }
@@ -205,6 +227,10 @@ impl ValuationRepositoryTrait for ValuationRepository {
             if let Some(valuation) = results_map.remove(acc_id_str) {
                 // Use remove to avoid cloning if DailyAccountValuation is large
                 ordered_results.push(valuation);
+            } else if let Some(valuation) =
+                valuation_archive::latest_archived_valuation(&mut conn, acc_id_str, None)?
+            {
+                ordered_results.push(valuation);
             }
         }
         Ok(ordered_results)
@@ -221,17 +247,21 @@ impl ValuationRepositoryTrait for ValuationRepository {
 
         let mut conn = get_connection(&self.pool)?;
 
-        let history_dbs = daily_account_valuation::table
-            .filter(account_id.eq_any(input_account_ids)) // Use eq_any for multiple IDs
-            .filter(valuation_date.eq(input_date)) // Filter by the specific date
-            .load::<DailyAccountValuationDb>(&mut conn)?;
-
-        // Convert Vec<DailyAccountValuationDb> to Vec<DailyAccountValuation>
-        let history_records: Vec<DailyAccountValuation> = history_dbs
-            .into_iter()
-            .map(DailyAccountValuation::from)
-            .collect();
+        valuation_archive::load_valuations(
+            &mut conn,
+            input_account_ids,
+            Some(input_date),
+            Some(input_date),
+        )
+    }
 
-        Ok(history_records)
+    fn get_monthly_summaries(
+        &self,
+        account_ids: Option<&[String]>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<AccountMonthlySummary>> {
+        let mut conn = get_connection(&self.pool)?;
+        valuation_summary::load_monthly_summaries(&mut conn, account_ids, start_date, end_date)
     }
 }
@@ -4,7 +4,7 @@ use crate::fx::fx_traits::FxServiceTrait;
 use crate::market_data::MarketDataServiceTrait;
 use crate::portfolio::snapshot::SnapshotServiceTrait;
 use crate::portfolio::valuation::valuation_calculator::calculate_valuation;
-use crate::portfolio::valuation::valuation_model::DailyAccountValuation;
+use crate::portfolio::valuation::valuation_model::{AccountMonthlySummary, DailyAccountValuation};
 use crate::portfolio::valuation::ValuationRepositoryTrait;
 use crate::utils::time_utils;
 use async_trait::async_trait;
@@ -13,6 +13,7 @@ use log::{debug, error, warn};
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
+use tracing::instrument;
 
 use super::DailyFxRateMap;
 
@@ -70,6 +71,15 @@ pub trait ValuationServiceTrait: Send + Sync {
         account_ids: &[String],
         date: NaiveDate,
     ) -> CoreResult<Vec<DailyAccountValuation>>;
+
+    /// Loads the month-end summaries of the accounts (all when `None`) for the months
+    /// between the dates, kept up to date as valuations are saved.
+    fn get_monthly_summaries(
+        &self,
+        account_ids: Option<&[String]>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> CoreResult<Vec<AccountMonthlySummary>>;
 }
 
 #[derive(Clone)]
@@ -140,6 +150,7 @@ impl ValuationService {
 
 #[async_trait]
 impl ValuationServiceTrait for ValuationService {
+    #[instrument(name = "valuation.calculate_history", skip(self))]
     async fn calculate_valuation_history(
         &self,
         account_id: &str,
@@ -378,4 +389,14 @@ impl ValuationServiceTrait for ValuationService {
         self.valuation_repository
             .get_valuations_on_date(account_ids, date)
     }
+
+    fn get_monthly_summaries(
+        &self,
+        account_ids: Option<&[String]>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> CoreResult<Vec<AccountMonthlySummary>> {
+        self.valuation_repository
+            .get_monthly_summaries(account_ids, start_date, end_date)
+    }
 }
@@ -0,0 +1,197 @@
+//! Month-end summaries of daily valuations, refreshed in the same transaction as the
+//! valuations they summarize so readers never see them disagree.
+
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use std::collections::BTreeSet;
+
+use crate::constants::DECIMAL_PRECISION;
+use crate::errors::Result;
+use crate::goals::monthly_summaries;
+use crate::portfolio::valuation::valuation_archive;
+use crate::portfolio::valuation::valuation_model::{
+    AccountMonthlySummary, AccountMonthlySummaryDb, DailyAccountValuation,
+};
+use crate::schema::account_monthly_summaries;
+use crate::utils::time_utils::{month_start, next_month_start};
+
+fn summarize(valuations: &[DailyAccountValuation]) -> Option<AccountMonthlySummaryDb> {
+    let first = valuations.first()?;
+    let last = valuations.last()?;
+    Some(AccountMonthlySummaryDb {
+        account_id: last.account_id.clone(),
+        month_start: month_start(last.valuation_date),
+        first_date: first.valuation_date,
+        last_date: last.valuation_date,
+        value_count: valuations.len() as i32,
+        account_currency: last.account_currency.clone(),
+        opening_total_value: first.total_value.round_dp(DECIMAL_PRECISION).to_string(),
+        closing_total_value: last.total_value.round_dp(DECIMAL_PRECISION).to_string(),
+        closing_fx_rate_to_base: last.fx_rate_to_base.to_string(),
+        closing_cost_basis: last.cost_basis.round_dp(DECIMAL_PRECISION).to_string(),
+        closing_net_contribution: last
+            .net_contribution
+            .round_dp(DECIMAL_PRECISION)
+            .to_string(),
+        updated_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// Recomputes the account's summaries of the months containing `dates` from its valuations.
+/// Goal progress from the earliest of those months on depends on them, so it is dropped.
+pub(crate) fn refresh_monthly_summaries(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+    dates: impl IntoIterator<Item = NaiveDate>,
+) -> Result<()> {
+    let months: BTreeSet<NaiveDate> = dates.into_iter().map(month_start).collect();
+    let Some(earliest) = months.first().copied() else {
+        return Ok(());
+    };
+    for month in months {
+        let month_end = next_month_start(month).pred_opt().unwrap_or(month);
+        let valuations = valuation_archive::load_valuations(
+            conn,
+            &[account_id.to_string()],
+            Some(month),
+            Some(month_end),
+        )?;
+        match summarize(&valuations) {
+            Some(summary) => {
+                diesel::replace_into(account_monthly_summaries::table)
+                    .values(&summary)
+                    .execute(conn)?;
+            }
+            None => {
+                diesel::delete(
+                    account_monthly_summaries::table
+                        .filter(account_monthly_summaries::account_id.eq(account_id))
+                        .filter(account_monthly_summaries::month_start.eq(month)),
+                )
+                .execute(conn)?;
+            }
+        }
+    }
+    monthly_summaries::clear_goal_progress(conn, None, Some(earliest))
+}
+
+pub(crate) fn delete_monthly_summaries(
+    conn: &mut SqliteConnection,
+    account_id: &str,
+) -> Result<()> {
+    diesel::delete(
+        account_monthly_summaries::table
+            .filter(account_monthly_summaries::account_id.eq(account_id)),
+    )
+    .execute(conn)?;
+    monthly_summaries::clear_goal_progress(conn, None, None)
+}
+
+/// Summaries of the accounts (all when `None`) for the months between the dates, by account
+/// and month
+pub(crate) fn load_monthly_summaries(
+    conn: &mut SqliteConnection,
+    account_ids: Option<&[String]>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+) -> Result<Vec<AccountMonthlySummary>> {
+    let mut query = account_monthly_summaries::table
+        .order((
+            account_monthly_summaries::account_id.asc(),
+            account_monthly_summaries::month_start.asc(),
+        ))
+        .into_boxed();
+    if let Some(account_ids) = account_ids {
+        query = query.filter(account_monthly_summaries::account_id.eq_any(account_ids));
+    }
+    if let Some(start_date) = start_date {
+        query = query.filter(account_monthly_summaries::month_start.ge(month_start(start_date)));
+    }
+    if let Some(end_date) = end_date {
+        query = query.filter(account_monthly_summaries::month_start.le(end_date));
+    }
+    Ok(query
+        .load::<AccountMonthlySummaryDb>(conn)?
+        .into_iter()
+        .map(AccountMonthlySummary::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::run_migrations;
+    use crate::portfolio::valuation::valuation_model::DailyAccountValuationDb;
+    use crate::schema::daily_account_valuation;
+    use diesel::r2d2::{ConnectionManager, Pool};
+    use rust_decimal::Decimal;
+
+    fn valuation(date: NaiveDate, total: i64) -> DailyAccountValuationDb {
+        DailyAccountValuationDb::from(DailyAccountValuation {
+            id: format!("broker_{}", date),
+            account_id: "broker".to_string(),
+            valuation_date: date,
+            account_currency: "USD".to_string(),
+            base_currency: "VND".to_string(),
+            fx_rate_to_base: Decimal::from(25_000),
+            cash_balance: Decimal::ZERO,
+            investment_market_value: Decimal::from(total),
+            total_value: Decimal::from(total),
+            cost_basis: Decimal::from(900),
+            net_contribution: Decimal::from(1_000),
+            calculated_at: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn summaries_follow_the_valuations_of_their_month() {
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .unwrap();
+        run_migrations(&pool).unwrap();
+        let conn = &mut pool.get().unwrap();
+
+        let start = NaiveDate::from_ymd_opt(2026, 8, 20).unwrap();
+        let dates: Vec<NaiveDate> = (0..20).map(|day| start + chrono::Days::new(day)).collect();
+        let rows: Vec<DailyAccountValuationDb> = dates
+            .iter()
+            .enumerate()
+            .map(|(day, date)| valuation(*date, 1_000 + day as i64))
+            .collect();
+        diesel::insert_into(daily_account_valuation::table)
+            .values(&rows)
+            .execute(conn)
+            .unwrap();
+        refresh_monthly_summaries(conn, "broker", dates.iter().copied()).unwrap();
+
+        let summaries = load_monthly_summaries(conn, None, None, None).unwrap();
+        let months: Vec<String> = summaries.iter().map(|s| s.month.to_string()).collect();
+        assert_eq!(months, ["2026-08-01", "2026-09-01"]);
+        let august = &summaries[0];
+        assert_eq!(august.value_count, 12);
+        assert_eq!(august.last_date.to_string(), "2026-08-31");
+        assert_eq!(august.opening_total_value, Decimal::from(1_000));
+        assert_eq!(august.closing_total_value, Decimal::from(1_011));
+        assert_eq!(august.closing_value_in_base(), Decimal::from(25_275_000));
+
+        // Removing September's rows and refreshing drops its summary
+        diesel::delete(
+            daily_account_valuation::table.filter(
+                daily_account_valuation::valuation_date
+                    .ge(NaiveDate::from_ymd_opt(2026, 9, 1).unwrap()),
+            ),
+        )
+        .execute(conn)
+        .unwrap();
+        refresh_monthly_summaries(conn, "broker", NaiveDate::from_ymd_opt(2026, 9, 1)).unwrap();
+        let summaries = load_monthly_summaries(
+            conn,
+            Some(&["broker".to_string()]),
+            NaiveDate::from_ymd_opt(2026, 8, 15),
+            None,
+        )
+        .unwrap();
+        assert_eq!(summaries.len(), 1);
+    }
+}
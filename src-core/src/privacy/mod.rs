@@ -0,0 +1,3 @@
+mod privacy_mask;
+
+pub use privacy_mask::{index_valuation_history, mask_if, MaskAmounts, PRIVACY_INDEX_BASE};
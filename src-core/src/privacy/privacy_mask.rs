@@ -0,0 +1,488 @@
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::accounts::AccountGroupValuation;
+use crate::activities::ActivityDetails;
+use crate::cash::AccountCash;
+use crate::dividends::{DividendPayment, DividendSummary, HoldingDividendYield};
+use crate::goals::{
+    AllocationChangePreview, AllocationDetail, AllocationRebalance, AllocationRebalanceSuggestion,
+    DashboardGoal, DashboardSummary, DebtBalance, DebtPayoffProgress, DepositDistribution,
+    EmergencyFundProgress, GoalChangePreview, GoalDeposit, GoalFundingRequirement,
+    GoalMonthlyProgress, GoalProgressSnapshot, MonthlySummaries, NetWorthGoalProgress,
+    NetWorthPoint, SinkingFundProgress,
+};
+use crate::portfolio::holdings::{Holding, MonetaryValue};
+use crate::portfolio::income::IncomeSummary;
+use crate::portfolio::performance::{PerformanceMetrics, SimplePerformanceMetrics};
+use crate::portfolio::valuation::{AccountMonthlySummary, DailyAccountValuation};
+use crate::reports::{
+    AssetClassValue, LiabilityBalance, NetWorthChange, NetWorthHistoryEntry, NetWorthStatement,
+};
+
+/// Value the first point of a masked valuation history is rescaled to
+pub const PRIVACY_INDEX_BASE: Decimal = Decimal::ONE_HUNDRED;
+
+/// Strips absolute amounts from a view model while keeping percentages and weights,
+/// so privacy mode can still show allocation and performance.
+pub trait MaskAmounts {
+    fn mask_amounts(&mut self);
+}
+
+/// Masks `value` when privacy mode is on; a no-op otherwise
+pub fn mask_if<T: MaskAmounts>(mut value: T, privacy_mode: bool) -> T {
+    if privacy_mode {
+        value.mask_amounts();
+    }
+    value
+}
+
+impl<T: MaskAmounts> MaskAmounts for Vec<T> {
+    fn mask_amounts(&mut self) {
+        self.iter_mut().for_each(MaskAmounts::mask_amounts);
+    }
+}
+
+impl<T: MaskAmounts> MaskAmounts for Option<T> {
+    fn mask_amounts(&mut self) {
+        if let Some(value) = self {
+            value.mask_amounts();
+        }
+    }
+}
+
+impl MaskAmounts for MonetaryValue {
+    fn mask_amounts(&mut self) {
+        *self = MonetaryValue::zero();
+    }
+}
+
+impl MaskAmounts for Holding {
+    fn mask_amounts(&mut self) {
+        // Quantity times the (public) price would reveal the position size
+        self.quantity = Decimal::ZERO;
+        self.lots = None;
+        self.market_value.mask_amounts();
+        self.cost_basis.mask_amounts();
+        self.unrealized_gain.mask_amounts();
+        self.realized_gain.mask_amounts();
+        self.total_gain.mask_amounts();
+        self.day_change.mask_amounts();
+        self.prev_close_value.mask_amounts();
+    }
+}
+
+impl MaskAmounts for DailyAccountValuation {
+    fn mask_amounts(&mut self) {
+        self.cash_balance = Decimal::ZERO;
+        self.investment_market_value = Decimal::ZERO;
+        self.total_value = Decimal::ZERO;
+        self.cost_basis = Decimal::ZERO;
+        self.net_contribution = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for IncomeSummary {
+    /// Breakdowns become percentages of the period total
+    fn mask_amounts(&mut self) {
+        let total = self.total_income;
+        for breakdown in [
+            &mut self.by_month,
+            &mut self.by_type,
+            &mut self.by_symbol,
+            &mut self.by_currency,
+        ] {
+            to_percent_of(breakdown, total);
+        }
+        self.total_income = Decimal::ZERO;
+        self.monthly_average = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for PerformanceMetrics {
+    fn mask_amounts(&mut self) {
+        self.gain_loss_amount = None;
+    }
+}
+
+impl MaskAmounts for SimplePerformanceMetrics {
+    fn mask_amounts(&mut self) {
+        self.total_value = None;
+        self.total_gain_loss_amount = None;
+        self.day_gain_loss_amount = None;
+    }
+}
+
+impl MaskAmounts for ActivityDetails {
+    /// Keeps the unit price, which is public for listed assets
+    fn mask_amounts(&mut self) {
+        self.quantity = Decimal::ZERO.to_string();
+        self.fee = Decimal::ZERO.to_string();
+        self.amount = None;
+    }
+}
+
+impl MaskAmounts for EmergencyFundProgress {
+    /// Keeps months covered and the share of the target reached
+    fn mask_amounts(&mut self) {
+        self.monthly_expenses = Decimal::ZERO;
+        self.target_amount = Decimal::ZERO;
+        self.current_value = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for SinkingFundProgress {
+    /// Keeps the share of the target reached and the months left
+    fn mask_amounts(&mut self) {
+        self.target_amount = Decimal::ZERO;
+        self.current_value = Decimal::ZERO;
+        self.remaining = Decimal::ZERO;
+        self.monthly_set_aside = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for NetWorthPoint {
+    fn mask_amounts(&mut self) {
+        self.total_assets = Decimal::ZERO;
+        self.total_liabilities = Decimal::ZERO;
+        self.net_worth = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for NetWorthGoalProgress {
+    /// Keeps the share of the target reached
+    fn mask_amounts(&mut self) {
+        self.target_amount = Decimal::ZERO;
+        self.start_net_worth = None;
+        self.remaining = Decimal::ZERO;
+        self.current.mask_amounts();
+    }
+}
+
+impl MaskAmounts for DebtBalance {
+    fn mask_amounts(&mut self) {
+        self.remaining_principal = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for DebtPayoffProgress {
+    /// Keeps the share of the target repaid and the payoff dates
+    fn mask_amounts(&mut self) {
+        self.target_amount = Decimal::ZERO;
+        self.start_balance = Decimal::ZERO;
+        self.remaining_principal = Decimal::ZERO;
+        self.principal_repaid = Decimal::ZERO;
+        self.debts.mask_amounts();
+    }
+}
+
+impl MaskAmounts for DashboardGoal {
+    /// Keeps the share of the target reached
+    fn mask_amounts(&mut self) {
+        self.current_amount = Decimal::ZERO;
+        self.target_amount = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for DashboardSummary {
+    /// Keeps the daily change percentage and goal progress
+    fn mask_amounts(&mut self) {
+        self.net_worth = Decimal::ZERO;
+        self.daily_change = Decimal::ZERO;
+        self.goals.mask_amounts();
+    }
+}
+
+impl MaskAmounts for AccountMonthlySummary {
+    /// Keeps the dates and the exchange rate
+    fn mask_amounts(&mut self) {
+        self.opening_total_value = Decimal::ZERO;
+        self.closing_total_value = Decimal::ZERO;
+        self.closing_cost_basis = Decimal::ZERO;
+        self.closing_net_contribution = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for GoalMonthlyProgress {
+    fn mask_amounts(&mut self) {
+        self.init_value = 0.0;
+        self.current_value = 0.0;
+        self.growth = 0.0;
+    }
+}
+
+impl MaskAmounts for AllocationDetail {
+    /// Keeps the allocation percentage
+    fn mask_amounts(&mut self) {
+        self.account_value_at_goal_start = 0.0;
+        self.account_current_value = 0.0;
+        self.account_growth = 0.0;
+        self.allocated_growth = 0.0;
+        self.contributed_amount = 0.0;
+        self.market_growth = 0.0;
+    }
+}
+
+impl MaskAmounts for GoalProgressSnapshot {
+    fn mask_amounts(&mut self) {
+        self.init_value = 0.0;
+        self.current_value = 0.0;
+        self.growth = 0.0;
+        self.contributed_amount = 0.0;
+        self.market_growth = 0.0;
+        self.allocation_details.mask_amounts();
+    }
+}
+
+impl MaskAmounts for MonthlySummaries {
+    fn mask_amounts(&mut self) {
+        self.accounts.mask_amounts();
+        self.goals.mask_amounts();
+    }
+}
+
+impl MaskAmounts for AllocationRebalanceSuggestion {
+    fn mask_amounts(&mut self) {
+        self.target_amount = 0.0;
+        self.current_value = 0.0;
+        self.remaining = 0.0;
+    }
+}
+
+impl MaskAmounts for AllocationRebalance {
+    fn mask_amounts(&mut self) {
+        self.suggestions.mask_amounts();
+    }
+}
+
+/// The deposit was typed in by the user, so only what the goals still need is hidden
+impl MaskAmounts for GoalDeposit {
+    fn mask_amounts(&mut self) {
+        self.remaining = 0.0;
+    }
+}
+
+impl MaskAmounts for DepositDistribution {
+    fn mask_amounts(&mut self) {
+        self.goals.mask_amounts();
+    }
+}
+
+impl MaskAmounts for GoalFundingRequirement {
+    fn mask_amounts(&mut self) {
+        self.target_amount = 0.0;
+        self.current_value = 0.0;
+        self.required_monthly = 0.0;
+        self.inflation_adjusted_target = 0.0;
+        self.inflation_adjusted_monthly = 0.0;
+    }
+}
+
+/// Completion dates stay, as they tell nothing of the amounts
+impl MaskAmounts for GoalChangePreview {
+    fn mask_amounts(&mut self) {
+        self.target_amount = 0.0;
+        self.current_value = 0.0;
+        self.new_value = 0.0;
+    }
+}
+
+/// The changed amount was typed in by the user, so it stays
+impl MaskAmounts for AllocationChangePreview {
+    fn mask_amounts(&mut self) {
+        self.unallocated_balance = 0.0;
+        self.goals.mask_amounts();
+    }
+}
+
+impl MaskAmounts for AccountGroupValuation {
+    fn mask_amounts(&mut self) {
+        self.account_values
+            .values_mut()
+            .for_each(|value| *value = Decimal::ZERO);
+        self.total_value = Decimal::ZERO;
+        self.children.mask_amounts();
+    }
+}
+
+impl MaskAmounts for AccountCash {
+    fn mask_amounts(&mut self) {
+        for balance in &mut self.balances {
+            balance.amount = Decimal::ZERO;
+        }
+        self.total = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for AssetClassValue {
+    /// Keeps the class's share of total assets
+    fn mask_amounts(&mut self) {
+        self.value = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for LiabilityBalance {
+    fn mask_amounts(&mut self) {
+        self.balance = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for NetWorthChange {
+    /// Keeps the net worth change percentage
+    fn mask_amounts(&mut self) {
+        self.total_assets = Decimal::ZERO;
+        self.total_liabilities = Decimal::ZERO;
+        self.net_worth = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for NetWorthHistoryEntry {
+    fn mask_amounts(&mut self) {
+        self.total_assets = Decimal::ZERO;
+        self.total_liabilities = Decimal::ZERO;
+        self.net_worth = Decimal::ZERO;
+        self.goal_allocated = Decimal::ZERO;
+        self.free_assets = Decimal::ZERO;
+        self.assets_by_class.mask_amounts();
+        self.change.mask_amounts();
+    }
+}
+
+impl MaskAmounts for NetWorthStatement {
+    fn mask_amounts(&mut self) {
+        self.assets_by_class.mask_amounts();
+        self.total_assets = Decimal::ZERO;
+        self.liabilities.mask_amounts();
+        self.total_liabilities = Decimal::ZERO;
+        self.net_worth = Decimal::ZERO;
+        self.goal_allocated = Decimal::ZERO;
+        self.free_assets = Decimal::ZERO;
+        self.previous_month.mask_amounts();
+        self.month_over_month.mask_amounts();
+    }
+}
+
+impl MaskAmounts for DividendPayment {
+    /// Keeps the per-share amount, which says nothing of the position size
+    fn mask_amounts(&mut self) {
+        self.gross_amount = Decimal::ZERO;
+        self.tax_withheld = Decimal::ZERO;
+        self.net_amount = Decimal::ZERO;
+        self.gross_amount_base = Decimal::ZERO;
+        self.net_amount_base = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for HoldingDividendYield {
+    /// Keeps the yields and per-share dividends
+    fn mask_amounts(&mut self) {
+        self.quantity = Decimal::ZERO;
+        self.market_value = Decimal::ZERO;
+        self.trailing_dividends = Decimal::ZERO;
+        self.trailing_tax_withheld = Decimal::ZERO;
+        self.trailing_reinvested = Decimal::ZERO;
+        self.projected_income = Decimal::ZERO;
+        self.projected_income_reinvested = Decimal::ZERO;
+    }
+}
+
+impl MaskAmounts for DividendSummary {
+    fn mask_amounts(&mut self) {
+        self.calendar.mask_amounts();
+        self.holdings.mask_amounts();
+        self.trailing_dividends = Decimal::ZERO;
+        self.trailing_tax_withheld = Decimal::ZERO;
+        self.trailing_reinvested = Decimal::ZERO;
+        self.projected_income = Decimal::ZERO;
+        self.projected_income_reinvested = Decimal::ZERO;
+    }
+}
+
+fn to_percent_of(values: &mut HashMap<String, Decimal>, total: Decimal) {
+    for value in values.values_mut() {
+        *value = if total.is_zero() {
+            Decimal::ZERO
+        } else {
+            (*value / total * Decimal::ONE_HUNDRED).round_dp(2)
+        };
+    }
+}
+
+/// Rescales a valuation history so each account starts at `PRIVACY_INDEX_BASE`.
+/// Charts keep their shape and relative moves while the absolute balances disappear.
+pub fn index_valuation_history(history: &mut [DailyAccountValuation]) {
+    let mut first_totals: HashMap<String, Decimal> = HashMap::new();
+    for valuation in history.iter_mut() {
+        let first = *first_totals
+            .entry(valuation.account_id.clone())
+            .or_insert(valuation.total_value);
+        if first.is_zero() {
+            valuation.mask_amounts();
+            continue;
+        }
+        let factor = PRIVACY_INDEX_BASE / first;
+        valuation.cash_balance *= factor;
+        valuation.investment_market_value *= factor;
+        valuation.total_value *= factor;
+        valuation.cost_basis *= factor;
+        valuation.net_contribution *= factor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use rust_decimal_macros::dec;
+
+    fn valuation(day: u32, total: Decimal) -> DailyAccountValuation {
+        DailyAccountValuation {
+            id: format!("acc-{}", day),
+            account_id: "acc".to_string(),
+            valuation_date: NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+            account_currency: "VND".to_string(),
+            base_currency: "VND".to_string(),
+            fx_rate_to_base: Decimal::ONE,
+            cash_balance: total / dec!(2),
+            investment_market_value: total / dec!(2),
+            total_value: total,
+            cost_basis: total,
+            net_contribution: total,
+            calculated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_valuation_history_is_indexed() {
+        let mut history = vec![
+            valuation(1, dec!(2_000_000_000)),
+            valuation(2, dec!(2_100_000_000)),
+        ];
+        index_valuation_history(&mut history);
+
+        assert_eq!(history[0].total_value, dec!(100));
+        assert_eq!(history[1].total_value, dec!(105));
+        assert_eq!(history[1].cash_balance, dec!(52.5));
+    }
+
+    #[test]
+    fn test_income_breakdown_becomes_percentages() {
+        let mut summary = IncomeSummary {
+            period: "TOTAL".to_string(),
+            by_month: HashMap::from([("2025-01".to_string(), dec!(3_000_000))]),
+            by_type: HashMap::from([("DIVIDEND".to_string(), dec!(4_000_000))]),
+            by_symbol: HashMap::new(),
+            by_currency: HashMap::new(),
+            total_income: dec!(4_000_000),
+            currency: "VND".to_string(),
+            monthly_average: dec!(1_000_000),
+            yoy_growth: Some(dec!(0.1)),
+        };
+        summary = mask_if(summary, true);
+
+        assert_eq!(summary.by_month["2025-01"], dec!(75));
+        assert_eq!(summary.by_type["DIVIDEND"], dec!(100));
+        assert_eq!(summary.total_income, Decimal::ZERO);
+        assert_eq!(summary.yoy_growth, Some(dec!(0.1)));
+    }
+}
@@ -0,0 +1,15 @@
+mod private_loans_model;
+mod private_loans_repository;
+mod private_loans_service;
+mod private_loans_traits;
+
+pub use private_loans_model::{
+    outstanding_principal, private_loan_asset_symbol, summarize_private_loan, LendingRepaymentPlan,
+    LendingRepaymentSource, LendingRiskStatus, LendingScheduleRow, NewPrivateLoan,
+    NewPrivateLoanRepayment, PrivateLoan, PrivateLoanDB, PrivateLoanMatchResult,
+    PrivateLoanRepayment, PrivateLoanRepaymentDB, PrivateLoanSummary, AT_RISK_DAYS_OVERDUE,
+    DEFAULT_DAYS_OVERDUE,
+};
+pub use private_loans_repository::PrivateLoanRepository;
+pub use private_loans_service::PrivateLoanService;
+pub use private_loans_traits::{PrivateLoanRepositoryTrait, PrivateLoanServiceTrait};
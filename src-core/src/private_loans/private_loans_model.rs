@@ -0,0 +1,678 @@
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+use crate::loans::MAX_LOAN_TERM_MONTHS;
+
+/// Symbol of the manual asset money lent out is held as
+pub fn private_loan_asset_symbol(loan_id: &str) -> String {
+    format!("PLOAN-{}", loan_id)
+}
+
+/// Days a repayment can be overdue before the loan is flagged at risk
+pub const AT_RISK_DAYS_OVERDUE: i64 = 30;
+
+/// Days a repayment can be overdue before the loan is flagged as in default
+pub const DEFAULT_DAYS_OVERDUE: i64 = 90;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LendingRepaymentPlan {
+    /// Level monthly instalments of principal and interest, as P2P platforms schedule them
+    #[default]
+    Installment,
+    /// Interest every month and the principal with the last payment
+    InterestOnly,
+    /// Principal and interest in one payment at the end of the term, as between family
+    Bullet,
+}
+
+impl LendingRepaymentPlan {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LendingRepaymentPlan::Installment => "INSTALLMENT",
+            LendingRepaymentPlan::InterestOnly => "INTEREST_ONLY",
+            LendingRepaymentPlan::Bullet => "BULLET",
+        }
+    }
+}
+
+impl FromStr for LendingRepaymentPlan {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "INSTALLMENT" => Ok(LendingRepaymentPlan::Installment),
+            "INTEREST_ONLY" => Ok(LendingRepaymentPlan::InterestOnly),
+            "BULLET" => Ok(LendingRepaymentPlan::Bullet),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown repayment plan: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// How a repayment was recorded
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LendingRepaymentSource {
+    /// Matched to an imported or entered deposit
+    Matched,
+    /// Entered by the user
+    Manual,
+}
+
+impl LendingRepaymentSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LendingRepaymentSource::Matched => "MATCHED",
+            LendingRepaymentSource::Manual => "MANUAL",
+        }
+    }
+}
+
+impl FromStr for LendingRepaymentSource {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "MATCHED" => Ok(LendingRepaymentSource::Matched),
+            "MANUAL" => Ok(LendingRepaymentSource::Manual),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown repayment source: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Where a loan stands against its schedule
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LendingRiskStatus {
+    /// Every repayment due so far has come in
+    Current,
+    /// A repayment is overdue, by less than `AT_RISK_DAYS_OVERDUE` days
+    Late,
+    /// A repayment is at least `AT_RISK_DAYS_OVERDUE` days overdue
+    AtRisk,
+    /// A repayment is at least `DEFAULT_DAYS_OVERDUE` days overdue
+    Default,
+    /// Given up on; the loan is valued at nothing
+    WrittenOff,
+    /// The principal has been paid back
+    Repaid,
+}
+
+impl LendingRiskStatus {
+    fn for_days_overdue(days: i64) -> Self {
+        if days >= DEFAULT_DAYS_OVERDUE {
+            LendingRiskStatus::Default
+        } else if days >= AT_RISK_DAYS_OVERDUE {
+            LendingRiskStatus::AtRisk
+        } else if days > 0 {
+            LendingRiskStatus::Late
+        } else {
+            LendingRiskStatus::Current
+        }
+    }
+}
+
+/// Database row for `private_loans`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::private_loans)]
+pub struct PrivateLoanDB {
+    pub id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub activity_id: Option<String>,
+    pub borrower: String,
+    pub platform: Option<String>,
+    pub principal: String,
+    pub interest_rate: String,
+    pub repayment_plan: String,
+    pub term_months: i32,
+    pub start_date: String,
+    pub match_pattern: Option<String>,
+    pub written_off: bool,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Money lent to a friend, relative or through a P2P platform, in the account currency
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateLoan {
+    pub id: String,
+    pub account_id: String,
+    /// Manual asset whose quotes value the outstanding principal
+    pub asset_id: String,
+    /// Activity that added the loan to the account
+    pub activity_id: Option<String>,
+    pub borrower: String,
+    /// P2P platform the loan was made through, if any
+    pub platform: Option<String>,
+    pub principal: Decimal,
+    /// Agreed annual interest rate in percent
+    pub interest_rate: Decimal,
+    pub repayment_plan: LendingRepaymentPlan,
+    pub term_months: i32,
+    pub start_date: NaiveDate,
+    /// Text a repayment's comment contains; the borrower's name when unset
+    pub match_pattern: Option<String>,
+    pub written_off: bool,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<PrivateLoanDB> for PrivateLoan {
+    type Error = Error;
+
+    fn try_from(db: PrivateLoanDB) -> Result<Self> {
+        Ok(PrivateLoan {
+            id: db.id,
+            account_id: db.account_id,
+            asset_id: db.asset_id,
+            activity_id: db.activity_id,
+            borrower: db.borrower,
+            platform: db.platform,
+            principal: Decimal::from_str(&db.principal)?,
+            interest_rate: Decimal::from_str(&db.interest_rate)?,
+            repayment_plan: db.repayment_plan.parse()?,
+            term_months: db.term_months,
+            start_date: parse_forecast_date(&db.start_date)?,
+            match_pattern: db.match_pattern,
+            written_off: db.written_off,
+            notes: db.notes,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// A repayment the borrower owes on a due date
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LendingScheduleRow {
+    pub period: i32,
+    pub due_date: NaiveDate,
+    pub principal: Decimal,
+    pub interest: Decimal,
+    /// Principal plus interest
+    pub payment: Decimal,
+}
+
+impl PrivateLoan {
+    /// Lowercased text a repayment's comment must contain
+    pub fn match_text(&self) -> String {
+        self.match_pattern
+            .as_deref()
+            .unwrap_or(&self.borrower)
+            .to_lowercase()
+    }
+
+    /// Asset sub-class the loan is reported under
+    pub fn asset_sub_class(&self) -> &'static str {
+        if self.platform.is_some() {
+            "P2P Loan"
+        } else {
+            "Private Loan"
+        }
+    }
+
+    fn due_date(&self, period: i32) -> Option<NaiveDate> {
+        self.start_date
+            .checked_add_months(Months::new(period as u32))
+    }
+
+    /// Repayments agreed with the borrower, monthly from a month after the start date
+    pub fn schedule(&self) -> Vec<LendingScheduleRow> {
+        let monthly_rate = self.interest_rate / Decimal::ONE_HUNDRED / Decimal::from(12);
+        let row = |period: i32, principal: Decimal, interest: Decimal| {
+            self.due_date(period).map(|due_date| LendingScheduleRow {
+                period,
+                due_date,
+                principal,
+                interest,
+                payment: principal + interest,
+            })
+        };
+        match self.repayment_plan {
+            LendingRepaymentPlan::Bullet => {
+                let interest =
+                    (self.principal * monthly_rate * Decimal::from(self.term_months)).round_dp(2);
+                row(self.term_months, self.principal, interest)
+                    .into_iter()
+                    .collect()
+            }
+            LendingRepaymentPlan::InterestOnly => {
+                let interest = (self.principal * monthly_rate).round_dp(2);
+                (1..=self.term_months)
+                    .filter_map(|period| {
+                        let principal = if period == self.term_months {
+                            self.principal
+                        } else {
+                            Decimal::ZERO
+                        };
+                        row(period, principal, interest)
+                    })
+                    .collect()
+            }
+            LendingRepaymentPlan::Installment => {
+                let payment = if monthly_rate.is_zero() {
+                    self.principal / Decimal::from(self.term_months)
+                } else {
+                    let growth = (Decimal::ONE + monthly_rate).powi(self.term_months as i64);
+                    self.principal * monthly_rate * growth / (growth - Decimal::ONE)
+                }
+                .round_dp(2);
+                let mut balance = self.principal;
+                let mut rows = Vec::new();
+                for period in 1..=self.term_months {
+                    let interest = (balance * monthly_rate).round_dp(2);
+                    let principal = if period == self.term_months {
+                        balance
+                    } else {
+                        (payment - interest).clamp(Decimal::ZERO, balance)
+                    };
+                    balance -= principal;
+                    rows.extend(row(period, principal, interest));
+                }
+                rows
+            }
+        }
+    }
+}
+
+/// Database row for `private_loan_repayments`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::private_loan_repayments)]
+pub struct PrivateLoanRepaymentDB {
+    pub id: String,
+    pub loan_id: String,
+    pub activity_id: Option<String>,
+    pub payment_date: String,
+    pub amount: String,
+    pub source: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// Money the borrower paid back
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateLoanRepayment {
+    pub id: String,
+    pub loan_id: String,
+    /// Deposit the repayment arrived with
+    pub activity_id: Option<String>,
+    pub payment_date: NaiveDate,
+    /// In the loan's currency
+    pub amount: Decimal,
+    pub source: LendingRepaymentSource,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<PrivateLoanRepaymentDB> for PrivateLoanRepayment {
+    type Error = Error;
+
+    fn try_from(db: PrivateLoanRepaymentDB) -> Result<Self> {
+        Ok(PrivateLoanRepayment {
+            id: db.id,
+            loan_id: db.loan_id,
+            activity_id: db.activity_id,
+            payment_date: parse_forecast_date(&db.payment_date)?,
+            amount: Decimal::from_str(&db.amount)?,
+            source: db.source.parse()?,
+            created_at: db.created_at,
+        })
+    }
+}
+
+/// Input for creating or updating a private loan
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPrivateLoan {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub borrower: String,
+    pub platform: Option<String>,
+    pub principal: Decimal,
+    pub interest_rate: Decimal,
+    #[serde(default)]
+    pub repayment_plan: LendingRepaymentPlan,
+    pub term_months: i32,
+    pub start_date: NaiveDate,
+    pub match_pattern: Option<String>,
+    #[serde(default)]
+    pub written_off: bool,
+    pub notes: Option<String>,
+}
+
+impl NewPrivateLoan {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("accountId", &self.account_id),
+            ("borrower", &self.borrower),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        if self.principal <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Amount lent must be positive".to_string(),
+            )));
+        }
+        if self.interest_rate < Decimal::ZERO || self.interest_rate >= Decimal::ONE_HUNDRED {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Interest rate must be a percentage between 0 and 100".to_string(),
+            )));
+        }
+        if self.term_months <= 0 || self.term_months > MAX_LOAN_TERM_MONTHS {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Loan term must be between 1 and {} months",
+                MAX_LOAN_TERM_MONTHS
+            ))));
+        }
+        if self
+            .match_pattern
+            .as_ref()
+            .is_some_and(|pattern| pattern.trim().is_empty())
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Match text cannot be blank".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Input for recording a repayment by hand
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPrivateLoanRepayment {
+    pub loan_id: String,
+    pub payment_date: NaiveDate,
+    pub amount: Decimal,
+    /// Deposit the repayment arrived with, if there is one
+    pub activity_id: Option<String>,
+}
+
+impl NewPrivateLoanRepayment {
+    pub fn validate(&self) -> Result<()> {
+        if self.loan_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "loanId".to_string(),
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Repayment amount must be positive".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A loan with its schedule, repayments and standing on a date
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateLoanSummary {
+    pub loan: PrivateLoan,
+    pub currency: String,
+    pub valued_on: NaiveDate,
+    pub schedule: Vec<LendingScheduleRow>,
+    pub repayments: Vec<PrivateLoanRepayment>,
+    pub total_repaid: Decimal,
+    /// Part of the repayments that paid interest
+    pub interest_received: Decimal,
+    /// Principal still owed
+    pub outstanding_principal: Decimal,
+    /// What the loan counts for in net worth: the outstanding principal, nothing once
+    /// written off
+    pub value: Decimal,
+    /// Scheduled payments due by the valuation date that have not come in
+    pub overdue_amount: Decimal,
+    /// Days since the oldest repayment not fully paid fell due
+    pub days_overdue: i64,
+    pub next_due: Option<LendingScheduleRow>,
+    pub status: LendingRiskStatus,
+}
+
+/// Repayments matched from deposits by one run
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateLoanMatchResult {
+    pub matched: usize,
+    pub repayments: Vec<PrivateLoanRepayment>,
+}
+
+/// Principal still owed after `repayments`, which settle the schedule in order, interest
+/// before principal within each due date
+pub fn outstanding_principal(
+    loan: &PrivateLoan,
+    schedule: &[LendingScheduleRow],
+    repayments: &[PrivateLoanRepayment],
+) -> Decimal {
+    let mut left: Decimal = repayments.iter().map(|r| r.amount).sum();
+    let mut principal_repaid = Decimal::ZERO;
+    for row in schedule {
+        if left <= Decimal::ZERO {
+            break;
+        }
+        left -= row.interest.min(left);
+        let principal = row.principal.min(left);
+        principal_repaid += principal;
+        left -= principal;
+    }
+    (loan.principal - principal_repaid).max(Decimal::ZERO)
+}
+
+/// Works out where `loan` stands on `date` from its schedule and repayments
+pub fn summarize_private_loan(
+    loan: PrivateLoan,
+    currency: String,
+    mut repayments: Vec<PrivateLoanRepayment>,
+    date: NaiveDate,
+) -> PrivateLoanSummary {
+    repayments.sort_by_key(|r| r.payment_date);
+    let schedule = loan.schedule();
+    let paid_by_date: Vec<PrivateLoanRepayment> = repayments
+        .iter()
+        .filter(|r| r.payment_date <= date)
+        .cloned()
+        .collect();
+    let total_repaid: Decimal = paid_by_date.iter().map(|r| r.amount).sum();
+    let outstanding = outstanding_principal(&loan, &schedule, &paid_by_date);
+    let interest_received = (total_repaid - (loan.principal - outstanding)).max(Decimal::ZERO);
+
+    // The oldest due date the repayments have not covered yet
+    let mut due_so_far = Decimal::ZERO;
+    let mut first_unpaid: Option<NaiveDate> = None;
+    for row in schedule.iter().filter(|row| row.due_date <= date) {
+        due_so_far += row.payment;
+        if first_unpaid.is_none() && due_so_far > total_repaid {
+            first_unpaid = Some(row.due_date);
+        }
+    }
+    let overdue_amount = if outstanding.is_zero() {
+        Decimal::ZERO
+    } else {
+        (due_so_far - total_repaid).max(Decimal::ZERO)
+    };
+    let days_overdue = first_unpaid
+        .filter(|_| !overdue_amount.is_zero())
+        .map_or(0, |due| (date - due).num_days());
+    let status = if loan.written_off {
+        LendingRiskStatus::WrittenOff
+    } else if outstanding.is_zero() {
+        LendingRiskStatus::Repaid
+    } else {
+        LendingRiskStatus::for_days_overdue(days_overdue)
+    };
+    let next_due = if outstanding.is_zero() || loan.written_off {
+        None
+    } else {
+        schedule.iter().find(|row| row.due_date > date).cloned()
+    };
+
+    PrivateLoanSummary {
+        currency,
+        valued_on: date,
+        schedule,
+        repayments,
+        total_repaid,
+        interest_received,
+        outstanding_principal: outstanding,
+        value: if loan.written_off {
+            Decimal::ZERO
+        } else {
+            outstanding
+        },
+        overdue_amount,
+        days_overdue,
+        next_due,
+        status,
+        loan,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_forecast_date(value).unwrap()
+    }
+
+    fn loan(plan: LendingRepaymentPlan) -> PrivateLoan {
+        let now = Utc::now().naive_utc();
+        PrivateLoan {
+            id: "loan".to_string(),
+            account_id: "cash".to_string(),
+            asset_id: private_loan_asset_symbol("loan"),
+            activity_id: None,
+            borrower: "Anh Minh".to_string(),
+            platform: None,
+            principal: dec!(120_000_000),
+            interest_rate: dec!(12),
+            repayment_plan: plan,
+            term_months: 12,
+            start_date: date("2026-01-10"),
+            match_pattern: None,
+            written_off: false,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn repayment(day: &str, amount: Decimal) -> PrivateLoanRepayment {
+        PrivateLoanRepayment {
+            id: day.to_string(),
+            loan_id: "loan".to_string(),
+            activity_id: None,
+            payment_date: date(day),
+            amount,
+            source: LendingRepaymentSource::Manual,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn schedules_follow_the_repayment_plan() {
+        let installments = loan(LendingRepaymentPlan::Installment).schedule();
+        assert_eq!(installments.len(), 12);
+        assert_eq!(installments[0].due_date, date("2026-02-10"));
+        // 1% a month on 120m
+        assert_eq!(installments[0].interest, dec!(1_200_000));
+        assert_eq!(installments[0].payment, dec!(10_661_854.64));
+        let principal: Decimal = installments.iter().map(|row| row.principal).sum();
+        assert_eq!(principal, dec!(120_000_000));
+
+        let bullet = loan(LendingRepaymentPlan::Bullet).schedule();
+        assert_eq!(bullet.len(), 1);
+        assert_eq!(bullet[0].due_date, date("2027-01-10"));
+        assert_eq!(bullet[0].payment, dec!(134_400_000));
+
+        let interest_only = loan(LendingRepaymentPlan::InterestOnly).schedule();
+        assert_eq!(interest_only[0].payment, dec!(1_200_000));
+        assert_eq!(interest_only[11].payment, dec!(121_200_000));
+    }
+
+    #[test]
+    fn missed_repayments_flag_the_loan_at_risk() {
+        let loan = loan(LendingRepaymentPlan::InterestOnly);
+        // February and March paid, April onwards missed
+        let repayments = vec![
+            repayment("2026-02-10", dec!(1_200_000)),
+            repayment("2026-03-12", dec!(1_200_000)),
+        ];
+
+        let summary = summarize_private_loan(
+            loan.clone(),
+            "VND".into(),
+            repayments.clone(),
+            date("2026-04-20"),
+        );
+        assert_eq!(summary.status, LendingRiskStatus::Late);
+        assert_eq!(summary.days_overdue, 10);
+        assert_eq!(summary.overdue_amount, dec!(1_200_000));
+        assert_eq!(summary.outstanding_principal, dec!(120_000_000));
+        assert_eq!(summary.interest_received, dec!(2_400_000));
+
+        let summary = summarize_private_loan(
+            loan.clone(),
+            "VND".into(),
+            repayments.clone(),
+            date("2026-05-15"),
+        );
+        assert_eq!(summary.status, LendingRiskStatus::AtRisk);
+        assert_eq!(summary.overdue_amount, dec!(2_400_000));
+
+        let summary =
+            summarize_private_loan(loan.clone(), "VND".into(), repayments, date("2026-07-15"));
+        assert_eq!(summary.status, LendingRiskStatus::Default);
+        assert_eq!(summary.days_overdue, 96);
+        assert_eq!(summary.value, dec!(120_000_000));
+
+        let written_off = PrivateLoan {
+            written_off: true,
+            ..loan
+        };
+        let summary = summarize_private_loan(written_off, "VND".into(), vec![], date("2026-07-15"));
+        assert_eq!(summary.status, LendingRiskStatus::WrittenOff);
+        assert_eq!(summary.value, Decimal::ZERO);
+    }
+
+    #[test]
+    fn repayments_pay_interest_before_principal() {
+        let loan = loan(LendingRepaymentPlan::Bullet);
+        let schedule = loan.schedule();
+        // The interest for the whole term is paid off first
+        let partial = vec![repayment("2026-06-10", dec!(20_000_000))];
+        assert_eq!(
+            outstanding_principal(&loan, &schedule, &partial),
+            dec!(114_400_000)
+        );
+        let in_full = vec![
+            repayment("2026-06-10", dec!(20_000_000)),
+            repayment("2027-01-10", dec!(114_400_000)),
+        ];
+        let summary = summarize_private_loan(loan, "VND".into(), in_full, date("2027-02-01"));
+        assert_eq!(summary.status, LendingRiskStatus::Repaid);
+        assert_eq!(summary.interest_received, dec!(14_400_000));
+        assert!(summary.next_due.is_none());
+    }
+}
@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::private_loans_model::{
+    NewPrivateLoan, PrivateLoan, PrivateLoanDB, PrivateLoanRepayment, PrivateLoanRepaymentDB,
+};
+use super::private_loans_traits::PrivateLoanRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::{private_loan_repayments, private_loans};
+use crate::utils::text_utils::trimmed;
+
+pub struct PrivateLoanRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl PrivateLoanRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        PrivateLoanRepository { pool, writer }
+    }
+}
+
+fn repayment_record(repayment: PrivateLoanRepayment) -> PrivateLoanRepaymentDB {
+    PrivateLoanRepaymentDB {
+        id: repayment.id,
+        loan_id: repayment.loan_id,
+        activity_id: repayment.activity_id,
+        payment_date: repayment
+            .payment_date
+            .format(FORECAST_DATE_FORMAT)
+            .to_string(),
+        amount: repayment.amount.to_string(),
+        source: repayment.source.as_str().to_string(),
+        created_at: repayment.created_at,
+    }
+}
+
+#[async_trait]
+impl PrivateLoanRepositoryTrait for PrivateLoanRepository {
+    fn get_loans(&self) -> Result<Vec<PrivateLoan>> {
+        let mut conn = get_connection(&self.pool)?;
+        private_loans::table
+            .order((
+                private_loans::start_date.asc(),
+                private_loans::borrower.asc(),
+            ))
+            .load::<PrivateLoanDB>(&mut conn)?
+            .into_iter()
+            .map(PrivateLoan::try_from)
+            .collect()
+    }
+
+    fn get_loan(&self, id: &str) -> Result<PrivateLoan> {
+        let mut conn = get_connection(&self.pool)?;
+        private_loans::table
+            .find(id)
+            .first::<PrivateLoanDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_loan(
+        &self,
+        loan: NewPrivateLoan,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<PrivateLoan> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<PrivateLoan> {
+                let now = Utc::now().naive_utc();
+                let record = PrivateLoanDB {
+                    id: loan.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: loan.account_id,
+                    asset_id,
+                    activity_id,
+                    borrower: loan.borrower.trim().to_string(),
+                    platform: trimmed(loan.platform),
+                    principal: loan.principal.to_string(),
+                    interest_rate: loan.interest_rate.to_string(),
+                    repayment_plan: loan.repayment_plan.as_str().to_string(),
+                    term_months: loan.term_months,
+                    start_date: loan.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    match_pattern: trimmed(loan.match_pattern),
+                    written_off: loan.written_off,
+                    notes: trimmed(loan.notes),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(private_loans::table)
+                    .values(&record)
+                    .get_result::<PrivateLoanDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_loan(&self, id: &str, loan: NewPrivateLoan) -> Result<PrivateLoan> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<PrivateLoan> {
+                diesel::update(private_loans::table.find(id_owned))
+                    .set((
+                        private_loans::borrower.eq(loan.borrower.trim().to_string()),
+                        private_loans::platform.eq(trimmed(loan.platform)),
+                        private_loans::principal.eq(loan.principal.to_string()),
+                        private_loans::interest_rate.eq(loan.interest_rate.to_string()),
+                        private_loans::repayment_plan.eq(loan.repayment_plan.as_str()),
+                        private_loans::term_months.eq(loan.term_months),
+                        private_loans::start_date
+                            .eq(loan.start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        private_loans::match_pattern.eq(trimmed(loan.match_pattern)),
+                        private_loans::written_off.eq(loan.written_off),
+                        private_loans::notes.eq(trimmed(loan.notes)),
+                        private_loans::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<PrivateLoanDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_loan(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(private_loans::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_repayments(&self, loan_id: Option<&str>) -> Result<Vec<PrivateLoanRepayment>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = private_loan_repayments::table.into_boxed();
+        if let Some(loan_id) = loan_id {
+            query = query.filter(private_loan_repayments::loan_id.eq(loan_id.to_string()));
+        }
+        query
+            .order((
+                private_loan_repayments::payment_date.asc(),
+                private_loan_repayments::created_at.asc(),
+            ))
+            .load::<PrivateLoanRepaymentDB>(&mut conn)?
+            .into_iter()
+            .map(PrivateLoanRepayment::try_from)
+            .collect()
+    }
+
+    async fn insert_repayments(
+        &self,
+        repayments: Vec<PrivateLoanRepayment>,
+    ) -> Result<Vec<PrivateLoanRepayment>> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<Vec<PrivateLoanRepayment>> {
+                    let records: Vec<PrivateLoanRepaymentDB> =
+                        repayments.iter().cloned().map(repayment_record).collect();
+                    diesel::insert_into(private_loan_repayments::table)
+                        .values(&records)
+                        .execute(conn)?;
+                    Ok(repayments)
+                },
+            )
+            .await
+    }
+
+    async fn delete_repayment(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(private_loan_repayments::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,549 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::private_loans_model::{
+    outstanding_principal, private_loan_asset_symbol, summarize_private_loan,
+    LendingRepaymentSource, NewPrivateLoan, NewPrivateLoanRepayment, PrivateLoan,
+    PrivateLoanMatchResult, PrivateLoanRepayment, PrivateLoanSummary,
+};
+use super::private_loans_traits::{PrivateLoanRepositoryTrait, PrivateLoanServiceTrait};
+use crate::accounts::{
+    Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY, ACCOUNT_TYPE_REAL_ESTATE,
+};
+use crate::activities::{
+    ActivityRepositoryTrait, ActivityServiceTrait, ActivityUpdate, NewActivity,
+    ACTIVITY_TYPE_ADD_HOLDING, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_TRANSFER_IN,
+};
+use crate::assets::{AssetServiceTrait, UpdateAssetProfile};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::fx::{convert_or_keep, FxServiceTrait};
+use crate::market_data::{DataSource, MarketDataServiceTrait, Quote};
+
+pub struct PrivateLoanService {
+    repository: Arc<dyn PrivateLoanRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    activity_repository: Arc<dyn ActivityRepositoryTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+}
+
+/// A deposit that may repay a loan, with its amount in the loan's currency
+#[derive(Debug, Clone)]
+struct ReceivedActivity {
+    activity_id: String,
+    date: NaiveDate,
+    amount: Decimal,
+    comment: String,
+}
+
+fn loan_quote(symbol: &str, date: NaiveDate, value: Decimal, currency: &str) -> Quote {
+    let now = Utc::now();
+    Quote {
+        id: format!("{}_{}", symbol, date.format(FORECAST_DATE_FORMAT)),
+        symbol: symbol.to_string(),
+        timestamp: date.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc(),
+        open: value,
+        high: value,
+        low: value,
+        close: value,
+        adjclose: value,
+        volume: Decimal::ZERO,
+        currency: currency.to_string(),
+        data_source: DataSource::Manual,
+        created_at: now,
+    }
+}
+
+/// Takes each deposit in date order whose comment mentions the loan as a repayment, until the
+/// principal is paid back. Deposits used here are added to `used`.
+fn match_repayments(
+    loan: &PrivateLoan,
+    existing: &[PrivateLoanRepayment],
+    received: &[ReceivedActivity],
+    used: &mut HashSet<String>,
+) -> Vec<PrivateLoanRepayment> {
+    let schedule = loan.schedule();
+    let text = loan.match_text();
+    let mut repayments: Vec<PrivateLoanRepayment> = existing.to_vec();
+    let mut matched = Vec::new();
+    for activity in received {
+        if outstanding_principal(loan, &schedule, &repayments).is_zero() {
+            break;
+        }
+        if activity.date < loan.start_date
+            || used.contains(&activity.activity_id)
+            || !activity.comment.contains(&text)
+        {
+            continue;
+        }
+        used.insert(activity.activity_id.clone());
+        let repayment = PrivateLoanRepayment {
+            id: Uuid::new_v4().to_string(),
+            loan_id: loan.id.clone(),
+            activity_id: Some(activity.activity_id.clone()),
+            payment_date: activity.date,
+            amount: activity.amount,
+            source: LendingRepaymentSource::Matched,
+            created_at: Utc::now().naive_utc(),
+        };
+        repayments.push(repayment.clone());
+        matched.push(repayment);
+    }
+    matched
+}
+
+impl PrivateLoanService {
+    pub fn new(
+        repository: Arc<dyn PrivateLoanRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        activity_repository: Arc<dyn ActivityRepositoryTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            asset_service,
+            activity_service,
+            activity_repository,
+            market_data_service,
+            fx_service,
+        }
+    }
+
+    fn holding_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type == ACCOUNT_TYPE_LIABILITY
+            || account.account_type == ACCOUNT_TYPE_REAL_ESTATE
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Money lent cannot be held in {}",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+
+    fn lending_activity(loan: &NewPrivateLoan, account: &Account, asset_id: &str) -> NewActivity {
+        NewActivity {
+            id: None,
+            account_id: account.id.clone(),
+            asset_id: asset_id.to_string(),
+            activity_type: ACTIVITY_TYPE_ADD_HOLDING.to_string(),
+            activity_date: loan.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+            quantity: Some(Decimal::ONE),
+            unit_price: Some(loan.principal),
+            currency: account.currency.clone(),
+            fee: Some(Decimal::ZERO),
+            amount: None,
+            is_draft: false,
+            comment: Some(format!("Loan to {}", loan.borrower.trim())),
+        }
+    }
+
+    /// Names the loan's asset after the borrower and files it under fixed income
+    async fn update_asset_profile(&self, loan: &PrivateLoan) -> Result<()> {
+        let name = match &loan.platform {
+            Some(platform) => format!("{} ({})", loan.borrower, platform),
+            None => loan.borrower.clone(),
+        };
+        self.asset_service
+            .update_asset_profile(
+                &loan.asset_id,
+                UpdateAssetProfile {
+                    symbol: loan.asset_id.clone(),
+                    name: Some(name),
+                    sectors: None,
+                    countries: None,
+                    notes: loan.notes.clone().unwrap_or_default(),
+                    asset_sub_class: Some(loan.asset_sub_class().to_string()),
+                    asset_class: Some("Fixed Income".to_string()),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Quotes the loan afresh: the principal on the start date, the principal still owed
+    /// after each repayment, and nothing from today once written off
+    async fn requote(&self, loan: &PrivateLoan) -> Result<()> {
+        for quote in self
+            .market_data_service
+            .get_historical_quotes_for_symbol(&loan.asset_id)?
+        {
+            self.market_data_service.delete_quote(&quote.id).await?;
+        }
+        let currency = self
+            .account_repository
+            .get_by_id(&loan.account_id)?
+            .currency;
+        let today = Utc::now().date_naive();
+        let schedule = loan.schedule();
+        let repayments = self.repository.get_repayments(Some(&loan.id))?;
+
+        self.market_data_service
+            .add_quote(&loan_quote(
+                &loan.asset_id,
+                loan.start_date,
+                loan.principal,
+                &currency,
+            ))
+            .await?;
+        let dates: Vec<NaiveDate> = repayments
+            .iter()
+            .map(|r| r.payment_date)
+            .filter(|date| *date <= today)
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        for date in dates {
+            let paid: Vec<PrivateLoanRepayment> = repayments
+                .iter()
+                .filter(|r| r.payment_date <= date)
+                .cloned()
+                .collect();
+            self.market_data_service
+                .add_quote(&loan_quote(
+                    &loan.asset_id,
+                    date,
+                    outstanding_principal(loan, &schedule, &paid),
+                    &currency,
+                ))
+                .await?;
+        }
+        if loan.written_off {
+            self.market_data_service
+                .add_quote(&loan_quote(
+                    &loan.asset_id,
+                    today.max(loan.start_date),
+                    Decimal::ZERO,
+                    &currency,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn summary(&self, loan: PrivateLoan) -> Result<PrivateLoanSummary> {
+        let account = self.account_repository.get_by_id(&loan.account_id)?;
+        let repayments = self.repository.get_repayments(Some(&loan.id))?;
+        Ok(summarize_private_loan(
+            loan,
+            account.currency,
+            repayments,
+            Utc::now().date_naive(),
+        ))
+    }
+}
+
+#[async_trait]
+impl PrivateLoanServiceTrait for PrivateLoanService {
+    fn get_private_loans(&self) -> Result<Vec<PrivateLoan>> {
+        self.repository.get_loans()
+    }
+
+    fn get_private_loan_summaries(&self) -> Result<Vec<PrivateLoanSummary>> {
+        self.repository
+            .get_loans()?
+            .into_iter()
+            .map(|loan| self.summary(loan))
+            .collect()
+    }
+
+    fn get_private_loan_summary(&self, id: &str) -> Result<PrivateLoanSummary> {
+        self.summary(self.repository.get_loan(id)?)
+    }
+
+    async fn create_private_loan(&self, mut loan: NewPrivateLoan) -> Result<PrivateLoan> {
+        loan.validate()?;
+        let account = self.holding_account(&loan.account_id)?;
+
+        let id = loan
+            .id
+            .get_or_insert_with(|| Uuid::new_v4().to_string())
+            .clone();
+        let asset = self
+            .asset_service
+            .create_manual_asset(&private_loan_asset_symbol(&id), account.currency.clone())
+            .await?;
+        let activity = self
+            .activity_service
+            .create_activity(Self::lending_activity(&loan, &account, &asset.id))
+            .await?;
+
+        let created = self
+            .repository
+            .insert_loan(loan, asset.id, Some(activity.id))
+            .await?;
+        self.update_asset_profile(&created).await?;
+        self.requote(&created).await?;
+        Ok(created)
+    }
+
+    async fn update_private_loan(&self, id: &str, loan: NewPrivateLoan) -> Result<PrivateLoan> {
+        loan.validate()?;
+        let existing = self.repository.get_loan(id)?;
+        if existing.account_id != loan.account_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A loan cannot be moved to another account".to_string(),
+            )));
+        }
+        let account = self.holding_account(&existing.account_id)?;
+
+        if loan.start_date != existing.start_date
+            || loan.principal != existing.principal
+            || loan.borrower.trim() != existing.borrower
+        {
+            if let Some(activity_id) = existing.activity_id.clone() {
+                let activity = Self::lending_activity(&loan, &account, &existing.asset_id);
+                self.activity_service
+                    .update_activity(ActivityUpdate {
+                        id: activity_id,
+                        account_id: activity.account_id,
+                        asset_id: activity.asset_id,
+                        activity_type: activity.activity_type,
+                        activity_date: activity.activity_date,
+                        quantity: activity.quantity,
+                        unit_price: activity.unit_price,
+                        currency: activity.currency,
+                        fee: activity.fee,
+                        amount: activity.amount,
+                        is_draft: activity.is_draft,
+                        comment: activity.comment,
+                    })
+                    .await?;
+            }
+        }
+        let updated = self.repository.update_loan(id, loan).await?;
+        self.update_asset_profile(&updated).await?;
+        self.requote(&updated).await?;
+        Ok(updated)
+    }
+
+    async fn delete_private_loan(&self, id: &str) -> Result<usize> {
+        let loan = self.repository.get_loan(id)?;
+        let deleted = self.repository.delete_loan(id).await?;
+
+        if let Some(activity_id) = loan.activity_id.clone() {
+            self.activity_service.delete_activity(activity_id).await?;
+        }
+        for quote in self
+            .market_data_service
+            .get_historical_quotes_for_symbol(&loan.asset_id)?
+        {
+            self.market_data_service.delete_quote(&quote.id).await?;
+        }
+        match self.asset_service.delete_asset(&loan.asset_id).await {
+            Ok(()) | Err(Error::ConstraintViolation(_)) => Ok(deleted),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn record_private_loan_repayment(
+        &self,
+        repayment: NewPrivateLoanRepayment,
+    ) -> Result<PrivateLoanRepayment> {
+        repayment.validate()?;
+        let loan = self.repository.get_loan(&repayment.loan_id)?;
+        if repayment.payment_date < loan.start_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Repayment date is before the loan was made".to_string(),
+            )));
+        }
+        let recorded = PrivateLoanRepayment {
+            id: Uuid::new_v4().to_string(),
+            loan_id: loan.id.clone(),
+            activity_id: repayment.activity_id,
+            payment_date: repayment.payment_date,
+            amount: repayment.amount,
+            source: LendingRepaymentSource::Manual,
+            created_at: Utc::now().naive_utc(),
+        };
+        let recorded = self
+            .repository
+            .insert_repayments(vec![recorded])
+            .await?
+            .remove(0);
+        self.requote(&loan).await?;
+        Ok(recorded)
+    }
+
+    async fn delete_private_loan_repayment(&self, id: &str) -> Result<usize> {
+        let repayment = self
+            .repository
+            .get_repayments(None)?
+            .into_iter()
+            .find(|r| r.id == id)
+            .ok_or_else(|| {
+                Error::Validation(ValidationError::InvalidInput(format!(
+                    "Repayment {} not found",
+                    id
+                )))
+            })?;
+        let deleted = self.repository.delete_repayment(id).await?;
+        let loan = self.repository.get_loan(&repayment.loan_id)?;
+        self.requote(&loan).await?;
+        Ok(deleted)
+    }
+
+    async fn match_private_loan_repayments(&self) -> Result<PrivateLoanMatchResult> {
+        let loans: Vec<PrivateLoan> = self
+            .repository
+            .get_loans()?
+            .into_iter()
+            .filter(|loan| !loan.written_off)
+            .collect();
+        if loans.is_empty() {
+            return Ok(PrivateLoanMatchResult::default());
+        }
+        let existing = self.repository.get_repayments(None)?;
+        let mut used: HashSet<String> = existing
+            .iter()
+            .filter_map(|r| r.activity_id.clone())
+            .collect();
+
+        let mut deposits: Vec<_> = self
+            .activity_repository
+            .get_activities()?
+            .into_iter()
+            .filter(|a| {
+                !a.is_draft
+                    && (a.activity_type == ACTIVITY_TYPE_DEPOSIT
+                        || a.activity_type == ACTIVITY_TYPE_TRANSFER_IN)
+                    && a.comment.is_some()
+            })
+            .collect();
+        deposits.sort_by_key(|a| a.activity_date);
+
+        let mut matched = Vec::new();
+        for loan in &loans {
+            let currency = self
+                .account_repository
+                .get_by_id(&loan.account_id)?
+                .currency;
+            let received: Vec<ReceivedActivity> = deposits
+                .iter()
+                .map(|a| {
+                    let date = a.activity_date.date_naive();
+                    let amount = a
+                        .amount
+                        .filter(|amount| !amount.is_zero())
+                        .unwrap_or(a.quantity * a.unit_price);
+                    ReceivedActivity {
+                        activity_id: a.id.clone(),
+                        date,
+                        amount: convert_or_keep(
+                            self.fx_service.as_ref(),
+                            amount,
+                            &a.currency,
+                            &currency,
+                            Some(date),
+                        ),
+                        comment: a.comment.clone().unwrap_or_default().to_lowercase(),
+                    }
+                })
+                .collect();
+            let paid: Vec<PrivateLoanRepayment> = existing
+                .iter()
+                .filter(|r| r.loan_id == loan.id)
+                .cloned()
+                .collect();
+            let repayments = match_repayments(loan, &paid, &received, &mut used);
+            if repayments.is_empty() {
+                continue;
+            }
+            let repayments = self.repository.insert_repayments(repayments).await?;
+            self.requote(loan).await?;
+            matched.extend(repayments);
+        }
+        Ok(PrivateLoanMatchResult {
+            matched: matched.len(),
+            repayments: matched,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::private_loans::LendingRepaymentPlan;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn received(id: &str, day: &str, amount: Decimal, comment: &str) -> ReceivedActivity {
+        ReceivedActivity {
+            activity_id: id.to_string(),
+            date: date(day),
+            amount,
+            comment: comment.to_lowercase(),
+        }
+    }
+
+    #[test]
+    fn deposits_naming_the_borrower_repay_until_the_principal_is_back() {
+        let now = Utc::now().naive_utc();
+        let loan = PrivateLoan {
+            id: "loan".to_string(),
+            account_id: "cash".to_string(),
+            asset_id: private_loan_asset_symbol("loan"),
+            activity_id: None,
+            borrower: "Tuan".to_string(),
+            platform: None,
+            principal: dec!(50_000_000),
+            interest_rate: Decimal::ZERO,
+            repayment_plan: LendingRepaymentPlan::Installment,
+            term_months: 2,
+            start_date: date("2026-03-01"),
+            match_pattern: Some("TUAN TRA NO".to_string()),
+            written_off: false,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let deposits = vec![
+            // Before the loan was made
+            received("early", "2026-02-20", dec!(25_000_000), "Tuan tra no"),
+            received("salary", "2026-03-05", dec!(30_000_000), "Luong thang 3"),
+            received(
+                "first",
+                "2026-04-01",
+                dec!(25_000_000),
+                "TUAN TRA NO thang 4",
+            ),
+            received(
+                "second",
+                "2026-05-02",
+                dec!(25_000_000),
+                "tuan tra no thang 5",
+            ),
+            // Paid off by then
+            received("third", "2026-06-01", dec!(1_000_000), "tuan tra no"),
+        ];
+        let mut used = HashSet::new();
+
+        let matched = match_repayments(&loan, &[], &deposits, &mut used);
+        let ids: Vec<_> = matched
+            .iter()
+            .map(|r| r.activity_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["first", "second"]);
+        assert!(matched
+            .iter()
+            .all(|r| r.source == LendingRepaymentSource::Matched));
+
+        // Already used deposits are not matched again
+        assert!(match_repayments(&loan, &[], &deposits[..4], &mut used).is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+
+use super::private_loans_model::{
+    NewPrivateLoan, NewPrivateLoanRepayment, PrivateLoan, PrivateLoanMatchResult,
+    PrivateLoanRepayment, PrivateLoanSummary,
+};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait PrivateLoanRepositoryTrait: Send + Sync {
+    fn get_loans(&self) -> Result<Vec<PrivateLoan>>;
+    fn get_loan(&self, id: &str) -> Result<PrivateLoan>;
+    async fn insert_loan(
+        &self,
+        loan: NewPrivateLoan,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<PrivateLoan>;
+    async fn update_loan(&self, id: &str, loan: NewPrivateLoan) -> Result<PrivateLoan>;
+    async fn delete_loan(&self, id: &str) -> Result<usize>;
+    fn get_repayments(&self, loan_id: Option<&str>) -> Result<Vec<PrivateLoanRepayment>>;
+    async fn insert_repayments(
+        &self,
+        repayments: Vec<PrivateLoanRepayment>,
+    ) -> Result<Vec<PrivateLoanRepayment>>;
+    async fn delete_repayment(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait PrivateLoanServiceTrait: Send + Sync {
+    fn get_private_loans(&self) -> Result<Vec<PrivateLoan>>;
+    /// Every loan with its schedule, repayments and risk status as of today
+    fn get_private_loan_summaries(&self) -> Result<Vec<PrivateLoanSummary>>;
+    fn get_private_loan_summary(&self, id: &str) -> Result<PrivateLoanSummary>;
+    /// Adds the loan to its account as a manual asset valued at the outstanding principal
+    async fn create_private_loan(&self, loan: NewPrivateLoan) -> Result<PrivateLoan>;
+    async fn update_private_loan(&self, id: &str, loan: NewPrivateLoan) -> Result<PrivateLoan>;
+    async fn delete_private_loan(&self, id: &str) -> Result<usize>;
+    async fn record_private_loan_repayment(
+        &self,
+        repayment: NewPrivateLoanRepayment,
+    ) -> Result<PrivateLoanRepayment>;
+    async fn delete_private_loan_repayment(&self, id: &str) -> Result<usize>;
+    /// Records deposits whose comment names a borrower as repayments of their loan, e.g.
+    /// after an import
+    async fn match_private_loan_repayments(&self) -> Result<PrivateLoanMatchResult>;
+}
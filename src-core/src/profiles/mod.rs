@@ -0,0 +1,8 @@
+mod profiles_manager;
+mod profiles_model;
+
+pub use profiles_manager::ProfileManager;
+pub use profiles_model::{
+    is_read_only_command, Profile, ProfileRegistry, ReadOnlyViolation, DEFAULT_PROFILE_ID,
+    READ_ONLY_ERROR_CODE,
+};
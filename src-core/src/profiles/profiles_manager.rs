@@ -0,0 +1,257 @@
+use log::{info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use uuid::Uuid;
+
+use super::profiles_model::{Profile, ProfileRegistry, DEFAULT_PROFILE_ID};
+use crate::errors::{Error, Result, ValidationError};
+
+const REGISTRY_FILE: &str = "profiles.json";
+const PROFILES_DIR: &str = "profiles";
+
+/// Keeps track of the profiles in an installation and which one is active.
+/// The registry is a small JSON file next to the default database so it can be
+/// read before any database is opened.
+pub struct ProfileManager {
+    app_data_dir: PathBuf,
+    registry: RwLock<ProfileRegistry>,
+}
+
+impl ProfileManager {
+    /// Loads `profiles.json`, creating it with the default profile on first run
+    pub fn load(app_data_dir: &str) -> Result<Self> {
+        let app_data_dir = PathBuf::from(app_data_dir);
+        let registry_path = app_data_dir.join(REGISTRY_FILE);
+
+        let registry = if registry_path.exists() {
+            let content = fs::read_to_string(&registry_path)?;
+            serde_json::from_str::<ProfileRegistry>(&content)?
+        } else {
+            let registry = ProfileRegistry::default();
+            fs::create_dir_all(&app_data_dir)?;
+            fs::write(&registry_path, serde_json::to_string_pretty(&registry)?)?;
+            registry
+        };
+
+        let manager = Self {
+            app_data_dir,
+            registry: RwLock::new(registry),
+        };
+
+        // Fall back to the default profile if the active one was removed by hand
+        if manager.find(&manager.registry.read().unwrap().active_profile_id).is_none() {
+            warn!("Active profile missing from registry, falling back to default");
+            manager.registry.write().unwrap().active_profile_id = DEFAULT_PROFILE_ID.to_string();
+            manager.save()?;
+        }
+
+        Ok(manager)
+    }
+
+    pub fn list_profiles(&self) -> Vec<Profile> {
+        self.registry.read().unwrap().profiles.clone()
+    }
+
+    pub fn active_profile(&self) -> Profile {
+        let active_id = self.registry.read().unwrap().active_profile_id.clone();
+        self.find(&active_id)
+            .expect("active profile is validated on load and on every switch")
+    }
+
+    /// Absolute directory holding the profile's `app.db`
+    pub fn profile_data_dir(&self, profile: &Profile) -> String {
+        let dir = if profile.data_dir.is_empty() {
+            self.app_data_dir.clone()
+        } else {
+            self.app_data_dir.join(&profile.data_dir)
+        };
+        dir.to_string_lossy().to_string()
+    }
+
+    pub fn create_profile(&self, name: &str) -> Result<Profile> {
+        let name = validate_name(name)?;
+        let id = Uuid::new_v4().to_string();
+        let data_dir = Path::new(PROFILES_DIR).join(&id).to_string_lossy().to_string();
+        fs::create_dir_all(self.app_data_dir.join(&data_dir))?;
+
+        let profile = Profile {
+            id,
+            name,
+            data_dir,
+            created_at: chrono::Utc::now().naive_utc(),
+            read_only: false,
+        };
+        self.registry.write().unwrap().profiles.push(profile.clone());
+        self.save()?;
+        info!("Created profile {} ({})", profile.name, profile.id);
+        Ok(profile)
+    }
+
+    pub fn rename_profile(&self, profile_id: &str, name: &str) -> Result<Profile> {
+        let name = validate_name(name)?;
+        let renamed = {
+            let mut registry = self.registry.write().unwrap();
+            let profile = registry
+                .profiles
+                .iter_mut()
+                .find(|p| p.id == profile_id)
+                .ok_or_else(|| not_found(profile_id))?;
+            profile.name = name;
+            profile.clone()
+        };
+        self.save()?;
+        Ok(renamed)
+    }
+
+    /// Turns viewer mode on or off for a profile
+    pub fn set_read_only(&self, profile_id: &str, read_only: bool) -> Result<Profile> {
+        let updated = {
+            let mut registry = self.registry.write().unwrap();
+            let profile = registry
+                .profiles
+                .iter_mut()
+                .find(|p| p.id == profile_id)
+                .ok_or_else(|| not_found(profile_id))?;
+            profile.read_only = read_only;
+            profile.clone()
+        };
+        self.save()?;
+        info!(
+            "Profile {} is now {}",
+            updated.id,
+            if read_only { "read-only" } else { "writable" }
+        );
+        Ok(updated)
+    }
+
+    /// Whether viewer mode is on for the active profile
+    pub fn is_read_only(&self) -> bool {
+        self.active_profile().read_only
+    }
+
+    /// Marks a profile as active. Callers are responsible for rebuilding services.
+    pub fn set_active_profile(&self, profile_id: &str) -> Result<Profile> {
+        let profile = self.find(profile_id).ok_or_else(|| not_found(profile_id))?;
+        self.registry.write().unwrap().active_profile_id = profile.id.clone();
+        self.save()?;
+        Ok(profile)
+    }
+
+    /// Removes a profile and its database. The default and the active profile cannot be deleted.
+    pub fn delete_profile(&self, profile_id: &str) -> Result<()> {
+        if profile_id == DEFAULT_PROFILE_ID {
+            return Err(invalid("The default profile cannot be deleted"));
+        }
+        if self.active_profile().id == profile_id {
+            return Err(invalid("Switch to another profile before deleting this one"));
+        }
+        let profile = self.find(profile_id).ok_or_else(|| not_found(profile_id))?;
+
+        self.registry
+            .write()
+            .unwrap()
+            .profiles
+            .retain(|p| p.id != profile_id);
+        self.save()?;
+
+        let dir = self.app_data_dir.join(&profile.data_dir);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        info!("Deleted profile {} ({})", profile.name, profile.id);
+        Ok(())
+    }
+
+    fn find(&self, profile_id: &str) -> Option<Profile> {
+        self.registry
+            .read()
+            .unwrap()
+            .profiles
+            .iter()
+            .find(|p| p.id == profile_id)
+            .cloned()
+    }
+
+    fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&*self.registry.read().unwrap())?;
+        fs::write(self.app_data_dir.join(REGISTRY_FILE), content)?;
+        Ok(())
+    }
+}
+
+fn validate_name(name: &str) -> Result<String> {
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        return Err(invalid("Profile name cannot be empty"));
+    }
+    Ok(trimmed.to_string())
+}
+
+fn invalid(message: &str) -> Error {
+    Error::Validation(ValidationError::InvalidInput(message.to_string()))
+}
+
+fn not_found(profile_id: &str) -> Error {
+    invalid(&format!("Profile {} not found", profile_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_app_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("wealthvn-profiles-{}", Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_default_profile_created_on_first_load() {
+        let dir = temp_app_dir();
+        let manager = ProfileManager::load(dir.to_str().unwrap()).unwrap();
+
+        let active = manager.active_profile();
+        assert_eq!(active.id, DEFAULT_PROFILE_ID);
+        assert_eq!(manager.profile_data_dir(&active), dir.to_string_lossy());
+        assert!(dir.join(REGISTRY_FILE).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_switch_and_delete_profile() {
+        let dir = temp_app_dir();
+        let manager = ProfileManager::load(dir.to_str().unwrap()).unwrap();
+
+        let parents = manager.create_profile("  Parents' money ").unwrap();
+        assert_eq!(parents.name, "Parents' money");
+        assert!(Path::new(&manager.profile_data_dir(&parents)).is_dir());
+
+        manager.set_active_profile(&parents.id).unwrap();
+        assert!(manager.delete_profile(&parents.id).is_err());
+
+        // Registry survives a reload
+        let reloaded = ProfileManager::load(dir.to_str().unwrap()).unwrap();
+        assert_eq!(reloaded.active_profile().id, parents.id);
+        assert_eq!(reloaded.list_profiles().len(), 2);
+
+        reloaded.set_active_profile(DEFAULT_PROFILE_ID).unwrap();
+        reloaded.delete_profile(&parents.id).unwrap();
+        assert_eq!(reloaded.list_profiles().len(), 1);
+        assert!(reloaded.delete_profile(DEFAULT_PROFILE_ID).is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_only_flag_persists() {
+        let dir = temp_app_dir();
+        let manager = ProfileManager::load(dir.to_str().unwrap()).unwrap();
+        assert!(!manager.is_read_only());
+
+        manager.set_read_only(DEFAULT_PROFILE_ID, true).unwrap();
+        let reloaded = ProfileManager::load(dir.to_str().unwrap()).unwrap();
+        assert!(reloaded.is_read_only());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
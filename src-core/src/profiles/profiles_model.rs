@@ -0,0 +1,101 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+/// The profile backed by the database that lives directly in the app data dir.
+/// Existing installations keep their data under this profile.
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+/// An independent portfolio with its own SQLite database (and therefore its own settings)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    pub name: String,
+    /// Directory holding `app.db`, relative to the app data dir ("" for the default profile)
+    pub data_dir: String,
+    pub created_at: NaiveDateTime,
+    /// Viewer mode: write commands are rejected while this profile is active
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+/// Contents of `profiles.json`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileRegistry {
+    pub active_profile_id: String,
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for ProfileRegistry {
+    fn default() -> Self {
+        Self {
+            active_profile_id: DEFAULT_PROFILE_ID.to_string(),
+            profiles: vec![Profile {
+                id: DEFAULT_PROFILE_ID.to_string(),
+                name: "Personal".to_string(),
+                data_dir: String::new(),
+                created_at: chrono::Utc::now().naive_utc(),
+                read_only: false,
+            }],
+        }
+    }
+}
+
+/// Error code returned to the frontend when viewer mode blocks a command
+pub const READ_ONLY_ERROR_CODE: &str = "READ_ONLY_MODE";
+
+/// Command name prefixes that only read data and stay available in viewer mode
+const READ_ONLY_COMMAND_PREFIXES: &[&str] = &[
+    "get_", "list_", "search_", "calculate_", "check_", "load_", "validate_", "fetch_", "is_",
+];
+
+/// Commands that do not match a read prefix but are still safe in viewer mode.
+/// `set_profile_read_only` verifies the passphrase itself before leaving viewer mode.
+const READ_ONLY_EXTRA_COMMANDS: &[&str] = &["unlock_app", "lock_app", "set_profile_read_only"];
+
+/// Returns true when the command may run while the active profile is read-only.
+/// Anything not recognised as a read is treated as a write.
+pub fn is_read_only_command(command: &str) -> bool {
+    READ_ONLY_EXTRA_COMMANDS.contains(&command)
+        || READ_ONLY_COMMAND_PREFIXES
+            .iter()
+            .any(|prefix| command.starts_with(prefix))
+}
+
+/// Structured rejection for commands blocked by viewer mode
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOnlyViolation {
+    pub code: String,
+    pub command: String,
+    pub message: String,
+}
+
+impl ReadOnlyViolation {
+    pub fn new(command: &str) -> Self {
+        Self {
+            code: READ_ONLY_ERROR_CODE.to_string(),
+            command: command.to_string(),
+            message: format!("'{}' is not allowed in read-only mode", command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_commands_are_not_read_only() {
+        assert!(is_read_only_command("get_holdings"));
+        assert!(is_read_only_command("calculate_performance_history"));
+        assert!(is_read_only_command("set_profile_read_only"));
+
+        assert!(!is_read_only_command("create_activity"));
+        assert!(!is_read_only_command("delete_account"));
+        assert!(!is_read_only_command("switch_profile"));
+        assert!(!is_read_only_command("set_setting"));
+        assert!(!is_read_only_command("restore_database"));
+    }
+}
@@ -0,0 +1,6 @@
+mod query_cache_service;
+
+pub use query_cache_service::{
+    QueryCache, RESOURCE_ACCOUNT, RESOURCE_ACTIVITY, RESOURCE_ALLOCATION, RESOURCE_GOAL,
+    RESOURCE_PORTFOLIO,
+};
@@ -0,0 +1,168 @@
+use log::debug;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+use crate::errors::{Error, Result};
+
+/// Resource types a cached query can depend on, as carried by resource-changed events
+pub const RESOURCE_ACCOUNT: &str = "account";
+pub const RESOURCE_ACTIVITY: &str = "activity";
+pub const RESOURCE_ALLOCATION: &str = "allocation";
+pub const RESOURCE_GOAL: &str = "goal";
+/// Not a user-edited resource: raised once holdings and valuations have been recalculated
+pub const RESOURCE_PORTFOLIO: &str = "portfolio";
+
+struct CachedQuery {
+    value: String,
+    depends_on: Vec<String>,
+}
+
+/// Results of expensive read commands, keyed by command and serialized parameters, so
+/// navigating back and forth does not re-run identical queries. An entry is dropped as soon
+/// as a resource it depends on changes; owners rebuild the cache when the profile changes.
+#[derive(Default)]
+pub struct QueryCache {
+    entries: RwLock<HashMap<(String, String), CachedQuery>>,
+    /// Bumped by every invalidation, so a result computed across one is not stored
+    generation: AtomicU64,
+}
+
+impl QueryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached result of `command` for `params`, or runs `load` and caches its
+    /// result until one of `depends_on` changes. Errors are never cached.
+    pub async fn get_or_load<T, P, F, Fut>(
+        &self,
+        command: &str,
+        params: &P,
+        depends_on: &[&str],
+        load: F,
+    ) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        P: Serialize + ?Sized,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let params = serde_json::to_string(params).map_err(|e| Error::Unexpected(e.to_string()))?;
+        let key = (command.to_string(), params);
+        if let Some(entry) = self.entries.read().unwrap().get(&key) {
+            if let Ok(value) = serde_json::from_str(&entry.value) {
+                return Ok(value);
+            }
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        let value = load().await?;
+        let serialized =
+            serde_json::to_string(&value).map_err(|e| Error::Unexpected(e.to_string()))?;
+        let mut entries = self.entries.write().unwrap();
+        if self.generation.load(Ordering::Acquire) == generation {
+            entries.insert(
+                key,
+                CachedQuery {
+                    value: serialized,
+                    depends_on: depends_on.iter().map(|r| r.to_string()).collect(),
+                },
+            );
+        }
+        Ok(value)
+    }
+
+    /// Drops every entry depending on `resource_type`; returns how many
+    pub fn invalidate(&self, resource_type: &str) -> usize {
+        let mut entries = self.entries.write().unwrap();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        let before = entries.len();
+        entries.retain(|_, entry| !entry.depends_on.iter().any(|r| r == resource_type));
+        let dropped = before - entries.len();
+        if dropped > 0 {
+            debug!(
+                "Dropped {} cached queries after a {} change",
+                dropped, resource_type
+            );
+        }
+        dropped
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.write().unwrap();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    async fn load_counted(cache: &QueryCache, calls: &AtomicUsize, account: &str) -> Vec<String> {
+        cache
+            .get_or_load("get_holdings", account, &[RESOURCE_ACTIVITY], || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(vec![account.to_string()])
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn identical_queries_are_served_until_a_dependency_changes() {
+        let cache = QueryCache::new();
+        let calls = AtomicUsize::new(0);
+
+        assert_eq!(load_counted(&cache, &calls, "a").await, vec!["a"]);
+        assert_eq!(load_counted(&cache, &calls, "a").await, vec!["a"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Other parameters are another entry
+        load_counted(&cache, &calls, "b").await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        assert_eq!(cache.invalidate(RESOURCE_GOAL), 0);
+        load_counted(&cache, &calls, "a").await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        assert_eq!(cache.invalidate(RESOURCE_ACTIVITY), 2);
+        load_counted(&cache, &calls, "a").await;
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn failures_and_results_raced_by_an_invalidation_are_not_cached() {
+        let cache = QueryCache::new();
+        let failed: Result<u32> = cache
+            .get_or_load("count", &(), &[RESOURCE_GOAL], || async {
+                Err(Error::Unexpected("boom".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+        assert!(cache.is_empty());
+
+        let value = cache
+            .get_or_load("count", &(), &[RESOURCE_GOAL], || async {
+                cache.invalidate(RESOURCE_ACCOUNT);
+                Ok(1u32)
+            })
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+        assert!(cache.is_empty());
+    }
+}
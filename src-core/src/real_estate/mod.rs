@@ -0,0 +1,13 @@
+mod real_estate_model;
+mod real_estate_repository;
+mod real_estate_service;
+mod real_estate_traits;
+
+pub use real_estate_model::{
+    property_asset_symbol, NewProperty, NewPropertyAppraisal, Property, PropertyAppraisal,
+    PropertySummary,
+};
+pub(crate) use real_estate_repository::load_property_mortgages;
+pub use real_estate_repository::PropertyRepository;
+pub use real_estate_service::PropertyService;
+pub use real_estate_traits::{PropertyRepositoryTrait, PropertyServiceTrait};
@@ -0,0 +1,222 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+
+/// Symbol of the manual asset a property is held as
+pub fn property_asset_symbol(property_id: &str) -> String {
+    format!("PROPERTY-{}", property_id)
+}
+
+/// Database row for `properties`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::properties)]
+pub struct PropertyDB {
+    pub id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub activity_id: Option<String>,
+    pub name: String,
+    pub address: Option<String>,
+    pub purchase_date: String,
+    pub purchase_price: String,
+    pub transaction_costs: String,
+    pub mortgage_account_id: Option<String>,
+    pub rental_income_source_id: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A house, apartment or plot held by a real-estate account. Amounts are in the account
+/// currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Property {
+    pub id: String,
+    pub account_id: String,
+    /// Manual asset whose quotes value the property
+    pub asset_id: String,
+    /// Activity that added the property to the account
+    pub activity_id: Option<String>,
+    pub name: String,
+    pub address: Option<String>,
+    pub purchase_date: NaiveDate,
+    pub purchase_price: Decimal,
+    /// Registration fee, notary, brokerage and other costs paid on top of the price
+    pub transaction_costs: Decimal,
+    /// Liability account holding the mortgage
+    pub mortgage_account_id: Option<String>,
+    /// Income source the rent is received as
+    pub rental_income_source_id: Option<String>,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Property {
+    /// Price paid plus the costs of buying
+    pub fn total_cost(&self) -> Decimal {
+        self.purchase_price + self.transaction_costs
+    }
+}
+
+impl TryFrom<PropertyDB> for Property {
+    type Error = Error;
+
+    fn try_from(db: PropertyDB) -> Result<Self> {
+        Ok(Property {
+            id: db.id,
+            account_id: db.account_id,
+            asset_id: db.asset_id,
+            activity_id: db.activity_id,
+            name: db.name,
+            address: db.address,
+            purchase_date: parse_forecast_date(&db.purchase_date)?,
+            purchase_price: Decimal::from_str(&db.purchase_price)?,
+            transaction_costs: Decimal::from_str(&db.transaction_costs)?,
+            mortgage_account_id: db.mortgage_account_id,
+            rental_income_source_id: db.rental_income_source_id,
+            notes: db.notes,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a property
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewProperty {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub name: String,
+    pub address: Option<String>,
+    pub purchase_date: NaiveDate,
+    pub purchase_price: Decimal,
+    #[serde(default)]
+    pub transaction_costs: Decimal,
+    pub mortgage_account_id: Option<String>,
+    pub rental_income_source_id: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl NewProperty {
+    pub fn validate(&self) -> Result<()> {
+        if self.account_id.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "accountId".to_string(),
+            )));
+        }
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.purchase_price <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Purchase price must be positive".to_string(),
+            )));
+        }
+        if self.transaction_costs < Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Transaction costs cannot be negative".to_string(),
+            )));
+        }
+        if self.mortgage_account_id.as_deref() == Some(self.account_id.as_str()) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A property cannot be its own mortgage account".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Database row for `property_appraisals`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::property_appraisals)]
+pub struct PropertyAppraisalDB {
+    pub id: String,
+    pub property_id: String,
+    pub appraisal_date: String,
+    pub value: String,
+    pub source: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Market value of a property on a date, e.g. from a bank valuation or recent sales nearby
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertyAppraisal {
+    pub id: String,
+    pub property_id: String,
+    pub appraisal_date: NaiveDate,
+    pub value: Decimal,
+    pub source: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl TryFrom<PropertyAppraisalDB> for PropertyAppraisal {
+    type Error = Error;
+
+    fn try_from(db: PropertyAppraisalDB) -> Result<Self> {
+        Ok(PropertyAppraisal {
+            id: db.id,
+            property_id: db.property_id,
+            appraisal_date: parse_forecast_date(&db.appraisal_date)?,
+            value: Decimal::from_str(&db.value)?,
+            source: db.source,
+            created_at: db.created_at,
+        })
+    }
+}
+
+/// Input for recording an appraisal; a second one on the same date replaces the first
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPropertyAppraisal {
+    pub property_id: String,
+    pub appraisal_date: NaiveDate,
+    pub value: Decimal,
+    pub source: Option<String>,
+}
+
+impl NewPropertyAppraisal {
+    pub fn validate(&self) -> Result<()> {
+        if self.value <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Appraised value must be positive".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A property with its value, equity and rental yield as of today
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PropertySummary {
+    pub property: Property,
+    pub currency: String,
+    /// Latest appraisal, or the purchase price before the first one
+    pub current_value: Decimal,
+    pub valued_on: NaiveDate,
+    /// Oldest first
+    pub appraisals: Vec<PropertyAppraisal>,
+    /// Balance still owed on the mortgage
+    pub mortgage_balance: Decimal,
+    /// Value less the mortgage balance
+    pub equity: Decimal,
+    /// Mortgage balance as a percentage of the value
+    pub loan_to_value: Decimal,
+    /// Value less the price and costs of buying
+    pub unrealized_gain: Decimal,
+    /// Expected rent over a year from the linked income source
+    pub annual_rent: Option<Decimal>,
+    /// Annual rent as a percentage of the value
+    pub gross_yield: Option<Decimal>,
+}
@@ -0,0 +1,341 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::real_estate_model::{
+    NewProperty, NewPropertyAppraisal, Property, PropertyAppraisal, PropertyAppraisalDB, PropertyDB,
+};
+use super::real_estate_traits::PropertyRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::goals::monthly_summaries::clear_goal_progress;
+use crate::loans::{
+    amortize, find_loan_for_account, load_prepayments, load_rate_resets, outstanding_on, Loan,
+    LoanPrepayment, LoanScheduleRow,
+};
+use crate::schema::{properties, property_appraisals};
+use crate::utils::text_utils::trimmed;
+
+pub struct PropertyRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl PropertyRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        PropertyRepository { pool, writer }
+    }
+}
+
+/// The mortgage on a property with its schedule, to look up the balance on any date
+pub(crate) struct PropertyMortgage {
+    loan: Loan,
+    rows: Vec<LoanScheduleRow>,
+    prepayments: Vec<LoanPrepayment>,
+}
+
+impl PropertyMortgage {
+    pub(crate) fn balance_on(&self, date: NaiveDate) -> Decimal {
+        outstanding_on(&self.loan, &self.rows, &self.prepayments, date)
+    }
+}
+
+/// Mortgages of the properties held by the given accounts, by property account id. Accounts
+/// without a property, or whose property has no mortgage with a loan set up, are left out.
+pub(crate) fn load_property_mortgages(
+    conn: &mut SqliteConnection,
+    account_ids: &[String],
+) -> Result<HashMap<String, PropertyMortgage>> {
+    let linked: Vec<(String, Option<String>)> = properties::table
+        .filter(properties::account_id.eq_any(account_ids))
+        .filter(properties::mortgage_account_id.is_not_null())
+        .select((properties::account_id, properties::mortgage_account_id))
+        .load(conn)?;
+
+    let mut mortgages = HashMap::new();
+    for (account_id, mortgage_account_id) in linked {
+        let Some(mortgage_account_id) = mortgage_account_id else {
+            continue;
+        };
+        if let Some(loan) = find_loan_for_account(conn, &mortgage_account_id)? {
+            let prepayments = load_prepayments(conn, Some(&loan.id))?;
+            let resets = load_rate_resets(conn, Some(&loan.id))?;
+            let rows = amortize(&loan, &prepayments, &resets);
+            mortgages.insert(
+                account_id,
+                PropertyMortgage {
+                    loan,
+                    rows,
+                    prepayments,
+                },
+            );
+        }
+    }
+    Ok(mortgages)
+}
+
+#[async_trait]
+impl PropertyRepositoryTrait for PropertyRepository {
+    fn get_properties(&self) -> Result<Vec<Property>> {
+        let mut conn = get_connection(&self.pool)?;
+        properties::table
+            .order(properties::purchase_date.asc())
+            .load::<PropertyDB>(&mut conn)?
+            .into_iter()
+            .map(Property::try_from)
+            .collect()
+    }
+
+    fn get_property(&self, id: &str) -> Result<Property> {
+        let mut conn = get_connection(&self.pool)?;
+        properties::table
+            .find(id)
+            .first::<PropertyDB>(&mut conn)?
+            .try_into()
+    }
+
+    fn get_property_for_account(&self, account_id: &str) -> Result<Option<Property>> {
+        let mut conn = get_connection(&self.pool)?;
+        properties::table
+            .filter(properties::account_id.eq(account_id))
+            .first::<PropertyDB>(&mut conn)
+            .optional()?
+            .map(Property::try_from)
+            .transpose()
+    }
+
+    async fn insert_property(
+        &self,
+        property: NewProperty,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<Property> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Property> {
+                let now = Utc::now().naive_utc();
+                let record = PropertyDB {
+                    id: property.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: property.account_id,
+                    asset_id,
+                    activity_id,
+                    name: property.name.trim().to_string(),
+                    address: trimmed(property.address),
+                    purchase_date: property
+                        .purchase_date
+                        .format(FORECAST_DATE_FORMAT)
+                        .to_string(),
+                    purchase_price: property.purchase_price.to_string(),
+                    transaction_costs: property.transaction_costs.to_string(),
+                    mortgage_account_id: property.mortgage_account_id,
+                    rental_income_source_id: property.rental_income_source_id,
+                    notes: trimmed(property.notes),
+                    created_at: now,
+                    updated_at: now,
+                };
+
+                // Goals count a mortgaged property at its equity, so the link moves their progress
+                clear_goal_progress(conn, None, None)?;
+                diesel::insert_into(properties::table)
+                    .values(&record)
+                    .get_result::<PropertyDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_property(&self, id: &str, property: NewProperty) -> Result<Property> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Property> {
+                clear_goal_progress(conn, None, None)?;
+                diesel::update(properties::table.find(id_owned))
+                    .set((
+                        properties::name.eq(property.name.trim().to_string()),
+                        properties::address.eq(trimmed(property.address)),
+                        properties::purchase_date.eq(property
+                            .purchase_date
+                            .format(FORECAST_DATE_FORMAT)
+                            .to_string()),
+                        properties::purchase_price.eq(property.purchase_price.to_string()),
+                        properties::transaction_costs.eq(property.transaction_costs.to_string()),
+                        properties::mortgage_account_id.eq(property.mortgage_account_id),
+                        properties::rental_income_source_id.eq(property.rental_income_source_id),
+                        properties::notes.eq(trimmed(property.notes)),
+                        properties::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<PropertyDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_property(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                clear_goal_progress(conn, None, None)?;
+                Ok(diesel::delete(properties::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    fn get_appraisals(&self, property_id: Option<&str>) -> Result<Vec<PropertyAppraisal>> {
+        let mut conn = get_connection(&self.pool)?;
+        let mut query = property_appraisals::table.into_boxed();
+        if let Some(property_id) = property_id {
+            query = query.filter(property_appraisals::property_id.eq(property_id.to_string()));
+        }
+        query
+            .order(property_appraisals::appraisal_date.asc())
+            .load::<PropertyAppraisalDB>(&mut conn)?
+            .into_iter()
+            .map(PropertyAppraisal::try_from)
+            .collect()
+    }
+
+    fn get_appraisal(&self, id: &str) -> Result<PropertyAppraisal> {
+        let mut conn = get_connection(&self.pool)?;
+        property_appraisals::table
+            .find(id)
+            .first::<PropertyAppraisalDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn upsert_appraisal(&self, appraisal: NewPropertyAppraisal) -> Result<PropertyAppraisal> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<PropertyAppraisal> {
+                    let appraisal_date = appraisal
+                        .appraisal_date
+                        .format(FORECAST_DATE_FORMAT)
+                        .to_string();
+                    diesel::delete(
+                        property_appraisals::table
+                            .filter(property_appraisals::property_id.eq(&appraisal.property_id))
+                            .filter(property_appraisals::appraisal_date.eq(&appraisal_date)),
+                    )
+                    .execute(conn)?;
+
+                    let record = PropertyAppraisalDB {
+                        id: Uuid::new_v4().to_string(),
+                        property_id: appraisal.property_id,
+                        appraisal_date,
+                        value: appraisal.value.to_string(),
+                        source: trimmed(appraisal.source),
+                        created_at: Utc::now().naive_utc(),
+                    };
+                    diesel::insert_into(property_appraisals::table)
+                        .values(&record)
+                        .get_result::<PropertyAppraisalDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn delete_appraisal(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(property_appraisals::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::run_migrations;
+    use crate::schema::{accounts, loans};
+    use rust_decimal_macros::dec;
+
+    fn insert_account(conn: &mut SqliteConnection, id: &str, account_type: &str) {
+        let now = Utc::now().naive_utc();
+        diesel::insert_into(accounts::table)
+            .values((
+                accounts::id.eq(id),
+                accounts::name.eq(id),
+                accounts::account_type.eq(account_type),
+                accounts::currency.eq("VND"),
+                accounts::is_default.eq(false),
+                accounts::is_active.eq(true),
+                accounts::created_at.eq(now),
+                accounts::updated_at.eq(now),
+            ))
+            .execute(conn)
+            .unwrap();
+    }
+
+    #[test]
+    fn mortgaged_property_carries_its_loan_balance() {
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+            .unwrap();
+        run_migrations(&pool).unwrap();
+        let conn = &mut pool.get().unwrap();
+        insert_account(conn, "home", "REAL_ESTATE");
+        insert_account(conn, "land", "REAL_ESTATE");
+        insert_account(conn, "mortgage", "LIABILITY");
+        let now = Utc::now().naive_utc();
+        diesel::insert_into(loans::table)
+            .values((
+                loans::id.eq("loan"),
+                loans::account_id.eq("mortgage"),
+                loans::principal.eq("1200000000"),
+                loans::start_date.eq("2026-01-10"),
+                loans::term_months.eq(12),
+                loans::fixed_rate.eq("6"),
+                loans::fixed_months.eq(12),
+                loans::created_at.eq(now),
+                loans::updated_at.eq(now),
+            ))
+            .execute(conn)
+            .unwrap();
+        for (id, account_id, mortgage) in
+            [("flat", "home", Some("mortgage")), ("plot", "land", None)]
+        {
+            diesel::insert_into(properties::table)
+                .values(PropertyDB {
+                    id: id.to_string(),
+                    account_id: account_id.to_string(),
+                    asset_id: format!("PROPERTY-{}", id),
+                    activity_id: None,
+                    name: id.to_string(),
+                    address: None,
+                    purchase_date: "2026-01-10".to_string(),
+                    purchase_price: "3000000000".to_string(),
+                    transaction_costs: "0".to_string(),
+                    mortgage_account_id: mortgage.map(str::to_string),
+                    rental_income_source_id: None,
+                    notes: None,
+                    created_at: now,
+                    updated_at: now,
+                })
+                .execute(conn)
+                .unwrap();
+        }
+
+        let mortgages =
+            load_property_mortgages(conn, &["home".to_string(), "land".to_string()]).unwrap();
+        assert_eq!(mortgages.len(), 1);
+        let mortgage = &mortgages["home"];
+        assert_eq!(
+            mortgage.balance_on(NaiveDate::from_ymd_opt(2026, 1, 10).unwrap()),
+            dec!(1_200_000_000)
+        );
+        // Three of twelve equal principal payments made by mid-April
+        assert_eq!(
+            mortgage.balance_on(NaiveDate::from_ymd_opt(2026, 4, 15).unwrap()),
+            dec!(900_000_000)
+        );
+    }
+}
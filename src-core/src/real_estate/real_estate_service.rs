@@ -0,0 +1,538 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+use super::real_estate_model::{
+    property_asset_symbol, NewProperty, NewPropertyAppraisal, Property, PropertyAppraisal,
+    PropertySummary,
+};
+use super::real_estate_traits::{PropertyRepositoryTrait, PropertyServiceTrait};
+use crate::accounts::{
+    Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY, ACCOUNT_TYPE_REAL_ESTATE,
+};
+use crate::activities::{
+    ActivityServiceTrait, ActivityUpdate, NewActivity, ACTIVITY_TYPE_ADD_HOLDING,
+};
+use crate::assets::{AssetServiceTrait, UpdateAssetProfile};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::income_sources::{IncomeSourceKind, IncomeSourceRepositoryTrait};
+use crate::loans::{amortize, outstanding_on, LoanRepositoryTrait};
+use crate::market_data::{DataSource, MarketDataServiceTrait, Quote};
+
+pub struct PropertyService {
+    repository: Arc<dyn PropertyRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    loan_repository: Arc<dyn LoanRepositoryTrait>,
+    income_source_repository: Arc<dyn IncomeSourceRepositoryTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+}
+
+/// Quote id of a property's value on a date, one per day like imported quotes
+fn quote_id(symbol: &str, date: NaiveDate) -> String {
+    format!("{}_{}", symbol, date.format(FORECAST_DATE_FORMAT))
+}
+
+fn property_quote(symbol: &str, date: NaiveDate, value: Decimal, currency: &str) -> Quote {
+    let now = Utc::now();
+    Quote {
+        id: quote_id(symbol, date),
+        symbol: symbol.to_string(),
+        timestamp: date.and_hms_opt(12, 0, 0).unwrap_or_default().and_utc(),
+        open: value,
+        high: value,
+        low: value,
+        close: value,
+        adjclose: value,
+        volume: Decimal::ZERO,
+        currency: currency.to_string(),
+        data_source: DataSource::Manual,
+        created_at: now,
+    }
+}
+
+fn percent_of(part: Decimal, whole: Decimal) -> Decimal {
+    if whole.is_zero() {
+        return Decimal::ZERO;
+    }
+    (part / whole * Decimal::ONE_HUNDRED).round_dp(2)
+}
+
+/// Values a property from its latest appraisal (or purchase price), mortgage balance and
+/// expected annual rent
+pub(crate) fn summarize(
+    property: Property,
+    currency: String,
+    appraisals: Vec<PropertyAppraisal>,
+    mortgage_balance: Decimal,
+    annual_rent: Option<Decimal>,
+) -> PropertySummary {
+    let (current_value, valued_on) = appraisals
+        .iter()
+        .max_by_key(|a| a.appraisal_date)
+        .map_or((property.purchase_price, property.purchase_date), |a| {
+            (a.value, a.appraisal_date)
+        });
+
+    PropertySummary {
+        currency,
+        current_value,
+        valued_on,
+        mortgage_balance,
+        equity: current_value - mortgage_balance,
+        loan_to_value: percent_of(mortgage_balance, current_value),
+        unrealized_gain: current_value - property.total_cost(),
+        gross_yield: annual_rent.map(|rent| percent_of(rent, current_value)),
+        annual_rent,
+        appraisals,
+        property,
+    }
+}
+
+impl PropertyService {
+    pub fn new(
+        repository: Arc<dyn PropertyRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        loan_repository: Arc<dyn LoanRepositoryTrait>,
+        income_source_repository: Arc<dyn IncomeSourceRepositoryTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            loan_repository,
+            income_source_repository,
+            asset_service,
+            activity_service,
+            market_data_service,
+        }
+    }
+
+    fn real_estate_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type != ACCOUNT_TYPE_REAL_ESTATE {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Properties can only be set up on real estate accounts, not {}",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+
+    /// Checks the mortgage and rental income the property is linked to
+    fn check_links(
+        &self,
+        property: &NewProperty,
+        account: &Account,
+        existing_id: Option<&str>,
+    ) -> Result<()> {
+        if let Some(mortgage_account_id) = property.mortgage_account_id.as_deref() {
+            let mortgage = self.account_repository.get_by_id(mortgage_account_id)?;
+            if mortgage.account_type != ACCOUNT_TYPE_LIABILITY {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "{} is not a liability account",
+                    mortgage.name
+                ))));
+            }
+            if mortgage.currency != account.currency {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "The mortgage must be in {} like the property",
+                    account.currency
+                ))));
+            }
+            // A mortgage shared by two properties would come off both of their equities
+            let shared = self.repository.get_properties()?.into_iter().any(|other| {
+                Some(other.id.as_str()) != existing_id
+                    && other.mortgage_account_id.as_deref() == Some(mortgage_account_id)
+            });
+            if shared {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "{} is already the mortgage of another property",
+                    mortgage.name
+                ))));
+            }
+        }
+        if let Some(source_id) = property.rental_income_source_id.as_deref() {
+            let source = self
+                .income_source_repository
+                .get_income_sources()?
+                .into_iter()
+                .find(|source| source.id == source_id)
+                .ok_or_else(|| {
+                    Error::Validation(ValidationError::InvalidInput(format!(
+                        "Income source {} not found",
+                        source_id
+                    )))
+                })?;
+            if source.kind != IncomeSourceKind::Rental {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "{} is not a rental income source",
+                    source.name
+                ))));
+            }
+            if source.currency != account.currency {
+                return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                    "The rent must be in {} like the property",
+                    account.currency
+                ))));
+            }
+        }
+        Ok(())
+    }
+
+    /// Names the property's asset after it so holdings show the property rather than its id
+    async fn update_asset_profile(&self, property: &Property) -> Result<()> {
+        self.asset_service
+            .update_asset_profile(
+                &property.asset_id,
+                UpdateAssetProfile {
+                    symbol: property.asset_id.clone(),
+                    name: Some(property.name.clone()),
+                    sectors: None,
+                    countries: None,
+                    notes: property.address.clone().unwrap_or_default(),
+                    asset_sub_class: Some("Property".to_string()),
+                    asset_class: Some("Real Estate".to_string()),
+                },
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn mortgage_balance(&self, property: &Property, date: NaiveDate) -> Result<Decimal> {
+        let Some(mortgage_account_id) = property.mortgage_account_id.as_deref() else {
+            return Ok(Decimal::ZERO);
+        };
+        let Some(loan) = self
+            .loan_repository
+            .get_loan_for_account(mortgage_account_id)?
+        else {
+            return Ok(Decimal::ZERO);
+        };
+        let prepayments = self.loan_repository.get_prepayments(Some(&loan.id))?;
+        let resets = self.loan_repository.get_rate_resets(Some(&loan.id))?;
+        let rows = amortize(&loan, &prepayments, &resets);
+        Ok(outstanding_on(&loan, &rows, &prepayments, date))
+    }
+
+    fn annual_rent(&self, property: &Property) -> Result<Option<Decimal>> {
+        let Some(source_id) = property.rental_income_source_id.as_deref() else {
+            return Ok(None);
+        };
+        Ok(self
+            .income_source_repository
+            .get_income_sources()?
+            .into_iter()
+            .find(|source| source.id == source_id && source.is_active)
+            .map(|source| source.amount * Decimal::from(source.frequency.payments_per_year())))
+    }
+
+    fn summary(&self, property: Property) -> Result<PropertySummary> {
+        let account = self.account_repository.get_by_id(&property.account_id)?;
+        let appraisals = self.repository.get_appraisals(Some(&property.id))?;
+        let mortgage_balance = self.mortgage_balance(&property, Utc::now().date_naive())?;
+        let annual_rent = self.annual_rent(&property)?;
+        Ok(summarize(
+            property,
+            account.currency,
+            appraisals,
+            mortgage_balance,
+            annual_rent,
+        ))
+    }
+
+    /// Quotes the property at its purchase price on the purchase date, unless an appraisal
+    /// already values it that day
+    async fn quote_purchase(&self, property: &Property, currency: &str) -> Result<()> {
+        let appraised = self
+            .repository
+            .get_appraisals(Some(&property.id))?
+            .iter()
+            .any(|a| a.appraisal_date == property.purchase_date);
+        if !appraised {
+            self.market_data_service
+                .add_quote(&property_quote(
+                    &property.asset_id,
+                    property.purchase_date,
+                    property.purchase_price,
+                    currency,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PropertyServiceTrait for PropertyService {
+    fn get_properties(&self) -> Result<Vec<Property>> {
+        self.repository.get_properties()
+    }
+
+    fn get_property_summaries(&self) -> Result<Vec<PropertySummary>> {
+        self.repository
+            .get_properties()?
+            .into_iter()
+            .map(|property| self.summary(property))
+            .collect()
+    }
+
+    fn get_property_summary(&self, id: &str) -> Result<PropertySummary> {
+        self.summary(self.repository.get_property(id)?)
+    }
+
+    async fn create_property(&self, mut property: NewProperty) -> Result<Property> {
+        property.validate()?;
+        let account = self.real_estate_account(&property.account_id)?;
+        if self
+            .repository
+            .get_property_for_account(&account.id)?
+            .is_some()
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} already holds a property",
+                account.name
+            ))));
+        }
+        self.check_links(&property, &account, None)?;
+
+        let id = property
+            .id
+            .get_or_insert_with(|| uuid::Uuid::new_v4().to_string())
+            .clone();
+        let asset = self
+            .asset_service
+            .create_manual_asset(&property_asset_symbol(&id), account.currency.clone())
+            .await?;
+        let activity = self
+            .activity_service
+            .create_activity(NewActivity {
+                id: None,
+                account_id: account.id.clone(),
+                asset_id: asset.id.clone(),
+                activity_type: ACTIVITY_TYPE_ADD_HOLDING.to_string(),
+                activity_date: property
+                    .purchase_date
+                    .format(FORECAST_DATE_FORMAT)
+                    .to_string(),
+                quantity: Some(Decimal::ONE),
+                unit_price: Some(property.purchase_price),
+                currency: account.currency.clone(),
+                fee: Some(property.transaction_costs),
+                amount: None,
+                is_draft: false,
+                comment: Some(property.name.trim().to_string()),
+            })
+            .await?;
+
+        let created = self
+            .repository
+            .insert_property(property, asset.id, Some(activity.id))
+            .await?;
+        self.update_asset_profile(&created).await?;
+        self.quote_purchase(&created, &account.currency).await?;
+        Ok(created)
+    }
+
+    async fn update_property(&self, id: &str, property: NewProperty) -> Result<Property> {
+        property.validate()?;
+        let existing = self.repository.get_property(id)?;
+        if existing.account_id != property.account_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A property cannot be moved to another account".to_string(),
+            )));
+        }
+        let account = self.real_estate_account(&existing.account_id)?;
+        self.check_links(&property, &account, Some(id))?;
+
+        let updated = self.repository.update_property(id, property).await?;
+        let purchase_changed = updated.purchase_date != existing.purchase_date
+            || updated.purchase_price != existing.purchase_price
+            || updated.transaction_costs != existing.transaction_costs;
+        if purchase_changed {
+            if let Some(activity_id) = updated.activity_id.clone() {
+                self.activity_service
+                    .update_activity(ActivityUpdate {
+                        id: activity_id,
+                        account_id: account.id.clone(),
+                        asset_id: updated.asset_id.clone(),
+                        activity_type: ACTIVITY_TYPE_ADD_HOLDING.to_string(),
+                        activity_date: updated
+                            .purchase_date
+                            .format(FORECAST_DATE_FORMAT)
+                            .to_string(),
+                        quantity: Some(Decimal::ONE),
+                        unit_price: Some(updated.purchase_price),
+                        currency: account.currency.clone(),
+                        fee: Some(updated.transaction_costs),
+                        amount: None,
+                        is_draft: false,
+                        comment: Some(updated.name.clone()),
+                    })
+                    .await?;
+            }
+            let old_quote_appraised = self
+                .repository
+                .get_appraisals(Some(id))?
+                .iter()
+                .any(|a| a.appraisal_date == existing.purchase_date);
+            if !old_quote_appraised {
+                self.market_data_service
+                    .delete_quote(&quote_id(&existing.asset_id, existing.purchase_date))
+                    .await?;
+            }
+            self.quote_purchase(&updated, &account.currency).await?;
+        }
+        self.update_asset_profile(&updated).await?;
+        Ok(updated)
+    }
+
+    async fn delete_property(&self, id: &str) -> Result<usize> {
+        let property = self.repository.get_property(id)?;
+        let appraisals = self.repository.get_appraisals(Some(id))?;
+        let deleted = self.repository.delete_property(id).await?;
+
+        if let Some(activity_id) = property.activity_id.clone() {
+            self.activity_service.delete_activity(activity_id).await?;
+        }
+        let dates = std::iter::once(property.purchase_date)
+            .chain(appraisals.iter().map(|a| a.appraisal_date));
+        for date in dates {
+            self.market_data_service
+                .delete_quote(&quote_id(&property.asset_id, date))
+                .await?;
+        }
+        match self.asset_service.delete_asset(&property.asset_id).await {
+            // Activities recorded on the property since, e.g. its sale, keep the asset around
+            Ok(()) | Err(Error::ConstraintViolation(_)) => Ok(deleted),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn get_property_appraisals(
+        &self,
+        property_id: Option<String>,
+    ) -> Result<Vec<PropertyAppraisal>> {
+        self.repository.get_appraisals(property_id.as_deref())
+    }
+
+    async fn add_property_appraisal(
+        &self,
+        appraisal: NewPropertyAppraisal,
+    ) -> Result<PropertyAppraisal> {
+        appraisal.validate()?;
+        let property = self.repository.get_property(&appraisal.property_id)?;
+        if appraisal.appraisal_date < property.purchase_date {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Appraisal date cannot be before the purchase date".to_string(),
+            )));
+        }
+        let account = self.account_repository.get_by_id(&property.account_id)?;
+
+        let stored = self.repository.upsert_appraisal(appraisal).await?;
+        self.market_data_service
+            .add_quote(&property_quote(
+                &property.asset_id,
+                stored.appraisal_date,
+                stored.value,
+                &account.currency,
+            ))
+            .await?;
+        Ok(stored)
+    }
+
+    async fn delete_property_appraisal(&self, id: &str) -> Result<usize> {
+        let appraisal = self.repository.get_appraisal(id)?;
+        let property = self.repository.get_property(&appraisal.property_id)?;
+        let deleted = self.repository.delete_appraisal(id).await?;
+
+        if appraisal.appraisal_date == property.purchase_date {
+            // The purchase price values the property again on the day it was bought
+            let account = self.account_repository.get_by_id(&property.account_id)?;
+            self.quote_purchase(&property, &account.currency).await?;
+        } else {
+            self.market_data_service
+                .delete_quote(&quote_id(&property.asset_id, appraisal.appraisal_date))
+                .await?;
+        }
+        Ok(deleted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn property() -> Property {
+        let now = Utc::now().naive_utc();
+        Property {
+            id: "flat".to_string(),
+            account_id: "home".to_string(),
+            asset_id: property_asset_symbol("flat"),
+            activity_id: None,
+            name: "Thu Duc flat".to_string(),
+            address: None,
+            purchase_date: date("2025-03-01"),
+            purchase_price: dec!(3_000_000_000),
+            transaction_costs: dec!(60_000_000),
+            mortgage_account_id: Some("mortgage".to_string()),
+            rental_income_source_id: None,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn appraisal(on: &str, value: Decimal) -> PropertyAppraisal {
+        PropertyAppraisal {
+            id: on.to_string(),
+            property_id: "flat".to_string(),
+            appraisal_date: date(on),
+            value,
+            source: None,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn summary_values_property_at_latest_appraisal() {
+        let summary = summarize(
+            property(),
+            "VND".to_string(),
+            vec![
+                appraisal("2025-09-01", dec!(3_200_000_000)),
+                appraisal("2026-03-01", dec!(3_500_000_000)),
+            ],
+            dec!(1_400_000_000),
+            Some(dec!(144_000_000)),
+        );
+        assert_eq!(summary.current_value, dec!(3_500_000_000));
+        assert_eq!(summary.valued_on, date("2026-03-01"));
+        assert_eq!(summary.equity, dec!(2_100_000_000));
+        assert_eq!(summary.loan_to_value, dec!(40));
+        // Gain over the price and the 60m of fees
+        assert_eq!(summary.unrealized_gain, dec!(440_000_000));
+        // 12m a month on 3.5bn
+        assert_eq!(summary.gross_yield, Some(dec!(4.11)));
+    }
+
+    #[test]
+    fn summary_falls_back_to_purchase_price() {
+        let summary = summarize(property(), "VND".to_string(), vec![], Decimal::ZERO, None);
+        assert_eq!(summary.current_value, dec!(3_000_000_000));
+        assert_eq!(summary.valued_on, date("2025-03-01"));
+        assert_eq!(summary.equity, summary.current_value);
+        assert_eq!(summary.unrealized_gain, dec!(-60_000_000));
+        assert_eq!(summary.gross_yield, None);
+    }
+}
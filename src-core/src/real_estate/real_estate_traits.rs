@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+
+use super::real_estate_model::{
+    NewProperty, NewPropertyAppraisal, Property, PropertyAppraisal, PropertySummary,
+};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait PropertyRepositoryTrait: Send + Sync {
+    fn get_properties(&self) -> Result<Vec<Property>>;
+    fn get_property(&self, id: &str) -> Result<Property>;
+    fn get_property_for_account(&self, account_id: &str) -> Result<Option<Property>>;
+    /// Stores a property under `property.id`, which the caller has already assigned
+    async fn insert_property(
+        &self,
+        property: NewProperty,
+        asset_id: String,
+        activity_id: Option<String>,
+    ) -> Result<Property>;
+    async fn update_property(&self, id: &str, property: NewProperty) -> Result<Property>;
+    async fn delete_property(&self, id: &str) -> Result<usize>;
+    /// Appraisals in date order; all properties when `property_id` is unset
+    fn get_appraisals(&self, property_id: Option<&str>) -> Result<Vec<PropertyAppraisal>>;
+    fn get_appraisal(&self, id: &str) -> Result<PropertyAppraisal>;
+    /// Inserts the appraisal, replacing one already recorded for the same date
+    async fn upsert_appraisal(&self, appraisal: NewPropertyAppraisal) -> Result<PropertyAppraisal>;
+    async fn delete_appraisal(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait PropertyServiceTrait: Send + Sync {
+    fn get_properties(&self) -> Result<Vec<Property>>;
+    fn get_property_summaries(&self) -> Result<Vec<PropertySummary>>;
+    fn get_property_summary(&self, id: &str) -> Result<PropertySummary>;
+    /// Sets up the property on its real-estate account: a manual asset added as a holding at
+    /// the purchase price and costs, quoted at the purchase price
+    async fn create_property(&self, property: NewProperty) -> Result<Property>;
+    async fn update_property(&self, id: &str, property: NewProperty) -> Result<Property>;
+    /// Removes the property with its holding and quotes; the account stays
+    async fn delete_property(&self, id: &str) -> Result<usize>;
+    fn get_property_appraisals(
+        &self,
+        property_id: Option<String>,
+    ) -> Result<Vec<PropertyAppraisal>>;
+    /// Records an appraisal and quotes the property at it from its date
+    async fn add_property_appraisal(
+        &self,
+        appraisal: NewPropertyAppraisal,
+    ) -> Result<PropertyAppraisal>;
+    async fn delete_property_appraisal(&self, id: &str) -> Result<usize>;
+}
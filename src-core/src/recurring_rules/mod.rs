@@ -0,0 +1,9 @@
+mod recurring_rules_model;
+mod recurring_rules_repository;
+mod recurring_rules_service;
+mod recurring_rules_traits;
+
+pub use recurring_rules_model::{NewRecurringRule, RecurringRule, RECURRING_ACTIVITY_TYPES};
+pub use recurring_rules_repository::RecurringRuleRepository;
+pub use recurring_rules_service::RecurringRuleService;
+pub use recurring_rules_traits::{RecurringRuleRepositoryTrait, RecurringRuleServiceTrait};
@@ -0,0 +1,321 @@
+use chrono::{Days, Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::activities::{
+    NewActivity, ACTIVITY_TYPE_BUY, ACTIVITY_TYPE_DEPOSIT, ACTIVITY_TYPE_FEE,
+    ACTIVITY_TYPE_INTEREST, ACTIVITY_TYPE_TAX, ACTIVITY_TYPE_WITHDRAWAL,
+};
+use crate::constants::CASH_ASSET_PREFIX;
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::{occurrences, parse_forecast_date, CashFlowFrequency, FORECAST_DATE_FORMAT};
+
+/// Activities a rule can post: cash coming in or going out (salary, loan payments, fees) and
+/// purchases of a fund or stock
+pub const RECURRING_ACTIVITY_TYPES: [&str; 6] = [
+    ACTIVITY_TYPE_DEPOSIT,
+    ACTIVITY_TYPE_WITHDRAWAL,
+    ACTIVITY_TYPE_BUY,
+    ACTIVITY_TYPE_FEE,
+    ACTIVITY_TYPE_INTEREST,
+    ACTIVITY_TYPE_TAX,
+];
+
+/// Database row for `recurring_rules`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::recurring_rules)]
+pub struct RecurringRuleDB {
+    pub id: String,
+    pub account_id: String,
+    pub name: String,
+    pub activity_type: String,
+    pub asset_id: Option<String>,
+    pub amount: String,
+    pub currency: Option<String>,
+    pub frequency: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub next_due_date: Option<String>,
+    pub is_paused: bool,
+    pub comment: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// An activity that repeats on a schedule, such as a salary deposit, a monthly fund purchase
+/// or a loan payment. Each occurrence is posted to the ledger once it falls due.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecurringRule {
+    pub id: String,
+    pub account_id: String,
+    pub name: String,
+    pub activity_type: String,
+    /// Asset bought by a purchase rule; cash movements use the cash asset
+    pub asset_id: Option<String>,
+    /// Cash moved or spent on each occurrence
+    pub amount: Decimal,
+    /// Defaults to the account currency
+    pub currency: Option<String>,
+    pub frequency: CashFlowFrequency,
+    /// First occurrence; later ones keep its day of the month
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    /// Next occurrence to post; `None` once the rule has run its course
+    pub next_due_date: Option<NaiveDate>,
+    pub is_paused: bool,
+    pub comment: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl RecurringRule {
+    /// Occurrences due by `today` that have not been posted yet, oldest first. Paused
+    /// rules have nothing due.
+    pub fn due_dates(&self, today: NaiveDate) -> Vec<NaiveDate> {
+        match self.next_due_date {
+            Some(next) if !self.is_paused => {
+                occurrences(self.start_date, self.end_date, self.frequency, next, today)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// First occurrence after `date`, if the schedule has one
+    pub fn next_due_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        let from = date.checked_add_days(Days::new(1))?;
+        // No schedule is sparser than yearly, so a year ahead holds the next one
+        let to = from
+            .checked_add_months(Months::new(12))
+            .unwrap_or(NaiveDate::MAX);
+        let to = if self.start_date > to {
+            self.start_date
+        } else {
+            to
+        };
+        occurrences(self.start_date, self.end_date, self.frequency, from, to)
+            .into_iter()
+            .next()
+    }
+
+    /// Where a paused rule picks up on `today`: occurrences missed while paused are skipped,
+    /// and one already posted is not posted again
+    pub fn resume_due_date(&self, today: NaiveDate) -> Option<NaiveDate> {
+        let next = self.next_due_date?;
+        let first_on_or_after_today = today
+            .pred_opt()
+            .and_then(|yesterday| self.next_due_after(yesterday))?;
+        Some(next.max(first_on_or_after_today))
+    }
+
+    pub fn is_purchase(&self) -> bool {
+        self.activity_type == ACTIVITY_TYPE_BUY
+    }
+
+    /// The activity posted for the occurrence on `date`. Purchases are posted as drafts
+    /// holding the amount, to be completed with the units and price actually filled.
+    pub fn activity_on(&self, date: NaiveDate, currency: &str) -> NewActivity {
+        let asset_id = match (&self.asset_id, self.is_purchase()) {
+            (Some(asset_id), true) => asset_id.clone(),
+            _ => format!("{}-{}", CASH_ASSET_PREFIX, currency),
+        };
+        NewActivity {
+            id: None,
+            account_id: self.account_id.clone(),
+            asset_id,
+            activity_type: self.activity_type.clone(),
+            activity_date: date.format(FORECAST_DATE_FORMAT).to_string(),
+            quantity: None,
+            unit_price: None,
+            currency: currency.to_string(),
+            fee: None,
+            amount: Some(self.amount),
+            is_draft: self.is_purchase(),
+            comment: Some(self.comment.clone().unwrap_or_else(|| self.name.clone())),
+        }
+    }
+}
+
+impl TryFrom<RecurringRuleDB> for RecurringRule {
+    type Error = Error;
+
+    fn try_from(db: RecurringRuleDB) -> Result<Self> {
+        Ok(RecurringRule {
+            id: db.id,
+            account_id: db.account_id,
+            name: db.name,
+            activity_type: db.activity_type,
+            asset_id: db.asset_id,
+            amount: Decimal::from_str(&db.amount)?,
+            currency: db.currency,
+            frequency: CashFlowFrequency::from_str(&db.frequency)?,
+            start_date: parse_forecast_date(&db.start_date)?,
+            end_date: db
+                .end_date
+                .as_deref()
+                .map(parse_forecast_date)
+                .transpose()?,
+            next_due_date: db
+                .next_due_date
+                .as_deref()
+                .map(parse_forecast_date)
+                .transpose()?,
+            is_paused: db.is_paused,
+            comment: db.comment,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating a recurring rule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewRecurringRule {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub name: String,
+    pub activity_type: String,
+    pub asset_id: Option<String>,
+    pub amount: Decimal,
+    pub currency: Option<String>,
+    pub frequency: CashFlowFrequency,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    pub comment: Option<String>,
+}
+
+impl NewRecurringRule {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [("accountId", &self.account_id), ("name", &self.name)] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        if !RECURRING_ACTIVITY_TYPES.contains(&self.activity_type.as_str()) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} activities cannot recur",
+                self.activity_type
+            ))));
+        }
+        if self.activity_type == ACTIVITY_TYPE_BUY
+            && self
+                .asset_id
+                .as_deref()
+                .is_none_or(|asset_id| asset_id.trim().is_empty())
+        {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "assetId".to_string(),
+            )));
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Recurring amount must be positive".to_string(),
+            )));
+        }
+        if self.end_date.is_some_and(|end| end < self.start_date) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "End date cannot be before the start date".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_forecast_date(value).unwrap()
+    }
+
+    fn salary() -> RecurringRule {
+        let now = Utc::now().naive_utc();
+        RecurringRule {
+            id: "salary".to_string(),
+            account_id: "cash".to_string(),
+            name: "Salary".to_string(),
+            activity_type: ACTIVITY_TYPE_DEPOSIT.to_string(),
+            asset_id: None,
+            amount: dec!(30_000_000),
+            currency: None,
+            frequency: CashFlowFrequency::Monthly,
+            start_date: date("2026-01-31"),
+            end_date: None,
+            next_due_date: Some(date("2026-01-31")),
+            is_paused: false,
+            comment: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn catches_up_every_missed_occurrence_keeping_the_day() {
+        let rule = salary();
+        let due = rule.due_dates(date("2026-04-15"));
+        assert_eq!(
+            due,
+            vec![date("2026-01-31"), date("2026-02-28"), date("2026-03-31")]
+        );
+        assert_eq!(
+            rule.next_due_after(date("2026-03-31")),
+            Some(date("2026-04-30"))
+        );
+
+        let paused = RecurringRule {
+            is_paused: true,
+            ..salary()
+        };
+        assert!(paused.due_dates(date("2026-04-15")).is_empty());
+
+        let ended = RecurringRule {
+            end_date: Some(date("2026-02-28")),
+            ..salary()
+        };
+        assert_eq!(ended.next_due_after(date("2026-02-28")), None);
+    }
+
+    #[test]
+    fn resuming_skips_what_was_missed_while_paused() {
+        let rule = RecurringRule {
+            next_due_date: Some(date("2026-02-28")),
+            is_paused: true,
+            ..salary()
+        };
+        assert_eq!(
+            rule.resume_due_date(date("2026-05-10")),
+            Some(date("2026-05-31"))
+        );
+        assert_eq!(
+            rule.resume_due_date(date("2026-02-01")),
+            Some(date("2026-02-28"))
+        );
+    }
+
+    #[test]
+    fn purchases_post_as_drafts_and_cash_uses_the_cash_asset() {
+        let deposit = salary().activity_on(date("2026-01-31"), "VND");
+        assert_eq!(deposit.asset_id, "$CASH-VND");
+        assert_eq!(deposit.amount, Some(dec!(30_000_000)));
+        assert!(!deposit.is_draft);
+        assert_eq!(deposit.comment.as_deref(), Some("Salary"));
+
+        let purchase = RecurringRule {
+            activity_type: ACTIVITY_TYPE_BUY.to_string(),
+            asset_id: Some("VESAF".to_string()),
+            ..salary()
+        }
+        .activity_on(date("2026-01-31"), "VND");
+        assert_eq!(purchase.asset_id, "VESAF");
+        assert!(purchase.is_draft);
+        assert_eq!(purchase.quantity, None);
+    }
+}
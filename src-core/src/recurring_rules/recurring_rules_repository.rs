@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::recurring_rules_model::{NewRecurringRule, RecurringRule, RecurringRuleDB};
+use super::recurring_rules_traits::RecurringRuleRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::recurring_rules;
+use crate::utils::text_utils::trimmed;
+
+pub struct RecurringRuleRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl RecurringRuleRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        RecurringRuleRepository { pool, writer }
+    }
+}
+
+fn format_date(date: Option<NaiveDate>) -> Option<String> {
+    date.map(|date| date.format(FORECAST_DATE_FORMAT).to_string())
+}
+
+#[async_trait]
+impl RecurringRuleRepositoryTrait for RecurringRuleRepository {
+    fn get_recurring_rules(&self) -> Result<Vec<RecurringRule>> {
+        let mut conn = get_connection(&self.pool)?;
+        recurring_rules::table
+            .order((
+                recurring_rules::start_date.asc(),
+                recurring_rules::name.asc(),
+            ))
+            .load::<RecurringRuleDB>(&mut conn)?
+            .into_iter()
+            .map(RecurringRule::try_from)
+            .collect()
+    }
+
+    fn get_recurring_rule(&self, id: &str) -> Result<RecurringRule> {
+        let mut conn = get_connection(&self.pool)?;
+        recurring_rules::table
+            .find(id)
+            .first::<RecurringRuleDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_recurring_rule(&self, rule: NewRecurringRule) -> Result<RecurringRule> {
+        let currency = trimmed(rule.currency).map(|currency| currency.to_uppercase());
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<RecurringRule> {
+                    let now = Utc::now().naive_utc();
+                    let record = RecurringRuleDB {
+                        id: rule.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                        account_id: rule.account_id,
+                        name: rule.name.trim().to_string(),
+                        activity_type: rule.activity_type,
+                        asset_id: trimmed(rule.asset_id),
+                        amount: rule.amount.to_string(),
+                        currency,
+                        frequency: rule.frequency.as_str().to_string(),
+                        start_date: rule.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                        end_date: format_date(rule.end_date),
+                        next_due_date: format_date(Some(rule.start_date)),
+                        is_paused: false,
+                        comment: trimmed(rule.comment),
+                        created_at: now,
+                        updated_at: now,
+                    };
+                    diesel::insert_into(recurring_rules::table)
+                        .values(&record)
+                        .get_result::<RecurringRuleDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn set_paused(
+        &self,
+        id: &str,
+        is_paused: bool,
+        next_due_date: Option<NaiveDate>,
+    ) -> Result<RecurringRule> {
+        let id_owned = id.to_string();
+        let next_due_date = format_date(next_due_date);
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<RecurringRule> {
+                    diesel::update(recurring_rules::table.find(id_owned))
+                        .set((
+                            recurring_rules::is_paused.eq(is_paused),
+                            recurring_rules::next_due_date.eq(next_due_date),
+                            recurring_rules::updated_at.eq(Utc::now().naive_utc()),
+                        ))
+                        .get_result::<RecurringRuleDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+
+    async fn set_next_due_date(&self, id: &str, next_due_date: Option<NaiveDate>) -> Result<()> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                diesel::update(recurring_rules::table.find(id_owned))
+                    .set((
+                        recurring_rules::next_due_date.eq(format_date(next_due_date)),
+                        recurring_rules::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn delete_recurring_rule(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(recurring_rules::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use log::warn;
+use std::sync::Arc;
+
+use super::recurring_rules_model::{NewRecurringRule, RecurringRule};
+use super::recurring_rules_traits::{RecurringRuleRepositoryTrait, RecurringRuleServiceTrait};
+use crate::accounts::AccountRepositoryTrait;
+use crate::activities::{Activity, ActivityServiceTrait};
+use crate::assets::AssetServiceTrait;
+use crate::constants::CASH_ASSET_PREFIX;
+use crate::errors::Result;
+
+pub struct RecurringRuleService {
+    repository: Arc<dyn RecurringRuleRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+}
+
+impl RecurringRuleService {
+    pub fn new(
+        repository: Arc<dyn RecurringRuleRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            activity_service,
+            asset_service,
+        }
+    }
+
+    /// Posts the due occurrences of one rule, moving its next due date past each as it goes
+    /// so an interrupted run picks up where it stopped
+    async fn materialize_rule(
+        &self,
+        rule: &RecurringRule,
+        due: Vec<NaiveDate>,
+    ) -> Result<Vec<Activity>> {
+        let account = self.account_repository.get_by_id(&rule.account_id)?;
+        let currency = rule.currency.clone().unwrap_or(account.currency);
+        if !rule.is_purchase() {
+            let cash_asset_id = format!("{}-{}", CASH_ASSET_PREFIX, currency);
+            if self.asset_service.get_asset_by_id(&cash_asset_id).is_err() {
+                self.asset_service.create_cash_asset(&currency).await?;
+            }
+        }
+
+        let mut created = Vec::with_capacity(due.len());
+        for date in due {
+            created.push(
+                self.activity_service
+                    .create_activity(rule.activity_on(date, &currency))
+                    .await?,
+            );
+            self.repository
+                .set_next_due_date(&rule.id, rule.next_due_after(date))
+                .await?;
+        }
+        Ok(created)
+    }
+}
+
+#[async_trait]
+impl RecurringRuleServiceTrait for RecurringRuleService {
+    fn get_recurring_rules(&self) -> Result<Vec<RecurringRule>> {
+        self.repository.get_recurring_rules()
+    }
+
+    async fn create_recurring_rule(&self, rule: NewRecurringRule) -> Result<RecurringRule> {
+        rule.validate()?;
+        self.account_repository.get_by_id(&rule.account_id)?;
+        self.repository.insert_recurring_rule(rule).await
+    }
+
+    async fn set_recurring_rule_paused(
+        &self,
+        id: &str,
+        is_paused: bool,
+        today: NaiveDate,
+    ) -> Result<RecurringRule> {
+        let rule = self.repository.get_recurring_rule(id)?;
+        if rule.is_paused == is_paused {
+            return Ok(rule);
+        }
+        let next_due_date = if is_paused {
+            rule.next_due_date
+        } else {
+            rule.resume_due_date(today)
+        };
+        self.repository
+            .set_paused(id, is_paused, next_due_date)
+            .await
+    }
+
+    async fn delete_recurring_rule(&self, id: &str) -> Result<usize> {
+        self.repository.delete_recurring_rule(id).await
+    }
+
+    async fn materialize_due_activities(&self, today: NaiveDate) -> Result<Vec<Activity>> {
+        let mut created = Vec::new();
+        for rule in self.repository.get_recurring_rules()? {
+            let due = rule.due_dates(today);
+            if due.is_empty() {
+                continue;
+            }
+            // One broken rule, such as one whose account is gone, doesn't hold up the rest
+            match self.materialize_rule(&rule, due).await {
+                Ok(activities) => created.extend(activities),
+                Err(e) => warn!("Recurring rule '{}' could not be posted: {}", rule.name, e),
+            }
+        }
+        Ok(created)
+    }
+}
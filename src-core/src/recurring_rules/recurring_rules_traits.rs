@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::recurring_rules_model::{NewRecurringRule, RecurringRule};
+use crate::activities::Activity;
+use crate::errors::Result;
+
+#[async_trait]
+pub trait RecurringRuleRepositoryTrait: Send + Sync {
+    fn get_recurring_rules(&self) -> Result<Vec<RecurringRule>>;
+    fn get_recurring_rule(&self, id: &str) -> Result<RecurringRule>;
+    async fn insert_recurring_rule(&self, rule: NewRecurringRule) -> Result<RecurringRule>;
+    async fn set_paused(
+        &self,
+        id: &str,
+        is_paused: bool,
+        next_due_date: Option<NaiveDate>,
+    ) -> Result<RecurringRule>;
+    async fn set_next_due_date(&self, id: &str, next_due_date: Option<NaiveDate>) -> Result<()>;
+    async fn delete_recurring_rule(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait RecurringRuleServiceTrait: Send + Sync {
+    fn get_recurring_rules(&self) -> Result<Vec<RecurringRule>>;
+    async fn create_recurring_rule(&self, rule: NewRecurringRule) -> Result<RecurringRule>;
+    /// Pauses a rule, or resumes it from the next occurrence on or after `today`
+    async fn set_recurring_rule_paused(
+        &self,
+        id: &str,
+        is_paused: bool,
+        today: NaiveDate,
+    ) -> Result<RecurringRule>;
+    async fn delete_recurring_rule(&self, id: &str) -> Result<usize>;
+    /// Posts every occurrence due by `today` that has not been posted yet, catching up on
+    /// the days the app was closed, and returns the activities created
+    async fn materialize_due_activities(&self, today: NaiveDate) -> Result<Vec<Activity>>;
+}
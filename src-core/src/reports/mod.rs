@@ -0,0 +1,12 @@
+mod reports_model;
+mod reports_repository;
+mod reports_service;
+mod reports_traits;
+
+pub use reports_model::{
+    assets_by_class, split_by_class, AssetClassValue, LiabilityBalance, NetWorthChange,
+    NetWorthHistoryEntry, NetWorthStatement, UNCLASSIFIED_ASSET_CLASS,
+};
+pub use reports_repository::NetWorthHistoryRepository;
+pub use reports_service::ReportService;
+pub use reports_traits::{NetWorthHistoryRepositoryTrait, ReportServiceTrait};
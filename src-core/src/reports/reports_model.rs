@@ -0,0 +1,309 @@
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::errors::{Error, Result};
+
+/// Class of holdings whose asset has none recorded, and of market value no holding accounts for
+pub const UNCLASSIFIED_ASSET_CLASS: &str = "Unclassified";
+
+/// Database row for `net_worth_history`
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::net_worth_history)]
+pub struct NetWorthHistoryDB {
+    pub month_start: NaiveDate,
+    pub as_of: NaiveDate,
+    pub base_currency: String,
+    pub total_assets: String,
+    pub total_liabilities: String,
+    pub net_worth: String,
+    pub goal_allocated: String,
+    pub free_assets: String,
+    pub assets_by_class: String,
+    pub updated_at: String,
+}
+
+/// Assets of one class in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetClassValue {
+    pub asset_class: String,
+    pub value: Decimal,
+    /// Percent of total assets
+    pub share: Decimal,
+}
+
+/// What is owed on one liability account in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiabilityBalance {
+    pub account_id: String,
+    pub account_name: String,
+    pub balance: Decimal,
+}
+
+/// Movement between two statements
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetWorthChange {
+    pub total_assets: Decimal,
+    pub total_liabilities: Decimal,
+    pub net_worth: Decimal,
+    /// Net worth change in percent of the earlier net worth; `None` when that was zero
+    pub net_worth_percent: Option<Decimal>,
+}
+
+impl NetWorthChange {
+    pub fn between(previous: &NetWorthHistoryEntry, current: &NetWorthHistoryEntry) -> Self {
+        let net_worth = current.net_worth - previous.net_worth;
+        let net_worth_percent = (!previous.net_worth.is_zero())
+            .then(|| (net_worth / previous.net_worth.abs() * Decimal::ONE_HUNDRED).round_dp(2));
+        NetWorthChange {
+            total_assets: current.total_assets - previous.total_assets,
+            total_liabilities: current.total_liabilities - previous.total_liabilities,
+            net_worth,
+            net_worth_percent,
+        }
+    }
+}
+
+/// A month's statement as recorded in the history: at month end once the month is over, or
+/// to date while it runs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetWorthHistoryEntry {
+    pub month_start: NaiveDate,
+    pub as_of: NaiveDate,
+    pub base_currency: String,
+    pub total_assets: Decimal,
+    pub total_liabilities: Decimal,
+    pub net_worth: Decimal,
+    pub goal_allocated: Decimal,
+    pub free_assets: Decimal,
+    pub assets_by_class: Vec<AssetClassValue>,
+    /// Change from the month before, when that month is recorded
+    pub change: Option<NetWorthChange>,
+}
+
+impl TryFrom<NetWorthHistoryDB> for NetWorthHistoryEntry {
+    type Error = Error;
+
+    fn try_from(db: NetWorthHistoryDB) -> Result<Self> {
+        Ok(NetWorthHistoryEntry {
+            month_start: db.month_start,
+            as_of: db.as_of,
+            base_currency: db.base_currency,
+            total_assets: Decimal::from_str(&db.total_assets)?,
+            total_liabilities: Decimal::from_str(&db.total_liabilities)?,
+            net_worth: Decimal::from_str(&db.net_worth)?,
+            goal_allocated: Decimal::from_str(&db.goal_allocated)?,
+            free_assets: Decimal::from_str(&db.free_assets)?,
+            assets_by_class: serde_json::from_str(&db.assets_by_class)
+                .map_err(|e| Error::Unexpected(e.to_string()))?,
+            change: None,
+        })
+    }
+}
+
+/// Point-in-time statement of what is owned and owed, in the base currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetWorthStatement {
+    pub as_of: NaiveDate,
+    pub base_currency: String,
+    /// Largest class first
+    pub assets_by_class: Vec<AssetClassValue>,
+    pub total_assets: Decimal,
+    pub liabilities: Vec<LiabilityBalance>,
+    pub total_liabilities: Decimal,
+    pub net_worth: Decimal,
+    /// Assets set aside for goals still being worked towards
+    pub goal_allocated: Decimal,
+    /// Assets no goal has a claim on
+    pub free_assets: Decimal,
+    /// The month before's recorded statement
+    pub previous_month: Option<NetWorthHistoryEntry>,
+    pub month_over_month: Option<NetWorthChange>,
+}
+
+impl NetWorthStatement {
+    /// The statement as recorded in the history for its month
+    pub fn history_entry(&self, month_start: NaiveDate) -> NetWorthHistoryEntry {
+        NetWorthHistoryEntry {
+            month_start,
+            as_of: self.as_of,
+            base_currency: self.base_currency.clone(),
+            total_assets: self.total_assets,
+            total_liabilities: self.total_liabilities,
+            net_worth: self.net_worth,
+            goal_allocated: self.goal_allocated,
+            free_assets: self.free_assets,
+            assets_by_class: self.assets_by_class.clone(),
+            change: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_assets.is_zero() && self.total_liabilities.is_zero()
+    }
+}
+
+/// Spreads an account's market value over the classes of its holdings in proportion to their
+/// values. Holdings are valued separately from the account valuation, so only their weights
+/// are used; the value lands in `Unclassified` when none of them could be valued.
+pub fn split_by_class(
+    market_value: Decimal,
+    holdings: &[(String, Decimal)],
+) -> Vec<(String, Decimal)> {
+    if market_value.is_zero() {
+        return Vec::new();
+    }
+    let mut weights: BTreeMap<&str, Decimal> = BTreeMap::new();
+    for (asset_class, value) in holdings.iter().filter(|(_, value)| *value > Decimal::ZERO) {
+        *weights.entry(asset_class.as_str()).or_default() += *value;
+    }
+    let total: Decimal = weights.values().copied().sum();
+    if total.is_zero() {
+        return vec![(UNCLASSIFIED_ASSET_CLASS.to_string(), market_value)];
+    }
+
+    // The last class takes what rounding leaves so the parts add up to the market value
+    let mut remaining = market_value;
+    let count = weights.len();
+    weights
+        .into_iter()
+        .enumerate()
+        .map(|(i, (asset_class, weight))| {
+            let value = if i + 1 == count {
+                remaining
+            } else {
+                (market_value * weight / total).round_dp(2)
+            };
+            remaining -= value;
+            (asset_class.to_string(), value)
+        })
+        .collect()
+}
+
+/// Adds up values per class, largest class first and then by name, with each class's share of `total_assets`
+pub fn assets_by_class(
+    values: impl IntoIterator<Item = (String, Decimal)>,
+    total_assets: Decimal,
+) -> Vec<AssetClassValue> {
+    let mut by_class: BTreeMap<String, Decimal> = BTreeMap::new();
+    for (asset_class, value) in values {
+        *by_class.entry(asset_class).or_default() += value;
+    }
+    let mut classes: Vec<AssetClassValue> = by_class
+        .into_iter()
+        .filter(|(_, value)| !value.is_zero())
+        .map(|(asset_class, value)| AssetClassValue {
+            share: if total_assets.is_zero() {
+                Decimal::ZERO
+            } else {
+                (value / total_assets * Decimal::ONE_HUNDRED).round_dp(2)
+            },
+            asset_class,
+            value,
+        })
+        .collect();
+    // Equal classes keep a stable order by name
+    classes.sort_by_key(|class| (std::cmp::Reverse(class.value), class.asset_class.clone()));
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn entry(
+        month: &str,
+        total_assets: Decimal,
+        total_liabilities: Decimal,
+    ) -> NetWorthHistoryEntry {
+        let month_start = NaiveDate::parse_from_str(month, "%Y-%m-%d").unwrap();
+        NetWorthHistoryEntry {
+            month_start,
+            as_of: month_start,
+            base_currency: "VND".to_string(),
+            total_assets,
+            total_liabilities,
+            net_worth: total_assets - total_liabilities,
+            goal_allocated: Decimal::ZERO,
+            free_assets: total_assets,
+            assets_by_class: Vec::new(),
+            change: None,
+        }
+    }
+
+    #[test]
+    fn market_value_follows_holding_weights() {
+        let holdings = vec![
+            ("Equity".to_string(), dec!(300)),
+            ("Fixed Income".to_string(), dec!(100)),
+            ("Equity".to_string(), dec!(200)),
+            ("Commodity".to_string(), dec!(0)),
+        ];
+        let split = split_by_class(dec!(1_200), &holdings);
+        assert_eq!(
+            split,
+            vec![
+                ("Equity".to_string(), dec!(1_000)),
+                ("Fixed Income".to_string(), dec!(200)),
+            ]
+        );
+
+        let unvalued = split_by_class(dec!(500), &[("Equity".to_string(), Decimal::ZERO)]);
+        assert_eq!(
+            unvalued,
+            vec![(UNCLASSIFIED_ASSET_CLASS.to_string(), dec!(500))]
+        );
+        assert!(split_by_class(Decimal::ZERO, &holdings).is_empty());
+    }
+
+    #[test]
+    fn classes_are_summed_and_ranked_by_value() {
+        let classes = assets_by_class(
+            vec![
+                ("CASH".to_string(), dec!(200)),
+                ("Equity".to_string(), dec!(600)),
+                ("CASH".to_string(), dec!(200)),
+                ("Commodity".to_string(), Decimal::ZERO),
+            ],
+            dec!(1_000),
+        );
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes[0].value, dec!(600));
+        assert_eq!(classes[1].asset_class, "CASH");
+        assert_eq!(classes[1].share, dec!(40));
+
+        // Classes of equal value are ranked by name
+        let tied = assets_by_class(
+            vec![
+                ("Equity".to_string(), dec!(500)),
+                ("CASH".to_string(), dec!(500)),
+            ],
+            dec!(1_000),
+        );
+        assert_eq!(tied[0].asset_class, "CASH");
+        assert_eq!(tied[1].asset_class, "Equity");
+    }
+
+    #[test]
+    fn month_over_month_change() {
+        let previous = entry("2026-08-01", dec!(1_000), dec!(200));
+        let current = entry("2026-09-01", dec!(1_100), dec!(100));
+        let change = NetWorthChange::between(&previous, &current);
+        assert_eq!(change.net_worth, dec!(200));
+        assert_eq!(change.total_liabilities, dec!(-100));
+        assert_eq!(change.net_worth_percent, Some(dec!(25)));
+
+        let from_nothing =
+            NetWorthChange::between(&entry("2026-08-01", dec!(0), dec!(0)), &current);
+        assert_eq!(from_nothing.net_worth_percent, None);
+    }
+}
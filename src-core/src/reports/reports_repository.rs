@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+
+use super::reports_model::{NetWorthHistoryDB, NetWorthHistoryEntry};
+use super::reports_traits::NetWorthHistoryRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Error, Result};
+use crate::schema::net_worth_history;
+
+pub struct NetWorthHistoryRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl NetWorthHistoryRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        NetWorthHistoryRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl NetWorthHistoryRepositoryTrait for NetWorthHistoryRepository {
+    fn get_history_entry(&self, month_start: NaiveDate) -> Result<Option<NetWorthHistoryEntry>> {
+        let mut conn = get_connection(&self.pool)?;
+        net_worth_history::table
+            .find(month_start)
+            .first::<NetWorthHistoryDB>(&mut conn)
+            .optional()?
+            .map(NetWorthHistoryEntry::try_from)
+            .transpose()
+    }
+
+    fn get_history(&self, from_month: NaiveDate) -> Result<Vec<NetWorthHistoryEntry>> {
+        let mut conn = get_connection(&self.pool)?;
+        net_worth_history::table
+            .filter(net_worth_history::month_start.ge(from_month))
+            .order(net_worth_history::month_start.asc())
+            .load::<NetWorthHistoryDB>(&mut conn)?
+            .into_iter()
+            .map(NetWorthHistoryEntry::try_from)
+            .collect()
+    }
+
+    async fn save_history_entry(
+        &self,
+        entry: NetWorthHistoryEntry,
+    ) -> Result<NetWorthHistoryEntry> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<NetWorthHistoryEntry> {
+                    let record = NetWorthHistoryDB {
+                        month_start: entry.month_start,
+                        as_of: entry.as_of,
+                        base_currency: entry.base_currency,
+                        total_assets: entry.total_assets.to_string(),
+                        total_liabilities: entry.total_liabilities.to_string(),
+                        net_worth: entry.net_worth.to_string(),
+                        goal_allocated: entry.goal_allocated.to_string(),
+                        free_assets: entry.free_assets.to_string(),
+                        assets_by_class: serde_json::to_string(&entry.assets_by_class)
+                            .map_err(|e| Error::Unexpected(e.to_string()))?,
+                        updated_at: Utc::now().to_rfc3339(),
+                    };
+                    diesel::replace_into(net_worth_history::table)
+                        .values(&record)
+                        .execute(conn)?;
+                    net_worth_history::table
+                        .find(record.month_start)
+                        .first::<NetWorthHistoryDB>(conn)?
+                        .try_into()
+                },
+            )
+            .await
+    }
+}
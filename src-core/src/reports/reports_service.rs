@@ -0,0 +1,362 @@
+use async_trait::async_trait;
+use chrono::{Days, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use super::reports_model::{
+    assets_by_class, split_by_class, LiabilityBalance, NetWorthChange, NetWorthHistoryEntry,
+    NetWorthStatement, UNCLASSIFIED_ASSET_CLASS,
+};
+use super::reports_traits::{NetWorthHistoryRepositoryTrait, ReportServiceTrait};
+use crate::accounts::{Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY};
+use crate::assets::{AssetServiceTrait, CASH_ASSET_CLASS};
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::fx::FxServiceTrait;
+use crate::goals::emergency_fund_service::allocated_value;
+use crate::goals::GoalRepositoryTrait;
+use crate::loans::{amortize, outstanding_on, LoanRepositoryTrait};
+use crate::market_data::{MarketDataServiceTrait, Quote};
+use crate::portfolio::snapshot::{Position, SnapshotServiceTrait};
+use crate::portfolio::valuation::{DailyAccountValuation, ValuationRepositoryTrait};
+use crate::utils::time_utils::{month_start, next_month_start};
+
+/// Longest run of months the statement history covers
+const MAX_HISTORY_MONTHS: u32 = 120;
+
+/// How far back an account's last valuation, or a holding's last close, still counts on a date
+const LOOKBACK_DAYS: u64 = 31;
+
+pub struct ReportService {
+    repository: Arc<dyn NetWorthHistoryRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    valuation_repository: Arc<dyn ValuationRepositoryTrait>,
+    snapshot_service: Arc<dyn SnapshotServiceTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    loan_repository: Arc<dyn LoanRepositoryTrait>,
+    goal_repo: Arc<dyn GoalRepositoryTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+}
+
+impl ReportService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repository: Arc<dyn NetWorthHistoryRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        valuation_repository: Arc<dyn ValuationRepositoryTrait>,
+        snapshot_service: Arc<dyn SnapshotServiceTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        loan_repository: Arc<dyn LoanRepositoryTrait>,
+        goal_repo: Arc<dyn GoalRepositoryTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            valuation_repository,
+            snapshot_service,
+            asset_service,
+            market_data_service,
+            loan_repository,
+            goal_repo,
+            fx_service,
+            base_currency,
+        }
+    }
+
+    /// Last valuation of the account on or shortly before `date`
+    fn valuation_on(
+        &self,
+        account_id: &str,
+        date: NaiveDate,
+    ) -> Result<Option<DailyAccountValuation>> {
+        let from = date.checked_sub_days(Days::new(LOOKBACK_DAYS));
+        Ok(self
+            .valuation_repository
+            .get_historical_valuations(account_id, from, Some(date))?
+            .pop())
+    }
+
+    /// Balance owed on a liability account: its loan's schedule when it has one, otherwise
+    /// its valuation
+    fn liability_balance(
+        &self,
+        account: &Account,
+        date: NaiveDate,
+        base_currency: &str,
+    ) -> Result<Decimal> {
+        match self.loan_repository.get_loan_for_account(&account.id)? {
+            Some(loan) if date < loan.start_date => Ok(Decimal::ZERO),
+            Some(loan) => {
+                let prepayments = self.loan_repository.get_prepayments(Some(&loan.id))?;
+                let resets = self.loan_repository.get_rate_resets(Some(&loan.id))?;
+                let rows = amortize(&loan, &prepayments, &resets);
+                let balance = outstanding_on(&loan, &rows, &prepayments, date);
+                if account.currency == base_currency {
+                    Ok(balance)
+                } else {
+                    self.fx_service.convert_currency_for_date(
+                        balance,
+                        &account.currency,
+                        base_currency,
+                        date,
+                    )
+                }
+            }
+            None => Ok(self
+                .valuation_on(&account.id, date)?
+                .map(|v| (v.total_value * v.fx_rate_to_base).abs())
+                .unwrap_or_default()),
+        }
+    }
+
+    /// Holdings valued in the base currency at their last close, or at cost when they have
+    /// no recent quote, tagged with their asset class
+    fn holding_values(
+        &self,
+        positions: &[Position],
+        asset_classes: &HashMap<String, String>,
+        closes: &HashMap<String, &Quote>,
+        date: NaiveDate,
+        base_currency: &str,
+    ) -> Result<Vec<(String, Decimal)>> {
+        positions
+            .iter()
+            .map(|position| {
+                let (value, currency) = match closes.get(&position.asset_id) {
+                    Some(quote) => (quote.close * position.quantity, quote.currency.as_str()),
+                    None => (position.total_cost_basis, position.currency.as_str()),
+                };
+                let value = if currency == base_currency {
+                    value
+                } else {
+                    self.fx_service.convert_currency_for_date(
+                        value,
+                        currency,
+                        base_currency,
+                        date,
+                    )?
+                };
+                let asset_class = asset_classes
+                    .get(&position.asset_id)
+                    .cloned()
+                    .unwrap_or_else(|| UNCLASSIFIED_ASSET_CLASS.to_string());
+                Ok((asset_class, value))
+            })
+            .collect()
+    }
+
+    /// The statement as of `date`, without the comparison to the month before
+    async fn build_statement(&self, date: NaiveDate) -> Result<NetWorthStatement> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+
+        let mut liabilities = Vec::new();
+        let mut account_values: HashMap<String, Decimal> = HashMap::new();
+        let mut class_values: Vec<(String, Decimal)> = Vec::new();
+        // Market value of each account with the positions it is spread over
+        let mut invested: Vec<(Decimal, Vec<Position>)> = Vec::new();
+        for account in self.account_repository.list(Some(true), None)? {
+            if account.account_type == ACCOUNT_TYPE_LIABILITY {
+                let balance = self.liability_balance(&account, date, &base_currency)?;
+                if !balance.is_zero() {
+                    liabilities.push(LiabilityBalance {
+                        account_id: account.id,
+                        account_name: account.name,
+                        balance,
+                    });
+                }
+                continue;
+            }
+
+            let Some(valuation) = self.valuation_on(&account.id, date)? else {
+                continue;
+            };
+            let rate = valuation.fx_rate_to_base;
+            account_values.insert(account.id.clone(), valuation.total_value * rate);
+            class_values.push((CASH_ASSET_CLASS.to_string(), valuation.cash_balance * rate));
+
+            let market_value = valuation.investment_market_value * rate;
+            if !market_value.is_zero() {
+                let positions = self
+                    .snapshot_service
+                    .get_daily_holdings_snapshots(&account.id, Some(date), Some(date))?
+                    .pop()
+                    .map(|snapshot| snapshot.positions.into_values().collect())
+                    .unwrap_or_default();
+                invested.push((market_value, positions));
+            }
+        }
+
+        if !invested.is_empty() {
+            let symbols: HashSet<String> = invested
+                .iter()
+                .flat_map(|(_, positions)| positions.iter().map(|p| p.asset_id.clone()))
+                .collect();
+            let from = date
+                .checked_sub_days(Days::new(LOOKBACK_DAYS))
+                .unwrap_or(date);
+            let quotes = self
+                .market_data_service
+                .get_historical_quotes_for_symbols_in_range(&symbols, from, date)?;
+            let mut closes: HashMap<String, &Quote> = HashMap::new();
+            for quote in &quotes {
+                let latest = closes.entry(quote.symbol.clone()).or_insert(quote);
+                if quote.timestamp > latest.timestamp {
+                    *latest = quote;
+                }
+            }
+            let asset_classes: HashMap<String, String> = self
+                .asset_service
+                .get_assets()?
+                .into_iter()
+                .filter(|asset| symbols.contains(&asset.id))
+                .filter_map(|asset| asset.asset_class.map(|asset_class| (asset.id, asset_class)))
+                .collect();
+
+            for (market_value, positions) in &invested {
+                let holdings =
+                    self.holding_values(positions, &asset_classes, &closes, date, &base_currency)?;
+                class_values.extend(split_by_class(*market_value, &holdings));
+            }
+        }
+
+        let total_assets: Decimal = class_values.iter().map(|(_, value)| *value).sum();
+        let total_liabilities: Decimal = liabilities.iter().map(|l| l.balance).sum();
+        let allocations = self
+            .goal_repo
+            .load_allocations_for_non_achieved_goals()
+            .await?;
+        let goal_allocated = allocated_value(
+            &allocations,
+            &account_values,
+            &date.format(FORECAST_DATE_FORMAT).to_string(),
+        )
+        .min(total_assets)
+        .max(Decimal::ZERO);
+
+        Ok(NetWorthStatement {
+            as_of: date,
+            base_currency,
+            assets_by_class: assets_by_class(class_values, total_assets),
+            total_assets,
+            liabilities,
+            total_liabilities,
+            net_worth: total_assets - total_liabilities,
+            goal_allocated,
+            free_assets: total_assets - goal_allocated,
+            previous_month: None,
+            month_over_month: None,
+        })
+    }
+
+    /// Month-end statement of the month before `month` as recorded, when it was recorded at
+    /// month end in the current base currency
+    fn recorded_previous_month(&self, month: NaiveDate) -> Result<Option<NetWorthHistoryEntry>> {
+        let Some(previous_end) = month.pred_opt() else {
+            return Ok(None);
+        };
+        let base_currency = self.base_currency.read().unwrap().clone();
+        Ok(self
+            .repository
+            .get_history_entry(month_start(previous_end))?
+            .filter(|entry| entry.as_of == previous_end && entry.base_currency == base_currency))
+    }
+
+    /// Month-end statement of the month before `month`, drawn up when it is not recorded yet.
+    /// `None` before there was anything to report.
+    async fn previous_month_entry(&self, month: NaiveDate) -> Result<Option<NetWorthHistoryEntry>> {
+        if let Some(entry) = self.recorded_previous_month(month)? {
+            return Ok(Some(entry));
+        }
+        let Some(previous_end) = month.pred_opt() else {
+            return Ok(None);
+        };
+        let statement = self.build_statement(previous_end).await?;
+        if statement.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(statement.history_entry(month_start(previous_end))))
+    }
+}
+
+#[async_trait]
+impl ReportServiceTrait for ReportService {
+    async fn get_net_worth_statement(&self, date: NaiveDate) -> Result<NetWorthStatement> {
+        let today = Utc::now().date_naive();
+        if date > today {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A net worth statement cannot be drawn up for a future date".to_string(),
+            )));
+        }
+
+        let mut statement = self.build_statement(date).await?;
+        let month = month_start(date);
+        let entry = statement.history_entry(month);
+        let previous = self.previous_month_entry(month).await?;
+        statement.month_over_month = previous
+            .as_ref()
+            .map(|previous| NetWorthChange::between(previous, &entry));
+        statement.previous_month = previous;
+        Ok(statement)
+    }
+
+    async fn record_net_worth_history(&self, today: NaiveDate) -> Result<()> {
+        let month = month_start(today);
+        if self.recorded_previous_month(month)?.is_none() {
+            if let Some(previous_end) = month.pred_opt() {
+                let previous = self.build_statement(previous_end).await?;
+                if !previous.is_empty() {
+                    self.repository
+                        .save_history_entry(previous.history_entry(month_start(previous_end)))
+                        .await?;
+                }
+            }
+        }
+
+        let statement = self.build_statement(today).await?;
+        if !statement.is_empty() {
+            self.repository
+                .save_history_entry(statement.history_entry(month))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn get_net_worth_statement_history(&self, months: u32) -> Result<Vec<NetWorthHistoryEntry>> {
+        if months == 0 || months > MAX_HISTORY_MONTHS {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Net worth history covers 1 to {} months",
+                MAX_HISTORY_MONTHS
+            ))));
+        }
+        // One month more is read so the first month shown has its change too
+        let first_month = month_start(Utc::now().date_naive())
+            .checked_sub_months(Months::new(months))
+            .unwrap_or(NaiveDate::MIN);
+        let recorded = self.repository.get_history(first_month)?;
+
+        let mut history: Vec<NetWorthHistoryEntry> = Vec::with_capacity(recorded.len());
+        let mut previous: Option<&NetWorthHistoryEntry> = None;
+        for entry in &recorded {
+            let follows = previous.is_some_and(|p| {
+                next_month_start(p.month_start) == entry.month_start
+                    && p.base_currency == entry.base_currency
+            });
+            if entry.month_start > first_month {
+                history.push(NetWorthHistoryEntry {
+                    change: previous
+                        .filter(|_| follows)
+                        .map(|p| NetWorthChange::between(p, entry)),
+                    ..entry.clone()
+                });
+            }
+            previous = Some(entry);
+        }
+        Ok(history)
+    }
+}
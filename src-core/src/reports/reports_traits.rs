@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::reports_model::{NetWorthHistoryEntry, NetWorthStatement};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait NetWorthHistoryRepositoryTrait: Send + Sync {
+    fn get_history_entry(&self, month_start: NaiveDate) -> Result<Option<NetWorthHistoryEntry>>;
+    /// Recorded months from `from_month` on, oldest first
+    fn get_history(&self, from_month: NaiveDate) -> Result<Vec<NetWorthHistoryEntry>>;
+    /// Records the entry for its month, replacing what was recorded before
+    async fn save_history_entry(&self, entry: NetWorthHistoryEntry)
+        -> Result<NetWorthHistoryEntry>;
+}
+
+#[async_trait]
+pub trait ReportServiceTrait: Send + Sync {
+    /// Net worth statement as of the end of `date`, with the change from the month before.
+    /// Nothing is recorded; see `record_net_worth_history`.
+    async fn get_net_worth_statement(&self, date: NaiveDate) -> Result<NetWorthStatement>;
+    /// Records today's statement for this month and, when it is not recorded at month end yet,
+    /// last month's. Run after each portfolio update, so the history fills in whether or not
+    /// anyone opens the statement.
+    async fn record_net_worth_history(&self, today: NaiveDate) -> Result<()>;
+    /// Recorded statements of the last `months` months, this one included, oldest first, each
+    /// with its change from the month before
+    fn get_net_worth_statement_history(&self, months: u32) -> Result<Vec<NetWorthHistoryEntry>>;
+}
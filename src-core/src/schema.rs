@@ -1,5 +1,39 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    account_monthly_summaries (account_id, month_start) {
+        account_id -> Text,
+        month_start -> Date,
+        first_date -> Date,
+        last_date -> Date,
+        value_count -> Integer,
+        account_currency -> Text,
+        opening_total_value -> Text,
+        closing_total_value -> Text,
+        closing_fx_rate_to_base -> Text,
+        closing_cost_basis -> Text,
+        closing_net_contribution -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    account_group_members (group_id, account_id) {
+        group_id -> Text,
+        account_id -> Text,
+    }
+}
+
+diesel::table! {
+    account_groups (id) {
+        id -> Text,
+        name -> Text,
+        parent_id -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     accounts (id) {
         id -> Text,
@@ -34,6 +68,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    activity_categories (activity_id) {
+        activity_id -> Text,
+        category_id -> Text,
+        source -> Text,
+    }
+}
+
+diesel::table! {
+    activity_dividends (activity_id) {
+        activity_id -> Text,
+        ex_date -> Nullable<Text>,
+        pay_date -> Nullable<Text>,
+        amount_per_share -> Nullable<Text>,
+        tax_withheld -> Text,
+        is_reinvested -> Bool,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     activity_import_profiles (account_id) {
         account_id -> Text,
@@ -43,6 +97,29 @@ diesel::table! {
         created_at -> Timestamp,
         updated_at -> Timestamp,
         account_mappings -> Text,
+        number_locale -> Text,
+    }
+}
+
+diesel::table! {
+    activity_tags (activity_id, tag) {
+        activity_id -> Text,
+        tag -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    api_tokens (id) {
+        id -> Text,
+        name -> Text,
+        token_hash -> Text,
+        token_prefix -> Text,
+        scopes -> Text,
+        expires_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
     }
 }
 
@@ -77,6 +154,203 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    audit_log (id) {
+        id -> Text,
+        entity_type -> Text,
+        entity_id -> Text,
+        action -> Text,
+        actor -> Text,
+        changes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    automation_rule_firings (rule_id, subject) {
+        rule_id -> Text,
+        subject -> Text,
+        marker -> Text,
+        fired_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    automation_rules (id) {
+        id -> Text,
+        name -> Text,
+        trigger_config -> Text,
+        actions -> Text,
+        is_enabled -> Bool,
+        last_triggered_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    bank_connection_imports (connection_id, external_id) {
+        connection_id -> Text,
+        external_id -> Text,
+        activity_id -> Text,
+        imported_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    bank_connections (id) {
+        id -> Text,
+        provider -> Text,
+        name -> Text,
+        account_id -> Text,
+        base_url -> Text,
+        sync_interval_hours -> Integer,
+        is_enabled -> Bool,
+        last_attempt_at -> Nullable<Timestamp>,
+        last_synced_at -> Nullable<Timestamp>,
+        last_sync_error -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    benchmarks (id) {
+        id -> Text,
+        name -> Text,
+        symbol -> Text,
+        data_source -> Text,
+        currency -> Text,
+        is_custom -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    bill_payments (id) {
+        id -> Text,
+        bill_id -> Text,
+        due_date -> Text,
+        activity_id -> Nullable<Text>,
+        amount -> Text,
+        paid_date -> Text,
+        source -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    bills (id) {
+        id -> Text,
+        name -> Text,
+        payee -> Text,
+        amount -> Text,
+        currency -> Text,
+        account_id -> Nullable<Text>,
+        category_id -> Nullable<Text>,
+        frequency -> Text,
+        due_day -> Integer,
+        start_date -> Text,
+        end_date -> Nullable<Text>,
+        reminder_days -> Integer,
+        match_pattern -> Nullable<Text>,
+        is_active -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        lunar_month -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    bonus_plan_lines (id) {
+        id -> Text,
+        plan_id -> Text,
+        kind -> Text,
+        goal_id -> Nullable<Text>,
+        envelope_id -> Nullable<Text>,
+        label -> Text,
+        amount -> Text,
+        allocation_id -> Nullable<Text>,
+        transfer_id -> Nullable<Text>,
+        sort_order -> Integer,
+    }
+}
+
+diesel::table! {
+    bonds (id) {
+        id -> Text,
+        account_id -> Text,
+        asset_id -> Text,
+        activity_id -> Nullable<Text>,
+        code -> Text,
+        issuer -> Text,
+        issuer_type -> Text,
+        face_value -> Text,
+        quantity -> Text,
+        coupon_rate -> Text,
+        coupon_frequency -> Text,
+        issue_date -> Text,
+        maturity_date -> Text,
+        purchase_date -> Text,
+        purchase_price -> Text,
+        market_price -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    bonus_plans (id) {
+        id -> Text,
+        account_id -> Text,
+        income_source_id -> Nullable<Text>,
+        amount -> Text,
+        currency -> Text,
+        expected_date -> Text,
+        activity_id -> Nullable<Text>,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    budget_categories (id) {
+        id -> Text,
+        name -> Text,
+        monthly_amount -> Text,
+        currency -> Text,
+        rollover -> Text,
+        start_month -> Text,
+        is_archived -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    budget_month_amounts (category_id, month) {
+        category_id -> Text,
+        month -> Text,
+        amount -> Text,
+    }
+}
+
+diesel::table! {
+    categorization_rules (id) {
+        id -> Text,
+        category_id -> Text,
+        field -> Text,
+        match_type -> Text,
+        pattern -> Text,
+        priority -> Integer,
+        is_active -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     contribution_limits (id) {
         id -> Text,
@@ -108,6 +382,66 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    deposit_rates (bank_code, term_months) {
+        bank_code -> Text,
+        term_months -> Integer,
+        bank_name -> Text,
+        rate -> Text,
+        source -> Text,
+        fetched_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    education_plans (id) {
+        id -> Text,
+        child_name -> Text,
+        birth_year -> Integer,
+        goal_id -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    education_stages (id) {
+        id -> Text,
+        plan_id -> Text,
+        name -> Text,
+        school_type -> Text,
+        currency -> Text,
+        start_year -> Integer,
+        years -> Integer,
+        annual_cost -> Text,
+        tuition_inflation -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    envelope_transfers (id) {
+        id -> Text,
+        account_id -> Text,
+        from_envelope_id -> Nullable<Text>,
+        to_envelope_id -> Nullable<Text>,
+        amount -> Text,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    envelopes (id) {
+        id -> Text,
+        account_id -> Text,
+        name -> Text,
+        balance -> Text,
+        is_archived -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     goals (id) {
         id -> Text,
@@ -120,6 +454,60 @@ diesel::table! {
         monthly_investment -> Nullable<Double>,
         start_date -> Nullable<Text>,
         initial_actual_value -> Nullable<Double>,
+        goal_type -> Text,
+        target_months -> Nullable<Integer>,
+        archived_at -> Nullable<Text>,
+        priority -> Integer,
+        depends_on_goal_id -> Nullable<Text>,
+        currency -> Nullable<Text>,
+        achieved_at -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    esop_grants (id) {
+        id -> Text,
+        account_id -> Text,
+        asset_id -> Text,
+        activity_id -> Nullable<Text>,
+        company -> Text,
+        symbol -> Nullable<Text>,
+        grant_date -> Text,
+        vesting_start_date -> Text,
+        total_shares -> Text,
+        strike_price -> Text,
+        cliff_months -> Integer,
+        vesting_months -> Integer,
+        vesting_interval_months -> Integer,
+        lockup_months -> Integer,
+        fair_value -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    goal_monthly_progress (goal_id, month_start) {
+        goal_id -> Text,
+        month_start -> Date,
+        as_of -> Date,
+        init_value -> Double,
+        current_value -> Double,
+        growth -> Double,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    goal_progress_history (goal_id, as_of) {
+        goal_id -> Text,
+        as_of -> Date,
+        init_value -> Double,
+        current_value -> Double,
+        growth -> Double,
+        allocation_details -> Text,
+        updated_at -> Text,
     }
 }
 
@@ -165,6 +553,65 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    income_sources (id) {
+        id -> Text,
+        name -> Text,
+        kind -> Text,
+        account_id -> Nullable<Text>,
+        amount -> Text,
+        currency -> Text,
+        frequency -> Text,
+        pay_day -> Integer,
+        start_date -> Text,
+        end_date -> Nullable<Text>,
+        match_pattern -> Nullable<Text>,
+        is_active -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    loan_prepayments (id) {
+        id -> Text,
+        loan_id -> Text,
+        payment_date -> Text,
+        amount -> Text,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    loan_rate_resets (id) {
+        id -> Text,
+        loan_id -> Text,
+        effective_date -> Text,
+        base_rate -> Text,
+        note -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    loans (id) {
+        id -> Text,
+        account_id -> Text,
+        principal -> Text,
+        start_date -> Text,
+        term_months -> Integer,
+        repayment_method -> Text,
+        fixed_rate -> Text,
+        fixed_months -> Integer,
+        base_rate -> Nullable<Text>,
+        floating_margin -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        kind -> Text,
+    }
+}
+
 diesel::table! {
     market_data_providers (id) {
         id -> Text,
@@ -180,6 +627,41 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    net_worth_history (month_start) {
+        month_start -> Date,
+        as_of -> Date,
+        base_currency -> Text,
+        total_assets -> Text,
+        total_liabilities -> Text,
+        net_worth -> Text,
+        goal_allocated -> Text,
+        free_assets -> Text,
+        assets_by_class -> Text,
+        updated_at -> Text,
+    }
+}
+
+diesel::table! {
+    planned_cash_flows (id) {
+        id -> Text,
+        name -> Text,
+        kind -> Text,
+        account_id -> Nullable<Text>,
+        amount -> Text,
+        currency -> Text,
+        frequency -> Text,
+        start_date -> Text,
+        end_date -> Nullable<Text>,
+        is_active -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+        bank_code -> Nullable<Text>,
+        term_months -> Nullable<Integer>,
+        interest_rate -> Nullable<Text>,
+    }
+}
+
 diesel::table! {
     platforms (id) {
         id -> Text,
@@ -188,6 +670,69 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    private_loan_repayments (id) {
+        id -> Text,
+        loan_id -> Text,
+        activity_id -> Nullable<Text>,
+        payment_date -> Text,
+        amount -> Text,
+        source -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    private_loans (id) {
+        id -> Text,
+        account_id -> Text,
+        asset_id -> Text,
+        activity_id -> Nullable<Text>,
+        borrower -> Text,
+        platform -> Nullable<Text>,
+        principal -> Text,
+        interest_rate -> Text,
+        repayment_plan -> Text,
+        term_months -> Integer,
+        start_date -> Text,
+        match_pattern -> Nullable<Text>,
+        written_off -> Bool,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    properties (id) {
+        id -> Text,
+        account_id -> Text,
+        asset_id -> Text,
+        activity_id -> Nullable<Text>,
+        name -> Text,
+        address -> Nullable<Text>,
+        purchase_date -> Text,
+        purchase_price -> Text,
+        transaction_costs -> Text,
+        mortgage_account_id -> Nullable<Text>,
+        rental_income_source_id -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    property_appraisals (id) {
+        id -> Text,
+        property_id -> Text,
+        appraisal_date -> Text,
+        value -> Text,
+        source -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     quotes (id) {
         id -> Text,
@@ -205,6 +750,103 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    recurring_rules (id) {
+        id -> Text,
+        account_id -> Text,
+        name -> Text,
+        activity_type -> Text,
+        asset_id -> Nullable<Text>,
+        amount -> Text,
+        currency -> Nullable<Text>,
+        frequency -> Text,
+        start_date -> Text,
+        end_date -> Nullable<Text>,
+        next_due_date -> Nullable<Text>,
+        is_paused -> Bool,
+        comment -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    scripts (id) {
+        id -> Text,
+        name -> Text,
+        event -> Text,
+        source -> Text,
+        is_enabled -> Bool,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sheet_exports (id) {
+        id -> Text,
+        name -> Text,
+        report -> Text,
+        spreadsheet_id -> Text,
+        sheet_name -> Text,
+        sync_interval_hours -> Integer,
+        is_enabled -> Bool,
+        last_attempt_at -> Nullable<Timestamp>,
+        last_synced_at -> Nullable<Timestamp>,
+        last_sync_error -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sip_plans (id) {
+        id -> Text,
+        account_id -> Text,
+        asset_id -> Text,
+        name -> Text,
+        amount -> Text,
+        start_date -> Text,
+        end_date -> Nullable<Text>,
+        planned_cash_flow_id -> Nullable<Text>,
+        is_active -> Bool,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    term_deposits (id) {
+        id -> Text,
+        account_id -> Text,
+        bank_name -> Text,
+        principal -> Text,
+        interest_rate -> Text,
+        term_months -> Integer,
+        start_date -> Text,
+        compounding -> Text,
+        rollover -> Text,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    valuation_archives (account_id, month_start) {
+        account_id -> Text,
+        month_start -> Date,
+        first_date -> Date,
+        last_date -> Date,
+        row_count -> Integer,
+        account_currency -> Text,
+        base_currency -> Text,
+        calculated_at -> Text,
+        data -> Binary,
+    }
+}
+
 diesel::table! {
     vn_assets (id) {
         id -> Nullable<Text>,
@@ -249,11 +891,50 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(account_group_members -> account_groups (group_id));
+diesel::joinable!(account_group_members -> accounts (account_id));
 diesel::joinable!(accounts -> platforms (platform_id));
+diesel::joinable!(activity_categories -> activities (activity_id));
+diesel::joinable!(activity_categories -> budget_categories (category_id));
+diesel::joinable!(activity_dividends -> activities (activity_id));
+diesel::joinable!(activity_tags -> activities (activity_id));
+diesel::joinable!(automation_rule_firings -> automation_rules (rule_id));
+diesel::joinable!(bank_connection_imports -> bank_connections (connection_id));
+diesel::joinable!(bank_connections -> accounts (account_id));
+diesel::joinable!(bill_payments -> activities (activity_id));
+diesel::joinable!(bill_payments -> bills (bill_id));
+diesel::joinable!(bills -> accounts (account_id));
+diesel::joinable!(bills -> budget_categories (category_id));
+diesel::joinable!(bonus_plan_lines -> bonus_plans (plan_id));
+diesel::joinable!(bonds -> accounts (account_id));
+diesel::joinable!(bonus_plans -> accounts (account_id));
+diesel::joinable!(budget_month_amounts -> budget_categories (category_id));
+diesel::joinable!(categorization_rules -> budget_categories (category_id));
+diesel::joinable!(education_plans -> goals (goal_id));
+diesel::joinable!(education_stages -> education_plans (plan_id));
+diesel::joinable!(envelope_transfers -> accounts (account_id));
+diesel::joinable!(envelopes -> accounts (account_id));
 diesel::joinable!(goals_allocation -> accounts (account_id));
+diesel::joinable!(planned_cash_flows -> accounts (account_id));
+diesel::joinable!(goal_monthly_progress -> goals (goal_id));
+diesel::joinable!(goal_progress_history -> goals (goal_id));
+diesel::joinable!(esop_grants -> accounts (account_id));
 diesel::joinable!(goals_allocation -> goals (goal_id));
+diesel::joinable!(income_sources -> accounts (account_id));
+diesel::joinable!(loan_prepayments -> loans (loan_id));
+diesel::joinable!(loan_rate_resets -> loans (loan_id));
+diesel::joinable!(loans -> accounts (account_id));
+diesel::joinable!(private_loan_repayments -> activities (activity_id));
+diesel::joinable!(private_loan_repayments -> private_loans (loan_id));
+diesel::joinable!(private_loans -> accounts (account_id));
+diesel::joinable!(properties -> accounts (account_id));
+diesel::joinable!(sip_plans -> accounts (account_id));
+diesel::joinable!(term_deposits -> accounts (account_id));
+diesel::joinable!(recurring_rules -> accounts (account_id));
+diesel::joinable!(sip_plans -> planned_cash_flows (planned_cash_flow_id));
+diesel::joinable!(property_appraisals -> properties (property_id));
 diesel::joinable!(allocation_versions -> goals_allocation (allocation_id));
 diesel::joinable!(quotes -> assets (symbol));
 
 diesel::allow_tables_to_appear_in_same_query!(
-    accounts,activities,activity_import_profiles,app_settings,assets,contribution_limits,daily_account_valuation,goals,goals_allocation,allocation_versions,holdings_snapshots,market_data_providers,platforms,quotes,vn_assets,vn_assets_sync,vn_historical_records,);
+    account_group_members,account_groups,account_monthly_summaries,accounts,activities,activity_categories,activity_dividends,activity_import_profiles,activity_tags,api_tokens,app_settings,assets,audit_log,automation_rule_firings,automation_rules,bank_connection_imports,bank_connections,benchmarks,bill_payments,bills,bonds,bonus_plan_lines,bonus_plans,budget_categories,budget_month_amounts,categorization_rules,contribution_limits,daily_account_valuation,deposit_rates,education_plans,education_stages,envelope_transfers,envelopes,esop_grants,goal_monthly_progress,goal_progress_history,goals,goals_allocation,allocation_versions,holdings_snapshots,income_sources,loan_prepayments,loan_rate_resets,loans,market_data_providers,net_worth_history,planned_cash_flows,platforms,private_loan_repayments,private_loans,properties,property_appraisals,quotes,recurring_rules,scripts,sheet_exports,sip_plans,term_deposits,valuation_archives,vn_assets,vn_assets_sync,vn_historical_records,);
@@ -0,0 +1,13 @@
+mod scripting_engine;
+mod scripting_model;
+mod scripting_repository;
+mod scripting_service;
+mod scripting_traits;
+
+pub use scripting_model::{
+    NewScript, Script, ScriptEvent, ScriptFailure, ScriptGoalProgress, ScriptRunReport,
+    MAX_SCRIPT_SIZE,
+};
+pub use scripting_repository::ScriptRepository;
+pub use scripting_service::ScriptService;
+pub use scripting_traits::{ScriptLookups, ScriptRepositoryTrait, ScriptServiceTrait};
@@ -0,0 +1,155 @@
+use chrono::Utc;
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use super::scripting_model::ScriptRunReport;
+use super::scripting_traits::ScriptLookups;
+use crate::errors::{Error, Result, ValidationError};
+use crate::notifications::{Notification, NotificationChannel};
+
+/// Budget for one run; a script that loops forever stops here instead of hanging the hook
+const MAX_OPERATIONS: u64 = 100_000;
+const MAX_CALL_LEVELS: usize = 16;
+const MAX_STRING_SIZE: usize = 10_000;
+const MAX_COLLECTION_SIZE: usize = 1_000;
+/// Notifications one script may send per run
+const MAX_NOTIFICATIONS: usize = 20;
+
+/// Engine with the limits and none of the host API, for checking syntax
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE)
+        // No `import`, and no `eval` to get around the checks done at compile time
+        .set_max_modules(0)
+        .disable_symbol("eval");
+    engine
+}
+
+/// Compiles a script so it can be rejected before it is saved
+pub(crate) fn check_syntax(source: &str) -> Result<()> {
+    sandboxed_engine().compile(source).map(|_| ()).map_err(|e| {
+        Error::Validation(ValidationError::InvalidInput(format!(
+            "Script error: {}",
+            e
+        )))
+    })
+}
+
+pub(crate) fn to_float(value: Decimal) -> Dynamic {
+    Dynamic::from_float(value.to_f64().unwrap_or_default())
+}
+
+pub(crate) fn optional_float(value: Option<Decimal>) -> Dynamic {
+    value.map_or(Dynamic::UNIT, to_float)
+}
+
+/// Reads a number a script left in a map; scripts may assign integers or floats
+fn read_decimal(map: &Map, key: &str) -> Option<Decimal> {
+    let value = map.get(key)?;
+    value
+        .as_float()
+        .ok()
+        .and_then(Decimal::from_f64)
+        .or_else(|| value.as_int().ok().map(Decimal::from))
+}
+
+fn read_number(map: &Map, key: &str) -> Option<f64> {
+    let value = map.get(key)?;
+    value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|i| i as f64))
+}
+
+/// The number at `key` if the script changed it, so values it left alone keep their exact
+/// decimal instead of a round trip through a float. `Some(None)` means it was cleared.
+pub(crate) fn changed_decimal(before: &Map, after: &Map, key: &str) -> Option<Option<Decimal>> {
+    if read_number(before, key) == read_number(after, key) {
+        return None;
+    }
+    Some(read_decimal(after, key))
+}
+
+pub(crate) fn read_string(map: &Map, key: &str) -> Option<String> {
+    map.get(key)?.clone().into_string().ok()
+}
+
+/// Runs compiled scripts for one hook and collects what they send back
+pub(crate) struct ScriptRunner {
+    engine: Engine,
+    report: Arc<Mutex<ScriptRunReport>>,
+}
+
+impl ScriptRunner {
+    /// Scripts get `notify(title, body)`, `log(message)`, `latest_price(symbol)` and
+    /// `fx_rate(from, to)`; lookups return `()` when there is no value
+    pub(crate) fn new(lookups: Arc<dyn ScriptLookups>, channel: NotificationChannel) -> Self {
+        let report = Arc::new(Mutex::new(ScriptRunReport::default()));
+        let mut engine = sandboxed_engine();
+
+        let sink = report.clone();
+        engine.on_print(move |message| sink.lock().unwrap().logs.push(message.to_string()));
+        let sink = report.clone();
+        engine.on_debug(move |message, _, _| sink.lock().unwrap().logs.push(message.to_string()));
+        let sink = report.clone();
+        engine.register_fn("log", move |message: &str| {
+            sink.lock().unwrap().logs.push(message.to_string())
+        });
+        let sink = report.clone();
+        engine.register_fn("notify", move |title: &str, body: &str| {
+            let mut report = sink.lock().unwrap();
+            if report.notifications.len() < MAX_NOTIFICATIONS {
+                report.notifications.push(Notification {
+                    id: Uuid::new_v4().to_string(),
+                    channel,
+                    title: title.to_string(),
+                    body: body.to_string(),
+                    created_at: Utc::now(),
+                });
+            }
+        });
+        let prices = lookups.clone();
+        engine.register_fn("latest_price", move |symbol: &str| {
+            optional_float(prices.latest_price(symbol))
+        });
+        engine.register_fn("fx_rate", move |from: &str, to: &str| {
+            optional_float(lookups.fx_rate(from, to))
+        });
+
+        Self { engine, report }
+    }
+
+    pub(crate) fn compile(&self, source: &str) -> std::result::Result<AST, String> {
+        self.engine.compile(source).map_err(|e| e.to_string())
+    }
+
+    /// Runs `ast` with `value` bound to `name` and returns the value as the script left it
+    pub(crate) fn run(
+        &self,
+        ast: &AST,
+        name: &str,
+        value: Map,
+    ) -> std::result::Result<Map, String> {
+        let mut scope = Scope::new();
+        scope.push(name.to_string(), value);
+        self.engine
+            .run_ast_with_scope(&mut scope, ast)
+            .map_err(|e| e.to_string())?;
+        scope
+            .get_value::<Map>(name)
+            .ok_or_else(|| format!("`{}` must stay an object map", name))
+    }
+
+    pub(crate) fn finish(self) -> ScriptRunReport {
+        std::mem::take(&mut *self.report.lock().unwrap())
+    }
+}
@@ -0,0 +1,203 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::goals::goals_model::{
+    GOAL_TYPE_EMERGENCY_FUND, GOAL_TYPE_NET_WORTH, GOAL_TYPE_SINKING_FUND,
+};
+use crate::goals::{EmergencyFundProgress, NetWorthGoalProgress, SinkingFundProgress};
+use crate::notifications::{Notification, NotificationChannel};
+
+/// Longest script accepted, in bytes
+pub const MAX_SCRIPT_SIZE: usize = 20_000;
+
+/// Point in the app a script runs at
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptEvent {
+    /// Each row of an activity import before it is checked; the script may edit `row`
+    OnImportRow,
+    /// Each quote after a market data sync
+    OnQuoteUpdate,
+    /// Each goal with tracked progress after the portfolio is recalculated
+    OnGoalProgress,
+}
+
+impl ScriptEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScriptEvent::OnImportRow => "on_import_row",
+            ScriptEvent::OnQuoteUpdate => "on_quote_update",
+            ScriptEvent::OnGoalProgress => "on_goal_progress",
+        }
+    }
+
+    /// Channel of the notifications its scripts send
+    pub fn notification_channel(&self) -> NotificationChannel {
+        match self {
+            ScriptEvent::OnImportRow => NotificationChannel::System,
+            ScriptEvent::OnQuoteUpdate => NotificationChannel::PriceAlert,
+            ScriptEvent::OnGoalProgress => NotificationChannel::GoalProgress,
+        }
+    }
+}
+
+impl FromStr for ScriptEvent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "on_import_row" => Ok(ScriptEvent::OnImportRow),
+            "on_quote_update" => Ok(ScriptEvent::OnQuoteUpdate),
+            "on_goal_progress" => Ok(ScriptEvent::OnGoalProgress),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown script event: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `scripts`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::scripts)]
+pub struct ScriptDB {
+    pub id: String,
+    pub name: String,
+    pub event: String,
+    pub source: String,
+    pub is_enabled: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A user's Rhai script bound to an event
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Script {
+    pub id: String,
+    pub name: String,
+    pub event: ScriptEvent,
+    pub source: String,
+    pub is_enabled: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<ScriptDB> for Script {
+    type Error = Error;
+
+    fn try_from(db: ScriptDB) -> Result<Self> {
+        Ok(Script {
+            id: db.id,
+            name: db.name,
+            event: ScriptEvent::from_str(&db.event)?,
+            source: db.source,
+            is_enabled: db.is_enabled,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewScript {
+    pub id: Option<String>,
+    pub name: String,
+    pub event: ScriptEvent,
+    pub source: String,
+    pub is_enabled: bool,
+}
+
+impl NewScript {
+    /// Checks the fields; the source is compiled separately by the engine
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::Validation(ValidationError::MissingField(
+                "name".to_string(),
+            )));
+        }
+        if self.source.len() > MAX_SCRIPT_SIZE {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Script must be at most {} bytes",
+                MAX_SCRIPT_SIZE
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// Progress of one goal as a script sees it in `goal`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptGoalProgress {
+    pub goal_id: String,
+    pub goal_title: String,
+    pub goal_type: String,
+    pub target_amount: Decimal,
+    pub current_amount: Decimal,
+    pub progress: Option<Decimal>,
+}
+
+impl From<&EmergencyFundProgress> for ScriptGoalProgress {
+    fn from(fund: &EmergencyFundProgress) -> Self {
+        Self {
+            goal_id: fund.goal_id.clone(),
+            goal_title: fund.goal_title.clone(),
+            goal_type: GOAL_TYPE_EMERGENCY_FUND.to_string(),
+            target_amount: fund.target_amount,
+            current_amount: fund.current_value,
+            progress: fund.progress,
+        }
+    }
+}
+
+impl From<&SinkingFundProgress> for ScriptGoalProgress {
+    fn from(fund: &SinkingFundProgress) -> Self {
+        Self {
+            goal_id: fund.goal_id.clone(),
+            goal_title: fund.goal_title.clone(),
+            goal_type: GOAL_TYPE_SINKING_FUND.to_string(),
+            target_amount: fund.target_amount,
+            current_amount: fund.current_value,
+            progress: fund.progress,
+        }
+    }
+}
+
+impl From<&NetWorthGoalProgress> for ScriptGoalProgress {
+    fn from(goal: &NetWorthGoalProgress) -> Self {
+        Self {
+            goal_id: goal.goal_id.clone(),
+            goal_title: goal.goal_title.clone(),
+            goal_type: GOAL_TYPE_NET_WORTH.to_string(),
+            target_amount: goal.target_amount,
+            current_amount: goal.current.net_worth,
+            progress: goal.progress,
+        }
+    }
+}
+
+/// A script that failed to run; the hook carries on with the other scripts
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptFailure {
+    pub script_id: String,
+    pub script_name: String,
+    pub message: String,
+}
+
+/// What the scripts of one hook produced
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptRunReport {
+    /// Sent with `notify(title, body)`
+    pub notifications: Vec<Notification>,
+    /// Written with `log(message)` or `print`
+    pub logs: Vec<String>,
+    pub failures: Vec<ScriptFailure>,
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::scripting_model::{NewScript, Script, ScriptDB, ScriptEvent};
+use super::scripting_traits::ScriptRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::scripts;
+
+pub struct ScriptRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl ScriptRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        ScriptRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl ScriptRepositoryTrait for ScriptRepository {
+    fn get_scripts(&self) -> Result<Vec<Script>> {
+        let mut conn = get_connection(&self.pool)?;
+        scripts::table
+            .order((scripts::event.asc(), scripts::created_at.asc()))
+            .load::<ScriptDB>(&mut conn)?
+            .into_iter()
+            .map(Script::try_from)
+            .collect()
+    }
+
+    fn get_script(&self, id: &str) -> Result<Script> {
+        let mut conn = get_connection(&self.pool)?;
+        scripts::table
+            .find(id)
+            .first::<ScriptDB>(&mut conn)?
+            .try_into()
+    }
+
+    fn get_enabled_scripts(&self, event: ScriptEvent) -> Result<Vec<Script>> {
+        let mut conn = get_connection(&self.pool)?;
+        scripts::table
+            .filter(scripts::event.eq(event.as_str()))
+            .filter(scripts::is_enabled.eq(true))
+            .order(scripts::created_at.asc())
+            .load::<ScriptDB>(&mut conn)?
+            .into_iter()
+            .map(Script::try_from)
+            .collect()
+    }
+
+    async fn insert_script(&self, script: NewScript) -> Result<Script> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Script> {
+                let now = Utc::now().naive_utc();
+                let record = ScriptDB {
+                    id: script.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    name: script.name.trim().to_string(),
+                    event: script.event.as_str().to_string(),
+                    source: script.source,
+                    is_enabled: script.is_enabled,
+                    created_at: now,
+                    updated_at: now,
+                };
+
+                diesel::insert_into(scripts::table)
+                    .values(&record)
+                    .get_result::<ScriptDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_script(&self, id: &str, script: NewScript) -> Result<Script> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<Script> {
+                diesel::update(scripts::table.find(id_owned))
+                    .set((
+                        scripts::name.eq(script.name.trim()),
+                        scripts::event.eq(script.event.as_str()),
+                        scripts::source.eq(script.source),
+                        scripts::is_enabled.eq(script.is_enabled),
+                        scripts::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<ScriptDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_script(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(scripts::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
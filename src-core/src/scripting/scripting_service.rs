@@ -0,0 +1,415 @@
+use async_trait::async_trait;
+use log::warn;
+use rhai::{Dynamic, Map};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+use super::scripting_engine::{
+    changed_decimal, check_syntax, optional_float, read_string, to_float, ScriptRunner,
+};
+use super::scripting_model::{
+    NewScript, Script, ScriptEvent, ScriptFailure, ScriptGoalProgress, ScriptRunReport,
+};
+use super::scripting_traits::{ScriptLookups, ScriptRepositoryTrait, ScriptServiceTrait};
+use crate::activities::ActivityImport;
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::fx::FxServiceTrait;
+use crate::market_data::{MarketDataServiceTrait, Quote};
+
+/// Lookups backed by stored quotes and exchange rates
+struct CoreLookups {
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    fx_service: Arc<dyn FxServiceTrait>,
+}
+
+impl ScriptLookups for CoreLookups {
+    fn latest_price(&self, symbol: &str) -> Option<Decimal> {
+        self.market_data_service
+            .get_latest_quote_for_symbol(symbol)
+            .ok()
+            .map(|quote| quote.close)
+    }
+
+    fn fx_rate(&self, from: &str, to: &str) -> Option<Decimal> {
+        self.fx_service.get_latest_exchange_rate(from, to).ok()
+    }
+}
+
+pub struct ScriptService {
+    repository: Arc<dyn ScriptRepositoryTrait>,
+    lookups: Arc<dyn ScriptLookups>,
+}
+
+impl ScriptService {
+    pub fn new(
+        repository: Arc<dyn ScriptRepositoryTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        fx_service: Arc<dyn FxServiceTrait>,
+    ) -> Self {
+        Self::with_lookups(
+            repository,
+            Arc::new(CoreLookups {
+                market_data_service,
+                fx_service,
+            }),
+        )
+    }
+
+    pub fn with_lookups(
+        repository: Arc<dyn ScriptRepositoryTrait>,
+        lookups: Arc<dyn ScriptLookups>,
+    ) -> Self {
+        Self {
+            repository,
+            lookups,
+        }
+    }
+
+    /// Runs each enabled script of `event` over the items in turn, binding each item to
+    /// `variable`, and returns the items as the scripts left them. A script that fails is
+    /// reported once and skipped for the remaining items.
+    fn run_hook(
+        &self,
+        event: ScriptEvent,
+        variable: &str,
+        mut items: Vec<Map>,
+    ) -> Result<(Vec<Map>, ScriptRunReport)> {
+        let scripts = self.repository.get_enabled_scripts(event)?;
+        if scripts.is_empty() || items.is_empty() {
+            return Ok((items, ScriptRunReport::default()));
+        }
+
+        let runner = ScriptRunner::new(self.lookups.clone(), event.notification_channel());
+        let mut failures = Vec::new();
+        for script in &scripts {
+            let result = runner.compile(&script.source).and_then(|ast| {
+                for item in items.iter_mut() {
+                    *item = runner.run(&ast, variable, item.clone())?;
+                }
+                Ok(())
+            });
+            if let Err(message) = result {
+                warn!(
+                    "Script '{}' failed on {}: {}",
+                    script.name,
+                    event.as_str(),
+                    message
+                );
+                failures.push(ScriptFailure {
+                    script_id: script.id.clone(),
+                    script_name: script.name.clone(),
+                    message,
+                });
+            }
+        }
+
+        let mut report = runner.finish();
+        report.failures = failures;
+        Ok((items, report))
+    }
+}
+
+fn text(value: &str) -> Dynamic {
+    Dynamic::from(value.to_string())
+}
+
+fn row_map(row: &ActivityImport) -> Map {
+    let mut map = Map::new();
+    map.insert("date".into(), text(&row.date));
+    map.insert("symbol".into(), text(&row.symbol));
+    map.insert("activityType".into(), text(&row.activity_type));
+    map.insert("quantity".into(), to_float(row.quantity));
+    map.insert("unitPrice".into(), to_float(row.unit_price));
+    map.insert("currency".into(), text(&row.currency));
+    map.insert("fee".into(), to_float(row.fee));
+    map.insert("amount".into(), optional_float(row.amount));
+    map.insert(
+        "comment".into(),
+        text(row.comment.as_deref().unwrap_or_default()),
+    );
+    map.insert(
+        "accountId".into(),
+        text(row.account_id.as_deref().unwrap_or_default()),
+    );
+    map
+}
+
+/// Copies back the fields a script may edit; the account and line stay as imported
+fn apply_row(row: &mut ActivityImport, before: &Map, after: &Map) {
+    if let Some(date) = read_string(after, "date") {
+        row.date = date;
+    }
+    if let Some(symbol) = read_string(after, "symbol") {
+        row.symbol = symbol;
+    }
+    if let Some(activity_type) = read_string(after, "activityType") {
+        row.activity_type = activity_type;
+    }
+    if let Some(currency) = read_string(after, "currency") {
+        row.currency = currency;
+    }
+    if let Some(comment) = read_string(after, "comment") {
+        row.comment = Some(comment).filter(|c| !c.trim().is_empty());
+    }
+    if let Some(quantity) = changed_decimal(before, after, "quantity") {
+        row.quantity = quantity.unwrap_or_default();
+    }
+    if let Some(unit_price) = changed_decimal(before, after, "unitPrice") {
+        row.unit_price = unit_price.unwrap_or_default();
+    }
+    if let Some(fee) = changed_decimal(before, after, "fee") {
+        row.fee = fee.unwrap_or_default();
+    }
+    if let Some(amount) = changed_decimal(before, after, "amount") {
+        row.amount = amount;
+    }
+}
+
+fn quote_map(quote: &Quote) -> Map {
+    let mut map = Map::new();
+    map.insert("symbol".into(), text(&quote.symbol));
+    map.insert(
+        "date".into(),
+        text(&quote.timestamp.format(FORECAST_DATE_FORMAT).to_string()),
+    );
+    map.insert("open".into(), to_float(quote.open));
+    map.insert("high".into(), to_float(quote.high));
+    map.insert("low".into(), to_float(quote.low));
+    map.insert("close".into(), to_float(quote.close));
+    map.insert("volume".into(), to_float(quote.volume));
+    map.insert("currency".into(), text(&quote.currency));
+    map
+}
+
+fn goal_map(goal: &ScriptGoalProgress) -> Map {
+    let mut map = Map::new();
+    map.insert("goalId".into(), text(&goal.goal_id));
+    map.insert("title".into(), text(&goal.goal_title));
+    map.insert("goalType".into(), text(&goal.goal_type));
+    map.insert("targetAmount".into(), to_float(goal.target_amount));
+    map.insert("currentAmount".into(), to_float(goal.current_amount));
+    map.insert("progress".into(), optional_float(goal.progress));
+    map
+}
+
+#[async_trait]
+impl ScriptServiceTrait for ScriptService {
+    fn get_scripts(&self) -> Result<Vec<Script>> {
+        self.repository.get_scripts()
+    }
+
+    fn get_script(&self, id: &str) -> Result<Script> {
+        self.repository.get_script(id)
+    }
+
+    async fn create_script(&self, script: NewScript) -> Result<Script> {
+        script.validate()?;
+        check_syntax(&script.source)?;
+        self.repository.insert_script(script).await
+    }
+
+    async fn update_script(&self, id: &str, script: NewScript) -> Result<Script> {
+        script.validate()?;
+        check_syntax(&script.source)?;
+        self.repository.update_script(id, script).await
+    }
+
+    async fn delete_script(&self, id: &str) -> Result<usize> {
+        self.repository.delete_script(id).await
+    }
+
+    fn run_import_row_hooks(&self, rows: &mut [ActivityImport]) -> Result<ScriptRunReport> {
+        let before: Vec<Map> = rows.iter().map(row_map).collect();
+        let (after, report) = self.run_hook(ScriptEvent::OnImportRow, "row", before.clone())?;
+        for ((row, before), after) in rows.iter_mut().zip(&before).zip(&after) {
+            apply_row(row, before, after);
+        }
+        Ok(report)
+    }
+
+    fn run_quote_update_hooks(&self, quotes: &[Quote]) -> Result<ScriptRunReport> {
+        let items = quotes.iter().map(quote_map).collect();
+        Ok(self.run_hook(ScriptEvent::OnQuoteUpdate, "quote", items)?.1)
+    }
+
+    fn run_goal_progress_hooks(&self, goals: &[ScriptGoalProgress]) -> Result<ScriptRunReport> {
+        let items = goals.iter().map(goal_map).collect();
+        Ok(self.run_hook(ScriptEvent::OnGoalProgress, "goal", items)?.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notifications::NotificationChannel;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    struct StubRepository(Vec<Script>);
+
+    #[async_trait]
+    impl ScriptRepositoryTrait for StubRepository {
+        fn get_scripts(&self) -> Result<Vec<Script>> {
+            Ok(self.0.clone())
+        }
+
+        fn get_script(&self, _id: &str) -> Result<Script> {
+            unimplemented!()
+        }
+
+        fn get_enabled_scripts(&self, event: ScriptEvent) -> Result<Vec<Script>> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|s| s.event == event && s.is_enabled)
+                .cloned()
+                .collect())
+        }
+
+        async fn insert_script(&self, _script: NewScript) -> Result<Script> {
+            unimplemented!()
+        }
+
+        async fn update_script(&self, _id: &str, _script: NewScript) -> Result<Script> {
+            unimplemented!()
+        }
+
+        async fn delete_script(&self, _id: &str) -> Result<usize> {
+            unimplemented!()
+        }
+    }
+
+    struct StubLookups;
+
+    impl ScriptLookups for StubLookups {
+        fn latest_price(&self, symbol: &str) -> Option<Decimal> {
+            (symbol == "FPT").then_some(dec!(118500))
+        }
+
+        fn fx_rate(&self, _from: &str, _to: &str) -> Option<Decimal> {
+            None
+        }
+    }
+
+    fn script(name: &str, event: ScriptEvent, source: &str) -> Script {
+        let now = Utc::now().naive_utc();
+        Script {
+            id: name.to_string(),
+            name: name.to_string(),
+            event,
+            source: source.to_string(),
+            is_enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn service(scripts: Vec<Script>) -> ScriptService {
+        ScriptService::with_lookups(Arc::new(StubRepository(scripts)), Arc::new(StubLookups))
+    }
+
+    fn row(symbol: &str, quantity: Decimal, unit_price: Decimal) -> ActivityImport {
+        ActivityImport {
+            id: None,
+            date: "2026-10-01".to_string(),
+            symbol: symbol.to_string(),
+            activity_type: "BUY".to_string(),
+            quantity,
+            unit_price,
+            currency: "VND".to_string(),
+            fee: Decimal::ZERO,
+            amount: None,
+            comment: None,
+            account_id: Some("acc".to_string()),
+            account_name: None,
+            symbol_name: None,
+            errors: None,
+            warnings: None,
+            is_draft: false,
+            is_valid: false,
+            line_number: Some(2),
+            asset_data_source: None,
+        }
+    }
+
+    #[test]
+    fn import_scripts_edit_rows_and_a_failing_script_does_not_stop_the_others() {
+        let service = service(vec![
+            script(
+                "broken",
+                ScriptEvent::OnImportRow,
+                r#"if row.symbol == "FPT" { throw "no FPT"; }"#,
+            ),
+            script("runaway", ScriptEvent::OnImportRow, "loop { }"),
+            script(
+                "dividends",
+                ScriptEvent::OnImportRow,
+                r#"
+                if row.symbol.starts_with("$CASH") {
+                    row.activityType = "DIVIDEND";
+                    row.comment = "Cổ tức";
+                    row.amount = 1500000;
+                }
+                let price = latest_price(row.symbol);
+                if price != () && row.unitPrice > price * 1.1 {
+                    notify("Price check", `${row.symbol} bought far above the market`);
+                }
+                "#,
+            ),
+        ]);
+        let mut rows = vec![
+            row("$CASH-VND", dec!(1), dec!(1)),
+            row("FPT", dec!(100.5), dec!(140000.25)),
+        ];
+
+        let report = service.run_import_row_hooks(&mut rows).unwrap();
+
+        assert_eq!(rows[0].activity_type, "DIVIDEND");
+        assert_eq!(rows[0].comment.as_deref(), Some("Cổ tức"));
+        assert_eq!(rows[0].amount, Some(dec!(1500000)));
+        // Untouched numbers keep their exact value
+        assert_eq!(rows[1].quantity, dec!(100.5));
+        assert_eq!(rows[1].unit_price, dec!(140000.25));
+        assert_eq!(rows[1].activity_type, "BUY");
+
+        let failed: Vec<&str> = report
+            .failures
+            .iter()
+            .map(|f| f.script_name.as_str())
+            .collect();
+        assert_eq!(failed, vec!["broken", "runaway"]);
+        assert_eq!(report.notifications.len(), 1);
+        assert_eq!(report.notifications[0].channel, NotificationChannel::System);
+        assert_eq!(
+            report.notifications[0].body,
+            "FPT bought far above the market"
+        );
+    }
+
+    #[test]
+    fn goal_scripts_read_progress() {
+        let service = service(vec![script(
+            "halfway",
+            ScriptEvent::OnGoalProgress,
+            r#"if goal.progress != () && goal.progress >= 0.5 { notify(goal.title, "Halfway there"); }"#,
+        )]);
+        let goals = vec![ScriptGoalProgress {
+            goal_id: "g".to_string(),
+            goal_title: "Quỹ khẩn cấp".to_string(),
+            goal_type: "EMERGENCY_FUND".to_string(),
+            target_amount: dec!(120_000_000),
+            current_amount: dec!(70_000_000),
+            progress: Some(dec!(0.5833)),
+        }];
+
+        let report = service.run_goal_progress_hooks(&goals).unwrap();
+        assert!(report.failures.is_empty(), "{:?}", report.failures);
+        assert_eq!(report.notifications.len(), 1);
+        assert_eq!(report.notifications[0].title, "Quỹ khẩn cấp");
+        assert_eq!(
+            report.notifications[0].channel,
+            NotificationChannel::GoalProgress
+        );
+    }
+}
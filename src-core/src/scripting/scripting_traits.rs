@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+use super::scripting_model::{NewScript, Script, ScriptEvent, ScriptGoalProgress, ScriptRunReport};
+use crate::activities::ActivityImport;
+use crate::errors::Result;
+use crate::market_data::Quote;
+
+#[async_trait]
+pub trait ScriptRepositoryTrait: Send + Sync {
+    fn get_scripts(&self) -> Result<Vec<Script>>;
+    fn get_script(&self, id: &str) -> Result<Script>;
+    fn get_enabled_scripts(&self, event: ScriptEvent) -> Result<Vec<Script>>;
+    async fn insert_script(&self, script: NewScript) -> Result<Script>;
+    async fn update_script(&self, id: &str, script: NewScript) -> Result<Script>;
+    async fn delete_script(&self, id: &str) -> Result<usize>;
+}
+
+/// Read-only lookups scripts can call; nothing else in core is reachable from a script
+pub trait ScriptLookups: Send + Sync {
+    /// Close of the latest quote, for `latest_price(symbol)`
+    fn latest_price(&self, symbol: &str) -> Option<Decimal>;
+    /// Latest exchange rate, for `fx_rate(from, to)`
+    fn fx_rate(&self, from: &str, to: &str) -> Option<Decimal>;
+}
+
+#[async_trait]
+pub trait ScriptServiceTrait: Send + Sync {
+    fn get_scripts(&self) -> Result<Vec<Script>>;
+    fn get_script(&self, id: &str) -> Result<Script>;
+    /// Scripts are compiled before they are saved, so syntax errors surface here
+    async fn create_script(&self, script: NewScript) -> Result<Script>;
+    async fn update_script(&self, id: &str, script: NewScript) -> Result<Script>;
+    async fn delete_script(&self, id: &str) -> Result<usize>;
+    /// Runs `on_import_row` scripts over each row, keeping the edits they make
+    fn run_import_row_hooks(&self, rows: &mut [ActivityImport]) -> Result<ScriptRunReport>;
+    fn run_quote_update_hooks(&self, quotes: &[Quote]) -> Result<ScriptRunReport>;
+    fn run_goal_progress_hooks(&self, goals: &[ScriptGoalProgress]) -> Result<ScriptRunReport>;
+}
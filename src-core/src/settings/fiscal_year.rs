@@ -0,0 +1,153 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::settings_model::FiscalYearCalendar;
+
+/// First year covered by `LUNAR_NEW_YEAR`
+const LUNAR_TABLE_FIRST_YEAR: i32 = 2000;
+
+/// Tết Nguyên Đán (month, day) in the Vietnamese calendar, starting from 2000
+const LUNAR_NEW_YEAR: [(u32, u32); 41] = [
+    (2, 5),
+    (1, 24),
+    (2, 12),
+    (2, 1),
+    (1, 22),
+    (2, 9),
+    (1, 29),
+    (2, 17),
+    (2, 7),
+    (1, 26),
+    (2, 14),
+    (2, 3),
+    (1, 23),
+    (2, 10),
+    (1, 31),
+    (2, 19),
+    (2, 8),
+    (1, 28),
+    (2, 16),
+    (2, 5),
+    (1, 25),
+    (2, 12),
+    (2, 1),
+    (1, 22),
+    (2, 10),
+    (1, 29),
+    (2, 17),
+    (2, 6),
+    (1, 26),
+    (2, 13),
+    (2, 3),
+    (1, 23),
+    (2, 11),
+    (1, 31),
+    (2, 19),
+    (2, 8),
+    (1, 28),
+    (2, 15),
+    (2, 4),
+    (1, 24),
+    (2, 12),
+];
+
+/// First day of the lunar year beginning in `year`, if it is covered by the table
+pub fn lunar_new_year(year: i32) -> Option<NaiveDate> {
+    let index = usize::try_from(year - LUNAR_TABLE_FIRST_YEAR).ok()?;
+    let (month, day) = LUNAR_NEW_YEAR.get(index)?;
+    NaiveDate::from_ymd_opt(year, *month, *day)
+}
+
+/// Reporting year boundaries used by YTD, annual limit and year-review figures.
+/// A fiscal year is labelled by the calendar year it starts in, so with an April
+/// start, fiscal 2025 runs from 2025-04-01 to 2026-03-31.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FiscalYear {
+    pub start_month: u32,
+    pub calendar: FiscalYearCalendar,
+}
+
+impl Default for FiscalYear {
+    fn default() -> Self {
+        Self {
+            start_month: 1,
+            calendar: FiscalYearCalendar::Gregorian,
+        }
+    }
+}
+
+impl FiscalYear {
+    pub fn new(start_month: u32, calendar: FiscalYearCalendar) -> Self {
+        Self {
+            start_month: start_month.clamp(1, 12),
+            calendar,
+        }
+    }
+
+    /// First day of the given fiscal year. Lunar years start at Tết; years outside
+    /// the lunar table fall back to the configured start month.
+    pub fn start_of(&self, year: i32) -> NaiveDate {
+        let lunar_start = match self.calendar {
+            FiscalYearCalendar::Lunar => lunar_new_year(year),
+            FiscalYearCalendar::Gregorian => None,
+        };
+        lunar_start.unwrap_or_else(|| {
+            NaiveDate::from_ymd_opt(year, self.start_month, 1).unwrap_or(NaiveDate::MIN)
+        })
+    }
+
+    /// Last day of the given fiscal year, inclusive
+    pub fn end_of(&self, year: i32) -> NaiveDate {
+        self.start_of(year + 1).pred_opt().unwrap_or(NaiveDate::MAX)
+    }
+
+    /// Fiscal year that contains `date`
+    pub fn year_of(&self, date: NaiveDate) -> i32 {
+        if date >= self.start_of(date.year()) {
+            date.year()
+        } else {
+            date.year() - 1
+        }
+    }
+
+    /// Calendar months touched between the start of `date`'s fiscal year and `date`, inclusive
+    pub fn months_elapsed(&self, date: NaiveDate) -> u32 {
+        let start = self.start_of(self.year_of(date));
+        let months = (date.year() - start.year()) * 12 + date.month() as i32 - start.month() as i32;
+        (months + 1).max(1) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn gregorian_fiscal_year_with_april_start() {
+        let fiscal = FiscalYear::new(4, FiscalYearCalendar::Gregorian);
+
+        assert_eq!(fiscal.year_of(date(2025, 3, 31)), 2024);
+        assert_eq!(fiscal.year_of(date(2025, 4, 1)), 2025);
+        assert_eq!(fiscal.start_of(2025), date(2025, 4, 1));
+        assert_eq!(fiscal.end_of(2025), date(2026, 3, 31));
+        assert_eq!(fiscal.months_elapsed(date(2026, 2, 10)), 11);
+        assert_eq!(FiscalYear::default().months_elapsed(date(2025, 6, 30)), 6);
+    }
+
+    #[test]
+    fn lunar_fiscal_year_starts_at_tet() {
+        let fiscal = FiscalYear::new(1, FiscalYearCalendar::Lunar);
+
+        assert_eq!(fiscal.start_of(2025), date(2025, 1, 29));
+        assert_eq!(fiscal.end_of(2025), date(2026, 2, 16));
+        assert_eq!(fiscal.year_of(date(2026, 2, 16)), 2025);
+        assert_eq!(fiscal.year_of(date(2026, 2, 17)), 2026);
+        // Outside the table the configured start month is used
+        assert_eq!(fiscal.start_of(1999), date(1999, 1, 1));
+    }
+}
@@ -1,6 +1,14 @@
+pub mod fiscal_year;
+pub mod settings_export_model;
+pub mod settings_export_repository;
+pub mod settings_export_service;
 pub mod settings_model;
 pub mod settings_repository;
 pub mod settings_service;
+pub use fiscal_year::FiscalYear;
+pub use settings_export_model::*;
+pub use settings_export_repository::{SettingsExportRepository, SettingsExportRepositoryTrait};
+pub use settings_export_service::{SettingsExportService, SettingsExportServiceTrait};
 pub use settings_model::*;
 pub use settings_repository::SettingsRepositoryTrait;
 pub use settings_service::{SettingsService, SettingsServiceTrait};
@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::activities::ImportMappingData;
+use crate::app_lock::PASSPHRASE_HASH_KEY;
+
+/// Bumped whenever the export layout changes in a way older builds can't read
+pub const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+/// `app_settings` keys that never leave the device: secrets and per-installation state
+pub const NON_PORTABLE_SETTING_KEYS: &[&str] =
+    &[PASSPHRASE_HASH_KEY, "instance_id", "onboarding_completed"];
+
+/// Portable snapshot of the app configuration. API keys live in the OS keychain and are never included.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsExport {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    #[serde(default)]
+    pub settings: BTreeMap<String, String>,
+    #[serde(default)]
+    pub import_profiles: Vec<ExportedImportProfile>,
+    #[serde(default)]
+    pub market_data_providers: Vec<ExportedProviderSetting>,
+}
+
+/// CSV import mapping of one account. The account name lets the profile find its account
+/// on a machine where ids differ.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedImportProfile {
+    pub account_name: Option<String>,
+    #[serde(flatten)]
+    pub mapping: ImportMappingData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedProviderSetting {
+    pub id: String,
+    pub priority: i32,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsImportResult {
+    pub settings_applied: usize,
+    pub import_profiles_applied: usize,
+    /// Profiles whose account exists on neither id nor name on this machine
+    pub import_profiles_skipped: Vec<String>,
+    pub providers_applied: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_round_trips_through_json() {
+        let json = r#"{
+            "version": 1,
+            "exportedAt": "2026-10-17T00:00:00Z",
+            "settings": { "theme": "light", "locale": "vi-VN" },
+            "importProfiles": [{
+                "accountName": "VPS Stocks",
+                "accountId": "acc-1",
+                "fieldMappings": { "date": "Ngày" },
+                "activityMappings": { "BUY": ["Mua"] },
+                "symbolMappings": {},
+                "accountMappings": {}
+            }]
+        }"#;
+
+        let export: SettingsExport = serde_json::from_str(json).unwrap();
+        assert_eq!(export.settings.len(), 2);
+        assert!(export.market_data_providers.is_empty());
+        let profile = &export.import_profiles[0];
+        assert_eq!(profile.account_name.as_deref(), Some("VPS Stocks"));
+        assert_eq!(profile.mapping.account_id, "acc-1");
+        assert_eq!(profile.mapping.field_mappings["date"], "Ngày");
+
+        let reparsed: SettingsExport =
+            serde_json::from_str(&serde_json::to_string(&export).unwrap()).unwrap();
+        assert_eq!(
+            reparsed.import_profiles[0].mapping.activity_mappings["BUY"],
+            vec!["Mua"]
+        );
+    }
+}
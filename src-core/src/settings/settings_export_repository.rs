@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use super::settings_export_model::{ExportedImportProfile, SettingsImportResult};
+use super::settings_model::AppSetting;
+use crate::activities::ImportMapping;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::{Error, Result, ValidationError};
+use crate::schema::{accounts, activity_import_profiles, app_settings};
+
+#[async_trait]
+pub trait SettingsExportRepositoryTrait: Send + Sync {
+    fn get_all_settings(&self) -> Result<BTreeMap<String, String>>;
+    fn get_import_profiles(&self) -> Result<Vec<ExportedImportProfile>>;
+    /// Writes settings and import profiles in one transaction so a bad file leaves them untouched
+    async fn apply_import(
+        &self,
+        settings: BTreeMap<String, String>,
+        import_profiles: Vec<ExportedImportProfile>,
+    ) -> Result<SettingsImportResult>;
+}
+
+pub struct SettingsExportRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl SettingsExportRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        SettingsExportRepository { pool, writer }
+    }
+}
+
+/// Finds the local account a profile belongs to, first by id, then by (case-insensitive) name
+fn resolve_account_id(
+    conn: &mut SqliteConnection,
+    profile: &ExportedImportProfile,
+) -> Result<Option<String>> {
+    let by_id = accounts::table
+        .filter(accounts::id.eq(&profile.mapping.account_id))
+        .select(accounts::id)
+        .first::<String>(conn)
+        .optional()?;
+    if by_id.is_some() {
+        return Ok(by_id);
+    }
+
+    let Some(name) = profile.account_name.as_deref() else {
+        return Ok(None);
+    };
+    let matches: Vec<(String, String)> = accounts::table
+        .select((accounts::id, accounts::name))
+        .load(conn)?;
+    let mut candidates = matches
+        .into_iter()
+        .filter(|(_, account_name)| account_name.eq_ignore_ascii_case(name.trim()));
+    match (candidates.next(), candidates.next()) {
+        (Some((id, _)), None) => Ok(Some(id)),
+        _ => Ok(None),
+    }
+}
+
+#[async_trait]
+impl SettingsExportRepositoryTrait for SettingsExportRepository {
+    fn get_all_settings(&self) -> Result<BTreeMap<String, String>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows = app_settings::table.load::<AppSetting>(&mut conn)?;
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.setting_key, row.setting_value))
+            .collect())
+    }
+
+    fn get_import_profiles(&self) -> Result<Vec<ExportedImportProfile>> {
+        let mut conn = get_connection(&self.pool)?;
+        let rows: Vec<(ImportMapping, Option<String>)> = activity_import_profiles::table
+            .left_join(accounts::table.on(accounts::id.eq(activity_import_profiles::account_id)))
+            .select((
+                activity_import_profiles::all_columns,
+                accounts::name.nullable(),
+            ))
+            .load(&mut conn)?;
+
+        rows.into_iter()
+            .map(|(mapping, account_name)| {
+                let mapping = mapping.to_mapping_data().map_err(|e| {
+                    Error::Validation(ValidationError::InvalidInput(format!(
+                        "Import profile for account {} is corrupt: {}",
+                        mapping.account_id, e
+                    )))
+                })?;
+                Ok(ExportedImportProfile {
+                    account_name,
+                    mapping,
+                })
+            })
+            .collect()
+    }
+
+    async fn apply_import(
+        &self,
+        settings: BTreeMap<String, String>,
+        import_profiles: Vec<ExportedImportProfile>,
+    ) -> Result<SettingsImportResult> {
+        self.writer
+            .exec(
+                move |conn: &mut SqliteConnection| -> Result<SettingsImportResult> {
+                    let mut result = SettingsImportResult::default();
+
+                    for (key, value) in settings {
+                        diesel::replace_into(app_settings::table)
+                            .values(&AppSetting {
+                                setting_key: key,
+                                setting_value: value,
+                            })
+                            .execute(conn)?;
+                        result.settings_applied += 1;
+                    }
+
+                    for mut profile in import_profiles {
+                        let Some(account_id) = resolve_account_id(conn, &profile)? else {
+                            result.import_profiles_skipped.push(
+                                profile
+                                    .account_name
+                                    .clone()
+                                    .unwrap_or_else(|| profile.mapping.account_id.clone()),
+                            );
+                            continue;
+                        };
+                        profile.mapping.account_id = account_id;
+                        let mapping =
+                            ImportMapping::from_mapping_data(&profile.mapping).map_err(|e| {
+                                Error::Validation(ValidationError::InvalidInput(e.to_string()))
+                            })?;
+                        diesel::insert_into(activity_import_profiles::table)
+                            .values(&mapping)
+                            .on_conflict(activity_import_profiles::account_id)
+                            .do_update()
+                            .set(&mapping)
+                            .execute(conn)?;
+                        result.import_profiles_applied += 1;
+                    }
+
+                    Ok(result)
+                },
+            )
+            .await
+    }
+}
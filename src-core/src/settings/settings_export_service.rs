@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use super::settings_export_model::{
+    ExportedProviderSetting, SettingsExport, SettingsImportResult, NON_PORTABLE_SETTING_KEYS,
+    SETTINGS_EXPORT_VERSION,
+};
+use super::settings_export_repository::SettingsExportRepositoryTrait;
+use super::settings_model::SettingKey;
+use super::settings_service::SettingsServiceTrait;
+use crate::errors::{Error, Result, ValidationError};
+use crate::market_data::MarketDataServiceTrait;
+
+#[async_trait]
+pub trait SettingsExportServiceTrait: Send + Sync {
+    async fn export_settings(&self) -> Result<SettingsExport>;
+    async fn import_settings(&self, export: SettingsExport) -> Result<SettingsImportResult>;
+}
+
+pub struct SettingsExportService {
+    repository: Arc<dyn SettingsExportRepositoryTrait>,
+    settings_service: Arc<dyn SettingsServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+}
+
+impl SettingsExportService {
+    pub fn new(
+        repository: Arc<dyn SettingsExportRepositoryTrait>,
+        settings_service: Arc<dyn SettingsServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            settings_service,
+            market_data_service,
+        }
+    }
+}
+
+fn is_portable(key: &str) -> bool {
+    !NON_PORTABLE_SETTING_KEYS.contains(&key)
+}
+
+#[async_trait]
+impl SettingsExportServiceTrait for SettingsExportService {
+    async fn export_settings(&self) -> Result<SettingsExport> {
+        let mut settings = self.repository.get_all_settings()?;
+        settings.retain(|key, _| is_portable(key));
+
+        Ok(SettingsExport {
+            version: SETTINGS_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            settings,
+            import_profiles: self.repository.get_import_profiles()?,
+            market_data_providers: self
+                .market_data_service
+                .get_market_data_providers_settings()
+                .await?
+                .into_iter()
+                .map(|provider| ExportedProviderSetting {
+                    id: provider.id,
+                    priority: provider.priority,
+                    enabled: provider.enabled,
+                })
+                .collect(),
+        })
+    }
+
+    async fn import_settings(&self, export: SettingsExport) -> Result<SettingsImportResult> {
+        if export.version > SETTINGS_EXPORT_VERSION {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Settings file version {} is newer than supported version {}",
+                export.version, SETTINGS_EXPORT_VERSION
+            ))));
+        }
+
+        let mut settings = export.settings;
+        settings.retain(|key, _| is_portable(key));
+        for (key, value) in &settings {
+            if let Ok(setting_key) = key.parse::<SettingKey>() {
+                setting_key
+                    .validate(value)
+                    .map_err(|e| Error::Validation(ValidationError::InvalidInput(e)))?;
+            }
+        }
+
+        // Changing the base currency also registers FX pairs, so it goes through the settings service
+        let base_currency = settings
+            .remove(SettingKey::BaseCurrency.as_str())
+            .filter(|new| {
+                self.settings_service
+                    .get_base_currency()
+                    .ok()
+                    .flatten()
+                    .as_ref()
+                    != Some(new)
+            });
+
+        let mut result = self
+            .repository
+            .apply_import(settings, export.import_profiles)
+            .await?;
+
+        // Provider changes go through the market data service so its registry is rebuilt;
+        // providers this build doesn't know about are ignored
+        let known_providers: HashSet<String> = self
+            .market_data_service
+            .get_market_data_providers_settings()
+            .await?
+            .into_iter()
+            .map(|provider| provider.id)
+            .collect();
+        for provider in export.market_data_providers {
+            if !known_providers.contains(&provider.id) {
+                continue;
+            }
+            self.market_data_service
+                .update_market_data_provider_settings(
+                    provider.id,
+                    provider.priority,
+                    provider.enabled,
+                )
+                .await?;
+            result.providers_applied += 1;
+        }
+
+        if let Some(new_base_currency) = base_currency {
+            self.settings_service
+                .update_base_currency(&new_base_currency)
+                .await?;
+            result.settings_applied += 1;
+        }
+
+        Ok(result)
+    }
+}
@@ -16,6 +16,11 @@ pub struct Settings {
     pub is_pro: bool,
     pub sync_enabled: bool,
     pub language: String,
+    pub locale: String,
+    pub fiscal_year_start_month: u32,
+    pub fiscal_year_calendar: FiscalYearCalendar,
+    pub default_cost_basis_method: CostBasisMethod,
+    pub privacy_mode: bool,
 }
 
 impl Default for Settings {
@@ -32,6 +37,11 @@ impl Default for Settings {
             is_pro: false,
             sync_enabled: true,
             language: "en".to_string(),
+            locale: "vi-VN".to_string(),
+            fiscal_year_start_month: 1,
+            fiscal_year_calendar: FiscalYearCalendar::Gregorian,
+            default_cost_basis_method: CostBasisMethod::Fifo,
+            privacy_mode: false,
         }
     }
 }
@@ -49,6 +59,171 @@ pub struct SettingsUpdate {
     pub is_pro: Option<bool>,
     pub sync_enabled: Option<bool>,
     pub language: Option<String>,
+    pub locale: Option<String>,
+    pub fiscal_year_start_month: Option<u32>,
+    pub fiscal_year_calendar: Option<FiscalYearCalendar>,
+    pub default_cost_basis_method: Option<CostBasisMethod>,
+    pub privacy_mode: Option<bool>,
+}
+
+/// Method used to match sells against lots when computing realized gains
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CostBasisMethod {
+    Fifo,
+    AverageCost,
+}
+
+impl CostBasisMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CostBasisMethod::Fifo => "FIFO",
+            CostBasisMethod::AverageCost => "AVERAGE_COST",
+        }
+    }
+}
+
+impl std::str::FromStr for CostBasisMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "FIFO" => Ok(CostBasisMethod::Fifo),
+            "AVERAGE_COST" => Ok(CostBasisMethod::AverageCost),
+            _ => Err(format!("Unknown cost basis method: {}", s)),
+        }
+    }
+}
+
+/// Calendar that fiscal year boundaries follow
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FiscalYearCalendar {
+    /// Years start on the first day of the configured start month
+    Gregorian,
+    /// Years start at Tết (Lunar New Year)
+    Lunar,
+}
+
+impl FiscalYearCalendar {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FiscalYearCalendar::Gregorian => "GREGORIAN",
+            FiscalYearCalendar::Lunar => "LUNAR",
+        }
+    }
+}
+
+impl std::str::FromStr for FiscalYearCalendar {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GREGORIAN" => Ok(FiscalYearCalendar::Gregorian),
+            "LUNAR" => Ok(FiscalYearCalendar::Lunar),
+            _ => Err(format!("Unknown fiscal year calendar: {}", s)),
+        }
+    }
+}
+
+/// Settings that core calculations depend on, addressable individually by key
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingKey {
+    BaseCurrency,
+    Locale,
+    FiscalYearStartMonth,
+    FiscalYearCalendar,
+    DefaultCostBasisMethod,
+    PrivacyMode,
+}
+
+impl SettingKey {
+    pub const ALL: [SettingKey; 6] = [
+        SettingKey::BaseCurrency,
+        SettingKey::Locale,
+        SettingKey::FiscalYearStartMonth,
+        SettingKey::FiscalYearCalendar,
+        SettingKey::DefaultCostBasisMethod,
+        SettingKey::PrivacyMode,
+    ];
+
+    /// Key under which the value is stored in `app_settings`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingKey::BaseCurrency => "base_currency",
+            SettingKey::Locale => "locale",
+            SettingKey::FiscalYearStartMonth => "fiscal_year_start_month",
+            SettingKey::FiscalYearCalendar => "fiscal_year_calendar",
+            SettingKey::DefaultCostBasisMethod => "default_cost_basis_method",
+            SettingKey::PrivacyMode => "privacy_mode",
+        }
+    }
+
+    pub fn default_value(&self) -> &'static str {
+        match self {
+            SettingKey::BaseCurrency => "",
+            SettingKey::Locale => "vi-VN",
+            SettingKey::FiscalYearStartMonth => "1",
+            SettingKey::FiscalYearCalendar => "GREGORIAN",
+            SettingKey::DefaultCostBasisMethod => "FIFO",
+            SettingKey::PrivacyMode => "false",
+        }
+    }
+
+    /// Checks that `value` can be parsed into the type this key holds
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            SettingKey::BaseCurrency => {
+                if value.len() == 3 && value.chars().all(|c| c.is_ascii_uppercase()) {
+                    Ok(())
+                } else {
+                    Err(format!("Invalid currency code: {}", value))
+                }
+            }
+            SettingKey::Locale => {
+                if value.is_empty() {
+                    Err("Locale cannot be empty".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            SettingKey::FiscalYearStartMonth => match value.parse::<u32>() {
+                Ok(month) if (1..=12).contains(&month) => Ok(()),
+                _ => Err(format!("Fiscal year start month must be 1-12, got {}", value)),
+            },
+            SettingKey::FiscalYearCalendar => {
+                value.parse::<FiscalYearCalendar>().map(|_| ())
+            }
+            SettingKey::DefaultCostBasisMethod => {
+                value.parse::<CostBasisMethod>().map(|_| ())
+            }
+            SettingKey::PrivacyMode => value
+                .parse::<bool>()
+                .map(|_| ())
+                .map_err(|_| format!("Privacy mode must be true or false, got {}", value)),
+        }
+    }
+}
+
+impl std::str::FromStr for SettingKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SettingKey::ALL
+            .into_iter()
+            .find(|key| key.as_str() == s)
+            .ok_or_else(|| format!("Unknown setting key: {}", s))
+    }
+}
+
+/// Emitted whenever a typed setting changes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingChange {
+    pub key: SettingKey,
+    pub old_value: Option<String>,
+    pub new_value: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -65,3 +240,31 @@ pub struct AppSetting {
     pub setting_key: String,
     pub setting_value: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setting_key_round_trip() {
+        for key in SettingKey::ALL {
+            assert_eq!(key.as_str().parse::<SettingKey>().unwrap(), key);
+            assert!(key != SettingKey::BaseCurrency || key.default_value().is_empty());
+        }
+        assert!("theme".parse::<SettingKey>().is_err());
+    }
+
+    #[test]
+    fn test_setting_key_validation() {
+        assert!(SettingKey::BaseCurrency.validate("VND").is_ok());
+        assert!(SettingKey::BaseCurrency.validate("vnd").is_err());
+        assert!(SettingKey::FiscalYearStartMonth.validate("4").is_ok());
+        assert!(SettingKey::FiscalYearStartMonth.validate("13").is_err());
+        assert!(SettingKey::FiscalYearCalendar.validate("LUNAR").is_ok());
+        assert!(SettingKey::FiscalYearCalendar.validate("lunar").is_err());
+        assert!(SettingKey::DefaultCostBasisMethod.validate("AVERAGE_COST").is_ok());
+        assert!(SettingKey::DefaultCostBasisMethod.validate("LIFO").is_err());
+        assert!(SettingKey::PrivacyMode.validate("true").is_ok());
+        assert!(SettingKey::PrivacyMode.validate("yes").is_err());
+    }
+}
@@ -2,7 +2,7 @@ use crate::db::{get_connection, DbPool, WriteHandle};
 use crate::errors::{Error, Result};
 use crate::schema::app_settings::dsl::*;
 use crate::schema::{accounts, assets};
-use crate::settings::{AppSetting, Settings, SettingsUpdate};
+use crate::settings::{AppSetting, CostBasisMethod, FiscalYearCalendar, Settings, SettingsUpdate};
 use async_trait::async_trait;
 use diesel::prelude::*;
 use std::sync::Arc;
@@ -64,6 +64,21 @@ impl SettingsRepositoryTrait for SettingsRepository {
                     settings.sync_enabled = value.parse().unwrap_or(true);
                 }
                 "language" => settings.language = value,
+                "locale" => settings.locale = value,
+                "fiscal_year_start_month" => {
+                    settings.fiscal_year_start_month = value.parse().unwrap_or(1);
+                }
+                "fiscal_year_calendar" => {
+                    settings.fiscal_year_calendar =
+                        value.parse().unwrap_or(FiscalYearCalendar::Gregorian);
+                }
+                "default_cost_basis_method" => {
+                    settings.default_cost_basis_method =
+                        value.parse().unwrap_or(CostBasisMethod::Fifo);
+                }
+                "privacy_mode" => {
+                    settings.privacy_mode = value.parse().unwrap_or(false);
+                }
                 _ => {} // Ignore unknown settings
             }
         }
@@ -166,6 +181,51 @@ impl SettingsRepositoryTrait for SettingsRepository {
                         .execute(conn)?;
                 }
 
+                if let Some(ref locale) = settings.locale {
+                    diesel::replace_into(app_settings)
+                        .values(&AppSetting {
+                            setting_key: "locale".to_string(),
+                            setting_value: locale.clone(),
+                        })
+                        .execute(conn)?;
+                }
+
+                if let Some(fiscal_year_start_month) = settings.fiscal_year_start_month {
+                    diesel::replace_into(app_settings)
+                        .values(&AppSetting {
+                            setting_key: "fiscal_year_start_month".to_string(),
+                            setting_value: fiscal_year_start_month.to_string(),
+                        })
+                        .execute(conn)?;
+                }
+
+                if let Some(fiscal_year_calendar) = settings.fiscal_year_calendar {
+                    diesel::replace_into(app_settings)
+                        .values(&AppSetting {
+                            setting_key: "fiscal_year_calendar".to_string(),
+                            setting_value: fiscal_year_calendar.as_str().to_string(),
+                        })
+                        .execute(conn)?;
+                }
+
+                if let Some(default_cost_basis_method) = settings.default_cost_basis_method {
+                    diesel::replace_into(app_settings)
+                        .values(&AppSetting {
+                            setting_key: "default_cost_basis_method".to_string(),
+                            setting_value: default_cost_basis_method.as_str().to_string(),
+                        })
+                        .execute(conn)?;
+                }
+
+                if let Some(privacy_mode) = settings.privacy_mode {
+                    diesel::replace_into(app_settings)
+                        .values(&AppSetting {
+                            setting_key: "privacy_mode".to_string(),
+                            setting_value: privacy_mode.to_string(),
+                        })
+                        .execute(conn)?;
+                }
+
                 Ok(())
             })
             .await
@@ -190,6 +250,11 @@ impl SettingsRepositoryTrait for SettingsRepository {
                     "menu_bar_visible" => "true",
                     "sync_enabled" => "true",
                     "language" => "en",
+                    "locale" => "vi-VN",
+                    "fiscal_year_start_month" => "1",
+                    "fiscal_year_calendar" => "GREGORIAN",
+                    "default_cost_basis_method" => "FIFO",
+                    "privacy_mode" => "false",
                     _ => return Err(Error::from(diesel::result::Error::NotFound)),
                 };
                 Ok(default_value.to_string())
@@ -1,7 +1,10 @@
 use super::settings_repository::SettingsRepositoryTrait;
-use crate::errors::{DatabaseError, Error, Result};
+use crate::errors::{DatabaseError, Error, Result, ValidationError};
 use crate::fx::fx_traits::FxServiceTrait;
-use crate::settings::{Settings, SettingsUpdate};
+use crate::settings::{
+    CostBasisMethod, FiscalYear, FiscalYearCalendar, SettingChange, SettingKey, Settings,
+    SettingsUpdate,
+};
 use async_trait::async_trait;
 use log::{debug, error};
 use std::sync::Arc;
@@ -20,6 +23,21 @@ pub trait SettingsServiceTrait: Send + Sync {
     fn is_auto_update_check_enabled(&self) -> Result<bool>;
 
     fn is_sync_enabled(&self) -> Result<bool>;
+
+    /// Reads a typed setting, falling back to its default when unset
+    fn get_setting_value(&self, key: SettingKey) -> Result<String>;
+
+    /// Validates and persists a typed setting, returning the change for event emission
+    async fn set_setting_value(&self, key: SettingKey, value: &str) -> Result<SettingChange>;
+
+    fn get_fiscal_year_start_month(&self) -> Result<u32>;
+
+    /// Fiscal year boundaries from the start month and calendar settings
+    fn get_fiscal_year(&self) -> Result<FiscalYear>;
+
+    fn get_default_cost_basis_method(&self) -> Result<CostBasisMethod>;
+
+    fn is_privacy_mode_enabled(&self) -> Result<bool>;
 }
 
 pub struct SettingsService {
@@ -112,6 +130,69 @@ impl SettingsServiceTrait for SettingsService {
             Err(e) => Err(e),
         }
     }
+
+    fn get_setting_value(&self, key: SettingKey) -> Result<String> {
+        match self.settings_repository.get_setting(key.as_str()) {
+            Ok(value) => Ok(value),
+            Err(Error::Database(DatabaseError::QueryFailed(diesel::result::Error::NotFound))) => {
+                Ok(key.default_value().to_string())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn set_setting_value(&self, key: SettingKey, value: &str) -> Result<SettingChange> {
+        key.validate(value)
+            .map_err(|msg| Error::Validation(ValidationError::InvalidInput(msg)))?;
+
+        let old_value = self.get_setting_value(key)?;
+
+        if key == SettingKey::BaseCurrency {
+            // Base currency changes must register the new currency pairs
+            self.update_base_currency(value).await?;
+        } else {
+            self.settings_repository
+                .update_setting(key.as_str(), value)
+                .await?;
+        }
+
+        debug!("Setting {} changed from {} to {}", key.as_str(), old_value, value);
+
+        Ok(SettingChange {
+            key,
+            old_value: Some(old_value).filter(|v| !v.is_empty()),
+            new_value: value.to_string(),
+        })
+    }
+
+    fn get_fiscal_year_start_month(&self) -> Result<u32> {
+        Ok(self
+            .get_setting_value(SettingKey::FiscalYearStartMonth)?
+            .parse()
+            .unwrap_or(1))
+    }
+
+    fn get_fiscal_year(&self) -> Result<FiscalYear> {
+        let calendar = self
+            .get_setting_value(SettingKey::FiscalYearCalendar)?
+            .parse()
+            .unwrap_or(FiscalYearCalendar::Gregorian);
+        Ok(FiscalYear::new(self.get_fiscal_year_start_month()?, calendar))
+    }
+
+    fn get_default_cost_basis_method(&self) -> Result<CostBasisMethod> {
+        Ok(self
+            .get_setting_value(SettingKey::DefaultCostBasisMethod)?
+            .parse()
+            .unwrap_or(CostBasisMethod::Fifo))
+    }
+
+    fn is_privacy_mode_enabled(&self) -> Result<bool> {
+        Ok(self
+            .get_setting_value(SettingKey::PrivacyMode)?
+            .parse()
+            .unwrap_or(false))
+    }
 }
 
 impl SettingsService {
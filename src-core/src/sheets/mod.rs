@@ -0,0 +1,16 @@
+mod sheets_google;
+mod sheets_model;
+mod sheets_repository;
+mod sheets_service;
+mod sheets_traits;
+
+pub use sheets_google::{GoogleSheetsClient, KeyringCredentialStore};
+pub use sheets_model::{
+    GoogleCredentials, NewSheetExport, SheetExport, SheetExportResult, SheetReport, SheetTable,
+    MAX_EXPORT_INTERVAL_HOURS,
+};
+pub use sheets_repository::SheetExportRepository;
+pub use sheets_service::SheetExportService;
+pub use sheets_traits::{
+    SheetCredentialStore, SheetExportRepositoryTrait, SheetExportServiceTrait, SheetsClient,
+};
@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use reqwest::{Client, Response, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use super::sheets_model::{GoogleCredentials, SheetTable, GOOGLE_SHEETS_SECRET_ID};
+use super::sheets_traits::{SheetCredentialStore, SheetsClient};
+use crate::errors::{Error, Result};
+use crate::secrets::SecretManager;
+
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const SHEETS_API_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Sheets API v4 client; trades the refresh token for an access token on every push
+pub struct GoogleSheetsClient {
+    client: Client,
+}
+
+impl Default for GoogleSheetsClient {
+    fn default() -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        GoogleSheetsClient { client }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+/// A1 range of a whole tab; quotes in the tab name are doubled
+pub(crate) fn tab_range(sheet_name: &str) -> String {
+    format!("'{}'", sheet_name.replace('\'', "''"))
+}
+
+/// Google's own message when the body has one, otherwise the status
+async fn api_error(response: Response, context: &str) -> Error {
+    let status = response.status();
+    let message = response
+        .json::<Value>()
+        .await
+        .ok()
+        .and_then(|body| body["error"]["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| status.to_string());
+    Error::SheetExport(format!("{}: {}", context, message))
+}
+
+impl GoogleSheetsClient {
+    async fn access_token(&self, credentials: &GoogleCredentials) -> Result<String> {
+        let response = self
+            .client
+            .post(TOKEN_URL)
+            .form(&[
+                ("client_id", credentials.client_id.as_str()),
+                ("client_secret", credentials.client_secret.as_str()),
+                ("refresh_token", credentials.refresh_token.as_str()),
+                ("grant_type", "refresh_token"),
+            ])
+            .send()
+            .await
+            .map_err(|e| Error::SheetExport(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(api_error(response, "Google sign-in failed").await);
+        }
+        response
+            .json::<TokenResponse>()
+            .await
+            .map(|token| token.access_token)
+            .map_err(|e| Error::SheetExport(format!("Unexpected token response: {}", e)))
+    }
+
+    async fn check(&self, response: Response, spreadsheet_id: &str) -> Result<()> {
+        match response.status() {
+            status if status.is_success() => Ok(()),
+            StatusCode::NOT_FOUND => Err(Error::SheetExport(format!(
+                "Spreadsheet {} not found",
+                spreadsheet_id
+            ))),
+            StatusCode::FORBIDDEN => {
+                Err(api_error(response, "The Google account cannot edit this spreadsheet").await)
+            }
+            _ => Err(api_error(response, "Writing the sheet failed").await),
+        }
+    }
+}
+
+#[async_trait]
+impl SheetsClient for GoogleSheetsClient {
+    async fn write_table(
+        &self,
+        credentials: &GoogleCredentials,
+        spreadsheet_id: &str,
+        sheet_name: &str,
+        table: &SheetTable,
+    ) -> Result<()> {
+        let token = self.access_token(credentials).await?;
+        let range = urlencoding::encode(&tab_range(sheet_name)).into_owned();
+
+        // Clear first so rows left over from a longer report do not linger
+        let response = self
+            .client
+            .post(format!(
+                "{}/{}/values/{}:clear",
+                SHEETS_API_URL, spreadsheet_id, range
+            ))
+            .bearer_auth(&token)
+            .json(&json!({}))
+            .send()
+            .await
+            .map_err(|e| Error::SheetExport(e.to_string()))?;
+        self.check(response, spreadsheet_id).await?;
+
+        let response = self
+            .client
+            .put(format!(
+                "{}/{}/values/{}",
+                SHEETS_API_URL, spreadsheet_id, range
+            ))
+            .query(&[("valueInputOption", "RAW")])
+            .bearer_auth(&token)
+            .json(&json!({ "majorDimension": "ROWS", "values": table.to_values() }))
+            .send()
+            .await
+            .map_err(|e| Error::SheetExport(e.to_string()))?;
+        self.check(response, spreadsheet_id).await
+    }
+}
+
+/// Keeps the Google credentials in the operating system keyring as one JSON entry
+#[derive(Default)]
+pub struct KeyringCredentialStore;
+
+impl SheetCredentialStore for KeyringCredentialStore {
+    fn get_credentials(&self) -> Result<Option<GoogleCredentials>> {
+        SecretManager::get_secret(GOOGLE_SHEETS_SECRET_ID)?
+            .map(|secret| {
+                serde_json::from_str(&secret).map_err(|e| {
+                    Error::Secret(format!("Saved Google credentials are unreadable: {}", e))
+                })
+            })
+            .transpose()
+    }
+
+    fn set_credentials(&self, credentials: &GoogleCredentials) -> Result<()> {
+        let secret = serde_json::to_string(credentials)
+            .map_err(|e| Error::Secret(format!("Failed to encode Google credentials: {}", e)))?;
+        SecretManager::set_secret(GOOGLE_SHEETS_SECRET_ID, &secret)
+    }
+
+    fn delete_credentials(&self) -> Result<()> {
+        SecretManager::delete_secret(GOOGLE_SHEETS_SECRET_ID)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_tab_names_for_a1_ranges() {
+        assert_eq!(tab_range("Holdings"), "'Holdings'");
+        assert_eq!(tab_range("Tài sản ròng"), "'Tài sản ròng'");
+        assert_eq!(tab_range("Bob's sheet"), "'Bob''s sheet'");
+    }
+}
@@ -0,0 +1,219 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Longest gap between scheduled pushes, in hours
+pub const MAX_EXPORT_INTERVAL_HOURS: i32 = 24 * 7;
+
+/// Keyring entry holding the Google OAuth client and refresh token shared by all exports
+pub(crate) const GOOGLE_SHEETS_SECRET_ID: &str = "google_sheets";
+
+/// Report a sheet export writes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SheetReport {
+    /// Holdings of all accounts combined
+    Holdings,
+    /// Daily net worth history
+    NetWorth,
+    /// Emergency fund, sinking fund and net worth goals
+    GoalProgress,
+}
+
+impl SheetReport {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SheetReport::Holdings => "HOLDINGS",
+            SheetReport::NetWorth => "NET_WORTH",
+            SheetReport::GoalProgress => "GOAL_PROGRESS",
+        }
+    }
+}
+
+impl FromStr for SheetReport {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "HOLDINGS" => Ok(SheetReport::Holdings),
+            "NET_WORTH" => Ok(SheetReport::NetWorth),
+            "GOAL_PROGRESS" => Ok(SheetReport::GoalProgress),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown sheet report: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `sheet_exports`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::sheet_exports)]
+pub struct SheetExportDB {
+    pub id: String,
+    pub name: String,
+    pub report: String,
+    pub spreadsheet_id: String,
+    pub sheet_name: String,
+    pub sync_interval_hours: i32,
+    pub is_enabled: bool,
+    pub last_attempt_at: Option<NaiveDateTime>,
+    pub last_synced_at: Option<NaiveDateTime>,
+    pub last_sync_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A report pushed to one tab of a Google Sheet on a schedule
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetExport {
+    pub id: String,
+    pub name: String,
+    pub report: SheetReport,
+    /// The id in the sheet's URL, between `/d/` and `/edit`
+    pub spreadsheet_id: String,
+    /// Tab the report overwrites
+    pub sheet_name: String,
+    pub sync_interval_hours: i32,
+    pub is_enabled: bool,
+    /// Last push, successful or not; the schedule counts from here
+    pub last_attempt_at: Option<NaiveDateTime>,
+    /// Last successful push
+    pub last_synced_at: Option<NaiveDateTime>,
+    /// Error of the last push, cleared once a push succeeds
+    pub last_sync_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TryFrom<SheetExportDB> for SheetExport {
+    type Error = Error;
+
+    fn try_from(db: SheetExportDB) -> Result<Self> {
+        Ok(SheetExport {
+            id: db.id,
+            name: db.name,
+            report: SheetReport::from_str(&db.report)?,
+            spreadsheet_id: db.spreadsheet_id,
+            sheet_name: db.sheet_name,
+            sync_interval_hours: db.sync_interval_hours,
+            is_enabled: db.is_enabled,
+            last_attempt_at: db.last_attempt_at,
+            last_synced_at: db.last_synced_at,
+            last_sync_error: db.last_sync_error,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewSheetExport {
+    pub id: Option<String>,
+    pub name: String,
+    pub report: SheetReport,
+    pub spreadsheet_id: String,
+    pub sheet_name: String,
+    pub sync_interval_hours: i32,
+    pub is_enabled: bool,
+}
+
+impl NewSheetExport {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("name", &self.name),
+            ("spreadsheetId", &self.spreadsheet_id),
+            ("sheetName", &self.sheet_name),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        // Spreadsheet ids are URL-safe base64; anything else is most likely a pasted URL
+        if !self
+            .spreadsheet_id
+            .trim()
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Spreadsheet id must be the id from the sheet's URL, not the URL itself"
+                    .to_string(),
+            )));
+        }
+        if !(1..=MAX_EXPORT_INTERVAL_HOURS).contains(&self.sync_interval_hours) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Export interval must be between 1 and {} hours",
+                MAX_EXPORT_INTERVAL_HOURS
+            ))));
+        }
+        Ok(())
+    }
+}
+
+/// OAuth client and refresh token of the Google account the sheets belong to
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoogleCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+}
+
+impl GoogleCredentials {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("clientId", &self.client_id),
+            ("clientSecret", &self.client_secret),
+            ("refreshToken", &self.refresh_token),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A report as rows of cells, header first
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SheetTable {
+    pub header: Vec<String>,
+    /// Numbers stay numbers so sheet formulas and charts can use them
+    pub rows: Vec<Vec<Value>>,
+}
+
+impl SheetTable {
+    /// Header and rows as one grid, the shape the Sheets API takes
+    pub fn to_values(&self) -> Vec<Vec<Value>> {
+        std::iter::once(
+            self.header
+                .iter()
+                .map(|h| Value::from(h.as_str()))
+                .collect(),
+        )
+        .chain(self.rows.iter().cloned())
+        .collect()
+    }
+}
+
+/// Outcome of one push
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SheetExportResult {
+    pub export_id: String,
+    pub export_name: String,
+    /// Data rows written, not counting the header
+    pub rows: usize,
+    pub error: Option<String>,
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::sheets_model::{NewSheetExport, SheetExport, SheetExportDB};
+use super::sheets_traits::SheetExportRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::schema::sheet_exports;
+
+pub struct SheetExportRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl SheetExportRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        SheetExportRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl SheetExportRepositoryTrait for SheetExportRepository {
+    fn get_exports(&self) -> Result<Vec<SheetExport>> {
+        let mut conn = get_connection(&self.pool)?;
+        sheet_exports::table
+            .order(sheet_exports::created_at.asc())
+            .load::<SheetExportDB>(&mut conn)?
+            .into_iter()
+            .map(SheetExport::try_from)
+            .collect()
+    }
+
+    fn get_export(&self, id: &str) -> Result<SheetExport> {
+        let mut conn = get_connection(&self.pool)?;
+        sheet_exports::table
+            .find(id)
+            .first::<SheetExportDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_export(&self, export: NewSheetExport) -> Result<SheetExport> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<SheetExport> {
+                let now = Utc::now().naive_utc();
+                let record = SheetExportDB {
+                    id: export.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    name: export.name.trim().to_string(),
+                    report: export.report.as_str().to_string(),
+                    spreadsheet_id: export.spreadsheet_id.trim().to_string(),
+                    sheet_name: export.sheet_name.trim().to_string(),
+                    sync_interval_hours: export.sync_interval_hours,
+                    is_enabled: export.is_enabled,
+                    last_attempt_at: None,
+                    last_synced_at: None,
+                    last_sync_error: None,
+                    created_at: now,
+                    updated_at: now,
+                };
+
+                diesel::insert_into(sheet_exports::table)
+                    .values(&record)
+                    .get_result::<SheetExportDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_export(&self, id: &str, export: NewSheetExport) -> Result<SheetExport> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<SheetExport> {
+                diesel::update(sheet_exports::table.find(id_owned))
+                    .set((
+                        sheet_exports::name.eq(export.name.trim()),
+                        sheet_exports::report.eq(export.report.as_str()),
+                        sheet_exports::spreadsheet_id.eq(export.spreadsheet_id.trim()),
+                        sheet_exports::sheet_name.eq(export.sheet_name.trim()),
+                        sheet_exports::sync_interval_hours.eq(export.sync_interval_hours),
+                        sheet_exports::is_enabled.eq(export.is_enabled),
+                        sheet_exports::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<SheetExportDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_export(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(sheet_exports::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+
+    async fn record_push(
+        &self,
+        id: &str,
+        attempted_at: NaiveDateTime,
+        error: Option<String>,
+    ) -> Result<()> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<()> {
+                let target = sheet_exports::table.find(&id_owned);
+                if error.is_none() {
+                    diesel::update(target)
+                        .set(sheet_exports::last_synced_at.eq(Some(attempted_at)))
+                        .execute(conn)?;
+                }
+                diesel::update(target)
+                    .set((
+                        sheet_exports::last_attempt_at.eq(Some(attempted_at)),
+                        sheet_exports::last_sync_error.eq(error),
+                    ))
+                    .execute(conn)?;
+                Ok(())
+            })
+            .await
+    }
+}
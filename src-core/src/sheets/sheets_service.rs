@@ -0,0 +1,443 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use log::debug;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+use super::sheets_google::{GoogleSheetsClient, KeyringCredentialStore};
+use super::sheets_model::{
+    GoogleCredentials, NewSheetExport, SheetExport, SheetExportResult, SheetReport, SheetTable,
+};
+use super::sheets_traits::{
+    SheetCredentialStore, SheetExportRepositoryTrait, SheetExportServiceTrait, SheetsClient,
+};
+use crate::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use crate::errors::{Error, Result};
+use crate::goals::{
+    EmergencyFundProgress, EmergencyFundServiceTrait, NetWorthGoalProgress,
+    NetWorthGoalServiceTrait, NetWorthPoint, SinkingFundProgress, SinkingFundServiceTrait,
+};
+use crate::portfolio::holdings::{Holding, HoldingType, HoldingsServiceTrait};
+
+pub struct SheetExportService {
+    repository: Arc<dyn SheetExportRepositoryTrait>,
+    holdings_service: Arc<dyn HoldingsServiceTrait>,
+    net_worth_goal_service: Arc<dyn NetWorthGoalServiceTrait>,
+    emergency_fund_service: Arc<dyn EmergencyFundServiceTrait>,
+    sinking_fund_service: Arc<dyn SinkingFundServiceTrait>,
+    base_currency: Arc<RwLock<String>>,
+    credential_store: Arc<dyn SheetCredentialStore>,
+    client: Arc<dyn SheetsClient>,
+}
+
+impl SheetExportService {
+    pub fn new(
+        repository: Arc<dyn SheetExportRepositoryTrait>,
+        holdings_service: Arc<dyn HoldingsServiceTrait>,
+        net_worth_goal_service: Arc<dyn NetWorthGoalServiceTrait>,
+        emergency_fund_service: Arc<dyn EmergencyFundServiceTrait>,
+        sinking_fund_service: Arc<dyn SinkingFundServiceTrait>,
+        base_currency: Arc<RwLock<String>>,
+    ) -> Self {
+        SheetExportService {
+            repository,
+            holdings_service,
+            net_worth_goal_service,
+            emergency_fund_service,
+            sinking_fund_service,
+            base_currency,
+            credential_store: Arc::new(KeyringCredentialStore),
+            client: Arc::new(GoogleSheetsClient::default()),
+        }
+    }
+
+    /// Uses the given credential store and client instead of the keyring and the Sheets API
+    pub fn with_client(
+        mut self,
+        credential_store: Arc<dyn SheetCredentialStore>,
+        client: Arc<dyn SheetsClient>,
+    ) -> Self {
+        self.credential_store = credential_store;
+        self.client = client;
+        self
+    }
+
+    async fn build_table(&self, report: SheetReport) -> Result<SheetTable> {
+        let base_currency = self.base_currency.read().unwrap().clone();
+        match report {
+            SheetReport::Holdings => {
+                let holdings = self
+                    .holdings_service
+                    .get_holdings(PORTFOLIO_TOTAL_ACCOUNT_ID, &base_currency)
+                    .await?;
+                Ok(holdings_table(holdings, &base_currency))
+            }
+            SheetReport::NetWorth => {
+                let series = self
+                    .net_worth_goal_service
+                    .get_net_worth_series(None, None)?;
+                Ok(net_worth_table(&series, &base_currency))
+            }
+            SheetReport::GoalProgress => Ok(goal_progress_table(
+                &self.emergency_fund_service.get_emergency_fund_progress().await?,
+                &self.sinking_fund_service.get_sinking_fund_progress().await?,
+                &self.net_worth_goal_service.get_net_worth_goal_progress().await?,
+                &base_currency,
+            )),
+        }
+    }
+
+    async fn push(&self, export: &SheetExport) -> Result<usize> {
+        let credentials = self.credential_store.get_credentials()?.ok_or_else(|| {
+            Error::SheetExport("Google account not connected; save credentials first".to_string())
+        })?;
+        let table = self.build_table(export.report).await?;
+        self.client
+            .write_table(
+                &credentials,
+                &export.spreadsheet_id,
+                &export.sheet_name,
+                &table,
+            )
+            .await?;
+        debug!(
+            "{}: wrote {} rows to {}",
+            export.name,
+            table.rows.len(),
+            export.sheet_name
+        );
+        Ok(table.rows.len())
+    }
+}
+
+/// Sheets get plain numbers; the currency is in the column header
+fn number(value: Decimal) -> Value {
+    value.to_f64().map(Value::from).unwrap_or(Value::Null)
+}
+
+fn optional_number(value: Option<Decimal>) -> Value {
+    value.map(number).unwrap_or(Value::Null)
+}
+
+fn text(value: impl Into<String>) -> Value {
+    Value::String(value.into())
+}
+
+pub(crate) fn holdings_table(mut holdings: Vec<Holding>, base_currency: &str) -> SheetTable {
+    holdings.sort_by_key(|holding| std::cmp::Reverse(holding.market_value.base));
+    SheetTable {
+        header: vec![
+            "Symbol".to_string(),
+            "Name".to_string(),
+            "Type".to_string(),
+            "Quantity".to_string(),
+            "Currency".to_string(),
+            "Price".to_string(),
+            "Market value".to_string(),
+            format!("Market value ({})", base_currency),
+            format!("Cost basis ({})", base_currency),
+            format!("Unrealized gain ({})", base_currency),
+            "Unrealized gain %".to_string(),
+        ],
+        rows: holdings
+            .into_iter()
+            .map(|holding| {
+                let (symbol, name) = match &holding.instrument {
+                    Some(instrument) => (instrument.symbol.clone(), instrument.name.clone()),
+                    None => (format!("$CASH-{}", holding.local_currency), None),
+                };
+                vec![
+                    text(symbol),
+                    name.map(text).unwrap_or(Value::Null),
+                    text(match holding.holding_type {
+                        HoldingType::Cash => "Cash",
+                        HoldingType::Security => "Security",
+                    }),
+                    number(holding.quantity),
+                    text(holding.local_currency),
+                    optional_number(holding.price),
+                    number(holding.market_value.local),
+                    number(holding.market_value.base),
+                    optional_number(holding.cost_basis.map(|value| value.base)),
+                    optional_number(holding.unrealized_gain.map(|value| value.base)),
+                    optional_number(holding.unrealized_gain_pct),
+                ]
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn net_worth_table(series: &[NetWorthPoint], base_currency: &str) -> SheetTable {
+    SheetTable {
+        header: vec![
+            "Date".to_string(),
+            format!("Assets ({})", base_currency),
+            format!("Liabilities ({})", base_currency),
+            format!("Net worth ({})", base_currency),
+        ],
+        rows: series
+            .iter()
+            .map(|point| {
+                vec![
+                    text(point.date.format("%Y-%m-%d").to_string()),
+                    number(point.total_assets),
+                    number(point.total_liabilities),
+                    number(point.net_worth),
+                ]
+            })
+            .collect(),
+    }
+}
+
+/// One row per tracked goal; progress is a fraction so the sheet can format it as a percentage
+pub(crate) fn goal_progress_table(
+    emergency_funds: &[EmergencyFundProgress],
+    sinking_funds: &[SinkingFundProgress],
+    net_worth_goals: &[NetWorthGoalProgress],
+    base_currency: &str,
+) -> SheetTable {
+    let row = |title: &str, kind: &str, target: Decimal, current: Decimal, progress| {
+        vec![
+            text(title),
+            text(kind),
+            number(target),
+            number(current),
+            optional_number(progress),
+        ]
+    };
+    SheetTable {
+        header: vec![
+            "Goal".to_string(),
+            "Type".to_string(),
+            format!("Target ({})", base_currency),
+            format!("Current ({})", base_currency),
+            "Progress".to_string(),
+        ],
+        rows: emergency_funds
+            .iter()
+            .map(|fund| {
+                row(
+                    &fund.goal_title,
+                    "Emergency fund",
+                    fund.target_amount,
+                    fund.current_value,
+                    fund.progress,
+                )
+            })
+            .chain(sinking_funds.iter().map(|fund| {
+                row(
+                    &fund.goal_title,
+                    "Sinking fund",
+                    fund.target_amount,
+                    fund.current_value,
+                    fund.progress,
+                )
+            }))
+            .chain(net_worth_goals.iter().map(|goal| {
+                row(
+                    &goal.goal_title,
+                    "Net worth",
+                    goal.target_amount,
+                    goal.current.net_worth,
+                    goal.progress,
+                )
+            }))
+            .collect(),
+    }
+}
+
+fn is_due(export: &SheetExport, now: NaiveDateTime) -> bool {
+    export.is_enabled
+        && export
+            .last_attempt_at
+            .is_none_or(|at| now - at >= Duration::hours(i64::from(export.sync_interval_hours)))
+}
+
+#[async_trait]
+impl SheetExportServiceTrait for SheetExportService {
+    fn get_exports(&self) -> Result<Vec<SheetExport>> {
+        self.repository.get_exports()
+    }
+
+    fn get_export(&self, id: &str) -> Result<SheetExport> {
+        self.repository.get_export(id)
+    }
+
+    async fn create_export(&self, export: NewSheetExport) -> Result<SheetExport> {
+        export.validate()?;
+        self.repository.insert_export(export).await
+    }
+
+    async fn update_export(&self, id: &str, export: NewSheetExport) -> Result<SheetExport> {
+        export.validate()?;
+        self.repository.update_export(id, export).await
+    }
+
+    async fn delete_export(&self, id: &str) -> Result<usize> {
+        self.repository.delete_export(id).await
+    }
+
+    fn has_credentials(&self) -> Result<bool> {
+        Ok(self.credential_store.get_credentials()?.is_some())
+    }
+
+    fn set_credentials(&self, credentials: GoogleCredentials) -> Result<()> {
+        credentials.validate()?;
+        let credentials = GoogleCredentials {
+            client_id: credentials.client_id.trim().to_string(),
+            client_secret: credentials.client_secret.trim().to_string(),
+            refresh_token: credentials.refresh_token.trim().to_string(),
+        };
+        self.credential_store.set_credentials(&credentials)
+    }
+
+    fn delete_credentials(&self) -> Result<()> {
+        self.credential_store.delete_credentials()
+    }
+
+    async fn push_export(&self, id: &str) -> Result<SheetExportResult> {
+        let export = self.repository.get_export(id)?;
+        let attempted_at = Utc::now().naive_utc();
+        match self.push(&export).await {
+            Ok(rows) => {
+                self.repository.record_push(id, attempted_at, None).await?;
+                Ok(SheetExportResult {
+                    export_id: export.id,
+                    export_name: export.name,
+                    rows,
+                    error: None,
+                })
+            }
+            Err(e) => {
+                self.repository
+                    .record_push(id, attempted_at, Some(e.to_string()))
+                    .await?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn push_due_exports(&self) -> Result<Vec<SheetExportResult>> {
+        let now = Utc::now().naive_utc();
+        let mut results = Vec::new();
+        for export in self.repository.get_exports()? {
+            if !is_due(&export, now) {
+                continue;
+            }
+            // One failing sheet must not stop the others
+            let result = self
+                .push_export(&export.id)
+                .await
+                .unwrap_or_else(|e| SheetExportResult {
+                    export_id: export.id.clone(),
+                    export_name: export.name.clone(),
+                    rows: 0,
+                    error: Some(e.to_string()),
+                });
+            results.push(result);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::holdings::MonetaryValue;
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn cash(currency: &str, base: Decimal) -> Holding {
+        Holding {
+            id: format!("CASH-{}", currency),
+            account_id: "TOTAL".to_string(),
+            holding_type: HoldingType::Cash,
+            instrument: None,
+            quantity: base,
+            open_date: None,
+            lots: None,
+            local_currency: currency.to_string(),
+            base_currency: "VND".to_string(),
+            fx_rate: None,
+            market_value: MonetaryValue { local: base, base },
+            cost_basis: None,
+            price: None,
+            unrealized_gain: None,
+            unrealized_gain_pct: None,
+            realized_gain: None,
+            realized_gain_pct: None,
+            total_gain: None,
+            total_gain_pct: None,
+            day_change: None,
+            day_change_pct: None,
+            prev_close_value: None,
+            weight: Decimal::ZERO,
+            as_of_date: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+        }
+    }
+
+    #[test]
+    fn holdings_are_sorted_by_value_and_cash_gets_a_symbol() {
+        let table = holdings_table(
+            vec![cash("USD", dec!(100)), cash("VND", dec!(5_000_000))],
+            "VND",
+        );
+        assert_eq!(table.header[7], "Market value (VND)");
+        assert_eq!(table.rows[0][0], json!("$CASH-VND"));
+        assert_eq!(table.rows[0][7], json!(5_000_000.0));
+        assert_eq!(table.rows[1][0], json!("$CASH-USD"));
+        // Missing values stay empty cells rather than zeros
+        assert_eq!(table.rows[1][5], Value::Null);
+    }
+
+    #[test]
+    fn net_worth_rows_put_the_header_first() {
+        let series = vec![NetWorthPoint {
+            date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            total_assets: dec!(1_200),
+            total_liabilities: dec!(200),
+            net_worth: dec!(1_000),
+        }];
+        let values = net_worth_table(&series, "VND").to_values();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0][3], json!("Net worth (VND)"));
+        assert_eq!(
+            values[1],
+            vec![
+                json!("2026-10-01"),
+                json!(1200.0),
+                json!(200.0),
+                json!(1000.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn due_after_the_interval_since_the_last_attempt() {
+        let now = Utc::now().naive_utc();
+        let mut export = SheetExport {
+            id: "export-1".to_string(),
+            name: "Net worth".to_string(),
+            report: SheetReport::NetWorth,
+            spreadsheet_id: "abc".to_string(),
+            sheet_name: "Net worth".to_string(),
+            sync_interval_hours: 24,
+            is_enabled: true,
+            last_attempt_at: None,
+            last_synced_at: None,
+            last_sync_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        assert!(is_due(&export, now));
+        export.last_attempt_at = Some(now - Duration::hours(2));
+        assert!(!is_due(&export, now));
+        export.last_attempt_at = Some(now - Duration::hours(24));
+        assert!(is_due(&export, now));
+        export.is_enabled = false;
+        assert!(!is_due(&export, now));
+    }
+}
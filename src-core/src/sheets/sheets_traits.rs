@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+
+use super::sheets_model::{
+    GoogleCredentials, NewSheetExport, SheetExport, SheetExportResult, SheetTable,
+};
+use crate::errors::Result;
+
+/// Writes a table into one tab of a spreadsheet
+#[async_trait]
+pub trait SheetsClient: Send + Sync {
+    /// Replaces everything in the tab with `table`
+    async fn write_table(
+        &self,
+        credentials: &GoogleCredentials,
+        spreadsheet_id: &str,
+        sheet_name: &str,
+        table: &SheetTable,
+    ) -> Result<()>;
+}
+
+/// Where the Google credentials are kept; the keyring in the apps
+pub trait SheetCredentialStore: Send + Sync {
+    fn get_credentials(&self) -> Result<Option<GoogleCredentials>>;
+    fn set_credentials(&self, credentials: &GoogleCredentials) -> Result<()>;
+    fn delete_credentials(&self) -> Result<()>;
+}
+
+#[async_trait]
+pub trait SheetExportRepositoryTrait: Send + Sync {
+    fn get_exports(&self) -> Result<Vec<SheetExport>>;
+    fn get_export(&self, id: &str) -> Result<SheetExport>;
+    async fn insert_export(&self, export: NewSheetExport) -> Result<SheetExport>;
+    async fn update_export(&self, id: &str, export: NewSheetExport) -> Result<SheetExport>;
+    async fn delete_export(&self, id: &str) -> Result<usize>;
+    /// Records the outcome of a push; `last_synced_at` only moves when there is no error
+    async fn record_push(
+        &self,
+        id: &str,
+        attempted_at: NaiveDateTime,
+        error: Option<String>,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+pub trait SheetExportServiceTrait: Send + Sync {
+    fn get_exports(&self) -> Result<Vec<SheetExport>>;
+    fn get_export(&self, id: &str) -> Result<SheetExport>;
+    async fn create_export(&self, export: NewSheetExport) -> Result<SheetExport>;
+    async fn update_export(&self, id: &str, export: NewSheetExport) -> Result<SheetExport>;
+    async fn delete_export(&self, id: &str) -> Result<usize>;
+    /// Whether Google credentials are saved; the credentials themselves are never read back
+    fn has_credentials(&self) -> Result<bool>;
+    fn set_credentials(&self, credentials: GoogleCredentials) -> Result<()>;
+    fn delete_credentials(&self) -> Result<()>;
+    /// Builds the export's report and writes it to the sheet
+    async fn push_export(&self, id: &str) -> Result<SheetExportResult>;
+    /// Pushes every enabled export whose interval has passed
+    async fn push_due_exports(&self) -> Result<Vec<SheetExportResult>>;
+}
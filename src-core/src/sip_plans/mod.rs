@@ -0,0 +1,13 @@
+mod sip_plans_model;
+mod sip_plans_repository;
+mod sip_plans_service;
+mod sip_plans_traits;
+
+pub use sip_plans_model::{
+    summarize_sip_plan, FundNavs, LumpSumComparison, NewSipPlan, SipInstallment,
+    SipInstallmentStatus, SipPerformancePoint, SipPlan, SipPlanDB, SipPlanSummary, SipPurchase,
+    SIP_MATCH_DAYS,
+};
+pub use sip_plans_repository::SipPlanRepository;
+pub use sip_plans_service::SipPlanService;
+pub use sip_plans_traits::{SipPlanRepositoryTrait, SipPlanServiceTrait};
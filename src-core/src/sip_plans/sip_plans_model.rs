@@ -0,0 +1,547 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::activities::Activity;
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::{occurrences, parse_forecast_date, CashFlowFrequency};
+
+/// Days either side of an installment's due date a purchase is counted towards it
+pub const SIP_MATCH_DAYS: i64 = 10;
+
+/// Database row for `sip_plans`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::sip_plans)]
+pub struct SipPlanDB {
+    pub id: String,
+    pub account_id: String,
+    pub asset_id: String,
+    pub name: String,
+    pub amount: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+    pub planned_cash_flow_id: Option<String>,
+    pub is_active: bool,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A monthly purchase program for a fund certificate (SIP). Amounts are in the account
+/// currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SipPlan {
+    pub id: String,
+    /// Account the certificates are bought in
+    pub account_id: String,
+    /// The fund bought, e.g. E1VFVN30 or VESAF
+    pub asset_id: String,
+    pub name: String,
+    /// Invested every month, fees included
+    pub amount: Decimal,
+    /// First installment; later ones fall on the same day of each month
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    /// Monthly contribution that puts the installments in the cash flow forecast
+    pub planned_cash_flow_id: Option<String>,
+    pub is_active: bool,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl SipPlan {
+    /// Due dates of the installments from the start up to and including `to`
+    pub fn installment_dates(&self, to: NaiveDate) -> Vec<NaiveDate> {
+        occurrences(
+            self.start_date,
+            self.end_date,
+            CashFlowFrequency::Monthly,
+            self.start_date,
+            to,
+        )
+    }
+}
+
+impl TryFrom<SipPlanDB> for SipPlan {
+    type Error = Error;
+
+    fn try_from(db: SipPlanDB) -> Result<Self> {
+        Ok(SipPlan {
+            id: db.id,
+            account_id: db.account_id,
+            asset_id: db.asset_id,
+            name: db.name,
+            amount: Decimal::from_str(&db.amount)?,
+            start_date: parse_forecast_date(&db.start_date)?,
+            end_date: db
+                .end_date
+                .as_deref()
+                .map(parse_forecast_date)
+                .transpose()?,
+            planned_cash_flow_id: db.planned_cash_flow_id,
+            is_active: db.is_active,
+            notes: db.notes,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for creating or updating a plan
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewSipPlan {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub asset_id: String,
+    /// Named after the fund when unset
+    pub name: Option<String>,
+    pub amount: Decimal,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+    pub notes: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl NewSipPlan {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [("accountId", &self.account_id), ("assetId", &self.asset_id)] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        if self.amount <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "The monthly amount must be positive".to_string(),
+            )));
+        }
+        if self.end_date.is_some_and(|end| end < self.start_date) {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A plan cannot end before it starts".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A purchase of the plan's fund
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SipPurchase {
+    pub activity_id: String,
+    pub date: NaiveDate,
+    pub units: Decimal,
+    /// NAV per unit paid
+    pub nav: Decimal,
+    pub fee: Decimal,
+    /// Units at the NAV plus the fee
+    pub amount: Decimal,
+}
+
+impl From<&Activity> for SipPurchase {
+    fn from(activity: &Activity) -> Self {
+        SipPurchase {
+            activity_id: activity.id.clone(),
+            date: activity.activity_date.date_naive(),
+            units: activity.quantity,
+            nav: activity.unit_price,
+            fee: activity.fee,
+            amount: activity.quantity * activity.unit_price + activity.fee,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SipInstallmentStatus {
+    Purchased,
+    /// Not bought yet, but still within the matching window
+    Due,
+    Missed,
+}
+
+/// One month's installment and the purchases made for it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SipInstallment {
+    pub due_date: NaiveDate,
+    pub expected_amount: Decimal,
+    pub actual_amount: Decimal,
+    pub units: Decimal,
+    pub activity_ids: Vec<String>,
+    pub status: SipInstallmentStatus,
+}
+
+/// What the plan and a lump sum of the same total would have been worth on a date
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SipPerformancePoint {
+    pub date: NaiveDate,
+    /// Paid into the fund by this date
+    pub invested: Decimal,
+    pub dca_value: Decimal,
+    pub lump_sum_value: Decimal,
+}
+
+/// Everything the plan invested, put into the fund at once on the start date instead
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LumpSumComparison {
+    pub date: NaiveDate,
+    pub nav: Decimal,
+    pub units: Decimal,
+    pub market_value: Decimal,
+    pub gain: Decimal,
+    /// Gain over the amount invested, in percent
+    pub return_percent: Decimal,
+}
+
+/// A plan reconciled against its purchases and valued as of a date
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SipPlanSummary {
+    pub plan: SipPlan,
+    pub currency: String,
+    pub valued_on: NaiveDate,
+    /// Installments due so far, then the next one
+    pub installments: Vec<SipInstallment>,
+    /// Purchases not near any installment, e.g. a one-off top-up
+    pub extra_purchases: Vec<SipPurchase>,
+    /// What the installments due so far should have invested
+    pub expected_amount: Decimal,
+    pub invested_amount: Decimal,
+    /// Expected less invested; negative when ahead of plan
+    pub shortfall: Decimal,
+    pub missed_installments: usize,
+    pub units: Decimal,
+    /// Average NAV paid per unit, fees excluded
+    pub average_nav: Decimal,
+    pub latest_nav: Option<Decimal>,
+    pub market_value: Decimal,
+    pub gain: Decimal,
+    /// Gain over the amount invested, in percent
+    pub return_percent: Decimal,
+    pub lump_sum: Option<LumpSumComparison>,
+    /// The plan against the lump sum on each installment date and the valuation date
+    pub performance: Vec<SipPerformancePoint>,
+}
+
+/// NAVs of the fund by date, from its quotes and the prices paid
+pub struct FundNavs {
+    navs: Vec<(NaiveDate, Decimal)>,
+}
+
+impl FundNavs {
+    /// Quotes win over purchase prices on the same date
+    pub fn new(quotes: Vec<(NaiveDate, Decimal)>, purchases: &[SipPurchase]) -> Self {
+        let mut navs = quotes;
+        for purchase in purchases {
+            if !navs.iter().any(|(date, _)| *date == purchase.date) {
+                navs.push((purchase.date, purchase.nav));
+            }
+        }
+        navs.sort_by_key(|(date, _)| *date);
+        FundNavs { navs }
+    }
+
+    /// NAV on the last date on or before `date`, or the first one known after it
+    pub fn on(&self, date: NaiveDate) -> Option<Decimal> {
+        self.navs
+            .iter()
+            .rev()
+            .find(|(navved_on, _)| *navved_on <= date)
+            .or_else(|| self.navs.first())
+            .map(|(_, nav)| *nav)
+    }
+}
+
+fn percent_of(gain: Decimal, invested: Decimal) -> Decimal {
+    if invested.is_zero() {
+        Decimal::ZERO
+    } else {
+        (gain / invested * Decimal::ONE_HUNDRED).round_dp(2)
+    }
+}
+
+/// Matches purchases to the plan's installments and compares the result with a lump sum
+/// invested on the start date, as of `date`
+pub fn summarize_sip_plan(
+    plan: SipPlan,
+    currency: String,
+    mut purchases: Vec<SipPurchase>,
+    navs: &FundNavs,
+    date: NaiveDate,
+) -> SipPlanSummary {
+    purchases.sort_by_key(|purchase| purchase.date);
+    let mut due_dates = plan.installment_dates(date);
+    let next = plan
+        .installment_dates(NaiveDate::MAX)
+        .into_iter()
+        .find(|due| *due > date);
+    let due_so_far = due_dates.len();
+    due_dates.extend(next);
+
+    let mut installments: Vec<SipInstallment> = due_dates
+        .iter()
+        .map(|due_date| SipInstallment {
+            due_date: *due_date,
+            expected_amount: plan.amount,
+            actual_amount: Decimal::ZERO,
+            units: Decimal::ZERO,
+            activity_ids: Vec::new(),
+            status: SipInstallmentStatus::Due,
+        })
+        .collect();
+    let mut extra_purchases = Vec::new();
+    for purchase in &purchases {
+        let nearest = installments
+            .iter_mut()
+            .filter(|installment| {
+                (purchase.date - installment.due_date).num_days().abs() <= SIP_MATCH_DAYS
+            })
+            .min_by_key(|installment| (purchase.date - installment.due_date).num_days().abs());
+        match nearest {
+            Some(installment) => {
+                installment.actual_amount += purchase.amount;
+                installment.units += purchase.units;
+                installment.activity_ids.push(purchase.activity_id.clone());
+                installment.status = SipInstallmentStatus::Purchased;
+            }
+            None => extra_purchases.push(purchase.clone()),
+        }
+    }
+    for installment in installments.iter_mut() {
+        if installment.status == SipInstallmentStatus::Due
+            && (date - installment.due_date).num_days() > SIP_MATCH_DAYS
+        {
+            installment.status = SipInstallmentStatus::Missed;
+        }
+    }
+
+    let expected_amount = plan.amount * Decimal::from(due_so_far);
+    let invested_amount: Decimal = purchases.iter().map(|purchase| purchase.amount).sum();
+    let units: Decimal = purchases.iter().map(|purchase| purchase.units).sum();
+    let paid_at_nav: Decimal = purchases
+        .iter()
+        .map(|purchase| purchase.units * purchase.nav)
+        .sum();
+    let average_nav = if units.is_zero() {
+        Decimal::ZERO
+    } else {
+        paid_at_nav / units
+    };
+    let latest_nav = navs.on(date);
+    let market_value = latest_nav.map_or(Decimal::ZERO, |nav| units * nav);
+    let gain = market_value - invested_amount;
+
+    let lump_sum = navs
+        .on(plan.start_date)
+        .filter(|nav| !nav.is_zero() && !invested_amount.is_zero())
+        .map(|nav| {
+            let lump_units = invested_amount / nav;
+            let value = latest_nav.map_or(Decimal::ZERO, |latest| lump_units * latest);
+            LumpSumComparison {
+                date: plan.start_date,
+                nav,
+                units: lump_units,
+                market_value: value,
+                gain: value - invested_amount,
+                return_percent: percent_of(value - invested_amount, invested_amount),
+            }
+        });
+
+    let mut points: Vec<NaiveDate> = due_dates[..due_so_far].to_vec();
+    if points.last() != Some(&date) {
+        points.push(date);
+    }
+    let performance = points
+        .into_iter()
+        .map(|point| {
+            let bought: Vec<&SipPurchase> = purchases
+                .iter()
+                .filter(|purchase| purchase.date <= point)
+                .collect();
+            let nav = navs.on(point).unwrap_or(Decimal::ZERO);
+            SipPerformancePoint {
+                date: point,
+                invested: bought.iter().map(|purchase| purchase.amount).sum(),
+                dca_value: bought
+                    .iter()
+                    .map(|purchase| purchase.units)
+                    .sum::<Decimal>()
+                    * nav,
+                lump_sum_value: lump_sum.as_ref().map_or(Decimal::ZERO, |lump| {
+                    if point >= lump.date {
+                        lump.units * nav
+                    } else {
+                        Decimal::ZERO
+                    }
+                }),
+            }
+        })
+        .collect();
+
+    SipPlanSummary {
+        currency,
+        valued_on: date,
+        missed_installments: installments
+            .iter()
+            .filter(|installment| installment.status == SipInstallmentStatus::Missed)
+            .count(),
+        installments,
+        extra_purchases,
+        expected_amount,
+        invested_amount,
+        shortfall: expected_amount - invested_amount,
+        units,
+        average_nav,
+        latest_nav,
+        market_value,
+        gain,
+        return_percent: percent_of(gain, invested_amount),
+        lump_sum,
+        performance,
+        plan,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn plan() -> SipPlan {
+        let now = Utc::now().naive_utc();
+        SipPlan {
+            id: "vesaf".to_string(),
+            account_id: "fmarket".to_string(),
+            asset_id: "VESAF".to_string(),
+            name: "VESAF monthly".to_string(),
+            amount: dec!(2_000_000),
+            start_date: date("2026-06-05"),
+            end_date: None,
+            planned_cash_flow_id: None,
+            is_active: true,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn purchase(id: &str, on: &str, units: Decimal, nav: Decimal) -> SipPurchase {
+        SipPurchase {
+            activity_id: id.to_string(),
+            date: date(on),
+            units,
+            nav,
+            fee: Decimal::ZERO,
+            amount: units * nav,
+        }
+    }
+
+    #[test]
+    fn purchases_are_matched_to_installments_and_gaps_are_missed() {
+        let purchases = vec![
+            purchase("jun", "2026-06-05", dec!(100), dec!(20_000)),
+            // Bought late, still counted for July
+            purchase("jul", "2026-07-12", dec!(125), dec!(16_000)),
+            // August skipped, then a top-up mid-month
+            purchase("top-up", "2026-08-20", dec!(50), dec!(20_000)),
+            purchase("sep", "2026-09-04", dec!(80), dec!(25_000)),
+        ];
+        let navs = FundNavs::new(vec![(date("2026-10-17"), dec!(22_000))], &purchases);
+        let summary = summarize_sip_plan(
+            plan(),
+            "VND".to_string(),
+            purchases,
+            &navs,
+            date("2026-10-10"),
+        );
+
+        let statuses: Vec<SipInstallmentStatus> = summary
+            .installments
+            .iter()
+            .map(|installment| installment.status)
+            .collect();
+        // October is due but within the window; November is next
+        assert_eq!(
+            statuses,
+            vec![
+                SipInstallmentStatus::Purchased,
+                SipInstallmentStatus::Purchased,
+                SipInstallmentStatus::Missed,
+                SipInstallmentStatus::Purchased,
+                SipInstallmentStatus::Due,
+                SipInstallmentStatus::Due,
+            ]
+        );
+        assert_eq!(summary.missed_installments, 1);
+        assert_eq!(summary.extra_purchases.len(), 1);
+        assert_eq!(summary.expected_amount, dec!(10_000_000));
+        assert_eq!(summary.invested_amount, dec!(7_000_000));
+        assert_eq!(summary.shortfall, dec!(3_000_000));
+        assert_eq!(summary.units, dec!(355));
+        // The October quote is after the valuation date, so the last NAV paid is used
+        assert_eq!(summary.latest_nav, Some(dec!(25_000)));
+        assert_eq!(summary.market_value, dec!(8_875_000));
+        assert_eq!(summary.average_nav.round_dp(2), dec!(19_718.31));
+    }
+
+    #[test]
+    fn lump_sum_wins_in_a_rising_market_and_dca_in_a_falling_one() {
+        let rising = vec![
+            purchase("jun", "2026-06-05", dec!(100), dec!(20_000)),
+            purchase("jul", "2026-07-05", dec!(80), dec!(25_000)),
+        ];
+        let navs = FundNavs::new(vec![(date("2026-07-20"), dec!(30_000))], &rising);
+        let summary =
+            summarize_sip_plan(plan(), "VND".to_string(), rising, &navs, date("2026-07-20"));
+        let lump = summary.lump_sum.unwrap();
+        assert_eq!(lump.units, dec!(200));
+        assert_eq!(lump.market_value, dec!(6_000_000));
+        assert_eq!(summary.market_value, dec!(5_400_000));
+        assert_eq!(summary.return_percent, dec!(35));
+        assert_eq!(lump.return_percent, dec!(50));
+        assert_eq!(summary.performance.len(), 3);
+        assert_eq!(
+            summary.performance[1],
+            SipPerformancePoint {
+                date: date("2026-07-05"),
+                invested: dec!(4_000_000),
+                dca_value: dec!(4_500_000),
+                lump_sum_value: dec!(5_000_000),
+            }
+        );
+
+        let falling = vec![
+            purchase("jun", "2026-06-05", dec!(100), dec!(20_000)),
+            purchase("jul", "2026-07-05", dec!(200), dec!(10_000)),
+        ];
+        let navs = FundNavs::new(vec![(date("2026-07-20"), dec!(15_000))], &falling);
+        let summary = summarize_sip_plan(
+            plan(),
+            "VND".to_string(),
+            falling,
+            &navs,
+            date("2026-07-20"),
+        );
+        assert_eq!(summary.market_value, dec!(4_500_000));
+        assert_eq!(summary.lump_sum.unwrap().market_value, dec!(3_000_000));
+    }
+}
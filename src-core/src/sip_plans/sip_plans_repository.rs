@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::sip_plans_model::{NewSipPlan, SipPlan, SipPlanDB};
+use super::sip_plans_traits::SipPlanRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::sip_plans;
+use crate::utils::text_utils::trimmed;
+
+pub struct SipPlanRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl SipPlanRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        SipPlanRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl SipPlanRepositoryTrait for SipPlanRepository {
+    fn get_plans(&self) -> Result<Vec<SipPlan>> {
+        let mut conn = get_connection(&self.pool)?;
+        sip_plans::table
+            .order((sip_plans::start_date.asc(), sip_plans::name.asc()))
+            .load::<SipPlanDB>(&mut conn)?
+            .into_iter()
+            .map(SipPlan::try_from)
+            .collect()
+    }
+
+    fn get_plan(&self, id: &str) -> Result<SipPlan> {
+        let mut conn = get_connection(&self.pool)?;
+        sip_plans::table
+            .find(id)
+            .first::<SipPlanDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_plan(
+        &self,
+        plan: NewSipPlan,
+        name: String,
+        planned_cash_flow_id: Option<String>,
+    ) -> Result<SipPlan> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<SipPlan> {
+                let now = Utc::now().naive_utc();
+                let record = SipPlanDB {
+                    id: plan.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: plan.account_id,
+                    asset_id: plan.asset_id.trim().to_string(),
+                    name,
+                    amount: plan.amount.to_string(),
+                    start_date: plan.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    end_date: plan
+                        .end_date
+                        .map(|date| date.format(FORECAST_DATE_FORMAT).to_string()),
+                    planned_cash_flow_id,
+                    is_active: plan.is_active,
+                    notes: trimmed(plan.notes),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(sip_plans::table)
+                    .values(&record)
+                    .get_result::<SipPlanDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_plan(
+        &self,
+        id: &str,
+        plan: NewSipPlan,
+        name: String,
+        planned_cash_flow_id: Option<String>,
+    ) -> Result<SipPlan> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<SipPlan> {
+                diesel::update(sip_plans::table.find(id_owned))
+                    .set((
+                        sip_plans::name.eq(name),
+                        sip_plans::amount.eq(plan.amount.to_string()),
+                        sip_plans::start_date
+                            .eq(plan.start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        sip_plans::end_date.eq(plan
+                            .end_date
+                            .map(|date| date.format(FORECAST_DATE_FORMAT).to_string())),
+                        sip_plans::planned_cash_flow_id.eq(planned_cash_flow_id),
+                        sip_plans::is_active.eq(plan.is_active),
+                        sip_plans::notes.eq(trimmed(plan.notes)),
+                        sip_plans::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<SipPlanDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_plan(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(sip_plans::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
@@ -0,0 +1,213 @@
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate, Utc};
+use std::sync::Arc;
+
+use super::sip_plans_model::{
+    summarize_sip_plan, FundNavs, NewSipPlan, SipPlan, SipPlanSummary, SipPurchase, SIP_MATCH_DAYS,
+};
+use super::sip_plans_traits::{SipPlanRepositoryTrait, SipPlanServiceTrait};
+use crate::accounts::{
+    Account, AccountRepositoryTrait, ACCOUNT_TYPE_LIABILITY, ACCOUNT_TYPE_REAL_ESTATE,
+};
+use crate::activities::{ActivityServiceTrait, ACTIVITY_TYPE_BUY};
+use crate::assets::AssetServiceTrait;
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::{CashFlowFrequency, CashFlowKind, ForecastServiceTrait, NewPlannedCashFlow};
+use crate::market_data::MarketDataServiceTrait;
+
+pub struct SipPlanService {
+    repository: Arc<dyn SipPlanRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+    asset_service: Arc<dyn AssetServiceTrait>,
+    activity_service: Arc<dyn ActivityServiceTrait>,
+    market_data_service: Arc<dyn MarketDataServiceTrait>,
+    forecast_service: Arc<dyn ForecastServiceTrait>,
+}
+
+impl SipPlanService {
+    pub fn new(
+        repository: Arc<dyn SipPlanRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+        asset_service: Arc<dyn AssetServiceTrait>,
+        activity_service: Arc<dyn ActivityServiceTrait>,
+        market_data_service: Arc<dyn MarketDataServiceTrait>,
+        forecast_service: Arc<dyn ForecastServiceTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+            asset_service,
+            activity_service,
+            market_data_service,
+            forecast_service,
+        }
+    }
+
+    fn investment_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type == ACCOUNT_TYPE_LIABILITY
+            || account.account_type == ACCOUNT_TYPE_REAL_ESTATE
+        {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Fund certificates cannot be bought in {}",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+
+    /// The plan's own name, or the fund's
+    fn plan_name(&self, plan: &NewSipPlan) -> Result<String> {
+        if let Some(name) = plan
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+        {
+            return Ok(name.to_string());
+        }
+        let asset = self.asset_service.get_asset_by_id(plan.asset_id.trim())?;
+        Ok(format!(
+            "{} monthly",
+            asset
+                .name
+                .filter(|name| !name.trim().is_empty())
+                .unwrap_or(asset.symbol)
+        ))
+    }
+
+    /// The monthly contribution the installments are planned as in the forecast
+    fn planned_contribution(
+        plan: &NewSipPlan,
+        name: &str,
+        account: &Account,
+    ) -> NewPlannedCashFlow {
+        NewPlannedCashFlow {
+            id: None,
+            name: name.to_string(),
+            kind: CashFlowKind::Contribution,
+            account_id: Some(account.id.clone()),
+            amount: plan.amount,
+            currency: account.currency.clone(),
+            frequency: CashFlowFrequency::Monthly,
+            start_date: plan.start_date,
+            end_date: plan.end_date,
+            is_active: plan.is_active,
+            term_deposit: Default::default(),
+        }
+    }
+
+    /// Buys of the plan's fund in its account around the plan's installments
+    fn purchases(&self, plan: &SipPlan) -> Result<Vec<SipPurchase>> {
+        let window = Days::new(SIP_MATCH_DAYS as u64);
+        let from = plan
+            .start_date
+            .checked_sub_days(window)
+            .unwrap_or(NaiveDate::MIN);
+        let to = plan
+            .end_date
+            .and_then(|end| end.checked_add_days(window))
+            .unwrap_or(NaiveDate::MAX);
+        Ok(self
+            .activity_service
+            .get_activities_by_account_id(&plan.account_id)?
+            .iter()
+            .filter(|activity| {
+                activity.activity_type == ACTIVITY_TYPE_BUY
+                    && activity.asset_id == plan.asset_id
+                    && !activity.is_draft
+            })
+            .map(SipPurchase::from)
+            .filter(|purchase| purchase.date >= from && purchase.date <= to)
+            .collect())
+    }
+
+    fn summary(&self, plan: SipPlan) -> Result<SipPlanSummary> {
+        let account = self.account_repository.get_by_id(&plan.account_id)?;
+        let purchases = self.purchases(&plan)?;
+        let quotes = self
+            .market_data_service
+            .get_historical_quotes_for_symbol(&plan.asset_id)?
+            .into_iter()
+            .map(|quote| (quote.timestamp.date_naive(), quote.close))
+            .collect();
+        let navs = FundNavs::new(quotes, &purchases);
+        Ok(summarize_sip_plan(
+            plan,
+            account.currency,
+            purchases,
+            &navs,
+            Utc::now().date_naive(),
+        ))
+    }
+}
+
+#[async_trait]
+impl SipPlanServiceTrait for SipPlanService {
+    fn get_sip_plans(&self) -> Result<Vec<SipPlan>> {
+        self.repository.get_plans()
+    }
+
+    fn get_sip_plan_summaries(&self) -> Result<Vec<SipPlanSummary>> {
+        self.repository
+            .get_plans()?
+            .into_iter()
+            .map(|plan| self.summary(plan))
+            .collect()
+    }
+
+    fn get_sip_plan_summary(&self, id: &str) -> Result<SipPlanSummary> {
+        self.summary(self.repository.get_plan(id)?)
+    }
+
+    async fn create_sip_plan(&self, plan: NewSipPlan) -> Result<SipPlan> {
+        plan.validate()?;
+        let account = self.investment_account(&plan.account_id)?;
+        let name = self.plan_name(&plan)?;
+        let flow = self
+            .forecast_service
+            .create_planned_cash_flow(Self::planned_contribution(&plan, &name, &account))
+            .await?;
+        self.repository.insert_plan(plan, name, Some(flow.id)).await
+    }
+
+    async fn update_sip_plan(&self, id: &str, plan: NewSipPlan) -> Result<SipPlan> {
+        plan.validate()?;
+        let existing = self.repository.get_plan(id)?;
+        if existing.account_id != plan.account_id || existing.asset_id != plan.asset_id.trim() {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A plan cannot be moved to another account or fund".to_string(),
+            )));
+        }
+        let account = self.investment_account(&existing.account_id)?;
+        let name = self.plan_name(&plan)?;
+        let contribution = Self::planned_contribution(&plan, &name, &account);
+        // The planned flow is recreated when it was deleted from the forecast
+        let flow = match &existing.planned_cash_flow_id {
+            Some(flow_id) => {
+                self.forecast_service
+                    .update_planned_cash_flow(flow_id, contribution)
+                    .await?
+            }
+            None => {
+                self.forecast_service
+                    .create_planned_cash_flow(contribution)
+                    .await?
+            }
+        };
+        self.repository
+            .update_plan(id, plan, name, Some(flow.id))
+            .await
+    }
+
+    async fn delete_sip_plan(&self, id: &str) -> Result<usize> {
+        let plan = self.repository.get_plan(id)?;
+        let deleted = self.repository.delete_plan(id).await?;
+        if let Some(flow_id) = &plan.planned_cash_flow_id {
+            self.forecast_service
+                .delete_planned_cash_flow(flow_id)
+                .await?;
+        }
+        Ok(deleted)
+    }
+}
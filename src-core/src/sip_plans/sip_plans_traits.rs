@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+
+use super::sip_plans_model::{NewSipPlan, SipPlan, SipPlanSummary};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait SipPlanRepositoryTrait: Send + Sync {
+    fn get_plans(&self) -> Result<Vec<SipPlan>>;
+    fn get_plan(&self, id: &str) -> Result<SipPlan>;
+    async fn insert_plan(
+        &self,
+        plan: NewSipPlan,
+        name: String,
+        planned_cash_flow_id: Option<String>,
+    ) -> Result<SipPlan>;
+    async fn update_plan(
+        &self,
+        id: &str,
+        plan: NewSipPlan,
+        name: String,
+        planned_cash_flow_id: Option<String>,
+    ) -> Result<SipPlan>;
+    async fn delete_plan(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait SipPlanServiceTrait: Send + Sync {
+    fn get_sip_plans(&self) -> Result<Vec<SipPlan>>;
+    /// Every plan reconciled against the fund's purchases and valued as of today
+    fn get_sip_plan_summaries(&self) -> Result<Vec<SipPlanSummary>>;
+    fn get_sip_plan_summary(&self, id: &str) -> Result<SipPlanSummary>;
+    /// Also plans the monthly installments as a contribution in the cash flow forecast
+    async fn create_sip_plan(&self, plan: NewSipPlan) -> Result<SipPlan>;
+    async fn update_sip_plan(&self, id: &str, plan: NewSipPlan) -> Result<SipPlan>;
+    async fn delete_sip_plan(&self, id: &str) -> Result<usize>;
+}
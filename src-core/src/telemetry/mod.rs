@@ -0,0 +1,10 @@
+mod telemetry_metrics;
+mod telemetry_model;
+
+pub use telemetry_metrics::{
+    install_metrics_subscriber, performance_metrics, MetricsLayer, PerformanceMetrics,
+};
+pub use telemetry_model::{
+    OperationStats, PerformanceStats, ServiceReadiness, SERVICE_MARKET_DATA, SERVICE_VALUATION,
+    SLOW_OPERATION_MS,
+};
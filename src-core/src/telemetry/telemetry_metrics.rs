@@ -0,0 +1,179 @@
+use chrono::{DateTime, Utc};
+use log::warn;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use super::telemetry_model::{OperationStats, PerformanceStats, SLOW_OPERATION_MS};
+
+/// Spans from this crate are timed; the apps' own spans (HTTP requests and such) are not
+const CORE_TARGET: &str = env!("CARGO_CRATE_NAME");
+
+struct Totals {
+    calls: u64,
+    total: Duration,
+    max: Duration,
+    last: Duration,
+    slow_calls: u64,
+    last_called_at: DateTime<Utc>,
+}
+
+/// In-memory timings per span name. Recording is off until `set_enabled(true)`.
+pub struct PerformanceMetrics {
+    enabled: AtomicBool,
+    state: Mutex<(DateTime<Utc>, HashMap<&'static str, Totals>)>,
+}
+
+impl PerformanceMetrics {
+    fn new() -> Self {
+        PerformanceMetrics {
+            enabled: AtomicBool::new(false),
+            state: Mutex::new((Utc::now(), HashMap::new())),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn record(&self, name: &'static str, elapsed: Duration) {
+        let slow = elapsed > Duration::from_millis(SLOW_OPERATION_MS);
+        if slow {
+            warn!("Slow operation {}: {} ms", name, elapsed.as_millis());
+        }
+        let mut state = self.state.lock().unwrap();
+        let totals = state.1.entry(name).or_insert(Totals {
+            calls: 0,
+            total: Duration::ZERO,
+            max: Duration::ZERO,
+            last: Duration::ZERO,
+            slow_calls: 0,
+            last_called_at: Utc::now(),
+        });
+        totals.calls += 1;
+        totals.total += elapsed;
+        totals.max = totals.max.max(elapsed);
+        totals.last = elapsed;
+        if slow {
+            totals.slow_calls += 1;
+        }
+        totals.last_called_at = Utc::now();
+    }
+
+    pub fn stats(&self) -> PerformanceStats {
+        let state = self.state.lock().unwrap();
+        let millis = |d: Duration| d.as_secs_f64() * 1_000.0;
+        let mut operations: Vec<OperationStats> = state
+            .1
+            .iter()
+            .map(|(name, totals)| OperationStats {
+                name: name.to_string(),
+                calls: totals.calls,
+                total_ms: millis(totals.total),
+                average_ms: millis(totals.total) / totals.calls as f64,
+                max_ms: millis(totals.max),
+                last_ms: millis(totals.last),
+                slow_calls: totals.slow_calls,
+                last_called_at: totals.last_called_at,
+            })
+            .collect();
+        operations.sort_by(|a, b| b.total_ms.total_cmp(&a.total_ms));
+        PerformanceStats {
+            enabled: self.is_enabled(),
+            since: state.0,
+            slow_threshold_ms: SLOW_OPERATION_MS,
+            operations,
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.state.lock().unwrap() = (Utc::now(), HashMap::new());
+    }
+}
+
+/// The process-wide sink the `MetricsLayer` records into
+pub fn performance_metrics() -> &'static PerformanceMetrics {
+    static METRICS: OnceLock<PerformanceMetrics> = OnceLock::new();
+    METRICS.get_or_init(PerformanceMetrics::new)
+}
+
+/// Start of a span that is being timed, kept in the span's extensions
+struct SpanStart(Instant);
+
+/// Times core spans from creation to close into `performance_metrics()`
+pub struct MetricsLayer;
+
+impl<S> Layer<S> for MetricsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if !performance_metrics().is_enabled()
+            || !attrs.metadata().target().starts_with(CORE_TARGET)
+        {
+            return;
+        }
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanStart(Instant::now()));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let started = span.extensions().get::<SpanStart>().map(|start| start.0);
+        if let Some(started) = started {
+            performance_metrics().record(span.metadata().name(), started.elapsed());
+        }
+    }
+}
+
+/// For apps that log through `log` and have no tracing subscriber of their own:
+/// installs one that only feeds the metrics sink. Does nothing if one is already set.
+pub fn install_metrics_subscriber() {
+    let subscriber = tracing_subscriber::registry().with(MetricsLayer);
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        warn!("A tracing subscriber is already installed; add MetricsLayer to it instead");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_spans_are_timed_only_while_enabled() {
+        let subscriber = tracing_subscriber::registry().with(MetricsLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            let metrics = performance_metrics();
+            metrics.reset();
+
+            metrics.set_enabled(false);
+            drop(tracing::info_span!("test.disabled").entered());
+            metrics.set_enabled(true);
+            for _ in 0..2 {
+                drop(tracing::info_span!("test.enabled").entered());
+            }
+            drop(tracing::info_span!(target: "axum", "test.foreign").entered());
+            metrics.set_enabled(false);
+
+            let stats = metrics.stats();
+            let names: Vec<&str> = stats.operations.iter().map(|o| o.name.as_str()).collect();
+            assert_eq!(names, ["test.enabled"]);
+            assert_eq!(stats.operations[0].calls, 2);
+            assert_eq!(stats.operations[0].slow_calls, 0);
+            assert_eq!(stats.slow_threshold_ms, SLOW_OPERATION_MS);
+        });
+    }
+}
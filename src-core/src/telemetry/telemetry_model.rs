@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Operations slower than this are counted as slow and logged
+pub const SLOW_OPERATION_MS: u64 = 1_000;
+
+/// Timings of one instrumented operation since the stats were last reset
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationStats {
+    /// Span name, e.g. `valuation.calculate_history`
+    pub name: String,
+    pub calls: u64,
+    pub total_ms: f64,
+    pub average_ms: f64,
+    pub max_ms: f64,
+    pub last_ms: f64,
+    /// Calls that took longer than `SLOW_OPERATION_MS`
+    pub slow_calls: u64,
+    pub last_called_at: DateTime<Utc>,
+}
+
+/// What the local metrics sink has collected; nothing leaves the machine
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceStats {
+    /// Whether timings are being recorded (the `performance_metrics` feature flag)
+    pub enabled: bool,
+    pub since: DateTime<Utc>,
+    pub slow_threshold_ms: u64,
+    /// Most total time first
+    pub operations: Vec<OperationStats>,
+}
+
+/// Name of the market data subsystem in readiness events
+pub const SERVICE_MARKET_DATA: &str = "marketData";
+/// Name of the valuation subsystem in readiness events
+pub const SERVICE_VALUATION: &str = "valuation";
+
+/// Which subsystems that come up after startup are ready. Cached data can be shown before
+/// they are; it is refreshed as each one comes up.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceReadiness {
+    /// Market data providers are configured, so quotes and symbol search can reach them
+    pub market_data: bool,
+    /// Valuations have been brought up to date since startup
+    pub valuation: bool,
+}
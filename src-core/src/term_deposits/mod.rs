@@ -0,0 +1,12 @@
+mod term_deposits_model;
+mod term_deposits_repository;
+mod term_deposits_service;
+mod term_deposits_traits;
+
+pub use term_deposits_model::{
+    DepositTerm, InterestCompounding, NewTermDeposit, RolloverRule, TermDeposit,
+    TermDepositMaturity, TermDepositSummary,
+};
+pub use term_deposits_repository::TermDepositRepository;
+pub use term_deposits_service::TermDepositService;
+pub use term_deposits_traits::{TermDepositRepositoryTrait, TermDepositServiceTrait};
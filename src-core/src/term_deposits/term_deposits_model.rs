@@ -0,0 +1,482 @@
+use chrono::{Months, NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+use crate::forecast::parse_forecast_date;
+
+/// Day count banks accrue deposit interest over (actual/365)
+const DAYS_IN_YEAR: i64 = 365;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InterestCompounding {
+    /// Interest is paid with the principal at maturity (lãi cuối kỳ)
+    Simple,
+    /// Interest is added to the balance every month and earns interest itself
+    Monthly,
+    /// Interest is added to the balance every quarter
+    Quarterly,
+}
+
+impl InterestCompounding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InterestCompounding::Simple => "SIMPLE",
+            InterestCompounding::Monthly => "MONTHLY",
+            InterestCompounding::Quarterly => "QUARTERLY",
+        }
+    }
+
+    /// Months between capitalizations; `None` when nothing compounds within a term
+    pub fn step_months(&self) -> Option<u32> {
+        match self {
+            InterestCompounding::Simple => None,
+            InterestCompounding::Monthly => Some(1),
+            InterestCompounding::Quarterly => Some(3),
+        }
+    }
+}
+
+impl FromStr for InterestCompounding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "SIMPLE" => Ok(InterestCompounding::Simple),
+            "MONTHLY" => Ok(InterestCompounding::Monthly),
+            "QUARTERLY" => Ok(InterestCompounding::Quarterly),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown interest compounding: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// What the bank does when a term ends
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RolloverRule {
+    /// The deposit is settled (tất toán) and stops earning
+    None,
+    /// The principal renews for another term at the same rate; the interest is paid out
+    Principal,
+    /// Principal and interest renew together (tái tục gốc và lãi)
+    PrincipalAndInterest,
+}
+
+impl RolloverRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RolloverRule::None => "NONE",
+            RolloverRule::Principal => "PRINCIPAL",
+            RolloverRule::PrincipalAndInterest => "PRINCIPAL_AND_INTEREST",
+        }
+    }
+}
+
+impl FromStr for RolloverRule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "NONE" => Ok(RolloverRule::None),
+            "PRINCIPAL" => Ok(RolloverRule::Principal),
+            "PRINCIPAL_AND_INTEREST" => Ok(RolloverRule::PrincipalAndInterest),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown rollover rule: {}",
+                other
+            )))),
+        }
+    }
+}
+
+/// Database row for `term_deposits`
+#[derive(Queryable, Identifiable, Insertable, Debug, Clone)]
+#[diesel(table_name = crate::schema::term_deposits)]
+pub struct TermDepositDB {
+    pub id: String,
+    pub account_id: String,
+    pub bank_name: String,
+    pub principal: String,
+    pub interest_rate: String,
+    pub term_months: i32,
+    pub start_date: String,
+    pub compounding: String,
+    pub rollover: String,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// A term deposit (sổ tiết kiệm) held with a bank. Amounts are in the account currency.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TermDeposit {
+    pub id: String,
+    /// Cash account the deposit belongs to
+    pub account_id: String,
+    pub bank_name: String,
+    pub principal: Decimal,
+    /// Annual rate in percent
+    pub interest_rate: Decimal,
+    pub term_months: i32,
+    /// Day the first term starts earning
+    pub start_date: NaiveDate,
+    pub compounding: InterestCompounding,
+    pub rollover: RolloverRule,
+    pub notes: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// One term of a deposit: the balance it opens with and what it matures to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DepositTerm {
+    pub start: NaiveDate,
+    pub maturity: NaiveDate,
+    pub opening: Decimal,
+    pub closing: Decimal,
+}
+
+impl TermDeposit {
+    /// Interest earned on `balance` from `from` to `to`, one day at a time over 365
+    fn interest(&self, balance: Decimal, from: NaiveDate, to: NaiveDate) -> Decimal {
+        let days = Decimal::from((to - from).num_days().max(0));
+        balance * self.interest_rate / Decimal::ONE_HUNDRED * days / Decimal::from(DAYS_IN_YEAR)
+    }
+
+    /// Balance on `date` of a term opened on `start` with `opening`. Compounding deposits
+    /// add the interest of every whole period to the balance, then accrue on the total.
+    fn grow(&self, opening: Decimal, start: NaiveDate, date: NaiveDate) -> Decimal {
+        let Some(step) = self.compounding.step_months() else {
+            return opening + self.interest(opening, start, date);
+        };
+        let mut balance = opening;
+        let mut period_start = start;
+        for n in 1.. {
+            let Some(period_end) = start.checked_add_months(Months::new(step * n)) else {
+                break;
+            };
+            if period_end > date {
+                break;
+            }
+            balance += self.interest(balance, period_start, period_end);
+            period_start = period_end;
+        }
+        balance + self.interest(balance, period_start, date)
+    }
+
+    /// The deposit's terms in order; one term unless it rolls over. Terms are counted from
+    /// the start date, so each matures on the start's day of the month.
+    pub fn terms(&self) -> impl Iterator<Item = DepositTerm> + '_ {
+        let term_months = u32::try_from(self.term_months).unwrap_or_default();
+        let mut next = (term_months > 0).then_some((self.start_date, self.principal, 1u32));
+        std::iter::from_fn(move || {
+            let (start, opening, index) = next?;
+            let maturity = self
+                .start_date
+                .checked_add_months(Months::new(term_months.checked_mul(index)?))?;
+            let closing = self.grow(opening, start, maturity);
+            next = match self.rollover {
+                RolloverRule::None => None,
+                RolloverRule::Principal => Some((maturity, self.principal, index + 1)),
+                RolloverRule::PrincipalAndInterest => Some((maturity, closing, index + 1)),
+            };
+            Some(DepositTerm {
+                start,
+                maturity,
+                opening,
+                closing,
+            })
+        })
+    }
+
+    /// Maturity of the first term
+    pub fn maturity_date(&self) -> Option<NaiveDate> {
+        self.terms().next().map(|term| term.maturity)
+    }
+
+    /// Values the deposit on `date`: the term running then, or the last one once settled
+    pub fn value_on(&self, date: NaiveDate, currency: String) -> TermDepositSummary {
+        let mut interest_paid_out = Decimal::ZERO;
+        let mut running = None;
+        let mut last = None;
+        if date >= self.start_date {
+            for term in self.terms() {
+                if date < term.maturity {
+                    running = Some(term);
+                    break;
+                }
+                if self.rollover == RolloverRule::Principal {
+                    interest_paid_out += term.closing - term.opening;
+                }
+                last = Some(term);
+            }
+        }
+
+        let (value, term_principal, maturity_date, is_settled) = match (running, last) {
+            (Some(term), _) => (
+                self.grow(term.opening, term.start, date),
+                term.opening,
+                Some(term.maturity),
+                false,
+            ),
+            // Settled at maturity: the balance is due back and earns nothing more
+            (None, Some(term)) => (term.closing, term.opening, Some(term.maturity), true),
+            // Not started yet
+            (None, None) => (Decimal::ZERO, Decimal::ZERO, self.maturity_date(), false),
+        };
+        TermDepositSummary {
+            valued_on: date,
+            value: value.round_dp(2),
+            term_principal,
+            accrued_interest: (value - term_principal).round_dp(2),
+            interest_paid_out: interest_paid_out.round_dp(2),
+            maturity_date,
+            days_to_maturity: maturity_date
+                .map_or(0, |maturity| (maturity - date).num_days().max(0)),
+            is_settled,
+            currency,
+            deposit: self.clone(),
+        }
+    }
+
+    /// Terms maturing after `from` up to and including `to`
+    pub fn maturities_between(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        currency: &str,
+    ) -> Vec<TermDepositMaturity> {
+        self.terms()
+            .take_while(|term| term.maturity <= to)
+            .filter(|term| term.maturity > from)
+            .map(|term| {
+                let interest = term.closing - term.opening;
+                TermDepositMaturity {
+                    deposit_id: self.id.clone(),
+                    account_id: self.account_id.clone(),
+                    bank_name: self.bank_name.clone(),
+                    maturity_date: term.maturity,
+                    principal: term.opening.round_dp(2),
+                    interest: interest.round_dp(2),
+                    maturity_value: term.closing.round_dp(2),
+                    paid_out: match self.rollover {
+                        RolloverRule::None => term.closing,
+                        RolloverRule::Principal => interest,
+                        RolloverRule::PrincipalAndInterest => Decimal::ZERO,
+                    }
+                    .round_dp(2),
+                    rollover: self.rollover,
+                    currency: currency.to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<TermDepositDB> for TermDeposit {
+    type Error = Error;
+
+    fn try_from(db: TermDepositDB) -> Result<Self> {
+        Ok(TermDeposit {
+            id: db.id,
+            account_id: db.account_id,
+            bank_name: db.bank_name,
+            principal: Decimal::from_str(&db.principal)?,
+            interest_rate: Decimal::from_str(&db.interest_rate)?,
+            term_months: db.term_months,
+            start_date: parse_forecast_date(&db.start_date)?,
+            compounding: InterestCompounding::from_str(&db.compounding)?,
+            rollover: RolloverRule::from_str(&db.rollover)?,
+            notes: db.notes,
+            created_at: db.created_at,
+            updated_at: db.updated_at,
+        })
+    }
+}
+
+/// Input for opening or updating a term deposit
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NewTermDeposit {
+    pub id: Option<String>,
+    pub account_id: String,
+    pub bank_name: String,
+    pub principal: Decimal,
+    pub interest_rate: Decimal,
+    pub term_months: i32,
+    pub start_date: NaiveDate,
+    pub compounding: InterestCompounding,
+    pub rollover: RolloverRule,
+    pub notes: Option<String>,
+}
+
+impl NewTermDeposit {
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("accountId", &self.account_id),
+            ("bankName", &self.bank_name),
+        ] {
+            if value.trim().is_empty() {
+                return Err(Error::Validation(ValidationError::MissingField(
+                    field.to_string(),
+                )));
+            }
+        }
+        if self.principal <= Decimal::ZERO {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Deposit principal must be positive".to_string(),
+            )));
+        }
+        if self.interest_rate < Decimal::ZERO || self.interest_rate >= Decimal::ONE_HUNDRED {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Interest rate must be a percentage between 0 and 100".to_string(),
+            )));
+        }
+        if self.term_months <= 0 {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "Deposit term must be at least a month".to_string(),
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A term coming to maturity
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TermDepositMaturity {
+    pub deposit_id: String,
+    pub account_id: String,
+    pub bank_name: String,
+    pub maturity_date: NaiveDate,
+    /// Balance the term opened with
+    pub principal: Decimal,
+    pub interest: Decimal,
+    /// Principal plus interest
+    pub maturity_value: Decimal,
+    /// Cash freed for other uses, such as funding goals: all of it when the deposit is
+    /// settled, the interest when only the principal renews, nothing when both renew
+    pub paid_out: Decimal,
+    pub rollover: RolloverRule,
+    pub currency: String,
+}
+
+/// A term deposit valued as of a date
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TermDepositSummary {
+    pub deposit: TermDeposit,
+    pub currency: String,
+    pub valued_on: NaiveDate,
+    /// Balance plus interest accrued in the running term
+    pub value: Decimal,
+    /// Balance the running (or last) term opened with
+    pub term_principal: Decimal,
+    /// Interest earned in the running term so far
+    pub accrued_interest: Decimal,
+    /// Interest paid out at earlier maturities when only the principal renews
+    pub interest_paid_out: Decimal,
+    /// End of the running term, or of the last one once settled
+    pub maturity_date: Option<NaiveDate>,
+    pub days_to_maturity: i64,
+    pub is_settled: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn date(value: &str) -> NaiveDate {
+        parse_forecast_date(value).unwrap()
+    }
+
+    fn deposit(compounding: InterestCompounding, rollover: RolloverRule) -> TermDeposit {
+        let now = Utc::now().naive_utc();
+        TermDeposit {
+            id: "td".to_string(),
+            account_id: "cash".to_string(),
+            bank_name: "Vietcombank".to_string(),
+            principal: dec!(100_000_000),
+            interest_rate: dec!(7.3),
+            term_months: 6,
+            start_date: date("2026-01-10"),
+            compounding,
+            rollover,
+            notes: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn simple_interest_accrues_daily_and_stops_once_settled() {
+        let deposit = deposit(InterestCompounding::Simple, RolloverRule::None);
+
+        // 100 days at 7.3% is 2%
+        let summary = deposit.value_on(date("2026-04-20"), "VND".to_string());
+        assert_eq!(summary.value, dec!(102_000_000));
+        assert_eq!(summary.accrued_interest, dec!(2_000_000));
+        assert_eq!(summary.maturity_date, Some(date("2026-07-10")));
+        assert_eq!(summary.days_to_maturity, 81);
+
+        // 181 days in the term
+        let settled = deposit.value_on(date("2026-12-01"), "VND".to_string());
+        assert!(settled.is_settled);
+        assert_eq!(settled.value, dec!(103_620_000));
+        assert_eq!(
+            deposit
+                .value_on(date("2026-01-01"), "VND".to_string())
+                .value,
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn rollovers_renew_the_principal_or_the_whole_balance() {
+        let interest_out = deposit(InterestCompounding::Simple, RolloverRule::Principal);
+        let summary = interest_out.value_on(date("2026-07-20"), "VND".to_string());
+        assert_eq!(summary.term_principal, dec!(100_000_000));
+        assert_eq!(summary.interest_paid_out, dec!(3_620_000));
+        assert_eq!(summary.maturity_date, Some(date("2027-01-10")));
+
+        let renewed = deposit(
+            InterestCompounding::Simple,
+            RolloverRule::PrincipalAndInterest,
+        );
+        let maturities = renewed.maturities_between(date("2026-01-10"), date("2027-12-31"), "VND");
+        assert_eq!(maturities.len(), 3);
+        assert_eq!(maturities[1].principal, dec!(103_620_000));
+        assert_eq!(maturities[1].paid_out, Decimal::ZERO);
+        assert_eq!(
+            interest_out.maturities_between(date("2026-01-10"), date("2026-07-10"), "VND")[0]
+                .paid_out,
+            dec!(3_620_000)
+        );
+    }
+
+    #[test]
+    fn compounding_adds_interest_to_the_balance_each_period() {
+        let monthly = deposit(InterestCompounding::Monthly, RolloverRule::None);
+        let simple = deposit(InterestCompounding::Simple, RolloverRule::None);
+        let day = date("2026-07-10");
+
+        let compounded = monthly.value_on(day, "VND".to_string()).value;
+        let flat = simple.value_on(day, "VND".to_string()).value;
+        assert!(compounded > flat);
+        // 31 days of January at 7.3% is 620,000, then February earns on the new balance
+        assert_eq!(
+            monthly
+                .value_on(date("2026-02-10"), "VND".to_string())
+                .value,
+            dec!(100_620_000)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::sqlite::SqliteConnection;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::term_deposits_model::{NewTermDeposit, TermDeposit, TermDepositDB};
+use super::term_deposits_traits::TermDepositRepositoryTrait;
+use crate::db::{get_connection, WriteHandle};
+use crate::errors::Result;
+use crate::forecast::FORECAST_DATE_FORMAT;
+use crate::schema::term_deposits;
+use crate::utils::text_utils::trimmed;
+
+pub struct TermDepositRepository {
+    pool: Arc<Pool<ConnectionManager<SqliteConnection>>>,
+    writer: WriteHandle,
+}
+
+impl TermDepositRepository {
+    pub fn new(pool: Arc<Pool<ConnectionManager<SqliteConnection>>>, writer: WriteHandle) -> Self {
+        TermDepositRepository { pool, writer }
+    }
+}
+
+#[async_trait]
+impl TermDepositRepositoryTrait for TermDepositRepository {
+    fn get_term_deposits(&self) -> Result<Vec<TermDeposit>> {
+        let mut conn = get_connection(&self.pool)?;
+        term_deposits::table
+            .order((
+                term_deposits::start_date.asc(),
+                term_deposits::bank_name.asc(),
+            ))
+            .load::<TermDepositDB>(&mut conn)?
+            .into_iter()
+            .map(TermDeposit::try_from)
+            .collect()
+    }
+
+    fn get_term_deposit(&self, id: &str) -> Result<TermDeposit> {
+        let mut conn = get_connection(&self.pool)?;
+        term_deposits::table
+            .find(id)
+            .first::<TermDepositDB>(&mut conn)?
+            .try_into()
+    }
+
+    async fn insert_term_deposit(&self, deposit: NewTermDeposit) -> Result<TermDeposit> {
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<TermDeposit> {
+                let now = Utc::now().naive_utc();
+                let record = TermDepositDB {
+                    id: deposit.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+                    account_id: deposit.account_id,
+                    bank_name: deposit.bank_name.trim().to_string(),
+                    principal: deposit.principal.to_string(),
+                    interest_rate: deposit.interest_rate.to_string(),
+                    term_months: deposit.term_months,
+                    start_date: deposit.start_date.format(FORECAST_DATE_FORMAT).to_string(),
+                    compounding: deposit.compounding.as_str().to_string(),
+                    rollover: deposit.rollover.as_str().to_string(),
+                    notes: trimmed(deposit.notes),
+                    created_at: now,
+                    updated_at: now,
+                };
+                diesel::insert_into(term_deposits::table)
+                    .values(&record)
+                    .get_result::<TermDepositDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn update_term_deposit(&self, id: &str, deposit: NewTermDeposit) -> Result<TermDeposit> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<TermDeposit> {
+                diesel::update(term_deposits::table.find(id_owned))
+                    .set((
+                        term_deposits::bank_name.eq(deposit.bank_name.trim().to_string()),
+                        term_deposits::principal.eq(deposit.principal.to_string()),
+                        term_deposits::interest_rate.eq(deposit.interest_rate.to_string()),
+                        term_deposits::term_months.eq(deposit.term_months),
+                        term_deposits::start_date
+                            .eq(deposit.start_date.format(FORECAST_DATE_FORMAT).to_string()),
+                        term_deposits::compounding.eq(deposit.compounding.as_str()),
+                        term_deposits::rollover.eq(deposit.rollover.as_str()),
+                        term_deposits::notes.eq(trimmed(deposit.notes)),
+                        term_deposits::updated_at.eq(Utc::now().naive_utc()),
+                    ))
+                    .get_result::<TermDepositDB>(conn)?
+                    .try_into()
+            })
+            .await
+    }
+
+    async fn delete_term_deposit(&self, id: &str) -> Result<usize> {
+        let id_owned = id.to_string();
+        self.writer
+            .exec(move |conn: &mut SqliteConnection| -> Result<usize> {
+                Ok(diesel::delete(term_deposits::table.find(id_owned)).execute(conn)?)
+            })
+            .await
+    }
+}
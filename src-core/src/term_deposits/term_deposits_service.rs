@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use chrono::{Days, NaiveDate, Utc};
+use std::sync::Arc;
+
+use super::term_deposits_model::{
+    NewTermDeposit, TermDeposit, TermDepositMaturity, TermDepositSummary,
+};
+use super::term_deposits_traits::{TermDepositRepositoryTrait, TermDepositServiceTrait};
+use crate::accounts::{Account, AccountRepositoryTrait, ACCOUNT_TYPE_CASH};
+use crate::errors::{Error, Result, ValidationError};
+
+pub struct TermDepositService {
+    repository: Arc<dyn TermDepositRepositoryTrait>,
+    account_repository: Arc<dyn AccountRepositoryTrait>,
+}
+
+impl TermDepositService {
+    pub fn new(
+        repository: Arc<dyn TermDepositRepositoryTrait>,
+        account_repository: Arc<dyn AccountRepositoryTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            account_repository,
+        }
+    }
+
+    /// Deposits sit in cash accounts, as the accounts a deposit ladder opens do
+    fn holding_account(&self, account_id: &str) -> Result<Account> {
+        let account = self.account_repository.get_by_id(account_id)?;
+        if account.account_type != ACCOUNT_TYPE_CASH {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "{} is not a cash account; term deposits are held in cash accounts",
+                account.name
+            ))));
+        }
+        Ok(account)
+    }
+
+    fn summary(&self, deposit: TermDeposit, date: NaiveDate) -> Result<TermDepositSummary> {
+        let account = self.account_repository.get_by_id(&deposit.account_id)?;
+        Ok(deposit.value_on(date, account.currency))
+    }
+}
+
+#[async_trait]
+impl TermDepositServiceTrait for TermDepositService {
+    fn get_term_deposits(&self) -> Result<Vec<TermDeposit>> {
+        self.repository.get_term_deposits()
+    }
+
+    fn get_term_deposit_summaries(&self) -> Result<Vec<TermDepositSummary>> {
+        let today = Utc::now().date_naive();
+        self.repository
+            .get_term_deposits()?
+            .into_iter()
+            .map(|deposit| self.summary(deposit, today))
+            .collect()
+    }
+
+    fn get_term_deposit_summary(
+        &self,
+        id: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<TermDepositSummary> {
+        self.summary(
+            self.repository.get_term_deposit(id)?,
+            date.unwrap_or_else(|| Utc::now().date_naive()),
+        )
+    }
+
+    async fn create_term_deposit(&self, deposit: NewTermDeposit) -> Result<TermDeposit> {
+        deposit.validate()?;
+        self.holding_account(&deposit.account_id)?;
+        self.repository.insert_term_deposit(deposit).await
+    }
+
+    async fn update_term_deposit(&self, id: &str, deposit: NewTermDeposit) -> Result<TermDeposit> {
+        deposit.validate()?;
+        let existing = self.repository.get_term_deposit(id)?;
+        if existing.account_id != deposit.account_id {
+            return Err(Error::Validation(ValidationError::InvalidInput(
+                "A term deposit cannot be moved to another account".to_string(),
+            )));
+        }
+        self.repository.update_term_deposit(id, deposit).await
+    }
+
+    async fn delete_term_deposit(&self, id: &str) -> Result<usize> {
+        self.repository.delete_term_deposit(id).await
+    }
+
+    fn get_upcoming_maturities(&self, days: u32) -> Result<Vec<TermDepositMaturity>> {
+        let today = Utc::now().date_naive();
+        let until = today
+            .checked_add_days(Days::new(u64::from(days)))
+            .unwrap_or(NaiveDate::MAX);
+        let mut maturities = Vec::new();
+        for deposit in self.repository.get_term_deposits()? {
+            let account = self.account_repository.get_by_id(&deposit.account_id)?;
+            maturities.extend(deposit.maturities_between(today, until, &account.currency));
+        }
+        maturities.sort_by(|a, b| {
+            a.maturity_date
+                .cmp(&b.maturity_date)
+                .then_with(|| a.bank_name.cmp(&b.bank_name))
+        });
+        Ok(maturities)
+    }
+}
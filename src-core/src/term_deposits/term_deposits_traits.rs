@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+use super::term_deposits_model::{
+    NewTermDeposit, TermDeposit, TermDepositMaturity, TermDepositSummary,
+};
+use crate::errors::Result;
+
+#[async_trait]
+pub trait TermDepositRepositoryTrait: Send + Sync {
+    fn get_term_deposits(&self) -> Result<Vec<TermDeposit>>;
+    fn get_term_deposit(&self, id: &str) -> Result<TermDeposit>;
+    async fn insert_term_deposit(&self, deposit: NewTermDeposit) -> Result<TermDeposit>;
+    async fn update_term_deposit(&self, id: &str, deposit: NewTermDeposit) -> Result<TermDeposit>;
+    async fn delete_term_deposit(&self, id: &str) -> Result<usize>;
+}
+
+#[async_trait]
+pub trait TermDepositServiceTrait: Send + Sync {
+    fn get_term_deposits(&self) -> Result<Vec<TermDeposit>>;
+    /// Every deposit valued today
+    fn get_term_deposit_summaries(&self) -> Result<Vec<TermDepositSummary>>;
+    /// A deposit valued on `date` (today when unset), with the interest accrued by then
+    fn get_term_deposit_summary(
+        &self,
+        id: &str,
+        date: Option<NaiveDate>,
+    ) -> Result<TermDepositSummary>;
+    async fn create_term_deposit(&self, deposit: NewTermDeposit) -> Result<TermDeposit>;
+    async fn update_term_deposit(&self, id: &str, deposit: NewTermDeposit) -> Result<TermDeposit>;
+    async fn delete_term_deposit(&self, id: &str) -> Result<usize>;
+    /// Terms maturing over the next `days` days, rolled-over terms included, in date order
+    fn get_upcoming_maturities(&self, days: u32) -> Result<Vec<TermDepositMaturity>>;
+}
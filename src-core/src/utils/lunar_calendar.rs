@@ -0,0 +1,279 @@
+//! Vietnamese lunisolar calendar (âm lịch), computed from astronomical new moons and solar
+//! terms at UTC+7 after Hồ Ngọc Đức's algorithm. It differs from the Chinese calendar in the
+//! years where a new moon falls near midnight between the two time zones.
+
+use std::f64::consts::PI;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Hours ahead of UTC of the Vietnamese calendar
+const VN_TIME_ZONE: f64 = 7.0;
+/// Julian day number of 0001-01-01 less one, to offset chrono's day count from the epoch
+const JULIAN_DAY_OFFSET: i64 = 1_721_425;
+/// Julian day of the new moon of 1900-01-01, the reference for new moon numbering
+const NEW_MOON_EPOCH: f64 = 2_415_021.076_998_695;
+const SYNODIC_MONTH: f64 = 29.530_588_853;
+
+/// A day in the lunar calendar
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LunarDate {
+    /// Lunar year, which starts at Tết and so lags the solar year in January and February
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    /// The intercalary month (tháng nhuận) that repeats `month` in a leap year
+    #[serde(default)]
+    pub is_leap_month: bool,
+}
+
+impl LunarDate {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            is_leap_month: false,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=12).contains(&self.month) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid lunar month {}",
+                self.month
+            ))));
+        }
+        if !(1..=30).contains(&self.day) {
+            return Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Invalid lunar day {}",
+                self.day
+            ))));
+        }
+        Ok(())
+    }
+}
+
+fn julian_day(date: NaiveDate) -> i64 {
+    i64::from(date.num_days_from_ce()) + JULIAN_DAY_OFFSET
+}
+
+fn from_julian_day(jd: i64) -> Option<NaiveDate> {
+    i32::try_from(jd - JULIAN_DAY_OFFSET)
+        .ok()
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+}
+
+/// Julian day number of the `k`th new moon after 1900-01-01, in Vietnamese local time
+fn new_moon_day(k: i64) -> i64 {
+    let k = k as f64;
+    let t = k / 1236.85;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let dr = PI / 180.0;
+    let mut jd1 = 2_415_020.759_33 + 29.530_588_68 * k + 0.000_117_8 * t2 - 0.000_000_155 * t3;
+    jd1 += 0.000_33 * ((166.56 + 132.87 * t - 0.009_173 * t2) * dr).sin();
+    let m = 359.2242 + 29.105_356_08 * k - 0.000_033_3 * t2 - 0.000_003_47 * t3;
+    let mpr = 306.0253 + 385.816_918_06 * k + 0.010_730_6 * t2 + 0.000_012_36 * t3;
+    let f = 21.2964 + 390.670_506_46 * k - 0.001_652_8 * t2 - 0.000_002_39 * t3;
+    let mut c1 = (0.1734 - 0.000_393 * t) * (m * dr).sin() + 0.0021 * (2.0 * dr * m).sin();
+    c1 -= 0.4068 * (mpr * dr).sin() + 0.0161 * (dr * 2.0 * mpr).sin();
+    c1 -= 0.0004 * (dr * 3.0 * mpr).sin();
+    c1 += 0.0104 * (dr * 2.0 * f).sin() - 0.0051 * (dr * (m + mpr)).sin();
+    c1 -= 0.0074 * (dr * (m - mpr)).sin() + 0.0004 * (dr * (2.0 * f + m)).sin();
+    c1 -= 0.0004 * (dr * (2.0 * f - m)).sin() - 0.0006 * (dr * (2.0 * f + mpr)).sin();
+    c1 += 0.0010 * (dr * (2.0 * f - mpr)).sin() + 0.0005 * (dr * (2.0 * mpr + m)).sin();
+    let delta_t = if t < -11.0 {
+        0.001 + 0.000_839 * t + 0.000_226_1 * t2 - 0.000_008_45 * t3 - 0.000_000_081 * t * t3
+    } else {
+        -0.000_278 + 0.000_265 * t + 0.000_262 * t2
+    };
+    (jd1 + c1 - delta_t + 0.5 + VN_TIME_ZONE / 24.0).floor() as i64
+}
+
+/// Which of the twelve 30° sectors the sun's longitude is in at the start of `jd`
+fn sun_longitude_sector(jd: i64) -> i64 {
+    let t = (jd as f64 - 2_451_545.5 - VN_TIME_ZONE / 24.0) / 36_525.0;
+    let t2 = t * t;
+    let dr = PI / 180.0;
+    let m = 357.529_10 + 35_999.050_30 * t - 0.000_155_9 * t2 - 0.000_000_48 * t * t2;
+    let l0 = 280.466_45 + 36_000.769_83 * t + 0.000_303_2 * t2;
+    let mut dl = (1.914_600 - 0.004_817 * t - 0.000_014 * t2) * (dr * m).sin();
+    dl += (0.019_993 - 0.000_101 * t) * (dr * 2.0 * m).sin() + 0.000_290 * (dr * 3.0 * m).sin();
+    let l = ((l0 + dl) * dr).rem_euclid(2.0 * PI);
+    (l / PI * 6.0).floor() as i64
+}
+
+/// Julian day the 11th lunar month, the one holding the winter solstice, starts before
+/// the end of `year`
+fn month_11_start(year: i32) -> i64 {
+    let last_day = NaiveDate::from_ymd_opt(year, 12, 31).map_or(0, julian_day);
+    let k = ((last_day as f64 - 2_415_021.0) / SYNODIC_MONTH).floor() as i64;
+    let new_moon = new_moon_day(k);
+    if sun_longitude_sector(new_moon) >= 9 {
+        new_moon_day(k - 1)
+    } else {
+        new_moon
+    }
+}
+
+/// Months after the 11th month starting at `a11` until the first one without a solar term,
+/// which is the leap month
+fn leap_month_offset(a11: i64) -> i64 {
+    let k = ((a11 as f64 - NEW_MOON_EPOCH) / SYNODIC_MONTH + 0.5).floor() as i64;
+    let mut i = 1;
+    let mut arc = sun_longitude_sector(new_moon_day(k + i));
+    loop {
+        let last = arc;
+        i += 1;
+        arc = sun_longitude_sector(new_moon_day(k + i));
+        if arc == last || i >= 14 {
+            break;
+        }
+    }
+    i - 1
+}
+
+/// The lunar date of a solar date
+pub fn solar_to_lunar(date: NaiveDate) -> LunarDate {
+    let day_number = julian_day(date);
+    let k = ((day_number as f64 - NEW_MOON_EPOCH) / SYNODIC_MONTH).floor() as i64;
+    let mut month_start = new_moon_day(k + 1);
+    if month_start > day_number {
+        month_start = new_moon_day(k);
+    }
+
+    let mut a11 = month_11_start(date.year());
+    let mut b11 = a11;
+    let mut year = date.year();
+    if a11 >= month_start {
+        a11 = month_11_start(date.year() - 1);
+    } else {
+        year += 1;
+        b11 = month_11_start(date.year() + 1);
+    }
+
+    let diff = (month_start - a11) / 29;
+    let mut month = diff + 11;
+    let mut is_leap_month = false;
+    if b11 - a11 > 365 {
+        let leap_offset = leap_month_offset(a11);
+        if diff >= leap_offset {
+            month = diff + 10;
+            is_leap_month = diff == leap_offset;
+        }
+    }
+    if month > 12 {
+        month -= 12;
+    }
+    if month >= 11 && diff < 4 {
+        year -= 1;
+    }
+
+    LunarDate {
+        year,
+        month: month as u32,
+        day: (day_number - month_start + 1) as u32,
+        is_leap_month,
+    }
+}
+
+/// Julian day a lunar month starts on and its length, if that month exists
+fn lunar_month_bounds(year: i32, month: u32, is_leap_month: bool) -> Option<(i64, u32)> {
+    if !(1..=12).contains(&month) {
+        return None;
+    }
+    let (a11, b11) = if month < 11 {
+        (month_11_start(year - 1), month_11_start(year))
+    } else {
+        (month_11_start(year), month_11_start(year + 1))
+    };
+    let k = ((a11 as f64 - NEW_MOON_EPOCH) / SYNODIC_MONTH + 0.5).floor() as i64;
+    let mut offset = (i64::from(month) - 11).rem_euclid(12);
+    if b11 - a11 > 365 {
+        let leap_offset = leap_month_offset(a11);
+        let leap_month = (leap_offset - 2).rem_euclid(12);
+        if is_leap_month && i64::from(month) != leap_month {
+            return None;
+        }
+        if is_leap_month || offset >= leap_offset {
+            offset += 1;
+        }
+    } else if is_leap_month {
+        return None;
+    }
+    let start = new_moon_day(k + offset);
+    let length = new_moon_day(k + offset + 1) - start;
+    Some((start, length as u32))
+}
+
+/// Days in a lunar month, 29 or 30; `None` when the year has no such month
+pub fn lunar_month_length(year: i32, month: u32, is_leap_month: bool) -> Option<u32> {
+    lunar_month_bounds(year, month, is_leap_month).map(|(_, length)| length)
+}
+
+/// The solar date of a lunar date; `None` for a leap month the year doesn't have or a 30th
+/// day in a 29-day month
+pub fn lunar_to_solar(date: LunarDate) -> Option<NaiveDate> {
+    let (start, length) = lunar_month_bounds(date.year, date.month, date.is_leap_month)?;
+    if date.day < 1 || date.day > length {
+        return None;
+    }
+    from_julian_day(start + i64::from(date.day) - 1)
+}
+
+/// Solar date a yearly lunar anniversary such as a giỗ falls on in lunar `year`. It is kept
+/// in the regular month when the year repeats that month, and a 30th day moves to the 29th
+/// in a short month.
+pub fn lunar_anniversary(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let length = lunar_month_length(year, month, false)?;
+    lunar_to_solar(LunarDate::new(year, month, day.clamp(1, length)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn converts_known_dates_both_ways() {
+        let cases = [
+            ("2024-02-10", LunarDate::new(2024, 1, 1)),
+            ("2026-02-17", LunarDate::new(2026, 1, 1)),
+            ("2026-02-16", LunarDate::new(2025, 12, 29)),
+            ("2026-09-25", LunarDate::new(2026, 8, 15)),
+            (
+                "2025-07-25",
+                LunarDate {
+                    year: 2025,
+                    month: 6,
+                    day: 1,
+                    is_leap_month: true,
+                },
+            ),
+        ];
+        for (solar, lunar) in cases {
+            assert_eq!(solar_to_lunar(date(solar)), lunar, "{}", solar);
+            assert_eq!(lunar_to_solar(lunar), Some(date(solar)), "{:?}", lunar);
+        }
+    }
+
+    #[test]
+    fn rejects_days_and_leap_months_that_do_not_exist() {
+        let leap = LunarDate {
+            is_leap_month: true,
+            ..LunarDate::new(2026, 6, 1)
+        };
+        assert_eq!(lunar_to_solar(leap), None);
+        // The last month of lunar 2025 has 29 days
+        assert_eq!(lunar_month_length(2025, 12, false), Some(29));
+        assert_eq!(lunar_to_solar(LunarDate::new(2025, 12, 30)), None);
+        assert_eq!(lunar_anniversary(2025, 12, 30), Some(date("2026-02-16")));
+    }
+}
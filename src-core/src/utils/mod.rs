@@ -1,2 +1,5 @@
 // This file declares utility modules
+pub mod lunar_calendar;
+pub mod number_format;
+pub mod text_utils;
 pub mod time_utils;
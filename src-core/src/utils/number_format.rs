@@ -0,0 +1,225 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+use crate::errors::{Error, Result, ValidationError};
+
+/// Decimal and grouping separators of each fixed locale
+const EN_SEPARATORS: (char, char) = ('.', ',');
+const VI_SEPARATORS: (char, char) = (',', '.');
+
+/// How numbers in an imported file or typed input are written
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum NumberLocale {
+    /// `1,500,000.50`
+    #[default]
+    En,
+    /// `1.500.000,50`, as Vietnamese banks and brokers export
+    Vi,
+    /// Decided per value; values that read either way are rejected
+    Auto,
+}
+
+impl NumberLocale {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NumberLocale::En => "en",
+            NumberLocale::Vi => "vi",
+            NumberLocale::Auto => "auto",
+        }
+    }
+
+    /// Decimal and grouping separators
+    fn separators(&self) -> Option<(char, char)> {
+        match self {
+            NumberLocale::En => Some(EN_SEPARATORS),
+            NumberLocale::Vi => Some(VI_SEPARATORS),
+            NumberLocale::Auto => None,
+        }
+    }
+}
+
+impl FromStr for NumberLocale {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "en" => Ok(NumberLocale::En),
+            "vi" => Ok(NumberLocale::Vi),
+            "auto" => Ok(NumberLocale::Auto),
+            other => Err(Error::Validation(ValidationError::InvalidInput(format!(
+                "Unknown number format: {}",
+                other
+            )))),
+        }
+    }
+}
+
+fn invalid(value: &str, reason: &str) -> Error {
+    Error::Validation(ValidationError::InvalidInput(format!(
+        "'{}' {}",
+        value, reason
+    )))
+}
+
+/// Separators of a value in `Auto`: the last of two different separators is the decimal
+/// one, a repeated separator groups thousands, and a single one is a decimal separator unless
+/// exactly three digits follow it, which could be either
+fn detect_separators(value: &str, digits: &str) -> Result<(char, char)> {
+    let dots = digits.matches('.').count();
+    let commas = digits.matches(',').count();
+    match (dots, commas) {
+        (0, 0) => Ok(EN_SEPARATORS),
+        (_, 0) if dots > 1 => Ok(VI_SEPARATORS),
+        (0, _) if commas > 1 => Ok(EN_SEPARATORS),
+        (_, 0) | (0, _) => {
+            let separator = if dots == 1 { '.' } else { ',' };
+            let (whole, fraction) = digits.split_once(separator).unwrap_or_default();
+            let grouping_possible =
+                fraction.len() == 3 && (1..=3).contains(&whole.len()) && whole != "0";
+            if grouping_possible {
+                return Err(invalid(
+                    value,
+                    "could be read either way; choose the number format of the file",
+                ));
+            }
+            Ok(if separator == '.' {
+                EN_SEPARATORS
+            } else {
+                VI_SEPARATORS
+            })
+        }
+        _ => Ok(if digits.rfind('.') > digits.rfind(',') {
+            EN_SEPARATORS
+        } else {
+            VI_SEPARATORS
+        }),
+    }
+}
+
+/// Parses a number written in `locale`, tolerating currency symbols or codes around it,
+/// spaces between digit groups and accounting parentheses for negatives. Blank, `-` and
+/// `N/A` are `None`. Values that don't fit the format are rejected rather than read with
+/// the wrong separators, which would be off by a factor of 1000.
+pub fn parse_localized_decimal(value: &str, locale: NumberLocale) -> Result<Option<Decimal>> {
+    let trimmed = value.trim();
+    if trimmed.is_empty()
+        || trimmed == "-"
+        || trimmed.eq_ignore_ascii_case("n/a")
+        || trimmed.eq_ignore_ascii_case("null")
+    {
+        return Ok(None);
+    }
+
+    let mut text: String = trimmed
+        .trim_matches(|c: char| {
+            !(c.is_ascii_digit() || matches!(c, '.' | ',' | '-' | '+' | '(' | ')'))
+        })
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let mut negative = false;
+    if text.starts_with('(') && text.ends_with(')') {
+        negative = true;
+        text = text[1..text.len() - 1].to_string();
+    }
+    if let Some(rest) = text.strip_prefix('-') {
+        negative = !negative;
+        text = rest.to_string();
+    } else if let Some(rest) = text.strip_prefix('+') {
+        text = rest.to_string();
+    }
+    // A currency symbol between the sign and the digits, as in `-$692.48`
+    let is_digit_or_separator = |c: char| c.is_ascii_digit() || c == '.' || c == ',';
+    let digits = text.trim_start_matches(|c: char| !is_digit_or_separator(c));
+    if digits.is_empty() || !digits.chars().all(is_digit_or_separator) {
+        return Err(invalid(value, "is not a number"));
+    }
+
+    let (decimal, group) = match locale.separators() {
+        Some(separators) => separators,
+        None => detect_separators(value, digits)?,
+    };
+    let (whole, fraction) = match digits.split_once(decimal) {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (digits, None),
+    };
+    let mismatch = || {
+        invalid(
+            value,
+            &format!("does not match the {} number format", locale.as_str()),
+        )
+    };
+    if fraction.is_some_and(|fraction| fraction.contains(decimal) || fraction.contains(group)) {
+        return Err(mismatch());
+    }
+    let mut groups = whole.split(group);
+    let first = groups.next().unwrap_or_default();
+    let grouped = whole.contains(group);
+    if grouped && (first.is_empty() || first.len() > 3 || groups.any(|g| g.len() != 3)) {
+        return Err(mismatch());
+    }
+
+    let mut normalized: String = whole.chars().filter(|c| *c != group).collect();
+    if normalized.is_empty() {
+        normalized.push('0');
+    }
+    if let Some(fraction) = fraction.filter(|fraction| !fraction.is_empty()) {
+        normalized.push('.');
+        normalized.push_str(fraction);
+    }
+    let number = Decimal::from_str(&normalized).map_err(|_| invalid(value, "is not a number"))?;
+    Ok(Some(if negative { -number } else { number }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn parse(value: &str, locale: NumberLocale) -> Option<Decimal> {
+        parse_localized_decimal(value, locale).unwrap()
+    }
+
+    #[test]
+    fn parses_each_locale() {
+        assert_eq!(
+            parse("1,500,000.50", NumberLocale::En),
+            Some(dec!(1500000.50))
+        );
+        assert_eq!(
+            parse("1.500.000,50", NumberLocale::Vi),
+            Some(dec!(1500000.50))
+        );
+        assert_eq!(parse("1.500.000 ₫", NumberLocale::Vi), Some(dec!(1500000)));
+        assert_eq!(parse("VND 25.000", NumberLocale::Vi), Some(dec!(25000)));
+        assert_eq!(parse("-$692.48", NumberLocale::En), Some(dec!(-692.48)));
+        assert_eq!(parse("(1.234,5)", NumberLocale::Vi), Some(dec!(-1234.5)));
+        assert_eq!(parse("1 234.56", NumberLocale::En), Some(dec!(1234.56)));
+        assert_eq!(parse("N/A", NumberLocale::Vi), None);
+    }
+
+    #[test]
+    fn rejects_values_written_in_the_other_locale() {
+        assert!(parse_localized_decimal("1.500.000,50", NumberLocale::En).is_err());
+        assert!(parse_localized_decimal("1,500,000.50", NumberLocale::Vi).is_err());
+        assert!(parse_localized_decimal("12,5", NumberLocale::En).is_err());
+        assert!(parse_localized_decimal("abc", NumberLocale::En).is_err());
+    }
+
+    #[test]
+    fn auto_detects_unambiguous_values() {
+        assert_eq!(parse("1.500.000", NumberLocale::Auto), Some(dec!(1500000)));
+        assert_eq!(parse("1,500,000", NumberLocale::Auto), Some(dec!(1500000)));
+        assert_eq!(
+            parse("1.500.000,5", NumberLocale::Auto),
+            Some(dec!(1500000.5))
+        );
+        assert_eq!(parse("1,500.25", NumberLocale::Auto), Some(dec!(1500.25)));
+        assert_eq!(parse("12,5", NumberLocale::Auto), Some(dec!(12.5)));
+        assert_eq!(parse("0.500", NumberLocale::Auto), Some(dec!(0.500)));
+        assert!(parse_localized_decimal("1.500", NumberLocale::Auto).is_err());
+        assert!(parse_localized_decimal("25,000", NumberLocale::Auto).is_err());
+    }
+}
@@ -0,0 +1,6 @@
+/// Optional text with surrounding whitespace removed; blank text becomes `None`
+pub fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
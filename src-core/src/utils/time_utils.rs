@@ -1,4 +1,4 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, Months, NaiveDate};
 
 pub fn get_days_between(start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
     if start > end {
@@ -17,3 +17,15 @@ pub fn get_days_between(start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
     }
     days
 }
+
+/// First day of the month of `date`
+pub fn month_start(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// First day of the month after the one of `date`
+pub fn next_month_start(date: NaiveDate) -> NaiveDate {
+    month_start(date)
+        .checked_add_months(Months::new(1))
+        .unwrap_or(NaiveDate::MAX)
+}
@@ -0,0 +1,157 @@
+//! DOJI Gold API client
+
+use chrono::NaiveDate;
+use reqwest::Client;
+use roxmltree::Document;
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+use crate::utils::number_format::{parse_localized_decimal, NumberLocale};
+use crate::vn_market::errors::VnMarketError;
+use crate::vn_market::models::gold::{GoldProduct, GoldQuote};
+use crate::vn_market::utils::headers::doji_headers;
+
+const DOJI_URL: &str = "http://giavang.doji.vn/api/giavang/";
+/// Public key of the price widget DOJI embeds on its own site
+const DOJI_API_KEY: &str = "258fbd2a72ce8481089d88c678e9fe4f";
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// DOJI quotes thousands of dong per chỉ; one lượng is ten chỉ
+const DONG_PER_LUONG_UNIT: Decimal = Decimal::from_parts(10_000, 0, 0, false, 0);
+
+/// DOJI Gold API client. The board only shows today's prices, so history builds up
+/// one day at a time as prices are fetched and cached.
+#[derive(Clone)]
+pub struct DojiClient {
+    client: Client,
+}
+
+impl DojiClient {
+    /// Create a new DOJI client
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .default_headers(doji_headers())
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { client }
+    }
+
+    /// Get today's quote of a DOJI product, per lượng
+    pub async fn get_current_price(
+        &self,
+        product: GoldProduct,
+    ) -> Result<GoldQuote, VnMarketError> {
+        let response = self
+            .client
+            .get(DOJI_URL)
+            .query(&[("api_key", DOJI_API_KEY)])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(VnMarketError::ApiError(format!(
+                "DOJI request failed: {}",
+                response.status()
+            )));
+        }
+
+        let today = chrono::Utc::now().date_naive();
+        let (buy, sell) = parse_price_board(&response.text().await?, product, today)?;
+        Ok(GoldQuote::new(product.base_symbol(), today, buy, sell))
+    }
+
+    /// Get today's quote for a gold symbol; prices are per lượng whatever its unit
+    pub async fn get_latest_quote(&self, symbol: &str) -> Result<GoldQuote, VnMarketError> {
+        let quote = self
+            .get_current_price(GoldProduct::from_symbol(symbol))
+            .await?;
+        Ok(GoldQuote::new(
+            symbol,
+            quote.date,
+            quote.buy_price,
+            quote.sell_price,
+        ))
+    }
+}
+
+impl Default for DojiClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Buy and sell prices of one lượng of `product` from the DOJI board. Bars are listed under
+/// `DGPlist` and jewellery (ring gold) under `JewelryList`; the first matching row is taken.
+pub(crate) fn parse_price_board(
+    xml: &str,
+    product: GoldProduct,
+    date: NaiveDate,
+) -> Result<(Decimal, Decimal), VnMarketError> {
+    let document = Document::parse(xml).map_err(|e| VnMarketError::ParseError(e.to_string()))?;
+    let price = |value: Option<&str>| {
+        value
+            .and_then(|value| parse_localized_decimal(value, NumberLocale::En).ok())
+            .flatten()
+            .filter(|price| *price > Decimal::ZERO)
+            .map(|price| price * DONG_PER_LUONG_UNIT)
+    };
+
+    document
+        .descendants()
+        .filter(|node| node.is_element() && node.tag_name().name() == "Row")
+        .filter(|node| product.matches(node.attribute("Name").unwrap_or_default()))
+        .find_map(|node| {
+            let sell = price(node.attribute("Sell"))?;
+            let buy = price(node.attribute("Buy")).unwrap_or(Decimal::ZERO);
+            Some((buy, sell))
+        })
+        .ok_or_else(|| VnMarketError::NoData {
+            symbol: product.base_symbol().to_string(),
+            date: date.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const BOARD: &str = r#"<GoldList>
+        <DGPlist>
+            <DateTime>09:15 16/10/2026</DateTime>
+            <Row Name="SJC - Bán Lẻ" Key="sjcbanle" Sell="9,120" Buy="8,920" />
+            <Row Name="AVPL / DOJI HN lẻ" Key="dojihanoile" Sell="9,120" Buy="8,920" />
+        </DGPlist>
+        <JewelryList>
+            <Row Name="Nhẫn Tròn 9999 Hưng Thịnh Vượng" Key="nhantron9999" Sell="8,850" Buy="" />
+        </JewelryList>
+    </GoldList>"#;
+
+    #[test]
+    fn reads_bars_and_rings_per_luong_from_the_board() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+
+        let (buy, sell) = parse_price_board(BOARD, GoldProduct::DojiBar, date).unwrap();
+        assert_eq!(buy, dec!(89_200_000));
+        assert_eq!(sell, dec!(91_200_000));
+        assert_eq!(
+            GoldQuote::new("VN.GOLD.DOJI", date, buy, sell).close,
+            dec!(91_200_000)
+        );
+
+        // No buy price quoted; the holding is valued at the sell price all the same
+        let (buy, sell) = parse_price_board(BOARD, GoldProduct::DojiRing, date).unwrap();
+        assert_eq!(buy, Decimal::ZERO);
+        assert_eq!(
+            GoldQuote::new("VN.GOLD.DOJI.RING", date, buy, sell).close,
+            dec!(88_500_000)
+        );
+
+        assert!(matches!(
+            parse_price_board("<GoldList />", GoldProduct::DojiBar, date),
+            Err(VnMarketError::NoData { .. })
+        ));
+    }
+}
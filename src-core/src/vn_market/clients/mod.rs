@@ -1,9 +1,11 @@
 //! API clients for Vietnamese market data providers
 
+pub mod doji_client;
 pub mod fmarket_client;
 pub mod sjc_client;
 pub mod vci_client;
 
+pub use doji_client::DojiClient;
 pub use fmarket_client::FMarketClient;
 pub use sjc_client::SjcClient;
 pub use vci_client::VciClient;
@@ -5,7 +5,7 @@ use reqwest::Client;
 use std::time::Duration;
 
 use crate::vn_market::errors::VnMarketError;
-use crate::vn_market::models::gold::{GoldQuote, SjcGoldPrice, SjcResponse};
+use crate::vn_market::models::gold::{GoldProduct, GoldQuote, SjcGoldPrice, SjcResponse};
 use crate::vn_market::utils::headers::sjc_headers;
 
 const SJC_URL: &str = "https://sjc.com.vn/GoldPrice/Services/PriceService.ashx";
@@ -32,11 +32,16 @@ impl SjcClient {
         Self { client }
     }
 
-    /// Get gold price for a specific date
+    /// Get gold price of a product for a specific date
     ///
     /// # Arguments
     /// * `date` - Date to fetch (must be >= 2016-01-02)
-    pub async fn get_gold_price(&self, date: NaiveDate) -> Result<SjcGoldPrice, VnMarketError> {
+    /// * `product` - SJC bars or ring gold, each quoted on its own row
+    pub async fn get_gold_price(
+        &self,
+        date: NaiveDate,
+        product: GoldProduct,
+    ) -> Result<SjcGoldPrice, VnMarketError> {
         // Validate date
         let min_date = NaiveDate::from_ymd_opt(MIN_DATE.0, MIN_DATE.1, MIN_DATE.2).unwrap();
         if date < min_date {
@@ -61,21 +66,31 @@ impl SjcClient {
 
         let result: SjcResponse = response.json().await?;
 
+        let no_data = || VnMarketError::NoData {
+            symbol: product.base_symbol().to_string(),
+            date: date.to_string(),
+        };
         if !result.success || result.data.is_empty() {
-            return Err(VnMarketError::NoData {
-                symbol: "VN.GOLD".to_string(),
-                date: date.to_string(),
-            });
+            return Err(no_data());
         }
 
-        // Return first entry (SJC standard gold bar - typically "Vàng miếng SJC")
-        Ok(result.data.into_iter().next().unwrap())
+        // Rows come per product and branch, the Hồ Chí Minh one first. Bars fall back to the
+        // first row, which is the standard "Vàng miếng SJC" bar when its name is unexpected.
+        let rows = result.data;
+        rows.iter()
+            .find(|price| product.matches(&price.type_name))
+            .or_else(|| rows.first().filter(|_| product == GoldProduct::SjcBar))
+            .cloned()
+            .ok_or_else(no_data)
     }
 
     /// Get current (today's) gold price
-    pub async fn get_current_price(&self) -> Result<SjcGoldPrice, VnMarketError> {
+    pub async fn get_current_price(
+        &self,
+        product: GoldProduct,
+    ) -> Result<SjcGoldPrice, VnMarketError> {
         let today = chrono::Utc::now().date_naive();
-        self.get_gold_price(today).await
+        self.get_gold_price(today, product).await
     }
 
     /// Get gold price history for a date range
@@ -86,6 +101,7 @@ impl SjcClient {
         &self,
         start: NaiveDate,
         end: NaiveDate,
+        product: GoldProduct,
     ) -> Result<Vec<GoldQuote>, VnMarketError> {
         let mut results = Vec::new();
         let mut current = start;
@@ -93,9 +109,9 @@ impl SjcClient {
         while current <= end {
             // Skip weekends (gold market doesn't trade)
             if current.weekday().num_days_from_monday() < 5 {
-                match self.get_gold_price(current).await {
+                match self.get_gold_price(current, product).await {
                     Ok(price) => {
-                        results.push(GoldQuote::from_sjc("VN.GOLD", current, &price));
+                        results.push(GoldQuote::from_sjc(product.base_symbol(), current, &price));
                     }
                     Err(VnMarketError::NoData { .. }) => {
                         // Skip dates with no data (holidays, etc.)
@@ -117,6 +133,7 @@ impl SjcClient {
     /// Get latest available gold quote (tries recent days if today fails)
     pub async fn get_latest_quote(&self, symbol: &str) -> Result<GoldQuote, VnMarketError> {
         let today = chrono::Utc::now().date_naive();
+        let product = GoldProduct::from_symbol(symbol);
 
         // Try last 7 days
         for days_back in 0..7 {
@@ -127,7 +144,7 @@ impl SjcClient {
                 continue;
             }
 
-            match self.get_gold_price(date).await {
+            match self.get_gold_price(date, product).await {
                 Ok(price) => {
                     return Ok(GoldQuote::from_sjc(symbol, date, &price));
                 }
@@ -164,7 +181,10 @@ mod tests {
         let client = SjcClient::new();
         let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
 
-        let price = client.get_gold_price(date).await.unwrap();
+        let price = client
+            .get_gold_price(date, GoldProduct::SjcBar)
+            .await
+            .unwrap();
 
         assert!(price.buy_value > 0.0);
         assert!(price.sell_value > 0.0);
@@ -188,7 +208,7 @@ mod tests {
         let client = SjcClient::new();
         let old_date = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
 
-        let result = client.get_gold_price(old_date).await;
+        let result = client.get_gold_price(old_date, GoldProduct::SjcBar).await;
 
         assert!(matches!(result, Err(VnMarketError::InvalidDate(_))));
     }
@@ -6,7 +6,7 @@
 //! Supported data sources:
 //! - VCI (Vietcap): Stocks and Indices
 //! - FMarket: Mutual Funds
-//! - SJC: Gold Prices
+//! - SJC: Gold Prices (bars and ring gold, in lượng, chỉ or grams)
 
 pub mod assets_model;
 pub mod assets_repository;
@@ -16,6 +16,7 @@ pub mod clients;
 pub mod errors;
 pub mod models;
 pub mod service;
+pub mod trading_rules;
 pub mod utils;
 
 pub use assets_model::{NewVnAsset, VnAsset};
@@ -24,4 +25,6 @@ pub use assets_sync_service::{VnAssetsSyncService, SyncResult};
 pub use cache::{VnAssetType, VnHistoricalCache, VnHistoricalRecord, VnQuoteCache};
 pub use clients::{FMarketClient, SjcClient, VciClient};
 pub use errors::VnMarketError;
+pub use models::gold::{GoldProduct, GoldUnit};
 pub use service::{SearchResult, VnMarketService};
+pub use trading_rules::{LotKind, PriceBand, VnExchange};
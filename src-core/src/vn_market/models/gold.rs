@@ -1,4 +1,4 @@
-//! Gold price models for SJC API
+//! Gold price models for the SJC and DOJI APIs
 
 use chrono::NaiveDate;
 use rust_decimal::Decimal;
@@ -42,14 +42,20 @@ impl SjcGoldPrice {
         Decimal::from_f64_retain(self.sell_value).unwrap_or_default()
     }
 
-    /// Get close price (using sell price as reference)
+    /// Get close price: the dealer's sell price, the quoted price gold is bought at (the buy
+    /// price when no sell price is quoted)
     pub fn close_price(&self) -> Decimal {
-        let sell = self.sell_price();
-        if sell > Decimal::ZERO {
-            sell
-        } else {
-            self.buy_price()
-        }
+        ask_price(self.buy_price(), self.sell_price())
+    }
+}
+
+/// Price a gold holding is valued at: the dealer's sell price (ask), the headline price of the
+/// board and what purchases are recorded at, so a fresh purchase shows no unrealized loss.
+pub fn ask_price(buy_price: Decimal, sell_price: Decimal) -> Decimal {
+    if sell_price > Decimal::ZERO {
+        sell_price
+    } else {
+        buy_price
     }
 }
 
@@ -64,58 +70,159 @@ pub struct GoldQuote {
     pub buy_price: Decimal,
     /// Sell price in VND
     pub sell_price: Decimal,
-    /// Close price (typically sell price)
+    /// Close price (sell price, see [`ask_price`])
     pub close: Decimal,
 }
 
 impl GoldQuote {
-    /// Create from SJC response
-    pub fn from_sjc(symbol: &str, date: NaiveDate, sjc: &SjcGoldPrice) -> Self {
+    /// Create from the buy and sell prices of a dealer
+    pub fn new(symbol: &str, date: NaiveDate, buy_price: Decimal, sell_price: Decimal) -> Self {
         Self {
             symbol: symbol.to_string(),
             date,
-            buy_price: sjc.buy_price(),
-            sell_price: sjc.sell_price(),
-            close: sjc.close_price(),
+            buy_price,
+            sell_price,
+            close: ask_price(buy_price, sell_price),
+        }
+    }
+
+    /// Create from SJC response
+    pub fn from_sjc(symbol: &str, date: NaiveDate, sjc: &SjcGoldPrice) -> Self {
+        Self::new(symbol, date, sjc.buy_price(), sjc.sell_price())
+    }
+}
+
+/// Gold dealers whose boards are read
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GoldDealer {
+    /// Saigon Jewelry Company, quoted with history back to 2016
+    Sjc,
+    /// DOJI, whose board only gives today's prices
+    Doji,
+}
+
+impl GoldDealer {
+    /// Name shown as the exchange of its gold symbols
+    pub fn name(&self) -> &'static str {
+        match self {
+            GoldDealer::Sjc => "SJC",
+            GoldDealer::Doji => "DOJI",
+        }
+    }
+}
+
+/// Gold products quoted separately; ring gold trades at a different price than bars, and
+/// each dealer sets its own prices
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GoldProduct {
+    /// Vàng miếng SJC - SJC gold bars (VN.GOLD)
+    SjcBar,
+    /// Vàng nhẫn - plain ring gold, 99.99% (VN.GOLD.RING)
+    Ring,
+    /// SJC bars bought back and sold by DOJI (VN.GOLD.DOJI)
+    DojiBar,
+    /// Nhẫn tròn 9999 - DOJI ring gold (VN.GOLD.DOJI.RING)
+    DojiRing,
+}
+
+impl GoldProduct {
+    /// Parse from symbol (`VN.GOLD.RING`, `VN.GOLD.RING.C`, ... are ring gold; symbols
+    /// containing `DOJI` are quoted by DOJI)
+    pub fn from_symbol(symbol: &str) -> Self {
+        let upper = symbol.to_uppercase();
+        match (upper.contains("DOJI"), upper.contains("RING")) {
+            (false, false) => GoldProduct::SjcBar,
+            (false, true) => GoldProduct::Ring,
+            (true, false) => GoldProduct::DojiBar,
+            (true, true) => GoldProduct::DojiRing,
+        }
+    }
+
+    /// Symbol of the product quoted per lượng; prices are cached under it
+    pub fn base_symbol(&self) -> &'static str {
+        match self {
+            GoldProduct::SjcBar => "VN.GOLD",
+            GoldProduct::Ring => "VN.GOLD.RING",
+            GoldProduct::DojiBar => "VN.GOLD.DOJI",
+            GoldProduct::DojiRing => "VN.GOLD.DOJI.RING",
+        }
+    }
+
+    /// Dealer whose board quotes the product
+    pub fn dealer(&self) -> GoldDealer {
+        match self {
+            GoldProduct::SjcBar | GoldProduct::Ring => GoldDealer::Sjc,
+            GoldProduct::DojiBar | GoldProduct::DojiRing => GoldDealer::Doji,
+        }
+    }
+
+    /// Whether a price row of the dealer's board (by its type name) quotes this product
+    pub fn matches(&self, type_name: &str) -> bool {
+        let type_name = type_name.to_lowercase();
+        match self {
+            GoldProduct::SjcBar => type_name.contains("miếng"),
+            GoldProduct::Ring => type_name.contains("nhẫn"),
+            GoldProduct::DojiBar => type_name.contains("sjc"),
+            GoldProduct::DojiRing => type_name.contains("nhẫn tròn"),
         }
     }
 }
 
+/// Grams in one lượng (tael)
+const GRAMS_PER_LUONG: Decimal = Decimal::from_parts(375, 0, 0, false, 1);
+
 /// Gold symbol types
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GoldUnit {
-    /// Lượng (tael) - standard unit
+    /// Lượng (tael) - standard unit, 37.5 g
     Luong,
-    /// Chỉ - 1/10 of Lượng
+    /// Chỉ - 1/10 of Lượng, 3.75 g
     Chi,
+    /// Gram
+    Gram,
 }
 
 impl GoldUnit {
-    /// Parse from symbol suffix
+    /// Parse from symbol suffix (`.C` for chỉ, `.G` for grams)
     pub fn from_symbol(symbol: &str) -> Self {
-        if symbol.to_uppercase().ends_with(".C") {
+        let upper = symbol.to_uppercase();
+        if upper.ends_with(".C") {
             GoldUnit::Chi
+        } else if upper.ends_with(".G") {
+            GoldUnit::Gram
         } else {
             GoldUnit::Luong
         }
     }
 
+    /// Weight of one unit in grams
+    pub fn grams(&self) -> Decimal {
+        match self {
+            GoldUnit::Luong => GRAMS_PER_LUONG,
+            GoldUnit::Chi => GRAMS_PER_LUONG / Decimal::TEN,
+            GoldUnit::Gram => Decimal::ONE,
+        }
+    }
+
     /// Get conversion factor from Lượng
     pub fn conversion_factor(&self) -> Decimal {
+        self.grams() / GRAMS_PER_LUONG
+    }
+
+    /// Price of one unit, given the price of one lượng
+    pub fn price_from_luong(&self, luong_price: Decimal) -> Decimal {
         match self {
-            GoldUnit::Luong => Decimal::ONE,
-            GoldUnit::Chi => Decimal::new(1, 1), // 0.1
+            GoldUnit::Luong => luong_price,
+            _ => luong_price * self.grams() / GRAMS_PER_LUONG,
         }
     }
 }
 
-/// Normalize gold symbol to base symbol (VN.GOLD)
+/// Normalize gold symbol to the base symbol of its product (VN.GOLD or VN.GOLD.RING)
 pub fn normalize_gold_symbol(symbol: &str) -> String {
     let upper = symbol.to_uppercase();
-    if upper.ends_with(".C") {
-        "VN.GOLD".to_string()
-    } else if upper.contains("GOLD") {
-        "VN.GOLD".to_string()
+    if upper.ends_with(".C") || upper.contains("GOLD") {
+        GoldProduct::from_symbol(&upper).base_symbol().to_string()
     } else {
         upper
     }
@@ -142,6 +249,8 @@ mod tests {
         assert_eq!(normalize_gold_symbol("VN.GOLD"), "VN.GOLD");
         assert_eq!(normalize_gold_symbol("VN.GOLD.C"), "VN.GOLD");
         assert_eq!(normalize_gold_symbol("vn.gold"), "VN.GOLD");
+        assert_eq!(normalize_gold_symbol("VN.GOLD.G"), "VN.GOLD");
+        assert_eq!(normalize_gold_symbol("VN.GOLD.RING.C"), "VN.GOLD.RING");
     }
 
     #[test]
@@ -157,4 +266,45 @@ mod tests {
         assert_eq!(GoldUnit::Luong.conversion_factor(), rust_decimal::Decimal::ONE);
         assert_eq!(GoldUnit::Chi.conversion_factor(), rust_decimal::Decimal::new(1, 1)); // 0.1
     }
+
+    #[test]
+    fn gold_units_convert_to_grams_and_prices_from_luong() {
+        assert_eq!(GoldUnit::from_symbol("VN.GOLD.RING.C"), GoldUnit::Chi);
+        assert_eq!(GoldUnit::from_symbol("VN.GOLD.G"), GoldUnit::Gram);
+        assert_eq!(GoldUnit::Chi.grams(), Decimal::new(375, 2));
+        assert_eq!(GoldUnit::Luong.grams(), Decimal::new(375, 1));
+
+        let luong_price = Decimal::from(90_000_000);
+        assert_eq!(GoldUnit::Chi.price_from_luong(luong_price), Decimal::from(9_000_000));
+        assert_eq!(GoldUnit::Gram.price_from_luong(luong_price), Decimal::from(2_400_000));
+    }
+
+    #[test]
+    fn doji_symbols_are_quoted_by_doji_and_cached_per_luong() {
+        assert_eq!(GoldProduct::from_symbol("VN.GOLD.DOJI.C"), GoldProduct::DojiBar);
+        assert_eq!(GoldProduct::from_symbol("vn.gold.doji.ring"), GoldProduct::DojiRing);
+        assert_eq!(GoldProduct::DojiRing.dealer(), GoldDealer::Doji);
+        assert_eq!(GoldProduct::Ring.dealer(), GoldDealer::Sjc);
+        assert_eq!(normalize_gold_symbol("VN.GOLD.DOJI.RING.C"), "VN.GOLD.DOJI.RING");
+        assert!(GoldProduct::DojiRing.matches("Nhẫn Tròn 9999 Hưng Thịnh Vượng"));
+        assert!(!GoldProduct::DojiBar.matches("AVPL / DOJI HN lẻ"));
+    }
+
+    #[test]
+    fn gold_products_pick_their_own_price_row_and_value_at_the_sell_price() {
+        assert_eq!(GoldProduct::from_symbol("VN.GOLD"), GoldProduct::SjcBar);
+        assert_eq!(GoldProduct::from_symbol("vn.gold.ring"), GoldProduct::Ring);
+        assert!(GoldProduct::SjcBar.matches("Vàng miếng SJC 1L, 10L, 1KG"));
+        assert!(GoldProduct::Ring.matches("Vàng nhẫn SJC 99,99% 1 chỉ, 2 chỉ, 5 chỉ"));
+        assert!(!GoldProduct::Ring.matches("Vàng miếng SJC 1L, 10L, 1KG"));
+
+        let price = SjcGoldPrice {
+            type_name: "Vàng nhẫn SJC 99,99% 1 chỉ, 2 chỉ, 5 chỉ".to_string(),
+            branch_name: "Hồ Chí Minh".to_string(),
+            buy_value: 82_000_000.0,
+            sell_value: 84_000_000.0,
+        };
+        assert_eq!(price.close_price(), Decimal::from(84_000_000));
+        assert_eq!(ask_price(Decimal::from(82), Decimal::ZERO), Decimal::from(82));
+    }
 }
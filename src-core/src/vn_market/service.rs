@@ -16,9 +16,11 @@ use crate::vn_market::assets_repository::VnAssetsRepository;
 use crate::vn_market::cache::historical_cache::VnHistoricalCache;
 use crate::vn_market::cache::models::{CachedQuote, VnAssetType, VnHistoricalRecord};
 use crate::vn_market::cache::quote_cache::VnQuoteCache;
-use crate::vn_market::clients::{FMarketClient, SjcClient, VciClient};
+use crate::vn_market::clients::{DojiClient, FMarketClient, SjcClient, VciClient};
 use crate::vn_market::errors::VnMarketError;
-use crate::vn_market::models::gold::is_gold_symbol;
+use crate::vn_market::models::gold::{
+    ask_price, is_gold_symbol, normalize_gold_symbol, GoldDealer, GoldProduct, GoldUnit,
+};
 use crate::vn_market::models::stock::map_index_symbol;
 
 /// Round a decimal to 2 decimal places
@@ -28,6 +30,44 @@ fn round_price(price: Decimal) -> Decimal {
 
 type DbPool = Pool<ConnectionManager<SqliteConnection>>;
 
+/// Gold record in the unit of `symbol`, from the buy and sell prices of one lượng. It is
+/// valued at the sell price (see [`ask_price`]); the low carries the buy price, so the
+/// spread between the two stays visible.
+fn gold_record(
+    symbol: &str,
+    date: NaiveDate,
+    luong_buy: Decimal,
+    luong_sell: Decimal,
+) -> VnHistoricalRecord {
+    let unit = GoldUnit::from_symbol(symbol);
+    let buy = unit.price_from_luong(luong_buy);
+    let sell = unit.price_from_luong(luong_sell);
+    let close = ask_price(buy, sell);
+    let low = if buy > Decimal::ZERO { buy.min(close) } else { close };
+    VnHistoricalRecord::new(
+        symbol,
+        VnAssetType::Gold,
+        date,
+        close,
+        close,
+        low,
+        close,
+        Decimal::ZERO,
+    )
+    .with_gold_prices(buy, sell)
+}
+
+/// A cached per-lượng gold record in the unit of `symbol`. Records cached before the spread
+/// was kept only carry their close.
+fn gold_record_for(symbol: &str, cached: &VnHistoricalRecord) -> VnHistoricalRecord {
+    gold_record(
+        symbol,
+        cached.date,
+        cached.buy_price.unwrap_or(cached.close),
+        cached.sell_price.unwrap_or(cached.close),
+    )
+}
+
 /// VN Market Service providing unified access to Vietnamese market data
 pub struct VnMarketService {
     /// VCI client for stocks and indices
@@ -36,6 +76,8 @@ pub struct VnMarketService {
     fmarket_client: Arc<RwLock<FMarketClient>>,
     /// SJC client for gold prices
     sjc_client: SjcClient,
+    /// DOJI client for gold prices (today's board only)
+    doji_client: DojiClient,
     /// In-memory quote cache
     quote_cache: VnQuoteCache,
     /// SQLite-backed historical cache (optional)
@@ -67,6 +109,7 @@ impl VnMarketService {
             vci_client: VciClient::new(),
             fmarket_client: Arc::new(RwLock::new(FMarketClient::new())),
             sjc_client: SjcClient::new(),
+            doji_client: DojiClient::new(),
             quote_cache: VnQuoteCache::new(),
             historical_cache: None,
             assets_repository: None,
@@ -83,6 +126,7 @@ impl VnMarketService {
             vci_client: VciClient::new(),
             fmarket_client: Arc::new(RwLock::new(FMarketClient::new())),
             sjc_client: SjcClient::new(),
+            doji_client: DojiClient::new(),
             quote_cache: VnQuoteCache::new(),
             historical_cache: Some(VnHistoricalCache::new(pool)),
             assets_repository: Some(assets_repo),
@@ -265,16 +309,13 @@ impl VnMarketService {
         })
     }
 
-    /// Fetch gold quote from SJC - tries cache first, falls back to API
+    /// Fetch gold quote from its dealer - tries cache first, falls back to API
     async fn fetch_gold_quote(&self, symbol: &str) -> Result<CachedQuote, VnMarketError> {
-        // Determine gold unit and conversion factor
-        let gold_unit = crate::vn_market::models::gold::GoldUnit::from_symbol(symbol);
-        let conversion_factor = gold_unit.conversion_factor();
+        // Prices are cached per lượng under the product's base symbol
+        let cache_symbol = normalize_gold_symbol(symbol);
 
         // Try to get latest from historical cache first
         if let Some(ref cache) = self.historical_cache {
-            // Use normalized symbol for cache lookup (always store base Luong price)
-            let cache_symbol = crate::vn_market::models::gold::normalize_gold_symbol(symbol);
             if let Ok(Some(latest)) = cache.get_latest_record(&cache_symbol, VnAssetType::Gold) {
                 let today = Utc::now().date_naive();
                 let days_old = (today - latest.date).num_days();
@@ -282,72 +323,26 @@ impl VnMarketService {
                 // If cached data is from today or yesterday (accounting for weekends), use it
                 if days_old <= 3 {
                     debug!("Using cached gold quote from {} for {}", latest.date, symbol);
-                    // Apply conversion factor for cached data
-                    let adjusted_close = latest.close * conversion_factor;
-                    let adjusted_buy = latest.buy_price.map(|price| price * conversion_factor);
-                    let adjusted_sell = latest.sell_price.map(|price| price * conversion_factor);
-                    
-                    return Ok(CachedQuote {
-                        symbol: symbol.to_string(),
-                        asset_type: VnAssetType::Gold,
-                        date: latest.date,
-                        open: adjusted_close,
-                        high: adjusted_close,
-                        low: adjusted_close,
-                        close: adjusted_close,
-                        volume: Decimal::ZERO,
-                        nav: None,
-                        buy_price: adjusted_buy,
-                        sell_price: adjusted_sell,
-                        currency: "VND".to_string(),
-                    });
+                    return Ok(gold_record_for(symbol, &latest).into());
                 }
             }
         }
 
         // Fetch from API
-        let quote = self.sjc_client.get_latest_quote(symbol).await?;
-
-        // Apply conversion factor for Chi unit
-        let adjusted_close = quote.close * conversion_factor;
-        let adjusted_buy = quote.buy_price * conversion_factor;
-        let adjusted_sell = quote.sell_price * conversion_factor;
+        let quote = match GoldProduct::from_symbol(symbol).dealer() {
+            GoldDealer::Sjc => self.sjc_client.get_latest_quote(symbol).await?,
+            GoldDealer::Doji => self.doji_client.get_latest_quote(symbol).await?,
+        };
 
         // Store in historical cache if available (always store as Luong - base unit)
         if let Some(ref cache) = self.historical_cache {
-            // Store base Luong price with normalized cache key
-            let cache_symbol = crate::vn_market::models::gold::normalize_gold_symbol(symbol);
-let record = VnHistoricalRecord::new(
-                &cache_symbol,
-                VnAssetType::Gold,
-                quote.date,
-                quote.close, // Store base price (Luong)
-                quote.close,
-                quote.close,
-                quote.close,
-                Decimal::ZERO,
-            )
-            .with_gold_prices(quote.buy_price, quote.sell_price); // Store base prices
-
+            let record = gold_record(&cache_symbol, quote.date, quote.buy_price, quote.sell_price);
             if let Err(e) = cache.store_records(&[record]) {
                 warn!("Failed to cache gold quote: {}", e);
             }
         }
 
-        Ok(CachedQuote {
-            symbol: quote.symbol,
-            asset_type: VnAssetType::Gold,
-            date: quote.date,
-            open: adjusted_close,
-            high: adjusted_close,
-            low: adjusted_close,
-            close: adjusted_close,
-            volume: Decimal::ZERO,
-            nav: None,
-            buy_price: Some(adjusted_buy),
-            sell_price: Some(adjusted_sell),
-            currency: "VND".to_string(),
-        })
+        Ok(gold_record(symbol, quote.date, quote.buy_price, quote.sell_price).into())
     }
 
     /// Fetch stock/index history from VCI
@@ -430,8 +425,8 @@ let record = VnHistoricalRecord::new(
         start: NaiveDate,
         end: NaiveDate,
     ) -> Result<Vec<VnHistoricalRecord>, VnMarketError> {
-        // Normalize symbol to VN.GOLD for cache lookup (always store base Luong price)
-        let cache_symbol = crate::vn_market::models::gold::normalize_gold_symbol(symbol);
+        // Normalize symbol to its product's base symbol for cache lookup (always per lượng)
+        let cache_symbol = normalize_gold_symbol(symbol);
 
         // Try to get from historical cache first
         if let Some(ref cache) = self.historical_cache {
@@ -458,41 +453,8 @@ let record = VnHistoricalRecord::new(
                     cache.calculate_missing_ranges(start, end, &cached_dates);
 
                 if missing_ranges.is_empty() {
-                    // All data is cached, return it
                     debug!("All gold data is cached, no API call needed");
-                    
-                    // Apply conversion factor if original symbol is Chi unit
-                    let gold_unit = crate::vn_market::models::gold::GoldUnit::from_symbol(symbol);
-                    let conversion_factor = gold_unit.conversion_factor();
-
-                    if conversion_factor != rust_decimal::Decimal::ONE {
-                        let converted_records = cached_records
-                            .into_iter()
-                            .map(|record| {
-                                VnHistoricalRecord::new(
-                                    symbol, // Use original symbol
-                                    VnAssetType::Gold,
-                                    record.date,
-                                    record.open * conversion_factor,
-                                    record.high * conversion_factor,
-                                    record.low * conversion_factor,
-                                    record.close * conversion_factor,
-                                    record.volume,
-                                )
-                            .with_gold_prices(
-                                record.buy_price.unwrap_or_default() * conversion_factor,
-                                record.sell_price.unwrap_or_default() * conversion_factor,
-                            )
-                            })
-                            .collect();
-                        return Ok(converted_records);
-                    }
-                    
-                    return Ok(cached_records);
                 }
-
-                // Only fetch missing ranges from API
-                debug!("Missing {} date ranges, fetching from API", missing_ranges.len());
                 let mut all_records = cached_records;
 
                 for (range_start, range_end) in missing_ranges {
@@ -526,38 +488,13 @@ let record = VnHistoricalRecord::new(
                     }
                 }
 
-                // Apply conversion factor if original symbol is Chi unit
-                let gold_unit = crate::vn_market::models::gold::GoldUnit::from_symbol(symbol);
-                let conversion_factor = gold_unit.conversion_factor();
-
-                let final_records = if conversion_factor != rust_decimal::Decimal::ONE {
-                    all_records
-                        .into_iter()
-                        .map(|record| {
-                            VnHistoricalRecord::new(
-                                symbol, // Use original symbol
-                                VnAssetType::Gold,
-                                record.date,
-                                record.open * conversion_factor,
-                                record.high * conversion_factor,
-                                record.low * conversion_factor,
-                                record.close * conversion_factor,
-                                record.volume,
-                            )
-                            .with_gold_prices(
-                                record.buy_price.unwrap_or_default() * conversion_factor,
-                                record.sell_price.unwrap_or_default() * conversion_factor,
-                            )
-                        })
-                        .collect()
-                } else {
-                    all_records
-                };
-
-                // Sort by date
-                let mut sorted_records = final_records;
-                sorted_records.sort_by(|a, b| a.date.cmp(&b.date));
-                return Ok(sorted_records);
+                // Convert to the unit of the requested symbol, sorted by date
+                let mut records: Vec<VnHistoricalRecord> = all_records
+                    .iter()
+                    .map(|record| gold_record_for(symbol, record))
+                    .collect();
+                records.sort_by_key(|record| record.date);
+                return Ok(records);
             }
         }
 
@@ -574,38 +511,37 @@ let record = VnHistoricalRecord::new(
             start
         };
 
-        self.fetch_gold_from_api(symbol, effective_start, end).await
-
+        let records = self.fetch_gold_from_api(symbol, effective_start, end).await?;
+        Ok(records
+            .iter()
+            .map(|record| gold_record_for(symbol, record))
+            .collect())
     }
 
-    /// Fetch gold data directly from SJC API
+    /// Fetch gold data directly from the dealer's API, as per-lượng records under the
+    /// product's base symbol (the form they are cached in). DOJI only quotes today, so its
+    /// history is whatever earlier syncs have cached.
     async fn fetch_gold_from_api(
         &self,
         symbol: &str,
         start: NaiveDate,
         end: NaiveDate,
     ) -> Result<Vec<VnHistoricalRecord>, VnMarketError> {
-        let quotes = self.sjc_client.get_history(start, end).await?;
-        
-        // Use normalized cache symbol (always store base Luong price)
-        let cache_symbol = crate::vn_market::models::gold::normalize_gold_symbol(symbol);
+        let product = GoldProduct::from_symbol(symbol);
+        let quotes = match product.dealer() {
+            GoldDealer::Sjc => self.sjc_client.get_history(start, end, product).await?,
+            GoldDealer::Doji => {
+                let today = Utc::now().date_naive();
+                if !(start..=end).contains(&today) {
+                    return Ok(Vec::new());
+                }
+                vec![self.doji_client.get_current_price(product).await?]
+            }
+        };
 
         Ok(quotes
             .into_iter()
-            .map(|q| {
-                // Store base Luong price with normalized cache key
-                VnHistoricalRecord::new(
-                    &cache_symbol, // Store with normalized symbol
-                    VnAssetType::Gold,
-                    q.date,
-                    q.close, // Store base Luong price
-                    q.close,
-                    q.close,
-                    q.close,
-                    Decimal::ZERO,
-                )
-                .with_gold_prices(q.buy_price, q.sell_price) // Store base Luong prices
-            })
+            .map(|q| gold_record(product.base_symbol(), q.date, q.buy_price, q.sell_price))
             .collect())
     }
 
@@ -733,22 +669,41 @@ let record = VnHistoricalRecord::new(
             }
         }
 
-        // Step 3: Add gold if query matches
-        if query_lower.contains("gold") || query_lower.contains("vàng") || query_lower == "sjc" {
-            results.push(SearchResult {
-                symbol: "VN.GOLD".to_string(),
-                name: "Vàng VN (Lượng)".to_string(),
-                asset_type: VnAssetType::Gold,
-                exchange: "SJC".to_string(),
-            });
-            if results.len() < 20 {
-                results.push(SearchResult {
-                    symbol: "VN.GOLD.C".to_string(),
-                    name: "Vàng VN (Chỉ)".to_string(),
-                    asset_type: VnAssetType::Gold,
-                    exchange: "SJC".to_string(),
-                });
-            }
+        // Step 3: Add gold if query matches, for every product in every unit
+        if query_lower.contains("gold")
+            || query_lower.contains("vàng")
+            || query_lower == "sjc"
+            || query_lower == "doji"
+        {
+            const GOLD_SYMBOLS: [(&str, &str); 10] = [
+                ("VN.GOLD", "Vàng miếng SJC (Lượng)"),
+                ("VN.GOLD.C", "Vàng miếng SJC (Chỉ)"),
+                ("VN.GOLD.G", "Vàng miếng SJC (Gram)"),
+                ("VN.GOLD.RING", "Vàng nhẫn SJC (Lượng)"),
+                ("VN.GOLD.RING.C", "Vàng nhẫn SJC (Chỉ)"),
+                ("VN.GOLD.RING.G", "Vàng nhẫn SJC (Gram)"),
+                ("VN.GOLD.DOJI", "Vàng miếng SJC tại DOJI (Lượng)"),
+                ("VN.GOLD.DOJI.C", "Vàng miếng SJC tại DOJI (Chỉ)"),
+                ("VN.GOLD.DOJI.RING", "Nhẫn tròn 9999 DOJI (Lượng)"),
+                ("VN.GOLD.DOJI.RING.C", "Nhẫn tròn 9999 DOJI (Chỉ)"),
+            ];
+            let room = 20usize.saturating_sub(results.len()).max(1);
+            results.extend(
+                GOLD_SYMBOLS
+                    .iter()
+                    .filter(|(symbol, _)| match query_lower.as_str() {
+                        "sjc" => !symbol.contains("DOJI"),
+                        "doji" => symbol.contains("DOJI"),
+                        _ => true,
+                    })
+                    .take(room)
+                    .map(|(symbol, name)| SearchResult {
+                        symbol: symbol.to_string(),
+                        name: name.to_string(),
+                        asset_type: VnAssetType::Gold,
+                        exchange: GoldProduct::from_symbol(symbol).dealer().name().to_string(),
+                    }),
+            );
         }
 
         Ok(results)
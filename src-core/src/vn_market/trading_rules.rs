@@ -0,0 +1,204 @@
+//! VN Exchange Trading Rules
+//!
+//! Order rules of the HOSE, HNX and UPCoM boards that recorded trades are checked against:
+//! - Board lots of 100 shares; smaller orders (1-99 shares) trade on the odd-lot board
+//! - Daily price bands around the reference price (the previous session's close)
+//! - T+2.5 settlement: bought shares arrive in the afternoon of the second trading day
+
+use chrono::{DateTime, Datelike, Days, FixedOffset, NaiveDate, TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Shares in a board lot on every Vietnamese exchange
+pub const BOARD_LOT: Decimal = dec!(100);
+
+/// Trading days after the trade date on which bought shares settle
+pub const SETTLEMENT_DAYS: u32 = 2;
+
+/// Hour (Vietnam time) of the settlement day from which bought shares can be sold
+pub const SETTLEMENT_HOUR: u32 = 13;
+
+/// Vietnam is UTC+7 all year
+fn vietnam_offset() -> FixedOffset {
+    FixedOffset::east_opt(7 * 3600).unwrap()
+}
+
+/// Exchange listing a Vietnamese stock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VnExchange {
+    Hose,
+    Hnx,
+    Upcom,
+}
+
+impl VnExchange {
+    /// Parse the exchange stored on VN assets, accepting VCI's "HSX" for HOSE
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.trim().to_uppercase().as_str() {
+            "HOSE" | "HSX" => Some(Self::Hose),
+            "HNX" => Some(Self::Hnx),
+            "UPCOM" => Some(Self::Upcom),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Hose => "HOSE",
+            Self::Hnx => "HNX",
+            Self::Upcom => "UPCOM",
+        }
+    }
+
+    /// Largest daily move either side of the reference price, as a fraction
+    pub fn price_band(&self) -> Decimal {
+        match self {
+            Self::Hose => dec!(0.07),
+            Self::Hnx => dec!(0.10),
+            Self::Upcom => dec!(0.15),
+        }
+    }
+}
+
+/// Board an order of a given size trades on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LotKind {
+    Board,
+    Odd,
+}
+
+/// Check an order size: whole board lots, or an odd lot of fewer than 100 shares.
+/// An order mixing both is rejected, as the exchanges only accept them separately.
+pub fn check_lot(exchange: VnExchange, quantity: Decimal) -> Result<LotKind, String> {
+    if quantity <= Decimal::ZERO {
+        return Err("Quantity must be positive".to_string());
+    }
+    if quantity.fract() != Decimal::ZERO {
+        return Err(format!(
+            "{} orders are in whole shares, got {}",
+            exchange.code(),
+            quantity.normalize()
+        ));
+    }
+    if quantity < BOARD_LOT {
+        return Ok(LotKind::Odd);
+    }
+    let odd = quantity % BOARD_LOT;
+    if odd != Decimal::ZERO {
+        return Err(format!(
+            "{} orders are in board lots of {} shares; record the odd lot of {} shares \
+             separately",
+            exchange.code(),
+            BOARD_LOT,
+            odd.normalize()
+        ));
+    }
+    Ok(LotKind::Board)
+}
+
+/// Floor and ceiling prices of a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceBand {
+    pub reference: Decimal,
+    pub floor: Decimal,
+    pub ceiling: Decimal,
+}
+
+impl PriceBand {
+    pub fn new(exchange: VnExchange, reference: Decimal) -> Self {
+        let band = exchange.price_band();
+        Self {
+            reference,
+            floor: reference * (Decimal::ONE - band),
+            ceiling: reference * (Decimal::ONE + band),
+        }
+    }
+
+    pub fn contains(&self, price: Decimal) -> bool {
+        price >= self.floor && price <= self.ceiling
+    }
+}
+
+/// Check a traded price against the session's band around the reference price
+pub fn check_price(exchange: VnExchange, price: Decimal, reference: Decimal) -> Result<(), String> {
+    let band = PriceBand::new(exchange, reference);
+    if band.contains(price) {
+        return Ok(());
+    }
+    Err(format!(
+        "Price {} is outside the {} band of {} to {} around the reference price {}",
+        price.normalize(),
+        exchange.code(),
+        band.floor.round_dp(0),
+        band.ceiling.round_dp(0),
+        reference.normalize()
+    ))
+}
+
+/// Trade date of a timestamp, in Vietnam time
+pub fn trade_date(timestamp: DateTime<Utc>) -> NaiveDate {
+    timestamp.with_timezone(&vietnam_offset()).date_naive()
+}
+
+/// Date bought shares settle: the second trading day after the trade. Weekends are skipped;
+/// public holidays are not known and count as trading days.
+pub fn settlement_date(trade_date: NaiveDate) -> NaiveDate {
+    let mut date = trade_date;
+    let mut remaining = SETTLEMENT_DAYS;
+    while remaining > 0 {
+        date = date + Days::new(1);
+        if date.weekday().num_days_from_monday() < 5 {
+            remaining -= 1;
+        }
+    }
+    date
+}
+
+/// When shares bought on `trade_date` can first be sold: the afternoon of the settlement date
+pub fn available_at(trade_date: NaiveDate) -> DateTime<Utc> {
+    let settles_on = settlement_date(trade_date);
+    vietnam_offset()
+        .from_local_datetime(&settles_on.and_hms_opt(SETTLEMENT_HOUR, 0, 0).unwrap())
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lot_sizes() {
+        assert_eq!(check_lot(VnExchange::Hose, dec!(1000)), Ok(LotKind::Board));
+        assert_eq!(check_lot(VnExchange::Hnx, dec!(35)), Ok(LotKind::Odd));
+        assert!(check_lot(VnExchange::Hose, dec!(150)).is_err());
+        assert!(check_lot(VnExchange::Upcom, dec!(10.5)).is_err());
+    }
+
+    #[test]
+    fn test_price_bands() {
+        assert!(check_price(VnExchange::Hose, dec!(53500), dec!(50000)).is_ok());
+        assert!(check_price(VnExchange::Hose, dec!(53600), dec!(50000)).is_err());
+        assert!(check_price(VnExchange::Upcom, dec!(42500), dec!(50000)).is_ok());
+        assert!(check_price(VnExchange::Hnx, dec!(44900), dec!(50000)).is_err());
+    }
+
+    #[test]
+    fn test_settlement_skips_weekends() {
+        // Thursday's buy settles on Monday, available from 13:00 Vietnam time
+        let thursday = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        assert_eq!(
+            settlement_date(thursday),
+            NaiveDate::from_ymd_opt(2026, 10, 19).unwrap()
+        );
+        assert_eq!(
+            available_at(thursday),
+            Utc.with_ymd_and_hms(2026, 10, 19, 6, 0, 0).unwrap()
+        );
+        let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+        assert_eq!(
+            settlement_date(monday),
+            NaiveDate::from_ymd_opt(2026, 10, 21).unwrap()
+        );
+    }
+}
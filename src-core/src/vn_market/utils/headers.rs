@@ -49,6 +49,15 @@ pub fn sjc_headers() -> HeaderMap {
     headers
 }
 
+/// Create headers for DOJI Gold API requests
+pub fn doji_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(ACCEPT, HeaderValue::from_static("application/xml"));
+    headers.insert(REFERER, HeaderValue::from_static("https://giavang.doji.vn/"));
+    headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+    headers
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
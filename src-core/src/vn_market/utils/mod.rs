@@ -2,4 +2,4 @@
 
 pub mod headers;
 
-pub use headers::{fmarket_headers, sjc_headers, vci_headers};
+pub use headers::{doji_headers, fmarket_headers, sjc_headers, vci_headers};
@@ -3,14 +3,52 @@ use std::sync::Arc;
 use axum::{extract::{Path, State, Query, RawQuery}, routing::{get, post, put, delete}, Json, Router};
 use tower_http::{cors::{Any, CorsLayer}, trace::TraceLayer, timeout::TimeoutLayer, request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer}};
 use utoipa::OpenApi;
-use crate::{error::ApiResult, models::{Account, NewAccount, AccountUpdate}, config::Config, main_lib::AppState};
+use crate::{error::ApiResult, models::{Account, NewAccount, AccountUpdate}, config::Config, graphql, main_lib::AppState, websocket};
+use crate::events::{ResourceEventPayload, ServerEvent, MARKET_SYNC_COMPLETE, MARKET_SYNC_ERROR, MARKET_SYNC_START, TRANSFER_PROGRESS};
 use wealthvn_core::addons::{self, *};
 use axum::http::StatusCode;
+use axum::extract::ws::WebSocketUpgrade;
+use axum::{extract::Request, http::header::AUTHORIZATION, middleware::{self, Next}, response::Response, Extension};
 use wealthvn_core::{
-    accounts::AccountServiceTrait,
-    settings::{Settings, SettingsUpdate, SettingsServiceTrait},
+    accounts::{AccountGroup, AccountGroupValuation, AccountServiceTrait, NewAccountGroup},
+    cash::{AccountCash, CashMovement},
+    settings::{SettingChange, SettingKey, Settings, SettingsExport, SettingsImportResult, SettingsUpdate, SettingsServiceTrait},
     portfolio::{holdings::holdings_model::Holding, valuation::valuation_model::DailyAccountValuation, performance::PerformanceMetrics, income::IncomeSummary},
-    goals::goals_model::{Goal, NewGoal, GoalsAllocation},
+    goals::{goals_model::{Goal, NewGoal, GoalsAllocation, AllocationVersion, AllocationBulkInsertSummary, AllocationChange, AllocationChangePreview, AllocationRebalance, DepositDistribution, GoalFundingRequirement, GoalPlanExport, GoalPlanImportResult}, get_dashboard_summary, get_goal_progress as read_goal_progress, get_goal_progress_series, get_monthly_summaries, DashboardSummary, GoalProgressSnapshot, ProgressInterval, MonthlySummaries, EmergencyFundProgress, DebtPayoffProgress, NetWorthGoalProgress, NetWorthPoint, SinkingFundProgress, DEFAULT_SUMMARY_GOALS},
+    budgets::{ActivityCategory, BudgetCategory, BudgetMonthAmount, BudgetMonthAmountUpdate, BudgetMonthProgress, BudgetReport, NewBudgetCategory},
+    categorization::{CategorizationResult, CategorizationRule, NewCategorizationRule},
+    forecast::{parse_forecast_date, CashFlowForecast, CashFlowForecastRequest, NewPlannedCashFlow, PlannedCashFlow},
+    income_sources::{IncomeSource, IncomeVariance, NewIncomeSource, SavingsRate},
+    bills::{Bill, BillMatchResult, BillPayment, BillReminder, NewBill, NewBillPayment},
+    envelopes::{AccountEnvelopes, Envelope, EnvelopeTransfer, NewEnvelope, NewEnvelopeTransfer},
+    loans::{Loan, LoanPrepayment, LoanRateReset, LoanSchedule, LoanStressTest, NewLoan, NewLoanPrepayment, NewLoanRateReset},
+    real_estate::{NewProperty, NewPropertyAppraisal, Property, PropertyAppraisal, PropertySummary},
+    bonds::{Bond, BondPayment, BondSummary, NewBond},
+    term_deposits::{NewTermDeposit, TermDeposit, TermDepositMaturity, TermDepositSummary},
+    recurring_rules::{NewRecurringRule, RecurringRule},
+    reports::{NetWorthHistoryEntry, NetWorthStatement},
+    dividends::{ActivityDividend, DividendSummary, NewActivityDividend},
+    esop::{EsopGrant, EsopGrantSummary, NewEsopGrant, UpcomingVesting, VESTING_REMINDER_DAYS},
+    sip_plans::{NewSipPlan, SipPlan, SipPlanSummary},
+    benchmarks::{Benchmark, BenchmarkComparison, NewBenchmark},
+    private_loans::{NewPrivateLoan, NewPrivateLoanRepayment, PrivateLoan, PrivateLoanMatchResult, PrivateLoanRepayment, PrivateLoanSummary},
+    bonus_plans::{BonusPlan, BonusPlanRequest, NewBonusPlan},
+    deposit_ladders::{ConfirmedDepositLadder, DepositLadder, DepositLadderRequest},
+    deposit_rates::{DepositRate, RolloverSuggestion},
+    education::{EducationPlan, EducationProjection, NewEducationPlan},
+    pension::{estimate_pension, PensionEstimate, PensionRequest},
+    scripting::{NewScript, Script},
+    automations::{ActivityTag, AutomationRule, NewAutomationRule},
+    connectors::{BankConnection, ConnectorSyncResult, NewBankConnection},
+    sheets::{GoogleCredentials, NewSheetExport, SheetExport, SheetExportResult},
+    import_payload::{ImportPayload, ImportPayloadPreview, ImportPayloadResult},
+    importers::ActivityImportRequest,
+    ledger::{LedgerExport, LedgerFormat},
+    data_transfer::{export_file_name, ExportDataset, ExportFileFormat, TransferProgress},
+    operations::{CancellationToken, OperationGuard, OperationInfo, OPERATION_EXPORT, OPERATION_LEDGER_EXPORT, OPERATION_MARKET_DATA_RESYNC, OPERATION_QUOTE_IMPORT},
+    telemetry::{performance_metrics, PerformanceStats, ServiceReadiness},
+    api_tokens::{ApiToken, ApiTokenScope, ApiTokenServiceTrait, IssuedApiToken, NewApiToken},
+    i18n::{message_catalog, MessageLanguage},
     activities::{
         ActivityBulkMutationRequest,
         ActivityBulkMutationResult,
@@ -19,218 +57,1746 @@ use wealthvn_core::{
         ActivityUpdate,
         ImportMappingData,
         NewActivity,
+        PendingSettlement,
     },
     fx::fx_model::{ExchangeRate, NewExchangeRate},
     limits::{ContributionLimit, NewContributionLimit, DepositsCalculation},
-    market_data::{MarketDataProviderSetting, MarketDataProviderInfo, Quote},
+    market_data::{MarketDataProviderSetting, MarketDataProviderInfo, ProviderCredentialStatus, ProviderCredentialTestResult, ImportValidationStatus, Quote, QuoteImport},
     assets::{Asset as CoreAsset, UpdateAssetProfile},
     secrets::SecretManager,
+    privacy::{index_valuation_history, mask_if},
+    query_cache::{RESOURCE_ACCOUNT, RESOURCE_ACTIVITY, RESOURCE_ALLOCATION, RESOURCE_GOAL, RESOURCE_PORTFOLIO},
+    feature_flags::{FeatureFlag, FeatureFlagState},
+    onboarding::{OnboardingPlan, OnboardingResult},
+    audit::{AuditAction, AuditLogQuery, AuditLogResponse, NewAuditLogEntry, AUDIT_ACTOR_IMPORT, AUDIT_ACTOR_USER},
 };
 
+/// Records an audit entry after a successful mutation and tells WebSocket clients about it;
+/// failures are logged, not returned
+async fn record_audit(state: &AppState, entry: NewAuditLogEntry) {
+    crate::main_lib::publish_resource_changed(state, ResourceEventPayload::from(&entry));
+    let description = format!("{} {} {}", entry.action.as_str(), entry.entity_type, entry.entity_id);
+    if let Err(e) = state.audit_service.record(entry).await {
+        tracing::warn!("Failed to record audit entry for {}: {}", description, e);
+    }
+}
+
 #[utoipa::path(get, path = "/api/v1/healthz", responses((status = 200, description = "Health")))]
 pub async fn healthz() -> &'static str { "ok" }
 
 #[utoipa::path(get, path = "/api/v1/readyz", responses((status = 200, description = "Ready")))]
 pub async fn readyz() -> &'static str { "ok" }
 
-#[utoipa::path(get, path="/api/v1/accounts", responses((status=200, body = [Account])))]
-async fn list_accounts(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<Account>>> {
-    let accounts = state.account_service.get_all_accounts()?;
-    Ok(Json(accounts.into_iter().map(Account::from).collect()))
+#[utoipa::path(get, path="/api/v1/accounts", responses((status=200, body = [Account])))]
+async fn list_accounts(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<Account>>> {
+    let accounts = state.account_service.get_all_accounts()?;
+    Ok(Json(accounts.into_iter().map(Account::from).collect()))
+}
+
+#[utoipa::path(post, path="/api/v1/accounts", request_body = NewAccount, responses((status=200, body = Account)))]
+async fn create_account(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<NewAccount>,
+) -> ApiResult<Json<Account>> {
+    let core_new = payload.into();
+    let created = state.account_service.create_account(core_new).await?;
+    record_audit(&state, NewAuditLogEntry::new("account", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&wealthvn_core::accounts::Account>, Some(&created))).await;
+    Ok(Json(Account::from(created)))
+}
+
+#[utoipa::path(put, path="/api/v1/accounts/{id}", request_body = AccountUpdate, responses((status=200, body=Account)))]
+async fn update_account(
+    Path(id): Path<String>,
+    State(state): State<Arc<AppState>>,
+    Json(mut payload): Json<AccountUpdate>,
+) -> ApiResult<Json<Account>> {
+    let previous = state.account_service.get_account(&id).ok();
+    payload.id = Some(id);
+    let updated = state.account_service.update_account(payload.into()).await?;
+    record_audit(&state, NewAuditLogEntry::new("account", &updated.id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(Account::from(updated)))
+}
+
+#[utoipa::path(delete, path="/api/v1/accounts/{id}", responses((status=204)))]
+async fn delete_account(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<()> {
+    let previous = state.account_service.get_account(&id).ok();
+    state.account_service.delete_account(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("account", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&wealthvn_core::accounts::Account>)).await;
+    Ok(())
+}
+
+async fn list_account_groups(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<AccountGroup>>> {
+    Ok(Json(state.account_group_service.get_account_groups()?))
+}
+
+async fn create_account_group(State(state): State<Arc<AppState>>, Json(group): Json<NewAccountGroup>) -> ApiResult<Json<AccountGroup>> {
+    Ok(Json(state.account_group_service.create_account_group(group).await?))
+}
+
+async fn update_account_group(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(group): Json<NewAccountGroup>) -> ApiResult<Json<AccountGroup>> {
+    Ok(Json(state.account_group_service.update_account_group(&id, group).await?))
+}
+
+async fn delete_account_group(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<()> {
+    state.account_group_service.delete_account_group(&id).await?;
+    Ok(())
+}
+
+/// Latest value of a group and the groups inside it, in the base currency
+async fn get_account_group_valuation(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<AccountGroupValuation>> {
+    let valuation = state.account_group_service.get_group_valuation(&id)?;
+    Ok(Json(mask_if(valuation, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+/// Unallocated balance summed over every account of a group, at their latest values
+async fn get_account_group_unallocated_balance(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<serde_json::Value>> {
+    use rust_decimal::prelude::ToPrimitive;
+    let valuation = state.account_group_service.get_group_valuation(&id)?;
+    let account_values: std::collections::HashMap<String, f64> = valuation.account_values.into_iter().map(|(account_id, value)| (account_id, value.to_f64().unwrap_or_default())).collect();
+    let unallocated = state.goal_service.get_unallocated_balance_for_accounts(&account_values).await?;
+    let unallocated = if state.settings_service.is_privacy_mode_enabled()? { 0.0 } else { unallocated };
+    Ok(Json(serde_json::json!({ "unallocated_balance": unallocated })))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupAllocationBody { goal_id: String, percentage: f64 }
+
+/// Funds a goal with the same percentage of every account in a group
+async fn allocate_goal_to_account_group(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(body): Json<GroupAllocationBody>) -> ApiResult<Json<AllocationBulkInsertSummary>> {
+    let account_ids = state.account_group_service.get_group_account_ids(&id)?;
+    Ok(Json(state.goal_service.allocate_goal_to_accounts(&body.goal_id, &account_ids, body.percentage).await?))
+}
+
+/// Cash of an account in each currency, as of its latest holdings snapshot
+async fn get_account_cash(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<AccountCash>> {
+    let cash = state.cash_service.get_account_cash(&id)?;
+    Ok(Json(mask_if(cash, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+async fn deposit_cash(State(state): State<Arc<AppState>>, Json(movement): Json<CashMovement>) -> ApiResult<Json<wealthvn_core::activities::Activity>> {
+    let created = state.cash_service.deposit_cash(movement).await?;
+    record_audit(&state, NewAuditLogEntry::new("activity", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&wealthvn_core::activities::Activity>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn withdraw_cash(State(state): State<Arc<AppState>>, Json(movement): Json<CashMovement>) -> ApiResult<Json<wealthvn_core::activities::Activity>> {
+    let created = state.cash_service.withdraw_cash(movement).await?;
+    record_audit(&state, NewAuditLogEntry::new("activity", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&wealthvn_core::activities::Activity>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+#[derive(serde::Deserialize)]
+struct UnallocatedBalanceQuery { #[serde(rename = "currentAccountValue")] current_account_value: Option<f64> }
+
+/// Unallocated balance of an account at the value given, or at its latest stored valuation
+async fn get_unallocated_balance(Path(id): Path<String>, State(state): State<Arc<AppState>>, Query(q): Query<UnallocatedBalanceQuery>) -> ApiResult<Json<serde_json::Value>> {
+    let unallocated = match q.current_account_value {
+        Some(value) => state.goal_service.get_unallocated_balance(&id, value).await?,
+        None => state.goal_service.get_unallocated_value(&id).await?,
+    };
+    let masked = q.current_account_value.is_none() && state.settings_service.is_privacy_mode_enabled()?;
+    let unallocated = if masked { 0.0 } else { unallocated };
+    Ok(Json(serde_json::json!({ "unallocated_balance": unallocated })))
+}
+
+/// Unallocated part of the cash an account actually holds
+async fn get_unallocated_cash(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<serde_json::Value>> {
+    let unallocated = state.goal_service.get_unallocated_cash(&id).await?;
+    let unallocated = if state.settings_service.is_privacy_mode_enabled()? { 0.0 } else { unallocated };
+    Ok(Json(serde_json::json!({ "unallocated_balance": unallocated })))
+}
+
+// Settings endpoints (web adapter relies on these)
+async fn get_settings(State(state): State<Arc<AppState>>) -> ApiResult<Json<Settings>> {
+    let s = state.settings_service.get_settings()?;
+    Ok(Json(s))
+}
+
+async fn update_settings(State(state): State<Arc<AppState>>, Json(payload): Json<SettingsUpdate>) -> ApiResult<Json<Settings>> {
+    state.settings_service.update_settings(&payload).await?;
+    let s = state.settings_service.get_settings()?;
+    Ok(Json(s))
+}
+
+async fn get_setting(Path(key): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<String>> {
+    let key: SettingKey = key.parse().map_err(|e: String| wealthvn_core::Error::Validation(wealthvn_core::errors::ValidationError::InvalidInput(e)))?;
+    let value = state.settings_service.get_setting_value(key)?;
+    Ok(Json(value))
+}
+
+#[derive(serde::Deserialize)]
+struct SettingValueBody { value: String }
+
+async fn set_setting(Path(key): Path<String>, State(state): State<Arc<AppState>>, Json(body): Json<SettingValueBody>) -> ApiResult<Json<SettingChange>> {
+    let key: SettingKey = key.parse().map_err(|e: String| wealthvn_core::Error::Validation(wealthvn_core::errors::ValidationError::InvalidInput(e)))?;
+    let change = state.settings_service.set_setting_value(key, &body.value).await?;
+    if key == SettingKey::BaseCurrency {
+        *state.base_currency.write().unwrap() = change.new_value.clone();
+    }
+    Ok(Json(change))
+}
+
+async fn export_settings(State(state): State<Arc<AppState>>) -> ApiResult<Json<SettingsExport>> {
+    Ok(Json(state.settings_export_service.export_settings().await?))
+}
+
+async fn import_settings(State(state): State<Arc<AppState>>, Json(export): Json<SettingsExport>) -> ApiResult<Json<SettingsImportResult>> {
+    let result = state.settings_export_service.import_settings(export).await?;
+    if let Some(base) = state.settings_service.get_base_currency()? {
+        *state.base_currency.write().unwrap() = base;
+    }
+    Ok(Json(result))
+}
+
+// Message catalog so clients can translate coded errors themselves
+#[derive(serde::Deserialize)]
+struct MessageCatalogQuery { language: Option<String> }
+
+async fn get_message_catalog(State(state): State<Arc<AppState>>, Query(q): Query<MessageCatalogQuery>) -> ApiResult<Json<std::collections::BTreeMap<String, String>>> {
+    let language = match q.language {
+        Some(language) => language,
+        None => state.settings_service.get_settings()?.language,
+    };
+    Ok(Json(message_catalog(MessageLanguage::from_tag(&language))))
+}
+
+// Holdings endpoint
+#[derive(serde::Deserialize)]
+struct HoldingsQuery { #[serde(rename = "accountId")] account_id: String }
+
+/// Holdings change with accounts, activities and every recalculation (quotes, FX, settings)
+const HOLDINGS_DEPEND_ON: &[&str] = &[RESOURCE_ACCOUNT, RESOURCE_ACTIVITY, RESOURCE_PORTFOLIO];
+
+async fn get_holdings(State(state): State<Arc<AppState>>, Query(q): Query<HoldingsQuery>) -> ApiResult<Json<Vec<Holding>>> {
+    let base = state.base_currency.read().unwrap().clone();
+    let holdings = state.query_cache.get_or_load("get_holdings", &(&q.account_id, &base), HOLDINGS_DEPEND_ON,
+        || state.holdings_service.get_holdings(&q.account_id, &base)).await?;
+    Ok(Json(mask_if(holdings, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+// Historical valuations endpoint
+#[derive(serde::Deserialize)]
+struct HistoryQuery { #[serde(rename = "accountId")] account_id: String, #[serde(rename = "startDate")] start_date: Option<String>, #[serde(rename = "endDate")] end_date: Option<String> }
+
+async fn get_historical_valuations(State(state): State<Arc<AppState>>, Query(q): Query<HistoryQuery>) -> ApiResult<Json<Vec<DailyAccountValuation>>> {
+    let start = match q.start_date {
+        Some(s) => Some(chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid startDate: {}", e))?),
+        None => None,
+    };
+    let end = match q.end_date {
+        Some(s) => Some(chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid endDate: {}", e))?),
+        None => None,
+    };
+    let mut vals = state.valuation_service.get_historical_valuations(&q.account_id, start, end)?;
+    if state.settings_service.is_privacy_mode_enabled()? {
+        index_valuation_history(&mut vals);
+    }
+    Ok(Json(vals))
+}
+
+/// Daily value history of one account, computed first when the account was never valued
+async fn get_account_valuation_history(State(state): State<Arc<AppState>>, Query(q): Query<HistoryQuery>) -> ApiResult<Json<Vec<DailyAccountValuation>>> {
+    if state.valuation_service.get_latest_valuations(std::slice::from_ref(&q.account_id))?.is_empty() {
+        state.valuation_service.calculate_valuation_history(&q.account_id, false).await?;
+    }
+    get_historical_valuations(State(state), Query(q)).await
+}
+
+// Latest valuations endpoint
+async fn get_latest_valuations(State(state): State<Arc<AppState>>, raw: RawQuery) -> ApiResult<Json<Vec<DailyAccountValuation>>> {
+    // Parse query manually for robustness (supports accountIds and accountIds[])
+    let mut ids: Vec<String> = Vec::new();
+    if let Some(qs) = raw.0 {
+        // Collect all values for both keys
+        if let Ok(pairs) = serde_urlencoded::from_str::<Vec<(String, String)>>(&qs) {
+            for (k, v) in pairs {
+                if k == "accountIds" || k == "accountIds[]" {
+                    ids.push(v);
+                }
+            }
+        }
+    }
+    if ids.is_empty() {
+        ids = state
+            .account_service
+            .get_active_accounts()?
+            .into_iter()
+            .map(|a| a.id)
+            .collect();
+    }
+    if ids.is_empty() { return Ok(Json(vec![])); }
+    let vals = state.valuation_service.get_latest_valuations(&ids)?;
+    Ok(Json(mask_if(vals, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+// Portfolio update endpoints for web
+async fn update_portfolio(State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    crate::main_lib::update_portfolio(&state).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn recalculate_portfolio(State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    crate::main_lib::recompute_valuations(&state, None).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct RecomputeBody { #[serde(rename = "accountIds", default)] account_ids: Vec<String> }
+
+/// Rebuilds the valuations of the accounts named, or of every active account when none are
+async fn recompute_valuations(State(state): State<Arc<AppState>>, Json(body): Json<RecomputeBody>) -> ApiResult<StatusCode> {
+    let account_ids = (!body.account_ids.is_empty()).then_some(body.account_ids.as_slice());
+    crate::main_lib::recompute_valuations(&state, account_ids).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Performance endpoints
+#[derive(serde::Deserialize)]
+struct PerfBody {
+    #[serde(rename = "itemType")] item_type: String,
+    #[serde(rename = "itemId")] item_id: String,
+    #[serde(rename = "startDate")] start_date: Option<String>,
+    #[serde(rename = "endDate")] end_date: Option<String>,
+}
+
+async fn calculate_performance_history(State(state): State<Arc<AppState>>, Json(body): Json<PerfBody>) -> ApiResult<Json<PerformanceMetrics>> {
+    let start = match &body.start_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid startDate: {}", e))?), None => None };
+    let end = match &body.end_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid endDate: {}", e))?), None => None };
+    let metrics = state.performance_service.calculate_performance_history(&body.item_type, &body.item_id, start, end).await?;
+    Ok(Json(mask_if(metrics, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+async fn calculate_performance_summary(State(state): State<Arc<AppState>>, Json(body): Json<PerfBody>) -> ApiResult<Json<PerformanceMetrics>> {
+    let start = match &body.start_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid startDate: {}", e))?), None => None };
+    let end = match &body.end_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid endDate: {}", e))?), None => None };
+    let metrics = state.performance_service.calculate_performance_summary(&body.item_type, &body.item_id, start, end).await?;
+    Ok(Json(mask_if(metrics, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+// Income
+async fn get_income_summary(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<IncomeSummary>>> {
+    let items = state.income_service.get_income_summary()?;
+    Ok(Json(mask_if(items, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+// Goals endpoints
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoalsQuery { include_archived: Option<bool> }
+
+async fn get_goals(State(state): State<Arc<AppState>>, Query(q): Query<GoalsQuery>) -> ApiResult<Json<Vec<Goal>>> {
+    let goals = if q.include_archived.unwrap_or(false) { state.goal_service.get_all_goals().await? } else { state.goal_service.get_goals().await? };
+    Ok(Json(goals))
+}
+
+async fn create_goal(State(state): State<Arc<AppState>>, Json(goal): Json<NewGoal>) -> ApiResult<Json<Goal>> {
+    let g = state.goal_service.create_goal(goal).await?;
+    record_audit(&state, NewAuditLogEntry::new("goal", &g.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Goal>, Some(&g))).await;
+    Ok(Json(g))
+}
+
+async fn update_goal(State(state): State<Arc<AppState>>, Json(goal): Json<Goal>) -> ApiResult<Json<Goal>> {
+    let previous = state.goal_service.get_all_goals().await?.into_iter().find(|p| p.id == goal.id);
+    let g = state.goal_service.update_goal(goal).await?;
+    record_audit(&state, NewAuditLogEntry::new("goal", &g.id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&g))).await;
+    Ok(Json(g))
+}
+
+async fn delete_goal(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<()> {
+    let previous = state.goal_service.get_all_goals().await?.into_iter().find(|p| p.id == id);
+    let _ = state.goal_service.delete_goal(id.clone()).await?;
+    record_audit(&state, NewAuditLogEntry::new("goal", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&Goal>)).await;
+    Ok(())
+}
+
+/// Hides a goal from lists and progress; its allocations and history stay for a restore
+async fn archive_goal(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<Goal>> {
+    let previous = state.goal_service.get_all_goals().await?.into_iter().find(|p| p.id == id);
+    let g = state.goal_service.archive_goal(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("goal", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&g))).await;
+    Ok(Json(g))
+}
+
+async fn restore_goal(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<Goal>> {
+    let previous = state.goal_service.get_all_goals().await?.into_iter().find(|p| p.id == id);
+    let g = state.goal_service.restore_goal(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("goal", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&g))).await;
+    Ok(Json(g))
+}
+
+async fn load_goals_allocations(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<GoalsAllocation>>> {
+    let allocs = state.query_cache.get_or_load("load_goals_allocations", &(), &[RESOURCE_GOAL, RESOURCE_ALLOCATION],
+        || state.goal_service.load_goals_allocations()).await?;
+    Ok(Json(allocs))
+}
+
+async fn get_emergency_fund_progress(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<EmergencyFundProgress>>> {
+    Ok(Json(state.emergency_fund_service.get_emergency_fund_progress().await?))
+}
+
+async fn get_sinking_fund_progress(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<SinkingFundProgress>>> {
+    Ok(Json(state.sinking_fund_service.get_sinking_fund_progress().await?))
+}
+
+async fn get_debt_payoff_progress(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<DebtPayoffProgress>>> {
+    let progress = state.debt_payoff_service.get_debt_payoff_progress().await?;
+    Ok(Json(mask_if(progress, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+async fn get_net_worth_goal_progress(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<NetWorthGoalProgress>>> {
+    let progress = state.net_worth_goal_service.get_net_worth_goal_progress().await?;
+    Ok(Json(mask_if(progress, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+#[derive(serde::Deserialize)]
+struct NetWorthHistoryQuery { #[serde(rename = "startDate")] start_date: Option<String>, #[serde(rename = "endDate")] end_date: Option<String> }
+
+async fn get_net_worth_history(State(state): State<Arc<AppState>>, Query(q): Query<NetWorthHistoryQuery>) -> ApiResult<Json<Vec<NetWorthPoint>>> {
+    let start = q.start_date.as_deref().map(parse_forecast_date).transpose()?;
+    let end = q.end_date.as_deref().map(parse_forecast_date).transpose()?;
+    let series = state.net_worth_goal_service.get_net_worth_series(start, end)?;
+    Ok(Json(mask_if(series, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+#[derive(serde::Deserialize)]
+struct MonthlySummariesQuery { #[serde(rename = "startDate")] start_date: Option<String> }
+
+async fn get_monthly_summaries_handler(State(state): State<Arc<AppState>>, Query(q): Query<MonthlySummariesQuery>) -> ApiResult<Json<MonthlySummaries>> {
+    let start = q.start_date.as_deref().map(parse_forecast_date).transpose()?;
+    let summaries = get_monthly_summaries(state.valuation_service.as_ref(), state.goal_service.as_ref(), state.net_worth_goal_service.as_ref(), start).await?;
+    Ok(Json(mask_if(summaries, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+#[derive(serde::Deserialize)]
+struct GoalProgressQuery { date: Option<String> }
+
+/// Progress of one goal as of `date` (today by default), read from the precomputed history
+async fn get_goal_progress(State(state): State<Arc<AppState>>, Path(id): Path<String>, Query(q): Query<GoalProgressQuery>) -> ApiResult<Json<GoalProgressSnapshot>> {
+    let date = q.date.as_deref().map(parse_forecast_date).transpose()?;
+    let progress = read_goal_progress(state.goal_service.as_ref(), state.net_worth_goal_service.as_ref(), &id, date).await?
+        .ok_or(crate::error::ApiError::NotFound)?;
+    Ok(Json(mask_if(progress, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoalProgressHistoryQuery { from_date: String, to_date: Option<String>, interval: Option<ProgressInterval> }
+
+/// Progress of one goal at daily, weekly or monthly points, for charting
+async fn get_goal_progress_history(State(state): State<Arc<AppState>>, Path(id): Path<String>, Query(q): Query<GoalProgressHistoryQuery>) -> ApiResult<Json<Vec<GoalProgressSnapshot>>> {
+    let from = parse_forecast_date(&q.from_date)?;
+    let to = q.to_date.as_deref().map(parse_forecast_date).transpose()?;
+    let series = get_goal_progress_series(state.goal_service.as_ref(), state.net_worth_goal_service.as_ref(), &id, from, to, q.interval.unwrap_or_default()).await?;
+    let privacy_mode = state.settings_service.is_privacy_mode_enabled()?;
+    Ok(Json(series.into_iter().map(|snapshot| mask_if(snapshot, privacy_mode)).collect()))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RebalanceQuery { account_id: String }
+
+/// Allocation percentages for an account that pace each goal to its due date; nothing is saved
+async fn suggest_goal_rebalance(State(state): State<Arc<AppState>>, Query(q): Query<RebalanceQuery>) -> ApiResult<Json<AllocationRebalance>> {
+    let rebalance = state.goal_service.suggest_allocation_rebalance(&q.account_id, chrono::Utc::now().date_naive()).await?;
+    let privacy_mode = state.settings_service.is_privacy_mode_enabled()?;
+    Ok(Json(mask_if(rebalance, privacy_mode)))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DistributeQuery { account_id: String, amount: f64 }
+
+/// How a new deposit into an account should be split across its goals; nothing is saved
+async fn distribute_goal_deposit(State(state): State<Arc<AppState>>, Query(q): Query<DistributeQuery>) -> ApiResult<Json<DepositDistribution>> {
+    let distribution = state.goal_service.distribute_unallocated(&q.account_id, q.amount, chrono::Utc::now().date_naive()).await?;
+    let privacy_mode = state.settings_service.is_privacy_mode_enabled()?;
+    Ok(Json(mask_if(distribution, privacy_mode)))
+}
+
+/// What changing an allocation would do, with why it could not be saved; nothing is saved
+async fn preview_allocation_change(State(state): State<Arc<AppState>>, Path(id): Path<String>, Json(change): Json<AllocationChange>) -> ApiResult<Json<AllocationChangePreview>> {
+    let preview = state.goal_service.preview_allocation_change(&id, change, chrono::Utc::now().date_naive()).await?;
+    let privacy_mode = state.settings_service.is_privacy_mode_enabled()?;
+    Ok(Json(mask_if(preview, privacy_mode)))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FundingRequirementQuery { expected_return_rate: Option<f64>, inflation_rate: Option<f64> }
+
+/// Monthly contribution a goal needs by its due date, nominal and adjusted for inflation
+async fn get_goal_funding_requirement(State(state): State<Arc<AppState>>, Path(id): Path<String>, Query(q): Query<FundingRequirementQuery>) -> ApiResult<Json<GoalFundingRequirement>> {
+    let requirement = state.goal_service.get_funding_requirement(&id, q.expected_return_rate, q.inflation_rate, chrono::Utc::now().date_naive()).await?;
+    let privacy_mode = state.settings_service.is_privacy_mode_enabled()?;
+    Ok(Json(mask_if(requirement, privacy_mode)))
+}
+
+// ===================== Dashboard (read-only, token auth) =====================
+
+/// Compares without stopping at the first differing byte, so response timing doesn't leak the token
+fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected.bytes().zip(given.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// What a token-protected route accepts: `WF_API_TOKEN` may do anything, issued tokens need `scope`
+#[derive(Clone)]
+struct TokenGuard { static_token: Option<Arc<str>>, tokens: Arc<dyn ApiTokenServiceTrait + Send + Sync>, scope: ApiTokenScope }
+
+async fn require_api_token(State(guard): State<TokenGuard>, request: Request, next: Next) -> ApiResult<Response> {
+    let given = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .ok_or(crate::error::ApiError::Unauthorized)?;
+    if guard.static_token.as_deref().is_some_and(|token| tokens_match(token, given)) {
+        return Ok(next.run(request).await);
+    }
+    match guard.tokens.authenticate(given).await? {
+        Some(token) if token.allows(guard.scope) => Ok(next.run(request).await),
+        Some(_) => Err(crate::error::ApiError::Forbidden),
+        None => Err(crate::error::ApiError::Unauthorized),
+    }
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DashboardGoalProgress {
+    emergency_funds: Vec<EmergencyFundProgress>,
+    sinking_funds: Vec<SinkingFundProgress>,
+    net_worth_goals: Vec<NetWorthGoalProgress>,
+}
+
+async fn get_dashboard_goal_progress(State(state): State<Arc<AppState>>) -> ApiResult<Json<DashboardGoalProgress>> {
+    let privacy_mode = state.settings_service.is_privacy_mode_enabled()?;
+    Ok(Json(DashboardGoalProgress {
+        emergency_funds: mask_if(state.emergency_fund_service.get_emergency_fund_progress().await?, privacy_mode),
+        sinking_funds: mask_if(state.sinking_fund_service.get_sinking_fund_progress().await?, privacy_mode),
+        net_worth_goals: mask_if(state.net_worth_goal_service.get_net_worth_goal_progress().await?, privacy_mode),
+    }))
+}
+
+#[derive(serde::Deserialize)]
+struct DashboardSummaryQuery { goals: Option<usize> }
+
+async fn get_dashboard_summary_handler(State(state): State<Arc<AppState>>, Query(q): Query<DashboardSummaryQuery>) -> ApiResult<Json<DashboardSummary>> {
+    let base_currency = state.base_currency.read().unwrap().clone();
+    let summary = get_dashboard_summary(
+        state.net_worth_goal_service.as_ref(),
+        state.emergency_fund_service.as_ref(),
+        state.sinking_fund_service.as_ref(),
+        &base_currency,
+        q.goals.unwrap_or(DEFAULT_SUMMARY_GOALS),
+    )
+    .await?;
+    Ok(Json(mask_if(summary, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+/// WebSocket carrying the same events as the desktop app, so dashboards update without polling
+async fn dashboard_events(State(state): State<Arc<AppState>>, ws: WebSocketUpgrade) -> Response {
+    websocket::upgrade(ws, state.events.subscribe())
+}
+
+/// Routes for scripts, home dashboards and other apps, behind `WF_API_TOKEN` or an issued token
+fn token_router(state: &AppState, static_token: Option<&str>) -> Router<Arc<AppState>> {
+    let guard = |scope| {
+        middleware::from_fn_with_state(
+            TokenGuard { static_token: static_token.map(Arc::<str>::from), tokens: state.api_token_service.clone(), scope },
+            require_api_token,
+        )
+    };
+    let dashboard = Router::new()
+        .route("/goals", get(get_goals))
+        .route("/goals/progress", get(get_dashboard_goal_progress))
+        .route("/summary", get(get_dashboard_summary_handler))
+        .route("/summary/monthly", get(get_monthly_summaries_handler))
+        .route("/holdings", get(get_holdings))
+        .route("/net-worth", get(get_net_worth_history))
+        .route("/graphql", post(graphql::graphql_handler))
+        .route("/events", get(dashboard_events))
+        .route_layer(guard(ApiTokenScope::ReadOnly));
+    let reports = Router::new()
+        .route("/exports/ledger", get(export_ledger))
+        .route("/exports/ledger/download", get(download_ledger))
+        .route("/exports/data", get(export_data))
+        .route_layer(guard(ApiTokenScope::Reports));
+    let integrations = Router::new()
+        .route("/import-payloads/preview", post(preview_import_payload))
+        .route("/import-payloads", post(import_payload))
+        .route("/import-payloads/einvoice/preview", post(preview_einvoice_import))
+        .route("/import-payloads/einvoice", post(import_einvoice))
+        .route("/activity-imports/preview", post(preview_activity_import))
+        .route("/activity-imports", post(commit_activity_import))
+        .route_layer(guard(ApiTokenScope::Import));
+    Router::new()
+        .nest("/dashboard", dashboard.merge(reports))
+        .nest("/integrations", integrations)
+}
+
+async fn update_goal_allocations(State(state): State<Arc<AppState>>, Json(allocs): Json<Vec<GoalsAllocation>>) -> ApiResult<()> {
+    let previous = state.goal_service.load_goals_allocations().await?;
+    let _ = state.goal_service.upsert_goal_allocations(allocs.clone()).await?;
+    for alloc in &allocs {
+        let before = previous.iter().find(|p| p.id == alloc.id);
+        let action = if before.is_some() { AuditAction::Update } else { AuditAction::Create };
+        record_audit(&state, NewAuditLogEntry::new("goal_allocation", &alloc.id, action, AUDIT_ACTOR_USER)
+            .with_snapshots(before, Some(alloc))).await;
+    }
+    Ok(())
+}
+
+#[derive(serde::Deserialize)]
+struct BulkAllocationsBody {
+    allocations: Vec<GoalsAllocation>,
+    #[serde(default)]
+    versions: Vec<AllocationVersion>,
+}
+
+/// Migration path for many allocations at once; nothing is saved unless the whole set is valid
+async fn bulk_insert_allocations(State(state): State<Arc<AppState>>, Json(body): Json<BulkAllocationsBody>) -> ApiResult<Json<AllocationBulkInsertSummary>> {
+    let summary = state.goal_service.bulk_insert_allocations(body.allocations.clone(), body.versions).await?;
+    for alloc in &body.allocations {
+        record_audit(&state, NewAuditLogEntry::new("goal_allocation", &alloc.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&GoalsAllocation>, Some(alloc))).await;
+    }
+    Ok(Json(summary))
+}
+
+/// Every goal with its allocations and their versions, as a document another profile can import
+async fn export_goal_plan(State(state): State<Arc<AppState>>) -> ApiResult<Json<GoalPlanExport>> {
+    let accounts = state.account_service.get_all_accounts()?;
+    Ok(Json(state.goal_service.export_goal_plan(&accounts).await?))
+}
+
+/// Recreates an exported goal plan under new ids; nothing is saved unless the whole plan is valid
+async fn import_goal_plan(State(state): State<Arc<AppState>>, Json(plan): Json<GoalPlanExport>) -> ApiResult<Json<GoalPlanImportResult>> {
+    let accounts = state.account_service.get_all_accounts()?;
+    Ok(Json(state.goal_service.import_goal_plan(plan, &accounts).await?))
+}
+
+// Budgets
+async fn get_budget_categories(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<BudgetCategory>>> {
+    Ok(Json(state.budget_service.get_budget_categories()?))
+}
+
+async fn create_budget_category(State(state): State<Arc<AppState>>, Json(category): Json<NewBudgetCategory>) -> ApiResult<Json<BudgetCategory>> {
+    let created = state.budget_service.create_budget_category(category).await?;
+    record_audit(&state, NewAuditLogEntry::new("budget_category", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&BudgetCategory>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_budget_category(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(category): Json<NewBudgetCategory>) -> ApiResult<Json<BudgetCategory>> {
+    let previous = state.budget_service.get_budget_categories()?.into_iter().find(|c| c.id == id);
+    let updated = state.budget_service.update_budget_category(&id, category).await?;
+    record_audit(&state, NewAuditLogEntry::new("budget_category", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_budget_category(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.budget_service.get_budget_categories()?.into_iter().find(|c| c.id == id);
+    state.budget_service.delete_budget_category(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("budget_category", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&BudgetCategory>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_budget_month_amounts(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<BudgetMonthAmount>>> {
+    Ok(Json(state.budget_service.get_budget_month_amounts()?))
+}
+
+async fn set_budget_month_amount(State(state): State<Arc<AppState>>, Json(update): Json<BudgetMonthAmountUpdate>) -> ApiResult<StatusCode> {
+    state.budget_service.set_budget_month_amount(&update.category_id, &update.month, update.amount).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CategorizeActivitiesBody { activity_ids: Vec<String>, category_id: Option<String> }
+
+async fn categorize_activities(State(state): State<Arc<AppState>>, Json(body): Json<CategorizeActivitiesBody>) -> ApiResult<Json<usize>> {
+    Ok(Json(state.budget_service.categorize_activities(body.activity_ids, body.category_id).await?))
+}
+
+#[derive(serde::Deserialize)]
+struct ActivityCategoriesQuery { #[serde(rename = "activityIds")] activity_ids: String }
+
+async fn get_activity_categories(State(state): State<Arc<AppState>>, Query(q): Query<ActivityCategoriesQuery>) -> ApiResult<Json<Vec<ActivityCategory>>> {
+    let ids: Vec<String> = q.activity_ids.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+    Ok(Json(state.budget_service.get_activity_categories(&ids)?))
+}
+
+#[derive(serde::Deserialize)]
+struct BudgetProgressQuery { month: String }
+
+async fn get_budget_progress(State(state): State<Arc<AppState>>, Query(q): Query<BudgetProgressQuery>) -> ApiResult<Json<BudgetMonthProgress>> {
+    Ok(Json(state.budget_service.get_budget_progress(&q.month)?))
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BudgetReportQuery { from_month: String, to_month: String }
+
+async fn get_budget_report(State(state): State<Arc<AppState>>, Query(q): Query<BudgetReportQuery>) -> ApiResult<Json<BudgetReport>> {
+    Ok(Json(state.budget_service.get_budget_report(&q.from_month, &q.to_month)?))
+}
+
+// Categorization rules
+async fn get_categorization_rules(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<CategorizationRule>>> {
+    Ok(Json(state.categorization_service.get_categorization_rules()?))
+}
+
+async fn create_categorization_rule(State(state): State<Arc<AppState>>, Json(rule): Json<NewCategorizationRule>) -> ApiResult<Json<CategorizationRule>> {
+    let created = state.categorization_service.create_categorization_rule(rule).await?;
+    record_audit(&state, NewAuditLogEntry::new("categorization_rule", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&CategorizationRule>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_categorization_rule(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(rule): Json<NewCategorizationRule>) -> ApiResult<Json<CategorizationRule>> {
+    let previous = state.categorization_service.get_categorization_rules()?.into_iter().find(|r| r.id == id);
+    let updated = state.categorization_service.update_categorization_rule(&id, rule).await?;
+    record_audit(&state, NewAuditLogEntry::new("categorization_rule", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_categorization_rule(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.categorization_service.get_categorization_rules()?.into_iter().find(|r| r.id == id);
+    state.categorization_service.delete_categorization_rule(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("categorization_rule", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&CategorizationRule>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn auto_categorize_activities(State(state): State<Arc<AppState>>) -> ApiResult<Json<CategorizationResult>> {
+    Ok(Json(state.categorization_service.auto_categorize().await?))
+}
+
+async fn recategorize_all_activities(State(state): State<Arc<AppState>>) -> ApiResult<Json<CategorizationResult>> {
+    Ok(Json(state.categorization_service.recategorize_all().await?))
+}
+
+// Cash flow forecast
+async fn get_planned_cash_flows(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<PlannedCashFlow>>> {
+    Ok(Json(state.forecast_service.get_planned_cash_flows()?))
+}
+
+async fn create_planned_cash_flow(State(state): State<Arc<AppState>>, Json(flow): Json<NewPlannedCashFlow>) -> ApiResult<Json<PlannedCashFlow>> {
+    let created = state.forecast_service.create_planned_cash_flow(flow).await?;
+    record_audit(&state, NewAuditLogEntry::new("planned_cash_flow", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&PlannedCashFlow>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_planned_cash_flow(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(flow): Json<NewPlannedCashFlow>) -> ApiResult<Json<PlannedCashFlow>> {
+    let previous = state.forecast_service.get_planned_cash_flows()?.into_iter().find(|f| f.id == id);
+    let updated = state.forecast_service.update_planned_cash_flow(&id, flow).await?;
+    record_audit(&state, NewAuditLogEntry::new("planned_cash_flow", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_planned_cash_flow(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.forecast_service.get_planned_cash_flows()?.into_iter().find(|f| f.id == id);
+    state.forecast_service.delete_planned_cash_flow(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("planned_cash_flow", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&PlannedCashFlow>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_cash_flow_forecast(State(state): State<Arc<AppState>>, Json(request): Json<CashFlowForecastRequest>) -> ApiResult<Json<CashFlowForecast>> {
+    Ok(Json(state.forecast_service.get_cash_flow_forecast(request).await?))
+}
+
+// Income sources
+async fn get_income_sources(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<IncomeSource>>> {
+    Ok(Json(state.income_source_service.get_income_sources()?))
+}
+
+async fn create_income_source(State(state): State<Arc<AppState>>, Json(source): Json<NewIncomeSource>) -> ApiResult<Json<IncomeSource>> {
+    let created = state.income_source_service.create_income_source(source).await?;
+    record_audit(&state, NewAuditLogEntry::new("income_source", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&IncomeSource>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_income_source(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(source): Json<NewIncomeSource>) -> ApiResult<Json<IncomeSource>> {
+    let previous = state.income_source_service.get_income_sources()?.into_iter().find(|s| s.id == id);
+    let updated = state.income_source_service.update_income_source(&id, source).await?;
+    record_audit(&state, NewAuditLogEntry::new("income_source", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_income_source(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.income_source_service.get_income_sources()?.into_iter().find(|s| s.id == id);
+    state.income_source_service.delete_income_source(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("income_source", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&IncomeSource>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct IncomeVarianceQuery { #[serde(rename = "startDate")] start_date: String, #[serde(rename = "endDate")] end_date: String }
+
+async fn get_income_variance(State(state): State<Arc<AppState>>, Query(q): Query<IncomeVarianceQuery>) -> ApiResult<Json<Vec<IncomeVariance>>> {
+    let start = parse_forecast_date(&q.start_date)?;
+    let end = parse_forecast_date(&q.end_date)?;
+    Ok(Json(state.income_source_service.get_income_variance(start, end)?))
+}
+
+async fn get_savings_rate(State(state): State<Arc<AppState>>) -> ApiResult<Json<SavingsRate>> {
+    Ok(Json(state.income_source_service.get_savings_rate()?))
+}
+
+// Bills
+async fn get_bills(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<Bill>>> {
+    Ok(Json(state.bill_service.get_bills()?))
+}
+
+async fn create_bill(State(state): State<Arc<AppState>>, Json(bill): Json<NewBill>) -> ApiResult<Json<Bill>> {
+    let created = state.bill_service.create_bill(bill).await?;
+    record_audit(&state, NewAuditLogEntry::new("bill", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Bill>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_bill(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(bill): Json<NewBill>) -> ApiResult<Json<Bill>> {
+    let previous = state.bill_service.get_bills()?.into_iter().find(|b| b.id == id);
+    let updated = state.bill_service.update_bill(&id, bill).await?;
+    record_audit(&state, NewAuditLogEntry::new("bill", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_bill(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.bill_service.get_bills()?.into_iter().find(|b| b.id == id);
+    state.bill_service.delete_bill(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("bill", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&Bill>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct BillPaymentsQuery { #[serde(rename = "billId")] bill_id: Option<String> }
+
+async fn get_bill_payments(State(state): State<Arc<AppState>>, Query(q): Query<BillPaymentsQuery>) -> ApiResult<Json<Vec<BillPayment>>> {
+    Ok(Json(state.bill_service.get_bill_payments(q.bill_id)?))
+}
+
+async fn mark_bill_paid(State(state): State<Arc<AppState>>, Json(payment): Json<NewBillPayment>) -> ApiResult<Json<BillPayment>> {
+    let created = state.bill_service.mark_bill_paid(payment).await?;
+    record_audit(&state, NewAuditLogEntry::new("bill_payment", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&BillPayment>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn delete_bill_payment(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.bill_service.get_bill_payments(None)?.into_iter().find(|p| p.id == id);
+    state.bill_service.delete_bill_payment(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("bill_payment", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&BillPayment>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_bill_reminders(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<BillReminder>>> {
+    Ok(Json(state.bill_service.get_bill_reminders()?))
+}
+
+async fn match_bill_payments(State(state): State<Arc<AppState>>) -> ApiResult<Json<BillMatchResult>> {
+    Ok(Json(state.bill_service.match_bill_payments().await?))
+}
+
+// Envelopes
+#[derive(serde::Deserialize)]
+struct EnvelopesQuery { #[serde(rename = "accountId")] account_id: String }
+
+async fn get_account_envelopes(State(state): State<Arc<AppState>>, Query(q): Query<EnvelopesQuery>) -> ApiResult<Json<AccountEnvelopes>> {
+    Ok(Json(state.envelope_service.get_account_envelopes(&q.account_id)?))
+}
+
+async fn create_envelope(State(state): State<Arc<AppState>>, Json(envelope): Json<NewEnvelope>) -> ApiResult<Json<Envelope>> {
+    let created = state.envelope_service.create_envelope(envelope).await?;
+    record_audit(&state, NewAuditLogEntry::new("envelope", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Envelope>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+#[derive(serde::Deserialize)]
+struct RenameEnvelopeBody { name: String }
+
+async fn rename_envelope(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(body): Json<RenameEnvelopeBody>) -> ApiResult<Json<Envelope>> {
+    let previous = state.envelope_service.get_envelope(&id)?;
+    let updated = state.envelope_service.rename_envelope(&id, &body.name).await?;
+    record_audit(&state, NewAuditLogEntry::new("envelope", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn archive_envelope(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<Envelope>> {
+    let previous = state.envelope_service.get_envelope(&id)?;
+    let archived = state.envelope_service.archive_envelope(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("envelope", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&archived))).await;
+    Ok(Json(archived))
+}
+
+async fn get_envelope_transfers(State(state): State<Arc<AppState>>, Query(q): Query<EnvelopesQuery>) -> ApiResult<Json<Vec<EnvelopeTransfer>>> {
+    Ok(Json(state.envelope_service.get_envelope_transfers(&q.account_id)?))
+}
+
+async fn transfer_envelope_funds(State(state): State<Arc<AppState>>, Json(transfer): Json<NewEnvelopeTransfer>) -> ApiResult<Json<EnvelopeTransfer>> {
+    let created = state.envelope_service.transfer_envelope_funds(transfer).await?;
+    record_audit(&state, NewAuditLogEntry::new("envelope_transfer", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&EnvelopeTransfer>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+// Loans
+async fn get_loans(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<Loan>>> {
+    Ok(Json(state.loan_service.get_loans()?))
+}
+
+async fn get_loan_schedule(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<LoanSchedule>> {
+    Ok(Json(state.loan_service.get_loan_schedule(&id)?))
+}
+
+async fn create_loan(State(state): State<Arc<AppState>>, Json(loan): Json<NewLoan>) -> ApiResult<Json<Loan>> {
+    let created = state.loan_service.create_loan(loan).await?;
+    record_audit(&state, NewAuditLogEntry::new("loan", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Loan>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_loan(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(loan): Json<NewLoan>) -> ApiResult<Json<Loan>> {
+    let previous = state.loan_service.get_loans()?.into_iter().find(|l| l.id == id);
+    let updated = state.loan_service.update_loan(&id, loan).await?;
+    record_audit(&state, NewAuditLogEntry::new("loan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_loan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.loan_service.get_loans()?.into_iter().find(|l| l.id == id);
+    state.loan_service.delete_loan(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("loan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&Loan>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct LoanPrepaymentsQuery { #[serde(rename = "loanId")] loan_id: Option<String> }
+
+async fn get_loan_prepayments(State(state): State<Arc<AppState>>, Query(q): Query<LoanPrepaymentsQuery>) -> ApiResult<Json<Vec<LoanPrepayment>>> {
+    Ok(Json(state.loan_service.get_loan_prepayments(q.loan_id)?))
+}
+
+async fn add_loan_prepayment(State(state): State<Arc<AppState>>, Json(prepayment): Json<NewLoanPrepayment>) -> ApiResult<Json<LoanPrepayment>> {
+    let created = state.loan_service.add_loan_prepayment(prepayment).await?;
+    record_audit(&state, NewAuditLogEntry::new("loan_prepayment", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&LoanPrepayment>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn delete_loan_prepayment(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.loan_service.get_loan_prepayments(None)?.into_iter().find(|p| p.id == id);
+    state.loan_service.delete_loan_prepayment(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("loan_prepayment", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&LoanPrepayment>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct LoanStressQuery { #[serde(rename = "rateIncrease")] rate_increase: rust_decimal::Decimal }
+
+async fn stress_test_loan(Path(id): Path<String>, State(state): State<Arc<AppState>>, Query(q): Query<LoanStressQuery>) -> ApiResult<Json<LoanStressTest>> {
+    Ok(Json(state.loan_service.stress_test_loan(&id, q.rate_increase)?))
+}
+
+async fn get_loan_rate_resets(State(state): State<Arc<AppState>>, Query(q): Query<LoanPrepaymentsQuery>) -> ApiResult<Json<Vec<LoanRateReset>>> {
+    Ok(Json(state.loan_service.get_loan_rate_resets(q.loan_id)?))
+}
+
+async fn add_loan_rate_reset(State(state): State<Arc<AppState>>, Json(reset): Json<NewLoanRateReset>) -> ApiResult<Json<LoanRateReset>> {
+    let created = state.loan_service.add_loan_rate_reset(reset).await?;
+    record_audit(&state, NewAuditLogEntry::new("loan_rate_reset", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&LoanRateReset>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn delete_loan_rate_reset(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.loan_service.get_loan_rate_resets(None)?.into_iter().find(|r| r.id == id);
+    state.loan_service.delete_loan_rate_reset(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("loan_rate_reset", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&LoanRateReset>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Real estate
+async fn get_properties(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<PropertySummary>>> {
+    Ok(Json(state.property_service.get_property_summaries()?))
+}
+
+async fn get_property(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<PropertySummary>> {
+    Ok(Json(state.property_service.get_property_summary(&id)?))
+}
+
+async fn create_property(State(state): State<Arc<AppState>>, Json(property): Json<NewProperty>) -> ApiResult<Json<Property>> {
+    let created = state.property_service.create_property(property).await?;
+    // The property's purchase is an activity on its account
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("property", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Property>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_property(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(property): Json<NewProperty>) -> ApiResult<Json<Property>> {
+    let previous = state.property_service.get_properties()?.into_iter().find(|p| p.id == id);
+    let updated = state.property_service.update_property(&id, property).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("property", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_property(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.property_service.get_properties()?.into_iter().find(|p| p.id == id);
+    state.property_service.delete_property(&id).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("property", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&Property>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct PropertyAppraisalsQuery { #[serde(rename = "propertyId")] property_id: Option<String> }
+
+async fn get_property_appraisals(State(state): State<Arc<AppState>>, Query(q): Query<PropertyAppraisalsQuery>) -> ApiResult<Json<Vec<PropertyAppraisal>>> {
+    Ok(Json(state.property_service.get_property_appraisals(q.property_id)?))
+}
+
+async fn add_property_appraisal(State(state): State<Arc<AppState>>, Json(appraisal): Json<NewPropertyAppraisal>) -> ApiResult<Json<PropertyAppraisal>> {
+    let created = state.property_service.add_property_appraisal(appraisal).await?;
+    record_audit(&state, NewAuditLogEntry::new("property_appraisal", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&PropertyAppraisal>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn delete_property_appraisal(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.property_service.get_property_appraisals(None)?.into_iter().find(|a| a.id == id);
+    state.property_service.delete_property_appraisal(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("property_appraisal", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&PropertyAppraisal>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Bonds
+async fn get_bonds(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<BondSummary>>> {
+    Ok(Json(state.bond_service.get_bond_summaries()?))
+}
+
+async fn get_bond(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<BondSummary>> {
+    Ok(Json(state.bond_service.get_bond_summary(&id)?))
+}
+
+async fn create_bond(State(state): State<Arc<AppState>>, Json(bond): Json<NewBond>) -> ApiResult<Json<Bond>> {
+    let created = state.bond_service.create_bond(bond).await?;
+    // The bonds are added to their account by an activity
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("bond", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Bond>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_bond(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(bond): Json<NewBond>) -> ApiResult<Json<Bond>> {
+    let previous = state.bond_service.get_bonds()?.into_iter().find(|b| b.id == id);
+    let updated = state.bond_service.update_bond(&id, bond).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("bond", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_bond(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.bond_service.get_bonds()?.into_iter().find(|b| b.id == id);
+    state.bond_service.delete_bond(&id).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("bond", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&Bond>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct BondPaymentsQuery { months: Option<u32> }
+
+async fn get_bond_payments(State(state): State<Arc<AppState>>, Query(q): Query<BondPaymentsQuery>) -> ApiResult<Json<Vec<BondPayment>>> {
+    Ok(Json(state.bond_service.get_bond_payments(q.months.unwrap_or(12))?))
+}
+
+// Term deposits
+async fn get_term_deposits(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<TermDepositSummary>>> {
+    Ok(Json(state.term_deposit_service.get_term_deposit_summaries()?))
+}
+
+#[derive(serde::Deserialize)]
+struct TermDepositValueQuery { date: Option<chrono::NaiveDate> }
+
+async fn get_term_deposit(Path(id): Path<String>, State(state): State<Arc<AppState>>, Query(q): Query<TermDepositValueQuery>) -> ApiResult<Json<TermDepositSummary>> {
+    Ok(Json(state.term_deposit_service.get_term_deposit_summary(&id, q.date)?))
+}
+
+async fn create_term_deposit(State(state): State<Arc<AppState>>, Json(deposit): Json<NewTermDeposit>) -> ApiResult<Json<TermDeposit>> {
+    let created = state.term_deposit_service.create_term_deposit(deposit).await?;
+    record_audit(&state, NewAuditLogEntry::new("term_deposit", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&TermDeposit>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_term_deposit(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(deposit): Json<NewTermDeposit>) -> ApiResult<Json<TermDeposit>> {
+    let previous = state.term_deposit_service.get_term_deposits()?.into_iter().find(|d| d.id == id);
+    let updated = state.term_deposit_service.update_term_deposit(&id, deposit).await?;
+    record_audit(&state, NewAuditLogEntry::new("term_deposit", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_term_deposit(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.term_deposit_service.get_term_deposits()?.into_iter().find(|d| d.id == id);
+    state.term_deposit_service.delete_term_deposit(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("term_deposit", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&TermDeposit>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct MaturitiesQuery { days: Option<u32> }
+
+async fn get_upcoming_maturities(State(state): State<Arc<AppState>>, Query(q): Query<MaturitiesQuery>) -> ApiResult<Json<Vec<TermDepositMaturity>>> {
+    Ok(Json(state.term_deposit_service.get_upcoming_maturities(q.days.unwrap_or(90))?))
+}
+
+// Recurring rules
+async fn list_recurring_rules(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<RecurringRule>>> {
+    Ok(Json(state.recurring_rule_service.get_recurring_rules()?))
+}
+
+async fn create_recurring_rule(State(state): State<Arc<AppState>>, Json(rule): Json<NewRecurringRule>) -> ApiResult<Json<RecurringRule>> {
+    let created = state.recurring_rule_service.create_recurring_rule(rule).await?;
+    record_audit(&state, NewAuditLogEntry::new("recurring_rule", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&RecurringRule>, Some(&created))).await;
+    // A rule starting in the past catches up right away rather than at the next check
+    if let Err(e) = crate::main_lib::materialize_recurring_rules(&state).await {
+        tracing::warn!("Posting recurring activities failed: {}", e);
+    }
+    Ok(Json(created))
+}
+
+async fn set_recurring_rule_paused(state: &AppState, id: String, paused: bool) -> ApiResult<Json<RecurringRule>> {
+    let previous = state.recurring_rule_service.get_recurring_rules()?.into_iter().find(|r| r.id == id);
+    let updated = state.recurring_rule_service.set_recurring_rule_paused(&id, paused, chrono::Utc::now().date_naive()).await?;
+    record_audit(state, NewAuditLogEntry::new("recurring_rule", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn pause_recurring_rule(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<RecurringRule>> {
+    set_recurring_rule_paused(&state, id, true).await
+}
+
+async fn resume_recurring_rule(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<RecurringRule>> {
+    set_recurring_rule_paused(&state, id, false).await
+}
+
+async fn delete_recurring_rule(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.recurring_rule_service.get_recurring_rules()?.into_iter().find(|r| r.id == id);
+    state.recurring_rule_service.delete_recurring_rule(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("recurring_rule", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&RecurringRule>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Reports
+#[derive(serde::Deserialize)]
+struct NetWorthStatementQuery { date: Option<String> }
+
+/// Net worth statement as of `date` (today by default), with the change from the month before
+async fn get_net_worth_statement(State(state): State<Arc<AppState>>, Query(q): Query<NetWorthStatementQuery>) -> ApiResult<Json<NetWorthStatement>> {
+    let date = q.date.as_deref().map(parse_forecast_date).transpose()?.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let statement = state.report_service.get_net_worth_statement(date).await?;
+    Ok(Json(mask_if(statement, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+#[derive(serde::Deserialize)]
+struct NetWorthStatementHistoryQuery { months: u32 }
+
+async fn get_net_worth_statement_history(State(state): State<Arc<AppState>>, Query(q): Query<NetWorthStatementHistoryQuery>) -> ApiResult<Json<Vec<NetWorthHistoryEntry>>> {
+    let history = state.report_service.get_net_worth_statement_history(q.months)?;
+    Ok(Json(mask_if(history, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+// Dividends
+#[derive(serde::Deserialize)]
+struct DividendSummaryQuery { date: Option<String> }
+
+/// Dividend calendar and trailing twelve-month yield per holding as of `date` (today by default)
+async fn get_dividend_summary(State(state): State<Arc<AppState>>, Query(q): Query<DividendSummaryQuery>) -> ApiResult<Json<DividendSummary>> {
+    let date = q.date.as_deref().map(parse_forecast_date).transpose()?.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let summary = state.dividend_service.get_dividend_summary(date).await?;
+    Ok(Json(mask_if(summary, state.settings_service.is_privacy_mode_enabled()?)))
+}
+
+async fn get_activity_dividend(Path(activity_id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<Option<ActivityDividend>>> {
+    Ok(Json(state.dividend_service.get_activity_dividend(&activity_id)?))
+}
+
+async fn save_activity_dividend(State(state): State<Arc<AppState>>, Json(dividend): Json<NewActivityDividend>) -> ApiResult<Json<ActivityDividend>> {
+    let previous = state.dividend_service.get_activity_dividend(&dividend.activity_id)?;
+    let saved = state.dividend_service.save_activity_dividend(dividend).await?;
+    let action = if previous.is_some() { AuditAction::Update } else { AuditAction::Create };
+    record_audit(&state, NewAuditLogEntry::new("activity_dividend", &saved.activity_id, action, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&saved))).await;
+    Ok(Json(saved))
+}
+
+async fn delete_activity_dividend(Path(activity_id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.dividend_service.get_activity_dividend(&activity_id)?;
+    state.dividend_service.delete_activity_dividend(&activity_id).await?;
+    record_audit(&state, NewAuditLogEntry::new("activity_dividend", &activity_id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&ActivityDividend>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ESOP grants
+async fn get_esop_grants(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<EsopGrantSummary>>> {
+    Ok(Json(state.esop_service.get_esop_grant_summaries()?))
+}
+
+async fn get_esop_grant(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<EsopGrantSummary>> {
+    Ok(Json(state.esop_service.get_esop_grant_summary(&id)?))
+}
+
+async fn create_esop_grant(State(state): State<Arc<AppState>>, Json(grant): Json<NewEsopGrant>) -> ApiResult<Json<EsopGrant>> {
+    let created = state.esop_service.create_esop_grant(grant).await?;
+    // The grant is added to its account by an activity
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("esop_grant", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&EsopGrant>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_esop_grant(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(grant): Json<NewEsopGrant>) -> ApiResult<Json<EsopGrant>> {
+    let previous = state.esop_service.get_esop_grants()?.into_iter().find(|g| g.id == id);
+    let updated = state.esop_service.update_esop_grant(&id, grant).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("esop_grant", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_esop_grant(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.esop_service.get_esop_grants()?.into_iter().find(|g| g.id == id);
+    state.esop_service.delete_esop_grant(&id).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("esop_grant", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&EsopGrant>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct UpcomingVestingsQuery { days: Option<i64> }
+
+async fn get_upcoming_vestings(State(state): State<Arc<AppState>>, Query(q): Query<UpcomingVestingsQuery>) -> ApiResult<Json<Vec<UpcomingVesting>>> {
+    Ok(Json(state.esop_service.get_upcoming_vestings(q.days.unwrap_or(VESTING_REMINDER_DAYS))?))
+}
+
+// Fund certificate purchase programs (SIP)
+async fn get_sip_plans(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<SipPlanSummary>>> {
+    Ok(Json(state.sip_plan_service.get_sip_plan_summaries()?))
+}
+
+async fn get_sip_plan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<SipPlanSummary>> {
+    Ok(Json(state.sip_plan_service.get_sip_plan_summary(&id)?))
+}
+
+async fn create_sip_plan(State(state): State<Arc<AppState>>, Json(plan): Json<NewSipPlan>) -> ApiResult<Json<SipPlan>> {
+    let created = state.sip_plan_service.create_sip_plan(plan).await?;
+    record_audit(&state, NewAuditLogEntry::new("sip_plan", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&SipPlan>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_sip_plan(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(plan): Json<NewSipPlan>) -> ApiResult<Json<SipPlan>> {
+    let previous = state.sip_plan_service.get_sip_plans()?.into_iter().find(|p| p.id == id);
+    let updated = state.sip_plan_service.update_sip_plan(&id, plan).await?;
+    record_audit(&state, NewAuditLogEntry::new("sip_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_sip_plan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.sip_plan_service.get_sip_plans()?.into_iter().find(|p| p.id == id);
+    state.sip_plan_service.delete_sip_plan(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("sip_plan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&SipPlan>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Benchmark indices
+async fn get_benchmarks(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<Benchmark>>> {
+    Ok(Json(state.benchmark_service.get_benchmarks()?))
+}
+
+async fn create_benchmark(State(state): State<Arc<AppState>>, Json(benchmark): Json<NewBenchmark>) -> ApiResult<Json<Benchmark>> {
+    let created = state.benchmark_service.create_benchmark(benchmark).await?;
+    record_audit(&state, NewAuditLogEntry::new("benchmark", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Benchmark>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn delete_benchmark(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.benchmark_service.get_benchmarks()?.into_iter().find(|b| b.id == id);
+    state.benchmark_service.delete_benchmark(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("benchmark", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&Benchmark>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(serde::Deserialize)]
+struct BenchmarkComparisonQuery {
+    #[serde(rename = "accountOrGoalId")] account_or_goal_id: String,
+    #[serde(rename = "startDate")] start_date: Option<chrono::NaiveDate>,
+    #[serde(rename = "endDate")] end_date: Option<chrono::NaiveDate>,
+}
+
+async fn compare_to_benchmark(Path(id): Path<String>, State(state): State<Arc<AppState>>, Query(q): Query<BenchmarkComparisonQuery>) -> ApiResult<Json<BenchmarkComparison>> {
+    Ok(Json(state.benchmark_service.compare_to_benchmark(&q.account_or_goal_id, &id, q.start_date, q.end_date).await?))
+}
+
+// Private loans
+async fn get_private_loans(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<PrivateLoanSummary>>> {
+    Ok(Json(state.private_loan_service.get_private_loan_summaries()?))
+}
+
+async fn get_private_loan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<PrivateLoanSummary>> {
+    Ok(Json(state.private_loan_service.get_private_loan_summary(&id)?))
+}
+
+async fn create_private_loan(State(state): State<Arc<AppState>>, Json(loan): Json<NewPrivateLoan>) -> ApiResult<Json<PrivateLoan>> {
+    let created = state.private_loan_service.create_private_loan(loan).await?;
+    // The money lent is added to its account by an activity
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("private_loan", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&PrivateLoan>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_private_loan(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(loan): Json<NewPrivateLoan>) -> ApiResult<Json<PrivateLoan>> {
+    let previous = state.private_loan_service.get_private_loans()?.into_iter().find(|l| l.id == id);
+    let updated = state.private_loan_service.update_private_loan(&id, loan).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("private_loan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_private_loan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.private_loan_service.get_private_loans()?.into_iter().find(|l| l.id == id);
+    state.private_loan_service.delete_private_loan(&id).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("private_loan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&PrivateLoan>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn record_private_loan_repayment(State(state): State<Arc<AppState>>, Json(repayment): Json<NewPrivateLoanRepayment>) -> ApiResult<Json<PrivateLoanRepayment>> {
+    let created = state.private_loan_service.record_private_loan_repayment(repayment).await?;
+    // The loan is requoted at the principal left
+    state.query_cache.invalidate(RESOURCE_PORTFOLIO);
+    record_audit(&state, NewAuditLogEntry::new("private_loan_repayment", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&PrivateLoanRepayment>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn delete_private_loan_repayment(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.private_loan_service.get_private_loan_summaries()?.into_iter().flat_map(|l| l.repayments).find(|r| r.id == id);
+    state.private_loan_service.delete_private_loan_repayment(&id).await?;
+    state.query_cache.invalidate(RESOURCE_PORTFOLIO);
+    record_audit(&state, NewAuditLogEntry::new("private_loan_repayment", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&PrivateLoanRepayment>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn match_private_loan_repayments(State(state): State<Arc<AppState>>) -> ApiResult<Json<PrivateLoanMatchResult>> {
+    let result = state.private_loan_service.match_private_loan_repayments().await?;
+    if result.matched > 0 {
+        state.query_cache.invalidate(RESOURCE_PORTFOLIO);
+    }
+    Ok(Json(result))
+}
+
+// Bonus plans
+async fn get_deposit_rates(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<DepositRate>>> {
+    Ok(Json(state.deposit_rate_service.get_deposit_rates()?))
+}
+
+/// Fetches the catalog even when it is still fresh
+async fn refresh_deposit_rates(State(state): State<Arc<AppState>>) -> ApiResult<Json<usize>> {
+    Ok(Json(state.deposit_rate_service.refresh_deposit_rates(true).await?))
+}
+
+#[derive(serde::Deserialize)]
+struct RolloverSuggestionsQuery { #[serde(rename = "withinDays")] within_days: Option<u64> }
+
+async fn get_rollover_suggestions(State(state): State<Arc<AppState>>, Query(q): Query<RolloverSuggestionsQuery>) -> ApiResult<Json<Vec<RolloverSuggestion>>> {
+    Ok(Json(state.deposit_rate_service.get_rollover_suggestions(q.within_days)?))
+}
+
+async fn propose_deposit_ladder(State(state): State<Arc<AppState>>, Json(request): Json<DepositLadderRequest>) -> ApiResult<Json<DepositLadder>> {
+    Ok(Json(state.deposit_ladder_service.propose_deposit_ladder(request)?))
+}
+
+async fn confirm_deposit_ladder(State(state): State<Arc<AppState>>, Json(ladder): Json<DepositLadder>) -> ApiResult<Json<ConfirmedDepositLadder>> {
+    let confirmed = state.deposit_ladder_service.confirm_deposit_ladder(ladder).await?;
+    // Each deposit is a new account funded by transfers from the source account
+    state.query_cache.invalidate(RESOURCE_ACCOUNT);
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    for account in &confirmed.accounts {
+        record_audit(&state, NewAuditLogEntry::new("account", &account.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&wealthvn_core::accounts::Account>, Some(account))).await;
+    }
+    Ok(Json(confirmed))
+}
+
+async fn estimate_pension_handler(Json(request): Json<PensionRequest>) -> ApiResult<Json<PensionEstimate>> {
+    Ok(Json(estimate_pension(&request, chrono::Utc::now().date_naive())?))
+}
+
+async fn get_bonus_plans(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<BonusPlan>>> {
+    Ok(Json(state.bonus_plan_service.get_bonus_plans()?))
+}
+
+async fn get_bonus_plan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<BonusPlan>> {
+    Ok(Json(state.bonus_plan_service.get_bonus_plan(&id)?))
+}
+
+async fn propose_bonus_plan(State(state): State<Arc<AppState>>, Json(request): Json<BonusPlanRequest>) -> ApiResult<Json<NewBonusPlan>> {
+    Ok(Json(state.bonus_plan_service.propose_bonus_plan(request).await?))
+}
+
+async fn confirm_bonus_plan(State(state): State<Arc<AppState>>, Json(plan): Json<NewBonusPlan>) -> ApiResult<Json<BonusPlan>> {
+    let created = state.bonus_plan_service.confirm_bonus_plan(plan).await?;
+    // The bonus is recorded as a draft deposit on the account
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("bonus_plan", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&BonusPlan>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn fund_bonus_plan_envelopes(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<BonusPlan>> {
+    let previous = state.bonus_plan_service.get_bonus_plan(&id).ok();
+    let updated = state.bonus_plan_service.fund_bonus_plan_envelopes(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("bonus_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_bonus_plan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.bonus_plan_service.get_bonus_plan(&id).ok();
+    state.bonus_plan_service.delete_bonus_plan(&id).await?;
+    state.query_cache.invalidate(RESOURCE_ACTIVITY);
+    record_audit(&state, NewAuditLogEntry::new("bonus_plan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), None::<&BonusPlan>)).await;
+    Ok(StatusCode::NO_CONTENT)
 }
 
-#[utoipa::path(post, path="/api/v1/accounts", request_body = NewAccount, responses((status=200, body = Account)))]
-async fn create_account(
-    State(state): State<Arc<AppState>>,
-    Json(payload): Json<NewAccount>,
-) -> ApiResult<Json<Account>> {
-    let core_new = payload.into();
-    let created = state.account_service.create_account(core_new).await?;
-    Ok(Json(Account::from(created)))
+// Education plans
+async fn get_education_plans(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<EducationPlan>>> {
+    Ok(Json(state.education_service.get_education_plans()?))
 }
 
-#[utoipa::path(put, path="/api/v1/accounts/{id}", request_body = AccountUpdate, responses((status=200, body=Account)))]
-async fn update_account(
-    Path(id): Path<String>,
-    State(state): State<Arc<AppState>>,
-    Json(mut payload): Json<AccountUpdate>,
-) -> ApiResult<Json<Account>> {
-    payload.id = Some(id);
-    let updated = state.account_service.update_account(payload.into()).await?;
-    Ok(Json(Account::from(updated)))
+async fn create_education_plan(State(state): State<Arc<AppState>>, Json(plan): Json<NewEducationPlan>) -> ApiResult<Json<EducationPlan>> {
+    let created = state.education_service.create_education_plan(plan).await?;
+    record_audit(&state, NewAuditLogEntry::new("education_plan", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&EducationPlan>, Some(&created))).await;
+    Ok(Json(created))
 }
 
-#[utoipa::path(delete, path="/api/v1/accounts/{id}", responses((status=204)))]
-async fn delete_account(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<()> {
-    state.account_service.delete_account(&id).await?;
-    Ok(())
+async fn update_education_plan(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(plan): Json<NewEducationPlan>) -> ApiResult<Json<EducationPlan>> {
+    let previous = state.education_service.get_education_plan(&id)?;
+    let updated = state.education_service.update_education_plan(&id, plan).await?;
+    record_audit(&state, NewAuditLogEntry::new("education_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&updated))).await;
+    Ok(Json(updated))
 }
 
-// Settings endpoints (web adapter relies on these)
-async fn get_settings(State(state): State<Arc<AppState>>) -> ApiResult<Json<Settings>> {
-    let s = state.settings_service.get_settings()?;
-    Ok(Json(s))
+async fn delete_education_plan(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.education_service.get_education_plan(&id)?;
+    state.education_service.delete_education_plan(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("education_plan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), None::<&EducationPlan>)).await;
+    Ok(StatusCode::NO_CONTENT)
 }
 
-async fn update_settings(State(state): State<Arc<AppState>>, Json(payload): Json<SettingsUpdate>) -> ApiResult<Json<Settings>> {
-    state.settings_service.update_settings(&payload).await?;
-    let s = state.settings_service.get_settings()?;
-    Ok(Json(s))
+async fn project_education_costs(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<EducationProjection>> {
+    Ok(Json(state.education_service.project_education_costs(&id)?))
 }
 
-// Holdings endpoint
 #[derive(serde::Deserialize)]
-struct HoldingsQuery { #[serde(rename = "accountId")] account_id: String }
+struct LinkEducationGoalBody { #[serde(rename = "goalId")] goal_id: Option<String> }
 
-async fn get_holdings(State(state): State<Arc<AppState>>, Query(q): Query<HoldingsQuery>) -> ApiResult<Json<Vec<Holding>>> {
-    let base = state.base_currency.read().unwrap().clone();
-    let holdings = state.holdings_service.get_holdings(&q.account_id, &base).await?;
-    Ok(Json(holdings))
+async fn link_education_goal(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(body): Json<LinkEducationGoalBody>) -> ApiResult<Json<EducationPlan>> {
+    let previous = state.education_service.get_education_plan(&id)?;
+    let linked = state.education_service.link_education_goal(&id, body.goal_id).await?;
+    record_audit(&state, NewAuditLogEntry::new("education_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&linked))).await;
+    Ok(Json(linked))
 }
 
-// Historical valuations endpoint
-#[derive(serde::Deserialize)]
-struct HistoryQuery { #[serde(rename = "accountId")] account_id: String, #[serde(rename = "startDate")] start_date: Option<String>, #[serde(rename = "endDate")] end_date: Option<String> }
+// ===================== Scripts =====================
 
-async fn get_historical_valuations(State(state): State<Arc<AppState>>, Query(q): Query<HistoryQuery>) -> ApiResult<Json<Vec<DailyAccountValuation>>> {
-    let start = match q.start_date {
-        Some(s) => Some(chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid startDate: {}", e))?),
-        None => None,
-    };
-    let end = match q.end_date {
-        Some(s) => Some(chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid endDate: {}", e))?),
-        None => None,
-    };
-    let vals = state.valuation_service.get_historical_valuations(&q.account_id, start, end)?;
-    Ok(Json(vals))
+async fn get_scripts(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<Script>>> {
+    Ok(Json(state.script_service.get_scripts()?))
 }
 
-// Latest valuations endpoint
-async fn get_latest_valuations(State(state): State<Arc<AppState>>, raw: RawQuery) -> ApiResult<Json<Vec<DailyAccountValuation>>> {
-    // Parse query manually for robustness (supports accountIds and accountIds[])
-    let mut ids: Vec<String> = Vec::new();
-    if let Some(qs) = raw.0 {
-        // Collect all values for both keys
-        if let Ok(pairs) = serde_urlencoded::from_str::<Vec<(String, String)>>(&qs) {
-            for (k, v) in pairs {
-                if k == "accountIds" || k == "accountIds[]" {
-                    ids.push(v);
-                }
-            }
-        }
-    }
-    if ids.is_empty() {
-        ids = state
-            .account_service
-            .get_active_accounts()?
-            .into_iter()
-            .map(|a| a.id)
-            .collect();
-    }
-    if ids.is_empty() { return Ok(Json(vec![])); }
-    let vals = state.valuation_service.get_latest_valuations(&ids)?;
-    Ok(Json(vals))
+async fn create_script(State(state): State<Arc<AppState>>, Json(script): Json<NewScript>) -> ApiResult<Json<Script>> {
+    let created = state.script_service.create_script(script).await?;
+    record_audit(&state, NewAuditLogEntry::new("script", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&Script>, Some(&created))).await;
+    Ok(Json(created))
 }
 
-// Portfolio update endpoints for web
-async fn update_portfolio(State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
-    // Incremental update: calculate holdings snapshots and append valuations for active accounts and TOTAL
-    let active = state.account_service.get_active_accounts()?;
-    let ids: Vec<String> = active.into_iter().map(|a| a.id).collect();
-    if let Err(e) = state.snapshot_service.calculate_holdings_snapshots(Some(&ids)).await {
-        tracing::warn!("calculate_holdings_snapshots failed: {}", e);
-    }
-    // Also refresh TOTAL
-    if let Err(e) = state.snapshot_service.calculate_total_portfolio_snapshots().await {
-        tracing::warn!("calculate_total_portfolio_snapshots failed: {}", e);
-    }
-    // Update valuations (incremental)
-    for id in ids.iter().chain(std::iter::once(&"TOTAL".to_string())) {
-        if let Err(e) = state.valuation_service.calculate_valuation_history(id, false).await {
-            tracing::warn!("calculate_valuation_history (incremental) failed for {}: {}", id, e);
-        }
-    }
+async fn update_script(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(script): Json<NewScript>) -> ApiResult<Json<Script>> {
+    let previous = state.script_service.get_script(&id)?;
+    let updated = state.script_service.update_script(&id, script).await?;
+    record_audit(&state, NewAuditLogEntry::new("script", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_script(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.script_service.get_script(&id)?;
+    state.script_service.delete_script(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("script", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), None::<&Script>)).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
-async fn recalculate_portfolio(State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
-    // Full recalculation of holdings snapshots for all accounts and TOTAL, then full valuation recompute
-    if let Err(e) = state.snapshot_service.force_recalculate_holdings_snapshots(None).await {
-        tracing::warn!("force_recalculate_holdings_snapshots failed: {}", e);
-    }
-    if let Err(e) = state.snapshot_service.calculate_total_portfolio_snapshots().await {
-        tracing::warn!("calculate_total_portfolio_snapshots failed: {}", e);
-    }
-    // Recompute valuations for all accounts (including TOTAL)
-    let active = state.account_service.get_active_accounts()?;
-    let mut ids: Vec<String> = active.into_iter().map(|a| a.id).collect();
-    ids.push("TOTAL".to_string());
-    for id in ids {
-        if let Err(e) = state.valuation_service.calculate_valuation_history(&id, true).await {
-            tracing::warn!("calculate_valuation_history (full) failed for {}: {}", id, e);
-        }
-    }
+// ===================== Automation rules =====================
+
+async fn get_automation_rules(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<AutomationRule>>> {
+    Ok(Json(state.automation_service.get_rules()?))
+}
+
+async fn create_automation_rule(State(state): State<Arc<AppState>>, Json(rule): Json<NewAutomationRule>) -> ApiResult<Json<AutomationRule>> {
+    let created = state.automation_service.create_rule(rule).await?;
+    record_audit(&state, NewAuditLogEntry::new("automation_rule", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&AutomationRule>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_automation_rule(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(rule): Json<NewAutomationRule>) -> ApiResult<Json<AutomationRule>> {
+    let previous = state.automation_service.get_rule(&id)?;
+    let updated = state.automation_service.update_rule(&id, rule).await?;
+    record_audit(&state, NewAuditLogEntry::new("automation_rule", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_automation_rule(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.automation_service.get_rule(&id)?;
+    state.automation_service.delete_rule(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("automation_rule", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), None::<&AutomationRule>)).await;
     Ok(StatusCode::NO_CONTENT)
 }
 
-// Performance endpoints
-#[derive(serde::Deserialize)]
-struct PerfBody {
-    #[serde(rename = "itemType")] item_type: String,
-    #[serde(rename = "itemId")] item_id: String,
-    #[serde(rename = "startDate")] start_date: Option<String>,
-    #[serde(rename = "endDate")] end_date: Option<String>,
+async fn get_activity_tags(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<ActivityTag>>> {
+    Ok(Json(state.automation_service.get_activity_tags()?))
 }
 
-async fn calculate_performance_history(State(state): State<Arc<AppState>>, Json(body): Json<PerfBody>) -> ApiResult<Json<PerformanceMetrics>> {
-    let start = match &body.start_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid startDate: {}", e))?), None => None };
-    let end = match &body.end_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid endDate: {}", e))?), None => None };
-    let metrics = state.performance_service.calculate_performance_history(&body.item_type, &body.item_id, start, end).await?;
-    Ok(Json(metrics))
+// ===================== Bank connections =====================
+
+async fn get_bank_connections(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<BankConnection>>> {
+    Ok(Json(state.connector_service.get_connections()?))
 }
 
-async fn calculate_performance_summary(State(state): State<Arc<AppState>>, Json(body): Json<PerfBody>) -> ApiResult<Json<PerformanceMetrics>> {
-    let start = match &body.start_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid startDate: {}", e))?), None => None };
-    let end = match &body.end_date { Some(s) => Some(chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|e| anyhow::anyhow!("Invalid endDate: {}", e))?), None => None };
-    let metrics = state.performance_service.calculate_performance_summary(&body.item_type, &body.item_id, start, end).await?;
-    Ok(Json(metrics))
+async fn create_bank_connection(State(state): State<Arc<AppState>>, Json(connection): Json<NewBankConnection>) -> ApiResult<Json<BankConnection>> {
+    let created = state.connector_service.create_connection(connection).await?;
+    record_audit(&state, NewAuditLogEntry::new("bank_connection", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&BankConnection>, Some(&created))).await;
+    Ok(Json(created))
 }
 
-// Income
-async fn get_income_summary(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<IncomeSummary>>> {
-    let items = state.income_service.get_income_summary()?;
-    Ok(Json(items))
+async fn update_bank_connection(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(connection): Json<NewBankConnection>) -> ApiResult<Json<BankConnection>> {
+    let previous = state.connector_service.get_connection(&id)?;
+    let updated = state.connector_service.update_connection(&id, connection).await?;
+    record_audit(&state, NewAuditLogEntry::new("bank_connection", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&updated))).await;
+    Ok(Json(updated))
 }
 
-// Goals endpoints
-async fn get_goals(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<Goal>>> {
-    let goals = state.goal_service.get_goals()?;
-    Ok(Json(goals))
+async fn delete_bank_connection(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.connector_service.get_connection(&id)?;
+    state.connector_service.delete_connection(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("bank_connection", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), None::<&BankConnection>)).await;
+    Ok(StatusCode::NO_CONTENT)
 }
 
-async fn create_goal(State(state): State<Arc<AppState>>, Json(goal): Json<NewGoal>) -> ApiResult<Json<Goal>> {
-    let g = state.goal_service.create_goal(goal).await?;
-    Ok(Json(g))
+async fn sync_bank_connection(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<ConnectorSyncResult>> {
+    let started_at = chrono::Utc::now().naive_utc();
+    let result = state.connector_service.sync_connection(&id).await?;
+    crate::main_lib::finish_connector_sync(&state, std::slice::from_ref(&result), started_at).await?;
+    Ok(Json(result))
 }
 
-async fn update_goal(State(state): State<Arc<AppState>>, Json(goal): Json<Goal>) -> ApiResult<Json<Goal>> {
-    let g = state.goal_service.update_goal(goal).await?;
-    Ok(Json(g))
+// ===================== API tokens =====================
+
+async fn get_api_tokens(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<ApiToken>>> {
+    Ok(Json(state.api_token_service.get_tokens()?))
 }
 
-async fn delete_goal(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<()> {
-    let _ = state.goal_service.delete_goal(id).await?;
-    Ok(())
+async fn create_api_token(State(state): State<Arc<AppState>>, Json(token): Json<NewApiToken>) -> ApiResult<Json<IssuedApiToken>> {
+    let issued = state.api_token_service.create_token(token).await?;
+    record_audit(&state, NewAuditLogEntry::new("api_token", &issued.token.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&ApiToken>, Some(&issued.token))).await;
+    Ok(Json(issued))
 }
 
-async fn load_goals_allocations(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<GoalsAllocation>>> {
-    let allocs = state.goal_service.load_goals_allocations()?;
-    Ok(Json(allocs))
+async fn revoke_api_token(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<ApiToken>> {
+    let previous = state.api_token_service.get_token(&id)?;
+    let revoked = state.api_token_service.revoke_token(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("api_token", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&revoked))).await;
+    Ok(Json(revoked))
 }
 
-async fn update_goal_allocations(State(state): State<Arc<AppState>>, Json(allocs): Json<Vec<GoalsAllocation>>) -> ApiResult<()> {
-    let _ = state.goal_service.upsert_goal_allocations(allocs).await?;
-    Ok(())
+async fn delete_api_token(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.api_token_service.get_token(&id)?;
+    state.api_token_service.delete_token(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("api_token", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), None::<&ApiToken>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ===================== Google Sheets exports =====================
+
+async fn get_sheet_exports(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<SheetExport>>> {
+    Ok(Json(state.sheet_export_service.get_exports()?))
+}
+
+async fn create_sheet_export(State(state): State<Arc<AppState>>, Json(export): Json<NewSheetExport>) -> ApiResult<Json<SheetExport>> {
+    let created = state.sheet_export_service.create_export(export).await?;
+    record_audit(&state, NewAuditLogEntry::new("sheet_export", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&SheetExport>, Some(&created))).await;
+    Ok(Json(created))
+}
+
+async fn update_sheet_export(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(export): Json<NewSheetExport>) -> ApiResult<Json<SheetExport>> {
+    let previous = state.sheet_export_service.get_export(&id)?;
+    let updated = state.sheet_export_service.update_export(&id, export).await?;
+    record_audit(&state, NewAuditLogEntry::new("sheet_export", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), Some(&updated))).await;
+    Ok(Json(updated))
+}
+
+async fn delete_sheet_export(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    let previous = state.sheet_export_service.get_export(&id)?;
+    state.sheet_export_service.delete_export(&id).await?;
+    record_audit(&state, NewAuditLogEntry::new("sheet_export", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&previous), None::<&SheetExport>)).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn push_sheet_export(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<SheetExportResult>> {
+    Ok(Json(state.sheet_export_service.push_export(&id).await?))
+}
+
+async fn has_google_sheets_credentials(State(state): State<Arc<AppState>>) -> ApiResult<Json<bool>> {
+    Ok(Json(state.sheet_export_service.has_credentials()?))
+}
+
+async fn set_google_sheets_credentials(State(state): State<Arc<AppState>>, Json(credentials): Json<GoogleCredentials>) -> ApiResult<StatusCode> {
+    state.sheet_export_service.set_credentials(credentials)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn delete_google_sheets_credentials(State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    state.sheet_export_service.delete_credentials()?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Onboarding
+async fn seed_initial_data(State(state): State<Arc<AppState>>, Json(plan): Json<OnboardingPlan>) -> ApiResult<Json<OnboardingResult>> {
+    let result = state.onboarding_service.seed_initial_data(plan).await?;
+    for account in &result.accounts {
+        record_audit(&state, NewAuditLogEntry::new("account", &account.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&wealthvn_core::accounts::Account>, Some(account))).await;
+    }
+    if let Some(goal) = &result.goal {
+        record_audit(&state, NewAuditLogEntry::new("goal", &goal.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Goal>, Some(goal))).await;
+    }
+    Ok(Json(result))
+}
+
+// Feature flags
+async fn get_feature_flags(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<FeatureFlagState>>> {
+    Ok(Json(state.feature_flag_service.list_flags()?))
+}
+
+#[derive(serde::Deserialize)]
+struct FeatureFlagBody { enabled: bool }
+
+async fn set_feature_flag(Path(flag): Path<String>, State(state): State<Arc<AppState>>, Json(body): Json<FeatureFlagBody>) -> ApiResult<Json<FeatureFlagState>> {
+    let flag: FeatureFlag = flag.parse()?;
+    Ok(Json(state.feature_flag_service.set_flag(flag, body.enabled).await?))
+}
+
+// Performance telemetry (timings collected locally while the performance_metrics flag is on)
+async fn get_performance_stats() -> ApiResult<Json<PerformanceStats>> {
+    Ok(Json(performance_metrics().stats()))
+}
+
+async fn reset_performance_stats() -> ApiResult<StatusCode> {
+    performance_metrics().reset();
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn get_service_readiness(State(state): State<Arc<AppState>>) -> ApiResult<Json<ServiceReadiness>> {
+    Ok(Json(crate::main_lib::service_readiness(&state)))
+}
+
+// Long-running operations
+async fn list_operations(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<OperationInfo>>> {
+    Ok(Json(state.operations.list()))
+}
+
+/// Asks a running export, import or resync to stop; it fails with an "operation cancelled" error
+async fn cancel_operation(State(state): State<Arc<AppState>>, Path(id): Path<String>) -> ApiResult<StatusCode> {
+    if !state.operations.cancel(&id) { return Err(crate::error::ApiError::NotFound); }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// Audit log (Data history screen)
+async fn query_audit_log(State(state): State<Arc<AppState>>, Query(q): Query<AuditLogQuery>) -> ApiResult<Json<AuditLogResponse>> {
+    let resp = state.audit_service.query_audit_log(q)?;
+    Ok(Json(resp))
 }
 
 // Exchange rates endpoints
@@ -310,11 +1876,16 @@ async fn search_activities(State(state): State<Arc<AppState>>, Json(body): Json<
 
 async fn create_activity(State(state): State<Arc<AppState>>, Json(activity): Json<NewActivity>) -> ApiResult<Json<wealthvn_core::activities::Activity>> {
     let created = state.activity_service.create_activity(activity).await?;
+    record_audit(&state, NewAuditLogEntry::new("activity", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+        .with_snapshots(None::<&wealthvn_core::activities::Activity>, Some(&created))).await;
     Ok(Json(created))
 }
 
 async fn update_activity(State(state): State<Arc<AppState>>, Json(activity): Json<ActivityUpdate>) -> ApiResult<Json<wealthvn_core::activities::Activity>> {
+    let previous = state.activity_service.get_activity(&activity.id).ok();
     let updated = state.activity_service.update_activity(activity).await?;
+    record_audit(&state, NewAuditLogEntry::new("activity", &updated.id, AuditAction::Update, AUDIT_ACTOR_USER)
+        .with_snapshots(previous.as_ref(), Some(&updated))).await;
     Ok(Json(updated))
 }
 
@@ -331,6 +1902,8 @@ async fn save_activities(
 
 async fn delete_activity(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<Json<wealthvn_core::activities::Activity>> {
     let deleted = state.activity_service.delete_activity(id).await?;
+    record_audit(&state, NewAuditLogEntry::new("activity", &deleted.id, AuditAction::Delete, AUDIT_ACTOR_USER)
+        .with_snapshots(Some(&deleted), None::<&wealthvn_core::activities::Activity>)).await;
     Ok(Json(deleted))
 }
 
@@ -338,7 +1911,10 @@ async fn delete_activity(Path(id): Path<String>, State(state): State<Arc<AppStat
 #[derive(serde::Deserialize)]
 struct ImportCheckBody { #[serde(rename = "accountId")] account_id: String, activities: Vec<ActivityImport> }
 
-async fn check_activities_import(State(state): State<Arc<AppState>>, Json(body): Json<ImportCheckBody>) -> ApiResult<Json<Vec<ActivityImport>>> {
+async fn check_activities_import(State(state): State<Arc<AppState>>, Json(mut body): Json<ImportCheckBody>) -> ApiResult<Json<Vec<ActivityImport>>> {
+    // Scripts edit the rows before they are checked, so the preview shows their changes
+    let report = state.script_service.run_import_row_hooks(&mut body.activities)?;
+    crate::main_lib::log_script_report("on_import_row", &report);
     let res = state.activity_service.check_activities_import(body.account_id, body.activities).await?;
     Ok(Json(res))
 }
@@ -347,13 +1923,166 @@ async fn check_activities_import(State(state): State<Arc<AppState>>, Json(body):
 struct ImportBody { #[serde(rename = "accountId")] account_id: String, activities: Vec<ActivityImport> }
 
 async fn import_activities(State(state): State<Arc<AppState>>, Json(body): Json<ImportBody>) -> ApiResult<Json<Vec<ActivityImport>>> {
-    let res = state.activity_service.import_activities(body.account_id, body.activities).await?;
+    let started_at = chrono::Utc::now().naive_utc();
+    let res = state.activity_service.import_activities(body.account_id.clone(), body.activities).await?;
+    record_audit(&state, NewAuditLogEntry::new("account", &body.account_id, AuditAction::Import, AUDIT_ACTOR_IMPORT)
+        .with_changes(serde_json::json!({ "importedCount": res.len() }))).await;
+    // Bill matches go first so a paid bill's category wins over categorization rules
+    if let Err(e) = state.bill_service.match_bill_payments().await {
+        tracing::warn!("Bill matching after import failed: {}", e);
+    }
+    if let Err(e) = state.private_loan_service.match_private_loan_repayments().await {
+        tracing::warn!("Private loan repayment matching after import failed: {}", e);
+    }
+    if let Err(e) = state.categorization_service.auto_categorize().await {
+        tracing::warn!("Auto-categorization after import failed: {}", e);
+    }
+    // A rejected import returns every row with its errors and saves nothing
+    let rejected = res.iter().any(|row| !row.is_valid || row.errors.as_ref().is_some_and(|errors| !errors.is_empty()));
+    let imported = if rejected { 0 } else { res.len() };
+    crate::main_lib::run_import_automations(&state, &body.account_id, imported, started_at).await;
     Ok(Json(res))
 }
 
+// Structured payloads handed over by other apps go through the same check and dedup as a file import
+async fn preview_import_payload(State(state): State<Arc<AppState>>, Json(payload): Json<ImportPayload>) -> ApiResult<Json<ImportPayloadPreview>> {
+    Ok(Json(state.import_payload_service.preview_payload(payload).await?))
+}
+
+async fn import_payload(State(state): State<Arc<AppState>>, Json(payload): Json<ImportPayload>) -> ApiResult<Json<ImportPayloadResult>> {
+    let source = payload.source.clone();
+    let started_at = chrono::Utc::now().naive_utc();
+    let result = state.import_payload_service.import_payload(payload).await?;
+    crate::main_lib::finish_payload_import(&state, &result, started_at).await?;
+    if let Some(account_id) = result.account_id.as_deref().filter(|_| result.imported > 0) {
+        record_audit(&state, NewAuditLogEntry::new("account", account_id, AuditAction::Import, AUDIT_ACTOR_IMPORT)
+            .with_changes(serde_json::json!({ "importedCount": result.imported, "source": source }))).await;
+    }
+    Ok(Json(result))
+}
+
+// E-invoice XML becomes an activities payload: the expense and its VAT as separate rows
+#[derive(serde::Deserialize)]
+struct EInvoiceBody { #[serde(rename = "accountId")] account_id: String, xml: String }
+
+async fn preview_einvoice_import(state: State<Arc<AppState>>, Json(body): Json<EInvoiceBody>) -> ApiResult<Json<ImportPayloadPreview>> {
+    preview_import_payload(state, Json(ImportPayload::from_einvoice_xml(&body.xml, &body.account_id)?)).await
+}
+
+async fn import_einvoice(state: State<Arc<AppState>>, Json(body): Json<EInvoiceBody>) -> ApiResult<Json<ImportPayloadResult>> {
+    import_payload(state, Json(ImportPayload::from_einvoice_xml(&body.xml, &body.account_id)?)).await
+}
+
+// Broker exports and CSVs read through the account's saved column mapping, checked like a payload
+async fn preview_activity_import(State(state): State<Arc<AppState>>, Json(request): Json<ActivityImportRequest>) -> ApiResult<Json<ImportPayloadPreview>> {
+    Ok(Json(state.activity_import_service.preview_activity_import(request).await?))
+}
+
+async fn commit_activity_import(State(state): State<Arc<AppState>>, Json(request): Json<ActivityImportRequest>) -> ApiResult<Json<ImportPayloadResult>> {
+    let source = request.format.label();
+    let started_at = chrono::Utc::now().naive_utc();
+    let result = state.activity_import_service.commit_activity_import(request).await?;
+    crate::main_lib::finish_payload_import(&state, &result, started_at).await?;
+    if let Some(account_id) = result.account_id.as_deref().filter(|_| result.imported > 0) {
+        record_audit(&state, NewAuditLogEntry::new("account", account_id, AuditAction::Import, AUDIT_ACTOR_IMPORT)
+            .with_changes(serde_json::json!({ "importedCount": result.imported, "source": source }))).await;
+    }
+    Ok(Json(result))
+}
+
+#[derive(serde::Deserialize)]
+struct LedgerExportQuery { format: Option<String>, #[serde(rename = "accountIds")] account_ids: Option<String>, #[serde(rename = "operationId")] operation_id: Option<String>, #[serde(rename = "timeoutSecs")] timeout_secs: Option<u64> }
+
+async fn export_ledger(State(state): State<Arc<AppState>>, Query(q): Query<LedgerExportQuery>) -> ApiResult<Json<LedgerExport>> {
+    let format = match q.format.as_deref() { Some(format) => format.parse::<LedgerFormat>()?, None => LedgerFormat::Beancount };
+    let ids: Option<Vec<String>> = q.account_ids.map(|ids| ids.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect());
+    Ok(Json(state.ledger_export_service.export_ledger(format, ids.as_deref())?))
+}
+
+/// Bytes a streaming export buffers before handing them to the response
+const EXPORT_BODY_CHUNK_BYTES: usize = 64 * 1024;
+/// Body chunks queued between the export thread and the client before the export waits
+const EXPORT_BODY_QUEUE: usize = 8;
+
+/// `Write` end of a streaming response body; the export thread blocks while the client catches up
+struct BodyWriter { tx: tokio::sync::mpsc::Sender<std::io::Result<axum::body::Bytes>>, buf: Vec<u8> }
+
+impl std::io::Write for BodyWriter {
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(data);
+        if self.buf.len() >= EXPORT_BODY_CHUNK_BYTES { self.flush()?; }
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() { return Ok(()); }
+        let chunk = axum::body::Bytes::from(std::mem::take(&mut self.buf));
+        self.tx.blocking_send(Ok(chunk)).map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "client went away"))
+    }
+}
+
+/// Runs `export` on the blocking pool and streams what it writes as the response body,
+/// publishing its progress to WebSocket clients. A failure part way through aborts the body;
+/// cancelling `operation`, or the client going away, stops the export at its next chunk.
+fn stream_export<F>(state: Arc<AppState>, operation: OperationGuard, content_type: &'static str, file_name: String, export: F) -> Response
+where F: FnOnce(&AppState, &mut dyn std::io::Write, &dyn Fn(&TransferProgress), &CancellationToken) -> wealthvn_core::errors::Result<()> + Send + 'static {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(EXPORT_BODY_QUEUE);
+    let name = file_name.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut out = BodyWriter { tx: tx.clone(), buf: Vec::with_capacity(EXPORT_BODY_CHUNK_BYTES) };
+        let progress = |p: &TransferProgress| state.events.publish(ServerEvent::with_payload(TRANSFER_PROGRESS, serde_json::json!(p)));
+        let written = export(&state, &mut out, &progress, operation.token()).and_then(|()| Ok(std::io::Write::flush(&mut out)?));
+        if let Err(e) = written {
+            tracing::warn!("Streaming export of {} failed: {}", name, e);
+            let _ = tx.blocking_send(Err(std::io::Error::other(e.to_string())));
+        }
+        drop(operation);
+    });
+    let body = axum::body::Body::from_stream(futures::stream::poll_fn(move |cx| rx.poll_recv(cx)));
+    Response::builder()
+        .header(axum::http::header::CONTENT_TYPE, content_type)
+        .header(axum::http::header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name))
+        .body(body)
+        .unwrap_or_else(|_| axum::response::IntoResponse::into_response(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+/// Registers a cancellable operation under the client's `operationId`, if it sent one
+fn start_operation(state: &AppState, operation_id: Option<String>, kind: &str, timeout_secs: Option<u64>) -> ApiResult<OperationGuard> {
+    Ok(state.operations.start(operation_id, kind, timeout_secs.map(std::time::Duration::from_secs))?)
+}
+
+#[derive(serde::Deserialize)]
+struct DataExportQuery { dataset: String, format: Option<String>, #[serde(rename = "operationId")] operation_id: Option<String>, #[serde(rename = "timeoutSecs")] timeout_secs: Option<u64> }
+
+async fn export_data(State(state): State<Arc<AppState>>, Query(q): Query<DataExportQuery>) -> ApiResult<Response> {
+    let dataset = q.dataset.parse::<ExportDataset>()?;
+    let format = match q.format.as_deref() { Some(format) => format.parse::<ExportFileFormat>()?, None => ExportFileFormat::Csv };
+    let operation = start_operation(&state, q.operation_id, OPERATION_EXPORT, q.timeout_secs)?;
+    Ok(stream_export(state, operation, format.content_type(), export_file_name(dataset, format), move |state, out, progress, cancel| {
+        state.data_transfer_service.export_dataset(dataset, format, out, progress, cancel).map(|_| ())
+    }))
+}
+
+async fn download_ledger(State(state): State<Arc<AppState>>, Query(q): Query<LedgerExportQuery>) -> ApiResult<Response> {
+    let format = match q.format.as_deref() { Some(format) => format.parse::<LedgerFormat>()?, None => LedgerFormat::Beancount };
+    let ids: Option<Vec<String>> = q.account_ids.map(|ids| ids.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect());
+    let file_name = format!("wealthvn-{}.{}", chrono::Utc::now().format("%Y-%m-%d"), format.file_extension());
+    let operation = start_operation(&state, q.operation_id, OPERATION_LEDGER_EXPORT, q.timeout_secs)?;
+    Ok(stream_export(state, operation, "text/plain; charset=utf-8", file_name, move |state, out, progress, cancel| {
+        state.ledger_export_service.export_ledger_to(format, ids.as_deref(), out, progress, cancel).map(|_| ())
+    }))
+}
+
 #[derive(serde::Deserialize)]
 struct MappingQuery { #[serde(rename = "accountId")] account_id: String }
 
+#[derive(serde::Deserialize)]
+struct PendingSettlementsQuery { #[serde(rename = "accountId")] account_id: Option<String> }
+
+async fn get_pending_settlements(State(state): State<Arc<AppState>>, Query(q): Query<PendingSettlementsQuery>) -> ApiResult<Json<Vec<PendingSettlement>>> {
+    Ok(Json(state.activity_service.get_pending_settlements(q.account_id)?))
+}
+
 async fn get_account_import_mapping(State(state): State<Arc<AppState>>, Query(q): Query<MappingQuery>) -> ApiResult<Json<ImportMappingData>> {
     let res = state.activity_service.get_import_mapping(q.account_id)?;
     Ok(Json(res))
@@ -386,6 +2115,28 @@ async fn update_market_data_provider_settings(State(state): State<Arc<AppState>>
     Ok(Json(updated))
 }
 
+// Provider credentials (OS keychain)
+async fn get_provider_credential_statuses(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<ProviderCredentialStatus>>> {
+    Ok(Json(state.market_data_service.get_provider_credential_statuses()?))
+}
+
+#[derive(serde::Deserialize)]
+struct ProviderCredentialBody { #[serde(rename = "apiKey")] api_key: Option<String> }
+
+async fn set_provider_credential(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(body): Json<ProviderCredentialBody>) -> ApiResult<StatusCode> {
+    state.market_data_service.set_provider_credential(&id, body.api_key.as_deref().unwrap_or_default()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn test_provider_credential(Path(id): Path<String>, State(state): State<Arc<AppState>>, Json(body): Json<ProviderCredentialBody>) -> ApiResult<Json<ProviderCredentialTestResult>> {
+    Ok(Json(state.market_data_service.test_provider_credential(&id, body.api_key).await?))
+}
+
+async fn remove_provider_credential(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<StatusCode> {
+    state.market_data_service.remove_provider_credential(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 // Contribution limits
 async fn get_contribution_limits(State(state): State<Arc<AppState>>) -> ApiResult<Json<Vec<ContributionLimit>>> {
     let limits = state.limits_service.get_contribution_limits()?;
@@ -455,26 +2206,65 @@ async fn get_quote_history(State(state): State<Arc<AppState>>, Query(q): Query<Q
 async fn update_quote(Path(symbol): Path<String>, State(state): State<Arc<AppState>>, Json(mut quote): Json<Quote>) -> ApiResult<()> {
     // Ensure symbol matches body
     quote.symbol = symbol;
+    let symbol = quote.symbol.clone();
     state.market_data_service.update_quote(quote).await?;
+    state.events.publish(ServerEvent::quotes_updated(Some(vec![symbol])));
     Ok(())
 }
 
 async fn delete_quote(Path(id): Path<String>, State(state): State<Arc<AppState>>) -> ApiResult<()> {
     state.market_data_service.delete_quote(&id).await?;
+    state.events.publish(ServerEvent::quotes_updated(None));
     Ok(())
 }
 
 #[derive(serde::Deserialize)]
-struct SyncBody { symbols: Option<Vec<String>>, #[serde(rename = "refetchAll")] refetch_all: bool }
+struct ImportQuotesBody { quotes: Vec<QuoteImport>, #[serde(rename = "overwriteExisting")] overwrite_existing: bool, #[serde(rename = "operationId")] operation_id: Option<String>, #[serde(rename = "timeoutSecs")] timeout_secs: Option<u64> }
+
+/// One chunk of a quote file; the web app sends large files a chunk at a time
+async fn import_quotes_csv(State(state): State<Arc<AppState>>, Json(body): Json<ImportQuotesBody>) -> ApiResult<Json<Vec<QuoteImport>>> {
+    let operation = start_operation(&state, body.operation_id, OPERATION_QUOTE_IMPORT, body.timeout_secs)?;
+    let imported = operation.token().run(state.market_data_service.import_quotes_from_csv(body.quotes, body.overwrite_existing)).await?;
+    let mut symbols: Vec<String> = imported.iter().filter(|quote| matches!(quote.validation_status, ImportValidationStatus::Valid)).map(|quote| quote.symbol.clone()).collect();
+    symbols.sort();
+    symbols.dedup();
+    if !symbols.is_empty() {
+        state.events.publish(ServerEvent::quotes_updated(Some(symbols)));
+    }
+    Ok(Json(imported))
+}
+
+#[derive(serde::Deserialize)]
+struct SyncBody { symbols: Option<Vec<String>>, #[serde(rename = "refetchAll")] refetch_all: bool, #[serde(rename = "operationId")] operation_id: Option<String>, #[serde(rename = "timeoutSecs")] timeout_secs: Option<u64> }
 
 async fn sync_market_data(State(state): State<Arc<AppState>>, Json(body): Json<SyncBody>) -> ApiResult<()> {
-    // Prefer targeted resync when symbols provided; otherwise do global sync/resync based on refetch_all
-    if let Some(symbols) = body.symbols.clone() {
-        let _ = state.market_data_service.resync_market_data(Some(symbols)).await?;
-    } else if body.refetch_all {
-        let _ = state.market_data_service.resync_market_data(None).await?;
+    // Prefer targeted resync when symbols provided; otherwise do global sync/resync based on refetch_all.
+    // Resyncs refetch whole histories, so they run as operations the client can cancel.
+    let resync = body.symbols.is_some() || body.refetch_all;
+    let operation = if resync { Some(start_operation(&state, body.operation_id, OPERATION_MARKET_DATA_RESYNC, body.timeout_secs)?) } else { None };
+    state.events.publish(ServerEvent::new(MARKET_SYNC_START));
+    let synced = if let Some(operation) = operation.as_ref() {
+        operation.token().run(state.market_data_service.resync_market_data(body.symbols.clone())).await
     } else {
-        let _ = state.market_data_service.sync_market_data().await?;
+        state.market_data_service.sync_market_data().await
+    };
+    match synced {
+        Ok((_, failed_syncs)) => {
+            state.events.publish(ServerEvent::with_payload(MARKET_SYNC_COMPLETE, serde_json::json!({ "failed_syncs": failed_syncs })));
+            state.events.publish(ServerEvent::quotes_updated(body.symbols.clone()));
+        }
+        Err(e) => {
+            state.events.publish(ServerEvent::with_payload(MARKET_SYNC_ERROR, serde_json::json!(e.to_string())));
+            return Err(e.into());
+        }
+    }
+    match crate::main_lib::run_quote_update_scripts(&state) {
+        Ok(report) => crate::main_lib::log_script_report("on_quote_update", &report),
+        Err(e) => tracing::warn!("on_quote_update scripts failed: {}", e),
+    }
+    match crate::main_lib::run_quote_automations(&state).await {
+        Ok(report) => crate::main_lib::log_automation_report("QUOTE_MOVED", &report),
+        Err(e) => tracing::warn!("QUOTE_MOVED rules failed: {}", e),
     }
     Ok(())
 }
@@ -522,16 +2312,41 @@ pub fn app_router(state: Arc<AppState>, config: &Config) -> Router {
     };
 
     let openapi = ApiDoc::openapi();
+    let schema = graphql::build_schema(state.clone());
 
     let api = Router::new()
         .route("/healthz", get(healthz))
         .route("/readyz", get(readyz))
         .route("/accounts", get(list_accounts).post(create_account))
         .route("/accounts/:id", put(update_account).delete(delete_account))
+        .route("/accounts/:id/cash", get(get_account_cash))
+        .route("/accounts/:id/unallocated-balance", get(get_unallocated_balance))
+        .route("/accounts/:id/unallocated-cash", get(get_unallocated_cash))
+        .route("/cash/deposit", post(deposit_cash))
+        .route("/cash/withdraw", post(withdraw_cash))
+        .route("/account-groups", get(list_account_groups).post(create_account_group))
+        .route("/account-groups/:id", put(update_account_group).delete(delete_account_group))
+        .route("/account-groups/:id/valuation", get(get_account_group_valuation))
+        .route("/account-groups/:id/unallocated-balance", get(get_account_group_unallocated_balance))
+        .route("/account-groups/:id/allocations", post(allocate_goal_to_account_group))
         .route("/settings", get(get_settings).put(update_settings))
+        .route("/settings/export", get(export_settings))
+        .route("/settings/import", post(import_settings))
+        .route("/settings/:key", get(get_setting).put(set_setting))
+        .route("/i18n/messages", get(get_message_catalog))
+        .route("/audit-log", get(query_audit_log))
+        .route("/onboarding/seed", post(seed_initial_data))
+        .route("/feature-flags", get(get_feature_flags))
+        .route("/feature-flags/:flag", put(set_feature_flag))
+        .route("/telemetry/performance", get(get_performance_stats).delete(reset_performance_stats))
+        .route("/telemetry/readiness", get(get_service_readiness))
+        .route("/operations", get(list_operations))
+        .route("/operations/:id/cancel", post(cancel_operation))
         .route("/holdings", get(get_holdings))
         .route("/valuations/history", get(get_historical_valuations))
         .route("/valuations/latest", get(get_latest_valuations))
+        .route("/valuations/account-history", get(get_account_valuation_history))
+        .route("/valuations/recompute", post(recompute_valuations))
         .route("/portfolio/update", post(update_portfolio))
         .route("/portfolio/recalculate", post(recalculate_portfolio))
         .route("/performance/history", post(calculate_performance_history))
@@ -543,14 +2358,24 @@ pub fn app_router(state: Arc<AppState>, config: &Config) -> Router {
         .route("/activities/search", post(search_activities))
         .route("/activities", post(create_activity).put(update_activity))
         .route("/activities/bulk", post(save_activities))
+        .route("/activities/pending-settlements", get(get_pending_settlements))
         .route("/activities/:id", delete(delete_activity))
         .route("/activities/import/check", post(check_activities_import))
         .route("/activities/import", post(import_activities))
         .route("/activities/import/mapping", get(get_account_import_mapping).post(save_account_import_mapping))
+        .route("/import-payloads/preview", post(preview_import_payload))
+        .route("/import-payloads", post(import_payload))
+        .route("/exports/ledger", get(export_ledger))
+        .route("/exports/ledger/download", get(download_ledger))
+        .route("/exports/data", get(export_data))
         .route("/providers", get(get_market_data_providers))
         .route("/providers/settings", get(get_market_data_providers_settings).put(update_market_data_provider_settings))
+        .route("/providers/credentials", get(get_provider_credential_statuses))
+        .route("/providers/:id/credential", put(set_provider_credential).delete(remove_provider_credential))
+        .route("/providers/:id/credential/test", post(test_provider_credential))
         .route("/market-data/search", get(search_symbol))
         .route("/market-data/quotes/history", get(get_quote_history))
+        .route("/market-data/quotes/import", post(import_quotes_csv))
         .route("/market-data/quotes/:symbol", put(update_quote))
         .route("/market-data/quotes/id/:id", delete(delete_quote))
         .route("/market-data/sync", post(sync_market_data))
@@ -562,8 +2387,123 @@ pub fn app_router(state: Arc<AppState>, config: &Config) -> Router {
         .route("/assets/data-source/:id", put(update_asset_data_source))
         .route("/secrets", post(set_secret).get(get_secret).delete(delete_secret))
         .route("/goals/allocations", get(load_goals_allocations).post(update_goal_allocations))
+        .route("/goals/allocations/bulk", post(bulk_insert_allocations))
+        .route("/goals/export", get(export_goal_plan))
+        .route("/goals/import", post(import_goal_plan))
+        .route("/goals/allocations/rebalance", get(suggest_goal_rebalance))
+        .route("/goals/allocations/distribute", get(distribute_goal_deposit))
+        .route("/goals/allocations/:id/preview", post(preview_allocation_change))
         .route("/goals", get(get_goals).post(create_goal).put(update_goal))
+        .route("/goals/emergency-fund", get(get_emergency_fund_progress))
+        .route("/goals/sinking-funds", get(get_sinking_fund_progress))
+        .route("/goals/net-worth", get(get_net_worth_goal_progress))
+        .route("/goals/debt-payoff", get(get_debt_payoff_progress))
+        .route("/goals/net-worth/history", get(get_net_worth_history))
+        .route("/summaries/monthly", get(get_monthly_summaries_handler))
+        .route("/goals/:id/progress", get(get_goal_progress))
+        .route("/goals/:id/progress/history", get(get_goal_progress_history))
+        .route("/goals/:id/funding-requirement", get(get_goal_funding_requirement))
         .route("/goals/:id", delete(delete_goal))
+        .route("/goals/:id/archive", post(archive_goal))
+        .route("/goals/:id/restore", post(restore_goal))
+        .route("/budgets/categories", get(get_budget_categories).post(create_budget_category))
+        .route("/budgets/categories/:id", put(update_budget_category).delete(delete_budget_category))
+        .route("/budgets/amounts", get(get_budget_month_amounts).put(set_budget_month_amount))
+        .route("/budgets/categorize", post(categorize_activities))
+        .route("/budgets/activity-categories", get(get_activity_categories))
+        .route("/budgets/progress", get(get_budget_progress))
+        .route("/budgets/report", get(get_budget_report))
+        .route("/categorization/rules", get(get_categorization_rules).post(create_categorization_rule))
+        .route("/categorization/rules/:id", put(update_categorization_rule).delete(delete_categorization_rule))
+        .route("/categorization/run", post(auto_categorize_activities))
+        .route("/categorization/recategorize", post(recategorize_all_activities))
+        .route("/forecast/cash-flows", get(get_planned_cash_flows).post(create_planned_cash_flow))
+        .route("/forecast/cash-flows/:id", put(update_planned_cash_flow).delete(delete_planned_cash_flow))
+        .route("/forecast", post(get_cash_flow_forecast))
+        .route("/income-sources", get(get_income_sources).post(create_income_source))
+        .route("/income-sources/variance", get(get_income_variance))
+        .route("/income-sources/savings-rate", get(get_savings_rate))
+        .route("/income-sources/:id", put(update_income_source).delete(delete_income_source))
+        .route("/bills", get(get_bills).post(create_bill))
+        .route("/bills/reminders", get(get_bill_reminders))
+        .route("/bills/payments", get(get_bill_payments).post(mark_bill_paid))
+        .route("/bills/payments/:id", delete(delete_bill_payment))
+        .route("/bills/match", post(match_bill_payments))
+        .route("/bills/:id", put(update_bill).delete(delete_bill))
+        .route("/envelopes", get(get_account_envelopes).post(create_envelope))
+        .route("/envelopes/transfers", get(get_envelope_transfers).post(transfer_envelope_funds))
+        .route("/envelopes/:id", put(rename_envelope).delete(archive_envelope))
+        .route("/loans", get(get_loans).post(create_loan))
+        .route("/loans/prepayments", get(get_loan_prepayments).post(add_loan_prepayment))
+        .route("/loans/prepayments/:id", delete(delete_loan_prepayment))
+        .route("/loans/rate-resets", get(get_loan_rate_resets).post(add_loan_rate_reset))
+        .route("/loans/rate-resets/:id", delete(delete_loan_rate_reset))
+        .route("/loans/:id", put(update_loan).delete(delete_loan))
+        .route("/loans/:id/schedule", get(get_loan_schedule))
+        .route("/loans/:id/stress-test", get(stress_test_loan))
+        .route("/properties", get(get_properties).post(create_property))
+        .route("/properties/appraisals", get(get_property_appraisals).post(add_property_appraisal))
+        .route("/properties/appraisals/:id", delete(delete_property_appraisal))
+        .route("/properties/:id", get(get_property).put(update_property).delete(delete_property))
+        .route("/bonds", get(get_bonds).post(create_bond))
+        .route("/bonds/payments", get(get_bond_payments))
+        .route("/bonds/:id", get(get_bond).put(update_bond).delete(delete_bond))
+        .route("/term-deposits", get(get_term_deposits).post(create_term_deposit))
+        .route("/term-deposits/maturities", get(get_upcoming_maturities))
+        .route("/term-deposits/:id", get(get_term_deposit).put(update_term_deposit).delete(delete_term_deposit))
+        .route("/recurring-rules", get(list_recurring_rules).post(create_recurring_rule))
+        .route("/recurring-rules/:id", delete(delete_recurring_rule))
+        .route("/recurring-rules/:id/pause", post(pause_recurring_rule))
+        .route("/recurring-rules/:id/resume", post(resume_recurring_rule))
+        .route("/reports/net-worth", get(get_net_worth_statement))
+        .route("/reports/net-worth/history", get(get_net_worth_statement_history))
+        .route("/dividends", post(save_activity_dividend))
+        .route("/dividends/summary", get(get_dividend_summary))
+        .route("/dividends/:activity_id", get(get_activity_dividend).delete(delete_activity_dividend))
+        .route("/esop-grants", get(get_esop_grants).post(create_esop_grant))
+        .route("/esop-grants/vestings", get(get_upcoming_vestings))
+        .route("/esop-grants/:id", get(get_esop_grant).put(update_esop_grant).delete(delete_esop_grant))
+        .route("/sip-plans", get(get_sip_plans).post(create_sip_plan))
+        .route("/benchmarks", get(get_benchmarks).post(create_benchmark))
+        .route("/benchmarks/:id", delete(delete_benchmark))
+        .route("/benchmarks/:id/comparison", get(compare_to_benchmark))
+        .route("/sip-plans/:id", get(get_sip_plan).put(update_sip_plan).delete(delete_sip_plan))
+        .route("/private-loans", get(get_private_loans).post(create_private_loan))
+        .route("/private-loans/match", post(match_private_loan_repayments))
+        .route("/private-loans/repayments", post(record_private_loan_repayment))
+        .route("/private-loans/repayments/:id", delete(delete_private_loan_repayment))
+        .route("/private-loans/:id", get(get_private_loan).put(update_private_loan).delete(delete_private_loan))
+        .route("/bonus-plans", get(get_bonus_plans).post(confirm_bonus_plan))
+        .route("/bonus-plans/propose", post(propose_bonus_plan))
+        .route("/bonus-plans/:id", get(get_bonus_plan).delete(delete_bonus_plan))
+        .route("/bonus-plans/:id/fund-envelopes", post(fund_bonus_plan_envelopes))
+        .route("/pension/estimate", post(estimate_pension_handler))
+        .route("/deposit-rates", get(get_deposit_rates))
+        .route("/deposit-rates/refresh", post(refresh_deposit_rates))
+        .route("/deposit-rates/suggestions", get(get_rollover_suggestions))
+        .route("/deposit-rates/ladder", post(confirm_deposit_ladder))
+        .route("/deposit-rates/ladder/propose", post(propose_deposit_ladder))
+        .route("/education-plans", get(get_education_plans).post(create_education_plan))
+        .route("/education-plans/:id", put(update_education_plan).delete(delete_education_plan))
+        .route("/education-plans/:id/projection", get(project_education_costs))
+        .route("/education-plans/:id/goal", post(link_education_goal))
+        .route("/scripts", get(get_scripts).post(create_script))
+        .route("/scripts/:id", put(update_script).delete(delete_script))
+        .route("/automation-rules", get(get_automation_rules).post(create_automation_rule))
+        .route("/automation-rules/:id", put(update_automation_rule).delete(delete_automation_rule))
+        .route("/activity-tags", get(get_activity_tags))
+        .route("/bank-connections", get(get_bank_connections).post(create_bank_connection))
+        .route("/bank-connections/:id", put(update_bank_connection).delete(delete_bank_connection))
+        .route("/bank-connections/:id/sync", post(sync_bank_connection))
+        .route("/api-tokens", get(get_api_tokens).post(create_api_token))
+        .route("/api-tokens/:id", delete(delete_api_token))
+        .route("/api-tokens/:id/revoke", post(revoke_api_token))
+        .route("/sheet-exports", get(get_sheet_exports).post(create_sheet_export))
+        .route("/sheet-exports/:id", put(update_sheet_export).delete(delete_sheet_export))
+        .route("/sheet-exports/:id/push", post(push_sheet_export))
+        .route("/google-sheets/credentials", get(has_google_sheets_credentials).put(set_google_sheets_credentials).delete(delete_google_sheets_credentials))
+        .route("/graphql", post(graphql::graphql_handler))
+        .route("/graphql/schema", get(graphql::graphql_sdl))
         // Addons (web mode)
         .route("/addons/installed", get(list_installed_addons_web))
         .route("/addons/install-zip", post(install_addon_zip_web))
@@ -580,10 +2520,13 @@ pub fn app_router(state: Arc<AppState>, config: &Config) -> Router {
         .route("/addons/store/install-from-staging", post(install_addon_from_staging_web))
         .route("/addons/store/staging", delete(clear_addon_staging_web));
 
+    let api = api.merge(token_router(&state, config.api_token.as_deref()));
+
     Router::new()
         .nest("/api/v1", api)
         .route("/openapi.json", get(|| async { Json(openapi) }))
         .with_state(state)
+        .layer(Extension(schema))
         .layer(cors)
         .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .layer(PropagateRequestIdLayer::x_request_id())
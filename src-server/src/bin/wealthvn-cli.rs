@@ -0,0 +1,1066 @@
+//! Headless command line for servers and NAS boxes without the desktop UI.
+//! Reads the same `WF_*` environment (or `.env`) as the web server.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use clap::{Parser, Subcommand};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use tracing_subscriber::EnvFilter;
+use wealthvn_core::{
+    accounts::AccountServiceTrait,
+    activities::ActivityImport,
+    api_tokens::{ApiTokenScope, NewApiToken},
+    automations::{AutomationRunReport, NewAutomationRule},
+    data_transfer::{ExportDataset, ExportFileFormat, TransferProgress},
+    db,
+    goals::{
+        goals_model::Goal, EmergencyFundProgress, NetWorthGoalProgress, NetWorthPoint,
+        SinkingFundProgress,
+    },
+    import_payload::{ImportPayload, ImportPayloadPreview},
+    ledger::LedgerFormat,
+    operations::CancellationToken,
+    scripting::ScriptRunReport,
+    utils::number_format::{parse_localized_decimal, NumberLocale},
+};
+use wealthvn_server::{
+    build_state, config::Config, finish_payload_import, mcp, push_sheet_exports,
+    run_import_automations, run_quote_automations, run_quote_update_scripts, sync_bank_connections,
+    update_portfolio, AppState,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "wealthvn-cli",
+    version,
+    about = "Manage a WealthVN database without the UI"
+)]
+struct Cli {
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Import activities into an account from a CSV file
+    Import {
+        /// Account to import into
+        #[arg(long)]
+        account: String,
+        /// CSV with columns date, symbol, activityType, quantity, unitPrice, currency, fee, amount, comment
+        file: PathBuf,
+        /// How numbers are written: en, vi or auto; defaults to the account's import profile
+        #[arg(long)]
+        number_locale: Option<String>,
+    },
+    /// Import an activities or quotes JSON payload handed over by another app
+    Payload {
+        /// Check and dedup the rows without importing them
+        #[arg(long)]
+        preview: bool,
+        /// JSON with `version`, `kind` and the `activities` or `quotes` rows, or an e-invoice
+        /// `.xml` file
+        file: PathBuf,
+        /// Account an e-invoice XML file is imported into
+        #[arg(long)]
+        account: Option<String>,
+    },
+    /// Write accounts and activities as a Beancount or ledger-cli journal
+    Ledger {
+        /// `beancount` or `ledger`
+        #[arg(long, default_value = "beancount")]
+        format: String,
+        /// Only these accounts; repeat for several
+        #[arg(long)]
+        account: Vec<String>,
+        /// File to write; the journal goes to stdout without it
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Write accounts, activities, goals, portfolio history or quotes as CSV or JSON
+    Export {
+        /// `accounts`, `activities`, `goals`, `portfolio-history` or `quotes`
+        #[arg(long)]
+        dataset: String,
+        /// `csv` or `json`
+        #[arg(long, default_value = "csv")]
+        format: String,
+        /// File to write; the rows go to stdout without it
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Copy the database to the backups folder next to it
+    Backup,
+    /// Print accounts, net worth and goal progress
+    Report,
+    /// Work with goals
+    Goal {
+        #[command(subcommand)]
+        command: GoalCommand,
+    },
+    /// Work with market quotes
+    Quote {
+        #[command(subcommand)]
+        command: QuoteCommand,
+    },
+    /// Work with bank and broker connections
+    Bank {
+        #[command(subcommand)]
+        command: BankCommand,
+    },
+    /// Work with Google Sheets exports
+    Sheets {
+        #[command(subcommand)]
+        command: SheetsCommand,
+    },
+    /// Manage tokens for the server's dashboard, report and import routes
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+    /// Manage automation rules
+    Rule {
+        #[command(subcommand)]
+        command: RuleCommand,
+    },
+    /// Serve read-only tools to an AI assistant over stdio (Model Context Protocol)
+    Mcp,
+}
+
+#[derive(Subcommand)]
+enum GoalCommand {
+    /// List goals
+    List,
+}
+
+#[derive(Subcommand)]
+enum BankCommand {
+    /// Pull new transactions from every connection that is due
+    Sync,
+}
+
+#[derive(Subcommand)]
+enum SheetsCommand {
+    /// Push every export that is due to its sheet
+    Push,
+}
+
+#[derive(Subcommand)]
+enum TokenCommand {
+    /// List issued tokens
+    List,
+    /// Issue a token and print its secret, which is not shown again
+    Create {
+        #[arg(long)]
+        name: String,
+        /// `read-only`, `reports` or `import`; repeat for several
+        #[arg(long = "scope", required = true)]
+        scopes: Vec<String>,
+        /// Days until the token stops working; never expires without it
+        #[arg(long)]
+        expires_in_days: Option<i64>,
+    },
+    /// Stop a token from working
+    Revoke { id: String },
+}
+
+#[derive(Subcommand)]
+enum RuleCommand {
+    /// List rules
+    List,
+    /// Add a rule from a JSON file with `name`, `trigger`, `actions` and `isEnabled`
+    Add { file: PathBuf },
+    /// Delete a rule
+    Delete { id: String },
+}
+
+#[derive(Subcommand)]
+enum QuoteCommand {
+    /// Fetch the latest quotes and update valuations
+    Sync,
+    /// Import quotes from a CSV with columns symbol, date, open, high, low, close, volume, currency
+    Import {
+        /// Replace quotes already stored for the same symbol and day
+        #[arg(long)]
+        overwrite: bool,
+        /// How numbers are written: en (1,234.5), vi (1.234,5) or auto
+        #[arg(long, default_value = "en")]
+        number_locale: String,
+        file: PathBuf,
+    },
+}
+
+/// One CSV line; amounts are parsed separately so errors can name the line
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportRow {
+    date: String,
+    symbol: String,
+    activity_type: String,
+    #[serde(default)]
+    quantity: String,
+    #[serde(default)]
+    unit_price: String,
+    currency: String,
+    #[serde(default)]
+    fee: String,
+    #[serde(default)]
+    amount: String,
+    #[serde(default)]
+    comment: String,
+}
+
+/// Script notifications and failures go to stderr next to the logs
+fn print_script_report(report: &ScriptRunReport) {
+    for notification in &report.notifications {
+        eprintln!("{}: {}", notification.title, notification.body);
+    }
+    for failure in &report.failures {
+        eprintln!(
+            "Script '{}' failed: {}",
+            failure.script_name, failure.message
+        );
+    }
+}
+
+/// Automation notifications and failures go to stderr like script output
+fn print_automation_report(report: &AutomationRunReport) {
+    for notification in &report.notifications {
+        eprintln!("{}: {}", notification.title, notification.body);
+    }
+    for failure in &report.failures {
+        eprintln!(
+            "Rule '{}' failed at {}: {}",
+            failure.rule_name, failure.action, failure.message
+        );
+    }
+}
+
+/// Running row count on stderr while a file is written or read
+fn print_progress(progress: &TransferProgress) {
+    match progress.total {
+        Some(total) => eprint!(
+            "\r{}: {} of {}",
+            progress.dataset, progress.processed, total
+        ),
+        None => eprint!("\r{}: {}", progress.dataset, progress.processed),
+    }
+    if progress.done {
+        eprintln!();
+    }
+}
+
+fn create_output(output: &Path) -> anyhow::Result<BufWriter<File>> {
+    let file =
+        File::create(output).with_context(|| format!("Cannot write {}", output.display()))?;
+    Ok(BufWriter::new(file))
+}
+
+fn parse_amount(
+    value: &str,
+    locale: NumberLocale,
+    column: &str,
+    line: usize,
+) -> anyhow::Result<Option<Decimal>> {
+    parse_localized_decimal(value, locale)
+        .with_context(|| format!("Line {}: invalid {}", line, column))
+}
+
+fn read_activities(
+    file: &Path,
+    account_id: &str,
+    locale: NumberLocale,
+) -> anyhow::Result<Vec<ActivityImport>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(file)
+        .with_context(|| format!("Cannot read {}", file.display()))?;
+    let mut activities = Vec::new();
+    for (index, row) in reader.deserialize::<ImportRow>().enumerate() {
+        // Line 1 is the header
+        let line = index + 2;
+        let row = row.with_context(|| format!("Line {}: malformed row", line))?;
+        activities.push(ActivityImport {
+            id: None,
+            date: row.date,
+            symbol: row.symbol,
+            activity_type: row.activity_type,
+            quantity: parse_amount(&row.quantity, locale, "quantity", line)?.unwrap_or_default(),
+            unit_price: parse_amount(&row.unit_price, locale, "unitPrice", line)?
+                .unwrap_or_default(),
+            currency: row.currency,
+            fee: parse_amount(&row.fee, locale, "fee", line)?.unwrap_or_default(),
+            amount: parse_amount(&row.amount, locale, "amount", line)?,
+            comment: Some(row.comment).filter(|c| !c.is_empty()),
+            account_id: Some(account_id.to_string()),
+            account_name: None,
+            symbol_name: None,
+            errors: None,
+            warnings: None,
+            is_draft: false,
+            is_valid: false,
+            line_number: Some(line as i32),
+            asset_data_source: None,
+        });
+    }
+    Ok(activities)
+}
+
+fn describe_errors(activity: &ActivityImport) -> String {
+    activity
+        .errors
+        .iter()
+        .flatten()
+        .flat_map(|(field, messages)| messages.iter().map(move |m| format!("{}: {}", field, m)))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+async fn import(
+    state: &AppState,
+    account_id: String,
+    file: &Path,
+    number_locale: Option<&str>,
+    json: bool,
+) -> anyhow::Result<()> {
+    let locale = match number_locale {
+        Some(locale) => NumberLocale::from_str(locale)?,
+        None => {
+            state
+                .activity_service
+                .get_import_mapping(account_id.clone())?
+                .number_locale
+        }
+    };
+    let mut activities = read_activities(file, &account_id, locale)?;
+    if activities.is_empty() {
+        bail!("{} has no activities", file.display());
+    }
+    print_script_report(&state.script_service.run_import_row_hooks(&mut activities)?);
+    let checked = state
+        .activity_service
+        .check_activities_import(account_id.clone(), activities)
+        .await?;
+    let invalid: Vec<&ActivityImport> = checked.iter().filter(|a| !a.is_valid).collect();
+    if !invalid.is_empty() {
+        for activity in &invalid {
+            eprintln!(
+                "Line {}: {}",
+                activity.line_number.unwrap_or_default(),
+                describe_errors(activity)
+            );
+        }
+        bail!(
+            "{} of {} activities are invalid; nothing was imported",
+            invalid.len(),
+            checked.len()
+        );
+    }
+
+    let started_at = chrono::Utc::now().naive_utc();
+    let imported = state
+        .activity_service
+        .import_activities(account_id.clone(), checked)
+        .await?;
+    // Same follow-up as an import from the UI
+    if let Err(e) = state.bill_service.match_bill_payments().await {
+        tracing::warn!("Bill matching after import failed: {}", e);
+    }
+    if let Err(e) = state.private_loan_service.match_private_loan_repayments().await {
+        tracing::warn!("Private loan repayment matching after import failed: {}", e);
+    }
+    if let Err(e) = state.categorization_service.auto_categorize().await {
+        tracing::warn!("Auto-categorization after import failed: {}", e);
+    }
+    run_import_automations(state, &account_id, imported.len(), started_at).await;
+    update_portfolio(state).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&imported)?);
+    } else {
+        println!(
+            "Imported {} activities from {}",
+            imported.len(),
+            file.display()
+        );
+    }
+    Ok(())
+}
+
+fn print_payload_preview(preview: &ImportPayloadPreview) {
+    for activity in preview.activities.iter().filter(|a| !a.is_valid) {
+        eprintln!(
+            "Row {}: {}",
+            activity.line_number.unwrap_or_default(),
+            describe_errors(activity)
+        );
+    }
+    for quote in &preview.quotes {
+        if let Some(message) = &quote.error_message {
+            eprintln!("{} {}: {}", quote.symbol, quote.date, message);
+        }
+    }
+    println!(
+        "{} to import, {} invalid, {} skipped",
+        preview.valid, preview.invalid, preview.skipped
+    );
+}
+
+async fn import_payload(
+    state: &AppState,
+    file: &Path,
+    account_id: Option<&str>,
+    preview_only: bool,
+    json: bool,
+) -> anyhow::Result<()> {
+    let body =
+        std::fs::read_to_string(file).with_context(|| format!("Cannot read {}", file.display()))?;
+    let is_xml = file
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"));
+    let payload = if is_xml {
+        let Some(account_id) = account_id else {
+            bail!("--account is required to import an e-invoice");
+        };
+        ImportPayload::from_einvoice_xml(&body, account_id)?
+    } else {
+        ImportPayload::from_json(&body)?
+    };
+    if preview_only {
+        let preview = state
+            .import_payload_service
+            .preview_payload(payload)
+            .await?;
+        if json {
+            println!("{}", serde_json::to_string_pretty(&preview)?);
+        } else {
+            print_payload_preview(&preview);
+        }
+        return Ok(());
+    }
+
+    let started_at = chrono::Utc::now().naive_utc();
+    let result = state.import_payload_service.import_payload(payload).await?;
+    finish_payload_import(state, &result, started_at).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        println!(
+            "Imported {} rows from {}, skipped {}",
+            result.imported,
+            file.display(),
+            result.skipped
+        );
+    }
+    Ok(())
+}
+
+fn export_ledger(
+    state: &AppState,
+    format: &str,
+    accounts: &[String],
+    output: Option<&Path>,
+    json: bool,
+) -> anyhow::Result<()> {
+    let format = LedgerFormat::from_str(format)?;
+    let account_ids = (!accounts.is_empty()).then_some(accounts);
+    let Some(output) = output else {
+        if json {
+            let export = state
+                .ledger_export_service
+                .export_ledger(format, account_ids)?;
+            for reason in &export.skipped {
+                eprintln!("Skipped {}", reason);
+            }
+            println!("{}", serde_json::to_string_pretty(&export)?);
+        } else {
+            let summary = state.ledger_export_service.export_ledger_to(
+                format,
+                account_ids,
+                &mut std::io::stdout().lock(),
+                &|_| {},
+                &CancellationToken::default(),
+            )?;
+            for reason in &summary.skipped {
+                eprintln!("Skipped {}", reason);
+            }
+        }
+        return Ok(());
+    };
+
+    // Written as it is rendered, so a long history never sits in memory as one string
+    let mut out = create_output(output)?;
+    let summary = state.ledger_export_service.export_ledger_to(
+        format,
+        account_ids,
+        &mut out,
+        &print_progress,
+        &CancellationToken::default(),
+    )?;
+    out.flush()
+        .with_context(|| format!("Cannot write {}", output.display()))?;
+    for reason in &summary.skipped {
+        eprintln!("Skipped {}", reason);
+    }
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "path": output,
+                "accounts": summary.accounts,
+                "transactions": summary.transactions,
+                "skipped": summary.skipped.len(),
+            })
+        );
+    } else {
+        println!(
+            "Wrote {} transactions from {} accounts to {}",
+            summary.transactions,
+            summary.accounts,
+            output.display()
+        );
+    }
+    Ok(())
+}
+
+fn export_data(
+    state: &AppState,
+    dataset: &str,
+    format: &str,
+    output: Option<&Path>,
+    json: bool,
+) -> anyhow::Result<()> {
+    let dataset = ExportDataset::from_str(dataset)?;
+    let format = ExportFileFormat::from_str(format)?;
+    let Some(output) = output else {
+        state.data_transfer_service.export_dataset(
+            dataset,
+            format,
+            &mut std::io::stdout().lock(),
+            &|_| {},
+            &CancellationToken::default(),
+        )?;
+        return Ok(());
+    };
+
+    let mut out = create_output(output)?;
+    let summary = state.data_transfer_service.export_dataset(
+        dataset,
+        format,
+        &mut out,
+        &print_progress,
+        &CancellationToken::default(),
+    )?;
+    out.flush()
+        .with_context(|| format!("Cannot write {}", output.display()))?;
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "path": output, "dataset": summary.dataset, "rows": summary.rows })
+        );
+    } else {
+        println!(
+            "Wrote {} {} rows to {}",
+            summary.rows,
+            summary.dataset.as_str(),
+            output.display()
+        );
+    }
+    Ok(())
+}
+
+fn backup(config: &Config, json: bool) -> anyhow::Result<()> {
+    let db_path = Path::new(&config.db_path);
+    let (data_dir, db_file) = if db_path.is_dir() {
+        (db_path, db_path.join("app.db"))
+    } else {
+        (
+            db_path.parent().unwrap_or_else(|| Path::new(".")),
+            db_path.to_path_buf(),
+        )
+    };
+    if !db_file.exists() {
+        bail!("No database at {}", db_file.display());
+    }
+    // Core resolves the database from DATABASE_URL, as in main
+    std::env::set_var("DATABASE_URL", &db_file);
+    let backup_path = db::backup_database(&data_dir.to_string_lossy())?;
+    if json {
+        println!("{}", serde_json::json!({ "backupPath": backup_path }));
+    } else {
+        println!("Backup written to {}", backup_path);
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountLine {
+    id: String,
+    name: String,
+    account_type: String,
+    currency: String,
+    /// Latest valuation in the base currency
+    value: Option<Decimal>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Report {
+    base_currency: String,
+    accounts: Vec<AccountLine>,
+    net_worth: Option<NetWorthPoint>,
+    emergency_funds: Vec<EmergencyFundProgress>,
+    sinking_funds: Vec<SinkingFundProgress>,
+    net_worth_goals: Vec<NetWorthGoalProgress>,
+}
+
+fn percent(progress: Option<Decimal>) -> String {
+    progress.map_or_else(
+        || "-".to_string(),
+        |p| format!("{}%", (p * Decimal::ONE_HUNDRED).round_dp(1)),
+    )
+}
+
+async fn report(state: &AppState, json: bool) -> anyhow::Result<()> {
+    let base_currency = state.base_currency.read().unwrap().clone();
+    let accounts = state.account_service.get_active_accounts()?;
+    let ids: Vec<String> = accounts.iter().map(|a| a.id.clone()).collect();
+    let latest = state.valuation_service.get_latest_valuations(&ids)?;
+    let today = chrono::Local::now().date_naive();
+
+    let report = Report {
+        accounts: accounts
+            .into_iter()
+            .map(|account| {
+                let value = latest
+                    .iter()
+                    .find(|v| v.account_id == account.id)
+                    .map(|v| (v.total_value * v.fx_rate_to_base).round_dp(2));
+                AccountLine {
+                    id: account.id,
+                    name: account.name,
+                    account_type: account.account_type,
+                    currency: account.currency,
+                    value,
+                }
+            })
+            .collect(),
+        net_worth: state
+            .net_worth_goal_service
+            .get_net_worth_series(Some(today), Some(today))?
+            .pop(),
+        emergency_funds: state
+            .emergency_fund_service
+            .get_emergency_fund_progress()
+            .await?,
+        sinking_funds: state
+            .sinking_fund_service
+            .get_sinking_fund_progress()
+            .await?,
+        net_worth_goals: state
+            .net_worth_goal_service
+            .get_net_worth_goal_progress()
+            .await?,
+        base_currency,
+    };
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("Accounts ({})", report.base_currency);
+    for account in &report.accounts {
+        println!(
+            "  {:<30} {:<12} {:>20}",
+            account.name,
+            account.account_type,
+            account
+                .value
+                .map_or_else(|| "-".to_string(), |v| v.to_string())
+        );
+    }
+    if let Some(point) = &report.net_worth {
+        println!();
+        println!("Net worth   {:>20}", point.net_worth.round_dp(2));
+        println!("  Assets    {:>20}", point.total_assets.round_dp(2));
+        println!("  Owed      {:>20}", point.total_liabilities.round_dp(2));
+    }
+    if !(report.emergency_funds.is_empty()
+        && report.sinking_funds.is_empty()
+        && report.net_worth_goals.is_empty())
+    {
+        println!();
+        println!("Goals");
+    }
+    for fund in &report.emergency_funds {
+        println!(
+            "  {:<30} {:>8} of {} months",
+            fund.goal_title,
+            percent(fund.progress),
+            fund.target_months
+        );
+    }
+    for fund in &report.sinking_funds {
+        println!(
+            "  {:<30} {:>8} due {}, set aside {} a month",
+            fund.goal_title,
+            percent(fund.progress),
+            fund.due_date,
+            fund.monthly_set_aside
+        );
+    }
+    for goal in &report.net_worth_goals {
+        println!(
+            "  {:<30} {:>8} of {}",
+            goal.goal_title,
+            percent(goal.progress),
+            goal.target_amount
+        );
+    }
+    Ok(())
+}
+
+async fn list_goals(state: &AppState, json: bool) -> anyhow::Result<()> {
+    let goals: Vec<Goal> = state.goal_service.get_goals().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&goals)?);
+        return Ok(());
+    }
+    for goal in &goals {
+        println!(
+            "{:<36} {:<30} {:<15} {:>18} {}",
+            goal.id,
+            goal.title,
+            goal.goal_type,
+            goal.target_amount,
+            if goal.is_achieved {
+                "achieved"
+            } else {
+                goal.due_date.as_deref().unwrap_or("-")
+            }
+        );
+    }
+    Ok(())
+}
+
+async fn sync_quotes(state: &AppState, json: bool) -> anyhow::Result<()> {
+    let (_, failures) = state.market_data_service.sync_market_data().await?;
+    print_script_report(&run_quote_update_scripts(state)?);
+    print_automation_report(&run_quote_automations(state).await?);
+    update_portfolio(state).await?;
+    if json {
+        let failures: Vec<_> = failures
+            .iter()
+            .map(|(symbol, error)| serde_json::json!({ "symbol": symbol, "error": error }))
+            .collect();
+        println!("{}", serde_json::json!({ "failures": failures }));
+    } else {
+        for (symbol, error) in &failures {
+            eprintln!("{}: {}", symbol, error);
+        }
+        println!("Quotes synced ({} failed)", failures.len());
+    }
+    Ok(())
+}
+
+async fn import_quotes(
+    state: &AppState,
+    file: &Path,
+    overwrite: bool,
+    number_locale: &str,
+    json: bool,
+) -> anyhow::Result<()> {
+    let number_locale = NumberLocale::from_str(number_locale)?;
+    let input = File::open(file).with_context(|| format!("Cannot read {}", file.display()))?;
+    let result = state
+        .data_transfer_service
+        .import_quotes_csv(
+            Box::new(input),
+            overwrite,
+            number_locale,
+            &print_progress,
+            &CancellationToken::default(),
+        )
+        .await?;
+    if result.imported > 0 {
+        update_portfolio(state).await?;
+    }
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+    for error in &result.errors {
+        eprintln!("{}", error);
+    }
+    println!(
+        "Imported {} of {} quotes from {} ({} already stored, {} failed)",
+        result.imported,
+        result.rows,
+        file.display(),
+        result.skipped,
+        result.failed
+    );
+    Ok(())
+}
+
+async fn sync_banks(state: &AppState, json: bool) -> anyhow::Result<()> {
+    let results = sync_bank_connections(state).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+    if results.is_empty() {
+        println!("No connections are due");
+    }
+    for result in &results {
+        match &result.error {
+            Some(error) => eprintln!("{}: {}", result.connection_name, error),
+            None => println!(
+                "{}: {} new, {} already imported, {} invalid",
+                result.connection_name, result.imported, result.duplicates, result.invalid
+            ),
+        }
+    }
+    Ok(())
+}
+
+async fn push_sheets(state: &AppState, json: bool) -> anyhow::Result<()> {
+    let results = push_sheet_exports(state).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+    if results.is_empty() {
+        println!("No exports are due");
+    }
+    for result in &results {
+        match &result.error {
+            Some(error) => eprintln!("{}: {}", result.export_name, error),
+            None => println!("{}: {} rows written", result.export_name, result.rows),
+        }
+    }
+    Ok(())
+}
+
+fn list_tokens(state: &AppState, json: bool) -> anyhow::Result<()> {
+    let tokens = state.api_token_service.get_tokens()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&tokens)?);
+        return Ok(());
+    }
+    let now = chrono::Utc::now().naive_utc();
+    for token in &tokens {
+        let scopes: Vec<&str> = token.scopes.iter().map(|scope| scope.as_str()).collect();
+        let status = match (token.revoked_at, token.is_active(now)) {
+            (Some(_), _) => "revoked".to_string(),
+            (None, false) => "expired".to_string(),
+            (None, true) => token
+                .last_used_at
+                .map_or("never used".to_string(), |used| format!("used {}", used)),
+        };
+        println!(
+            "{:<36} {:<24} {:<14} {:<24} {}",
+            token.id,
+            token.name,
+            token.token_prefix,
+            scopes.join(","),
+            status
+        );
+    }
+    Ok(())
+}
+
+async fn create_token(
+    state: &AppState,
+    name: String,
+    scopes: &[String],
+    expires_in_days: Option<i64>,
+    json: bool,
+) -> anyhow::Result<()> {
+    let scopes = scopes
+        .iter()
+        .map(|scope| ApiTokenScope::from_str(&scope.trim().to_ascii_uppercase().replace('-', "_")))
+        .collect::<Result<Vec<_>, _>>()?;
+    let expires_at =
+        expires_in_days.map(|days| chrono::Utc::now().naive_utc() + chrono::Duration::days(days));
+    let issued = state
+        .api_token_service
+        .create_token(NewApiToken {
+            name,
+            scopes,
+            expires_at,
+        })
+        .await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&issued)?);
+    } else {
+        println!("{}", issued.secret);
+        eprintln!(
+            "Token {} created; store the secret now, it is not shown again",
+            issued.token.id
+        );
+    }
+    Ok(())
+}
+
+async fn revoke_token(state: &AppState, id: &str, json: bool) -> anyhow::Result<()> {
+    let token = state.api_token_service.revoke_token(id).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&token)?);
+    } else {
+        println!("Token {} revoked", token.name);
+    }
+    Ok(())
+}
+
+fn list_rules(state: &AppState, json: bool) -> anyhow::Result<()> {
+    let rules = state.automation_service.get_rules()?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rules)?);
+        return Ok(());
+    }
+    for rule in &rules {
+        let actions: Vec<&str> = rule.actions.iter().map(|action| action.as_str()).collect();
+        println!(
+            "{:<36} {:<30} {:<22} {:<40} {}",
+            rule.id,
+            rule.name,
+            rule.trigger.as_str(),
+            actions.join(","),
+            if rule.is_enabled {
+                rule.last_triggered_at
+                    .map_or("never fired".to_string(), |at| format!("fired {}", at))
+            } else {
+                "disabled".to_string()
+            }
+        );
+    }
+    Ok(())
+}
+
+async fn add_rule(state: &AppState, file: &Path, json: bool) -> anyhow::Result<()> {
+    let body =
+        std::fs::read_to_string(file).with_context(|| format!("Cannot read {}", file.display()))?;
+    let rule: NewAutomationRule = serde_json::from_str(&body)
+        .with_context(|| format!("{} is not a valid rule", file.display()))?;
+    let created = state.automation_service.create_rule(rule).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&created)?);
+    } else {
+        println!("Rule {} created", created.id);
+    }
+    Ok(())
+}
+
+async fn delete_rule(state: &AppState, id: &str, json: bool) -> anyhow::Result<()> {
+    let deleted = state.automation_service.delete_rule(id).await?;
+    if deleted == 0 {
+        bail!("No rule {}", id);
+    }
+    if json {
+        println!("{}", serde_json::json!({ "deleted": deleted }));
+    } else {
+        println!("Rule {} deleted", id);
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    // Logs go to stderr so stdout stays clean for scripts
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        )
+        .init();
+
+    let config = Config::from_env();
+    if let Command::Backup = cli.command {
+        return backup(&config, cli.json);
+    }
+
+    // Core's backup and restore resolve the database from DATABASE_URL
+    std::env::set_var("DATABASE_URL", &config.db_path);
+    let state: Arc<AppState> = build_state(&config).await?;
+    match cli.command {
+        Command::Import {
+            account,
+            file,
+            number_locale,
+        } => import(&state, account, &file, number_locale.as_deref(), cli.json).await,
+        Command::Payload {
+            preview,
+            file,
+            account,
+        } => import_payload(&state, &file, account.as_deref(), preview, cli.json).await,
+        Command::Ledger {
+            format,
+            account,
+            output,
+        } => export_ledger(&state, &format, &account, output.as_deref(), cli.json),
+        Command::Export {
+            dataset,
+            format,
+            output,
+        } => export_data(&state, &dataset, &format, output.as_deref(), cli.json),
+        Command::Backup => unreachable!("handled before the services start"),
+        Command::Report => report(&state, cli.json).await,
+        Command::Goal {
+            command: GoalCommand::List,
+        } => list_goals(&state, cli.json).await,
+        Command::Quote {
+            command: QuoteCommand::Sync,
+        } => sync_quotes(&state, cli.json).await,
+        Command::Quote {
+            command:
+                QuoteCommand::Import {
+                    overwrite,
+                    number_locale,
+                    file,
+                },
+        } => import_quotes(&state, &file, overwrite, &number_locale, cli.json).await,
+        Command::Bank {
+            command: BankCommand::Sync,
+        } => sync_banks(&state, cli.json).await,
+        Command::Sheets {
+            command: SheetsCommand::Push,
+        } => push_sheets(&state, cli.json).await,
+        Command::Token {
+            command: TokenCommand::List,
+        } => list_tokens(&state, cli.json),
+        Command::Token {
+            command:
+                TokenCommand::Create {
+                    name,
+                    scopes,
+                    expires_in_days,
+                },
+        } => create_token(&state, name, &scopes, expires_in_days, cli.json).await,
+        Command::Token {
+            command: TokenCommand::Revoke { id },
+        } => revoke_token(&state, &id, cli.json).await,
+        Command::Rule {
+            command: RuleCommand::List,
+        } => list_rules(&state, cli.json),
+        Command::Rule {
+            command: RuleCommand::Add { file },
+        } => add_rule(&state, &file, cli.json).await,
+        Command::Rule {
+            command: RuleCommand::Delete { id },
+        } => delete_rule(&state, &id, cli.json).await,
+        Command::Mcp => mcp::serve_stdio(state).await,
+    }
+}
@@ -7,6 +7,12 @@ pub struct Config {
     pub request_timeout: Duration,
     pub static_dir: String,
     pub addons_root: String,
+    /// Bearer token for the read-only dashboard API; the API is off when unset
+    pub api_token: Option<String>,
+    /// Address for the gRPC services when built with the `grpc` feature; off when unset
+    pub grpc_listen_addr: Option<SocketAddr>,
+    /// JSON feed of bank term-deposit rates; the bundled rates are used when unset
+    pub deposit_rates_url: Option<String>,
 }
 
 impl Config {
@@ -35,6 +41,17 @@ impl Config {
                 .to_string_lossy()
                 .into_owned()
         });
+        let api_token = std::env::var("WF_API_TOKEN")
+            .ok()
+            .map(|token| token.trim().to_string())
+            .filter(|token| !token.is_empty());
+        let grpc_listen_addr = std::env::var("WF_GRPC_LISTEN_ADDR")
+            .ok()
+            .map(|addr| addr.parse().expect("Invalid WF_GRPC_LISTEN_ADDR"));
+        let deposit_rates_url = std::env::var("WF_DEPOSIT_RATES_URL")
+            .ok()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
         Self {
             listen_addr,
             db_path,
@@ -42,6 +59,9 @@ impl Config {
             request_timeout: Duration::from_millis(timeout_ms),
             static_dir,
             addons_root,
+            api_token,
+            grpc_listen_addr,
+            deposit_rates_url,
         }
     }
 }
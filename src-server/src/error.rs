@@ -6,6 +6,7 @@ use axum::{
 use serde::Serialize;
 use thiserror::Error;
 use wealthvn_core::errors::Error as CoreError;
+use wealthvn_core::i18n::{MessageCode, MessageParams};
 
 #[allow(dead_code)]
 #[derive(Error, Debug)]
@@ -14,6 +15,10 @@ pub enum ApiError {
     Core(#[from] CoreError),
     #[error("Not Found")]
     NotFound,
+    #[error("Unauthorized")]
+    Unauthorized,
+    #[error("Forbidden")]
+    Forbidden,
     #[error("{0}")]
     NotImplemented(String),
     // Surface the underlying error message to help debugging during development
@@ -22,9 +27,14 @@ pub enum ApiError {
 }
 
 #[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
 struct ErrorBody {
     code: u16,
     message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<MessageCode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    params: Option<MessageParams>,
 }
 
 impl IntoResponse for ApiError {
@@ -36,12 +46,20 @@ impl IntoResponse for ApiError {
                 _ => (StatusCode::BAD_REQUEST, e.to_string()),
             },
             ApiError::NotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            ApiError::Unauthorized => (StatusCode::UNAUTHORIZED, self.to_string()),
+            ApiError::Forbidden => (StatusCode::FORBIDDEN, self.to_string()),
             ApiError::NotImplemented(reason) => (StatusCode::NOT_IMPLEMENTED, reason.clone()),
             ApiError::Anyhow(_) => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
+        let localized = match &self {
+            ApiError::Core(e) => e.localized_message().cloned(),
+            _ => None,
+        };
         let body = Json(ErrorBody {
             code: status.as_u16(),
             message: msg,
+            error_code: localized.as_ref().map(|m| m.code),
+            params: localized.map(|m| m.params),
         });
         (status, body).into_response()
     }
@@ -1,13 +1,23 @@
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use tokio::sync::broadcast;
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry};
+use wealthvn_core::query_cache::RESOURCE_ALLOCATION;
 
 /// Canonical event names shared with the desktop (Tauri) runtime.
 pub const MARKET_SYNC_START: &str = "market:sync-start";
 pub const MARKET_SYNC_COMPLETE: &str = "market:sync-complete";
 pub const MARKET_SYNC_ERROR: &str = "market:sync-error";
+/// Stored quotes changed; the payload's `symbols` is null when every symbol was synced
+pub const QUOTES_UPDATED: &str = "quotes:updated";
 pub const PORTFOLIO_UPDATE_START: &str = "portfolio:update-start";
 pub const PORTFOLIO_UPDATE_COMPLETE: &str = "portfolio:update-complete";
 pub const PORTFOLIO_UPDATE_ERROR: &str = "portfolio:update-error";
+pub const RESOURCE_CHANGED: &str = "resource:changed";
+/// A subsystem started after the server began listening came up (e.g. market data providers)
+pub const SERVICE_READY: &str = "service:ready";
+/// Rows written or read so far by a streaming export or import
+pub const TRANSFER_PROGRESS: &str = "transfer:progress";
 
 /// Serializable envelope that carries event names and optional payloads.
 #[derive(Clone, Debug)]
@@ -30,6 +40,74 @@ impl ServerEvent {
             payload: Some(payload),
         }
     }
+
+    pub fn resource_changed(payload: ResourceEventPayload) -> Self {
+        Self::with_payload(RESOURCE_CHANGED, json!(payload))
+    }
+
+    pub fn quotes_updated(symbols: Option<Vec<String>>) -> Self {
+        Self::with_payload(QUOTES_UPDATED, json!({ "symbols": symbols }))
+    }
+
+    /// `{"event": "<name>", "payload": ...}`, as sent to WebSocket clients
+    pub fn to_json(&self) -> String {
+        json!({ "event": self.name, "payload": self.payload }).to_string()
+    }
+}
+
+/// Same shape as the desktop `resource:changed` payload
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ResourceEventPayload {
+    pub resource_type: String,
+    pub action: String,
+    #[serde(default)]
+    pub payload: Value,
+}
+
+impl ResourceEventPayload {
+    pub fn new(
+        resource_type: impl Into<String>,
+        action: impl Into<String>,
+        payload: Value,
+    ) -> Self {
+        Self {
+            resource_type: resource_type.into(),
+            action: action.into(),
+            payload,
+        }
+    }
+}
+
+impl From<&NewAuditLogEntry> for ResourceEventPayload {
+    /// Every audited mutation is a resource change; an import into an account is reported
+    /// as imported activities, as the desktop app does
+    fn from(entry: &NewAuditLogEntry) -> Self {
+        if matches!(entry.action, AuditAction::Import) && entry.entity_type == "account" {
+            return Self::new(
+                "activity",
+                "imported",
+                json!({ "account_id": entry.entity_id }),
+            );
+        }
+        // Allocations are audited under their table's name but announced as the desktop
+        // app announces them
+        let resource_type = match entry.entity_type.as_str() {
+            "goal_allocation" => RESOURCE_ALLOCATION,
+            entity_type => entity_type,
+        };
+        let action = match entry.action {
+            AuditAction::Create => "created",
+            AuditAction::Update => "updated",
+            AuditAction::Delete => "deleted",
+            AuditAction::Import => "imported",
+        };
+        let mut payload = serde_json::Map::new();
+        payload.insert(
+            format!("{}_id", resource_type),
+            Value::String(entry.entity_id.clone()),
+        );
+        Self::new(resource_type, action, Value::Object(payload))
+    }
 }
 
 /// Lightweight broadcast bus that fans out events to any connected clients.
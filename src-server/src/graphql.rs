@@ -0,0 +1,397 @@
+//! Read-only GraphQL schema over the core read models, so a client can fetch goals with
+//! their allocations, or accounts with holdings and history, in one request.
+
+use std::sync::Arc;
+
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Object, Result, Schema, SimpleObject,
+};
+use axum::{Extension, Json};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use wealthvn_core::{
+    accounts::{Account as CoreAccount, AccountServiceTrait},
+    goals::goals_model::{
+        AllocationVersion as CoreAllocationVersion, Goal as CoreGoal, GoalsAllocation,
+    },
+    portfolio::{
+        holdings::holdings_model::{Holding as CoreHolding, HoldingType},
+        valuation::valuation_model::DailyAccountValuation,
+    },
+    privacy::{index_valuation_history, mask_if},
+    settings::SettingsServiceTrait,
+};
+
+use crate::main_lib::AppState;
+
+pub type WealthSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Deep enough for goal → allocation → account → holdings plus introspection
+const MAX_QUERY_DEPTH: usize = 12;
+
+pub fn build_schema(state: Arc<AppState>) -> WealthSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+pub async fn graphql_handler(
+    Extension(schema): Extension<WealthSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+/// Schema in SDL, for client code generators
+pub async fn graphql_sdl(Extension(schema): Extension<WealthSchema>) -> String {
+    schema.sdl()
+}
+
+fn app_state<'a>(ctx: &Context<'a>) -> &'a Arc<AppState> {
+    ctx.data_unchecked::<Arc<AppState>>()
+}
+
+fn privacy_mode(state: &AppState) -> Result<bool> {
+    Ok(state.settings_service.is_privacy_mode_enabled()?)
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Goal {
+    id: String,
+    title: String,
+    description: Option<String>,
+    target_amount: f64,
+    is_achieved: bool,
+    target_return_rate: Option<f64>,
+    due_date: Option<String>,
+    monthly_investment: Option<f64>,
+    start_date: Option<String>,
+    goal_type: String,
+    target_months: Option<i32>,
+}
+
+impl From<CoreGoal> for Goal {
+    fn from(goal: CoreGoal) -> Self {
+        Self {
+            id: goal.id,
+            title: goal.title,
+            description: goal.description,
+            target_amount: goal.target_amount,
+            is_achieved: goal.is_achieved,
+            target_return_rate: goal.target_return_rate,
+            due_date: goal.due_date,
+            monthly_investment: goal.monthly_investment,
+            start_date: goal.start_date,
+            goal_type: goal.goal_type,
+            target_months: goal.target_months,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Goal {
+    async fn allocations(&self, ctx: &Context<'_>) -> Result<Vec<Allocation>> {
+        let state = app_state(ctx);
+        let allocations = state
+            .goal_service
+            .get_repository()
+            .get_allocations_for_goal(&self.id)
+            .await?;
+        Ok(allocations.into_iter().map(Allocation::from).collect())
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Allocation {
+    id: String,
+    goal_id: String,
+    account_id: String,
+    initial_contribution: f64,
+    allocated_percent: f64,
+    allocation_amount: f64,
+    start_date: Option<String>,
+    end_date: Option<String>,
+}
+
+impl From<GoalsAllocation> for Allocation {
+    fn from(allocation: GoalsAllocation) -> Self {
+        Self {
+            id: allocation.id,
+            goal_id: allocation.goal_id,
+            account_id: allocation.account_id,
+            initial_contribution: allocation.init_amount,
+            allocated_percent: allocation.allocation_percentage,
+            allocation_amount: allocation.allocation_amount,
+            start_date: allocation.start_date,
+            end_date: allocation.end_date,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Allocation {
+    /// Earlier percentages and contributions, one per period they applied
+    async fn versions(&self, ctx: &Context<'_>) -> Result<Vec<AllocationVersion>> {
+        let state = app_state(ctx);
+        let versions = state
+            .goal_service
+            .get_repository()
+            .get_allocation_versions(&self.id)
+            .await?;
+        Ok(versions.into_iter().map(AllocationVersion::from).collect())
+    }
+
+    async fn account(&self, ctx: &Context<'_>) -> Result<Account> {
+        let state = app_state(ctx);
+        Ok(state.account_service.get_account(&self.account_id)?.into())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct AllocationVersion {
+    id: String,
+    allocated_percent: f64,
+    initial_contribution: f64,
+    version_start_date: String,
+    version_end_date: Option<String>,
+    created_at: String,
+}
+
+impl From<CoreAllocationVersion> for AllocationVersion {
+    fn from(version: CoreAllocationVersion) -> Self {
+        Self {
+            id: version.id,
+            allocated_percent: version.allocation_percentage,
+            initial_contribution: version.allocation_amount,
+            version_start_date: version.version_start_date,
+            version_end_date: version.version_end_date,
+            created_at: version.created_at,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Account {
+    id: String,
+    name: String,
+    account_type: String,
+    group: Option<String>,
+    currency: String,
+    is_default: bool,
+    is_active: bool,
+}
+
+impl From<CoreAccount> for Account {
+    fn from(account: CoreAccount) -> Self {
+        Self {
+            id: account.id,
+            name: account.name,
+            account_type: account.account_type,
+            group: account.group,
+            currency: account.currency,
+            is_default: account.is_default,
+            is_active: account.is_active,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Account {
+    async fn holdings(&self, ctx: &Context<'_>) -> Result<Vec<Holding>> {
+        let state = app_state(ctx);
+        let base_currency = state.base_currency.read().unwrap().clone();
+        let holdings = state
+            .holdings_service
+            .get_holdings(&self.id, &base_currency)
+            .await?;
+        let holdings = mask_if(holdings, privacy_mode(state)?);
+        Ok(holdings.into_iter().map(Holding::from).collect())
+    }
+
+    /// Daily valuations between the dates, both inclusive and optional
+    async fn valuations(
+        &self,
+        ctx: &Context<'_>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<Valuation>> {
+        let state = app_state(ctx);
+        let mut valuations = state
+            .valuation_service
+            .get_historical_valuations(&self.id, start_date, end_date)?;
+        // Same as the history endpoint: keep the chart's shape, hide the amounts
+        if privacy_mode(state)? {
+            index_valuation_history(&mut valuations);
+        }
+        Ok(valuations.into_iter().map(Valuation::from).collect())
+    }
+
+    async fn latest_valuation(&self, ctx: &Context<'_>) -> Result<Option<Valuation>> {
+        let state = app_state(ctx);
+        let valuations = state
+            .valuation_service
+            .get_latest_valuations(std::slice::from_ref(&self.id))?;
+        let valuations = mask_if(valuations, privacy_mode(state)?);
+        Ok(valuations.into_iter().next().map(Valuation::from))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Holding {
+    id: String,
+    /// CASH or SECURITY
+    holding_type: String,
+    symbol: Option<String>,
+    name: Option<String>,
+    quantity: Decimal,
+    local_currency: String,
+    base_currency: String,
+    price: Option<Decimal>,
+    market_value_local: Decimal,
+    market_value_base: Decimal,
+    cost_basis_base: Option<Decimal>,
+    unrealized_gain_base: Option<Decimal>,
+    unrealized_gain_pct: Option<Decimal>,
+    weight: Decimal,
+}
+
+impl From<CoreHolding> for Holding {
+    fn from(holding: CoreHolding) -> Self {
+        let (symbol, name) = match holding.instrument {
+            Some(instrument) => (Some(instrument.symbol), instrument.name),
+            None => (None, None),
+        };
+        Self {
+            id: holding.id,
+            holding_type: match holding.holding_type {
+                HoldingType::Cash => "CASH",
+                HoldingType::Security => "SECURITY",
+            }
+            .to_string(),
+            symbol,
+            name,
+            quantity: holding.quantity,
+            local_currency: holding.local_currency,
+            base_currency: holding.base_currency,
+            price: holding.price,
+            market_value_local: holding.market_value.local,
+            market_value_base: holding.market_value.base,
+            cost_basis_base: holding.cost_basis.map(|v| v.base),
+            unrealized_gain_base: holding.unrealized_gain.map(|v| v.base),
+            unrealized_gain_pct: holding.unrealized_gain_pct,
+            weight: holding.weight,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct Valuation {
+    valuation_date: NaiveDate,
+    account_currency: String,
+    base_currency: String,
+    fx_rate_to_base: Decimal,
+    cash_balance: Decimal,
+    investment_market_value: Decimal,
+    total_value: Decimal,
+    cost_basis: Decimal,
+    net_contribution: Decimal,
+}
+
+impl From<DailyAccountValuation> for Valuation {
+    fn from(valuation: DailyAccountValuation) -> Self {
+        Self {
+            valuation_date: valuation.valuation_date,
+            account_currency: valuation.account_currency,
+            base_currency: valuation.base_currency,
+            fx_rate_to_base: valuation.fx_rate_to_base,
+            cash_balance: valuation.cash_balance,
+            investment_market_value: valuation.investment_market_value,
+            total_value: valuation.total_value,
+            cost_basis: valuation.cost_basis,
+            net_contribution: valuation.net_contribution,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct NetWorthPoint {
+    date: NaiveDate,
+    total_assets: Decimal,
+    total_liabilities: Decimal,
+    net_worth: Decimal,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn goals(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = true)] include_achieved: bool,
+    ) -> Result<Vec<Goal>> {
+        let state = app_state(ctx);
+        Ok(state
+            .goal_service
+            .get_goals().await?
+            .into_iter()
+            .filter(|goal| include_achieved || !goal.is_achieved)
+            .map(Goal::from)
+            .collect())
+    }
+
+    async fn goal(&self, ctx: &Context<'_>, id: String) -> Result<Option<Goal>> {
+        let state = app_state(ctx);
+        Ok(state
+            .goal_service
+            .get_goals().await?
+            .into_iter()
+            .find(|goal| goal.id == id)
+            .map(Goal::from))
+    }
+
+    async fn accounts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = true)] active_only: bool,
+    ) -> Result<Vec<Account>> {
+        let state = app_state(ctx);
+        let accounts = if active_only {
+            state.account_service.get_active_accounts()?
+        } else {
+            state.account_service.get_all_accounts()?
+        };
+        Ok(accounts.into_iter().map(Account::from).collect())
+    }
+
+    async fn account(&self, ctx: &Context<'_>, id: String) -> Result<Account> {
+        let state = app_state(ctx);
+        Ok(state.account_service.get_account(&id)?.into())
+    }
+
+    /// Net worth in the base currency on each date between the bounds
+    async fn net_worth(
+        &self,
+        ctx: &Context<'_>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> Result<Vec<NetWorthPoint>> {
+        let state = app_state(ctx);
+        let series = state
+            .net_worth_goal_service
+            .get_net_worth_series(start_date, end_date)?;
+        Ok(mask_if(series, privacy_mode(state)?)
+            .into_iter()
+            .map(|point| NetWorthPoint {
+                date: point.date,
+                total_assets: point.total_assets,
+                total_liabilities: point.total_liabilities,
+                net_worth: point.net_worth,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,429 @@
+//! gRPC surface over the core services, built with the `grpc` feature.
+//! The contract lives in `proto/wealthvn.proto`.
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use tonic::{
+    metadata::{Ascii, MetadataValue},
+    transport::Server,
+    Request, Response, Status,
+};
+use wealthvn_core::{
+    errors::Error as CoreError,
+    forecast::parse_forecast_date,
+    goals::goals_model,
+    portfolio::{
+        holdings::holdings_model::{Holding as CoreHolding, HoldingType},
+        valuation::valuation_model::DailyAccountValuation,
+    },
+    privacy::mask_if,
+};
+
+use crate::main_lib::AppState;
+
+pub mod proto {
+    tonic::include_proto!("wealthvn.v1");
+}
+
+use proto::goal_service_server::{GoalService, GoalServiceServer};
+use proto::portfolio_service_server::{PortfolioService, PortfolioServiceServer};
+
+fn to_status(error: CoreError) -> Status {
+    match error {
+        CoreError::Validation(_) => Status::invalid_argument(error.to_string()),
+        CoreError::ConstraintViolation(_) => Status::failed_precondition(error.to_string()),
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+fn parse_date(value: Option<String>) -> Result<Option<NaiveDate>, Status> {
+    value
+        .filter(|date| !date.is_empty())
+        .map(|date| parse_forecast_date(&date).map_err(to_status))
+        .transpose()
+}
+
+fn privacy_mode(state: &AppState) -> Result<bool, Status> {
+    state
+        .settings_service
+        .is_privacy_mode_enabled()
+        .map_err(to_status)
+}
+
+fn decimal(value: impl std::fmt::Display) -> String {
+    value.to_string()
+}
+
+impl From<goals_model::Goal> for proto::Goal {
+    fn from(goal: goals_model::Goal) -> Self {
+        Self {
+            id: goal.id,
+            title: goal.title,
+            description: goal.description,
+            target_amount: goal.target_amount,
+            is_achieved: goal.is_achieved,
+            target_return_rate: goal.target_return_rate,
+            due_date: goal.due_date,
+            monthly_investment: goal.monthly_investment,
+            start_date: goal.start_date,
+            initial_actual_value: goal.initial_actual_value,
+            goal_type: goal.goal_type,
+            target_months: goal.target_months,
+            archived_at: goal.archived_at,
+            priority: goal.priority,
+            depends_on_goal_id: goal.depends_on_goal_id,
+            currency: goal.currency,
+            achieved_at: goal.achieved_at,
+        }
+    }
+}
+
+impl From<proto::Goal> for goals_model::Goal {
+    fn from(goal: proto::Goal) -> Self {
+        Self {
+            id: goal.id,
+            title: goal.title,
+            description: goal.description,
+            target_amount: goal.target_amount,
+            is_achieved: goal.is_achieved,
+            target_return_rate: goal.target_return_rate,
+            due_date: goal.due_date,
+            monthly_investment: goal.monthly_investment,
+            start_date: goal.start_date,
+            initial_actual_value: goal.initial_actual_value,
+            goal_type: goal.goal_type,
+            target_months: goal.target_months,
+            archived_at: goal.archived_at,
+            priority: goal.priority,
+            depends_on_goal_id: goal.depends_on_goal_id,
+            currency: goal.currency,
+            achieved_at: goal.achieved_at,
+        }
+    }
+}
+
+impl From<proto::NewGoal> for goals_model::NewGoal {
+    fn from(goal: proto::NewGoal) -> Self {
+        Self {
+            id: goal.id,
+            title: goal.title,
+            description: goal.description,
+            target_amount: goal.target_amount,
+            is_achieved: goal.is_achieved,
+            target_return_rate: goal.target_return_rate,
+            due_date: goal.due_date,
+            monthly_investment: goal.monthly_investment,
+            start_date: goal.start_date,
+            initial_actual_value: goal.initial_actual_value,
+            goal_type: if goal.goal_type.is_empty() {
+                goals_model::GOAL_TYPE_STANDARD.to_string()
+            } else {
+                goal.goal_type
+            },
+            target_months: goal.target_months,
+            priority: goal.priority,
+            depends_on_goal_id: goal.depends_on_goal_id,
+            currency: goal.currency,
+        }
+    }
+}
+
+impl From<goals_model::GoalsAllocation> for proto::GoalAllocation {
+    fn from(allocation: goals_model::GoalsAllocation) -> Self {
+        Self {
+            id: allocation.id,
+            goal_id: allocation.goal_id,
+            account_id: allocation.account_id,
+            initial_contribution: allocation.init_amount,
+            allocated_percent: allocation.allocation_percentage,
+            allocation_date: allocation.allocation_date,
+            start_date: allocation.start_date,
+            end_date: allocation.end_date,
+            allocation_amount: allocation.allocation_amount,
+        }
+    }
+}
+
+impl From<proto::GoalAllocation> for goals_model::GoalsAllocation {
+    fn from(allocation: proto::GoalAllocation) -> Self {
+        Self {
+            id: allocation.id,
+            goal_id: allocation.goal_id,
+            account_id: allocation.account_id,
+            init_amount: allocation.initial_contribution,
+            allocation_percentage: allocation.allocated_percent,
+            allocation_date: allocation.allocation_date,
+            percent_allocation: allocation.allocated_percent.round() as i32,
+            start_date: allocation.start_date,
+            end_date: allocation.end_date,
+            allocation_amount: allocation.allocation_amount,
+        }
+    }
+}
+
+impl From<CoreHolding> for proto::Holding {
+    fn from(holding: CoreHolding) -> Self {
+        let (symbol, name) = match holding.instrument {
+            Some(instrument) => (Some(instrument.symbol), instrument.name),
+            None => (None, None),
+        };
+        Self {
+            id: holding.id,
+            account_id: holding.account_id,
+            holding_type: match holding.holding_type {
+                HoldingType::Cash => "CASH",
+                HoldingType::Security => "SECURITY",
+            }
+            .to_string(),
+            symbol,
+            name,
+            quantity: decimal(holding.quantity),
+            local_currency: holding.local_currency,
+            base_currency: holding.base_currency,
+            price: holding.price.map(decimal),
+            market_value_local: decimal(holding.market_value.local),
+            market_value_base: decimal(holding.market_value.base),
+            cost_basis_base: holding.cost_basis.map(|v| decimal(v.base)),
+            unrealized_gain_base: holding.unrealized_gain.map(|v| decimal(v.base)),
+            unrealized_gain_pct: holding.unrealized_gain_pct.map(decimal),
+            weight: decimal(holding.weight),
+        }
+    }
+}
+
+impl From<DailyAccountValuation> for proto::AccountValuation {
+    fn from(valuation: DailyAccountValuation) -> Self {
+        Self {
+            account_id: valuation.account_id,
+            valuation_date: valuation.valuation_date.to_string(),
+            account_currency: valuation.account_currency,
+            base_currency: valuation.base_currency,
+            fx_rate_to_base: decimal(valuation.fx_rate_to_base),
+            cash_balance: decimal(valuation.cash_balance),
+            investment_market_value: decimal(valuation.investment_market_value),
+            total_value: decimal(valuation.total_value),
+            cost_basis: decimal(valuation.cost_basis),
+            net_contribution: decimal(valuation.net_contribution),
+        }
+    }
+}
+
+pub struct GrpcGoalService {
+    state: Arc<AppState>,
+}
+
+#[tonic::async_trait]
+impl GoalService for GrpcGoalService {
+    async fn list_goals(
+        &self,
+        _request: Request<proto::ListGoalsRequest>,
+    ) -> Result<Response<proto::ListGoalsResponse>, Status> {
+        let goals = self.state.goal_service.get_goals().await.map_err(to_status)?;
+        Ok(Response::new(proto::ListGoalsResponse {
+            goals: goals.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn create_goal(
+        &self,
+        request: Request<proto::CreateGoalRequest>,
+    ) -> Result<Response<proto::Goal>, Status> {
+        let goal = request
+            .into_inner()
+            .goal
+            .ok_or_else(|| Status::invalid_argument("goal is required"))?;
+        let created = self
+            .state
+            .goal_service
+            .create_goal(goal.into())
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(created.into()))
+    }
+
+    async fn update_goal(
+        &self,
+        request: Request<proto::UpdateGoalRequest>,
+    ) -> Result<Response<proto::Goal>, Status> {
+        let goal = request
+            .into_inner()
+            .goal
+            .ok_or_else(|| Status::invalid_argument("goal is required"))?;
+        let updated = self
+            .state
+            .goal_service
+            .update_goal(goal.into())
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(updated.into()))
+    }
+
+    async fn delete_goal(
+        &self,
+        request: Request<proto::DeleteGoalRequest>,
+    ) -> Result<Response<proto::DeleteGoalResponse>, Status> {
+        let deleted = self
+            .state
+            .goal_service
+            .delete_goal(request.into_inner().id)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(proto::DeleteGoalResponse {
+            deleted: deleted as u64,
+        }))
+    }
+
+    async fn list_goal_allocations(
+        &self,
+        _request: Request<proto::ListGoalAllocationsRequest>,
+    ) -> Result<Response<proto::ListGoalAllocationsResponse>, Status> {
+        let allocations = self
+            .state
+            .goal_service
+            .load_goals_allocations()
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(proto::ListGoalAllocationsResponse {
+            allocations: allocations.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn upsert_goal_allocations(
+        &self,
+        request: Request<proto::UpsertGoalAllocationsRequest>,
+    ) -> Result<Response<proto::UpsertGoalAllocationsResponse>, Status> {
+        let allocations = request
+            .into_inner()
+            .allocations
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let upserted = self
+            .state
+            .goal_service
+            .upsert_goal_allocations(allocations)
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(proto::UpsertGoalAllocationsResponse {
+            upserted: upserted as u64,
+        }))
+    }
+}
+
+pub struct GrpcPortfolioService {
+    state: Arc<AppState>,
+}
+
+#[tonic::async_trait]
+impl PortfolioService for GrpcPortfolioService {
+    async fn get_holdings(
+        &self,
+        request: Request<proto::GetHoldingsRequest>,
+    ) -> Result<Response<proto::GetHoldingsResponse>, Status> {
+        let base_currency = self.state.base_currency.read().unwrap().clone();
+        let holdings = self
+            .state
+            .holdings_service
+            .get_holdings(&request.into_inner().account_id, &base_currency)
+            .await
+            .map_err(to_status)?;
+        let holdings = mask_if(holdings, privacy_mode(&self.state)?);
+        Ok(Response::new(proto::GetHoldingsResponse {
+            holdings: holdings.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_historical_valuations(
+        &self,
+        request: Request<proto::GetHistoricalValuationsRequest>,
+    ) -> Result<Response<proto::ValuationsResponse>, Status> {
+        let request = request.into_inner();
+        let valuations = self
+            .state
+            .valuation_service
+            .get_historical_valuations(
+                &request.account_id,
+                parse_date(request.start_date)?,
+                parse_date(request.end_date)?,
+            )
+            .map_err(to_status)?;
+        let valuations = mask_if(valuations, privacy_mode(&self.state)?);
+        Ok(Response::new(proto::ValuationsResponse {
+            valuations: valuations.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_latest_valuations(
+        &self,
+        request: Request<proto::GetLatestValuationsRequest>,
+    ) -> Result<Response<proto::ValuationsResponse>, Status> {
+        let valuations = self
+            .state
+            .valuation_service
+            .get_latest_valuations(&request.into_inner().account_ids)
+            .map_err(to_status)?;
+        let valuations = mask_if(valuations, privacy_mode(&self.state)?);
+        Ok(Response::new(proto::ValuationsResponse {
+            valuations: valuations.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_net_worth_history(
+        &self,
+        request: Request<proto::GetNetWorthHistoryRequest>,
+    ) -> Result<Response<proto::GetNetWorthHistoryResponse>, Status> {
+        let request = request.into_inner();
+        let series = self
+            .state
+            .net_worth_goal_service
+            .get_net_worth_series(
+                parse_date(request.start_date)?,
+                parse_date(request.end_date)?,
+            )
+            .map_err(to_status)?;
+        let series = mask_if(series, privacy_mode(&self.state)?);
+        Ok(Response::new(proto::GetNetWorthHistoryResponse {
+            points: series
+                .into_iter()
+                .map(|point| proto::NetWorthPoint {
+                    date: point.date.to_string(),
+                    total_assets: decimal(point.total_assets),
+                    total_liabilities: decimal(point.total_liabilities),
+                    net_worth: decimal(point.net_worth),
+                })
+                .collect(),
+        }))
+    }
+}
+
+/// Serves the gRPC services on `addr`; every call must carry `authorization: Bearer <token>`
+pub async fn serve(
+    state: Arc<AppState>,
+    addr: std::net::SocketAddr,
+    token: String,
+) -> anyhow::Result<()> {
+    let expected: MetadataValue<Ascii> = format!("Bearer {}", token).parse()?;
+    let check_token = move |request: Request<()>| -> Result<Request<()>, Status> {
+        match request.metadata().get("authorization") {
+            Some(given) if given == &expected => Ok(request),
+            _ => Err(Status::unauthenticated("Missing or invalid API token")),
+        }
+    };
+
+    tracing::info!("gRPC listening on {}", addr);
+    Server::builder()
+        .add_service(GoalServiceServer::with_interceptor(
+            GrpcGoalService {
+                state: state.clone(),
+            },
+            check_token.clone(),
+        ))
+        .add_service(PortfolioServiceServer::with_interceptor(
+            GrpcPortfolioService { state },
+            check_token,
+        ))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
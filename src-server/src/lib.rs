@@ -1,7 +1,17 @@
 pub mod api;
 pub mod config;
 pub mod error;
+pub mod events;
+pub mod graphql;
+pub mod mcp;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 mod main_lib;
 pub mod models;
+mod websocket;
 
-pub use main_lib::{build_state, init_tracing, AppState};
+pub use main_lib::{
+    build_state, check_deposit_rates, check_esop_vesting, finish_connector_sync, finish_payload_import, init_tracing, initialize_market_data, log_automation_report,
+    log_script_report, push_sheet_exports, record_goal_progress, refresh_bond_quotes, run_import_automations, run_quote_automations, run_quote_update_scripts,
+    publish_resource_changed, service_readiness, sync_bank_connections, update_portfolio, AppState,
+};
@@ -1,22 +1,80 @@
 mod api;
 mod config;
 mod error;
+mod events;
+mod graphql;
+#[cfg(feature = "grpc")]
+mod grpc;
 mod main_lib;
 mod models;
+mod websocket;
 
 use api::app_router;
 use config::Config;
-use main_lib::{build_state, init_tracing};
+use main_lib::{build_state, check_deposit_rates, check_esop_vesting, init_tracing, initialize_market_data, materialize_recurring_rules, push_sheet_exports, record_goal_progress, refresh_bond_quotes, sync_bank_connections};
 use tower_http::services::{ServeDir, ServeFile};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let config = Config::from_env();
+    // Core's backup and restore resolve the database from DATABASE_URL
+    std::env::set_var("DATABASE_URL", &config.db_path);
     init_tracing();
     let state = build_state(&config).await?;
     let static_dir = std::path::PathBuf::from(&config.static_dir);
     let index_file = static_dir.join("index.html");
     let static_service = ServeDir::new(static_dir).fallback(ServeFile::new(index_file));
+    #[cfg(feature = "grpc")]
+    if let Some(addr) = config.grpc_listen_addr {
+        match config.api_token.clone() {
+            Some(token) => {
+                let state = state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = grpc::serve(state, addr, token).await {
+                        tracing::error!("gRPC server stopped: {e}");
+                    }
+                });
+            }
+            None => tracing::warn!("WF_GRPC_LISTEN_ADDR is set but WF_API_TOKEN is not; gRPC is disabled"),
+        }
+    }
+    // Requests are served meanwhile; anything needing a provider waits for this
+    let market_data_state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = initialize_market_data(&market_data_state).await {
+            tracing::warn!("Market data providers failed to initialize: {e}");
+        }
+    });
+    // Each connection and sheet export has its own interval; this only decides how often they are checked
+    let sync_state = state.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            if let Err(e) = materialize_recurring_rules(&sync_state).await {
+                tracing::warn!("Posting recurring activities failed: {e}");
+            }
+            if let Err(e) = sync_bank_connections(&sync_state).await {
+                tracing::warn!("Bank connection sync failed: {e}");
+            }
+            // After the bank pull so the sheets see the newly imported activities
+            if let Err(e) = push_sheet_exports(&sync_state).await {
+                tracing::warn!("Sheet export failed: {e}");
+            }
+            if let Err(e) = check_deposit_rates(&sync_state).await {
+                tracing::warn!("Deposit rate check failed: {e}");
+            }
+            if let Err(e) = refresh_bond_quotes(&sync_state).await {
+                tracing::warn!("Bond quote refresh failed: {e}");
+            }
+            if let Err(e) = check_esop_vesting(&sync_state).await {
+                tracing::warn!("ESOP vesting check failed: {e}");
+            }
+            if let Err(e) = record_goal_progress(&sync_state).await {
+                tracing::warn!("Goal progress history failed: {e}");
+            }
+        }
+    });
     let router = app_router(state, &config).fallback_service(static_service);
     tracing::info!("Listening on {}", config.listen_addr);
     let listener = tokio::net::TcpListener::bind(config.listen_addr).await?;
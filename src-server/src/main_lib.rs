@@ -1,22 +1,67 @@
 use std::sync::{Arc, RwLock};
 
+use chrono::{NaiveDateTime, Utc};
+
 use crate::config::Config;
+use crate::events::{EventBus, ResourceEventPayload, ServerEvent, PORTFOLIO_UPDATE_COMPLETE, PORTFOLIO_UPDATE_ERROR, PORTFOLIO_UPDATE_START, SERVICE_READY};
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::{fmt, EnvFilter};
 use wealthvn_core::{
-    accounts::{AccountRepository, AccountService},
+    accounts::{AccountGroupRepository, AccountGroupService, AccountGroupServiceTrait, AccountRepository, AccountService, AccountServiceTrait},
     activities::{
         ActivityRepository, ActivityService as CoreActivityService, ActivityServiceTrait,
     },
-    assets::{AssetRepository, AssetService, AssetServiceTrait},
+    assets::{AssetRepository, AssetService, AssetServiceTrait, CASH_ASSET_TYPE, FOREX_ASSET_TYPE},
+    audit::{AuditAction, AuditRepository, AuditService, AuditServiceTrait, NewAuditLogEntry, AUDIT_ACTOR_RECURRING},
+    benchmarks::{BenchmarkRepository, BenchmarkService, BenchmarkServiceTrait},
+    automations::{AutomationRepository, AutomationRunReport, AutomationService, AutomationServiceTrait, ImportCompletion},
+    bills::{BillRepository, BillService, BillServiceTrait},
+    bonus_plans::{BonusPlanRepository, BonusPlanService, BonusPlanServiceTrait},
+    budgets::{BudgetRepository, BudgetService, BudgetServiceTrait},
+    cash::{CashService, CashServiceTrait},
+    categorization::{CategorizationRepository, CategorizationService, CategorizationServiceTrait},
+    feature_flags::{FeatureFlag, FeatureFlagService, FeatureFlagServiceTrait},
     db::{self, write_actor},
+    education::{EducationRepository, EducationService, EducationServiceTrait},
+    envelopes::{EnvelopeRepository, EnvelopeService, EnvelopeServiceTrait},
+    connectors::{ConnectorRepository, ConnectorService, ConnectorServiceTrait, ConnectorSyncResult},
+    forecast::{ForecastRepository, ForecastService, ForecastServiceTrait},
+    sheets::{SheetExportRepository, SheetExportResult, SheetExportService, SheetExportServiceTrait},
+    import_payload::{ImportPayloadResult, ImportPayloadService, ImportPayloadServiceTrait},
+    importers::{ActivityImportService, ActivityImportServiceTrait},
+    ledger::{LedgerExportService, LedgerExportServiceTrait},
+    deposit_ladders::{DepositLadderService, DepositLadderServiceTrait},
+    deposit_rates::{DepositRateFeed, DepositRateRepository, DepositRateService, DepositRateServiceTrait, HttpDepositRateFeed},
+    dividends::{DividendRepository, DividendService, DividendServiceTrait},
+    data_transfer::{DataTransferRepository, DataTransferService, DataTransferServiceTrait},
+    query_cache::{QueryCache, RESOURCE_PORTFOLIO},
+    operations::OperationRegistry,
+    telemetry::{performance_metrics, MetricsLayer, ServiceReadiness, SERVICE_MARKET_DATA},
+    api_tokens::{ApiTokenRepository, ApiTokenService, ApiTokenServiceTrait},
     fx::{FxRepository, FxService, FxServiceTrait},
-    goals::{GoalRepository, GoalService, GoalServiceTrait},
+    goals::{
+        DebtPayoffService, DebtPayoffServiceTrait, EmergencyFundService,
+        EmergencyFundServiceTrait, GoalRepository, GoalService, GoalServiceTrait,
+        NetWorthGoalService, NetWorthGoalServiceTrait, SinkingFundService,
+        SinkingFundServiceTrait, record_goal_progress_history, refresh_goal_progress,
+    },
+    income_sources::{IncomeSourceRepository, IncomeSourceService, IncomeSourceServiceTrait},
     limits::{
         ContributionLimitRepository, ContributionLimitService, ContributionLimitServiceTrait,
     },
+    loans::{LoanRepository, LoanService, LoanServiceTrait},
     market_data::{MarketDataRepository, MarketDataService, MarketDataServiceTrait},
+    onboarding::{OnboardingRepository, OnboardingService, OnboardingServiceTrait},
     portfolio::income::{IncomeService, IncomeServiceTrait},
+    real_estate::{PropertyRepository, PropertyService, PropertyServiceTrait},
+    bonds::{BondRepository, BondService, BondServiceTrait},
+    term_deposits::{TermDepositRepository, TermDepositService, TermDepositServiceTrait},
+    recurring_rules::{RecurringRuleRepository, RecurringRuleService, RecurringRuleServiceTrait},
+    reports::{NetWorthHistoryRepository, ReportService, ReportServiceTrait},
+    esop::{EsopRepository, EsopService, EsopServiceTrait},
+    sip_plans::{SipPlanRepository, SipPlanService, SipPlanServiceTrait},
+    private_loans::{PrivateLoanRepository, PrivateLoanService, PrivateLoanServiceTrait},
+    scripting::{ScriptGoalProgress, ScriptRepository, ScriptRunReport, ScriptService, ScriptServiceTrait},
     portfolio::{
         holdings::{
             holdings_valuation_service::HoldingsValuationService, HoldingsService,
@@ -25,7 +70,10 @@ use wealthvn_core::{
         snapshot::{SnapshotRepository, SnapshotService, SnapshotServiceTrait},
         valuation::{ValuationRepository, ValuationService, ValuationServiceTrait},
     },
-    settings::{settings_repository::SettingsRepository, SettingsService, SettingsServiceTrait},
+    settings::{
+        settings_repository::SettingsRepository, SettingsExportRepository, SettingsExportService,
+        SettingsExportServiceTrait, SettingsService, SettingsServiceTrait,
+    },
 };
 
 #[cfg(feature = "wealthfolio-pro")]
@@ -33,7 +81,9 @@ use wealthvn_core::sync::store;
 
 pub struct AppState {
     pub account_service: Arc<AccountService<Arc<db::DbPool>>>,
+    pub account_group_service: Arc<dyn AccountGroupServiceTrait + Send + Sync>,
     pub settings_service: Arc<SettingsService>,
+    pub settings_export_service: Arc<dyn SettingsExportServiceTrait + Send + Sync>,
     pub holdings_service: Arc<dyn HoldingsServiceTrait + Send + Sync>,
     pub valuation_service: Arc<dyn ValuationServiceTrait + Send + Sync>,
     pub market_data_service: Arc<dyn MarketDataServiceTrait + Send + Sync>,
@@ -43,10 +93,54 @@ pub struct AppState {
         Arc<dyn wealthvn_core::portfolio::performance::PerformanceServiceTrait + Send + Sync>,
     pub income_service: Arc<dyn IncomeServiceTrait + Send + Sync>,
     pub goal_service: Arc<dyn GoalServiceTrait + Send + Sync>,
+    pub emergency_fund_service: Arc<dyn EmergencyFundServiceTrait + Send + Sync>,
+    pub sinking_fund_service: Arc<dyn SinkingFundServiceTrait + Send + Sync>,
+    pub net_worth_goal_service: Arc<dyn NetWorthGoalServiceTrait + Send + Sync>,
+    pub debt_payoff_service: Arc<dyn DebtPayoffServiceTrait + Send + Sync>,
     pub limits_service: Arc<dyn ContributionLimitServiceTrait + Send + Sync>,
+    pub budget_service: Arc<dyn BudgetServiceTrait + Send + Sync>,
+    pub cash_service: Arc<dyn CashServiceTrait + Send + Sync>,
+    pub categorization_service: Arc<dyn CategorizationServiceTrait + Send + Sync>,
+    pub forecast_service: Arc<dyn ForecastServiceTrait + Send + Sync>,
+    pub income_source_service: Arc<dyn IncomeSourceServiceTrait + Send + Sync>,
+    pub bill_service: Arc<dyn BillServiceTrait + Send + Sync>,
+    pub envelope_service: Arc<dyn EnvelopeServiceTrait + Send + Sync>,
+    pub loan_service: Arc<dyn LoanServiceTrait + Send + Sync>,
+    pub property_service: Arc<dyn PropertyServiceTrait + Send + Sync>,
+    pub bond_service: Arc<dyn BondServiceTrait + Send + Sync>,
+    pub term_deposit_service: Arc<dyn TermDepositServiceTrait + Send + Sync>,
+    pub recurring_rule_service: Arc<dyn RecurringRuleServiceTrait + Send + Sync>,
+    pub dividend_service: Arc<dyn DividendServiceTrait + Send + Sync>,
+    pub report_service: Arc<dyn ReportServiceTrait + Send + Sync>,
+    pub private_loan_service: Arc<dyn PrivateLoanServiceTrait + Send + Sync>,
+    pub esop_service: Arc<dyn EsopServiceTrait + Send + Sync>,
+    pub sip_plan_service: Arc<dyn SipPlanServiceTrait + Send + Sync>,
+    pub benchmark_service: Arc<dyn BenchmarkServiceTrait + Send + Sync>,
+    pub bonus_plan_service: Arc<dyn BonusPlanServiceTrait + Send + Sync>,
+    pub deposit_ladder_service: Arc<dyn DepositLadderServiceTrait + Send + Sync>,
+    pub deposit_rate_service: Arc<dyn DepositRateServiceTrait + Send + Sync>,
+    pub education_service: Arc<dyn EducationServiceTrait + Send + Sync>,
+    pub script_service: Arc<dyn ScriptServiceTrait + Send + Sync>,
+    pub connector_service: Arc<dyn ConnectorServiceTrait + Send + Sync>,
+    pub sheet_export_service: Arc<dyn SheetExportServiceTrait + Send + Sync>,
+    pub import_payload_service: Arc<dyn ImportPayloadServiceTrait + Send + Sync>,
+    pub activity_import_service: Arc<dyn ActivityImportServiceTrait + Send + Sync>,
+    pub ledger_export_service: Arc<dyn LedgerExportServiceTrait + Send + Sync>,
+    pub data_transfer_service: Arc<dyn DataTransferServiceTrait + Send + Sync>,
+    pub api_token_service: Arc<dyn ApiTokenServiceTrait + Send + Sync>,
+    pub automation_service: Arc<dyn AutomationServiceTrait + Send + Sync>,
     pub fx_service: Arc<dyn FxServiceTrait + Send + Sync>,
     pub activity_service: Arc<dyn ActivityServiceTrait + Send + Sync>,
     pub asset_service: Arc<dyn AssetServiceTrait + Send + Sync>,
+    pub audit_service: Arc<dyn AuditServiceTrait + Send + Sync>,
+    pub feature_flag_service: Arc<dyn FeatureFlagServiceTrait + Send + Sync>,
+    pub onboarding_service: Arc<dyn OnboardingServiceTrait + Send + Sync>,
+    /// Results of expensive read endpoints, dropped on resource changes
+    pub query_cache: Arc<QueryCache>,
+    /// Long-running requests that clients can cancel
+    pub operations: Arc<OperationRegistry>,
+    /// Pushed to WebSocket clients of the dashboard API
+    pub events: EventBus,
     pub addons_root: String,
     pub data_root: String,
     pub instance_id: String,
@@ -55,16 +149,361 @@ pub struct AppState {
 pub fn init_tracing() {
     let fmt_layer = fmt::layer().json().with_current_span(false);
     let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    // The filter only applies to log output so that core spans below the log level are still
+    // timed by the metrics layer (which records nothing unless `performance_metrics` is on)
     tracing_subscriber::registry()
-        .with(filter)
-        .with(fmt_layer)
+        .with(fmt_layer.with_filter(filter))
+        .with(MetricsLayer)
         .init();
 }
 
+/// The server does not recalculate at startup, so valuations are as current as they get
+/// and only market data can still be coming up
+pub fn service_readiness(state: &AppState) -> ServiceReadiness {
+    ServiceReadiness { market_data: state.market_data_service.providers_ready(), valuation: true }
+}
+
+/// Configures the market data providers left out of `build_state` (OS keychain and network
+/// lookups) and tells WebSocket clients they are up.
+pub async fn initialize_market_data(state: &AppState) -> wealthvn_core::errors::Result<()> {
+    state.market_data_service.initialize_providers().await?;
+    let payload = serde_json::json!({ "service": SERVICE_MARKET_DATA, "readiness": service_readiness(state) });
+    state.events.publish(ServerEvent::with_payload(SERVICE_READY, payload));
+    Ok(())
+}
+
+/// Drops cached reads depending on the changed resource, then tells WebSocket clients
+pub fn publish_resource_changed(state: &AppState, payload: ResourceEventPayload) {
+    state.query_cache.invalidate(&payload.resource_type);
+    state.events.publish(ServerEvent::resource_changed(payload));
+}
+
+/// Full recalculation: rebuilds the holdings snapshots and valuations of the accounts (every
+/// active one when `None`) and of TOTAL from their activities and the stored quotes
+pub async fn recompute_valuations(
+    state: &AppState,
+    account_ids: Option<&[String]>,
+) -> wealthvn_core::errors::Result<()> {
+    if let Err(e) = state.snapshot_service.force_recalculate_holdings_snapshots(account_ids).await {
+        tracing::warn!("force_recalculate_holdings_snapshots failed: {}", e);
+    }
+    if let Err(e) = state.snapshot_service.calculate_total_portfolio_snapshots().await {
+        tracing::warn!("calculate_total_portfolio_snapshots failed: {}", e);
+    }
+    let mut ids: Vec<String> = match account_ids {
+        Some(ids) => ids.to_vec(),
+        None => state.account_service.get_active_accounts()?.into_iter().map(|a| a.id).collect(),
+    };
+    ids.push("TOTAL".to_string());
+    for id in ids {
+        if let Err(e) = state.valuation_service.calculate_valuation_history(&id, true).await {
+            tracing::warn!("calculate_valuation_history (full) failed for {}: {}", id, e);
+        }
+    }
+    if let Err(e) = state.report_service.record_net_worth_history(Utc::now().date_naive()).await {
+        tracing::warn!("Failed to record net worth history: {}", e);
+    }
+    state.query_cache.invalidate(RESOURCE_PORTFOLIO);
+    Ok(())
+}
+
+/// Incremental update: calculates holdings snapshots and appends valuations for active
+/// accounts and TOTAL. Failures for one account are logged and don't stop the others.
+pub async fn update_portfolio(state: &AppState) -> wealthvn_core::errors::Result<()> {
+    state.events.publish(ServerEvent::new(PORTFOLIO_UPDATE_START));
+    let active = match state.account_service.get_active_accounts() {
+        Ok(active) => active,
+        Err(e) => {
+            state.events.publish(ServerEvent::with_payload(PORTFOLIO_UPDATE_ERROR, serde_json::json!(e.to_string())));
+            return Err(e);
+        }
+    };
+    let ids: Vec<String> = active.into_iter().map(|a| a.id).collect();
+    if let Err(e) = state.snapshot_service.calculate_holdings_snapshots(Some(&ids)).await {
+        tracing::warn!("calculate_holdings_snapshots failed: {}", e);
+    }
+    // Also refresh TOTAL
+    if let Err(e) = state.snapshot_service.calculate_total_portfolio_snapshots().await {
+        tracing::warn!("calculate_total_portfolio_snapshots failed: {}", e);
+    }
+    // Update valuations (incremental)
+    for id in ids.iter().chain(std::iter::once(&"TOTAL".to_string())) {
+        if let Err(e) = state.valuation_service.calculate_valuation_history(id, false).await {
+            tracing::warn!("calculate_valuation_history (incremental) failed for {}: {}", id, e);
+        }
+    }
+    match run_goal_progress_scripts(state).await {
+        Ok(report) => log_script_report("on_goal_progress", &report),
+        Err(e) => tracing::warn!("on_goal_progress scripts failed: {}", e),
+    }
+    match run_goal_progress_automations(state).await {
+        Ok(report) => log_automation_report("GOAL_PROGRESS_REACHED", &report),
+        Err(e) => tracing::warn!("GOAL_PROGRESS_REACHED rules failed: {}", e),
+    }
+    // Goals whose progress reached their target are marked achieved
+    let today = Utc::now().date_naive();
+    match refresh_goal_progress(state.goal_service.as_ref(), state.net_worth_goal_service.as_ref(), today).await {
+        Ok(progress) => match state.goal_service.mark_achieved_goals(&progress, today).await {
+            Ok(achieved) => {
+                for goal in achieved {
+                    tracing::info!("Goal '{}' reached its target", goal.title);
+                    publish_resource_changed(state, ResourceEventPayload::new("goal", "achieved", serde_json::json!(goal)));
+                }
+            }
+            Err(e) => tracing::warn!("Failed to mark achieved goals: {}", e),
+        },
+        Err(e) => tracing::warn!("Failed to refresh goal progress: {}", e),
+    }
+    if let Err(e) = state.report_service.record_net_worth_history(today).await {
+        tracing::warn!("Failed to record net worth history: {}", e);
+    }
+    state.query_cache.invalidate(RESOURCE_PORTFOLIO);
+    state.events.publish(ServerEvent::new(PORTFOLIO_UPDATE_COMPLETE));
+    Ok(())
+}
+
+/// Runs the usual import follow-up once bank connections have pulled new activities;
+/// `started_at` is when the pull began
+pub async fn finish_connector_sync(state: &AppState, results: &[ConnectorSyncResult], started_at: NaiveDateTime) -> wealthvn_core::errors::Result<()> {
+    for result in results {
+        match &result.error {
+            Some(error) => tracing::warn!("Bank connection '{}' failed: {}", result.connection_name, error),
+            None => tracing::info!("Bank connection '{}': {} new activities", result.connection_name, result.imported),
+        }
+    }
+    if results.iter().all(|r| r.imported == 0) {
+        return Ok(());
+    }
+    for result in results.iter().filter(|r| r.imported > 0) {
+        match state.connector_service.get_connection(&result.connection_id) {
+            Ok(connection) => publish_resource_changed(state, ResourceEventPayload::new("activity", "imported", serde_json::json!({ "account_id": connection.account_id }))),
+            Err(e) => tracing::warn!("Bank connection {} vanished: {}", result.connection_id, e),
+        }
+    }
+    if let Err(e) = state.bill_service.match_bill_payments().await {
+        tracing::warn!("Bill matching after bank sync failed: {}", e);
+    }
+    if let Err(e) = state.private_loan_service.match_private_loan_repayments().await {
+        tracing::warn!("Private loan repayment matching after bank sync failed: {}", e);
+    }
+    if let Err(e) = state.categorization_service.auto_categorize().await {
+        tracing::warn!("Auto-categorization after bank sync failed: {}", e);
+    }
+    for result in results.iter().filter(|r| r.imported > 0) {
+        if let Ok(connection) = state.connector_service.get_connection(&result.connection_id) {
+            run_import_automations(state, &connection.account_id, result.imported, started_at).await;
+        }
+    }
+    update_portfolio(state).await
+}
+
+/// Runs the usual import follow-up once a handed-over payload has been imported;
+/// `started_at` is when the import began
+pub async fn finish_payload_import(state: &AppState, result: &ImportPayloadResult, started_at: NaiveDateTime) -> wealthvn_core::errors::Result<()> {
+    if result.imported == 0 {
+        return Ok(());
+    }
+    // Quote payloads only change valuations
+    if result.account_id.is_some() {
+        if let Err(e) = state.bill_service.match_bill_payments().await {
+            tracing::warn!("Bill matching after payload import failed: {}", e);
+        }
+        if let Err(e) = state.private_loan_service.match_private_loan_repayments().await {
+            tracing::warn!("Private loan repayment matching after payload import failed: {}", e);
+        }
+        if let Err(e) = state.categorization_service.auto_categorize().await {
+            tracing::warn!("Auto-categorization after payload import failed: {}", e);
+        }
+    }
+    if let Some(account_id) = result.account_id.as_deref() {
+        run_import_automations(state, account_id, result.imported, started_at).await;
+    }
+    update_portfolio(state).await
+}
+
+/// Pulls every bank connection that is due
+pub async fn sync_bank_connections(state: &AppState) -> wealthvn_core::errors::Result<Vec<ConnectorSyncResult>> {
+    let started_at = Utc::now().naive_utc();
+    let results = state.connector_service.sync_due_connections().await?;
+    finish_connector_sync(state, &results, started_at).await?;
+    Ok(results)
+}
+
+/// Pushes every Google Sheets export that is due
+pub async fn push_sheet_exports(state: &AppState) -> wealthvn_core::errors::Result<Vec<SheetExportResult>> {
+    let results = state.sheet_export_service.push_due_exports().await?;
+    for result in &results {
+        match &result.error {
+            Some(error) => tracing::warn!("Sheet export '{}' failed: {}", result.export_name, error),
+            None => tracing::info!("Sheet export '{}': {} rows written", result.export_name, result.rows),
+        }
+    }
+    Ok(results)
+}
+
+/// Refreshes the bank rate catalog when stale and logs deposits worth rolling over elsewhere
+pub async fn check_deposit_rates(state: &AppState) -> wealthvn_core::errors::Result<()> {
+    state.deposit_rate_service.refresh_deposit_rates(false).await?;
+    for notification in state.deposit_rate_service.rollover_notifications(Utc::now())? {
+        tracing::info!("Deposit rates: {}: {}", notification.title, notification.body);
+    }
+    Ok(())
+}
+
+/// Quotes bonds at today's clean price plus accrued interest, which grows every day
+pub async fn refresh_bond_quotes(state: &AppState) -> wealthvn_core::errors::Result<()> {
+    let quoted = state.bond_service.refresh_bond_quotes(Utc::now().date_naive()).await?;
+    if quoted > 0 {
+        state.query_cache.invalidate(RESOURCE_PORTFOLIO);
+    }
+    Ok(())
+}
+
+/// Posts the recurring activities that have fallen due, catching up on the days the server
+/// was down, and updates the portfolio when any were posted
+pub async fn materialize_recurring_rules(state: &AppState) -> wealthvn_core::errors::Result<()> {
+    let created = state.recurring_rule_service.materialize_due_activities(Utc::now().date_naive()).await?;
+    if created.is_empty() {
+        return Ok(());
+    }
+    tracing::info!("Posted {} recurring activities", created.len());
+    for activity in &created {
+        let entry = NewAuditLogEntry::new("activity", &activity.id, AuditAction::Create, AUDIT_ACTOR_RECURRING)
+            .with_snapshots(None::<&wealthvn_core::activities::Activity>, Some(activity));
+        publish_resource_changed(state, ResourceEventPayload::from(&entry));
+        if let Err(e) = state.audit_service.record(entry).await {
+            tracing::warn!("Failed to record audit entry for recurring activity {}: {}", activity.id, e);
+        }
+    }
+    update_portfolio(state).await
+}
+
+/// Requotes ESOP grants as shares vest and the share price moves, and logs the vesting dates
+/// coming up
+pub async fn check_esop_vesting(state: &AppState) -> wealthvn_core::errors::Result<()> {
+    let quoted = state.esop_service.refresh_esop_quotes(Utc::now().date_naive()).await?;
+    if quoted > 0 {
+        state.query_cache.invalidate(RESOURCE_PORTFOLIO);
+    }
+    for notification in state.esop_service.vesting_notifications(Utc::now())? {
+        tracing::info!("ESOP vesting: {}: {}", notification.title, notification.body);
+    }
+    Ok(())
+}
+
+/// Stores goal progress for the days missing from the history, so it can be charted
+pub async fn record_goal_progress(state: &AppState) -> wealthvn_core::errors::Result<()> {
+    let days = record_goal_progress_history(state.goal_service.as_ref(), state.net_worth_goal_service.as_ref(), Utc::now().date_naive()).await?;
+    if days > 0 {
+        tracing::debug!("Stored goal progress for {} days", days);
+    }
+    Ok(())
+}
+
+/// The server has no way to push notifications to the browser yet, so script output is logged
+pub fn log_script_report(event: &str, report: &ScriptRunReport) {
+    for notification in &report.notifications {
+        tracing::info!("{} script: {}: {}", event, notification.title, notification.body);
+    }
+    for line in &report.logs {
+        tracing::debug!("{} script log: {}", event, line);
+    }
+    for failure in &report.failures {
+        tracing::warn!("{} script '{}' failed: {}", event, failure.script_name, failure.message);
+    }
+}
+
+/// Symbols of every security, leaving out cash and currency pairs
+fn security_symbols(state: &AppState) -> wealthvn_core::errors::Result<Vec<String>> {
+    Ok(state
+        .asset_service
+        .get_assets()?
+        .into_iter()
+        .filter(|asset| {
+            !matches!(asset.asset_type.as_deref(), Some(CASH_ASSET_TYPE) | Some(FOREX_ASSET_TYPE))
+        })
+        .map(|asset| asset.symbol)
+        .collect())
+}
+
+/// Runs `on_quote_update` scripts over the latest quote of every security
+pub fn run_quote_update_scripts(state: &AppState) -> wealthvn_core::errors::Result<ScriptRunReport> {
+    let symbols = security_symbols(state)?;
+    let mut quotes: Vec<_> = state
+        .market_data_service
+        .get_latest_quotes_for_symbols(&symbols)?
+        .into_values()
+        .collect();
+    quotes.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    state.script_service.run_quote_update_hooks(&quotes)
+}
+
+/// Every goal whose progress is tracked
+async fn goal_progress(state: &AppState) -> wealthvn_core::errors::Result<Vec<ScriptGoalProgress>> {
+    Ok(state
+        .emergency_fund_service
+        .get_emergency_fund_progress().await?
+        .iter()
+        .map(ScriptGoalProgress::from)
+        .chain(state.sinking_fund_service.get_sinking_fund_progress().await?.iter().map(ScriptGoalProgress::from))
+        .chain(state.net_worth_goal_service.get_net_worth_goal_progress().await?.iter().map(ScriptGoalProgress::from))
+        .collect())
+}
+
+/// Runs `on_goal_progress` scripts over every goal whose progress is tracked
+pub async fn run_goal_progress_scripts(state: &AppState) -> wealthvn_core::errors::Result<ScriptRunReport> {
+    state.script_service.run_goal_progress_hooks(&goal_progress(state).await?)
+}
+
+/// Like script output, what automation rules did is logged; their notifications go nowhere else yet
+pub fn log_automation_report(trigger: &str, report: &AutomationRunReport) {
+    for fired in &report.fired {
+        tracing::info!("{} rule '{}' fired for {}", trigger, fired.rule_name, fired.subject);
+    }
+    for notification in &report.notifications {
+        tracing::info!("{} rule: {}: {}", trigger, notification.title, notification.body);
+    }
+    for failure in &report.failures {
+        tracing::warn!("{} rule '{}' failed at {}: {}", trigger, failure.rule_name, failure.action, failure.message);
+    }
+}
+
+/// Runs IMPORT_COMPLETED rules for an import into `account_id` that began at `started_at`
+pub async fn run_import_automations(state: &AppState, account_id: &str, imported: usize, started_at: NaiveDateTime) {
+    let import = ImportCompletion { account_id: account_id.to_string(), imported, started_at };
+    match state.automation_service.on_import_completed(&import).await {
+        Ok(report) => {
+            if report.tagged > 0 {
+                publish_resource_changed(state, ResourceEventPayload::new("activity_tag", "created", serde_json::json!({ "account_id": account_id, "tagged": report.tagged })));
+            }
+            log_automation_report("IMPORT_COMPLETED", &report);
+        }
+        Err(e) => tracing::warn!("IMPORT_COMPLETED rules failed: {}", e),
+    }
+}
+
+/// Runs QUOTE_MOVED rules over the latest two quotes of every security
+pub async fn run_quote_automations(state: &AppState) -> wealthvn_core::errors::Result<AutomationRunReport> {
+    let symbols = security_symbols(state)?;
+    let mut pairs: Vec<_> = state
+        .market_data_service
+        .get_latest_quotes_pair_for_symbols(&symbols)?
+        .into_values()
+        .collect();
+    pairs.sort_by(|a, b| a.latest.symbol.cmp(&b.latest.symbol));
+    state.automation_service.on_quote_update(&pairs).await
+}
+
+/// Runs GOAL_PROGRESS_REACHED rules over every goal whose progress is tracked
+pub async fn run_goal_progress_automations(state: &AppState) -> wealthvn_core::errors::Result<AutomationRunReport> {
+    state.automation_service.on_goal_progress(&goal_progress(state).await?).await
+}
+
+/// Events a slow WebSocket client may fall behind by before it starts missing them
+const EVENT_BUFFER: usize = 256;
+
 pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
-    // Ensure DATABASE_URL aligns with WF_DB_PATH so core picks the right file
-    std::env::set_var("DATABASE_URL", &config.db_path);
-    let db_path = db::init(&config.db_path)?;
+    let db_path = db::init_file(&config.db_path)?;
     tracing::info!("Database path in use: {}", db_path);
     let pool = db::create_pool(&db_path)?;
     db::run_migrations(&pool)?;
@@ -75,6 +514,8 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
     fx_service.initialize()?;
 
     let settings_repo = Arc::new(SettingsRepository::new(pool.clone(), writer.clone()));
+    let feature_flag_service = Arc::new(FeatureFlagService::new(settings_repo.clone()));
+    performance_metrics().set_enabled(feature_flag_service.is_enabled(FeatureFlag::PerformanceMetrics));
     let settings_service = Arc::new(SettingsService::new(settings_repo, fx_service.clone()));
     let settings = settings_service.get_settings()?;
     let base_currency = Arc::new(RwLock::new(settings.base_currency));
@@ -100,9 +541,16 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
     let asset_repository = Arc::new(AssetRepository::new(pool.clone(), writer.clone()));
     let market_data_repository = Arc::new(MarketDataRepository::new(pool.clone(), writer.clone()));
     let market_data_service = Arc::new(
-        MarketDataService::with_pool(market_data_repository.clone(), asset_repository.clone(), Some(pool.clone())).await?,
+        // Providers are configured by `initialize_market_data` once the server is listening
+        MarketDataService::deferred(market_data_repository.clone(), asset_repository.clone(), Some(pool.clone())).await?,
     );
 
+    let settings_export_service = Arc::new(SettingsExportService::new(
+        Arc::new(SettingsExportRepository::new(pool.clone(), writer.clone())),
+        settings_service.clone(),
+        market_data_service.clone(),
+    ));
+
     let asset_service = Arc::new(AssetService::new(
         asset_repository.clone(),
         market_data_service.clone(),
@@ -127,6 +575,12 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
         market_data_service.clone(),
         fx_service.clone(),
     ));
+    let account_group_service: Arc<dyn AccountGroupServiceTrait + Send + Sync> = Arc::new(AccountGroupService::new(
+        Arc::new(AccountGroupRepository::new(pool.clone(), writer.clone())),
+        account_repo.clone(),
+        valuation_service.clone(),
+        base_currency.clone(),
+    ));
 
     let holdings_valuation_service = Arc::new(HoldingsValuationService::new(
         fx_service.clone(),
@@ -148,11 +602,44 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
     let income_service = Arc::new(IncomeService::new(
         fx_service.clone(),
         activity_repository.clone(),
+        settings_service.clone(),
         base_currency.clone(),
     ));
 
     let goal_repository = Arc::new(GoalRepository::new(pool.clone(), writer.clone()));
-    let goal_service = Arc::new(GoalService::new(goal_repository));
+    let activity_service: Arc<dyn ActivityServiceTrait + Send + Sync> =
+        Arc::new(CoreActivityService::new(
+            activity_repository.clone(),
+            account_service.clone(),
+            asset_service.clone(),
+            fx_service.clone(),
+            market_data_service.clone(),
+        ));
+    let cash_service: Arc<dyn CashServiceTrait + Send + Sync> = Arc::new(CashService::new(
+        account_repo.clone(),
+        snapshot_service.clone(),
+        activity_service.clone(),
+        asset_service.clone(),
+        fx_service.clone(),
+    ));
+    let loan_repository = Arc::new(LoanRepository::new(pool.clone(), writer.clone()));
+    let goal_service = Arc::new(
+        GoalService::new(goal_repository.clone())
+            .with_fx(fx_service.clone(), base_currency.clone())
+            .with_cash(cash_service.clone())
+            .with_valuations(valuation_service.clone())
+            .with_loans(loan_repository.clone(), account_repo.clone()),
+    );
+
+    let audit_repository = Arc::new(AuditRepository::new(pool.clone(), writer.clone()));
+    let audit_service = Arc::new(AuditService::new(audit_repository));
+
+    let onboarding_repository = Arc::new(OnboardingRepository::new(pool.clone(), writer.clone()));
+    let onboarding_service = Arc::new(OnboardingService::new(
+        onboarding_repository,
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
 
     let limits_repository = Arc::new(ContributionLimitRepository::new(
         pool.clone(),
@@ -163,16 +650,260 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
             fx_service.clone(),
             limits_repository.clone(),
             activity_repository.clone(),
+            settings_service.clone(),
         ));
 
-    let activity_service: Arc<dyn ActivityServiceTrait + Send + Sync> =
-        Arc::new(CoreActivityService::new(
+    let budget_repository = Arc::new(BudgetRepository::new(pool.clone(), writer.clone()));
+    let budget_service: Arc<dyn BudgetServiceTrait + Send + Sync> = Arc::new(BudgetService::new(
+        budget_repository.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let emergency_fund_service: Arc<dyn EmergencyFundServiceTrait + Send + Sync> =
+        Arc::new(EmergencyFundService::new(
+            goal_repository.clone(),
+            budget_service.clone(),
+        ));
+    let sinking_fund_service: Arc<dyn SinkingFundServiceTrait + Send + Sync> =
+        Arc::new(SinkingFundService::new(
+            goal_repository.clone(),
+            base_currency.clone(),
+        ));
+    let categorization_service: Arc<dyn CategorizationServiceTrait + Send + Sync> =
+        Arc::new(CategorizationService::new(
+            Arc::new(CategorizationRepository::new(pool.clone(), writer.clone())),
+            budget_repository,
+        ));
+    let income_source_repository =
+        Arc::new(IncomeSourceRepository::new(pool.clone(), writer.clone()));
+    let income_source_service: Arc<dyn IncomeSourceServiceTrait + Send + Sync> =
+        Arc::new(IncomeSourceService::new(
+            income_source_repository.clone(),
             activity_repository.clone(),
-            account_service.clone(),
+            budget_service.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+    let bill_service: Arc<dyn BillServiceTrait + Send + Sync> = Arc::new(BillService::new(
+        Arc::new(BillRepository::new(pool.clone(), writer.clone())),
+        activity_repository.clone(),
+        fx_service.clone(),
+    ));
+    let envelope_service: Arc<dyn EnvelopeServiceTrait + Send + Sync> =
+        Arc::new(EnvelopeService::new(
+            Arc::new(EnvelopeRepository::new(pool.clone(), writer.clone())),
+            account_repo.clone(),
+            snapshot_repository.clone(),
+            fx_service.clone(),
+        ));
+    let loan_service: Arc<dyn LoanServiceTrait + Send + Sync> = Arc::new(LoanService::new(
+        loan_repository.clone(),
+        account_repo.clone(),
+    ));
+    let net_worth_goal_service: Arc<dyn NetWorthGoalServiceTrait + Send + Sync> =
+        Arc::new(NetWorthGoalService::new(
+            goal_repository.clone(),
+            account_repo.clone(),
+            valuation_repository.clone(),
+            loan_repository.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+    let debt_payoff_service: Arc<dyn DebtPayoffServiceTrait + Send + Sync> =
+        Arc::new(DebtPayoffService::new(
+            goal_repository.clone(),
+            account_repo.clone(),
+            loan_repository.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+    let education_service: Arc<dyn EducationServiceTrait + Send + Sync> =
+        Arc::new(EducationService::new(
+            Arc::new(EducationRepository::new(pool.clone(), writer.clone())),
+            goal_service.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+    let forecast_repository = Arc::new(ForecastRepository::new(pool.clone(), writer.clone()));
+    let forecast_service: Arc<dyn ForecastServiceTrait + Send + Sync> =
+        Arc::new(ForecastService::new(
+            forecast_repository.clone(),
+            account_repo.clone(),
+            snapshot_repository.clone(),
+            goal_repository.clone(),
+            income_source_repository.clone(),
+            loan_repository.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+
+    let script_service: Arc<dyn ScriptServiceTrait + Send + Sync> = Arc::new(ScriptService::new(
+        Arc::new(ScriptRepository::new(pool.clone(), writer.clone())),
+        market_data_service.clone(),
+        fx_service.clone(),
+    ));
+
+    let property_service: Arc<dyn PropertyServiceTrait + Send + Sync> =
+        Arc::new(PropertyService::new(
+            Arc::new(PropertyRepository::new(pool.clone(), writer.clone())),
+            account_repo.clone(),
+            loan_repository.clone(),
+            income_source_repository.clone(),
             asset_service.clone(),
+            activity_service.clone(),
+            market_data_service.clone(),
+        ));
+    let bond_service: Arc<dyn BondServiceTrait + Send + Sync> = Arc::new(BondService::new(
+        Arc::new(BondRepository::new(pool.clone(), writer.clone())),
+        account_repo.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        market_data_service.clone(),
+    ));
+    let term_deposit_service: Arc<dyn TermDepositServiceTrait + Send + Sync> =
+        Arc::new(TermDepositService::new(
+            Arc::new(TermDepositRepository::new(pool.clone(), writer.clone())),
+            account_repo.clone(),
+        ));
+    let recurring_rule_service: Arc<dyn RecurringRuleServiceTrait + Send + Sync> =
+        Arc::new(RecurringRuleService::new(
+            Arc::new(RecurringRuleRepository::new(pool.clone(), writer.clone())),
+            account_repo.clone(),
+            activity_service.clone(),
+            asset_service.clone(),
+        ));
+    let report_service: Arc<dyn ReportServiceTrait + Send + Sync> =
+        Arc::new(ReportService::new(
+            Arc::new(NetWorthHistoryRepository::new(pool.clone(), writer.clone())),
+            account_repo.clone(),
+            valuation_repository.clone(),
+            snapshot_service.clone(),
+            asset_service.clone(),
+            market_data_service.clone(),
+            loan_repository.clone(),
+            goal_repository.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+    let dividend_service: Arc<dyn DividendServiceTrait + Send + Sync> =
+        Arc::new(DividendService::new(
+            Arc::new(DividendRepository::new(pool.clone(), writer.clone())),
+            activity_repository.clone(),
+            holdings_service.clone(),
             fx_service.clone(),
+            base_currency.clone(),
+        ));
+    let private_loan_service: Arc<dyn PrivateLoanServiceTrait + Send + Sync> =
+        Arc::new(PrivateLoanService::new(
+            Arc::new(PrivateLoanRepository::new(pool.clone(), writer.clone())),
+            account_repo.clone(),
+            asset_service.clone(),
+            activity_service.clone(),
+            activity_repository.clone(),
             market_data_service.clone(),
+            fx_service.clone(),
+        ));
+    let esop_service: Arc<dyn EsopServiceTrait + Send + Sync> = Arc::new(EsopService::new(
+        Arc::new(EsopRepository::new(pool.clone(), writer.clone())),
+        account_repo.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        market_data_service.clone(),
+    ));
+    let sip_plan_service: Arc<dyn SipPlanServiceTrait + Send + Sync> = Arc::new(SipPlanService::new(
+        Arc::new(SipPlanRepository::new(pool.clone(), writer.clone())),
+        account_repo.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        market_data_service.clone(),
+        forecast_service.clone(),
+    ));
+    let benchmark_service: Arc<dyn BenchmarkServiceTrait + Send + Sync> = Arc::new(BenchmarkService::new(
+        Arc::new(BenchmarkRepository::new(pool.clone(), writer.clone())),
+        market_data_service.clone(),
+        valuation_service.clone(),
+        goal_repository.clone(),
+    ));
+    let bonus_plan_service: Arc<dyn BonusPlanServiceTrait + Send + Sync> =
+        Arc::new(BonusPlanService::new(
+            Arc::new(BonusPlanRepository::new(pool.clone(), writer.clone())),
+            account_repo.clone(),
+            income_source_repository,
+            goal_service.clone(),
+            net_worth_goal_service.clone(),
+            envelope_service.clone(),
+            asset_service.clone(),
+            fx_service.clone(),
+            base_currency.clone(),
+        ));
+    let deposit_rate_feed = config
+        .deposit_rates_url
+        .clone()
+        .map(|url| Arc::new(HttpDepositRateFeed::new(url)) as Arc<dyn DepositRateFeed>);
+    let deposit_rate_repository = Arc::new(DepositRateRepository::new(pool.clone(), writer.clone()));
+    let deposit_rate_service: Arc<dyn DepositRateServiceTrait + Send + Sync> =
+        Arc::new(DepositRateService::new(
+            deposit_rate_repository.clone(),
+            forecast_repository,
+            deposit_rate_feed,
         ));
+    let deposit_ladder_service: Arc<dyn DepositLadderServiceTrait + Send + Sync> =
+        Arc::new(DepositLadderService::new(
+            deposit_rate_repository,
+            account_repo.clone(),
+            account_service.clone(),
+            asset_service.clone(),
+            activity_service.clone(),
+            forecast_service.clone(),
+        ));
+
+    let connector_service: Arc<dyn ConnectorServiceTrait + Send + Sync> = Arc::new(ConnectorService::new(
+        Arc::new(ConnectorRepository::new(pool.clone(), writer.clone())),
+        activity_service.clone(),
+        script_service.clone(),
+    ));
+
+    let sheet_export_service: Arc<dyn SheetExportServiceTrait + Send + Sync> = Arc::new(SheetExportService::new(
+        Arc::new(SheetExportRepository::new(pool.clone(), writer.clone())),
+        holdings_service.clone(),
+        net_worth_goal_service.clone(),
+        emergency_fund_service.clone(),
+        sinking_fund_service.clone(),
+        base_currency.clone(),
+    ));
+
+    let import_payload_service: Arc<dyn ImportPayloadServiceTrait + Send + Sync> = Arc::new(ImportPayloadService::new(
+        activity_service.clone(),
+        market_data_service.clone(),
+        script_service.clone(),
+    ));
+
+    let activity_import_service: Arc<dyn ActivityImportServiceTrait + Send + Sync> = Arc::new(ActivityImportService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        import_payload_service.clone(),
+    ));
+
+    let ledger_export_service: Arc<dyn LedgerExportServiceTrait + Send + Sync> = Arc::new(LedgerExportService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        asset_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+
+    let data_transfer_service: Arc<dyn DataTransferServiceTrait + Send + Sync> = Arc::new(DataTransferService::new(
+        Arc::new(DataTransferRepository::new(pool.clone())),
+        market_data_service.clone(),
+    ));
+
+    let api_token_service: Arc<dyn ApiTokenServiceTrait + Send + Sync> =
+        Arc::new(ApiTokenService::new(Arc::new(ApiTokenRepository::new(pool.clone(), writer.clone()))));
+
+    let automation_service: Arc<dyn AutomationServiceTrait + Send + Sync> = Arc::new(AutomationService::new(
+        Arc::new(AutomationRepository::new(pool.clone(), writer.clone())),
+        sheet_export_service.clone(),
+    ));
 
     // Determine data root directory (parent of DB path)
     let data_root = std::path::Path::new(&db_path)
@@ -183,7 +914,9 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
 
     Ok(Arc::new(AppState {
         account_service,
+        account_group_service,
         settings_service,
+        settings_export_service,
         holdings_service,
         valuation_service,
         market_data_service: market_data_service.clone(),
@@ -192,10 +925,51 @@ pub async fn build_state(config: &Config) -> anyhow::Result<Arc<AppState>> {
         performance_service,
         income_service,
         goal_service,
+        emergency_fund_service,
+        sinking_fund_service,
+        net_worth_goal_service,
+        debt_payoff_service,
         limits_service,
+        budget_service,
+        cash_service,
+        categorization_service,
+        forecast_service,
+        income_source_service,
+        bill_service,
+        envelope_service,
+        loan_service,
+        property_service,
+        bond_service,
+        term_deposit_service,
+        recurring_rule_service,
+        dividend_service,
+        report_service,
+        private_loan_service,
+        esop_service,
+        sip_plan_service,
+        benchmark_service,
+        bonus_plan_service,
+        deposit_ladder_service,
+        deposit_rate_service,
+        education_service,
+        script_service,
+        connector_service,
+        sheet_export_service,
+        import_payload_service,
+        activity_import_service,
+        ledger_export_service,
+        data_transfer_service,
+        api_token_service,
+        automation_service,
         fx_service: fx_service.clone(),
         activity_service,
         asset_service,
+        audit_service,
+        feature_flag_service,
+        onboarding_service,
+        query_cache: Arc::new(QueryCache::new()),
+        operations: Arc::new(OperationRegistry::new()),
+        events: EventBus::new(EVENT_BUFFER),
         addons_root: config.addons_root.clone(),
         data_root,
         instance_id: settings.instance_id,
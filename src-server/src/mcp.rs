@@ -0,0 +1,409 @@
+//! Model Context Protocol server, so a local AI assistant can ask about the portfolio
+//! through a few read-only tools instead of pasted exports.
+//!
+//! Messages are JSON-RPC 2.0, one per line on stdin and stdout. Nothing here writes to
+//! the database, and amounts follow privacy mode as in the REST API.
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use wealthvn_core::{
+    activities::{ActivityDetails, Sort},
+    goals::{EmergencyFundProgress, NetWorthGoalProgress, SinkingFundProgress},
+    privacy::mask_if,
+    settings::SettingsServiceTrait,
+};
+
+use crate::main_lib::AppState;
+
+/// Protocol revision answered when the client asks for one we do not know
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2024-11-05", "2025-03-26", PROTOCOL_VERSION];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Longest net worth series one call returns, in days
+const MAX_NET_WORTH_DAYS: i64 = 3 * 366;
+const DEFAULT_ACTIVITY_LIMIT: usize = 50;
+const MAX_ACTIVITY_LIMIT: usize = 200;
+/// Rows read per query while filtering activities by date
+const ACTIVITY_PAGE_SIZE: i64 = 200;
+
+type ToolResult = std::result::Result<Value, String>;
+
+/// Serves MCP on stdin/stdout until the client closes stdin
+pub async fn serve_stdio(state: Arc<AppState>) -> anyhow::Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&state, &line).await {
+            stdout.write_all(response.to_string().as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+/// Answers one line of input; `None` for notifications, which get no response
+pub async fn handle_line(state: &AppState, line: &str) -> Option<Value> {
+    match serde_json::from_str::<Value>(line) {
+        Ok(message) => handle_message(state, message).await,
+        Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &e.to_string())),
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+pub async fn handle_message(state: &AppState, message: Value) -> Option<Value> {
+    let request: RpcRequest = match serde_json::from_value(message) {
+        Ok(request) => request,
+        Err(e) => return Some(error_response(Value::Null, INVALID_REQUEST, &e.to_string())),
+    };
+    // Notifications such as `notifications/initialized` need nothing from us
+    let id = request.id?;
+    let result = match request.method.as_str() {
+        "initialize" => Ok(initialize(&request.params)),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(state, request.params).await,
+        other => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", other))),
+    };
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|v| SUPPORTED_PROTOCOL_VERSIONS.contains(v))
+        .unwrap_or(PROTOCOL_VERSION);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "wealthvn", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Read-only access to a WealthVN portfolio. Amounts are in the base currency unless a currency is given. When privacyMode is true, amounts are reported as 0 and only percentages are real.",
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "get_net_worth",
+            "description": "Net worth (assets minus liabilities) in the base currency. Without dates returns today's figure; with startDate returns one point per day.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "startDate": { "type": "string", "format": "date", "description": "First day, YYYY-MM-DD" },
+                    "endDate": { "type": "string", "format": "date", "description": "Last day, YYYY-MM-DD; defaults to today" },
+                },
+            },
+            "annotations": { "readOnlyHint": true },
+        },
+        {
+            "name": "get_goal_progress",
+            "description": "Goals with their targets, plus tracked progress for emergency funds, sinking funds and net-worth goals. Progress is a fraction of the target (1 = reached).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "goalId": { "type": "string", "description": "Only this goal" },
+                },
+            },
+            "annotations": { "readOnlyHint": true },
+        },
+        {
+            "name": "search_activities",
+            "description": "Transactions (buys, sells, deposits, dividends, ...) newest first.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "symbol": { "type": "string", "description": "Part of the asset symbol, e.g. FPT" },
+                    "accountId": { "type": "string" },
+                    "activityType": { "type": "string", "description": "e.g. BUY, SELL, DEPOSIT, WITHDRAWAL, DIVIDEND, INTEREST, FEE" },
+                    "startDate": { "type": "string", "format": "date" },
+                    "endDate": { "type": "string", "format": "date" },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": MAX_ACTIVITY_LIMIT, "default": DEFAULT_ACTIVITY_LIMIT },
+                },
+            },
+            "annotations": { "readOnlyHint": true },
+        },
+    ])
+}
+
+#[derive(Deserialize)]
+struct ToolCall {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+async fn call_tool(state: &AppState, params: Value) -> std::result::Result<Value, (i64, String)> {
+    let call: ToolCall =
+        serde_json::from_value(params).map_err(|e| (INVALID_PARAMS, e.to_string()))?;
+    let output = match call.name.as_str() {
+        "get_net_worth" => parse_arguments(call.arguments).and_then(|a| get_net_worth(state, a)),
+        "get_goal_progress" => match parse_arguments(call.arguments) {
+            Ok(arguments) => get_goal_progress(state, arguments).await,
+            Err(e) => Err(e),
+        },
+        "search_activities" => {
+            parse_arguments(call.arguments).and_then(|a| search_activities(state, a))
+        }
+        other => return Err((INVALID_PARAMS, format!("Unknown tool: {}", other))),
+    };
+    // Tool failures go back to the model as results so it can correct its arguments
+    Ok(match output {
+        Ok(value) => json!({
+            "content": [{ "type": "text", "text": value.to_string() }],
+            "structuredContent": value,
+            "isError": false,
+        }),
+        Err(message) => json!({
+            "content": [{ "type": "text", "text": message }],
+            "isError": true,
+        }),
+    })
+}
+
+fn parse_arguments<T: DeserializeOwned + Default>(
+    arguments: Value,
+) -> std::result::Result<T, String> {
+    if arguments.is_null() {
+        return Ok(T::default());
+    }
+    serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))
+}
+
+fn privacy_mode(state: &AppState) -> std::result::Result<bool, String> {
+    state
+        .settings_service
+        .is_privacy_mode_enabled()
+        .map_err(|e| e.to_string())
+}
+
+fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct NetWorthArguments {
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+}
+
+fn get_net_worth(state: &AppState, arguments: NetWorthArguments) -> ToolResult {
+    let end = arguments.end_date.unwrap_or_else(today);
+    let start = arguments.start_date.unwrap_or(end);
+    if start > end {
+        return Err("startDate must not be after endDate".to_string());
+    }
+    if (end - start).num_days() > MAX_NET_WORTH_DAYS {
+        return Err(format!(
+            "At most {} days can be requested at once",
+            MAX_NET_WORTH_DAYS
+        ));
+    }
+    let privacy_mode = privacy_mode(state)?;
+    let points = state
+        .net_worth_goal_service
+        .get_net_worth_series(Some(start), Some(end))
+        .map_err(|e| e.to_string())?;
+    Ok(json!({
+        "baseCurrency": *state.base_currency.read().unwrap(),
+        "privacyMode": privacy_mode,
+        "points": mask_if(points, privacy_mode),
+    }))
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct GoalProgressArguments {
+    goal_id: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GoalSummary {
+    id: String,
+    title: String,
+    goal_type: String,
+    target_amount: f64,
+    due_date: Option<String>,
+    is_achieved: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GoalProgressOutput {
+    base_currency: String,
+    privacy_mode: bool,
+    goals: Vec<GoalSummary>,
+    emergency_funds: Vec<EmergencyFundProgress>,
+    sinking_funds: Vec<SinkingFundProgress>,
+    net_worth_goals: Vec<NetWorthGoalProgress>,
+}
+
+async fn get_goal_progress(state: &AppState, arguments: GoalProgressArguments) -> ToolResult {
+    let privacy_mode = privacy_mode(state)?;
+    let wanted = |id: &str| arguments.goal_id.as_deref().is_none_or(|g| g == id);
+    let goals: Vec<GoalSummary> = state
+        .goal_service
+        .get_goals()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .filter(|goal| wanted(&goal.id))
+        .map(|goal| GoalSummary {
+            target_amount: if privacy_mode {
+                0.0
+            } else {
+                goal.target_amount
+            },
+            id: goal.id,
+            title: goal.title,
+            goal_type: goal.goal_type,
+            due_date: goal.due_date,
+            is_achieved: goal.is_achieved,
+        })
+        .collect();
+    if let Some(goal_id) = &arguments.goal_id {
+        if goals.is_empty() {
+            return Err(format!("No goal with id {}", goal_id));
+        }
+    }
+
+    let mut emergency_funds = state
+        .emergency_fund_service
+        .get_emergency_fund_progress()
+        .await
+        .map_err(|e| e.to_string())?;
+    emergency_funds.retain(|f| wanted(&f.goal_id));
+    let mut sinking_funds = state
+        .sinking_fund_service
+        .get_sinking_fund_progress()
+        .await
+        .map_err(|e| e.to_string())?;
+    sinking_funds.retain(|f| wanted(&f.goal_id));
+    let mut net_worth_goals = state
+        .net_worth_goal_service
+        .get_net_worth_goal_progress()
+        .await
+        .map_err(|e| e.to_string())?;
+    net_worth_goals.retain(|g| wanted(&g.goal_id));
+
+    let output = GoalProgressOutput {
+        base_currency: state.base_currency.read().unwrap().clone(),
+        privacy_mode,
+        goals,
+        emergency_funds: mask_if(emergency_funds, privacy_mode),
+        sinking_funds: mask_if(sinking_funds, privacy_mode),
+        net_worth_goals: mask_if(net_worth_goals, privacy_mode),
+    };
+    serde_json::to_value(output).map_err(|e| e.to_string())
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct ActivitySearchArguments {
+    symbol: Option<String>,
+    account_id: Option<String>,
+    activity_type: Option<String>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    limit: Option<usize>,
+}
+
+/// Day of an activity; dates are stored as RFC 3339 timestamps
+fn activity_day(activity: &ActivityDetails) -> Option<NaiveDate> {
+    activity
+        .date
+        .get(..10)
+        .and_then(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").ok())
+}
+
+fn search_activities(state: &AppState, arguments: ActivitySearchArguments) -> ToolResult {
+    let limit = arguments.limit.unwrap_or(DEFAULT_ACTIVITY_LIMIT);
+    if limit == 0 || limit > MAX_ACTIVITY_LIMIT {
+        return Err(format!(
+            "limit must be between 1 and {}",
+            MAX_ACTIVITY_LIMIT
+        ));
+    }
+    let privacy_mode = privacy_mode(state)?;
+
+    // The search has no date filter, so pages are read newest first until one starts
+    // before `start_date`
+    let mut activities = Vec::new();
+    let mut truncated = false;
+    let mut page = 0;
+    'pages: loop {
+        let response = state
+            .activity_service
+            .search_activities(
+                page,
+                ACTIVITY_PAGE_SIZE,
+                arguments.account_id.clone().map(|id| vec![id]),
+                arguments
+                    .activity_type
+                    .clone()
+                    .map(|t| vec![t.to_uppercase()]),
+                arguments.symbol.clone().filter(|s| !s.trim().is_empty()),
+                Some(Sort {
+                    id: "date".to_string(),
+                    desc: true,
+                }),
+            )
+            .map_err(|e| e.to_string())?;
+        let page_len = response.data.len();
+        for activity in response.data {
+            let day = activity_day(&activity);
+            if matches!((day, arguments.end_date), (Some(d), Some(end)) if d > end) {
+                continue;
+            }
+            if matches!((day, arguments.start_date), (Some(d), Some(start)) if d < start) {
+                break 'pages;
+            }
+            if activities.len() == limit {
+                truncated = true;
+                break 'pages;
+            }
+            activities.push(activity);
+        }
+        if (page_len as i64) < ACTIVITY_PAGE_SIZE {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(json!({
+        "privacyMode": privacy_mode,
+        "activities": mask_if(activities, privacy_mode),
+        "truncated": truncated,
+    }))
+}
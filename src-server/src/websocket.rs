@@ -0,0 +1,60 @@
+//! Event channel of the dashboard API. axum does the WebSocket protocol; this only streams
+//! events out and keeps the connection alive.
+
+use std::time::Duration;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::Response;
+use futures::SinkExt;
+use tokio::sync::broadcast;
+
+use crate::events::ServerEvent;
+
+/// Clients only send control frames, which are capped at 125 bytes; anything larger is a misuse
+const MAX_CLIENT_MESSAGE: usize = 4096;
+/// Keeps reverse proxies from dropping a quiet connection
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Accepts a WebSocket handshake and streams every event published after it as a text message
+pub fn upgrade(ws: WebSocketUpgrade, events: broadcast::Receiver<ServerEvent>) -> Response {
+    ws.max_message_size(MAX_CLIENT_MESSAGE)
+        .max_frame_size(MAX_CLIENT_MESSAGE)
+        .on_upgrade(move |socket| async move {
+            if let Err(e) = push_events(socket, events).await {
+                tracing::debug!("WebSocket client disconnected: {}", e);
+            }
+        })
+}
+
+async fn push_events(
+    mut socket: WebSocket,
+    mut events: broadcast::Receiver<ServerEvent>,
+) -> Result<(), axum::Error> {
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    ping.tick().await;
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => socket.send(Message::Text(event.to_json())).await?,
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    tracing::warn!("WebSocket client fell behind and missed {} events", missed);
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    // The client may already be gone
+                    let _ = socket.close().await;
+                    return Ok(());
+                }
+            },
+            // Pings are answered by axum; anything else but a close is ignored
+            message = socket.recv() => match message {
+                // The reply to a close is queued by axum and only has to go out
+                Some(Ok(Message::Close(_))) => return socket.flush().await,
+                None => return Ok(()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e),
+            },
+            _ = ping.tick() => socket.send(Message::Ping(Vec::new())).await?,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Fixture shared by the server's integration tests
+
+#![allow(dead_code)]
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    body::{to_bytes, Body},
+    http::Request,
+    Router,
+};
+use serde_json::Value;
+use tempfile::{tempdir, TempDir};
+use tower::ServiceExt;
+use wealthvn_core::accounts::AccountServiceTrait;
+use wealthvn_server::{api::app_router, build_state, config::Config, models::NewAccount, AppState};
+
+/// A server on a fresh database in a temporary directory. Its configuration is built here
+/// rather than read from the environment, so tests in one binary can run side by side.
+pub struct TestServer {
+    pub config: Config,
+    pub state: Arc<AppState>,
+    _db_dir: TempDir,
+}
+
+impl TestServer {
+    pub async fn start() -> Self {
+        Self::start_with(|_| {}).await
+    }
+
+    /// Starts a server with `WF_API_TOKEN` set to `token`
+    pub async fn with_api_token(token: &str) -> Self {
+        Self::start_with(|config| config.api_token = Some(token.to_string())).await
+    }
+
+    /// Starts a server after `configure` has adjusted the defaults
+    pub async fn start_with(configure: impl FnOnce(&mut Config)) -> Self {
+        let db_dir = tempdir().unwrap();
+        let mut config = Config {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            db_path: db_dir.path().join("test.db").to_string_lossy().into_owned(),
+            cors_allow: vec!["*".to_string()],
+            request_timeout: Duration::from_secs(30),
+            static_dir: db_dir.path().join("dist").to_string_lossy().into_owned(),
+            addons_root: db_dir.path().to_string_lossy().into_owned(),
+            api_token: None,
+            grpc_listen_addr: None,
+            deposit_rates_url: None,
+        };
+        configure(&mut config);
+        let state = build_state(&config).await.unwrap();
+        Self {
+            config,
+            state,
+            _db_dir: db_dir,
+        }
+    }
+
+    /// The API routes over the server's state
+    pub fn app(&self) -> Router {
+        app_router(self.state.clone(), &self.config)
+    }
+
+    /// Creates an active VND account and returns its id
+    pub async fn create_account(&self, name: &str, account_type: &str) -> String {
+        self.state
+            .account_service
+            .create_account(
+                NewAccount {
+                    id: None,
+                    name: name.to_string(),
+                    account_type: account_type.to_string(),
+                    group: None,
+                    currency: "VND".to_string(),
+                    is_default: false,
+                    is_active: true,
+                    platform_id: None,
+                }
+                .into(),
+            )
+            .await
+            .unwrap()
+            .id
+    }
+}
+
+/// Sends a request with an optional JSON body; the response body is `Null` unless it is JSON
+pub async fn send(app: &Router, method: &str, uri: &str, body: Option<Value>) -> (u16, Value) {
+    send_with_token(app, method, uri, None, body).await
+}
+
+/// Like `send`, with the token as a bearer `Authorization` header
+pub async fn send_with_token(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    token: Option<&str>,
+    body: Option<Value>,
+) -> (u16, Value) {
+    let mut request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    if let Some(token) = token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let body = body.map_or_else(Body::empty, |body| Body::from(body.to_string()));
+    let response = app
+        .clone()
+        .oneshot(request.body(body).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (
+        status,
+        serde_json::from_slice(&bytes).unwrap_or(Value::Null),
+    )
+}
@@ -0,0 +1,272 @@
+//! The read-only dashboard API: token checks, the compact summary, GraphQL, the event channel
+//! and the query cache behind it
+
+mod common;
+
+use axum::{body::Body, http::Request};
+use common::{send, send_with_token, TestServer};
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tower::ServiceExt;
+use wealthvn_core::goals::goals_model::NewGoal;
+
+#[tokio::test]
+async fn dashboard_requires_api_token() {
+    let server = TestServer::with_api_token("dashboard-token").await;
+    let app = server.app();
+
+    for (token, status) in [
+        (None, 401),
+        (Some("wrong-token"), 401),
+        (Some("dashboard-token"), 200),
+    ] {
+        let (actual, _) =
+            send_with_token(&app, "GET", "/api/v1/dashboard/goals", token, None).await;
+        assert_eq!(actual, status, "{:?}", token);
+    }
+
+    // The token has to come as a bearer credential
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/api/v1/dashboard/goals")
+                .header("Authorization", "dashboard-token")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    // The dashboard only reads
+    let (status, _) = send_with_token(
+        &app,
+        "POST",
+        "/api/v1/dashboard/goals",
+        Some("dashboard-token"),
+        Some(json!({ "title": "House" })),
+    )
+    .await;
+    assert_eq!(status, 405);
+}
+
+#[tokio::test]
+async fn summary_is_compact_json() {
+    let server = TestServer::with_api_token("summary-token").await;
+    let app = server.app();
+
+    let (status, _) = send(&app, "GET", "/api/v1/dashboard/summary?goals=5", None).await;
+    assert_eq!(status, 401);
+
+    let (status, summary) = send_with_token(
+        &app,
+        "GET",
+        "/api/v1/dashboard/summary?goals=5",
+        Some("summary-token"),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(summary["netWorth"], json!(0.0));
+    assert_eq!(summary["dailyChange"], json!(0.0));
+    assert!(summary["asOf"].is_null());
+    assert_eq!(summary["goals"], json!([]));
+
+    let (status, _) = send_with_token(
+        &app,
+        "GET",
+        "/api/v1/dashboard/summary?goals=many",
+        Some("summary-token"),
+        None,
+    )
+    .await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn graphql_returns_goals_and_accounts_in_one_request() {
+    let server = TestServer::start().await;
+    server.create_account("Tiết kiệm", "SAVINGS").await;
+    server
+        .state
+        .goal_service
+        .create_goal(NewGoal {
+            id: None,
+            title: "Mua nhà".to_string(),
+            description: None,
+            target_amount: 2_000_000_000.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: None,
+            monthly_investment: None,
+            start_date: None,
+            initial_actual_value: None,
+            goal_type: "STANDARD".to_string(),
+            target_months: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+        })
+        .await
+        .unwrap();
+    let app = server.app();
+
+    let query = json!({
+        "query": "{ goals { title goalType allocations { id versions { id } } } accounts { name currency holdings { id } latestValuation { totalValue } } }"
+    });
+    let (status, body) = send(&app, "POST", "/api/v1/graphql", Some(query)).await;
+    assert_eq!(status, 200);
+    assert!(body.get("errors").is_none(), "{}", body);
+    assert_eq!(body["data"]["goals"][0]["title"], "Mua nhà");
+    assert_eq!(body["data"]["goals"][0]["allocations"], json!([]));
+    assert_eq!(body["data"]["accounts"][0]["name"], "Tiết kiệm");
+
+    // The schema only reads, and unknown fields are reported rather than ignored
+    for query in [
+        "mutation { deleteGoal(id: \"x\") }",
+        "{ goals { balance } }",
+    ] {
+        let (_, body) = send(
+            &app,
+            "POST",
+            "/api/v1/graphql",
+            Some(json!({ "query": query })),
+        )
+        .await;
+        assert!(body["errors"].is_array(), "{}", body);
+        assert!(body["data"].is_null(), "{}", body);
+    }
+}
+
+/// Reads one unmasked server frame: (opcode, payload)
+async fn read_frame(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+    let mut head = [0u8; 2];
+    stream.read_exact(&mut head).await.unwrap();
+    let len = match head[1] & 0x7F {
+        126 => stream.read_u16().await.unwrap() as usize,
+        127 => stream.read_u64().await.unwrap() as usize,
+        len => len as usize,
+    };
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).await.unwrap();
+    (head[0] & 0x0F, payload)
+}
+
+#[tokio::test]
+async fn events_reach_websocket_clients() {
+    let server = TestServer::with_api_token("events-token").await;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = server.app();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    // The key and accept value are the example from RFC 6455
+    let handshake = |token: &str| {
+        format!(
+            "GET /api/v1/dashboard/events HTTP/1.1\r\nHost: {addr}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\nAuthorization: Bearer {token}\r\n\r\n"
+        )
+    };
+    let mut rejected = TcpStream::connect(addr).await.unwrap();
+    rejected
+        .write_all(handshake("wrong").as_bytes())
+        .await
+        .unwrap();
+    let mut response = vec![0u8; 12];
+    rejected.read_exact(&mut response).await.unwrap();
+    assert_eq!(&response, b"HTTP/1.1 401");
+
+    let mut socket = TcpStream::connect(addr).await.unwrap();
+    socket
+        .write_all(handshake("events-token").as_bytes())
+        .await
+        .unwrap();
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        head.push(socket.read_u8().await.unwrap());
+    }
+    let head = String::from_utf8(head).unwrap().to_lowercase();
+    assert!(head.starts_with("http/1.1 101"), "{}", head);
+    assert!(
+        head.contains("sec-websocket-accept: s3pplmbitxaq9kygzzhzrbk+xoo="),
+        "{}",
+        head
+    );
+
+    // A mutation through the REST API is pushed to the client
+    let created: serde_json::Value = reqwest::Client::new()
+        .post(format!("http://{addr}/api/v1/income-sources"))
+        .json(&json!({
+            "name": "Salary",
+            "kind": "SALARY",
+            "amount": 30000000,
+            "currency": "VND",
+            "frequency": "MONTHLY",
+            "payDay": 5,
+            "startDate": "2026-01-01",
+        }))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let (opcode, payload) = read_frame(&mut socket).await;
+    assert_eq!(opcode, 0x1);
+    let event: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+    assert_eq!(event["event"], "resource:changed");
+    assert_eq!(event["payload"]["resource_type"], "income_source");
+    assert_eq!(event["payload"]["action"], "created");
+    assert_eq!(
+        event["payload"]["payload"]["income_source_id"],
+        created["id"]
+    );
+
+    // Pings are answered; clients send masked frames
+    socket.write_all(&[0x89, 0x80, 1, 2, 3, 4]).await.unwrap();
+    assert_eq!(read_frame(&mut socket).await, (0xA, Vec::new()));
+    // A close is echoed before the connection ends
+    socket.write_all(&[0x88, 0x80, 1, 2, 3, 4]).await.unwrap();
+    assert_eq!(read_frame(&mut socket).await.0, 0x8);
+}
+
+#[tokio::test]
+async fn cached_reads_are_dropped_when_their_resources_change() {
+    let server = TestServer::start().await;
+    let app = server.app();
+
+    for _ in 0..2 {
+        let (status, allocations) = send(&app, "GET", "/api/v1/goals/allocations", None).await;
+        assert_eq!(status, 200);
+        assert_eq!(allocations, json!([]));
+    }
+    assert_eq!(server.state.query_cache.len(), 1);
+
+    // A rejected write leaves the cache alone
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/goals",
+        Some(json!({ "title": "House" })),
+    )
+    .await;
+    assert_eq!(status, 422);
+    assert_eq!(server.state.query_cache.len(), 1);
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/goals",
+        Some(json!({
+            "title": "House",
+            "targetAmount": 1000000000.0,
+            "isAchieved": false,
+        })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert!(server.state.query_cache.is_empty());
+}
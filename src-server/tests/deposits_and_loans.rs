@@ -0,0 +1,428 @@
+//! Cash and what it is lent or deposited into: withdrawals, term-deposit rates and ladders,
+//! private loans and floating-rate loan resets
+
+mod common;
+
+use chrono::{Days, Months, NaiveDate, Utc};
+use common::{send, TestServer};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use wealthvn_core::activities::NewActivity;
+
+#[tokio::test]
+async fn cash_deposited_can_be_withdrawn_straight_away() {
+    let server = TestServer::start().await;
+    let account_id = server.create_account("Techcombank", "CASH").await;
+    let app = server.app();
+
+    let (status, created) = send(
+        &app,
+        "POST",
+        "/api/v1/cash/deposit",
+        Some(json!({ "accountId": account_id, "amount": 10_000_000 })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", created);
+
+    // No portfolio update has run since the deposit
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/cash/withdraw",
+        Some(json!({ "accountId": account_id, "amount": 10_000_001 })),
+    )
+    .await;
+    assert_eq!(status, 400);
+    let (status, withdrawn) = send(
+        &app,
+        "POST",
+        "/api/v1/cash/withdraw",
+        Some(json!({ "accountId": account_id, "amount": 4_000_000 })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", withdrawn);
+    assert_eq!(withdrawn["activityType"], "WITHDRAWAL");
+
+    // Nothing moves for an account that doesn't exist or an amount that isn't positive
+    for body in [
+        json!({ "accountId": "missing", "amount": 1_000_000 }),
+        json!({ "accountId": account_id, "amount": 0 }),
+    ] {
+        for route in ["/api/v1/cash/deposit", "/api/v1/cash/withdraw"] {
+            let (status, _) = send(&app, "POST", route, Some(body.clone())).await;
+            assert_eq!(status, 400, "{} {}", route, body);
+        }
+    }
+}
+
+#[tokio::test]
+async fn maturing_deposit_gets_a_better_rate_suggestion() {
+    // The fixture leaves WF_DEPOSIT_RATES_URL unset
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+    let app = server.app();
+
+    // Without a feed the refresh stores the bundled rates
+    let (status, stored) = send(&app, "POST", "/api/v1/deposit-rates/refresh", None).await;
+    assert_eq!(status, 200, "{}", stored);
+    assert!(stored.as_u64().unwrap() > 0);
+    let (_, rates) = send(&app, "GET", "/api/v1/deposit-rates", None).await;
+    let vcb_12 = rates
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|rate| rate["bankCode"] == "VCB" && rate["termMonths"] == 12)
+        .unwrap();
+    assert_eq!(vcb_12["source"], "BUNDLED");
+
+    let maturity_date = Utc::now().date_naive() + Days::new(10);
+    let (status, flow) = send(
+        &app,
+        "POST",
+        "/api/v1/forecast/cash-flows",
+        Some(json!({
+            "name": "Tiết kiệm VCB 12 tháng",
+            "kind": "TERM_DEPOSIT_MATURITY",
+            "amount": 200_000_000u64,
+            "currency": "VND",
+            "frequency": "ONCE",
+            "startDate": maturity_date.to_string(),
+            "bankCode": "VCB",
+            "termMonths": 12,
+            "interestRate": 4.6,
+        })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", flow);
+    assert_eq!(flow["bankCode"], "VCB");
+
+    let (status, suggestions) = send(&app, "GET", "/api/v1/deposit-rates/suggestions", None).await;
+    assert_eq!(status, 200, "{}", suggestions);
+    let suggestions = suggestions.as_array().unwrap();
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0]["cashFlowId"], flow["id"]);
+    assert_ne!(suggestions[0]["bestBankCode"], "VCB");
+    assert!(suggestions[0]["extraInterest"].as_f64().unwrap() > 0.0);
+
+    // Out of a narrower window
+    let (_, suggestions) = send(
+        &app,
+        "GET",
+        "/api/v1/deposit-rates/suggestions?withinDays=5",
+        None,
+    )
+    .await;
+    assert!(suggestions.as_array().unwrap().is_empty());
+
+    let notifications = state
+        .deposit_rate_service
+        .rollover_notifications(Utc::now())
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+}
+
+#[tokio::test]
+async fn confirmed_ladder_opens_a_deposit_per_rung() {
+    let server = TestServer::start().await;
+    let cash_id = server.create_account("Vietcombank", "CASH").await;
+    let securities_id = server.create_account("SSI", "SECURITIES").await;
+    let app = server.app();
+    send(&app, "POST", "/api/v1/deposit-rates/refresh", None).await;
+
+    let request = json!({
+        "accountId": cash_id,
+        "amount": 600_000_000u64,
+        "startDate": "2026-10-18",
+        "liquidity": [
+            { "withinMonths": 3, "percent": 50 },
+            { "withinMonths": 12, "percent": 50 },
+        ],
+    });
+    let mut from_securities = request.clone();
+    from_securities["accountId"] = json!(securities_id);
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/deposit-rates/ladder/propose",
+        Some(from_securities),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    let (status, ladder) = send(
+        &app,
+        "POST",
+        "/api/v1/deposit-rates/ladder/propose",
+        Some(request),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", ladder);
+    let rungs = ladder["rungs"].as_array().unwrap();
+    assert_eq!(rungs.len(), 2);
+    assert_eq!(rungs[0]["termMonths"], 3);
+    assert_eq!(rungs[0]["maturityDate"], "2027-01-18");
+    assert_eq!(rungs[1]["termMonths"], 12);
+    // Nothing is stored until confirmed
+    let (_, accounts) = send(&app, "GET", "/api/v1/accounts", None).await;
+    assert_eq!(accounts.as_array().unwrap().len(), 2);
+
+    let (status, confirmed) =
+        send(&app, "POST", "/api/v1/deposit-rates/ladder", Some(ladder)).await;
+    assert_eq!(status, 200, "{}", confirmed);
+    let opened = confirmed["accounts"].as_array().unwrap();
+    assert_eq!(opened.len(), 2);
+    assert!(opened
+        .iter()
+        .all(|account| account["group"] == "Term deposits" && account["accountType"] == "CASH"));
+    let maturities = confirmed["maturities"].as_array().unwrap();
+    assert_eq!(maturities[0]["kind"], "TERM_DEPOSIT_MATURITY");
+    assert_eq!(maturities[0]["startDate"], "2027-01-18");
+    assert_eq!(maturities[0]["accountId"], opened[0]["id"]);
+    assert!(maturities[1]["amount"].as_f64().unwrap() > 300_000_000.0);
+
+    // The lump sum moved out of the funding account into the deposits
+    let (_, activities) = send(
+        &app,
+        "POST",
+        "/api/v1/activities/search",
+        Some(json!({ "page": 0, "pageSize": 50 })),
+    )
+    .await;
+    let types: Vec<&str> = activities["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|activity| activity["activityType"].as_str().unwrap())
+        .collect();
+    assert_eq!(types.len(), 4);
+    assert_eq!(types.iter().filter(|t| **t == "TRANSFER_OUT").count(), 2);
+}
+
+#[tokio::test]
+async fn private_loan_is_repaid_from_matching_deposits_and_flagged_when_overdue() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let account_id = server.create_account("Vietcombank", "CASH").await;
+    let app = server.app();
+
+    let loan = json!({
+        "accountId": account_id,
+        "borrower": "Anh Minh",
+        "principal": 12_000_000,
+        "interestRate": 12,
+        "repaymentPlan": "BULLET",
+        "termMonths": 6,
+        "startDate": "2025-10-18",
+    });
+    let mut no_principal = loan.clone();
+    no_principal["principal"] = json!(0);
+    let (status, _) = send(&app, "POST", "/api/v1/private-loans", Some(no_principal)).await;
+    assert_eq!(status, 400);
+
+    let (status, created) = send(&app, "POST", "/api/v1/private-loans", Some(loan.clone())).await;
+    assert_eq!(status, 200, "{}", created);
+    let id = created["id"].as_str().unwrap().to_string();
+    assert!(created["activityId"].is_string());
+
+    // Principal plus 6% interest fell due in April and nothing came back
+    let (status, summary) = send(&app, "GET", &format!("/api/v1/private-loans/{}", id), None).await;
+    assert_eq!(status, 200);
+    assert_eq!(summary["status"], "DEFAULT");
+    assert_eq!(summary["value"], json!(12_000_000.0));
+    assert_eq!(summary["overdueAmount"], json!(12_720_000.0));
+
+    for (date, comment) in [
+        ("2026-04-20", "Anh Minh tra no"),
+        ("2026-04-21", "Luong thang 4"),
+    ] {
+        state
+            .activity_service
+            .create_activity(NewActivity {
+                id: None,
+                account_id: account_id.clone(),
+                asset_id: "$CASH-VND".to_string(),
+                activity_type: "DEPOSIT".to_string(),
+                activity_date: date.to_string(),
+                quantity: None,
+                unit_price: None,
+                currency: "VND".to_string(),
+                fee: None,
+                amount: Some(Decimal::from(12_720_000)),
+                is_draft: false,
+                comment: Some(comment.to_string()),
+            })
+            .await
+            .unwrap();
+    }
+    let (status, matched) = send(&app, "POST", "/api/v1/private-loans/match", None).await;
+    assert_eq!(status, 200, "{}", matched);
+    assert_eq!(matched["matched"], 1);
+    assert_eq!(matched["repayments"][0]["source"], "MATCHED");
+    // A deposit is only matched once
+    let (_, matched) = send(&app, "POST", "/api/v1/private-loans/match", None).await;
+    assert_eq!(matched["matched"], 0);
+
+    let (_, summary) = send(&app, "GET", &format!("/api/v1/private-loans/{}", id), None).await;
+    assert_eq!(summary["status"], "REPAID");
+    assert_eq!(summary["interestReceived"], json!(720_000.0));
+    assert_eq!(summary["value"], json!(0.0));
+
+    let repayment_id = summary["repayments"][0]["id"].as_str().unwrap().to_string();
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/private-loans/repayments/{}", repayment_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 204);
+    let mut written_off = loan;
+    written_off["writtenOff"] = json!(true);
+    let (status, _) = send(
+        &app,
+        "PUT",
+        &format!("/api/v1/private-loans/{}", id),
+        Some(written_off),
+    )
+    .await;
+    assert_eq!(status, 200);
+    let (_, summary) = send(&app, "GET", &format!("/api/v1/private-loans/{}", id), None).await;
+    assert_eq!(summary["status"], "WRITTEN_OFF");
+    assert_eq!(summary["value"], json!(0.0));
+
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/private-loans/{}", id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (_, loans) = send(&app, "GET", "/api/v1/private-loans", None).await;
+    assert_eq!(loans, json!([]));
+}
+
+fn months_after(date: NaiveDate, months: u32) -> NaiveDate {
+    date.checked_add_months(Months::new(months)).unwrap()
+}
+
+fn loan_payments(forecast: &Value) -> Vec<f64> {
+    forecast["points"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .flat_map(|point| point["events"].as_array().unwrap())
+        .filter(|event| event["kind"] == "LOAN_PAYMENT")
+        .map(|event| event["amount"].as_f64().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn rate_resets_reprice_the_loan_and_the_stress_test_feeds_the_forecast() {
+    let server = TestServer::start().await;
+    let account_id = server.create_account("Mortgage", "LIABILITY").await;
+    let app = server.app();
+
+    // Eight months into a two-year loan fixed at 6% for the first six
+    let today = Utc::now().date_naive();
+    let start = today.checked_sub_months(Months::new(8)).unwrap();
+    let (status, loan) = send(
+        &app,
+        "POST",
+        "/api/v1/loans",
+        Some(json!({
+            "accountId": account_id,
+            "principal": 1_200_000_000,
+            "startDate": start,
+            "termMonths": 24,
+            "fixedRate": 6,
+            "fixedMonths": 6,
+            "baseRate": 6.5,
+            "floatingMargin": 3.5,
+        })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", loan);
+    let loan_id = loan["id"].as_str().unwrap().to_string();
+
+    let reset = json!({
+        "loanId": loan_id,
+        "effectiveDate": months_after(start, 7),
+        "baseRate": 8.5,
+    });
+    let mut unrealistic = reset.clone();
+    unrealistic["baseRate"] = json!(99);
+    let (status, _) = send(&app, "POST", "/api/v1/loans/rate-resets", Some(unrealistic)).await;
+    assert_eq!(status, 400);
+    let (status, created) = send(&app, "POST", "/api/v1/loans/rate-resets", Some(reset)).await;
+    assert_eq!(status, 200, "{}", created);
+
+    // The reset prices the payments due after it
+    let (status, schedule) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/loans/{}/schedule", loan_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", schedule);
+    assert_eq!(schedule["rateResets"].as_array().unwrap().len(), 1);
+    let rows = schedule["rows"].as_array().unwrap();
+    assert_eq!(rows[5]["annualRate"], json!(6.0));
+    assert_eq!(rows[6]["annualRate"], json!(10.0));
+    assert_eq!(rows[7]["annualRate"], json!(12.0));
+
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/loans/{}/stress-test?rateIncrease=25", loan_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 400);
+    let (status, stress) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/loans/{}/stress-test?rateIncrease=2", loan_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200, "{}", stress);
+    assert_eq!(stress["baseline"][0]["annualRate"], json!(12.0));
+    assert_eq!(stress["stressed"][0]["annualRate"], json!(14.0));
+    assert!(stress["extraInterest"].as_f64().unwrap() > 0.0);
+
+    // The forecast carries the monthly payments, bigger under the same shock
+    let request = json!({ "months": 6 });
+    let (status, forecast) = send(&app, "POST", "/api/v1/forecast", Some(request)).await;
+    assert_eq!(status, 200, "{}", forecast);
+    let payments = loan_payments(&forecast);
+    assert_eq!(payments.len(), 6);
+    assert!(payments.iter().all(|amount| *amount < 0.0));
+    let shocked = json!({ "months": 6, "loanRateIncrease": 2 });
+    let (_, stressed) = send(&app, "POST", "/api/v1/forecast", Some(shocked)).await;
+    let stressed_payments = loan_payments(&stressed);
+    assert!(stressed_payments.iter().sum::<f64>() < payments.iter().sum::<f64>());
+    let excluded = json!({ "months": 6, "includeLoanPayments": false });
+    let (_, without) = send(&app, "POST", "/api/v1/forecast", Some(excluded)).await;
+    assert!(loan_payments(&without).is_empty());
+
+    let id = created["id"].as_str().unwrap();
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/loans/rate-resets/{}", id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (_, resets) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/loans/rate-resets?loanId={}", loan_id),
+        None,
+    )
+    .await;
+    assert_eq!(resets, json!([]));
+}
@@ -0,0 +1,263 @@
+//! Goals over the REST API: archiving, plan export and import, progress and the net worth
+//! history the goals are measured against
+
+mod common;
+
+use common::{send, TestServer};
+use serde_json::{json, Value};
+use wealthvn_server::update_portfolio;
+
+/// Creates a goal starting in January 2026 and returns it
+async fn create_goal(app: &axum::Router, title: &str, target_amount: f64) -> Value {
+    let (status, goal) = send(
+        app,
+        "POST",
+        "/api/v1/goals",
+        Some(json!({
+            "title": title,
+            "targetAmount": target_amount,
+            "isAchieved": false,
+            "startDate": "2026-01-01",
+        })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", goal);
+    goal
+}
+
+#[tokio::test]
+async fn archived_goals_are_hidden_until_restored() {
+    let server = TestServer::start().await;
+    let app = server.app();
+    let goal = create_goal(&app, "Car", 300_000_000.0).await;
+    let goal_id = goal["id"].as_str().unwrap();
+
+    let (status, archived) = send(
+        &app,
+        "POST",
+        &format!("/api/v1/goals/{}/archive", goal_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert!(archived["archivedAt"].is_string());
+
+    let (_, goals) = send(&app, "GET", "/api/v1/goals", None).await;
+    assert_eq!(goals, json!([]));
+    let (_, goals) = send(&app, "GET", "/api/v1/goals?includeArchived=true", None).await;
+    assert_eq!(goals[0]["id"], goal_id);
+
+    let (status, restored) = send(
+        &app,
+        "POST",
+        &format!("/api/v1/goals/{}/restore", goal_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert!(restored["archivedAt"].is_null());
+    let (_, goals) = send(&app, "GET", "/api/v1/goals", None).await;
+    assert_eq!(goals.as_array().unwrap().len(), 1);
+
+    for action in ["archive", "restore"] {
+        let (status, _) = send(
+            &app,
+            "POST",
+            &format!("/api/v1/goals/missing/{}", action),
+            None,
+        )
+        .await;
+        assert_eq!(status, 400, "{}", action);
+    }
+
+    // Active goals are archived before they can be deleted
+    let goal_uri = format!("/api/v1/goals/{}", goal_id);
+    let (status, _) = send(&app, "DELETE", &goal_uri, None).await;
+    assert_eq!(status, 400);
+    send(&app, "POST", &format!("{}/archive", goal_uri), None).await;
+    let (status, _) = send(&app, "DELETE", &goal_uri, None).await;
+    assert!(status < 300, "{}", status);
+    let (_, goals) = send(&app, "GET", "/api/v1/goals?includeArchived=true", None).await;
+    assert_eq!(goals, json!([]));
+}
+
+#[tokio::test]
+async fn exported_goal_plans_import_under_new_ids() {
+    let server = TestServer::start().await;
+    let app = server.app();
+    let goal = create_goal(&app, "Car", 300_000_000.0).await;
+
+    let (status, plan) = send(&app, "GET", "/api/v1/goals/export", None).await;
+    assert_eq!(status, 200);
+    assert_eq!(plan["version"], 1);
+    assert_eq!(plan["goals"][0]["id"], goal["id"]);
+
+    let (status, result) = send(&app, "POST", "/api/v1/goals/import", Some(plan.clone())).await;
+    assert_eq!(status, 200);
+    assert_eq!(result["goals"], 1);
+    let (_, goals) = send(&app, "GET", "/api/v1/goals", None).await;
+    let goals = goals.as_array().unwrap();
+    assert_eq!(goals.len(), 2);
+    assert_ne!(goals[0]["id"], goals[1]["id"]);
+    assert!(goals.iter().all(|g| g["title"] == "Car"));
+
+    // A plan from a newer version, or one that isn't a plan at all, leaves the goals alone
+    for body in [
+        json!({ "version": 99, "exportedAt": "2026-10-18T00:00:00Z" }),
+        json!({ "goals": "Car" }),
+    ] {
+        let (status, _) = send(&app, "POST", "/api/v1/goals/import", Some(body.clone())).await;
+        assert!((400..500).contains(&status), "{} for {}", status, body);
+    }
+    let (_, goals) = send(&app, "GET", "/api/v1/goals", None).await;
+    assert_eq!(goals.as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn goal_progress_is_stored_on_first_read() {
+    let server = TestServer::start().await;
+    let app = server.app();
+
+    let (status, _) = send(
+        &app,
+        "GET",
+        "/api/v1/goals/missing/progress?date=2026-09-30",
+        None,
+    )
+    .await;
+    assert_eq!(status, 404);
+
+    let goal = create_goal(&app, "House", 1_000_000_000.0).await;
+    let goal_id = goal["id"].as_str().unwrap();
+
+    let (status, progress) = send(
+        &app,
+        "GET",
+        &format!("/api/v1/goals/{}/progress?date=2026-09-30", goal_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(progress["goalTitle"], "House");
+    assert_eq!(progress["queryDate"], "2026-09-30");
+    assert_eq!(progress["currentValue"], json!(0.0));
+
+    // The second read is served from the stored row
+    let date = chrono::NaiveDate::from_ymd_opt(2026, 9, 30).unwrap();
+    let stored = server
+        .state
+        .goal_service
+        .get_repository()
+        .load_goal_progress(&[goal_id.to_string()], date)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+
+    // A weekly series ends on its last day and reuses the stored one
+    let (status, series) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/v1/goals/{}/progress/history?fromDate=2026-09-10&toDate=2026-09-30&interval=WEEKLY",
+            goal_id
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    let dates: Vec<&str> = series
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|point| point["queryDate"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        dates,
+        ["2026-09-10", "2026-09-17", "2026-09-24", "2026-09-30"]
+    );
+
+    // An account without allocations has nothing to rebalance
+    let (status, rebalance) = send(
+        &app,
+        "GET",
+        "/api/v1/goals/allocations/rebalance?accountId=none",
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(rebalance["suggestions"], json!([]));
+    assert_eq!(rebalance["unallocatedPercent"], json!(100.0));
+
+    // Nor a goal to take a deposit, which has to be positive
+    let distribute = |amount: &str| {
+        format!(
+            "/api/v1/goals/allocations/distribute?accountId=none&amount={}",
+            amount
+        )
+    };
+    let (status, distribution) = send(&app, "GET", &distribute("5000000"), None).await;
+    assert_eq!(status, 200);
+    assert_eq!(distribution["goals"], json!([]));
+    assert_eq!(distribution["unassigned"], json!(5000000.0));
+    let (status, _) = send(&app, "GET", &distribute("0"), None).await;
+    assert_eq!(status, 400);
+
+    // Without a due date there is no monthly contribution to solve for
+    let (status, _) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/v1/goals/{}/funding-requirement?expectedReturnRate=6",
+            goal_id
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn net_worth_history_is_recorded_by_the_portfolio_update_not_by_reads() {
+    let server = TestServer::start().await;
+    *server.state.base_currency.write().unwrap() = "VND".to_string();
+    let account_id = server.create_account("Techcombank", "CASH").await;
+    let app = server.app();
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/cash/deposit",
+        Some(json!({ "accountId": account_id, "amount": 10_000_000 })),
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, statement) = send(&app, "GET", "/api/v1/reports/net-worth", None).await;
+    assert_eq!(status, 200, "{}", statement);
+    let (_, history) = send(
+        &app,
+        "GET",
+        "/api/v1/reports/net-worth/history?months=1",
+        None,
+    )
+    .await;
+    assert_eq!(history, json!([]));
+
+    update_portfolio(&server.state).await.unwrap();
+    let (_, history) = send(
+        &app,
+        "GET",
+        "/api/v1/reports/net-worth/history?months=1",
+        None,
+    )
+    .await;
+    assert_eq!(history.as_array().unwrap().len(), 1, "{}", history);
+
+    let (status, _) = send(
+        &app,
+        "GET",
+        "/api/v1/reports/net-worth/history?months=none",
+        None,
+    )
+    .await;
+    assert_eq!(status, 400);
+}
@@ -0,0 +1,244 @@
+//! Issued API tokens and the routes they open: reports exports and the import integrations
+
+mod common;
+
+use axum::{body::Body, http::Request};
+use common::{send, send_with_token, TestServer};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const INVOICE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<HDon>
+  <DLHDon>
+    <TTChung>
+      <KHMSHDon>1</KHMSHDon>
+      <KHHDon>C26TBB</KHHDon>
+      <SHDon>88</SHDon>
+      <NLap>2026-10-12</NLap>
+      <DVTTe>VND</DVTTe>
+    </TTChung>
+    <NDHDon>
+      <NBan><Ten>Công ty CP Bán lẻ Điện máy Xanh</Ten><MST>0303217354</MST></NBan>
+      <TToan>
+        <TgTCThue>5000000</TgTCThue>
+        <TgTThue>500000</TgTThue>
+        <TgTTTBSo>5500000</TgTTTBSo>
+      </TToan>
+    </NDHDon>
+  </DLHDon>
+</HDon>"#;
+
+/// Routes behind the `IMPORT` scope
+const IMPORT_ROUTES: [&str; 6] = [
+    "/api/v1/integrations/import-payloads/preview",
+    "/api/v1/integrations/import-payloads",
+    "/api/v1/integrations/import-payloads/einvoice/preview",
+    "/api/v1/integrations/import-payloads/einvoice",
+    "/api/v1/integrations/activity-imports/preview",
+    "/api/v1/integrations/activity-imports",
+];
+
+/// Issues a token with `scopes` and returns its id and secret
+async fn issue_token(app: &axum::Router, name: &str, scopes: &[&str]) -> (String, String) {
+    let (status, issued) = send(
+        app,
+        "POST",
+        "/api/v1/api-tokens",
+        Some(json!({ "name": name, "scopes": scopes })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", issued);
+    (
+        issued["token"]["id"].as_str().unwrap().to_string(),
+        issued["secret"].as_str().unwrap().to_string(),
+    )
+}
+
+#[tokio::test]
+async fn issued_tokens_are_limited_to_their_scopes() {
+    let server = TestServer::start().await;
+    let app = server.app();
+
+    // Token routes are mounted without WF_API_TOKEN, but need a token
+    let (status, _) = send(&app, "GET", "/api/v1/dashboard/goals", None).await;
+    assert_eq!(status, 401);
+
+    let (id, read_only) = issue_token(&app, "Wall display", &["READ_ONLY"]).await;
+    let (_, reports) = issue_token(&app, "Spreadsheet", &["REPORTS"]).await;
+    let (_, import) = issue_token(&app, "Bank mail", &["IMPORT"]).await;
+
+    let read = |token| send_with_token(&app, "GET", "/api/v1/dashboard/goals", Some(token), None);
+    let export = |token| {
+        send_with_token(
+            &app,
+            "GET",
+            "/api/v1/dashboard/exports/ledger",
+            Some(token),
+            None,
+        )
+    };
+    assert_eq!(read(&read_only).await.0, 200);
+    assert_eq!(read(&reports).await.0, 403);
+    assert_eq!(read(&import).await.0, 403);
+    assert_eq!(export(&read_only).await.0, 403);
+    assert_eq!(export(&reports).await.0, 200);
+    assert_eq!(export(&import).await.0, 403);
+
+    // Tokens without the import scope are refused every write, before the body is read
+    for route in IMPORT_ROUTES {
+        for token in [&read_only, &reports] {
+            let (status, _) =
+                send_with_token(&app, "POST", route, Some(token), Some(json!({}))).await;
+            assert_eq!(status, 403, "{}", route);
+        }
+        let (status, _) =
+            send_with_token(&app, "POST", route, Some("wf_unknown"), Some(json!({}))).await;
+        assert_eq!(status, 401, "{}", route);
+    }
+
+    let (_, tokens) = send(&app, "GET", "/api/v1/api-tokens", None).await;
+    let wall_display = tokens
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|token| token["id"] == id)
+        .unwrap();
+    assert!(wall_display["lastUsedAt"].is_string());
+    assert!(tokens[0].get("tokenHash").is_none());
+
+    let (status, revoked) = send(
+        &app,
+        "POST",
+        &format!("/api/v1/api-tokens/{}/revoke", id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert!(revoked["revokedAt"].is_string());
+    assert_eq!(read(&read_only).await.0, 401);
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/api-tokens",
+        Some(json!({ "name": "Nothing", "scopes": ["ADMIN"] })),
+    )
+    .await;
+    assert_eq!(status, 422);
+}
+
+#[tokio::test]
+async fn the_server_token_passes_every_scope() {
+    let server = TestServer::with_api_token("server-token").await;
+    let app = server.app();
+    let (_, read_only) = issue_token(&app, "Wall display", &["READ_ONLY"]).await;
+
+    let (status, _) = send_with_token(
+        &app,
+        "GET",
+        "/api/v1/dashboard/exports/ledger",
+        Some("server-token"),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    // The issued token is checked on its own, whatever the server token is
+    let (status, _) = send_with_token(
+        &app,
+        "GET",
+        "/api/v1/dashboard/exports/ledger",
+        Some(&read_only),
+        None,
+    )
+    .await;
+    assert_eq!(status, 403);
+}
+
+#[tokio::test]
+async fn data_export_streams_a_downloadable_file() {
+    let server = TestServer::start().await;
+    for name in ["Tiết kiệm", "Chứng khoán"] {
+        server.create_account(name, "SAVINGS").await;
+    }
+    let app = server.app();
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/api/v1/exports/data?dataset=accounts&format=json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let disposition = response.headers()["content-disposition"].to_str().unwrap();
+    assert!(disposition.starts_with("attachment; filename=\"accounts_"));
+    assert!(disposition.ends_with(".json\""));
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rows: Value = serde_json::from_slice(&body).unwrap();
+    let mut names: Vec<_> = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["name"].as_str().unwrap().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Chứng khoán", "Tiết kiệm"]);
+
+    let (status, _) = send(&app, "GET", "/api/v1/exports/data?dataset=receipts", None).await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn einvoice_imports_expense_and_vat_once() {
+    let server = TestServer::start().await;
+    let account_id = server.create_account("Techcombank", "CASH").await;
+    let app = server.app();
+
+    let (_, token) = issue_token(&app, "Invoices", &["IMPORT"]).await;
+    let body = json!({ "accountId": account_id, "xml": INVOICE });
+    let import = |uri, body| send_with_token(&app, "POST", uri, Some(&token), Some(body));
+
+    let (status, preview) = import(
+        "/api/v1/integrations/import-payloads/einvoice/preview",
+        body.clone(),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(preview["valid"], 2);
+    let types: Vec<&str> = preview["activities"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["activityType"].as_str().unwrap())
+        .collect();
+    assert_eq!(types, vec!["WITHDRAWAL", "TAX"]);
+
+    let (status, result) = import(
+        "/api/v1/integrations/import-payloads/einvoice",
+        body.clone(),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(result["imported"], 2);
+
+    // Importing the same invoice again finds both rows already stored
+    let (_, again) = import(
+        "/api/v1/integrations/import-payloads/einvoice/preview",
+        body,
+    )
+    .await;
+    assert_eq!(again["valid"], 0);
+    assert_eq!(again["skipped"], 2);
+
+    let (status, _) = import(
+        "/api/v1/integrations/import-payloads/einvoice/preview",
+        json!({ "accountId": account_id, "xml": "<TDiep/>" }),
+    )
+    .await;
+    assert_eq!(status, 400);
+}
@@ -0,0 +1,486 @@
+//! Holdings tracked outside a broker's feed: bonds, ESOP grants, fund SIPs and property, and the
+//! HOSE trading rules applied to listed stocks
+
+mod common;
+
+use chrono::{Days, FixedOffset, Months, NaiveDate, Utc};
+use common::{send, TestServer};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use wealthvn_core::{
+    activities::NewActivity,
+    assets::UpdateAssetProfile,
+    loans::{LoanKind, NewLoan, RepaymentMethod},
+    market_data::{DataSource, Quote},
+    notifications::NotificationChannel,
+};
+
+fn months_ago(today: NaiveDate, months: u32) -> NaiveDate {
+    today.checked_sub_months(Months::new(months)).unwrap()
+}
+
+#[tokio::test]
+async fn bond_is_valued_with_accrued_interest_and_projects_coupons() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let account_id = server.create_account("SSI", "SECURITIES").await;
+    let app = server.app();
+
+    let bond = json!({
+        "accountId": account_id,
+        "code": "vhm12403",
+        "issuer": "Vinhomes",
+        "issuerType": "CORPORATE",
+        "faceValue": 100_000,
+        "quantity": 50,
+        "couponRate": 9.5,
+        "couponFrequency": "SEMI_ANNUAL",
+        "issueDate": "2024-01-15",
+        "maturityDate": "2034-01-15",
+        "purchaseDate": "2024-01-15",
+        "purchasePrice": 100_000,
+    });
+    let mut matures_first = bond.clone();
+    matures_first["maturityDate"] = json!("2023-12-31");
+    let (status, _) = send(&app, "POST", "/api/v1/bonds", Some(matures_first)).await;
+    assert_eq!(status, 400);
+
+    let (status, created) = send(&app, "POST", "/api/v1/bonds", Some(bond)).await;
+    assert_eq!(status, 200, "{}", created);
+    let id = created["id"].as_str().unwrap().to_string();
+    let symbol = created["assetId"].as_str().unwrap().to_string();
+    assert_eq!(created["code"], "VHM12403");
+    assert!(created["activityId"].is_string());
+    // Quoted at the purchase price and at today's clean price plus accrued interest
+    let quotes = state
+        .market_data_service
+        .get_historical_quotes_for_symbol(&symbol)
+        .unwrap();
+    assert_eq!(quotes.len(), 2);
+
+    let (status, summary) = send(&app, "GET", &format!("/api/v1/bonds/{}", id), None).await;
+    assert_eq!(status, 200);
+    assert_eq!(summary["cleanValue"], json!(5_000_000.0));
+    assert_eq!(summary["annualCouponIncome"], json!(475_000.0));
+    let accrued = summary["accruedInterest"].as_f64().unwrap();
+    assert!((0.0..237_500.0).contains(&accrued));
+    assert_eq!(
+        summary["marketValue"].as_f64().unwrap(),
+        5_000_000.0 + accrued
+    );
+    assert_eq!(summary["nextPayment"]["coupon"], json!(237_500.0));
+
+    // Two semi-annual coupons fall in any twelve months
+    let (status, payments) = send(&app, "GET", "/api/v1/bonds/payments?months=12", None).await;
+    assert_eq!(status, 200);
+    assert_eq!(payments.as_array().unwrap().len(), 2);
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/v1/bonds/{}", id), None).await;
+    assert_eq!(status, 204);
+    let (status, _) = send(&app, "GET", &format!("/api/v1/bonds/{}", id), None).await;
+    assert_eq!(status, 400);
+    let quotes = state
+        .market_data_service
+        .get_historical_quotes_for_symbol(&symbol)
+        .unwrap();
+    assert!(quotes.is_empty());
+    let (_, bonds) = send(&app, "GET", "/api/v1/bonds", None).await;
+    assert_eq!(bonds, json!([]));
+}
+
+#[tokio::test]
+async fn esop_grant_counts_vested_shares_and_reminds_before_vesting() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let account_id = server.create_account("ESOP", "SECURITIES").await;
+    let app = server.app();
+
+    // Past the one-year cliff by a day; the first quarter after it has not vested yet
+    let today = Utc::now().date_naive();
+    let grant_date = months_ago(today, 12) - Days::new(1);
+    let grant = json!({
+        "accountId": account_id,
+        "company": "Tiki",
+        "grantDate": grant_date,
+        "totalShares": 1_000,
+        "strikePrice": 10_000,
+        "cliffMonths": 12,
+        "vestingMonths": 48,
+        "vestingIntervalMonths": 3,
+        "lockupMonths": 12,
+        "fairValue": 50_000,
+    });
+    let mut unpriced = grant.clone();
+    unpriced["fairValue"] = Value::Null;
+    let (status, _) = send(&app, "POST", "/api/v1/esop-grants", Some(unpriced)).await;
+    assert_eq!(status, 400);
+
+    let (status, created) = send(&app, "POST", "/api/v1/esop-grants", Some(grant)).await;
+    assert_eq!(status, 200, "{}", created);
+    let id = created["id"].as_str().unwrap().to_string();
+    let symbol = created["assetId"].as_str().unwrap().to_string();
+    assert!(created["activityId"].is_string());
+    // Quoted at nothing on the grant date, then on the cliff and today
+    let quotes = state
+        .market_data_service
+        .get_historical_quotes_for_symbol(&symbol)
+        .unwrap();
+    assert_eq!(quotes.len(), 3);
+
+    let (status, summary) = send(&app, "GET", &format!("/api/v1/esop-grants/{}", id), None).await;
+    assert_eq!(status, 200);
+    assert_eq!(summary["vestedShares"], json!(250.0));
+    assert_eq!(summary["lockedShares"], json!(250.0));
+    assert_eq!(summary["vestedValue"], json!(10_000_000.0));
+    assert_eq!(summary["nextVesting"]["shares"], json!(62.0));
+    assert_eq!(summary["fullyVested"], json!(false));
+
+    let (status, vestings) = send(&app, "GET", "/api/v1/esop-grants/vestings?days=100", None).await;
+    assert_eq!(status, 200);
+    assert_eq!(vestings.as_array().unwrap().len(), 1);
+    assert_eq!(vestings[0]["value"], json!(2_480_000.0));
+    // Nothing vests within the reminder window
+    assert!(state
+        .esop_service
+        .vesting_notifications(Utc::now())
+        .unwrap()
+        .is_empty());
+
+    let mut soon = created.clone();
+    soon["vestingStartDate"] = json!(months_ago(today, 12) + Days::new(3));
+    let (status, updated) = send(
+        &app,
+        "PUT",
+        &format!("/api/v1/esop-grants/{}", id),
+        Some(soon),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", updated);
+    let notifications = state
+        .esop_service
+        .vesting_notifications(Utc::now())
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].channel, NotificationChannel::Vesting);
+    let (_, summary) = send(&app, "GET", &format!("/api/v1/esop-grants/{}", id), None).await;
+    assert_eq!(summary["vestedValue"], json!(0.0));
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/v1/esop-grants/{}", id), None).await;
+    assert_eq!(status, 204);
+    let (status, _) = send(&app, "GET", &format!("/api/v1/esop-grants/{}", id), None).await;
+    assert_eq!(status, 400);
+    let quotes = state
+        .market_data_service
+        .get_historical_quotes_for_symbol(&symbol)
+        .unwrap();
+    assert!(quotes.is_empty());
+    let (_, grants) = send(&app, "GET", "/api/v1/esop-grants", None).await;
+    assert_eq!(grants, json!([]));
+}
+
+#[tokio::test]
+async fn sip_plan_reconciles_purchases_and_plans_the_installments() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let account_id = server.create_account("Fmarket", "SECURITIES").await;
+    state
+        .asset_service
+        .create_manual_asset("VESAF", "VND".to_string())
+        .await
+        .unwrap();
+    let app = server.app();
+
+    // Three months in: the first two installments bought, the third skipped, today's due
+    let today = Utc::now().date_naive();
+    let start = months_ago(today, 3);
+    for (date, units, nav) in [
+        (start, 100, 20_000),
+        (months_ago(today, 2) + Days::new(2), 80, 25_000),
+    ] {
+        state
+            .activity_service
+            .create_activity(NewActivity {
+                id: None,
+                account_id: account_id.clone(),
+                asset_id: "VESAF".to_string(),
+                activity_type: "BUY".to_string(),
+                activity_date: date.to_string(),
+                quantity: Some(Decimal::from(units)),
+                unit_price: Some(Decimal::from(nav)),
+                currency: "VND".to_string(),
+                fee: Some(Decimal::ZERO),
+                amount: None,
+                is_draft: false,
+                comment: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    let plan = json!({
+        "accountId": account_id,
+        "assetId": "VESAF",
+        "amount": 2_000_000,
+        "startDate": start,
+    });
+    let mut unpriced = plan.clone();
+    unpriced["amount"] = json!(0);
+    let (status, _) = send(&app, "POST", "/api/v1/sip-plans", Some(unpriced)).await;
+    assert_eq!(status, 400);
+
+    let (status, created) = send(&app, "POST", "/api/v1/sip-plans", Some(plan)).await;
+    assert_eq!(status, 200, "{}", created);
+    let id = created["id"].as_str().unwrap().to_string();
+    assert_eq!(created["name"], "VESAF monthly");
+    // The installments show up in the forecast as a monthly contribution
+    let (_, flows) = send(&app, "GET", "/api/v1/forecast/cash-flows", None).await;
+    assert_eq!(flows.as_array().unwrap().len(), 1);
+    assert_eq!(flows[0]["id"], created["plannedCashFlowId"]);
+    assert_eq!(flows[0]["kind"], "CONTRIBUTION");
+    assert_eq!(flows[0]["frequency"], "MONTHLY");
+
+    let (status, summary) = send(&app, "GET", &format!("/api/v1/sip-plans/{}", id), None).await;
+    assert_eq!(status, 200, "{}", summary);
+    let statuses: Vec<&str> = summary["installments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|installment| installment["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(statuses, ["PURCHASED", "PURCHASED", "MISSED", "DUE", "DUE"]);
+    assert_eq!(summary["missedInstallments"], 1);
+    assert_eq!(summary["expectedAmount"], json!(8_000_000.0));
+    assert_eq!(summary["investedAmount"], json!(4_000_000.0));
+    assert_eq!(summary["units"], json!(180.0));
+    assert_eq!(summary["marketValue"], json!(4_500_000.0));
+    // The same 4m at the first NAV would have bought 200 units
+    assert_eq!(summary["lumpSum"]["units"], json!(200.0));
+    assert_eq!(summary["lumpSum"]["marketValue"], json!(5_000_000.0));
+
+    let mut ended = created.clone();
+    ended["amount"] = json!(3_000_000);
+    let (status, updated) = send(
+        &app,
+        "PUT",
+        &format!("/api/v1/sip-plans/{}", id),
+        Some(ended),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", updated);
+    let (_, flows) = send(&app, "GET", "/api/v1/forecast/cash-flows", None).await;
+    assert_eq!(flows[0]["amount"], json!(3_000_000.0));
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/v1/sip-plans/{}", id), None).await;
+    assert_eq!(status, 204);
+    let (status, _) = send(&app, "GET", &format!("/api/v1/sip-plans/{}", id), None).await;
+    assert_eq!(status, 400);
+    let (_, flows) = send(&app, "GET", "/api/v1/forecast/cash-flows", None).await;
+    assert_eq!(flows, json!([]));
+    let (_, plans) = send(&app, "GET", "/api/v1/sip-plans", None).await;
+    assert_eq!(plans, json!([]));
+}
+
+#[tokio::test]
+async fn hose_trades_follow_lots_and_price_bands() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let account_id = server.create_account("SSI", "SECURITIES").await;
+    // A HOSE stock as the VN market provider records it
+    state
+        .asset_service
+        .create_manual_asset("FPT", "VND".to_string())
+        .await
+        .unwrap();
+    state
+        .asset_service
+        .update_asset_profile(
+            "FPT",
+            UpdateAssetProfile {
+                symbol: "FPT".to_string(),
+                name: Some("FPT Corporation".to_string()),
+                sectors: None,
+                countries: None,
+                notes: String::new(),
+                asset_sub_class: Some("HOSE".to_string()),
+                asset_class: Some("EQUITY".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+    state
+        .asset_service
+        .update_asset_data_source("FPT", "VN_MARKET".to_string())
+        .await
+        .unwrap();
+
+    let today = Utc::now()
+        .with_timezone(&FixedOffset::east_opt(7 * 3600).unwrap())
+        .date_naive();
+    let reference = 100_000u64.into();
+    state
+        .market_data_service
+        .add_quote(&Quote {
+            id: "FPT_reference".to_string(),
+            symbol: "FPT".to_string(),
+            timestamp: (today - Days::new(1))
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc(),
+            open: reference,
+            high: reference,
+            low: reference,
+            close: reference,
+            adjclose: reference,
+            volume: 0u64.into(),
+            currency: "VND".to_string(),
+            data_source: DataSource::VnMarket,
+            created_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    let app = server.app();
+
+    let buy = |quantity: u64, unit_price: u64| {
+        json!({
+            "accountId": account_id,
+            "assetId": "FPT",
+            "activityType": "BUY",
+            "activityDate": today.format("%Y-%m-%d").to_string(),
+            "quantity": quantity,
+            "unitPrice": unit_price,
+            "currency": "VND",
+            "fee": 0,
+            "isDraft": false,
+        })
+    };
+    // Board lots and odd lots trade separately
+    let (status, body) = send(&app, "POST", "/api/v1/activities", Some(buy(150, 100_000))).await;
+    assert_eq!(status, 400, "{}", body);
+    // HOSE moves at most 7% from the reference price
+    let (status, body) = send(&app, "POST", "/api/v1/activities", Some(buy(100, 108_000))).await;
+    assert_eq!(status, 400, "{}", body);
+    let (status, body) = send(&app, "POST", "/api/v1/activities", Some(buy(200, 106_500))).await;
+    assert_eq!(status, 200, "{}", body);
+    let (status, body) = send(&app, "POST", "/api/v1/activities", Some(buy(30, 99_000))).await;
+    assert_eq!(status, 200, "{}", body);
+
+    let (status, pending) = send(
+        &app,
+        "GET",
+        &format!(
+            "/api/v1/activities/pending-settlements?accountId={}",
+            account_id
+        ),
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    let pending = pending.as_array().unwrap();
+    assert_eq!(pending.len(), 2);
+    assert!(pending.iter().all(|p| p["exchange"] == "HOSE"));
+    assert_eq!(pending.iter().filter(|p| p["oddLot"] == true).count(), 1);
+    assert!(pending[0]["settlesOn"].as_str().unwrap() > today.to_string().as_str());
+}
+
+#[tokio::test]
+async fn property_is_valued_at_its_appraisals_less_the_mortgage() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let home = server.create_account("Căn hộ", "REAL_ESTATE").await;
+    let mortgage = server.create_account("Vay mua nhà", "LIABILITY").await;
+    state
+        .loan_service
+        .create_loan(NewLoan {
+            id: None,
+            account_id: mortgage.clone(),
+            kind: LoanKind::Mortgage,
+            principal: 1_800_000_000u64.into(),
+            start_date: chrono::NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            term_months: 300,
+            repayment_method: RepaymentMethod::EqualPrincipal,
+            fixed_rate: 7.into(),
+            fixed_months: 300,
+            base_rate: None,
+            floating_margin: None,
+        })
+        .await
+        .unwrap();
+    let app = server.app();
+
+    let property = json!({
+        "accountId": home,
+        "name": "Căn hộ Thủ Đức",
+        "purchaseDate": "2025-03-01",
+        "purchasePrice": 3_000_000_000u64,
+        "transactionCosts": 60_000_000u64,
+        "mortgageAccountId": mortgage,
+    });
+    // Only a real-estate account can hold the property
+    let mut on_liability = property.clone();
+    on_liability["accountId"] = json!(mortgage);
+    on_liability["mortgageAccountId"] = Value::Null;
+    let (status, _) = send(&app, "POST", "/api/v1/properties", Some(on_liability)).await;
+    assert_eq!(status, 400);
+    let (status, created) = send(&app, "POST", "/api/v1/properties", Some(property.clone())).await;
+    assert_eq!(status, 200, "{}", created);
+    let id = created["id"].as_str().unwrap().to_string();
+    let symbol = created["assetId"].as_str().unwrap().to_string();
+    assert!(created["activityId"].is_string());
+    // One property per account
+    let (status, _) = send(&app, "POST", "/api/v1/properties", Some(property)).await;
+    assert_eq!(status, 400);
+
+    let (status, appraisal) = send(
+        &app,
+        "POST",
+        "/api/v1/properties/appraisals",
+        Some(json!({
+            "propertyId": id,
+            "appraisalDate": "2026-03-01",
+            "value": 3_500_000_000u64,
+        })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", appraisal);
+    // The purchase and the appraisal both value the asset
+    let quotes = state
+        .market_data_service
+        .get_historical_quotes_for_symbol(&symbol)
+        .unwrap();
+    assert_eq!(quotes.len(), 2);
+
+    let (status, summary) = send(&app, "GET", &format!("/api/v1/properties/{}", id), None).await;
+    assert_eq!(status, 200);
+    assert_eq!(summary["currentValue"], json!(3_500_000_000.0));
+    assert_eq!(summary["unrealizedGain"], json!(440_000_000.0));
+    let balance = summary["mortgageBalance"].as_f64().unwrap();
+    assert!(balance > 0.0 && balance < 1_800_000_000.0);
+    assert_eq!(
+        summary["equity"].as_f64().unwrap(),
+        3_500_000_000.0 - balance
+    );
+
+    let appraisal_uri = format!(
+        "/api/v1/properties/appraisals/{}",
+        appraisal["id"].as_str().unwrap()
+    );
+    let (status, _) = send(&app, "DELETE", &appraisal_uri, None).await;
+    assert_eq!(status, 204);
+    let (_, summary) = send(&app, "GET", &format!("/api/v1/properties/{}", id), None).await;
+    assert_eq!(summary["currentValue"], json!(3_000_000_000.0));
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/v1/properties/{}", id), None).await;
+    assert_eq!(status, 204);
+    let (status, _) = send(&app, "GET", &format!("/api/v1/properties/{}", id), None).await;
+    assert_eq!(status, 400);
+    let quotes = state
+        .market_data_service
+        .get_historical_quotes_for_symbol(&symbol)
+        .unwrap();
+    assert!(quotes.is_empty());
+}
@@ -0,0 +1,204 @@
+//! Running the server: cancellable operations, automation rules, service readiness and the MCP
+//! tools offered to assistants
+
+mod common;
+
+use common::{send, TestServer};
+use serde_json::json;
+use wealthvn_core::goals::goals_model::NewGoal;
+use wealthvn_server::mcp::handle_line;
+
+#[tokio::test]
+async fn finished_operations_leave_the_registry_and_can_no_longer_be_cancelled() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+    let app = server.app();
+
+    let (status, _) = send(&app, "POST", "/api/v1/operations/unknown/cancel", None).await;
+    assert_eq!(status, 404);
+
+    // The export runs under the client's id while it streams, and is gone once it is read
+    let (status, _) = send(
+        &app,
+        "GET",
+        "/api/v1/exports/data?dataset=accounts&format=csv&operationId=export-1&timeoutSecs=60",
+        None,
+    )
+    .await;
+    assert_eq!(status, 200);
+    for _ in 0..50 {
+        if state.operations.list().is_empty() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let (status, operations) = send(&app, "GET", "/api/v1/operations", None).await;
+    assert_eq!(status, 200);
+    assert_eq!(operations, json!([]));
+    let (status, _) = send(&app, "POST", "/api/v1/operations/export-1/cancel", None).await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn automation_rules_are_validated_and_stored() {
+    let server = TestServer::start().await;
+    let app = server.app();
+
+    // Tags only make sense for the activities of an import
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/automation-rules",
+        Some(json!({
+            "name": "Tag movers",
+            "trigger": { "type": "QUOTE_MOVED", "percent": "5" },
+            "actions": [{ "type": "TAG_ACTIVITIES", "tag": "volatile" }],
+            "isEnabled": true
+        })),
+    )
+    .await;
+    assert_eq!(status, 400);
+
+    let (status, created) = send(
+        &app,
+        "POST",
+        "/api/v1/automation-rules",
+        Some(json!({
+            "name": "Big move",
+            "trigger": { "type": "QUOTE_MOVED", "symbol": "FPT", "percent": "5" },
+            "actions": [{ "type": "NOTIFY", "title": "{symbol} moved {change}%" }],
+            "isEnabled": true
+        })),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(created["trigger"]["type"], "QUOTE_MOVED");
+    assert_eq!(created["actions"][0]["body"], "");
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let (_, rules) = send(&app, "GET", "/api/v1/automation-rules", None).await;
+    assert_eq!(rules.as_array().unwrap().len(), 1);
+    assert!(rules[0]["lastTriggeredAt"].is_null());
+
+    let (status, _) = send(
+        &app,
+        "DELETE",
+        &format!("/api/v1/automation-rules/{}", id),
+        None,
+    )
+    .await;
+    assert_eq!(status, 204);
+    let (_, rules) = send(&app, "GET", "/api/v1/automation-rules", None).await;
+    assert!(rules.as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn market_data_is_not_ready_until_initialized() {
+    let server = TestServer::start().await;
+    let app = server.app();
+
+    let (status, readiness) = send(&app, "GET", "/api/v1/telemetry/readiness", None).await;
+    assert_eq!(status, 200);
+    assert_eq!(readiness, json!({ "marketData": false, "valuation": true }));
+}
+
+#[tokio::test]
+async fn mcp_lists_and_calls_read_only_tools() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let goal = state
+        .goal_service
+        .create_goal(NewGoal {
+            id: None,
+            title: "Quỹ khẩn cấp".to_string(),
+            description: None,
+            target_amount: 120_000_000.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: None,
+            monthly_investment: None,
+            start_date: None,
+            initial_actual_value: None,
+            goal_type: "STANDARD".to_string(),
+            target_months: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+        })
+        .await
+        .unwrap();
+
+    let init = handle_line(
+        &state,
+        &json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "protocolVersion": "2024-11-05" } })
+            .to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(init["result"]["protocolVersion"], "2024-11-05");
+    assert!(handle_line(
+        &state,
+        r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#
+    )
+    .await
+    .is_none());
+
+    let list = handle_line(&state, r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#)
+        .await
+        .unwrap();
+    let names: Vec<&str> = list["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        ["get_net_worth", "get_goal_progress", "search_activities"]
+    );
+
+    let call = |id: i64, name: &str, arguments: serde_json::Value| {
+        let state = &state;
+        let line = json!({ "jsonrpc": "2.0", "id": id, "method": "tools/call", "params": { "name": name, "arguments": arguments } })
+            .to_string();
+        async move { handle_line(state, &line).await.unwrap() }
+    };
+
+    let goals = call(3, "get_goal_progress", json!({ "goalId": goal.id })).await;
+    assert_eq!(goals["result"]["isError"], false, "{}", goals);
+    let content = &goals["result"]["structuredContent"];
+    assert_eq!(content["goals"][0]["title"], "Quỹ khẩn cấp");
+    assert_eq!(content["goals"][0]["targetAmount"], 120_000_000.0);
+
+    let activities = call(4, "search_activities", json!({ "symbol": "FPT" })).await;
+    assert_eq!(
+        activities["result"]["structuredContent"]["activities"],
+        json!([])
+    );
+
+    let net_worth = call(5, "get_net_worth", json!({})).await;
+    assert_eq!(net_worth["result"]["isError"], false, "{}", net_worth);
+
+    // Bad arguments come back as a tool error the assistant can read
+    let invalid = call(
+        6,
+        "get_net_worth",
+        json!({ "startDate": "2025-02-01", "endDate": "2025-01-01" }),
+    )
+    .await;
+    assert_eq!(invalid["result"]["isError"], true);
+
+    let unknown = handle_line(
+        &state,
+        r#"{"jsonrpc":"2.0","id":7,"method":"resources/list"}"#,
+    )
+    .await
+    .unwrap();
+    assert_eq!(unknown["error"]["code"], -32601);
+
+    // Only the listed tools can be called, whatever the assistant asks for
+    let write = call(8, "delete_goal", json!({ "goalId": goal.id })).await;
+    assert_eq!(write["error"]["code"], -32602, "{}", write);
+    assert_eq!(state.goal_service.get_goals().await.unwrap().len(), 1);
+}
@@ -0,0 +1,165 @@
+//! Planning around one-off income: the state pension estimate and the Tet bonus plan
+
+mod common;
+
+use common::{send, TestServer};
+use serde_json::json;
+use wealthvn_core::{envelopes::NewEnvelope, goals::goals_model::NewGoal};
+
+#[tokio::test]
+async fn pension_estimate_offsets_the_nest_egg() {
+    let server = TestServer::start().await;
+    let app = server.app();
+
+    let (status, estimate) = send(
+        &app,
+        "POST",
+        "/api/v1/pension/estimate",
+        Some(json!({
+            "gender": "MALE",
+            "birthDate": "1988-03-10",
+            "salaryHistory": [
+                { "startDate": "2010-07-01", "endDate": "2019-12-31", "monthlySalary": 8_000_000 },
+                // Above the cap of 20 times the reference level
+                { "startDate": "2020-01-01", "monthlySalary": 60_000_000 },
+            ],
+            "salaryAdjustmentRate": 0,
+            "desiredMonthlySpending": 30_000_000,
+        })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", estimate);
+    assert_eq!(estimate["retirementDate"], "2050-03-10");
+    assert_eq!(estimate["eligible"], true);
+    assert_eq!(estimate["pensionRate"].as_f64(), Some(75.0));
+    let average = estimate["averageSalary"].as_f64().unwrap();
+    assert!(average < 46_800_000.0);
+    let nest_egg = &estimate["nestEgg"];
+    assert_eq!(nest_egg["withoutPension"].as_f64(), Some(9_000_000_000.0));
+    assert!(nest_egg["withPension"].as_f64().unwrap() < 9_000_000_000.0);
+
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/v1/pension/estimate",
+        Some(json!({ "gender": "FEMALE", "birthDate": "1990-01-01", "salaryHistory": [] })),
+    )
+    .await;
+    assert_eq!(status, 400);
+}
+
+#[tokio::test]
+async fn confirmed_bonus_plan_records_deposit_and_goal_allocations() {
+    let server = TestServer::start().await;
+    let state = server.state.clone();
+
+    let account_id = server.create_account("Vietcombank", "CASH").await;
+    let envelope = state
+        .envelope_service
+        .create_envelope(NewEnvelope {
+            id: None,
+            account_id: account_id.clone(),
+            name: "Sửa nhà".to_string(),
+        })
+        .await
+        .unwrap();
+    let goal = state
+        .goal_service
+        .create_goal(NewGoal {
+            id: None,
+            title: "Xe máy".to_string(),
+            description: None,
+            target_amount: 20_000_000.0,
+            is_achieved: false,
+            target_return_rate: None,
+            due_date: None,
+            monthly_investment: None,
+            start_date: None,
+            initial_actual_value: None,
+            goal_type: "STANDARD".to_string(),
+            target_months: None,
+            priority: 0,
+            depends_on_goal_id: None,
+            currency: None,
+        })
+        .await
+        .unwrap();
+    let app = server.app();
+
+    let (status, proposal) = send(
+        &app,
+        "POST",
+        "/api/v1/bonus-plans/propose",
+        Some(json!({
+            "accountId": account_id,
+            "amount": 50_000_000u64,
+            "expectedDate": "2027-01-25",
+        })),
+    )
+    .await;
+    assert_eq!(status, 200, "{}", proposal);
+    let lines = proposal["lines"].as_array().unwrap();
+    let share = |kind: &str| {
+        lines
+            .iter()
+            .find(|line| line["kind"] == kind)
+            .map(|line| line["amount"].as_f64().unwrap())
+    };
+    assert_eq!(share("GIFTS"), Some(5_000_000.0));
+    assert_eq!(share("ENVELOPE"), Some(10_000_000.0));
+    // The goal only needs 20m of the 35m left
+    assert_eq!(share("GOAL"), Some(20_000_000.0));
+
+    // A share for an unknown goal fails the whole plan, leaving nothing behind
+    let mut unknown = proposal.clone();
+    unknown["lines"]
+        .as_array_mut()
+        .unwrap()
+        .push(json!({ "kind": "GOAL", "goalId": "missing", "label": "?", "amount": 1 }));
+    let (status, _) = send(&app, "POST", "/api/v1/bonus-plans", Some(unknown)).await;
+    assert_eq!(status, 400);
+    assert!(state.activity_service.get_activities().unwrap().is_empty());
+
+    let (status, plan) = send(&app, "POST", "/api/v1/bonus-plans", Some(proposal)).await;
+    assert_eq!(status, 200, "{}", plan);
+    let id = plan["id"].as_str().unwrap().to_string();
+    assert_eq!(plan["depositPending"], true);
+    let deposit = state
+        .activity_service
+        .get_activity(plan["activityId"].as_str().unwrap())
+        .unwrap();
+    assert!(deposit.is_draft);
+    assert_eq!(deposit.activity_type, "DEPOSIT");
+    let allocations = state.goal_service.load_goals_allocations().await.unwrap();
+    assert_eq!(allocations.len(), 1);
+    assert_eq!(allocations[0].goal_id, goal.id);
+    assert_eq!(allocations[0].allocation_amount, 20_000_000.0);
+    let goal_line = plan["lines"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|line| line["kind"] == "GOAL")
+        .unwrap();
+    assert_eq!(goal_line["allocationId"], json!(allocations[0].id));
+
+    // Envelopes are filled from the bonus only once it has arrived
+    let fund_uri = format!("/api/v1/bonus-plans/{}/fund-envelopes", id);
+    let (status, _) = send(&app, "POST", &fund_uri, None).await;
+    assert_eq!(status, 400);
+    assert!(state
+        .envelope_service
+        .get_envelope_transfers(&account_id)
+        .unwrap()
+        .iter()
+        .all(|transfer| transfer.to_envelope_id.as_deref() != Some(envelope.id.as_str())));
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/v1/bonus-plans/{}", id), None).await;
+    assert_eq!(status, 204);
+    assert!(state.activity_service.get_activities().unwrap().is_empty());
+    assert!(state
+        .goal_service
+        .load_goals_allocations()
+        .await
+        .unwrap()
+        .is_empty());
+}
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use super::audit::record_audit;
+use super::portfolio::privacy_mode;
 use crate::{
     context::ServiceContext,
     events::{emit_resource_changed, ResourceEventPayload},
@@ -8,7 +10,11 @@ use log::{debug, error};
 use tauri::{AppHandle, State};
 
 use serde_json::json;
-use wealthvn_core::accounts::{Account, AccountUpdate, NewAccount};
+use wealthvn_core::accounts::{
+    Account, AccountGroup, AccountGroupValuation, AccountUpdate, NewAccount, NewAccountGroup,
+};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::privacy::mask_if;
 
 #[tauri::command]
 pub async fn get_accounts(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Account>, String> {
@@ -41,6 +47,12 @@ pub async fn create_account(
 
     match result {
         Ok(acc) => {
+            record_audit(
+                &state,
+                NewAuditLogEntry::new("account", &acc.id, AuditAction::Create, AUDIT_ACTOR_USER)
+                    .with_snapshots(None::<&Account>, Some(&acc)),
+            )
+            .await;
             emit_resource_changed(
                 &handle,
                 ResourceEventPayload::new(
@@ -69,6 +81,11 @@ pub async fn update_account(
 ) -> Result<Account, String> {
     debug!("Updating account {:?}...", account_update.id);
 
+    let previous_account = account_update
+        .id
+        .as_deref()
+        .and_then(|id| state.account_service().get_account(id).ok());
+
     // Perform the update - Assuming synchronous based on prior linter errors
     let updated_account = state
         .account_service()
@@ -76,6 +93,18 @@ pub async fn update_account(
         .await // Add .await here
         .map_err(|e| format!("Failed to update account {:?}: {}", account_update.id, e))?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "account",
+            &updated_account.id,
+            AuditAction::Update,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous_account.as_ref(), Some(&updated_account)),
+    )
+    .await;
+
     // Trigger recalculation after successful update
     let handle = handle.clone();
     let account_id_clone = updated_account.id.clone();
@@ -102,6 +131,7 @@ pub async fn delete_account(
     handle: tauri::AppHandle,
 ) -> Result<(), String> {
     debug!("Deleting account {}...", account_id); // Add account_id to log
+    let previous_account = state.account_service().get_account(&account_id).ok();
     state
         .account_service()
         .delete_account(&account_id)
@@ -111,6 +141,13 @@ pub async fn delete_account(
             e.to_string()
         })?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("account", &account_id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous_account.as_ref(), None::<&Account>),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new(
@@ -124,3 +161,87 @@ pub async fn delete_account(
 
     Ok(())
 }
+
+#[tauri::command]
+pub async fn get_account_groups(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<AccountGroup>, String> {
+    debug!("Fetching account groups...");
+    state
+        .account_group_service()
+        .get_account_groups()
+        .map_err(|e| format!("Failed to load account groups: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_account_group(
+    group: NewAccountGroup,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<AccountGroup, String> {
+    debug!("Adding new account group...");
+    let group = state
+        .account_group_service()
+        .create_account_group(group)
+        .await
+        .map_err(|e| format!("Failed to add account group: {}", e))?;
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("account_group", "created", json!({ "group_id": group.id })),
+    );
+    Ok(group)
+}
+
+#[tauri::command]
+pub async fn update_account_group(
+    id: String,
+    group: NewAccountGroup,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<AccountGroup, String> {
+    debug!("Updating account group {}...", id);
+    let group = state
+        .account_group_service()
+        .update_account_group(&id, group)
+        .await
+        .map_err(|e| format!("Failed to update account group {}: {}", id, e))?;
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("account_group", "updated", json!({ "group_id": group.id })),
+    );
+    Ok(group)
+}
+
+#[tauri::command]
+pub async fn delete_account_group(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<(), String> {
+    debug!("Deleting account group {}...", id);
+    state
+        .account_group_service()
+        .delete_account_group(&id)
+        .await
+        .map_err(|e| format!("Failed to delete account group {}: {}", id, e))?;
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("account_group", "deleted", json!({ "group_id": id })),
+    );
+    Ok(())
+}
+
+/// Latest value of a group and the groups inside it, in the base currency
+#[tauri::command]
+pub async fn get_account_group_valuation(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AccountGroupValuation, String> {
+    debug!("Valuing account group {}...", id);
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .account_group_service()
+        .get_group_valuation(&id)
+        .map(|valuation| mask_if(valuation, privacy_mode))
+        .map_err(|e| e.to_string())
+}
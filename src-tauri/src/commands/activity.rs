@@ -1,15 +1,19 @@
 use std::sync::Arc;
 
+use super::audit::record_audit;
+use super::automations::run_import_automations;
 use crate::context::ServiceContext;
-use crate::events::{emit_resource_changed, ResourceEventPayload};
+use crate::events::{emit_resource_changed, emit_script_report, ResourceEventPayload};
 use log::debug;
 use tauri::{AppHandle, State};
 use wealthvn_core::activities::{
     Activity, ActivityBulkMutationRequest, ActivityBulkMutationResult, ActivityImport,
-    ActivitySearchResponse, ActivityUpdate, ImportMappingData, NewActivity, Sort,
+    ActivitySearchResponse, ActivityUpdate, ImportMappingData, NewActivity, PendingSettlement,
+    Sort,
 };
 
 use serde_json::json;
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_IMPORT, AUDIT_ACTOR_USER};
 
 #[tauri::command]
 pub async fn get_activities(
@@ -49,6 +53,13 @@ pub async fn create_activity(
     debug!("Creating activity...");
     let result = state.activity_service().create_activity(activity).await?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("activity", &result.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Activity>, Some(&result)),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new(
@@ -81,6 +92,13 @@ pub async fn update_activity(
 
     let result = state.activity_service().update_activity(activity).await?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("activity", &result.id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&original_activity), Some(&result)),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new(
@@ -114,6 +132,13 @@ pub async fn delete_activity(
         .await
         .map_err(|e| e.to_string())?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("activity", &result.id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&result), None::<&Activity>),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new(
@@ -169,6 +194,17 @@ pub async fn save_activities(
     Ok(result)
 }
 
+#[tauri::command]
+pub async fn get_pending_settlements(
+    account_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<PendingSettlement>, String> {
+    debug!("Fetching pending settlements...");
+    Ok(state
+        .activity_service()
+        .get_pending_settlements(account_id)?)
+}
+
 #[tauri::command]
 pub async fn get_account_import_mapping(
     account_id: String,
@@ -194,10 +230,16 @@ pub async fn save_account_import_mapping(
 #[tauri::command]
 pub async fn check_activities_import(
     account_id: String,
-    activities: Vec<ActivityImport>,
+    mut activities: Vec<ActivityImport>,
     state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
 ) -> Result<Vec<ActivityImport>, String> {
     debug!("Checking activities import for account: {}", account_id);
+    // Scripts edit the rows before they are checked, so the preview shows their changes
+    let report = state
+        .script_service()
+        .run_import_row_hooks(&mut activities)?;
+    emit_script_report(&handle, "on_import_row", &report);
     let result = state
         .activity_service()
         .check_activities_import(account_id, activities)
@@ -223,10 +265,41 @@ pub async fn import_activities(
         })
         .collect();
 
+    let started_at = chrono::Utc::now().naive_utc();
     let result = state
         .activity_service()
         .import_activities(account_id.clone(), activities) // activities is moved here
         .await?;
+
+    // Imported payments settle bills first so a paid bill's category wins over rules
+    if let Err(e) = state.bill_service().match_bill_payments().await {
+        log::warn!("Bill matching after import failed: {}", e);
+    }
+    if let Err(e) = state
+        .private_loan_service()
+        .match_private_loan_repayments()
+        .await
+    {
+        log::warn!("Private loan repayment matching after import failed: {}", e);
+    }
+
+    // Imported bank activities pick up categories from rules; a failure here never fails the import
+    if let Err(e) = state.categorization_service().auto_categorize().await {
+        log::warn!("Auto-categorization after import failed: {}", e);
+    }
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("account", &account_id, AuditAction::Import, AUDIT_ACTOR_IMPORT)
+            .with_changes(json!({ "importedCount": result.len() })),
+    )
+    .await;
+    // A rejected import returns every row with its errors and saves nothing
+    let rejected = result.iter().any(|row| {
+        !row.is_valid || row.errors.as_ref().is_some_and(|errors| !errors.is_empty())
+    });
+    let imported = if rejected { 0 } else { result.len() };
+    run_import_automations(&state, &handle, &account_id, imported, started_at).await;
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new(
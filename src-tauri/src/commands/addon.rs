@@ -295,7 +295,8 @@ pub async fn check_addon_update(
     current_version: String,
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<AddonUpdateCheckResult, String> {
-    let instance_id = state.instance_id.as_str();
+    let instance_id = state.instance_id();
+    let instance_id = instance_id.as_str();
     // Check for updates from addon store
     match addons::check_addon_update_from_api(&addon_id, &current_version, Some(instance_id)).await {
         Ok(update_check_result) => {
@@ -336,7 +337,8 @@ pub async fn check_all_addon_updates(
 ) -> Result<Vec<AddonUpdateCheckResult>, String> {
     let installed_addons = list_installed_addons(app_handle.clone()).await?;
     let mut results = Vec::new();
-    let instance_id = state.instance_id.as_str();
+    let instance_id = state.instance_id();
+    let instance_id = instance_id.as_str();
 
     for addon in installed_addons {
         match addons::check_addon_update_from_api(
@@ -384,7 +386,8 @@ pub async fn update_addon_from_store_by_id(
     addon_id: String,
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<AddonManifest, String> {
-    let instance_id = state.instance_id.as_str();
+    let instance_id = state.instance_id();
+    let instance_id = instance_id.as_str();
 
     // Download the addon package using the new download API
     let zip_data = addons::download_addon_from_store(&addon_id, instance_id)
@@ -426,7 +429,8 @@ pub async fn update_addon_from_store_by_id(
 pub async fn fetch_addon_store_listings(
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<Vec<serde_json::Value>, String> {
-    let instance_id = state.instance_id.as_str();
+    let instance_id = state.instance_id();
+    let instance_id = instance_id.as_str();
     addons::fetch_addon_store_listings(Some(instance_id)).await
 }
 
@@ -445,7 +449,8 @@ pub async fn download_addon_to_staging(
         .ok_or("Failed to convert app data dir path to string")?
         .to_string();
 
-    let instance_id = state.instance_id.as_str();
+    let instance_id = state.instance_id();
+    let instance_id = instance_id.as_str();
 
     // Download addon data
     let zip_data = addons::download_addon_from_store(&addon_id, instance_id)
@@ -519,6 +524,7 @@ pub async fn submit_addon_rating(
     review: Option<String>,
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<serde_json::Value, String> {
-    let instance_id = state.instance_id.as_str();
+    let instance_id = state.instance_id();
+    let instance_id = instance_id.as_str();
     addons::submit_addon_rating(&addon_id, rating, review, instance_id).await
 }
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::api_tokens::{ApiToken, IssuedApiToken, NewApiToken};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+
+#[tauri::command]
+pub async fn get_api_tokens(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ApiToken>, String> {
+    debug!("Fetching API tokens...");
+    state
+        .api_token_service()
+        .get_tokens()
+        .map_err(|e| format!("Failed to load API tokens: {}", e))
+}
+
+/// The secret in the result is shown to the user once and never stored
+#[tauri::command]
+pub async fn create_api_token(
+    token: NewApiToken,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<IssuedApiToken, String> {
+    debug!("Creating API token {}...", token.name);
+    let issued = state
+        .api_token_service()
+        .create_token(token)
+        .await
+        .map_err(|e| format!("Failed to create API token: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "api_token",
+            &issued.token.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&ApiToken>, Some(&issued.token)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "api_token",
+            "created",
+            json!({ "api_token_id": issued.token.id }),
+        ),
+    );
+
+    Ok(issued)
+}
+
+#[tauri::command]
+pub async fn revoke_api_token(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ApiToken, String> {
+    debug!("Revoking API token {}...", id);
+    let service = state.api_token_service();
+    let previous = service.get_token(&id).map_err(|e| e.to_string())?;
+    let revoked = service
+        .revoke_token(&id)
+        .await
+        .map_err(|e| format!("Failed to revoke API token: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("api_token", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), Some(&revoked)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("api_token", "updated", json!({ "api_token_id": id })),
+    );
+
+    Ok(revoked)
+}
+
+#[tauri::command]
+pub async fn delete_api_token(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting API token {}...", id);
+    let service = state.api_token_service();
+    let previous = service.get_token(&id).map_err(|e| e.to_string())?;
+    let deleted = service
+        .delete_token(&id)
+        .await
+        .map_err(|e| format!("Failed to delete API token: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("api_token", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), None::<&ApiToken>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("api_token", "deleted", json!({ "api_token_id": id })),
+    );
+
+    Ok(deleted)
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use log::debug;
+use tauri::State;
+use wealthvn_core::app_lock::AppLockStatus;
+
+#[tauri::command]
+pub async fn get_app_lock_status(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AppLockStatus, String> {
+    Ok(state.app_lock_service().get_status())
+}
+
+#[tauri::command]
+pub async fn unlock_app(
+    passphrase: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AppLockStatus, String> {
+    debug!("Unlocking app...");
+    state
+        .app_lock_service()
+        .unlock(&passphrase)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn lock_app(state: State<'_, Arc<ServiceContext>>) -> Result<AppLockStatus, String> {
+    debug!("Locking app...");
+    let service = state.app_lock_service();
+    service.lock();
+    Ok(service.get_status())
+}
+
+#[tauri::command]
+pub async fn set_app_lock_passphrase(
+    current_passphrase: Option<String>,
+    new_passphrase: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AppLockStatus, String> {
+    debug!("Setting app lock passphrase...");
+    state
+        .app_lock_service()
+        .set_passphrase(current_passphrase.as_deref(), &new_passphrase)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_app_lock_passphrase(
+    current_passphrase: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AppLockStatus, String> {
+    debug!("Removing app lock passphrase...");
+    state
+        .app_lock_service()
+        .remove_passphrase(&current_passphrase)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_app_lock_timeout(
+    minutes: u32,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AppLockStatus, String> {
+    debug!("Setting app lock timeout to {} minutes...", minutes);
+    state
+        .app_lock_service()
+        .set_auto_lock_minutes(minutes)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use super::error::localize_error;
+use crate::context::ServiceContext;
+use log::{debug, warn};
+use tauri::State;
+use wealthvn_core::audit::{AuditLogQuery, AuditLogResponse, NewAuditLogEntry};
+
+#[tauri::command]
+pub async fn query_audit_log(
+    query: AuditLogQuery,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AuditLogResponse, String> {
+    debug!("Querying audit log: {:?}", query);
+    state
+        .audit_service()
+        .query_audit_log(query)
+        .map_err(|e| localize_error(&state, e))
+}
+
+/// Writes an audit entry after a successful mutation. A failed write is logged
+/// rather than surfaced, since the change itself has already been committed.
+pub async fn record_audit(state: &ServiceContext, entry: NewAuditLogEntry) {
+    let description = format!(
+        "{} {} {}",
+        entry.action.as_str(),
+        entry.entity_type,
+        entry.entity_id
+    );
+    if let Err(e) = state.audit_service().record(entry).await {
+        warn!("Failed to record audit entry for {}: {}", description, e);
+    }
+}
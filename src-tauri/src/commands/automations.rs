@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use super::scripts::{goal_progress, security_symbols};
+use crate::{
+    context::ServiceContext,
+    events::{emit_automation_report, emit_resource_changed, ResourceEventPayload},
+};
+use chrono::NaiveDateTime;
+use log::{debug, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::automations::{
+    ActivityTag, AutomationRule, AutomationRunReport, ImportCompletion, NewAutomationRule,
+};
+use wealthvn_core::errors::Result as CoreResult;
+
+#[tauri::command]
+pub async fn get_automation_rules(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<AutomationRule>, String> {
+    debug!("Fetching automation rules...");
+    state
+        .automation_service()
+        .get_rules()
+        .map_err(|e| format!("Failed to load automation rules: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_automation_rule(
+    rule: NewAutomationRule,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<AutomationRule, String> {
+    debug!("Creating automation rule {}...", rule.name);
+    let created = state
+        .automation_service()
+        .create_rule(rule)
+        .await
+        .map_err(|e| format!("Failed to create automation rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "automation_rule",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&AutomationRule>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "automation_rule",
+            "created",
+            json!({ "rule_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_automation_rule(
+    id: String,
+    rule: NewAutomationRule,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<AutomationRule, String> {
+    debug!("Updating automation rule {}...", id);
+    let service = state.automation_service();
+    let previous = service.get_rule(&id).map_err(|e| e.to_string())?;
+    let updated = service
+        .update_rule(&id, rule)
+        .await
+        .map_err(|e| format!("Failed to update automation rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "automation_rule",
+            &id,
+            AuditAction::Update,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(Some(&previous), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("automation_rule", "updated", json!({ "rule_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_automation_rule(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting automation rule {}...", id);
+    let service = state.automation_service();
+    let previous = service.get_rule(&id).map_err(|e| e.to_string())?;
+    let deleted = service
+        .delete_rule(&id)
+        .await
+        .map_err(|e| format!("Failed to delete automation rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "automation_rule",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(Some(&previous), None::<&AutomationRule>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("automation_rule", "deleted", json!({ "rule_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_activity_tags(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ActivityTag>, String> {
+    debug!("Fetching activity tags...");
+    state
+        .automation_service()
+        .get_activity_tags()
+        .map_err(|e| format!("Failed to load activity tags: {}", e))
+}
+
+/// Runs IMPORT_COMPLETED rules for an import into `account_id` that began at `started_at`
+pub async fn run_import_automations(
+    context: &ServiceContext,
+    handle: &AppHandle,
+    account_id: &str,
+    imported: usize,
+    started_at: NaiveDateTime,
+) {
+    let import = ImportCompletion {
+        account_id: account_id.to_string(),
+        imported,
+        started_at,
+    };
+    match context.automation_service().on_import_completed(&import).await {
+        Ok(report) => {
+            if report.tagged > 0 {
+                emit_resource_changed(
+                    handle,
+                    ResourceEventPayload::new(
+                        "activity_tag",
+                        "created",
+                        json!({ "account_id": account_id, "tagged": report.tagged }),
+                    ),
+                );
+            }
+            emit_automation_report(handle, "IMPORT_COMPLETED", &report);
+        }
+        Err(e) => warn!("IMPORT_COMPLETED rules failed: {}", e),
+    }
+}
+
+/// Runs QUOTE_MOVED rules over the latest two quotes of every security
+pub async fn run_quote_automations(context: &ServiceContext) -> CoreResult<AutomationRunReport> {
+    let symbols = security_symbols(context)?;
+    let mut pairs: Vec<_> = context
+        .market_data_service()
+        .get_latest_quotes_pair_for_symbols(&symbols)?
+        .into_values()
+        .collect();
+    pairs.sort_by(|a, b| a.latest.symbol.cmp(&b.latest.symbol));
+    context.automation_service().on_quote_update(&pairs).await
+}
+
+/// Runs GOAL_PROGRESS_REACHED rules over every goal whose progress is tracked
+pub async fn run_goal_progress_automations(
+    context: &ServiceContext,
+) -> CoreResult<AutomationRunReport> {
+    context
+        .automation_service()
+        .on_goal_progress(&goal_progress(context).await?)
+        .await
+}
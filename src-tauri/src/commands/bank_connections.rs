@@ -0,0 +1,230 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use super::automations::run_import_automations;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use chrono::NaiveDateTime;
+use log::{debug, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_IMPORT, AUDIT_ACTOR_USER};
+use wealthvn_core::connectors::{BankConnection, ConnectorSyncResult, NewBankConnection};
+
+#[tauri::command]
+pub async fn get_bank_connections(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BankConnection>, String> {
+    debug!("Fetching bank connections...");
+    state
+        .connector_service()
+        .get_connections()
+        .map_err(|e| format!("Failed to load bank connections: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_bank_connection(
+    connection: NewBankConnection,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BankConnection, String> {
+    debug!("Creating bank connection {}...", connection.name);
+    let created = state
+        .connector_service()
+        .create_connection(connection)
+        .await
+        .map_err(|e| format!("Failed to create bank connection: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "bank_connection",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&BankConnection>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "bank_connection",
+            "created",
+            json!({ "connection_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_bank_connection(
+    id: String,
+    connection: NewBankConnection,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BankConnection, String> {
+    debug!("Updating bank connection {}...", id);
+    let service = state.connector_service();
+    let previous = service.get_connection(&id).map_err(|e| e.to_string())?;
+    let updated = service
+        .update_connection(&id, connection)
+        .await
+        .map_err(|e| format!("Failed to update bank connection: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "bank_connection",
+            &id,
+            AuditAction::Update,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(Some(&previous), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bank_connection", "updated", json!({ "connection_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_bank_connection(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting bank connection {}...", id);
+    let service = state.connector_service();
+    let previous = service.get_connection(&id).map_err(|e| e.to_string())?;
+    let deleted = service
+        .delete_connection(&id)
+        .await
+        .map_err(|e| format!("Failed to delete bank connection: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "bank_connection",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(Some(&previous), None::<&BankConnection>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bank_connection", "deleted", json!({ "connection_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn sync_bank_connection(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ConnectorSyncResult, String> {
+    debug!("Syncing bank connection {}...", id);
+    let started_at = chrono::Utc::now().naive_utc();
+    let result = state
+        .connector_service()
+        .sync_connection(&id)
+        .await
+        .map_err(|e| format!("Failed to sync bank connection: {}", e))?;
+    finish_sync(&state, &handle, std::slice::from_ref(&result), started_at).await;
+    Ok(result)
+}
+
+/// Pulls every connection that is due; called on a timer from app setup
+pub async fn sync_due_bank_connections(context: &ServiceContext, handle: &AppHandle) {
+    let started_at = chrono::Utc::now().naive_utc();
+    match context.connector_service().sync_due_connections().await {
+        Ok(results) => finish_sync(context, handle, &results, started_at).await,
+        Err(e) => warn!("Bank connection sync failed: {}", e),
+    }
+}
+
+/// Same follow-up as a file import for every connection that brought in activities;
+/// `started_at` is when the pull began
+async fn finish_sync(
+    context: &ServiceContext,
+    handle: &AppHandle,
+    results: &[ConnectorSyncResult],
+    started_at: NaiveDateTime,
+) {
+    for result in results {
+        if let Some(error) = &result.error {
+            warn!(
+                "Bank connection '{}' failed: {}",
+                result.connection_name, error
+            );
+        }
+    }
+    if results.iter().all(|result| result.imported == 0) {
+        return;
+    }
+
+    // Imported payments settle bills first so a paid bill's category wins over rules
+    if let Err(e) = context.bill_service().match_bill_payments().await {
+        warn!("Bill matching after bank sync failed: {}", e);
+    }
+    if let Err(e) = context
+        .private_loan_service()
+        .match_private_loan_repayments()
+        .await
+    {
+        warn!("Private loan repayment matching after bank sync failed: {}", e);
+    }
+    if let Err(e) = context.categorization_service().auto_categorize().await {
+        warn!("Auto-categorization after bank sync failed: {}", e);
+    }
+
+    for result in results.iter().filter(|result| result.imported > 0) {
+        let account_id = match context
+            .connector_service()
+            .get_connection(&result.connection_id)
+        {
+            Ok(connection) => connection.account_id,
+            Err(e) => {
+                warn!("Bank connection {} vanished: {}", result.connection_id, e);
+                continue;
+            }
+        };
+        record_audit(
+            context,
+            NewAuditLogEntry::new(
+                "account",
+                &account_id,
+                AuditAction::Import,
+                AUDIT_ACTOR_IMPORT,
+            )
+            .with_changes(json!({
+                "importedCount": result.imported,
+                "bankConnectionId": result.connection_id,
+            })),
+        )
+        .await;
+        run_import_automations(context, handle, &account_id, result.imported, started_at).await;
+        // Recalculates the account's portfolio like a file import does
+        emit_resource_changed(
+            handle,
+            ResourceEventPayload::new(
+                "activity",
+                "imported",
+                json!({ "account_id": account_id, "activities": [] }),
+            ),
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use super::portfolio::parse_date;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::benchmarks::{Benchmark, BenchmarkComparison, NewBenchmark};
+
+#[tauri::command]
+pub async fn get_benchmarks(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<Benchmark>, String> {
+    debug!("Fetching benchmarks...");
+    state
+        .benchmark_service()
+        .get_benchmarks()
+        .map_err(|e| format!("Failed to load benchmarks: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_benchmark(
+    benchmark: NewBenchmark,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Benchmark, String> {
+    debug!("Creating benchmark {}...", benchmark.symbol);
+    let created = state
+        .benchmark_service()
+        .create_benchmark(benchmark)
+        .await
+        .map_err(|e| format!("Failed to create benchmark: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "benchmark",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&Benchmark>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "benchmark",
+            "created",
+            json!({ "benchmark_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn delete_benchmark(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting benchmark {}...", id);
+    let service = state.benchmark_service();
+    let previous = service
+        .get_benchmarks()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|b| b.id == id);
+    let deleted = service
+        .delete_benchmark(&id)
+        .await
+        .map_err(|e| format!("Failed to delete benchmark: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("benchmark", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&Benchmark>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("benchmark", "deleted", json!({ "benchmark_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+/// An account, or the accounts funding a goal, against a benchmark; both series are rebased
+/// to 100 so no amounts are revealed
+#[tauri::command]
+pub async fn compare_to_benchmark(
+    account_or_goal_id: String,
+    benchmark_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<BenchmarkComparison, String> {
+    debug!(
+        "Comparing {} to benchmark {}...",
+        account_or_goal_id, benchmark_id
+    );
+    let start_date = parse_date(start_date, "start")?;
+    let end_date = parse_date(end_date, "end")?;
+    state
+        .benchmark_service()
+        .compare_to_benchmark(&account_or_goal_id, &benchmark_id, start_date, end_date)
+        .await
+        .map_err(|e| format!("Failed to compare to benchmark: {}", e))
+}
@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::bills::{
+    Bill, BillMatchResult, BillPayment, BillReminder, NewBill, NewBillPayment,
+};
+
+#[tauri::command]
+pub async fn get_bills(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Bill>, String> {
+    debug!("Fetching bills...");
+    state
+        .bill_service()
+        .get_bills()
+        .map_err(|e| format!("Failed to load bills: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_bill(
+    bill: NewBill,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Bill, String> {
+    debug!("Creating bill {}...", bill.name);
+    let created = state
+        .bill_service()
+        .create_bill(bill)
+        .await
+        .map_err(|e| format!("Failed to create bill: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bill", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Bill>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bill", "created", json!({ "bill_id": created.id })),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_bill(
+    id: String,
+    bill: NewBill,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Bill, String> {
+    debug!("Updating bill {}...", id);
+    let service = state.bill_service();
+    let previous = service
+        .get_bills()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|b| b.id == id);
+    let updated = service
+        .update_bill(&id, bill)
+        .await
+        .map_err(|e| format!("Failed to update bill: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bill", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bill", "updated", json!({ "bill_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_bill(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting bill {}...", id);
+    let service = state.bill_service();
+    let previous = service
+        .get_bills()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|b| b.id == id);
+    let deleted = service
+        .delete_bill(&id)
+        .await
+        .map_err(|e| format!("Failed to delete bill: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bill", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&Bill>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bill", "deleted", json!({ "bill_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_bill_payments(
+    bill_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BillPayment>, String> {
+    debug!("Fetching bill payments...");
+    state
+        .bill_service()
+        .get_bill_payments(bill_id)
+        .map_err(|e| format!("Failed to load bill payments: {}", e))
+}
+
+#[tauri::command]
+pub async fn mark_bill_paid(
+    payment: NewBillPayment,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BillPayment, String> {
+    debug!(
+        "Marking bill {} paid for {}...",
+        payment.bill_id, payment.due_date
+    );
+    let created = state
+        .bill_service()
+        .mark_bill_paid(payment)
+        .await
+        .map_err(|e| format!("Failed to mark bill paid: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "bill_payment",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&BillPayment>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "bill",
+            "paid",
+            json!({ "bill_id": created.bill_id, "payment_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn delete_bill_payment(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting bill payment {}...", id);
+    let service = state.bill_service();
+    let previous = service
+        .get_bill_payments(None)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|p| p.id == id);
+    let deleted = service
+        .delete_bill_payment(&id)
+        .await
+        .map_err(|e| format!("Failed to delete bill payment: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bill_payment", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&BillPayment>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bill", "unpaid", json!({ "payment_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_bill_reminders(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BillReminder>, String> {
+    debug!("Fetching bill reminders...");
+    state
+        .bill_service()
+        .get_bill_reminders()
+        .map_err(|e| format!("Failed to load bill reminders: {}", e))
+}
+
+#[tauri::command]
+pub async fn match_bill_payments(
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BillMatchResult, String> {
+    debug!("Matching bill payments...");
+    let result = state
+        .bill_service()
+        .match_bill_payments()
+        .await
+        .map_err(|e| format!("Failed to match bill payments: {}", e))?;
+
+    if result.matched > 0 {
+        emit_resource_changed(
+            &handle,
+            ResourceEventPayload::new("bill", "matched", json!({ "matched": result.matched })),
+        );
+    }
+
+    Ok(result)
+}
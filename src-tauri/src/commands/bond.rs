@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use chrono::Utc;
+use log::{debug, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::bonds::{Bond, BondPayment, BondSummary, NewBond};
+use wealthvn_core::query_cache::{RESOURCE_ACTIVITY, RESOURCE_PORTFOLIO};
+
+/// Months of coupons and redemptions listed when the caller doesn't say
+const DEFAULT_BOND_PAYMENT_MONTHS: u32 = 12;
+
+#[tauri::command]
+pub async fn get_bonds(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BondSummary>, String> {
+    debug!("Fetching bonds...");
+    state
+        .bond_service()
+        .get_bond_summaries()
+        .map_err(|e| format!("Failed to load bonds: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_bond(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<BondSummary, String> {
+    debug!("Fetching bond {}...", id);
+    state
+        .bond_service()
+        .get_bond_summary(&id)
+        .map_err(|e| format!("Failed to load bond: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_bond(
+    bond: NewBond,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Bond, String> {
+    debug!("Creating bond {} for account {}...", bond.code, bond.account_id);
+    let created = state
+        .bond_service()
+        .create_bond(bond)
+        .await
+        .map_err(|e| format!("Failed to create bond: {}", e))?;
+    // The bonds are added to their account by an activity
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bond", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Bond>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "bond",
+            "created",
+            json!({ "bond_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_bond(
+    id: String,
+    bond: NewBond,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Bond, String> {
+    debug!("Updating bond {}...", id);
+    let service = state.bond_service();
+    let previous = service
+        .get_bonds()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|b| b.id == id);
+    let updated = service
+        .update_bond(&id, bond)
+        .await
+        .map_err(|e| format!("Failed to update bond: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bond", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "bond",
+            "updated",
+            json!({ "bond_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_bond(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting bond {}...", id);
+    let service = state.bond_service();
+    let previous = service
+        .get_bonds()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|b| b.id == id);
+    let deleted = service
+        .delete_bond(&id)
+        .await
+        .map_err(|e| format!("Failed to delete bond: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bond", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&Bond>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bond", "deleted", json!({ "bond_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_bond_payments(
+    months: Option<u32>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BondPayment>, String> {
+    debug!("Projecting bond coupons...");
+    state
+        .bond_service()
+        .get_bond_payments(months.unwrap_or(DEFAULT_BOND_PAYMENT_MONTHS))
+        .map_err(|e| format!("Failed to project bond coupons: {}", e))
+}
+
+/// Quotes bonds at today's clean price plus accrued interest; called on a timer from app
+/// setup
+pub async fn refresh_bond_quotes(context: &ServiceContext) {
+    match context
+        .bond_service()
+        .refresh_bond_quotes(Utc::now().date_naive())
+        .await
+    {
+        Ok(0) => {}
+        Ok(quoted) => {
+            context.query_cache().invalidate(RESOURCE_PORTFOLIO);
+            debug!("Quoted {} bonds with accrued interest", quoted);
+        }
+        Err(e) => warn!("Bond quote refresh failed: {}", e),
+    }
+}
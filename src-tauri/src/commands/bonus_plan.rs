@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::bonus_plans::{BonusPlan, BonusPlanRequest, NewBonusPlan};
+use wealthvn_core::query_cache::RESOURCE_ACTIVITY;
+
+#[tauri::command]
+pub async fn get_bonus_plans(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BonusPlan>, String> {
+    debug!("Fetching bonus plans...");
+    state
+        .bonus_plan_service()
+        .get_bonus_plans()
+        .map_err(|e| format!("Failed to load bonus plans: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_bonus_plan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<BonusPlan, String> {
+    debug!("Fetching bonus plan {}...", id);
+    state
+        .bonus_plan_service()
+        .get_bonus_plan(&id)
+        .map_err(|e| format!("Failed to load bonus plan: {}", e))
+}
+
+#[tauri::command]
+pub async fn propose_bonus_plan(
+    request: BonusPlanRequest,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<NewBonusPlan, String> {
+    debug!(
+        "Proposing a bonus plan for account {}...",
+        request.account_id
+    );
+    state
+        .bonus_plan_service()
+        .propose_bonus_plan(request)
+        .await
+        .map_err(|e| format!("Failed to propose bonus plan: {}", e))
+}
+
+#[tauri::command]
+pub async fn confirm_bonus_plan(
+    plan: NewBonusPlan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BonusPlan, String> {
+    debug!("Confirming bonus plan for account {}...", plan.account_id);
+    let created = state
+        .bonus_plan_service()
+        .confirm_bonus_plan(plan)
+        .await
+        .map_err(|e| format!("Failed to confirm bonus plan: {}", e))?;
+    // The bonus is recorded as a draft deposit on the account
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "bonus_plan",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&BonusPlan>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "bonus_plan",
+            "created",
+            json!({ "plan_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn fund_bonus_plan_envelopes(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BonusPlan, String> {
+    debug!("Filling envelopes of bonus plan {}...", id);
+    let service = state.bonus_plan_service();
+    let previous = service.get_bonus_plan(&id).ok();
+    let updated = service
+        .fund_bonus_plan_envelopes(&id)
+        .await
+        .map_err(|e| format!("Failed to fill envelopes: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bonus_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "bonus_plan",
+            "funded",
+            json!({ "plan_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_bonus_plan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting bonus plan {}...", id);
+    let service = state.bonus_plan_service();
+    let previous = service.get_bonus_plan(&id).ok();
+    let deleted = service
+        .delete_bonus_plan(&id)
+        .await
+        .map_err(|e| format!("Failed to delete bonus plan: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("bonus_plan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&BonusPlan>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("bonus_plan", "deleted", json!({ "plan_id": id })),
+    );
+
+    Ok(deleted)
+}
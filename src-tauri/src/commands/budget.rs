@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::budgets::{
+    ActivityCategory, BudgetCategory, BudgetMonthAmount, BudgetMonthAmountUpdate,
+    BudgetMonthProgress, BudgetReport, NewBudgetCategory,
+};
+
+#[tauri::command]
+pub async fn get_budget_categories(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BudgetCategory>, String> {
+    debug!("Fetching budget categories...");
+    state
+        .budget_service()
+        .get_budget_categories()
+        .map_err(|e| format!("Failed to load budget categories: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_budget_category(
+    category: NewBudgetCategory,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BudgetCategory, String> {
+    debug!("Creating budget category {}...", category.name);
+    let created = state
+        .budget_service()
+        .create_budget_category(category)
+        .await
+        .map_err(|e| format!("Failed to create budget category: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "budget_category",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&BudgetCategory>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("budget", "created", json!({ "category_id": created.id })),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_budget_category(
+    id: String,
+    category: NewBudgetCategory,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<BudgetCategory, String> {
+    debug!("Updating budget category {}...", id);
+    let service = state.budget_service();
+    let previous = service
+        .get_budget_categories()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|c| c.id == id);
+    let updated = service
+        .update_budget_category(&id, category)
+        .await
+        .map_err(|e| format!("Failed to update budget category: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "budget_category",
+            &id,
+            AuditAction::Update,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("budget", "updated", json!({ "category_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_budget_category(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting budget category {}...", id);
+    let service = state.budget_service();
+    let previous = service
+        .get_budget_categories()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|c| c.id == id);
+    let deleted = service
+        .delete_budget_category(&id)
+        .await
+        .map_err(|e| format!("Failed to delete budget category: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "budget_category",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&BudgetCategory>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("budget", "deleted", json!({ "category_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_budget_month_amounts(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<BudgetMonthAmount>, String> {
+    debug!("Fetching budget month amounts...");
+    state
+        .budget_service()
+        .get_budget_month_amounts()
+        .map_err(|e| format!("Failed to load budget amounts: {}", e))
+}
+
+/// Overrides one month's budget for a category; passing no amount restores the monthly default
+#[tauri::command]
+pub async fn set_budget_month_amount(
+    update: BudgetMonthAmountUpdate,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<(), String> {
+    let BudgetMonthAmountUpdate {
+        category_id,
+        month,
+        amount,
+    } = update;
+    debug!("Setting budget for {} in {}...", category_id, month);
+    state
+        .budget_service()
+        .set_budget_month_amount(&category_id, &month, amount)
+        .await
+        .map_err(|e| format!("Failed to set budget amount: {}", e))?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "budget",
+            "updated",
+            json!({ "category_id": category_id, "month": month }),
+        ),
+    );
+
+    Ok(())
+}
+
+/// Assigns activities to a budget category, or clears their category when none is given
+#[tauri::command]
+pub async fn categorize_activities(
+    activity_ids: Vec<String>,
+    category_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Categorizing {} activities...", activity_ids.len());
+    let updated = state
+        .budget_service()
+        .categorize_activities(activity_ids.clone(), category_id.clone())
+        .await
+        .map_err(|e| format!("Failed to categorize activities: {}", e))?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "budget",
+            "categorized",
+            json!({ "category_id": category_id, "activity_ids": activity_ids }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn get_activity_categories(
+    activity_ids: Vec<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<ActivityCategory>, String> {
+    state
+        .budget_service()
+        .get_activity_categories(&activity_ids)
+        .map_err(|e| format!("Failed to load activity categories: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_budget_progress(
+    month: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<BudgetMonthProgress, String> {
+    debug!("Calculating budget progress for {}...", month);
+    state
+        .budget_service()
+        .get_budget_progress(&month)
+        .map_err(|e| format!("Failed to calculate budget progress: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_budget_report(
+    from_month: String,
+    to_month: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<BudgetReport, String> {
+    debug!(
+        "Building budget report for {} to {}...",
+        from_month, to_month
+    );
+    state
+        .budget_service()
+        .get_budget_report(&from_month, &to_month)
+        .map_err(|e| format!("Failed to build budget report: {}", e))
+}
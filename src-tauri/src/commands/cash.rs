@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use super::portfolio::privacy_mode;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::activities::Activity;
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::cash::{AccountCash, CashMovement};
+use wealthvn_core::privacy::mask_if;
+
+/// Audits and announces a cash activity like any other, so the portfolio update moves the
+/// balance
+async fn activity_recorded(state: &ServiceContext, handle: &AppHandle, activity: &Activity) {
+    record_audit(
+        state,
+        NewAuditLogEntry::new("activity", &activity.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Activity>, Some(activity)),
+    )
+    .await;
+    emit_resource_changed(
+        handle,
+        ResourceEventPayload::new(
+            "activity",
+            "created",
+            json!({
+                "activity_id": activity.id,
+                "account_id": activity.account_id,
+                "currency": activity.currency,
+                "asset_id": activity.asset_id,
+            }),
+        ),
+    );
+}
+
+#[tauri::command]
+pub async fn get_account_cash(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AccountCash, String> {
+    debug!("Fetching cash balances of account {}...", account_id);
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .cash_service()
+        .get_account_cash(&account_id)
+        .map(|cash| mask_if(cash, privacy_mode))
+        .map_err(|e| format!("Failed to load cash balances: {}", e))
+}
+
+#[tauri::command]
+pub async fn deposit_cash(
+    movement: CashMovement,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Activity, String> {
+    debug!("Depositing cash into account {}...", movement.account_id);
+    let activity = state
+        .cash_service()
+        .deposit_cash(movement)
+        .await
+        .map_err(|e| format!("Failed to deposit cash: {}", e))?;
+    activity_recorded(&state, &handle, &activity).await;
+    Ok(activity)
+}
+
+#[tauri::command]
+pub async fn withdraw_cash(
+    movement: CashMovement,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Activity, String> {
+    debug!("Withdrawing cash from account {}...", movement.account_id);
+    let activity = state
+        .cash_service()
+        .withdraw_cash(movement)
+        .await
+        .map_err(|e| format!("Failed to withdraw cash: {}", e))?;
+    activity_recorded(&state, &handle, &activity).await;
+    Ok(activity)
+}
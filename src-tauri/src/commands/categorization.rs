@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::categorization::{
+    CategorizationResult, CategorizationRule, NewCategorizationRule,
+};
+
+#[tauri::command]
+pub async fn get_categorization_rules(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<CategorizationRule>, String> {
+    debug!("Fetching categorization rules...");
+    state
+        .categorization_service()
+        .get_categorization_rules()
+        .map_err(|e| format!("Failed to load categorization rules: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_categorization_rule(
+    rule: NewCategorizationRule,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<CategorizationRule, String> {
+    debug!("Creating categorization rule for {}...", rule.category_id);
+    let created = state
+        .categorization_service()
+        .create_categorization_rule(rule)
+        .await
+        .map_err(|e| format!("Failed to create categorization rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "categorization_rule",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&CategorizationRule>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "categorization_rule",
+            "created",
+            json!({ "rule_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_categorization_rule(
+    id: String,
+    rule: NewCategorizationRule,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<CategorizationRule, String> {
+    debug!("Updating categorization rule {}...", id);
+    let service = state.categorization_service();
+    let previous = service
+        .get_categorization_rules()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|r| r.id == id);
+    let updated = service
+        .update_categorization_rule(&id, rule)
+        .await
+        .map_err(|e| format!("Failed to update categorization rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "categorization_rule",
+            &id,
+            AuditAction::Update,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("categorization_rule", "updated", json!({ "rule_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_categorization_rule(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting categorization rule {}...", id);
+    let service = state.categorization_service();
+    let previous = service
+        .get_categorization_rules()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|r| r.id == id);
+    let deleted = service
+        .delete_categorization_rule(&id)
+        .await
+        .map_err(|e| format!("Failed to delete categorization rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "categorization_rule",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&CategorizationRule>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("categorization_rule", "deleted", json!({ "rule_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+/// Categorizes activities that don't have a category yet
+#[tauri::command]
+pub async fn auto_categorize_activities(
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<CategorizationResult, String> {
+    debug!("Auto-categorizing activities...");
+    let result = state
+        .categorization_service()
+        .auto_categorize()
+        .await
+        .map_err(|e| format!("Failed to categorize activities: {}", e))?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("budget", "categorized", json!(result)),
+    );
+
+    Ok(result)
+}
+
+/// Re-applies rules and learned categories to every activity not categorized by hand
+#[tauri::command]
+pub async fn recategorize_all_activities(
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<CategorizationResult, String> {
+    debug!("Recategorizing all activities...");
+    let result = state
+        .categorization_service()
+        .recategorize_all()
+        .await
+        .map_err(|e| format!("Failed to recategorize activities: {}", e))?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("budget", "categorized", json!(result)),
+    );
+
+    Ok(result)
+}
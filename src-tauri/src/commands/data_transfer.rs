@@ -0,0 +1,84 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{context::ServiceContext, events::emit_transfer_progress};
+use log::{debug, warn};
+use tauri::{AppHandle, State};
+use wealthvn_core::data_transfer::{DataExportSummary, ExportDataset, ExportFileFormat};
+use wealthvn_core::operations::{OperationGuard, OPERATION_EXPORT};
+
+/// Creates `path`, hands a buffered writer to `write` and removes the file again if it fails,
+/// so a cancelled or broken export does not leave half a file behind
+pub(crate) fn write_export_file<T>(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> wealthvn_core::errors::Result<T>,
+) -> Result<T, String> {
+    let file = File::create(path).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+    let mut out = BufWriter::new(file);
+    let written = write(&mut out)
+        .map_err(|e| e.to_string())
+        .and_then(|value| {
+            out.flush()
+                .map(|_| value)
+                .map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+        });
+    if written.is_err() {
+        drop(out);
+        if let Err(e) = std::fs::remove_file(path) {
+            warn!("Failed to remove partial export {}: {}", path.display(), e);
+        }
+    }
+    written
+}
+
+/// Registers a cancellable operation under the frontend's `operation_id`, if it sent one
+pub(crate) fn start_operation(
+    state: &ServiceContext,
+    operation_id: Option<String>,
+    kind: &str,
+    timeout_secs: Option<u64>,
+) -> Result<OperationGuard, String> {
+    state
+        .operations()
+        .start(operation_id, kind, timeout_secs.map(Duration::from_secs))
+        .map_err(|e| e.to_string())
+}
+
+/// Streams a dataset to `path` a chunk at a time, emitting `transfer:progress` as it goes.
+/// `cancel_operation(operation_id)` stops it at the next chunk.
+#[tauri::command]
+pub async fn export_data_to_file(
+    dataset: ExportDataset,
+    format: ExportFileFormat,
+    path: PathBuf,
+    operation_id: Option<String>,
+    timeout_secs: Option<u64>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<DataExportSummary, String> {
+    debug!(
+        "Exporting {} as {} to {}",
+        dataset.as_str(),
+        format.as_str(),
+        path.display()
+    );
+    let operation = start_operation(&state, operation_id, OPERATION_EXPORT, timeout_secs)?;
+    let service = state.data_transfer_service();
+    tauri::async_runtime::spawn_blocking(move || {
+        write_export_file(&path, |out| {
+            service.export_dataset(
+                dataset,
+                format,
+                out,
+                &|progress| emit_transfer_progress(&handle, progress),
+                operation.token(),
+            )
+        })
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))?
+    .map_err(|e| format!("Failed to export {}: {}", dataset.as_str(), e))
+}
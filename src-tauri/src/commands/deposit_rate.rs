@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_deposit_rate_notifications, emit_resource_changed, ResourceEventPayload},
+};
+use chrono::Utc;
+use log::{debug, info, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::accounts::Account;
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::deposit_ladders::{ConfirmedDepositLadder, DepositLadder, DepositLadderRequest};
+use wealthvn_core::deposit_rates::{DepositRate, RolloverSuggestion};
+use wealthvn_core::query_cache::{RESOURCE_ACCOUNT, RESOURCE_ACTIVITY};
+
+#[tauri::command]
+pub async fn get_deposit_rates(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<DepositRate>, String> {
+    debug!("Fetching deposit rates...");
+    state
+        .deposit_rate_service()
+        .get_deposit_rates()
+        .map_err(|e| format!("Failed to load deposit rates: {}", e))
+}
+
+#[tauri::command]
+pub async fn refresh_deposit_rates(state: State<'_, Arc<ServiceContext>>) -> Result<usize, String> {
+    debug!("Refreshing deposit rates...");
+    state
+        .deposit_rate_service()
+        .refresh_deposit_rates(true)
+        .await
+        .map_err(|e| format!("Failed to refresh deposit rates: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_rollover_suggestions(
+    within_days: Option<u64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<RolloverSuggestion>, String> {
+    debug!("Comparing maturing deposits against bank rates...");
+    state
+        .deposit_rate_service()
+        .get_rollover_suggestions(within_days)
+        .map_err(|e| format!("Failed to compare deposit rates: {}", e))
+}
+
+#[tauri::command]
+pub async fn propose_deposit_ladder(
+    request: DepositLadderRequest,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<DepositLadder, String> {
+    debug!(
+        "Proposing a deposit ladder from account {}...",
+        request.account_id
+    );
+    state
+        .deposit_ladder_service()
+        .propose_deposit_ladder(request)
+        .map_err(|e| format!("Failed to propose deposit ladder: {}", e))
+}
+
+#[tauri::command]
+pub async fn confirm_deposit_ladder(
+    ladder: DepositLadder,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ConfirmedDepositLadder, String> {
+    debug!("Confirming deposit ladder from account {}...", ladder.account_id);
+    let confirmed = state
+        .deposit_ladder_service()
+        .confirm_deposit_ladder(ladder)
+        .await
+        .map_err(|e| format!("Failed to confirm deposit ladder: {}", e))?;
+    // Each deposit is a new account funded by transfers from the source account
+    state.query_cache().invalidate(RESOURCE_ACCOUNT);
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    for account in &confirmed.accounts {
+        record_audit(
+            &state,
+            NewAuditLogEntry::new("account", &account.id, AuditAction::Create, AUDIT_ACTOR_USER)
+                .with_snapshots(None::<&Account>, Some(account)),
+        )
+        .await;
+    }
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "account",
+            "created",
+            json!({
+                "account_ids": confirmed
+                    .accounts
+                    .iter()
+                    .map(|account| account.id.as_str())
+                    .collect::<Vec<_>>(),
+            }),
+        ),
+    );
+
+    Ok(confirmed)
+}
+
+/// Refreshes the rate catalog when stale and notifies about deposits worth moving; called on a
+/// timer from app setup
+pub async fn check_deposit_rates(context: &ServiceContext, handle: &AppHandle) {
+    let service = context.deposit_rate_service();
+    match service.refresh_deposit_rates(false).await {
+        Ok(0) => {}
+        Ok(stored) => info!("Stored {} deposit rates", stored),
+        Err(e) => warn!("Deposit rate refresh failed: {}", e),
+    }
+    match service.rollover_notifications(Utc::now()) {
+        Ok(notifications) => emit_deposit_rate_notifications(handle, &notifications),
+        Err(e) => warn!("Deposit rate check failed: {}", e),
+    }
+}
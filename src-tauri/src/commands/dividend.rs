@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use super::portfolio::{parse_date, privacy_mode};
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use chrono::Utc;
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::dividends::{ActivityDividend, DividendSummary, NewActivityDividend};
+use wealthvn_core::privacy::mask_if;
+
+/// Dividend calendar and trailing twelve-month yield per holding as of `date` (today by
+/// default)
+#[tauri::command]
+pub async fn get_dividend_summary(
+    date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<DividendSummary, String> {
+    let date = parse_date(date, "summary")?.unwrap_or_else(|| Utc::now().date_naive());
+    debug!("Building dividend summary as of {}...", date);
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .dividend_service()
+        .get_dividend_summary(date)
+        .await
+        .map(|summary| mask_if(summary, privacy_mode))
+        .map_err(|e| format!("Failed to build dividend summary: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_activity_dividend(
+    activity_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Option<ActivityDividend>, String> {
+    debug!("Fetching dividend details of activity {}...", activity_id);
+    state
+        .dividend_service()
+        .get_activity_dividend(&activity_id)
+        .map_err(|e| format!("Failed to load dividend details: {}", e))
+}
+
+/// Records ex-date, pay date, per-share amount and tax withheld on a dividend activity
+#[tauri::command]
+pub async fn save_activity_dividend(
+    dividend: NewActivityDividend,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ActivityDividend, String> {
+    debug!(
+        "Saving dividend details of activity {}...",
+        dividend.activity_id
+    );
+    let service = state.dividend_service();
+    let previous = service
+        .get_activity_dividend(&dividend.activity_id)
+        .map_err(|e| e.to_string())?;
+    let saved = service
+        .save_activity_dividend(dividend)
+        .await
+        .map_err(|e| format!("Failed to save dividend details: {}", e))?;
+
+    let action = if previous.is_some() {
+        AuditAction::Update
+    } else {
+        AuditAction::Create
+    };
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "activity_dividend",
+            &saved.activity_id,
+            action,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), Some(&saved)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "activity_dividend",
+            "updated",
+            json!({ "activity_id": saved.activity_id }),
+        ),
+    );
+
+    Ok(saved)
+}
+
+#[tauri::command]
+pub async fn delete_activity_dividend(
+    activity_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting dividend details of activity {}...", activity_id);
+    let service = state.dividend_service();
+    let previous = service
+        .get_activity_dividend(&activity_id)
+        .map_err(|e| e.to_string())?;
+    let deleted = service
+        .delete_activity_dividend(&activity_id)
+        .await
+        .map_err(|e| format!("Failed to delete dividend details: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "activity_dividend",
+            &activity_id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&ActivityDividend>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "activity_dividend",
+            "deleted",
+            json!({ "activity_id": activity_id }),
+        ),
+    );
+
+    Ok(deleted)
+}
@@ -0,0 +1,161 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::education::{EducationPlan, EducationProjection, NewEducationPlan};
+
+#[tauri::command]
+pub async fn get_education_plans(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<EducationPlan>, String> {
+    debug!("Fetching education plans...");
+    state
+        .education_service()
+        .get_education_plans()
+        .map_err(|e| format!("Failed to load education plans: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_education_plan(
+    plan: NewEducationPlan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<EducationPlan, String> {
+    debug!("Creating education plan for {}...", plan.child_name);
+    let created = state
+        .education_service()
+        .create_education_plan(plan)
+        .await
+        .map_err(|e| format!("Failed to create education plan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "education_plan",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&EducationPlan>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("education_plan", "created", json!({ "plan_id": created.id })),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_education_plan(
+    id: String,
+    plan: NewEducationPlan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<EducationPlan, String> {
+    debug!("Updating education plan {}...", id);
+    let service = state.education_service();
+    let previous = service.get_education_plan(&id).map_err(|e| e.to_string())?;
+    let updated = service
+        .update_education_plan(&id, plan)
+        .await
+        .map_err(|e| format!("Failed to update education plan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("education_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("education_plan", "updated", json!({ "plan_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_education_plan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting education plan {}...", id);
+    let service = state.education_service();
+    let previous = service.get_education_plan(&id).map_err(|e| e.to_string())?;
+    let deleted = service
+        .delete_education_plan(&id)
+        .await
+        .map_err(|e| format!("Failed to delete education plan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("education_plan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), None::<&EducationPlan>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("education_plan", "deleted", json!({ "plan_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn project_education_costs(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<EducationProjection, String> {
+    debug!("Projecting education costs for plan {}...", id);
+    state
+        .education_service()
+        .project_education_costs(&id)
+        .map_err(|e| format!("Failed to project education costs: {}", e))
+}
+
+#[tauri::command]
+pub async fn link_education_goal(
+    id: String,
+    goal_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<EducationPlan, String> {
+    debug!("Linking education plan {} to a goal...", id);
+    let service = state.education_service();
+    let previous = service.get_education_plan(&id).map_err(|e| e.to_string())?;
+    let linked = service
+        .link_education_goal(&id, goal_id)
+        .await
+        .map_err(|e| format!("Failed to link education goal: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("education_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), Some(&linked)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "goal",
+            "updated",
+            json!({ "goal_id": linked.goal_id, "plan_id": id }),
+        ),
+    );
+
+    Ok(linked)
+}
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::envelopes::{
+    AccountEnvelopes, Envelope, EnvelopeTransfer, NewEnvelope, NewEnvelopeTransfer,
+};
+
+#[tauri::command]
+pub async fn get_account_envelopes(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AccountEnvelopes, String> {
+    debug!("Fetching envelopes for account {}...", account_id);
+    state
+        .envelope_service()
+        .get_account_envelopes(&account_id)
+        .map_err(|e| format!("Failed to load envelopes: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_envelope(
+    envelope: NewEnvelope,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Envelope, String> {
+    debug!("Creating envelope {}...", envelope.name);
+    let created = state
+        .envelope_service()
+        .create_envelope(envelope)
+        .await
+        .map_err(|e| format!("Failed to create envelope: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "envelope",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&Envelope>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "envelope",
+            "created",
+            json!({ "envelope_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn rename_envelope(
+    id: String,
+    name: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Envelope, String> {
+    debug!("Renaming envelope {}...", id);
+    let service = state.envelope_service();
+    let previous = service.get_envelope(&id).map_err(|e| e.to_string())?;
+    let updated = service
+        .rename_envelope(&id, &name)
+        .await
+        .map_err(|e| format!("Failed to rename envelope: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("envelope", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "envelope",
+            "updated",
+            json!({ "envelope_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn archive_envelope(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Envelope, String> {
+    debug!("Archiving envelope {}...", id);
+    let service = state.envelope_service();
+    let previous = service.get_envelope(&id).map_err(|e| e.to_string())?;
+    let archived = service
+        .archive_envelope(&id)
+        .await
+        .map_err(|e| format!("Failed to archive envelope: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("envelope", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), Some(&archived)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "envelope",
+            "archived",
+            json!({ "envelope_id": id, "account_id": archived.account_id }),
+        ),
+    );
+
+    Ok(archived)
+}
+
+#[tauri::command]
+pub async fn get_envelope_transfers(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<EnvelopeTransfer>, String> {
+    debug!("Fetching envelope transfers for account {}...", account_id);
+    state
+        .envelope_service()
+        .get_envelope_transfers(&account_id)
+        .map_err(|e| format!("Failed to load envelope transfers: {}", e))
+}
+
+#[tauri::command]
+pub async fn transfer_envelope_funds(
+    transfer: NewEnvelopeTransfer,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<EnvelopeTransfer, String> {
+    debug!(
+        "Transferring envelope funds in account {}...",
+        transfer.account_id
+    );
+    let created = state
+        .envelope_service()
+        .transfer_envelope_funds(transfer)
+        .await
+        .map_err(|e| format!("Failed to transfer envelope funds: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "envelope_transfer",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&EnvelopeTransfer>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "envelope",
+            "transferred",
+            json!({ "transfer_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
@@ -1,5 +1,7 @@
 use serde::Serialize;
 
+use crate::context::ServiceContext;
+
 #[derive(Debug, Serialize)]
 pub enum CommandError {
     ServiceError(String),
@@ -15,3 +17,9 @@ where
 }
 
 pub type CommandResult<T, E = CommandError> = Result<T, E>;
+
+/// Renders a core error in the user's language for commands that return plain strings.
+/// Errors without a message code keep their English text.
+pub fn localize_error(state: &ServiceContext, error: wealthvn_core::Error) -> String {
+    error.localized(state.message_language())
+}
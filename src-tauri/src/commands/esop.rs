@@ -0,0 +1,184 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_esop_vesting_notifications, emit_resource_changed, ResourceEventPayload},
+};
+use chrono::Utc;
+use log::{debug, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::esop::{
+    EsopGrant, EsopGrantSummary, NewEsopGrant, UpcomingVesting, VESTING_REMINDER_DAYS,
+};
+use wealthvn_core::query_cache::{RESOURCE_ACTIVITY, RESOURCE_PORTFOLIO};
+
+#[tauri::command]
+pub async fn get_esop_grants(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<EsopGrantSummary>, String> {
+    debug!("Fetching ESOP grants...");
+    state
+        .esop_service()
+        .get_esop_grant_summaries()
+        .map_err(|e| format!("Failed to load ESOP grants: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_esop_grant(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<EsopGrantSummary, String> {
+    debug!("Fetching ESOP grant {}...", id);
+    state
+        .esop_service()
+        .get_esop_grant_summary(&id)
+        .map_err(|e| format!("Failed to load ESOP grant: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_esop_grant(
+    grant: NewEsopGrant,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<EsopGrant, String> {
+    debug!(
+        "Creating {} ESOP grant for account {}...",
+        grant.company, grant.account_id
+    );
+    let created = state
+        .esop_service()
+        .create_esop_grant(grant)
+        .await
+        .map_err(|e| format!("Failed to create ESOP grant: {}", e))?;
+    // The grant is added to its account by an activity
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "esop_grant",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&EsopGrant>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "esop_grant",
+            "created",
+            json!({ "grant_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_esop_grant(
+    id: String,
+    grant: NewEsopGrant,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<EsopGrant, String> {
+    debug!("Updating ESOP grant {}...", id);
+    let service = state.esop_service();
+    let previous = service
+        .get_esop_grants()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|g| g.id == id);
+    let updated = service
+        .update_esop_grant(&id, grant)
+        .await
+        .map_err(|e| format!("Failed to update ESOP grant: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("esop_grant", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "esop_grant",
+            "updated",
+            json!({ "grant_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_esop_grant(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting ESOP grant {}...", id);
+    let service = state.esop_service();
+    let previous = service
+        .get_esop_grants()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|g| g.id == id);
+    let deleted = service
+        .delete_esop_grant(&id)
+        .await
+        .map_err(|e| format!("Failed to delete ESOP grant: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("esop_grant", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&EsopGrant>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("esop_grant", "deleted", json!({ "grant_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_upcoming_vestings(
+    days: Option<i64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<UpcomingVesting>, String> {
+    debug!("Listing upcoming ESOP vestings...");
+    state
+        .esop_service()
+        .get_upcoming_vestings(days.unwrap_or(VESTING_REMINDER_DAYS))
+        .map_err(|e| format!("Failed to list upcoming vestings: {}", e))
+}
+
+/// Requotes grants as shares vest and the share price moves, and reminds about vesting dates
+/// coming up; called on a timer from app setup
+pub async fn check_esop_vesting(context: &ServiceContext, handle: &AppHandle) {
+    let service = context.esop_service();
+    match service.refresh_esop_quotes(Utc::now().date_naive()).await {
+        Ok(0) => {}
+        Ok(quoted) => {
+            context.query_cache().invalidate(RESOURCE_PORTFOLIO);
+            debug!("Quoted {} ESOP grants at their vested value", quoted);
+        }
+        Err(e) => warn!("ESOP quote refresh failed: {}", e),
+    }
+    match service.vesting_notifications(Utc::now()) {
+        Ok(notifications) => emit_esop_vesting_notifications(handle, &notifications),
+        Err(e) => warn!("ESOP vesting check failed: {}", e),
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use crate::events::{emit_resource_changed, ResourceEventPayload};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::feature_flags::{FeatureFlag, FeatureFlagState};
+
+#[tauri::command]
+pub async fn get_feature_flags(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<FeatureFlagState>, String> {
+    debug!("Fetching feature flags...");
+    state
+        .feature_flag_service()
+        .list_flags()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_feature_flag(
+    flag: FeatureFlag,
+    enabled: bool,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<FeatureFlagState, String> {
+    debug!("Setting feature flag {} to {}...", flag.as_str(), enabled);
+    let flag_state = state
+        .feature_flag_service()
+        .set_flag(flag, enabled)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "feature_flag",
+            "updated",
+            json!({ "flag": flag.as_str(), "enabled": flag_state.enabled }),
+        ),
+    );
+    Ok(flag_state)
+}
@@ -0,0 +1,148 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::forecast::{
+    CashFlowForecast, CashFlowForecastRequest, NewPlannedCashFlow, PlannedCashFlow,
+};
+
+#[tauri::command]
+pub async fn get_planned_cash_flows(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<PlannedCashFlow>, String> {
+    debug!("Fetching planned cash flows...");
+    state
+        .forecast_service()
+        .get_planned_cash_flows()
+        .map_err(|e| format!("Failed to load planned cash flows: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_planned_cash_flow(
+    flow: NewPlannedCashFlow,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<PlannedCashFlow, String> {
+    debug!("Creating planned cash flow {}...", flow.name);
+    let created = state
+        .forecast_service()
+        .create_planned_cash_flow(flow)
+        .await
+        .map_err(|e| format!("Failed to create planned cash flow: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "planned_cash_flow",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&PlannedCashFlow>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("forecast", "created", json!({ "flow_id": created.id })),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_planned_cash_flow(
+    id: String,
+    flow: NewPlannedCashFlow,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<PlannedCashFlow, String> {
+    debug!("Updating planned cash flow {}...", id);
+    let service = state.forecast_service();
+    let previous = service
+        .get_planned_cash_flows()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|f| f.id == id);
+    let updated = service
+        .update_planned_cash_flow(&id, flow)
+        .await
+        .map_err(|e| format!("Failed to update planned cash flow: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "planned_cash_flow",
+            &id,
+            AuditAction::Update,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("forecast", "updated", json!({ "flow_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_planned_cash_flow(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting planned cash flow {}...", id);
+    let service = state.forecast_service();
+    let previous = service
+        .get_planned_cash_flows()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|f| f.id == id);
+    let deleted = service
+        .delete_planned_cash_flow(&id)
+        .await
+        .map_err(|e| format!("Failed to delete planned cash flow: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "planned_cash_flow",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&PlannedCashFlow>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("forecast", "deleted", json!({ "flow_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_cash_flow_forecast(
+    request: CashFlowForecastRequest,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<CashFlowForecast, String> {
+    debug!("Forecasting cash flow for {} months...", request.months);
+    state
+        .forecast_service()
+        .get_cash_flow_forecast(request)
+        .await
+        .map_err(|e| format!("Failed to forecast cash flow: {}", e))
+}
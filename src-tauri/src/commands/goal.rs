@@ -1,14 +1,34 @@
 use std::sync::Arc;
 
+use super::audit::record_audit;
+use super::error::localize_error;
 use crate::{
     context::ServiceContext,
     events::{emit_resource_changed, ResourceEventPayload},
 };
-use log::debug;
+use chrono::Utc;
+use log::{debug, warn};
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use tauri::{AppHandle, State};
-use wealthvn_core::goals::goals_model::{Goal, GoalsAllocation, NewGoal, AllocationVersion};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::goals::goals_model::{
+    AllocationBulkInsertSummary, AllocationChange, AllocationChangePreview, AllocationRebalance,
+    AllocationVersion, DepositDistribution, Goal, GoalFundingRequirement, GoalPlanExport,
+    GoalPlanImportResult, GoalsAllocation, NewGoal,
+};
+use super::portfolio::privacy_mode;
+use wealthvn_core::goals::{
+    get_dashboard_summary as build_summary, get_goal_progress as read_goal_progress,
+    get_goal_progress_series, get_monthly_summaries as build_monthly_summaries,
+    record_goal_progress_history, DashboardSummary, DebtPayoffProgress, EmergencyFundProgress,
+    GoalProgressSnapshot, MonthlySummaries, NetWorthGoalProgress, NetWorthPoint, ProgressInterval,
+    SinkingFundProgress, DEFAULT_SUMMARY_GOALS,
+};
+use wealthvn_core::privacy::mask_if;
+use wealthvn_core::query_cache::{RESOURCE_ALLOCATION, RESOURCE_GOAL};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -46,9 +66,18 @@ pub struct UnallocatedBalanceResponse {
 }
 
 #[tauri::command]
-pub async fn get_goals(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Goal>, String> {
+pub async fn get_goals(
+    include_archived: Option<bool>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<Goal>, String> {
     debug!("Fetching active goals...");
-    state.goal_service().get_goals().map_err(|e| e.to_string())
+    let goal_service = state.goal_service();
+    if include_archived.unwrap_or(false) {
+        goal_service.get_all_goals().await
+    } else {
+        goal_service.get_goals().await
+    }
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -64,6 +93,13 @@ pub async fn create_goal(
         .await
         .map_err(|e| e.to_string())?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("goal", &new_goal.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Goal>, Some(&new_goal)),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new("goal", "created", json!({ "goal_id": new_goal.id })),
@@ -80,12 +116,26 @@ pub async fn update_goal(
 ) -> Result<Goal, String> {
     debug!("Updating goal...");
     let goal_id = goal.id.clone();
+    let previous_goal = state
+        .goal_service()
+        .get_all_goals()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|g| g.id == goal_id);
     let updated_goal = state
         .goal_service()
         .update_goal(goal)
         .await
         .map_err(|e| e.to_string())?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("goal", &goal_id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous_goal.as_ref(), Some(&updated_goal)),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new("goal", "updated", json!({ "goal_id": goal_id })),
@@ -101,12 +151,26 @@ pub async fn delete_goal(
     handle: AppHandle,
 ) -> Result<usize, String> {
     debug!("Deleting goal...");
+    let previous_goal = state
+        .goal_service()
+        .get_all_goals()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|g| g.id == goal_id);
     let result = state
         .goal_service()
         .delete_goal(goal_id.clone())
         .await
         .map_err(|e| e.to_string())?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("goal", &goal_id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous_goal.as_ref(), None::<&Goal>),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new("goal", "deleted", json!({ "goal_id": goal_id })),
@@ -115,17 +179,185 @@ pub async fn delete_goal(
     Ok(result)
 }
 
+/// Hides a goal from lists and progress; its allocations and history stay for a restore
+#[tauri::command]
+pub async fn archive_goal(
+    goal_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Goal, String> {
+    debug!("Archiving goal {}...", goal_id);
+    set_goal_archived(goal_id, true, &state, &handle).await
+}
+
+#[tauri::command]
+pub async fn restore_goal(
+    goal_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Goal, String> {
+    debug!("Restoring goal {}...", goal_id);
+    set_goal_archived(goal_id, false, &state, &handle).await
+}
+
+async fn set_goal_archived(
+    goal_id: String,
+    archive: bool,
+    state: &State<'_, Arc<ServiceContext>>,
+    handle: &AppHandle,
+) -> Result<Goal, String> {
+    let previous_goal = state
+        .goal_service()
+        .get_all_goals()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|g| g.id == goal_id);
+    let goal = if archive {
+        state.goal_service().archive_goal(&goal_id).await
+    } else {
+        state.goal_service().restore_goal(&goal_id).await
+    }
+    .map_err(|e| e.to_string())?;
+
+    record_audit(
+        state,
+        NewAuditLogEntry::new("goal", &goal_id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous_goal.as_ref(), Some(&goal)),
+    )
+    .await;
+
+    let action = if archive { "archived" } else { "restored" };
+    emit_resource_changed(
+        handle,
+        ResourceEventPayload::new("goal", action, json!({ "goal_id": goal_id })),
+    );
+
+    Ok(goal)
+}
+
 #[tauri::command]
 pub async fn update_goal_allocations(
     allocations: Vec<GoalsAllocation>,
     state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
 ) -> Result<usize, String> {
     debug!("Updating goal allocations...");
+    let previous = state
+        .goal_service()
+        .load_goals_allocations()
+        .await
+        .map_err(|e| e.to_string())?;
+    let updated = state
+        .goal_service()
+        .upsert_goal_allocations(allocations.clone())
+        .await
+        .map_err(|e| localize_error(&state, e))?;
+
+    for allocation in &allocations {
+        let before = previous.iter().find(|a| a.id == allocation.id);
+        let action = if before.is_some() {
+            AuditAction::Update
+        } else {
+            AuditAction::Create
+        };
+        record_audit(
+            &state,
+            NewAuditLogEntry::new("goal_allocation", &allocation.id, action, AUDIT_ACTOR_USER)
+                .with_snapshots(before, Some(allocation)),
+        )
+        .await;
+    }
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("allocation", "updated", json!({ "count": updated })),
+    );
+
+    Ok(updated)
+}
+
+/// Inserts many allocations and their versions in one go, e.g. historical earmarks migrated
+/// from a spreadsheet; nothing is saved unless the whole set is valid
+#[tauri::command]
+pub async fn bulk_insert_allocations(
+    allocations: Vec<GoalsAllocation>,
+    versions: Vec<AllocationVersion>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<AllocationBulkInsertSummary, String> {
+    debug!("Bulk inserting {} goal allocations...", allocations.len());
+    let summary = state
+        .goal_service()
+        .bulk_insert_allocations(allocations.clone(), versions)
+        .await
+        .map_err(|e| localize_error(&state, e))?;
+
+    for allocation in &allocations {
+        record_audit(
+            &state,
+            NewAuditLogEntry::new(
+                "goal_allocation",
+                &allocation.id,
+                AuditAction::Create,
+                AUDIT_ACTOR_USER,
+            )
+            .with_snapshots(None::<&GoalsAllocation>, Some(allocation)),
+        )
+        .await;
+    }
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("allocation", "created", json!(summary)),
+    );
+
+    Ok(summary)
+}
+
+/// Every goal with its allocations and their versions, as a document another profile or
+/// machine can import
+#[tauri::command]
+pub async fn export_goal_allocations(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<GoalPlanExport, String> {
+    debug!("Exporting goals and allocations...");
+    let accounts = state
+        .account_service()
+        .get_all_accounts()
+        .map_err(|e| e.to_string())?;
     state
         .goal_service()
-        .upsert_goal_allocations(allocations)
+        .export_goal_plan(&accounts)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| format!("Failed to export goals: {}", e))
+}
+
+/// Recreates an exported goal plan under new ids; allocations go to the account with the same
+/// id or name, and nothing is saved unless the whole plan is valid
+#[tauri::command]
+pub async fn import_goal_allocations(
+    plan: GoalPlanExport,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<GoalPlanImportResult, String> {
+    debug!("Importing {} goals exported at {}...", plan.goals.len(), plan.exported_at);
+    let accounts = state
+        .account_service()
+        .get_all_accounts()
+        .map_err(|e| e.to_string())?;
+    let result = state
+        .goal_service()
+        .import_goal_plan(plan, &accounts)
+        .await
+        .map_err(|e| localize_error(&state, e))?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("goal", "imported", json!(result)),
+    );
+
+    Ok(result)
 }
 
 #[tauri::command]
@@ -133,12 +365,269 @@ pub async fn load_goals_allocations(
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<Vec<GoalsAllocation>, String> {
     debug!("Loading goal allocations...");
+    let goal_service = state.goal_service();
+    state
+        .query_cache()
+        .get_or_load(
+            "load_goals_allocations",
+            &(),
+            &[RESOURCE_GOAL, RESOURCE_ALLOCATION],
+            || goal_service.load_goals_allocations(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_emergency_fund_progress(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<EmergencyFundProgress>, String> {
+    debug!("Calculating emergency fund progress...");
+    state
+        .emergency_fund_service()
+        .get_emergency_fund_progress()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_sinking_fund_progress(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<SinkingFundProgress>, String> {
+    debug!("Calculating sinking fund progress...");
+    state
+        .sinking_fund_service()
+        .get_sinking_fund_progress()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_net_worth_goal_progress(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<NetWorthGoalProgress>, String> {
+    debug!("Calculating net worth goal progress...");
+    state
+        .net_worth_goal_service()
+        .get_net_worth_goal_progress()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_debt_payoff_progress(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<DebtPayoffProgress>, String> {
+    debug!("Calculating debt payoff progress...");
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .debt_payoff_service()
+        .get_debt_payoff_progress()
+        .await
+        .map(|progress| mask_if(progress, privacy_mode))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_net_worth_history(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<NetWorthPoint>, String> {
+    debug!("Fetching net worth history...");
+    let parse = |date: Option<String>| {
+        date.map(|value| {
+            chrono::NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date '{}': {}", value, e))
+        })
+        .transpose()
+    };
+    state
+        .net_worth_goal_service()
+        .get_net_worth_series(parse(start_date)?, parse(end_date)?)
+        .map_err(|e| e.to_string())
+}
+
+/// Net worth, daily change and the goals closest to their target, for widgets and tray apps
+#[tauri::command]
+pub async fn get_dashboard_summary(
+    goal_limit: Option<usize>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<DashboardSummary, String> {
+    debug!("Building dashboard summary...");
+    let privacy_mode = privacy_mode(&state)?;
+    build_summary(
+        state.net_worth_goal_service().as_ref(),
+        state.emergency_fund_service().as_ref(),
+        state.sinking_fund_service().as_ref(),
+        &state.get_base_currency(),
+        goal_limit.unwrap_or(DEFAULT_SUMMARY_GOALS),
+    )
+    .await
+    .map(|summary| mask_if(summary, privacy_mode))
+    .map_err(|e| e.to_string())
+}
+
+/// Progress of one goal as of `date` (today by default), read from the precomputed history
+#[tauri::command]
+pub async fn get_goal_progress(
+    goal_id: String,
+    date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<GoalProgressSnapshot, String> {
+    debug!("Fetching progress of goal {}...", goal_id);
+    let date = date
+        .map(|value| {
+            chrono::NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date '{}': {}", value, e))
+        })
+        .transpose()?;
+    let privacy_mode = privacy_mode(&state)?;
+    read_goal_progress(
+        state.goal_service().as_ref(),
+        state.net_worth_goal_service().as_ref(),
+        &goal_id,
+        date,
+    )
+    .await
+    .map_err(|e| e.to_string())?
+    .map(|snapshot| mask_if(snapshot, privacy_mode))
+    .ok_or_else(|| format!("Goal {} not found or has no start date", goal_id))
+}
+
+/// Progress of one goal at daily, weekly or monthly points from `from_date` to `to_date`
+/// (today by default), for charting
+#[tauri::command]
+pub async fn get_goal_progress_history(
+    goal_id: String,
+    from_date: String,
+    to_date: Option<String>,
+    interval: Option<ProgressInterval>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<GoalProgressSnapshot>, String> {
+    debug!("Fetching progress history of goal {}...", goal_id);
+    let parse = |value: &str| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}': {}", value, e))
+    };
+    let from_date = parse(&from_date)?;
+    let to_date = to_date.as_deref().map(parse).transpose()?;
+    let privacy_mode = privacy_mode(&state)?;
+    let series = get_goal_progress_series(
+        state.goal_service().as_ref(),
+        state.net_worth_goal_service().as_ref(),
+        &goal_id,
+        from_date,
+        to_date,
+        interval.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to load goal progress history: {}", e))?;
+    Ok(series
+        .into_iter()
+        .map(|snapshot| mask_if(snapshot, privacy_mode))
+        .collect())
+}
+
+/// Allocation percentages for an account that pace each goal to its due date; nothing is saved
+#[tauri::command]
+pub async fn suggest_goal_rebalance(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AllocationRebalance, String> {
+    debug!("Suggesting allocation rebalance for account {}...", account_id);
+    let privacy_mode = privacy_mode(&state)?;
     state
         .goal_service()
-        .load_goals_allocations()
+        .suggest_allocation_rebalance(&account_id, Utc::now().date_naive())
+        .await
+        .map(|rebalance| mask_if(rebalance, privacy_mode))
+        .map_err(|e| format!("Failed to suggest allocation rebalance: {}", e))
+}
+
+/// How a new deposit into an account should be split across its goals; nothing is saved
+#[tauri::command]
+pub async fn distribute_goal_deposit(
+    account_id: String,
+    amount: f64,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<DepositDistribution, String> {
+    debug!("Distributing a deposit into account {} across goals...", account_id);
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .goal_service()
+        .distribute_unallocated(&account_id, amount, Utc::now().date_naive())
+        .await
+        .map(|distribution| mask_if(distribution, privacy_mode))
         .map_err(|e| e.to_string())
 }
 
+/// What changing an allocation to a new percentage or amount would do, for a live preview;
+/// nothing is saved
+#[tauri::command]
+pub async fn preview_allocation_change(
+    allocation_id: String,
+    change: AllocationChange,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<AllocationChangePreview, String> {
+    debug!("Previewing a change to allocation {}...", allocation_id);
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .goal_service()
+        .preview_allocation_change(&allocation_id, change, Utc::now().date_naive())
+        .await
+        .map(|preview| mask_if(preview, privacy_mode))
+        .map_err(|e| e.to_string())
+}
+
+/// Monthly contribution a goal needs by its due date, nominal and adjusted for inflation
+#[tauri::command]
+pub async fn get_goal_funding_requirement(
+    goal_id: String,
+    expected_return_rate: Option<f64>,
+    inflation_rate: Option<f64>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<GoalFundingRequirement, String> {
+    debug!("Calculating funding requirement for goal {}...", goal_id);
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .goal_service()
+        .get_funding_requirement(
+            &goal_id,
+            expected_return_rate,
+            inflation_rate,
+            Utc::now().date_naive(),
+        )
+        .await
+        .map(|requirement| mask_if(requirement, privacy_mode))
+        .map_err(|e| e.to_string())
+}
+
+/// Account totals and goal progress by month, read from the maintained summary tables
+#[tauri::command]
+pub async fn get_monthly_summaries(
+    start_date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<MonthlySummaries, String> {
+    debug!("Fetching monthly summaries...");
+    let start_date = start_date
+        .map(|value| {
+            chrono::NaiveDate::parse_from_str(&value, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid date '{}': {}", value, e))
+        })
+        .transpose()?;
+    let privacy_mode = privacy_mode(&state)?;
+    build_monthly_summaries(
+        state.valuation_service().as_ref(),
+        state.goal_service().as_ref(),
+        state.net_worth_goal_service().as_ref(),
+        start_date,
+    )
+    .await
+    .map(|summaries| mask_if(summaries, privacy_mode))
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn validate_allocation_conflict(
     request: AllocationConflictValidationRequest,
@@ -152,7 +641,8 @@ pub async fn validate_allocation_conflict(
         &request.end_date,
         request.percent_allocation,
         request.exclude_allocation_id.as_deref(),
-    );
+    )
+    .await;
 
     match result {
         Ok(()) => Ok(AllocationConflictValidationResponse {
@@ -161,29 +651,112 @@ pub async fn validate_allocation_conflict(
         }),
         Err(e) => Ok(AllocationConflictValidationResponse {
             valid: false,
-            message: e.to_string(),
+            message: localize_error(&state, e),
         }),
     }
 }
 
 // New hybrid allocation commands
 
+/// Unallocated balance of an account at the value given, or at its latest stored valuation
+/// when none is
 #[tauri::command]
 pub async fn get_unallocated_balance(
     account_id: String,
-    current_account_value: f64,
+    current_account_value: Option<f64>,
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<UnallocatedBalanceResponse, String> {
     debug!("Getting unallocated balance for account...");
 
+    let goal_service = state.goal_service();
+    let unallocated_balance = match current_account_value {
+        Some(value) => goal_service.get_unallocated_balance(&account_id, value).await,
+        None => goal_service.get_unallocated_value(&account_id).await,
+    }
+    .map_err(|e| e.to_string())?;
+    // Read from stored valuations, the balance would otherwise reveal the account's value
+    let unallocated_balance = if current_account_value.is_none() && privacy_mode(&state)? {
+        0.0
+    } else {
+        unallocated_balance
+    };
+
+    Ok(UnallocatedBalanceResponse { unallocated_balance })
+}
+
+/// Unallocated part of the cash an account actually holds
+#[tauri::command]
+pub async fn get_unallocated_cash(
+    account_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<UnallocatedBalanceResponse, String> {
+    debug!("Getting unallocated cash of account {}...", account_id);
+    let unallocated_balance = state
+        .goal_service()
+        .get_unallocated_cash(&account_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let unallocated_balance = if privacy_mode(&state)? { 0.0 } else { unallocated_balance };
+
+    Ok(UnallocatedBalanceResponse { unallocated_balance })
+}
+
+/// Unallocated balance summed over every account of a group, at their latest values in the
+/// base currency
+#[tauri::command]
+pub async fn get_account_group_unallocated_balance(
+    group_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<UnallocatedBalanceResponse, String> {
+    debug!("Getting unallocated balance for account group {}...", group_id);
+    let valuation = state
+        .account_group_service()
+        .get_group_valuation(&group_id)
+        .map_err(|e| e.to_string())?;
+    let account_values: HashMap<String, f64> = valuation
+        .account_values
+        .into_iter()
+        .map(|(account_id, value)| (account_id, value.to_f64().unwrap_or_default()))
+        .collect();
     let unallocated_balance = state
         .goal_service()
-        .get_unallocated_balance(&account_id, current_account_value)
+        .get_unallocated_balance_for_accounts(&account_values)
+        .await
         .map_err(|e| e.to_string())?;
+    // The balance comes from stored valuations rather than from the caller
+    let unallocated_balance = if privacy_mode(&state)? { 0.0 } else { unallocated_balance };
 
     Ok(UnallocatedBalanceResponse { unallocated_balance })
 }
 
+/// Funds a goal with the same percentage of every account in a group
+#[tauri::command]
+pub async fn allocate_goal_to_account_group(
+    goal_id: String,
+    group_id: String,
+    percentage: f64,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<AllocationBulkInsertSummary, String> {
+    debug!("Allocating goal {} across account group {}...", goal_id, group_id);
+    let account_ids = state
+        .account_group_service()
+        .get_group_account_ids(&group_id)
+        .map_err(|e| e.to_string())?;
+    let summary = state
+        .goal_service()
+        .allocate_goal_to_accounts(&goal_id, &account_ids, percentage)
+        .await
+        .map_err(|e| localize_error(&state, e))?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("allocation", "created", json!(summary)),
+    );
+
+    Ok(summary)
+}
+
 
 
 #[tauri::command]
@@ -197,7 +770,8 @@ pub async fn validate_allocation_percentages(
 
     let result = state
         .goal_service()
-        .validate_allocation_percentages(&account_id, new_percentage, exclude_allocation_id.as_deref());
+        .validate_allocation_percentages(&account_id, new_percentage, exclude_allocation_id.as_deref())
+        .await;
 
     match result {
         Ok(()) => Ok(AllocationValidationResponse {
@@ -206,7 +780,7 @@ pub async fn validate_allocation_percentages(
         }),
         Err(e) => Ok(AllocationValidationResponse {
             valid: false,
-            message: e.to_string(),
+            message: localize_error(&state, e),
         }),
     }
 }
@@ -224,6 +798,7 @@ pub async fn get_allocation_versions(
         .goal_service()
         .get_repository()
         .get_allocation_versions(&allocation_id)
+        .await
         .map_err(|e| e.to_string())
 }
 
@@ -234,6 +809,13 @@ pub async fn delete_goal_allocation(
     handle: AppHandle,
 ) -> Result<usize, String> {
     debug!("Deleting goal allocation...");
+    let previous = state
+        .goal_service()
+        .load_goals_allocations()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|a| a.id == allocation_id);
     let result = state
         .goal_service()
         .get_repository()
@@ -241,6 +823,18 @@ pub async fn delete_goal_allocation(
         .await
         .map_err(|e| e.to_string())?;
 
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "goal_allocation",
+            &allocation_id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&GoalsAllocation>),
+    )
+    .await;
+
     emit_resource_changed(
         &handle,
         ResourceEventPayload::new("allocation", "deleted", json!({ "allocation_id": allocation_id })),
@@ -248,3 +842,19 @@ pub async fn delete_goal_allocation(
 
     Ok(result)
 }
+
+/// Stores goal progress for the days missing from the history; called on a timer from app
+/// setup
+pub async fn record_goal_progress(context: &ServiceContext) {
+    match record_goal_progress_history(
+        context.goal_service().as_ref(),
+        context.net_worth_goal_service().as_ref(),
+        Utc::now().date_naive(),
+    )
+    .await
+    {
+        Ok(0) => {}
+        Ok(days) => debug!("Stored goal progress for {} days", days),
+        Err(e) => warn!("Goal progress history failed: {}", e),
+    }
+}
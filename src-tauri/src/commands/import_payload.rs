@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use super::automations::run_import_automations;
+use crate::{
+    context::ServiceContext,
+    events::{
+        emit_portfolio_trigger_update, emit_resource_changed, PortfolioRequestPayload,
+        ResourceEventPayload,
+    },
+};
+use log::{debug, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_IMPORT};
+use wealthvn_core::import_payload::{ImportPayload, ImportPayloadPreview, ImportPayloadResult};
+use wealthvn_core::importers::ActivityImportRequest;
+
+/// Checks a payload handed over by another app or a dropped file without saving anything
+#[tauri::command]
+pub async fn preview_import_payload(
+    payload: ImportPayload,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ImportPayloadPreview, String> {
+    debug!(
+        "Previewing import payload from {}...",
+        payload.source.as_deref().unwrap_or("an unnamed source")
+    );
+    state
+        .import_payload_service()
+        .preview_payload(payload)
+        .await
+        .map_err(|e| format!("Failed to check import payload: {}", e))
+}
+
+#[tauri::command]
+pub async fn import_payload(
+    payload: ImportPayload,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ImportPayloadResult, String> {
+    let source = payload.source.clone();
+    debug!(
+        "Importing payload from {}...",
+        source.as_deref().unwrap_or("an unnamed source")
+    );
+    let started_at = chrono::Utc::now().naive_utc();
+    let result = state
+        .import_payload_service()
+        .import_payload(payload)
+        .await
+        .map_err(|e| format!("Failed to import payload: {}", e))?;
+    if result.imported == 0 {
+        return Ok(result);
+    }
+
+    let Some(account_id) = result.account_id.clone() else {
+        // Quotes only change valuations
+        emit_portfolio_trigger_update(
+            &handle,
+            PortfolioRequestPayload::builder()
+                .account_ids(None)
+                .refetch_all_market_data(false)
+                .symbols(None)
+                .build(),
+        );
+        return Ok(result);
+    };
+
+    finish_activity_import(
+        &state,
+        &handle,
+        &account_id,
+        result.imported,
+        started_at,
+        source,
+    )
+    .await;
+    Ok(result)
+}
+
+/// Same follow-up as a file import: match bills and loan repayments, categorize, run
+/// automations, then record and announce the import
+async fn finish_activity_import(
+    state: &ServiceContext,
+    handle: &AppHandle,
+    account_id: &str,
+    imported: usize,
+    started_at: chrono::NaiveDateTime,
+    source: Option<String>,
+) {
+    if let Err(e) = state.bill_service().match_bill_payments().await {
+        warn!("Bill matching after import failed: {}", e);
+    }
+    if let Err(e) = state
+        .private_loan_service()
+        .match_private_loan_repayments()
+        .await
+    {
+        warn!("Private loan repayment matching after import failed: {}", e);
+    }
+    if let Err(e) = state.categorization_service().auto_categorize().await {
+        warn!("Auto-categorization after import failed: {}", e);
+    }
+    run_import_automations(state, handle, account_id, imported, started_at).await;
+
+    record_audit(
+        state,
+        NewAuditLogEntry::new(
+            "account",
+            account_id,
+            AuditAction::Import,
+            AUDIT_ACTOR_IMPORT,
+        )
+        .with_changes(json!({ "importedCount": imported, "source": source })),
+    )
+    .await;
+    emit_resource_changed(
+        handle,
+        ResourceEventPayload::new(
+            "activity",
+            "imported",
+            json!({ "account_id": account_id, "activities": [] }),
+        ),
+    );
+}
+
+/// Checks the invoices in an e-invoice XML file as expenses of `account_id`
+#[tauri::command]
+pub async fn preview_einvoice_import(
+    account_id: String,
+    xml: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ImportPayloadPreview, String> {
+    let payload = ImportPayload::from_einvoice_xml(&xml, &account_id)
+        .map_err(|e| format!("Failed to read e-invoice: {}", e))?;
+    preview_import_payload(payload, state).await
+}
+
+#[tauri::command]
+pub async fn import_einvoice(
+    account_id: String,
+    xml: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ImportPayloadResult, String> {
+    let payload = ImportPayload::from_einvoice_xml(&xml, &account_id)
+        .map_err(|e| format!("Failed to read e-invoice: {}", e))?;
+    import_payload(payload, state, handle).await
+}
+
+/// Reads a broker export or a CSV mapped by the account's saved profile and checks every
+/// row, without saving anything
+#[tauri::command]
+pub async fn preview_activity_import(
+    request: ActivityImportRequest,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ImportPayloadPreview, String> {
+    debug!("Previewing {} activity import...", request.format.label());
+    state
+        .activity_import_service()
+        .preview_activity_import(request)
+        .await
+        .map_err(|e| format!("Failed to read activity file: {}", e))
+}
+
+#[tauri::command]
+pub async fn commit_activity_import(
+    request: ActivityImportRequest,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<ImportPayloadResult, String> {
+    let source = request.format.label().to_string();
+    debug!("Importing {} activity file...", source);
+    let started_at = chrono::Utc::now().naive_utc();
+    let account_id = request.account_id.clone();
+    let result = state
+        .activity_import_service()
+        .commit_activity_import(request)
+        .await
+        .map_err(|e| format!("Failed to import activity file: {}", e))?;
+    if result.imported > 0 {
+        finish_activity_import(
+            &state,
+            &handle,
+            &account_id,
+            result.imported,
+            started_at,
+            Some(source),
+        )
+        .await;
+    }
+    Ok(result)
+}
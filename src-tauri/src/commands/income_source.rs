@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::forecast::parse_forecast_date;
+use wealthvn_core::income_sources::{IncomeSource, IncomeVariance, NewIncomeSource, SavingsRate};
+
+#[tauri::command]
+pub async fn get_income_sources(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<IncomeSource>, String> {
+    debug!("Fetching income sources...");
+    state
+        .income_source_service()
+        .get_income_sources()
+        .map_err(|e| format!("Failed to load income sources: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_income_source(
+    source: NewIncomeSource,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<IncomeSource, String> {
+    debug!("Creating income source {}...", source.name);
+    let created = state
+        .income_source_service()
+        .create_income_source(source)
+        .await
+        .map_err(|e| format!("Failed to create income source: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "income_source",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&IncomeSource>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "income_source",
+            "created",
+            json!({ "income_source_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_income_source(
+    id: String,
+    source: NewIncomeSource,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<IncomeSource, String> {
+    debug!("Updating income source {}...", id);
+    let service = state.income_source_service();
+    let previous = service
+        .get_income_sources()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|s| s.id == id);
+    let updated = service
+        .update_income_source(&id, source)
+        .await
+        .map_err(|e| format!("Failed to update income source: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("income_source", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "income_source",
+            "updated",
+            json!({ "income_source_id": id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_income_source(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting income source {}...", id);
+    let service = state.income_source_service();
+    let previous = service
+        .get_income_sources()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|s| s.id == id);
+    let deleted = service
+        .delete_income_source(&id)
+        .await
+        .map_err(|e| format!("Failed to delete income source: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("income_source", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&IncomeSource>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "income_source",
+            "deleted",
+            json!({ "income_source_id": id }),
+        ),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_income_variance(
+    start_date: String,
+    end_date: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<IncomeVariance>, String> {
+    debug!("Comparing income from {} to {}...", start_date, end_date);
+    let start = parse_forecast_date(&start_date).map_err(|e| e.to_string())?;
+    let end = parse_forecast_date(&end_date).map_err(|e| e.to_string())?;
+    state
+        .income_source_service()
+        .get_income_variance(start, end)
+        .map_err(|e| format!("Failed to compare income: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_savings_rate(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SavingsRate, String> {
+    debug!("Calculating savings rate...");
+    state
+        .income_source_service()
+        .get_savings_rate()
+        .map_err(|e| format!("Failed to calculate savings rate: {}", e))
+}
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::data_transfer::{start_operation, write_export_file};
+use crate::{context::ServiceContext, events::emit_transfer_progress};
+use log::debug;
+use tauri::{AppHandle, State};
+use wealthvn_core::ledger::{LedgerExport, LedgerExportSummary, LedgerFormat};
+use wealthvn_core::operations::OPERATION_LEDGER_EXPORT;
+
+/// Renders a Beancount or ledger-cli journal; the frontend saves `content` to `fileName`
+#[tauri::command]
+pub async fn export_ledger(
+    format: LedgerFormat,
+    account_ids: Option<Vec<String>>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<LedgerExport, String> {
+    debug!("Exporting {} journal...", format.as_str());
+    state
+        .ledger_export_service()
+        .export_ledger(format, account_ids.as_deref())
+        .map_err(|e| format!("Failed to export journal: {}", e))
+}
+
+/// Writes the journal straight to `path`, for histories too long to pass through the frontend.
+/// `cancel_operation(operation_id)` stops it and removes the partial file.
+#[tauri::command]
+pub async fn export_ledger_to_file(
+    format: LedgerFormat,
+    account_ids: Option<Vec<String>>,
+    path: PathBuf,
+    operation_id: Option<String>,
+    timeout_secs: Option<u64>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<LedgerExportSummary, String> {
+    debug!(
+        "Exporting {} journal to {}...",
+        format.as_str(),
+        path.display()
+    );
+    let operation = start_operation(&state, operation_id, OPERATION_LEDGER_EXPORT, timeout_secs)?;
+    let service = state.ledger_export_service();
+    tauri::async_runtime::spawn_blocking(move || {
+        write_export_file(&path, |out| {
+            service.export_ledger_to(
+                format,
+                account_ids.as_deref(),
+                out,
+                &|progress| emit_transfer_progress(&handle, progress),
+                operation.token(),
+            )
+        })
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))?
+    .map_err(|e| format!("Failed to export journal: {}", e))
+}
@@ -0,0 +1,329 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use rust_decimal::Decimal;
+use wealthvn_core::loans::{
+    Loan, LoanPrepayment, LoanRateReset, LoanSchedule, LoanStressTest, NewLoan,
+    NewLoanPrepayment, NewLoanRateReset,
+};
+
+#[tauri::command]
+pub async fn get_loans(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Loan>, String> {
+    debug!("Fetching loans...");
+    state
+        .loan_service()
+        .get_loans()
+        .map_err(|e| format!("Failed to load loans: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_loan_schedule(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<LoanSchedule, String> {
+    debug!("Generating schedule for loan {}...", id);
+    state
+        .loan_service()
+        .get_loan_schedule(&id)
+        .map_err(|e| format!("Failed to generate loan schedule: {}", e))
+}
+
+#[tauri::command]
+pub async fn stress_test_loan(
+    id: String,
+    rate_increase: Decimal,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<LoanStressTest, String> {
+    debug!("Stress testing loan {} at +{} points...", id, rate_increase);
+    state
+        .loan_service()
+        .stress_test_loan(&id, rate_increase)
+        .map_err(|e| format!("Failed to stress test loan: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_loan(
+    loan: NewLoan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Loan, String> {
+    debug!("Creating loan for account {}...", loan.account_id);
+    let created = state
+        .loan_service()
+        .create_loan(loan)
+        .await
+        .map_err(|e| format!("Failed to create loan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("loan", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Loan>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "loan",
+            "created",
+            json!({ "loan_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_loan(
+    id: String,
+    loan: NewLoan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Loan, String> {
+    debug!("Updating loan {}...", id);
+    let service = state.loan_service();
+    let previous = service
+        .get_loans()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|l| l.id == id);
+    let updated = service
+        .update_loan(&id, loan)
+        .await
+        .map_err(|e| format!("Failed to update loan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("loan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("loan", "updated", json!({ "loan_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_loan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting loan {}...", id);
+    let service = state.loan_service();
+    let previous = service
+        .get_loans()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|l| l.id == id);
+    let deleted = service
+        .delete_loan(&id)
+        .await
+        .map_err(|e| format!("Failed to delete loan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("loan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&Loan>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("loan", "deleted", json!({ "loan_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_loan_prepayments(
+    loan_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<LoanPrepayment>, String> {
+    debug!("Fetching loan prepayments...");
+    state
+        .loan_service()
+        .get_loan_prepayments(loan_id)
+        .map_err(|e| format!("Failed to load loan prepayments: {}", e))
+}
+
+#[tauri::command]
+pub async fn add_loan_prepayment(
+    prepayment: NewLoanPrepayment,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<LoanPrepayment, String> {
+    debug!(
+        "Recording prepayment on loan {} for {}...",
+        prepayment.loan_id, prepayment.payment_date
+    );
+    let created = state
+        .loan_service()
+        .add_loan_prepayment(prepayment)
+        .await
+        .map_err(|e| format!("Failed to record loan prepayment: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "loan_prepayment",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&LoanPrepayment>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "loan",
+            "prepaid",
+            json!({ "loan_id": created.loan_id, "prepayment_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn delete_loan_prepayment(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting loan prepayment {}...", id);
+    let service = state.loan_service();
+    let previous = service
+        .get_loan_prepayments(None)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|p| p.id == id);
+    let deleted = service
+        .delete_loan_prepayment(&id)
+        .await
+        .map_err(|e| format!("Failed to delete loan prepayment: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "loan_prepayment",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&LoanPrepayment>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("loan", "prepayment_deleted", json!({ "prepayment_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_loan_rate_resets(
+    loan_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<LoanRateReset>, String> {
+    debug!("Fetching loan rate resets...");
+    state
+        .loan_service()
+        .get_loan_rate_resets(loan_id)
+        .map_err(|e| format!("Failed to load loan rate resets: {}", e))
+}
+
+#[tauri::command]
+pub async fn add_loan_rate_reset(
+    reset: NewLoanRateReset,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<LoanRateReset, String> {
+    debug!(
+        "Recording base rate reset on loan {} from {}...",
+        reset.loan_id, reset.effective_date
+    );
+    let created = state
+        .loan_service()
+        .add_loan_rate_reset(reset)
+        .await
+        .map_err(|e| format!("Failed to record loan rate reset: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "loan_rate_reset",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&LoanRateReset>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "loan",
+            "rate_reset",
+            json!({ "loan_id": created.loan_id, "rate_reset_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn delete_loan_rate_reset(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting loan rate reset {}...", id);
+    let service = state.loan_service();
+    let previous = service
+        .get_loan_rate_resets(None)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|r| r.id == id);
+    let deleted = service
+        .delete_loan_rate_reset(&id)
+        .await
+        .map_err(|e| format!("Failed to delete loan rate reset: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "loan_rate_reset",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&LoanRateReset>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("loan", "rate_reset_deleted", json!({ "rate_reset_id": id })),
+    );
+
+    Ok(deleted)
+}
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use super::data_transfer::start_operation;
 use crate::{
     context::ServiceContext,
     events::{emit_portfolio_trigger_update, PortfolioRequestPayload},
@@ -9,6 +10,7 @@ use crate::{
 use log::{debug, error};
 use tauri::{AppHandle, State};
 use wealthvn_core::market_data::{MarketDataProviderInfo, Quote, QuoteImport, QuoteSummary};
+use wealthvn_core::operations::OPERATION_QUOTE_IMPORT;
 
 #[tauri::command]
 pub async fn search_symbol(
@@ -26,12 +28,15 @@ pub async fn search_symbol(
 pub async fn sync_market_data(
     symbols: Option<Vec<String>>,
     refetch_all: bool,
+    operation_id: Option<String>,
+    timeout_secs: Option<u64>,
     handle: AppHandle,
 ) -> Result<(), String> {
     let payload = PortfolioRequestPayload::builder()
         .account_ids(None)
         .refetch_all_market_data(refetch_all)
         .symbols(symbols)
+        .operation(operation_id, timeout_secs)
         .build();
     emit_portfolio_trigger_update(&handle, payload);
     Ok(())
@@ -130,6 +135,8 @@ pub async fn get_market_data_providers(
 pub async fn import_quotes_csv(
     quotes: Vec<QuoteImport>,
     overwrite_existing: bool,
+    operation_id: Option<String>,
+    timeout_secs: Option<u64>,
     state: State<'_, Arc<ServiceContext>>,
     handle: AppHandle,
 ) -> Result<Vec<QuoteImport>, String> {
@@ -138,9 +145,14 @@ pub async fn import_quotes_csv(
         quotes.len(),
         overwrite_existing
     );
-    let result = state
-        .market_data_service()
-        .import_quotes_from_csv(quotes, overwrite_existing)
+    let operation = start_operation(&state, operation_id, OPERATION_QUOTE_IMPORT, timeout_secs)?;
+    let result = operation
+        .token()
+        .run(
+            state
+                .market_data_service()
+                .import_quotes_from_csv(quotes, overwrite_existing),
+        )
         .await
         .map_err(|e| {
             error!("❌ TAURI COMMAND: import_quotes_csv failed: {}", e);
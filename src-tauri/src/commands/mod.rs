@@ -1,14 +1,51 @@
 pub mod account;
 pub mod activity;
 pub mod addon;
+pub mod api_tokens;
+pub mod app_lock;
 pub mod asset;
+pub mod audit;
+pub mod automations;
+pub mod benchmark;
+pub mod bank_connections;
+pub mod bill;
+pub mod bond;
+pub mod bonus_plan;
+pub mod budget;
+pub mod cash;
+pub mod categorization;
+pub mod data_transfer;
+pub mod deposit_rate;
+pub mod dividend;
+pub mod education;
+pub mod envelope;
+pub mod esop;
 pub mod error;
+pub mod feature_flags;
+pub mod forecast;
 pub mod goal;
+pub mod import_payload;
+pub mod income_source;
+pub mod ledger;
 pub mod limits;
+pub mod loan;
 pub mod market_data;
+pub mod onboarding;
+pub mod operations;
+pub mod pension;
 pub mod platform;
 pub mod portfolio;
+pub mod private_loan;
+pub mod profile;
+pub mod property;
 pub mod providers_settings;
+pub mod recurring_rule;
+pub mod report;
+pub mod scripts;
 pub mod secrets;
 pub mod settings;
+pub mod sheet_exports;
+pub mod sip_plan;
+pub mod telemetry;
+pub mod term_deposit;
 pub mod utilities;
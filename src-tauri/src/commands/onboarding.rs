@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use super::error::localize_error;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::{debug, error};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::accounts::Account;
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::goals::goals_model::Goal;
+use wealthvn_core::onboarding::{OnboardingPlan, OnboardingResult};
+
+/// Creates the accounts, starting balances and first goal from the onboarding wizard in one go
+#[tauri::command]
+pub async fn seed_initial_data(
+    plan: OnboardingPlan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<OnboardingResult, String> {
+    debug!("Seeding initial data for {} accounts...", plan.accounts.len());
+    let result = state
+        .onboarding_service()
+        .seed_initial_data(plan)
+        .await
+        .map_err(|e| {
+            error!("Failed to seed initial data: {}", e);
+            localize_error(&state, e)
+        })?;
+
+    for account in &result.accounts {
+        record_audit(
+            &state,
+            NewAuditLogEntry::new("account", &account.id, AuditAction::Create, AUDIT_ACTOR_USER)
+                .with_snapshots(None::<&Account>, Some(account)),
+        )
+        .await;
+    }
+    if let Some(goal) = &result.goal {
+        record_audit(
+            &state,
+            NewAuditLogEntry::new("goal", &goal.id, AuditAction::Create, AUDIT_ACTOR_USER)
+                .with_snapshots(None::<&Goal>, Some(goal)),
+        )
+        .await;
+    }
+
+    // No single account_id, so the listener recalculates every account
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "account",
+            "created",
+            json!({
+                "account_ids": result.accounts.iter().map(|a| a.id.clone()).collect::<Vec<_>>(),
+                "base_currency": result.base_currency,
+            }),
+        ),
+    );
+
+    Ok(result)
+}
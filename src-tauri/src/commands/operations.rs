@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use log::debug;
+use tauri::State;
+use wealthvn_core::operations::OperationInfo;
+
+/// Exports, quote imports and market data resyncs still running, oldest first
+#[tauri::command]
+pub async fn list_operations(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<OperationInfo>, String> {
+    Ok(state.operations().list())
+}
+
+/// Asks a running operation to stop; it then fails with an "operation cancelled" error.
+/// False when nothing runs under `operation_id` (it may have just finished).
+#[tauri::command]
+pub async fn cancel_operation(
+    operation_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<bool, String> {
+    debug!("Cancelling operation {}", operation_id);
+    Ok(state.operations().cancel(&operation_id))
+}
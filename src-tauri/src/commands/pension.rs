@@ -0,0 +1,10 @@
+use chrono::Utc;
+use log::debug;
+use wealthvn_core::pension::{self, PensionEstimate, PensionRequest};
+
+#[tauri::command]
+pub async fn estimate_pension(request: PensionRequest) -> Result<PensionEstimate, String> {
+    debug!("Estimating BHXH pension...");
+    pension::estimate_pension(&request, Utc::now().date_naive())
+        .map_err(|e| format!("Failed to estimate pension: {}", e))
+}
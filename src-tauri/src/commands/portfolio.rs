@@ -7,15 +7,29 @@ use crate::{
     },
 };
 
+use chrono::NaiveDate;
 use log::debug;
 use tauri::{AppHandle, State};
 use wealthvn_core::{
     holdings::Holding,
     income::IncomeSummary,
     performance::{PerformanceMetrics, SimplePerformanceMetrics},
+    privacy::{index_valuation_history, mask_if},
+    query_cache::{RESOURCE_ACCOUNT, RESOURCE_ACTIVITY, RESOURCE_PORTFOLIO},
     valuation::DailyAccountValuation,
 };
 
+/// Holdings change with accounts, activities and every recalculation (quotes, FX, settings)
+const HOLDINGS_DEPEND_ON: &[&str] = &[RESOURCE_ACCOUNT, RESOURCE_ACTIVITY, RESOURCE_PORTFOLIO];
+
+/// Reads the privacy mode setting; amounts are masked before leaving the command layer
+pub(super) fn privacy_mode(state: &ServiceContext) -> Result<bool, String> {
+    state
+        .settings_service()
+        .is_privacy_mode_enabled()
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn recalculate_portfolio(handle: AppHandle) -> Result<(), String> {
     debug!("Emitting PORTFOLIO_TRIGGER_RECALCULATE event...");
@@ -28,6 +42,20 @@ pub async fn recalculate_portfolio(handle: AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Rebuilds the holdings snapshots and daily valuations of the accounts (all when empty) from
+/// their activities and the stored quotes, without refetching market history
+#[tauri::command]
+pub async fn recompute_valuations(handle: AppHandle, account_ids: Vec<String>) -> Result<(), String> {
+    debug!("Recomputing valuations for accounts: {:?}", account_ids);
+    let payload = PortfolioRequestPayload::builder()
+        .account_ids((!account_ids.is_empty()).then_some(account_ids))
+        .symbols(None)
+        .refetch_all_market_data(false)
+        .build();
+    emit_portfolio_trigger_recalculate(&handle, payload);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn update_portfolio(handle: AppHandle) -> Result<(), String> {
     debug!("Emitting PORTFOLIO_TRIGGER_UPDATE event...");
@@ -46,11 +74,19 @@ pub async fn get_holdings(
     account_id: String,
 ) -> Result<Vec<Holding>, String> {
     debug!("Get holdings...");
+    let privacy_mode = privacy_mode(&state)?;
     let base_currency = state.get_base_currency();
+    let holdings_service = state.holdings_service();
     state
-        .holdings_service()
-        .get_holdings(&account_id, &base_currency)
+        .query_cache()
+        .get_or_load(
+            "get_holdings",
+            &(&account_id, &base_currency),
+            HOLDINGS_DEPEND_ON,
+            || holdings_service.get_holdings(&account_id, &base_currency),
+        )
         .await
+        .map(|holdings| mask_if(holdings, privacy_mode))
         .map_err(|e| e.to_string())
 }
 
@@ -64,11 +100,19 @@ pub async fn get_holding(
         "Get specific holding for asset {} in account {}",
         asset_id, account_id
     );
+    let privacy_mode = privacy_mode(&state)?;
     let base_currency = state.get_base_currency();
+    let holdings_service = state.holdings_service();
     state
-        .holdings_service()
-        .get_holding(&account_id, &asset_id, &base_currency)
+        .query_cache()
+        .get_or_load(
+            "get_holding",
+            &(&account_id, &asset_id, &base_currency),
+            HOLDINGS_DEPEND_ON,
+            || holdings_service.get_holding(&account_id, &asset_id, &base_currency),
+        )
         .await
+        .map(|holding| mask_if(holding, privacy_mode))
         .map_err(|e| e.to_string())
 }
 
@@ -79,26 +123,60 @@ pub async fn get_historical_valuations(
     start_date: Option<String>,
     end_date: Option<String>,
 ) -> Result<Vec<DailyAccountValuation>, String> {
+    let from_date_opt = parse_date(start_date, "start")?;
+    let to_date_opt = parse_date(end_date, "end")?;
 
-    //     // Parse optional dates into Option<NaiveDate>
-    let from_date_opt: Option<chrono::NaiveDate> = start_date
-        .map(|date_str| {
-            chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-                .map_err(|e| format!("Invalid start date: {}", e))
-        })
-        .transpose()?;
-
-    let to_date_opt: Option<chrono::NaiveDate> = end_date
-        .map(|date_str| {
-            chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d")
-                .map_err(|e| format!("Invalid end date: {}", e))
-        })
-        .transpose()?;
-
-    state
+    let mut valuations = state
         .valuation_service()
         .get_historical_valuations(&account_id, from_date_opt, to_date_opt)
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    if privacy_mode(&state)? {
+        index_valuation_history(&mut valuations);
+    }
+    Ok(valuations)
+}
+
+/// Daily value history of one account. An account never valued yet, such as one just
+/// created, has its history computed from its activities before it is read.
+#[tauri::command]
+pub async fn get_account_valuation_history(
+    state: State<'_, Arc<ServiceContext>>,
+    account_id: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<Vec<DailyAccountValuation>, String> {
+    debug!("Get valuation history of account {}", account_id);
+    let start_date = parse_date(start_date, "start")?;
+    let end_date = parse_date(end_date, "end")?;
+    let valuation_service = state.valuation_service();
+    let valued = !valuation_service
+        .get_latest_valuations(std::slice::from_ref(&account_id))
+        .map_err(|e| e.to_string())?
+        .is_empty();
+    if !valued {
+        valuation_service
+            .calculate_valuation_history(&account_id, false)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    let mut valuations = valuation_service
+        .get_historical_valuations(&account_id, start_date, end_date)
+        .map_err(|e| e.to_string())?;
+    if privacy_mode(&state)? {
+        index_valuation_history(&mut valuations);
+    }
+    Ok(valuations)
+}
+
+/// Parses an optional `YYYY-MM-DD` bound of a date range
+pub(super) fn parse_date(value: Option<String>, bound: &str) -> Result<Option<NaiveDate>, String> {
+    value
+        .map(|date| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map_err(|e| format!("Invalid {} date: {}", bound, e))
+        })
+        .transpose()
 }
 
 #[tauri::command]
@@ -125,9 +203,11 @@ pub async fn get_latest_valuations(
         return Ok(Vec::new());
     }
 
+    let privacy_mode = privacy_mode(&state)?;
     state
         .valuation_service()
         .get_latest_valuations(&ids_to_process)
+        .map(|valuations| mask_if(valuations, privacy_mode))
         .map_err(|e| e.to_string())
 }
 
@@ -136,9 +216,11 @@ pub async fn get_income_summary(
     state: State<'_, Arc<ServiceContext>>,
 ) -> Result<Vec<IncomeSummary>, String> {
     debug!("Fetching income summary...");
+    let privacy_mode = privacy_mode(&state)?;
     state
         .income_service()
         .get_income_summary()
+        .map(|summaries| mask_if(summaries, privacy_mode))
         .map_err(|e| e.to_string())
 }
 
@@ -169,9 +251,11 @@ pub async fn calculate_accounts_simple_performance(
         return Ok(Vec::new());
     }
 
+    let privacy_mode = privacy_mode(&state)?;
     state
         .performance_service()
         .calculate_accounts_simple_performance(&ids_to_process) // Pass the potentially modified list
+        .map(|metrics| mask_if(metrics, privacy_mode))
         .map_err(|e| e.to_string())
 }
 
@@ -205,10 +289,12 @@ pub async fn calculate_performance_history(
         })
         .transpose()?;
 
+    let privacy_mode = privacy_mode(&state)?;
     state
         .performance_service()
         .calculate_performance_history(&item_type, &item_id, start_date_opt, end_date_opt)
         .await
+        .map(|metrics| mask_if(metrics, privacy_mode))
         .map_err(|e| format!("Failed to calculate performance: {}", e.to_string()))
 }
 
@@ -242,9 +328,11 @@ pub async fn calculate_performance_summary(
         })
         .transpose()?;
 
+    let privacy_mode = privacy_mode(&state)?;
     state
         .performance_service()
         .calculate_performance_summary(&item_type, &item_id, start_date_opt, end_date_opt)
         .await
+        .map(|metrics| mask_if(metrics, privacy_mode))
         .map_err(|e| format!("Failed to calculate performance: {}", e.to_string()))
 }
@@ -0,0 +1,265 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::private_loans::{
+    NewPrivateLoan, NewPrivateLoanRepayment, PrivateLoan, PrivateLoanMatchResult,
+    PrivateLoanRepayment, PrivateLoanSummary,
+};
+use wealthvn_core::query_cache::{RESOURCE_ACTIVITY, RESOURCE_PORTFOLIO};
+
+#[tauri::command]
+pub async fn get_private_loans(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<PrivateLoanSummary>, String> {
+    debug!("Fetching private loans...");
+    state
+        .private_loan_service()
+        .get_private_loan_summaries()
+        .map_err(|e| format!("Failed to load private loans: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_private_loan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<PrivateLoanSummary, String> {
+    debug!("Fetching private loan {}...", id);
+    state
+        .private_loan_service()
+        .get_private_loan_summary(&id)
+        .map_err(|e| format!("Failed to load private loan: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_private_loan(
+    loan: NewPrivateLoan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<PrivateLoan, String> {
+    debug!(
+        "Creating loan to {} for account {}...",
+        loan.borrower, loan.account_id
+    );
+    let created = state
+        .private_loan_service()
+        .create_private_loan(loan)
+        .await
+        .map_err(|e| format!("Failed to create private loan: {}", e))?;
+    // The money lent is added to its account by an activity
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "private_loan",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&PrivateLoan>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "private_loan",
+            "created",
+            json!({ "loan_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_private_loan(
+    id: String,
+    loan: NewPrivateLoan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<PrivateLoan, String> {
+    debug!("Updating private loan {}...", id);
+    let service = state.private_loan_service();
+    let previous = service
+        .get_private_loans()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|l| l.id == id);
+    let updated = service
+        .update_private_loan(&id, loan)
+        .await
+        .map_err(|e| format!("Failed to update private loan: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("private_loan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "private_loan",
+            "updated",
+            json!({ "loan_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_private_loan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting private loan {}...", id);
+    let service = state.private_loan_service();
+    let previous = service
+        .get_private_loans()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|l| l.id == id);
+    let deleted = service
+        .delete_private_loan(&id)
+        .await
+        .map_err(|e| format!("Failed to delete private loan: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("private_loan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&PrivateLoan>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("private_loan", "deleted", json!({ "loan_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn record_private_loan_repayment(
+    repayment: NewPrivateLoanRepayment,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<PrivateLoanRepayment, String> {
+    debug!(
+        "Recording repayment on private loan {} for {}...",
+        repayment.loan_id, repayment.payment_date
+    );
+    let created = state
+        .private_loan_service()
+        .record_private_loan_repayment(repayment)
+        .await
+        .map_err(|e| format!("Failed to record repayment: {}", e))?;
+    // The loan is requoted at the principal left
+    state.query_cache().invalidate(RESOURCE_PORTFOLIO);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "private_loan_repayment",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&PrivateLoanRepayment>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "private_loan",
+            "repaid",
+            json!({ "loan_id": created.loan_id, "repayment_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn delete_private_loan_repayment(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting private loan repayment {}...", id);
+    let service = state.private_loan_service();
+    let previous = service
+        .get_private_loan_summaries()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .flat_map(|l| l.repayments)
+        .find(|r| r.id == id);
+    let deleted = service
+        .delete_private_loan_repayment(&id)
+        .await
+        .map_err(|e| format!("Failed to delete repayment: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_PORTFOLIO);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "private_loan_repayment",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&PrivateLoanRepayment>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "private_loan",
+            "repayment_deleted",
+            json!({ "repayment_id": id }),
+        ),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn match_private_loan_repayments(
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<PrivateLoanMatchResult, String> {
+    debug!("Matching deposits to private loans...");
+    let result = state
+        .private_loan_service()
+        .match_private_loan_repayments()
+        .await
+        .map_err(|e| format!("Failed to match repayments: {}", e))?;
+    if result.matched > 0 {
+        state.query_cache().invalidate(RESOURCE_PORTFOLIO);
+        emit_resource_changed(
+            &handle,
+            ResourceEventPayload::new(
+                "private_loan",
+                "matched",
+                json!({ "matched": result.matched }),
+            ),
+        );
+    }
+    Ok(result)
+}
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use super::error::localize_error;
+use crate::context::{self, ServiceContext};
+use crate::events::{
+    emit_portfolio_trigger_recalculate, emit_portfolio_trigger_update, emit_resource_changed,
+    PortfolioRequestPayload, ResourceEventPayload,
+};
+use crate::listeners;
+use log::{debug, error, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::demo::DEMO_PROFILE_NAME;
+use wealthvn_core::profiles::Profile;
+
+#[tauri::command]
+pub async fn list_profiles(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Profile>, String> {
+    debug!("Fetching profiles...");
+    Ok(state.profile_manager().list_profiles())
+}
+
+#[tauri::command]
+pub async fn get_active_profile(state: State<'_, Arc<ServiceContext>>) -> Result<Profile, String> {
+    Ok(state.profile_manager().active_profile())
+}
+
+#[tauri::command]
+pub async fn create_profile(
+    name: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Profile, String> {
+    debug!("Creating profile {}...", name);
+    let profile = state
+        .profile_manager()
+        .create_profile(&name)
+        .map_err(|e| e.to_string())?;
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("profile", "created", json!({ "profile_id": profile.id })),
+    );
+    Ok(profile)
+}
+
+#[tauri::command]
+pub async fn rename_profile(
+    profile_id: String,
+    name: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Profile, String> {
+    debug!("Renaming profile {}...", profile_id);
+    let profile = state
+        .profile_manager()
+        .rename_profile(&profile_id, &name)
+        .map_err(|e| e.to_string())?;
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("profile", "updated", json!({ "profile_id": profile.id })),
+    );
+    Ok(profile)
+}
+
+#[tauri::command]
+pub async fn delete_profile(
+    profile_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<(), String> {
+    debug!("Deleting profile {}...", profile_id);
+    state
+        .profile_manager()
+        .delete_profile(&profile_id)
+        .map_err(|e| e.to_string())?;
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("profile", "deleted", json!({ "profile_id": profile_id })),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn switch_profile(
+    profile_id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Profile, String> {
+    debug!("Switching to profile {}...", profile_id);
+    let profile = context::switch_profile(&state, &profile_id)
+        .await
+        .map_err(|e| format!("Failed to switch profile: {}", e))?;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("profile", "switched", json!({ "profile_id": profile.id })),
+    );
+    listeners::spawn_service_warm_up(handle.clone(), state.inner().clone());
+    // Everything on screen belongs to the previous profile, so refresh from the new database
+    emit_portfolio_trigger_update(&handle, PortfolioRequestPayload::builder().build());
+    Ok(profile)
+}
+
+/// Creates a new profile filled with generated sample data and switches to it.
+/// If seeding fails the previous profile is restored and the half-made profile removed.
+#[tauri::command]
+pub async fn create_demo_profile(
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Profile, String> {
+    debug!("Creating demo profile...");
+    let previous = state.profile_manager().active_profile();
+    let profile = state
+        .profile_manager()
+        .create_profile(DEMO_PROFILE_NAME)
+        .map_err(|e| e.to_string())?;
+
+    let seeded = match context::switch_profile(&state, &profile.id).await {
+        Ok(_) => state
+            .demo_service()
+            .seed_demo_data()
+            .await
+            .map_err(|e| localize_error(&state, e)),
+        Err(e) => Err(format!("Failed to switch profile: {}", e)),
+    };
+
+    if let Err(e) = seeded {
+        error!("Failed to create demo profile: {}", e);
+        if let Err(restore_err) = context::switch_profile(&state, &previous.id).await {
+            warn!("Failed to restore profile {}: {}", previous.id, restore_err);
+        } else if let Err(delete_err) = state.profile_manager().delete_profile(&profile.id) {
+            warn!("Failed to remove demo profile {}: {}", profile.id, delete_err);
+        }
+        return Err(e);
+    }
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("profile", "created", json!({ "profile_id": profile.id })),
+    );
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("profile", "switched", json!({ "profile_id": profile.id })),
+    );
+    listeners::spawn_service_warm_up(handle.clone(), state.inner().clone());
+    // Demo prices are stored locally, so rebuild history without hitting providers
+    emit_portfolio_trigger_recalculate(
+        &handle,
+        PortfolioRequestPayload::builder()
+            .account_ids(None)
+            .refetch_all_market_data(false)
+            .build(),
+    );
+    Ok(profile)
+}
+
+/// Turning viewer mode off requires the app lock passphrase when one is configured,
+/// otherwise whoever is browsing could simply switch it off again.
+#[tauri::command]
+pub async fn set_profile_read_only(
+    profile_id: String,
+    read_only: bool,
+    passphrase: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Profile, String> {
+    debug!("Setting read-only={} for profile {}...", read_only, profile_id);
+    if !read_only {
+        state
+            .app_lock_service()
+            .verify_passphrase(passphrase.as_deref().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+    }
+    let profile = state
+        .profile_manager()
+        .set_read_only(&profile_id, read_only)
+        .map_err(|e| e.to_string())?;
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "profile",
+            "updated",
+            json!({ "profile_id": profile.id, "read_only": profile.read_only }),
+        ),
+    );
+    Ok(profile)
+}
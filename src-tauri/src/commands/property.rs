@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::query_cache::RESOURCE_ACTIVITY;
+use wealthvn_core::real_estate::{
+    NewProperty, NewPropertyAppraisal, Property, PropertyAppraisal, PropertySummary,
+};
+
+#[tauri::command]
+pub async fn get_properties(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<PropertySummary>, String> {
+    debug!("Fetching properties...");
+    state
+        .property_service()
+        .get_property_summaries()
+        .map_err(|e| format!("Failed to load properties: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_property(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<PropertySummary, String> {
+    debug!("Fetching property {}...", id);
+    state
+        .property_service()
+        .get_property_summary(&id)
+        .map_err(|e| format!("Failed to load property: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_property(
+    property: NewProperty,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Property, String> {
+    debug!("Creating property for account {}...", property.account_id);
+    let created = state
+        .property_service()
+        .create_property(property)
+        .await
+        .map_err(|e| format!("Failed to create property: {}", e))?;
+    // The property's purchase is an activity on its account
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "property",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&Property>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "property",
+            "created",
+            json!({ "property_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_property(
+    id: String,
+    property: NewProperty,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Property, String> {
+    debug!("Updating property {}...", id);
+    let service = state.property_service();
+    let previous = service
+        .get_properties()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|p| p.id == id);
+    let updated = service
+        .update_property(&id, property)
+        .await
+        .map_err(|e| format!("Failed to update property: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("property", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "property",
+            "updated",
+            json!({ "property_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_property(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting property {}...", id);
+    let service = state.property_service();
+    let previous = service
+        .get_properties()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|p| p.id == id);
+    let deleted = service
+        .delete_property(&id)
+        .await
+        .map_err(|e| format!("Failed to delete property: {}", e))?;
+    state.query_cache().invalidate(RESOURCE_ACTIVITY);
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("property", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&Property>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("property", "deleted", json!({ "property_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_property_appraisals(
+    property_id: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<PropertyAppraisal>, String> {
+    debug!("Fetching property appraisals...");
+    state
+        .property_service()
+        .get_property_appraisals(property_id)
+        .map_err(|e| format!("Failed to load property appraisals: {}", e))
+}
+
+#[tauri::command]
+pub async fn add_property_appraisal(
+    appraisal: NewPropertyAppraisal,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<PropertyAppraisal, String> {
+    debug!(
+        "Recording appraisal of property {} on {}...",
+        appraisal.property_id, appraisal.appraisal_date
+    );
+    let created = state
+        .property_service()
+        .add_property_appraisal(appraisal)
+        .await
+        .map_err(|e| format!("Failed to record property appraisal: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "property_appraisal",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&PropertyAppraisal>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "property",
+            "appraised",
+            json!({ "property_id": created.property_id, "appraisal_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn delete_property_appraisal(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting property appraisal {}...", id);
+    let service = state.property_service();
+    let previous = service
+        .get_property_appraisals(None)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|a| a.id == id);
+    let deleted = service
+        .delete_property_appraisal(&id)
+        .await
+        .map_err(|e| format!("Failed to delete property appraisal: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "property_appraisal",
+            &id,
+            AuditAction::Delete,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(previous.as_ref(), None::<&PropertyAppraisal>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "property",
+            "appraisal_deleted",
+            json!({ "appraisal_id": id }),
+        ),
+    );
+
+    Ok(deleted)
+}
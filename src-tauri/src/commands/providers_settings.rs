@@ -1,5 +1,7 @@
 use tauri::State;
-use wealthvn_core::market_data::MarketDataProviderSetting;
+use wealthvn_core::market_data::{
+    MarketDataProviderSetting, ProviderCredentialStatus, ProviderCredentialTestResult,
+};
 
 use crate::context::ServiceContext; // To access the service
 use std::sync::Arc;
@@ -11,7 +13,7 @@ pub async fn get_market_data_providers_settings(
     context: State<'_, Arc<ServiceContext>>,
 ) -> CommandResult<Vec<MarketDataProviderSetting>> {
     Ok(context
-        .market_data_service
+        .market_data_service()
         .get_market_data_providers_settings()
         .await?)
 }
@@ -24,7 +26,52 @@ pub async fn update_market_data_provider_settings(
     enabled: bool,
 ) -> CommandResult<MarketDataProviderSetting> {
     Ok(context
-        .market_data_service
+        .market_data_service()
         .update_market_data_provider_settings(provider_id, priority, enabled)
         .await?)
 }
+
+#[tauri::command]
+pub async fn get_provider_credential_statuses(
+    context: State<'_, Arc<ServiceContext>>,
+) -> CommandResult<Vec<ProviderCredentialStatus>> {
+    Ok(context
+        .market_data_service()
+        .get_provider_credential_statuses()?)
+}
+
+#[tauri::command]
+pub async fn set_provider_credential(
+    context: State<'_, Arc<ServiceContext>>,
+    provider_id: String,
+    api_key: String,
+) -> CommandResult<()> {
+    Ok(context
+        .market_data_service()
+        .set_provider_credential(&provider_id, &api_key)
+        .await?)
+}
+
+/// Tests the given key, or the stored one when `api_key` is omitted
+#[tauri::command]
+pub async fn test_provider_credential(
+    context: State<'_, Arc<ServiceContext>>,
+    provider_id: String,
+    api_key: Option<String>,
+) -> CommandResult<ProviderCredentialTestResult> {
+    Ok(context
+        .market_data_service()
+        .test_provider_credential(&provider_id, api_key)
+        .await?)
+}
+
+#[tauri::command]
+pub async fn remove_provider_credential(
+    context: State<'_, Arc<ServiceContext>>,
+    provider_id: String,
+) -> CommandResult<()> {
+    Ok(context
+        .market_data_service()
+        .remove_provider_credential(&provider_id)
+        .await?)
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use chrono::Utc;
+use log::{debug, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::activities::Activity;
+use wealthvn_core::audit::{
+    AuditAction, NewAuditLogEntry, AUDIT_ACTOR_RECURRING, AUDIT_ACTOR_USER,
+};
+use wealthvn_core::recurring_rules::{NewRecurringRule, RecurringRule};
+
+#[tauri::command]
+pub async fn list_recurring_rules(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<RecurringRule>, String> {
+    debug!("Fetching recurring rules...");
+    state
+        .recurring_rule_service()
+        .get_recurring_rules()
+        .map_err(|e| format!("Failed to load recurring rules: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_recurring_rule(
+    rule: NewRecurringRule,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<RecurringRule, String> {
+    debug!(
+        "Creating recurring rule {} for account {}...",
+        rule.name, rule.account_id
+    );
+    let created = state
+        .recurring_rule_service()
+        .create_recurring_rule(rule)
+        .await
+        .map_err(|e| format!("Failed to create recurring rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "recurring_rule",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&RecurringRule>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "recurring_rule",
+            "created",
+            json!({ "recurring_rule_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    // A rule starting in the past catches up right away rather than at the next check
+    materialize_recurring_rules(&state, &handle).await;
+
+    Ok(created)
+}
+
+/// Pauses a rule, or resumes it with `paused: false` from its next occurrence
+#[tauri::command]
+pub async fn pause_recurring_rule(
+    id: String,
+    paused: bool,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<RecurringRule, String> {
+    debug!(
+        "{} recurring rule {}...",
+        if paused { "Pausing" } else { "Resuming" },
+        id
+    );
+    let service = state.recurring_rule_service();
+    let previous = service
+        .get_recurring_rules()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|r| r.id == id);
+    let updated = service
+        .set_recurring_rule_paused(&id, paused, Utc::now().date_naive())
+        .await
+        .map_err(|e| format!("Failed to pause recurring rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("recurring_rule", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "recurring_rule",
+            "updated",
+            json!({ "recurring_rule_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_recurring_rule(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting recurring rule {}...", id);
+    let service = state.recurring_rule_service();
+    let previous = service
+        .get_recurring_rules()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|r| r.id == id);
+    let deleted = service
+        .delete_recurring_rule(&id)
+        .await
+        .map_err(|e| format!("Failed to delete recurring rule: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("recurring_rule", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&RecurringRule>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "recurring_rule",
+            "deleted",
+            json!({ "recurring_rule_id": id }),
+        ),
+    );
+
+    Ok(deleted)
+}
+
+/// Posts the recurring activities that have fallen due, including those missed while the app
+/// was closed; called on a timer from app setup, which runs it once at start
+pub async fn materialize_recurring_rules(context: &ServiceContext, handle: &AppHandle) {
+    let created = match context
+        .recurring_rule_service()
+        .materialize_due_activities(Utc::now().date_naive())
+        .await
+    {
+        Ok(created) => created,
+        Err(e) => {
+            warn!("Posting recurring activities failed: {}", e);
+            return;
+        }
+    };
+    if !created.is_empty() {
+        debug!("Posted {} recurring activities", created.len());
+    }
+
+    // Announced like any other new activity, so the portfolio recalculates
+    for activity in &created {
+        record_audit(
+            context,
+            NewAuditLogEntry::new(
+                "activity",
+                &activity.id,
+                AuditAction::Create,
+                AUDIT_ACTOR_RECURRING,
+            )
+            .with_snapshots(None::<&Activity>, Some(activity)),
+        )
+        .await;
+        emit_resource_changed(
+            handle,
+            ResourceEventPayload::new(
+                "activity",
+                "created",
+                json!({
+                    "activity_id": activity.id,
+                    "account_id": activity.account_id,
+                    "currency": activity.currency,
+                    "asset_id": activity.asset_id,
+                }),
+            ),
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use super::portfolio::{parse_date, privacy_mode};
+use crate::context::ServiceContext;
+use chrono::Utc;
+use log::debug;
+use tauri::State;
+use wealthvn_core::privacy::mask_if;
+use wealthvn_core::reports::{NetWorthHistoryEntry, NetWorthStatement};
+
+/// Assets by class, liabilities, net worth and goal-allocated assets as of `date` (today by
+/// default), with the change from the month before
+#[tauri::command]
+pub async fn get_net_worth_statement(
+    date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<NetWorthStatement, String> {
+    let date = parse_date(date, "statement")?.unwrap_or_else(|| Utc::now().date_naive());
+    debug!("Building net worth statement as of {}...", date);
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .report_service()
+        .get_net_worth_statement(date)
+        .await
+        .map(|statement| mask_if(statement, privacy_mode))
+        .map_err(|e| format!("Failed to build net worth statement: {}", e))
+}
+
+/// Recorded month-end statements of the last `months` months, oldest first
+#[tauri::command]
+pub async fn get_net_worth_statement_history(
+    months: u32,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<NetWorthHistoryEntry>, String> {
+    debug!(
+        "Fetching net worth statement history for {} months...",
+        months
+    );
+    let privacy_mode = privacy_mode(&state)?;
+    state
+        .report_service()
+        .get_net_worth_statement_history(months)
+        .map(|history| mask_if(history, privacy_mode))
+        .map_err(|e| format!("Failed to load net worth statement history: {}", e))
+}
@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::assets::{CASH_ASSET_TYPE, FOREX_ASSET_TYPE};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::errors::Result as CoreResult;
+use wealthvn_core::scripting::{NewScript, Script, ScriptGoalProgress, ScriptRunReport};
+
+#[tauri::command]
+pub async fn get_scripts(state: State<'_, Arc<ServiceContext>>) -> Result<Vec<Script>, String> {
+    debug!("Fetching scripts...");
+    state
+        .script_service()
+        .get_scripts()
+        .map_err(|e| format!("Failed to load scripts: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_script(
+    script: NewScript,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Script, String> {
+    debug!("Creating script {}...", script.name);
+    let created = state
+        .script_service()
+        .create_script(script)
+        .await
+        .map_err(|e| format!("Failed to create script: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("script", &created.id, AuditAction::Create, AUDIT_ACTOR_USER)
+            .with_snapshots(None::<&Script>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("script", "created", json!({ "script_id": created.id })),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_script(
+    id: String,
+    script: NewScript,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<Script, String> {
+    debug!("Updating script {}...", id);
+    let service = state.script_service();
+    let previous = service.get_script(&id).map_err(|e| e.to_string())?;
+    let updated = service
+        .update_script(&id, script)
+        .await
+        .map_err(|e| format!("Failed to update script: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("script", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("script", "updated", json!({ "script_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_script(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting script {}...", id);
+    let service = state.script_service();
+    let previous = service.get_script(&id).map_err(|e| e.to_string())?;
+    let deleted = service
+        .delete_script(&id)
+        .await
+        .map_err(|e| format!("Failed to delete script: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("script", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), None::<&Script>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("script", "deleted", json!({ "script_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+/// Symbols of every security, leaving out cash and currency pairs
+pub(crate) fn security_symbols(context: &ServiceContext) -> CoreResult<Vec<String>> {
+    Ok(context
+        .asset_service()
+        .get_assets()?
+        .into_iter()
+        .filter(|asset| {
+            !matches!(
+                asset.asset_type.as_deref(),
+                Some(CASH_ASSET_TYPE) | Some(FOREX_ASSET_TYPE)
+            )
+        })
+        .map(|asset| asset.symbol)
+        .collect())
+}
+
+/// Runs `on_quote_update` scripts over the latest quote of every security
+pub fn run_quote_update_scripts(context: &ServiceContext) -> CoreResult<ScriptRunReport> {
+    let symbols = security_symbols(context)?;
+    let mut quotes: Vec<_> = context
+        .market_data_service()
+        .get_latest_quotes_for_symbols(&symbols)?
+        .into_values()
+        .collect();
+    quotes.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    context.script_service().run_quote_update_hooks(&quotes)
+}
+
+/// Every goal whose progress is tracked
+pub(crate) async fn goal_progress(context: &ServiceContext) -> CoreResult<Vec<ScriptGoalProgress>> {
+    let emergency_funds = context
+        .emergency_fund_service()
+        .get_emergency_fund_progress()
+        .await?;
+    let sinking_funds = context
+        .sinking_fund_service()
+        .get_sinking_fund_progress()
+        .await?;
+    let net_worth_goals = context
+        .net_worth_goal_service()
+        .get_net_worth_goal_progress()
+        .await?;
+    let goals: Vec<ScriptGoalProgress> = emergency_funds
+        .iter()
+        .map(ScriptGoalProgress::from)
+        .chain(sinking_funds.iter().map(ScriptGoalProgress::from))
+        .chain(net_worth_goals.iter().map(ScriptGoalProgress::from))
+        .collect();
+    Ok(goals)
+}
+
+/// Runs `on_goal_progress` scripts over every goal whose progress is tracked
+pub async fn run_goal_progress_scripts(context: &ServiceContext) -> CoreResult<ScriptRunReport> {
+    context
+        .script_service()
+        .run_goal_progress_hooks(&goal_progress(context).await?)
+}
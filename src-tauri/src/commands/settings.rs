@@ -1,11 +1,19 @@
 use std::sync::Arc;
 
 use crate::context::ServiceContext;
-use crate::events::{emit_portfolio_trigger_recalculate, PortfolioRequestPayload};
+use crate::events::{
+    emit_portfolio_trigger_recalculate, emit_resource_changed, PortfolioRequestPayload,
+    ResourceEventPayload,
+};
 use log::debug;
+use serde_json::json;
+use std::collections::BTreeMap;
 use tauri::{AppHandle, State};
 use wealthvn_core::fx::fx_model::{ExchangeRate, NewExchangeRate};
-use wealthvn_core::settings::{Settings, SettingsUpdate};
+use wealthvn_core::i18n::{message_catalog, MessageLanguage};
+use wealthvn_core::settings::{
+    SettingChange, SettingKey, Settings, SettingsExport, SettingsImportResult, SettingsUpdate,
+};
 
 #[tauri::command]
 pub async fn get_settings(state: State<'_, Arc<ServiceContext>>) -> Result<Settings, String> {
@@ -111,12 +119,131 @@ pub async fn update_settings(
         }
     }
 
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("settings", "updated", json!({})),
+    );
+
     // Return the latest settings from the database
     service
         .get_settings()
         .map_err(|e| format!("Failed to load updated settings after change: {}", e))
 }
 
+#[tauri::command]
+pub async fn get_setting(
+    key: SettingKey,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<String, String> {
+    debug!("Fetching setting {}...", key.as_str());
+    state
+        .settings_service()
+        .get_setting_value(key)
+        .map_err(|e| format!("Failed to load setting: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_setting(
+    key: SettingKey,
+    value: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<SettingChange, String> {
+    debug!("Updating setting {}...", key.as_str());
+    let change = state
+        .settings_service()
+        .set_setting_value(key, &value)
+        .await
+        .map_err(|e| format!("Failed to update setting: {}", e))?;
+
+    if key == SettingKey::BaseCurrency && change.old_value.as_deref() != Some(value.as_str()) {
+        state.update_base_currency(value);
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let payload = PortfolioRequestPayload::builder()
+                .account_ids(None)
+                .refetch_all_market_data(true)
+                .symbols(None)
+                .build();
+            emit_portfolio_trigger_recalculate(&handle, payload);
+        });
+    }
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "setting",
+            "updated",
+            json!({ "key": change.key, "old_value": change.old_value, "new_value": change.new_value }),
+        ),
+    );
+
+    Ok(change)
+}
+
+/// Exports settings, import profiles and provider preferences; API keys are never included
+#[tauri::command]
+pub async fn export_settings(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SettingsExport, String> {
+    debug!("Exporting settings...");
+    state
+        .settings_export_service()
+        .export_settings()
+        .await
+        .map_err(|e| format!("Failed to export settings: {}", e))
+}
+
+#[tauri::command]
+pub async fn import_settings(
+    export: SettingsExport,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<SettingsImportResult, String> {
+    debug!("Importing settings exported at {}...", export.exported_at);
+    let current_base_currency = state.get_base_currency();
+    let result = state
+        .settings_export_service()
+        .import_settings(export)
+        .await
+        .map_err(|e| format!("Failed to import settings: {}", e))?;
+
+    let new_base_currency = state
+        .settings_service()
+        .get_base_currency()
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    if !new_base_currency.is_empty() && new_base_currency != current_base_currency {
+        state.update_base_currency(new_base_currency);
+        let payload = PortfolioRequestPayload::builder()
+            .account_ids(None)
+            .refetch_all_market_data(true)
+            .symbols(None)
+            .build();
+        emit_portfolio_trigger_recalculate(&handle, payload);
+    }
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("settings", "imported", json!({})),
+    );
+    Ok(result)
+}
+
+/// Message templates keyed by code, so the frontend can translate coded errors itself.
+/// Defaults to the language in settings.
+#[tauri::command]
+pub async fn get_message_catalog(
+    language: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<BTreeMap<String, String>, String> {
+    let language = match language {
+        Some(tag) => MessageLanguage::from_tag(&tag),
+        None => state.message_language(),
+    };
+    Ok(message_catalog(language))
+}
+
 #[tauri::command]
 pub async fn update_exchange_rate(
     rate: ExchangeRate,
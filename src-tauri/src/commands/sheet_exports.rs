@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::{debug, info, warn};
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::sheets::{GoogleCredentials, NewSheetExport, SheetExport, SheetExportResult};
+
+#[tauri::command]
+pub async fn get_sheet_exports(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<SheetExport>, String> {
+    debug!("Fetching sheet exports...");
+    state
+        .sheet_export_service()
+        .get_exports()
+        .map_err(|e| format!("Failed to load sheet exports: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_sheet_export(
+    export: NewSheetExport,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<SheetExport, String> {
+    debug!("Creating sheet export {}...", export.name);
+    let created = state
+        .sheet_export_service()
+        .create_export(export)
+        .await
+        .map_err(|e| format!("Failed to create sheet export: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "sheet_export",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&SheetExport>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "sheet_export",
+            "created",
+            json!({ "export_id": created.id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_sheet_export(
+    id: String,
+    export: NewSheetExport,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<SheetExport, String> {
+    debug!("Updating sheet export {}...", id);
+    let service = state.sheet_export_service();
+    let previous = service.get_export(&id).map_err(|e| e.to_string())?;
+    let updated = service
+        .update_export(&id, export)
+        .await
+        .map_err(|e| format!("Failed to update sheet export: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("sheet_export", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("sheet_export", "updated", json!({ "export_id": id })),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_sheet_export(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting sheet export {}...", id);
+    let service = state.sheet_export_service();
+    let previous = service.get_export(&id).map_err(|e| e.to_string())?;
+    let deleted = service
+        .delete_export(&id)
+        .await
+        .map_err(|e| format!("Failed to delete sheet export: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("sheet_export", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(Some(&previous), None::<&SheetExport>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("sheet_export", "deleted", json!({ "export_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn push_sheet_export(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SheetExportResult, String> {
+    debug!("Pushing sheet export {}...", id);
+    state
+        .sheet_export_service()
+        .push_export(&id)
+        .await
+        .map_err(|e| format!("Failed to push sheet export: {}", e))
+}
+
+#[tauri::command]
+pub async fn has_google_sheets_credentials(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<bool, String> {
+    state
+        .sheet_export_service()
+        .has_credentials()
+        .map_err(|e| format!("Failed to read Google credentials: {}", e))
+}
+
+#[tauri::command]
+pub async fn set_google_sheets_credentials(
+    credentials: GoogleCredentials,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    debug!("Saving Google Sheets credentials...");
+    state
+        .sheet_export_service()
+        .set_credentials(credentials)
+        .map_err(|e| format!("Failed to save Google credentials: {}", e))
+}
+
+#[tauri::command]
+pub async fn delete_google_sheets_credentials(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<(), String> {
+    debug!("Deleting Google Sheets credentials...");
+    state
+        .sheet_export_service()
+        .delete_credentials()
+        .map_err(|e| format!("Failed to delete Google credentials: {}", e))
+}
+
+/// Pushes every export that is due; called on a timer from app setup
+pub async fn push_due_sheet_exports(context: &ServiceContext) {
+    match context.sheet_export_service().push_due_exports().await {
+        Ok(results) => {
+            for result in results {
+                match result.error {
+                    Some(error) => warn!("Sheet export '{}' failed: {}", result.export_name, error),
+                    None => info!(
+                        "Sheet export '{}': {} rows written",
+                        result.export_name, result.rows
+                    ),
+                }
+            }
+        }
+        Err(e) => warn!("Sheet export failed: {}", e),
+    }
+}
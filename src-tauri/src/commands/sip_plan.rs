@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::sip_plans::{NewSipPlan, SipPlan, SipPlanSummary};
+
+#[tauri::command]
+pub async fn get_sip_plans(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<SipPlanSummary>, String> {
+    debug!("Fetching SIP plans...");
+    state
+        .sip_plan_service()
+        .get_sip_plan_summaries()
+        .map_err(|e| format!("Failed to load SIP plans: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_sip_plan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<SipPlanSummary, String> {
+    debug!("Fetching SIP plan {}...", id);
+    state
+        .sip_plan_service()
+        .get_sip_plan_summary(&id)
+        .map_err(|e| format!("Failed to load SIP plan: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_sip_plan(
+    plan: NewSipPlan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<SipPlan, String> {
+    debug!(
+        "Creating SIP plan for {} in account {}...",
+        plan.asset_id, plan.account_id
+    );
+    let created = state
+        .sip_plan_service()
+        .create_sip_plan(plan)
+        .await
+        .map_err(|e| format!("Failed to create SIP plan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "sip_plan",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&SipPlan>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "sip_plan",
+            "created",
+            json!({ "plan_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_sip_plan(
+    id: String,
+    plan: NewSipPlan,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<SipPlan, String> {
+    debug!("Updating SIP plan {}...", id);
+    let service = state.sip_plan_service();
+    let previous = service
+        .get_sip_plans()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|p| p.id == id);
+    let updated = service
+        .update_sip_plan(&id, plan)
+        .await
+        .map_err(|e| format!("Failed to update SIP plan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("sip_plan", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "sip_plan",
+            "updated",
+            json!({ "plan_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_sip_plan(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting SIP plan {}...", id);
+    let service = state.sip_plan_service();
+    let previous = service
+        .get_sip_plans()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|p| p.id == id);
+    let deleted = service
+        .delete_sip_plan(&id)
+        .await
+        .map_err(|e| format!("Failed to delete SIP plan: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("sip_plan", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&SipPlan>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("sip_plan", "deleted", json!({ "plan_id": id })),
+    );
+
+    Ok(deleted)
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::context::ServiceContext;
+use log::debug;
+use tauri::State;
+use wealthvn_core::telemetry::{performance_metrics, PerformanceStats, ServiceReadiness};
+
+/// Timings of instrumented core operations, recorded locally while the
+/// `performance_metrics` feature flag is on
+#[tauri::command]
+pub async fn get_performance_stats() -> Result<PerformanceStats, String> {
+    debug!("Fetching performance stats...");
+    Ok(performance_metrics().stats())
+}
+
+#[tauri::command]
+pub async fn reset_performance_stats() -> Result<(), String> {
+    debug!("Resetting performance stats...");
+    performance_metrics().reset();
+    Ok(())
+}
+
+/// Which subsystems started after the window are up yet; `service:ready` reports each change
+#[tauri::command]
+pub async fn get_service_readiness(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<ServiceReadiness, String> {
+    Ok(state.service_readiness())
+}
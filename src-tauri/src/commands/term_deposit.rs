@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use super::audit::record_audit;
+use crate::{
+    context::ServiceContext,
+    events::{emit_resource_changed, ResourceEventPayload},
+};
+use log::debug;
+use serde_json::json;
+use tauri::{AppHandle, State};
+use wealthvn_core::audit::{AuditAction, NewAuditLogEntry, AUDIT_ACTOR_USER};
+use wealthvn_core::term_deposits::{
+    NewTermDeposit, TermDeposit, TermDepositMaturity, TermDepositSummary,
+};
+
+/// Days of maturities listed when the caller doesn't say
+const DEFAULT_MATURITY_DAYS: u32 = 90;
+
+#[tauri::command]
+pub async fn get_term_deposits(
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<TermDepositSummary>, String> {
+    debug!("Fetching term deposits...");
+    state
+        .term_deposit_service()
+        .get_term_deposit_summaries()
+        .map_err(|e| format!("Failed to load term deposits: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_term_deposit(
+    id: String,
+    date: Option<String>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<TermDepositSummary, String> {
+    debug!("Valuing term deposit {}...", id);
+    let date = super::portfolio::parse_date(date, "valuation")?;
+    state
+        .term_deposit_service()
+        .get_term_deposit_summary(&id, date)
+        .map_err(|e| format!("Failed to value term deposit: {}", e))
+}
+
+#[tauri::command]
+pub async fn create_term_deposit(
+    deposit: NewTermDeposit,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<TermDeposit, String> {
+    debug!(
+        "Opening {} term deposit for account {}...",
+        deposit.bank_name, deposit.account_id
+    );
+    let created = state
+        .term_deposit_service()
+        .create_term_deposit(deposit)
+        .await
+        .map_err(|e| format!("Failed to create term deposit: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new(
+            "term_deposit",
+            &created.id,
+            AuditAction::Create,
+            AUDIT_ACTOR_USER,
+        )
+        .with_snapshots(None::<&TermDeposit>, Some(&created)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "term_deposit",
+            "created",
+            json!({ "term_deposit_id": created.id, "account_id": created.account_id }),
+        ),
+    );
+
+    Ok(created)
+}
+
+#[tauri::command]
+pub async fn update_term_deposit(
+    id: String,
+    deposit: NewTermDeposit,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<TermDeposit, String> {
+    debug!("Updating term deposit {}...", id);
+    let service = state.term_deposit_service();
+    let previous = service
+        .get_term_deposits()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|d| d.id == id);
+    let updated = service
+        .update_term_deposit(&id, deposit)
+        .await
+        .map_err(|e| format!("Failed to update term deposit: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("term_deposit", &id, AuditAction::Update, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), Some(&updated)),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new(
+            "term_deposit",
+            "updated",
+            json!({ "term_deposit_id": id, "account_id": updated.account_id }),
+        ),
+    );
+
+    Ok(updated)
+}
+
+#[tauri::command]
+pub async fn delete_term_deposit(
+    id: String,
+    state: State<'_, Arc<ServiceContext>>,
+    handle: AppHandle,
+) -> Result<usize, String> {
+    debug!("Deleting term deposit {}...", id);
+    let service = state.term_deposit_service();
+    let previous = service
+        .get_term_deposits()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|d| d.id == id);
+    let deleted = service
+        .delete_term_deposit(&id)
+        .await
+        .map_err(|e| format!("Failed to delete term deposit: {}", e))?;
+
+    record_audit(
+        &state,
+        NewAuditLogEntry::new("term_deposit", &id, AuditAction::Delete, AUDIT_ACTOR_USER)
+            .with_snapshots(previous.as_ref(), None::<&TermDeposit>),
+    )
+    .await;
+
+    emit_resource_changed(
+        &handle,
+        ResourceEventPayload::new("term_deposit", "deleted", json!({ "term_deposit_id": id })),
+    );
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub async fn get_upcoming_maturities(
+    days: Option<u32>,
+    state: State<'_, Arc<ServiceContext>>,
+) -> Result<Vec<TermDepositMaturity>, String> {
+    debug!("Listing upcoming term deposit maturities...");
+    state
+        .term_deposit_service()
+        .get_upcoming_maturities(days.unwrap_or(DEFAULT_MATURITY_DAYS))
+        .map_err(|e| format!("Failed to list deposit maturities: {}", e))
+}
@@ -2,5 +2,5 @@
 mod providers;
 mod registry;
 
-pub use providers::initialize_context;
+pub use providers::{initialize_context, switch_profile};
 pub use registry::ServiceContext;
@@ -1,20 +1,68 @@
-use super::registry::ServiceContext;
+use super::registry::{ProfileServices, ServiceContext};
+use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, RwLock};
 use wealthvn_core::{
-    accounts::{AccountRepository, AccountService},
+    accounts::{AccountGroupRepository, AccountGroupService, AccountRepository, AccountService},
     activities::{ActivityRepository, ActivityService},
+    app_lock::AppLockService,
+    audit::{AuditRepository, AuditService},
+    benchmarks::{BenchmarkRepository, BenchmarkService},
+    bills::{BillRepository, BillService},
+    bonds::{BondRepository, BondService},
+    bonus_plans::{BonusPlanRepository, BonusPlanService},
+    budgets::{BudgetRepository, BudgetService},
+    cash::CashService,
+    categorization::{CategorizationRepository, CategorizationService},
+    connectors::{ConnectorRepository, ConnectorService},
+    data_transfer::{DataTransferRepository, DataTransferService},
+    feature_flags::{FeatureFlag, FeatureFlagService, FeatureFlagServiceTrait},
     db::{self, write_actor},
+    demo::{DemoRepository, DemoService},
+    deposit_ladders::DepositLadderService,
+    deposit_rates::{
+        DepositRateFeed, DepositRateRepository, DepositRateService, HttpDepositRateFeed,
+    },
+    dividends::{DividendRepository, DividendService},
+    education::{EducationRepository, EducationService},
+    envelopes::{EnvelopeRepository, EnvelopeService},
+    esop::{EsopRepository, EsopService},
+    forecast::{ForecastRepository, ForecastService},
     fx::{FxRepository, FxService, FxServiceTrait},
-    goals::{GoalRepository, GoalService},
+    import_payload::ImportPayloadService,
+    importers::ActivityImportService,
+    ledger::LedgerExportService,
+    api_tokens::{ApiTokenRepository, ApiTokenService},
+    automations::{AutomationRepository, AutomationService},
+    goals::{
+        DebtPayoffService, EmergencyFundService, GoalRepository, GoalService, NetWorthGoalService,
+        SinkingFundService,
+    },
+    income_sources::{IncomeSourceRepository, IncomeSourceService},
     limits::{ContributionLimitRepository, ContributionLimitService},
+    loans::{LoanRepository, LoanService},
     market_data::{MarketDataRepository, MarketDataService, MarketDataServiceTrait},
+    onboarding::{OnboardingRepository, OnboardingService},
     portfolio::{
         holdings::{HoldingsService, HoldingsValuationService},
         income::IncomeService,
         performance::PerformanceService,
     },
-    settings::{settings_repository::SettingsRepository, SettingsService, SettingsServiceTrait},
+    private_loans::{PrivateLoanRepository, PrivateLoanService},
+    profiles::{Profile, ProfileManager},
+    query_cache::QueryCache,
+    real_estate::{PropertyRepository, PropertyService},
+    recurring_rules::{RecurringRuleRepository, RecurringRuleService},
+    reports::{NetWorthHistoryRepository, ReportService},
+    scripting::{ScriptRepository, ScriptService},
+    settings::{
+        settings_repository::SettingsRepository, SettingsExportRepository, SettingsExportService,
+        SettingsService, SettingsServiceTrait,
+    },
+    sheets::{SheetExportRepository, SheetExportService},
+    sip_plans::{SipPlanRepository, SipPlanService},
     snapshot::{SnapshotRepository, SnapshotService},
+    telemetry::performance_metrics,
+    term_deposits::{TermDepositRepository, TermDepositService},
     valuation::{ValuationRepository, ValuationService},
     vn_market::VnAssetsSyncService,
     AssetRepository, AssetService,
@@ -25,6 +73,47 @@ use wealthvn_core::{
 pub async fn initialize_context(
     app_data_dir: &str,
 ) -> Result<ServiceContext, Box<dyn std::error::Error>> {
+    let profile_manager = Arc::new(ProfileManager::load(app_data_dir)?);
+
+    // The app lock belongs to the installation, so its passphrase lives in the default database
+    let lock_db_path = db::init(app_data_dir)?;
+    let lock_pool = db::create_pool(&lock_db_path)?;
+    db::run_migrations(&lock_pool)?;
+    let lock_writer = write_actor::spawn_writer(lock_pool.as_ref().clone());
+    let app_lock_service = Arc::new(AppLockService::new(Arc::new(SettingsRepository::new(
+        lock_pool,
+        lock_writer,
+    )))?);
+
+    let active_profile = profile_manager.active_profile();
+    let services =
+        build_profile_services(&profile_manager.profile_data_dir(&active_profile)).await?;
+
+    Ok(ServiceContext::new(profile_manager, app_lock_service, services))
+}
+
+/// Activates another profile and rebuilds every service against its database.
+/// The registry is only updated once the new services are ready.
+pub async fn switch_profile(
+    context: &ServiceContext,
+    profile_id: &str,
+) -> Result<Profile, Box<dyn std::error::Error>> {
+    let profile_manager = context.profile_manager();
+    let profile = profile_manager
+        .list_profiles()
+        .into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| format!("Profile {} not found", profile_id))?;
+
+    let services = build_profile_services(&profile_manager.profile_data_dir(&profile)).await?;
+    profile_manager.set_active_profile(&profile.id)?;
+    context.replace_services(services);
+    Ok(profile)
+}
+
+async fn build_profile_services(
+    app_data_dir: &str,
+) -> Result<ProfileServices, Box<dyn std::error::Error>> {
     let db_path = db::init(app_data_dir)?;
     let pool = db::create_pool(&db_path)?;
     let writer = write_actor::spawn_writer(pool.as_ref().clone());
@@ -46,6 +135,18 @@ pub async fn initialize_context(
     let fx_repository = Arc::new(FxRepository::new(pool.clone(), writer.clone()));
     let snapshot_repository = Arc::new(SnapshotRepository::new(pool.clone(), writer.clone()));
     let valuation_repository = Arc::new(ValuationRepository::new(pool.clone(), writer.clone()));
+    let audit_repository = Arc::new(AuditRepository::new(pool.clone(), writer.clone()));
+    let onboarding_repository = Arc::new(OnboardingRepository::new(pool.clone(), writer.clone()));
+    let demo_repository = Arc::new(DemoRepository::new(pool.clone(), writer.clone()));
+    let budget_repository = Arc::new(BudgetRepository::new(pool.clone(), writer.clone()));
+    let forecast_repository = Arc::new(ForecastRepository::new(pool.clone(), writer.clone()));
+    let income_source_repository =
+        Arc::new(IncomeSourceRepository::new(pool.clone(), writer.clone()));
+    let bill_repository = Arc::new(BillRepository::new(pool.clone(), writer.clone()));
+    let envelope_repository = Arc::new(EnvelopeRepository::new(pool.clone(), writer.clone()));
+    let loan_repository = Arc::new(LoanRepository::new(pool.clone(), writer.clone()));
+    let categorization_repository =
+        Arc::new(CategorizationRepository::new(pool.clone(), writer.clone()));
     // Instantiate Transaction Executor using the Arc<DbPool> directly
     let transaction_executor = pool.clone();
 
@@ -61,8 +162,10 @@ pub async fn initialize_context(
     let base_currency = Arc::new(RwLock::new(base_currency_string.clone()));
     let instance_id = Arc::new(settings.instance_id.clone());
 
+    // Providers are configured in the background after the window is up (see
+    // `spawn_service_warm_up`), or by whichever call needs them first
     let market_data_service: Arc<dyn MarketDataServiceTrait> = Arc::new(
-        MarketDataService::with_pool(
+        MarketDataService::deferred(
             market_data_repo.clone(),
             asset_repository.clone(),
             Some(pool.clone()),
@@ -70,6 +173,12 @@ pub async fn initialize_context(
         .await?,
     );
 
+    let settings_export_service = Arc::new(SettingsExportService::new(
+        Arc::new(SettingsExportRepository::new(pool.clone(), writer.clone())),
+        settings_service.clone(),
+        market_data_service.clone(),
+    ));
+
     let asset_service = Arc::new(AssetService::new(
         asset_repository.clone(),
         market_data_service.clone(),
@@ -89,26 +198,259 @@ pub async fn initialize_context(
         fx_service.clone(),
         market_data_service.clone(),
     ));
-    let goal_service = Arc::new(GoalService::new(goal_repo.clone()));
+    let snapshot_service = Arc::new(SnapshotService::new(
+        base_currency.clone(),
+        account_repository.clone(),
+        activity_repository.clone(),
+        snapshot_repository.clone(),
+        asset_repository.clone(),
+        fx_service.clone(),
+    ));
+    let cash_service = Arc::new(CashService::new(
+        account_repository.clone(),
+        snapshot_service.clone(),
+        activity_service.clone(),
+        asset_service.clone(),
+        fx_service.clone(),
+    ));
+    let valuation_service = Arc::new(ValuationService::new(
+        base_currency.clone(),
+        valuation_repository.clone(),
+        snapshot_service.clone(),
+        market_data_service.clone(),
+        fx_service.clone(),
+    ));
+
+    let goal_service = Arc::new(
+        GoalService::new(goal_repo.clone())
+            .with_fx(fx_service.clone(), base_currency.clone())
+            .with_cash(cash_service.clone())
+            .with_valuations(valuation_service.clone())
+            .with_loans(loan_repository.clone(), account_repository.clone()),
+    );
+    let audit_service = Arc::new(AuditService::new(audit_repository.clone()));
+    let feature_flag_service = Arc::new(FeatureFlagService::new(settings_repository.clone()));
+    performance_metrics()
+        .set_enabled(feature_flag_service.is_enabled(FeatureFlag::PerformanceMetrics));
+    let onboarding_service = Arc::new(OnboardingService::new(
+        onboarding_repository.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let demo_service = Arc::new(DemoService::new(
+        demo_repository.clone(),
+        base_currency.clone(),
+    ));
     let limits_service = Arc::new(ContributionLimitService::new(
         fx_service.clone(),
         limit_repository.clone(),
         activity_repository.clone(),
+        settings_service.clone(),
+    ));
+    let budget_service = Arc::new(BudgetService::new(
+        budget_repository.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let emergency_fund_service = Arc::new(EmergencyFundService::new(
+        goal_repo.clone(),
+        budget_service.clone(),
+    ));
+    let sinking_fund_service = Arc::new(SinkingFundService::new(
+        goal_repo.clone(),
+        base_currency.clone(),
+    ));
+    let net_worth_goal_service = Arc::new(NetWorthGoalService::new(
+        goal_repo.clone(),
+        account_repository.clone(),
+        valuation_repository.clone(),
+        loan_repository.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let debt_payoff_service = Arc::new(DebtPayoffService::new(
+        goal_repo.clone(),
+        account_repository.clone(),
+        loan_repository.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let categorization_service = Arc::new(CategorizationService::new(
+        categorization_repository.clone(),
+        budget_repository.clone(),
     ));
 
-    let income_service = Arc::new(IncomeService::new(
+    let forecast_service = Arc::new(ForecastService::new(
+        forecast_repository.clone(),
+        account_repository.clone(),
+        snapshot_repository.clone(),
+        goal_repo.clone(),
+        income_source_repository.clone(),
+        loan_repository.clone(),
         fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let bill_service = Arc::new(BillService::new(
+        bill_repository.clone(),
         activity_repository.clone(),
+        fx_service.clone(),
+    ));
+    let envelope_service = Arc::new(EnvelopeService::new(
+        envelope_repository.clone(),
+        account_repository.clone(),
+        snapshot_repository.clone(),
+        fx_service.clone(),
+    ));
+    let loan_service = Arc::new(LoanService::new(
+        loan_repository.clone(),
+        account_repository.clone(),
+    ));
+    let property_service = Arc::new(PropertyService::new(
+        Arc::new(PropertyRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        loan_repository.clone(),
+        income_source_repository.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        market_data_service.clone(),
+    ));
+    let bond_service = Arc::new(BondService::new(
+        Arc::new(BondRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        market_data_service.clone(),
+    ));
+    let term_deposit_service = Arc::new(TermDepositService::new(
+        Arc::new(TermDepositRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+    ));
+    let recurring_rule_service = Arc::new(RecurringRuleService::new(
+        Arc::new(RecurringRuleRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        activity_service.clone(),
+        asset_service.clone(),
+    ));
+    let report_service = Arc::new(ReportService::new(
+        Arc::new(NetWorthHistoryRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        valuation_repository.clone(),
+        snapshot_service.clone(),
+        asset_service.clone(),
+        market_data_service.clone(),
+        loan_repository.clone(),
+        goal_repo.clone(),
+        fx_service.clone(),
         base_currency.clone(),
     ));
-
-    let snapshot_service = Arc::new(SnapshotService::new(
+    let private_loan_service = Arc::new(PrivateLoanService::new(
+        Arc::new(PrivateLoanRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        activity_repository.clone(),
+        market_data_service.clone(),
+        fx_service.clone(),
+    ));
+    let esop_service = Arc::new(EsopService::new(
+        Arc::new(EsopRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        market_data_service.clone(),
+    ));
+    let sip_plan_service = Arc::new(SipPlanService::new(
+        Arc::new(SipPlanRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        market_data_service.clone(),
+        forecast_service.clone(),
+    ));
+    let bonus_plan_service = Arc::new(BonusPlanService::new(
+        Arc::new(BonusPlanRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        income_source_repository.clone(),
+        goal_service.clone(),
+        net_worth_goal_service.clone(),
+        envelope_service.clone(),
+        asset_service.clone(),
+        fx_service.clone(),
         base_currency.clone(),
+    ));
+    // Same variable as the web server; the bundled rates are used when unset
+    let deposit_rate_feed = std::env::var("WF_DEPOSIT_RATES_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| Arc::new(HttpDepositRateFeed::new(url)) as Arc<dyn DepositRateFeed>);
+    let deposit_rate_repository = Arc::new(DepositRateRepository::new(pool.clone(), writer.clone()));
+    let deposit_rate_service = Arc::new(DepositRateService::new(
+        deposit_rate_repository.clone(),
+        forecast_repository.clone(),
+        deposit_rate_feed,
+    ));
+    let deposit_ladder_service = Arc::new(DepositLadderService::new(
+        deposit_rate_repository,
         account_repository.clone(),
+        account_service.clone(),
+        asset_service.clone(),
+        activity_service.clone(),
+        forecast_service.clone(),
+    ));
+    let education_service = Arc::new(EducationService::new(
+        Arc::new(EducationRepository::new(pool.clone(), writer.clone())),
+        goal_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let script_service = Arc::new(ScriptService::new(
+        Arc::new(ScriptRepository::new(pool.clone(), writer.clone())),
+        market_data_service.clone(),
+        fx_service.clone(),
+    ));
+    let connector_service = Arc::new(ConnectorService::new(
+        Arc::new(ConnectorRepository::new(pool.clone(), writer.clone())),
+        activity_service.clone(),
+        script_service.clone(),
+    ));
+    let import_payload_service = Arc::new(ImportPayloadService::new(
+        activity_service.clone(),
+        market_data_service.clone(),
+        script_service.clone(),
+    ));
+    let activity_import_service = Arc::new(ActivityImportService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        import_payload_service.clone(),
+    ));
+    let ledger_export_service = Arc::new(LedgerExportService::new(
+        account_service.clone(),
+        activity_service.clone(),
+        asset_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let data_transfer_service = Arc::new(DataTransferService::new(
+        Arc::new(DataTransferRepository::new(pool.clone())),
+        market_data_service.clone(),
+    ));
+    let api_token_service = Arc::new(ApiTokenService::new(Arc::new(ApiTokenRepository::new(
+        pool.clone(),
+        writer.clone(),
+    ))));
+    let income_source_service = Arc::new(IncomeSourceService::new(
+        income_source_repository.clone(),
         activity_repository.clone(),
-        snapshot_repository.clone(),
-        asset_repository.clone(),
+        budget_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+
+    let income_service = Arc::new(IncomeService::new(
         fx_service.clone(),
+        activity_repository.clone(),
+        settings_service.clone(),
+        base_currency.clone(),
     ));
 
     let holdings_valuation_service = Arc::new(HoldingsValuationService::new(
@@ -116,43 +458,109 @@ pub async fn initialize_context(
         market_data_service.clone(),
     ));
 
-    let valuation_service = Arc::new(ValuationService::new(
-        base_currency.clone(),
-        valuation_repository.clone(),
-        snapshot_service.clone(),
+    let benchmark_service = Arc::new(BenchmarkService::new(
+        Arc::new(BenchmarkRepository::new(pool.clone(), writer.clone())),
         market_data_service.clone(),
-        fx_service.clone(),
+        valuation_service.clone(),
+        goal_repo.clone(),
     ));
-
     let performance_service = Arc::new(PerformanceService::new(
         valuation_service.clone(),
         market_data_service.clone(),
     ));
+    let account_group_service = Arc::new(AccountGroupService::new(
+        Arc::new(AccountGroupRepository::new(pool.clone(), writer.clone())),
+        account_repository.clone(),
+        valuation_service.clone(),
+        base_currency.clone(),
+    ));
 
     let holdings_service = Arc::new(HoldingsService::new(
         asset_service.clone(),
         snapshot_service.clone(),
         holdings_valuation_service.clone(),
     ));
+    let dividend_service = Arc::new(DividendService::new(
+        Arc::new(DividendRepository::new(pool.clone(), writer.clone())),
+        activity_repository.clone(),
+        holdings_service.clone(),
+        fx_service.clone(),
+        base_currency.clone(),
+    ));
+    let sheet_export_service = Arc::new(SheetExportService::new(
+        Arc::new(SheetExportRepository::new(pool.clone(), writer.clone())),
+        holdings_service.clone(),
+        net_worth_goal_service.clone(),
+        emergency_fund_service.clone(),
+        sinking_fund_service.clone(),
+        base_currency.clone(),
+    ));
+    let automation_service = Arc::new(AutomationService::new(
+        Arc::new(AutomationRepository::new(pool.clone(), writer.clone())),
+        sheet_export_service.clone(),
+    ));
 
     let vn_assets_sync_service = Arc::new(VnAssetsSyncService::new(pool.clone()));
 
-    Ok(ServiceContext {
+    Ok(ProfileServices {
         base_currency,
         instance_id,
         settings_service,
+        settings_export_service,
         account_service,
+        account_group_service,
         activity_service,
         asset_service,
+        audit_service,
+        feature_flag_service,
         goal_service,
+        emergency_fund_service,
+        sinking_fund_service,
+        net_worth_goal_service,
+        debt_payoff_service,
+        onboarding_service,
+        demo_service,
         market_data_service,
         limits_service,
+        budget_service,
+        cash_service,
+        categorization_service,
+        forecast_service,
+        income_source_service,
+        bill_service,
+        envelope_service,
+        loan_service,
+        property_service,
+        bond_service,
+        term_deposit_service,
+        recurring_rule_service,
+        dividend_service,
+        report_service,
+        private_loan_service,
+        esop_service,
+        sip_plan_service,
+        bonus_plan_service,
+        deposit_ladder_service,
+        deposit_rate_service,
+        education_service,
+        script_service,
+        connector_service,
+        sheet_export_service,
+        import_payload_service,
+        activity_import_service,
+        ledger_export_service,
+        data_transfer_service,
+        api_token_service,
+        automation_service,
         fx_service,
         performance_service,
         income_service,
         snapshot_service,
         holdings_service,
         valuation_service,
+        benchmark_service,
         vn_assets_sync_service,
+        query_cache: Arc::new(QueryCache::new()),
+        valuations_ready: AtomicBool::new(false),
     })
 }
@@ -1,91 +1,379 @@
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, RwLock};
 use wealthvn_core::{
-    self, accounts, activities, assets, fx, goals, limits, market_data, portfolio, settings,
-    vn_market::VnAssetsSyncService,
+    self, accounts, activities, api_tokens, app_lock, assets, audit, automations, benchmarks, bills, bonds, bonus_plans, budgets, cash, categorization, connectors, data_transfer, demo, deposit_ladders, deposit_rates, dividends, education, envelopes, esop, feature_flags, forecast, fx, goals, i18n, import_payload, importers, income_sources, ledger, limits, loans, market_data, onboarding, portfolio, private_loans, real_estate, recurring_rules, reports, scripting, sheets, sip_plans, term_deposits,
+    operations::OperationRegistry, profiles::ProfileManager, query_cache::QueryCache, settings, telemetry, vn_market::VnAssetsSyncService,
 };
-pub struct ServiceContext {
+
+/// Services bound to one profile's database. Rebuilt whenever the active profile changes.
+pub struct ProfileServices {
     pub base_currency: Arc<RwLock<String>>,
     pub instance_id: Arc<String>,
 
     // Services
     pub settings_service: Arc<dyn settings::SettingsServiceTrait>,
+    pub settings_export_service: Arc<dyn settings::SettingsExportServiceTrait>,
     pub activity_service: Arc<dyn activities::ActivityServiceTrait>,
     pub account_service: Arc<dyn accounts::AccountServiceTrait>,
+    pub account_group_service: Arc<dyn accounts::AccountGroupServiceTrait>,
     pub goal_service: Arc<dyn goals::GoalServiceTrait>,
+    pub emergency_fund_service: Arc<dyn goals::EmergencyFundServiceTrait>,
+    pub sinking_fund_service: Arc<dyn goals::SinkingFundServiceTrait>,
+    pub net_worth_goal_service: Arc<dyn goals::NetWorthGoalServiceTrait>,
+    pub debt_payoff_service: Arc<dyn goals::DebtPayoffServiceTrait>,
     pub asset_service: Arc<dyn assets::AssetServiceTrait>,
+    pub audit_service: Arc<dyn audit::AuditServiceTrait>,
+    pub feature_flag_service: Arc<dyn feature_flags::FeatureFlagServiceTrait>,
     pub market_data_service: Arc<dyn market_data::MarketDataServiceTrait>,
+    pub onboarding_service: Arc<dyn onboarding::OnboardingServiceTrait>,
+    pub demo_service: Arc<dyn demo::DemoServiceTrait>,
     pub limits_service: Arc<dyn limits::ContributionLimitServiceTrait>,
+    pub budget_service: Arc<dyn budgets::BudgetServiceTrait>,
+    pub cash_service: Arc<dyn cash::CashServiceTrait>,
+    pub categorization_service: Arc<dyn categorization::CategorizationServiceTrait>,
+    pub forecast_service: Arc<dyn forecast::ForecastServiceTrait>,
+    pub income_source_service: Arc<dyn income_sources::IncomeSourceServiceTrait>,
+    pub bill_service: Arc<dyn bills::BillServiceTrait>,
+    pub envelope_service: Arc<dyn envelopes::EnvelopeServiceTrait>,
+    pub loan_service: Arc<dyn loans::LoanServiceTrait>,
+    pub property_service: Arc<dyn real_estate::PropertyServiceTrait>,
+    pub bond_service: Arc<dyn bonds::BondServiceTrait>,
+    pub term_deposit_service: Arc<dyn term_deposits::TermDepositServiceTrait>,
+    pub recurring_rule_service: Arc<dyn recurring_rules::RecurringRuleServiceTrait>,
+    pub dividend_service: Arc<dyn dividends::DividendServiceTrait>,
+    pub report_service: Arc<dyn reports::ReportServiceTrait>,
+    pub private_loan_service: Arc<dyn private_loans::PrivateLoanServiceTrait>,
+    pub esop_service: Arc<dyn esop::EsopServiceTrait>,
+    pub sip_plan_service: Arc<dyn sip_plans::SipPlanServiceTrait>,
+    pub bonus_plan_service: Arc<dyn bonus_plans::BonusPlanServiceTrait>,
+    pub deposit_ladder_service: Arc<dyn deposit_ladders::DepositLadderServiceTrait>,
+    pub deposit_rate_service: Arc<dyn deposit_rates::DepositRateServiceTrait>,
+    pub education_service: Arc<dyn education::EducationServiceTrait>,
+    pub script_service: Arc<dyn scripting::ScriptServiceTrait>,
+    pub connector_service: Arc<dyn connectors::ConnectorServiceTrait>,
+    pub sheet_export_service: Arc<dyn sheets::SheetExportServiceTrait>,
+    pub import_payload_service: Arc<dyn import_payload::ImportPayloadServiceTrait>,
+    pub activity_import_service: Arc<dyn importers::ActivityImportServiceTrait>,
+    pub ledger_export_service: Arc<dyn ledger::LedgerExportServiceTrait>,
+    pub data_transfer_service: Arc<dyn data_transfer::DataTransferServiceTrait>,
+    pub api_token_service: Arc<dyn api_tokens::ApiTokenServiceTrait>,
+    pub automation_service: Arc<dyn automations::AutomationServiceTrait>,
     pub fx_service: Arc<dyn fx::FxServiceTrait>,
     pub performance_service: Arc<dyn portfolio::performance::PerformanceServiceTrait>,
     pub income_service: Arc<dyn portfolio::income::IncomeServiceTrait>,
     pub snapshot_service: Arc<dyn portfolio::snapshot::SnapshotServiceTrait>,
     pub holdings_service: Arc<dyn portfolio::holdings::HoldingsServiceTrait>,
     pub valuation_service: Arc<dyn portfolio::valuation::ValuationServiceTrait>,
+    pub benchmark_service: Arc<dyn benchmarks::BenchmarkServiceTrait>,
     pub vn_assets_sync_service: Arc<VnAssetsSyncService>,
+
+    /// Results of expensive read commands, dropped on resource changes
+    pub query_cache: Arc<QueryCache>,
+
+    /// Set once the first portfolio update of this profile has brought valuations up to date
+    pub valuations_ready: AtomicBool,
+}
+
+pub struct ServiceContext {
+    pub profile_manager: Arc<ProfileManager>,
+    // The app lock guards the whole installation, so it survives profile switches
+    pub app_lock_service: Arc<dyn app_lock::AppLockServiceTrait>,
+    // Kept across switches too, so an export still running for the previous profile stays cancellable
+    pub operations: Arc<OperationRegistry>,
+    services: RwLock<Arc<ProfileServices>>,
 }
 
 impl ServiceContext {
+    pub fn new(
+        profile_manager: Arc<ProfileManager>,
+        app_lock_service: Arc<dyn app_lock::AppLockServiceTrait>,
+        services: ProfileServices,
+    ) -> Self {
+        Self {
+            profile_manager,
+            app_lock_service,
+            operations: Arc::new(OperationRegistry::new()),
+            services: RwLock::new(Arc::new(services)),
+        }
+    }
+
+    fn services(&self) -> Arc<ProfileServices> {
+        Arc::clone(&self.services.read().unwrap())
+    }
+
+    /// Swaps in the services of a newly activated profile. Commands already running
+    /// keep the previous services until they finish.
+    pub fn replace_services(&self, services: ProfileServices) {
+        *self.services.write().unwrap() = Arc::new(services);
+    }
+
+    pub fn profile_manager(&self) -> Arc<ProfileManager> {
+        Arc::clone(&self.profile_manager)
+    }
+
+    pub fn instance_id(&self) -> Arc<String> {
+        Arc::clone(&self.services().instance_id)
+    }
+
     pub fn get_base_currency(&self) -> String {
-        self.base_currency.read().unwrap().clone()
+        self.services().base_currency.read().unwrap().clone()
     }
 
     pub fn update_base_currency(&self, new_currency: String) {
-        *self.base_currency.write().unwrap() = new_currency;
+        *self.services().base_currency.write().unwrap() = new_currency;
+    }
+
+    /// Language for user-facing messages, taken from the `language` setting
+    pub fn message_language(&self) -> i18n::MessageLanguage {
+        self.settings_service()
+            .get_settings()
+            .map(|settings| i18n::MessageLanguage::from_tag(&settings.language))
+            .unwrap_or_default()
     }
 
     pub fn settings_service(&self) -> Arc<dyn settings::SettingsServiceTrait> {
-        Arc::clone(&self.settings_service)
+        Arc::clone(&self.services().settings_service)
+    }
+
+    pub fn settings_export_service(&self) -> Arc<dyn settings::SettingsExportServiceTrait> {
+        Arc::clone(&self.services().settings_export_service)
     }
 
     pub fn account_service(&self) -> Arc<dyn accounts::AccountServiceTrait> {
-        Arc::clone(&self.account_service)
+        Arc::clone(&self.services().account_service)
     }
 
     pub fn activity_service(&self) -> Arc<dyn activities::ActivityServiceTrait> {
-        Arc::clone(&self.activity_service)
+        Arc::clone(&self.services().activity_service)
+    }
+
+    pub fn account_group_service(&self) -> Arc<dyn accounts::AccountGroupServiceTrait> {
+        Arc::clone(&self.services().account_group_service)
     }
 
     pub fn asset_service(&self) -> Arc<dyn assets::AssetServiceTrait> {
-        Arc::clone(&self.asset_service)
+        Arc::clone(&self.services().asset_service)
+    }
+
+    pub fn audit_service(&self) -> Arc<dyn audit::AuditServiceTrait> {
+        Arc::clone(&self.services().audit_service)
+    }
+
+    pub fn feature_flag_service(&self) -> Arc<dyn feature_flags::FeatureFlagServiceTrait> {
+        Arc::clone(&self.services().feature_flag_service)
+    }
+
+    pub fn onboarding_service(&self) -> Arc<dyn onboarding::OnboardingServiceTrait> {
+        Arc::clone(&self.services().onboarding_service)
+    }
+
+    pub fn demo_service(&self) -> Arc<dyn demo::DemoServiceTrait> {
+        Arc::clone(&self.services().demo_service)
     }
 
     pub fn goal_service(&self) -> Arc<dyn goals::GoalServiceTrait> {
-        Arc::clone(&self.goal_service)
+        Arc::clone(&self.services().goal_service)
+    }
+
+    pub fn emergency_fund_service(&self) -> Arc<dyn goals::EmergencyFundServiceTrait> {
+        Arc::clone(&self.services().emergency_fund_service)
+    }
+
+    pub fn sinking_fund_service(&self) -> Arc<dyn goals::SinkingFundServiceTrait> {
+        Arc::clone(&self.services().sinking_fund_service)
+    }
+
+    pub fn net_worth_goal_service(&self) -> Arc<dyn goals::NetWorthGoalServiceTrait> {
+        Arc::clone(&self.services().net_worth_goal_service)
+    }
+
+    pub fn debt_payoff_service(&self) -> Arc<dyn goals::DebtPayoffServiceTrait> {
+        Arc::clone(&self.services().debt_payoff_service)
     }
 
     pub fn market_data_service(&self) -> Arc<dyn market_data::MarketDataServiceTrait> {
-        Arc::clone(&self.market_data_service)
+        Arc::clone(&self.services().market_data_service)
     }
 
     pub fn limits_service(&self) -> Arc<dyn limits::ContributionLimitServiceTrait> {
-        Arc::clone(&self.limits_service)
+        Arc::clone(&self.services().limits_service)
+    }
+
+    pub fn budget_service(&self) -> Arc<dyn budgets::BudgetServiceTrait> {
+        Arc::clone(&self.services().budget_service)
+    }
+
+    pub fn cash_service(&self) -> Arc<dyn cash::CashServiceTrait> {
+        Arc::clone(&self.services().cash_service)
+    }
+
+    pub fn categorization_service(&self) -> Arc<dyn categorization::CategorizationServiceTrait> {
+        Arc::clone(&self.services().categorization_service)
+    }
+
+    pub fn forecast_service(&self) -> Arc<dyn forecast::ForecastServiceTrait> {
+        Arc::clone(&self.services().forecast_service)
+    }
+
+    pub fn income_source_service(&self) -> Arc<dyn income_sources::IncomeSourceServiceTrait> {
+        Arc::clone(&self.services().income_source_service)
+    }
+
+    pub fn bill_service(&self) -> Arc<dyn bills::BillServiceTrait> {
+        Arc::clone(&self.services().bill_service)
+    }
+
+    pub fn envelope_service(&self) -> Arc<dyn envelopes::EnvelopeServiceTrait> {
+        Arc::clone(&self.services().envelope_service)
+    }
+
+    pub fn loan_service(&self) -> Arc<dyn loans::LoanServiceTrait> {
+        Arc::clone(&self.services().loan_service)
+    }
+
+    pub fn property_service(&self) -> Arc<dyn real_estate::PropertyServiceTrait> {
+        Arc::clone(&self.services().property_service)
+    }
+
+    pub fn bond_service(&self) -> Arc<dyn bonds::BondServiceTrait> {
+        Arc::clone(&self.services().bond_service)
+    }
+
+    pub fn term_deposit_service(&self) -> Arc<dyn term_deposits::TermDepositServiceTrait> {
+        Arc::clone(&self.services().term_deposit_service)
+    }
+
+    pub fn recurring_rule_service(&self) -> Arc<dyn recurring_rules::RecurringRuleServiceTrait> {
+        Arc::clone(&self.services().recurring_rule_service)
+    }
+
+    pub fn dividend_service(&self) -> Arc<dyn dividends::DividendServiceTrait> {
+        Arc::clone(&self.services().dividend_service)
+    }
+
+    pub fn report_service(&self) -> Arc<dyn reports::ReportServiceTrait> {
+        Arc::clone(&self.services().report_service)
+    }
+
+    pub fn private_loan_service(&self) -> Arc<dyn private_loans::PrivateLoanServiceTrait> {
+        Arc::clone(&self.services().private_loan_service)
+    }
+
+    pub fn esop_service(&self) -> Arc<dyn esop::EsopServiceTrait> {
+        Arc::clone(&self.services().esop_service)
+    }
+
+    pub fn sip_plan_service(&self) -> Arc<dyn sip_plans::SipPlanServiceTrait> {
+        Arc::clone(&self.services().sip_plan_service)
+    }
+
+    pub fn bonus_plan_service(&self) -> Arc<dyn bonus_plans::BonusPlanServiceTrait> {
+        Arc::clone(&self.services().bonus_plan_service)
+    }
+
+    pub fn deposit_ladder_service(&self) -> Arc<dyn deposit_ladders::DepositLadderServiceTrait> {
+        Arc::clone(&self.services().deposit_ladder_service)
+    }
+
+    pub fn deposit_rate_service(&self) -> Arc<dyn deposit_rates::DepositRateServiceTrait> {
+        Arc::clone(&self.services().deposit_rate_service)
+    }
+
+    pub fn education_service(&self) -> Arc<dyn education::EducationServiceTrait> {
+        Arc::clone(&self.services().education_service)
+    }
+
+    pub fn script_service(&self) -> Arc<dyn scripting::ScriptServiceTrait> {
+        Arc::clone(&self.services().script_service)
+    }
+
+    pub fn connector_service(&self) -> Arc<dyn connectors::ConnectorServiceTrait> {
+        Arc::clone(&self.services().connector_service)
+    }
+
+    pub fn sheet_export_service(&self) -> Arc<dyn sheets::SheetExportServiceTrait> {
+        Arc::clone(&self.services().sheet_export_service)
+    }
+
+    pub fn import_payload_service(&self) -> Arc<dyn import_payload::ImportPayloadServiceTrait> {
+        Arc::clone(&self.services().import_payload_service)
+    }
+
+    pub fn activity_import_service(&self) -> Arc<dyn importers::ActivityImportServiceTrait> {
+        Arc::clone(&self.services().activity_import_service)
+    }
+
+    pub fn ledger_export_service(&self) -> Arc<dyn ledger::LedgerExportServiceTrait> {
+        Arc::clone(&self.services().ledger_export_service)
+    }
+
+    pub fn data_transfer_service(&self) -> Arc<dyn data_transfer::DataTransferServiceTrait> {
+        Arc::clone(&self.services().data_transfer_service)
+    }
+
+    pub fn api_token_service(&self) -> Arc<dyn api_tokens::ApiTokenServiceTrait> {
+        Arc::clone(&self.services().api_token_service)
+    }
+
+    pub fn automation_service(&self) -> Arc<dyn automations::AutomationServiceTrait> {
+        Arc::clone(&self.services().automation_service)
     }
 
     pub fn fx_service(&self) -> Arc<dyn fx::FxServiceTrait> {
-        Arc::clone(&self.fx_service)
+        Arc::clone(&self.services().fx_service)
     }
 
     pub fn performance_service(&self) -> Arc<dyn portfolio::performance::PerformanceServiceTrait> {
-        Arc::clone(&self.performance_service)
+        Arc::clone(&self.services().performance_service)
     }
 
     pub fn income_service(&self) -> Arc<dyn portfolio::income::IncomeServiceTrait> {
-        Arc::clone(&self.income_service)
+        Arc::clone(&self.services().income_service)
     }
 
     pub fn snapshot_service(&self) -> Arc<dyn portfolio::snapshot::SnapshotServiceTrait> {
-        Arc::clone(&self.snapshot_service)
+        Arc::clone(&self.services().snapshot_service)
     }
 
     pub fn holdings_service(&self) -> Arc<dyn portfolio::holdings::HoldingsServiceTrait> {
-        Arc::clone(&self.holdings_service)
+        Arc::clone(&self.services().holdings_service)
     }
 
     pub fn valuation_service(&self) -> Arc<dyn portfolio::valuation::ValuationServiceTrait> {
-        Arc::clone(&self.valuation_service)
+        Arc::clone(&self.services().valuation_service)
+    }
+
+    pub fn benchmark_service(&self) -> Arc<dyn benchmarks::BenchmarkServiceTrait> {
+        Arc::clone(&self.services().benchmark_service)
     }
 
     pub fn vn_assets_sync_service(&self) -> Arc<VnAssetsSyncService> {
-        Arc::clone(&self.vn_assets_sync_service)
+        Arc::clone(&self.services().vn_assets_sync_service)
+    }
+
+    pub fn query_cache(&self) -> Arc<QueryCache> {
+        Arc::clone(&self.services().query_cache)
+    }
+
+    /// Which of the active profile's background subsystems have come up
+    pub fn service_readiness(&self) -> telemetry::ServiceReadiness {
+        let services = self.services();
+        telemetry::ServiceReadiness {
+            market_data: services.market_data_service.providers_ready(),
+            valuation: services.valuations_ready.load(Ordering::Acquire),
+        }
+    }
+
+    /// Records that valuations are up to date; true only the first time for this profile
+    pub fn mark_valuations_ready(&self) -> bool {
+        !self.services().valuations_ready.swap(true, Ordering::AcqRel)
+    }
+
+    pub fn app_lock_service(&self) -> Arc<dyn app_lock::AppLockServiceTrait> {
+        Arc::clone(&self.app_lock_service)
+    }
+
+    pub fn operations(&self) -> Arc<OperationRegistry> {
+        Arc::clone(&self.operations)
     }
 }
@@ -1,12 +1,20 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tauri::Emitter;
+use wealthvn_core::automations::AutomationRunReport;
+use wealthvn_core::data_transfer::TransferProgress;
+use wealthvn_core::notifications::Notification;
+use wealthvn_core::scripting::ScriptRunReport;
+use wealthvn_core::telemetry::ServiceReadiness;
 
 pub const PORTFOLIO_TOTAL_ACCOUNT_ID: &str = "TOTAL";
 
 /// Event emitted when core context/services are ready to use.
 pub const APP_READY: &str = "app:ready";
 
+/// Event emitted each time a subsystem started after APP_READY (market data, valuation) comes up.
+pub const SERVICE_READY: &str = "service:ready";
+
 /// Event requesting a portfolio update, which may include market data sync and recalculation.
 pub const PORTFOLIO_TRIGGER_UPDATE: &str = "portfolio:trigger-update";
 
@@ -31,9 +39,30 @@ pub const MARKET_SYNC_COMPLETE: &str = "market:sync-complete";
 /// Event emitted when the market data sync process encounters an error.
 pub const MARKET_SYNC_ERROR: &str = "market:sync-error";
 
+/// Event emitted when stored quotes change, once a market data sync has written them.
+pub const QUOTES_UPDATED: &str = "quotes:updated";
+
+/// Event emitted when the app locks itself after the inactivity timeout.
+pub const APP_LOCKED: &str = "app:locked";
+
+/// Event emitted for each notification a user script sends with `notify`.
+pub const SCRIPT_NOTIFICATION: &str = "script:notification";
+
+/// Event emitted for each notification an automation rule sends with a NOTIFY action.
+pub const AUTOMATION_NOTIFICATION: &str = "automation:notification";
+
+/// Event emitted for each maturing term deposit that would earn more at another bank.
+pub const DEPOSIT_RATE_NOTIFICATION: &str = "deposit-rate:notification";
+
+/// Event emitted for each ESOP vesting date coming up within the reminder window.
+pub const ESOP_VESTING_NOTIFICATION: &str = "esop-vesting:notification";
+
 /// Event emitted whenever an application resource changes (account, activity, etc.).
 pub const RESOURCE_CHANGED: &str = "resource:changed";
 
+/// Event emitted as a streaming export or import gets through its rows.
+pub const TRANSFER_PROGRESS: &str = "transfer:progress";
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct ResourceEventPayload {
     pub resource_type: String,
@@ -56,6 +85,14 @@ impl ResourceEventPayload {
     }
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceReadyPayload {
+    /// The subsystem that just came up, e.g. `marketData`
+    pub service: String,
+    pub readiness: ServiceReadiness,
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, Default)]
 pub struct PortfolioRequestPayload {
     /// Optional list of account IDs. None implies all/total accounts.
@@ -65,6 +102,12 @@ pub struct PortfolioRequestPayload {
     /// If syncing, specifies whether to refetch all symbols.
     #[serde(default)]
     pub refetch_all_market_data: bool,
+    /// If refetching, the id the resync runs under so it can be cancelled
+    #[serde(default)]
+    pub operation_id: Option<String>,
+    /// If refetching, seconds after which the resync gives up
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
 }
 
 impl PortfolioRequestPayload {
@@ -80,6 +123,8 @@ pub struct PortfolioRequestPayloadBuilder {
     account_ids: Option<Vec<String>>,
     symbols: Option<Vec<String>>,
     refetch_all_market_data: Option<bool>,
+    operation_id: Option<String>,
+    timeout_secs: Option<u64>,
 }
 
 impl PortfolioRequestPayloadBuilder {
@@ -109,12 +154,21 @@ impl PortfolioRequestPayloadBuilder {
         self
     }
 
+    /// Sets the cancellable operation a refetch runs as, and its timeout.
+    pub fn operation(mut self, operation_id: Option<String>, timeout_secs: Option<u64>) -> Self {
+        self.operation_id = operation_id;
+        self.timeout_secs = timeout_secs;
+        self
+    }
+
     /// Builds the PortfolioRequestPayload.
     pub fn build(self) -> PortfolioRequestPayload {
         PortfolioRequestPayload {
             account_ids: self.account_ids,
             symbols: self.symbols,
             refetch_all_market_data: self.refetch_all_market_data.unwrap_or(false),
+            operation_id: self.operation_id,
+            timeout_secs: self.timeout_secs,
         }
     }
 }
@@ -161,9 +215,128 @@ pub fn emit_resource_changed(handle: &tauri::AppHandle, payload: ResourceEventPa
     });
 }
 
+pub fn emit_transfer_progress(handle: &tauri::AppHandle, progress: &TransferProgress) {
+    handle
+        .emit(TRANSFER_PROGRESS, progress)
+        .unwrap_or_else(|e| {
+            log::error!(
+                "Failed to emit {} event for {} {}: {}",
+                TRANSFER_PROGRESS,
+                progress.dataset,
+                progress.processed,
+                e
+            )
+        });
+}
+
+/// Symbols whose quotes changed; `None` when every symbol was synced
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct QuotesUpdatedPayload {
+    pub symbols: Option<Vec<String>>,
+}
+
+/// Emits the QUOTES_UPDATED event so open charts and holdings reload their prices.
+pub fn emit_quotes_updated(handle: &tauri::AppHandle, symbols: Option<Vec<String>>) {
+    let payload = QuotesUpdatedPayload { symbols };
+    handle.emit(QUOTES_UPDATED, &payload).unwrap_or_else(|e| {
+        log::error!("Failed to emit {} event: {}", QUOTES_UPDATED, e);
+    });
+}
+
 /// Emits the APP_READY event once the ServiceContext has been initialized.
 pub fn emit_app_ready(handle: &tauri::AppHandle) {
     handle.emit(APP_READY, &()).unwrap_or_else(|e| {
         log::error!("Failed to emit {} event: {}", APP_READY, e);
     });
 }
+
+/// Emits the SERVICE_READY event with the readiness of every subsystem as of now.
+pub fn emit_service_ready(handle: &tauri::AppHandle, service: &str, readiness: ServiceReadiness) {
+    let payload = ServiceReadyPayload {
+        service: service.to_string(),
+        readiness,
+    };
+    handle.emit(SERVICE_READY, &payload).unwrap_or_else(|e| {
+        log::error!(
+            "Failed to emit {} event for {}: {}",
+            SERVICE_READY,
+            service,
+            e
+        );
+    });
+}
+
+/// Emits the APP_LOCKED event so the frontend can show the lock screen.
+pub fn emit_app_locked(handle: &tauri::AppHandle) {
+    handle.emit(APP_LOCKED, &()).unwrap_or_else(|e| {
+        log::error!("Failed to emit {} event: {}", APP_LOCKED, e);
+    });
+}
+
+/// Emits each notification scripts sent and logs the scripts that failed.
+pub fn emit_script_report(handle: &tauri::AppHandle, event: &str, report: &ScriptRunReport) {
+    for notification in &report.notifications {
+        handle
+            .emit(SCRIPT_NOTIFICATION, notification)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to emit {} event: {}", SCRIPT_NOTIFICATION, e);
+            });
+    }
+    for line in &report.logs {
+        log::debug!("{} script log: {}", event, line);
+    }
+    for failure in &report.failures {
+        log::warn!(
+            "{} script '{}' failed: {}",
+            event,
+            failure.script_name,
+            failure.message
+        );
+    }
+}
+
+/// Emits each notification automation rules sent and logs the actions that failed.
+pub fn emit_automation_report(
+    handle: &tauri::AppHandle,
+    trigger: &str,
+    report: &AutomationRunReport,
+) {
+    for notification in &report.notifications {
+        handle
+            .emit(AUTOMATION_NOTIFICATION, notification)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to emit {} event: {}", AUTOMATION_NOTIFICATION, e);
+            });
+    }
+    for failure in &report.failures {
+        log::warn!(
+            "{} rule '{}' failed at {}: {}",
+            trigger,
+            failure.rule_name,
+            failure.action,
+            failure.message
+        );
+    }
+}
+
+/// Emits a notification for each maturing deposit worth rolling over elsewhere.
+pub fn emit_deposit_rate_notifications(handle: &tauri::AppHandle, notifications: &[Notification]) {
+    for notification in notifications {
+        handle
+            .emit(DEPOSIT_RATE_NOTIFICATION, notification)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to emit {} event: {}", DEPOSIT_RATE_NOTIFICATION, e);
+            });
+    }
+}
+
+/// Emits a reminder for each ESOP vesting date coming up.
+pub fn emit_esop_vesting_notifications(handle: &tauri::AppHandle, notifications: &[Notification]) {
+    for notification in notifications {
+        handle
+            .emit(ESOP_VESTING_NOTIFICATION, notification)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to emit {} event: {}", ESOP_VESTING_NOTIFICATION, e);
+            });
+    }
+}
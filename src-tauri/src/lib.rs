@@ -20,11 +20,70 @@ use dotenvy::dotenv;
 use std::env;
 use std::sync::Arc;
 
+use tauri::ipc::Invoke;
 use tauri::AppHandle;
 use tauri::Manager;
+use tauri::Runtime;
 
 use context::ServiceContext;
-use events::{emit_app_ready, emit_portfolio_trigger_update, PortfolioRequestPayload};
+use events::{
+    emit_app_locked, emit_app_ready, emit_portfolio_trigger_update, PortfolioRequestPayload,
+};
+use wealthvn_core::app_lock::APP_LOCK_ALLOWED_COMMANDS;
+use wealthvn_core::profiles::{is_read_only_command, ReadOnlyViolation};
+
+/// Returns true when the app lock is engaged and this command is not on the allow-list.
+/// Every allowed invocation counts as user activity for the auto-lock timer.
+fn is_blocked_by_app_lock<R: Runtime>(invoke: &Invoke<R>) -> bool {
+    let command = invoke.message.command();
+    if APP_LOCK_ALLOWED_COMMANDS.contains(&command) {
+        return false;
+    }
+    // Context is not managed yet during early startup; nothing to guard then.
+    match invoke.message.webview().try_state::<Arc<ServiceContext>>() {
+        Some(context) => context.app_lock_service().record_activity(),
+        None => false,
+    }
+}
+
+/// Wraps the command handler so every command passes the app lock check first.
+fn with_app_lock<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        if is_blocked_by_app_lock(&invoke) {
+            invoke.resolver.reject("App is locked");
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+/// Wraps the command handler so write commands are rejected while the active profile
+/// is in read-only (viewer) mode. The rejection is a structured `ReadOnlyViolation`.
+fn with_read_only_guard<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let command = invoke.message.command().to_string();
+        if !is_read_only_command(&command) {
+            let read_only = invoke
+                .message
+                .webview()
+                .try_state::<Arc<ServiceContext>>()
+                .is_some_and(|context| context.profile_manager().is_read_only());
+            if read_only {
+                invoke.resolver.reject(ReadOnlyViolation::new(&command));
+                return true;
+            }
+        }
+        handler(invoke)
+    }
+}
 
 /// Spawns background tasks such as menu setup, update checks, and initial portfolio update.
 fn spawn_background_tasks(
@@ -86,7 +145,103 @@ fn spawn_background_tasks(
         }
     });
 
-    // Trigger initial portfolio update on startup
+    // Periodically engage the app lock once the inactivity timeout passes
+    let lock_handle = handle.clone();
+    let lock_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(30));
+        loop {
+            interval.tick().await;
+            if lock_context.app_lock_service().check_auto_lock() {
+                emit_app_locked(&lock_handle);
+            }
+        }
+    });
+
+    // Pull bank connections whose interval has passed; each connection has its own interval
+    let bank_handle = handle.clone();
+    let bank_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            commands::bank_connections::sync_due_bank_connections(&bank_context, &bank_handle)
+                .await;
+        }
+    });
+
+    // Push Google Sheets exports whose interval has passed
+    let sheets_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            commands::sheet_exports::push_due_sheet_exports(&sheets_context).await;
+        }
+    });
+
+    // Refresh bank deposit rates once a day and flag maturing deposits worth moving
+    let rates_handle = handle.clone();
+    let rates_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            commands::deposit_rate::check_deposit_rates(&rates_context, &rates_handle).await;
+        }
+    });
+
+    // Requote bonds as their accrued interest grows; a run replaces the quote of the same day
+    let bonds_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            commands::bond::refresh_bond_quotes(&bonds_context).await;
+        }
+    });
+
+    // Post recurring activities as they fall due; the first tick catches up on the days the
+    // app was closed
+    let recurring_handle = handle.clone();
+    let recurring_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            commands::recurring_rule::materialize_recurring_rules(
+                &recurring_context,
+                &recurring_handle,
+            )
+            .await;
+        }
+    });
+
+    // Requote ESOP grants as shares vest and remind about vesting dates coming up
+    let esop_handle = handle.clone();
+    let esop_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            commands::esop::check_esop_vesting(&esop_context, &esop_handle).await;
+        }
+    });
+
+    // Store daily goal progress for the days the history is missing, such as after midnight
+    let goals_context = context.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+        loop {
+            interval.tick().await;
+            commands::goal::record_goal_progress(&goals_context).await;
+        }
+    });
+
+    // Configure market data providers without holding up the window
+    listeners::spawn_service_warm_up(handle.clone(), context);
+
+    // Trigger initial portfolio update on startup; its market sync waits for the providers
     let initial_payload = PortfolioRequestPayload::builder()
         .account_ids(None)
         .refetch_all_market_data(false)
@@ -112,6 +267,9 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .setup(move |app| {
+            // Core services emit tracing spans; time them when performance metrics are enabled
+            wealthvn_core::telemetry::install_metrics_subscriber();
+
             // Only initialize desktop-only plugins on non-mobile platforms
             #[cfg(not(any(target_os = "android", target_os = "ios")))]
             {
@@ -160,7 +318,7 @@ pub fn run() {
                     handle.manage(context.clone());
 
                     // Spawn background non-critical tasks
-                    let instance_id = context.instance_id.clone();
+                    let instance_id = context.instance_id();
                     spawn_background_tasks(handle.clone(), context.clone(), instance_id);
 
                     // Optionally notify frontend that the app is ready
@@ -186,7 +344,7 @@ pub fn run() {
                             let ctx = Arc::new(ctx);
                             handle_clone.manage(ctx.clone());
                             // Spawn background non-critical tasks
-                            let instance_id = ctx.instance_id.clone();
+                            let instance_id = ctx.instance_id();
                             spawn_background_tasks(handle_clone.clone(), ctx.clone(), instance_id);
                             // Signal readiness to the frontend
                             emit_app_ready(&handle_clone);
@@ -203,12 +361,17 @@ pub fn run() {
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(with_app_lock(with_read_only_guard(tauri::generate_handler![
             commands::account::get_accounts,
             commands::account::get_active_accounts,
             commands::account::create_account,
             commands::account::update_account,
             commands::account::delete_account,
+            commands::account::get_account_groups,
+            commands::account::create_account_group,
+            commands::account::update_account_group,
+            commands::account::delete_account_group,
+            commands::account::get_account_group_valuation,
             commands::activity::search_activities,
             commands::activity::get_activities,
             commands::activity::create_activity,
@@ -217,11 +380,26 @@ pub fn run() {
             commands::activity::delete_activity,
             commands::activity::check_activities_import,
             commands::activity::import_activities,
+            commands::activity::get_pending_settlements,
             commands::activity::get_account_import_mapping,
             commands::activity::save_account_import_mapping,
+            commands::import_payload::preview_import_payload,
+            commands::import_payload::import_payload,
+            commands::import_payload::preview_einvoice_import,
+            commands::import_payload::import_einvoice,
+            commands::import_payload::preview_activity_import,
+            commands::import_payload::commit_activity_import,
+            commands::ledger::export_ledger,
+            commands::ledger::export_ledger_to_file,
+            commands::data_transfer::export_data_to_file,
             commands::settings::get_settings,
             commands::settings::is_auto_update_check_enabled,
             commands::settings::update_settings,
+            commands::settings::get_setting,
+            commands::settings::set_setting,
+            commands::settings::export_settings,
+            commands::settings::import_settings,
+            commands::settings::get_message_catalog,
             commands::settings::get_latest_exchange_rates,
             commands::settings::update_exchange_rate,
             commands::settings::add_exchange_rate,
@@ -229,22 +407,49 @@ pub fn run() {
             commands::goal::create_goal,
             commands::goal::update_goal,
             commands::goal::delete_goal,
+            commands::goal::archive_goal,
+            commands::goal::restore_goal,
             commands::goal::get_goals,
             commands::goal::update_goal_allocations,
+            commands::goal::bulk_insert_allocations,
             commands::goal::load_goals_allocations,
+            commands::goal::get_emergency_fund_progress,
+            commands::goal::get_sinking_fund_progress,
+            commands::goal::get_net_worth_goal_progress,
+            commands::goal::get_debt_payoff_progress,
+            commands::goal::get_net_worth_history,
+            commands::goal::get_dashboard_summary,
+            commands::goal::get_monthly_summaries,
+            commands::goal::get_goal_progress,
+            commands::goal::get_goal_progress_history,
+            commands::goal::suggest_goal_rebalance,
+            commands::goal::distribute_goal_deposit,
+            commands::goal::preview_allocation_change,
+            commands::goal::get_goal_funding_requirement,
+            commands::goal::export_goal_allocations,
+            commands::goal::import_goal_allocations,
             commands::goal::validate_allocation_conflict,
             commands::goal::delete_goal_allocation,
             commands::goal::get_unallocated_balance,
+            commands::goal::get_unallocated_cash,
+            commands::goal::get_account_group_unallocated_balance,
+            commands::goal::allocate_goal_to_account_group,
             commands::goal::validate_allocation_percentages,
             commands::goal::get_allocation_versions,
             commands::portfolio::get_holdings,
             commands::portfolio::get_holding,
             commands::portfolio::get_income_summary,
             commands::portfolio::get_historical_valuations,
+            commands::portfolio::get_account_valuation_history,
             commands::portfolio::get_latest_valuations,
             commands::portfolio::calculate_accounts_simple_performance,
             commands::portfolio::update_portfolio,
             commands::portfolio::recalculate_portfolio,
+            commands::portfolio::recompute_valuations,
+            commands::benchmark::get_benchmarks,
+            commands::benchmark::create_benchmark,
+            commands::benchmark::delete_benchmark,
+            commands::benchmark::compare_to_benchmark,
             commands::portfolio::calculate_performance_summary,
             commands::portfolio::calculate_performance_history,
             commands::limits::get_contribution_limits,
@@ -252,6 +457,156 @@ pub fn run() {
             commands::limits::update_contribution_limit,
             commands::limits::delete_contribution_limit,
             commands::limits::calculate_deposits_for_contribution_limit,
+            commands::cash::get_account_cash,
+            commands::cash::deposit_cash,
+            commands::cash::withdraw_cash,
+            commands::budget::get_budget_categories,
+            commands::budget::create_budget_category,
+            commands::budget::update_budget_category,
+            commands::budget::delete_budget_category,
+            commands::budget::get_budget_month_amounts,
+            commands::budget::set_budget_month_amount,
+            commands::budget::categorize_activities,
+            commands::budget::get_activity_categories,
+            commands::budget::get_budget_progress,
+            commands::budget::get_budget_report,
+            commands::categorization::get_categorization_rules,
+            commands::categorization::create_categorization_rule,
+            commands::categorization::update_categorization_rule,
+            commands::categorization::delete_categorization_rule,
+            commands::categorization::auto_categorize_activities,
+            commands::categorization::recategorize_all_activities,
+            commands::forecast::get_planned_cash_flows,
+            commands::forecast::create_planned_cash_flow,
+            commands::forecast::update_planned_cash_flow,
+            commands::forecast::delete_planned_cash_flow,
+            commands::forecast::get_cash_flow_forecast,
+            commands::income_source::get_income_sources,
+            commands::income_source::create_income_source,
+            commands::income_source::update_income_source,
+            commands::income_source::delete_income_source,
+            commands::income_source::get_income_variance,
+            commands::income_source::get_savings_rate,
+            commands::bill::get_bills,
+            commands::bill::create_bill,
+            commands::bill::update_bill,
+            commands::bill::delete_bill,
+            commands::bill::get_bill_payments,
+            commands::bill::mark_bill_paid,
+            commands::bill::delete_bill_payment,
+            commands::bill::get_bill_reminders,
+            commands::bill::match_bill_payments,
+            commands::envelope::get_account_envelopes,
+            commands::envelope::create_envelope,
+            commands::envelope::rename_envelope,
+            commands::envelope::archive_envelope,
+            commands::envelope::get_envelope_transfers,
+            commands::envelope::transfer_envelope_funds,
+            commands::loan::get_loans,
+            commands::loan::get_loan_schedule,
+            commands::loan::stress_test_loan,
+            commands::loan::create_loan,
+            commands::loan::update_loan,
+            commands::loan::delete_loan,
+            commands::loan::get_loan_prepayments,
+            commands::loan::add_loan_prepayment,
+            commands::loan::delete_loan_prepayment,
+            commands::loan::get_loan_rate_resets,
+            commands::loan::add_loan_rate_reset,
+            commands::loan::delete_loan_rate_reset,
+            commands::property::get_properties,
+            commands::property::get_property,
+            commands::property::create_property,
+            commands::property::update_property,
+            commands::property::delete_property,
+            commands::property::get_property_appraisals,
+            commands::property::add_property_appraisal,
+            commands::property::delete_property_appraisal,
+            commands::bonus_plan::get_bonus_plans,
+            commands::bonus_plan::get_bonus_plan,
+            commands::bonus_plan::propose_bonus_plan,
+            commands::bonus_plan::confirm_bonus_plan,
+            commands::bonus_plan::fund_bonus_plan_envelopes,
+            commands::bonus_plan::delete_bonus_plan,
+            commands::bond::get_bonds,
+            commands::bond::get_bond,
+            commands::bond::create_bond,
+            commands::bond::update_bond,
+            commands::bond::delete_bond,
+            commands::bond::get_bond_payments,
+            commands::term_deposit::get_term_deposits,
+            commands::term_deposit::get_term_deposit,
+            commands::term_deposit::create_term_deposit,
+            commands::term_deposit::update_term_deposit,
+            commands::term_deposit::delete_term_deposit,
+            commands::term_deposit::get_upcoming_maturities,
+            commands::recurring_rule::list_recurring_rules,
+            commands::recurring_rule::create_recurring_rule,
+            commands::recurring_rule::pause_recurring_rule,
+            commands::recurring_rule::delete_recurring_rule,
+            commands::report::get_net_worth_statement,
+            commands::report::get_net_worth_statement_history,
+            commands::dividend::get_dividend_summary,
+            commands::dividend::get_activity_dividend,
+            commands::dividend::save_activity_dividend,
+            commands::dividend::delete_activity_dividend,
+            commands::esop::get_esop_grants,
+            commands::esop::get_esop_grant,
+            commands::esop::create_esop_grant,
+            commands::esop::update_esop_grant,
+            commands::esop::delete_esop_grant,
+            commands::esop::get_upcoming_vestings,
+            commands::sip_plan::get_sip_plans,
+            commands::sip_plan::get_sip_plan,
+            commands::sip_plan::create_sip_plan,
+            commands::sip_plan::update_sip_plan,
+            commands::sip_plan::delete_sip_plan,
+            commands::private_loan::get_private_loans,
+            commands::private_loan::get_private_loan,
+            commands::private_loan::create_private_loan,
+            commands::private_loan::update_private_loan,
+            commands::private_loan::delete_private_loan,
+            commands::private_loan::record_private_loan_repayment,
+            commands::private_loan::delete_private_loan_repayment,
+            commands::private_loan::match_private_loan_repayments,
+            commands::deposit_rate::get_deposit_rates,
+            commands::deposit_rate::refresh_deposit_rates,
+            commands::deposit_rate::get_rollover_suggestions,
+            commands::deposit_rate::propose_deposit_ladder,
+            commands::deposit_rate::confirm_deposit_ladder,
+            commands::pension::estimate_pension,
+            commands::education::get_education_plans,
+            commands::education::create_education_plan,
+            commands::education::update_education_plan,
+            commands::education::delete_education_plan,
+            commands::education::project_education_costs,
+            commands::education::link_education_goal,
+            commands::scripts::get_scripts,
+            commands::scripts::create_script,
+            commands::scripts::update_script,
+            commands::scripts::delete_script,
+            commands::bank_connections::get_bank_connections,
+            commands::bank_connections::create_bank_connection,
+            commands::bank_connections::update_bank_connection,
+            commands::bank_connections::delete_bank_connection,
+            commands::bank_connections::sync_bank_connection,
+            commands::sheet_exports::get_sheet_exports,
+            commands::sheet_exports::create_sheet_export,
+            commands::sheet_exports::update_sheet_export,
+            commands::sheet_exports::delete_sheet_export,
+            commands::sheet_exports::push_sheet_export,
+            commands::sheet_exports::has_google_sheets_credentials,
+            commands::sheet_exports::set_google_sheets_credentials,
+            commands::sheet_exports::delete_google_sheets_credentials,
+            commands::api_tokens::get_api_tokens,
+            commands::api_tokens::create_api_token,
+            commands::api_tokens::revoke_api_token,
+            commands::api_tokens::delete_api_token,
+            commands::automations::get_automation_rules,
+            commands::automations::create_automation_rule,
+            commands::automations::update_automation_rule,
+            commands::automations::delete_automation_rule,
+            commands::automations::get_activity_tags,
             commands::utilities::get_app_info,
             commands::utilities::backup_database,
             commands::utilities::backup_database_to_path,
@@ -277,6 +632,10 @@ pub fn run() {
             commands::secrets::delete_secret,
             commands::providers_settings::get_market_data_providers_settings,
             commands::providers_settings::update_market_data_provider_settings,
+            commands::providers_settings::get_provider_credential_statuses,
+            commands::providers_settings::set_provider_credential,
+            commands::providers_settings::test_provider_credential,
+            commands::providers_settings::remove_provider_credential,
             commands::addon::extract_addon_zip,
             commands::addon::install_addon_zip,
             commands::addon::list_installed_addons,
@@ -292,7 +651,30 @@ pub fn run() {
             commands::addon::install_addon_from_staging,
             commands::addon::clear_addon_staging,
             commands::addon::submit_addon_rating,
-        ])
+            commands::app_lock::get_app_lock_status,
+            commands::app_lock::unlock_app,
+            commands::app_lock::lock_app,
+            commands::app_lock::set_app_lock_passphrase,
+            commands::app_lock::remove_app_lock_passphrase,
+            commands::app_lock::set_app_lock_timeout,
+            commands::profile::list_profiles,
+            commands::profile::get_active_profile,
+            commands::profile::create_profile,
+            commands::profile::rename_profile,
+            commands::profile::delete_profile,
+            commands::profile::switch_profile,
+            commands::profile::create_demo_profile,
+            commands::profile::set_profile_read_only,
+            commands::audit::query_audit_log,
+            commands::feature_flags::get_feature_flags,
+            commands::feature_flags::set_feature_flag,
+            commands::telemetry::get_performance_stats,
+            commands::telemetry::reset_performance_stats,
+            commands::telemetry::get_service_readiness,
+            commands::operations::list_operations,
+            commands::operations::cancel_operation,
+            commands::onboarding::seed_initial_data,
+        ])))
         .build(tauri::generate_context!())
         .expect("error while running WealthVN application");
 
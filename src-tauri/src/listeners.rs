@@ -3,13 +3,20 @@ use log::{debug, error, info, warn};
 use serde::Serialize;
 use std::collections::HashSet;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tauri::{async_runtime::spawn, AppHandle, Emitter, Listener, Manager};
+use chrono::Utc;
 use wealthvn_core::constants::PORTFOLIO_TOTAL_ACCOUNT_ID;
+use wealthvn_core::goals::refresh_goal_progress;
+use wealthvn_core::operations::OPERATION_MARKET_DATA_RESYNC;
+use wealthvn_core::query_cache::RESOURCE_PORTFOLIO;
+use wealthvn_core::telemetry::{SERVICE_MARKET_DATA, SERVICE_VALUATION};
 
+use crate::commands::automations::{run_goal_progress_automations, run_quote_automations};
+use crate::commands::scripts::{run_goal_progress_scripts, run_quote_update_scripts};
 use crate::context::ServiceContext;
 use crate::events::{
-    emit_portfolio_trigger_recalculate, emit_portfolio_trigger_update, PortfolioRequestPayload,
+    emit_automation_report, emit_portfolio_trigger_recalculate, emit_quotes_updated, emit_script_report, emit_portfolio_trigger_update, emit_resource_changed, emit_service_ready, PortfolioRequestPayload,
     ResourceEventPayload, MARKET_SYNC_COMPLETE, MARKET_SYNC_ERROR, MARKET_SYNC_START,
     PORTFOLIO_TRIGGER_RECALCULATE, PORTFOLIO_TRIGGER_UPDATE, PORTFOLIO_UPDATE_COMPLETE,
     PORTFOLIO_UPDATE_ERROR, PORTFOLIO_UPDATE_START, RESOURCE_CHANGED,
@@ -35,6 +42,21 @@ pub fn setup_event_listeners(handle: AppHandle) {
     });
 }
 
+/// Brings up the subsystems left out of context initialization so the window can show cached
+/// data first. Called at startup and after every profile switch, since that rebuilds them.
+pub fn spawn_service_warm_up(handle: AppHandle, context: Arc<ServiceContext>) {
+    spawn(async move {
+        let started = Instant::now();
+        let market_data_service = context.market_data_service();
+        if let Err(e) = market_data_service.initialize_providers().await {
+            warn!("Failed to initialize market data providers: {}", e);
+            return;
+        }
+        info!("Market data providers ready in {:?}", started.elapsed());
+        emit_service_ready(&handle, SERVICE_MARKET_DATA, context.service_readiness());
+    });
+}
+
 /// Handles the common logic for both portfolio update and recalculation requests.
 fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc: bool) {
     debug!(
@@ -56,6 +78,8 @@ fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc:
                 let symbols_to_sync = payload.symbols.clone(); // None means sync all relevant symbols
                 let accounts_to_recalc = payload.account_ids.clone();
                 let refetch_all = payload.refetch_all_market_data;
+                // A plain sync covers every symbol, whatever the request named
+                let updated_symbols = symbols_to_sync.clone().filter(|_| refetch_all);
                 let context_result = handle_clone.try_state::<Arc<ServiceContext>>();
 
                 if let Some(context) = context_result {
@@ -67,10 +91,22 @@ fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc:
                     }
 
                     let sync_start = Instant::now();
+                    // A refetch can reach back decades, so it runs as a cancellable operation
                     let sync_result = if refetch_all {
-                        market_data_service
-                            .resync_market_data(symbols_to_sync)
-                            .await
+                        let timeout = payload.timeout_secs.map(Duration::from_secs);
+                        match context.operations().start(
+                            payload.operation_id.clone(),
+                            OPERATION_MARKET_DATA_RESYNC,
+                            timeout,
+                        ) {
+                            Ok(operation) => {
+                                operation
+                                    .token()
+                                    .run(market_data_service.resync_market_data(symbols_to_sync))
+                                    .await
+                            }
+                            Err(e) => Err(e),
+                        }
                     } else {
                         market_data_service.sync_market_data().await
                     };
@@ -84,6 +120,7 @@ fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc:
                             {
                                 error!("Failed to emit market:sync-complete event: {}", e);
                             }
+                            emit_quotes_updated(&handle_clone, updated_symbols);
                             // Initialize the FxService after successful sync
                             let fx_service = context.fx_service();
                             if let Err(e) = fx_service.initialize() {
@@ -93,6 +130,19 @@ fn handle_portfolio_request(handle: AppHandle, payload_str: &str, force_recalc:
                                 );
                             }
 
+                            match run_quote_update_scripts(&context) {
+                                Ok(report) => {
+                                    emit_script_report(&handle_clone, "on_quote_update", &report)
+                                }
+                                Err(e) => error!("on_quote_update scripts failed: {}", e),
+                            }
+                            match run_quote_automations(&context).await {
+                                Ok(report) => {
+                                    emit_automation_report(&handle_clone, "QUOTE_MOVED", &report)
+                                }
+                                Err(e) => error!("QUOTE_MOVED rules failed: {}", e),
+                            }
+
                             // Trigger calculation after successful sync
                             handle_portfolio_calculation(
                                 handle_clone.clone(), // Clone again for this call
@@ -142,9 +192,13 @@ fn handle_resource_change(handle: AppHandle, payload_str: &str) {
 
     match serde_json::from_str::<ResourceEventPayload>(payload_str) {
         Ok(event) => {
+            if let Some(context) = handle.try_state::<Arc<ServiceContext>>() {
+                context.query_cache().invalidate(&event.resource_type);
+            }
             match event.resource_type.as_str() {
                 "account" => handle_account_resource_change(handle.clone(), &event),
                 "activity" => handle_activity_resource_change(handle.clone(), &event),
+                "allocation" => handle_allocation_resource_change(handle.clone()),
                 _ => {
                     // Default to a lightweight portfolio update when resource type is unknown
                     emit_portfolio_trigger_update(
@@ -161,6 +215,20 @@ fn handle_resource_change(handle: AppHandle, payload_str: &str) {
     }
 }
 
+/// Allocations changed, possibly behind the goal service's back, so its cache is stale
+fn handle_allocation_resource_change(handle: AppHandle) {
+    if let Some(context) = handle.try_state::<Arc<ServiceContext>>() {
+        context.goal_service().invalidate_allocation_cache();
+    }
+    emit_portfolio_trigger_update(
+        &handle,
+        PortfolioRequestPayload::builder()
+            .account_ids(None)
+            .symbols(None)
+            .build(),
+    );
+}
+
 fn handle_account_resource_change(handle: AppHandle, event: &ResourceEventPayload) {
     let account_id = event
         .payload
@@ -447,9 +515,50 @@ fn handle_portfolio_calculation(
             }
         }
 
+        // Store today's goal progress now, so the goals screen only has to read it, and mark
+        // the goals it shows reaching their target
+        let today = Utc::now().date_naive();
+        match refresh_goal_progress(
+            context.goal_service().as_ref(),
+            context.net_worth_goal_service().as_ref(),
+            today,
+        )
+        .await
+        {
+            Ok(progress) => match context.goal_service().mark_achieved_goals(&progress, today).await {
+                Ok(achieved) => {
+                    for goal in achieved {
+                        info!("Goal '{}' reached its target", goal.title);
+                        emit_resource_changed(
+                            &app_handle,
+                            ResourceEventPayload::new("goal", "achieved", serde_json::json!(goal)),
+                        );
+                    }
+                }
+                Err(e) => error!("Failed to mark achieved goals: {}", e),
+            },
+            Err(e) => error!("Failed to refresh goal progress: {}", e),
+        }
+        // Net worth history is recorded here rather than when the statement is read
+        if let Err(e) = context.report_service().record_net_worth_history(today).await {
+            error!("Failed to record net worth history: {}", e);
+        }
+        context.query_cache().invalidate(RESOURCE_PORTFOLIO);
         if let Err(e) = app_handle.emit(PORTFOLIO_UPDATE_COMPLETE, ()) {
             error!("Failed to emit {} event: {}", PORTFOLIO_UPDATE_COMPLETE, e);
         }
+        if context.mark_valuations_ready() {
+            emit_service_ready(&app_handle, SERVICE_VALUATION, context.service_readiness());
+        }
+
+        match run_goal_progress_scripts(&context).await {
+            Ok(report) => emit_script_report(&app_handle, "on_goal_progress", &report),
+            Err(e) => error!("on_goal_progress scripts failed: {}", e),
+        }
+        match run_goal_progress_automations(&context).await {
+            Ok(report) => emit_automation_report(&app_handle, "GOAL_PROGRESS_REACHED", &report),
+            Err(e) => error!("GOAL_PROGRESS_REACHED rules failed: {}", e),
+        }
     });
 }
 
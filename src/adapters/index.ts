@@ -34,7 +34,7 @@ export {
   listenDatabaseRestoredTauri, listenFileDropCancelledTauri, listenFileDropHoverTauri,
   listenFileDropTauri, listenMarketSyncCompleteTauri,
   listenMarketSyncStartTauri,
-  listenNavigateToRouteTauri, listenPortfolioUpdateCompleteTauri, listenPortfolioUpdateErrorTauri, listenPortfolioUpdateStartTauri, openAddonZipFileDialogTauri, openCsvFileDialogTauri, openDatabaseFileDialogTauri, openFileSaveDialogTauri, openFolderDialogTauri, readBinaryFileTauri
+  listenNavigateToRouteTauri, listenPortfolioUpdateCompleteTauri, listenPortfolioUpdateErrorTauri, listenPortfolioUpdateStartTauri, listenQuotesUpdatedTauri, listenServiceReadyTauri, openAddonZipFileDialogTauri, openCsvFileDialogTauri, openDatabaseFileDialogTauri, openFileSaveDialogTauri, openFolderDialogTauri, openSavePathDialogTauri, readBinaryFileTauri
 } from "./tauri";
 
 export * from "./web";
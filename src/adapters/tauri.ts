@@ -156,18 +156,43 @@ export async function listenMarketSyncCompleteTauri<T>(
   return listen("market:sync-complete", handler);
 }
 
+export async function listenQuotesUpdatedTauri<T>(
+  handler: EventCallback<T>,
+): Promise<UnlistenFn> {
+  return listen("quotes:updated", handler);
+}
+
 export async function listenMarketSyncStartTauri<T>(
   handler: EventCallback<T>,
 ): Promise<UnlistenFn> {
   return listen("market:sync-start", handler);
 }
 
+export async function listenServiceReadyTauri<T>(
+  handler: EventCallback<T>,
+): Promise<UnlistenFn> {
+  return listen("service:ready", handler);
+}
+
 export async function listenNavigateToRouteTauri<T>(
   handler: EventCallback<T>,
 ): Promise<UnlistenFn> {
   return listen("navigate-to-route", handler);
 }
 
+/** Asks where to save `fileName` and returns the path, for files the backend writes itself */
+export const openSavePathDialogTauri = async (fileName: string): Promise<string | null> => {
+  return save({
+    defaultPath: fileName,
+    filters: [
+      {
+        name: fileName,
+        extensions: [fileName.split(".").pop() ?? ""],
+      },
+    ],
+  });
+};
+
 export const openFileSaveDialogTauri = async (
   fileContent: string | Blob | Uint8Array,
   fileName: string,
@@ -18,11 +18,19 @@ const COMMANDS: CommandMap = {
   check_update: { method: "GET", path: "/app/check-update" },
   backup_database: { method: "POST", path: "/utilities/database/backup" },
   backup_database_to_path: { method: "POST", path: "/utilities/database/backup-to-path" },
+  export_data: { method: "GET", path: "/exports/data" },
+  get_performance_stats: { method: "GET", path: "/telemetry/performance" },
+  reset_performance_stats: { method: "DELETE", path: "/telemetry/performance" },
+  get_service_readiness: { method: "GET", path: "/telemetry/readiness" },
+  list_operations: { method: "GET", path: "/operations" },
+  cancel_operation: { method: "POST", path: "/operations" },
   restore_database: { method: "POST", path: "/utilities/database/restore" },
   get_holdings: { method: "GET", path: "/holdings" },
   get_holding: { method: "GET", path: "/holdings/item" },
   get_historical_valuations: { method: "GET", path: "/valuations/history" },
   get_latest_valuations: { method: "GET", path: "/valuations/latest" },
+  get_account_valuation_history: { method: "GET", path: "/valuations/account-history" },
+  recompute_valuations: { method: "POST", path: "/valuations/recompute" },
   update_portfolio: { method: "POST", path: "/portfolio/update" },
   recalculate_portfolio: { method: "POST", path: "/portfolio/recalculate" },
   // Performance
@@ -35,11 +43,15 @@ const COMMANDS: CommandMap = {
   create_goal: { method: "POST", path: "/goals" },
   update_goal: { method: "PUT", path: "/goals" },
   delete_goal: { method: "DELETE", path: "/goals" },
+  archive_goal: { method: "POST", path: "/goals" },
+  restore_goal: { method: "POST", path: "/goals" },
   update_goal_allocations: { method: "POST", path: "/goals/allocations" },
+  bulk_insert_allocations: { method: "POST", path: "/goals/allocations/bulk" },
   load_goals_allocations: { method: "GET", path: "/goals/allocations" },
   get_goal_progress: { method: "GET", path: "/goals/{id}/progress" },
   get_goal_allocations_on_date: { method: "GET", path: "/goals/{id}/allocations-on-date" },
   validate_allocation_conflict: { method: "POST", path: "/goals/validate-allocation-conflict" },
+  get_monthly_summaries: { method: "GET", path: "/summaries/monthly" },
   // FX
   get_latest_exchange_rates: { method: "GET", path: "/exchange-rates/latest" },
   update_exchange_rate: { method: "PUT", path: "/exchange-rates" },
@@ -51,11 +63,14 @@ const COMMANDS: CommandMap = {
   update_activity: { method: "PUT", path: "/activities" },
   save_activities: { method: "POST", path: "/activities/bulk" },
   delete_activity: { method: "DELETE", path: "/activities" },
+  get_pending_settlements: { method: "GET", path: "/activities/pending-settlements" },
   // Activity import
   check_activities_import: { method: "POST", path: "/activities/import/check" },
   import_activities: { method: "POST", path: "/activities/import" },
   get_account_import_mapping: { method: "GET", path: "/activities/import/mapping" },
   save_account_import_mapping: { method: "POST", path: "/activities/import/mapping" },
+  preview_activity_import: { method: "POST", path: "/activity-imports/preview" },
+  commit_activity_import: { method: "POST", path: "/activity-imports" },
   // Market data providers
   get_market_data_providers: { method: "GET", path: "/providers" },
   get_market_data_providers_settings: { method: "GET", path: "/providers/settings" },
@@ -167,7 +182,8 @@ export const invokeWeb = async <T>(
       url += `?${params.toString()}`;
       break;
     }
-    case "get_historical_valuations": {
+    case "get_historical_valuations":
+    case "get_account_valuation_history": {
       const p = payload as { accountId?: string; startDate?: string; endDate?: string };
       const params = new URLSearchParams();
       if (p?.accountId) params.set("accountId", p.accountId);
@@ -177,6 +193,11 @@ export const invokeWeb = async <T>(
       if (qs) url += `?${qs}`;
       break;
     }
+    case "get_monthly_summaries": {
+      const p = payload as { startDate?: string };
+      if (p?.startDate) url += `?${new URLSearchParams({ startDate: p.startDate }).toString()}`;
+      break;
+    }
     case "get_latest_valuations": {
       const p = payload as { accountIds?: string[] };
       const params = new URLSearchParams();
@@ -187,6 +208,7 @@ export const invokeWeb = async <T>(
       if (qs) url += `?${qs}`;
       break;
     }
+    case "recompute_valuations":
     case "calculate_accounts_simple_performance": {
       const { accountIds } = (payload ?? {}) as { accountIds?: string[] };
       body = JSON.stringify({ accountIds });
@@ -230,11 +252,28 @@ export const invokeWeb = async <T>(
     }
     case "get_income_summary":
       break;
+    case "get_goals": {
+      const p = payload as { includeArchived?: boolean } | undefined;
+      if (p?.includeArchived) url += "?includeArchived=true";
+      break;
+    }
     case "delete_goal": {
       const { goalId } = payload as { goalId: string };
       url += `/${encodeURIComponent(goalId)}`;
       break;
     }
+    case "archive_goal":
+    case "restore_goal": {
+      const { goalId } = payload as { goalId: string };
+      const action = command === "archive_goal" ? "archive" : "restore";
+      url += `/${encodeURIComponent(goalId)}/${action}`;
+      break;
+    }
+    case "cancel_operation": {
+      const { operationId } = payload as { operationId: string };
+      url += `/${encodeURIComponent(operationId)}/cancel`;
+      break;
+    }
     case "get_goal_progress": {
       const { goalId, date } = payload as { goalId: string; date?: string };
       url += `/${encodeURIComponent(goalId)}/progress`;
@@ -271,6 +310,14 @@ export const invokeWeb = async <T>(
       body = JSON.stringify(allocations);
       break;
     }
+    case "bulk_insert_allocations": {
+      const { allocations, versions } = payload as {
+        allocations: Record<string, unknown>[];
+        versions: Record<string, unknown>[];
+      };
+      body = JSON.stringify({ allocations, versions });
+      break;
+    }
     case "update_exchange_rate": {
       const { rate } = payload as { rate: Record<string, unknown> };
       body = JSON.stringify(rate);
@@ -312,6 +359,13 @@ export const invokeWeb = async <T>(
       url += `/${encodeURIComponent(activityId)}`;
       break;
     }
+    case "get_pending_settlements": {
+      const { accountId } = payload as { accountId?: string };
+      if (accountId) {
+        url += `?accountId=${encodeURIComponent(accountId)}`;
+      }
+      break;
+    }
     case "check_activities_import":
     case "import_activities": {
       body = JSON.stringify(payload);
@@ -329,6 +383,12 @@ export const invokeWeb = async <T>(
       body = JSON.stringify({ mapping });
       break;
     }
+    case "preview_activity_import":
+    case "commit_activity_import": {
+      const { request } = payload as { request: Record<string, unknown> };
+      body = JSON.stringify(request);
+      break;
+    }
     case "update_market_data_provider_settings": {
       body = JSON.stringify(payload);
       break;
@@ -388,6 +448,21 @@ export const invokeWeb = async <T>(
       url += `?${params.toString()}`;
       break;
     }
+    case "export_data": {
+      const { dataset, format, operationId, timeoutSecs } = payload as {
+        dataset: string;
+        format: string;
+        operationId?: string;
+        timeoutSecs?: number;
+      };
+      const params = new URLSearchParams();
+      params.set("dataset", dataset);
+      params.set("format", format);
+      if (operationId) params.set("operationId", operationId);
+      if (timeoutSecs) params.set("timeoutSecs", String(timeoutSecs));
+      url += `?${params.toString()}`;
+      break;
+    }
     case "get_quote_history": {
       const { symbol } = payload as { symbol: string };
       const params = new URLSearchParams();
@@ -412,11 +487,13 @@ export const invokeWeb = async <T>(
       break;
     }
     case "import_quotes_csv": {
-      const { quotes, overwriteExisting } = payload as {
+      const { quotes, overwriteExisting, operationId, timeoutSecs } = payload as {
         quotes: unknown;
         overwriteExisting: boolean;
+        operationId?: string;
+        timeoutSecs?: number;
       };
-      body = JSON.stringify({ quotes, overwriteExisting });
+      body = JSON.stringify({ quotes, overwriteExisting, operationId, timeoutSecs });
       break;
     }
     case "sync_market_data": {
@@ -577,6 +654,12 @@ export const invokeWeb = async <T>(
       data: fromBase64(parsed.dataB64),
     } as T;
   }
+  if (command === "export_data") {
+    // The server streams the file; its name comes from Content-Disposition
+    const disposition = res.headers.get("Content-Disposition") ?? "";
+    const fileName = /filename="([^"]+)"/.exec(disposition)?.[1] ?? "export";
+    return { fileName, data: await res.blob() } as T;
+  }
   if (command === "backup_database_to_path") {
     const parsed = (await res.json()) as { path: string };
     return parsed.path as T;
@@ -742,6 +825,18 @@ export const listenMarketSyncCompleteWeb = async <T>(
   return portfolioEventBridge.listen("market:sync-complete", handler);
 };
 
+export const listenQuotesUpdatedWeb = async <T>(
+  handler: EventCallback<T>,
+): Promise<UnlistenFn> => {
+  return portfolioEventBridge.listen("quotes:updated", handler);
+};
+
+export const listenServiceReadyWeb = async <T>(
+  handler: EventCallback<T>,
+): Promise<UnlistenFn> => {
+  return portfolioEventBridge.listen("service:ready", handler);
+};
+
 // Helpers
 function toBase64(data: Uint8Array | number[]): string {
   const bytes = Array.isArray(data) ? new Uint8Array(data) : data;
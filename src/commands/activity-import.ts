@@ -1,4 +1,10 @@
-import { ActivityImport, ImportMappingData } from "@/lib/types";
+import {
+  ActivityImport,
+  ActivityImportRequest,
+  ImportMappingData,
+  ImportPayloadPreview,
+  ImportPayloadResult,
+} from "@/lib/types";
 import { getRunEnv, RUN_ENV, invokeTauri, invokeWeb } from "@/adapters";
 import { logger } from "@/adapters";
 
@@ -91,3 +97,39 @@ export const saveAccountImportMapping = async (
     throw error;
   }
 };
+
+export const previewActivityImport = async (
+  request: ActivityImportRequest,
+): Promise<ImportPayloadPreview> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("preview_activity_import", { request });
+      case RUN_ENV.WEB:
+        return invokeWeb("preview_activity_import", { request });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error previewing activity file.");
+    throw error;
+  }
+};
+
+export const commitActivityImport = async (
+  request: ActivityImportRequest,
+): Promise<ImportPayloadResult> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("commit_activity_import", { request });
+      case RUN_ENV.WEB:
+        return invokeWeb("commit_activity_import", { request });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error importing activity file.");
+    throw error;
+  }
+};
@@ -6,6 +6,7 @@ import {
   ActivityDetails,
   ActivitySearchResponse,
   ActivityUpdate,
+  PendingSettlement,
 } from "@/lib/types";
 import { getRunEnv, RUN_ENV, invokeTauri, invokeWeb, logger } from "@/adapters";
 
@@ -145,3 +146,19 @@ export const deleteActivity = async (activityId: string): Promise<Activity> => {
     throw error;
   }
 };
+
+export const getPendingSettlements = async (accountId?: string): Promise<PendingSettlement[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("get_pending_settlements", { accountId });
+      case RUN_ENV.WEB:
+        return invokeWeb("get_pending_settlements", { accountId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error fetching pending settlements.");
+    throw error;
+  }
+};
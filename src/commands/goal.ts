@@ -1,6 +1,6 @@
 import { getRunEnv, invokeTauri, invokeWeb, logger, RUN_ENV } from "@/adapters";
 import { newGoalSchema } from "@/lib/schemas";
-import { Goal, GoalAllocation } from "@/lib/types";
+import { Goal, GoalAllocation, MonthlySummaries } from "@/lib/types";
 import z from "zod";
 
 // Form schema type (uses Date for date fields)
@@ -43,6 +43,23 @@ export const getGoals = async (): Promise<Goal[]> => {
   }
 };
 
+/** Goals including archived ones */
+export const getAllGoals = async (): Promise<Goal[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("get_goals", { includeArchived: true });
+      case RUN_ENV.WEB:
+        return invokeWeb("get_goals", { includeArchived: true });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error fetching goals.");
+    throw error;
+  }
+};
+
 export const createGoal = async (goal: NewGoalInput): Promise<Goal> => {
   const newGoal = {
     ...goal,
@@ -99,6 +116,38 @@ export const deleteGoal = async (goalId: string): Promise<void> => {
   }
 };
 
+export const archiveGoal = async (goalId: string): Promise<Goal> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("archive_goal", { goalId });
+      case RUN_ENV.WEB:
+        return invokeWeb("archive_goal", { goalId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error archiving goal.");
+    throw error;
+  }
+};
+
+export const restoreGoal = async (goalId: string): Promise<Goal> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("restore_goal", { goalId });
+      case RUN_ENV.WEB:
+        return invokeWeb("restore_goal", { goalId });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error restoring goal.");
+    throw error;
+  }
+};
+
 export const updateGoalsAllocations = async (allocations: GoalAllocation[]): Promise<void> => {
   try {
     switch (getRunEnv()) {
@@ -117,6 +166,26 @@ export const updateGoalsAllocations = async (allocations: GoalAllocation[]): Pro
   }
 };
 
+/** Inserts many allocations and their versions at once; nothing is saved unless all are valid */
+export const bulkInsertAllocations = async (
+  allocations: GoalAllocation[],
+  versions: AllocationVersion[],
+): Promise<AllocationBulkInsertSummary> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("bulk_insert_allocations", { allocations, versions });
+      case RUN_ENV.WEB:
+        return invokeWeb("bulk_insert_allocations", { allocations, versions });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error bulk inserting goal allocations.");
+    throw error;
+  }
+};
+
 export const getGoalsAllocation = async (): Promise<GoalAllocation[]> => {
   try {
     let allocations: RawGoalAllocation[] = [];
@@ -144,6 +213,10 @@ export interface GoalProgressSnapshot {
   initValue: number;
   currentValue: number;
   growth: number;
+  /** Part of the growth paid in; zero for net-worth goals */
+  contributedAmount: number;
+  /** Part of the growth earned by the investments */
+  marketGrowth: number;
   allocationDetails: AllocationDetail[];
 }
 
@@ -154,6 +227,10 @@ export interface AllocationDetail {
   accountCurrentValue: number;
   accountGrowth: number;
   allocatedGrowth: number;
+  /** Share of the deposits, net of withdrawals, since the goal started */
+  contributedAmount: number;
+  /** Share of the investment returns: allocatedGrowth - contributedAmount */
+  marketGrowth: number;
 }
 
 export const getGoalProgress = async (
@@ -246,3 +323,20 @@ export const deleteGoalAllocation = async (allocationId: string): Promise<void>
     throw error;
   }
 };
+
+// Account totals and goal progress by month; the last 12 months when no start date is given
+export const getMonthlySummaries = async (startDate?: string): Promise<MonthlySummaries> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("get_monthly_summaries", { startDate });
+      case RUN_ENV.WEB:
+        return invokeWeb("get_monthly_summaries", { startDate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error fetching monthly summaries.");
+    throw error;
+  }
+};
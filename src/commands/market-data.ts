@@ -5,6 +5,7 @@ import {
   Quote,
   UpdateAssetProfile,
   MarketDataProviderInfo,
+  OperationOptions,
 } from "@/lib/types";
 import { getRunEnv, RUN_ENV, invokeTauri, invokeWeb, logger } from "@/adapters";
 
@@ -169,14 +170,18 @@ export const updateQuote = async (symbol: string, quote: Quote): Promise<void> =
   }
 };
 
-export const syncMarketData = async (symbols: string[], refetchAll: boolean): Promise<void> => {
+export const syncMarketData = async (
+  symbols: string[],
+  refetchAll: boolean,
+  options: OperationOptions = {},
+): Promise<void> => {
   try {
     switch (getRunEnv()) {
       case RUN_ENV.DESKTOP:
-        await invokeTauri("sync_market_data", { symbols, refetchAll });
+        await invokeTauri("sync_market_data", { symbols, refetchAll, ...options });
         return;
       case RUN_ENV.WEB:
-        await invokeWeb("sync_market_data", { symbols, refetchAll });
+        await invokeWeb("sync_market_data", { symbols, refetchAll, ...options });
         return;
       default:
         throw new Error(`Unsupported`);
@@ -274,13 +279,14 @@ export const updateMarketDataProviderSettings = async (payload: {
 export const importManualQuotes = async (
   quotes: QuoteImport[],
   overwriteExisting: boolean = true,
+  options: OperationOptions = {},
 ): Promise<QuoteImport[]> => {
   try {
     switch (getRunEnv()) {
       case RUN_ENV.DESKTOP:
-        return invokeTauri("import_quotes_csv", { quotes, overwriteExisting });
+        return invokeTauri("import_quotes_csv", { quotes, overwriteExisting, ...options });
       case RUN_ENV.WEB:
-        return invokeWeb("import_quotes_csv", { quotes, overwriteExisting });
+        return invokeWeb("import_quotes_csv", { quotes, overwriteExisting, ...options });
       default:
         throw new Error("Manual quote import is not supported in this environment.");
     }
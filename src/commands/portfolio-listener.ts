@@ -13,6 +13,10 @@ import {
   listenPortfolioUpdateErrorWeb,
   listenMarketSyncStartWeb,
   listenMarketSyncCompleteWeb,
+  listenServiceReadyTauri,
+  listenServiceReadyWeb,
+  listenQuotesUpdatedTauri,
+  listenQuotesUpdatedWeb,
 } from "@/adapters";
 
 // listenPortfolioUpdateStart
@@ -107,3 +111,37 @@ export const listenMarketSyncComplete = async <T>(
     throw error;
   }
 };
+
+// listenQuotesUpdated
+export const listenQuotesUpdated = async <T>(handler: EventCallback<T>): Promise<UnlistenFn> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return listenQuotesUpdatedTauri<T>(handler);
+      case RUN_ENV.WEB:
+        return listenQuotesUpdatedWeb<T>(handler);
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error listen quotes:updated.");
+    throw error;
+  }
+};
+
+// listenServiceReady
+export const listenServiceReady = async <T>(handler: EventCallback<T>): Promise<UnlistenFn> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return listenServiceReadyTauri<T>(handler);
+      case RUN_ENV.WEB:
+        return listenServiceReadyWeb<T>(handler);
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error listen service:ready.");
+    throw error;
+  }
+};
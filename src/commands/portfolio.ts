@@ -109,6 +109,42 @@ export const getHistoricalValuations = async (
   }
 };
 
+export const getAccountValuationHistory = async (
+  accountId: string,
+  startDate?: string,
+  endDate?: string,
+): Promise<AccountValuation[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("get_account_valuation_history", { accountId, startDate, endDate });
+      case RUN_ENV.WEB:
+        return invokeWeb("get_account_valuation_history", { accountId, startDate, endDate });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error fetching account valuation history.");
+    throw error;
+  }
+};
+
+export const recomputeValuations = async (accountIds: string[] = []): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("recompute_valuations", { accountIds });
+      case RUN_ENV.WEB:
+        return invokeWeb("recompute_valuations", { accountIds });
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error recomputing valuations.");
+    throw error;
+  }
+};
+
 export const getLatestValuations = async (accountIds: string[]): Promise<AccountValuation[]> => {
   try {
     switch (getRunEnv()) {
@@ -1,5 +1,5 @@
 import { getRunEnv, invokeTauri, invokeWeb, logger, RUN_ENV } from "@/adapters";
-import { Settings } from "@/lib/types";
+import { DataExportSummary, ExportDataType, OperationOptions, Settings } from "@/lib/types";
 
 export const getSettings = async (): Promise<Settings> => {
   try {
@@ -27,6 +27,11 @@ export const getSettings = async (): Promise<Settings> => {
       menuBarVisible: true,
       isPro: false,
       syncEnabled: true,
+      locale: "vi-VN",
+      fiscalYearStartMonth: 1,
+      fiscalYearCalendar: "GREGORIAN",
+      defaultCostBasisMethod: "FIFO",
+      privacyMode: false,
     };
   }
 };
@@ -98,6 +103,50 @@ export const backupDatabaseToPath = async (backupDir: string): Promise<string> =
   }
 };
 
+/** Desktop: the backend streams `dataset` straight into the file at `path` */
+export const exportDataToFile = async (
+  dataset: ExportDataType,
+  format: "CSV" | "JSON",
+  path: string,
+  options: OperationOptions = {},
+): Promise<DataExportSummary> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return await invokeTauri<DataExportSummary>("export_data_to_file", {
+          dataset,
+          format,
+          path,
+          ...options,
+        });
+      default:
+        throw new Error(`Unsupported environment for exporting to a file`);
+    }
+  } catch (error) {
+    logger.error("Error exporting data to file.");
+    throw error;
+  }
+};
+
+/** Web: downloads `dataset` as the server streams it */
+export const downloadDataExport = async (
+  dataset: ExportDataType,
+  format: "CSV" | "JSON",
+  options: OperationOptions = {},
+): Promise<{ fileName: string; data: Blob }> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.WEB:
+        return await invokeWeb("export_data", { dataset, format, ...options });
+      default:
+        throw new Error(`Unsupported environment for downloading an export`);
+    }
+  } catch (error) {
+    logger.error("Error downloading data export.");
+    throw error;
+  }
+};
+
 export const restoreDatabase = async (backupFilePath: string): Promise<void> => {
   try {
     switch (getRunEnv()) {
@@ -0,0 +1,86 @@
+import { getRunEnv, invokeTauri, invokeWeb, logger, RUN_ENV } from "@/adapters";
+import { OperationInfo, PerformanceStats, ServiceReadiness } from "@/lib/types";
+
+export const getPerformanceStats = async (): Promise<PerformanceStats> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("get_performance_stats");
+      case RUN_ENV.WEB:
+        return invokeWeb("get_performance_stats");
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error fetching performance stats.");
+    throw error;
+  }
+};
+
+export const resetPerformanceStats = async (): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("reset_performance_stats");
+      case RUN_ENV.WEB:
+        return invokeWeb("reset_performance_stats");
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error resetting performance stats.");
+    throw error;
+  }
+};
+
+export const getServiceReadiness = async (): Promise<ServiceReadiness> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("get_service_readiness");
+      case RUN_ENV.WEB:
+        return invokeWeb("get_service_readiness");
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error fetching service readiness.");
+    throw error;
+  }
+};
+
+/** Exports, quote imports and market data resyncs still running, oldest first */
+export const listOperations = async (): Promise<OperationInfo[]> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        return invokeTauri("list_operations");
+      case RUN_ENV.WEB:
+        return invokeWeb("list_operations");
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error listing operations.");
+    throw error;
+  }
+};
+
+/** Stops a running operation; it then rejects with an "operation cancelled" error */
+export const cancelOperation = async (operationId: string): Promise<void> => {
+  try {
+    switch (getRunEnv()) {
+      case RUN_ENV.DESKTOP:
+        await invokeTauri("cancel_operation", { operationId });
+        return;
+      case RUN_ENV.WEB:
+        await invokeWeb("cancel_operation", { operationId });
+        return;
+      default:
+        throw new Error(`Unsupported`);
+    }
+  } catch (error) {
+    logger.error("Error cancelling operation.");
+    throw error;
+  }
+};
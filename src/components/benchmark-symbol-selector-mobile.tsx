@@ -34,6 +34,8 @@ const getDataSourceLabel = (dataSource: string | undefined, t: any): string => {
       return t("settings:securities.form.dataSource.options.metalPriceApi");
     case DataSource.VN_MARKET:
       return t("settings:securities.form.dataSource.options.vnMarket");
+    case DataSource.VIETCOMBANK:
+      return t("settings:securities.form.dataSource.options.vietcombank");
     default:
       return dataSource;
   }
@@ -8,6 +8,9 @@ import {
   QuoteImportState,
 } from "../lib/types/quote-import";
 
+/** Quotes sent per import call, so a long history is not handed over in one request */
+const IMPORT_CHUNK_SIZE = 1000;
+
 function prepareQuotesForImport(quotes: QuoteImport[]): QuoteImport[] {
   return quotes.map((quote) => ({
     ...quote,
@@ -139,22 +142,14 @@ export function useQuoteImport(): QuoteImportState & QuoteImportActions {
         return false;
       }
 
-      setImportProgress(25);
-
-      // Simulate progress during import since Tauri command doesn't provide updates
-      const progressInterval = setInterval(() => {
-        setImportProgress((prev) => {
-          const newProgress = prev + Math.random() * 10; // Random increment for realism
-          return Math.min(newProgress, 90); // Don't go over 90% until actually complete
-        });
-      }, 200);
-
-      const result = await importManualQuotes(prepareQuotesForImport(allQuotes));
-      const normalizedResult = result.map(normalizeQuoteImport);
-
-      clearInterval(progressInterval);
-
-      setImportProgress(100);
+      const prepared = prepareQuotesForImport(allQuotes);
+      const normalizedResult: QuoteImport[] = [];
+      for (let start = 0; start < prepared.length; start += IMPORT_CHUNK_SIZE) {
+        const chunk = prepared.slice(start, start + IMPORT_CHUNK_SIZE);
+        const result = await importManualQuotes(chunk);
+        normalizedResult.push(...result.map(normalizeQuoteImport));
+        setImportProgress(Math.round(((start + chunk.length) / prepared.length) * 100));
+      }
 
       // Update preview with import results
       setPreview((prev) =>
@@ -13,6 +13,9 @@ export const AccountType = {
   SECURITIES: "SECURITIES",
   CASH: "CASH",
   CRYPTOCURRENCY: "CRYPTOCURRENCY",
+  LIABILITY: "LIABILITY",
+  REAL_ESTATE: "REAL_ESTATE",
+  GOLD: "GOLD",
 } as const;
 
 export type AccountType = (typeof AccountType)[keyof typeof AccountType];
@@ -21,6 +24,9 @@ export const accountTypeSchema = z.enum([
   AccountType.SECURITIES,
   AccountType.CASH,
   AccountType.CRYPTOCURRENCY,
+  AccountType.LIABILITY,
+  AccountType.REAL_ESTATE,
+  AccountType.GOLD,
 ]);
 
 export const DataSource = {
@@ -30,6 +36,7 @@ export const DataSource = {
   ALPHA_VANTAGE: "ALPHA_VANTAGE",
   METAL_PRICE_API: "METAL_PRICE_API",
   VN_MARKET: "VN_MARKET",
+  VIETCOMBANK: "VIETCOMBANK",
 } as const;
 
 export type DataSource = (typeof DataSource)[keyof typeof DataSource];
@@ -42,6 +49,7 @@ export const dataSourceSchema = z.enum([
   DataSource.ALPHA_VANTAGE,
   DataSource.METAL_PRICE_API,
   DataSource.VN_MARKET,
+  DataSource.VIETCOMBANK,
 ]);
 
 export const ImportFormat = {
@@ -16,6 +16,7 @@ export const importMappingSchema = z.object({
   activityMappings: z.record(z.string(), z.array(z.string())),
   symbolMappings: z.record(z.string(), z.string()),
   accountMappings: z.record(z.string(), z.string()),
+  numberLocale: z.enum(["en", "vi", "auto"]).optional(),
 });
 
 export const newAccountSchema = z.object({
@@ -113,6 +114,7 @@ export const importActivitySchema = z
     accountName: z.string().optional(),
     symbolName: z.string().optional(),
     errors: z.record(z.string(), z.array(z.string())).optional(),
+    warnings: z.array(z.string()).optional(),
     isValid: z.boolean().default(false),
     lineNumber: z.number().optional(),
     isDraft: z.boolean(),
@@ -1,6 +1,7 @@
 import { importActivitySchema, importMappingSchema } from "@/lib/schemas";
 import * as z from "zod";
 import { AccountType, ActivityType, DataSource, HoldingType } from "./constants";
+import type { QuoteImport } from "./types/quote-import";
 
 export {
   AccountType,
@@ -28,6 +29,60 @@ export interface Account {
   platformId?: string; // Optional
 }
 
+export interface CashBalance {
+  accountId: string;
+  currency: string;
+  amount: number;
+}
+
+/** Cash of an account per currency, from its latest holdings snapshot */
+export interface AccountCash {
+  accountId: string;
+  accountCurrency: string;
+  asOf: string | null;
+  balances: CashBalance[];
+  /** Every balance in the account's currency */
+  total: number;
+}
+
+export interface CashMovement {
+  accountId: string;
+  /** Defaults to the account's currency */
+  currency?: string;
+  amount: number;
+  /** Defaults to today */
+  date?: string;
+  comment?: string;
+}
+
+/** A named set of accounts; groups nest through `parentId` */
+export interface AccountGroup {
+  id: string;
+  name: string;
+  parentId: string | null;
+  /** Accounts put in this group directly */
+  accountIds: string[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewAccountGroup {
+  id?: string;
+  name: string;
+  parentId?: string | null;
+  accountIds: string[];
+}
+
+/** Value of a group and its subgroups in the base currency; shared accounts count once */
+export interface AccountGroupValuation {
+  groupId: string;
+  name: string;
+  baseCurrency: string;
+  accountValues: Record<string, number>;
+  totalValue: number;
+  children: AccountGroupValuation[];
+}
+
 export interface Activity {
   id: string;
   type: ActivityType;
@@ -74,6 +129,19 @@ export interface ActivitySearchResponse {
   };
 }
 
+// Shares bought on HOSE, HNX or UPCOM that settle at T+2.5
+export interface PendingSettlement {
+  activityId: string;
+  accountId: string;
+  assetId: string;
+  exchange: string;
+  quantity: number;
+  tradeDate: string;
+  settlesOn: string;
+  availableAt: string;
+  oddLot: boolean;
+}
+
 export interface ActivityCreate {
   id?: string;
   accountId: string;
@@ -347,200 +415,2012 @@ export interface Settings {
   isPro: boolean;
   syncEnabled: boolean;
   language: string;
+  locale: string;
+  fiscalYearStartMonth: number;
+  fiscalYearCalendar: "GREGORIAN" | "LUNAR";
+  defaultCostBasisMethod: "FIFO" | "AVERAGE_COST";
+  privacyMode: boolean;
 }
 
-export interface SettingsContextType {
-  settings: Settings | null;
-  isLoading: boolean;
-  isError: boolean;
-  updateBaseCurrency: (currency: Settings["baseCurrency"]) => Promise<void>;
-  accountsGrouped: boolean;
-  setAccountsGrouped: (value: boolean) => void;
+export interface Profile {
+  id: string;
+  name: string;
+  dataDir: string;
+  createdAt: string;
+  readOnly: boolean;
 }
 
-export interface Goal {
-  id: string;
-  title: string;
-  description?: string;
-  targetAmount: number;
-  targetReturnRate?: number;
-  isAchieved?: boolean;
-  dueDate?: string;
-  monthlyInvestment?: number;
-  startDate?: string;
-  allocations?: GoalAllocation[];
+export type FeatureFlag = "new_allocation_model" | "sync" | "rest_server" | "performance_metrics";
+
+export interface FeatureFlagState {
+  flag: FeatureFlag;
+  enabled: boolean;
+  source: "default" | "setting" | "env";
 }
 
-export interface GoalAllocation {
-  id: string;
-  goalId: string;
+export interface ExportedImportProfile {
   accountId: string;
-  initialContribution: number; // Initial contribution amount at allocation start date
-  allocatedPercent: number;
-  allocationDate?: string; // YYYY-MM-DD format (legacy, may be null)
-  startDate?: string; // Allocation start date (backfilled from goal)
-  endDate?: string; // Allocation end date (backfilled from goal)
+  accountName?: string;
+  fieldMappings: Record<string, string>;
+  activityMappings: Record<string, string[]>;
+  symbolMappings: Record<string, string>;
+  accountMappings: Record<string, string>;
+  numberLocale?: "en" | "vi" | "auto";
 }
 
-export interface AllocationVersion {
+export interface ExportedProviderSetting {
   id: string;
-  allocationId: string;
-  allocatedPercent: number;
-  initialContribution: number;
-  versionStartDate: string;
-  versionEndDate?: string;
-  createdAt: string;
+  priority: number;
+  enabled: boolean;
 }
 
-export interface GoalProgress {
+export interface SettingsExport {
+  version: number;
+  exportedAt: string;
+  settings: Record<string, string>;
+  importProfiles: ExportedImportProfile[];
+  marketDataProviders: ExportedProviderSetting[];
+}
+
+export interface SettingsImportResult {
+  settingsApplied: number;
+  importProfilesApplied: number;
+  importProfilesSkipped: string[];
+  providersApplied: number;
+}
+
+export type BudgetRollover = "NONE" | "CARRY_SURPLUS" | "CARRY_ALL";
+
+export interface BudgetCategory {
+  id: string;
   name: string;
-  targetValue: number;
-  currentValue: number;
-  progress: number;
+  monthlyAmount: number;
   currency: string;
+  rollover: BudgetRollover;
+  startMonth: string;
+  isArchived: boolean;
+  createdAt: string;
+  updatedAt: string;
 }
 
-export interface IncomeSummary {
-  period: string;
-  byMonth: Record<string, number>;
-  byType: Record<string, number>;
-  bySymbol: Record<string, number>;
-  byCurrency: Record<string, number>;
-  totalIncome: number;
+export interface NewBudgetCategory {
+  id?: string;
+  name: string;
+  monthlyAmount: number;
   currency: string;
-  monthlyAverage: number;
-  yoyGrowth: number | null; // Changed from optional to nullable
+  rollover: BudgetRollover;
+  startMonth: string;
+  isArchived?: boolean;
 }
 
-// Define custom DateRange type matching react-day-picker's
-export interface DateRange {
-  from: Date | undefined;
-  to: Date | undefined;
+export interface BudgetMonthAmount {
+  categoryId: string;
+  month: string;
+  amount: number;
 }
 
-export type TimePeriod = "1D" | "1W" | "1M" | "3M" | "6M" | "YTD" | "1Y" | "3Y" | "5Y" | "ALL";
-
-export interface AccountValuation {
-  id: string;
-  accountId: string;
-  valuationDate: string;
-  accountCurrency: string;
-  baseCurrency: string;
-  fxRateToBase: number;
-  cashBalance: number;
-  investmentMarketValue: number;
-  totalValue: number;
-  costBasis: number;
-  netContribution: number;
-  calculatedAt: string;
+export interface BudgetMonthAmountUpdate {
+  categoryId: string;
+  month: string;
+  amount?: number | null;
 }
 
-export interface AccountSummaryView {
-  accountId: string;
-  accountName: string;
-  accountType: string;
-  accountGroup: string | null;
-  accountCurrency: string;
-  totalValueAccountCurrency: number;
-  totalValueBaseCurrency: number;
-  baseCurrency: string;
-  performance: SimplePerformanceMetrics;
+export type CategorySource = "MANUAL" | "RULE" | "LEARNED";
+
+export interface ActivityCategory {
+  activityId: string;
+  categoryId: string;
+  source: CategorySource;
 }
 
-export interface SimplePerformanceMetrics {
-  accountId: string;
-  totalValue?: number | null;
-  accountCurrency?: string | null;
-  baseCurrency?: string | null;
-  fxRateToBase?: number | null;
-  totalGainLossAmount?: number | null;
-  cumulativeReturnPercent?: number | null;
-  dayGainLossAmount?: number | null;
-  dayReturnPercentModDietz?: number | null;
-  portfolioWeight?: number | null;
+export interface BudgetProgress {
+  categoryId: string;
+  categoryName: string;
+  month: string;
+  currency: string;
+  budgeted: number;
+  rolloverIn: number;
+  available: number;
+  spent: number;
+  remaining: number;
+  percentUsed: number | null;
+  isOverBudget: boolean;
 }
 
-export interface AccountGroup {
-  groupName: string;
-  accounts: AccountSummaryView[];
-  totalValueBaseCurrency: number;
+export interface BudgetMonthProgress {
+  month: string;
   baseCurrency: string;
-  performance: SimplePerformanceMetrics;
-  accountCount: number;
+  categories: BudgetProgress[];
+  totalAvailable: number;
+  totalSpent: number;
+  totalRemaining: number;
 }
 
-export interface ExchangeRate {
+export type RuleField = "PAYEE" | "ACCOUNT_ID" | "ASSET_ID" | "ACTIVITY_TYPE";
+
+export type RuleMatchType = "CONTAINS" | "EQUALS" | "STARTS_WITH" | "REGEX";
+
+export interface CategorizationRule {
   id: string;
-  fromCurrency: string;
-  toCurrency: string;
-  fromCurrencyName?: string;
-  toCurrencyName?: string;
-  rate: number;
-  source: string;
-  isLoading?: boolean;
-  timestamp: string;
+  categoryId: string;
+  field: RuleField;
+  matchType: RuleMatchType;
+  pattern: string;
+  priority: number;
+  isActive: boolean;
+  createdAt: string;
+  updatedAt: string;
 }
 
-export interface ContributionLimit {
+export interface NewCategorizationRule {
+  id?: string;
+  categoryId: string;
+  field: RuleField;
+  matchType: RuleMatchType;
+  pattern: string;
+  priority?: number;
+  isActive?: boolean;
+}
+
+export interface CategorizationResult {
+  categorizedByRule: number;
+  categorizedByHistory: number;
+  cleared: number;
+  uncategorized: number;
+}
+
+export type CashFlowKind =
+  | "INCOME"
+  | "CONTRIBUTION"
+  | "BILL"
+  | "TERM_DEPOSIT_MATURITY"
+  | "LOAN_PAYMENT";
+
+export type CashFlowFrequency = "ONCE" | "MONTHLY" | "QUARTERLY" | "YEARLY";
+
+export interface PlannedCashFlow {
   id: string;
-  groupName: string;
-  contributionYear: number;
-  limitAmount: number;
-  accountIds?: string | null;
-  startDate?: string | null;
+  name: string;
+  kind: CashFlowKind;
+  accountId?: string | null;
+  amount: number;
+  currency: string;
+  frequency: CashFlowFrequency;
+  startDate: string;
   endDate?: string | null;
-  createdAt?: string;
-  updatedAt?: string;
+  isActive: boolean;
+  createdAt: string;
+  updatedAt: string;
+  bankCode?: string | null;
+  termMonths?: number | null;
+  interestRate?: number | null;
 }
 
-export type NewContributionLimit = Omit<ContributionLimit, "id" | "createdAt" | "updatedAt">;
-
-export interface AccountDeposit {
+export interface NewPlannedCashFlow {
+  id?: string;
+  name: string;
+  kind: CashFlowKind;
+  accountId?: string | null;
   amount: number;
   currency: string;
-  convertedAmount: number;
+  frequency: CashFlowFrequency;
+  startDate: string;
+  endDate?: string | null;
+  isActive?: boolean;
+  bankCode?: string | null;
+  termMonths?: number | null;
+  interestRate?: number | null;
 }
 
-export interface DepositsCalculation {
-  total: number;
+export interface CashFlowForecastRequest {
+  months: number;
+  minimumBalance?: number;
+  accountIds?: string[] | null;
+  includeGoalContributions?: boolean;
+  includeLoanPayments?: boolean;
+  loanRateIncrease?: number;
+}
+
+export interface ForecastEvent {
+  date: string;
+  name: string;
+  kind: CashFlowKind;
+  source: "PLANNED_CASH_FLOW" | "GOAL" | "INCOME_SOURCE" | "LOAN";
+  sourceId: string;
+  amount: number;
+}
+
+export interface ForecastPoint {
+  date: string;
+  inflow: number;
+  outflow: number;
+  balance: number;
+  belowMinimum: boolean;
+  events: ForecastEvent[];
+}
+
+export interface CashFlowForecast {
   baseCurrency: string;
-  byAccount: Record<string, AccountDeposit>;
+  startDate: string;
+  endDate: string;
+  startingBalance: number;
+  minimumBalance: number;
+  points: ForecastPoint[];
+  lowestBalance: number;
+  lowestBalanceDate: string;
+  firstShortfallDate?: string | null;
+  endingBalance: number;
 }
 
-export const ACTIVITY_TYPE_PREFIX_LENGTH = 12;
+export type MessageCode =
+  | "GOAL_ALLOCATION_EXCEEDS_LIMIT"
+  | "GOAL_ALLOCATION_EXCEEDS_LIMIT_IN_PERIOD"
+  | "GOAL_ALLOCATION_EXCEEDS_LIMIT_ON_DATE"
+  | "INVALID_DATE_RANGE"
+  | "OPERATION_ALREADY_RUNNING"
+  | "OPERATION_CANCELLED"
+  | "OPERATION_TIMED_OUT"
+  | "PROFILE_NOT_EMPTY";
 
-// Renamed from CumulativeReturn to match Rust struct ReturnData
-export interface ReturnData {
-  date: string; // Changed from CumulativeReturn
-  value: number;
+/** Error payload from the web API; `errorCode` and `params` are set for catalog messages */
+export interface ApiErrorBody {
+  code: number;
+  message: string;
+  errorCode?: MessageCode;
+  params?: Record<string, string>;
 }
 
-// Renamed from PerformanceData to match Rust struct
-export interface PerformanceMetrics {
+export interface OnboardingAccount {
+  name: string;
+  accountType?: string;
+  currency?: string;
+  group?: string;
+  startingBalance?: number;
+  startingBalanceDate?: string;
+}
+
+export interface OnboardingGoal {
+  title: string;
+  description?: string;
+  targetAmount: number;
+  targetReturnRate?: number;
+  dueDate?: string;
+  monthlyInvestment?: number;
+}
+
+export interface OnboardingPlan {
+  baseCurrency?: string;
+  accounts: OnboardingAccount[];
+  firstGoal?: OnboardingGoal;
+}
+
+export interface OnboardingResult {
+  baseCurrency: string;
+  accounts: Account[];
+  activities: Activity[];
+  goal?: Goal;
+}
+
+export type AuditAction = "CREATE" | "UPDATE" | "DELETE" | "IMPORT";
+
+export interface AuditLogEntry {
   id: string;
-  returns: ReturnData[]; // Changed from CumulativeReturn[]
-  periodStartDate?: string | null; // Changed from periodStartDate?
-  periodEndDate?: string | null; // Changed from periodEndDate?
-  currency: string;
-  cumulativeTwr: number; // Added field, corresponds to TWR
-  gainLossAmount?: number | null; // Made explicitly nullable
-  annualizedTwr: number; // Added field, corresponds to TWR
-  simpleReturn: number; // Added field
-  annualizedSimpleReturn: number; // Added field
-  cumulativeMwr: number; // Added field, corresponds to MWR
-  annualizedMwr: number; // Added field, corresponds to MWR
-  volatility: number;
-  maxDrawdown: number;
+  entityType: string;
+  entityId: string;
+  action: AuditAction;
+  actor: string;
+  changes: string | null;
+  createdAt: string;
 }
 
-export interface UpdateAssetProfile {
-  symbol: string;
-  name?: string;
-  sectors: string;
-  countries: string;
-  notes: string;
-  assetClass: string;
-  assetSubClass: string;
+export interface AuditLogQuery {
+  entityType?: string;
+  entityId?: string;
+  action?: AuditAction;
+  actor?: string;
+  startDate?: string;
+  endDate?: string;
+  page: number;
+  pageSize: number;
+}
+
+export interface ReadOnlyViolation {
+  code: "READ_ONLY_MODE";
+  command: string;
+  message: string;
+}
+
+export interface SettingsContextType {
+  settings: Settings | null;
+  isLoading: boolean;
+  isError: boolean;
+  updateBaseCurrency: (currency: Settings["baseCurrency"]) => Promise<void>;
+  accountsGrouped: boolean;
+  setAccountsGrouped: (value: boolean) => void;
+}
+
+export interface Goal {
+  id: string;
+  title: string;
+  description?: string;
+  targetAmount: number;
+  targetReturnRate?: number;
+  isAchieved?: boolean;
+  dueDate?: string;
+  monthlyInvestment?: number;
+  startDate?: string;
+  allocations?: GoalAllocation[];
+  goalType?: GoalType;
+  /** Months of expenses an emergency fund should cover */
+  targetMonths?: number | null;
+  /** When the goal was archived; archived goals are only listed on request */
+  archivedAt?: string | null;
+  /** Funding order; new cash goes to a higher priority first */
+  priority?: number;
+  /** Goal that has to be achieved before this one takes new cash */
+  dependsOnGoalId?: string | null;
+  /** Currency the target is set in; the base currency when empty */
+  currency?: string | null;
+  /** Day the goal reached its target, YYYY-MM-DD */
+  achievedAt?: string | null;
+}
+
+export type GoalType =
+  | "STANDARD"
+  | "EMERGENCY_FUND"
+  | "SINKING_FUND"
+  | "EDUCATION"
+  | "NET_WORTH"
+  | "PROPERTY_PAYOFF";
+
+export type ExpenseBasis = "SPENDING" | "BUDGET";
+
+export interface EmergencyFundProgress {
+  goalId: string;
+  goalTitle: string;
+  baseCurrency: string;
+  targetMonths: number;
+  monthlyExpenses: number;
+  expenseBasis: ExpenseBasis;
+  targetAmount: number;
+  currentValue: number;
+  monthsCovered?: number | null;
+  progress?: number | null;
+}
+
+export interface SinkingFundProgress {
+  goalId: string;
+  goalTitle: string;
+  baseCurrency: string;
+  targetAmount: number;
+  dueDate: string;
+  currentValue: number;
+  remaining: number;
+  monthsRemaining: number;
+  monthlySetAside: number;
+  progress?: number | null;
+  isFunded: boolean;
+}
+
+export interface NetWorthPoint {
+  date: string;
+  totalAssets: number;
+  totalLiabilities: number;
+  netWorth: number;
+}
+
+export interface NetWorthGoalProgress {
+  goalId: string;
+  goalTitle: string;
+  baseCurrency: string;
+  targetAmount: number;
+  dueDate?: string | null;
+  startNetWorth?: number | null;
+  current: NetWorthPoint;
+  remaining: number;
+  progress?: number | null;
+  isReached: boolean;
+}
+
+export interface DashboardGoal {
+  goalId: string;
+  title: string;
+  goalType: string;
+  currentAmount: number;
+  targetAmount: number;
+  progress?: number | null;
+}
+
+// Compact snapshot for widgets; dailyChangePct and progress are fractions (0.05 = 5%)
+export interface DashboardSummary {
+  baseCurrency: string;
+  asOf?: string | null;
+  netWorth: number;
+  dailyChange: number;
+  dailyChangePct?: number | null;
+  goals: DashboardGoal[];
+}
+
+// Month-end totals of one account; amounts are in the account currency
+export interface AccountMonthlySummary {
+  accountId: string;
+  month: string;
+  firstDate: string;
+  lastDate: string;
+  valueCount: number;
+  accountCurrency: string;
+  openingTotalValue: number;
+  closingTotalValue: number;
+  closingFxRateToBase: number;
+  closingCostBasis: number;
+  closingNetContribution: number;
+}
+
+// Progress of a goal at the end of a month; the current month is as of today
+export interface GoalMonthlyProgress {
+  goalId: string;
+  month: string;
+  asOf: string;
+  initValue: number;
+  currentValue: number;
+  growth: number;
+}
+
+export interface MonthlySummaries {
+  accounts: AccountMonthlySummary[];
+  goals: GoalMonthlyProgress[];
+}
+
+export type IncomeSourceKind = "SALARY" | "BONUS" | "RENTAL" | "OTHER";
+
+// LUNAR_NEW_YEAR pays once a year, payDay days before Tết
+export type IncomeFrequency = "MONTHLY" | "QUARTERLY" | "YEARLY" | "LUNAR_NEW_YEAR";
+
+export interface IncomeSource {
+  id: string;
+  name: string;
+  kind: IncomeSourceKind;
+  accountId?: string | null;
+  amount: number;
+  currency: string;
+  frequency: IncomeFrequency;
+  payDay: number;
+  startDate: string;
+  endDate?: string | null;
+  matchPattern?: string | null;
+  isActive: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewIncomeSource {
+  id?: string;
+  name: string;
+  kind: IncomeSourceKind;
+  accountId?: string | null;
+  amount: number;
+  currency: string;
+  frequency: IncomeFrequency;
+  payDay: number;
+  startDate: string;
+  endDate?: string | null;
+  matchPattern?: string | null;
+  isActive?: boolean;
+}
+
+export type IncomeVarianceStatus = "RECEIVED" | "DIFFERENT" | "MISSING" | "UPCOMING";
+
+export interface IncomeVariance {
+  sourceId: string;
+  sourceName: string;
+  kind: IncomeSourceKind;
+  expectedDate: string;
+  expectedAmount: number;
+  currency: string;
+  activityId?: string | null;
+  actualDate?: string | null;
+  actualAmount?: number | null;
+  variance: number;
+  status: IncomeVarianceStatus;
+}
+
+export interface SavingsRate {
+  baseCurrency: string;
+  monthlyIncome: number;
+  monthlyExpenses: number;
+  expenseBasis: ExpenseBasis;
+  monthlySavings: number;
+  savingsRate?: number | null;
+}
+
+export type BillFrequency = "MONTHLY" | "QUARTERLY" | "YEARLY" | "LUNAR_YEARLY";
+
+export interface LunarDate {
+  year: number;
+  month: number;
+  day: number;
+  isLeapMonth: boolean;
+}
+
+export interface Bill {
+  id: string;
+  name: string;
+  payee: string;
+  amount: number;
+  currency: string;
+  accountId?: string | null;
+  categoryId?: string | null;
+  frequency: BillFrequency;
+  dueDay: number;
+  lunarMonth?: number | null;
+  startDate: string;
+  endDate?: string | null;
+  reminderDays: number;
+  matchPattern?: string | null;
+  isActive: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewBill {
+  id?: string;
+  name: string;
+  payee: string;
+  amount: number;
+  currency: string;
+  accountId?: string | null;
+  categoryId?: string | null;
+  frequency: BillFrequency;
+  dueDay: number;
+  lunarMonth?: number | null;
+  startDate: string;
+  endDate?: string | null;
+  reminderDays?: number;
+  matchPattern?: string | null;
+  isActive?: boolean;
+}
+
+export type BillPaymentSource = "MATCHED" | "MANUAL";
+
+export interface BillPayment {
+  id: string;
+  billId: string;
+  dueDate: string;
+  activityId?: string | null;
+  amount: number;
+  paidDate: string;
+  source: BillPaymentSource;
+  createdAt: string;
+}
+
+export interface NewBillPayment {
+  billId: string;
+  dueDate: string;
+  activityId?: string | null;
+  amount?: number | null;
+  paidDate?: string | null;
+}
+
+export type BillReminderStatus = "UPCOMING" | "DUE_TODAY" | "OVERDUE";
+
+export interface BillReminder {
+  billId: string;
+  billName: string;
+  payee: string;
+  dueDate: string;
+  amount: number;
+  currency: string;
+  accountId?: string | null;
+  lunarDueDate?: LunarDate | null;
+  daysUntilDue: number;
+  status: BillReminderStatus;
+}
+
+export interface BillMatchResult {
+  matched: number;
+  payments: BillPayment[];
+}
+
+export interface Envelope {
+  id: string;
+  accountId: string;
+  name: string;
+  balance: number;
+  isArchived: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewEnvelope {
+  id?: string;
+  accountId: string;
+  name: string;
+}
+
+// A missing envelope side is the account's unassigned cash
+export interface EnvelopeTransfer {
+  id: string;
+  accountId: string;
+  fromEnvelopeId?: string | null;
+  toEnvelopeId?: string | null;
+  amount: number;
+  note?: string | null;
+  createdAt: string;
+  fromEnvelopeName?: string | null;
+  toEnvelopeName?: string | null;
+}
+
+export interface NewEnvelopeTransfer {
+  accountId: string;
+  fromEnvelopeId?: string | null;
+  toEnvelopeId?: string | null;
+  amount: number;
+  note?: string | null;
+}
+
+export interface AccountEnvelopes {
+  accountId: string;
+  currency: string;
+  cashBalance: number;
+  allocated: number;
+  unassigned: number;
+  overallocated: boolean;
+  envelopes: Envelope[];
+}
+
+// EQUAL_PRINCIPAL: same principal each month, interest on the reducing balance
+export type RepaymentMethod = "EQUAL_PRINCIPAL" | "ANNUITY";
+
+// Rates are annual percentages
+export interface Loan {
+  id: string;
+  accountId: string;
+  principal: number;
+  startDate: string;
+  termMonths: number;
+  repaymentMethod: RepaymentMethod;
+  fixedRate: number;
+  fixedMonths: number;
+  baseRate?: number | null;
+  floatingMargin?: number | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewLoan {
+  id?: string;
+  accountId: string;
+  principal: number;
+  startDate: string;
+  termMonths: number;
+  repaymentMethod?: RepaymentMethod;
+  fixedRate: number;
+  fixedMonths?: number;
+  baseRate?: number | null;
+  floatingMargin?: number | null;
+}
+
+export interface LoanPrepayment {
+  id: string;
+  loanId: string;
+  paymentDate: string;
+  amount: number;
+  note?: string | null;
+  createdAt: string;
+}
+
+export interface NewLoanPrepayment {
+  loanId: string;
+  paymentDate: string;
+  amount: number;
+  note?: string | null;
+}
+
+export interface LoanRateReset {
+  id: string;
+  loanId: string;
+  effectiveDate: string;
+  baseRate: number;
+  note?: string | null;
+  createdAt: string;
+}
+
+export interface NewLoanRateReset {
+  loanId: string;
+  effectiveDate: string;
+  baseRate: number;
+  note?: string | null;
+}
+
+export interface LoanScheduleRow {
+  period: number;
+  paymentDate: string;
+  annualRate: number;
+  isFixedRate: boolean;
+  openingBalance: number;
+  principal: number;
+  interest: number;
+  payment: number;
+  prepayment: number;
+  closingBalance: number;
+}
+
+export interface LoanSchedule {
+  loan: Loan;
+  currency: string;
+  rows: LoanScheduleRow[];
+  prepayments: LoanPrepayment[];
+  rateResets: LoanRateReset[];
+  totalInterest: number;
+  totalPaid: number;
+  payoffDate?: string | null;
+  outstandingBalance: number;
+  nextPayment?: LoanScheduleRow | null;
+}
+
+export interface LoanStressTest {
+  loanId: string;
+  currency: string;
+  rateIncrease: number;
+  fromDate: string;
+  baseline: LoanScheduleRow[];
+  stressed: LoanScheduleRow[];
+  baselineInterest: number;
+  stressedInterest: number;
+  extraInterest: number;
+  peakPayment: number;
+  maxPaymentIncrease: number;
+}
+
+// Held by a REAL_ESTATE account; amounts are in the account currency
+export interface Property {
+  id: string;
+  accountId: string;
+  assetId: string;
+  activityId?: string | null;
+  name: string;
+  address?: string | null;
+  purchaseDate: string;
+  purchasePrice: number;
+  transactionCosts: number;
+  mortgageAccountId?: string | null;
+  rentalIncomeSourceId?: string | null;
+  notes?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewProperty {
+  id?: string;
+  accountId: string;
+  name: string;
+  address?: string | null;
+  purchaseDate: string;
+  purchasePrice: number;
+  transactionCosts?: number;
+  mortgageAccountId?: string | null;
+  rentalIncomeSourceId?: string | null;
+  notes?: string | null;
+}
+
+export interface PropertyAppraisal {
+  id: string;
+  propertyId: string;
+  appraisalDate: string;
+  value: number;
+  source?: string | null;
+  createdAt: string;
+}
+
+export interface NewPropertyAppraisal {
+  propertyId: string;
+  appraisalDate: string;
+  value: number;
+  source?: string | null;
+}
+
+// Loan-to-value and gross yield are percentages of the current value
+export interface PropertySummary {
+  property: Property;
+  currency: string;
+  currentValue: number;
+  valuedOn: string;
+  appraisals: PropertyAppraisal[];
+  mortgageBalance: number;
+  equity: number;
+  loanToValue: number;
+  unrealizedGain: number;
+  annualRent?: number | null;
+  grossYield?: number | null;
+}
+
+export type BondIssuerType = "GOVERNMENT" | "CORPORATE";
+
+export type CouponFrequency = "ANNUAL" | "SEMI_ANNUAL" | "QUARTERLY" | "MONTHLY" | "AT_MATURITY";
+
+// Prices are per bond; the coupon rate is a yearly percentage of the face value
+export interface Bond {
+  id: string;
+  accountId: string;
+  assetId: string;
+  activityId?: string | null;
+  code: string;
+  issuer: string;
+  issuerType: BondIssuerType;
+  faceValue: number;
+  quantity: number;
+  couponRate: number;
+  couponFrequency: CouponFrequency;
+  issueDate: string;
+  maturityDate: string;
+  purchaseDate: string;
+  purchasePrice: number;
+  marketPrice?: number | null;
+  notes?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewBond {
+  id?: string;
+  accountId: string;
+  code: string;
+  issuer: string;
+  issuerType: BondIssuerType;
+  faceValue: number;
+  quantity: number;
+  couponRate: number;
+  couponFrequency: CouponFrequency;
+  issueDate: string;
+  maturityDate: string;
+  purchaseDate: string;
+  purchasePrice: number;
+  marketPrice?: number | null;
+  notes?: string | null;
+}
+
+export interface BondPayment {
+  bondId: string;
+  code: string;
+  issuer: string;
+  paymentDate: string;
+  coupon: number;
+  principal: number;
+  currency: string;
+}
+
+// Market value is the clean value plus accrued interest; current yield is a percentage
+export interface BondSummary {
+  bond: Bond;
+  currency: string;
+  valuedOn: string;
+  cleanValue: number;
+  accruedInterest: number;
+  marketValue: number;
+  cost: number;
+  unrealizedGain: number;
+  annualCouponIncome: number;
+  currentYield: number;
+  nextPayment?: BondPayment | null;
+  daysToMaturity: number;
+  isMatured: boolean;
+}
+
+// Prices are per share; the vested value is what the vested shares are worth above their
+// strike price, and is what counts in net worth
+export interface EsopGrant {
+  id: string;
+  accountId: string;
+  assetId: string;
+  activityId?: string | null;
+  company: string;
+  symbol?: string | null;
+  grantDate: string;
+  vestingStartDate: string;
+  totalShares: number;
+  strikePrice: number;
+  cliffMonths: number;
+  vestingMonths: number;
+  vestingIntervalMonths: number;
+  lockupMonths: number;
+  fairValue?: number | null;
+  notes?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewEsopGrant {
+  id?: string;
+  accountId: string;
+  company: string;
+  symbol?: string | null;
+  grantDate: string;
+  vestingStartDate?: string | null;
+  totalShares: number;
+  strikePrice?: number;
+  cliffMonths?: number;
+  vestingMonths: number;
+  vestingIntervalMonths: number;
+  lockupMonths?: number;
+  fairValue?: number | null;
+  notes?: string | null;
+}
+
+export interface EsopVestingEvent {
+  vestingDate: string;
+  shares: number;
+  vestedShares: number;
+  lockupEndDate: string;
+}
+
+export interface EsopGrantSummary {
+  grant: EsopGrant;
+  currency: string;
+  valuedOn: string;
+  sharePrice: number;
+  schedule: EsopVestingEvent[];
+  vestedShares: number;
+  unvestedShares: number;
+  lockedShares: number;
+  sellableShares: number;
+  vestedValue: number;
+  unvestedValue: number;
+  exerciseCost: number;
+  nextVesting?: EsopVestingEvent | null;
+  fullyVested: boolean;
+}
+
+export interface UpcomingVesting {
+  grantId: string;
+  company: string;
+  vestingDate: string;
+  shares: number;
+  value: number;
+  currency: string;
+  lockupEndDate: string;
+}
+
+export interface SipPlan {
+  id: string;
+  accountId: string;
+  assetId: string;
+  name: string;
+  amount: number;
+  startDate: string;
+  endDate?: string | null;
+  plannedCashFlowId?: string | null;
+  isActive: boolean;
+  notes?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewSipPlan {
+  id?: string;
+  accountId: string;
+  assetId: string;
+  name?: string | null;
+  amount: number;
+  startDate: string;
+  endDate?: string | null;
+  isActive?: boolean;
+  notes?: string | null;
+}
+
+export interface SipPurchase {
+  activityId: string;
+  date: string;
+  units: number;
+  nav: number;
+  fee: number;
+  amount: number;
+}
+
+export type SipInstallmentStatus = "PURCHASED" | "DUE" | "MISSED";
+
+export interface SipInstallment {
+  dueDate: string;
+  expectedAmount: number;
+  actualAmount: number;
+  units: number;
+  activityIds: string[];
+  status: SipInstallmentStatus;
+}
+
+export interface SipPerformancePoint {
+  date: string;
+  invested: number;
+  dcaValue: number;
+  lumpSumValue: number;
+}
+
+export interface LumpSumComparison {
+  date: string;
+  nav: number;
+  units: number;
+  marketValue: number;
+  gain: number;
+  returnPercent: number;
+}
+
+export interface SipPlanSummary {
+  plan: SipPlan;
+  currency: string;
+  valuedOn: string;
+  installments: SipInstallment[];
+  extraPurchases: SipPurchase[];
+  expectedAmount: number;
+  investedAmount: number;
+  shortfall: number;
+  missedInstallments: number;
+  units: number;
+  averageNav: number;
+  latestNav?: number | null;
+  marketValue: number;
+  gain: number;
+  returnPercent: number;
+  lumpSum?: LumpSumComparison | null;
+  performance: SipPerformancePoint[];
+}
+
+export type LendingRepaymentPlan = "INSTALLMENT" | "INTEREST_ONLY" | "BULLET";
+
+export type LendingRepaymentSource = "MATCHED" | "MANUAL";
+
+export type LendingRiskStatus = "CURRENT" | "LATE" | "AT_RISK" | "DEFAULT" | "WRITTEN_OFF" | "REPAID";
+
+// The interest rate is a yearly percentage; repayments are matched from deposits whose
+// comment contains the match pattern, or the borrower's name when it is unset
+export interface PrivateLoan {
+  id: string;
+  accountId: string;
+  assetId: string;
+  activityId?: string | null;
+  borrower: string;
+  platform?: string | null;
+  principal: number;
+  interestRate: number;
+  repaymentPlan: LendingRepaymentPlan;
+  termMonths: number;
+  startDate: string;
+  matchPattern?: string | null;
+  writtenOff: boolean;
+  notes?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewPrivateLoan {
+  id?: string;
+  accountId: string;
+  borrower: string;
+  platform?: string | null;
+  principal: number;
+  interestRate: number;
+  repaymentPlan?: LendingRepaymentPlan;
+  termMonths: number;
+  startDate: string;
+  matchPattern?: string | null;
+  writtenOff?: boolean;
+  notes?: string | null;
+}
+
+export interface LendingScheduleRow {
+  period: number;
+  dueDate: string;
+  principal: number;
+  interest: number;
+  payment: number;
+}
+
+export interface PrivateLoanRepayment {
+  id: string;
+  loanId: string;
+  activityId?: string | null;
+  paymentDate: string;
+  amount: number;
+  source: LendingRepaymentSource;
+  createdAt: string;
+}
+
+export interface NewPrivateLoanRepayment {
+  loanId: string;
+  paymentDate: string;
+  amount: number;
+  activityId?: string | null;
+}
+
+// Value is the outstanding principal counted in net worth, zero once written off
+export interface PrivateLoanSummary {
+  loan: PrivateLoan;
+  currency: string;
+  valuedOn: string;
+  schedule: LendingScheduleRow[];
+  repayments: PrivateLoanRepayment[];
+  totalRepaid: number;
+  interestReceived: number;
+  outstandingPrincipal: number;
+  value: number;
+  overdueAmount: number;
+  daysOverdue: number;
+  nextDue?: LendingScheduleRow | null;
+  status: LendingRiskStatus;
+}
+
+export interface PrivateLoanMatchResult {
+  matched: number;
+  repayments: PrivateLoanRepayment[];
+}
+
+export type BonusLineKind = "GOAL" | "ENVELOPE" | "GIFTS";
+
+export interface BonusPlanLine {
+  id: string;
+  planId: string;
+  kind: BonusLineKind;
+  goalId?: string | null;
+  envelopeId?: string | null;
+  label: string;
+  amount: number;
+  allocationId?: string | null;
+  transferId?: string | null;
+}
+
+// depositPending stays true while the bonus deposit is a draft
+export interface BonusPlan {
+  id: string;
+  accountId: string;
+  incomeSourceId?: string | null;
+  amount: number;
+  currency: string;
+  expectedDate: string;
+  activityId?: string | null;
+  depositPending: boolean;
+  note?: string | null;
+  lines: BonusPlanLine[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+// Percentages default to 10 for gifts and 20 for envelopes
+export interface BonusPlanRequest {
+  accountId: string;
+  incomeSourceId?: string | null;
+  amount?: number | null;
+  expectedDate?: string | null;
+  giftPercent?: number | null;
+  envelopePercent?: number | null;
+}
+
+export interface NewBonusPlanLine {
+  kind: BonusLineKind;
+  goalId?: string | null;
+  envelopeId?: string | null;
+  label: string;
+  amount: number;
+}
+
+export interface NewBonusPlan {
+  accountId: string;
+  incomeSourceId?: string | null;
+  amount: number;
+  expectedDate: string;
+  note?: string | null;
+  lines: NewBonusPlanLine[];
+}
+
+export interface DepositRate {
+  bankCode: string;
+  bankName: string;
+  termMonths: number;
+  rate: number;
+  source: string;
+  fetchedAt: string;
+}
+
+export interface RolloverSuggestion {
+  cashFlowId: string;
+  name: string;
+  accountId?: string | null;
+  maturityDate: string;
+  amount: number;
+  termMonths: number;
+  currentBankCode?: string | null;
+  currentRate: number;
+  bestBankCode: string;
+  bestBankName: string;
+  bestRate: number;
+  extraInterest: number;
+}
+
+export interface LiquidityNeed {
+  withinMonths: number;
+  /** Percent of the lump sum; the needs add up to 100 */
+  percent: number;
+}
+
+export interface DepositLadderRequest {
+  /** Cash account the deposits are funded from */
+  accountId: string;
+  amount: number;
+  startDate?: string | null;
+  liquidity: LiquidityNeed[];
+}
+
+export interface DepositLadderRung {
+  bankCode: string;
+  bankName: string;
+  termMonths: number;
+  rate: number;
+  amount: number;
+  maturityDate: string;
+  interest: number;
+}
+
+export interface DepositLadder {
+  accountId: string;
+  startDate: string;
+  rungs: DepositLadderRung[];
+  totalAmount: number;
+  totalInterest: number;
+  averageRate: number;
+}
+
+export interface ConfirmedDepositLadder {
+  accounts: Account[];
+  maturities: PlannedCashFlow[];
+}
+
+export type Gender = "MALE" | "FEMALE";
+
+export interface SalaryPeriod {
+  startDate: string;
+  endDate?: string | null;
+  monthlySalary: number;
+}
+
+export interface PensionRequest {
+  gender: Gender;
+  birthDate: string;
+  salaryHistory: SalaryPeriod[];
+  salaryAdjustmentRate?: number | null;
+  desiredMonthlySpending?: number | null;
+  withdrawalRate?: number | null;
+}
+
+export interface NestEggOffset {
+  monthlySpending: number;
+  withdrawalRate: number;
+  withoutPension: number;
+  withPension: number;
+  reduction: number;
+}
+
+export interface PensionEstimate {
+  retirementDate: string;
+  contributionMonths: number;
+  averageSalary: number;
+  eligible: boolean;
+  pensionRate: number;
+  monthlyPension: number;
+  lumpSum: number;
+  nestEgg?: NestEggOffset | null;
+}
+
+export type SchoolType = "VN_PUBLIC" | "VN_PRIVATE" | "INTERNATIONAL" | "OVERSEAS";
+
+// Costs are per school year in today's money; inflation is an annual percentage
+export interface EducationStage {
+  id: string;
+  name: string;
+  schoolType: SchoolType;
+  currency: string;
+  startYear: number;
+  years: number;
+  annualCost: number;
+  tuitionInflation?: number | null;
+}
+
+export interface EducationPlan {
+  id: string;
+  childName: string;
+  birthYear: number;
+  goalId?: string | null;
+  stages: EducationStage[];
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewEducationPlan {
+  id?: string;
+  childName: string;
+  birthYear: number;
+  stages: Omit<EducationStage, "id">[];
+}
+
+export interface EducationCostYear {
+  year: number;
+  stageName: string;
+  schoolType: SchoolType;
+  currency: string;
+  costToday: number;
+  projectedCost: number;
+  projectedCostBase: number;
+}
+
+export interface EducationFundingYear {
+  year: number;
+  dueDate: string;
+  cost: number;
+  cumulativeCost: number;
+  monthsUntilDue: number;
+}
+
+export interface EducationProjection {
+  planId: string;
+  childName: string;
+  baseCurrency: string;
+  costs: EducationCostYear[];
+  schedule: EducationFundingYear[];
+  totalNeed: number;
+  firstDueDate?: string | null;
+  lastDueDate?: string | null;
+  monthlyContribution: number;
+}
+
+// Point in the app a user script runs at
+export type ScriptEvent = "on_import_row" | "on_quote_update" | "on_goal_progress";
+
+export interface Script {
+  id: string;
+  name: string;
+  event: ScriptEvent;
+  source: string;
+  isEnabled: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewScript {
+  id?: string;
+  name: string;
+  event: ScriptEvent;
+  source: string;
+  isEnabled: boolean;
+}
+
+// Payload of the script:notification event
+export interface ScriptNotification {
+  id: string;
+  channel: "PRICE_ALERT" | "GOAL_PROGRESS" | "BILL_REMINDER" | "DEPOSIT_RATE" | "VESTING" | "SYSTEM";
+  title: string;
+  body: string;
+  createdAt: string;
+}
+
+export interface ScriptFailure {
+  scriptId: string;
+  scriptName: string;
+  message: string;
+}
+
+export interface ScriptRunReport {
+  notifications: ScriptNotification[];
+  logs: string[];
+  failures: ScriptFailure[];
+}
+
+export type ConnectorProvider = "TIMO" | "CAKE" | "BROKER_API";
+
+export interface BankConnection {
+  id: string;
+  provider: ConnectorProvider;
+  name: string;
+  accountId: string;
+  baseUrl: string;
+  syncIntervalHours: number;
+  isEnabled: boolean;
+  lastAttemptAt?: string | null;
+  lastSyncedAt?: string | null;
+  lastSyncError?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+// The token is stored in the OS keyring; omit it on update to keep the saved one
+export interface NewBankConnection {
+  id?: string;
+  provider: ConnectorProvider;
+  name: string;
+  accountId: string;
+  baseUrl: string;
+  syncIntervalHours: number;
+  isEnabled: boolean;
+  token?: string;
+}
+
+export interface ConnectorSyncResult {
+  connectionId: string;
+  connectionName: string;
+  fetched: number;
+  imported: number;
+  duplicates: number;
+  invalid: number;
+  error?: string | null;
+}
+
+export type SheetReport = "HOLDINGS" | "NET_WORTH" | "GOAL_PROGRESS";
+
+export interface SheetExport {
+  id: string;
+  name: string;
+  report: SheetReport;
+  spreadsheetId: string;
+  sheetName: string;
+  syncIntervalHours: number;
+  isEnabled: boolean;
+  lastAttemptAt?: string | null;
+  lastSyncedAt?: string | null;
+  lastSyncError?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewSheetExport {
+  id?: string;
+  name: string;
+  report: SheetReport;
+  spreadsheetId: string;
+  sheetName: string;
+  syncIntervalHours: number;
+  isEnabled: boolean;
+}
+
+// Kept in the OS keyring and shared by every export; never read back
+export interface GoogleCredentials {
+  clientId: string;
+  clientSecret: string;
+  refreshToken: string;
+}
+
+export interface SheetExportResult {
+  exportId: string;
+  exportName: string;
+  rows: number;
+  error?: string | null;
+}
+
+export interface PayloadActivity {
+  date: string; // YYYY-MM-DD or RFC 3339
+  symbol: string;
+  activityType: string;
+  quantity?: number;
+  unitPrice?: number;
+  currency: string;
+  fee?: number;
+  amount?: number | null;
+  comment?: string | null;
+}
+
+export interface PayloadQuote {
+  symbol: string;
+  date: string; // YYYY-MM-DD
+  open?: number | null;
+  high?: number | null;
+  low?: number | null;
+  close: number;
+  volume?: number | null;
+  currency: string;
+}
+
+// Handed over by another app or a dropped JSON file
+export type ImportPayload = {
+  version: number;
+  source?: string | null;
+} & (
+  | { kind: "activities"; accountId: string; activities: PayloadActivity[] }
+  | { kind: "quotes"; overwrite?: boolean; quotes: PayloadQuote[] }
+);
+
+export interface ImportPayloadPreview {
+  activities: ActivityImport[]; // Duplicates carry a `duplicate` error
+  quotes: QuoteImport[];
+  valid: number;
+  invalid: number;
+  skipped: number;
+}
+
+export interface ImportPayloadResult {
+  accountId?: string | null;
+  imported: number;
+  skipped: number;
+}
+
+// Broker trade history exports, or GENERIC for a CSV read through the account's saved mapping
+export type ImportFormat = "SSI" | "VNDIRECT" | "TCBS" | "VPS" | "GENERIC";
+
+export interface ActivityImportRequest {
+  accountId: string;
+  format: ImportFormat;
+  content: string; // Text of the CSV file
+}
+
+export type ApiTokenScope = "READ_ONLY" | "REPORTS" | "IMPORT";
+
+export interface ApiToken {
+  id: string;
+  name: string;
+  tokenPrefix: string; // e.g. "wvn_1a2b3c4d"
+  scopes: ApiTokenScope[];
+  expiresAt?: string | null;
+  revokedAt?: string | null;
+  lastUsedAt?: string | null; // Updated at most once a minute
+  createdAt: string;
+}
+
+export interface NewApiToken {
+  name: string;
+  scopes: ApiTokenScope[];
+  expiresAt?: string | null;
+}
+
+export interface IssuedApiToken {
+  token: ApiToken;
+  secret: string; // Only returned once
+}
+
+export type AutomationTrigger =
+  | { type: "IMPORT_COMPLETED"; accountId?: string | null }
+  | { type: "GOAL_PROGRESS_REACHED"; goalId?: string | null; percent: number }
+  | { type: "QUOTE_MOVED"; symbol?: string | null; percent: number };
+
+export type AutomationAction =
+  | { type: "TAG_ACTIVITIES"; tag: string } // IMPORT_COMPLETED only
+  | { type: "NOTIFY"; title: string; body?: string }
+  | { type: "RUN_REPORT"; sheetExportId: string }
+  | { type: "CALL_WEBHOOK"; url: string };
+
+export interface AutomationRule {
+  id: string;
+  name: string;
+  trigger: AutomationTrigger;
+  actions: AutomationAction[];
+  isEnabled: boolean;
+  lastTriggeredAt?: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NewAutomationRule {
+  id?: string;
+  name: string;
+  trigger: AutomationTrigger;
+  actions: AutomationAction[];
+  isEnabled: boolean;
+}
+
+export interface ActivityTag {
+  activityId: string;
+  tag: string;
+  createdAt: string;
+}
+
+/** Payload of `transfer:progress`, sent while an export or import streams its rows */
+export interface TransferProgress {
+  /** Id to pass to `cancelOperation`, when the transfer was started as an operation */
+  operationId?: string | null;
+  operation: "EXPORT" | "IMPORT";
+  dataset: string;
+  processed: number;
+  total?: number | null;
+  done: boolean;
+}
+
+export interface DataExportSummary {
+  dataset: string;
+  format: "CSV" | "JSON";
+  fileName: string;
+  rows: number;
+}
+
+export interface OperationStats {
+  /** Span name, e.g. `valuation.calculate_history` */
+  name: string;
+  calls: number;
+  totalMs: number;
+  averageMs: number;
+  maxMs: number;
+  lastMs: number;
+  slowCalls: number;
+  lastCalledAt: string;
+}
+
+export interface PerformanceStats {
+  /** Whether the `performance_metrics` feature flag is recording timings */
+  enabled: boolean;
+  since: string;
+  slowThresholdMs: number;
+  operations: OperationStats[];
+}
+
+/** Subsystems that come up after the window; cached data is shown until they do */
+export interface ServiceReadiness {
+  /** Market data providers are configured (quotes, symbol search) */
+  marketData: boolean;
+  /** Valuations have been brought up to date since startup */
+  valuation: boolean;
+}
+
+/** A long-running export, import or market data resync that can still be cancelled */
+export interface OperationInfo {
+  id: string;
+  kind: "export" | "ledger-export" | "quote-import" | "market-data-resync";
+  startedAt: string;
+  timeoutSecs?: number | null;
+}
+
+/** Lets the caller cancel a long-running command (`cancelOperation(operationId)`) or bound it */
+export interface OperationOptions {
+  operationId?: string;
+  timeoutSecs?: number;
+}
+
+/** Payload of the `service:ready` event */
+export interface ServiceReadyEvent {
+  service: keyof ServiceReadiness;
+  readiness: ServiceReadiness;
+}
+
+export type LedgerFormat = "BEANCOUNT" | "LEDGER";
+
+export interface LedgerExport {
+  format: LedgerFormat;
+  fileName: string;
+  content: string;
+  accounts: number;
+  transactions: number;
+  skipped: string[]; // "<activityId>: <reason>"
+}
+
+export interface GoalAllocation {
+  id: string;
+  goalId: string;
+  accountId: string;
+  initialContribution: number; // Initial contribution amount at allocation start date
+  allocatedPercent: number;
+  allocationDate?: string; // YYYY-MM-DD format (legacy, may be null)
+  startDate?: string; // Allocation start date (backfilled from goal)
+  endDate?: string; // Allocation end date (backfilled from goal)
+}
+
+export interface AllocationVersion {
+  id: string;
+  allocationId: string;
+  allocatedPercent: number;
+  initialContribution: number;
+  versionStartDate: string;
+  versionEndDate?: string;
+  createdAt: string;
+}
+
+/** What `bulkInsertAllocations` wrote, all in one transaction */
+export interface AllocationBulkInsertSummary {
+  allocations: number;
+  versions: number;
+  goals: number;
+  accounts: number;
+}
+
+export interface GoalProgress {
+  name: string;
+  targetValue: number;
+  currentValue: number;
+  progress: number;
+  currency: string;
+}
+
+export interface AllocationDetail {
+  accountId: string;
+  percentAllocation: number;
+  accountValueAtGoalStart: number;
+  accountCurrentValue: number;
+  accountGrowth: number;
+  allocatedGrowth: number;
+  /** Share of the deposits, net of withdrawals, since the goal started */
+  contributedAmount: number;
+  /** Share of the investment returns: allocatedGrowth - contributedAmount */
+  marketGrowth: number;
+}
+
+export interface GoalProgressSnapshot {
+  goalId: string;
+  goalTitle: string;
+  queryDate: string;
+  initValue: number;
+  currentValue: number;
+  growth: number;
+  /** Part of the growth paid in; zero for net-worth goals */
+  contributedAmount: number;
+  /** Part of the growth earned by the investments */
+  marketGrowth: number;
+  allocationDetails: AllocationDetail[];
+}
+
+export type ProgressInterval = "DAILY" | "WEEKLY" | "MONTHLY";
+
+export interface AllocationRebalanceSuggestion {
+  allocationId: string;
+  goalId: string;
+  goalTitle: string;
+  targetAmount: number;
+  currentValue: number;
+  remaining: number;
+  dueDate?: string;
+  monthsLeft?: number;
+  currentPercent: number;
+  suggestedPercent: number;
+}
+
+export interface AllocationRebalance {
+  accountId: string;
+  asOf: string;
+  rebalancedPercent: number;
+  unallocatedPercent: number;
+  suggestions: AllocationRebalanceSuggestion[];
+}
+
+export interface GoalDeposit {
+  goalId: string;
+  goalTitle: string;
+  priority: number;
+  dueDate?: string;
+  monthsLeft?: number;
+  remaining: number;
+  amount: number;
+}
+
+export interface DepositDistribution {
+  accountId: string;
+  asOf: string;
+  amount: number;
+  unassigned: number;
+  goals: GoalDeposit[];
+}
+
+export type AllocationChange =
+  | { type: "PERCENTAGE"; value: number }
+  | { type: "AMOUNT"; value: number };
+
+export interface GoalChangePreview {
+  goalId: string;
+  goalTitle: string;
+  targetAmount: number;
+  currentValue: number;
+  newValue: number;
+  /** Null while the goal is not growing */
+  completionDate: string | null;
+  newCompletionDate: string | null;
+}
+
+export interface AllocationChangePreview {
+  allocationId: string;
+  accountId: string;
+  asOf: string;
+  change: AllocationChange;
+  unallocatedBalance: number;
+  goals: GoalChangePreview[];
+  /** Why the change could not be saved; empty when it can */
+  errors: string[];
+}
+
+export interface GoalPlanExport {
+  version: number;
+  exportedAt: string;
+  goals: Goal[];
+  allocations: GoalAllocation[];
+  versions: AllocationVersion[];
+  /** Names of the allocated accounts by id */
+  accountNames: Record<string, string>;
+}
+
+export interface GoalPlanImportResult {
+  goals: number;
+  allocations: number;
+  versions: number;
+  /** Exported accounts no account here matches; their allocations were left out */
+  unmatchedAccounts: string[];
+}
+
+export interface GoalFundingRequirement {
+  goalId: string;
+  goalTitle: string;
+  targetAmount: number;
+  currentValue: number;
+  dueDate: string;
+  monthsLeft: number;
+  expectedReturnRate: number;
+  inflationRate: number;
+  requiredMonthly: number;
+  inflationAdjustedTarget: number;
+  inflationAdjustedMonthly: number;
+}
+
+export interface IncomeSummary {
+  period: string;
+  byMonth: Record<string, number>;
+  byType: Record<string, number>;
+  bySymbol: Record<string, number>;
+  byCurrency: Record<string, number>;
+  totalIncome: number;
+  currency: string;
+  monthlyAverage: number;
+  yoyGrowth: number | null; // Changed from optional to nullable
+}
+
+// Define custom DateRange type matching react-day-picker's
+export interface DateRange {
+  from: Date | undefined;
+  to: Date | undefined;
+}
+
+export type TimePeriod = "1D" | "1W" | "1M" | "3M" | "6M" | "YTD" | "1Y" | "3Y" | "5Y" | "ALL";
+
+export interface AccountValuation {
+  id: string;
+  accountId: string;
+  valuationDate: string;
+  accountCurrency: string;
+  baseCurrency: string;
+  fxRateToBase: number;
+  cashBalance: number;
+  investmentMarketValue: number;
+  totalValue: number;
+  costBasis: number;
+  netContribution: number;
+  calculatedAt: string;
+}
+
+export interface AccountSummaryView {
+  accountId: string;
+  accountName: string;
+  accountType: string;
+  accountGroup: string | null;
+  accountCurrency: string;
+  totalValueAccountCurrency: number;
+  totalValueBaseCurrency: number;
+  baseCurrency: string;
+  performance: SimplePerformanceMetrics;
+}
+
+export interface SimplePerformanceMetrics {
+  accountId: string;
+  totalValue?: number | null;
+  accountCurrency?: string | null;
+  baseCurrency?: string | null;
+  fxRateToBase?: number | null;
+  totalGainLossAmount?: number | null;
+  cumulativeReturnPercent?: number | null;
+  dayGainLossAmount?: number | null;
+  dayReturnPercentModDietz?: number | null;
+  portfolioWeight?: number | null;
+}
+
+export interface AccountGroup {
+  groupName: string;
+  accounts: AccountSummaryView[];
+  totalValueBaseCurrency: number;
+  baseCurrency: string;
+  performance: SimplePerformanceMetrics;
+  accountCount: number;
+}
+
+export interface ExchangeRate {
+  id: string;
+  fromCurrency: string;
+  toCurrency: string;
+  fromCurrencyName?: string;
+  toCurrencyName?: string;
+  rate: number;
+  source: string;
+  isLoading?: boolean;
+  timestamp: string;
+}
+
+export interface ContributionLimit {
+  id: string;
+  groupName: string;
+  contributionYear: number;
+  limitAmount: number;
+  accountIds?: string | null;
+  startDate?: string | null;
+  endDate?: string | null;
+  createdAt?: string;
+  updatedAt?: string;
+}
+
+export type NewContributionLimit = Omit<ContributionLimit, "id" | "createdAt" | "updatedAt">;
+
+export interface AccountDeposit {
+  amount: number;
+  currency: string;
+  convertedAmount: number;
+}
+
+export interface DepositsCalculation {
+  total: number;
+  baseCurrency: string;
+  byAccount: Record<string, AccountDeposit>;
+}
+
+export const ACTIVITY_TYPE_PREFIX_LENGTH = 12;
+
+// Renamed from CumulativeReturn to match Rust struct ReturnData
+export interface ReturnData {
+  date: string; // Changed from CumulativeReturn
+  value: number;
+}
+
+// Renamed from PerformanceData to match Rust struct
+export interface PerformanceMetrics {
+  id: string;
+  returns: ReturnData[]; // Changed from CumulativeReturn[]
+  periodStartDate?: string | null; // Changed from periodStartDate?
+  periodEndDate?: string | null; // Changed from periodEndDate?
+  currency: string;
+  cumulativeTwr: number; // Added field, corresponds to TWR
+  gainLossAmount?: number | null; // Made explicitly nullable
+  annualizedTwr: number; // Added field, corresponds to TWR
+  simpleReturn: number; // Added field
+  annualizedSimpleReturn: number; // Added field
+  cumulativeMwr: number; // Added field, corresponds to MWR
+  annualizedMwr: number; // Added field, corresponds to MWR
+  volatility: number;
+  maxDrawdown: number;
+}
+
+export interface UpdateAssetProfile {
+  symbol: string;
+  name?: string;
+  sectors: string;
+  countries: string;
+  notes: string;
+  assetClass: string;
+  assetSubClass: string;
+}
+
+export interface Benchmark {
+  id: string;
+  name: string;
+  symbol: string;
+  dataSource: string;
+  currency: string;
+  isCustom: boolean;
+  createdAt: string;
+}
+
+export interface NewBenchmark {
+  name: string;
+  symbol: string;
+  dataSource: string;
+  currency: string;
+}
+
+export interface BenchmarkComparisonPoint {
+  date: string;
+  target: number;
+  benchmark: number;
+}
+
+export interface BenchmarkComparison {
+  targetId: string;
+  targetType: "ACCOUNT" | "GOAL";
+  benchmarkId: string;
+  startDate?: string | null;
+  endDate?: string | null;
+  series: BenchmarkComparisonPoint[];
+  targetReturn: number;
+  benchmarkReturn: number;
+  excessReturn: number;
 }
 
 // Rename ComparisonItem to TrackedItem
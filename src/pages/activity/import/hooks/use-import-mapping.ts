@@ -28,6 +28,7 @@ const initialMapping: ImportMappingData = {
   activityMappings: {},
   symbolMappings: {},
   accountMappings: {},
+  numberLocale: "en",
 };
 
 interface UseImportMappingProps {
@@ -287,8 +287,13 @@ function getColumns(
           fieldErrors.map((err) => `${field}: ${err}`),
         );
 
+        const warnings = row.original.warnings ?? [];
+
         return isValid ? (
-          <div className="flex w-[60px] items-center gap-1 text-xs">
+          <div
+            className="flex w-[60px] items-center gap-1 text-xs"
+            title={warnings.length > 0 ? warnings.join("\n") : undefined}
+          >
             <div className="bg-success/20 text-success flex h-5 w-5 items-center justify-center rounded-full">
               <Icons.CheckCircle className="h-3.5 w-3.5" />
             </div>
@@ -2,12 +2,15 @@ import { Button } from "@/components/ui/button";
 import { CsvMappingEditor } from "../components/mapping-editor";
 import { ImportFormat, ActivityType, ImportMappingData, CsvRowData, Account } from "@/lib/types";
 import { useMemo } from "react";
-import { validateTickerSymbol } from "../utils/validation-utils";
+import { NumberLocale, validateTickerSymbol } from "../utils/validation-utils";
 import { useImportMapping } from "../hooks/use-import-mapping";
 import { IMPORT_REQUIRED_FIELDS } from "@/lib/constants";
 import { ImportAlert } from "../components/import-alert";
 import { Icons, Icon } from "@/components/ui/icons";
 import { useTranslation } from "react-i18next";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@wealthvn/ui";
+
+const NUMBER_LOCALES: NumberLocale[] = ["en", "vi", "auto"];
 
 interface MappingStepProps {
   headers: string[];
@@ -29,6 +32,7 @@ export const MappingStep = ({
   const { t } = useTranslation("activity");
   const {
     mapping,
+    updateMapping,
     handleColumnMapping,
     handleActivityTypeMapping,
     handleSymbolMapping,
@@ -44,6 +48,7 @@ export const MappingStep = ({
       activityMappings: {},
       symbolMappings: {},
       accountMappings: {},
+      numberLocale: "en",
     },
     onSaveSuccess: (savedMapping) => {
       onNext(savedMapping);
@@ -260,6 +265,25 @@ export const MappingStep = ({
         )}
       </div>
 
+      <div className="mb-4 flex items-center gap-3">
+        <span className="text-sm font-medium">{t("import.mapping.numberFormat")}</span>
+        <Select
+          value={mapping.numberLocale ?? "en"}
+          onValueChange={(value) => updateMapping({ numberLocale: value as NumberLocale })}
+        >
+          <SelectTrigger className="w-[240px]">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {NUMBER_LOCALES.map((locale) => (
+              <SelectItem key={locale} value={locale}>
+                {t(`import.mapping.numberFormats.${locale}`)}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </div>
+
       <CsvMappingEditor
         mapping={mapping}
         headers={headers}
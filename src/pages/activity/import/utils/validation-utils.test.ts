@@ -46,6 +46,15 @@ describe("validation-utils", () => {
       expect(normalizeNumericValue("0")).toBe(0);
       expect(normalizeNumericValue("0.00")).toBe(0);
     });
+
+    it("should read numbers in the chosen locale", () => {
+      expect(normalizeNumericValue("1.500.000,50", "vi")).toBe(1500000.5);
+      expect(normalizeNumericValue("25.000 ₫", "vi")).toBe(25000);
+      expect(normalizeNumericValue("1,500,000.50", "vi")).toBeUndefined();
+      expect(normalizeNumericValue("1.500.000", "auto")).toBe(1500000);
+      expect(normalizeNumericValue("12,5", "auto")).toBe(12.5);
+      expect(normalizeNumericValue("1.500", "auto")).toBeUndefined();
+    });
   });
 
   describe("parseAndAbsoluteValue", () => {
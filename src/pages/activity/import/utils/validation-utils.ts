@@ -18,14 +18,44 @@ export function validateTickerSymbol(symbol: string): boolean {
   return tickerRegex.test(symbol.trim());
 }
 
+export type NumberLocale = "en" | "vi" | "auto";
+
+/**
+ * Picks the decimal separator of a value written in `locale`. In "auto" the last of two
+ * different separators is the decimal one, a repeated separator groups thousands, and a
+ * single separator followed by exactly three digits could be either, so it is undefined.
+ */
+function decimalSeparator(value: string, locale: NumberLocale): "." | "," | undefined {
+  if (locale === "en") return ".";
+  if (locale === "vi") return ",";
+
+  const dots = value.split(".").length - 1;
+  const commas = value.split(",").length - 1;
+  if (dots > 0 && commas > 0) {
+    return value.lastIndexOf(".") > value.lastIndexOf(",") ? "." : ",";
+  }
+  if (dots > 1) return ",";
+  if (commas > 1) return ".";
+  if (dots === 0 && commas === 0) return ".";
+
+  const separator = dots === 1 ? "." : ",";
+  const [whole, fraction] = value.replace(/[^\d.,]/g, "").split(separator);
+  const couldGroup = fraction.length === 3 && whole.length >= 1 && whole.length <= 3 && whole !== "0";
+  return couldGroup ? undefined : separator;
+}
+
 /**
  * Normalizes and cleans numeric values from CSV data
  * Handles currency symbols, commas, spaces, and other formatting characters
  *
  * @param value The raw string value from CSV
- * @returns Cleaned numeric value or undefined if invalid
+ * @param locale How the file writes numbers: "en" (1,234.5), "vi" (1.234,5) or "auto"
+ * @returns Cleaned numeric value or undefined if invalid or ambiguous
  */
-export function normalizeNumericValue(value: string | undefined): number | undefined {
+export function normalizeNumericValue(
+  value: string | undefined,
+  locale: NumberLocale = "en",
+): number | undefined {
   if (!value || typeof value !== "string") {
     return undefined;
   }
@@ -38,6 +68,19 @@ export function normalizeNumericValue(value: string | undefined): number | undef
     return undefined;
   }
 
+  // Rewrite Vietnamese-style numbers with English separators; mixed-up values are rejected
+  // rather than read a factor of 1000 off
+  const separator = decimalSeparator(cleaned, locale);
+  if (separator === undefined) {
+    return undefined;
+  }
+  if (separator === ",") {
+    cleaned = cleaned.replace(/\./g, "").replace(/,/g, ".");
+    if (cleaned.split(".").length > 2) {
+      return undefined;
+    }
+  }
+
   // Remove common currency symbols and formatting
   cleaned = cleaned
     .replace(/[$£€¥₹₦₹₽¢]/g, "") // Remove currency symbols
@@ -62,10 +105,14 @@ export function normalizeNumericValue(value: string | undefined): number | undef
  * Uses normalization to handle currency symbols and formatting
  *
  * @param value The raw string value from CSV
+ * @param locale How the file writes numbers
  * @returns Absolute numeric value or undefined if invalid
  */
-export function parseAndAbsoluteValue(value: string | undefined): number | undefined {
-  const normalized = normalizeNumericValue(value);
+export function parseAndAbsoluteValue(
+  value: string | undefined,
+  locale: NumberLocale = "en",
+): number | undefined {
+  const normalized = normalizeNumericValue(value, locale);
   return normalized !== undefined ? Math.abs(normalized) : undefined;
 }
 
@@ -319,10 +366,11 @@ function transformRowToActivity(
   // Store raw parsed values temporarily before applying logic
   // Use absolute values for numeric fields to handle brokers that use negative values for direction
   // Also normalize values to handle currency symbols and formatting
-  const rawQuantity = parseAndAbsoluteValue(getMappedValue(ImportFormat.QUANTITY));
-  const rawUnitPrice = parseAndAbsoluteValue(getMappedValue(ImportFormat.UNIT_PRICE));
-  const rawFee = parseAndAbsoluteValue(getMappedValue(ImportFormat.FEE));
-  const rawAmount = parseAndAbsoluteValue(getMappedValue(ImportFormat.AMOUNT));
+  const locale = mapping.numberLocale ?? "en";
+  const rawQuantity = parseAndAbsoluteValue(getMappedValue(ImportFormat.QUANTITY), locale);
+  const rawUnitPrice = parseAndAbsoluteValue(getMappedValue(ImportFormat.UNIT_PRICE), locale);
+  const rawFee = parseAndAbsoluteValue(getMappedValue(ImportFormat.FEE), locale);
+  const rawAmount = parseAndAbsoluteValue(getMappedValue(ImportFormat.AMOUNT), locale);
 
   // Assign potentially NaN values first, they will be cleaned up later
   activity.quantity = rawQuantity;
@@ -53,6 +53,8 @@ const getDataSourceLabel = (dataSource: string, t: any): string => {
       return t("securities.form.dataSource.options.metalPriceApi");
     case DataSource.VN_MARKET:
       return t("securities.form.dataSource.options.vnMarket");
+    case DataSource.VIETCOMBANK:
+      return t("securities.form.dataSource.options.vietcombank");
     default:
       return dataSource;
   }
@@ -100,6 +102,7 @@ export function AssetForm({
       DataSource.ALPHA_VANTAGE,
       DataSource.METAL_PRICE_API,
       DataSource.VN_MARKET,
+      DataSource.VIETCOMBANK,
     ];
 
     allAutoProviders.forEach((provider) => {
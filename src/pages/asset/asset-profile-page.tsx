@@ -559,6 +559,7 @@ export const AssetProfilePage = () => {
              DataSource.ALPHA_VANTAGE,
              DataSource.METAL_PRICE_API,
              DataSource.VN_MARKET,
+             DataSource.VIETCOMBANK,
            ]}
            onChangeDataSource={async (dataSource) => {
              try {
@@ -646,6 +647,7 @@ export const AssetProfilePage = () => {
               DataSource.ALPHA_VANTAGE,
               DataSource.METAL_PRICE_API,
               DataSource.VN_MARKET,
+              DataSource.VIETCOMBANK,
             ]}
             onSaveQuote={(quote: Quote) => {
               const updatedQuote = { ...quote };
@@ -1021,6 +1023,7 @@ export const AssetProfilePage = () => {
                  DataSource.ALPHA_VANTAGE,
                  DataSource.METAL_PRICE_API,
                  DataSource.VN_MARKET,
+                 DataSource.VIETCOMBANK,
                ]}
                onChangeDataSource={(dataSource) => {
                  // Only allow changing data source if there's a profile/holding to update
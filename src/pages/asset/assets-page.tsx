@@ -49,6 +49,8 @@ const getDataSourceLabel = (dataSource: string, t: any): string => {
       return t("securities.form.dataSource.options.metalPriceApi");
     case DataSource.VN_MARKET:
       return t("securities.form.dataSource.options.vnMarket");
+    case DataSource.VIETCOMBANK:
+      return t("securities.form.dataSource.options.vietcombank");
     default:
       return dataSource;
   }
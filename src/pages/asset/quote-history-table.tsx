@@ -68,6 +68,8 @@ const getDataSourceLabel = (dataSource: string, t: any): string => {
       return t("assets:quotesTable.dataSource.metalPriceApi");
     case DataSource.VN_MARKET:
       return t("assets:quotesTable.dataSource.vnMarket");
+    case DataSource.VIETCOMBANK:
+      return t("assets:quotesTable.dataSource.vietcombank");
     default:
       return dataSource;
   }
@@ -96,6 +98,7 @@ export const QuoteHistoryTable: React.FC<QuoteHistoryTableProps> = ({
     DataSource.ALPHA_VANTAGE,
     DataSource.METAL_PRICE_API,
     DataSource.VN_MARKET,
+    DataSource.VIETCOMBANK,
   ],
 }) => {
   const { t } = useTranslation(["assets"]);
@@ -0,0 +1,85 @@
+import { useState } from "react";
+import { useTranslation } from "react-i18next";
+
+import {
+  AlertDialog,
+  AlertDialogCancel,
+  AlertDialogContent,
+  AlertDialogDescription,
+  AlertDialogFooter,
+  AlertDialogHeader,
+  AlertDialogTitle,
+} from "@/components/ui/alert-dialog";
+import { Button } from "@/components/ui/button";
+import { Icons } from "@/components/ui/icons";
+
+import type { Goal } from "@/lib/types";
+
+export interface ArchivedGoalsProps {
+  goals: Goal[];
+  onRestore: (goal: Goal) => void;
+  onDelete: (goal: Goal) => void;
+}
+
+export function ArchivedGoals({ goals, onRestore, onDelete }: ArchivedGoalsProps) {
+  const { t } = useTranslation("goals");
+  const [goalToDelete, setGoalToDelete] = useState<Goal | null>(null);
+
+  const handleDelete = () => {
+    if (goalToDelete) {
+      onDelete(goalToDelete);
+    }
+    setGoalToDelete(null);
+  };
+
+  return (
+    <div>
+      <div className="mb-6">
+        <h2 className="text-foreground text-xl font-bold">{t("archived.heading")}</h2>
+        <p className="text-muted-foreground text-sm">{t("archived.description")}</p>
+      </div>
+      <div className="divide-y rounded-md border">
+        {goals.map((goal) => (
+          <div key={goal.id} className="flex items-center justify-between gap-4 p-4">
+            <div className="min-w-0">
+              <p className="text-foreground truncate font-medium">{goal.title}</p>
+              {goal.description && (
+                <p className="text-muted-foreground truncate text-sm">{goal.description}</p>
+              )}
+            </div>
+            <div className="flex shrink-0 items-center gap-2">
+              <Button variant="outline" size="sm" onClick={() => onRestore(goal)}>
+                {t("archived.restore")}
+              </Button>
+              <Button
+                variant="outline"
+                size="sm"
+                className="text-destructive hover:text-destructive"
+                onClick={() => setGoalToDelete(goal)}
+              >
+                <Icons.Trash className="mr-2 h-4 w-4" />
+                {t("operations.delete")}
+              </Button>
+            </div>
+          </div>
+        ))}
+      </div>
+
+      <AlertDialog open={goalToDelete !== null} onOpenChange={(open) => !open && setGoalToDelete(null)}>
+        <AlertDialogContent>
+          <AlertDialogHeader>
+            <AlertDialogTitle>{t("delete.title")}</AlertDialogTitle>
+            <AlertDialogDescription>{t("delete.description")}</AlertDialogDescription>
+          </AlertDialogHeader>
+          <AlertDialogFooter>
+            <AlertDialogCancel>{t("form.buttons.cancel")}</AlertDialogCancel>
+            <Button onClick={() => handleDelete()} className="bg-red-600 focus:ring-red-600">
+              <Icons.Trash className="mr-2 h-4 w-4" />
+              <span>{t("delete.button")}</span>
+            </Button>
+          </AlertDialogFooter>
+        </AlertDialogContent>
+      </AlertDialog>
+    </div>
+  );
+}
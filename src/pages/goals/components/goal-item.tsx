@@ -25,7 +25,7 @@ export interface GoalItemProps {
   allocations?: GoalAllocation[];
   totalAccountCount?: number;
   onEdit: (goal: Goal) => void;
-  onDelete: (goal: Goal) => void;
+  onArchive: (goal: Goal) => void;
   onComplete?: (goal: Goal) => void;
   // Optional: Pass goals array to enable data fetching
   // If not provided, use the values below
@@ -41,7 +41,7 @@ export function GoalItem({
   allocations = [],
   totalAccountCount = 0,
   onEdit,
-  onDelete,
+  onArchive,
   onComplete,
   goals,
   currentValue: passedCurrentValue,
@@ -198,7 +198,7 @@ export function GoalItem({
           </div>
         </div>
         <div onClick={(e) => e.stopPropagation()}>
-          <GoalOperations goal={goal} onEdit={onEdit} onDelete={onDelete} onComplete={onComplete} />
+          <GoalOperations goal={goal} onEdit={onEdit} onArchive={onArchive} onComplete={onComplete} />
         </div>
       </div>
 
@@ -1,16 +1,5 @@
-import { useState } from "react";
 import { useTranslation } from "react-i18next";
 
-import {
-  AlertDialog,
-  AlertDialogCancel,
-  AlertDialogContent,
-  AlertDialogDescription,
-  AlertDialogFooter,
-  AlertDialogHeader,
-  AlertDialogTitle,
-} from "@/components/ui/alert-dialog";
-import { Button } from "@/components/ui/button";
 import {
   DropdownMenu,
   DropdownMenuContent,
@@ -25,19 +14,13 @@ import type { Goal } from "@/lib/types";
 export interface GoalOperationsProps {
   goal: Goal;
   onEdit: (goal: Goal) => void | undefined;
-  onDelete: (goal: Goal) => void | undefined;
+  /** Archiving is the only way out of the list; archived goals are deleted from there */
+  onArchive: (goal: Goal) => void | undefined;
   onComplete?: (goal: Goal) => void | undefined;
 }
 
-export function GoalOperations({ goal, onEdit, onDelete, onComplete }: GoalOperationsProps) {
+export function GoalOperations({ goal, onEdit, onArchive, onComplete }: GoalOperationsProps) {
   const { t } = useTranslation("goals");
-  const [showDeleteAlert, setShowDeleteAlert] = useState(false);
-  //const navigation = useNavigation();
-  const isDeleting = false; //navigation?.formData?.get('intent') === 'delete';
-  const handleDelete = () => {
-    onDelete(goal);
-    setShowDeleteAlert(false);
-  };
 
   const handleComplete = () => {
     if (onComplete) {
@@ -77,39 +60,13 @@ export function GoalOperations({ goal, onEdit, onDelete, onComplete }: GoalOpera
           )}
           <DropdownMenuSeparator />
           <DropdownMenuItem
-            className="text-destructive focus:text-destructive flex cursor-pointer items-center"
-            onSelect={() => setShowDeleteAlert(true)}
+            className="flex cursor-pointer items-center"
+            onSelect={() => onArchive(goal)}
           >
-            {t("operations.delete")}
+            {t("operations.archive")}
           </DropdownMenuItem>
         </DropdownMenuContent>
       </DropdownMenu>
-      <AlertDialog open={showDeleteAlert} onOpenChange={setShowDeleteAlert}>
-        <AlertDialogContent>
-          <AlertDialogHeader>
-            <AlertDialogTitle>{t("delete.title")}</AlertDialogTitle>
-            <AlertDialogDescription>{t("delete.description")}</AlertDialogDescription>
-          </AlertDialogHeader>
-
-          <AlertDialogFooter>
-            <input type="hidden" name="id" value={goal.id} />
-            <AlertDialogCancel>{t("form.buttons.cancel")}</AlertDialogCancel>
-
-            <Button
-              disabled={isDeleting}
-              onClick={() => handleDelete()}
-              className="bg-red-600 focus:ring-red-600"
-            >
-              {isDeleting ? (
-                <Icons.Spinner className="mr-2 h-4 w-4 animate-spin" />
-              ) : (
-                <Icons.Trash className="mr-2 h-4 w-4" />
-              )}
-              <span>{t("delete.button")}</span>
-            </Button>
-          </AlertDialogFooter>
-        </AlertDialogContent>
-      </AlertDialog>
     </>
   );
 }
@@ -1,4 +1,4 @@
-import { getGoals, getGoalsAllocation } from "@/commands/goal";
+import { getAllGoals, getGoals, getGoalsAllocation } from "@/commands/goal";
 import { useAccounts } from "@/hooks/use-accounts";
 import { useLatestValuations } from "@/hooks/use-latest-valuations";
 import { QueryKeys } from "@/lib/query-keys";
@@ -8,6 +8,7 @@ import { useQuery } from "@tanstack/react-query";
 import { Button, EmptyPlaceholder, Icons, Page, Skeleton } from "@wealthvn/ui";
 import { useState } from "react";
 import { useTranslation } from "react-i18next";
+import { ArchivedGoals } from "./components/archived-goals";
 import GoalsAllocations from "./components/goal-allocations";
 import { GoalEditModal } from "./components/goal-edit-modal";
 import { GoalItem } from "./components/goal-item";
@@ -20,6 +21,12 @@ const GoalsPage = () => {
     queryFn: getGoals,
   });
 
+  const { data: allGoals } = useQuery<Goal[], Error>({
+    queryKey: [QueryKeys.GOALS, "all"],
+    queryFn: getAllGoals,
+  });
+  const archivedGoals = allGoals?.filter((goal) => goal.archivedAt) ?? [];
+
   const { data: allocations } = useQuery<GoalAllocation[], Error>({
     queryKey: [QueryKeys.GOALS_ALLOCATIONS],
     queryFn: getGoalsAllocation,
@@ -40,7 +47,13 @@ const GoalsPage = () => {
   const [visibleModal, setVisibleModal] = useState(false);
   const [selectedGoal, setSelectedGoal] = useState<Goal | null>(null);
 
-  const { deleteGoalMutation, saveAllocationsMutation, updateGoalMutation } = useGoalMutations();
+  const {
+    deleteGoalMutation,
+    archiveGoalMutation,
+    restoreGoalMutation,
+    saveAllocationsMutation,
+    updateGoalMutation,
+  } = useGoalMutations();
 
   const handleAddGoal = () => {
     setSelectedGoal(null);
@@ -52,6 +65,15 @@ const GoalsPage = () => {
     setVisibleModal(true);
   };
 
+  const handleArchiveGoal = (goal: Goal) => {
+    archiveGoalMutation.mutate(goal.id);
+  };
+
+  const handleRestoreGoal = (goal: Goal) => {
+    restoreGoalMutation.mutate(goal.id);
+  };
+
+  // Only archived goals can be deleted
   const handleDeleteGoal = (goal: Goal) => {
     deleteGoalMutation.mutate(goal.id);
   };
@@ -124,7 +146,7 @@ const GoalsPage = () => {
                       allocations={goalAllocations}
                       totalAccountCount={accounts?.length ?? 0}
                       onEdit={handleEditGoal}
-                      onDelete={handleDeleteGoal}
+                      onArchive={handleArchiveGoal}
                       onComplete={handleCompleteGoal}
                     />
                   );
@@ -161,6 +183,14 @@ const GoalsPage = () => {
             </Button>
           </EmptyPlaceholder>
         )}
+
+        {archivedGoals.length > 0 && (
+          <ArchivedGoals
+            goals={archivedGoals}
+            onRestore={handleRestoreGoal}
+            onDelete={handleDeleteGoal}
+          />
+        )}
       </div>
 
       <GoalEditModal
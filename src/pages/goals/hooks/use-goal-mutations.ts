@@ -1,5 +1,14 @@
 import { logger } from "@/adapters";
-import { NewGoalInput, createGoal, deleteGoal, deleteGoalAllocation, updateGoal, updateGoalsAllocations } from "@/commands/goal";
+import {
+  NewGoalInput,
+  archiveGoal,
+  createGoal,
+  deleteGoal,
+  deleteGoalAllocation,
+  restoreGoal,
+  updateGoal,
+  updateGoalsAllocations,
+} from "@/commands/goal";
 import { QueryKeys } from "@/lib/query-keys";
 import { Goal, GoalAllocation } from "@/lib/types";
 import { UseMutationResult, useMutation, useQueryClient } from "@tanstack/react-query";
@@ -10,6 +19,8 @@ export interface GoalMutations {
   addGoalMutation: UseMutationResult<Goal, Error, NewGoalInput, unknown>;
   updateGoalMutation: UseMutationResult<Goal, Error, Goal, unknown>;
   deleteGoalMutation: UseMutationResult<void, Error, string, unknown>;
+  archiveGoalMutation: UseMutationResult<Goal, Error, string, unknown>;
+  restoreGoalMutation: UseMutationResult<Goal, Error, string, unknown>;
   saveAllocationsMutation: UseMutationResult<void, Error, GoalAllocation[], unknown>;
   updateAllocationMutation: UseMutationResult<void, Error, GoalAllocation, unknown>;
   deleteAllocationMutation: UseMutationResult<void, Error, string, unknown>;
@@ -59,6 +70,26 @@ export const useGoalMutations = (): GoalMutations => {
     onError: (e) => handleError("deleting this goal", e),
   });
 
+  const archiveGoalMutation = useMutation({
+    mutationFn: archiveGoal,
+    onSuccess: () =>
+      handleSuccess(queryClient, "Goal archived successfully.", [
+        QueryKeys.GOALS,
+        QueryKeys.GOALS_ALLOCATIONS,
+      ]),
+    onError: (e) => handleError("archiving this goal", e),
+  });
+
+  const restoreGoalMutation = useMutation({
+    mutationFn: restoreGoal,
+    onSuccess: () =>
+      handleSuccess(queryClient, "Goal restored successfully.", [
+        QueryKeys.GOALS,
+        QueryKeys.GOALS_ALLOCATIONS,
+      ]),
+    onError: (e) => handleError("restoring this goal", e),
+  });
+
   const saveAllocationsMutation = useMutation({
     mutationFn: updateGoalsAllocations,
     onSuccess: () =>
@@ -93,6 +124,8 @@ export const useGoalMutations = (): GoalMutations => {
     addGoalMutation,
     updateGoalMutation,
     deleteGoalMutation,
+    archiveGoalMutation,
+    restoreGoalMutation,
     saveAllocationsMutation,
     updateAllocationMutation,
     deleteAllocationMutation,
@@ -16,7 +16,7 @@ export function AccountEditModal({ account, open, onClose }: AccountEditModalPro
     id: account?.id ?? undefined,
     name: account?.name ?? "",
     balance: account?.balance ?? 0,
-    accountType: (account?.accountType ?? "SECURITIES") as "SECURITIES" | "CASH" | "CRYPTOCURRENCY",
+    accountType: (account?.accountType ?? "SECURITIES") as "SECURITIES" | "CASH" | "CRYPTOCURRENCY" | "LIABILITY" | "REAL_ESTATE" | "GOLD",
     group: account?.group ?? undefined,
     currency: account?.currency ?? settings?.baseCurrency ?? "USD",
     isDefault: account?.isDefault ?? false,
@@ -43,6 +43,9 @@ export function AccountForm({ defaultValues, onSuccess = () => undefined }: Acco
     { label: t("accounts.form.fields.accountType.options.securities"), value: "SECURITIES" },
     { label: t("accounts.form.fields.accountType.options.cash"), value: "CASH" },
     { label: t("accounts.form.fields.accountType.options.crypto"), value: "CRYPTOCURRENCY" },
+    { label: t("accounts.form.fields.accountType.options.liability"), value: "LIABILITY" },
+    { label: t("accounts.form.fields.accountType.options.realEstate"), value: "REAL_ESTATE" },
+    { label: t("accounts.form.fields.accountType.options.gold"), value: "GOLD" },
   ];
 
   const form = useForm<NewAccount>({
@@ -1,22 +1,14 @@
-import { getRunEnv, logger, RUN_ENV } from "@/adapters";
-import { getAccounts } from "@/commands/account";
-import { getActivities } from "@/commands/activity";
+import { getRunEnv, logger, openSavePathDialogTauri, RUN_ENV } from "@/adapters";
 import { openFileSaveDialog, openFolderDialog } from "@/commands/file";
-import { getGoals } from "@/commands/goal";
-import { getHistoricalValuations } from "@/commands/portfolio";
-import { backupDatabase, backupDatabaseToPath } from "@/commands/settings";
-import { toast } from "@/components/ui/use-toast";
-import { formatData } from "@/lib/export-utils";
-import { QueryKeys } from "@/lib/query-keys";
 import {
-  Account,
-  AccountValuation,
-  ActivityDetails,
-  ExportDataType,
-  ExportedFileFormat,
-  Goal,
-} from "@/lib/types";
-import { QueryObserverResult, useMutation, useQuery } from "@tanstack/react-query";
+  backupDatabase,
+  backupDatabaseToPath,
+  downloadDataExport,
+  exportDataToFile,
+} from "@/commands/settings";
+import { toast } from "@/components/ui/use-toast";
+import { ExportDataType, ExportedFileFormat } from "@/lib/types";
+import { useMutation } from "@tanstack/react-query";
 
 interface ExportParams {
   format: ExportedFileFormat;
@@ -35,27 +27,6 @@ export function useExportData() {
   const runEnv = getRunEnv();
   const isDesktop = runEnv === RUN_ENV.DESKTOP;
 
-  const { refetch: fetchAccounts } = useQuery<Account[], Error>({
-    queryKey: [QueryKeys.ACCOUNTS],
-    queryFn: getAccounts,
-    enabled: false,
-  });
-  const { refetch: fetchActivities } = useQuery<ActivityDetails[], Error>({
-    queryKey: [QueryKeys.ACTIVITIES],
-    queryFn: () => getActivities(),
-    enabled: false,
-  });
-  const { refetch: fetchGoals } = useQuery<Goal[], Error>({
-    queryKey: [QueryKeys.GOALS],
-    queryFn: getGoals,
-    enabled: false,
-  });
-  const { refetch: fetchPortfolioHistory } = useQuery<AccountValuation[], Error>({
-    queryKey: [QueryKeys.HISTORY_VALUATION],
-    queryFn: () => getHistoricalValuations("TOTAL"),
-    enabled: false,
-  });
-
   const {
     mutateAsync: exportDataMutation,
     isPending: isExporting,
@@ -80,48 +51,36 @@ export function useExportData() {
 
         const { filename } = await backupDatabase();
         return { mode: "sqlite", target: "server" as const, value: filename };
-      } else {
-        let exportedData: string | undefined;
-        let fileName: string;
-        let datasetLabel: string | null = null;
+      }
 
+      // Rows are streamed a chunk at a time by the backend instead of being built up here
+      const labels: Record<ExportDataType, string> = {
+        accounts: "accounts",
+        activities: "activities",
+        goals: "goals",
+        "portfolio-history": "portfolio history records",
+      };
+      if (isDesktop) {
         const currentDate = new Date().toISOString().split("T")[0];
-        switch (desiredData) {
-          case "accounts":
-            exportedData = await fetchAndFormatData(fetchAccounts, format);
-            fileName = `accounts_${currentDate}.${format.toLowerCase()}`;
-            datasetLabel = "accounts";
-            break;
-          case "activities":
-            exportedData = await fetchAndFormatData(fetchActivities, format);
-            fileName = `activities_${currentDate}.${format.toLowerCase()}`;
-            datasetLabel = "activities";
-            break;
-          case "goals":
-            exportedData = await fetchAndFormatData(fetchGoals, format);
-            fileName = `goals_${currentDate}.${format.toLowerCase()}`;
-            datasetLabel = "goals";
-            break;
-          case "portfolio-history":
-            exportedData = await fetchAndFormatData(fetchPortfolioHistory, format);
-            fileName = `portfolio-history_${currentDate}.${format.toLowerCase()}`;
-            datasetLabel = "portfolio history records";
-            break;
+        const path = await openSavePathDialogTauri(
+          `${desiredData}_${currentDate}.${format.toLowerCase()}`,
+        );
+        if (!path) {
+          return null;
         }
-
-        if (exportedData) {
-          return openFileSaveDialog(exportedData, fileName);
-        }
-
-        if (datasetLabel) {
+        const summary = await exportDataToFile(desiredData, format, path);
+        if (summary.rows === 0) {
           toast({
             title: "Nothing to export.",
-            description: `No ${datasetLabel} available to export right now.`,
+            description: `No ${labels[desiredData]} available to export right now.`,
           });
+          return null;
         }
-
-        return null;
+        return true;
       }
+
+      const { fileName, data } = await downloadDataExport(desiredData, format);
+      return openFileSaveDialog(data, fileName);
     },
     onSuccess: (result) => {
       if (!result) {
@@ -173,14 +132,3 @@ export function useExportData() {
     exportingData: isExporting ? mutationVariables?.data : null,
   };
 }
-
-async function fetchAndFormatData(
-  queryFn: () => Promise<QueryObserverResult<unknown[], Error>>,
-  format: ExportedFileFormat,
-): Promise<string | undefined> {
-  const response = await queryFn();
-
-  // Handle empty data gracefully - export empty file instead of error
-  const data = response.data ?? [];
-  return formatData(data, format);
-}
@@ -33,7 +33,10 @@ import {
 const useApiKeyStatus = (providerId: string, isOpen: boolean) => {
   const queryClient = useQueryClient();
   const needsApiKey =
-    providerId !== "YAHOO" && providerId !== "MANUAL" && providerId !== "VN_MARKET";
+    providerId !== "YAHOO" &&
+    providerId !== "MANUAL" &&
+    providerId !== "VN_MARKET" &&
+    providerId !== "VIETCOMBANK";
 
   const { data: apiKey, isLoading } = useQuery({
     queryKey: QueryKeys.secrets.apiKey(providerId),
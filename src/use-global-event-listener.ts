@@ -9,7 +9,10 @@ import {
     listenPortfolioUpdateComplete,
     listenPortfolioUpdateError,
     listenPortfolioUpdateStart,
+    listenQuotesUpdated,
+    listenServiceReady,
 } from "@/commands/portfolio-listener";
+import { QueryKeys } from "@/lib/query-keys";
 import { logger } from "./adapters";
 
 const TOAST_IDS = {
@@ -70,6 +73,23 @@ const useGlobalEventListener = () => {
     queryClient.invalidateQueries();
   }, [queryClient]);
 
+  // Prices shown outside the portfolio valuation reload as soon as a sync writes them
+  const handleQuotesUpdated = useCallback(() => {
+    for (const key of [
+      QueryKeys.QUOTE_HISTORY,
+      QueryKeys.LATEST_QUOTES_FOR_HOLDINGS,
+      QueryKeys.ASSETS,
+      QueryKeys.ASSET_DATA,
+    ]) {
+      queryClient.invalidateQueries({ queryKey: [key] });
+    }
+  }, [queryClient]);
+
+  // The window starts on cached data; refetch as each background subsystem comes up
+  const handleServiceReady = useCallback(() => {
+    queryClient.invalidateQueries();
+  }, [queryClient]);
+
   useEffect(() => {
     let actualCleanup = () => {
       return;
@@ -87,6 +107,8 @@ const useGlobalEventListener = () => {
       });
       const unlistenMarketStart = await listenMarketSyncStart(handleMarketSyncStart);
       const unlistenMarketComplete = await listenMarketSyncComplete(handleMarketSyncComplete);
+      const unlistenServiceReady = await listenServiceReady(handleServiceReady);
+      const unlistenQuotesUpdated = await listenQuotesUpdated(handleQuotesUpdated);
 
       return () => {
         unlistenPortfolioSyncStart();
@@ -94,6 +116,8 @@ const useGlobalEventListener = () => {
         unlistenPortfolioSyncError();
         unlistenMarketStart();
         unlistenMarketComplete();
+        unlistenServiceReady();
+        unlistenQuotesUpdated();
       };
     };
 
@@ -108,7 +132,7 @@ const useGlobalEventListener = () => {
     return () => {
       actualCleanup();
     };
-  }, [handlePortfolioUpdateComplete]);
+  }, [handlePortfolioUpdateComplete, handleServiceReady, handleQuotesUpdated]);
 
   return null;
 };